@@ -18,7 +18,7 @@ use zksync_config::{
         wallets::Wallets, BasicWitnessInputProducerConfig, DatabaseSecrets, GeneralConfig,
         L1Secrets, ObservabilityConfig, ProtectiveReadsWriterConfig,
     },
-    ContractsConfig, DBConfig, EthConfig, GenesisConfig, PostgresConfig,
+    ContractsConfig, DBConfig, EthConfig, GenesisConfig, ObjectStoreConfig, PostgresConfig,
 };
 use zksync_contracts::getters_facet_contract;
 use zksync_core_leftovers::temp_config_store::read_yaml_repr;
@@ -52,6 +52,9 @@ struct Cli {
     /// It should be skipped in case a chain is not settling on top of Gateway.
     #[arg(long, global = true)]
     gateway_chain_path: Option<PathBuf>,
+    /// Identity of the operator recorded in the audit log. If not set, the `USER` env variable is used.
+    #[arg(long, global = true)]
+    operator: Option<String>,
 }
 
 #[derive(Debug, Subcommand)]
@@ -103,6 +106,12 @@ enum Command {
         /// Flag that specifies if snapshot files in GCS should be rolled back.
         #[arg(long, requires = "rollback_postgres")]
         rollback_snapshots: bool,
+        /// Flag that specifies if witness inputs of reverted L1 batches should be removed from the object store.
+        #[arg(long, requires = "rollback_postgres")]
+        rollback_witness_inputs: bool,
+        /// Prints the rollback report as a JSON object, so that it is machine-readable.
+        #[arg(long)]
+        json: bool,
         /// Flag that allows to roll back already executed blocks. It's ultra dangerous and required only for fixing external nodes.
         #[arg(long)]
         allow_executed_block_reversion: bool,
@@ -301,6 +310,11 @@ async fn main() -> anyhow::Result<()> {
     .await
     .context("failed to build a connection pool")?;
     let mut block_reverter = BlockReverter::new(NodeRole::Main, connection_pool);
+    let operator = opts
+        .operator
+        .or_else(|| env::var("USER").ok())
+        .unwrap_or_else(|| "block_reverter".to_owned());
+    block_reverter.enable_audit_log(operator);
 
     match opts.command {
         Command::Display {
@@ -363,6 +377,8 @@ async fn main() -> anyhow::Result<()> {
             rollback_sk_cache,
             rollback_vm_runners_cache,
             rollback_snapshots,
+            rollback_witness_inputs,
+            json,
             allow_executed_block_reversion,
         } => {
             if !rollback_tree && rollback_postgres {
@@ -404,6 +420,21 @@ async fn main() -> anyhow::Result<()> {
                             .await?,
                     );
                 }
+                if rollback_witness_inputs {
+                    let object_store_config = match &general_config {
+                        Some(general_config) => general_config
+                            .core_object_store
+                            .clone()
+                            .context("Failed to find core object store config")?,
+                        None => ObjectStoreConfig::from_env()
+                            .context("ObjectStoreConfig::from_env()")?,
+                    };
+                    block_reverter.enable_rolling_back_witness_inputs(
+                        ObjectStoreFactory::new(object_store_config)
+                            .create_store()
+                            .await?,
+                    );
+                }
             }
             if rollback_tree {
                 block_reverter.enable_rolling_back_merkle_tree(db_config.merkle_tree.path);
@@ -442,9 +473,14 @@ async fn main() -> anyhow::Result<()> {
                 }
             }
 
-            block_reverter
+            let report = block_reverter
                 .roll_back(L1BatchNumber(l1_batch_number))
                 .await?;
+            if json {
+                println!("{}", serde_json::to_string(&report)?);
+            } else {
+                println!("Rollback report: {report:#?}");
+            }
         }
         Command::ClearFailedL1Transactions => {
             block_reverter.clear_failed_l1_transactions().await?;
@@ -31,7 +31,7 @@ use zksync_dal::{ConnectionPool, Core};
 use zksync_env_config::da_client::{da_client_config_from_env, da_client_secrets_from_env};
 use zksync_metadata_calculator::MetadataCalculatorRecoveryConfig;
 use zksync_node_api_server::{
-    tx_sender::{TimestampAsserterParams, TxSenderConfig},
+    tx_sender::{load_shedding::LoadSheddingConfig, TimestampAsserterParams, TxSenderConfig},
     web3::{state::InternalApiConfigBase, Namespace},
 };
 use zksync_protobuf_config::proto;
@@ -311,6 +311,9 @@ pub(crate) struct OptionalENConfig {
     /// Method-specific overrides in MiBs for the maximum response body size.
     #[serde(default = "MaxResponseSizeOverrides::empty")]
     max_response_body_size_overrides_mb: MaxResponseSizeOverrides,
+    /// Maximum size of a trace returned by `debug_trace*` methods in MiBs. Traces exceeding this size are progressively
+    /// truncated. If not set, the global response body size limit is used.
+    max_trace_size_mb: Option<usize>,
 
     // Other API config settings
     /// Interval between polling DB for Web3 subscriptions.
@@ -336,6 +339,22 @@ pub(crate) struct OptionalENConfig {
     /// values cache will be disabled.
     #[serde(default = "OptionalENConfig::default_latest_values_cache_size_mb")]
     latest_values_cache_size_mb: usize,
+    /// Time window (in milliseconds) to coalesce storage reads from concurrently executing VMs into batched
+    /// Postgres queries. If not set, storage reads are not batched.
+    storage_read_batching_window_ms: Option<u64>,
+    /// Maximum number of `eth_call` results executed on sealed L2 blocks to cache. If not set or set to 0,
+    /// results are not cached.
+    eth_call_cache_size: Option<usize>,
+    /// Average latency of waiting for a VM permit (in milliseconds) above which low-priority requests
+    /// (`eth_call`, gas estimation, call tracing) are rejected. If not set, the VM queue latency doesn't trigger
+    /// load shedding.
+    load_shedding_vm_queue_latency_ms: Option<u64>,
+    /// Share of connections in use in the connection pool (from 0 to 1) above which low-priority requests
+    /// are rejected. If not set, the pool utilization doesn't trigger load shedding.
+    load_shedding_pool_utilization: Option<f64>,
+    /// Delay (in milliseconds) that clients are advised to wait before retrying a rejected request.
+    #[serde(default = "OptionalENConfig::default_load_shedding_retry_after_ms")]
+    load_shedding_retry_after_ms: u64,
     /// Enabled JSON RPC API namespaces.
     api_namespaces: Option<Vec<Namespace>>,
     /// Whether to support HTTP methods that install filters and query filter changes.
@@ -426,9 +445,21 @@ pub(crate) struct OptionalENConfig {
     /// Timeout to wait for the Merkle tree database to run compaction on stalled writes.
     #[serde(default = "OptionalENConfig::default_merkle_tree_stalled_writes_timeout_sec")]
     merkle_tree_stalled_writes_timeout_sec: u64,
+    /// Number of threads in a dedicated thread pool used to compute Merkle tree hashes. If not specified,
+    /// the global `rayon` thread pool is used.
+    pub merkle_tree_hashing_thread_count: Option<usize>,
+    /// If set, the Merkle tree will retain versions for at most this many latest L1 batches, regardless
+    /// of Postgres pruning. Tree versions for batches pruned in Postgres are still pruned if pruning is enabled.
+    pub merkle_tree_pruning_retained_l1_batches: Option<NonZeroU32>,
     /// Enables the stale keys repair task for the Merkle tree.
     #[serde(default)]
     pub merkle_tree_repair_stale_keys: bool,
+    /// If set, enables the background consistency check for the Merkle tree, which checks the latest tree version
+    /// with this interval.
+    pub merkle_tree_consistency_check_interval_sec: Option<u64>,
+    /// Delay between checking subtrees of the tree root by the Merkle tree consistency check.
+    #[serde(default = "OptionalENConfig::default_merkle_tree_consistency_check_subtree_delay_ms")]
+    merkle_tree_consistency_check_subtree_delay_ms: u64,
 
     // Postgres config (new parameters)
     /// Threshold in milliseconds for the DB connection lifetime to denote it as long-living and log its details.
@@ -451,6 +482,10 @@ pub(crate) struct OptionalENConfig {
     /// (presumably, to participate in L1 batch proving).
     #[serde(default)]
     pub protective_reads_persistence_enabled: bool,
+    /// Configures whether to persist hashes of transactions that last modified each storage slot in an L2 block.
+    /// Allows querying the writer of a storage slot via `zks_getStorageSlotWriter`.
+    #[serde(default)]
+    pub storage_slot_writers_enabled: bool,
     /// Address of the L1 diamond proxy contract used by the consistency checker to match with the origin of logs emitted
     /// by commit transactions. If not set, it will not be verified.
     // This is intentionally not a part of `RemoteENConfig` because fetching this info from the main node would defeat
@@ -496,6 +531,33 @@ pub(crate) struct OptionalENConfig {
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 7 days.
     #[serde(default = "OptionalENConfig::default_pruning_data_retention_sec")]
     pruning_data_retention_sec: u64,
+    /// If set, call traces will be removed for L1 batches whose timestamp is this old (in seconds). Unlike
+    /// `pruning_data_retention_sec`, this doesn't require pruning to be enabled; L1 batches and L2 blocks are retained.
+    pruning_call_traces_retention_sec: Option<u64>,
+    /// If set, events will be removed for L1 batches whose timestamp is this old (in seconds).
+    pruning_events_retention_sec: Option<u64>,
+    /// If set, the pruner only reports which L1 batches would be pruned without removing any data.
+    #[serde(default)]
+    pub pruning_dry_run: bool,
+    /// Enables the cross-verification mode. In this mode, the node additionally compares events emitted in locally
+    /// executed L2 blocks with the main node, and halts with a divergence report instead of reverting L1 batches
+    /// that diverge from the main node. Pubdata is not compared.
+    #[serde(default)]
+    pub cross_verification_enabled: bool,
+    /// Maximum number of L1 batches that the node may roll back automatically after detecting a reorg. Rolling back
+    /// reverts Postgres, the Merkle tree and the state keeper cache to the last L1 batch consistent with the main node
+    /// and happens on node restart. If a reorg is deeper, the node halts and must be reverted manually.
+    /// If not set, automatic rollbacks are unbounded.
+    pub max_auto_rollback_l1_batches: Option<u32>,
+    /// Enables the response checker. The checker periodically compares L2 blocks and logs retained by the node
+    /// with an archive peer and reports divergences via health checks and metrics. Useful to continuously monitor
+    /// correctness of pruned nodes.
+    #[serde(default)]
+    pub response_checker_enabled: bool,
+    /// URL of the archive peer used by the response checker. If not set, the main node is used.
+    pub response_checker_peer_url: Option<SensitiveUrl>,
+    /// Distance between consecutive L2 blocks checked by the response checker. The default value is 100.
+    pub response_checker_stride: Option<NonZeroU32>,
     /// Gateway RPC URL, needed for operating during migration.
     pub gateway_url: Option<SensitiveUrl>,
     /// Interval for bridge addresses refreshing in seconds.
@@ -560,6 +622,10 @@ impl OptionalENConfig {
                 web3_json_rpc.max_response_body_size_overrides_mb,
                 default_max_response_body_size_overrides_mb
             ),
+            max_trace_size_mb: load_config!(
+                general_config.api_config,
+                web3_json_rpc.max_trace_size_mb
+            ),
             pubsub_polling_interval_ms: load_optional_config_or_default!(
                 general_config.api_config,
                 web3_json_rpc.pubsub_polling_interval,
@@ -590,6 +656,27 @@ impl OptionalENConfig {
                 web3_json_rpc.latest_values_cache_size_mb,
                 default_latest_values_cache_size_mb
             ),
+            storage_read_batching_window_ms: load_config!(
+                general_config.api_config,
+                web3_json_rpc.storage_read_batching_window_ms
+            ),
+            eth_call_cache_size: load_config!(
+                general_config.api_config,
+                web3_json_rpc.eth_call_cache_size
+            ),
+            load_shedding_vm_queue_latency_ms: load_config!(
+                general_config.api_config,
+                web3_json_rpc.load_shedding_vm_queue_latency_ms
+            ),
+            load_shedding_pool_utilization: load_config!(
+                general_config.api_config,
+                web3_json_rpc.load_shedding_pool_utilization
+            ),
+            load_shedding_retry_after_ms: load_optional_config_or_default!(
+                general_config.api_config,
+                web3_json_rpc.load_shedding_retry_after_ms,
+                default_load_shedding_retry_after_ms
+            ),
             filters_disabled: general_config
                 .api_config
                 .as_ref()
@@ -643,6 +730,18 @@ impl OptionalENConfig {
                 general_config.db_config,
                 experimental.state_keeper_db_max_open_files
             ),
+            state_keeper_db_state_cache_capacity_mb: load_config!(
+                general_config.db_config,
+                experimental.state_keeper_db_state_cache_capacity_mb
+            ),
+            state_keeper_db_soft_pending_compaction_limit_mb: load_config!(
+                general_config.db_config,
+                experimental.state_keeper_db_soft_pending_compaction_limit_mb
+            ),
+            state_keeper_db_hard_pending_compaction_limit_mb: load_config!(
+                general_config.db_config,
+                experimental.state_keeper_db_hard_pending_compaction_limit_mb
+            ),
             merkle_tree_multi_get_chunk_size: load_config_or_default!(
                 general_config.db_config,
                 merkle_tree.multi_get_chunk_size,
@@ -663,12 +762,38 @@ impl OptionalENConfig {
                 merkle_tree.stalled_writes_timeout_sec,
                 default_merkle_tree_stalled_writes_timeout_sec
             ),
+            merkle_tree_hashing_thread_count: load_config!(
+                general_config.db_config,
+                merkle_tree.hashing_thread_count
+            ),
+            merkle_tree_pruning_retained_l1_batches: load_config!(
+                general_config.db_config,
+                merkle_tree.pruning_retained_l1_batches
+            ),
             merkle_tree_repair_stale_keys: general_config
                 .db_config
                 .as_ref()
                 .map_or(false, |config| {
                     config.experimental.merkle_tree_repair_stale_keys
                 }),
+            merkle_tree_consistency_check_interval_sec: general_config.db_config.as_ref().and_then(
+                |config| {
+                    config
+                        .experimental
+                        .merkle_tree_consistency_check_interval_sec
+                },
+            ),
+            merkle_tree_consistency_check_subtree_delay_ms: general_config
+                .db_config
+                .as_ref()
+                .map_or(
+                    Self::default_merkle_tree_consistency_check_subtree_delay_ms(),
+                    |config| {
+                        config
+                            .experimental
+                            .merkle_tree_consistency_check_subtree_delay_ms
+                    },
+                ),
             database_long_connection_threshold_ms: load_config!(
                 general_config.postgres_config,
                 long_connection_threshold_ms
@@ -717,11 +842,29 @@ impl OptionalENConfig {
                 data_retention_sec,
                 default_pruning_data_retention_sec
             ),
+            pruning_call_traces_retention_sec: load_config!(
+                general_config.pruning,
+                call_traces_retention_sec
+            ),
+            pruning_events_retention_sec: load_config!(
+                general_config.pruning,
+                events_retention_sec
+            ),
+            pruning_dry_run: general_config
+                .pruning
+                .as_ref()
+                .map(|a| a.dry_run)
+                .unwrap_or_default(),
             protective_reads_persistence_enabled: general_config
                 .db_config
                 .as_ref()
                 .map(|a| a.experimental.protective_reads_persistence_enabled)
                 .unwrap_or_default(),
+            storage_slot_writers_enabled: general_config
+                .state_keeper_config
+                .as_ref()
+                .map(|config| config.storage_slot_writers_enabled)
+                .unwrap_or_default(),
             merkle_tree_processing_delay_ms: load_config_or_default!(
                 general_config.db_config,
                 experimental.processing_delay_ms,
@@ -747,6 +890,11 @@ impl OptionalENConfig {
                 .as_ref()
                 .and_then(|l1| l1.gateway_rpc_url.clone()),
             bridge_addresses_refresh_interval_sec: enconfig.bridge_addresses_refresh_interval_sec,
+            cross_verification_enabled: enconfig.cross_verification_enabled,
+            max_auto_rollback_l1_batches: enconfig.max_auto_rollback_l1_batches,
+            response_checker_enabled: enconfig.response_checker_enabled,
+            response_checker_peer_url: enconfig.response_checker_peer_url.clone(),
+            response_checker_stride: enconfig.response_checker_stride,
             timestamp_asserter_min_time_till_end_sec: general_config
                 .timestamp_asserter_config
                 .as_ref()
@@ -826,6 +974,10 @@ impl OptionalENConfig {
         128
     }
 
+    const fn default_merkle_tree_consistency_check_subtree_delay_ms() -> u64 {
+        100
+    }
+
     const fn default_merkle_tree_memtable_capacity_mb() -> usize {
         256
     }
@@ -842,6 +994,10 @@ impl OptionalENConfig {
         500 // The default limit is chosen to be reasonably permissive.
     }
 
+    const fn default_load_shedding_retry_after_ms() -> u64 {
+        1_000
+    }
+
     const fn default_max_response_body_size_mb() -> usize {
         10
     }
@@ -902,6 +1058,16 @@ impl OptionalENConfig {
         Duration::from_millis(self.pubsub_polling_interval_ms)
     }
 
+    /// Returns the interval between Merkle tree consistency checks, or `None` if the check is disabled.
+    pub fn merkle_tree_consistency_check_interval(&self) -> Option<Duration> {
+        self.merkle_tree_consistency_check_interval_sec
+            .map(Duration::from_secs)
+    }
+
+    pub fn merkle_tree_consistency_check_subtree_delay(&self) -> Duration {
+        Duration::from_millis(self.merkle_tree_consistency_check_subtree_delay_ms)
+    }
+
     pub fn merkle_tree_processing_delay(&self) -> Duration {
         Duration::from_millis(self.merkle_tree_processing_delay_ms)
     }
@@ -921,6 +1087,27 @@ impl OptionalENConfig {
         self.latest_values_cache_size_mb * BYTES_IN_MEGABYTE
     }
 
+    /// Returns the time window for batching storage reads, if read batching is enabled.
+    pub fn storage_read_batching_window(&self) -> Option<Duration> {
+        self.storage_read_batching_window_ms
+            .map(Duration::from_millis)
+    }
+
+    /// Returns the capacity of the `eth_call` cache, or `None` if the cache is disabled.
+    pub fn eth_call_cache_size(&self) -> Option<NonZeroUsize> {
+        self.eth_call_cache_size.and_then(NonZeroUsize::new)
+    }
+
+    fn load_shedding_config(&self) -> LoadSheddingConfig {
+        LoadSheddingConfig {
+            vm_queue_latency_threshold: self
+                .load_shedding_vm_queue_latency_ms
+                .map(Duration::from_millis),
+            pool_utilization_threshold: self.load_shedding_pool_utilization,
+            retry_after: Duration::from_millis(self.load_shedding_retry_after_ms),
+        }
+    }
+
     /// Returns the size of block cache for Merkle tree in bytes.
     pub fn merkle_tree_block_cache_size(&self) -> usize {
         self.merkle_tree_block_cache_size_mb * BYTES_IN_MEGABYTE
@@ -960,6 +1147,13 @@ impl OptionalENConfig {
         }
     }
 
+    /// Returns the maximum size of a trace returned by `debug_trace*` methods in bytes.
+    pub fn max_trace_size(&self) -> usize {
+        self.max_trace_size_mb
+            .unwrap_or(self.max_response_body_size_mb)
+            * BYTES_IN_MEGABYTE
+    }
+
     pub fn healthcheck_slow_time_limit(&self) -> Option<Duration> {
         self.healthcheck_slow_time_limit_ms
             .map(Duration::from_millis)
@@ -982,6 +1176,21 @@ impl OptionalENConfig {
         Duration::from_secs(self.pruning_data_retention_sec)
     }
 
+    pub fn pruning_call_traces_retention(&self) -> Option<Duration> {
+        self.pruning_call_traces_retention_sec
+            .map(Duration::from_secs)
+    }
+
+    pub fn pruning_events_retention(&self) -> Option<Duration> {
+        self.pruning_events_retention_sec.map(Duration::from_secs)
+    }
+
+    /// Returns `true` if at least one data retention policy is configured.
+    pub fn has_data_retention_policies(&self) -> bool {
+        self.pruning_call_traces_retention_sec.is_some()
+            || self.pruning_events_retention_sec.is_some()
+    }
+
     pub fn bridge_addresses_refresh_interval(&self) -> Option<Duration> {
         self.bridge_addresses_refresh_interval_sec
             .map(|n| Duration::from_secs(n.get()))
@@ -1132,6 +1341,13 @@ pub(crate) struct ExperimentalENConfig {
     /// Maximum number of files concurrently opened by state keeper cache RocksDB. Useful to fit into OS limits; can be used
     /// as a rudimentary way to control RAM usage of the cache.
     pub state_keeper_db_max_open_files: Option<NonZeroU32>,
+    /// Capacity of the dedicated block cache for the state column family of the state keeper RocksDB cache.
+    /// If not set, the state column family uses the shared block cache.
+    state_keeper_db_state_cache_capacity_mb: Option<usize>,
+    /// Estimated number of bytes pending compaction in the state keeper RocksDB cache at which writes are slowed down.
+    state_keeper_db_soft_pending_compaction_limit_mb: Option<usize>,
+    /// Estimated number of bytes pending compaction in the state keeper RocksDB cache at which writes are stopped.
+    state_keeper_db_hard_pending_compaction_limit_mb: Option<usize>,
 
     // Snapshot recovery
     /// L1 batch number of the snapshot to use during recovery. Specifying this parameter is mostly useful for testing.
@@ -1140,6 +1356,9 @@ pub(crate) struct ExperimentalENConfig {
     /// This is a temporary flag that will eventually be removed together with version 0 snapshot support.
     #[serde(default)]
     pub snapshots_recovery_drop_storage_key_preimages: bool,
+    /// Enables recovering from a chain of differential snapshots if the main node advertises one.
+    #[serde(default)]
+    pub snapshots_recovery_apply_differential_snapshots: bool,
     /// Approximate chunk size (measured in the number of entries) to recover in a single iteration.
     /// Reasonable values are order of 100,000 (meaning an iteration takes several seconds).
     ///
@@ -1153,6 +1372,10 @@ pub(crate) struct ExperimentalENConfig {
     /// If not set, parallel persistence will be disabled.
     #[serde(default)] // Temporarily use a conservative option (sequential recovery) as default
     pub snapshots_recovery_tree_parallel_persistence_buffer: Option<NonZeroUsize>,
+    /// Enables inserting entries of each recovered chunk into the Merkle tree in parallel, with a separate writer
+    /// for each top-level subtree of the tree.
+    #[serde(default)]
+    pub snapshots_recovery_tree_parallel_extension: bool,
 
     // Commitment generator
     /// Maximum degree of parallelism during commitment generation, i.e., the maximum number of L1 batches being processed in parallel.
@@ -1175,10 +1398,15 @@ impl ExperimentalENConfig {
             state_keeper_db_block_cache_capacity_mb:
                 Self::default_state_keeper_db_block_cache_capacity_mb(),
             state_keeper_db_max_open_files: None,
+            state_keeper_db_state_cache_capacity_mb: None,
+            state_keeper_db_soft_pending_compaction_limit_mb: None,
+            state_keeper_db_hard_pending_compaction_limit_mb: None,
             snapshots_recovery_l1_batch: None,
             snapshots_recovery_drop_storage_key_preimages: false,
+            snapshots_recovery_apply_differential_snapshots: false,
             snapshots_recovery_tree_chunk_size: Self::default_snapshots_recovery_tree_chunk_size(),
             snapshots_recovery_tree_parallel_persistence_buffer: None,
+            snapshots_recovery_tree_parallel_extension: false,
             commitment_generator_max_parallelism: None,
         }
     }
@@ -1188,6 +1416,22 @@ impl ExperimentalENConfig {
         self.state_keeper_db_block_cache_capacity_mb * BYTES_IN_MEGABYTE
     }
 
+    /// Returns the size of the dedicated block cache for the state column family in bytes.
+    pub fn state_keeper_db_state_cache_capacity(&self) -> Option<usize> {
+        self.state_keeper_db_state_cache_capacity_mb
+            .map(|mb| mb * BYTES_IN_MEGABYTE)
+    }
+
+    pub fn state_keeper_db_soft_pending_compaction_limit(&self) -> Option<usize> {
+        self.state_keeper_db_soft_pending_compaction_limit_mb
+            .map(|mb| mb * BYTES_IN_MEGABYTE)
+    }
+
+    pub fn state_keeper_db_hard_pending_compaction_limit(&self) -> Option<usize> {
+        self.state_keeper_db_hard_pending_compaction_limit_mb
+            .map(|mb| mb * BYTES_IN_MEGABYTE)
+    }
+
     pub fn from_configs(general_config: &GeneralConfig) -> anyhow::Result<Self> {
         Ok(Self {
             state_keeper_db_block_cache_capacity_mb: load_config_or_default!(
@@ -1209,10 +1453,18 @@ impl ExperimentalENConfig {
                 general_config.snapshot_recovery,
                 tree.parallel_persistence_buffer
             ),
+            snapshots_recovery_tree_parallel_extension: general_config
+                .snapshot_recovery
+                .as_ref()
+                .map_or(false, |config| config.tree.parallel_extension),
             snapshots_recovery_drop_storage_key_preimages: general_config
                 .snapshot_recovery
                 .as_ref()
                 .map_or(false, |config| config.drop_storage_key_preimages),
+            snapshots_recovery_apply_differential_snapshots: general_config
+                .snapshot_recovery
+                .as_ref()
+                .map_or(false, |config| config.apply_differential_snapshots),
             commitment_generator_max_parallelism: general_config
                 .commitment_generator
                 .as_ref()
@@ -1490,6 +1742,7 @@ impl From<&ExternalNodeConfig> for InternalApiConfigBase {
             estimate_gas_optimize_search: config.optional.estimate_gas_optimize_search,
             req_entities_limit: config.optional.req_entities_limit,
             fee_history_limit: config.optional.fee_history_limit,
+            max_trace_size: config.optional.max_trace_size(),
             filters_disabled: config.optional.filters_disabled,
             dummy_verifier: config.remote.dummy_verifier,
             l1_batch_commit_data_generator_mode: config.remote.l1_batch_commit_data_generator_mode,
@@ -1513,6 +1766,8 @@ impl From<&ExternalNodeConfig> for TxSenderConfig {
             // and they will be enforced by the main node anyway.
             max_allowed_l2_tx_gas_limit: u64::MAX,
             validation_computational_gas_limit: u32::MAX,
+            max_tx_abi_encoded_size: None,
+            max_tx_compressed_bytecodes_size: None,
             chain_id: config.required.l2_chain_id,
             // Does not matter for EN.
             whitelisted_tokens_for_aa: Default::default(),
@@ -1524,6 +1779,7 @@ impl From<&ExternalNodeConfig> for TxSenderConfig {
                     ),
                 }
             }),
+            load_shedding: config.optional.load_shedding_config(),
         }
     }
 }
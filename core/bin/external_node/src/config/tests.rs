@@ -101,6 +101,8 @@ fn parsing_optional_config_from_empty_env() {
         config.max_response_body_size().overrides,
         MaxResponseSizeOverrides::empty()
     );
+    assert_eq!(config.max_trace_size(), 10 * BYTES_IN_MEGABYTE);
+    assert!(!config.storage_slot_writers_enabled);
     assert_eq!(
         config.l1_batch_commit_data_generator_mode,
         L1BatchCommitmentMode::Rollup
@@ -122,15 +124,23 @@ fn parsing_optional_config_from_env() {
         ("EN_VM_CONCURRENCY_LIMIT", "1000"),
         ("EN_FACTORY_DEPS_CACHE_SIZE_MB", "64"),
         ("EN_LATEST_VALUES_CACHE_SIZE_MB", "50"),
+        ("EN_STORAGE_READ_BATCHING_WINDOW_MS", "5"),
+        ("EN_ETH_CALL_CACHE_SIZE", "1000"),
+        ("EN_LOAD_SHEDDING_VM_QUEUE_LATENCY_MS", "500"),
+        ("EN_LOAD_SHEDDING_POOL_UTILIZATION", "0.9"),
         ("EN_MERKLE_TREE_MULTI_GET_CHUNK_SIZE", "1000"),
         ("EN_MERKLE_TREE_BLOCK_CACHE_SIZE_MB", "32"),
         ("EN_MAX_RESPONSE_BODY_SIZE_MB", "1"),
+        ("EN_MAX_TRACE_SIZE_MB", "2"),
+        ("EN_STORAGE_SLOT_WRITERS_ENABLED", "true"),
         (
             "EN_MAX_RESPONSE_BODY_SIZE_OVERRIDES_MB",
             "zks_getProof=100,eth_call=2",
         ),
         ("EN_L1_BATCH_COMMIT_DATA_GENERATOR_MODE", "Validium"),
         ("EN_TIMESTAMP_ASSERTER_MIN_TIME_TILL_END_SEC", "2"),
+        ("EN_PRUNING_EVENTS_RETENTION_SEC", "86400"),
+        ("EN_PRUNING_DRY_RUN", "true"),
     ];
     let env_vars = env_vars
         .into_iter()
@@ -152,6 +162,25 @@ fn parsing_optional_config_from_env() {
     assert_eq!(config.vm_concurrency_limit, 1_000);
     assert_eq!(config.factory_deps_cache_size(), 64 * BYTES_IN_MEGABYTE);
     assert_eq!(config.latest_values_cache_size(), 50 * BYTES_IN_MEGABYTE);
+    assert_eq!(
+        config.storage_read_batching_window(),
+        Some(Duration::from_millis(5))
+    );
+    assert_eq!(config.eth_call_cache_size(), NonZeroUsize::new(1_000));
+    let load_shedding = config.load_shedding_config();
+    assert_eq!(
+        load_shedding.vm_queue_latency_threshold,
+        Some(Duration::from_millis(500))
+    );
+    assert_eq!(load_shedding.pool_utilization_threshold, Some(0.9));
+    assert_eq!(load_shedding.retry_after, Duration::from_secs(1));
+    assert_eq!(
+        config.pruning_events_retention(),
+        Some(Duration::from_secs(86_400))
+    );
+    assert_eq!(config.pruning_call_traces_retention(), None);
+    assert!(config.pruning_dry_run);
+    assert!(config.has_data_retention_policies());
     assert_eq!(config.merkle_tree_multi_get_chunk_size, 1_000);
     assert_eq!(
         config.merkle_tree_block_cache_size(),
@@ -172,6 +201,8 @@ fn parsing_optional_config_from_env() {
             )
         ])
     );
+    assert_eq!(config.max_trace_size(), 2 * BYTES_IN_MEGABYTE);
+    assert!(config.storage_slot_writers_enabled);
     assert_eq!(
         config.l1_batch_commit_data_generator_mode,
         L1BatchCommitmentMode::Validium
@@ -202,6 +233,8 @@ fn parsing_experimental_config_from_empty_env() {
     let config: ExperimentalENConfig = envy::prefixed("EN_EXPERIMENTAL_").from_iter([]).unwrap();
     assert_eq!(config.state_keeper_db_block_cache_capacity(), 128 << 20);
     assert_eq!(config.state_keeper_db_max_open_files, None);
+    assert_eq!(config.state_keeper_db_state_cache_capacity(), None);
+    assert_eq!(config.state_keeper_db_soft_pending_compaction_limit(), None);
 }
 
 #[test]
@@ -212,6 +245,14 @@ fn parsing_experimental_config_from_env() {
             "64",
         ),
         ("EN_EXPERIMENTAL_STATE_KEEPER_DB_MAX_OPEN_FILES", "100"),
+        (
+            "EN_EXPERIMENTAL_STATE_KEEPER_DB_STATE_CACHE_CAPACITY_MB",
+            "256",
+        ),
+        (
+            "EN_EXPERIMENTAL_STATE_KEEPER_DB_SOFT_PENDING_COMPACTION_LIMIT_MB",
+            "131072",
+        ),
     ];
     let env_vars = env_vars
         .into_iter()
@@ -222,4 +263,13 @@ fn parsing_experimental_config_from_env() {
         .unwrap();
     assert_eq!(config.state_keeper_db_block_cache_capacity(), 64 << 20);
     assert_eq!(config.state_keeper_db_max_open_files, NonZeroU32::new(100));
+    assert_eq!(
+        config.state_keeper_db_state_cache_capacity(),
+        Some(256 << 20)
+    );
+    assert_eq!(
+        config.state_keeper_db_soft_pending_compaction_limit(),
+        Some(128 << 30)
+    );
+    assert_eq!(config.state_keeper_db_hard_pending_compaction_limit(), None);
 }
@@ -32,6 +32,10 @@ struct Cli {
     /// do not use unless you know what you're doing.
     #[arg(long)]
     enable_consensus: bool,
+    /// When consensus-based syncing is enabled, fetches blocks from the main node via JSON-RPC whenever
+    /// the node lags behind the main node, e.g. because certificates for older blocks are not available yet.
+    #[arg(long, requires = "enable_consensus")]
+    consensus_gap_fallback: bool,
 
     /// Comma-separated list of components to launch.
     #[arg(long, default_value = "all")]
@@ -69,6 +73,8 @@ pub enum Component {
     TreeFetcher,
     Core,
     DataAvailabilityFetcher,
+    /// Read-only API served from a snapshot without Postgres. Cannot be combined with other components.
+    SnapshotApi,
 }
 
 impl Component {
@@ -82,6 +88,7 @@ impl Component {
             "tree_fetcher" => Ok(&[Component::TreeFetcher]),
             "da_fetcher" => Ok(&[Component::DataAvailabilityFetcher]),
             "core" => Ok(&[Component::Core]),
+            "snapshot_api" => Ok(&[Component::SnapshotApi]),
             "all" => Ok(&[
                 Component::HttpApi,
                 Component::WsApi,
@@ -174,6 +181,7 @@ fn main() -> anyhow::Result<()> {
         .context("failed fetching remote part of node config from main node")?;
 
     let node = ExternalNodeBuilder::on_runtime(runtime, config)
+        .with_consensus_gap_fallback(opt.consensus_gap_fallback)
         .build(opt.components.0.into_iter().collect())?;
     node.run(guard)?;
     anyhow::Ok(())
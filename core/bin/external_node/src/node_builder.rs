@@ -13,7 +13,8 @@ use zksync_config::{
     DAClientConfig, PostgresConfig,
 };
 use zksync_metadata_calculator::{
-    MerkleTreeReaderConfig, MetadataCalculatorConfig, MetadataCalculatorRecoveryConfig,
+    MerkleTreePruningPolicy, MerkleTreeReaderConfig, MetadataCalculatorConfig,
+    MetadataCalculatorRecoveryConfig,
 };
 use zksync_node_api_server::web3::{state::InternalApiConfigBase, Namespace};
 use zksync_node_framework::{
@@ -28,6 +29,7 @@ use zksync_node_framework::{
             no_da::NoDAClientWiringLayer, object_store::ObjectStorageClientWiringLayer,
         },
         data_availability_fetcher::DataAvailabilityFetcherLayer,
+        data_retention::DataRetentionLayer,
         healtcheck_server::HealthCheckLayer,
         l1_batch_commitment_mode_validation::L1BatchCommitmentModeValidationLayer,
         logs_bloom_backfill::LogsBloomBackfillLayer,
@@ -38,16 +40,19 @@ use zksync_node_framework::{
             external_node_strategy::{ExternalNodeInitStrategyLayer, SnapshotRecoveryConfig},
             NodeStorageInitializerLayer,
         },
+        object_store::ObjectStoreLayer,
         pools_layer::PoolsLayerBuilder,
         postgres::PostgresLayer,
         prometheus_exporter::PrometheusExporterLayer,
         pruning::PruningLayer,
         query_eth_client::QueryEthClientLayer,
         reorg_detector::ReorgDetectorLayer,
+        response_checker::ResponseCheckerLayer,
         settlement_layer_client::SettlementLayerClientLayer,
         settlement_layer_data,
         settlement_layer_data::SettlementLayerData,
         sigint::SigintHandlerLayer,
+        snapshot_api::SnapshotApiLayer,
         state_keeper::{
             external_io::ExternalIOLayer, main_batch_executor::MainBatchExecutorLayer,
             output_handler::OutputHandlerLayer, StateKeeperLayer,
@@ -65,7 +70,8 @@ use zksync_node_framework::{
     },
     service::{ZkStackService, ZkStackServiceBuilder},
 };
-use zksync_state::RocksdbStorageOptions;
+use zksync_node_snapshot_api::SnapshotApiConfig;
+use zksync_state::{CompactionOptions, RocksdbStorageOptions};
 
 use crate::{config::ExternalNodeConfig, metrics::framework::ExternalNodeMetricsLayer, Component};
 
@@ -74,6 +80,7 @@ use crate::{config::ExternalNodeConfig, metrics::framework::ExternalNodeMetricsL
 pub(crate) struct ExternalNodeBuilder {
     pub(crate) node: ZkStackServiceBuilder,
     config: ExternalNodeConfig,
+    consensus_gap_fallback: bool,
 }
 
 impl ExternalNodeBuilder {
@@ -82,6 +89,7 @@ impl ExternalNodeBuilder {
         Ok(Self {
             node: ZkStackServiceBuilder::new().context("Cannot create ZkStackServiceBuilder")?,
             config,
+            consensus_gap_fallback: false,
         })
     }
 
@@ -89,9 +97,16 @@ impl ExternalNodeBuilder {
         Self {
             node: ZkStackServiceBuilder::on_runtime(runtime),
             config,
+            consensus_gap_fallback: false,
         }
     }
 
+    /// Enables fetching blocks via JSON-RPC if consensus-based syncing lags behind the main node.
+    pub fn with_consensus_gap_fallback(mut self, consensus_gap_fallback: bool) -> Self {
+        self.consensus_gap_fallback = consensus_gap_fallback;
+        self
+    }
+
     fn add_sigint_handler_layer(mut self) -> anyhow::Result<Self> {
         self.node.add_layer(SigintHandlerLayer);
         Ok(self)
@@ -229,6 +244,9 @@ impl ExternalNodeBuilder {
                 .with_pre_insert_txs(true) // EN requires txs to be pre-inserted.
                 .with_protective_reads_persistence_enabled(
                     self.config.optional.protective_reads_persistence_enabled,
+                )
+                .with_storage_slot_writers_enabled(
+                    self.config.optional.storage_slot_writers_enabled,
                 );
 
         let io_layer = ExternalIOLayer::new(self.config.required.l2_chain_id);
@@ -248,6 +266,21 @@ impl ExternalNodeBuilder {
                 .experimental
                 .state_keeper_db_block_cache_capacity(),
             max_open_files: self.config.experimental.state_keeper_db_max_open_files,
+            state_block_cache_capacity: self
+                .config
+                .experimental
+                .state_keeper_db_state_cache_capacity(),
+            state_compaction: CompactionOptions {
+                soft_pending_compaction_bytes_limit: self
+                    .config
+                    .experimental
+                    .state_keeper_db_soft_pending_compaction_limit(),
+                hard_pending_compaction_bytes_limit: self
+                    .config
+                    .experimental
+                    .state_keeper_db_hard_pending_compaction_limit(),
+                ..CompactionOptions::default()
+            },
         };
         let state_keeper_layer = StateKeeperLayer::new(
             self.config.required.state_cache_path.clone(),
@@ -270,6 +303,7 @@ impl ExternalNodeBuilder {
                 .context("CRATE_VERSION.parse()")?,
             config,
             secrets,
+            gap_fallback: self.consensus_gap_fallback,
         };
         self.node.add_layer(layer);
         Ok(self)
@@ -281,7 +315,8 @@ impl ExternalNodeBuilder {
                 self.config.optional.pruning_removal_delay(),
                 self.config.optional.pruning_chunk_size,
                 self.config.optional.pruning_data_retention(),
-            );
+            )
+            .with_dry_run(self.config.optional.pruning_dry_run);
             self.node.add_layer(layer);
         } else {
             tracing::info!("Pruning is disabled");
@@ -289,6 +324,17 @@ impl ExternalNodeBuilder {
         Ok(self)
     }
 
+    fn add_data_retention_layer(mut self) -> anyhow::Result<Self> {
+        if self.config.optional.has_data_retention_policies() {
+            let layer = DataRetentionLayer::new(
+                self.config.optional.pruning_call_traces_retention(),
+                self.config.optional.pruning_events_retention(),
+            );
+            self.node.add_layer(layer);
+        }
+        Ok(self)
+    }
+
     fn add_l1_batch_commitment_mode_validation_layer(mut self) -> anyhow::Result<Self> {
         let layer = L1BatchCommitmentModeValidationLayer::new(
             self.config.optional.l1_batch_commit_data_generator_mode,
@@ -383,6 +429,18 @@ impl ExternalNodeBuilder {
         Ok(self)
     }
 
+    fn add_response_checker_layer(mut self) -> anyhow::Result<Self> {
+        if self.config.optional.response_checker_enabled {
+            let layer = ResponseCheckerLayer::new(
+                self.config.optional.response_checker_peer_url.clone(),
+                self.config.required.l2_chain_id,
+            )
+            .with_stride(self.config.optional.response_checker_stride);
+            self.node.add_layer(layer);
+        }
+        Ok(self)
+    }
+
     fn add_sync_state_updater_layer(mut self) -> anyhow::Result<Self> {
         // This layer may be used as a fallback for EN API if API server runs without the core component.
         self.node.add_layer(SyncStateUpdaterLayer);
@@ -404,6 +462,7 @@ impl ExternalNodeBuilder {
                 .merkle_tree_include_indices_and_filters_in_block_cache,
             memtable_capacity: self.config.optional.merkle_tree_memtable_capacity(),
             stalled_writes_timeout: self.config.optional.merkle_tree_stalled_writes_timeout(),
+            hashing_thread_count: self.config.optional.merkle_tree_hashing_thread_count,
             sealed_batches_have_protective_reads: self
                 .config
                 .optional
@@ -414,7 +473,14 @@ impl ExternalNodeBuilder {
                     .config
                     .experimental
                     .snapshots_recovery_tree_parallel_persistence_buffer,
+                parallel_extension: self
+                    .config
+                    .experimental
+                    .snapshots_recovery_tree_parallel_extension,
             },
+            // External nodes always keep the tree on disk.
+            in_memory: false,
+            in_memory_persistence_interval: None,
         };
 
         // Configure basic tree layer.
@@ -437,9 +503,30 @@ impl ExternalNodeBuilder {
             layer = layer.with_stale_keys_repair();
         }
 
+        // Add tree consistency check if requested.
+        if let Some(poll_interval) = self
+            .config
+            .optional
+            .merkle_tree_consistency_check_interval()
+        {
+            layer = layer.with_consistency_check(
+                poll_interval,
+                self.config
+                    .optional
+                    .merkle_tree_consistency_check_subtree_delay(),
+            );
+        }
+
         // Add tree pruning if needed.
-        if self.config.optional.pruning_enabled {
-            layer = layer.with_pruning_config(self.config.optional.pruning_removal_delay());
+        let retained_l1_batches = self.config.optional.merkle_tree_pruning_retained_l1_batches;
+        if self.config.optional.pruning_enabled || retained_l1_batches.is_some() {
+            layer = layer
+                .with_pruning_config(self.config.optional.pruning_removal_delay())
+                .with_pruning_policy(MerkleTreePruningPolicy {
+                    follow_postgres_pruning: self.config.optional.pruning_enabled,
+                    retained_l1_batches,
+                    retain_unproven_l1_batches: false,
+                });
         }
 
         self.node.add_layer(layer);
@@ -475,6 +562,7 @@ impl ExternalNodeBuilder {
             initial_writes_cache_size: self.config.optional.initial_writes_cache_size() as u64,
             latest_values_cache_size: self.config.optional.latest_values_cache_size() as u64,
             latest_values_max_block_lag: 20, // reasonable default
+            storage_read_batching_window: self.config.optional.storage_read_batching_window(),
         };
         let max_vm_concurrency = self.config.optional.vm_concurrency_limit;
         let tx_sender_layer = TxSenderLayer::new(
@@ -538,8 +626,10 @@ impl ExternalNodeBuilder {
                 .optional
                 .bridge_addresses_refresh_interval(),
             polling_interval: Some(self.config.optional.polling_interval()),
+            eth_call_cache_size: self.config.optional.eth_call_cache_size(),
             websocket_requests_per_minute_limit: None, // To be set by WS server layer method if required.
             replication_lag_limit: None,               // TODO: Support replication lag limit
+            cold_storage_object_store: None,           // Cold storage archiving is main node-only
         }
     }
 
@@ -571,7 +661,11 @@ impl ExternalNodeBuilder {
     }
 
     fn add_reorg_detector_layer(mut self) -> anyhow::Result<Self> {
-        self.node.add_layer(ReorgDetectorLayer);
+        self.node.add_layer(
+            ReorgDetectorLayer::default()
+                .with_cross_verification(self.config.optional.cross_verification_enabled)
+                .with_max_rollback_depth(self.config.optional.max_auto_rollback_l1_batches),
+        );
         Ok(self)
     }
 
@@ -611,6 +705,9 @@ impl ExternalNodeBuilder {
                     drop_storage_key_preimages: config
                         .experimental
                         .snapshots_recovery_drop_storage_key_preimages,
+                    apply_differential_snapshots: config
+                        .experimental
+                        .snapshots_recovery_apply_differential_snapshots,
                     object_store_config: config.optional.snapshots_recovery_object_store.clone(),
                 });
         self.node.add_layer(ExternalNodeInitStrategyLayer {
@@ -620,6 +717,8 @@ impl ExternalNodeBuilder {
                 .optional
                 .snapshots_recovery_postgres_max_concurrency,
             snapshot_recovery_config,
+            cross_verification: self.config.optional.cross_verification_enabled,
+            max_rollback_depth: self.config.optional.max_auto_rollback_l1_batches,
         });
         let mut layer = NodeStorageInitializerLayer::new();
         if matches!(kind, LayerKind::Precondition) {
@@ -629,7 +728,41 @@ impl ExternalNodeBuilder {
         Ok(self)
     }
 
+    /// Builds a node serving the read-only snapshot API. Unlike other components, it doesn't use Postgres,
+    /// so most of the "base" layers are not added.
+    fn build_snapshot_api(mut self) -> anyhow::Result<ZkStackService> {
+        let object_store_config = self
+            .config
+            .optional
+            .snapshots_recovery_object_store
+            .clone()
+            .context("snapshot API requires snapshots recovery object store config")?;
+        let config = SnapshotApiConfig {
+            bind_address: ([0, 0, 0, 0], self.config.required.http_port).into(),
+            state_path: self.config.required.state_cache_path.clone().into(),
+            l2_chain_id: self.config.required.l2_chain_id,
+        };
+
+        self = self
+            .add_sigint_handler_layer()?
+            .add_healthcheck_layer()?
+            .add_prometheus_exporter_layer()?
+            .add_main_node_client_layer()?;
+        self.node
+            .add_layer(ObjectStoreLayer::new(object_store_config))
+            .add_layer(SnapshotApiLayer::new(config));
+        Ok(self.node.build())
+    }
+
     pub fn build(mut self, mut components: Vec<Component>) -> anyhow::Result<ZkStackService> {
+        if components.contains(&Component::SnapshotApi) {
+            anyhow::ensure!(
+                components.len() == 1,
+                "Snapshot API cannot be combined with other components"
+            );
+            return self.build_snapshot_api();
+        }
+
         // Add "base" layers
         self = self
             .add_sigint_handler_layer()?
@@ -717,16 +850,19 @@ impl ExternalNodeBuilder {
                         .add_da_client_layer()?
                         .add_data_availability_fetcher_layer()?;
                 }
+                Component::SnapshotApi => unreachable!("snapshot API is built separately"),
                 Component::Core => {
                     // Main tasks
                     self = self
                         .add_state_keeper_layer()?
                         .add_consensus_layer()?
                         .add_pruning_layer()?
+                        .add_data_retention_layer()?
                         .add_consistency_checker_layer()?
                         .add_commitment_generator_layer()?
                         .add_batch_status_updater_layer()?
-                        .add_logs_bloom_backfill_layer()?;
+                        .add_logs_bloom_backfill_layer()?
+                        .add_response_checker_layer()?;
                 }
             }
         }
@@ -10,8 +10,7 @@ use zksync_object_store::{ObjectStore, StoredObject};
 use zksync_types::{
     snapshots::{
         uniform_hashed_keys_chunk, SnapshotFactoryDependencies, SnapshotFactoryDependency,
-        SnapshotMetadata, SnapshotStorageLog, SnapshotStorageLogsChunk,
-        SnapshotStorageLogsStorageKey, SnapshotVersion,
+        SnapshotMetadata, SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey, SnapshotVersion,
     },
     L1BatchNumber, L2BlockNumber,
 };
@@ -25,6 +24,8 @@ use crate::tests::HandleEvent;
 struct SnapshotProgress {
     version: SnapshotVersion,
     l1_batch_number: L1BatchNumber,
+    /// L1 batch of the base snapshot for differential snapshots.
+    base_l1_batch_number: Option<L1BatchNumber>,
     /// `true` if the snapshot is new (i.e., its progress is not recovered from Postgres).
     is_new_snapshot: bool,
     chunk_count: u64,
@@ -32,10 +33,16 @@ struct SnapshotProgress {
 }
 
 impl SnapshotProgress {
-    fn new(version: SnapshotVersion, l1_batch_number: L1BatchNumber, chunk_count: u64) -> Self {
+    fn new(
+        version: SnapshotVersion,
+        l1_batch_number: L1BatchNumber,
+        base_l1_batch_number: Option<L1BatchNumber>,
+        chunk_count: u64,
+    ) -> Self {
         Self {
             version,
             l1_batch_number,
+            base_l1_batch_number,
             is_new_snapshot: true,
             chunk_count,
             remaining_chunk_ids: (0..chunk_count).collect(),
@@ -53,6 +60,7 @@ impl SnapshotProgress {
         Self {
             version: snapshot.version,
             l1_batch_number: snapshot.l1_batch_number,
+            base_l1_batch_number: snapshot.base_l1_batch_number,
             is_new_snapshot: false,
             chunk_count: snapshot.storage_logs_filepaths.len() as u64,
             remaining_chunk_ids,
@@ -82,6 +90,7 @@ impl SnapshotCreator {
         semaphore: &Semaphore,
         progress: &SnapshotProgress,
         l2_block_number: L2BlockNumber,
+        base_l2_block_number: Option<L2BlockNumber>,
         chunk_id: u64,
     ) -> anyhow::Result<()> {
         let chunk_count = progress.chunk_count;
@@ -98,7 +107,7 @@ impl SnapshotCreator {
 
         let latency =
             METRICS.storage_logs_processing_duration[&StorageChunkStage::LoadFromPostgres].start();
-        let (output_filepath, latency) = match progress.version {
+        let (output_filepath, checksum, latency) = match progress.version {
             SnapshotVersion::Version0 => {
                 #[allow(deprecated)] // support of version 0 snapshots will be removed eventually
                 let logs = conn
@@ -117,15 +126,27 @@ impl SnapshotCreator {
                     "Loaded chunk {chunk_id} ({} logs) from Postgres in {latency:?}",
                     logs.len()
                 );
-                self.store_storage_logs_chunk(l1_batch_number, chunk_id, logs)
-                    .await?
+                let chunk = SnapshotStorageLogsChunk { storage_logs: logs };
+                let (output_filepath, latency) = self
+                    .store_storage_logs_chunk(l1_batch_number, chunk_id, &chunk)
+                    .await?;
+                (output_filepath, None, latency)
             }
-            SnapshotVersion::Version1 => {
-                let logs = conn
-                    .snapshots_creator_dal()
-                    .get_storage_logs_chunk(l2_block_number, l1_batch_number, hashed_keys_range)
+            SnapshotVersion::Version1 | SnapshotVersion::Version2 => {
+                let mut dal = conn.snapshots_creator_dal();
+                let logs = if let Some(base_l2_block_number) = base_l2_block_number {
+                    dal.get_storage_logs_chunk_diff(
+                        base_l2_block_number,
+                        l2_block_number,
+                        l1_batch_number,
+                        hashed_keys_range,
+                    )
                     .await
-                    .context("error fetching storage logs")?;
+                } else {
+                    dal.get_storage_logs_chunk(l2_block_number, l1_batch_number, hashed_keys_range)
+                        .await
+                };
+                let logs = logs.context("error fetching storage logs")?;
                 drop(conn);
 
                 let latency = latency.observe();
@@ -133,8 +154,15 @@ impl SnapshotCreator {
                     "Loaded chunk {chunk_id} ({} logs) from Postgres in {latency:?}",
                     logs.len()
                 );
-                self.store_storage_logs_chunk(l1_batch_number, chunk_id, logs)
-                    .await?
+                let chunk = SnapshotStorageLogsChunk { storage_logs: logs };
+                let checksum = progress
+                    .version
+                    .has_chunk_indexes()
+                    .then(|| chunk.checksum());
+                let (output_filepath, latency) = self
+                    .store_storage_logs_chunk(l1_batch_number, chunk_id, &chunk)
+                    .await?;
+                (output_filepath, checksum, latency)
             }
         };
 
@@ -142,10 +170,19 @@ impl SnapshotCreator {
             .master_pool
             .connection_tagged("snapshots_creator")
             .await?;
-        master_conn
-            .snapshots_dal()
-            .add_storage_logs_filepath_for_snapshot(l1_batch_number, chunk_id, &output_filepath)
+        let mut dal = master_conn.snapshots_dal();
+        if let Some(checksum) = checksum {
+            dal.add_storage_logs_chunk_for_snapshot(
+                l1_batch_number,
+                chunk_id,
+                &output_filepath,
+                checksum,
+            )
             .await?;
+        } else {
+            dal.add_storage_logs_filepath_for_snapshot(l1_batch_number, chunk_id, &output_filepath)
+                .await?;
+        }
         #[cfg(test)]
         self.event_listener.on_chunk_saved();
 
@@ -161,21 +198,20 @@ impl SnapshotCreator {
         &self,
         l1_batch_number: L1BatchNumber,
         chunk_id: u64,
-        logs: Vec<SnapshotStorageLog<K>>,
+        storage_logs_chunk: &SnapshotStorageLogsChunk<K>,
     ) -> anyhow::Result<(String, Duration)>
     where
         for<'a> SnapshotStorageLogsChunk<K>: StoredObject<Key<'a> = SnapshotStorageLogsStorageKey>,
     {
         let latency =
             METRICS.storage_logs_processing_duration[&StorageChunkStage::SaveToGcs].start();
-        let storage_logs_chunk = SnapshotStorageLogsChunk { storage_logs: logs };
         let key = SnapshotStorageLogsStorageKey {
             l1_batch_number,
             chunk_id,
         };
         let filename = self
             .blob_store
-            .put(key, &storage_logs_chunk)
+            .put(key, storage_logs_chunk)
             .await
             .context("Error storing storage logs chunk in blob store")?;
         let output_filepath_prefix = self
@@ -189,6 +225,7 @@ impl SnapshotCreator {
     async fn process_factory_deps(
         &self,
         l2_block_number: L2BlockNumber,
+        base_l2_block_number: Option<L2BlockNumber>,
         l1_batch_number: L1BatchNumber,
     ) -> anyhow::Result<String> {
         let mut conn = self.connect_to_replica().await?;
@@ -196,10 +233,13 @@ impl SnapshotCreator {
         tracing::info!("Loading factory deps from Postgres...");
         let latency =
             METRICS.factory_deps_processing_duration[&FactoryDepsStage::LoadFromPostgres].start();
-        let factory_deps = conn
-            .snapshots_creator_dal()
-            .get_all_factory_deps(l2_block_number)
-            .await?;
+        let mut dal = conn.snapshots_creator_dal();
+        let factory_deps = if let Some(base_l2_block_number) = base_l2_block_number {
+            dal.get_factory_deps_diff(base_l2_block_number, l2_block_number)
+                .await?
+        } else {
+            dal.get_all_factory_deps(l2_block_number).await?
+        };
         drop(conn);
         let latency = latency.observe();
         tracing::info!("Loaded {} factory deps in {latency:?}", factory_deps.len());
@@ -255,6 +295,25 @@ impl SnapshotCreator {
                 )
             })?;
 
+        if let Some(base_snapshot) =
+            Self::select_base_snapshot(config, snapshot_version, l1_batch_number, conn).await?
+        {
+            // Differential snapshots must use the same chunking as the base snapshot, so that chunks
+            // with the same ID can be merged during recovery.
+            let chunk_count = base_snapshot.storage_logs_filepaths.len() as u64;
+            tracing::info!(
+                "Creating differential snapshot for L1 batch {l1_batch_number} based on snapshot for L1 batch {} \
+                 with {chunk_count} chunks",
+                base_snapshot.l1_batch_number
+            );
+            return Ok(Some(SnapshotProgress::new(
+                snapshot_version,
+                l1_batch_number,
+                Some(base_snapshot.l1_batch_number),
+                chunk_count,
+            )));
+        }
+
         let distinct_storage_logs_keys_count = conn
             .snapshots_creator_dal()
             .get_distinct_storage_logs_keys_count(l1_batch_number)
@@ -272,10 +331,68 @@ impl SnapshotCreator {
         Ok(Some(SnapshotProgress::new(
             snapshot_version,
             l1_batch_number,
+            None,
             chunk_count,
         )))
     }
 
+    /// Selects a snapshot to base a differential snapshot on. Returns `Ok(None)` if a full snapshot should be created.
+    async fn select_base_snapshot(
+        config: &SnapshotsCreatorConfig,
+        snapshot_version: SnapshotVersion,
+        l1_batch_number: L1BatchNumber,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<Option<SnapshotMetadata>> {
+        if config.max_differential_snapshots == 0 {
+            return Ok(None);
+        }
+        if snapshot_version != SnapshotVersion::Version1 {
+            tracing::info!(
+                "Differential snapshots are not supported for snapshot version {snapshot_version:?}; creating a full snapshot"
+            );
+            return Ok(None);
+        }
+
+        let Some(base_snapshot) = conn
+            .snapshots_dal()
+            .get_newest_complete_snapshot_metadata()
+            .await?
+        else {
+            return Ok(None);
+        };
+        if base_snapshot.l1_batch_number >= l1_batch_number
+            || base_snapshot.version != SnapshotVersion::Version1
+        {
+            return Ok(None);
+        }
+
+        // Compute the number of differential snapshots in the chain ending with `base_snapshot`.
+        let mut chain_len = 0;
+        let mut current_base = base_snapshot.base_l1_batch_number;
+        while let Some(number) = current_base {
+            chain_len += 1;
+            let snapshot = conn
+                .snapshots_dal()
+                .get_snapshot_metadata(number)
+                .await?
+                .with_context(|| {
+                    format!(
+                        "snapshot for L1 batch #{number} is referenced as a base, but is missing"
+                    )
+                })?;
+            current_base = snapshot.base_l1_batch_number;
+        }
+
+        if chain_len >= config.max_differential_snapshots {
+            tracing::info!(
+                "Snapshot chain ending with L1 batch #{} contains {chain_len} differential snapshots; creating a full snapshot",
+                base_snapshot.l1_batch_number
+            );
+            return Ok(None);
+        }
+        Ok(Some(base_snapshot))
+    }
+
     /// Returns `Ok(None)` if a snapshot should not be created / resumed.
     async fn load_or_initialize_snapshot_progress(
         &self,
@@ -363,12 +480,28 @@ impl SnapshotCreator {
             return Ok(());
         };
 
+        anyhow::ensure!(
+            progress.base_l1_batch_number.is_none()
+                || progress.version == SnapshotVersion::Version1,
+            "Differential snapshots are only supported for snapshot version 1"
+        );
         let mut conn = self.connect_to_replica().await?;
         let (_, last_l2_block_number_in_batch) = conn
             .blocks_dal()
             .get_l2_block_range_of_l1_batch(progress.l1_batch_number)
             .await?
             .context("No L2 blocks for L1 batch")?;
+        let base_l2_block_number = if let Some(base_l1_batch_number) = progress.base_l1_batch_number
+        {
+            let (_, last_l2_block_number) = conn
+                .blocks_dal()
+                .get_l2_block_range_of_l1_batch(base_l1_batch_number)
+                .await?
+                .context("No L2 blocks for base L1 batch")?;
+            Some(last_l2_block_number)
+        } else {
+            None
+        };
         drop(conn);
 
         METRICS.storage_logs_chunks_count.set(progress.chunk_count);
@@ -380,22 +513,36 @@ impl SnapshotCreator {
 
         if progress.is_new_snapshot {
             let factory_deps_output_file = self
-                .process_factory_deps(last_l2_block_number_in_batch, progress.l1_batch_number)
+                .process_factory_deps(
+                    last_l2_block_number_in_batch,
+                    base_l2_block_number,
+                    progress.l1_batch_number,
+                )
                 .await?;
 
             let mut master_conn = self
                 .master_pool
                 .connection_tagged("snapshots_creator")
                 .await?;
-            master_conn
-                .snapshots_dal()
-                .add_snapshot(
+            let mut dal = master_conn.snapshots_dal();
+            if let Some(base_l1_batch_number) = progress.base_l1_batch_number {
+                dal.add_differential_snapshot(
                     progress.version,
                     progress.l1_batch_number,
+                    base_l1_batch_number,
                     progress.chunk_count,
                     &factory_deps_output_file,
                 )
                 .await?;
+            } else {
+                dal.add_snapshot(
+                    progress.version,
+                    progress.l1_batch_number,
+                    progress.chunk_count,
+                    &factory_deps_output_file,
+                )
+                .await?;
+            }
         }
 
         METRICS
@@ -411,6 +558,7 @@ impl SnapshotCreator {
                     &semaphore,
                     &progress,
                     last_l2_block_number_in_batch,
+                    base_l2_block_number,
                     chunk_id,
                 )
             });
@@ -18,7 +18,8 @@ use zksync_types::{
     block::{L1BatchHeader, L1BatchTreeData, L2BlockHeader},
     snapshots::{
         SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotStorageLog,
-        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsChunkIndex, SnapshotStorageLogsStorageKey,
+        SnapshotVersion,
     },
     AccountTreeId, Address, L1BatchNumber, L2BlockNumber, ProtocolVersion, StorageKey, StorageLog,
     H256,
@@ -31,6 +32,7 @@ const TEST_CONFIG: SnapshotsCreatorConfig = SnapshotsCreatorConfig {
     l1_batch_number: None,
     storage_logs_chunk_size: 1_000_000,
     concurrent_queries_count: 10,
+    max_differential_snapshots: 0,
     object_store: None,
 };
 const SEQUENTIAL_TEST_CONFIG: SnapshotsCreatorConfig = SnapshotsCreatorConfig {
@@ -384,6 +386,96 @@ async fn persisting_snapshot_logs_with_specified_l1_batch() {
     }
 }
 
+#[tokio::test]
+async fn persisting_differential_snapshots() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let base_l1_batch_number = L1BatchNumber(4);
+    let config = SnapshotsCreatorConfig {
+        l1_batch_number: Some(base_l1_batch_number),
+        max_differential_snapshots: 1,
+        ..TEST_CONFIG
+    };
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config.clone(), MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    let snapshot_l1_batch_number = L1BatchNumber(7);
+    let config = SnapshotsCreatorConfig {
+        l1_batch_number: Some(snapshot_l1_batch_number),
+        ..config
+    };
+    // Use a different min chunk count to check that chunking is inherited from the base snapshot.
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config.clone(), MIN_CHUNK_COUNT * 2)
+        .await
+        .unwrap();
+
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert!(snapshot_metadata.is_complete());
+    assert_eq!(
+        snapshot_metadata.base_l1_batch_number,
+        Some(base_l1_batch_number)
+    );
+    assert_eq!(
+        snapshot_metadata.storage_logs_filepaths.len(),
+        MIN_CHUNK_COUNT as usize
+    );
+
+    let mut actual_logs = HashSet::new();
+    for chunk_id in 0..MIN_CHUNK_COUNT {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: snapshot_l1_batch_number,
+            chunk_id,
+        };
+        let chunk: SnapshotStorageLogsChunk = object_store.get(key).await.unwrap();
+        actual_logs.extend(chunk.storage_logs);
+    }
+    // All generated storage logs are initial writes, so the differential snapshot must contain logs for L1 batches 5..=7.
+    let expected_logs: HashSet<_> = expected_outputs
+        .storage_logs
+        .iter()
+        .filter(|log| {
+            log.l1_batch_number_of_initial_write > base_l1_batch_number
+                && log.l1_batch_number_of_initial_write <= snapshot_l1_batch_number
+        })
+        .cloned()
+        .collect();
+    assert_eq!(actual_logs, expected_logs);
+
+    let SnapshotFactoryDependencies { factory_deps } =
+        object_store.get(snapshot_l1_batch_number).await.unwrap();
+    assert_eq!(factory_deps.len(), 30);
+
+    // The maximum chain length is reached, so the next snapshot must be full.
+    let config = SnapshotsCreatorConfig {
+        l1_batch_number: Some(L1BatchNumber(8)),
+        ..config
+    };
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(L1BatchNumber(8))
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert!(!snapshot_metadata.is_differential());
+    assert_storage_logs(&*object_store, L1BatchNumber(8), &expected_outputs).await;
+}
+
 async fn assert_storage_logs(
     object_store: &dyn ObjectStore,
     snapshot_l1_batch_number: L1BatchNumber,
@@ -450,6 +542,50 @@ async fn persisting_snapshot_logs_for_v0_snapshot() {
     assert_eq!(actual_logs, expected_outputs.storage_logs);
 }
 
+#[tokio::test]
+async fn persisting_snapshot_logs_for_v2_snapshot() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let config = SnapshotsCreatorConfig {
+        version: 2,
+        ..TEST_CONFIG
+    };
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+    assert_storage_logs(&*object_store, snapshot_l1_batch_number, &expected_outputs).await;
+
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert_eq!(snapshot_metadata.version, SnapshotVersion::Version2);
+    assert_eq!(
+        snapshot_metadata.storage_logs_checksums.len(),
+        MIN_CHUNK_COUNT as usize
+    );
+    for (chunk_id, checksum) in snapshot_metadata.storage_logs_checksums.iter().enumerate() {
+        let checksum = checksum.expect("no checksum for chunk");
+        let chunk_id = chunk_id as u64;
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: snapshot_l1_batch_number,
+            chunk_id,
+        };
+        let chunk: SnapshotStorageLogsChunk = object_store.get(key).await.unwrap();
+        SnapshotStorageLogsChunkIndex::new(chunk_id, MIN_CHUNK_COUNT, checksum)
+            .verify(&chunk)
+            .unwrap();
+    }
+}
+
 #[test_casing(2, [false, true])]
 #[tokio::test]
 async fn recovery_workflow(specify_batch_after_recovery: bool) {
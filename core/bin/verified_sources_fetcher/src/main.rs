@@ -45,6 +45,20 @@ async fn main() {
                     std::fs::File::create(format!("{}/{}.sol", &dir, contact_name)).unwrap();
                 file.write_all(content.as_bytes()).unwrap();
             }
+            SourceCodeData::VyperSingleFile(content) => {
+                let file_name = if let Some((file_name, _contract_name)) =
+                    req.req.contract_name.rsplit_once(':')
+                {
+                    file_name.to_string()
+                } else {
+                    format!("{}.vy", req.req.contract_name)
+                };
+                let p = format!("{}/{}", &dir, file_name);
+                let path = std::path::Path::new(p.as_str());
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                let mut file = std::fs::File::create(path).unwrap();
+                file.write_all(content.as_bytes()).unwrap();
+            }
             SourceCodeData::YulSingleFile(content) => {
                 let mut file =
                     std::fs::File::create(format!("{}/{}.yul", &dir, req.req.contract_name))
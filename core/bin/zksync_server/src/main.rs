@@ -10,11 +10,13 @@ use zksync_config::{
             TimestampAsserterConfig,
         },
         house_keeper::HouseKeeperConfig,
-        BasicWitnessInputProducerConfig, ContractVerifierSecrets, DataAvailabilitySecrets,
-        DatabaseSecrets, ExperimentalVmConfig, ExternalPriceApiClientConfig,
-        FriProofCompressorConfig, FriProverConfig, FriProverGatewayConfig,
-        FriWitnessGeneratorConfig, L1Secrets, ObservabilityConfig, PrometheusConfig,
-        ProofDataHandlerConfig, ProtectiveReadsWriterConfig, Secrets,
+        wallets::Wallets,
+        AdminApiSecrets, BasicWitnessInputProducerConfig, BatchExporterConfig,
+        ContractVerifierSecrets, DataAvailabilitySecrets, DatabaseSecrets, ExperimentalVmConfig,
+        ExternalPriceApiClientConfig, FriProofCompressorConfig, FriProverConfig,
+        FriProverGatewayConfig, FriWitnessGeneratorConfig, L1Secrets, ObservabilityConfig,
+        PrometheusConfig, ProofDataHandlerConfig, ProtectiveReadsWriterConfig, Secrets,
+        TxPolicyConfig,
     },
     ApiConfig, BaseTokenAdjusterConfig, ContractVerifierConfig, ContractsConfig, DAClientConfig,
     DADispatcherConfig, DBConfig, EthConfig, EthWatchConfig, ExternalProofIntegrationApiConfig,
@@ -29,6 +31,7 @@ use zksync_env_config::FromEnv;
 use crate::node_builder::MainNodeBuilder;
 
 mod config;
+mod multi_chain;
 mod node_builder;
 
 #[cfg(not(target_env = "msvc"))]
@@ -65,6 +68,21 @@ struct Cli {
     /// Now the node framework is used by default and this argument is left for backward compatibility.
     #[arg(long)]
     use_node_framework: bool,
+    /// Path to the directory with configs of a chain (`general.yaml`, `secrets.yaml`, `contracts.yaml`, `wallets.yaml`
+    /// and `genesis.yaml`). Can be specified multiple times to serve multiple chains in a single process;
+    /// all chains run the same set of components and share the L1 client and the object store.
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "genesis",
+            "config_path",
+            "secrets_path",
+            "contracts_config_path",
+            "wallets_path",
+            "genesis_path",
+        ]
+    )]
+    chain_config_dir: Vec<std::path::PathBuf>,
 
     /// Only compose the node with the provided list of the components and then exit.
     /// Can be used to catch issues with configuration.
@@ -90,21 +108,22 @@ impl FromStr for ComponentsToRun {
 
 fn main() -> anyhow::Result<()> {
     let opt = Cli::parse();
+    if !opt.chain_config_dir.is_empty() {
+        return multi_chain::run(&opt.chain_config_dir, &opt.components.0, opt.no_run);
+    }
 
     // Load env config and use it if file config is not provided
     let tmp_config = load_env_config()?;
 
-    let configs = match opt.config_path {
+    let configs = match &opt.config_path {
         None => {
             let mut configs = tmp_config.general();
             configs.consensus_config =
                 config::read_consensus_config().context("read_consensus_config()")?;
             configs
         }
-        Some(path) => {
-            read_yaml_repr::<zksync_protobuf_config::proto::general::GeneralConfig>(&path)
-                .context("failed decoding general YAML config")?
-        }
+        Some(path) => read_yaml_repr::<zksync_protobuf_config::proto::general::GeneralConfig>(path)
+            .context("failed decoding general YAML config")?,
     };
 
     let wallets = match opt.wallets_path {
@@ -122,6 +141,7 @@ fn main() -> anyhow::Result<()> {
             l1: L1Secrets::from_env().ok(),
             data_availability: DataAvailabilitySecrets::from_env().ok(),
             contract_verifier: ContractVerifierSecrets::from_env().ok(),
+            admin_api: AdminApiSecrets::from_env().ok(),
         },
     };
 
@@ -141,7 +161,7 @@ fn main() -> anyhow::Result<()> {
         .clone()
         .context("observability config")?;
 
-    let node = MainNodeBuilder::new(
+    let mut node = MainNodeBuilder::new(
         configs,
         wallets,
         genesis,
@@ -154,6 +174,9 @@ fn main() -> anyhow::Result<()> {
         Some(contracts_config.settlement_layer_specific_contracts()),
         Some(contracts_config.l1_multicall3_addr),
     )?;
+    if let Some(config_path) = opt.config_path {
+        node = node.with_reloadable_config(config_path);
+    }
 
     let observability_guard = {
         // Observability initialization should be performed within tokio context.
@@ -217,5 +240,10 @@ fn load_env_config() -> anyhow::Result<TempConfigStore> {
         experimental_vm_config: ExperimentalVmConfig::from_env().ok(),
         prover_job_monitor_config: None,
         timestamp_asserter_config: TimestampAsserterConfig::from_env().ok(),
+        tx_policy_config: TxPolicyConfig::from_env().ok(),
+        batch_exporter_config: BatchExporterConfig::from_env().ok(),
+        soft_confirmation_signer: Wallets::from_env()
+            .ok()
+            .and_then(|wallets| wallets.soft_confirmation_signer),
     })
 }
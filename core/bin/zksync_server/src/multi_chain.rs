@@ -0,0 +1,217 @@
+//! Running multiple chains in a single process.
+//!
+//! Each chain is served by a separate [`ZkStackService`](zksync_node_framework::service::ZkStackService)
+//! with its own configs, database and API ports. All services run on a shared Tokio runtime and share
+//! the L1 client and the object store.
+//!
+//! Known limitations:
+//!
+//! - Circuit geometry overrides are process-global, so all chains must use the same geometry.
+//! - Metrics are process-global and are exported only by the Prometheus exporter of the first chain.
+//!   Likewise, observability is configured based on the config of the first chain.
+//! - The L1 client and the object store are created from the configs of the first chain.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Context as _;
+use zksync_config::{
+    configs::{wallets::Wallets, GeneralConfig, Secrets},
+    ContractsConfig, GenesisConfig,
+};
+use zksync_core_leftovers::{temp_config_store::read_yaml_repr, Component};
+use zksync_eth_client::clients::Client;
+use zksync_node_framework::{
+    implementations::resources::eth_interface::EthInterfaceResource, service::ZkStackServiceGroup,
+};
+use zksync_object_store::{ObjectStore, ObjectStoreFactory};
+
+use crate::node_builder::MainNodeBuilder;
+
+/// Resources shared by all chains served by the node.
+#[derive(Debug, Clone)]
+pub(crate) struct SharedResources {
+    pub l1_client: EthInterfaceResource,
+    pub object_store: Arc<dyn ObjectStore>,
+}
+
+/// Configs of a single chain read from a directory.
+#[derive(Debug)]
+struct ChainConfigs {
+    general: GeneralConfig,
+    general_config_path: PathBuf,
+    secrets: Secrets,
+    contracts: ContractsConfig,
+    wallets: Wallets,
+    genesis: GenesisConfig,
+}
+
+impl ChainConfigs {
+    /// Reads configs from the standard files in the specified directory.
+    fn read(dir: &Path) -> anyhow::Result<Self> {
+        let general_config_path = dir.join("general.yaml");
+        let general = read_yaml_repr::<zksync_protobuf_config::proto::general::GeneralConfig>(
+            &general_config_path,
+        )
+        .context("failed decoding general YAML config")?;
+        let secrets = read_yaml_repr::<zksync_protobuf_config::proto::secrets::Secrets>(
+            &dir.join("secrets.yaml"),
+        )
+        .context("failed decoding secrets YAML config")?;
+        let contracts = read_yaml_repr::<zksync_protobuf_config::proto::contracts::Contracts>(
+            &dir.join("contracts.yaml"),
+        )
+        .context("failed decoding contracts YAML config")?;
+        let wallets = read_yaml_repr::<zksync_protobuf_config::proto::wallets::Wallets>(
+            &dir.join("wallets.yaml"),
+        )
+        .context("failed decoding wallets YAML config")?;
+        let genesis = read_yaml_repr::<zksync_protobuf_config::proto::genesis::Genesis>(
+            &dir.join("genesis.yaml"),
+        )
+        .context("failed decoding genesis YAML config")?;
+        Ok(Self {
+            general,
+            general_config_path,
+            secrets,
+            contracts,
+            wallets,
+            genesis,
+        })
+    }
+
+    fn into_node_builder(self, group: &ZkStackServiceGroup) -> anyhow::Result<MainNodeBuilder> {
+        let node = MainNodeBuilder::on_service_builder(
+            group.service_builder(),
+            self.general,
+            self.wallets,
+            self.genesis,
+            self.secrets,
+            self.contracts.l1_specific_contracts(),
+            self.contracts.l2_contracts(),
+            Some(self.contracts.settlement_layer_specific_contracts()),
+            Some(self.contracts.l1_multicall3_addr),
+        )?;
+        Ok(node.with_reloadable_config(self.general_config_path))
+    }
+}
+
+/// Checks that the chains can be served by a single node.
+fn validate_chains(chains: &[ChainConfigs]) -> anyhow::Result<()> {
+    let first_chain = chains.first().context("no chains specified")?;
+
+    let mut chain_ids = HashSet::new();
+    let mut master_db_urls = HashSet::new();
+    for chain in chains {
+        let chain_id = chain.genesis.l2_chain_id;
+        anyhow::ensure!(
+            chain_ids.insert(chain_id),
+            "L2 chain ID {chain_id:?} is specified multiple times"
+        );
+
+        let master_db_url = chain
+            .secrets
+            .database
+            .as_ref()
+            .context("database secrets")?
+            .master_url()?;
+        anyhow::ensure!(
+            master_db_urls.insert(master_db_url.expose_str().to_owned()),
+            "chain {chain_id:?} shares the master DB URL with another chain"
+        );
+
+        anyhow::ensure!(
+            chain.genesis.l1_chain_id == first_chain.genesis.l1_chain_id,
+            "chain {chain_id:?} settles on a different L1 ({:?}) than other chains ({:?})",
+            chain.genesis.l1_chain_id,
+            first_chain.genesis.l1_chain_id
+        );
+        anyhow::ensure!(
+            chain.genesis.circuit_geometry == first_chain.genesis.circuit_geometry,
+            "chain {chain_id:?} uses a different circuit geometry than other chains"
+        );
+    }
+    Ok(())
+}
+
+/// Runs the node serving the chains with configs in the specified directories. `components` are launched for each chain.
+pub(crate) fn run(
+    chain_config_dirs: &[PathBuf],
+    components: &[Component],
+    no_run: bool,
+) -> anyhow::Result<()> {
+    let chains = chain_config_dirs
+        .iter()
+        .map(|dir| {
+            ChainConfigs::read(dir)
+                .with_context(|| format!("failed reading chain configs from {}", dir.display()))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    validate_chains(&chains)?;
+
+    let first_chain = &chains[0];
+    let observability_config = first_chain
+        .general
+        .observability
+        .clone()
+        .context("observability config")?;
+    let l1_chain_id = first_chain.genesis.l1_chain_id;
+    let l1_rpc_url = first_chain
+        .secrets
+        .l1
+        .as_ref()
+        .context("L1 secrets")?
+        .l1_rpc_url
+        .clone();
+    let object_store_config = first_chain
+        .general
+        .core_object_store
+        .clone()
+        .context("core object store config")?;
+
+    let mut group = ZkStackServiceGroup::new().context("Cannot create ZkStackServiceGroup")?;
+    let observability_guard = {
+        // Observability initialization should be performed within tokio context.
+        let _context_guard = group.runtime_handle().enter();
+        observability_config.install()?
+    };
+
+    let shared_resources = group.runtime_handle().block_on(async {
+        anyhow::Ok(SharedResources {
+            l1_client: EthInterfaceResource(Box::new(
+                Client::http(l1_rpc_url)
+                    .context("Client::new()")?
+                    .for_network(l1_chain_id.into())
+                    .build(),
+            )),
+            object_store: ObjectStoreFactory::new(object_store_config)
+                .create_store()
+                .await?,
+        })
+    })?;
+
+    for (i, chain) in chains.into_iter().enumerate() {
+        let chain_id = chain.genesis.l2_chain_id;
+        let mut node = chain
+            .into_node_builder(&group)?
+            .with_shared_resources(shared_resources.clone());
+        if i > 0 {
+            node = node.without_process_global_tasks();
+        }
+        let service = node
+            .compose(components.to_vec())
+            .with_context(|| format!("failed composing node for chain {chain_id:?}"))?;
+        group.add_service(format!("chain_{}", chain_id.as_u64()), service);
+    }
+
+    if no_run {
+        tracing::info!("Node composed successfully; exiting due to --no-run flag");
+        return Ok(());
+    }
+
+    group.run(observability_guard)?;
+    Ok(())
+}
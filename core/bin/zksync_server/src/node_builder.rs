@@ -1,89 +1,113 @@
 //! This module provides a "builder" for the main node,
 //! as well as an interface to run the node with the specified components.
 
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
 use anyhow::{bail, Context};
 use zksync_config::{
     configs::{
+        chain::StateKeeperConfig,
         contracts::{
             chain::L2Contracts, ecosystem::L1SpecificContracts, SettlementLayerSpecificContracts,
         },
         da_client::DAClientConfig,
         secrets::DataAvailabilitySecrets,
         wallets::Wallets,
-        GeneralConfig, Secrets,
+        CircuitGeometryConfig, GeneralConfig, Secrets,
     },
     GenesisConfig,
 };
-use zksync_core_leftovers::Component;
-use zksync_metadata_calculator::MetadataCalculatorConfig;
+use zksync_core_leftovers::{temp_config_store::read_yaml_repr, Component};
+use zksync_metadata_calculator::{MerkleTreePruningPolicy, MetadataCalculatorConfig};
+use zksync_multivm::{
+    tracers::{FrameGasCap, ResourceLimits},
+    utils::{set_circuit_geometry_overrides, CircuitGeometryOverrides},
+};
 use zksync_node_api_server::{
     tx_sender::TxSenderConfig,
     web3::{state::InternalApiConfigBase, Namespace},
 };
 use zksync_node_framework::{
-    implementations::layers::{
-        base_token::{
-            base_token_ratio_persister::BaseTokenRatioPersisterLayer,
-            base_token_ratio_provider::BaseTokenRatioProviderLayer, ExternalPriceApiLayer,
-        },
-        circuit_breaker_checker::CircuitBreakerCheckerLayer,
-        commitment_generator::CommitmentGeneratorLayer,
-        consensus::MainNodeConsensusLayer,
-        contract_verification_api::ContractVerificationApiLayer,
-        da_clients::{
-            avail::AvailWiringLayer, celestia::CelestiaWiringLayer, eigen::EigenWiringLayer,
-            no_da::NoDAClientWiringLayer, object_store::ObjectStorageClientWiringLayer,
-        },
-        da_dispatcher::DataAvailabilityDispatcherLayer,
-        eth_sender::{EthTxAggregatorLayer, EthTxManagerLayer},
-        eth_watch::EthWatchLayer,
-        external_proof_integration_api::ExternalProofIntegrationApiLayer,
-        gas_adjuster::GasAdjusterLayer,
-        gateway_migrator_layer::GatewayMigratorLayer,
-        healtcheck_server::HealthCheckLayer,
-        house_keeper::HouseKeeperLayer,
-        l1_batch_commitment_mode_validation::L1BatchCommitmentModeValidationLayer,
-        l1_gas::L1GasLayer,
-        logs_bloom_backfill::LogsBloomBackfillLayer,
-        metadata_calculator::MetadataCalculatorLayer,
-        node_storage_init::{
-            main_node_strategy::MainNodeInitStrategyLayer, NodeStorageInitializerLayer,
-        },
-        object_store::ObjectStoreLayer,
-        pk_signing_eth_client::PKSigningEthClientLayer,
-        pools_layer::PoolsLayerBuilder,
-        postgres::PostgresLayer,
-        prometheus_exporter::PrometheusExporterLayer,
-        proof_data_handler::ProofDataHandlerLayer,
-        query_eth_client::QueryEthClientLayer,
-        settlement_layer_client::SettlementLayerClientLayer,
-        settlement_layer_data::{MainNodeConfig, SettlementLayerData},
-        sigint::SigintHandlerLayer,
-        state_keeper::{
-            main_batch_executor::MainBatchExecutorLayer, mempool_io::MempoolIOLayer,
-            output_handler::OutputHandlerLayer, RocksdbStorageOptions, StateKeeperLayer,
-        },
-        vm_runner::{
-            bwip::BasicWitnessInputProducerLayer, playground::VmPlaygroundLayer,
-            protective_reads::ProtectiveReadsWriterLayer,
-        },
-        web3_api::{
-            caches::MempoolCacheLayer,
-            server::{Web3ServerLayer, Web3ServerOptionalConfig},
-            tree_api_client::TreeApiClientLayer,
-            tx_sender::{PostgresStorageCachesConfig, TxSenderLayer},
-            tx_sink::{whitelist::WhitelistedMasterPoolSinkLayer, MasterPoolSinkLayer},
+    implementations::{
+        layers::{
+            base_token::{
+                base_token_ratio_persister::BaseTokenRatioPersisterLayer,
+                base_token_ratio_provider::BaseTokenRatioProviderLayer, ExternalPriceApiLayer,
+            },
+            batch_exporter::BatchExporterLayer,
+            circuit_breaker_checker::CircuitBreakerCheckerLayer,
+            cold_storage::ColdStorageLayer,
+            commitment_generator::CommitmentGeneratorLayer,
+            config_reloader::{ConfigReloaderLayer, ReloadableConfig},
+            consensus::MainNodeConsensusLayer,
+            contract_verification_api::ContractVerificationApiLayer,
+            da_clients::{
+                avail::AvailWiringLayer, celestia::CelestiaWiringLayer, eigen::EigenWiringLayer,
+                no_da::NoDAClientWiringLayer, object_store::ObjectStorageClientWiringLayer,
+            },
+            da_dispatcher::DataAvailabilityDispatcherLayer,
+            data_retention::DataRetentionLayer,
+            eth_sender::{EthTxAggregatorLayer, EthTxManagerLayer},
+            eth_watch::EthWatchLayer,
+            external_proof_integration_api::ExternalProofIntegrationApiLayer,
+            gas_adjuster::GasAdjusterLayer,
+            gateway_migrator_layer::GatewayMigratorLayer,
+            healtcheck_server::HealthCheckLayer,
+            house_keeper::HouseKeeperLayer,
+            l1_batch_commitment_mode_validation::L1BatchCommitmentModeValidationLayer,
+            l1_gas::L1GasLayer,
+            logs_bloom_backfill::LogsBloomBackfillLayer,
+            metadata_calculator::MetadataCalculatorLayer,
+            node_storage_init::{
+                main_node_strategy::MainNodeInitStrategyLayer, NodeStorageInitializerLayer,
+            },
+            object_store::ObjectStoreLayer,
+            pk_signing_eth_client::PKSigningEthClientLayer,
+            pools_layer::PoolsLayerBuilder,
+            postgres::PostgresLayer,
+            prometheus_exporter::PrometheusExporterLayer,
+            proof_data_handler::ProofDataHandlerLayer,
+            query_eth_client::QueryEthClientLayer,
+            settlement_layer_client::SettlementLayerClientLayer,
+            settlement_layer_data::{MainNodeConfig, SettlementLayerData},
+            shared_resource::SharedResourceLayer,
+            sigint::SigintHandlerLayer,
+            state_keeper::{
+                main_batch_executor::MainBatchExecutorLayer, mempool_io::MempoolIOLayer,
+                output_handler::OutputHandlerLayer, sequencer_lease::SequencerLeaseLayer,
+                CompactionOptions, RocksdbStorageOptions, StateKeeperLayer,
+            },
+            vm_runner::{
+                bwip::BasicWitnessInputProducerLayer, playground::VmPlaygroundLayer,
+                protective_reads::ProtectiveReadsWriterLayer,
+                upgrade_dry_run::ProtocolUpgradeDryRunLayer,
+            },
+            web3_api::{
+                caches::MempoolCacheLayer,
+                server::{Web3ServerLayer, Web3ServerOptionalConfig},
+                tree_api_client::TreeApiClientLayer,
+                tx_sender::{PostgresStorageCachesConfig, TxSenderLayer},
+                tx_sink::{
+                    policy::PolicyEnforcingMasterPoolSinkLayer,
+                    whitelist::WhitelistedMasterPoolSinkLayer, MasterPoolSinkLayer,
+                },
+            },
         },
+        resources::object_store::ObjectStoreResource,
     },
     service::{ZkStackService, ZkStackServiceBuilder},
 };
+use zksync_object_store::PrefixedObjectStore;
 use zksync_types::{
     commitment::{L1BatchCommitmentMode, PubdataType},
     pubdata_da::PubdataSendingMode,
+    vm::FastVmMode,
     Address, SHARED_BRIDGE_ETHER_TOKEN_ADDRESS,
 };
 use zksync_vlog::prometheus::PrometheusExporterConfig;
 
+use crate::multi_chain::SharedResources;
+
 /// Macro that looks into a path to fetch an optional config,
 /// and clones it into a variable.
 macro_rules! try_load_config {
@@ -104,6 +128,55 @@ pub struct MainNodeBuilder {
     l1_sl_contracts: Option<SettlementLayerSpecificContracts>,
     l2_contracts: L2Contracts,
     multicall3: Option<Address>,
+    /// Path to the general config; if set, a subset of the config can be reloaded at runtime.
+    reloadable_config_path: Option<PathBuf>,
+    /// Resources shared with other chains if the node serves multiple chains.
+    shared_resources: Option<SharedResources>,
+    /// Whether to add tasks that can only be present once per process (the SIGINT handler and the Prometheus exporter).
+    process_global_tasks: bool,
+}
+
+fn circuit_geometry_overrides(
+    config: &CircuitGeometryConfig,
+) -> anyhow::Result<CircuitGeometryOverrides> {
+    Ok(CircuitGeometryOverrides {
+        cycles_per_vm_snapshot: config.cycles_per_vm_snapshot,
+        cycles_per_ram_permutation: config.cycles_per_ram_permutation,
+        cycles_per_storage_application: config.cycles_per_storage_application,
+        cycles_per_storage_sorter: config.cycles_per_storage_sorter,
+        cycles_per_code_decommitter: config.cycles_per_code_decommitter,
+        cycles_code_decommitter_sorter: config.cycles_code_decommitter_sorter,
+        cycles_per_log_demuxer: config.cycles_per_log_demuxer,
+        cycles_per_events_or_l1_messages_sorter: config.cycles_per_events_or_l1_messages_sorter,
+        cycles_per_keccak256_circuit: config.cycles_per_keccak256_circuit,
+        cycles_per_ecrecover_circuit: config.cycles_per_ecrecover_circuit,
+        cycles_per_sha256_circuit: config.cycles_per_sha256_circuit,
+        cycles_per_secp256r1_verify_circuit: config.cycles_per_secp256r1_verify_circuit,
+        cycles_per_transient_storage_sorter: config.cycles_per_transient_storage_sorter,
+        max_base_layer_circuits: config
+            .max_base_layer_circuits
+            .map(usize::try_from)
+            .transpose()
+            .context("max_base_layer_circuits")?,
+    })
+}
+
+/// Creates the per-frame gas cap from the state keeper config. The cap is only enforced by the legacy VM,
+/// so it cannot be combined with the fast VM.
+fn frame_gas_cap(
+    config: &StateKeeperConfig,
+    fast_vm_mode: FastVmMode,
+) -> anyhow::Result<FrameGasCap> {
+    anyhow::ensure!(
+        config.frame_gas_forwarding_divisor != Some(0),
+        "state_keeper.frame_gas_forwarding_divisor must be positive"
+    );
+    let frame_gas_cap = FrameGasCap::new(config.frame_gas_forwarding_divisor, config.max_frame_gas);
+    anyhow::ensure!(
+        !frame_gas_cap.is_enabled() || matches!(fast_vm_mode, FastVmMode::Old),
+        "frame gas cap is not supported by the fast VM (mode: {fast_vm_mode:?})"
+    );
+    Ok(frame_gas_cap)
 }
 
 impl MainNodeBuilder {
@@ -118,8 +191,40 @@ impl MainNodeBuilder {
         l1_sl_contracts: Option<SettlementLayerSpecificContracts>,
         multicall3: Option<Address>,
     ) -> anyhow::Result<Self> {
+        let node = ZkStackServiceBuilder::new().context("Cannot create ZkStackServiceBuilder")?;
+        Self::on_service_builder(
+            node,
+            configs,
+            wallets,
+            genesis_config,
+            secrets,
+            l1_specific_contracts,
+            l2_contracts,
+            l1_sl_contracts,
+            multicall3,
+        )
+    }
+
+    /// Same as [`Self::new()`], but uses the provided service builder (e.g., one running on a shared runtime).
+    pub fn on_service_builder(
+        node: ZkStackServiceBuilder,
+        configs: GeneralConfig,
+        wallets: Wallets,
+        genesis_config: GenesisConfig,
+        secrets: Secrets,
+        l1_specific_contracts: L1SpecificContracts,
+        l2_contracts: L2Contracts,
+        l1_sl_contracts: Option<SettlementLayerSpecificContracts>,
+        multicall3: Option<Address>,
+    ) -> anyhow::Result<Self> {
+        if let Some(geometry) = &genesis_config.circuit_geometry {
+            tracing::info!("Using custom circuit geometry: {geometry:?}");
+            set_circuit_geometry_overrides(circuit_geometry_overrides(geometry)?)
+                .context("failed setting circuit geometry")?;
+        }
+
         Ok(Self {
-            node: ZkStackServiceBuilder::new().context("Cannot create ZkStackServiceBuilder")?,
+            node,
             configs,
             wallets,
             genesis_config,
@@ -128,9 +233,33 @@ impl MainNodeBuilder {
             l1_sl_contracts,
             l2_contracts,
             multicall3,
+            reloadable_config_path: None,
+            shared_resources: None,
+            process_global_tasks: true,
         })
     }
 
+    /// Enables reloading a subset of the general config (fee model params, seal criteria limits,
+    /// API rate limits and log directives) from the specified YAML file on SIGHUP.
+    pub fn with_reloadable_config(mut self, config_path: PathBuf) -> Self {
+        self.reloadable_config_path = Some(config_path);
+        self
+    }
+
+    /// Uses the provided L1 client and object store instead of creating them from the configs.
+    /// Objects of the chain are stored under the `chain_{l2_chain_id}/` key prefix, so that chains don't collide.
+    pub fn with_shared_resources(mut self, resources: SharedResources) -> Self {
+        self.shared_resources = Some(resources);
+        self
+    }
+
+    /// Skips adding tasks that can only be present once per process. Should be used for all chains
+    /// but one if the node serves multiple chains.
+    pub fn without_process_global_tasks(mut self) -> Self {
+        self.process_global_tasks = false;
+        self
+    }
+
     pub fn runtime_handle(&self) -> tokio::runtime::Handle {
         self.node.runtime_handle()
     }
@@ -154,10 +283,30 @@ impl MainNodeBuilder {
     }
 
     fn add_sigint_handler_layer(mut self) -> anyhow::Result<Self> {
+        if !self.process_global_tasks {
+            return Ok(self);
+        }
         self.node.add_layer(SigintHandlerLayer);
         Ok(self)
     }
 
+    fn add_config_reloader_layer(mut self) -> anyhow::Result<Self> {
+        let Some(config_path) = self.reloadable_config_path.clone() else {
+            return Ok(self);
+        };
+        let initial_config = ReloadableConfig::from_general_config(&self.configs)?;
+        self.node
+            .add_layer(ConfigReloaderLayer::new(initial_config, move || {
+                let configs =
+                    read_yaml_repr::<zksync_protobuf_config::proto::general::GeneralConfig>(
+                        &config_path,
+                    )
+                    .context("failed decoding general YAML config")?;
+                ReloadableConfig::from_general_config(&configs)
+            }));
+        Ok(self)
+    }
+
     fn add_pools_layer(mut self) -> anyhow::Result<Self> {
         let config = try_load_config!(self.configs.postgres_config);
         let secrets = try_load_config!(self.secrets.database);
@@ -170,6 +319,10 @@ impl MainNodeBuilder {
     }
 
     fn add_prometheus_exporter_layer(mut self) -> anyhow::Result<Self> {
+        // Metrics are process-global, so they are exported by a single chain.
+        if !self.process_global_tasks {
+            return Ok(self);
+        }
         let prom_config = try_load_config!(self.configs.prometheus_config);
         let prom_config = PrometheusExporterConfig::pull(prom_config.listener_port);
         self.node.add_layer(PrometheusExporterLayer(prom_config));
@@ -193,6 +346,14 @@ impl MainNodeBuilder {
     }
 
     fn add_query_eth_client_layer(mut self) -> anyhow::Result<Self> {
+        if let Some(resources) = &self.shared_resources {
+            self.node.add_layer(SharedResourceLayer::new(
+                "shared_query_eth_client_layer",
+                resources.l1_client.clone(),
+            ));
+            return Ok(self);
+        }
+
         let genesis = self.genesis_config.clone();
         let eth_config = try_load_config!(self.secrets.l1);
         let query_eth_client_layer =
@@ -213,8 +374,15 @@ impl MainNodeBuilder {
         let gas_adjuster_config = try_load_config!(self.configs.eth)
             .gas_adjuster
             .context("Gas adjuster")?;
+        let fee_source_urls = self
+            .secrets
+            .l1
+            .as_ref()
+            .map(|secrets| secrets.fee_source_rpc_urls.clone())
+            .unwrap_or_default();
         let gas_adjuster_layer =
-            GasAdjusterLayer::new(gas_adjuster_config, self.genesis_config.clone());
+            GasAdjusterLayer::new(gas_adjuster_config, self.genesis_config.clone())
+                .with_fee_source_urls(fee_source_urls);
         self.node.add_layer(gas_adjuster_layer);
         Ok(self)
     }
@@ -233,6 +401,16 @@ impl MainNodeBuilder {
     }
 
     fn add_object_store_layer(mut self) -> anyhow::Result<Self> {
+        if let Some(resources) = &self.shared_resources {
+            let prefix = format!("chain_{}", self.genesis_config.l2_chain_id.as_u64());
+            let object_store = PrefixedObjectStore::new(resources.object_store.clone(), prefix);
+            self.node.add_layer(SharedResourceLayer::new(
+                "shared_object_store_layer",
+                ObjectStoreResource(Arc::new(object_store)),
+            ));
+            return Ok(self);
+        }
+
         let object_store_config = try_load_config!(self.configs.core_object_store);
         self.node
             .add_layer(ObjectStoreLayer::new(object_store_config));
@@ -248,7 +426,8 @@ impl MainNodeBuilder {
     }
 
     fn add_metadata_calculator_layer(mut self, with_tree_api: bool) -> anyhow::Result<Self> {
-        let merkle_tree_env_config = try_load_config!(self.configs.db_config).merkle_tree;
+        let db_config = try_load_config!(self.configs.db_config);
+        let merkle_tree_env_config = db_config.merkle_tree;
         let operations_manager_env_config =
             try_load_config!(self.configs.operations_manager_config);
         let state_keeper_env_config = try_load_config!(self.configs.state_keeper_config);
@@ -262,6 +441,26 @@ impl MainNodeBuilder {
             let merkle_tree_api_config = try_load_config!(self.configs.api_config).merkle_tree;
             layer = layer.with_tree_api_config(merkle_tree_api_config);
         }
+        if let Some(retained_l1_batches) = merkle_tree_env_config.pruning_retained_l1_batches {
+            let mut policy = MerkleTreePruningPolicy::retention_window(retained_l1_batches);
+            if merkle_tree_env_config.pruning_retain_unproven_l1_batches {
+                policy = policy.retaining_unproven_l1_batches();
+            }
+            layer = layer
+                .with_pruning_config(merkle_tree_env_config.pruning_poll_interval())
+                .with_pruning_policy(policy);
+        }
+        if let Some(poll_interval) = db_config
+            .experimental
+            .merkle_tree_consistency_check_interval()
+        {
+            layer = layer.with_consistency_check(
+                poll_interval,
+                db_config
+                    .experimental
+                    .merkle_tree_consistency_check_subtree_delay(),
+            );
+        }
         self.node.add_layer(layer);
         Ok(self)
     }
@@ -272,35 +471,82 @@ impl MainNodeBuilder {
 
         let wallets = self.wallets.clone();
         let sk_config = try_load_config!(self.configs.state_keeper_config);
-        let persistence_layer = OutputHandlerLayer::new(sk_config.l2_block_seal_queue_capacity)
+        // In the dev mode, L2 blocks are sealed synchronously so that reverting to a snapshot cannot race with sealing.
+        let l2_block_seal_queue_capacity = if sk_config.dev_mode {
+            0
+        } else {
+            sk_config.l2_block_seal_queue_capacity
+        };
+        let persistence_layer = OutputHandlerLayer::new(l2_block_seal_queue_capacity)
             .with_protective_reads_persistence_enabled(
                 sk_config.protective_reads_persistence_enabled,
+            )
+            .with_witness_inputs_pregeneration_enabled(
+                sk_config.witness_inputs_pregeneration_enabled,
+            )
+            .with_storage_slot_writers_enabled(sk_config.storage_slot_writers_enabled)
+            .with_batch_checkpoint_interval(sk_config.batch_checkpoint_interval)
+            .with_soft_confirmation_signer(
+                wallets
+                    .soft_confirmation_signer
+                    .as_ref()
+                    .map(|signer| signer.wallet.private_key().clone()),
             );
-        let mempool_io_layer = MempoolIOLayer::new(
+        let mut mempool_io_layer = MempoolIOLayer::new(
             self.genesis_config.l2_chain_id,
             sk_config.clone(),
             try_load_config!(self.configs.mempool_config),
             try_load_config!(wallets.state_keeper),
             self.get_pubdata_type()?,
         );
+        if let Some(tx_policy_config) = self.configs.tx_policy_config.clone() {
+            mempool_io_layer = mempool_io_layer.with_tx_policy(tx_policy_config);
+        }
         let db_config = try_load_config!(self.configs.db_config);
         let experimental_vm_config = self
             .configs
             .experimental_vm_config
             .clone()
             .unwrap_or_default();
+        let frame_gas_cap =
+            frame_gas_cap(&sk_config, experimental_vm_config.state_keeper_fast_vm_mode)
+                .context("invalid frame gas cap for state keeper")?;
         let main_node_batch_executor_builder_layer =
             MainBatchExecutorLayer::new(sk_config.save_call_traces, OPTIONAL_BYTECODE_COMPRESSION)
-                .with_fast_vm_mode(experimental_vm_config.state_keeper_fast_vm_mode);
+                .with_fast_vm_mode(experimental_vm_config.state_keeper_fast_vm_mode)
+                .with_frame_gas_cap(frame_gas_cap)
+                .with_precompile_inputs_recording(sk_config.witness_inputs_pregeneration_enabled);
 
         let rocksdb_options = RocksdbStorageOptions {
             block_cache_capacity: db_config
                 .experimental
                 .state_keeper_db_block_cache_capacity(),
             max_open_files: db_config.experimental.state_keeper_db_max_open_files,
+            state_block_cache_capacity: db_config
+                .experimental
+                .state_keeper_db_state_cache_capacity(),
+            state_compaction: CompactionOptions {
+                soft_pending_compaction_bytes_limit: db_config
+                    .experimental
+                    .state_keeper_db_soft_pending_compaction_limit(),
+                hard_pending_compaction_bytes_limit: db_config
+                    .experimental
+                    .state_keeper_db_hard_pending_compaction_limit(),
+                ..CompactionOptions::default()
+            },
         };
         let state_keeper_layer =
-            StateKeeperLayer::new(db_config.state_keeper_db_path, rocksdb_options);
+            StateKeeperLayer::new(db_config.state_keeper_db_path, rocksdb_options)
+                .with_witness_inputs_pregeneration_enabled(
+                    sk_config.witness_inputs_pregeneration_enabled,
+                )
+                .with_pending_batch_state_enabled(sk_config.pending_batch_state_enabled);
+        if let Some(instance_id) = sk_config.sequencer_instance_id.clone() {
+            self.node.add_layer(SequencerLeaseLayer::new(
+                instance_id,
+                sk_config.sequencer_lease_ttl(),
+            ));
+        }
         self.node
             .add_layer(persistence_layer)
             .add_layer(mempool_io_layer)
@@ -347,11 +593,28 @@ impl MainNodeBuilder {
     }
 
     fn add_proof_data_handler_layer(mut self) -> anyhow::Result<Self> {
-        self.node.add_layer(ProofDataHandlerLayer::new(
-            try_load_config!(self.configs.proof_data_handler_config),
+        let proof_data_handler_config = try_load_config!(self.configs.proof_data_handler_config);
+        let tee_support = proof_data_handler_config.tee_config.tee_support;
+        let mut layer = ProofDataHandlerLayer::new(
+            proof_data_handler_config,
             self.genesis_config.l1_batch_commit_data_generator_mode,
             self.genesis_config.l2_chain_id,
-        ));
+        );
+        // The admin API of the proof data handler shares the auth token with the admin JSON-RPC server.
+        if let Some(admin_secrets) = &self.secrets.admin_api {
+            layer = layer.with_admin_auth_token(admin_secrets.auth_token.clone());
+        }
+        // For validium chains, TEE provers additionally receive DA inclusion proofs signed by the operator.
+        let is_validium = self.genesis_config.l1_batch_commit_data_generator_mode
+            == L1BatchCommitmentMode::Validium;
+        if tee_support && is_validium {
+            if let Some(eth_sender_wallets) = &self.wallets.eth_sender {
+                layer = layer.with_tee_auxiliary_data_signer(
+                    eth_sender_wallets.operator.private_key().clone(),
+                );
+            }
+        }
+        self.node.add_layer(layer);
         Ok(self)
     }
 
@@ -371,6 +634,7 @@ impl MainNodeBuilder {
             initial_writes_cache_size: rpc_config.initial_writes_cache_size() as u64,
             latest_values_cache_size: rpc_config.latest_values_cache_size() as u64,
             latest_values_max_block_lag: rpc_config.latest_values_max_block_lag(),
+            storage_read_batching_window: rpc_config.storage_read_batching_window(),
         };
         let vm_config = self
             .configs
@@ -379,7 +643,14 @@ impl MainNodeBuilder {
             .unwrap_or_default();
 
         // On main node we always use master pool sink.
-        if deployment_allowlist.is_enabled() {
+        if let Some(tx_policy) = self.configs.tx_policy_config.clone() {
+            self.node.add_layer(PolicyEnforcingMasterPoolSinkLayer {
+                tx_policy,
+                deployment_allowlist: deployment_allowlist
+                    .is_enabled()
+                    .then(|| deployment_allowlist.clone()),
+            });
+        } else if deployment_allowlist.is_enabled() {
             self.node.add_layer(WhitelistedMasterPoolSinkLayer {
                 deployment_allowlist: deployment_allowlist.clone(),
             });
@@ -400,7 +671,18 @@ impl MainNodeBuilder {
             ),
             self.configs.timestamp_asserter_config.clone(),
         );
-        let layer = layer.with_vm_mode(vm_config.api_fast_vm_mode);
+        let frame_gas_cap = frame_gas_cap(&sk_config, vm_config.api_fast_vm_mode)
+            .context("invalid frame gas cap for API server")?;
+        let mut layer = layer
+            .with_vm_mode(vm_config.api_fast_vm_mode)
+            .with_vm_warm_pool_size(vm_config.api_vm_warm_pool_size.unwrap_or(0))
+            .with_frame_gas_cap(frame_gas_cap);
+        if vm_config.api_vm_resource_accounting_enabled() {
+            layer = layer.with_vm_resource_limits(ResourceLimits {
+                max_memory: vm_config.api_vm_max_memory(),
+                max_cpu_time: vm_config.api_vm_max_cpu_time(),
+            });
+        }
         self.node.add_layer(layer);
         Ok(self)
     }
@@ -446,6 +728,12 @@ impl MainNodeBuilder {
             batch_request_size_limit: Some(rpc_config.max_batch_request_size()),
             response_body_size_limit: Some(rpc_config.max_response_body_size()),
             with_extended_tracing: rpc_config.extended_api_tracing,
+            eth_call_cache_size: rpc_config.eth_call_cache_size(),
+            cold_storage_object_store: self
+                .configs
+                .pruning
+                .as_ref()
+                .and_then(|config| config.cold_storage_object_store.clone()),
             ..Default::default()
         };
         let http_port = rpc_config.http_port;
@@ -467,6 +755,35 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_admin_web3_api_layer(mut self) -> anyhow::Result<Self> {
+        let rpc_config = try_load_config!(self.configs.api_config).web3_json_rpc;
+        let Some(admin_port) = rpc_config.admin_port else {
+            return Ok(self);
+        };
+        let Some(admin_secrets) = &self.secrets.admin_api else {
+            tracing::warn!(
+                "Admin API port is configured, but the auth token is not set; the admin API server will not be started"
+            );
+            return Ok(self);
+        };
+        let auth_token = admin_secrets.auth_token.clone();
+
+        let internal_config_base = InternalApiConfigBase::new(&self.genesis_config, &rpc_config);
+        let optional_config = Web3ServerOptionalConfig {
+            batch_request_size_limit: Some(rpc_config.max_batch_request_size()),
+            response_body_size_limit: Some(rpc_config.max_response_body_size()),
+            ..Default::default()
+        };
+        self.node.add_layer(Web3ServerLayer::admin(
+            admin_port,
+            internal_config_base,
+            optional_config,
+            auth_token,
+        ));
+
+        Ok(self)
+    }
+
     fn add_ws_web3_api_layer(mut self) -> anyhow::Result<Self> {
         let rpc_config = try_load_config!(self.configs.api_config).web3_json_rpc;
         let state_keeper_config = try_load_config!(self.configs.state_keeper_config);
@@ -497,6 +814,12 @@ impl MainNodeBuilder {
             ),
             replication_lag_limit: circuit_breaker_config.replication_lag_limit(),
             with_extended_tracing: rpc_config.extended_api_tracing,
+            eth_call_cache_size: rpc_config.eth_call_cache_size(),
+            cold_storage_object_store: self
+                .configs
+                .pruning
+                .as_ref()
+                .and_then(|config| config.cold_storage_object_store.clone()),
             ..Default::default()
         };
         let ws_port = rpc_config.ws_port;
@@ -698,6 +1021,16 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_protocol_upgrade_dry_run_layer(mut self) -> anyhow::Result<Self> {
+        let sk_config = try_load_config!(self.configs.state_keeper_config);
+        self.node.add_layer(ProtocolUpgradeDryRunLayer::new(
+            self.genesis_config.l2_chain_id,
+            sk_config.validation_computational_gas_limit,
+        ));
+
+        Ok(self)
+    }
+
     fn add_base_token_ratio_persister_layer(mut self) -> anyhow::Result<Self> {
         let config = try_load_config!(self.configs.base_token_adjuster);
         let wallets = self.wallets.clone();
@@ -721,6 +1054,41 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_data_retention_layer(mut self) -> anyhow::Result<Self> {
+        let config = try_load_config!(self.configs.pruning);
+        if !config.has_data_retention_policies() {
+            tracing::warn!(
+                "Data retention component is enabled, but no retention policies are configured"
+            );
+        }
+        self.node.add_layer(DataRetentionLayer::new(
+            config.call_traces_retention_sec.map(Duration::from_secs),
+            config.events_retention_sec.map(Duration::from_secs),
+        ));
+        Ok(self)
+    }
+
+    fn add_cold_storage_layer(mut self) -> anyhow::Result<Self> {
+        let config = try_load_config!(self.configs.pruning);
+        let archive_after = config
+            .cold_storage_archive_after_sec
+            .context("`cold_storage_archive_after_sec` must be set for cold storage component")?;
+        let object_store_config = config
+            .cold_storage_object_store
+            .context("`cold_storage_object_store` must be set for cold storage component")?;
+        self.node.add_layer(ColdStorageLayer::new(
+            Duration::from_secs(archive_after),
+            object_store_config,
+        ));
+        Ok(self)
+    }
+
+    fn add_batch_exporter_layer(mut self) -> anyhow::Result<Self> {
+        let config = try_load_config!(self.configs.batch_exporter_config);
+        self.node.add_layer(BatchExporterLayer::new(config));
+        Ok(self)
+    }
+
     fn add_logs_bloom_backfill_layer(mut self) -> anyhow::Result<Self> {
         self.node.add_layer(LogsBloomBackfillLayer);
 
@@ -763,11 +1131,20 @@ impl MainNodeBuilder {
     }
 
     /// Builds the node with the specified components.
-    pub fn build(mut self, mut components: Vec<Component>) -> anyhow::Result<ZkStackService> {
+    pub fn build(self, components: Vec<Component>) -> anyhow::Result<ZkStackService> {
+        Ok(self.compose(components)?.build())
+    }
+
+    /// Adds layers for the specified components to the service builder without building the service.
+    pub fn compose(
+        mut self,
+        mut components: Vec<Component>,
+    ) -> anyhow::Result<ZkStackServiceBuilder> {
         // Add "base" layers (resources and helper tasks).
         self = self
             .add_sigint_handler_layer()?
             .add_pools_layer()?
+            .add_config_reloader_layer()?
             .add_object_store_layer()?
             .add_circuit_breaker_checker_layer()?
             .add_healthcheck_layer()?
@@ -810,7 +1187,8 @@ impl MainNodeBuilder {
                         .add_tx_sender_layer()?
                         .add_tree_api_client_layer()?
                         .add_api_caches_layer()?
-                        .add_http_web3_api_layer()?;
+                        .add_http_web3_api_layer()?
+                        .add_admin_web3_api_layer()?;
                 }
                 Component::WsApi => {
                     self = self
@@ -875,12 +1253,24 @@ impl MainNodeBuilder {
                 Component::VmPlayground => {
                     self = self.add_vm_playground_layer()?;
                 }
+                Component::ProtocolUpgradeDryRun => {
+                    self = self.add_protocol_upgrade_dry_run_layer()?;
+                }
                 Component::ExternalProofIntegrationApi => {
                     self = self.add_external_proof_integration_api_layer()?;
                 }
+                Component::DataRetention => {
+                    self = self.add_data_retention_layer()?;
+                }
+                Component::ColdStorage => {
+                    self = self.add_cold_storage_layer()?;
+                }
+                Component::BatchExporter => {
+                    self = self.add_batch_exporter_layer()?;
+                }
             }
         }
-        Ok(self.node.build())
+        Ok(self.node)
     }
 }
 
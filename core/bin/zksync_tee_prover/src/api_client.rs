@@ -4,8 +4,10 @@ use serde::Serialize;
 use url::Url;
 use zksync_basic_types::{tee_types::TeeType, L1BatchNumber, H256};
 use zksync_prover_interface::{
-    api::{RegisterTeeAttestationRequest, SubmitTeeProofRequest, TeeProofGenerationDataRequest},
-    inputs::TeeVerifierInput,
+    api::{
+        RegisterTeeAttestationRequest, SubmitTeeProofRequest, TeeProofGenerationDataRequest,
+        TeeProofGenerationDataResponse,
+    },
     outputs::L1BatchTeeProofForL1,
 };
 
@@ -69,14 +71,16 @@ impl TeeApiClient {
     pub async fn get_job(
         &self,
         tee_type: TeeType,
-    ) -> Result<Option<TeeVerifierInput>, TeeProverError> {
+    ) -> Result<Option<TeeProofGenerationDataResponse>, TeeProverError> {
         let request = TeeProofGenerationDataRequest { tee_type };
         let response = self.post("/tee/proof_inputs", request).await?;
         match response.status() {
-            StatusCode::OK => Ok(Some(response.json::<TeeVerifierInput>().await?)),
+            StatusCode::OK => Ok(Some(
+                response.json::<TeeProofGenerationDataResponse>().await?,
+            )),
             StatusCode::NO_CONTENT => Ok(None),
             _ => response
-                .json::<Option<TeeVerifierInput>>()
+                .json::<Option<TeeProofGenerationDataResponse>>()
                 .await
                 .map_err(TeeProverError::Request),
         }
@@ -4,7 +4,7 @@ use anyhow::Context as _;
 use secp256k1::SecretKey;
 use serde::Deserialize;
 use url::Url;
-use zksync_basic_types::tee_types::TeeType;
+use zksync_basic_types::{tee_types::TeeType, Address};
 use zksync_config::configs::{ObservabilityConfig, PrometheusConfig};
 use zksync_env_config::FromEnv;
 
@@ -26,6 +26,10 @@ pub(crate) struct TeeProverSigConfig {
     pub attestation_quote_file_path: PathBuf,
     /// Attestation quote file.
     pub tee_type: TeeType,
+    /// Address of the operator signing auxiliary data (e.g., DA inclusion proofs for validium chains) attached to jobs.
+    /// If set, jobs without auxiliary data validly signed by this address are rejected.
+    #[serde(default)]
+    pub operator_address: Option<Address>,
 }
 
 /// TEE proof data handler API parameter.
@@ -100,6 +104,7 @@ impl FromEnv for TeeProverSigConfig {
     /// export TEE_PROVER_SIGNING_KEY="b50b38c8d396c88728fc032ece558ebda96907a0b1a9340289715eef7bf29deb"
     /// export TEE_PROVER_ATTESTATION_QUOTE_FILE_PATH="/tmp/test"  # run `echo test > /tmp/test` beforehand
     /// export TEE_PROVER_TEE_TYPE="sgx"
+    /// export TEE_PROVER_OPERATOR_ADDRESS="0x1f9840a85d5af5bf1d1762f925bdaddc4201f984"  # optional
     /// ```
     fn from_env() -> anyhow::Result<Self> {
         let config = envy::prefixed("TEE_PROVER_").from_env()?;
@@ -1,7 +1,7 @@
 use std::fmt;
 
 use secp256k1::{PublicKey, Secp256k1};
-use zksync_basic_types::{L1BatchNumber, H256};
+use zksync_basic_types::{Address, L1BatchNumber, H256};
 use zksync_crypto_primitives::{sign, K256PrivateKey, Signature};
 use zksync_node_framework::{
     service::StopReceiver,
@@ -9,7 +9,10 @@ use zksync_node_framework::{
     wiring_layer::{WiringError, WiringLayer},
     IntoContext,
 };
-use zksync_prover_interface::inputs::TeeVerifierInput;
+use zksync_prover_interface::{
+    api::{SignedTeeAuxiliaryData, TeeAuxiliaryDataKind, TeeProofGenerationDataResponse},
+    inputs::TeeVerifierInput,
+};
 use zksync_tee_verifier::Verify;
 
 use crate::{
@@ -77,10 +80,18 @@ impl TeeProver {
 
     fn verify(
         &self,
-        tvi: TeeVerifierInput,
+        response: TeeProofGenerationDataResponse,
     ) -> Result<(Signature, L1BatchNumber, H256), TeeProverError> {
-        match tvi {
+        match *response.input {
             TeeVerifierInput::V1(tvi) => {
+                if let Some(operator_address) = self.config.sig_conf.operator_address {
+                    verify_auxiliary_data(
+                        tvi.l1_batch_env.number,
+                        response.auxiliary_data.as_ref(),
+                        operator_address,
+                    )
+                    .map_err(TeeProverError::Verification)?;
+                }
                 let observer = METRICS.proof_generation_time.start();
                 let verification_result = tvi.verify().map_err(TeeProverError::Verification)?;
                 let batch_number = verification_result.batch_number;
@@ -124,6 +135,41 @@ impl TeeProver {
     }
 }
 
+/// Checks that auxiliary data for the batch is present and is signed by the operator.
+fn verify_auxiliary_data(
+    l1_batch_number: L1BatchNumber,
+    auxiliary_data: Option<&SignedTeeAuxiliaryData>,
+    operator_address: Address,
+) -> anyhow::Result<()> {
+    let auxiliary_data = auxiliary_data.ok_or_else(|| {
+        anyhow::anyhow!("auxiliary data is missing for L1 batch #{l1_batch_number}")
+    })?;
+    anyhow::ensure!(
+        auxiliary_data.l1_batch_number == l1_batch_number,
+        "auxiliary data is provided for L1 batch #{}, expected #{l1_batch_number}",
+        auxiliary_data.l1_batch_number
+    );
+    anyhow::ensure!(
+        auxiliary_data.kind == TeeAuxiliaryDataKind::DaInclusionProof,
+        "unexpected auxiliary data kind: {:?}",
+        auxiliary_data.kind
+    );
+    let message = SignedTeeAuxiliaryData::signed_message(
+        auxiliary_data.l1_batch_number,
+        auxiliary_data.kind,
+        &auxiliary_data.data,
+    );
+    let signer = auxiliary_data
+        .signature
+        .signature_recover_signer(&message)
+        .map_err(|err| anyhow::anyhow!("invalid auxiliary data signature: {err}"))?;
+    anyhow::ensure!(
+        signer == operator_address,
+        "auxiliary data is signed by {signer:?}, expected operator {operator_address:?}"
+    );
+    Ok(())
+}
+
 #[async_trait::async_trait]
 impl Task for TeeProver {
     fn id(&self) -> TaskId {
@@ -200,7 +246,7 @@ mod tests {
     use secp256k1::SecretKey;
     use url::Url;
     use zksync_basic_types::{self, tee_types::TeeType};
-    use zksync_crypto_primitives::{public_to_address, recover};
+    use zksync_crypto_primitives::{public_to_address, recover, PackedEthSignature};
 
     use super::*;
     use crate::config::{TeeProverApiConfig, TeeProverSigConfig};
@@ -217,6 +263,7 @@ mod tests {
                 signing_key,
                 attestation_quote_file_path: PathBuf::from("/tmp/mock"),
                 tee_type: TeeType::Sgx,
+                operator_address: None,
             },
             prover_api: TeeProverApiConfig {
                 api_url: Url::parse("http://mock").unwrap(),
@@ -248,4 +295,54 @@ mod tests {
 
         assert_eq!(proof_address, expected_address);
     }
+
+    #[test]
+    fn verifying_auxiliary_data() {
+        let operator_key = K256PrivateKey::random();
+        let l1_batch_number = L1BatchNumber(1);
+        let kind = TeeAuxiliaryDataKind::DaInclusionProof;
+        let data = vec![1, 2, 3];
+        let message = SignedTeeAuxiliaryData::signed_message(l1_batch_number, kind, &data);
+        let auxiliary_data = SignedTeeAuxiliaryData {
+            l1_batch_number,
+            kind,
+            data,
+            signature: PackedEthSignature::sign_raw(&operator_key, &message).unwrap(),
+        };
+
+        verify_auxiliary_data(
+            l1_batch_number,
+            Some(&auxiliary_data),
+            operator_key.address(),
+        )
+        .unwrap();
+
+        let err = verify_auxiliary_data(l1_batch_number, None, operator_key.address()).unwrap_err();
+        assert!(err.to_string().contains("missing"), "{err:#}");
+        let err = verify_auxiliary_data(
+            L1BatchNumber(2),
+            Some(&auxiliary_data),
+            operator_key.address(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("expected #2"), "{err:#}");
+        let err = verify_auxiliary_data(
+            l1_batch_number,
+            Some(&auxiliary_data),
+            Address::repeat_byte(1),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("expected operator"), "{err:#}");
+
+        let tampered_data = SignedTeeAuxiliaryData {
+            data: vec![1, 2, 4],
+            ..auxiliary_data
+        };
+        verify_auxiliary_data(
+            l1_batch_number,
+            Some(&tampered_data),
+            operator_key.address(),
+        )
+        .unwrap_err();
+    }
 }
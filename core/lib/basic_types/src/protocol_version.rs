@@ -162,6 +162,12 @@ impl ProtocolVersionId {
         self >= &Self::Version27
     }
 
+    /// State diff compression V2 requires support in the `Compressor` and `L1Messenger` system contracts,
+    /// so it will be activated with the first protocol version after 28 shipping such support.
+    pub fn is_pre_state_diff_compression_v2(&self) -> bool {
+        self <= &Self::Version28
+    }
+
     pub fn is_1_4_0(&self) -> bool {
         self >= &ProtocolVersionId::Version18 && self < &ProtocolVersionId::Version20
     }
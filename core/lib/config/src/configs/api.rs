@@ -138,6 +138,9 @@ pub struct Web3JsonRpcConfig {
     pub http_port: u16,
     /// Port to which the WebSocket RPC server is listening.
     pub ws_port: u16,
+    /// Port to which the HTTP server of the `admin` namespace is listening. The server is only started
+    /// if this port is set and an admin auth token is provided in secrets.
+    pub admin_port: Option<u16>,
     /// Max possible limit of entities to be requested once.
     pub req_entities_limit: Option<u32>,
     /// Whether to support HTTP methods that install filters and query filter changes.
@@ -171,6 +174,12 @@ pub struct Web3JsonRpcConfig {
     pub estimate_gas_optimize_search: bool,
     ///  Max possible size of an ABI encoded tx (in bytes).
     pub max_tx_size: usize,
+    /// Max size of a transaction ABI-encoded in the bootloader format (in bytes) accepted at transaction intake.
+    /// If not set, only `max_tx_size` is enforced.
+    pub max_tx_abi_encoded_size: Option<usize>,
+    /// Max total size of compressed factory dependencies of a transaction (in bytes) accepted at transaction intake.
+    /// Approximates the pubdata that a transaction contributes by publishing bytecodes. If not set, the size is not limited.
+    pub max_tx_compressed_bytecodes_size: Option<usize>,
     /// Max number of cache misses during one VM execution. If the number of cache misses exceeds this value, the API server panics.
     /// This is a temporary solution to mitigate API request resulting in thousands of DB queries.
     pub vm_execution_cache_misses_limit: Option<usize>,
@@ -189,6 +198,9 @@ pub struct Web3JsonRpcConfig {
     /// lead to increased the cache update latency, i.e., less storage queries being processed by the cache. OTOH, smaller values
     /// can lead to spurious resets when Postgres lags for whatever reason (e.g., when sealing L1 batches).
     pub latest_values_max_block_lag: Option<NonZeroU32>,
+    /// Time window in milliseconds to batch storage slot reads from VM sandboxes that miss caches. If set, reads
+    /// from concurrently executing VMs are coalesced into a single Postgres query. If not set, each read is a separate query.
+    pub storage_read_batching_window_ms: Option<u64>,
     /// Limit for fee history block range.
     pub fee_history_limit: Option<u64>,
     /// Maximum number of requests in a single batch JSON RPC request. Default is 500.
@@ -198,6 +210,9 @@ pub struct Web3JsonRpcConfig {
     /// Method-specific overrides in MiBs for the maximum response body size.
     #[serde(default = "MaxResponseSizeOverrides::empty")]
     pub max_response_body_size_overrides_mb: MaxResponseSizeOverrides,
+    /// Maximum size of a trace returned by `debug_trace*` methods in MiBs. Traces exceeding this size are progressively
+    /// truncated instead of failing the request. If not set, the global response body size limit is used.
+    pub max_trace_size_mb: Option<usize>,
     /// Maximum number of requests per minute for the WebSocket server.
     /// The value is per active connection.
     /// Note: For HTTP, rate limiting is expected to be configured on the infra level.
@@ -209,6 +224,18 @@ pub struct Web3JsonRpcConfig {
     pub mempool_cache_update_interval: Option<u64>,
     /// Maximum number of transactions to be stored in the mempool cache. Default is 10000.
     pub mempool_cache_size: Option<usize>,
+    /// Maximum number of `eth_call` results executed on sealed L2 blocks to cache. If not set or set to 0,
+    /// results are not cached.
+    pub eth_call_cache_size: Option<usize>,
+    /// Average latency of waiting for a VM permit (in milliseconds) above which low-priority requests
+    /// (`eth_call`, gas estimation, call tracing) are rejected. If not set, the VM queue latency doesn't trigger
+    /// load shedding.
+    pub load_shedding_vm_queue_latency_ms: Option<u64>,
+    /// Share of connections in use in the replica connection pool (from 0 to 1) above which low-priority requests
+    /// are rejected. If not set, the pool utilization doesn't trigger load shedding.
+    pub load_shedding_pool_utilization: Option<f64>,
+    /// Delay (in milliseconds) that clients are advised to wait before retrying a rejected request. Default is 1000.
+    pub load_shedding_retry_after_ms: Option<u64>,
     /// List of L2 token addresses that are white-listed to use by paymasters
     /// (additionally to natively bridged tokens).
     #[serde(default)]
@@ -233,6 +260,7 @@ impl Web3JsonRpcConfig {
         Self {
             http_port: 3050,
             ws_port: 3051,
+            admin_port: None,
             req_entities_limit: Some(10000),
             filters_disabled: false,
             filters_limit: Some(10000),
@@ -244,19 +272,27 @@ impl Web3JsonRpcConfig {
             estimate_gas_acceptable_overestimation: 1000,
             estimate_gas_optimize_search: false,
             max_tx_size: 1000000,
+            max_tx_abi_encoded_size: None,
+            max_tx_compressed_bytecodes_size: None,
             vm_execution_cache_misses_limit: None,
             vm_concurrency_limit: None,
             factory_deps_cache_size_mb: None,
             initial_writes_cache_size_mb: None,
             latest_values_cache_size_mb: None,
             latest_values_max_block_lag: None,
+            storage_read_batching_window_ms: None,
             fee_history_limit: None,
             max_batch_request_size: None,
             max_response_body_size_mb: None,
             max_response_body_size_overrides_mb: MaxResponseSizeOverrides::empty(),
+            max_trace_size_mb: None,
             websocket_requests_per_minute_limit: None,
             mempool_cache_update_interval: None,
             mempool_cache_size: None,
+            eth_call_cache_size: None,
+            load_shedding_vm_queue_latency_ms: None,
+            load_shedding_pool_utilization: None,
+            load_shedding_retry_after_ms: None,
             tree_api_url: None,
             whitelisted_tokens_for_aa: vec![],
             api_namespaces: None,
@@ -316,6 +352,12 @@ impl Web3JsonRpcConfig {
         self.latest_values_max_block_lag.map_or(20, NonZeroU32::get)
     }
 
+    /// Returns the time window to batch storage slot reads, or `None` if read batching is disabled.
+    pub fn storage_read_batching_window(&self) -> Option<Duration> {
+        self.storage_read_batching_window_ms
+            .map(Duration::from_millis)
+    }
+
     pub fn fee_history_limit(&self) -> u64 {
         self.fee_history_limit.unwrap_or(1024)
     }
@@ -333,6 +375,14 @@ impl Web3JsonRpcConfig {
         }
     }
 
+    /// Returns the maximum size of a trace returned by `debug_trace*` methods in bytes.
+    pub fn max_trace_size(&self) -> usize {
+        self.max_trace_size_mb
+            .map_or(self.max_response_body_size().global, |size_mb| {
+                size_mb * super::BYTES_IN_MEGABYTE
+            })
+    }
+
     pub fn websocket_requests_per_minute_limit(&self) -> NonZeroU32 {
         // The default limit is chosen to be reasonably permissive.
         self.websocket_requests_per_minute_limit
@@ -350,6 +400,20 @@ impl Web3JsonRpcConfig {
     pub fn mempool_cache_size(&self) -> usize {
         self.mempool_cache_size.unwrap_or(10_000)
     }
+
+    /// Returns the capacity of the `eth_call` cache, or `None` if the cache is disabled.
+    pub fn eth_call_cache_size(&self) -> Option<NonZeroUsize> {
+        self.eth_call_cache_size.and_then(NonZeroUsize::new)
+    }
+
+    pub fn load_shedding_vm_queue_latency(&self) -> Option<Duration> {
+        self.load_shedding_vm_queue_latency_ms
+            .map(Duration::from_millis)
+    }
+
+    pub fn load_shedding_retry_after(&self) -> Duration {
+        Duration::from_millis(self.load_shedding_retry_after_ms.unwrap_or(1_000))
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
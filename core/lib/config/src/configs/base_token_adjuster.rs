@@ -44,6 +44,9 @@ const DEFAULT_MAX_ACCEPTABLE_PRIORITY_FEE_IN_GWEI: u64 = 100_000_000_000;
 /// Default value for halting on error
 const DEFAULT_HALT_ON_ERROR: bool = false;
 
+/// Default number of decimals of the base token (same as ETH)
+const DEFAULT_BASE_TOKEN_DECIMALS: u8 = 18;
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct BaseTokenAdjusterConfig {
     /// How often to spark a new cycle of the ratio persister to fetch external prices and persis ratios.
@@ -100,6 +103,13 @@ pub struct BaseTokenAdjusterConfig {
     /// the server process if an external api is not available or if L1 is congested.
     #[serde(default = "BaseTokenAdjusterConfig::default_halt_on_error")]
     pub halt_on_error: bool,
+
+    /// Number of decimals of the base token. Prices fetched from external APIs are specified for whole tokens,
+    /// so this value is used to convert them to the ratio between WEI and the smallest units of the base token.
+    /// The converted ratio is used for fee parameters (and thus for gas prices returned by the API and used in gas
+    /// estimation, which are derived from them) and for the token multiplier set on L1. Other values are unaffected.
+    #[serde(default = "BaseTokenAdjusterConfig::default_base_token_decimals")]
+    pub base_token_decimals: u8,
 }
 
 impl Default for BaseTokenAdjusterConfig {
@@ -119,11 +129,16 @@ impl Default for BaseTokenAdjusterConfig {
             price_fetching_sleep_ms: Self::default_price_fetching_sleep_ms(),
             price_fetching_max_attempts: Self::default_price_fetching_max_attempts(),
             halt_on_error: Self::default_halt_on_error(),
+            base_token_decimals: Self::default_base_token_decimals(),
         }
     }
 }
 
 impl BaseTokenAdjusterConfig {
+    /// Maximum supported number of base token decimals. Larger values don't make sense in practice
+    /// and could overflow conversions to the smallest token units.
+    pub const MAX_BASE_TOKEN_DECIMALS: u8 = 36;
+
     pub fn default_price_polling_interval_ms() -> u64 {
         DEFAULT_PRICE_POLLING_INTERVAL_MS
     }
@@ -148,6 +163,10 @@ impl BaseTokenAdjusterConfig {
         DEFAULT_HALT_ON_ERROR
     }
 
+    pub fn default_base_token_decimals() -> u8 {
+        DEFAULT_BASE_TOKEN_DECIMALS
+    }
+
     pub fn price_cache_update_interval(&self) -> Duration {
         Duration::from_millis(self.price_cache_update_interval_ms)
     }
@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// Format of exported L1 batch artifacts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchExportFormat {
+    /// A single JSON file per L1 batch.
+    #[default]
+    Json,
+    /// Parquet files with transactions, storage diffs, events and batch-level stats, each in a separate
+    /// subdirectory, so that they can be queried as tables by analytics tools.
+    Parquet,
+}
+
+/// Configuration of the exporter streaming artifacts of sealed L1 batches (transaction results, storage diffs,
+/// events and pubdata stats) to an external sink.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BatchExporterConfig {
+    /// Directory the exported artifacts are written to.
+    pub output_path: String,
+    /// Format of exported artifacts. The default format is JSON.
+    #[serde(default)]
+    pub format: BatchExportFormat,
+    /// Interval between polls for newly sealed L1 batches, in milliseconds. The default value is 1 second.
+    pub poll_interval_ms: Option<u64>,
+    /// Maximum number of L1 batches exported during a single iteration. The default value is 10.
+    pub max_batches_per_iteration: Option<u32>,
+}
+
+impl BatchExporterConfig {
+    const DEFAULT_POLL_INTERVAL_MS: u64 = 1_000;
+    const DEFAULT_MAX_BATCHES_PER_ITERATION: u32 = 10;
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(
+            self.poll_interval_ms
+                .unwrap_or(Self::DEFAULT_POLL_INTERVAL_MS),
+        )
+    }
+
+    pub fn max_batches_per_iteration(&self) -> u32 {
+        self.max_batches_per_iteration
+            .unwrap_or(Self::DEFAULT_MAX_BATCHES_PER_ITERATION)
+    }
+}
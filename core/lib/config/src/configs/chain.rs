@@ -22,6 +22,20 @@ impl Default for FeeModelVersion {
     }
 }
 
+/// Policy used by the state keeper to select timestamps for new L1 batches and L2 blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampPolicyKind {
+    /// Use the current wall-clock time.
+    #[default]
+    RealTime,
+    /// Advance the timestamp by a fixed increment for each L2 block, independently of wall-clock time.
+    /// Mostly useful for test networks that need accelerated deterministic time.
+    FixedIncrement,
+    /// Use the median timestamp of the latest L1 blocks, so that L2 time follows L1 consensus time.
+    MedianOfL1,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Default)]
 pub struct StateKeeperConfig {
     /// The max number of slots for txs in a block before it should be sealed by the slots sealer.
@@ -90,6 +104,27 @@ pub struct StateKeeperConfig {
 
     /// The version of the fee model to use.
     pub fee_model_version: FeeModelVersion,
+    /// Enables EIP-1559-style congestion adjustment of the fair L2 gas price. If enabled, `minimal_l2_gas_price`
+    /// is multiplied by a factor that grows while recent batches are fuller than the utilization targets
+    /// and decays back to 1 otherwise.
+    #[serde(default)]
+    pub fee_congestion_enabled: bool,
+    /// Target share of `max_gas_per_batch` used by a batch for the congestion adjustment. Range is (0, 1].
+    #[serde(default = "StateKeeperConfig::default_fee_congestion_compute_target")]
+    pub fee_congestion_compute_target: f64,
+    /// Target share of `max_pubdata_per_batch` used by a batch for the congestion adjustment. Range is (0, 1].
+    #[serde(default = "StateKeeperConfig::default_fee_congestion_pubdata_target")]
+    pub fee_congestion_pubdata_target: f64,
+    /// Bounds the price change per batch: a batch using twice the target changes the price by `1 / denominator`.
+    /// Same as `BASE_FEE_MAX_CHANGE_DENOMINATOR` in EIP-1559.
+    #[serde(default = "StateKeeperConfig::default_fee_congestion_max_change_denominator")]
+    pub fee_congestion_max_change_denominator: u32,
+    /// Maximum multiplier applied to `minimal_l2_gas_price` by the congestion adjustment.
+    #[serde(default = "StateKeeperConfig::default_fee_congestion_max_multiplier")]
+    pub fee_congestion_max_multiplier: f64,
+    /// Number of latest sealed batches that the congestion adjustment is computed over.
+    #[serde(default = "StateKeeperConfig::default_fee_congestion_window_batches")]
+    pub fee_congestion_window_batches: u32,
 
     /// Max number of computational gas that validation step is allowed to take.
     pub validation_computational_gas_limit: u32,
@@ -99,6 +134,23 @@ pub struct StateKeeperConfig {
     /// Note, that this number corresponds to the "base layer" circuits, i.e. it does not include
     /// the recursion layers' circuits.
     pub max_circuits_per_batch: usize,
+    /// Enables adjusting `close_block_at_geometry_percentage` based on proving statistics of recent batches
+    /// reported by the prover subsystem. The percentage is chosen so that the expected proving time of a batch
+    /// matches `geometry_adjustment_target_proving_time_sec`, and is clamped to the configured bounds.
+    #[serde(default)]
+    pub geometry_adjustment_enabled: bool,
+    /// Target total time spent by provers on all proving jobs of a batch, in seconds.
+    #[serde(default = "StateKeeperConfig::default_geometry_adjustment_target_proving_time_sec")]
+    pub geometry_adjustment_target_proving_time_sec: u64,
+    /// Lower bound for the adjusted `close_block_at_geometry_percentage`.
+    #[serde(default = "StateKeeperConfig::default_geometry_adjustment_min_percentage")]
+    pub geometry_adjustment_min_percentage: f64,
+    /// Upper bound for the adjusted `close_block_at_geometry_percentage`.
+    #[serde(default = "StateKeeperConfig::default_geometry_adjustment_max_percentage")]
+    pub geometry_adjustment_max_percentage: f64,
+    /// Number of latest proven batches that the geometry adjustment is computed over.
+    #[serde(default = "StateKeeperConfig::default_geometry_adjustment_window_batches")]
+    pub geometry_adjustment_window_batches: u32,
 
     /// Configures whether to persist protective reads when persisting L1 batches in the state keeper.
     /// Protective reads can be written asynchronously in VM runner instead.
@@ -106,6 +158,57 @@ pub struct StateKeeperConfig {
     /// which is capable of saving protective reads is run.
     #[serde(default)]
     pub protective_reads_persistence_enabled: bool,
+    /// Configures whether the state keeper generates VM run data for witness inputs (used bytecodes, storage refunds,
+    /// storage access data for Merkle paths, etc.) when sealing L1 batches. This makes re-executing batches
+    /// in the `vm_runner_bwip` component unnecessary, so the component can be disabled.
+    #[serde(default)]
+    pub witness_inputs_pregeneration_enabled: bool,
+    /// Configures whether to persist hashes of transactions that last modified each storage slot in an L2 block.
+    /// Allows querying the writer of a storage slot via `zks_getStorageSlotWriter`.
+    #[serde(default)]
+    pub storage_slot_writers_enabled: bool,
+    /// Configures whether the state keeper publishes state changes made by transactions in the unsealed L1 batch,
+    /// so that the API server running in the same process reflects them in the `pending` block.
+    #[serde(default)]
+    pub pending_batch_state_enabled: bool,
+
+    /// Policy used to select timestamps for new L1 batches and L2 blocks.
+    #[serde(default)]
+    pub timestamp_policy: TimestampPolicyKind,
+    /// Increment (in seconds) between timestamps of consecutive L2 blocks. Only used by the `fixed_increment` policy.
+    #[serde(default = "StateKeeperConfig::default_timestamp_increment_sec")]
+    pub timestamp_increment_sec: u64,
+    /// Number of latest L1 blocks to take the median timestamp of. Only used by the `median_of_l1` policy.
+    #[serde(default = "StateKeeperConfig::default_l1_timestamp_window")]
+    pub l1_timestamp_window: u32,
+    /// Enables the deterministic development mode: each transaction is sealed in a separate L2 block, timestamps
+    /// are selected without waiting, and the `evm` API namespace (`evm_mine`, `evm_increaseTime`, `evm_snapshot`,
+    /// `evm_revert`) can control block production. Must never be enabled in production.
+    #[serde(default)]
+    pub dev_mode: bool,
+    /// Identifier of this sequencer instance. If set, the state keeper only runs while holding the sequencer lease
+    /// stored in Postgres, and checks the lease when sealing L2 blocks and L1 batches. This allows running a standby
+    /// instance that keeps its caches warm and takes over once the lease is transferred to it or expires.
+    #[serde(default)]
+    pub sequencer_instance_id: Option<String>,
+    /// Time-to-live of the sequencer lease in milliseconds. The lease holder renews the lease several times
+    /// during this interval. Only used if `sequencer_instance_id` is set.
+    #[serde(default = "StateKeeperConfig::default_sequencer_lease_ttl_ms")]
+    pub sequencer_lease_ttl_ms: u64,
+    /// If set, a contract can forward at most `available - available / divisor` gas to a single call frame
+    /// (the EIP-150 "all but 1/N" rule; EraVM itself enforces it with the divisor 64). Transactions violating
+    /// the rule are rejected. Calls made by system contracts and by accounts invoked by the bootloader are not limited.
+    #[serde(default)]
+    pub frame_gas_forwarding_divisor: Option<u32>,
+    /// If set, a contract can forward at most this amount of gas to a single call frame. Transactions violating
+    /// the cap are rejected. Calls made by system contracts and by accounts invoked by the bootloader are not limited.
+    #[serde(default)]
+    pub max_frame_gas: Option<u32>,
+    /// If set, the state keeper persists a checkpoint of the unsealed L1 batch every this many L2 blocks. After a restart,
+    /// the state reached by re-executing the pending batch is compared against these checkpoints, and the state keeper
+    /// stops on a mismatch. Must be positive.
+    #[serde(default)]
+    pub batch_checkpoint_interval: Option<u32>,
 
     // Base system contract hashes, required only for generating genesis config.
     // #PLA-811
@@ -121,6 +224,62 @@ pub struct StateKeeperConfig {
 }
 
 impl StateKeeperConfig {
+    pub const fn default_fee_congestion_compute_target() -> f64 {
+        0.5
+    }
+
+    pub const fn default_fee_congestion_pubdata_target() -> f64 {
+        0.5
+    }
+
+    pub const fn default_fee_congestion_max_change_denominator() -> u32 {
+        8
+    }
+
+    pub const fn default_fee_congestion_max_multiplier() -> f64 {
+        10.0
+    }
+
+    pub const fn default_fee_congestion_window_batches() -> u32 {
+        64
+    }
+
+    pub const fn default_geometry_adjustment_target_proving_time_sec() -> u64 {
+        3_600
+    }
+
+    pub const fn default_geometry_adjustment_min_percentage() -> f64 {
+        0.5
+    }
+
+    pub const fn default_geometry_adjustment_max_percentage() -> f64 {
+        0.95
+    }
+
+    pub const fn default_geometry_adjustment_window_batches() -> u32 {
+        16
+    }
+
+    pub fn geometry_adjustment_target_proving_time(&self) -> Duration {
+        Duration::from_secs(self.geometry_adjustment_target_proving_time_sec)
+    }
+
+    pub const fn default_timestamp_increment_sec() -> u64 {
+        1
+    }
+
+    pub const fn default_l1_timestamp_window() -> u32 {
+        11
+    }
+
+    pub const fn default_sequencer_lease_ttl_ms() -> u64 {
+        10_000
+    }
+
+    pub fn sequencer_lease_ttl(&self) -> Duration {
+        Duration::from_millis(self.sequencer_lease_ttl_ms)
+    }
+
     /// Creates a config object suitable for use in unit tests.
     /// Values mostly repeat the values used in the localhost environment.
     pub fn for_tests() -> Self {
@@ -149,10 +308,35 @@ impl StateKeeperConfig {
             max_pubdata_per_batch: 100_000,
             minimal_l2_gas_price: 100000000,
             fee_model_version: FeeModelVersion::V2,
+            fee_congestion_enabled: false,
+            fee_congestion_compute_target: Self::default_fee_congestion_compute_target(),
+            fee_congestion_pubdata_target: Self::default_fee_congestion_pubdata_target(),
+            fee_congestion_max_change_denominator:
+                Self::default_fee_congestion_max_change_denominator(),
+            fee_congestion_max_multiplier: Self::default_fee_congestion_max_multiplier(),
+            fee_congestion_window_batches: Self::default_fee_congestion_window_batches(),
             validation_computational_gas_limit: 300000,
             save_call_traces: true,
             max_circuits_per_batch: 24100,
+            geometry_adjustment_enabled: false,
+            geometry_adjustment_target_proving_time_sec:
+                Self::default_geometry_adjustment_target_proving_time_sec(),
+            geometry_adjustment_min_percentage: Self::default_geometry_adjustment_min_percentage(),
+            geometry_adjustment_max_percentage: Self::default_geometry_adjustment_max_percentage(),
+            geometry_adjustment_window_batches: Self::default_geometry_adjustment_window_batches(),
             protective_reads_persistence_enabled: true,
+            witness_inputs_pregeneration_enabled: false,
+            pending_batch_state_enabled: false,
+            storage_slot_writers_enabled: false,
+            timestamp_policy: TimestampPolicyKind::RealTime,
+            timestamp_increment_sec: Self::default_timestamp_increment_sec(),
+            l1_timestamp_window: Self::default_l1_timestamp_window(),
+            dev_mode: false,
+            sequencer_instance_id: None,
+            sequencer_lease_ttl_ms: Self::default_sequencer_lease_ttl_ms(),
+            frame_gas_forwarding_divisor: None,
+            max_frame_gas: None,
+            batch_checkpoint_interval: None,
             bootloader_hash: None,
             default_aa_hash: None,
             evm_emulator_hash: None,
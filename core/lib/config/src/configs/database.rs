@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{num::NonZeroU32, time::Duration};
 
 use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
@@ -46,6 +46,30 @@ pub struct MerkleTreeConfig {
     /// Maximum number of L1 batches to be processed by the Merkle tree at a time.
     #[serde(default = "MerkleTreeConfig::default_max_l1_batches_per_iter")]
     pub max_l1_batches_per_iter: usize,
+    /// Number of threads in a dedicated thread pool used to compute hashes for the Merkle tree. Large batches
+    /// can benefit from a greater thread count. If not specified, hashing will use the global `rayon` thread pool
+    /// shared with other components.
+    #[serde(default)]
+    pub hashing_thread_count: Option<usize>,
+    /// If set, the main node will prune the Merkle tree, retaining all versions for at least this many latest
+    /// L1 batches. If not set, the tree is not pruned on the main node.
+    #[serde(default)]
+    pub pruning_retained_l1_batches: Option<NonZeroU32>,
+    /// Whether tree versions for L1 batches without a generated proof are protected from pruning. Only has effect
+    /// if tree pruning is enabled.
+    #[serde(default = "MerkleTreeConfig::default_pruning_retain_unproven_l1_batches")]
+    pub pruning_retain_unproven_l1_batches: bool,
+    /// Interval between checks whether the target retained tree version should be updated.
+    #[serde(default = "MerkleTreeConfig::default_pruning_poll_interval_sec")]
+    pub pruning_poll_interval_sec: u64,
+    /// If set, the Merkle tree is kept entirely in RAM instead of RocksDB on disk. Intended for short-lived
+    /// test networks and load tests. If a persisted tree exists at [`Self::path`], it is loaded on startup.
+    #[serde(default)]
+    pub in_memory: bool,
+    /// Interval between persisting the in-memory tree to [`Self::path`]. If not set, an in-memory tree
+    /// is never persisted. Has no effect if [`Self::in_memory`] is not set.
+    #[serde(default)]
+    pub in_memory_persistence_interval_sec: Option<u64>,
 }
 
 impl Default for MerkleTreeConfig {
@@ -58,6 +82,12 @@ impl Default for MerkleTreeConfig {
             memtable_capacity_mb: Self::default_memtable_capacity_mb(),
             stalled_writes_timeout_sec: Self::default_stalled_writes_timeout_sec(),
             max_l1_batches_per_iter: Self::default_max_l1_batches_per_iter(),
+            hashing_thread_count: None,
+            pruning_retained_l1_batches: None,
+            pruning_retain_unproven_l1_batches: Self::default_pruning_retain_unproven_l1_batches(),
+            pruning_poll_interval_sec: Self::default_pruning_poll_interval_sec(),
+            in_memory: false,
+            in_memory_persistence_interval_sec: None,
         }
     }
 }
@@ -87,6 +117,14 @@ impl MerkleTreeConfig {
         20
     }
 
+    pub const fn default_pruning_retain_unproven_l1_batches() -> bool {
+        true
+    }
+
+    pub const fn default_pruning_poll_interval_sec() -> u64 {
+        60
+    }
+
     /// Returns the size of block cache size for Merkle tree in bytes.
     pub fn block_cache_size(&self) -> usize {
         self.block_cache_size_mb * super::BYTES_IN_MEGABYTE
@@ -101,6 +139,17 @@ impl MerkleTreeConfig {
     pub fn stalled_writes_timeout(&self) -> Duration {
         Duration::from_secs(self.stalled_writes_timeout_sec)
     }
+
+    /// Returns the interval between pruning checks for the Merkle tree.
+    pub fn pruning_poll_interval(&self) -> Duration {
+        Duration::from_secs(self.pruning_poll_interval_sec)
+    }
+
+    /// Returns the interval between persisting the in-memory Merkle tree, if any.
+    pub fn in_memory_persistence_interval(&self) -> Option<Duration> {
+        self.in_memory_persistence_interval_sec
+            .map(Duration::from_secs)
+    }
 }
 
 /// Database configuration.
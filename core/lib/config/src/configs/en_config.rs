@@ -1,4 +1,4 @@
-use std::num::{NonZeroU64, NonZeroUsize};
+use std::num::{NonZeroU32, NonZeroU64, NonZeroUsize};
 
 use serde::Deserialize;
 use zksync_basic_types::{
@@ -20,4 +20,18 @@ pub struct ENConfig {
     pub bridge_addresses_refresh_interval_sec: Option<NonZeroU64>,
 
     pub gateway_chain_id: Option<SLChainId>,
+
+    /// Whether the node should halt on divergence from the main node instead of reverting diverged L1 batches.
+    #[serde(default)]
+    pub cross_verification_enabled: bool,
+    /// Maximum number of L1 batches that the node may roll back automatically after detecting a reorg.
+    /// If not set, rollbacks are unbounded.
+    pub max_auto_rollback_l1_batches: Option<u32>,
+    /// Whether the node should spot-check its API responses against an archive peer.
+    #[serde(default)]
+    pub response_checker_enabled: bool,
+    /// URL of the archive peer used by the response checker. If not set, the main node is used.
+    pub response_checker_peer_url: Option<SensitiveUrl>,
+    /// Distance between consecutive L2 blocks checked by the response checker.
+    pub response_checker_stride: Option<NonZeroU32>,
 }
@@ -55,6 +55,18 @@ impl EthConfig {
                 time_in_mempool_in_l1_blocks_cap: 1800,
                 is_verifier_pre_fflonk: true,
                 gas_limit_mode: GasLimitMode::Maximum,
+                pubdata_auto_selection: false,
+                pubdata_auto_selection_max_blob_base_fee: None,
+                pubdata_auto_selection_max_calldata_size:
+                    SenderConfig::default_pubdata_auto_selection_max_calldata_size(),
+                operator_takeover_after_l1_blocks: None,
+                high_gas_price_aggregation_threshold: None,
+                max_aggregated_blocks_on_high_gas_price:
+                    SenderConfig::default_max_aggregated_blocks_on_high_gas_price(),
+                fee_bump_interval_in_l1_blocks: None,
+                fee_bump_interval_sec: None,
+                fee_bump_percent: SenderConfig::default_fee_bump_percent(),
+                max_fee_per_gas_cap: None,
             }),
             gas_adjuster: Some(GasAdjusterConfig {
                 default_priority_fee_per_gas: 1000000000,
@@ -69,10 +81,19 @@ impl EthConfig {
                 num_samples_for_blob_base_fee_estimate: 10,
                 internal_pubdata_pricing_multiplier: 1.0,
                 max_blob_base_fee: None,
+                fee_sources_quorum: None,
+                fee_sources_max_deviation: 0.5,
             }),
             watcher: Some(EthWatchConfig {
                 confirmations_for_eth_event: None,
                 eth_node_poll_interval: 0,
+                reorg_check_depth_in_l1_blocks: None,
+                l2_to_l1_message_tracking_enabled: false,
+                l2_to_l1_message_tracking_interval_ms:
+                    EthWatchConfig::default_l2_to_l1_message_tracking_interval_ms(),
+                l2_to_l1_message_tracking_first_l1_batch: None,
+                l2_to_l1_message_retention_l1_batches:
+                    EthWatchConfig::default_l2_to_l1_message_retention_l1_batches(),
             }),
         }
     }
@@ -148,6 +169,42 @@ pub struct SenderConfig {
     pub is_verifier_pre_fflonk: bool,
     #[serde(default = "SenderConfig::default_gas_limit_mode")]
     pub gas_limit_mode: GasLimitMode,
+    /// If set and `pubdata_sending_mode` is `Blobs`, DA is chosen separately for each commit operation:
+    /// calldata is used instead of blobs if it's estimated to be cheaper given the current L1 fees.
+    #[serde(default)]
+    pub pubdata_auto_selection: bool,
+    /// Blob base fee (in wei) above which calldata is always preferred if pubdata auto-selection is enabled
+    /// and pubdata fits into calldata.
+    pub pubdata_auto_selection_max_blob_base_fee: Option<u64>,
+    /// Max pubdata size (in bytes) that can be published using calldata if pubdata auto-selection is enabled.
+    /// Commits with larger pubdata always use blobs.
+    #[serde(default = "SenderConfig::default_pubdata_auto_selection_max_calldata_size")]
+    pub pubdata_auto_selection_max_calldata_size: usize,
+    /// Number of L1 blocks a non-blob transaction may stay unmined before it's taken over by the next key
+    /// from the operator pool. If not set, or if the pool consists of a single key, stuck transactions
+    /// are only resent with increased fees.
+    pub operator_takeover_after_l1_blocks: Option<u32>,
+    /// L1 base fee per gas (in wei) above which L1 batches are packed more aggressively: up to
+    /// `max_aggregated_blocks_on_high_gas_price` consecutive L1 batches are published in a single L1 transaction
+    /// (subject to the respective deadlines). If not set, aggregation doesn't depend on L1 gas prices.
+    pub high_gas_price_aggregation_threshold: Option<u64>,
+    /// Number of L1 batches to pack into a single commit / prove / execute transaction while L1 gas prices
+    /// are above `high_gas_price_aggregation_threshold`.
+    #[serde(default = "SenderConfig::default_max_aggregated_blocks_on_high_gas_price")]
+    pub max_aggregated_blocks_on_high_gas_price: u32,
+    /// Min number of L1 blocks between consecutive sending attempts of a pending transaction. If neither this
+    /// nor `fee_bump_interval_sec` is set, a pending transaction is resent with bumped fees in every new L1 block.
+    /// If both are set, the transaction is resent once either of the intervals has elapsed.
+    pub fee_bump_interval_in_l1_blocks: Option<u32>,
+    /// Min time (in seconds) between consecutive sending attempts of a pending transaction.
+    pub fee_bump_interval_sec: Option<u64>,
+    /// Percentage by which fees of a pending transaction are increased when it's replaced. L1 nodes reject
+    /// replacements with a bump below 10%; blob transactions are always bumped by at least 100%.
+    #[serde(default = "SenderConfig::default_fee_bump_percent")]
+    pub fee_bump_percent: u64,
+    /// Cap (in wei) on `max_fee_per_gas` (base fee + priority fee) of transactions sent by the operator.
+    /// Transactions exceeding the cap are not sent; the `server_eth_sender_fee_cap_reached` metric is increased instead.
+    pub max_fee_per_gas_cap: Option<u64>,
 }
 
 impl SenderConfig {
@@ -187,6 +244,19 @@ impl SenderConfig {
         GasLimitMode::Maximum
     }
 
+    /// Stays below the default 128 KiB transaction size limit enforced by L1 nodes.
+    pub const fn default_pubdata_auto_selection_max_calldata_size() -> usize {
+        120_000
+    }
+
+    pub const fn default_max_aggregated_blocks_on_high_gas_price() -> u32 {
+        10
+    }
+
+    pub const fn default_fee_bump_percent() -> u64 {
+        20
+    }
+
     const fn default_tx_aggregation_paused() -> bool {
         false
     }
@@ -236,6 +306,14 @@ pub struct GasAdjusterConfig {
     pub internal_pubdata_pricing_multiplier: f64,
     /// Max blob base fee that is allowed to be used.
     pub max_blob_base_fee: Option<u64>,
+    /// Minimum number of fee sources that must agree on a fee value (after outlier rejection) for it to be accepted.
+    /// Only used if additional fee sources are configured. If not set, a single source is sufficient, i.e. fee data
+    /// is accepted as long as any source responds; set this explicitly to require agreement among several sources.
+    pub fee_sources_quorum: Option<usize>,
+    /// Maximum relative deviation of a fee value reported by a source from the median across all sources.
+    /// Values deviating more are considered outliers and are rejected. Only used if additional fee sources are configured.
+    #[serde(default = "GasAdjusterConfig::default_fee_sources_max_deviation")]
+    pub fee_sources_max_deviation: f64,
 }
 
 impl GasAdjusterConfig {
@@ -267,4 +345,8 @@ impl GasAdjusterConfig {
     pub const fn default_pricing_formula_parameter_b() -> f64 {
         1.001
     }
+
+    pub const fn default_fee_sources_max_deviation() -> f64 {
+        0.5
+    }
 }
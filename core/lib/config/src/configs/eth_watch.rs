@@ -11,11 +11,42 @@ pub struct EthWatchConfig {
     /// How often we want to poll the Ethereum node.
     /// Value in milliseconds.
     pub eth_node_poll_interval: u64,
+    /// Depth (in L1 blocks below the latest processed block) at which processed priority operations are checked
+    /// against L1 on each poll. If an L1 reorg deeper than the confirmation threshold has reverted some of them,
+    /// they are rolled back and replayed from L1. If not specified, L1 reorgs are not checked for.
+    pub reorg_check_depth_in_l1_blocks: Option<u64>,
+    /// Whether to track consumption on L1 of the L2-to-L1 messages sent by the bridge contracts
+    /// (i.e., finalization of withdrawals).
+    #[serde(default)]
+    pub l2_to_l1_message_tracking_enabled: bool,
+    /// How often tracked L2-to-L1 messages are checked for consumption on L1. Value in milliseconds.
+    #[serde(default = "EthWatchConfig::default_l2_to_l1_message_tracking_interval_ms")]
+    pub l2_to_l1_message_tracking_interval_ms: u64,
+    /// First L1 batch which L2-to-L1 messages are tracked. If not specified, tracking starts from the last L1 batch
+    /// executed on L1 at the moment tracking is first run, i.e., messages in older batches are not tracked.
+    pub l2_to_l1_message_tracking_first_l1_batch: Option<u32>,
+    /// Number of the latest tracked L1 batches for which the consumption status of L2-to-L1 messages is retained.
+    /// Statuses for older batches are pruned.
+    #[serde(default = "EthWatchConfig::default_l2_to_l1_message_retention_l1_batches")]
+    pub l2_to_l1_message_retention_l1_batches: u32,
 }
 
 impl EthWatchConfig {
+    pub fn default_l2_to_l1_message_tracking_interval_ms() -> u64 {
+        10_000
+    }
+
+    pub fn default_l2_to_l1_message_retention_l1_batches() -> u32 {
+        100_000
+    }
+
     /// Converts `self.eth_node_poll_interval` into `Duration`.
     pub fn poll_interval(&self) -> Duration {
         Duration::from_millis(self.eth_node_poll_interval)
     }
+
+    /// Converts `self.l2_to_l1_message_tracking_interval_ms` into `Duration`.
+    pub fn l2_to_l1_message_tracking_interval(&self) -> Duration {
+        Duration::from_millis(self.l2_to_l1_message_tracking_interval_ms)
+    }
 }
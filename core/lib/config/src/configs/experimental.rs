@@ -1,6 +1,6 @@
 //! Experimental part of configuration.
 
-use std::num::NonZeroU32;
+use std::{num::NonZeroU32, time::Duration};
 
 use serde::Deserialize;
 use zksync_basic_types::{vm::FastVmMode, L1BatchNumber};
@@ -13,6 +13,18 @@ pub struct ExperimentalDBConfig {
     /// Maximum number of files concurrently opened by state keeper cache RocksDB. Useful to fit into OS limits; can be used
     /// as a rudimentary way to control RAM usage of the cache.
     pub state_keeper_db_max_open_files: Option<NonZeroU32>,
+    /// Capacity of the dedicated block cache for the state column family of the state keeper RocksDB cache.
+    /// If not set, the state column family uses the shared block cache.
+    #[serde(default)]
+    pub state_keeper_db_state_cache_capacity_mb: Option<usize>,
+    /// Estimated number of bytes pending compaction in the state keeper RocksDB cache at which writes are slowed down.
+    /// If not set, the RocksDB default is used.
+    #[serde(default)]
+    pub state_keeper_db_soft_pending_compaction_limit_mb: Option<usize>,
+    /// Estimated number of bytes pending compaction in the state keeper RocksDB cache at which writes are stopped.
+    /// If not set, the RocksDB default is used.
+    #[serde(default)]
+    pub state_keeper_db_hard_pending_compaction_limit_mb: Option<usize>,
     /// Configures whether to persist protective reads when persisting L1 batches in the state keeper.
     /// Protective reads are never required by full nodes so far, not until such a node runs a full Merkle tree
     /// (presumably, to participate in L1 batch proving).
@@ -32,6 +44,16 @@ pub struct ExperimentalDBConfig {
     /// Enables the stale keys repair task for the Merkle tree.
     #[serde(default)]
     pub merkle_tree_repair_stale_keys: bool,
+    /// If set, enables the background consistency check for the Merkle tree, which checks the latest tree version
+    /// with this interval.
+    #[serde(default)]
+    pub merkle_tree_consistency_check_interval_sec: Option<u64>,
+    /// Delay between checking subtrees of the tree root by the Merkle tree consistency check. Used to limit the load
+    /// caused by the check.
+    #[serde(
+        default = "ExperimentalDBConfig::default_merkle_tree_consistency_check_subtree_delay_ms"
+    )]
+    pub merkle_tree_consistency_check_subtree_delay_ms: u64,
 }
 
 impl Default for ExperimentalDBConfig {
@@ -40,10 +62,16 @@ impl Default for ExperimentalDBConfig {
             state_keeper_db_block_cache_capacity_mb:
                 Self::default_state_keeper_db_block_cache_capacity_mb(),
             state_keeper_db_max_open_files: None,
+            state_keeper_db_state_cache_capacity_mb: None,
+            state_keeper_db_soft_pending_compaction_limit_mb: None,
+            state_keeper_db_hard_pending_compaction_limit_mb: None,
             protective_reads_persistence_enabled: false,
             processing_delay_ms: Self::default_merkle_tree_processing_delay_ms(),
             include_indices_and_filters_in_block_cache: false,
             merkle_tree_repair_stale_keys: false,
+            merkle_tree_consistency_check_interval_sec: None,
+            merkle_tree_consistency_check_subtree_delay_ms:
+                Self::default_merkle_tree_consistency_check_subtree_delay_ms(),
         }
     }
 }
@@ -57,9 +85,38 @@ impl ExperimentalDBConfig {
         self.state_keeper_db_block_cache_capacity_mb * super::BYTES_IN_MEGABYTE
     }
 
+    pub fn state_keeper_db_state_cache_capacity(&self) -> Option<usize> {
+        self.state_keeper_db_state_cache_capacity_mb
+            .map(|mb| mb * super::BYTES_IN_MEGABYTE)
+    }
+
+    pub fn state_keeper_db_soft_pending_compaction_limit(&self) -> Option<usize> {
+        self.state_keeper_db_soft_pending_compaction_limit_mb
+            .map(|mb| mb * super::BYTES_IN_MEGABYTE)
+    }
+
+    pub fn state_keeper_db_hard_pending_compaction_limit(&self) -> Option<usize> {
+        self.state_keeper_db_hard_pending_compaction_limit_mb
+            .map(|mb| mb * super::BYTES_IN_MEGABYTE)
+    }
+
     const fn default_merkle_tree_processing_delay_ms() -> u64 {
         100
     }
+
+    pub const fn default_merkle_tree_consistency_check_subtree_delay_ms() -> u64 {
+        100
+    }
+
+    /// Returns the interval between Merkle tree consistency checks, or `None` if the check is disabled.
+    pub fn merkle_tree_consistency_check_interval(&self) -> Option<Duration> {
+        self.merkle_tree_consistency_check_interval_sec
+            .map(Duration::from_secs)
+    }
+
+    pub fn merkle_tree_consistency_check_subtree_delay(&self) -> Duration {
+        Duration::from_millis(self.merkle_tree_consistency_check_subtree_delay_ms)
+    }
 }
 
 /// Configuration for the VM playground (an experimental component that's unlikely to ever be stabilized).
@@ -115,4 +172,35 @@ pub struct ExperimentalVmConfig {
     /// or transaction validation), so the legacy VM will always be used for them.
     #[serde(default)]
     pub api_fast_vm_mode: FastVmMode,
+
+    /// Number of pre-initialized legacy VM states kept by the API server for each set of base system contracts,
+    /// so that VM setup is excluded from `eth_call` latency. If not set or set to 0, VM states are not pre-initialized.
+    pub api_vm_warm_pool_size: Option<usize>,
+    /// Whether to account memory and CPU time consumed by legacy VM executions in the API server (calls,
+    /// gas estimations and call tracing). Accounting is also enabled implicitly if any of the limits below is set.
+    #[serde(default)]
+    pub api_vm_resource_accounting: bool,
+    /// Maximum estimated memory consumed by a single VM execution in the API server, in MiB.
+    /// Executions exceeding the limit are halted.
+    pub api_vm_max_memory_mb: Option<usize>,
+    /// Maximum CPU time of a single VM execution in the API server, in milliseconds.
+    /// Executions exceeding the limit are halted.
+    pub api_vm_max_cpu_time_ms: Option<u64>,
+}
+
+impl ExperimentalVmConfig {
+    /// Checks whether resource accounting for API VM executions is enabled.
+    pub fn api_vm_resource_accounting_enabled(&self) -> bool {
+        self.api_vm_resource_accounting
+            || self.api_vm_max_memory_mb.is_some()
+            || self.api_vm_max_cpu_time_ms.is_some()
+    }
+
+    pub fn api_vm_max_memory(&self) -> Option<usize> {
+        self.api_vm_max_memory_mb.map(|mb| mb << 20)
+    }
+
+    pub fn api_vm_max_cpu_time(&self) -> Option<Duration> {
+        self.api_vm_max_cpu_time_ms.map(Duration::from_millis)
+    }
 }
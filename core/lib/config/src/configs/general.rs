@@ -1,6 +1,7 @@
 use crate::{
     configs::{
         base_token_adjuster::BaseTokenAdjusterConfig,
+        batch_exporter::BatchExporterConfig,
         chain::{
             CircuitBreakerConfig, MempoolConfig, OperationsManagerConfig, StateKeeperConfig,
             TimestampAsserterConfig,
@@ -12,6 +13,7 @@ use crate::{
         prover_job_monitor::ProverJobMonitorConfig,
         pruning::PruningConfig,
         snapshot_recovery::SnapshotRecoveryConfig,
+        tx_policy::TxPolicyConfig,
         vm_runner::{BasicWitnessInputProducerConfig, ProtectiveReadsWriterConfig},
         CommitmentGeneratorConfig, ExperimentalVmConfig, ExternalPriceApiClientConfig,
         FriProofCompressorConfig, FriProverConfig, FriProverGatewayConfig,
@@ -56,4 +58,6 @@ pub struct GeneralConfig {
     pub experimental_vm_config: Option<ExperimentalVmConfig>,
     pub prover_job_monitor_config: Option<ProverJobMonitorConfig>,
     pub timestamp_asserter_config: Option<TimestampAsserterConfig>,
+    pub tx_policy_config: Option<TxPolicyConfig>,
+    pub batch_exporter_config: Option<BatchExporterConfig>,
 }
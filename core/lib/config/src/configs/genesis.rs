@@ -25,6 +25,29 @@ pub struct GenesisConfig {
     pub dummy_verifier: bool,
     pub l1_batch_commit_data_generator_mode: L1BatchCommitmentMode,
     pub custom_genesis_state_path: Option<String>,
+    /// Circuit geometry used by the prover. Must be set only if the prover uses a non-standard geometry.
+    pub circuit_geometry: Option<CircuitGeometryConfig>,
+}
+
+/// Overrides for the circuit geometry used to estimate circuit usage during batch execution.
+/// Unset fields fall back to the geometry of the latest protocol version.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CircuitGeometryConfig {
+    pub cycles_per_vm_snapshot: Option<u32>,
+    pub cycles_per_ram_permutation: Option<u32>,
+    pub cycles_per_storage_application: Option<u32>,
+    pub cycles_per_storage_sorter: Option<u32>,
+    pub cycles_per_code_decommitter: Option<u32>,
+    pub cycles_code_decommitter_sorter: Option<u32>,
+    pub cycles_per_log_demuxer: Option<u32>,
+    pub cycles_per_events_or_l1_messages_sorter: Option<u32>,
+    pub cycles_per_keccak256_circuit: Option<u32>,
+    pub cycles_per_ecrecover_circuit: Option<u32>,
+    pub cycles_per_sha256_circuit: Option<u32>,
+    pub cycles_per_secp256r1_verify_circuit: Option<u32>,
+    pub cycles_per_transient_storage_sorter: Option<u32>,
+    /// Maximum number of base layer circuits in a single L1 batch.
+    pub max_base_layer_circuits: Option<u64>,
 }
 
 impl GenesisConfig {
@@ -48,6 +71,7 @@ impl GenesisConfig {
             dummy_verifier: false,
             l1_batch_commit_data_generator_mode: L1BatchCommitmentMode::Rollup,
             custom_genesis_state_path: None,
+            circuit_geometry: None,
         }
     }
 }
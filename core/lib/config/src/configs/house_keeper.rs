@@ -1,7 +1,25 @@
+use std::time::Duration;
+
 use serde::Deserialize;
 
 /// Configuration for the house keeper.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct HouseKeeperConfig {
     pub l1_batch_metrics_reporting_interval_ms: u64,
+    /// Interval between recomputing transaction inclusion stats. If not set, inclusion stats are not tracked.
+    pub inclusion_stats_reporting_interval_ms: Option<u64>,
+    /// Inclusion stats are computed for transactions received during this period. Defaults to 1 hour.
+    pub inclusion_stats_window_secs: Option<u64>,
+    /// Interval between aggregating gas and pubdata usage per contract for newly sealed L2 blocks.
+    /// If not set, contract usage is not aggregated.
+    pub contract_usage_aggregation_interval_ms: Option<u64>,
+}
+
+impl HouseKeeperConfig {
+    const DEFAULT_INCLUSION_STATS_WINDOW: Duration = Duration::from_secs(3_600);
+
+    pub fn inclusion_stats_window(&self) -> Duration {
+        self.inclusion_stats_window_secs
+            .map_or(Self::DEFAULT_INCLUSION_STATS_WINDOW, Duration::from_secs)
+    }
 }
@@ -2,6 +2,7 @@
 pub use self::{
     api::ApiConfig,
     base_token_adjuster::BaseTokenAdjusterConfig,
+    batch_exporter::BatchExporterConfig,
     commitment_generator::CommitmentGeneratorConfig,
     contract_verifier::ContractVerifierConfig,
     contracts::chain::AllContractsConfig,
@@ -18,23 +19,26 @@ pub use self::{
     fri_prover_gateway::FriProverGatewayConfig,
     fri_witness_generator::FriWitnessGeneratorConfig,
     general::GeneralConfig,
-    genesis::GenesisConfig,
+    genesis::{CircuitGeometryConfig, GenesisConfig},
     object_store::ObjectStoreConfig,
     observability::{ObservabilityConfig, OpentelemetryConfig},
     proof_data_handler::{ProofDataHandlerConfig, TeeConfig},
     prover_job_monitor::ProverJobMonitorConfig,
     pruning::PruningConfig,
     secrets::{
-        ContractVerifierSecrets, DataAvailabilitySecrets, DatabaseSecrets, L1Secrets, Secrets,
+        AdminApiSecrets, ContractVerifierSecrets, DataAvailabilitySecrets, DatabaseSecrets,
+        L1Secrets, Secrets,
     },
     snapshot_recovery::SnapshotRecoveryConfig,
     snapshots_creator::SnapshotsCreatorConfig,
+    tx_policy::TxPolicyConfig,
     utils::PrometheusConfig,
     vm_runner::{BasicWitnessInputProducerConfig, ProtectiveReadsWriterConfig},
 };
 
 pub mod api;
 pub mod base_token_adjuster;
+pub mod batch_exporter;
 pub mod chain;
 mod commitment_generator;
 pub mod consensus;
@@ -64,6 +68,7 @@ pub mod pruning;
 pub mod secrets;
 pub mod snapshot_recovery;
 pub mod snapshots_creator;
+pub mod tx_policy;
 pub mod utils;
 pub mod vm_runner;
 pub mod wallets;
@@ -16,6 +16,9 @@ pub struct ObjectStoreConfig {
     /// **Important.** Mirroring logic assumes that objects in the underlying store are immutable. If this is not the case,
     /// the mirrored objects may become stale.
     pub local_mirror_path: Option<String>,
+    /// Content addressing mode for the store. If not specified, content addressing is disabled.
+    #[serde(default)]
+    pub content_addressing: ContentAddressingMode,
 }
 
 impl ObjectStoreConfig {
@@ -24,6 +27,25 @@ impl ObjectStoreConfig {
     }
 }
 
+/// Content addressing mode for an object store.
+///
+/// In content-addressed mode, payloads are stored under keys derived from their SHA-256 digest, and logical keys
+/// point to these payloads. Each read verifies the payload digest, so that corrupted objects are never returned.
+/// The recommended migration path for an existing bucket is `Disabled` -> `Enabled` -> `Strict`, with the switch
+/// to `Strict` performed once all legacy objects are either migrated or no longer needed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentAddressingMode {
+    /// Objects are stored under their logical keys without integrity verification.
+    #[default]
+    Disabled,
+    /// Objects are stored in the content-addressed form. Legacy objects stored under their logical keys
+    /// are still readable (without integrity verification) and are migrated to the content-addressed form on read.
+    Enabled,
+    /// Objects are stored in the content-addressed form, and reading legacy objects results in an error.
+    Strict,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(tag = "mode")]
 pub enum ObjectStoreMode {
@@ -47,6 +69,15 @@ pub enum ObjectStoreMode {
         s3_credential_file_path: String,
         endpoint: Option<String>,
         region: Option<String>,
+        /// Size of a single part for multipart uploads, in MiB. Objects larger than this size are uploaded
+        /// using multipart upload; smaller objects are uploaded with a single `PUT` request. Must be at least 5 MiB.
+        /// If not specified, 64 MiB parts are used.
+        multipart_part_size_mb: Option<u64>,
+        /// Server-side encryption algorithm specified for uploaded objects, e.g. `AES256` or `aws:kms`.
+        /// If not specified, the bucket default is used.
+        server_side_encryption: Option<String>,
+        /// KMS key ID used for server-side encryption. Only used if `server_side_encryption` is `aws:kms` or `aws:kms:dsse`.
+        sse_kms_key_id: Option<String>,
     },
     FileBacked {
         file_backed_base_path: String,
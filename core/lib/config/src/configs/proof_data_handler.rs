@@ -15,6 +15,19 @@ pub struct TeeConfig {
     pub tee_proof_generation_timeout_in_secs: u16,
     /// Timeout in hours after which a batch will be permanently ignored if repeated retries failed.
     pub tee_batch_permanently_ignored_timeout_in_hours: u16,
+    /// If true, attestation quotes registered by TEE provers are checked against the measurement allowlists below,
+    /// and quotes produced by debug-mode enclaves are rejected.
+    #[serde(default)]
+    pub attestation_policy_enabled: bool,
+    /// Hex-encoded SGX enclave measurements (MRENCLAVE) accepted by the attestation policy.
+    #[serde(default)]
+    pub sgx_mrenclave_allowlist: Vec<String>,
+    /// Hex-encoded SGX enclave signer measurements (MRSIGNER) accepted by the attestation policy.
+    #[serde(default)]
+    pub sgx_mrsigner_allowlist: Vec<String>,
+    /// Hex-encoded TDX trust domain measurements (MRTD) accepted by the attestation policy.
+    #[serde(default)]
+    pub tdx_mrtd_allowlist: Vec<String>,
 }
 
 impl Default for TeeConfig {
@@ -26,6 +39,10 @@ impl Default for TeeConfig {
                 Self::default_tee_proof_generation_timeout_in_secs(),
             tee_batch_permanently_ignored_timeout_in_hours:
                 Self::default_tee_batch_permanently_ignored_timeout_in_hours(),
+            attestation_policy_enabled: false,
+            sgx_mrenclave_allowlist: vec![],
+            sgx_mrsigner_allowlist: vec![],
+            tdx_mrtd_allowlist: vec![],
         }
     }
 }
@@ -64,6 +81,18 @@ pub struct ProofDataHandlerConfig {
     // ^ Filled in separately in `Self::from_env()`. We cannot use `serde(flatten)` because it
     // doesn't work with `envy`: https://github.com/softprops/envy/issues/26
     pub tee_config: TeeConfig,
+    /// If set, real proofs are generated only for a pseudo-random sample of L1 batches with the specified rate
+    /// (from 0 to 1); other batches are marked as skipped. Sampling is deterministic and is derived
+    /// from batch commitments, so that it can be verified by third parties.
+    #[serde(default)]
+    pub proof_sampling_rate: Option<f64>,
+    /// Inclusive L1 batch ranges (e.g., `100-200`) for which real proofs are always generated if sampling is enabled.
+    /// Takes precedence over `proof_sampling_never_prove_ranges`.
+    #[serde(default)]
+    pub proof_sampling_always_prove_ranges: Vec<String>,
+    /// Inclusive L1 batch ranges (e.g., `100-200`) for which proofs are never generated if sampling is enabled.
+    #[serde(default)]
+    pub proof_sampling_never_prove_ranges: Vec<String>,
 }
 
 impl ProofDataHandlerConfig {
@@ -2,6 +2,8 @@ use std::num::NonZeroU64;
 
 use serde::Deserialize;
 
+use crate::ObjectStoreConfig;
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct PruningConfig {
     pub enabled: bool,
@@ -16,4 +18,30 @@ pub struct PruningConfig {
     /// the retention period greater than that implicitly imposed by other criteria (e.g., 7 or 30 days).
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 1 hour.
     pub data_retention_sec: Option<u64>,
+    /// If set, call traces will be removed from Postgres for L1 batches whose timestamp is this old (in seconds).
+    /// Unlike `data_retention_sec`, this works independently of `enabled`; L1 batches and L2 blocks are retained.
+    /// Similarly to L1 batch pruning, data is only removed once the L1 batch is executed on L1 and has
+    /// its commitment generated. Requests for removed data are rejected by the API server with a dedicated error.
+    pub call_traces_retention_sec: Option<u64>,
+    /// If set, events will be removed from Postgres for L1 batches whose timestamp is this old (in seconds).
+    pub events_retention_sec: Option<u64>,
+    /// If set, the pruner only reports (via logs and its health check) which L1 batches would be pruned
+    /// without removing any data.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// If set, call traces and events will be moved to `cold_storage_object_store` for L1 batches whose timestamp
+    /// is this old (in seconds) and which are executed on L1 and have their commitment generated. Archives are stored
+    /// as Parquet files. Postgres rows are retained as stubs, and the API server fetches archived data
+    /// from the object store on demand.
+    pub cold_storage_archive_after_sec: Option<u64>,
+    /// Object store used as cold storage for call traces and events. Required for the cold storage component,
+    /// and for the API server to serve archived data.
+    pub cold_storage_object_store: Option<ObjectStoreConfig>,
+}
+
+impl PruningConfig {
+    /// Returns `true` if at least one data retention policy is configured.
+    pub fn has_data_retention_policies(&self) -> bool {
+        self.call_traces_retention_sec.is_some() || self.events_retention_sec.is_some()
+    }
 }
@@ -17,6 +17,8 @@ pub struct DatabaseSecrets {
 pub struct L1Secrets {
     pub l1_rpc_url: SensitiveUrl,
     pub gateway_rpc_url: Option<SensitiveUrl>,
+    /// Additional L1 RPC endpoints used as fee sources by the gas adjuster, e.g. third-party RPC providers.
+    pub fee_source_rpc_urls: Vec<SensitiveUrl>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -33,6 +35,13 @@ pub struct ContractVerifierSecrets {
     pub etherscan_api_key: Option<APIKey>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdminApiSecrets {
+    /// Bearer token that must be provided by clients of the `admin` JSON-RPC namespace and of the admin API
+    /// of the proof data handler.
+    pub auth_token: APIKey,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Secrets {
     pub consensus: Option<ConsensusSecrets>,
@@ -40,6 +49,7 @@ pub struct Secrets {
     pub l1: Option<L1Secrets>,
     pub data_availability: Option<DataAvailabilitySecrets>,
     pub contract_verifier: Option<ContractVerifierSecrets>,
+    pub admin_api: Option<AdminApiSecrets>,
 }
 
 impl DatabaseSecrets {
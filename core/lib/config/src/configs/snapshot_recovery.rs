@@ -18,6 +18,9 @@ pub struct TreeRecoveryConfig {
     ///
     /// If not set, parallel persistence will be disabled.
     pub parallel_persistence_buffer: Option<NonZeroUsize>,
+    /// Enables inserting entries of each recovered chunk into the tree in parallel (one writer per top-level subtree).
+    #[serde(default)]
+    pub parallel_extension: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Default)]
@@ -42,6 +45,10 @@ pub struct SnapshotRecoveryConfig {
     /// This is a temporary flag that will eventually be removed together with version 0 snapshot support.
     #[serde(default)]
     pub drop_storage_key_preimages: bool,
+    /// Enables recovering from a chain of differential snapshots (a full base snapshot and deltas on top of it)
+    /// if the main node advertises one. If disabled, only full snapshots are used.
+    #[serde(default)]
+    pub apply_differential_snapshots: bool,
     pub tree: TreeRecoveryConfig,
     pub postgres: PostgresRecoveryConfig,
     pub object_store: Option<ObjectStoreConfig>,
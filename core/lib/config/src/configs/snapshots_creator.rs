@@ -20,6 +20,13 @@ pub struct SnapshotsCreatorConfig {
     pub storage_logs_chunk_size: u64,
     #[serde(default = "SnapshotsCreatorConfig::concurrent_queries_count")]
     pub concurrent_queries_count: u32,
+    /// Maximum number of differential snapshots based on a single full snapshot. A differential snapshot only contains
+    /// storage logs and factory deps changed since the previous snapshot, which makes it much faster to produce.
+    /// If set to 0 (the default), only full snapshots are created.
+    ///
+    /// Differential snapshots are only created for snapshot version 1.
+    #[serde(default)]
+    pub max_differential_snapshots: u32,
     pub object_store: Option<ObjectStoreConfig>,
 }
 
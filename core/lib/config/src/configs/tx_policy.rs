@@ -0,0 +1,21 @@
+use serde::Deserialize;
+use zksync_basic_types::Address;
+
+/// Configuration of the transaction policy engine, which is evaluated both when a transaction is submitted via the API
+/// (`eth_sendRawTransaction`) and when it's included into a block by the state keeper.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+pub struct TxPolicyConfig {
+    /// If non-empty, only transactions initiated by these addresses are accepted.
+    #[serde(default)]
+    pub allowed_initiators: Vec<Address>,
+    /// Transactions initiated by or sent to these addresses are rejected. Takes precedence over `allowed_initiators`.
+    #[serde(default)]
+    pub denied_addresses: Vec<Address>,
+    /// If set, only these addresses are allowed to deploy contracts. Unlike `allowed_initiators`, an empty list
+    /// disables contract deployments altogether.
+    #[serde(default)]
+    pub allowed_deployers: Option<Vec<Address>>,
+    /// Maximum number of transactions a single initiator can submit per minute. Not limited if not set.
+    #[serde(default)]
+    pub max_txs_per_address_per_minute: Option<u32>,
+}
@@ -1,5 +1,5 @@
 use serde::Deserialize;
-use zksync_basic_types::L1BatchNumber;
+use zksync_basic_types::{vm::FastVmMode, L1BatchNumber};
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct ProtectiveReadsWriterConfig {
@@ -10,6 +10,14 @@ pub struct ProtectiveReadsWriterConfig {
     pub window_size: u32,
     /// All batches before this one (inclusive) are always considered to be processed.
     pub first_processed_batch: L1BatchNumber,
+    /// Fast VM mode used to re-execute batches. Since re-execution results are cross-checked against the state keeper
+    /// output, running the fast VM here allows validating it without affecting the critical sealing path.
+    #[serde(default)]
+    pub fast_vm_mode: FastVmMode,
+    /// Whether to stop the component if re-execution results diverge from the state keeper output. If not set,
+    /// divergences are only logged, reported in metrics and persisted.
+    #[serde(default)]
+    pub fail_on_divergence: bool,
 }
 
 impl ProtectiveReadsWriterConfig {
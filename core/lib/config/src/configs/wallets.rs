@@ -62,6 +62,9 @@ impl Wallet {
 pub struct EthSender {
     pub operator: Wallet,
     pub blob_operator: Option<Wallet>,
+    /// Additional keys forming an operator pool together with `operator`. Non-blob transactions
+    /// stuck for one of the keys are taken over by the next key from the pool.
+    pub additional_operators: Vec<Wallet>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -74,11 +77,18 @@ pub struct TokenMultiplierSetter {
     pub wallet: Wallet,
 }
 
+/// Wallet used by the state keeper to sign soft confirmations for included transactions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoftConfirmationSigner {
+    pub wallet: Wallet,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Wallets {
     pub eth_sender: Option<EthSender>,
     pub state_keeper: Option<StateKeeper>,
     pub token_multiplier_setter: Option<TokenMultiplierSetter>,
+    pub soft_confirmation_signer: Option<SoftConfirmationSigner>,
 }
 
 impl Wallets {
@@ -89,6 +99,7 @@ impl Wallets {
                 blob_operator: Some(
                     Wallet::from_private_key_bytes(H256::repeat_byte(0x2), None).unwrap(),
                 ),
+                additional_operators: vec![],
             }),
             state_keeper: Some(StateKeeper {
                 fee_account: AddressWallet::from_address(H160::repeat_byte(0x3)),
@@ -96,6 +107,9 @@ impl Wallets {
             token_multiplier_setter: Some(TokenMultiplierSetter {
                 wallet: Wallet::from_private_key_bytes(H256::repeat_byte(0x4), None).unwrap(),
             }),
+            soft_confirmation_signer: Some(SoftConfirmationSigner {
+                wallet: Wallet::from_private_key_bytes(H256::repeat_byte(0x5), None).unwrap(),
+            }),
         }
     }
 }
@@ -36,6 +36,17 @@ impl Distribution<configs::chain::FeeModelVersion> for EncodeDist {
     }
 }
 
+impl Distribution<configs::chain::TimestampPolicyKind> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::chain::TimestampPolicyKind {
+        type T = configs::chain::TimestampPolicyKind;
+        match rng.gen_range(0..3) {
+            0 => T::RealTime,
+            1 => T::FixedIncrement,
+            _ => T::MedianOfL1,
+        }
+    }
+}
+
 impl Distribution<configs::ApiConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::ApiConfig {
         configs::ApiConfig {
@@ -52,6 +63,7 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
         configs::api::Web3JsonRpcConfig {
             http_port: self.sample(rng),
             ws_port: self.sample(rng),
+            admin_port: self.sample(rng),
             req_entities_limit: self.sample(rng),
             filters_disabled: self.sample(rng),
             filters_limit: self.sample(rng),
@@ -63,12 +75,15 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             estimate_gas_acceptable_overestimation: self.sample(rng),
             estimate_gas_optimize_search: self.sample(rng),
             max_tx_size: self.sample(rng),
+            max_tx_abi_encoded_size: self.sample(rng),
+            max_tx_compressed_bytecodes_size: self.sample(rng),
             vm_execution_cache_misses_limit: self.sample(rng),
             vm_concurrency_limit: self.sample(rng),
             factory_deps_cache_size_mb: self.sample(rng),
             initial_writes_cache_size_mb: self.sample(rng),
             latest_values_cache_size_mb: self.sample(rng),
             latest_values_max_block_lag: self.sample(rng),
+            storage_read_batching_window_ms: self.sample(rng),
             fee_history_limit: self.sample(rng),
             max_batch_request_size: self.sample(rng),
             max_response_body_size_mb: self.sample(rng),
@@ -84,10 +99,15 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             ]
             .into_iter()
             .collect(),
+            max_trace_size_mb: self.sample(rng),
             websocket_requests_per_minute_limit: self.sample(rng),
             tree_api_url: self.sample(rng),
             mempool_cache_update_interval: self.sample(rng),
             mempool_cache_size: self.sample(rng),
+            eth_call_cache_size: self.sample(rng),
+            load_shedding_vm_queue_latency_ms: self.sample(rng),
+            load_shedding_pool_utilization: self.sample(rng),
+            load_shedding_retry_after_ms: self.sample(rng),
             whitelisted_tokens_for_aa: self.sample_range(rng).map(|_| rng.gen()).collect(),
             api_namespaces: self
                 .sample_opt(|| self.sample_range(rng).map(|_| self.sample(rng)).collect()),
@@ -158,10 +178,33 @@ impl Distribution<configs::chain::StateKeeperConfig> for EncodeDist {
             max_gas_per_batch: self.sample(rng),
             max_pubdata_per_batch: self.sample(rng),
             fee_model_version: self.sample(rng),
+            fee_congestion_enabled: self.sample(rng),
+            fee_congestion_compute_target: self.sample(rng),
+            fee_congestion_pubdata_target: self.sample(rng),
+            fee_congestion_max_change_denominator: self.sample(rng),
+            fee_congestion_max_multiplier: self.sample(rng),
+            fee_congestion_window_batches: self.sample(rng),
             validation_computational_gas_limit: self.sample(rng),
             save_call_traces: self.sample(rng),
             max_circuits_per_batch: self.sample(rng),
+            geometry_adjustment_enabled: self.sample(rng),
+            geometry_adjustment_target_proving_time_sec: self.sample(rng),
+            geometry_adjustment_min_percentage: self.sample(rng),
+            geometry_adjustment_max_percentage: self.sample(rng),
+            geometry_adjustment_window_batches: self.sample(rng),
             protective_reads_persistence_enabled: self.sample(rng),
+            witness_inputs_pregeneration_enabled: self.sample(rng),
+            storage_slot_writers_enabled: self.sample(rng),
+            pending_batch_state_enabled: self.sample(rng),
+            timestamp_policy: self.sample(rng),
+            timestamp_increment_sec: self.sample(rng),
+            l1_timestamp_window: self.sample(rng),
+            dev_mode: self.sample(rng),
+            sequencer_instance_id: self.sample(rng),
+            sequencer_lease_ttl_ms: self.sample(rng),
+            frame_gas_forwarding_divisor: self.sample_opt(|| rng.gen_range(1..=64)),
+            max_frame_gas: self.sample(rng),
+            batch_checkpoint_interval: self.sample_opt(|| rng.gen_range(1..=100)),
             // These values are not involved into files serialization skip them
             fee_account_addr: None,
             bootloader_hash: None,
@@ -271,6 +314,12 @@ impl Distribution<configs::database::MerkleTreeConfig> for EncodeDist {
             memtable_capacity_mb: self.sample(rng),
             stalled_writes_timeout_sec: self.sample(rng),
             max_l1_batches_per_iter: self.sample(rng),
+            hashing_thread_count: self.sample(rng),
+            pruning_retained_l1_batches: self.sample(rng),
+            pruning_retain_unproven_l1_batches: self.sample(rng),
+            pruning_poll_interval_sec: self.sample(rng),
+            in_memory: self.sample(rng),
+            in_memory_persistence_interval_sec: self.sample(rng),
         }
     }
 }
@@ -280,10 +329,15 @@ impl Distribution<configs::ExperimentalDBConfig> for EncodeDist {
         configs::ExperimentalDBConfig {
             state_keeper_db_block_cache_capacity_mb: self.sample(rng),
             state_keeper_db_max_open_files: self.sample(rng),
+            state_keeper_db_state_cache_capacity_mb: self.sample(rng),
+            state_keeper_db_soft_pending_compaction_limit_mb: self.sample(rng),
+            state_keeper_db_hard_pending_compaction_limit_mb: self.sample(rng),
             protective_reads_persistence_enabled: self.sample(rng),
             processing_delay_ms: self.sample(rng),
             include_indices_and_filters_in_block_cache: self.sample(rng),
             merkle_tree_repair_stale_keys: self.sample(rng),
+            merkle_tree_consistency_check_interval_sec: self.sample(rng),
+            merkle_tree_consistency_check_subtree_delay_ms: self.sample(rng),
         }
     }
 }
@@ -314,6 +368,10 @@ impl Distribution<configs::ExperimentalVmConfig> for EncodeDist {
             playground: self.sample(rng),
             state_keeper_fast_vm_mode: gen_fast_vm_mode(rng),
             api_fast_vm_mode: gen_fast_vm_mode(rng),
+            api_vm_warm_pool_size: self.sample(rng),
+            api_vm_resource_accounting: self.sample(rng),
+            api_vm_max_memory_mb: self.sample(rng),
+            api_vm_max_cpu_time_ms: self.sample(rng),
         }
     }
 }
@@ -402,6 +460,16 @@ impl Distribution<configs::eth_sender::SenderConfig> for EncodeDist {
             time_in_mempool_in_l1_blocks_cap: self.sample(rng),
             is_verifier_pre_fflonk: self.sample(rng),
             gas_limit_mode: self.sample(rng),
+            pubdata_auto_selection: self.sample(rng),
+            pubdata_auto_selection_max_blob_base_fee: self.sample(rng),
+            pubdata_auto_selection_max_calldata_size: self.sample(rng),
+            operator_takeover_after_l1_blocks: self.sample(rng),
+            high_gas_price_aggregation_threshold: self.sample(rng),
+            max_aggregated_blocks_on_high_gas_price: self.sample(rng),
+            fee_bump_interval_in_l1_blocks: self.sample(rng),
+            fee_bump_interval_sec: self.sample(rng),
+            fee_bump_percent: self.sample(rng),
+            max_fee_per_gas_cap: self.sample(rng),
         }
     }
 }
@@ -421,6 +489,8 @@ impl Distribution<configs::eth_sender::GasAdjusterConfig> for EncodeDist {
             num_samples_for_blob_base_fee_estimate: self.sample(rng),
             internal_pubdata_pricing_multiplier: self.sample(rng),
             max_blob_base_fee: self.sample(rng),
+            fee_sources_quorum: self.sample(rng),
+            fee_sources_max_deviation: self.sample(rng),
         }
     }
 }
@@ -430,6 +500,11 @@ impl Distribution<configs::EthWatchConfig> for EncodeDist {
         configs::EthWatchConfig {
             confirmations_for_eth_event: self.sample(rng),
             eth_node_poll_interval: self.sample(rng),
+            reorg_check_depth_in_l1_blocks: self.sample(rng),
+            l2_to_l1_message_tracking_enabled: self.sample(rng),
+            l2_to_l1_message_tracking_interval_ms: self.sample(rng),
+            l2_to_l1_message_tracking_first_l1_batch: self.sample(rng),
+            l2_to_l1_message_retention_l1_batches: self.sample(rng),
         }
     }
 }
@@ -495,6 +570,9 @@ impl Distribution<configs::house_keeper::HouseKeeperConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::house_keeper::HouseKeeperConfig {
         configs::house_keeper::HouseKeeperConfig {
             l1_batch_metrics_reporting_interval_ms: self.sample(rng),
+            inclusion_stats_reporting_interval_ms: self.sample(rng),
+            inclusion_stats_window_secs: self.sample(rng),
+            contract_usage_aggregation_interval_ms: self.sample(rng),
         }
     }
 }
@@ -502,10 +580,19 @@ impl Distribution<configs::house_keeper::HouseKeeperConfig> for EncodeDist {
 impl Distribution<configs::object_store::ObjectStoreMode> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::object_store::ObjectStoreMode {
         type T = configs::object_store::ObjectStoreMode;
-        match rng.gen_range(0..4) {
+        match rng.gen_range(0..5) {
             0 => T::GCS {
                 bucket_base_url: self.sample(rng),
             },
+            3 => T::S3WithCredentialFile {
+                bucket_base_url: self.sample(rng),
+                s3_credential_file_path: self.sample(rng),
+                endpoint: self.sample(rng),
+                region: self.sample(rng),
+                multipart_part_size_mb: self.sample(rng),
+                server_side_encryption: self.sample(rng),
+                sse_kms_key_id: self.sample(rng),
+            },
             1 => T::GCSWithCredentialFile {
                 bucket_base_url: self.sample(rng),
                 gcs_credential_file_path: self.sample(rng),
@@ -526,6 +613,18 @@ impl Distribution<configs::ObjectStoreConfig> for EncodeDist {
             mode: self.sample(rng),
             max_retries: self.sample(rng),
             local_mirror_path: self.sample(rng),
+            content_addressing: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::object_store::ContentAddressingMode> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::object_store::ContentAddressingMode {
+        type T = configs::object_store::ContentAddressingMode;
+        match rng.gen_range(0..3) {
+            0 => T::Disabled,
+            1 => T::Enabled,
+            _ => T::Strict,
         }
     }
 }
@@ -540,7 +639,14 @@ impl Distribution<configs::ProofDataHandlerConfig> for EncodeDist {
                 first_tee_processed_batch: L1BatchNumber(rng.gen()),
                 tee_proof_generation_timeout_in_secs: self.sample(rng),
                 tee_batch_permanently_ignored_timeout_in_hours: self.sample(rng),
+                attestation_policy_enabled: self.sample(rng),
+                sgx_mrenclave_allowlist: self.sample_collect(rng),
+                sgx_mrsigner_allowlist: self.sample_collect(rng),
+                tdx_mrtd_allowlist: self.sample_collect(rng),
             },
+            proof_sampling_rate: self.sample_opt(|| rng.gen()),
+            proof_sampling_always_prove_ranges: self.sample_collect(rng),
+            proof_sampling_never_prove_ranges: self.sample_collect(rng),
         }
     }
 }
@@ -552,6 +658,7 @@ impl Distribution<configs::SnapshotsCreatorConfig> for EncodeDist {
             version: if rng.gen() { 0 } else { 1 },
             storage_logs_chunk_size: self.sample(rng),
             concurrent_queries_count: self.sample(rng),
+            max_differential_snapshots: self.sample(rng),
             object_store: self.sample(rng),
         }
     }
@@ -606,6 +713,28 @@ impl Distribution<configs::GenesisConfig> for EncodeDist {
                 _ => L1BatchCommitmentMode::Validium,
             },
             custom_genesis_state_path: None,
+            circuit_geometry: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::CircuitGeometryConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::CircuitGeometryConfig {
+        configs::CircuitGeometryConfig {
+            cycles_per_vm_snapshot: self.sample_opt(|| rng.gen()),
+            cycles_per_ram_permutation: self.sample_opt(|| rng.gen()),
+            cycles_per_storage_application: self.sample_opt(|| rng.gen()),
+            cycles_per_storage_sorter: self.sample_opt(|| rng.gen()),
+            cycles_per_code_decommitter: self.sample_opt(|| rng.gen()),
+            cycles_code_decommitter_sorter: self.sample_opt(|| rng.gen()),
+            cycles_per_log_demuxer: self.sample_opt(|| rng.gen()),
+            cycles_per_events_or_l1_messages_sorter: self.sample_opt(|| rng.gen()),
+            cycles_per_keccak256_circuit: self.sample_opt(|| rng.gen()),
+            cycles_per_ecrecover_circuit: self.sample_opt(|| rng.gen()),
+            cycles_per_sha256_circuit: self.sample_opt(|| rng.gen()),
+            cycles_per_secp256r1_verify_circuit: self.sample_opt(|| rng.gen()),
+            cycles_per_transient_storage_sorter: self.sample_opt(|| rng.gen()),
+            max_base_layer_circuits: self.sample_opt(|| rng.gen()),
         }
     }
 }
@@ -703,6 +832,9 @@ impl Distribution<configs::secrets::L1Secrets> for EncodeDist {
         L1Secrets {
             l1_rpc_url: format!("localhost:{}", rng.gen::<u16>()).parse().unwrap(),
             gateway_rpc_url: Some(format!("localhost:{}", rng.gen::<u16>()).parse().unwrap()),
+            fee_source_rpc_urls: (0..rng.gen_range(0..3))
+                .map(|_| format!("localhost:{}", rng.gen::<u16>()).parse().unwrap())
+                .collect(),
         }
     }
 }
@@ -727,6 +859,7 @@ impl Distribution<configs::secrets::Secrets> for EncodeDist {
             l1: self.sample_opt(|| self.sample(rng)),
             data_availability: self.sample_opt(|| self.sample(rng)),
             contract_verifier: self.sample_opt(|| self.sample(rng)),
+            admin_api: self.sample_opt(|| self.sample(rng)),
         }
     }
 }
@@ -756,6 +889,7 @@ impl Distribution<configs::wallets::EthSender> for EncodeDist {
         configs::wallets::EthSender {
             operator: self.sample(rng),
             blob_operator: self.sample_opt(|| self.sample(rng)),
+            additional_operators: self.sample_collect(rng),
         }
     }
 }
@@ -768,12 +902,21 @@ impl Distribution<configs::wallets::TokenMultiplierSetter> for EncodeDist {
     }
 }
 
+impl Distribution<configs::wallets::SoftConfirmationSigner> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::wallets::SoftConfirmationSigner {
+        configs::wallets::SoftConfirmationSigner {
+            wallet: self.sample(rng),
+        }
+    }
+}
+
 impl Distribution<configs::wallets::Wallets> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::wallets::Wallets {
         configs::wallets::Wallets {
             state_keeper: self.sample_opt(|| self.sample(rng)),
             eth_sender: self.sample_opt(|| self.sample(rng)),
             token_multiplier_setter: self.sample_opt(|| self.sample(rng)),
+            soft_confirmation_signer: self.sample_opt(|| self.sample(rng)),
         }
     }
 }
@@ -791,6 +934,12 @@ impl Distribution<configs::en_config::ENConfig> for EncodeDist {
             main_node_rate_limit_rps: self.sample_opt(|| rng.gen()),
             bridge_addresses_refresh_interval_sec: self.sample_opt(|| rng.gen()),
             gateway_chain_id: self.sample_opt(|| SLChainId(rng.gen())),
+            cross_verification_enabled: rng.gen(),
+            max_auto_rollback_l1_batches: self.sample_opt(|| rng.gen()),
+            response_checker_enabled: rng.gen(),
+            response_checker_peer_url: self
+                .sample_opt(|| format!("localhost:{}", rng.gen::<u16>()).parse().unwrap()),
+            response_checker_stride: self.sample_opt(|| rng.gen()),
         }
     }
 }
@@ -840,6 +989,8 @@ impl Distribution<configs::vm_runner::ProtectiveReadsWriterConfig> for EncodeDis
             db_path: self.sample(rng),
             window_size: self.sample(rng),
             first_processed_batch: L1BatchNumber(rng.gen()),
+            fast_vm_mode: gen_fast_vm_mode(rng),
+            fail_on_divergence: self.sample(rng),
         }
     }
 }
@@ -873,6 +1024,7 @@ impl Distribution<configs::snapshot_recovery::TreeRecoveryConfig> for EncodeDist
         configs::snapshot_recovery::TreeRecoveryConfig {
             chunk_size: self.sample(rng),
             parallel_persistence_buffer: self.sample_opt(|| rng.gen()),
+            parallel_extension: self.sample(rng),
         }
     }
 }
@@ -899,6 +1051,8 @@ impl Distribution<configs::snapshot_recovery::SnapshotRecoveryConfig> for Encode
             enabled: self.sample(rng),
             l1_batch: self.sample_opt(|| L1BatchNumber(rng.gen())),
             drop_storage_key_preimages: (tree != TreeRecoveryConfig::default()) && self.sample(rng),
+            apply_differential_snapshots: (tree != TreeRecoveryConfig::default())
+                && self.sample(rng),
             tree,
             postgres: self.sample(rng),
             object_store: self.sample(rng),
@@ -913,6 +1067,11 @@ impl Distribution<configs::pruning::PruningConfig> for EncodeDist {
             chunk_size: self.sample(rng),
             removal_delay_sec: self.sample_opt(|| rng.gen()),
             data_retention_sec: self.sample(rng),
+            call_traces_retention_sec: self.sample(rng),
+            events_retention_sec: self.sample(rng),
+            dry_run: self.sample(rng),
+            cold_storage_archive_after_sec: self.sample(rng),
+            cold_storage_object_store: self.sample(rng),
         }
     }
 }
@@ -936,6 +1095,9 @@ impl Distribution<configs::base_token_adjuster::BaseTokenAdjusterConfig> for Enc
             price_fetching_max_attempts: self.sample(rng),
             price_fetching_sleep_ms: self.sample(rng),
             halt_on_error: self.sample(rng),
+            base_token_decimals: rng.gen_range(
+                0..=configs::base_token_adjuster::BaseTokenAdjusterConfig::MAX_BASE_TOKEN_DECIMALS,
+            ),
         }
     }
 }
@@ -1034,6 +1196,8 @@ impl Distribution<configs::GeneralConfig> for EncodeDist {
             experimental_vm_config: self.sample(rng),
             prover_job_monitor_config: self.sample(rng),
             timestamp_asserter_config: self.sample(rng),
+            tx_policy_config: self.sample(rng),
+            batch_exporter_config: self.sample(rng),
         }
     }
 }
@@ -1046,6 +1210,47 @@ impl Distribution<TimestampAsserterConfig> for EncodeDist {
     }
 }
 
+impl Distribution<configs::TxPolicyConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::TxPolicyConfig {
+        configs::TxPolicyConfig {
+            allowed_initiators: self.sample_range(rng).map(|_| rng.gen()).collect(),
+            denied_addresses: self.sample_range(rng).map(|_| rng.gen()).collect(),
+            allowed_deployers: self
+                .sample_opt(|| self.sample_range(rng).map(|_| rng.gen()).collect()),
+            max_txs_per_address_per_minute: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::secrets::AdminApiSecrets> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::secrets::AdminApiSecrets {
+        configs::secrets::AdminApiSecrets {
+            auth_token: <APIKey as From<String>>::from(self.sample(rng)),
+        }
+    }
+}
+
+impl Distribution<configs::batch_exporter::BatchExportFormat> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::batch_exporter::BatchExportFormat {
+        type T = configs::batch_exporter::BatchExportFormat;
+        match rng.gen_range(0..2) {
+            0 => T::Json,
+            _ => T::Parquet,
+        }
+    }
+}
+
+impl Distribution<configs::BatchExporterConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::BatchExporterConfig {
+        configs::BatchExporterConfig {
+            output_path: self.sample(rng),
+            format: self.sample(rng),
+            poll_interval_ms: self.sample(rng),
+            max_batches_per_iteration: self.sample(rng),
+        }
+    }
+}
+
 impl Distribution<configs::secrets::ContractVerifierSecrets> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::secrets::ContractVerifierSecrets {
         configs::secrets::ContractVerifierSecrets {
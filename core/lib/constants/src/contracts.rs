@@ -134,6 +134,20 @@ pub const SECP256R1_VERIFY_PRECOMPILE_ADDRESS: Address = H160([
     0x00, 0x00, 0x01, 0x00,
 ]);
 
+/// Precompiles together with their names used in API responses (e.g., in per-precompile usage reported by fee estimates).
+/// Precompiles introduced in new protocol versions must be added here so that their usage is reported.
+pub const PRECOMPILES: &[(&str, Address)] = &[
+    ("ecrecover", ECRECOVER_PRECOMPILE_ADDRESS),
+    ("sha256", SHA256_PRECOMPILE_ADDRESS),
+    ("identity", IDENTITY_ADDRESS),
+    ("modexp", MODEXP_PRECOMPILE_ADDRESS),
+    ("ecAdd", EC_ADD_PRECOMPILE_ADDRESS),
+    ("ecMul", EC_MUL_PRECOMPILE_ADDRESS),
+    ("ecPairing", EC_PAIRING_PRECOMPILE_ADDRESS),
+    ("p256Verify", SECP256R1_VERIFY_PRECOMPILE_ADDRESS),
+    ("keccak256", KECCAK256_PRECOMPILE_ADDRESS),
+];
+
 pub const CODE_ORACLE_ADDRESS: Address = H160([
     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
     0x00, 0x00, 0x80, 0x12,
@@ -24,6 +24,7 @@ impl VyperInput {
 
         let sources = match req.source_code_data {
             SourceCodeData::VyperMultiFile(s) => s,
+            SourceCodeData::VyperSingleFile(source) => HashMap::from([(file_name.clone(), source)]),
             other => unreachable!("unexpected `SourceCodeData` variant: {other:?}"),
         };
         Ok(Self {
@@ -112,3 +113,36 @@ impl Compiler<VyperInput> for Vyper {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{contract_verification::api::CompilerVersions, Address};
+
+    use super::*;
+
+    #[test]
+    fn creating_input_from_single_file() {
+        let req = VerificationIncomingRequest {
+            contract_address: Address::repeat_byte(1),
+            source_code_data: SourceCodeData::VyperSingleFile("# @version 0.3.10".to_owned()),
+            contract_name: "Counter".to_owned(),
+            compiler_versions: CompilerVersions::Vyper {
+                compiler_zkvyper_version: None,
+                compiler_vyper_version: "0.3.10".to_owned(),
+            },
+            optimization_used: true,
+            optimizer_mode: None,
+            constructor_arguments: Default::default(),
+            is_system: false,
+            force_evmla: false,
+            evm_specific: Default::default(),
+        };
+        let input = VyperInput::new(req).unwrap();
+        assert_eq!(input.contract_name, "Counter");
+        assert_eq!(input.file_name, "Counter.vy");
+        assert_eq!(
+            input.sources,
+            HashMap::from([("Counter.vy".to_owned(), "# @version 0.3.10".to_owned())])
+        );
+    }
+}
@@ -454,7 +454,12 @@ async fn assert_request_success(
         .expect("no status");
     assert_eq!(status.error, None);
     assert_eq!(status.compilation_errors, None);
-    assert_eq!(status.status, "successful");
+    let expected_status = if verification_problems.is_empty() {
+        "successful"
+    } else {
+        "partial_match"
+    };
+    assert_eq!(status.status, expected_status);
 
     let verification_info = storage
         .contract_verification_dal()
@@ -0,0 +1,212 @@
+//! Diffing of base system contracts, e.g. for reviewing protocol upgrades.
+
+use std::{collections::BTreeSet, fmt};
+
+use zksync_basic_types::{
+    bytecode::{validate_bytecode, BytecodeHash},
+    H256,
+};
+
+use crate::{BaseSystemContracts, SystemContractCode};
+
+/// Size of a bytecode word in bytes. EraVM bytecodes always consist of whole 32-byte words.
+const WORD_SIZE: usize = 32;
+
+/// Summary of a single system contract bytecode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SystemContractSummary {
+    /// Hash declared for the contract.
+    pub hash: H256,
+    /// Hash computed from the contract bytecode. `None` if the bytecode is malformed (e.g., has an even number of words).
+    pub computed_hash: Option<H256>,
+    /// Length of the bytecode in bytes.
+    pub len: usize,
+}
+
+impl SystemContractSummary {
+    fn new(contract: &SystemContractCode) -> Self {
+        Self {
+            hash: contract.hash,
+            computed_hash: validate_bytecode(&contract.code)
+                .ok()
+                .map(|()| BytecodeHash::for_bytecode(&contract.code).value()),
+            len: contract.code.len(),
+        }
+    }
+
+    /// Checks whether the declared hash matches the bytecode.
+    pub fn is_hash_valid(&self) -> bool {
+        self.computed_hash == Some(self.hash)
+    }
+}
+
+/// Difference between two versions of a single base system contract.
+#[derive(Debug, Clone)]
+pub struct SystemContractDiff {
+    /// Human-readable contract name, e.g. `bootloader`.
+    pub name: &'static str,
+    pub old: Option<SystemContractSummary>,
+    pub new: Option<SystemContractSummary>,
+    /// Byte offset of the first difference between bytecodes. `None` if bytecodes are equal or one of them is missing.
+    pub first_difference: Option<usize>,
+    /// 32-byte words present in the new bytecode, but not in the old one. Since this comparison
+    /// doesn't depend on word positions, it captures changed embedded constants (addresses, selectors, hashes etc.)
+    /// even if the surrounding code has shifted.
+    pub added_words: BTreeSet<H256>,
+    /// 32-byte words present in the old bytecode, but not in the new one.
+    pub removed_words: BTreeSet<H256>,
+}
+
+impl SystemContractDiff {
+    fn new(
+        name: &'static str,
+        old: Option<&SystemContractCode>,
+        new: Option<&SystemContractCode>,
+    ) -> Self {
+        let old_words = old
+            .map(|contract| words(&contract.code))
+            .unwrap_or_default();
+        let new_words = new
+            .map(|contract| words(&contract.code))
+            .unwrap_or_default();
+        let first_difference = old.zip(new).and_then(|(old, new)| {
+            let position = old
+                .code
+                .iter()
+                .zip(&new.code)
+                .position(|(old_byte, new_byte)| old_byte != new_byte);
+            position.or_else(|| {
+                (old.code.len() != new.code.len()).then(|| old.code.len().min(new.code.len()))
+            })
+        });
+
+        Self {
+            name,
+            old: old.map(SystemContractSummary::new),
+            new: new.map(SystemContractSummary::new),
+            first_difference,
+            added_words: new_words.difference(&old_words).copied().collect(),
+            removed_words: old_words.difference(&new_words).copied().collect(),
+        }
+    }
+
+    /// Checks whether the contract has changed.
+    pub fn is_changed(&self) -> bool {
+        let old_hash = self.old.map(|summary| summary.hash);
+        let new_hash = self.new.map(|summary| summary.hash);
+        old_hash != new_hash || self.first_difference.is_some()
+    }
+}
+
+fn words(bytecode: &[u8]) -> BTreeSet<H256> {
+    bytecode
+        .chunks(WORD_SIZE)
+        .filter(|chunk| chunk.len() == WORD_SIZE)
+        .map(H256::from_slice)
+        .collect()
+}
+
+/// Difference between two sets of [`BaseSystemContracts`].
+#[derive(Debug, Clone)]
+pub struct BaseSystemContractsDiff {
+    /// Diffs for all base system contracts (including unchanged ones) in a fixed order:
+    /// bootloader, default account, EVM emulator.
+    pub contracts: Vec<SystemContractDiff>,
+}
+
+impl BaseSystemContractsDiff {
+    /// Computes the difference between `old` and `new` contracts.
+    pub fn new(old: &BaseSystemContracts, new: &BaseSystemContracts) -> Self {
+        Self {
+            contracts: vec![
+                SystemContractDiff::new("bootloader", Some(&old.bootloader), Some(&new.bootloader)),
+                SystemContractDiff::new("default_aa", Some(&old.default_aa), Some(&new.default_aa)),
+                SystemContractDiff::new(
+                    "evm_emulator",
+                    old.evm_emulator.as_ref(),
+                    new.evm_emulator.as_ref(),
+                ),
+            ],
+        }
+    }
+
+    /// Returns diffs for contracts that have changed.
+    pub fn changed(&self) -> impl Iterator<Item = &SystemContractDiff> + '_ {
+        self.contracts.iter().filter(|diff| diff.is_changed())
+    }
+
+    /// Checks whether any of the contracts has changed.
+    pub fn is_empty(&self) -> bool {
+        self.changed().next().is_none()
+    }
+
+    /// Returns names of the contracts (old or new) with declared hashes not matching their bytecodes.
+    pub fn invalid_hashes(&self) -> Vec<&'static str> {
+        self.contracts
+            .iter()
+            .filter(|diff| {
+                let mut summaries = diff.old.iter().chain(&diff.new);
+                summaries.any(|summary| !summary.is_hash_valid())
+            })
+            .map(|diff| diff.name)
+            .collect()
+    }
+
+    /// Returns new contract hashes that are not marked as known according to `is_known`, together with
+    /// the corresponding contract names. `is_known` should check the `KnownCodesStorage` system contract
+    /// (e.g., by reading the storage slot returned by `zksync_types::get_known_code_key()`); upgrades
+    /// referencing unknown bytecodes would fail on-chain.
+    pub fn unknown_new_hashes(
+        &self,
+        mut is_known: impl FnMut(H256) -> bool,
+    ) -> Vec<(&'static str, H256)> {
+        self.changed()
+            .filter_map(|diff| Some((diff.name, diff.new?.hash)))
+            .filter(|&(_, hash)| !is_known(hash))
+            .collect()
+    }
+}
+
+fn fmt_summary(summary: Option<&SystemContractSummary>) -> String {
+    match summary {
+        Some(summary) if summary.is_hash_valid() => {
+            format!("{:?} ({} bytes)", summary.hash, summary.len)
+        }
+        Some(summary) => match summary.computed_hash {
+            Some(computed_hash) => format!(
+                "{:?} ({} bytes; INVALID, computed hash: {computed_hash:?})",
+                summary.hash, summary.len
+            ),
+            None => format!(
+                "{:?} ({} bytes; INVALID, malformed bytecode)",
+                summary.hash, summary.len
+            ),
+        },
+        None => "(none)".to_owned(),
+    }
+}
+
+impl fmt::Display for BaseSystemContractsDiff {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for diff in &self.contracts {
+            if !diff.is_changed() {
+                writeln!(formatter, "{}: unchanged", diff.name)?;
+                continue;
+            }
+
+            writeln!(formatter, "{}:", diff.name)?;
+            writeln!(formatter, "  old: {}", fmt_summary(diff.old.as_ref()))?;
+            writeln!(formatter, "  new: {}", fmt_summary(diff.new.as_ref()))?;
+            if let Some(offset) = diff.first_difference {
+                writeln!(formatter, "  first difference at byte {offset:#x}")?;
+            }
+            for word in &diff.removed_words {
+                writeln!(formatter, "  - {word:?}")?;
+            }
+            for word in &diff.added_words {
+                writeln!(formatter, "  + {word:?}")?;
+            }
+        }
+        Ok(())
+    }
+}
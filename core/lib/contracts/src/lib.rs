@@ -19,6 +19,7 @@ use zksync_basic_types::{
 };
 use zksync_utils::env::Workspace;
 
+pub mod diff;
 mod serde_bytecode;
 #[cfg(test)]
 mod tests;
@@ -71,6 +72,7 @@ const L1_ASSET_ROUTER_FILE: (&str, &str) = (
     "bridge/asset-router",
     "L1AssetRouter.sol/L1AssetRouter.json",
 );
+const L1_NULLIFIER_FILE: (&str, &str) = ("bridge", "L1Nullifier.sol/L1Nullifier.json");
 const L2_WRAPPED_BASE_TOKEN_STORE: (&str, &str) = (
     "bridge",
     "L2WrappedBaseTokenStore.sol/L2WrappedBaseTokenStore.json",
@@ -203,6 +205,10 @@ pub fn l1_asset_router_contract() -> Contract {
     load_contract_for_both_compilers(L1_ASSET_ROUTER_FILE)
 }
 
+pub fn l1_nullifier_contract() -> Contract {
+    load_contract_for_both_compilers(L1_NULLIFIER_FILE)
+}
+
 pub fn wrapped_base_token_store_contract() -> Contract {
     load_contract_for_both_compilers(L2_WRAPPED_BASE_TOKEN_STORE)
 }
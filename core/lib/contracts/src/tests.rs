@@ -1,4 +1,6 @@
-use crate::BaseSystemContracts;
+use zksync_basic_types::{bytecode::BytecodeHash, H256};
+
+use crate::{diff::BaseSystemContractsDiff, BaseSystemContracts, SystemContractCode};
 
 #[test]
 fn loading_historic_estimation_base_contracts() {
@@ -47,3 +49,75 @@ fn loading_historic_playground_contracts() {
         assert!(!base_contracts.default_aa.code.is_empty());
     }
 }
+
+fn mock_contract_code(words: &[u8]) -> SystemContractCode {
+    let code: Vec<_> = words.iter().flat_map(|&byte| [byte; 32]).collect();
+    let hash = BytecodeHash::for_bytecode(&code).value();
+    SystemContractCode { code, hash }
+}
+
+#[test]
+fn diffing_base_system_contracts() {
+    let old = BaseSystemContracts {
+        bootloader: mock_contract_code(&[1, 2, 3]),
+        default_aa: mock_contract_code(&[4]),
+        evm_emulator: None,
+    };
+    let diff = BaseSystemContractsDiff::new(&old, &old);
+    assert!(diff.is_empty(), "{diff:?}");
+    assert!(diff.invalid_hashes().is_empty());
+
+    let mut new = old.clone();
+    new.bootloader = mock_contract_code(&[1, 5, 3, 6, 8]);
+    new.evm_emulator = Some(mock_contract_code(&[7]));
+    let diff = BaseSystemContractsDiff::new(&old, &new);
+    let changed: Vec<_> = diff.changed().map(|diff| diff.name).collect();
+    assert_eq!(changed, ["bootloader", "evm_emulator"]);
+
+    let bootloader_diff = &diff.contracts[0];
+    assert_eq!(bootloader_diff.first_difference, Some(32));
+    assert_eq!(bootloader_diff.old.unwrap().len, 96);
+    assert_eq!(bootloader_diff.new.unwrap().len, 160);
+    assert_eq!(
+        bootloader_diff
+            .removed_words
+            .iter()
+            .copied()
+            .collect::<Vec<_>>(),
+        [H256::repeat_byte(2)]
+    );
+    assert_eq!(
+        bootloader_diff
+            .added_words
+            .iter()
+            .copied()
+            .collect::<Vec<_>>(),
+        [
+            H256::repeat_byte(5),
+            H256::repeat_byte(6),
+            H256::repeat_byte(8)
+        ]
+    );
+    let evm_emulator_diff = &diff.contracts[2];
+    assert!(evm_emulator_diff.old.is_none());
+    assert_eq!(evm_emulator_diff.first_difference, None);
+
+    let new_bootloader_hash = new.bootloader.hash;
+    let unknown = diff.unknown_new_hashes(|hash| hash == new_bootloader_hash);
+    assert_eq!(
+        unknown,
+        [("evm_emulator", new.evm_emulator.as_ref().unwrap().hash)]
+    );
+
+    let diff_str = diff.to_string();
+    assert!(diff_str.contains("default_aa: unchanged"), "{diff_str}");
+    assert!(
+        diff_str.contains("first difference at byte 0x20"),
+        "{diff_str}"
+    );
+
+    new.default_aa.hash = H256::zero();
+    let diff = BaseSystemContractsDiff::new(&old, &new);
+    assert_eq!(diff.invalid_hashes(), ["default_aa"]);
+    assert!(diff.to_string().contains("INVALID"));
+}
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS soft_confirmations
+(
+    tx_hash          BYTEA     PRIMARY KEY,
+    miniblock_number BIGINT    NOT NULL,
+    index_in_block   INT       NOT NULL,
+    signer           BYTEA     NOT NULL,
+    signature        BYTEA     NOT NULL,
+    created_at       TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS soft_confirmations_miniblock_number_idx ON soft_confirmations (miniblock_number);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS unsealed_batch_checkpoints;
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS unsealed_batch_checkpoints
+(
+    miniblock_number      BIGINT    PRIMARY KEY,
+    l1_batch_number       BIGINT    NOT NULL,
+    tx_count              INT       NOT NULL,
+    storage_logs_checksum BYTEA     NOT NULL,
+    created_at            TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS unsealed_batch_checkpoints_l1_batch_number_idx ON unsealed_batch_checkpoints (l1_batch_number);
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS tx_policy_decisions
+(
+    id                BIGSERIAL PRIMARY KEY,
+    tx_hash           BYTEA     NOT NULL,
+    initiator_address BYTEA     NOT NULL,
+    stage             TEXT      NOT NULL,
+    rule              TEXT      NOT NULL,
+    reason            TEXT      NOT NULL,
+    created_at        TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS tx_policy_decisions_initiator_address_idx ON tx_policy_decisions (initiator_address, id);
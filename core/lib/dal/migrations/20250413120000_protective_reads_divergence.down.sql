@@ -0,0 +1 @@
+ALTER TABLE vm_runner_protective_reads DROP COLUMN IF EXISTS divergence;
@@ -0,0 +1 @@
+ALTER TABLE vm_runner_protective_reads ADD COLUMN IF NOT EXISTS divergence TEXT;
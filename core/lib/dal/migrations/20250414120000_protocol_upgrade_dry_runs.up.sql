@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS protocol_upgrade_dry_runs
+(
+    protocol_version      INT       NOT NULL PRIMARY KEY REFERENCES protocol_versions (id) ON DELETE CASCADE,
+    upgrade_tx_hash       BYTEA     NOT NULL,
+    base_l1_batch_number  BIGINT    NOT NULL,
+    error                 TEXT,
+    system_contract_diffs JSONB     NOT NULL,
+    created_at            TIMESTAMP NOT NULL
+);
@@ -0,0 +1,3 @@
+-- no-transaction
+CREATE INDEX CONCURRENTLY IF NOT EXISTS transactions_initiator_address_miniblock_number_idx
+    ON transactions (initiator_address, miniblock_number) WHERE miniblock_number IS NOT NULL;
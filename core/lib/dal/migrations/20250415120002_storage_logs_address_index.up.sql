@@ -0,0 +1,3 @@
+-- no-transaction
+CREATE INDEX CONCURRENTLY IF NOT EXISTS storage_logs_address_miniblock_number_idx
+    ON storage_logs (address, miniblock_number);
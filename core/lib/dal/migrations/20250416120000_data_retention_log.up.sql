@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS data_retention_log (
+    kind TEXT PRIMARY KEY,
+    last_pruned_l1_batch BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
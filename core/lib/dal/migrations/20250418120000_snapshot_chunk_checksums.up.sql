@@ -0,0 +1,2 @@
+ALTER TABLE snapshots
+    ADD COLUMN storage_logs_checksums BYTEA[];
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS cold_storage_archives (
+    kind TEXT NOT NULL,
+    l1_batch_number BIGINT NOT NULL,
+    object_key TEXT NOT NULL,
+    archived_count BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (kind, l1_batch_number)
+);
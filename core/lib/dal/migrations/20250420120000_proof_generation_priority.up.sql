@@ -0,0 +1 @@
+ALTER TABLE proof_generation_details ADD COLUMN IF NOT EXISTS priority INT NOT NULL DEFAULT 0;
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS transaction_inclusion_stats (
+    tx_class TEXT NOT NULL,
+    stage TEXT NOT NULL,
+    sample_count BIGINT NOT NULL,
+    p50_ms BIGINT NOT NULL,
+    p90_ms BIGINT NOT NULL,
+    p99_ms BIGINT NOT NULL,
+    window_start TIMESTAMP NOT NULL,
+    computed_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (tx_class, stage)
+);
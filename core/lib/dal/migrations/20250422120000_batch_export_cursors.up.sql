@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS batch_export_cursors (
+    sink TEXT PRIMARY KEY,
+    last_exported_l1_batch BIGINT NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
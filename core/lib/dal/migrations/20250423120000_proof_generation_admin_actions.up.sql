@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS proof_generation_admin_actions (
+    id BIGSERIAL PRIMARY KEY,
+    l1_batch_number BIGINT NOT NULL REFERENCES l1_batches (number) ON DELETE CASCADE,
+    action TEXT NOT NULL,
+    previous_status TEXT NOT NULL,
+    reason TEXT,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_proof_generation_admin_actions_l1_batch_number
+    ON proof_generation_admin_actions (l1_batch_number);
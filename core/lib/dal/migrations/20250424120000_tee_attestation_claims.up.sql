@@ -0,0 +1,3 @@
+ALTER TABLE tee_attestations
+    ADD COLUMN IF NOT EXISTS tee_type TEXT,
+    ADD COLUMN IF NOT EXISTS claims JSONB;
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS proof_sampling_decisions (
+    l1_batch_number BIGINT PRIMARY KEY REFERENCES l1_batches (number) ON DELETE CASCADE,
+    sampled BOOLEAN NOT NULL,
+    reason TEXT NOT NULL,
+    commitment BYTEA NOT NULL,
+    seed BYTEA NOT NULL,
+    sampling_rate DOUBLE PRECISION NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS sequencer_lease;
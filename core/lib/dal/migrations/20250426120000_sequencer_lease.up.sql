@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS sequencer_lease (
+    -- artificial primary key ensuring that the table contains at most 1 row.
+    fake_key BOOLEAN PRIMARY KEY CHECK (fake_key),
+    instance_id TEXT NOT NULL,
+    epoch BIGINT NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS operator_audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    action TEXT NOT NULL,
+    actor TEXT NOT NULL,
+    before_state JSONB,
+    after_state JSONB,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
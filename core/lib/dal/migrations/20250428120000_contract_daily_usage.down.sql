@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS contract_usage_aggregation;
+DROP TABLE IF EXISTS contract_daily_usage;
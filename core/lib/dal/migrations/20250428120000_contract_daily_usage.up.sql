@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS contract_daily_usage (
+    contract_address BYTEA NOT NULL,
+    day DATE NOT NULL,
+    tx_count BIGINT NOT NULL,
+    gas_used BIGINT NOT NULL,
+    pubdata_published BIGINT NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (day, contract_address)
+);
+
+-- Last L2 block aggregated into `contract_daily_usage`.
+CREATE TABLE IF NOT EXISTS contract_usage_aggregation (
+    -- artificial primary key ensuring that the table contains at most 1 row.
+    fake_key BOOLEAN PRIMARY KEY CHECK (fake_key),
+    last_l2_block BIGINT NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS l1_batches_protocol_version_number_idx ON l1_batches (protocol_version, number);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS l2_to_l1_message_tracking;
+DROP TABLE IF EXISTS l2_to_l1_message_statuses;
@@ -0,0 +1,23 @@
+-- L2-to-L1 messages sent by the bridge contracts in L1 batches executed on L1, together with their consumption status on L1.
+CREATE TABLE IF NOT EXISTS l2_to_l1_message_statuses (
+    l1_batch_number BIGINT NOT NULL REFERENCES l1_batches (number) ON DELETE CASCADE,
+    -- Index of the message log in the L2-to-L1 logs Merkle tree of the L1 batch.
+    index_in_batch INT NOT NULL,
+    -- L1 block at which the message was observed to be consumed; NULL if the message isn't consumed yet.
+    consumed_at_l1_block BIGINT,
+    checked_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (l1_batch_number, index_in_batch)
+);
+
+CREATE INDEX IF NOT EXISTS l2_to_l1_message_statuses_unconsumed_idx
+    ON l2_to_l1_message_statuses (checked_at NULLS FIRST, l1_batch_number, index_in_batch)
+    WHERE consumed_at_l1_block IS NULL;
+
+-- Last L1 batch which messages were added to `l2_to_l1_message_statuses`.
+CREATE TABLE IF NOT EXISTS l2_to_l1_message_tracking (
+    -- artificial primary key ensuring that the table contains at most 1 row.
+    fake_key BOOLEAN PRIMARY KEY CHECK (fake_key),
+    last_l1_batch BIGINT NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1,2 @@
+ALTER TABLE proof_generation_details DROP COLUMN IF EXISTS proving_time_ms;
+ALTER TABLE proof_generation_details DROP COLUMN IF EXISTS base_layer_circuits;
@@ -0,0 +1,2 @@
+ALTER TABLE proof_generation_details ADD COLUMN IF NOT EXISTS base_layer_circuits INT;
+ALTER TABLE proof_generation_details ADD COLUMN IF NOT EXISTS proving_time_ms BIGINT;
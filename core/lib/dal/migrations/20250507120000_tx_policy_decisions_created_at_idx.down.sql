@@ -0,0 +1 @@
+DROP INDEX IF EXISTS tx_policy_decisions_created_at_idx;
@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS tx_policy_decisions_created_at_idx ON tx_policy_decisions (created_at);
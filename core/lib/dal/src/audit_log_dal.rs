@@ -0,0 +1,147 @@
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::api;
+
+use crate::Core;
+
+/// DAL for the audit log of privileged actions performed by node operators (admin RPC calls,
+/// L1 batch reverts, config reloads etc.).
+#[derive(Debug)]
+pub struct AuditLogDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl AuditLogDal<'_, '_> {
+    /// Records an action together with the affected state before and after it. Returns the ID of the inserted entry.
+    pub async fn insert_entry(
+        &mut self,
+        action: &str,
+        actor: &str,
+        before: Option<&serde_json::Value>,
+        after: Option<&serde_json::Value>,
+    ) -> DalResult<u64> {
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO
+            operator_audit_log (action, actor, before_state, after_state, created_at)
+            VALUES
+            ($1, $2, $3, $4, NOW())
+            RETURNING
+            id
+            "#,
+            action,
+            actor,
+            before,
+            after
+        )
+        .instrument("insert_audit_log_entry")
+        .with_arg("action", &action)
+        .with_arg("actor", &actor)
+        .fetch_one(self.storage)
+        .await?;
+        Ok(id as u64)
+    }
+
+    /// Returns up to `limit` entries with IDs starting from `from_id`, in the order they were recorded.
+    pub async fn get_entries(
+        &mut self,
+        from_id: u64,
+        limit: usize,
+    ) -> DalResult<Vec<api::AuditLogEntry>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                id,
+                action,
+                actor,
+                before_state,
+                after_state,
+                created_at
+            FROM
+                operator_audit_log
+            WHERE
+                id >= $1
+            ORDER BY
+                id
+            LIMIT
+                $2
+            "#,
+            from_id as i64,
+            limit as i64
+        )
+        .instrument("get_audit_log_entries")
+        .with_arg("from_id", &from_id)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| api::AuditLogEntry {
+                id: row.id as u64,
+                action: row.action,
+                actor: row.actor,
+                before: row.before_state,
+                after: row.after_state,
+                created_at: row.created_at.and_utc(),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn recording_and_exporting_audit_log() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        assert!(conn
+            .audit_log_dal()
+            .get_entries(0, 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let first_id = conn
+            .audit_log_dal()
+            .insert_entry(
+                "admin_pauseTxIntake",
+                "alice",
+                Some(&json!({ "txIntakePaused": false })),
+                Some(&json!({ "txIntakePaused": true })),
+            )
+            .await
+            .unwrap();
+        let second_id = conn
+            .audit_log_dal()
+            .insert_entry("admin_sealL1Batch", "bob", None, None)
+            .await
+            .unwrap();
+        assert!(second_id > first_id);
+
+        let entries = conn.audit_log_dal().get_entries(0, 10).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, first_id);
+        assert_eq!(entries[0].action, "admin_pauseTxIntake");
+        assert_eq!(entries[0].actor, "alice");
+        assert_eq!(entries[0].before, Some(json!({ "txIntakePaused": false })));
+        assert_eq!(entries[0].after, Some(json!({ "txIntakePaused": true })));
+        assert_eq!(entries[1].actor, "bob");
+        assert_eq!(entries[1].before, None);
+
+        let entries = conn
+            .audit_log_dal()
+            .get_entries(second_id, 10)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, second_id);
+        let entries = conn.audit_log_dal().get_entries(0, 1).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, first_id);
+    }
+}
@@ -0,0 +1,180 @@
+use std::ops;
+
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{
+    batch_export::{ExportedEvent, ExportedTransaction},
+    Address, L1BatchNumber, L2BlockNumber, H256, U256,
+};
+
+use crate::{models::bigdecimal_to_u256, Core};
+
+/// DAL for exporting artifacts of sealed L1 batches to external systems.
+///
+/// Export progress is tracked per sink in the `batch_export_cursors` table.
+#[derive(Debug)]
+pub struct BatchExportDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl BatchExportDal<'_, '_> {
+    /// Returns the last L1 batch exported to the specified sink, or `None` if nothing was exported yet.
+    pub async fn get_cursor(&mut self, sink: &str) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                last_exported_l1_batch
+            FROM
+                batch_export_cursors
+            WHERE
+                sink = $1
+            "#,
+            sink
+        )
+        .instrument("get_cursor")
+        .with_arg("sink", &sink)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| L1BatchNumber(row.last_exported_l1_batch as u32)))
+    }
+
+    pub async fn set_cursor(
+        &mut self,
+        sink: &str,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            batch_export_cursors (sink, last_exported_l1_batch, updated_at)
+            VALUES
+            ($1, $2, NOW())
+            ON CONFLICT (sink) DO
+            UPDATE
+            SET
+            last_exported_l1_batch = excluded.last_exported_l1_batch,
+            updated_at = NOW()
+            "#,
+            sink,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("set_cursor")
+        .with_arg("sink", &sink)
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns execution results of all transactions in the specified L1 batch.
+    pub async fn get_transactions_for_export(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<ExportedTransaction>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                hash,
+                miniblock_number,
+                index_in_block,
+                is_priority,
+                initiator_address,
+                contract_address,
+                nonce,
+                error,
+                gas_limit,
+                refunded_gas,
+                effective_gas_price
+            FROM
+                transactions
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                miniblock_number,
+                index_in_block
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_transactions_for_export")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let gas_limit = row.gas_limit.map(bigdecimal_to_u256).unwrap_or_default();
+                let refunded_gas = U256::from(row.refunded_gas as u64);
+                ExportedTransaction {
+                    hash: H256::from_slice(&row.hash),
+                    l2_block_number: L2BlockNumber(row.miniblock_number.unwrap_or(0) as u32),
+                    index_in_l2_block: row.index_in_block.unwrap_or(0) as u32,
+                    is_priority: row.is_priority,
+                    initiator_address: Address::from_slice(&row.initiator_address),
+                    to: row.contract_address.as_deref().map(Address::from_slice),
+                    nonce: row.nonce.map(|nonce| nonce as u64),
+                    error: row.error,
+                    gas_limit,
+                    gas_used: gas_limit.saturating_sub(refunded_gas),
+                    effective_gas_price: row
+                        .effective_gas_price
+                        .map(bigdecimal_to_u256)
+                        .unwrap_or_default(),
+                }
+            })
+            .collect())
+    }
+
+    /// Returns all events emitted in the specified range of L2 blocks.
+    pub async fn get_events_for_export(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<ExportedEvent>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                tx_hash,
+                miniblock_number,
+                event_index_in_block,
+                address,
+                topic1,
+                topic2,
+                topic3,
+                topic4,
+                value
+            FROM
+                events
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            ORDER BY
+                miniblock_number,
+                event_index_in_block
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0)
+        )
+        .instrument("get_events_for_export")
+        .with_arg("l2_blocks", &l2_blocks)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let topics = [row.topic1, row.topic2, row.topic3, row.topic4]
+                    .into_iter()
+                    .filter(|topic| !topic.is_empty())
+                    .map(|topic| H256::from_slice(&topic))
+                    .collect();
+                ExportedEvent {
+                    tx_hash: H256::from_slice(&row.tx_hash),
+                    l2_block_number: L2BlockNumber(row.miniblock_number as u32),
+                    index_in_l2_block: row.event_index_in_block as u32,
+                    address: Address::from_slice(&row.address),
+                    topics,
+                    data: row.value,
+                }
+            })
+            .collect())
+    }
+}
@@ -20,6 +20,7 @@ use zksync_types::{
         StorageOracleInfo, UnsealedL1BatchHeader,
     },
     commitment::{L1BatchCommitmentArtifacts, L1BatchWithMetadata},
+    fee_model::L1BatchUtilization,
     l2_to_l1_log::{BatchAndChainMerklePath, UserL2ToL1Log},
     writes::TreeWrite,
     Address, Bloom, L1BatchNumber, L2BlockNumber, ProtocolVersionId, SLChainId, H256, U256,
@@ -156,6 +157,52 @@ impl BlocksDal<'_, '_> {
         Ok(Some(header.into()))
     }
 
+    /// Returns resource utilization for the specified number of latest sealed L1 batches, in the ascending order
+    /// of batch numbers.
+    pub async fn get_latest_l1_batches_utilization(
+        &mut self,
+        limit: usize,
+    ) -> DalResult<Vec<L1BatchUtilization>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batches.number,
+                COALESCE(LENGTH(l1_batches.pubdata_input), 0)::BIGINT AS "pubdata_size!",
+                (
+                    SELECT
+                        COALESCE(SUM(transactions.gas_limit - transactions.refunded_gas), 0)::BIGINT
+                    FROM
+                        transactions
+                    WHERE
+                        transactions.l1_batch_number = l1_batches.number
+                ) AS "gas_used!"
+            FROM
+                l1_batches
+            WHERE
+                is_sealed
+            ORDER BY
+                number DESC
+            LIMIT
+                $1
+            "#,
+            limit as i64
+        )
+        .instrument("get_latest_l1_batches_utilization")
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .rev()
+            .map(|row| L1BatchUtilization {
+                l1_batch_number: L1BatchNumber(row.number as u32),
+                gas_used: row.gas_used as u64,
+                pubdata_size: row.pubdata_size as u64,
+            })
+            .collect())
+    }
+
     pub async fn get_sealed_l2_block_number(&mut self) -> DalResult<Option<L2BlockNumber>> {
         let row = sqlx::query!(
             r#"
@@ -258,6 +305,28 @@ impl BlocksDal<'_, '_> {
         Ok(row.map(|row| L1BatchNumber(row.number as u32)))
     }
 
+    /// Checks whether the commitment generator has processed the specified L1 batch. Returns `false`
+    /// if the batch is not present in the storage.
+    pub async fn has_l1_batch_commitment(&mut self, number: L1BatchNumber) -> DalResult<bool> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                commitment IS NOT NULL AS "has_commitment!"
+            FROM
+                l1_batches
+            WHERE
+                number = $1
+            "#,
+            i64::from(number.0)
+        )
+        .instrument("has_l1_batch_commitment")
+        .with_arg("number", &number)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.is_some_and(|row| row.has_commitment))
+    }
+
     /// Gets a number of the last L1 batch that is ready for commitment generation (i.e., doesn't have commitment
     /// yet, and has tree data).
     pub async fn get_last_l1_batch_ready_for_commitment_generation(
@@ -2979,6 +3048,55 @@ impl BlocksDal<'_, '_> {
 
         Ok(())
     }
+
+    /// Computes the logs bloom for an L1 batch by combining blooms of its L2 blocks and persists it.
+    /// If any of the L2 blocks doesn't have a bloom, the batch bloom is not saved. Should be called after
+    /// L2 blocks are assigned to the batch.
+    pub async fn save_l1_batch_logs_bloom(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<Bloom>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                logs_bloom
+            FROM
+                miniblocks
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("save_l1_batch_logs_bloom#get_l2_block_blooms")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        let mut bloom = Bloom::zero();
+        for row in rows {
+            let Some(l2_block_bloom) = row.logs_bloom else {
+                return Ok(None);
+            };
+            bloom.accrue_bloom(&Bloom::from_slice(&l2_block_bloom));
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE l1_batches
+            SET
+                logs_bloom = $2
+            WHERE
+                number = $1
+            "#,
+            i64::from(l1_batch_number.0),
+            bloom.as_bytes()
+        )
+        .instrument("save_l1_batch_logs_bloom")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(Some(bloom))
+    }
 }
 
 /// These methods should only be used for tests.
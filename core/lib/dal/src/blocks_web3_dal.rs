@@ -5,6 +5,7 @@ use zksync_db_connection::{
 use zksync_system_constants::EMPTY_UNCLES_HASH;
 use zksync_types::{
     api,
+    cold_storage::CallTracesArchive,
     debug_flat_call::CallTraceMeta,
     fee_model::BatchFeeInput,
     l2_to_l1_log::L2ToL1Log,
@@ -20,7 +21,7 @@ use crate::{
             ResolvedL1BatchForL2Block, StorageBlockDetails, StorageL1BatchDetails,
             LEGACY_BLOCK_GAS_LIMIT,
         },
-        storage_transaction::CallTrace,
+        storage_transaction::{parse_call_trace, CallTrace},
     },
     Core, CoreDal,
 };
@@ -527,10 +528,29 @@ impl BlocksWeb3Dal<'_, '_> {
         Ok(result)
     }
 
-    /// Returns call traces for all transactions in the specified L2 block in the order of their execution.
+    /// Returns call traces for all transactions in the specified L2 block. Traces moved to cold storage are skipped;
+    /// use [`Self::get_archived_traces_for_l2_block()`] to get them.
     pub async fn get_traces_for_l2_block(
         &mut self,
         block_number: L2BlockNumber,
+    ) -> DalResult<Vec<(Call, CallTraceMeta)>> {
+        self.get_traces_for_l2_block_inner(block_number, None).await
+    }
+
+    /// Same as [`Self::get_traces_for_l2_block()`], but takes serialized traces from the provided cold storage archive.
+    pub async fn get_archived_traces_for_l2_block(
+        &mut self,
+        block_number: L2BlockNumber,
+        archive: &CallTracesArchive,
+    ) -> DalResult<Vec<(Call, CallTraceMeta)>> {
+        self.get_traces_for_l2_block_inner(block_number, Some(archive))
+            .await
+    }
+
+    async fn get_traces_for_l2_block_inner(
+        &mut self,
+        block_number: L2BlockNumber,
+        archive: Option<&CallTracesArchive>,
     ) -> DalResult<Vec<(Call, CallTraceMeta)>> {
         let row = sqlx::query!(
             r#"
@@ -584,7 +604,7 @@ impl BlocksWeb3Dal<'_, '_> {
         .fetch_all(self.storage)
         .await?
         .into_iter()
-        .map(|mut call_trace| {
+        .filter_map(|mut call_trace| {
             let tx_hash = H256::from_slice(&call_trace.tx_hash);
             let index = call_trace.tx_index_in_block.unwrap_or_default() as usize;
             let meta = CallTraceMeta {
@@ -594,7 +614,13 @@ impl BlocksWeb3Dal<'_, '_> {
                 block_hash,
                 internal_error: call_trace.tx_error.take(),
             };
-            (call_trace.into_call(protocol_version), meta)
+            let call = match archive {
+                Some(archive) => parse_call_trace(archive.get(tx_hash)?, protocol_version),
+                // Empty traces are stubs for traces moved to cold storage.
+                None if call_trace.call_trace.is_empty() => return None,
+                None => call_trace.into_call(protocol_version),
+            };
+            Some((call, meta))
         })
         .collect())
     }
@@ -784,7 +810,8 @@ impl BlocksWeb3Dal<'_, '_> {
                 mb.fair_pubdata_price,
                 l1_batches.bootloader_code_hash,
                 l1_batches.default_aa_code_hash,
-                l1_batches.evm_emulator_code_hash
+                l1_batches.evm_emulator_code_hash,
+                l1_batches.logs_bloom
             FROM
                 l1_batches
             INNER JOIN mb ON TRUE
@@ -0,0 +1,508 @@
+use std::ops;
+
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{
+    cold_storage::{ArchivedCallTrace, ArchivedEvent, ColdStorageDataKind},
+    L1BatchNumber, L2BlockNumber, H256,
+};
+
+use crate::Core;
+
+/// DAL for call traces and events moved to cold storage.
+///
+/// Archived Postgres rows are retained as stubs with cleared payload; see [`ColdStorageDataKind`] for details.
+/// Each archived L1 batch is recorded in the `cold_storage_archives` table together with the key of the object
+/// storing the payload.
+#[derive(Debug)]
+pub struct ColdStorageDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl ColdStorageDal<'_, '_> {
+    pub async fn get_last_archived_l1_batch(
+        &mut self,
+        kind: ColdStorageDataKind,
+    ) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(l1_batch_number) AS "l1_batch_number"
+            FROM
+                cold_storage_archives
+            WHERE
+                kind = $1
+            "#,
+            kind.as_str()
+        )
+        .instrument("get_last_archived_l1_batch")
+        .with_arg("kind", &kind)
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(row
+            .l1_batch_number
+            .map(|number| L1BatchNumber(number as u32)))
+    }
+
+    /// Returns call traces in the specified range of L2 blocks that are not archived yet.
+    pub async fn get_call_traces_for_archive(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<ArchivedCallTrace>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                call_traces.tx_hash,
+                call_traces.call_trace
+            FROM
+                call_traces
+            INNER JOIN transactions ON call_traces.tx_hash = transactions.hash
+            WHERE
+                transactions.miniblock_number BETWEEN $1 AND $2
+                AND call_traces.call_trace <> ''::BYTEA
+            ORDER BY
+                transactions.miniblock_number,
+                transactions.index_in_block
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0)
+        )
+        .instrument("get_call_traces_for_archive")
+        .with_arg("l2_blocks", &l2_blocks)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ArchivedCallTrace {
+                tx_hash: H256::from_slice(&row.tx_hash),
+                call_trace: row.call_trace,
+            })
+            .collect())
+    }
+
+    /// Returns event payloads in the specified range of L2 blocks that are not archived yet.
+    pub async fn get_events_for_archive(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<ArchivedEvent>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number,
+                event_index_in_block,
+                value
+            FROM
+                events
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+                AND value <> ''::BYTEA
+            ORDER BY
+                miniblock_number,
+                event_index_in_block
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0)
+        )
+        .instrument("get_events_for_archive")
+        .with_arg("l2_blocks", &l2_blocks)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ArchivedEvent {
+                l2_block_number: L2BlockNumber(row.miniblock_number as u32),
+                event_index_in_block: row.event_index_in_block as u32,
+                value: row.value,
+            })
+            .collect())
+    }
+
+    /// Clears the payload of archived data in the specified range of L2 blocks, retaining Postgres rows as stubs.
+    /// Does not record the archive; the caller is responsible to do this using [`Self::insert_archive()`].
+    pub async fn stub_archived_data(
+        &mut self,
+        kind: ColdStorageDataKind,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<u64> {
+        let execution_result = match kind {
+            ColdStorageDataKind::CallTraces => {
+                sqlx::query!(
+                    r#"
+                    UPDATE call_traces
+                    SET
+                        call_trace = ''::BYTEA
+                    WHERE
+                        tx_hash IN (
+                            SELECT
+                                hash
+                            FROM
+                                transactions
+                            WHERE
+                                miniblock_number BETWEEN $1 AND $2
+                        )
+                    "#,
+                    i64::from(l2_blocks.start().0),
+                    i64::from(l2_blocks.end().0)
+                )
+                .instrument("stub_archived_data#call_traces")
+                .with_arg("l2_blocks", &l2_blocks)
+                .report_latency()
+                .execute(self.storage)
+                .await?
+            }
+            ColdStorageDataKind::Events => {
+                sqlx::query!(
+                    r#"
+                    UPDATE events
+                    SET
+                        value = ''::BYTEA,
+                        updated_at = NOW()
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                    "#,
+                    i64::from(l2_blocks.start().0),
+                    i64::from(l2_blocks.end().0)
+                )
+                .instrument("stub_archived_data#events")
+                .with_arg("l2_blocks", &l2_blocks)
+                .report_latency()
+                .execute(self.storage)
+                .await?
+            }
+        };
+        Ok(execution_result.rows_affected())
+    }
+
+    pub async fn insert_archive(
+        &mut self,
+        kind: ColdStorageDataKind,
+        l1_batch_number: L1BatchNumber,
+        object_key: &str,
+        archived_count: usize,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            cold_storage_archives (kind, l1_batch_number, object_key, archived_count, created_at)
+            VALUES
+            ($1, $2, $3, $4, NOW())
+            "#,
+            kind.as_str(),
+            i64::from(l1_batch_number.0),
+            object_key,
+            archived_count as i64
+        )
+        .instrument("insert_archive")
+        .with_arg("kind", &kind)
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the number of the L1 batch containing the specified L2 block if data of the specified kind
+    /// was archived for this batch.
+    pub async fn get_archived_l1_batch_for_l2_block(
+        &mut self,
+        kind: ColdStorageDataKind,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                cold_storage_archives.l1_batch_number
+            FROM
+                cold_storage_archives
+            INNER JOIN
+                miniblocks
+                ON miniblocks.l1_batch_number = cold_storage_archives.l1_batch_number
+            WHERE
+                cold_storage_archives.kind = $1
+                AND miniblocks.number = $2
+            "#,
+            kind.as_str(),
+            i64::from(l2_block_number.0)
+        )
+        .instrument("get_archived_l1_batch_for_l2_block")
+        .with_arg("kind", &kind)
+        .with_arg("l2_block_number", &l2_block_number)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| L1BatchNumber(row.l1_batch_number as u32)))
+    }
+
+    /// Returns the number of the L1 batch containing the specified transaction if data of the specified kind
+    /// was archived for this batch.
+    pub async fn get_archived_l1_batch_for_transaction(
+        &mut self,
+        kind: ColdStorageDataKind,
+        tx_hash: H256,
+    ) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                cold_storage_archives.l1_batch_number
+            FROM
+                cold_storage_archives
+            INNER JOIN
+                transactions
+                ON transactions.l1_batch_number = cold_storage_archives.l1_batch_number
+            WHERE
+                cold_storage_archives.kind = $1
+                AND transactions.hash = $2
+            "#,
+            kind.as_str(),
+            tx_hash.as_bytes()
+        )
+        .instrument("get_archived_l1_batch_for_transaction")
+        .with_arg("kind", &kind)
+        .with_arg("tx_hash", &tx_hash)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| L1BatchNumber(row.l1_batch_number as u32)))
+    }
+
+    /// Filters the provided L1 batch numbers, leaving only batches for which data of the specified kind was archived.
+    pub async fn filter_archived_l1_batches(
+        &mut self,
+        kind: ColdStorageDataKind,
+        l1_batch_numbers: &[L1BatchNumber],
+    ) -> DalResult<Vec<L1BatchNumber>> {
+        let l1_batch_numbers: Vec<_> = l1_batch_numbers
+            .iter()
+            .map(|number| i64::from(number.0))
+            .collect();
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number
+            FROM
+                cold_storage_archives
+            WHERE
+                kind = $1
+                AND l1_batch_number = ANY($2)
+            ORDER BY
+                l1_batch_number
+            "#,
+            kind.as_str(),
+            &l1_batch_numbers
+        )
+        .instrument("filter_archived_l1_batches")
+        .with_arg("kind", &kind)
+        .with_arg("l1_batch_numbers.len", &l1_batch_numbers.len())
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L1BatchNumber(row.l1_batch_number as u32))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        cold_storage::CallTracesArchive, tx::IncludedTxLocation, Address, L2ChainId,
+        ProtocolVersion, ProtocolVersionId,
+    };
+    use zksync_vm_interface::{Call, TransactionExecutionResult};
+
+    use super::*;
+    use crate::{
+        models::storage_transaction::parse_call_trace,
+        tests::{
+            create_l1_batch_header, create_l2_block_header, mock_execution_result,
+            mock_l2_transaction, mock_vm_event,
+        },
+        ConnectionPool, CoreDal,
+    };
+
+    async fn prepare_storage(conn: &mut Connection<'_, Core>) -> (H256, Call) {
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(1))
+            .await
+            .unwrap();
+
+        let tx = mock_l2_transaction();
+        let tx_hash = tx.hash();
+        conn.transactions_dal()
+            .insert_transaction_l2(&tx, Default::default(), L2ChainId::default())
+            .await
+            .unwrap();
+        let call = Call {
+            from: Address::repeat_byte(1),
+            to: Address::repeat_byte(2),
+            gas: 100,
+            ..Call::default()
+        };
+        let tx_results = [TransactionExecutionResult {
+            call_traces: vec![call.clone()],
+            ..mock_execution_result(tx)
+        }];
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l2_block(
+                L2BlockNumber(1),
+                &tx_results,
+                1.into(),
+                ProtocolVersionId::latest(),
+                false,
+            )
+            .await
+            .unwrap();
+
+        let tx_location = IncludedTxLocation {
+            tx_hash,
+            tx_index_in_l2_block: 0,
+        };
+        let events = [mock_vm_event(0), mock_vm_event(1)];
+        conn.events_dal()
+            .save_events(L2BlockNumber(1), &[(tx_location, events.iter().collect())])
+            .await
+            .unwrap();
+
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch_header(1))
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .mark_l2_blocks_as_executed_in_l1_batch(L1BatchNumber(1))
+            .await
+            .unwrap();
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l1_batch(L1BatchNumber(1), &tx_results)
+            .await
+            .unwrap();
+        (tx_hash, call)
+    }
+
+    #[tokio::test]
+    async fn archiving_call_traces_and_events() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let (tx_hash, call) = prepare_storage(&mut conn).await;
+        let l2_blocks = L2BlockNumber(1)..=L2BlockNumber(1);
+
+        let mut dal = conn.cold_storage_dal();
+        assert_eq!(
+            dal.get_last_archived_l1_batch(ColdStorageDataKind::CallTraces)
+                .await
+                .unwrap(),
+            None
+        );
+        let call_traces = dal
+            .get_call_traces_for_archive(l2_blocks.clone())
+            .await
+            .unwrap();
+        assert_eq!(call_traces.len(), 1);
+        assert_eq!(call_traces[0].tx_hash, tx_hash);
+        assert_eq!(
+            parse_call_trace(&call_traces[0].call_trace, ProtocolVersionId::latest()),
+            call
+        );
+        let archive = CallTracesArchive {
+            l1_batch_number: L1BatchNumber(1),
+            call_traces,
+        };
+        let events = dal.get_events_for_archive(l2_blocks.clone()).await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].event_index_in_block, 1);
+
+        for kind in ColdStorageDataKind::ALL {
+            let stubbed_count = dal
+                .stub_archived_data(kind, l2_blocks.clone())
+                .await
+                .unwrap();
+            assert!(stubbed_count > 0, "{kind:?}");
+            dal.insert_archive(kind, L1BatchNumber(1), "archive", stubbed_count as usize)
+                .await
+                .unwrap();
+        }
+
+        assert!(dal
+            .get_call_traces_for_archive(l2_blocks.clone())
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(dal
+            .get_events_for_archive(l2_blocks)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            dal.get_last_archived_l1_batch(ColdStorageDataKind::Events)
+                .await
+                .unwrap(),
+            Some(L1BatchNumber(1))
+        );
+        assert_eq!(
+            dal.get_archived_l1_batch_for_l2_block(ColdStorageDataKind::Events, L2BlockNumber(1))
+                .await
+                .unwrap(),
+            Some(L1BatchNumber(1))
+        );
+        assert_eq!(
+            dal.get_archived_l1_batch_for_transaction(ColdStorageDataKind::CallTraces, tx_hash)
+                .await
+                .unwrap(),
+            Some(L1BatchNumber(1))
+        );
+        assert_eq!(
+            dal.get_archived_l1_batch_for_transaction(
+                ColdStorageDataKind::CallTraces,
+                H256::repeat_byte(0xff)
+            )
+            .await
+            .unwrap(),
+            None
+        );
+        assert_eq!(
+            dal.filter_archived_l1_batches(
+                ColdStorageDataKind::Events,
+                &[L1BatchNumber(0), L1BatchNumber(1), L1BatchNumber(2)]
+            )
+            .await
+            .unwrap(),
+            [L1BatchNumber(1)]
+        );
+
+        // Stubbed traces must not be returned by default, but can be restored from the archive.
+        let call_trace = conn
+            .transactions_dal()
+            .get_call_trace(tx_hash)
+            .await
+            .unwrap();
+        assert!(call_trace.is_none());
+        let block_traces = conn
+            .blocks_web3_dal()
+            .get_traces_for_l2_block(L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert!(block_traces.is_empty());
+
+        let (restored_call, meta) = conn
+            .transactions_dal()
+            .get_archived_call_trace(tx_hash, &archive)
+            .await
+            .unwrap()
+            .expect("no archived call trace");
+        assert_eq!(restored_call, call);
+        assert_eq!(meta.tx_hash, tx_hash);
+        assert_eq!(meta.block_number, 1);
+        let block_traces = conn
+            .blocks_web3_dal()
+            .get_archived_traces_for_l2_block(L2BlockNumber(1), &archive)
+            .await
+            .unwrap();
+        assert_eq!(block_traces.len(), 1);
+        assert_eq!(block_traces[0].0, call);
+    }
+}
@@ -0,0 +1,415 @@
+use std::ops;
+
+use chrono::NaiveDate;
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{
+    api::{ContractUsage, ContractUsageMetric},
+    Address, L2BlockNumber,
+};
+
+use crate::{Core, CoreDal};
+
+/// DAL for gas and pubdata usage aggregated per called contract and day (UTC).
+///
+/// Usage is aggregated incrementally for sealed L2 blocks; the last aggregated L2 block is persisted alongside
+/// the aggregated data. Usage of reverted L2 blocks is subtracted via [`Self::roll_back()`].
+#[derive(Debug)]
+pub struct ContractUsageDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl ContractUsageDal<'_, '_> {
+    /// Returns the last L2 block aggregated into the contract usage.
+    pub async fn get_last_aggregated_l2_block(&mut self) -> DalResult<Option<L2BlockNumber>> {
+        Self::get_last_aggregated_l2_block_inner(self.storage).await
+    }
+
+    async fn get_last_aggregated_l2_block_inner(
+        conn: &mut Connection<'_, Core>,
+    ) -> DalResult<Option<L2BlockNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                last_l2_block
+            FROM
+                contract_usage_aggregation
+            FOR UPDATE
+            "#
+        )
+        .instrument("get_last_aggregated_l2_block")
+        .fetch_optional(conn)
+        .await?;
+        Ok(row.map(|row| L2BlockNumber(row.last_l2_block as u32)))
+    }
+
+    async fn set_last_aggregated_l2_block(
+        conn: &mut Connection<'_, Core>,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            contract_usage_aggregation (fake_key, last_l2_block, updated_at)
+            VALUES
+            (TRUE, $1, NOW())
+            ON CONFLICT (fake_key) DO
+            UPDATE
+            SET
+            last_l2_block = excluded.last_l2_block,
+            updated_at = NOW()
+            "#,
+            i64::from(l2_block_number.0)
+        )
+        .instrument("set_last_aggregated_l2_block")
+        .with_arg("l2_block_number", &l2_block_number)
+        .execute(conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Adds (if `sign == 1`) or subtracts (if `sign == -1`) usage by transactions in the specified L2 blocks.
+    async fn apply_usage(
+        conn: &mut Connection<'_, Core>,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+        sign: i64,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            contract_daily_usage (
+                contract_address,
+                day,
+                tx_count,
+                gas_used,
+                pubdata_published,
+                updated_at
+            )
+            SELECT
+                transactions.contract_address,
+                (TO_TIMESTAMP(miniblocks.timestamp) AT TIME ZONE 'UTC')::DATE AS day,
+                $3 * COUNT(*) AS tx_count,
+                $3 * SUM(COALESCE(transactions.gas_limit, 0) - transactions.refunded_gas)::BIGINT
+                AS gas_used,
+                $3 * SUM(
+                    COALESCE((transactions.execution_info ->> 'pubdata_published')::BIGINT, 0)
+                )::BIGINT AS pubdata_published,
+                NOW()
+            FROM
+                transactions
+            JOIN miniblocks ON miniblocks.number = transactions.miniblock_number
+            WHERE
+                transactions.miniblock_number BETWEEN $1 AND $2
+                AND transactions.contract_address IS NOT NULL
+            GROUP BY
+                transactions.contract_address,
+                day
+            ON CONFLICT (day, contract_address) DO
+            UPDATE
+            SET
+            tx_count = contract_daily_usage.tx_count + excluded.tx_count,
+            gas_used = contract_daily_usage.gas_used + excluded.gas_used,
+            pubdata_published = contract_daily_usage.pubdata_published + excluded.pubdata_published,
+            updated_at = NOW()
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+            sign
+        )
+        .instrument("apply_contract_usage")
+        .with_arg("l2_blocks", &l2_blocks)
+        .with_arg("sign", &sign)
+        .execute(conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Aggregates usage for up to `max_l2_blocks` sealed L2 blocks following the last aggregated one. Returns the new
+    /// last aggregated L2 block, or `None` if there are no L2 blocks to aggregate.
+    pub async fn aggregate_next_l2_blocks(
+        &mut self,
+        max_l2_blocks: u32,
+    ) -> DalResult<Option<L2BlockNumber>> {
+        let mut transaction = self.storage.start_transaction().await?;
+        let first_l2_block =
+            match Self::get_last_aggregated_l2_block_inner(&mut transaction).await? {
+                Some(last_aggregated) => last_aggregated + 1,
+                // Start from the earliest L2 block present in the DB, e.g. the first block after snapshot recovery.
+                None => match transaction
+                    .blocks_dal()
+                    .get_earliest_l2_block_number()
+                    .await?
+                {
+                    Some(number) => number,
+                    None => return Ok(None),
+                },
+            };
+        let Some(sealed_l2_block) = transaction
+            .blocks_dal()
+            .get_sealed_l2_block_number()
+            .await?
+        else {
+            return Ok(None);
+        };
+        if first_l2_block > sealed_l2_block {
+            return Ok(None);
+        }
+        let last_l2_block = sealed_l2_block.min(first_l2_block + max_l2_blocks.saturating_sub(1));
+
+        Self::apply_usage(&mut transaction, first_l2_block..=last_l2_block, 1).await?;
+        Self::set_last_aggregated_l2_block(&mut transaction, last_l2_block).await?;
+        transaction.commit().await?;
+        Ok(Some(last_l2_block))
+    }
+
+    /// Subtracts usage of aggregated L2 blocks after `last_l2_block_to_keep`. Must be called before
+    /// transactions in these L2 blocks are rolled back.
+    pub async fn roll_back(&mut self, last_l2_block_to_keep: L2BlockNumber) -> DalResult<()> {
+        let mut transaction = self.storage.start_transaction().await?;
+        let last_aggregated = Self::get_last_aggregated_l2_block_inner(&mut transaction).await?;
+        let Some(last_aggregated) = last_aggregated.filter(|&n| n > last_l2_block_to_keep) else {
+            return Ok(());
+        };
+
+        Self::apply_usage(
+            &mut transaction,
+            (last_l2_block_to_keep + 1)..=last_aggregated,
+            -1,
+        )
+        .await?;
+        sqlx::query!(
+            r#"
+            DELETE FROM contract_daily_usage
+            WHERE
+                tx_count <= 0
+            "#
+        )
+        .instrument("roll_back_contract_usage#delete")
+        .execute(&mut transaction)
+        .await?;
+        Self::set_last_aggregated_l2_block(&mut transaction, last_l2_block_to_keep).await?;
+        transaction.commit().await
+    }
+
+    /// Returns up to `limit` contracts with the greatest usage during the specified day, ordered by the specified metric.
+    pub async fn get_top_contracts(
+        &mut self,
+        day: NaiveDate,
+        metric: ContractUsageMetric,
+        limit: usize,
+    ) -> DalResult<Vec<ContractUsage>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                contract_address,
+                day,
+                tx_count,
+                gas_used,
+                pubdata_published
+            FROM
+                contract_daily_usage
+            WHERE
+                day = $1
+            ORDER BY
+                CASE
+                    WHEN $2 THEN pubdata_published
+                    ELSE gas_used
+                END DESC,
+                contract_address
+            LIMIT
+                $3
+            "#,
+            day,
+            metric == ContractUsageMetric::Pubdata,
+            limit as i64
+        )
+        .instrument("get_top_contracts")
+        .with_arg("day", &day)
+        .with_arg("metric", &metric)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ContractUsage {
+                address: Address::from_slice(&row.contract_address),
+                day: row.day,
+                tx_count: row.tx_count as u64,
+                gas_used: row.gas_used as u64,
+                pubdata_published: row.pubdata_published as u64,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{ProtocolVersion, ProtocolVersionId, U256};
+    use zksync_vm_interface::{tracer::ValidationTraces, TransactionExecutionMetrics};
+
+    use super::*;
+    use crate::{
+        tests::{create_l2_block_header, mock_execution_result, mock_l2_transaction},
+        ConnectionPool,
+    };
+
+    async fn insert_l2_block_with_txs(
+        conn: &mut Connection<'_, Core>,
+        number: u32,
+        timestamp: u64,
+        contract_address: Address,
+        pubdata_published: u32,
+    ) {
+        let txs: Vec<_> = (0..2)
+            .map(|_| {
+                let mut tx = mock_l2_transaction();
+                tx.execute.contract_address = Some(contract_address);
+                tx
+            })
+            .collect();
+        for tx in &txs {
+            conn.transactions_dal()
+                .insert_transaction_l2(
+                    tx,
+                    TransactionExecutionMetrics::default(),
+                    ValidationTraces::default(),
+                )
+                .await
+                .unwrap();
+        }
+        let mut l2_block_header = create_l2_block_header(number);
+        l2_block_header.timestamp = timestamp;
+        l2_block_header.l2_tx_count = txs.len() as u16;
+        conn.blocks_dal()
+            .insert_l2_block(&l2_block_header)
+            .await
+            .unwrap();
+        let tx_results: Vec<_> = txs
+            .into_iter()
+            .map(|tx| {
+                let mut result = mock_execution_result(tx);
+                result.refunded_gas = 400_000;
+                result.execution_info.pubdata_published = pubdata_published;
+                result
+            })
+            .collect();
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l2_block(
+                L2BlockNumber(number),
+                &tx_results,
+                U256::from(1),
+                ProtocolVersionId::latest(),
+                false,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn aggregating_contract_usage() {
+        const DAY_SECS: u64 = 86_400;
+
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            conn.contract_usage_dal()
+                .aggregate_next_l2_blocks(10)
+                .await
+                .unwrap(),
+            None
+        );
+
+        let heavy_contract = Address::repeat_byte(1);
+        let light_contract = Address::repeat_byte(2);
+        let first_day = NaiveDate::from_ymd_opt(1970, 1, 2).unwrap();
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(0))
+            .await
+            .unwrap();
+        insert_l2_block_with_txs(&mut conn, 1, DAY_SECS + 1, heavy_contract, 100).await;
+        insert_l2_block_with_txs(&mut conn, 2, DAY_SECS + 2, light_contract, 500).await;
+        insert_l2_block_with_txs(&mut conn, 3, 2 * DAY_SECS + 1, heavy_contract, 100).await;
+
+        let last_block = conn
+            .contract_usage_dal()
+            .aggregate_next_l2_blocks(2)
+            .await
+            .unwrap();
+        assert_eq!(last_block, Some(L2BlockNumber(1)));
+        let last_block = conn
+            .contract_usage_dal()
+            .aggregate_next_l2_blocks(10)
+            .await
+            .unwrap();
+        assert_eq!(last_block, Some(L2BlockNumber(3)));
+        assert_eq!(
+            conn.contract_usage_dal()
+                .aggregate_next_l2_blocks(10)
+                .await
+                .unwrap(),
+            None
+        );
+
+        let top_by_gas = conn
+            .contract_usage_dal()
+            .get_top_contracts(first_day, ContractUsageMetric::Gas, 10)
+            .await
+            .unwrap();
+        assert_eq!(top_by_gas.len(), 2);
+        // Both contracts have the same gas usage, so they are ordered by address.
+        assert_eq!(top_by_gas[0].address, heavy_contract);
+        assert_eq!(top_by_gas[0].day, first_day);
+        assert_eq!(top_by_gas[0].tx_count, 2);
+        assert_eq!(top_by_gas[0].gas_used, 2 * 600_000);
+        assert_eq!(top_by_gas[0].pubdata_published, 200);
+
+        let top_by_pubdata = conn
+            .contract_usage_dal()
+            .get_top_contracts(first_day, ContractUsageMetric::Pubdata, 1)
+            .await
+            .unwrap();
+        assert_eq!(top_by_pubdata.len(), 1);
+        assert_eq!(top_by_pubdata[0].address, light_contract);
+        assert_eq!(top_by_pubdata[0].pubdata_published, 1_000);
+
+        let next_day = first_day.succ_opt().unwrap();
+        let top = conn
+            .contract_usage_dal()
+            .get_top_contracts(next_day, ContractUsageMetric::Gas, 10)
+            .await
+            .unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].address, heavy_contract);
+        assert_eq!(top[0].tx_count, 2);
+
+        // Roll back L2 blocks #2 and #3.
+        conn.contract_usage_dal()
+            .roll_back(L2BlockNumber(1))
+            .await
+            .unwrap();
+        let last_block = conn
+            .contract_usage_dal()
+            .get_last_aggregated_l2_block()
+            .await
+            .unwrap();
+        assert_eq!(last_block, Some(L2BlockNumber(1)));
+        let top = conn
+            .contract_usage_dal()
+            .get_top_contracts(first_day, ContractUsageMetric::Gas, 10)
+            .await
+            .unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].address, heavy_contract);
+        assert_eq!(top[0].tx_count, 2);
+        let top = conn
+            .contract_usage_dal()
+            .get_top_contracts(next_day, ContractUsageMetric::Gas, 10)
+            .await
+            .unwrap();
+        assert!(top.is_empty());
+    }
+}
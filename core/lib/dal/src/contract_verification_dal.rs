@@ -219,6 +219,7 @@ impl ContractVerificationDal<'_, '_> {
     }
 
     /// Updates the verification request status and inserts the verification info upon successful verification.
+    /// Partial matches are marked with a distinct status (see [`VerificationInfo::request_status()`]).
     pub async fn save_verification_info(
         &mut self,
         verification_info: VerificationInfo,
@@ -228,21 +229,24 @@ impl ContractVerificationDal<'_, '_> {
         let mut transaction = self.storage.start_transaction().await?;
         let id = verification_info.request.id;
         let address = verification_info.request.req.contract_address;
+        let status = verification_info.request_status();
 
         sqlx::query!(
             r#"
             UPDATE contract_verification_requests
             SET
-                status = 'successful',
+                status = $2,
                 updated_at = NOW()
             WHERE
                 id = $1
             "#,
             verification_info.request.id as i64,
+            status,
         )
         .instrument("save_verification_info#set_status")
         .with_arg("id", &id)
         .with_arg("address", &address)
+        .with_arg("status", &status)
         .execute(&mut transaction)
         .await?;
 
@@ -526,6 +530,7 @@ impl ContractVerificationDal<'_, '_> {
         self.set_compiler_versions(Compiler::Vyper, versions).await
     }
 
+    /// Returns all requests that resulted in a full or partial match.
     pub async fn get_all_successful_requests(&mut self) -> DalResult<Vec<VerificationRequest>> {
         let result = sqlx::query_as!(
             StorageVerificationRequest,
@@ -546,7 +551,7 @@ impl ContractVerificationDal<'_, '_> {
             FROM
                 contract_verification_requests
             WHERE
-                status = 'successful'
+                status IN ('successful', 'partial_match')
             ORDER BY
                 id
             "#,
@@ -363,6 +363,37 @@ impl DataAvailabilityDal<'_, '_> {
         .map(DataAvailabilityDetails::from))
     }
 
+    /// Returns blob IDs of L1 batches after the specified one that were dispatched to the DA layer.
+    /// The blob ID is `None` if the blob was dispatched, but the DA layer hasn't assigned an ID to it yet.
+    pub async fn get_dispatched_blobs_after(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<(L1BatchNumber, Option<String>)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                blob_id
+            FROM
+                data_availability
+            WHERE
+                l1_batch_number > $1
+            ORDER BY
+                l1_batch_number
+            "#,
+            i64::from(l1_batch_number.0),
+        )
+        .instrument("get_dispatched_blobs_after")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (L1BatchNumber(row.l1_batch_number as u32), row.blob_id))
+            .collect())
+    }
+
     pub async fn get_latest_batch_with_inclusion_data(
         &mut self,
     ) -> DalResult<Option<L1BatchNumber>> {
@@ -749,6 +749,95 @@ impl EthSenderDal<'_, '_> {
         Ok(nonce.map(|row| row.nonce as u64 + 1))
     }
 
+    /// Returns the sender of the most recently created transaction, ignoring transactions sent from
+    /// `excluded_address`. `None` is returned both for the main operator and if there are no transactions.
+    pub async fn get_sender_of_last_eth_tx(
+        &mut self,
+        excluded_address: Option<Address>,
+        is_gateway: bool,
+    ) -> DalResult<Option<Address>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                from_addr
+            FROM
+                eth_txs
+            WHERE
+                (
+                    $1::BYTEA IS NULL
+                    OR from_addr IS DISTINCT FROM $1
+                )
+                AND is_gateway = $2
+            ORDER BY
+                id DESC
+            LIMIT
+                1
+            "#,
+            excluded_address.as_ref().map(|h160| h160.as_bytes()),
+            is_gateway
+        )
+        .instrument("get_sender_of_last_eth_tx")
+        .with_arg("excluded_address", &excluded_address)
+        .with_arg("is_gateway", &is_gateway)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row
+            .and_then(|row| row.from_addr)
+            .map(|addr| Address::from_slice(&addr)))
+    }
+
+    /// Moves all unconfirmed L1 transactions of `from_address` with nonces starting from `first_nonce`
+    /// to `to_address`, assigning them consecutive nonces starting from `to_first_nonce` in the order
+    /// the transactions were created. Returns the number of moved transactions.
+    ///
+    /// Previously sent attempts are kept in the history, so that the transactions can still be confirmed
+    /// if one of the old attempts gets mined.
+    pub async fn reassign_unconfirmed_txs(
+        &mut self,
+        from_address: Option<Address>,
+        first_nonce: u64,
+        to_address: Option<Address>,
+        to_first_nonce: u64,
+    ) -> sqlx::Result<usize> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE eth_txs
+            SET
+                from_addr = $3,
+                nonce = reassigned.new_nonce,
+                updated_at = NOW()
+            FROM
+                (
+                    SELECT
+                        id,
+                        $4 + ROW_NUMBER() OVER (
+                            ORDER BY
+                                id
+                        ) - 1 AS new_nonce
+                    FROM
+                        eth_txs
+                    WHERE
+                        from_addr IS NOT DISTINCT FROM $1
+                        AND nonce >= $2
+                        AND confirmed_eth_tx_history_id IS NULL
+                        AND NOT has_failed
+                        AND NOT is_gateway
+                ) AS reassigned
+            WHERE
+                eth_txs.id = reassigned.id
+            "#,
+            from_address.as_ref().map(|h160| h160.as_bytes()),
+            first_nonce as i64,
+            to_address.as_ref().map(|h160| h160.as_bytes()),
+            to_first_nonce as i64
+        )
+        .execute(self.storage.conn())
+        .await?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
     pub async fn mark_failed_transaction(&mut self, eth_tx_id: u32) -> sqlx::Result<()> {
         sqlx::query!(
             r#"
@@ -1,3 +1,5 @@
+use std::ops;
+
 use sqlx::{
     postgres::PgArguments,
     query::{Query, QueryAs},
@@ -7,7 +9,7 @@ use zksync_db_connection::{connection::Connection, error::DalResult, instrument:
 use zksync_system_constants::CONTRACT_DEPLOYER_ADDRESS;
 use zksync_types::{
     api::{GetLogsFilter, Log},
-    h256_to_address, Address, L2BlockNumber, H256,
+    h256_to_address, Address, Bloom, L1BatchNumber, L2BlockNumber, H256,
 };
 use zksync_vm_interface::VmEvent;
 
@@ -60,6 +62,9 @@ impl EventsWeb3Dal<'_, '_> {
                 topics.iter().map(H256::as_bytes).collect(),
             );
         }
+        if let Some(l2_blocks) = &filter.l2_blocks {
+            query = query.bind(Self::l2_block_numbers(l2_blocks));
+        }
         query = query.bind(offset as i32);
         let log = query
             .instrument("get_log_block_number")
@@ -111,6 +116,9 @@ impl EventsWeb3Dal<'_, '_> {
                 topics.iter().map(H256::as_bytes).collect(),
             );
         }
+        if let Some(l2_blocks) = &filter.l2_blocks {
+            query = query.bind(Self::l2_block_numbers(l2_blocks));
+        }
         query = query.bind(limit as i32);
 
         let db_logs: Vec<StorageWeb3Log> = query
@@ -150,9 +158,126 @@ impl EventsWeb3Dal<'_, '_> {
             }
         }
 
+        if filter.l2_blocks.is_some() {
+            where_sql += &format!(" AND (miniblock_number = ANY(${arg_index}))");
+            arg_index += 1;
+        }
+
         (where_sql, arg_index)
     }
 
+    fn l2_block_numbers(l2_blocks: &[L2BlockNumber]) -> Vec<i64> {
+        l2_blocks
+            .iter()
+            .map(|&number| i64::from(number.0))
+            .collect()
+    }
+
+    /// Returns log blooms for sealed L1 batches that contain L2 blocks in the specified range, in the ascending order.
+    /// A bloom is `None` if it wasn't computed for the batch.
+    pub async fn get_l1_batch_logs_blooms(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<(L1BatchNumber, Option<Bloom>)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                number,
+                logs_bloom
+            FROM
+                l1_batches
+            WHERE
+                is_sealed
+                AND number >= COALESCE(
+                    (
+                        SELECT
+                            MIN(l1_batch_number)
+                        FROM
+                            miniblocks
+                        WHERE
+                            number = $1
+                    ),
+                    0
+                )
+                AND number <= (
+                    SELECT
+                        l1_batch_number
+                    FROM
+                        miniblocks
+                    WHERE
+                        number <= $2
+                        AND l1_batch_number IS NOT NULL
+                    ORDER BY
+                        number DESC
+                    LIMIT
+                        1
+                )
+            ORDER BY
+                number
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0)
+        )
+        .instrument("get_l1_batch_logs_blooms")
+        .with_arg("l2_blocks", &l2_blocks)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let bloom = row.logs_bloom.as_deref().map(Bloom::from_slice);
+                (L1BatchNumber(row.number as u32), bloom)
+            })
+            .collect())
+    }
+
+    /// Returns log blooms for L2 blocks in the specified range that either belong to one of `l1_batches`
+    /// or are not included into a sealed L1 batch yet. A bloom is `None` if it wasn't computed for the block.
+    pub async fn get_l2_block_logs_blooms(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+        l1_batches: &[L1BatchNumber],
+    ) -> DalResult<Vec<(L2BlockNumber, Option<Bloom>)>> {
+        let l1_batch_numbers: Vec<_> = l1_batches
+            .iter()
+            .map(|&number| i64::from(number.0))
+            .collect();
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                number,
+                logs_bloom
+            FROM
+                miniblocks
+            WHERE
+                number BETWEEN $1 AND $2
+                AND (
+                    l1_batch_number = ANY($3)
+                    OR l1_batch_number IS NULL
+                )
+            ORDER BY
+                number
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+            &l1_batch_numbers
+        )
+        .instrument("get_l2_block_logs_blooms")
+        .with_arg("l2_blocks", &l2_blocks)
+        .with_arg("l1_batches.len", &l1_batches.len())
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let bloom = row.logs_bloom.as_deref().map(Bloom::from_slice);
+                (L2BlockNumber(row.number as u32), bloom)
+            })
+            .collect())
+    }
+
     // Builds SQL filter for optional filter (like address or topics).
     fn build_sql_filter(
         number_of_entities: u32,
@@ -304,6 +429,7 @@ mod tests {
             to_block: L2BlockNumber(200),
             addresses: vec![Address::from_low_u64_be(123)],
             topics: vec![(0, vec![H256::from_low_u64_be(456)])],
+            l2_blocks: None,
         };
 
         let expected_sql = "(miniblock_number >= 100) AND (miniblock_number <= 200) AND (address = $1) AND (topic0 = $2)";
@@ -338,6 +464,7 @@ mod tests {
                 ),
                 (2, vec![H256::from_low_u64_be(789)]),
             ],
+            l2_blocks: None,
         };
 
         let expected_sql = "(miniblock_number >= 10) AND (miniblock_number <= 400) AND (address = ANY($1)) AND (topic0 = ANY($2)) AND (topic2 = $3)";
@@ -359,6 +486,7 @@ mod tests {
             to_block: L2BlockNumber(400),
             addresses: vec![],
             topics: vec![(2, vec![H256::from_low_u64_be(789)])],
+            l2_blocks: None,
         };
 
         let expected_sql =
@@ -370,4 +498,26 @@ mod tests {
         assert_eq!(actual_sql, expected_sql);
         assert_eq!(actual_arg_index, expected_arg_index);
     }
+
+    #[tokio::test]
+    async fn test_build_get_logs_with_l2_blocks_where_clause() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let storage = &mut connection_pool.connection().await.unwrap();
+        let events_web3_dal = EventsWeb3Dal { storage };
+        let filter = GetLogsFilter {
+            from_block: L2BlockNumber(10),
+            to_block: L2BlockNumber(400),
+            addresses: vec![Address::from_low_u64_be(123)],
+            topics: vec![],
+            l2_blocks: Some(vec![L2BlockNumber(20), L2BlockNumber(300)]),
+        };
+
+        let expected_sql = "(miniblock_number >= 10) AND (miniblock_number <= 400) AND (address = $1) AND (miniblock_number = ANY($2))";
+        let expected_arg_index = 3;
+
+        let (actual_sql, actual_arg_index) = events_web3_dal.build_get_logs_where_clause(&filter);
+
+        assert_eq!(actual_sql, expected_sql);
+        assert_eq!(actual_arg_index, expected_arg_index);
+    }
 }
@@ -0,0 +1,340 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::api::{InclusionStats, TxInclusionClass, TxInclusionStage};
+
+use crate::Core;
+
+/// Storage of transaction inclusion stats (time to inclusion / time to L1 finality percentiles).
+#[derive(Debug)]
+pub struct InclusionStatsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl InclusionStatsDal<'_, '_> {
+    /// Computes inclusion stats for transactions received since `window_start`. Stats are only returned for class / stage pairs
+    /// with at least one transaction; e.g., transactions in L1 batches not executed on L1 yet are not counted for the finality stage.
+    ///
+    /// Protocol upgrade transactions are not accounted for.
+    pub async fn compute_inclusion_stats(
+        &mut self,
+        window_start: DateTime<Utc>,
+    ) -> DalResult<Vec<InclusionStats>> {
+        let rows = sqlx::query!(
+            r#"
+            WITH
+            latencies AS (
+                SELECT
+                    transactions.is_priority,
+                    GREATEST(
+                        miniblocks.timestamp * 1000
+                        - EXTRACT(EPOCH FROM transactions.received_at) * 1000,
+                        0
+                    )::DOUBLE PRECISION AS inclusion_ms,
+                    CASE
+                        WHEN execute_tx.confirmed_at IS NOT NULL THEN GREATEST(
+                            EXTRACT(EPOCH FROM execute_tx.confirmed_at - transactions.received_at) * 1000,
+                            0
+                        )::DOUBLE PRECISION
+                    END AS finality_ms
+                FROM
+                    transactions
+                JOIN miniblocks ON miniblocks.number = transactions.miniblock_number
+                LEFT JOIN l1_batches ON l1_batches.number = miniblocks.l1_batch_number
+                LEFT JOIN eth_txs_history AS execute_tx
+                    ON
+                        l1_batches.eth_execute_tx_id = execute_tx.eth_tx_id
+                        AND execute_tx.confirmed_at IS NOT NULL
+                WHERE
+                    transactions.received_at >= $1
+                    AND transactions.upgrade_id IS NULL
+            )
+
+            SELECT
+                is_priority AS "is_priority!",
+                COUNT(inclusion_ms) AS "inclusion_count!",
+                PERCENTILE_CONT(0.5) WITHIN GROUP (
+                    ORDER BY inclusion_ms
+                ) AS inclusion_p50_ms,
+                PERCENTILE_CONT(0.9) WITHIN GROUP (
+                    ORDER BY inclusion_ms
+                ) AS inclusion_p90_ms,
+                PERCENTILE_CONT(0.99) WITHIN GROUP (
+                    ORDER BY inclusion_ms
+                ) AS inclusion_p99_ms,
+                COUNT(finality_ms) AS "finality_count!",
+                PERCENTILE_CONT(0.5) WITHIN GROUP (
+                    ORDER BY finality_ms
+                ) AS finality_p50_ms,
+                PERCENTILE_CONT(0.9) WITHIN GROUP (
+                    ORDER BY finality_ms
+                ) AS finality_p90_ms,
+                PERCENTILE_CONT(0.99) WITHIN GROUP (
+                    ORDER BY finality_ms
+                ) AS finality_p99_ms
+            FROM
+                latencies
+            GROUP BY
+                is_priority
+            "#,
+            window_start.naive_utc()
+        )
+        .instrument("compute_inclusion_stats")
+        .with_arg("window_start", &window_start)
+        .fetch_all(self.storage)
+        .await?;
+
+        let computed_at = Utc::now();
+        let mut stats = vec![];
+        for row in rows {
+            let tx_class = if row.is_priority {
+                TxInclusionClass::L1
+            } else {
+                TxInclusionClass::L2
+            };
+            let stages = [
+                (
+                    TxInclusionStage::Inclusion,
+                    row.inclusion_count,
+                    [
+                        row.inclusion_p50_ms,
+                        row.inclusion_p90_ms,
+                        row.inclusion_p99_ms,
+                    ],
+                ),
+                (
+                    TxInclusionStage::Finality,
+                    row.finality_count,
+                    [
+                        row.finality_p50_ms,
+                        row.finality_p90_ms,
+                        row.finality_p99_ms,
+                    ],
+                ),
+            ];
+            for (stage, sample_count, percentiles) in stages {
+                let [Some(p50), Some(p90), Some(p99)] = percentiles else {
+                    continue;
+                };
+                if sample_count == 0 {
+                    continue;
+                }
+                stats.push(InclusionStats {
+                    tx_class,
+                    stage,
+                    sample_count: sample_count as u64,
+                    p50_ms: p50.round() as u64,
+                    p90_ms: p90.round() as u64,
+                    p99_ms: p99.round() as u64,
+                    window_start,
+                    computed_at,
+                });
+            }
+        }
+        stats.sort_unstable_by_key(|stats| (stats.tx_class.as_str(), stats.stage.as_str()));
+        Ok(stats)
+    }
+
+    /// Replaces all persisted stats with the provided ones.
+    pub async fn save_inclusion_stats(&mut self, stats: &[InclusionStats]) -> DalResult<()> {
+        let mut transaction = self.storage.start_transaction().await?;
+        sqlx::query!(
+            r#"
+            DELETE FROM transaction_inclusion_stats
+            "#
+        )
+        .instrument("save_inclusion_stats#delete")
+        .execute(&mut transaction)
+        .await?;
+
+        for stats in stats {
+            sqlx::query!(
+                r#"
+                INSERT INTO
+                transaction_inclusion_stats (
+                    tx_class,
+                    stage,
+                    sample_count,
+                    p50_ms,
+                    p90_ms,
+                    p99_ms,
+                    window_start,
+                    computed_at
+                )
+                VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8)
+                "#,
+                stats.tx_class.as_str(),
+                stats.stage.as_str(),
+                stats.sample_count as i64,
+                stats.p50_ms as i64,
+                stats.p90_ms as i64,
+                stats.p99_ms as i64,
+                stats.window_start.naive_utc(),
+                stats.computed_at.naive_utc()
+            )
+            .instrument("save_inclusion_stats#insert")
+            .with_arg("tx_class", &stats.tx_class)
+            .with_arg("stage", &stats.stage)
+            .execute(&mut transaction)
+            .await?;
+        }
+        transaction.commit().await
+    }
+
+    /// Returns persisted stats ordered by the transaction class and stage.
+    pub async fn get_inclusion_stats(&mut self) -> DalResult<Vec<InclusionStats>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                tx_class,
+                stage,
+                sample_count,
+                p50_ms,
+                p90_ms,
+                p99_ms,
+                window_start,
+                computed_at
+            FROM
+                transaction_inclusion_stats
+            ORDER BY
+                tx_class,
+                stage
+            "#
+        )
+        .instrument("get_inclusion_stats")
+        .fetch_all(self.storage)
+        .await?;
+
+        let to_utc = |time: NaiveDateTime| DateTime::<Utc>::from_naive_utc_and_offset(time, Utc);
+        let stats = rows.into_iter().filter_map(|row| {
+            let tx_class = match row.tx_class.as_str() {
+                "l1" => TxInclusionClass::L1,
+                "l2" => TxInclusionClass::L2,
+                _ => return None,
+            };
+            let stage = match row.stage.as_str() {
+                "inclusion" => TxInclusionStage::Inclusion,
+                "finality" => TxInclusionStage::Finality,
+                _ => return None,
+            };
+            Some(InclusionStats {
+                tx_class,
+                stage,
+                sample_count: row.sample_count as u64,
+                p50_ms: row.p50_ms as u64,
+                p90_ms: row.p90_ms as u64,
+                p99_ms: row.p99_ms as u64,
+                window_start: to_utc(row.window_start),
+                computed_at: to_utc(row.computed_at),
+            })
+        });
+        Ok(stats.collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{L2BlockNumber, ProtocolVersion, ProtocolVersionId, U256};
+    use zksync_vm_interface::{tracer::ValidationTraces, TransactionExecutionMetrics};
+
+    use super::*;
+    use crate::{
+        tests::{create_l2_block_header, mock_execution_result, mock_l2_transaction},
+        ConnectionPool, CoreDal,
+    };
+
+    #[tokio::test]
+    async fn computing_and_persisting_inclusion_stats() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        let window_start = Utc::now() - chrono::Duration::hours(1);
+
+        let txs: Vec<_> = (0..3).map(|_| mock_l2_transaction()).collect();
+        for tx in &txs {
+            conn.transactions_dal()
+                .insert_transaction_l2(
+                    tx,
+                    TransactionExecutionMetrics::default(),
+                    ValidationTraces::default(),
+                )
+                .await
+                .unwrap();
+        }
+        let mut l2_block_header = create_l2_block_header(1);
+        // Include transactions into an L2 block 2 seconds after they were received.
+        l2_block_header.timestamp = Utc::now().timestamp() as u64 + 2;
+        l2_block_header.l2_tx_count = txs.len() as u16;
+        conn.blocks_dal()
+            .insert_l2_block(&l2_block_header)
+            .await
+            .unwrap();
+        let tx_results: Vec<_> = txs.into_iter().map(mock_execution_result).collect();
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l2_block(
+                L2BlockNumber(1),
+                &tx_results,
+                U256::from(1),
+                ProtocolVersionId::latest(),
+                false,
+            )
+            .await
+            .unwrap();
+
+        let stats = conn
+            .inclusion_stats_dal()
+            .compute_inclusion_stats(window_start)
+            .await
+            .unwrap();
+        // Finality stats are not computed since the L1 batch is not executed.
+        assert_eq!(stats.len(), 1, "{stats:?}");
+        let inclusion_stats = &stats[0];
+        assert_eq!(inclusion_stats.tx_class, TxInclusionClass::L2);
+        assert_eq!(inclusion_stats.stage, TxInclusionStage::Inclusion);
+        assert_eq!(inclusion_stats.sample_count, 3);
+        assert!(
+            (1_000..=3_000).contains(&inclusion_stats.p50_ms),
+            "{inclusion_stats:?}"
+        );
+        assert!(inclusion_stats.p50_ms <= inclusion_stats.p99_ms);
+
+        // Transactions received before the window start are not counted.
+        let stats = conn
+            .inclusion_stats_dal()
+            .compute_inclusion_stats(Utc::now() + chrono::Duration::hours(1))
+            .await
+            .unwrap();
+        assert!(stats.is_empty(), "{stats:?}");
+
+        conn.inclusion_stats_dal()
+            .save_inclusion_stats(&[inclusion_stats.clone()])
+            .await
+            .unwrap();
+        let persisted_stats = conn
+            .inclusion_stats_dal()
+            .get_inclusion_stats()
+            .await
+            .unwrap();
+        assert_eq!(persisted_stats.len(), 1);
+        assert_eq!(persisted_stats[0].sample_count, 3);
+        assert_eq!(persisted_stats[0].p50_ms, inclusion_stats.p50_ms);
+        assert_eq!(
+            persisted_stats[0].window_start.timestamp_millis(),
+            window_start.timestamp_millis()
+        );
+
+        conn.inclusion_stats_dal()
+            .save_inclusion_stats(&[])
+            .await
+            .unwrap();
+        let persisted_stats = conn
+            .inclusion_stats_dal()
+            .get_inclusion_stats()
+            .await
+            .unwrap();
+        assert!(persisted_stats.is_empty());
+    }
+}
@@ -0,0 +1,500 @@
+use std::ops;
+
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_system_constants::L1_MESSENGER_ADDRESS;
+use zksync_types::{
+    address_to_h256, api, h256_to_address, Address, L1BatchNumber, L1BlockNumber, L2BlockNumber,
+    H256,
+};
+
+use crate::{models::storage_event::StorageL2ToL1Message, Core};
+
+/// Storage of the outgoing L2-to-L1 message queue, i.e. messages sent via the `L1Messenger` system contract,
+/// and their consumption status on L1.
+#[derive(Debug)]
+pub struct L2ToL1MessagesDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl L2ToL1MessagesDal<'_, '_> {
+    /// Returns all messages in the specified L1 batch ordered by their index in the L2-to-L1 logs Merkle tree.
+    /// Returns an empty list if the batch is not sealed.
+    pub async fn get_messages_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<api::L2ToL1MessageStatus>> {
+        let messages = sqlx::query_as!(
+            StorageL2ToL1Message,
+            r#"
+            WITH
+            batch_logs AS (
+                SELECT
+                    l2_to_l1_logs.tx_hash,
+                    l2_to_l1_logs.miniblock_number,
+                    l2_to_l1_logs.sender,
+                    l2_to_l1_logs.key,
+                    l2_to_l1_logs.value,
+                    (
+                        ROW_NUMBER() OVER (
+                            ORDER BY l2_to_l1_logs.miniblock_number, l2_to_l1_logs.log_index_in_miniblock
+                        ) - 1
+                    )::INT AS index_in_batch
+                FROM
+                    l2_to_l1_logs
+                JOIN miniblocks ON miniblocks.number = l2_to_l1_logs.miniblock_number
+                WHERE
+                    miniblocks.l1_batch_number = $1
+            )
+
+            SELECT
+                batch_logs.tx_hash AS "tx_hash!",
+                batch_logs.miniblock_number AS "miniblock_number!",
+                batch_logs.key AS "key!",
+                batch_logs.value AS "value!",
+                l1_batches.number AS l1_batch_number,
+                batch_logs.index_in_batch AS "index_in_batch!",
+                l1_batches.l2_l1_merkle_root IS NOT NULL AS "proof_available!",
+                execute_tx.eth_tx_id IS NOT NULL AS "l1_batch_executed!",
+                l2_to_l1_message_statuses.l1_batch_number IS NOT NULL AS "is_tracked!",
+                l2_to_l1_message_statuses.consumed_at_l1_block
+            FROM
+                batch_logs
+            JOIN l1_batches ON l1_batches.number = $1
+            LEFT JOIN eth_txs_history AS execute_tx
+                ON
+                    l1_batches.eth_execute_tx_id = execute_tx.eth_tx_id
+                    AND execute_tx.confirmed_at IS NOT NULL
+            LEFT JOIN l2_to_l1_message_statuses
+                ON
+                    l2_to_l1_message_statuses.l1_batch_number = $1
+                    AND l2_to_l1_message_statuses.index_in_batch = batch_logs.index_in_batch
+            WHERE
+                batch_logs.sender = $2
+            ORDER BY
+                batch_logs.index_in_batch
+            "#,
+            i64::from(l1_batch_number.0),
+            L1_MESSENGER_ADDRESS.as_bytes()
+        )
+        .instrument("get_l2_to_l1_messages_for_l1_batch")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(messages.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns all messages sent by the specified transaction.
+    pub async fn get_messages_by_tx_hash(
+        &mut self,
+        tx_hash: H256,
+    ) -> DalResult<Vec<api::L2ToL1MessageStatus>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l2_to_l1_logs.miniblock_number,
+                l2_to_l1_logs.key,
+                l2_to_l1_logs.value,
+                miniblocks.l1_batch_number
+            FROM
+                l2_to_l1_logs
+            JOIN miniblocks ON miniblocks.number = l2_to_l1_logs.miniblock_number
+            WHERE
+                l2_to_l1_logs.tx_hash = $1
+                AND l2_to_l1_logs.sender = $2
+            ORDER BY
+                l2_to_l1_logs.log_index_in_tx
+            "#,
+            tx_hash.as_bytes(),
+            L1_MESSENGER_ADDRESS.as_bytes()
+        )
+        .instrument("get_l2_to_l1_messages_by_tx_hash")
+        .with_arg("tx_hash", &tx_hash)
+        .fetch_all(self.storage)
+        .await?;
+
+        // All logs of a transaction belong to the same L2 block.
+        let Some(first_row) = rows.first() else {
+            return Ok(vec![]);
+        };
+        if let Some(l1_batch_number) = first_row.l1_batch_number {
+            let l1_batch_number = L1BatchNumber(l1_batch_number as u32);
+            let mut messages = self.get_messages_for_l1_batch(l1_batch_number).await?;
+            messages.retain(|message| message.transaction_hash == tx_hash);
+            return Ok(messages);
+        }
+
+        // The L1 batch is not sealed yet, so only the basic message info is available.
+        Ok(rows
+            .into_iter()
+            .map(|row| api::L2ToL1MessageStatus {
+                transaction_hash: tx_hash,
+                block_number: L2BlockNumber(row.miniblock_number as u32),
+                sender: h256_to_address(&H256::from_slice(&row.key)),
+                message_hash: H256::from_slice(&row.value),
+                l1_batch_number: None,
+                merkle_index: None,
+                proof_available: false,
+                l1_batch_executed: false,
+                consumption: api::L2ToL1MessageConsumption::Untracked,
+                consumed_at_l1_block: None,
+            })
+            .collect())
+    }
+
+    /// Returns the last L1 batch which messages were registered for consumption tracking.
+    pub async fn get_last_tracked_l1_batch(&mut self) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                last_l1_batch
+            FROM
+                l2_to_l1_message_tracking
+            "#
+        )
+        .instrument("get_last_tracked_l1_batch")
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| L1BatchNumber(row.last_l1_batch as u32)))
+    }
+
+    /// Registers messages sent by any of the `senders` in the specified range of L1 batches for consumption tracking,
+    /// and marks the range as tracked. Returns the number of newly tracked messages.
+    pub async fn track_messages(
+        &mut self,
+        l1_batches: ops::RangeInclusive<L1BatchNumber>,
+        senders: &[Address],
+    ) -> DalResult<usize> {
+        let senders: Vec<_> = senders
+            .iter()
+            .map(|address| address_to_h256(address).as_bytes().to_vec())
+            .collect();
+        let mut transaction = self.storage.start_transaction().await?;
+
+        let inserted_count = sqlx::query!(
+            r#"
+            INSERT INTO
+            l2_to_l1_message_statuses (l1_batch_number, index_in_batch, created_at)
+            SELECT
+                l1_batch_number,
+                index_in_batch,
+                NOW()
+            FROM
+                (
+                    SELECT
+                        miniblocks.l1_batch_number,
+                        l2_to_l1_logs.sender,
+                        l2_to_l1_logs.key,
+                        (
+                            ROW_NUMBER() OVER (
+                                PARTITION BY miniblocks.l1_batch_number
+                                ORDER BY l2_to_l1_logs.miniblock_number, l2_to_l1_logs.log_index_in_miniblock
+                            ) - 1
+                        )::INT AS index_in_batch
+                    FROM
+                        l2_to_l1_logs
+                    JOIN miniblocks ON miniblocks.number = l2_to_l1_logs.miniblock_number
+                    WHERE
+                        miniblocks.l1_batch_number BETWEEN $1 AND $2
+                ) AS batch_logs
+            WHERE
+                sender = $3
+                AND key = ANY($4)
+            ON CONFLICT DO NOTHING
+            "#,
+            i64::from(l1_batches.start().0),
+            i64::from(l1_batches.end().0),
+            L1_MESSENGER_ADDRESS.as_bytes(),
+            &senders
+        )
+        .instrument("track_l2_to_l1_messages")
+        .with_arg("l1_batches", &l1_batches)
+        .execute(&mut transaction)
+        .await?
+        .rows_affected();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            l2_to_l1_message_tracking (fake_key, last_l1_batch, updated_at)
+            VALUES
+            (TRUE, $1, NOW())
+            ON CONFLICT (fake_key) DO
+            UPDATE
+            SET
+            last_l1_batch = excluded.last_l1_batch,
+            updated_at = NOW()
+            "#,
+            i64::from(l1_batches.end().0)
+        )
+        .instrument("set_last_tracked_l1_batch")
+        .with_arg("l1_batches", &l1_batches)
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await?;
+        Ok(inserted_count as usize)
+    }
+
+    /// Returns up to `limit` tracked messages that are not consumed yet as `(L1 batch number, index in batch)` tuples,
+    /// starting from the messages checked least recently.
+    pub async fn get_unconsumed_messages(
+        &mut self,
+        limit: usize,
+    ) -> DalResult<Vec<(L1BatchNumber, u32)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                index_in_batch
+            FROM
+                l2_to_l1_message_statuses
+            WHERE
+                consumed_at_l1_block IS NULL
+            ORDER BY
+                checked_at NULLS FIRST,
+                l1_batch_number,
+                index_in_batch
+            LIMIT
+                $1
+            "#,
+            limit as i64
+        )
+        .instrument("get_unconsumed_l2_to_l1_messages")
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    L1BatchNumber(row.l1_batch_number as u32),
+                    row.index_in_batch as u32,
+                )
+            })
+            .collect())
+    }
+
+    /// Records that the specified messages were checked for consumption on L1. If `consumed_at_l1_block` is set,
+    /// the messages are marked as consumed.
+    pub async fn mark_messages_checked(
+        &mut self,
+        messages: &[(L1BatchNumber, u32)],
+        consumed_at_l1_block: Option<L1BlockNumber>,
+    ) -> DalResult<()> {
+        let (l1_batch_numbers, indices): (Vec<_>, Vec<_>) = messages
+            .iter()
+            .map(|&(l1_batch_number, index)| (i64::from(l1_batch_number.0), index as i32))
+            .unzip();
+        sqlx::query!(
+            r#"
+            UPDATE l2_to_l1_message_statuses
+            SET
+                consumed_at_l1_block = $3,
+                checked_at = NOW()
+            FROM
+                UNNEST($1::BIGINT [], $2::INT []) AS checked (l1_batch_number, index_in_batch)
+            WHERE
+                l2_to_l1_message_statuses.l1_batch_number = checked.l1_batch_number
+                AND l2_to_l1_message_statuses.index_in_batch = checked.index_in_batch
+            "#,
+            &l1_batch_numbers,
+            &indices,
+            consumed_at_l1_block.map(|block| i64::from(block.0))
+        )
+        .instrument("mark_l2_to_l1_messages_checked")
+        .with_arg("messages.len", &messages.len())
+        .with_arg("consumed_at_l1_block", &consumed_at_l1_block)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes the consumption status of messages in L1 batches preceding `first_retained_l1_batch`.
+    /// Returns the number of removed messages.
+    pub async fn prune_messages(
+        &mut self,
+        first_retained_l1_batch: L1BatchNumber,
+    ) -> DalResult<usize> {
+        let pruned_count = sqlx::query!(
+            r#"
+            DELETE FROM l2_to_l1_message_statuses
+            WHERE
+                l1_batch_number < $1
+            "#,
+            i64::from(first_retained_l1_batch.0)
+        )
+        .instrument("prune_l2_to_l1_messages")
+        .with_arg("first_retained_l1_batch", &first_retained_l1_batch)
+        .execute(self.storage)
+        .await?
+        .rows_affected();
+        Ok(pruned_count as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_system_constants::L2_ASSET_ROUTER_ADDRESS;
+    use zksync_types::{
+        l2_to_l1_log::{L2ToL1Log, UserL2ToL1Log},
+        tx::IncludedTxLocation,
+        ProtocolVersion,
+    };
+
+    use super::*;
+    use crate::{
+        tests::{create_l1_batch_header, create_l2_block_header},
+        ConnectionPool, CoreDal,
+    };
+
+    fn message_log(sender: Address, message_hash: H256) -> UserL2ToL1Log {
+        UserL2ToL1Log(L2ToL1Log {
+            shard_id: 0,
+            is_service: true,
+            tx_number_in_block: 0,
+            sender: L1_MESSENGER_ADDRESS,
+            key: address_to_h256(&sender),
+            value: message_hash,
+        })
+    }
+
+    async fn prepare_storage(conn: &mut Connection<'_, Core>) -> H256 {
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        let l2_block = create_l2_block_header(1);
+        conn.blocks_dal().insert_l2_block(&l2_block).await.unwrap();
+
+        let tx_hash = H256::repeat_byte(1);
+        let tx_location = IncludedTxLocation {
+            tx_hash,
+            tx_index_in_l2_block: 0,
+        };
+        let other_log = UserL2ToL1Log(L2ToL1Log {
+            sender: Address::repeat_byte(0x10),
+            ..message_log(Address::zero(), H256::zero()).0
+        });
+        let logs = [
+            other_log,
+            message_log(Address::repeat_byte(0x20), H256::repeat_byte(2)),
+            message_log(L2_ASSET_ROUTER_ADDRESS, H256::repeat_byte(3)),
+        ];
+        let logs: Vec<_> = logs.iter().collect();
+        conn.events_dal()
+            .save_user_l2_to_l1_logs(l2_block.number, &[(tx_location, logs)])
+            .await
+            .unwrap();
+        tx_hash
+    }
+
+    #[tokio::test]
+    async fn getting_messages() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let tx_hash = prepare_storage(&mut conn).await;
+
+        let messages = conn
+            .l2_to_l1_messages_dal()
+            .get_messages_by_tx_hash(tx_hash)
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].sender, Address::repeat_byte(0x20));
+        assert_eq!(messages[0].message_hash, H256::repeat_byte(2));
+        assert_eq!(messages[0].l1_batch_number, None);
+        assert_eq!(messages[0].merkle_index, None);
+
+        let l1_batch_header = create_l1_batch_header(1);
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&l1_batch_header)
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .mark_l2_blocks_as_executed_in_l1_batch(L1BatchNumber(1))
+            .await
+            .unwrap();
+
+        let messages = conn
+            .l2_to_l1_messages_dal()
+            .get_messages_by_tx_hash(tx_hash)
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].l1_batch_number, Some(L1BatchNumber(1)));
+        // The first log in the batch is not a message, but it's still included into the Merkle tree.
+        assert_eq!(messages[0].merkle_index, Some(1));
+        assert_eq!(messages[1].merkle_index, Some(2));
+        assert!(!messages[1].proof_available);
+        assert!(!messages[1].l1_batch_executed);
+        assert_eq!(
+            messages[1].consumption,
+            api::L2ToL1MessageConsumption::Untracked
+        );
+
+        let batch_messages = conn
+            .l2_to_l1_messages_dal()
+            .get_messages_for_l1_batch(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(batch_messages, messages);
+    }
+
+    #[tokio::test]
+    async fn tracking_message_consumption() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let tx_hash = prepare_storage(&mut conn).await;
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch_header(1))
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .mark_l2_blocks_as_executed_in_l1_batch(L1BatchNumber(1))
+            .await
+            .unwrap();
+
+        let mut dal = conn.l2_to_l1_messages_dal();
+        assert_eq!(dal.get_last_tracked_l1_batch().await.unwrap(), None);
+        let tracked_count = dal
+            .track_messages(
+                L1BatchNumber(0)..=L1BatchNumber(1),
+                &[L2_ASSET_ROUTER_ADDRESS],
+            )
+            .await
+            .unwrap();
+        assert_eq!(tracked_count, 1);
+        assert_eq!(
+            dal.get_last_tracked_l1_batch().await.unwrap(),
+            Some(L1BatchNumber(1))
+        );
+
+        let unconsumed = dal.get_unconsumed_messages(10).await.unwrap();
+        assert_eq!(unconsumed, [(L1BatchNumber(1), 2)]);
+        let messages = dal.get_messages_by_tx_hash(tx_hash).await.unwrap();
+        assert_eq!(
+            messages[0].consumption,
+            api::L2ToL1MessageConsumption::Untracked
+        );
+        assert_eq!(
+            messages[1].consumption,
+            api::L2ToL1MessageConsumption::Pending
+        );
+
+        dal.mark_messages_checked(&unconsumed, None).await.unwrap();
+        assert_eq!(dal.get_unconsumed_messages(10).await.unwrap(), unconsumed);
+        dal.mark_messages_checked(&unconsumed, Some(L1BlockNumber(100)))
+            .await
+            .unwrap();
+        assert_eq!(dal.get_unconsumed_messages(10).await.unwrap(), []);
+
+        let messages = dal.get_messages_by_tx_hash(tx_hash).await.unwrap();
+        assert_eq!(
+            messages[1].consumption,
+            api::L2ToL1MessageConsumption::Consumed
+        );
+        assert_eq!(messages[1].consumed_at_l1_block, Some(100));
+    }
+}
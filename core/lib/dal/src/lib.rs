@@ -12,28 +12,39 @@ pub use zksync_db_connection::{
 };
 
 use crate::{
-    base_token_dal::BaseTokenDal, blocks_dal::BlocksDal, blocks_web3_dal::BlocksWeb3Dal,
-    consensus_dal::ConsensusDal, contract_verification_dal::ContractVerificationDal,
+    audit_log_dal::AuditLogDal, base_token_dal::BaseTokenDal, batch_export_dal::BatchExportDal,
+    blocks_dal::BlocksDal, blocks_web3_dal::BlocksWeb3Dal, cold_storage_dal::ColdStorageDal,
+    consensus_dal::ConsensusDal, contract_usage_dal::ContractUsageDal,
+    contract_verification_dal::ContractVerificationDal,
     custom_genesis_export_dal::CustomGenesisExportDal, data_availability_dal::DataAvailabilityDal,
     eth_sender_dal::EthSenderDal, eth_watcher_dal::EthWatcherDal,
     etherscan_verification_dal::EtherscanVerificationDal, events_dal::EventsDal,
     events_web3_dal::EventsWeb3Dal, factory_deps_dal::FactoryDepsDal,
-    proof_generation_dal::ProofGenerationDal, protocol_versions_dal::ProtocolVersionsDal,
+    inclusion_stats_dal::InclusionStatsDal, l2_to_l1_messages_dal::L2ToL1MessagesDal,
+    proof_generation_dal::ProofGenerationDal,
+    protocol_upgrade_dry_runs_dal::ProtocolUpgradeDryRunsDal,
+    protocol_versions_dal::ProtocolVersionsDal,
     protocol_versions_web3_dal::ProtocolVersionsWeb3Dal, pruning_dal::PruningDal,
-    server_notifications::ServerNotificationsDal, snapshot_recovery_dal::SnapshotRecoveryDal,
-    snapshots_creator_dal::SnapshotsCreatorDal, snapshots_dal::SnapshotsDal,
+    sequencer_lease_dal::SequencerLeaseDal, server_notifications::ServerNotificationsDal,
+    snapshot_recovery_dal::SnapshotRecoveryDal, snapshots_creator_dal::SnapshotsCreatorDal,
+    snapshots_dal::SnapshotsDal, soft_confirmations_dal::SoftConfirmationsDal,
     storage_logs_dal::StorageLogsDal, storage_logs_dedup_dal::StorageLogsDedupDal,
     storage_web3_dal::StorageWeb3Dal, sync_dal::SyncDal, system_dal::SystemDal,
     tee_proof_generation_dal::TeeProofGenerationDal, tokens_dal::TokensDal,
     tokens_web3_dal::TokensWeb3Dal, transactions_dal::TransactionsDal,
-    transactions_web3_dal::TransactionsWeb3Dal, vm_runner_dal::VmRunnerDal,
+    transactions_web3_dal::TransactionsWeb3Dal, tx_policy_decisions_dal::TxPolicyDecisionsDal,
+    unsealed_batch_checkpoints_dal::UnsealedBatchCheckpointsDal, vm_runner_dal::VmRunnerDal,
 };
 
+pub mod audit_log_dal;
 pub mod base_token_dal;
+pub mod batch_export_dal;
 pub mod blocks_dal;
 pub mod blocks_web3_dal;
+pub mod cold_storage_dal;
 pub mod consensus;
 pub mod consensus_dal;
+pub mod contract_usage_dal;
 pub mod contract_verification_dal;
 pub mod custom_genesis_export_dal;
 mod data_availability_dal;
@@ -44,16 +55,21 @@ pub mod events_dal;
 pub mod events_web3_dal;
 pub mod factory_deps_dal;
 pub mod helpers;
+pub mod inclusion_stats_dal;
+pub mod l2_to_l1_messages_dal;
 pub mod metrics;
 mod models;
 pub mod proof_generation_dal;
+pub mod protocol_upgrade_dry_runs_dal;
 pub mod protocol_versions_dal;
 pub mod protocol_versions_web3_dal;
 pub mod pruning_dal;
+pub mod sequencer_lease_dal;
 mod server_notifications;
 pub mod snapshot_recovery_dal;
 pub mod snapshots_creator_dal;
 pub mod snapshots_dal;
+pub mod soft_confirmations_dal;
 pub mod storage_logs_dal;
 pub mod storage_logs_dedup_dal;
 pub mod storage_web3_dal;
@@ -64,6 +80,8 @@ pub mod tokens_dal;
 pub mod tokens_web3_dal;
 pub mod transactions_dal;
 pub mod transactions_web3_dal;
+pub mod tx_policy_decisions_dal;
+pub mod unsealed_batch_checkpoints_dal;
 pub mod vm_runner_dal;
 
 #[cfg(test)]
@@ -143,6 +161,28 @@ where
     fn custom_genesis_export_dal(&mut self) -> CustomGenesisExportDal<'_, 'a>;
 
     fn server_notifications_dal(&mut self) -> ServerNotificationsDal<'_, 'a>;
+
+    fn soft_confirmations_dal(&mut self) -> SoftConfirmationsDal<'_, 'a>;
+
+    fn unsealed_batch_checkpoints_dal(&mut self) -> UnsealedBatchCheckpointsDal<'_, 'a>;
+
+    fn tx_policy_decisions_dal(&mut self) -> TxPolicyDecisionsDal<'_, 'a>;
+
+    fn protocol_upgrade_dry_runs_dal(&mut self) -> ProtocolUpgradeDryRunsDal<'_, 'a>;
+
+    fn cold_storage_dal(&mut self) -> ColdStorageDal<'_, 'a>;
+
+    fn inclusion_stats_dal(&mut self) -> InclusionStatsDal<'_, 'a>;
+
+    fn batch_export_dal(&mut self) -> BatchExportDal<'_, 'a>;
+
+    fn sequencer_lease_dal(&mut self) -> SequencerLeaseDal<'_, 'a>;
+
+    fn audit_log_dal(&mut self) -> AuditLogDal<'_, 'a>;
+
+    fn contract_usage_dal(&mut self) -> ContractUsageDal<'_, 'a>;
+
+    fn l2_to_l1_messages_dal(&mut self) -> L2ToL1MessagesDal<'_, 'a>;
 }
 
 #[derive(Clone, Debug)]
@@ -230,6 +270,10 @@ impl<'a> CoreDal<'a> for Connection<'a, Core> {
         ServerNotificationsDal { storage: self }
     }
 
+    fn soft_confirmations_dal(&mut self) -> SoftConfirmationsDal<'_, 'a> {
+        SoftConfirmationsDal { storage: self }
+    }
+
     fn sync_dal(&mut self) -> SyncDal<'_, 'a> {
         SyncDal { storage: self }
     }
@@ -281,4 +325,44 @@ impl<'a> CoreDal<'a> for Connection<'a, Core> {
     fn custom_genesis_export_dal(&mut self) -> CustomGenesisExportDal<'_, 'a> {
         CustomGenesisExportDal { storage: self }
     }
+
+    fn unsealed_batch_checkpoints_dal(&mut self) -> UnsealedBatchCheckpointsDal<'_, 'a> {
+        UnsealedBatchCheckpointsDal { storage: self }
+    }
+
+    fn tx_policy_decisions_dal(&mut self) -> TxPolicyDecisionsDal<'_, 'a> {
+        TxPolicyDecisionsDal { storage: self }
+    }
+
+    fn protocol_upgrade_dry_runs_dal(&mut self) -> ProtocolUpgradeDryRunsDal<'_, 'a> {
+        ProtocolUpgradeDryRunsDal { storage: self }
+    }
+
+    fn cold_storage_dal(&mut self) -> ColdStorageDal<'_, 'a> {
+        ColdStorageDal { storage: self }
+    }
+
+    fn inclusion_stats_dal(&mut self) -> InclusionStatsDal<'_, 'a> {
+        InclusionStatsDal { storage: self }
+    }
+
+    fn batch_export_dal(&mut self) -> BatchExportDal<'_, 'a> {
+        BatchExportDal { storage: self }
+    }
+
+    fn sequencer_lease_dal(&mut self) -> SequencerLeaseDal<'_, 'a> {
+        SequencerLeaseDal { storage: self }
+    }
+
+    fn audit_log_dal(&mut self) -> AuditLogDal<'_, 'a> {
+        AuditLogDal { storage: self }
+    }
+
+    fn contract_usage_dal(&mut self) -> ContractUsageDal<'_, 'a> {
+        ContractUsageDal { storage: self }
+    }
+
+    fn l2_to_l1_messages_dal(&mut self) -> L2ToL1MessagesDal<'_, 'a> {
+        L2ToL1MessagesDal { storage: self }
+    }
 }
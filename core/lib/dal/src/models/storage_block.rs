@@ -477,6 +477,7 @@ pub(crate) struct StorageL1BatchDetails {
     pub bootloader_code_hash: Option<Vec<u8>>,
     pub default_aa_code_hash: Option<Vec<u8>>,
     pub evm_emulator_code_hash: Option<Vec<u8>>,
+    pub logs_bloom: Option<Vec<u8>>,
 }
 
 impl From<StorageL1BatchDetails> for api::L1BatchDetails {
@@ -529,6 +530,7 @@ impl From<StorageL1BatchDetails> for api::L1BatchDetails {
         api::L1BatchDetails {
             base,
             number: L1BatchNumber(details.number as u32),
+            logs_bloom: details.logs_bloom.as_deref().map(Bloom::from_slice),
         }
     }
 }
@@ -112,6 +112,7 @@ impl From<StorageTxHistory> for TxHistory {
 
             sent_at_block: history.sent_at_block.map(|block| block as u32),
             max_gas_per_pubdata: history.max_gas_per_pubdata.map(|v| v as u64),
+            created_at_timestamp: history.created_at.and_utc().timestamp() as u64,
         }
     }
 }
@@ -1,8 +1,8 @@
 use zksync_types::{
-    api,
+    api, h256_to_address,
     l2_to_l1_log::{self, UserL2ToL1Log},
     web3::{Bytes, Index},
-    Address, H256, U256, U64,
+    Address, L1BatchNumber, L2BlockNumber, H256, U256, U64,
 };
 
 #[derive(sqlx::FromRow, Debug, Clone)]
@@ -107,3 +107,42 @@ impl From<StorageL2ToL1Log> for l2_to_l1_log::UserL2ToL1Log {
         UserL2ToL1Log(log.into())
     }
 }
+
+/// L2-to-L1 message sent via the `L1Messenger` system contract in a sealed L1 batch.
+#[derive(Debug, Clone)]
+pub(crate) struct StorageL2ToL1Message {
+    pub tx_hash: Vec<u8>,
+    pub miniblock_number: i64,
+    pub key: Vec<u8>,
+    pub value: Vec<u8>,
+    pub l1_batch_number: i64,
+    pub index_in_batch: i32,
+    pub proof_available: bool,
+    pub l1_batch_executed: bool,
+    pub is_tracked: bool,
+    pub consumed_at_l1_block: Option<i64>,
+}
+
+impl From<StorageL2ToL1Message> for api::L2ToL1MessageStatus {
+    fn from(message: StorageL2ToL1Message) -> Self {
+        let consumption = if message.consumed_at_l1_block.is_some() {
+            api::L2ToL1MessageConsumption::Consumed
+        } else if message.is_tracked {
+            api::L2ToL1MessageConsumption::Pending
+        } else {
+            api::L2ToL1MessageConsumption::Untracked
+        };
+        Self {
+            transaction_hash: H256::from_slice(&message.tx_hash),
+            block_number: L2BlockNumber(message.miniblock_number as u32),
+            sender: h256_to_address(&H256::from_slice(&message.key)),
+            message_hash: H256::from_slice(&message.value),
+            l1_batch_number: Some(L1BatchNumber(message.l1_batch_number as u32)),
+            merkle_index: Some(message.index_in_batch as u32),
+            proof_available: message.proof_available,
+            l1_batch_executed: message.l1_batch_executed,
+            consumption,
+            consumed_at_l1_block: message.consumed_at_l1_block.map(|block| block as u64),
+        }
+    }
+}
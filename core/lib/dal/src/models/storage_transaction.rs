@@ -3,7 +3,10 @@ use std::{convert::TryInto, str::FromStr};
 use bigdecimal::Zero;
 use sqlx::types::chrono::{DateTime, NaiveDateTime, Utc};
 use zksync_types::{
-    api::{self, TransactionDetails, TransactionReceipt, TransactionStatus},
+    api::{
+        self, PaymasterReceiptInfo, ReceiptL1Finality, TransactionDetails, TransactionReceipt,
+        TransactionStatus,
+    },
     fee::Fee,
     l1::{OpProcessingType, PriorityQueueType},
     l2::TransactionType,
@@ -354,7 +357,38 @@ pub(crate) struct StorageTransactionReceipt {
     pub effective_gas_price: Option<BigDecimal>,
     pub initiator_address: Vec<u8>,
     pub nonce: Option<i64>,
+    pub paymaster: Vec<u8>,
+    pub paymaster_input: Vec<u8>,
     pub block_timestamp: Option<i64>,
+    pub commit_tx_hash: Option<String>,
+    pub committed_at: Option<NaiveDateTime>,
+    pub prove_tx_hash: Option<String>,
+    pub proven_at: Option<NaiveDateTime>,
+    pub execute_tx_hash: Option<String>,
+    pub executed_at: Option<NaiveDateTime>,
+}
+
+impl StorageTransactionReceipt {
+    fn l1_finality(&self) -> Option<ReceiptL1Finality> {
+        let parse_hash = |hash: &Option<String>| {
+            hash.as_deref()
+                .map(|hash| H256::from_str(hash).expect("Incorrect L1 tx hash"))
+        };
+        let to_utc = |timestamp: Option<NaiveDateTime>| {
+            timestamp.map(|timestamp| DateTime::from_naive_utc_and_offset(timestamp, Utc))
+        };
+
+        // Batches are committed, proven and executed in order, so a missing commit tx means no finality info.
+        self.commit_tx_hash.as_ref()?;
+        Some(ReceiptL1Finality {
+            commit_tx_hash: parse_hash(&self.commit_tx_hash),
+            committed_at: to_utc(self.committed_at),
+            prove_tx_hash: parse_hash(&self.prove_tx_hash),
+            proven_at: to_utc(self.proven_at),
+            execute_tx_hash: parse_hash(&self.execute_tx_hash),
+            executed_at: to_utc(self.executed_at),
+        })
+    }
 }
 
 impl From<StorageTransactionReceipt> for ExtendedTransactionReceipt {
@@ -376,7 +410,31 @@ impl From<StorageTransactionReceipt> for ExtendedTransactionReceipt {
                     .expect("invalid address value in the database")
             });
 
+        let gas_used = {
+            let refunded_gas: U256 = storage_receipt.refunded_gas.into();
+            storage_receipt.gas_limit.map(|val| {
+                let gas_limit = bigdecimal_to_u256(val);
+                gas_limit - refunded_gas
+            })
+        };
+        let effective_gas_price = storage_receipt
+            .effective_gas_price
+            .map(bigdecimal_to_u256)
+            .unwrap_or_default();
+        // L1 and upgrade transactions have zero paymaster address.
+        let paymaster = Address::from_slice(&storage_receipt.paymaster);
+        let paymaster_info = (paymaster != Address::zero()).then(|| PaymasterReceiptInfo {
+            paymaster,
+            paymaster_input_selector: storage_receipt
+                .paymaster_input
+                .get(..4)
+                .map(|selector| selector.to_vec().into()),
+            sponsored_fee: gas_used.unwrap_or_default() * effective_gas_price,
+            user_paid_fee: U256::zero(),
+        });
+
         let block_hash = H256::from_slice(&storage_receipt.block_hash);
+        let l1_finality = storage_receipt.l1_finality();
         let inner = TransactionReceipt {
             transaction_hash: H256::from_slice(&storage_receipt.tx_hash),
             transaction_index,
@@ -387,19 +445,8 @@ impl From<StorageTransactionReceipt> for ExtendedTransactionReceipt {
             from: H160::from_slice(&storage_receipt.initiator_address),
             to,
             cumulative_gas_used: Default::default(), // TODO: Should be actually calculated (SMA-1183).
-            gas_used: {
-                let refunded_gas: U256 = storage_receipt.refunded_gas.into();
-                storage_receipt.gas_limit.map(|val| {
-                    let gas_limit = bigdecimal_to_u256(val);
-                    gas_limit - refunded_gas
-                })
-            },
-            effective_gas_price: Some(
-                storage_receipt
-                    .effective_gas_price
-                    .map(bigdecimal_to_u256)
-                    .unwrap_or_default(),
-            ),
+            gas_used,
+            effective_gas_price: Some(effective_gas_price),
             contract_address: None, // Must be filled in separately
             logs: vec![],
             l2_to_l1_logs: vec![],
@@ -408,6 +455,8 @@ impl From<StorageTransactionReceipt> for ExtendedTransactionReceipt {
             // Even though the Rust SDK recommends us to supply "None" for legacy transactions
             // we always supply some number anyway to have the same behavior as most popular RPCs
             transaction_type: Some(tx_type),
+            paymaster_info,
+            l1_finality,
         };
 
         Self {
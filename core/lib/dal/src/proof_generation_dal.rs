@@ -1,6 +1,7 @@
 #![doc = include_str!("../doc/ProofGenerationDal.md")]
 use std::time::Duration;
 
+use chrono::NaiveDateTime;
 use strum::{Display, EnumString};
 use zksync_db_connection::{
     connection::Connection,
@@ -8,7 +9,7 @@ use zksync_db_connection::{
     instrument::{InstrumentExt, Instrumented},
     utils::pg_interval_from_duration,
 };
-use zksync_types::L1BatchNumber;
+use zksync_types::{L1BatchNumber, H256};
 
 use crate::Core;
 
@@ -17,8 +18,9 @@ pub struct ProofGenerationDal<'a, 'c> {
     pub(crate) storage: &'a mut Connection<'c, Core>,
 }
 
-#[derive(Debug, EnumString, Display)]
-enum ProofGenerationJobStatus {
+/// Status of a proof generation job for an L1 batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum ProofGenerationJobStatus {
     #[strum(serialize = "unpicked")]
     Unpicked,
     #[strum(serialize = "picked_by_prover")]
@@ -29,9 +31,92 @@ enum ProofGenerationJobStatus {
     Skipped,
 }
 
+/// Action applied to a proof generation job by the operator via the proof data handler admin API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum ProofGenerationAdminAction {
+    /// Returns a picked or skipped job to the queue.
+    #[strum(serialize = "requeue")]
+    Requeue,
+    /// Marks a job that isn't proven yet as skipped.
+    #[strum(serialize = "skip")]
+    Skip,
+    /// Discards a generated proof that wasn't sent to L1 yet and returns the job to the queue.
+    #[strum(serialize = "invalidate_proof")]
+    InvalidateProof,
+}
+
+impl ProofGenerationAdminAction {
+    /// Returns statuses of a proof generation job this action can be applied to.
+    pub fn allowed_statuses(self) -> &'static [ProofGenerationJobStatus] {
+        match self {
+            Self::Requeue => &[
+                ProofGenerationJobStatus::PickedByProver,
+                ProofGenerationJobStatus::Skipped,
+            ],
+            Self::Skip => &[
+                ProofGenerationJobStatus::Unpicked,
+                ProofGenerationJobStatus::PickedByProver,
+            ],
+            Self::InvalidateProof => &[ProofGenerationJobStatus::Generated],
+        }
+    }
+
+    /// Returns the status of a proof generation job after applying this action.
+    pub fn target_status(self) -> ProofGenerationJobStatus {
+        match self {
+            Self::Requeue | Self::InvalidateProof => ProofGenerationJobStatus::Unpicked,
+            Self::Skip => ProofGenerationJobStatus::Skipped,
+        }
+    }
+}
+
+/// Audit record of an action applied to a proof generation job by the operator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofGenerationAdminActionRecord {
+    pub l1_batch_number: L1BatchNumber,
+    pub action: ProofGenerationAdminAction,
+    pub previous_status: ProofGenerationJobStatus,
+    pub reason: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+/// Reason for a proof sampling decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum ProofSamplingReason {
+    /// The decision is derived from the seed and the sampling rate.
+    #[strum(serialize = "rate")]
+    Rate,
+    /// The batch is in a range of batches that are always proven.
+    #[strum(serialize = "always_prove_range")]
+    AlwaysProveRange,
+    /// The batch is in a range of batches that are never proven.
+    #[strum(serialize = "never_prove_range")]
+    NeverProveRange,
+}
+
+/// Decision whether a real proof is generated for an L1 batch, together with the inputs it was derived from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofSamplingDecision {
+    pub l1_batch_number: L1BatchNumber,
+    pub sampled: bool,
+    pub reason: ProofSamplingReason,
+    pub commitment: H256,
+    pub seed: H256,
+    pub sampling_rate: f64,
+}
+
+/// Proving statistics reported by the prover subsystem for an L1 batch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatchProvingStats {
+    pub l1_batch_number: L1BatchNumber,
+    pub base_layer_circuits: u32,
+    pub proving_time: Duration,
+}
+
 impl ProofGenerationDal<'_, '_> {
     /// Chooses the batch number so that it has all the necessary data to generate the proof
-    /// and is not already picked.
+    /// and is not already picked. Batches with a higher priority (see [`Self::set_priority()`]) are chosen first;
+    /// among batches with the same priority, the oldest one is chosen.
     ///
     /// Marks the batch as picked by the prover, preventing it from being picked twice.
     ///
@@ -70,6 +155,7 @@ impl ProofGenerationDal<'_, '_> {
                             AND prover_taken_at < NOW() - $1::INTERVAL
                         )
                     ORDER BY
+                        priority DESC,
                         l1_batch_number ASC
                     LIMIT
                         1
@@ -134,6 +220,55 @@ impl ProofGenerationDal<'_, '_> {
         Ok(())
     }
 
+    /// Sets the proof generation priority for the specified batch. Returns `false` if the batch
+    /// has no proof generation details.
+    pub async fn set_priority(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        priority: i32,
+    ) -> DalResult<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE proof_generation_details
+            SET
+                priority = $2,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0),
+            priority,
+        )
+        .instrument("set_priority")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("priority", &priority)
+        .execute(self.storage)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Returns the proof generation priority for the specified batch, or `None` if the batch
+    /// has no proof generation details.
+    pub async fn get_priority(&mut self, l1_batch_number: L1BatchNumber) -> DalResult<Option<i32>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                priority
+            FROM
+                proof_generation_details
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_priority")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| row.priority))
+    }
+
     pub async fn save_proof_artifacts_metadata(
         &mut self,
         batch_number: L1BatchNumber,
@@ -313,6 +448,327 @@ impl ProofGenerationDal<'_, '_> {
         Ok(())
     }
 
+    /// Returns numbers of L1 batches after the specified one that have proof generation details.
+    /// Witness inputs for such batches may be persisted in the object store.
+    pub async fn get_l1_batches_with_proof_generation_details_after(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<L1BatchNumber>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number
+            FROM
+                proof_generation_details
+            WHERE
+                l1_batch_number > $1
+            ORDER BY
+                l1_batch_number
+            "#,
+            i64::from(l1_batch_number.0),
+        )
+        .instrument("get_l1_batches_with_proof_generation_details_after")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L1BatchNumber(row.l1_batch_number as u32))
+            .collect())
+    }
+
+    /// Returns the status of the proof generation job for the specified L1 batch, locking the job row
+    /// until the end of the current transaction. Returns `None` if the batch has no proof generation details.
+    pub async fn get_status_for_update(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<ProofGenerationJobStatus>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                status
+            FROM
+                proof_generation_details
+            WHERE
+                l1_batch_number = $1
+            FOR UPDATE
+            "#,
+            i64::from(l1_batch_number.0),
+        )
+        .instrument("get_status_for_update")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let status = row.status.parse().map_err(|err| {
+            Instrumented::new("get_status_for_update")
+                .with_arg("l1_batch_number", &l1_batch_number)
+                .constraint_error(anyhow::anyhow!("invalid status {:?}: {err}", row.status))
+        })?;
+        Ok(Some(status))
+    }
+
+    /// Applies an operator action to the proof generation job for the specified L1 batch and records it
+    /// in the audit log. The caller is responsible for checking that the action is allowed for the current
+    /// job status (see [`ProofGenerationAdminAction::allowed_statuses()`]) and should call this method
+    /// in the same transaction as [`Self::get_status_for_update()`].
+    pub async fn apply_admin_action(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        action: ProofGenerationAdminAction,
+        previous_status: ProofGenerationJobStatus,
+        reason: Option<&str>,
+    ) -> DalResult<()> {
+        let target_status = action.target_status().to_string();
+        let clear_proof = action == ProofGenerationAdminAction::InvalidateProof;
+        let mut transaction = self.storage.start_transaction().await?;
+        sqlx::query!(
+            r#"
+            UPDATE proof_generation_details
+            SET
+                status = $2,
+                prover_taken_at = NULL,
+                proof_blob_url = CASE
+                    WHEN $3 THEN NULL
+                    ELSE proof_blob_url
+                END,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0),
+            target_status,
+            clear_proof
+        )
+        .instrument("apply_admin_action#update")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("action", &action)
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            proof_generation_admin_actions (l1_batch_number, action, previous_status, reason, created_at)
+            VALUES
+            ($1, $2, $3, $4, NOW())
+            "#,
+            i64::from(l1_batch_number.0),
+            action.to_string(),
+            previous_status.to_string(),
+            reason
+        )
+        .instrument("apply_admin_action#insert")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("action", &action)
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await
+    }
+
+    /// Returns the audit log of operator actions for the specified L1 batch, oldest first.
+    pub async fn get_admin_actions(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<ProofGenerationAdminActionRecord>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                action,
+                previous_status,
+                reason,
+                created_at
+            FROM
+                proof_generation_admin_actions
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                id
+            "#,
+            i64::from(l1_batch_number.0),
+        )
+        .instrument("get_admin_actions")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let action = row.action.parse();
+                let previous_status = row.previous_status.parse();
+                let (Ok(action), Ok(previous_status)) = (action, previous_status) else {
+                    let err = Instrumented::new("get_admin_actions")
+                        .with_arg("l1_batch_number", &l1_batch_number)
+                        .constraint_error(anyhow::anyhow!(
+                            "invalid admin action record: action = {:?}, previous_status = {:?}",
+                            row.action,
+                            row.previous_status
+                        ));
+                    return Err(err);
+                };
+                Ok(ProofGenerationAdminActionRecord {
+                    l1_batch_number,
+                    action,
+                    previous_status,
+                    reason: row.reason,
+                    created_at: row.created_at,
+                })
+            })
+            .collect()
+    }
+
+    /// Records the proof sampling decision for an L1 batch. If a decision is already recorded, it is left intact.
+    pub async fn save_sampling_decision(
+        &mut self,
+        decision: &ProofSamplingDecision,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            proof_sampling_decisions (
+                l1_batch_number, sampled, reason, commitment, seed, sampling_rate, created_at
+            )
+            VALUES
+            ($1, $2, $3, $4, $5, $6, NOW())
+            ON CONFLICT (l1_batch_number) DO NOTHING
+            "#,
+            i64::from(decision.l1_batch_number.0),
+            decision.sampled,
+            decision.reason.to_string(),
+            decision.commitment.as_bytes(),
+            decision.seed.as_bytes(),
+            decision.sampling_rate
+        )
+        .instrument("save_sampling_decision")
+        .with_arg("decision", decision)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the recorded proof sampling decision for the specified L1 batch.
+    pub async fn get_sampling_decision(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<ProofSamplingDecision>> {
+        let Some(row) = sqlx::query!(
+            r#"
+            SELECT
+                sampled,
+                reason,
+                commitment,
+                seed,
+                sampling_rate
+            FROM
+                proof_sampling_decisions
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0),
+        )
+        .instrument("get_sampling_decision")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let reason = row.reason.parse().map_err(|_| {
+            Instrumented::new("get_sampling_decision")
+                .with_arg("l1_batch_number", &l1_batch_number)
+                .constraint_error(anyhow::anyhow!(
+                    "invalid proof sampling reason: {:?}",
+                    row.reason
+                ))
+        })?;
+        Ok(Some(ProofSamplingDecision {
+            l1_batch_number,
+            sampled: row.sampled,
+            reason,
+            commitment: H256::from_slice(&row.commitment),
+            seed: H256::from_slice(&row.seed),
+            sampling_rate: row.sampling_rate,
+        }))
+    }
+
+    /// Saves proving statistics reported by the prover subsystem for an L1 batch. Returns `false` if the batch
+    /// has no proof generation details.
+    pub async fn save_proving_stats(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        base_layer_circuits: u32,
+        proving_time: Duration,
+    ) -> DalResult<bool> {
+        let proving_time_ms = i64::try_from(proving_time.as_millis()).unwrap_or(i64::MAX);
+        let result = sqlx::query!(
+            r#"
+            UPDATE proof_generation_details
+            SET
+                base_layer_circuits = $2,
+                proving_time_ms = $3,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0),
+            base_layer_circuits as i32,
+            proving_time_ms
+        )
+        .instrument("save_proving_stats")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("base_layer_circuits", &base_layer_circuits)
+        .with_arg("proving_time", &proving_time)
+        .execute(self.storage)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Returns proving statistics for at most `limit` latest L1 batches that have them, in the descending
+    /// batch number order.
+    pub async fn get_latest_proving_stats(
+        &mut self,
+        limit: usize,
+    ) -> DalResult<Vec<BatchProvingStats>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                base_layer_circuits AS "base_layer_circuits!",
+                proving_time_ms AS "proving_time_ms!"
+            FROM
+                proof_generation_details
+            WHERE
+                base_layer_circuits IS NOT NULL
+                AND proving_time_ms IS NOT NULL
+            ORDER BY
+                l1_batch_number DESC
+            LIMIT
+                $1
+            "#,
+            limit as i64
+        )
+        .instrument("get_latest_proving_stats")
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| BatchProvingStats {
+                l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+                base_layer_circuits: row.base_layer_circuits as u32,
+                proving_time: Duration::from_millis(row.proving_time_ms as u64),
+            })
+            .collect())
+    }
+
     pub async fn get_oldest_unpicked_batch(&mut self) -> DalResult<Option<L1BatchNumber>> {
         let result: Option<L1BatchNumber> = sqlx::query!(
             r#"
@@ -491,4 +947,121 @@ mod tests {
             .unwrap();
         assert_eq!(unpicked_l1_batch, None);
     }
+
+    #[tokio::test]
+    async fn saving_sampling_decision() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch_header(1))
+            .await
+            .unwrap();
+
+        let decision = conn
+            .proof_generation_dal()
+            .get_sampling_decision(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(decision, None);
+
+        let decision = ProofSamplingDecision {
+            l1_batch_number: L1BatchNumber(1),
+            sampled: false,
+            reason: ProofSamplingReason::Rate,
+            commitment: H256::repeat_byte(1),
+            seed: H256::repeat_byte(2),
+            sampling_rate: 0.25,
+        };
+        conn.proof_generation_dal()
+            .save_sampling_decision(&decision)
+            .await
+            .unwrap();
+        // The decision must not be overwritten.
+        conn.proof_generation_dal()
+            .save_sampling_decision(&ProofSamplingDecision {
+                sampled: true,
+                reason: ProofSamplingReason::AlwaysProveRange,
+                ..decision.clone()
+            })
+            .await
+            .unwrap();
+
+        let saved_decision = conn
+            .proof_generation_dal()
+            .get_sampling_decision(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(saved_decision, Some(decision));
+    }
+
+    #[tokio::test]
+    async fn saving_proving_stats() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in 1..=3 {
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&create_l1_batch_header(number))
+                .await
+                .unwrap();
+            conn.proof_generation_dal()
+                .insert_proof_generation_details(L1BatchNumber(number))
+                .await
+                .unwrap();
+        }
+
+        let saved = conn
+            .proof_generation_dal()
+            .save_proving_stats(L1BatchNumber(5), 100, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(!saved);
+        for number in [1, 3] {
+            let saved = conn
+                .proof_generation_dal()
+                .save_proving_stats(
+                    L1BatchNumber(number),
+                    number * 100,
+                    Duration::from_secs(number.into()),
+                )
+                .await
+                .unwrap();
+            assert!(saved);
+        }
+
+        let stats = conn
+            .proof_generation_dal()
+            .get_latest_proving_stats(10)
+            .await
+            .unwrap();
+        assert_eq!(
+            stats,
+            [
+                BatchProvingStats {
+                    l1_batch_number: L1BatchNumber(3),
+                    base_layer_circuits: 300,
+                    proving_time: Duration::from_secs(3),
+                },
+                BatchProvingStats {
+                    l1_batch_number: L1BatchNumber(1),
+                    base_layer_circuits: 100,
+                    proving_time: Duration::from_secs(1),
+                },
+            ]
+        );
+        let stats = conn
+            .proof_generation_dal()
+            .get_latest_proving_stats(1)
+            .await
+            .unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].l1_batch_number, L1BatchNumber(3));
+    }
 }
@@ -0,0 +1,163 @@
+use chrono::{DateTime, Utc};
+use zksync_db_connection::{
+    connection::Connection,
+    error::{DalResult, SqlxContext},
+    instrument::InstrumentExt,
+};
+use zksync_types::{
+    api::{ProtocolUpgradeDryRun, SystemContractDiff},
+    L1BatchNumber, ProtocolVersionId, H256,
+};
+
+use crate::Core;
+
+/// Storage of protocol upgrade transaction dry runs.
+#[derive(Debug)]
+pub struct ProtocolUpgradeDryRunsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl ProtocolUpgradeDryRunsDal<'_, '_> {
+    /// Inserts a dry run result, overwriting the previous result for the same protocol version.
+    pub async fn insert_dry_run(
+        &mut self,
+        protocol_version: ProtocolVersionId,
+        upgrade_tx_hash: H256,
+        base_l1_batch_number: L1BatchNumber,
+        error: Option<&str>,
+        system_contract_diffs: &[SystemContractDiff],
+    ) -> DalResult<()> {
+        let system_contract_diffs = serde_json::to_value(system_contract_diffs)
+            .expect("failed serializing system contract diffs");
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            protocol_upgrade_dry_runs (
+                protocol_version,
+                upgrade_tx_hash,
+                base_l1_batch_number,
+                error,
+                system_contract_diffs,
+                created_at
+            )
+            VALUES
+            ($1, $2, $3, $4, $5, NOW())
+            ON CONFLICT (protocol_version) DO
+            UPDATE
+            SET
+            upgrade_tx_hash = excluded.upgrade_tx_hash,
+            base_l1_batch_number = excluded.base_l1_batch_number,
+            error = excluded.error,
+            system_contract_diffs = excluded.system_contract_diffs,
+            created_at = excluded.created_at
+            "#,
+            protocol_version as i32,
+            upgrade_tx_hash.as_bytes(),
+            i64::from(base_l1_batch_number.0),
+            error,
+            system_contract_diffs
+        )
+        .instrument("insert_protocol_upgrade_dry_run")
+        .with_arg("protocol_version", &protocol_version)
+        .with_arg("base_l1_batch_number", &base_l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_dry_run(
+        &mut self,
+        protocol_version: ProtocolVersionId,
+    ) -> DalResult<Option<ProtocolUpgradeDryRun>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                upgrade_tx_hash,
+                base_l1_batch_number,
+                error,
+                system_contract_diffs,
+                created_at
+            FROM
+                protocol_upgrade_dry_runs
+            WHERE
+                protocol_version = $1
+            "#,
+            protocol_version as i32
+        )
+        .instrument("get_protocol_upgrade_dry_run")
+        .with_arg("protocol_version", &protocol_version)
+        .fetch_optional(self.storage)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let system_contract_diffs = serde_json::from_value(row.system_contract_diffs)
+            .decode_column("system_contract_diffs")?;
+        Ok(Some(ProtocolUpgradeDryRun {
+            protocol_version,
+            upgrade_tx_hash: H256::from_slice(&row.upgrade_tx_hash),
+            base_l1_batch_number: L1BatchNumber(row.base_l1_batch_number as u32),
+            error: row.error,
+            system_contract_diffs,
+            created_at: DateTime::<Utc>::from_naive_utc_and_offset(row.created_at, Utc),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{Address, ProtocolVersion};
+
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn inserting_and_querying_dry_runs() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let version = ProtocolVersion::default();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&version)
+            .await
+            .unwrap();
+        let protocol_version = version.version.minor;
+
+        let mut dal = conn.protocol_upgrade_dry_runs_dal();
+        assert_eq!(dal.get_dry_run(protocol_version).await.unwrap(), None);
+
+        let diffs = [SystemContractDiff {
+            address: Address::repeat_byte(1),
+            previous_code_hash: H256::zero(),
+            new_code_hash: H256::repeat_byte(2),
+        }];
+        dal.insert_dry_run(
+            protocol_version,
+            H256::repeat_byte(3),
+            L1BatchNumber(5),
+            None,
+            &diffs,
+        )
+        .await
+        .unwrap();
+        let dry_run = dal.get_dry_run(protocol_version).await.unwrap().unwrap();
+        assert_eq!(dry_run.upgrade_tx_hash, H256::repeat_byte(3));
+        assert_eq!(dry_run.base_l1_batch_number, L1BatchNumber(5));
+        assert_eq!(dry_run.error, None);
+        assert_eq!(dry_run.system_contract_diffs, diffs);
+
+        dal.insert_dry_run(
+            protocol_version,
+            H256::repeat_byte(3),
+            L1BatchNumber(6),
+            Some("reverted"),
+            &[],
+        )
+        .await
+        .unwrap();
+        let dry_run = dal.get_dry_run(protocol_version).await.unwrap().unwrap();
+        assert_eq!(dry_run.base_l1_batch_number, L1BatchNumber(6));
+        assert_eq!(dry_run.error.as_deref(), Some("reverted"));
+        assert!(dry_run.system_contract_diffs.is_empty());
+    }
+}
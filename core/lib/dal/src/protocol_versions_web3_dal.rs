@@ -1,5 +1,11 @@
+use std::collections::HashMap;
+
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
-use zksync_types::api::ProtocolVersion;
+use zksync_types::{
+    api::{ProtocolVersion, ProtocolVersionActivation, ProtocolVersionPatch},
+    protocol_version::L1VerifierConfig,
+    L1BatchNumber, H256,
+};
 
 use crate::{models::storage_protocol_version::StorageApiProtocolVersion, Core, CoreDal};
 
@@ -38,6 +44,98 @@ impl ProtocolVersionsWeb3Dal<'_, '_> {
         Ok(storage_protocol_version.map(ProtocolVersion::from))
     }
 
+    /// Returns all protocol versions known to the node in the ascending order, together with their activation info.
+    pub async fn get_protocol_version_activations(
+        &mut self,
+    ) -> DalResult<Vec<ProtocolVersionActivation>> {
+        let patch_rows = sqlx::query!(
+            r#"
+            SELECT
+                minor,
+                patch,
+                snark_wrapper_vk_hash,
+                fflonk_snark_wrapper_vk_hash
+            FROM
+                protocol_patches
+            ORDER BY
+                minor,
+                patch
+            "#
+        )
+        .instrument("get_protocol_version_activations#patches")
+        .fetch_all(self.storage)
+        .await?;
+
+        let mut patches = HashMap::<i32, Vec<_>>::new();
+        for row in patch_rows {
+            patches
+                .entry(row.minor)
+                .or_default()
+                .push(ProtocolVersionPatch {
+                    patch: row.patch as u32,
+                    verification_keys_hashes: L1VerifierConfig {
+                        snark_wrapper_vk_hash: H256::from_slice(&row.snark_wrapper_vk_hash),
+                        fflonk_snark_wrapper_vk_hash: row
+                            .fflonk_snark_wrapper_vk_hash
+                            .as_deref()
+                            .map(H256::from_slice),
+                    },
+                });
+        }
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                protocol_versions.id,
+                protocol_versions.timestamp,
+                protocol_versions.bootloader_code_hash,
+                protocol_versions.default_account_code_hash,
+                protocol_versions.evm_emulator_code_hash,
+                protocol_versions.upgrade_tx_hash,
+                first_batch.number AS "first_l1_batch_number?",
+                first_batch.timestamp AS "first_l1_batch_timestamp?"
+            FROM
+                protocol_versions
+            LEFT JOIN LATERAL (
+                SELECT
+                    number,
+                    timestamp
+                FROM
+                    l1_batches
+                WHERE
+                    l1_batches.protocol_version = protocol_versions.id
+                    AND is_sealed
+                ORDER BY
+                    number
+                LIMIT
+                    1
+            ) first_batch ON TRUE
+            ORDER BY
+                protocol_versions.id
+            "#
+        )
+        .instrument("get_protocol_version_activations")
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ProtocolVersionActivation {
+                minor_version: row.id as u16,
+                timestamp: row.timestamp as u64,
+                first_l1_batch: row
+                    .first_l1_batch_number
+                    .map(|number| L1BatchNumber(number as u32)),
+                first_l1_batch_timestamp: row.first_l1_batch_timestamp.map(|ts| ts as u64),
+                bootloader_code_hash: H256::from_slice(&row.bootloader_code_hash),
+                default_account_code_hash: H256::from_slice(&row.default_account_code_hash),
+                evm_emulator_code_hash: row.evm_emulator_code_hash.as_deref().map(H256::from_slice),
+                l2_system_upgrade_tx_hash: row.upgrade_tx_hash.as_deref().map(H256::from_slice),
+                patches: patches.remove(&row.id).unwrap_or_default(),
+            })
+            .collect())
+    }
+
     pub async fn get_latest_protocol_version(&mut self) -> DalResult<ProtocolVersion> {
         let latest_version = self
             .storage
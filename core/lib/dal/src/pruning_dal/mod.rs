@@ -49,6 +49,28 @@ pub struct HardPruningStats {
     pub deleted_events: u64,
     pub deleted_call_traces: u64,
     pub deleted_l2_to_l1_logs: u64,
+    pub deleted_soft_confirmations: u64,
+}
+
+/// Kind of data that can be removed from Postgres according to a retention policy, independently of
+/// the L1 batch pruning performed by [`PruningDal::hard_prune_batches_range()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetainedDataKind {
+    /// Call traces returned by `debug_traceTransaction` and similar methods.
+    CallTraces,
+    /// Events emitted by transactions.
+    Events,
+}
+
+impl RetainedDataKind {
+    pub const ALL: [Self; 2] = [Self::CallTraces, Self::Events];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CallTraces => "call_traces",
+            Self::Events => "events",
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -226,6 +248,9 @@ impl PruningDal<'_, '_> {
         let deleted_call_traces = self
             .delete_call_traces(first_l2_block_to_prune..=last_l2_block_to_prune)
             .await?;
+        let deleted_soft_confirmations = self
+            .delete_soft_confirmations(first_l2_block_to_prune..=last_l2_block_to_prune)
+            .await?;
         self.clear_transaction_fields(first_l2_block_to_prune..=last_l2_block_to_prune)
             .await?;
 
@@ -242,6 +267,7 @@ impl PruningDal<'_, '_> {
             deleted_l2_to_l1_logs,
             deleted_call_traces,
             deleted_storage_logs,
+            deleted_soft_confirmations,
         };
         Ok(stats)
     }
@@ -319,6 +345,27 @@ impl PruningDal<'_, '_> {
         Ok(execution_result.rows_affected())
     }
 
+    async fn delete_soft_confirmations(
+        &mut self,
+        l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<u64> {
+        let execution_result = sqlx::query!(
+            r#"
+            DELETE FROM soft_confirmations
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            "#,
+            i64::from(l2_blocks_to_prune.start().0),
+            i64::from(l2_blocks_to_prune.end().0)
+        )
+        .instrument("hard_prune_batches_range#delete_soft_confirmations")
+        .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
     // The pruned fields are accessed as follows:
     //
     // - `input`: is a part of `StorageTransaction`, read via `TransactionsDal` (`get_l2_blocks_to_reexecute`,
@@ -437,6 +484,71 @@ impl PruningDal<'_, '_> {
         Ok(execution_result.rows_affected())
     }
 
+    /// Returns the last L1 batch for which data of the specified kind was removed according to a retention policy.
+    pub async fn get_last_retention_pruned_l1_batch(
+        &mut self,
+        kind: RetainedDataKind,
+    ) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                last_pruned_l1_batch
+            FROM
+                data_retention_log
+            WHERE
+                kind = $1
+            "#,
+            kind.as_str()
+        )
+        .instrument("get_last_retention_pruned_l1_batch")
+        .with_arg("kind", &kind)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| L1BatchNumber(row.last_pruned_l1_batch as u32)))
+    }
+
+    /// Removes data of the specified kind for the specified range of L2 blocks. Does not update the data retention log;
+    /// the caller is responsible to do this using [`Self::insert_retention_log()`].
+    pub async fn prune_retained_data(
+        &mut self,
+        kind: RetainedDataKind,
+        l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<u64> {
+        match kind {
+            RetainedDataKind::CallTraces => self.delete_call_traces(l2_blocks_to_prune).await,
+            RetainedDataKind::Events => self.delete_events(l2_blocks_to_prune).await,
+        }
+    }
+
+    pub async fn insert_retention_log(
+        &mut self,
+        kind: RetainedDataKind,
+        last_pruned_l1_batch: L1BatchNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            data_retention_log (kind, last_pruned_l1_batch, created_at, updated_at)
+            VALUES
+            ($1, $2, NOW(), NOW())
+            ON CONFLICT (kind) DO
+            UPDATE
+            SET
+            last_pruned_l1_batch = excluded.last_pruned_l1_batch,
+            updated_at = NOW()
+            "#,
+            kind.as_str(),
+            i64::from(last_pruned_l1_batch.0)
+        )
+        .instrument("insert_retention_log")
+        .with_arg("kind", &kind)
+        .with_arg("last_pruned_l1_batch", &last_pruned_l1_batch)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     pub async fn insert_hard_pruning_log(
         &mut self,
         last_l1_batch_to_prune: L1BatchNumber,
@@ -2,8 +2,9 @@ use std::ops;
 
 use zksync_db_connection::connection::Connection;
 use zksync_types::{
-    tx::IncludedTxLocation, AccountTreeId, L1BatchNumber, L2BlockNumber, L2ChainId,
-    ProtocolVersion, ProtocolVersionId, StorageKey, StorageLog, H256,
+    soft_confirmation::SoftConfirmationCommitment, tx::IncludedTxLocation, AccountTreeId,
+    K256PrivateKey, L1BatchNumber, L2BlockNumber, L2ChainId, ProtocolVersion, ProtocolVersionId,
+    StorageKey, StorageLog, H256,
 };
 use zksync_vm_interface::{tracer::ValidationTraces, TransactionExecutionMetrics};
 
@@ -432,6 +433,49 @@ async fn l1_batches_can_be_hard_pruned() {
     assert_l1_batches_not_exist(&mut transaction, L1BatchNumber(1)..=L1BatchNumber(9)).await;
 }
 
+#[tokio::test]
+async fn soft_confirmations_are_hard_pruned() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_realistic_l1_batches(&mut conn, 10).await;
+
+    let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(0x11)).unwrap();
+    let confirmations: Vec<_> = (0..20_u32)
+        .map(|number| {
+            let commitment = SoftConfirmationCommitment {
+                tx_hash: H256::from_low_u64_be(number.into()),
+                l2_block_number: L2BlockNumber(number),
+                index_in_block: 0,
+            };
+            (commitment, commitment.sign(&private_key).unwrap())
+        })
+        .collect();
+    conn.soft_confirmations_dal()
+        .insert_soft_confirmations(private_key.address(), &confirmations)
+        .await
+        .unwrap();
+
+    let stats = conn
+        .pruning_dal()
+        .hard_prune_batches_range(L1BatchNumber(5), L2BlockNumber(11))
+        .await
+        .unwrap();
+    assert_eq!(stats.deleted_soft_confirmations, 12);
+
+    for (commitment, _) in &confirmations {
+        let confirmation = conn
+            .soft_confirmations_dal()
+            .get_soft_confirmation(commitment.tx_hash)
+            .await
+            .unwrap();
+        assert_eq!(
+            confirmation.is_some(),
+            commitment.l2_block_number > L2BlockNumber(11),
+            "{commitment:?}"
+        );
+    }
+}
+
 #[tokio::test]
 async fn transactions_are_handled_correctly_after_pruning() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -496,3 +540,115 @@ async fn transactions_are_handled_correctly_after_pruning() {
         .unwrap();
     assert!(transaction_details.is_none(), "{transaction_details:?}");
 }
+
+#[tokio::test]
+async fn retained_data_can_be_pruned() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_realistic_l1_batches(&mut conn, 5).await;
+
+    let mut dal = conn.pruning_dal();
+    for kind in RetainedDataKind::ALL {
+        let last_pruned = dal.get_last_retention_pruned_l1_batch(kind).await.unwrap();
+        assert_eq!(last_pruned, None);
+    }
+
+    let deleted_events = dal
+        .prune_retained_data(
+            RetainedDataKind::Events,
+            L2BlockNumber(0)..=L2BlockNumber(3),
+        )
+        .await
+        .unwrap();
+    assert_eq!(deleted_events, 20);
+    dal.insert_retention_log(RetainedDataKind::Events, L1BatchNumber(1))
+        .await
+        .unwrap();
+    let last_pruned = dal
+        .get_last_retention_pruned_l1_batch(RetainedDataKind::Events)
+        .await
+        .unwrap();
+    assert_eq!(last_pruned, Some(L1BatchNumber(1)));
+
+    dal.insert_retention_log(RetainedDataKind::Events, L1BatchNumber(3))
+        .await
+        .unwrap();
+    let last_pruned = dal
+        .get_last_retention_pruned_l1_batch(RetainedDataKind::Events)
+        .await
+        .unwrap();
+    assert_eq!(last_pruned, Some(L1BatchNumber(3)));
+    let last_pruned = dal
+        .get_last_retention_pruned_l1_batch(RetainedDataKind::CallTraces)
+        .await
+        .unwrap();
+    assert_eq!(last_pruned, None);
+
+    // Retention pruning must not affect L1 batches and L2 blocks.
+    assert_l1_batches_exist(&mut conn, L1BatchNumber(1)..=L1BatchNumber(4)).await;
+    let events = conn
+        .events_web3_dal()
+        .get_all_logs(L2BlockNumber(0))
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 30);
+    assert!(
+        events
+            .iter()
+            .all(|log| log.block_number.unwrap() >= 4.into()),
+        "{events:?}"
+    );
+}
+
+#[tokio::test]
+async fn raw_transactions_are_available_after_retention_pruning() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    conn.protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+
+    let l2_block_header = create_l2_block_header(1);
+    let tx = mock_l2_transaction();
+    conn.transactions_dal()
+        .insert_transaction_l2(
+            &tx,
+            TransactionExecutionMetrics::default(),
+            ValidationTraces::default(),
+        )
+        .await
+        .unwrap();
+    conn.blocks_dal()
+        .insert_l2_block(&l2_block_header)
+        .await
+        .unwrap();
+    conn.transactions_dal()
+        .mark_txs_as_executed_in_l2_block(
+            L2BlockNumber(1),
+            &[mock_execution_result(tx.clone())],
+            1.into(),
+            ProtocolVersionId::latest(),
+            false,
+        )
+        .await
+        .unwrap();
+
+    for kind in RetainedDataKind::ALL {
+        conn.pruning_dal()
+            .prune_retained_data(kind, L2BlockNumber(1)..=L2BlockNumber(1))
+            .await
+            .unwrap();
+    }
+
+    // Raw transactions are used by `zks_getRawBlockTransactions`, external node syncing and consensus,
+    // so retention pruning must not affect them.
+    let raw_transactions = conn
+        .transactions_web3_dal()
+        .get_raw_l2_block_transactions(L2BlockNumber(1))
+        .await
+        .unwrap();
+    assert_eq!(raw_transactions.len(), 1);
+    assert_eq!(raw_transactions[0].hash(), tx.hash());
+    assert_eq!(raw_transactions[0].execute, tx.execute);
+}
@@ -0,0 +1,235 @@
+use std::time::Duration;
+
+use zksync_db_connection::{
+    connection::Connection, error::DalResult, instrument::InstrumentExt,
+    utils::pg_interval_from_duration,
+};
+use zksync_types::api;
+
+use crate::Core;
+
+/// DAL for the lease that allows a single sequencer instance to seal L2 blocks and L1 batches.
+///
+/// The lease is held by one instance at a time; its epoch is incremented each time the lease changes hands.
+/// The epoch is used as a fencing token: the holder checks it when sealing data, so that a former holder
+/// cannot persist anything after the lease was taken over.
+#[derive(Debug)]
+pub struct SequencerLeaseDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl SequencerLeaseDal<'_, '_> {
+    /// Acquires or renews the lease for the specified instance. The lease can be acquired if it's vacant,
+    /// expired, or is already held by the instance. Returns the lease epoch, or `None` if the lease is held
+    /// by another instance.
+    pub async fn try_acquire_lease(
+        &mut self,
+        instance_id: &str,
+        ttl: Duration,
+    ) -> DalResult<Option<u64>> {
+        let ttl = pg_interval_from_duration(ttl);
+        let epoch = sqlx::query_scalar!(
+            r#"
+            INSERT INTO
+            sequencer_lease (fake_key, instance_id, epoch, expires_at, updated_at)
+            VALUES
+            (TRUE, $1, 1, NOW() + $2::INTERVAL, NOW())
+            ON CONFLICT (fake_key) DO
+            UPDATE
+            SET
+            epoch = CASE
+                WHEN sequencer_lease.instance_id = excluded.instance_id THEN sequencer_lease.epoch
+                ELSE sequencer_lease.epoch + 1
+            END,
+            instance_id = excluded.instance_id,
+            expires_at = excluded.expires_at,
+            updated_at = NOW()
+            WHERE
+            sequencer_lease.instance_id = excluded.instance_id
+            OR sequencer_lease.expires_at < NOW()
+            RETURNING
+            epoch
+            "#,
+            instance_id,
+            ttl
+        )
+        .instrument("try_acquire_lease")
+        .with_arg("instance_id", &instance_id)
+        .with_arg("ttl", &ttl)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(epoch.map(|epoch| epoch as u64))
+    }
+
+    /// Unconditionally transfers the lease to the specified instance, incrementing its epoch. This is used
+    /// to promote a standby sequencer. Returns the new lease epoch.
+    pub async fn transfer_lease(&mut self, instance_id: &str, ttl: Duration) -> DalResult<u64> {
+        let ttl = pg_interval_from_duration(ttl);
+        let epoch = sqlx::query_scalar!(
+            r#"
+            INSERT INTO
+            sequencer_lease (fake_key, instance_id, epoch, expires_at, updated_at)
+            VALUES
+            (TRUE, $1, 1, NOW() + $2::INTERVAL, NOW())
+            ON CONFLICT (fake_key) DO
+            UPDATE
+            SET
+            epoch = sequencer_lease.epoch + 1,
+            instance_id = excluded.instance_id,
+            expires_at = excluded.expires_at,
+            updated_at = NOW()
+            RETURNING
+            epoch
+            "#,
+            instance_id,
+            ttl
+        )
+        .instrument("transfer_lease")
+        .with_arg("instance_id", &instance_id)
+        .with_arg("ttl", &ttl)
+        .fetch_one(self.storage)
+        .await?;
+        Ok(epoch as u64)
+    }
+
+    /// Checks whether the lease is held by the specified instance with the specified epoch. If called in a transaction,
+    /// the lease row is locked until the transaction ends, so that the lease cannot change hands concurrently.
+    pub async fn holds_lease(&mut self, instance_id: &str, epoch: u64) -> DalResult<bool> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                epoch
+            FROM
+                sequencer_lease
+            WHERE
+                instance_id = $1
+                AND epoch = $2
+            FOR SHARE
+            "#,
+            instance_id,
+            epoch as i64
+        )
+        .instrument("holds_lease")
+        .with_arg("instance_id", &instance_id)
+        .with_arg("epoch", &epoch)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.is_some())
+    }
+
+    /// Returns the current lease, if any.
+    pub async fn get_lease(&mut self) -> DalResult<Option<api::SequencerLease>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                instance_id,
+                epoch,
+                expires_at
+            FROM
+                sequencer_lease
+            "#
+        )
+        .instrument("get_lease")
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| api::SequencerLease {
+            instance_id: row.instance_id,
+            epoch: row.epoch as u64,
+            expires_at: row.expires_at.and_utc(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn acquiring_and_transferring_lease() {
+        const TTL: Duration = Duration::from_secs(60);
+
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        assert_eq!(conn.sequencer_lease_dal().get_lease().await.unwrap(), None);
+
+        let epoch = conn
+            .sequencer_lease_dal()
+            .try_acquire_lease("primary", TTL)
+            .await
+            .unwrap();
+        assert_eq!(epoch, Some(1));
+        // Renewing the lease doesn't change the epoch.
+        let epoch = conn
+            .sequencer_lease_dal()
+            .try_acquire_lease("primary", TTL)
+            .await
+            .unwrap();
+        assert_eq!(epoch, Some(1));
+        let epoch = conn
+            .sequencer_lease_dal()
+            .try_acquire_lease("standby", TTL)
+            .await
+            .unwrap();
+        assert_eq!(epoch, None);
+        assert!(conn
+            .sequencer_lease_dal()
+            .holds_lease("primary", 1)
+            .await
+            .unwrap());
+        assert!(!conn
+            .sequencer_lease_dal()
+            .holds_lease("standby", 1)
+            .await
+            .unwrap());
+
+        let epoch = conn
+            .sequencer_lease_dal()
+            .transfer_lease("standby", TTL)
+            .await
+            .unwrap();
+        assert_eq!(epoch, 2);
+        assert!(!conn
+            .sequencer_lease_dal()
+            .holds_lease("primary", 1)
+            .await
+            .unwrap());
+        assert!(conn
+            .sequencer_lease_dal()
+            .holds_lease("standby", 2)
+            .await
+            .unwrap());
+        let epoch = conn
+            .sequencer_lease_dal()
+            .try_acquire_lease("primary", TTL)
+            .await
+            .unwrap();
+        assert_eq!(epoch, None);
+
+        let lease = conn.sequencer_lease_dal().get_lease().await.unwrap();
+        let lease = lease.expect("no lease");
+        assert_eq!(lease.instance_id, "standby");
+        assert_eq!(lease.epoch, 2);
+    }
+
+    #[tokio::test]
+    async fn acquiring_expired_lease() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+
+        let epoch = conn
+            .sequencer_lease_dal()
+            .try_acquire_lease("primary", Duration::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(epoch, Some(1));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let epoch = conn
+            .sequencer_lease_dal()
+            .try_acquire_lease("standby", Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(epoch, Some(2));
+    }
+}
@@ -109,6 +109,77 @@ impl SnapshotsCreatorDal<'_, '_> {
         Ok(storage_logs)
     }
 
+    /// Constructs a differential `storage_logs` chunk containing the latest values of the storage slots that were written to
+    /// in `(base_l2_block_number..=l2_block_number]` L2 blocks. `l2_block_number` MUST be the last L2 block
+    /// of the `l1_batch_number` batch.
+    pub async fn get_storage_logs_chunk_diff(
+        &mut self,
+        base_l2_block_number: L2BlockNumber,
+        l2_block_number: L2BlockNumber,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys_range: std::ops::RangeInclusive<H256>,
+    ) -> DalResult<Vec<SnapshotStorageLog>> {
+        // Phantom writes are filtered out in the same way as in `get_storage_logs_chunk()`.
+        let storage_logs = sqlx::query!(
+            r#"
+            SELECT
+                storage_logs.hashed_key AS "hashed_key!",
+                storage_logs.value AS "value!",
+                storage_logs.miniblock_number AS "miniblock_number!",
+                initial_writes.l1_batch_number AS "l1_batch_number!",
+                initial_writes.index
+            FROM
+                (
+                    SELECT
+                        hashed_key,
+                        MAX(ARRAY[miniblock_number, operation_number]::INT []) AS op
+                    FROM
+                        storage_logs
+                    WHERE
+                        miniblock_number > $1
+                        AND miniblock_number <= $2
+                        AND hashed_key >= $4
+                        AND hashed_key <= $5
+                    GROUP BY
+                        hashed_key
+                    ORDER BY
+                        hashed_key
+                ) AS keys
+            INNER JOIN storage_logs
+                ON
+                    keys.hashed_key = storage_logs.hashed_key
+                    AND storage_logs.miniblock_number = keys.op[1]
+                    AND storage_logs.operation_number = keys.op[2]
+            INNER JOIN initial_writes ON keys.hashed_key = initial_writes.hashed_key
+            WHERE
+                initial_writes.l1_batch_number <= $3
+            "#,
+            i64::from(base_l2_block_number.0),
+            i64::from(l2_block_number.0),
+            i64::from(l1_batch_number.0),
+            hashed_keys_range.start().as_bytes(),
+            hashed_keys_range.end().as_bytes()
+        )
+        .instrument("get_storage_logs_chunk_diff")
+        .with_arg("base_l2_block_number", &base_l2_block_number)
+        .with_arg("l2_block_number", &l2_block_number)
+        .with_arg("min_hashed_key", &hashed_keys_range.start())
+        .with_arg("max_hashed_key", &hashed_keys_range.end())
+        .report_latency()
+        .expect_slow_query()
+        .fetch_all(self.storage)
+        .await?
+        .iter()
+        .map(|row| SnapshotStorageLog {
+            key: H256::from_slice(&row.hashed_key),
+            value: H256::from_slice(&row.value),
+            l1_batch_number_of_initial_write: L1BatchNumber(row.l1_batch_number as u32),
+            enumeration_index: row.index as u64,
+        })
+        .collect();
+        Ok(storage_logs)
+    }
+
     /// Same as [`Self::get_storage_logs_chunk()`], but returns full keys.
     #[deprecated(
         note = "will fail if called on a node restored from a v1 snapshot; use `get_storage_logs_chunk()` instead"
@@ -208,6 +279,39 @@ impl SnapshotsCreatorDal<'_, '_> {
             .map(|row| (H256::from_slice(&row.bytecode_hash), row.bytecode))
             .collect())
     }
+
+    /// Returns factory dependencies added in `(base_l2_block_number..=l2_block_number]` L2 blocks.
+    pub async fn get_factory_deps_diff(
+        &mut self,
+        base_l2_block_number: L2BlockNumber,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<Vec<(H256, Vec<u8>)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                bytecode_hash,
+                bytecode
+            FROM
+                factory_deps
+            WHERE
+                miniblock_number > $1
+                AND miniblock_number <= $2
+            "#,
+            i64::from(base_l2_block_number.0),
+            i64::from(l2_block_number.0),
+        )
+        .instrument("get_factory_deps_diff")
+        .with_arg("base_l2_block_number", &base_l2_block_number)
+        .with_arg("l2_block_number", &l2_block_number)
+        .report_latency()
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (H256::from_slice(&row.bytecode_hash), row.bytecode))
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -289,6 +393,26 @@ mod tests {
             .unwrap();
         assert_eq!(log_row_count, (logs.len() + all_new_logs_len) as u64);
         assert_logs_for_snapshot(&mut conn, L2BlockNumber(1), L1BatchNumber(1), &logs).await;
+
+        // A differential chunk should contain only the logs from the new L2 block.
+        let mut diff_logs = conn
+            .snapshots_creator_dal()
+            .get_storage_logs_chunk_diff(
+                L2BlockNumber(1),
+                L2BlockNumber(2),
+                L1BatchNumber(2),
+                H256::zero()..=H256::repeat_byte(0xff),
+            )
+            .await
+            .unwrap();
+        assert_eq!(diff_logs.len(), all_new_logs_len);
+        diff_logs.sort_unstable_by_key(|log| log.key);
+        let mut expected_diff_logs = all_new_logs;
+        expected_diff_logs.sort_unstable_by_key(|log| log.key.hashed_key());
+        for (log, expected_log) in diff_logs.iter().zip(&expected_diff_logs) {
+            assert_eq!(log.key, expected_log.key.hashed_key());
+            assert_eq!(log.value, expected_log.value);
+        }
     }
 
     async fn assert_logs_for_snapshot(
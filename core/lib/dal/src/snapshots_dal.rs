@@ -5,7 +5,7 @@ use zksync_db_connection::{
 };
 use zksync_types::{
     snapshots::{AllSnapshots, SnapshotMetadata, SnapshotVersion},
-    L1BatchNumber,
+    L1BatchNumber, H256,
 };
 
 use crate::Core;
@@ -14,7 +14,9 @@ use crate::Core;
 struct StorageSnapshotMetadata {
     version: i32,
     l1_batch_number: i64,
+    base_l1_batch_number: Option<i64>,
     storage_logs_filepaths: Vec<String>,
+    storage_logs_checksums: Option<Vec<Vec<u8>>>,
     factory_deps_filepath: String,
 }
 
@@ -28,11 +30,32 @@ impl TryFrom<StorageSnapshotMetadata> for SnapshotMetadata {
         Ok(Self {
             version,
             l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            base_l1_batch_number: row
+                .base_l1_batch_number
+                .map(|number| L1BatchNumber(number as u32)),
             storage_logs_filepaths: row
                 .storage_logs_filepaths
                 .into_iter()
                 .map(|path| (!path.is_empty()).then_some(path))
                 .collect(),
+            storage_logs_checksums: row
+                .storage_logs_checksums
+                .unwrap_or_default()
+                .into_iter()
+                .map(|checksum| {
+                    if checksum.is_empty() {
+                        Ok(None)
+                    } else if checksum.len() == 32 {
+                        Ok(Some(H256::from_slice(&checksum)))
+                    } else {
+                        Err(anyhow::anyhow!(
+                            "unexpected checksum length: {}",
+                            checksum.len()
+                        ))
+                    }
+                })
+                .collect::<anyhow::Result<_>>()
+                .decode_column("storage_logs_checksums")?,
             factory_deps_filepath: row.factory_deps_filepath,
         })
     }
@@ -58,12 +81,21 @@ impl SnapshotsDal<'_, '_> {
                 version,
                 l1_batch_number,
                 storage_logs_filepaths,
+                storage_logs_checksums,
                 factory_deps_filepath,
                 created_at,
                 updated_at
             )
             VALUES
-            ($1, $2, ARRAY_FILL(''::TEXT, ARRAY[$3::INTEGER]), $4, NOW(), NOW())
+            (
+                $1,
+                $2,
+                ARRAY_FILL(''::TEXT, ARRAY[$3::INTEGER]),
+                ARRAY_FILL(''::BYTEA, ARRAY[$3::INTEGER]),
+                $4,
+                NOW(),
+                NOW()
+            )
             "#,
             version as i32,
             l1_batch_number.0 as i32,
@@ -79,6 +111,56 @@ impl SnapshotsDal<'_, '_> {
         Ok(())
     }
 
+    /// Adds a differential snapshot based on the snapshot for `base_l1_batch_number`.
+    pub async fn add_differential_snapshot(
+        &mut self,
+        version: SnapshotVersion,
+        l1_batch_number: L1BatchNumber,
+        base_l1_batch_number: L1BatchNumber,
+        storage_logs_chunk_count: u64,
+        factory_deps_filepaths: &str,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            snapshots (
+                version,
+                l1_batch_number,
+                base_l1_batch_number,
+                storage_logs_filepaths,
+                storage_logs_checksums,
+                factory_deps_filepath,
+                created_at,
+                updated_at
+            )
+            VALUES
+            (
+                $1,
+                $2,
+                $3,
+                ARRAY_FILL(''::TEXT, ARRAY[$4::INTEGER]),
+                ARRAY_FILL(''::BYTEA, ARRAY[$4::INTEGER]),
+                $5,
+                NOW(),
+                NOW()
+            )
+            "#,
+            version as i32,
+            l1_batch_number.0 as i32,
+            base_l1_batch_number.0 as i32,
+            storage_logs_chunk_count as i32,
+            factory_deps_filepaths,
+        )
+        .instrument("add_differential_snapshot")
+        .with_arg("version", &version)
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("base_l1_batch_number", &base_l1_batch_number)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     pub async fn add_storage_logs_filepath_for_snapshot(
         &mut self,
         l1_batch_number: L1BatchNumber,
@@ -107,6 +189,41 @@ impl SnapshotsDal<'_, '_> {
         Ok(())
     }
 
+    /// Same as [`Self::add_storage_logs_filepath_for_snapshot()`], but also persists the chunk checksum.
+    /// Used for snapshots with version 2.
+    pub async fn add_storage_logs_chunk_for_snapshot(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        chunk_id: u64,
+        storage_logs_filepath: &str,
+        checksum: H256,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE snapshots
+            SET
+                storage_logs_filepaths[$2] = $3,
+                storage_logs_checksums[$2] = $4,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+            "#,
+            l1_batch_number.0 as i32,
+            chunk_id as i32 + 1,
+            storage_logs_filepath,
+            checksum.as_bytes(),
+        )
+        .instrument("add_storage_logs_chunk_for_snapshot")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("chunk_id", &chunk_id)
+        .execute(self.storage)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns L1 batch numbers of all complete full snapshots. Differential snapshots are not returned since recovering
+    /// from them requires applying the whole snapshot chain.
     pub async fn get_all_complete_snapshots(&mut self) -> DalResult<AllSnapshots> {
         let rows = sqlx::query!(
             r#"
@@ -116,6 +233,7 @@ impl SnapshotsDal<'_, '_> {
                 snapshots
             WHERE
                 NOT (''::TEXT = ANY(storage_logs_filepaths))
+                AND base_l1_batch_number IS NULL
             ORDER BY
                 l1_batch_number DESC
             "#
@@ -135,6 +253,32 @@ impl SnapshotsDal<'_, '_> {
         })
     }
 
+    /// Returns the earliest L1 batch referenced by incomplete snapshots, i.e., the minimum over incomplete snapshots
+    /// of the snapshot L1 batch and the base snapshot L1 batch (for differential snapshots). Data for this L1 batch
+    /// and all following batches is required to finish creating snapshots.
+    pub async fn get_earliest_l1_batch_referenced_by_incomplete_snapshots(
+        &mut self,
+    ) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MIN(LEAST(l1_batch_number, COALESCE(base_l1_batch_number, l1_batch_number))) AS "l1_batch_number"
+            FROM
+                snapshots
+            WHERE
+                ''::TEXT = ANY(storage_logs_filepaths)
+            "#
+        )
+        .instrument("get_earliest_l1_batch_referenced_by_incomplete_snapshots")
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(row
+            .l1_batch_number
+            .map(|number| L1BatchNumber(number as u32)))
+    }
+
     pub async fn get_newest_snapshot_metadata(&mut self) -> DalResult<Option<SnapshotMetadata>> {
         sqlx::query_as!(
             StorageSnapshotMetadata,
@@ -142,8 +286,10 @@ impl SnapshotsDal<'_, '_> {
             SELECT
                 VERSION,
                 L1_BATCH_NUMBER,
+                BASE_L1_BATCH_NUMBER,
                 FACTORY_DEPS_FILEPATH,
-                STORAGE_LOGS_FILEPATHS
+                STORAGE_LOGS_FILEPATHS,
+                STORAGE_LOGS_CHECKSUMS
             FROM
                 SNAPSHOTS
             ORDER BY
@@ -159,6 +305,37 @@ impl SnapshotsDal<'_, '_> {
         .await
     }
 
+    /// Returns metadata of the newest complete snapshot, either full or differential.
+    pub async fn get_newest_complete_snapshot_metadata(
+        &mut self,
+    ) -> DalResult<Option<SnapshotMetadata>> {
+        sqlx::query_as!(
+            StorageSnapshotMetadata,
+            r#"
+            SELECT
+                VERSION,
+                L1_BATCH_NUMBER,
+                BASE_L1_BATCH_NUMBER,
+                FACTORY_DEPS_FILEPATH,
+                STORAGE_LOGS_FILEPATHS,
+                STORAGE_LOGS_CHECKSUMS
+            FROM
+                SNAPSHOTS
+            WHERE
+                NOT (''::TEXT = ANY(STORAGE_LOGS_FILEPATHS))
+            ORDER BY
+                L1_BATCH_NUMBER DESC
+            LIMIT
+                1
+            "#
+        )
+        .try_map(SnapshotMetadata::try_from)
+        .instrument("get_newest_complete_snapshot_metadata")
+        .report_latency()
+        .fetch_optional(self.storage)
+        .await
+    }
+
     pub async fn get_snapshot_metadata(
         &mut self,
         l1_batch_number: L1BatchNumber,
@@ -169,8 +346,10 @@ impl SnapshotsDal<'_, '_> {
             SELECT
                 VERSION,
                 L1_BATCH_NUMBER,
+                BASE_L1_BATCH_NUMBER,
                 FACTORY_DEPS_FILEPATH,
-                STORAGE_LOGS_FILEPATHS
+                STORAGE_LOGS_FILEPATHS,
+                STORAGE_LOGS_CHECKSUMS
             FROM
                 SNAPSHOTS
             WHERE
@@ -200,8 +379,10 @@ impl SnapshotsDal<'_, '_> {
             RETURNING
             version,
             l1_batch_number,
+            base_l1_batch_number,
             factory_deps_filepath,
-            storage_logs_filepaths
+            storage_logs_filepaths,
+            storage_logs_checksums
             "#,
             last_retained_l1_batch_number.0 as i32
         )
@@ -218,7 +399,7 @@ impl SnapshotsDal<'_, '_> {
 
 #[cfg(test)]
 mod tests {
-    use zksync_types::{snapshots::SnapshotVersion, L1BatchNumber};
+    use zksync_types::{snapshots::SnapshotVersion, L1BatchNumber, H256};
 
     use crate::{ConnectionPool, Core, CoreDal};
 
@@ -379,4 +560,158 @@ mod tests {
             ]
         );
     }
+
+    #[tokio::test]
+    async fn adding_files_with_checksums() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.snapshots_dal();
+        let l1_batch_number = L1BatchNumber(100);
+        dal.add_snapshot(
+            SnapshotVersion::Version2,
+            l1_batch_number,
+            2,
+            "gs:///bucket/factory_deps.bin",
+        )
+        .await
+        .unwrap();
+
+        let checksum = H256::repeat_byte(0x23);
+        dal.add_storage_logs_chunk_for_snapshot(
+            l1_batch_number,
+            1,
+            "gs:///bucket/test_file2.bin",
+            checksum,
+        )
+        .await
+        .unwrap();
+
+        let snapshot_metadata = dal
+            .get_snapshot_metadata(l1_batch_number)
+            .await
+            .unwrap()
+            .expect("snapshot is not persisted");
+        assert_eq!(snapshot_metadata.version, SnapshotVersion::Version2);
+        assert_eq!(
+            snapshot_metadata.storage_logs_filepaths,
+            [None, Some("gs:///bucket/test_file2.bin".to_string())]
+        );
+        assert_eq!(
+            snapshot_metadata.storage_logs_checksums,
+            [None, Some(checksum)]
+        );
+        assert!(!snapshot_metadata.is_complete());
+    }
+
+    #[tokio::test]
+    async fn getting_l1_batch_referenced_by_incomplete_snapshots() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.snapshots_dal();
+        let referenced_l1_batch = dal
+            .get_earliest_l1_batch_referenced_by_incomplete_snapshots()
+            .await
+            .unwrap();
+        assert_eq!(referenced_l1_batch, None);
+
+        dal.add_snapshot(SnapshotVersion::Version1, L1BatchNumber(100), 1, "file")
+            .await
+            .unwrap();
+        dal.add_differential_snapshot(
+            SnapshotVersion::Version1,
+            L1BatchNumber(120),
+            L1BatchNumber(100),
+            1,
+            "file",
+        )
+        .await
+        .unwrap();
+        let referenced_l1_batch = dal
+            .get_earliest_l1_batch_referenced_by_incomplete_snapshots()
+            .await
+            .unwrap();
+        assert_eq!(referenced_l1_batch, Some(L1BatchNumber(100)));
+
+        dal.add_storage_logs_filepath_for_snapshot(L1BatchNumber(100), 0, "file")
+            .await
+            .unwrap();
+        // The differential snapshot is still incomplete and references its base snapshot.
+        let referenced_l1_batch = dal
+            .get_earliest_l1_batch_referenced_by_incomplete_snapshots()
+            .await
+            .unwrap();
+        assert_eq!(referenced_l1_batch, Some(L1BatchNumber(100)));
+
+        dal.add_storage_logs_filepath_for_snapshot(L1BatchNumber(120), 0, "file")
+            .await
+            .unwrap();
+        let referenced_l1_batch = dal
+            .get_earliest_l1_batch_referenced_by_incomplete_snapshots()
+            .await
+            .unwrap();
+        assert_eq!(referenced_l1_batch, None);
+    }
+
+    #[tokio::test]
+    async fn adding_differential_snapshot() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.snapshots_dal();
+        let base_l1_batch_number = L1BatchNumber(100);
+        let l1_batch_number = L1BatchNumber(110);
+        dal.add_snapshot(
+            SnapshotVersion::Version1,
+            base_l1_batch_number,
+            1,
+            "gs:///bucket/factory_deps.bin",
+        )
+        .await
+        .unwrap();
+        dal.add_storage_logs_filepath_for_snapshot(
+            base_l1_batch_number,
+            0,
+            "gs:///bucket/chunk.bin",
+        )
+        .await
+        .unwrap();
+        dal.add_differential_snapshot(
+            SnapshotVersion::Version1,
+            l1_batch_number,
+            base_l1_batch_number,
+            1,
+            "gs:///bucket/factory_deps_delta.bin",
+        )
+        .await
+        .unwrap();
+
+        let newest_snapshot = dal
+            .get_newest_complete_snapshot_metadata()
+            .await
+            .unwrap()
+            .expect("no complete snapshots");
+        assert_eq!(newest_snapshot.l1_batch_number, base_l1_batch_number);
+        assert!(!newest_snapshot.is_differential());
+
+        dal.add_storage_logs_filepath_for_snapshot(
+            l1_batch_number,
+            0,
+            "gs:///bucket/chunk_delta.bin",
+        )
+        .await
+        .unwrap();
+        let newest_snapshot = dal
+            .get_newest_complete_snapshot_metadata()
+            .await
+            .unwrap()
+            .expect("no complete snapshots");
+        assert_eq!(newest_snapshot.l1_batch_number, l1_batch_number);
+        assert_eq!(
+            newest_snapshot.base_l1_batch_number,
+            Some(base_l1_batch_number)
+        );
+
+        // Differential snapshots must not be returned among full snapshots.
+        let snapshots = dal.get_all_complete_snapshots().await.unwrap();
+        assert_eq!(snapshots.snapshots_l1_batch_numbers, [base_l1_batch_number]);
+    }
 }
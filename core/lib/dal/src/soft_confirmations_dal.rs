@@ -0,0 +1,201 @@
+use zksync_db_connection::{
+    connection::Connection,
+    error::{DalResult, SqlxContext},
+    instrument::InstrumentExt,
+};
+use zksync_types::{
+    api, soft_confirmation::SoftConfirmationCommitment, Address, L2BlockNumber, PackedEthSignature,
+    H256,
+};
+
+use crate::Core;
+
+#[derive(Debug)]
+pub struct SoftConfirmationsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl SoftConfirmationsDal<'_, '_> {
+    /// Persists soft confirmations signed by `signer`. If a confirmation for a transaction already exists
+    /// (e.g., because the L2 block was re-executed after a restart), it is overwritten.
+    pub async fn insert_soft_confirmations(
+        &mut self,
+        signer: Address,
+        confirmations: &[(SoftConfirmationCommitment, PackedEthSignature)],
+    ) -> DalResult<()> {
+        if confirmations.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx_hashes = Vec::with_capacity(confirmations.len());
+        let mut l2_block_numbers = Vec::with_capacity(confirmations.len());
+        let mut indices_in_block = Vec::with_capacity(confirmations.len());
+        let mut signatures = Vec::with_capacity(confirmations.len());
+        for (commitment, signature) in confirmations {
+            tx_hashes.push(commitment.tx_hash.as_bytes());
+            l2_block_numbers.push(i64::from(commitment.l2_block_number.0));
+            indices_in_block.push(commitment.index_in_block as i32);
+            signatures.push(signature.serialize_packed().to_vec());
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            soft_confirmations (
+                tx_hash, miniblock_number, index_in_block, signer, signature, created_at
+            )
+            SELECT
+                u.tx_hash,
+                u.miniblock_number,
+                u.index_in_block,
+                $5,
+                u.signature,
+                NOW()
+            FROM
+                UNNEST($1::bytea [], $2::bigint [], $3::int [], $4::bytea [])
+                AS u (tx_hash, miniblock_number, index_in_block, signature)
+            ON CONFLICT (tx_hash) DO
+            UPDATE
+            SET
+            miniblock_number = excluded.miniblock_number,
+            index_in_block = excluded.index_in_block,
+            signer = excluded.signer,
+            signature = excluded.signature,
+            created_at = excluded.created_at
+            "#,
+            &tx_hashes as &[&[u8]],
+            &l2_block_numbers,
+            &indices_in_block,
+            &signatures,
+            signer.as_bytes()
+        )
+        .instrument("insert_soft_confirmations")
+        .with_arg("confirmations.len", &confirmations.len())
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the soft confirmation issued for the specified transaction, if any.
+    pub async fn get_soft_confirmation(
+        &mut self,
+        tx_hash: H256,
+    ) -> DalResult<Option<api::SoftConfirmation>> {
+        sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number,
+                index_in_block,
+                signer,
+                signature,
+                created_at
+            FROM
+                soft_confirmations
+            WHERE
+                tx_hash = $1
+            "#,
+            tx_hash.as_bytes()
+        )
+        .try_map(|row| {
+            Ok(api::SoftConfirmation {
+                transaction_hash: tx_hash,
+                block_number: L2BlockNumber(row.miniblock_number as u32),
+                transaction_index: row.index_in_block as u32,
+                signer: Address::from_slice(&row.signer),
+                signature: PackedEthSignature::deserialize_packed(&row.signature)
+                    .decode_column("signature")?,
+                signed_at: row.created_at.and_utc(),
+            })
+        })
+        .instrument("get_soft_confirmation")
+        .with_arg("tx_hash", &tx_hash)
+        .fetch_optional(self.storage)
+        .await
+    }
+
+    /// Removes soft confirmations for all L2 blocks after the specified one. This is used when the corresponding
+    /// L2 blocks are re-executed or reverted.
+    pub async fn delete_soft_confirmations_after(
+        &mut self,
+        last_retained_l2_block: L2BlockNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM soft_confirmations
+            WHERE
+                miniblock_number > $1
+            "#,
+            i64::from(last_retained_l2_block.0)
+        )
+        .instrument("delete_soft_confirmations_after")
+        .with_arg("last_retained_l2_block", &last_retained_l2_block)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::K256PrivateKey;
+
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn soft_confirmations_roundtrip() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(0x11)).unwrap();
+        let signer = private_key.address();
+
+        let confirmations: Vec<_> = (0..3_u32)
+            .map(|i| {
+                let commitment = SoftConfirmationCommitment {
+                    tx_hash: H256::repeat_byte(i as u8 + 1),
+                    l2_block_number: L2BlockNumber(i + 1),
+                    index_in_block: i,
+                };
+                (commitment, commitment.sign(&private_key).unwrap())
+            })
+            .collect();
+        conn.soft_confirmations_dal()
+            .insert_soft_confirmations(signer, &confirmations)
+            .await
+            .unwrap();
+
+        for (commitment, signature) in &confirmations {
+            let confirmation = conn
+                .soft_confirmations_dal()
+                .get_soft_confirmation(commitment.tx_hash)
+                .await
+                .unwrap()
+                .expect("no soft confirmation");
+            assert_eq!(confirmation.block_number, commitment.l2_block_number);
+            assert_eq!(confirmation.transaction_index, commitment.index_in_block);
+            assert_eq!(confirmation.signer, signer);
+            assert_eq!(confirmation.signature, *signature);
+            assert_eq!(
+                commitment.recover_signer(&confirmation.signature).unwrap(),
+                signer
+            );
+        }
+
+        conn.soft_confirmations_dal()
+            .delete_soft_confirmations_after(L2BlockNumber(1))
+            .await
+            .unwrap();
+        let retained = conn
+            .soft_confirmations_dal()
+            .get_soft_confirmation(H256::repeat_byte(1))
+            .await
+            .unwrap();
+        assert!(retained.is_some());
+        let removed = conn
+            .soft_confirmations_dal()
+            .get_soft_confirmation(H256::repeat_byte(2))
+            .await
+            .unwrap();
+        assert!(removed.is_none());
+    }
+}
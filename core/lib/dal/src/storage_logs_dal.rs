@@ -28,7 +28,21 @@ impl StorageLogsDal<'_, '_> {
         block_number: L2BlockNumber,
         logs: &[StorageLog],
     ) -> DalResult<()> {
-        self.insert_storage_logs_inner(block_number, logs, 0).await
+        self.insert_storage_logs_inner(block_number, logs, 0, None)
+            .await
+    }
+
+    /// Same as [`Self::insert_storage_logs()`], but additionally records hashes of transactions that wrote
+    /// the logs. `writers` maps storage keys to the last transaction in the L2 block that modified them;
+    /// logs for keys missing from `writers` are inserted without a transaction hash.
+    pub async fn insert_storage_logs_with_writers(
+        &mut self,
+        block_number: L2BlockNumber,
+        logs: &[StorageLog],
+        writers: &HashMap<StorageKey, H256>,
+    ) -> DalResult<()> {
+        self.insert_storage_logs_inner(block_number, logs, 0, Some(writers))
+            .await
     }
 
     async fn insert_storage_logs_inner(
@@ -36,11 +50,12 @@ impl StorageLogsDal<'_, '_> {
         block_number: L2BlockNumber,
         logs: &[StorageLog],
         mut operation_number: u32,
+        writers: Option<&HashMap<StorageKey, H256>>,
     ) -> DalResult<()> {
         let logs_len = logs.len();
         let copy = CopyStatement::new(
             "COPY storage_logs(
-                hashed_key, address, key, value, operation_number, miniblock_number,
+                hashed_key, address, key, value, operation_number, tx_hash, miniblock_number,
                 created_at, updated_at
             )
             FROM STDIN WITH (DELIMITER '|')",
@@ -62,10 +77,12 @@ impl StorageLogsDal<'_, '_> {
                 key = log.key.key(),
                 value = log.value
             );
-            writeln_str!(
-                &mut buffer,
-                r"{operation_number}|{block_number}|{now}|{now}"
-            );
+            write_str!(&mut buffer, "{operation_number}|");
+            match writers.and_then(|writers| writers.get(&log.key)) {
+                Some(tx_hash) => write_str!(&mut buffer, r"\\x{tx_hash:x}|"),
+                None => write_str!(&mut buffer, r"\N|"),
+            }
+            writeln_str!(&mut buffer, r"{block_number}|{now}|{now}");
 
             operation_number += 1;
         }
@@ -175,7 +192,7 @@ impl StorageLogsDal<'_, '_> {
         .map(|max| max as u32 + 1)
         .unwrap_or(0);
 
-        self.insert_storage_logs_inner(block_number, logs, operation_number)
+        self.insert_storage_logs_inner(block_number, logs, operation_number, None)
             .await
     }
 
@@ -826,6 +843,57 @@ mod tests {
             .unwrap();
     }
 
+    #[tokio::test]
+    async fn inserting_storage_logs_with_writers() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        insert_l2_block(&mut conn, 1, vec![]).await;
+
+        let account = AccountTreeId::new(Address::repeat_byte(1));
+        let first_key = StorageKey::new(account, H256::zero());
+        let second_key = StorageKey::new(account, H256::from_low_u64_be(1));
+        let logs = [
+            StorageLog::new_write_log(first_key, H256::repeat_byte(1)),
+            StorageLog::new_write_log(second_key, H256::repeat_byte(2)),
+        ];
+        let tx_hash = H256::repeat_byte(0xaa);
+        let writers = HashMap::from([(first_key, tx_hash)]);
+        conn.storage_logs_dal()
+            .insert_storage_logs_with_writers(L2BlockNumber(1), &logs, &writers)
+            .await
+            .unwrap();
+
+        let writer = conn
+            .storage_web3_dal()
+            .get_storage_slot_writer(first_key.hashed_key(), L2BlockNumber(1))
+            .await
+            .unwrap()
+            .expect("no writer");
+        assert_eq!(writer.l2_block_number, L2BlockNumber(1));
+        assert_eq!(writer.tx_hash, Some(tx_hash));
+        assert_eq!(writer.value, H256::repeat_byte(1));
+
+        let writer = conn
+            .storage_web3_dal()
+            .get_storage_slot_writer(second_key.hashed_key(), L2BlockNumber(1))
+            .await
+            .unwrap()
+            .expect("no writer");
+        assert_eq!(writer.tx_hash, None);
+        assert_eq!(writer.value, H256::repeat_byte(2));
+
+        let writer = conn
+            .storage_web3_dal()
+            .get_storage_slot_writer(first_key.hashed_key(), L2BlockNumber(0))
+            .await
+            .unwrap();
+        assert_eq!(writer, None);
+    }
+
     #[tokio::test]
     async fn inserting_storage_logs() {
         let pool = ConnectionPool::<Core>::test_pool().await;
@@ -6,7 +6,7 @@ use zksync_db_connection::{
     instrument::{InstrumentExt, Instrumented},
 };
 use zksync_types::{
-    get_code_key, get_nonce_key, h256_to_u256,
+    api, get_code_key, get_nonce_key, h256_to_u256,
     utils::{decompose_full_nonce, storage_key_for_standard_token_balance},
     AccountTreeId, Address, L1BatchNumber, L2BlockNumber, Nonce, StorageKey,
     FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256, U256,
@@ -160,6 +160,51 @@ impl StorageWeb3Dal<'_, '_> {
         })
     }
 
+    /// Returns the latest write to the storage slot with the specified hashed key at or before the specified L2 block.
+    /// The writing transaction is only known if it was recorded when persisting the L2 block
+    /// (see [`StorageLogsDal::insert_storage_logs_with_writers()`](crate::storage_logs_dal::StorageLogsDal::insert_storage_logs_with_writers())).
+    pub async fn get_storage_slot_writer(
+        &mut self,
+        hashed_key: H256,
+        block_number: L2BlockNumber,
+    ) -> DalResult<Option<api::StorageSlotWriter>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number,
+                tx_hash,
+                value
+            FROM
+                storage_logs
+            WHERE
+                storage_logs.hashed_key = $1
+                AND storage_logs.miniblock_number <= $2
+            ORDER BY
+                storage_logs.miniblock_number DESC,
+                storage_logs.operation_number DESC
+            LIMIT
+                1
+            "#,
+            hashed_key.as_bytes(),
+            i64::from(block_number.0)
+        )
+        .instrument("get_storage_slot_writer")
+        .with_arg("key", &hashed_key)
+        .with_arg("block_number", &block_number)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| api::StorageSlotWriter {
+            l2_block_number: L2BlockNumber(row.miniblock_number as u32),
+            // Logs recovered from a snapshot have a zero transaction hash.
+            tx_hash: row
+                .tx_hash
+                .map(|hash| H256::from_slice(&hash))
+                .filter(|hash| !hash.is_zero()),
+            value: H256::from_slice(&row.value),
+        }))
+    }
+
     /// Provides information about the L1 batch that the specified L2 block is a part of.
     /// Assumes that the L2 block is present in the DB; this is not checked, and if this is false,
     /// the returned value will be meaningless.
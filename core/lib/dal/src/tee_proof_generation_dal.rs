@@ -268,6 +268,64 @@ impl TeeProofGenerationDal<'_, '_> {
         Ok(())
     }
 
+    /// Saves an attestation together with the claims verified by the attestation policy.
+    pub async fn save_verified_attestation(
+        &mut self,
+        pubkey: &[u8],
+        attestation: &[u8],
+        tee_type: TeeType,
+        claims: &serde_json::Value,
+    ) -> DalResult<()> {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO
+            tee_attestations (pubkey, attestation, tee_type, claims)
+            VALUES
+            ($1, $2, $3, $4)
+            ON CONFLICT (pubkey) DO NOTHING
+            "#,
+            pubkey,
+            attestation,
+            tee_type.to_string(),
+            claims
+        );
+        let instrumentation = Instrumented::new("save_verified_attestation")
+            .with_arg("pubkey", &pubkey)
+            .with_arg("tee_type", &tee_type);
+        instrumentation
+            .clone()
+            .with(query)
+            .execute(self.storage)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns claims recorded for the attestation with the specified public key. Returns `None` if there is
+    /// no such attestation, or if it was saved without verified claims.
+    pub async fn get_attestation_claims(
+        &mut self,
+        pubkey: &[u8],
+    ) -> DalResult<Option<serde_json::Value>> {
+        let claims = sqlx::query!(
+            r#"
+            SELECT
+                claims
+            FROM
+                tee_attestations
+            WHERE
+                pubkey = $1
+            "#,
+            pubkey
+        )
+        .instrument("get_attestation_claims")
+        .with_arg("pubkey", &pubkey)
+        .fetch_optional(self.storage)
+        .await?
+        .and_then(|row| row.claims);
+        Ok(claims)
+    }
+
     pub async fn get_tee_proofs(
         &mut self,
         batch_number: L1BatchNumber,
@@ -10,10 +10,10 @@ use zksync_db_connection::{
     utils::pg_interval_from_duration,
 };
 use zksync_types::{
-    block::L2BlockExecutionData, debug_flat_call::CallTraceMeta, l1::L1Tx, l2::L2Tx,
-    protocol_upgrade::ProtocolUpgradeTx, Address, ExecuteTransactionCommon, L1BatchNumber,
-    L1BlockNumber, L2BlockNumber, PriorityOpId, ProtocolVersionId, Transaction,
-    TransactionTimeRangeConstraint, H256, PROTOCOL_UPGRADE_TX_TYPE, U256,
+    block::L2BlockExecutionData, cold_storage::CallTracesArchive, debug_flat_call::CallTraceMeta,
+    l1::L1Tx, l2::L2Tx, protocol_upgrade::ProtocolUpgradeTx, Address, ExecuteTransactionCommon,
+    L1BatchNumber, L1BlockNumber, L2BlockNumber, Nonce, PriorityOpId, ProtocolVersionId,
+    Transaction, TransactionTimeRangeConstraint, H256, PROTOCOL_UPGRADE_TX_TYPE, U256,
 };
 use zksync_vm_interface::{
     tracer::ValidationTraces, Call, TransactionExecutionMetrics, TransactionExecutionResult,
@@ -51,6 +51,18 @@ impl fmt::Display for L2TxSubmissionResult {
     }
 }
 
+/// Priority operation persisted by the L1 watcher, as used to detect L1 reorgs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceivedPriorityOp {
+    pub id: PriorityOpId,
+    pub hash: H256,
+    pub l1_block_number: L1BlockNumber,
+    /// Whether the operation is included into an L2 block.
+    pub is_included: bool,
+    /// Whether the operation was loaded into the mempool.
+    pub in_mempool: bool,
+}
+
 #[derive(Debug)]
 pub struct TransactionsDal<'c, 'a> {
     pub(crate) storage: &'c mut Connection<'a, Core>,
@@ -1687,6 +1699,30 @@ impl TransactionsDal<'_, '_> {
         Ok(())
     }
 
+    /// Deletes L2 transactions included into L2 blocks after `last_l2_block_to_keep`, e.g. to revert the pending state
+    /// in the development mode. Priority and protocol upgrade transactions are left intact. Returns the number
+    /// of deleted transactions.
+    pub async fn delete_l2_transactions_after(
+        &mut self,
+        last_l2_block_to_keep: L2BlockNumber,
+    ) -> DalResult<usize> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM transactions
+            WHERE
+                miniblock_number > $1
+                AND is_priority = FALSE
+                AND upgrade_id IS NULL
+            "#,
+            i64::from(last_l2_block_to_keep.0)
+        )
+        .instrument("delete_l2_transactions_after")
+        .with_arg("last_l2_block_to_keep", &last_l2_block_to_keep)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() as usize)
+    }
+
     pub async fn reset_transactions_state(
         &mut self,
         l2_block_number: L2BlockNumber,
@@ -1928,6 +1964,38 @@ impl TransactionsDal<'_, '_> {
         Ok(transactions_with_constraints)
     }
 
+    /// Returns pending L2 transactions in the order they would be picked up by the mempool. Unlike
+    /// [`Self::sync_mempool()`], doesn't modify transaction state, so it's safe to use outside the state keeper.
+    pub async fn get_pending_l2_transactions(
+        &mut self,
+        limit: usize,
+    ) -> DalResult<Vec<Transaction>> {
+        let transactions = sqlx::query_as!(
+            StorageTransaction,
+            r#"
+            SELECT
+                *
+            FROM
+                transactions
+            WHERE
+                miniblock_number IS NULL
+                AND error IS NULL
+                AND is_priority = FALSE
+            ORDER BY
+                received_at
+            LIMIT
+                $1
+            "#,
+            limit as i64
+        )
+        .instrument("get_pending_l2_transactions")
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(transactions.into_iter().map(Into::into).collect())
+    }
+
     pub async fn reset_mempool(&mut self) -> DalResult<()> {
         sqlx::query!(
             r#"
@@ -1944,6 +2012,71 @@ impl TransactionsDal<'_, '_> {
         Ok(())
     }
 
+    /// Returns hashes, initiators and nonces of all pending L2 transactions, i.e. ones that were accepted by the API server,
+    /// but were neither included into an L2 block nor rejected. Such transactions are loaded into the mempool by the state keeper
+    /// on startup.
+    pub async fn get_pending_l2_tx_nonces(&mut self) -> DalResult<Vec<(H256, Address, Nonce)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                hash,
+                initiator_address,
+                nonce AS "nonce!"
+            FROM
+                transactions
+            WHERE
+                miniblock_number IS NULL
+                AND error IS NULL
+                AND is_priority = FALSE
+                AND nonce IS NOT NULL
+            "#
+        )
+        .instrument("get_pending_l2_tx_nonces")
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    H256::from_slice(&row.hash),
+                    Address::from_slice(&row.initiator_address),
+                    Nonce(row.nonce as u32),
+                )
+            })
+            .collect())
+    }
+
+    /// Marks the specified pending L2 transactions as rejected with the provided error. Transactions that are already
+    /// included into an L2 block are not affected. Returns the number of rejected transactions.
+    pub async fn reject_pending_l2_txs(
+        &mut self,
+        transaction_hashes: &[H256],
+        error: &str,
+    ) -> DalResult<usize> {
+        let hashes: Vec<_> = transaction_hashes.iter().map(H256::as_bytes).collect();
+        let result = sqlx::query!(
+            r#"
+            UPDATE transactions
+            SET
+                error = $1,
+                in_mempool = FALSE,
+                updated_at = NOW()
+            WHERE
+                hash = ANY($2)
+                AND miniblock_number IS NULL
+                AND is_priority = FALSE
+            "#,
+            error,
+            &hashes as &[&[u8]]
+        )
+        .instrument("reject_pending_l2_txs")
+        .with_arg("transaction_hashes.len", &hashes.len())
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() as usize)
+    }
+
     pub async fn get_last_processed_l1_block(&mut self) -> DalResult<Option<L1BlockNumber>> {
         let maybe_row = sqlx::query!(
             r#"
@@ -1968,6 +2101,67 @@ impl TransactionsDal<'_, '_> {
             .map(|number| L1BlockNumber(number as u32)))
     }
 
+    /// Returns priority operations received in L1 blocks starting from `first_l1_block`, ordered by ID.
+    pub async fn get_priority_ops_received_since(
+        &mut self,
+        first_l1_block: L1BlockNumber,
+    ) -> DalResult<Vec<ReceivedPriorityOp>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                priority_op_id AS "priority_op_id!",
+                hash,
+                l1_block_number AS "l1_block_number!",
+                miniblock_number IS NOT NULL AS "is_included!",
+                in_mempool
+            FROM
+                transactions
+            WHERE
+                is_priority = TRUE
+                AND l1_block_number >= $1
+            ORDER BY
+                priority_op_id
+            "#,
+            i32::try_from(first_l1_block.0).unwrap_or(i32::MAX)
+        )
+        .instrument("get_priority_ops_received_since")
+        .with_arg("first_l1_block", &first_l1_block)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ReceivedPriorityOp {
+                id: PriorityOpId(row.priority_op_id as u64),
+                hash: H256::from_slice(&row.hash),
+                l1_block_number: L1BlockNumber(row.l1_block_number as u32),
+                is_included: row.is_included,
+                in_mempool: row.in_mempool,
+            })
+            .collect())
+    }
+
+    /// Removes priority operations with IDs starting from `first_id` that are not included into L2 blocks yet.
+    /// Returns the number of removed operations.
+    pub async fn remove_priority_ops_since(&mut self, first_id: PriorityOpId) -> DalResult<usize> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM transactions
+            WHERE
+                is_priority = TRUE
+                AND priority_op_id >= $1
+                AND miniblock_number IS NULL
+            "#,
+            first_id.0 as i64
+        )
+        .instrument("remove_priority_ops_since")
+        .with_arg("first_id", &first_id)
+        .execute(self.storage)
+        .await?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
     pub async fn last_priority_id(&mut self) -> DalResult<Option<PriorityOpId>> {
         let maybe_row = sqlx::query!(
             r#"
@@ -2237,9 +2431,28 @@ impl TransactionsDal<'_, '_> {
         Ok(data)
     }
 
+    /// Returns the call trace for the specified transaction. Returns `None` if the trace was moved to cold storage;
+    /// use [`Self::get_archived_call_trace()`] in this case.
     pub async fn get_call_trace(
         &mut self,
         tx_hash: H256,
+    ) -> DalResult<Option<(Call, CallTraceMeta)>> {
+        self.get_call_trace_inner(tx_hash, None).await
+    }
+
+    /// Same as [`Self::get_call_trace()`], but takes the serialized trace from the provided cold storage archive.
+    pub async fn get_archived_call_trace(
+        &mut self,
+        tx_hash: H256,
+        archive: &CallTracesArchive,
+    ) -> DalResult<Option<(Call, CallTraceMeta)>> {
+        self.get_call_trace_inner(tx_hash, Some(archive)).await
+    }
+
+    async fn get_call_trace_inner(
+        &mut self,
+        tx_hash: H256,
+        archive: Option<&CallTracesArchive>,
     ) -> DalResult<Option<(Call, CallTraceMeta)>> {
         let row = sqlx::query!(
             r#"
@@ -2287,9 +2500,15 @@ impl TransactionsDal<'_, '_> {
         .with_arg("tx_hash", &tx_hash)
         .fetch_optional(self.storage)
         .await?
-        .map(|mut call_trace| {
-            (
-                parse_call_trace(&call_trace.call_trace, protocol_version),
+        .and_then(|mut call_trace| {
+            let serialized_trace = match archive {
+                Some(archive) => archive.get(tx_hash)?,
+                // Empty traces are stubs for traces moved to cold storage.
+                None if call_trace.call_trace.is_empty() => return None,
+                None => &call_trace.call_trace,
+            };
+            Some((
+                parse_call_trace(serialized_trace, protocol_version),
                 CallTraceMeta {
                     index_in_block: row.index_in_block.unwrap_or_default() as usize,
                     tx_hash,
@@ -2297,7 +2516,7 @@ impl TransactionsDal<'_, '_> {
                     block_hash: H256::from_slice(&row.miniblocks_hash),
                     internal_error: call_trace.tx_error.take(),
                 },
-            )
+            ))
         }))
     }
 
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, iter::once};
+use std::{collections::HashMap, iter::once, ops};
 
 use anyhow::Context as _;
 use sqlx::types::chrono::NaiveDateTime;
@@ -70,12 +70,36 @@ impl TransactionsWeb3Dal<'_, '_> {
                 transactions.refunded_gas,
                 transactions.gas_limit,
                 transactions.nonce,
+                transactions.paymaster,
+                transactions.paymaster_input,
                 miniblocks.hash AS "block_hash",
                 miniblocks.l1_batch_number AS "l1_batch_number?",
-                miniblocks.timestamp AS "block_timestamp?"
+                miniblocks.timestamp AS "block_timestamp?",
+                commit_tx.tx_hash AS "commit_tx_hash?",
+                commit_tx.confirmed_at AS "committed_at?",
+                prove_tx.tx_hash AS "prove_tx_hash?",
+                prove_tx.confirmed_at AS "proven_at?",
+                execute_tx.tx_hash AS "execute_tx_hash?",
+                execute_tx.confirmed_at AS "executed_at?"
             FROM
                 transactions
             JOIN miniblocks ON miniblocks.number = transactions.miniblock_number
+            LEFT JOIN l1_batches ON miniblocks.l1_batch_number = l1_batches.number
+            LEFT JOIN eth_txs_history AS commit_tx
+                ON (
+                    l1_batches.eth_commit_tx_id = commit_tx.eth_tx_id
+                    AND commit_tx.confirmed_at IS NOT NULL
+                )
+            LEFT JOIN eth_txs_history AS prove_tx
+                ON (
+                    l1_batches.eth_prove_tx_id = prove_tx.eth_tx_id
+                    AND prove_tx.confirmed_at IS NOT NULL
+                )
+            LEFT JOIN eth_txs_history AS execute_tx
+                ON (
+                    l1_batches.eth_execute_tx_id = execute_tx.eth_tx_id
+                    AND execute_tx.confirmed_at IS NOT NULL
+                )
             WHERE
                 transactions.hash = ANY($1)
                 AND transactions.data != '{}'::jsonb
@@ -416,6 +440,42 @@ impl TransactionsWeb3Dal<'_, '_> {
         Ok(U256::from(pending_nonce))
     }
 
+    /// Returns nonces of non-rejected L2 transactions from the specified account that are waiting in the mempool
+    /// (i.e., not included into an L2 block yet), starting from `committed_next_nonce`. Nonces are returned in the ascending order.
+    pub async fn get_mempool_nonces_by_initiator_account(
+        &mut self,
+        initiator_address: Address,
+        committed_next_nonce: u64,
+    ) -> DalResult<Vec<u64>> {
+        let nonces = sqlx::query!(
+            r#"
+            SELECT
+                nonce AS "nonce!"
+            FROM
+                transactions
+            WHERE
+                initiator_address = $1
+                AND nonce >= $2
+                AND is_priority = FALSE
+                AND miniblock_number IS NULL
+                AND error IS NULL
+            ORDER BY
+                nonce
+            "#,
+            initiator_address.as_bytes(),
+            committed_next_nonce as i64
+        )
+        .instrument("get_mempool_nonces_by_initiator_account")
+        .with_arg("initiator_address", &initiator_address)
+        .with_arg("committed_next_nonce", &committed_next_nonce)
+        .fetch_all(self.storage)
+        .await?
+        .into_iter()
+        .map(|row| row.nonce as u64)
+        .collect();
+        Ok(nonces)
+    }
+
     /// Returns the server transactions (not API ones) from a L2 block range.
     pub async fn get_raw_l2_blocks_transactions(
         &mut self,
@@ -477,18 +537,157 @@ impl TransactionsWeb3Dal<'_, '_> {
             .remove(&block)
             .unwrap_or_default())
     }
+
+    /// Returns activity of the specified account in an L2 block range: transactions initiated by or sent to the account,
+    /// and L2 blocks modifying the account storage.
+    ///
+    /// At most `limit` entries are returned. If the range contains more entries, the returned activity covers a prefix
+    /// of the range consisting of whole L2 blocks. Returns `None` if the first L2 block in the range alone contains
+    /// more than `limit` entries.
+    pub async fn get_account_activity(
+        &mut self,
+        address: Address,
+        block_range: ops::RangeInclusive<L2BlockNumber>,
+        limit: usize,
+    ) -> DalResult<Option<api::AccountActivity>> {
+        let from_block = i64::from(block_range.start().0);
+        let to_block = i64::from(block_range.end().0);
+        // Request an extra row to detect whether the range contains more entries than the limit.
+        let query_limit = limit as i64 + 1;
+
+        let tx_rows = sqlx::query!(
+            r#"
+            SELECT
+                hash,
+                miniblock_number AS "miniblock_number!",
+                index_in_block AS "index_in_block!",
+                initiator_address = $1 AS "is_sender!",
+                COALESCE(contract_address = $1, FALSE) AS "is_receiver!"
+            FROM
+                transactions
+            WHERE
+                miniblock_number BETWEEN $2 AND $3
+                AND (
+                    initiator_address = $1
+                    OR contract_address = $1
+                )
+            ORDER BY
+                miniblock_number,
+                index_in_block
+            LIMIT
+                $4
+            "#,
+            address.as_bytes(),
+            from_block,
+            to_block,
+            query_limit
+        )
+        .instrument("get_account_activity_transactions")
+        .with_arg("address", &address)
+        .with_arg("block_range", &block_range)
+        .fetch_all(self.storage)
+        .await?;
+
+        let storage_rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT
+                miniblock_number
+            FROM
+                storage_logs
+            WHERE
+                address = $1
+                AND miniblock_number BETWEEN $2 AND $3
+            ORDER BY
+                miniblock_number
+            LIMIT
+                $4
+            "#,
+            address.as_bytes(),
+            from_block,
+            to_block,
+            query_limit
+        )
+        .instrument("get_account_activity_storage_writes")
+        .with_arg("address", &address)
+        .with_arg("block_range", &block_range)
+        .fetch_all(self.storage)
+        .await?;
+
+        // If a query has hit the limit, the last returned L2 block may be incomplete.
+        let mut covered_to_block = to_block;
+        if let Some(row) = tx_rows.get(limit) {
+            covered_to_block = covered_to_block.min(row.miniblock_number - 1);
+        }
+        if let Some(row) = storage_rows.get(limit) {
+            covered_to_block = covered_to_block.min(row.miniblock_number - 1);
+        }
+
+        let tx_entries = tx_rows.into_iter().map(|row| {
+            let mut kinds = vec![];
+            if row.is_sender {
+                kinds.push(api::AccountActivityKind::Sender);
+            }
+            if row.is_receiver {
+                kinds.push(api::AccountActivityKind::Receiver);
+            }
+            let entry = api::AccountActivityEntry {
+                block_number: L2BlockNumber(row.miniblock_number as u32),
+                transaction_hash: Some(H256::from_slice(&row.hash)),
+                transaction_index: Some(row.index_in_block as u32),
+                kinds,
+            };
+            (row.miniblock_number, i64::from(row.index_in_block), entry)
+        });
+        let storage_entries = storage_rows.into_iter().map(|row| {
+            let entry = api::AccountActivityEntry {
+                block_number: L2BlockNumber(row.miniblock_number as u32),
+                transaction_hash: None,
+                transaction_index: None,
+                kinds: vec![api::AccountActivityKind::StorageWrite],
+            };
+            // Storage writes go after all transactions in the block.
+            (row.miniblock_number, i64::MAX, entry)
+        });
+
+        let mut entries: Vec<_> = tx_entries
+            .chain(storage_entries)
+            .filter(|(block_number, ..)| *block_number <= covered_to_block)
+            .collect();
+        entries.sort_unstable_by_key(|(block_number, index, _)| (*block_number, *index));
+        if let Some((block_number, ..)) = entries.get(limit) {
+            covered_to_block = block_number - 1;
+            entries.retain(|(block_number, ..)| *block_number <= covered_to_block);
+        }
+
+        if covered_to_block < from_block {
+            return Ok(None);
+        }
+        Ok(Some(api::AccountActivity {
+            from_block: *block_range.start(),
+            to_block: L2BlockNumber(covered_to_block as u32),
+            entries: entries.into_iter().map(|(_, _, entry)| entry).collect(),
+        }))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
-    use zksync_types::{l2::L2Tx, Nonce, ProtocolVersion, ProtocolVersionId};
+    use chrono::DateTime;
+    use zksync_types::{
+        aggregated_operations::AggregatedActionType, l2::L2Tx,
+        transaction_request::PaymasterParams, AccountTreeId, L1BatchNumber, Nonce, ProtocolVersion,
+        ProtocolVersionId, StorageKey, StorageLog,
+    };
     use zksync_vm_interface::{tracer::ValidationTraces, TransactionExecutionMetrics};
 
     use super::*;
     use crate::{
-        tests::{create_l2_block_header, mock_execution_result, mock_l2_transaction},
+        tests::{
+            create_l1_batch_header, create_l2_block_header, mock_execution_result,
+            mock_l2_transaction,
+        },
         ConnectionPool, Core, CoreDal,
     };
 
@@ -690,6 +889,140 @@ mod tests {
         assert_eq!(receipt.to, None);
     }
 
+    #[tokio::test]
+    async fn getting_receipt_with_paymaster() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        let tx = mock_l2_transaction();
+        let tx_hash = tx.hash();
+        let mut paymaster_tx = mock_l2_transaction();
+        let paymaster_tx_hash = paymaster_tx.hash();
+        let paymaster = Address::repeat_byte(0x11);
+        paymaster_tx.common_data.paymaster_params = PaymasterParams {
+            paymaster,
+            paymaster_input: vec![0x8c, 0x5a, 0x34, 0x45, 0, 0, 0, 0],
+        };
+        prepare_transactions(&mut conn, vec![tx, paymaster_tx]).await;
+
+        let receipts = conn
+            .transactions_web3_dal()
+            .get_transaction_receipts(&[tx_hash, paymaster_tx_hash])
+            .await
+            .unwrap();
+        assert_eq!(receipts.len(), 2);
+        for receipt in receipts {
+            let receipt = receipt.inner;
+            if receipt.transaction_hash == tx_hash {
+                assert_eq!(receipt.paymaster_info, None);
+                continue;
+            }
+
+            let paymaster_info = receipt.paymaster_info.unwrap();
+            assert_eq!(paymaster_info.paymaster, paymaster);
+            assert_eq!(
+                paymaster_info.paymaster_input_selector.unwrap().0,
+                [0x8c, 0x5a, 0x34, 0x45]
+            );
+            let fee = receipt.gas_used.unwrap() * receipt.effective_gas_price.unwrap();
+            assert_eq!(paymaster_info.sponsored_fee, fee);
+            assert_eq!(paymaster_info.user_paid_fee, U256::zero());
+        }
+    }
+
+    #[tokio::test]
+    async fn getting_receipt_with_l1_finality() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        let tx = mock_l2_transaction();
+        let tx_hash = tx.hash();
+        prepare_transactions(&mut conn, vec![tx]).await;
+
+        let receipts = conn
+            .transactions_web3_dal()
+            .get_transaction_receipts(&[tx_hash])
+            .await
+            .unwrap();
+        assert_eq!(receipts[0].inner.l1_batch_number, None);
+        assert_eq!(receipts[0].inner.l1_finality, None);
+
+        let l1_batch_header = create_l1_batch_header(1);
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&l1_batch_header)
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .mark_l2_blocks_as_executed_in_l1_batch(l1_batch_header.number)
+            .await
+            .unwrap();
+
+        let receipts = conn
+            .transactions_web3_dal()
+            .get_transaction_receipts(&[tx_hash])
+            .await
+            .unwrap();
+        assert_eq!(receipts[0].inner.l1_batch_number, Some(1.into()));
+        assert_eq!(receipts[0].inner.l1_finality, None);
+
+        let committed_at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let commit_tx_hash = H256::repeat_byte(1);
+        conn.eth_sender_dal()
+            .insert_bogus_confirmed_eth_tx(
+                L1BatchNumber(1),
+                AggregatedActionType::Commit,
+                commit_tx_hash,
+                committed_at,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let receipts = conn
+            .transactions_web3_dal()
+            .get_transaction_receipts(&[tx_hash])
+            .await
+            .unwrap();
+        let l1_finality = receipts[0].inner.l1_finality.clone().unwrap();
+        assert_eq!(l1_finality.commit_tx_hash, Some(commit_tx_hash));
+        assert_eq!(l1_finality.committed_at, Some(committed_at));
+        assert_eq!(l1_finality.prove_tx_hash, None);
+        assert_eq!(l1_finality.execute_tx_hash, None);
+
+        let proven_at = committed_at + chrono::Duration::minutes(10);
+        let prove_tx_hash = H256::repeat_byte(2);
+        conn.eth_sender_dal()
+            .insert_bogus_confirmed_eth_tx(
+                L1BatchNumber(1),
+                AggregatedActionType::PublishProofOnchain,
+                prove_tx_hash,
+                proven_at,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let receipts = conn
+            .transactions_web3_dal()
+            .get_transaction_receipts(&[tx_hash])
+            .await
+            .unwrap();
+        let l1_finality = receipts[0].inner.l1_finality.clone().unwrap();
+        assert_eq!(l1_finality.commit_tx_hash, Some(commit_tx_hash));
+        assert_eq!(l1_finality.prove_tx_hash, Some(prove_tx_hash));
+        assert_eq!(l1_finality.proven_at, Some(proven_at));
+        assert_eq!(l1_finality.execute_tx_hash, None);
+        assert_eq!(l1_finality.executed_at, None);
+    }
+
     #[tokio::test]
     async fn getting_l2_block_transactions() {
         let connection_pool = ConnectionPool::<Core>::test_pool().await;
@@ -718,6 +1051,95 @@ mod tests {
         assert_eq!(raw_txs[0].hash(), tx_hash);
     }
 
+    #[tokio::test]
+    async fn getting_account_activity() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        let txs = [mock_l2_transaction(), mock_l2_transaction()];
+        let tx_hashes = txs.each_ref().map(L2Tx::hash);
+        let sender = txs[0].initiator_account();
+        let recipient = txs[1].recipient_account().unwrap();
+        prepare_transactions(&mut conn, txs.to_vec()).await;
+
+        let storage_key = StorageKey::new(AccountTreeId::new(recipient), H256::zero());
+        let storage_logs = [StorageLog::new_write_log(storage_key, H256::repeat_byte(1))];
+        conn.storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(1), &storage_logs)
+            .await
+            .unwrap();
+
+        let block_range = L2BlockNumber(0)..=L2BlockNumber(10);
+        let activity = conn
+            .transactions_web3_dal()
+            .get_account_activity(sender, block_range.clone(), 100)
+            .await
+            .unwrap()
+            .expect("no activity");
+        assert_eq!(activity.to_block, L2BlockNumber(10));
+        assert_eq!(
+            activity.entries,
+            [api::AccountActivityEntry {
+                block_number: L2BlockNumber(1),
+                transaction_hash: Some(tx_hashes[0]),
+                transaction_index: Some(0),
+                kinds: vec![api::AccountActivityKind::Sender],
+            }]
+        );
+
+        let activity = conn
+            .transactions_web3_dal()
+            .get_account_activity(recipient, block_range.clone(), 100)
+            .await
+            .unwrap()
+            .expect("no activity");
+        assert_eq!(activity.to_block, L2BlockNumber(10));
+        assert_eq!(
+            activity.entries,
+            [
+                api::AccountActivityEntry {
+                    block_number: L2BlockNumber(1),
+                    transaction_hash: Some(tx_hashes[1]),
+                    transaction_index: Some(1),
+                    kinds: vec![api::AccountActivityKind::Receiver],
+                },
+                api::AccountActivityEntry {
+                    block_number: L2BlockNumber(1),
+                    transaction_hash: None,
+                    transaction_index: None,
+                    kinds: vec![api::AccountActivityKind::StorageWrite],
+                }
+            ]
+        );
+
+        // L2 block #1 doesn't fit into the limit.
+        let activity = conn
+            .transactions_web3_dal()
+            .get_account_activity(recipient, block_range.clone(), 1)
+            .await
+            .unwrap()
+            .expect("no activity");
+        assert_eq!(activity.to_block, L2BlockNumber(0));
+        assert!(activity.entries.is_empty());
+        let activity = conn
+            .transactions_web3_dal()
+            .get_account_activity(recipient, L2BlockNumber(1)..=L2BlockNumber(10), 1)
+            .await
+            .unwrap();
+        assert_eq!(activity, None);
+
+        let activity = conn
+            .transactions_web3_dal()
+            .get_account_activity(Address::repeat_byte(1), block_range, 1)
+            .await
+            .unwrap()
+            .expect("no activity");
+        assert!(activity.entries.is_empty());
+    }
+
     #[tokio::test]
     async fn getting_next_nonce_by_initiator_account() {
         let connection_pool = ConnectionPool::<Core>::test_pool().await;
@@ -758,6 +1180,12 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(next_nonce, 2.into());
+        let mempool_nonces = conn
+            .transactions_web3_dal()
+            .get_mempool_nonces_by_initiator_account(initiator, 1)
+            .await
+            .unwrap();
+        assert_eq!(mempool_nonces, [1, 4]);
 
         // Reject the transaction with nonce 1, so that it'd be not taken into account.
         conn.transactions_dal()
@@ -770,6 +1198,12 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(next_nonce, 1.into());
+        let mempool_nonces = conn
+            .transactions_web3_dal()
+            .get_mempool_nonces_by_initiator_account(initiator, 0)
+            .await
+            .unwrap();
+        assert_eq!(mempool_nonces, [0, 4]);
 
         // Include transactions in a L2 block (including the rejected one), so that they are taken into account again.
         let mut l2_block = create_l2_block_header(1);
@@ -796,6 +1230,12 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(next_nonce, 2.into());
+        let mempool_nonces = conn
+            .transactions_web3_dal()
+            .get_mempool_nonces_by_initiator_account(initiator, 0)
+            .await
+            .unwrap();
+        assert_eq!(mempool_nonces, [4]);
     }
 
     #[tokio::test]
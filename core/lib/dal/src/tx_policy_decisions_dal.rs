@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use zksync_db_connection::{
+    connection::Connection, error::DalResult, instrument::InstrumentExt,
+    utils::pg_interval_from_duration,
+};
+use zksync_types::{api::TxPolicyDecision, Address, H256};
+
+use crate::Core;
+
+/// Storage of transactions rejected by the transaction policy engine.
+#[derive(Debug)]
+pub struct TxPolicyDecisionsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl TxPolicyDecisionsDal<'_, '_> {
+    pub async fn insert_decision(
+        &mut self,
+        tx_hash: H256,
+        initiator_address: Address,
+        stage: &str,
+        rule: &str,
+        reason: &str,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            tx_policy_decisions (
+                tx_hash, initiator_address, stage, rule, reason, created_at
+            )
+            VALUES
+            ($1, $2, $3, $4, $5, NOW())
+            "#,
+            tx_hash.as_bytes(),
+            initiator_address.as_bytes(),
+            stage,
+            rule,
+            reason
+        )
+        .instrument("insert_tx_policy_decision")
+        .with_arg("tx_hash", &tx_hash)
+        .with_arg("rule", &rule)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` latest decisions, optionally filtered by the initiator address.
+    pub async fn get_latest_decisions(
+        &mut self,
+        initiator_address: Option<Address>,
+        limit: usize,
+    ) -> DalResult<Vec<TxPolicyDecision>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                tx_hash,
+                initiator_address,
+                stage,
+                rule,
+                reason,
+                created_at
+            FROM
+                tx_policy_decisions
+            WHERE
+                $1::BYTEA IS NULL
+                OR initiator_address = $1
+            ORDER BY
+                id DESC
+            LIMIT
+                $2
+            "#,
+            initiator_address.as_ref().map(Address::as_bytes),
+            limit as i64
+        )
+        .instrument("get_latest_tx_policy_decisions")
+        .with_arg("initiator_address", &initiator_address)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| TxPolicyDecision {
+                transaction_hash: H256::from_slice(&row.tx_hash),
+                initiator_address: Address::from_slice(&row.initiator_address),
+                stage: row.stage,
+                rule: row.rule,
+                reason: row.reason,
+                created_at: DateTime::<Utc>::from_naive_utc_and_offset(row.created_at, Utc),
+            })
+            .collect())
+    }
+
+    /// Removes decisions older than `retention`. Returns the number of removed decisions.
+    pub async fn prune_decisions(&mut self, retention: Duration) -> DalResult<usize> {
+        let retention = pg_interval_from_duration(retention);
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM tx_policy_decisions
+            WHERE
+                created_at < NOW() - $1::INTERVAL
+            "#,
+            retention
+        )
+        .instrument("prune_tx_policy_decisions")
+        .with_arg("retention", &retention)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn inserting_and_querying_decisions() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let alice = Address::repeat_byte(1);
+        let bob = Address::repeat_byte(2);
+
+        let mut dal = conn.tx_policy_decisions_dal();
+        dal.insert_decision(
+            H256::repeat_byte(1),
+            alice,
+            "submission",
+            "address_denied",
+            "denied",
+        )
+        .await
+        .unwrap();
+        dal.insert_decision(
+            H256::repeat_byte(2),
+            bob,
+            "inclusion",
+            "address_denied",
+            "denied",
+        )
+        .await
+        .unwrap();
+        dal.insert_decision(H256::repeat_byte(3), alice, "submission", "rule", "reason")
+            .await
+            .unwrap();
+
+        let decisions = dal.get_latest_decisions(None, 10).await.unwrap();
+        let hashes: Vec<_> = decisions.iter().map(|d| d.transaction_hash).collect();
+        assert_eq!(
+            hashes,
+            [
+                H256::repeat_byte(3),
+                H256::repeat_byte(2),
+                H256::repeat_byte(1)
+            ]
+        );
+        assert_eq!(decisions[1].stage, "inclusion");
+
+        let decisions = dal.get_latest_decisions(Some(alice), 1).await.unwrap();
+        assert_eq!(decisions.len(), 1);
+        assert_eq!(decisions[0].transaction_hash, H256::repeat_byte(3));
+        assert_eq!(decisions[0].initiator_address, alice);
+
+        let pruned_count = dal
+            .prune_decisions(Duration::from_secs(3_600))
+            .await
+            .unwrap();
+        assert_eq!(pruned_count, 0);
+        let pruned_count = dal.prune_decisions(Duration::ZERO).await.unwrap();
+        assert_eq!(pruned_count, 3);
+        let decisions = dal.get_latest_decisions(None, 10).await.unwrap();
+        assert!(decisions.is_empty(), "{decisions:?}");
+    }
+}
@@ -0,0 +1,189 @@
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{L1BatchNumber, L2BlockNumber, H256};
+
+use crate::Core;
+
+/// Execution checkpoint of an unsealed L1 batch taken after sealing an L2 block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsealedBatchCheckpoint {
+    pub l1_batch_number: L1BatchNumber,
+    /// Last L2 block covered by the checkpoint.
+    pub l2_block_number: L2BlockNumber,
+    /// Number of transactions executed in the batch up to and including `l2_block_number`.
+    pub tx_count: usize,
+    /// Checksum of storage slots modified by the batch up to and including `l2_block_number`.
+    pub storage_logs_checksum: H256,
+}
+
+#[derive(Debug)]
+pub struct UnsealedBatchCheckpointsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl UnsealedBatchCheckpointsDal<'_, '_> {
+    /// Persists a checkpoint, overwriting the existing one for the same L2 block (e.g., if the block was re-executed).
+    pub async fn insert_checkpoint(
+        &mut self,
+        checkpoint: &UnsealedBatchCheckpoint,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            unsealed_batch_checkpoints (
+                miniblock_number, l1_batch_number, tx_count, storage_logs_checksum, created_at
+            )
+            VALUES
+            ($1, $2, $3, $4, NOW())
+            ON CONFLICT (miniblock_number) DO
+            UPDATE
+            SET
+            l1_batch_number = excluded.l1_batch_number,
+            tx_count = excluded.tx_count,
+            storage_logs_checksum = excluded.storage_logs_checksum,
+            created_at = excluded.created_at
+            "#,
+            i64::from(checkpoint.l2_block_number.0),
+            i64::from(checkpoint.l1_batch_number.0),
+            checkpoint.tx_count as i32,
+            checkpoint.storage_logs_checksum.as_bytes()
+        )
+        .instrument("insert_unsealed_batch_checkpoint")
+        .with_arg("checkpoint", checkpoint)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns all checkpoints for the specified L1 batch ordered by L2 block number.
+    pub async fn get_checkpoints(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<UnsealedBatchCheckpoint>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number,
+                tx_count,
+                storage_logs_checksum
+            FROM
+                unsealed_batch_checkpoints
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                miniblock_number
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_unsealed_batch_checkpoints")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| UnsealedBatchCheckpoint {
+                l1_batch_number,
+                l2_block_number: L2BlockNumber(row.miniblock_number as u32),
+                tx_count: row.tx_count as usize,
+                storage_logs_checksum: H256::from_slice(&row.storage_logs_checksum),
+            })
+            .collect())
+    }
+
+    /// Removes checkpoints for the L2 blocks starting from `first_removed_l2_block`. This is used when
+    /// the corresponding L2 blocks were never persisted or were reverted.
+    pub async fn delete_checkpoints_starting_from(
+        &mut self,
+        first_removed_l2_block: L2BlockNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM unsealed_batch_checkpoints
+            WHERE
+                miniblock_number >= $1
+            "#,
+            i64::from(first_removed_l2_block.0)
+        )
+        .instrument("delete_unsealed_batch_checkpoints_starting_from")
+        .with_arg("first_removed_l2_block", &first_removed_l2_block)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes checkpoints for all L1 batches up to and including the specified one.
+    pub async fn delete_checkpoints_for_sealed_batches(
+        &mut self,
+        last_sealed_l1_batch: L1BatchNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM unsealed_batch_checkpoints
+            WHERE
+                l1_batch_number <= $1
+            "#,
+            i64::from(last_sealed_l1_batch.0)
+        )
+        .instrument("delete_unsealed_batch_checkpoints_for_sealed_batches")
+        .with_arg("last_sealed_l1_batch", &last_sealed_l1_batch)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn unsealed_batch_checkpoints_roundtrip() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+
+        let checkpoints: Vec<_> = (1..=4_u32)
+            .map(|i| UnsealedBatchCheckpoint {
+                l1_batch_number: L1BatchNumber(1 + i / 3),
+                l2_block_number: L2BlockNumber(i),
+                tx_count: i as usize * 2,
+                storage_logs_checksum: H256::repeat_byte(i as u8),
+            })
+            .collect();
+        for checkpoint in &checkpoints {
+            conn.unsealed_batch_checkpoints_dal()
+                .insert_checkpoint(checkpoint)
+                .await
+                .unwrap();
+        }
+
+        let batch_checkpoints = conn
+            .unsealed_batch_checkpoints_dal()
+            .get_checkpoints(L1BatchNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(batch_checkpoints, checkpoints[2..]);
+
+        conn.unsealed_batch_checkpoints_dal()
+            .delete_checkpoints_starting_from(L2BlockNumber(4))
+            .await
+            .unwrap();
+        let batch_checkpoints = conn
+            .unsealed_batch_checkpoints_dal()
+            .get_checkpoints(L1BatchNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(batch_checkpoints, [checkpoints[2]]);
+
+        conn.unsealed_batch_checkpoints_dal()
+            .delete_checkpoints_for_sealed_batches(L1BatchNumber(1))
+            .await
+            .unwrap();
+        let batch_checkpoints = conn
+            .unsealed_batch_checkpoints_dal()
+            .get_checkpoints(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert!(batch_checkpoints.is_empty());
+    }
+}
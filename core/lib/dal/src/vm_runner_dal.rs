@@ -124,6 +124,53 @@ impl VmRunnerDal<'_, '_> {
         Ok(())
     }
 
+    /// Records a divergence between the state keeper output and the re-executed L1 batch.
+    pub async fn mark_protective_reads_batch_as_divergent(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        divergence: &str,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE vm_runner_protective_reads
+            SET
+                divergence = $2
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0),
+            divergence
+        )
+        .instrument("mark_protective_reads_batch_as_divergent")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the divergence recorded for the specified L1 batch, if any.
+    pub async fn get_protective_reads_divergence(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<String>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                divergence
+            FROM
+                vm_runner_protective_reads
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_protective_reads_divergence")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.and_then(|row| row.divergence))
+    }
+
     pub async fn delete_protective_reads(
         &mut self,
         last_batch_to_keep: L1BatchNumber,
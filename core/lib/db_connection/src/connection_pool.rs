@@ -368,6 +368,13 @@ impl<DB: DbMarker> ConnectionPool<DB> {
         self.max_size
     }
 
+    /// Returns the share of connections in this pool that are currently in use, relative to [`Self::max_size()`].
+    /// The returned value is in the `0.0..=1.0` range.
+    pub fn utilization(&self) -> f64 {
+        let used_connections = (self.inner.size() as usize).saturating_sub(self.inner.num_idle());
+        (used_connections as f64 / f64::from(self.max_size.max(1))).min(1.0)
+    }
+
     /// Creates a `Connection` entity over a recoverable connection.
     /// Upon a database outage connection will block the thread until
     /// it will be able to recover the connection (or, if connection cannot
@@ -3,7 +3,7 @@ use zksync_config::configs::{
     api::{
         ContractVerificationApiConfig, HealthCheckConfig, MerkleTreeApiConfig, Web3JsonRpcConfig,
     },
-    ApiConfig, PrometheusConfig,
+    AdminApiSecrets, ApiConfig, PrometheusConfig,
 };
 
 use crate::{envy_load, FromEnv};
@@ -44,10 +44,21 @@ impl FromEnv for MerkleTreeApiConfig {
     }
 }
 
+impl FromEnv for AdminApiSecrets {
+    fn from_env() -> anyhow::Result<Self> {
+        Ok(Self {
+            auth_token: std::env::var("API_ADMIN_AUTH_TOKEN")
+                .context("API_ADMIN_AUTH_TOKEN")?
+                .into(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::num::{NonZeroU32, NonZeroUsize};
 
+    use zksync_basic_types::secrets::APIKey;
     use zksync_config::configs::api::DeploymentAllowlist;
 
     use super::*;
@@ -60,6 +71,7 @@ mod tests {
             web3_json_rpc: Web3JsonRpcConfig {
                 http_port: 3050,
                 ws_port: 3051,
+                admin_port: Some(3052),
                 req_entities_limit: Some(10000),
                 filters_disabled: false,
                 filters_limit: Some(10000),
@@ -71,12 +83,15 @@ mod tests {
                 estimate_gas_acceptable_overestimation: 1000,
                 estimate_gas_optimize_search: false,
                 max_tx_size: 1000000,
+                max_tx_abi_encoded_size: Some(500000),
+                max_tx_compressed_bytecodes_size: Some(100000),
                 vm_execution_cache_misses_limit: None,
                 vm_concurrency_limit: Some(512),
                 factory_deps_cache_size_mb: Some(128),
                 initial_writes_cache_size_mb: Some(32),
                 latest_values_cache_size_mb: Some(256),
                 latest_values_max_block_lag: Some(NonZeroU32::new(50).unwrap()),
+                storage_read_batching_window_ms: Some(5),
                 fee_history_limit: Some(100),
                 max_batch_request_size: Some(200),
                 max_response_body_size_mb: Some(10),
@@ -87,10 +102,15 @@ mod tests {
                 ]
                 .into_iter()
                 .collect(),
+                max_trace_size_mb: Some(5),
                 websocket_requests_per_minute_limit: Some(NonZeroU32::new(10).unwrap()),
                 tree_api_url: None,
                 mempool_cache_update_interval: Some(50),
                 mempool_cache_size: Some(10000),
+                eth_call_cache_size: Some(1000),
+                load_shedding_vm_queue_latency_ms: Some(500),
+                load_shedding_pool_utilization: Some(0.9),
+                load_shedding_retry_after_ms: Some(2000),
                 whitelisted_tokens_for_aa: vec![
                     addr("0x0000000000000000000000000000000000000001"),
                     addr("0x0000000000000000000000000000000000000002"),
@@ -120,6 +140,7 @@ mod tests {
             API_WEB3_JSON_RPC_HTTP_PORT="3050"
             API_WEB3_JSON_RPC_HTTP_URL="http://127.0.0.1:3050"
             API_WEB3_JSON_RPC_WS_PORT="3051"
+            API_WEB3_JSON_RPC_ADMIN_PORT="3052"
             API_WEB3_JSON_RPC_WS_URL="ws://127.0.0.1:3051"
             API_WEB3_JSON_RPC_REQ_ENTITIES_LIMIT=10000
             API_WEB3_JSON_RPC_FILTERS_DISABLED=false
@@ -134,20 +155,28 @@ mod tests {
             API_WEB3_JSON_RPC_ESTIMATE_GAS_SCALE_FACTOR=1.0
             API_WEB3_JSON_RPC_ESTIMATE_GAS_ACCEPTABLE_OVERESTIMATION=1000
             API_WEB3_JSON_RPC_MAX_TX_SIZE=1000000
+            API_WEB3_JSON_RPC_MAX_TX_ABI_ENCODED_SIZE=500000
+            API_WEB3_JSON_RPC_MAX_TX_COMPRESSED_BYTECODES_SIZE=100000
             API_WEB3_JSON_RPC_VM_CONCURRENCY_LIMIT=512
             API_WEB3_JSON_RPC_FACTORY_DEPS_CACHE_SIZE_MB=128
             API_WEB3_JSON_RPC_INITIAL_WRITES_CACHE_SIZE_MB=32
             API_WEB3_JSON_RPC_LATEST_VALUES_CACHE_SIZE_MB=256
             API_WEB3_JSON_RPC_LATEST_VALUES_MAX_BLOCK_LAG=50
+            API_WEB3_JSON_RPC_STORAGE_READ_BATCHING_WINDOW_MS=5
             API_WEB3_JSON_RPC_FEE_HISTORY_LIMIT=100
             API_WEB3_JSON_RPC_MAX_BATCH_REQUEST_SIZE=200
             API_WEB3_JSON_RPC_WEBSOCKET_REQUESTS_PER_MINUTE_LIMIT=10
             API_WEB3_JSON_RPC_MEMPOOL_CACHE_SIZE=10000
             API_WEB3_JSON_RPC_MEMPOOL_CACHE_UPDATE_INTERVAL=50
+            API_WEB3_JSON_RPC_ETH_CALL_CACHE_SIZE=1000
+            API_WEB3_JSON_RPC_LOAD_SHEDDING_VM_QUEUE_LATENCY_MS=500
+            API_WEB3_JSON_RPC_LOAD_SHEDDING_POOL_UTILIZATION=0.9
+            API_WEB3_JSON_RPC_LOAD_SHEDDING_RETRY_AFTER_MS=2000
             API_CONTRACT_VERIFICATION_PORT="3070"
             API_CONTRACT_VERIFICATION_URL="http://127.0.0.1:3070"
             API_WEB3_JSON_RPC_MAX_RESPONSE_BODY_SIZE_MB=10
             API_WEB3_JSON_RPC_MAX_RESPONSE_BODY_SIZE_OVERRIDES_MB="eth_call=1, eth_getTransactionReceipt=None, zks_getProof=32"
+            API_WEB3_JSON_RPC_MAX_TRACE_SIZE_MB=5
             API_PROMETHEUS_LISTENER_PORT="3312"
             API_PROMETHEUS_PUSHGATEWAY_URL="http://127.0.0.1:9091"
             API_PROMETHEUS_PUSH_INTERVAL_MS=100
@@ -161,4 +190,15 @@ mod tests {
         let actual = ApiConfig::from_env().unwrap();
         assert_eq!(actual, expected_config());
     }
+
+    #[test]
+    fn admin_api_secrets_from_env() {
+        let mut lock = MUTEX.lock();
+        lock.remove_env(&["API_ADMIN_AUTH_TOKEN"]);
+        AdminApiSecrets::from_env().unwrap_err();
+
+        lock.set_env("API_ADMIN_AUTH_TOKEN=secret");
+        let actual = AdminApiSecrets::from_env().unwrap();
+        assert_eq!(actual.auth_token, APIKey::from("secret"));
+    }
 }
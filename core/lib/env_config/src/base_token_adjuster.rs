@@ -4,7 +4,14 @@ use crate::{envy_load, FromEnv};
 
 impl FromEnv for BaseTokenAdjusterConfig {
     fn from_env() -> anyhow::Result<Self> {
-        envy_load("base_token_adjuster", "BASE_TOKEN_ADJUSTER_")
+        let config: Self = envy_load("base_token_adjuster", "BASE_TOKEN_ADJUSTER_")?;
+        anyhow::ensure!(
+            config.base_token_decimals <= Self::MAX_BASE_TOKEN_DECIMALS,
+            "base token decimals ({}) must not exceed {}",
+            config.base_token_decimals,
+            Self::MAX_BASE_TOKEN_DECIMALS
+        );
+        Ok(config)
     }
 }
 
@@ -30,6 +37,7 @@ mod tests {
             price_fetching_sleep_ms: 10_000,
             l1_update_deviation_percentage: 20,
             halt_on_error: true,
+            base_token_decimals: 8,
         }
     }
 
@@ -48,6 +56,7 @@ mod tests {
             price_fetching_sleep_ms: 5_000,
             l1_update_deviation_percentage: 10,
             halt_on_error: false,
+            base_token_decimals: 18,
         }
     }
 
@@ -68,6 +77,7 @@ mod tests {
             BASE_TOKEN_ADJUSTER_PRICE_FETCHING_MAX_ATTEMPTS=20
             BASE_TOKEN_ADJUSTER_PRICE_FETCHING_SLEEP_MS=10000
             BASE_TOKEN_ADJUSTER_HALT_ON_ERROR=true
+            BASE_TOKEN_ADJUSTER_BASE_TOKEN_DECIMALS=8
         "#;
         lock.set_env(config);
 
@@ -92,9 +102,19 @@ mod tests {
             "BASE_TOKEN_ADJUSTER_PRICE_FETCHING_MAX_ATTEMPTS",
             "BASE_TOKEN_ADJUSTER_PRICE_FETCHING_SLEEP_MS",
             "BASE_TOKEN_ADJUSTER_HALT_ON_ERROR",
+            "BASE_TOKEN_ADJUSTER_BASE_TOKEN_DECIMALS",
         ]);
 
         let actual = BaseTokenAdjusterConfig::from_env().unwrap();
         assert_eq!(actual, expected_config_with_defaults());
     }
+
+    #[test]
+    fn from_env_base_token_adjuster_with_too_many_decimals() {
+        let mut lock = MUTEX.lock();
+        lock.set_env("BASE_TOKEN_ADJUSTER_BASE_TOKEN_DECIMALS=37");
+
+        let err = BaseTokenAdjusterConfig::from_env().unwrap_err();
+        assert!(err.to_string().contains("must not exceed 36"), "{err}");
+    }
 }
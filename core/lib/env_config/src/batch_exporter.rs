@@ -0,0 +1,40 @@
+use zksync_config::configs::{batch_exporter::BatchExportFormat, BatchExporterConfig};
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for BatchExporterConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("batch_exporter", "BATCH_EXPORTER_")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::EnvMutex;
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    #[test]
+    fn from_env_batch_exporter() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            BATCH_EXPORTER_OUTPUT_PATH="/db/batch_exports"
+            BATCH_EXPORTER_FORMAT="parquet"
+            BATCH_EXPORTER_POLL_INTERVAL_MS=500
+            BATCH_EXPORTER_MAX_BATCHES_PER_ITERATION=5
+        "#;
+        lock.set_env(config);
+
+        let actual = BatchExporterConfig::from_env().unwrap();
+        assert_eq!(
+            actual,
+            BatchExporterConfig {
+                output_path: "/db/batch_exports".to_owned(),
+                format: BatchExportFormat::Parquet,
+                poll_interval_ms: Some(500),
+                max_batches_per_iteration: Some(5),
+            }
+        );
+    }
+}
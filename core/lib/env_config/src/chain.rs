@@ -31,7 +31,7 @@ impl FromEnv for MempoolConfig {
 #[cfg(test)]
 mod tests {
     use zksync_basic_types::commitment::L1BatchCommitmentMode;
-    use zksync_config::configs::chain::FeeModelVersion;
+    use zksync_config::configs::chain::{FeeModelVersion, TimestampPolicyKind};
 
     use super::*;
     use crate::test_utils::{addr, hash, EnvMutex};
@@ -66,6 +66,12 @@ mod tests {
             max_gas_per_batch: 200_000_000,
             max_pubdata_per_batch: 100_000,
             fee_model_version: FeeModelVersion::V2,
+            fee_congestion_enabled: true,
+            fee_congestion_compute_target: 0.6,
+            fee_congestion_pubdata_target: 0.5,
+            fee_congestion_max_change_denominator: 8,
+            fee_congestion_max_multiplier: 10.0,
+            fee_congestion_window_batches: 64,
             validation_computational_gas_limit: 10_000_000,
             save_call_traces: false,
             bootloader_hash: Some(hash(
@@ -77,7 +83,24 @@ mod tests {
             evm_emulator_hash: None,
             l1_batch_commit_data_generator_mode,
             max_circuits_per_batch: 24100,
+            geometry_adjustment_enabled: true,
+            geometry_adjustment_target_proving_time_sec: 1_800,
+            geometry_adjustment_min_percentage: 0.5,
+            geometry_adjustment_max_percentage: 0.95,
+            geometry_adjustment_window_batches: 16,
             protective_reads_persistence_enabled: true,
+            witness_inputs_pregeneration_enabled: true,
+            storage_slot_writers_enabled: true,
+            pending_batch_state_enabled: true,
+            timestamp_policy: TimestampPolicyKind::FixedIncrement,
+            timestamp_increment_sec: 12,
+            l1_timestamp_window: 11,
+            dev_mode: true,
+            sequencer_instance_id: Some("sequencer-1".to_owned()),
+            sequencer_lease_ttl_ms: 5_000,
+            frame_gas_forwarding_divisor: Some(2),
+            max_frame_gas: Some(10_000_000),
+            batch_checkpoint_interval: Some(10),
         }
     }
 
@@ -105,12 +128,27 @@ mod tests {
             CHAIN_STATE_KEEPER_MAX_GAS_PER_BATCH="200000000"
             CHAIN_STATE_KEEPER_MAX_PUBDATA_PER_BATCH="100000"
             CHAIN_STATE_KEEPER_MAX_CIRCUITS_PER_BATCH="24100"
+            CHAIN_STATE_KEEPER_GEOMETRY_ADJUSTMENT_ENABLED=true
+            CHAIN_STATE_KEEPER_GEOMETRY_ADJUSTMENT_TARGET_PROVING_TIME_SEC="1800"
             CHAIN_STATE_KEEPER_FEE_MODEL_VERSION="V2"
+            CHAIN_STATE_KEEPER_FEE_CONGESTION_ENABLED=true
+            CHAIN_STATE_KEEPER_FEE_CONGESTION_COMPUTE_TARGET="0.6"
             CHAIN_STATE_KEEPER_VALIDATION_COMPUTATIONAL_GAS_LIMIT="10000000"
             CHAIN_STATE_KEEPER_SAVE_CALL_TRACES="false"
             CHAIN_STATE_KEEPER_BOOTLOADER_HASH=0x010007ede999d096c84553fb514d3d6ca76fbf39789dda76bfeda9f3ae06236e
             CHAIN_STATE_KEEPER_DEFAULT_AA_HASH=0x0100055b041eb28aff6e3a6e0f37c31fd053fc9ef142683b05e5f0aee6934066
             CHAIN_STATE_KEEPER_PROTECTIVE_READS_PERSISTENCE_ENABLED=true
+            CHAIN_STATE_KEEPER_WITNESS_INPUTS_PREGENERATION_ENABLED=true
+            CHAIN_STATE_KEEPER_STORAGE_SLOT_WRITERS_ENABLED=true
+            CHAIN_STATE_KEEPER_PENDING_BATCH_STATE_ENABLED=true
+            CHAIN_STATE_KEEPER_TIMESTAMP_POLICY="fixed_increment"
+            CHAIN_STATE_KEEPER_TIMESTAMP_INCREMENT_SEC="12"
+            CHAIN_STATE_KEEPER_DEV_MODE="true"
+            CHAIN_STATE_KEEPER_SEQUENCER_INSTANCE_ID="sequencer-1"
+            CHAIN_STATE_KEEPER_SEQUENCER_LEASE_TTL_MS="5000"
+            CHAIN_STATE_KEEPER_FRAME_GAS_FORWARDING_DIVISOR="2"
+            CHAIN_STATE_KEEPER_MAX_FRAME_GAS="10000000"
+            CHAIN_STATE_KEEPER_BATCH_CHECKPOINT_INTERVAL="10"
             CHAIN_STATE_KEEPER_L1_BATCH_COMMIT_DATA_GENERATOR_MODE="{l1_batch_commit_data_generator_mode}"
         "#
         )
@@ -152,7 +152,7 @@ mod tests {
                 eigen::PointsSource,
                 DAClientConfig::{self, ObjectStore},
             },
-            object_store::ObjectStoreMode::GCS,
+            object_store::{ContentAddressingMode, ObjectStoreMode::GCS},
         },
         AvailConfig, CelestiaConfig, EigenConfig, ObjectStoreConfig,
     };
@@ -169,6 +169,7 @@ mod tests {
             },
             max_retries,
             local_mirror_path: None,
+            content_addressing: ContentAddressingMode::Disabled,
         })
     }
 
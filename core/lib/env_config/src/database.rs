@@ -82,9 +82,19 @@ mod tests {
             DATABASE_MERKLE_TREE_MEMTABLE_CAPACITY_MB=512
             DATABASE_MERKLE_TREE_STALLED_WRITES_TIMEOUT_SEC=60
             DATABASE_MERKLE_TREE_MAX_L1_BATCHES_PER_ITER=50
+            DATABASE_MERKLE_TREE_HASHING_THREAD_COUNT=8
+            DATABASE_MERKLE_TREE_PRUNING_RETAINED_L1_BATCHES=1000
+            DATABASE_MERKLE_TREE_PRUNING_RETAIN_UNPROVEN_L1_BATCHES=false
+            DATABASE_MERKLE_TREE_PRUNING_POLL_INTERVAL_SEC=10
+            DATABASE_MERKLE_TREE_IN_MEMORY=true
+            DATABASE_MERKLE_TREE_IN_MEMORY_PERSISTENCE_INTERVAL_SEC=300
             DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_BLOCK_CACHE_CAPACITY_MB=64
             DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_MAX_OPEN_FILES=100
+            DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_STATE_CACHE_CAPACITY_MB=256
+            DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_HARD_PENDING_COMPACTION_LIMIT_MB=524288
             DATABASE_EXPERIMENTAL_MERKLE_TREE_REPAIR_STALE_KEYS=true
+            DATABASE_EXPERIMENTAL_MERKLE_TREE_CONSISTENCY_CHECK_INTERVAL_SEC=600
+            DATABASE_EXPERIMENTAL_MERKLE_TREE_CONSISTENCY_CHECK_SUBTREE_DELAY_MS=50
         "#;
         lock.set_env(config);
 
@@ -96,6 +106,18 @@ mod tests {
         assert_eq!(db_config.merkle_tree.max_l1_batches_per_iter, 50);
         assert_eq!(db_config.merkle_tree.memtable_capacity_mb, 512);
         assert_eq!(db_config.merkle_tree.stalled_writes_timeout_sec, 60);
+        assert_eq!(db_config.merkle_tree.hashing_thread_count, Some(8));
+        assert_eq!(
+            db_config.merkle_tree.pruning_retained_l1_batches,
+            NonZeroU32::new(1000)
+        );
+        assert!(!db_config.merkle_tree.pruning_retain_unproven_l1_batches);
+        assert_eq!(db_config.merkle_tree.pruning_poll_interval_sec, 10);
+        assert!(db_config.merkle_tree.in_memory);
+        assert_eq!(
+            db_config.merkle_tree.in_memory_persistence_interval(),
+            Some(Duration::from_secs(300))
+        );
         assert_eq!(
             db_config
                 .experimental
@@ -106,7 +128,37 @@ mod tests {
             db_config.experimental.state_keeper_db_max_open_files,
             NonZeroU32::new(100)
         );
+        assert_eq!(
+            db_config
+                .experimental
+                .state_keeper_db_state_cache_capacity(),
+            Some(256 << 20)
+        );
+        assert_eq!(
+            db_config
+                .experimental
+                .state_keeper_db_soft_pending_compaction_limit_mb,
+            None
+        );
+        assert_eq!(
+            db_config
+                .experimental
+                .state_keeper_db_hard_pending_compaction_limit(),
+            Some(512 << 30)
+        );
         assert!(db_config.experimental.merkle_tree_repair_stale_keys);
+        assert_eq!(
+            db_config
+                .experimental
+                .merkle_tree_consistency_check_interval(),
+            Some(Duration::from_secs(600))
+        );
+        assert_eq!(
+            db_config
+                .experimental
+                .merkle_tree_consistency_check_subtree_delay_ms,
+            50
+        );
     }
 
     #[test]
@@ -116,7 +168,11 @@ mod tests {
             "DATABASE_STATE_KEEPER_DB_PATH",
             "DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_MAX_OPEN_FILES",
             "DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_BLOCK_CACHE_CAPACITY_MB",
+            "DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_STATE_CACHE_CAPACITY_MB",
+            "DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_HARD_PENDING_COMPACTION_LIMIT_MB",
             "DATABASE_EXPERIMENTAL_MERKLE_TREE_REPAIR_STALE_KEYS",
+            "DATABASE_EXPERIMENTAL_MERKLE_TREE_CONSISTENCY_CHECK_INTERVAL_SEC",
+            "DATABASE_EXPERIMENTAL_MERKLE_TREE_CONSISTENCY_CHECK_SUBTREE_DELAY_MS",
             "DATABASE_MERKLE_TREE_BACKUP_PATH",
             "DATABASE_MERKLE_TREE_PATH",
             "DATABASE_MERKLE_TREE_MODE",
@@ -125,6 +181,12 @@ mod tests {
             "DATABASE_MERKLE_TREE_MEMTABLE_CAPACITY_MB",
             "DATABASE_MERKLE_TREE_STALLED_WRITES_TIMEOUT_SEC",
             "DATABASE_MERKLE_TREE_MAX_L1_BATCHES_PER_ITER",
+            "DATABASE_MERKLE_TREE_HASHING_THREAD_COUNT",
+            "DATABASE_MERKLE_TREE_PRUNING_RETAINED_L1_BATCHES",
+            "DATABASE_MERKLE_TREE_PRUNING_RETAIN_UNPROVEN_L1_BATCHES",
+            "DATABASE_MERKLE_TREE_PRUNING_POLL_INTERVAL_SEC",
+            "DATABASE_MERKLE_TREE_IN_MEMORY",
+            "DATABASE_MERKLE_TREE_IN_MEMORY_PERSISTENCE_INTERVAL_SEC",
         ]);
 
         let db_config = DBConfig::from_env().unwrap();
@@ -133,6 +195,15 @@ mod tests {
         assert_eq!(db_config.merkle_tree.mode, MerkleTreeMode::Full);
         assert_eq!(db_config.merkle_tree.multi_get_chunk_size, 500);
         assert_eq!(db_config.merkle_tree.max_l1_batches_per_iter, 20);
+        assert_eq!(db_config.merkle_tree.hashing_thread_count, None);
+        assert_eq!(db_config.merkle_tree.pruning_retained_l1_batches, None);
+        assert!(db_config.merkle_tree.pruning_retain_unproven_l1_batches);
+        assert_eq!(db_config.merkle_tree.pruning_poll_interval_sec, 60);
+        assert!(!db_config.merkle_tree.in_memory);
+        assert_eq!(
+            db_config.merkle_tree.in_memory_persistence_interval_sec,
+            None
+        );
         assert_eq!(db_config.merkle_tree.block_cache_size_mb, 128);
         assert_eq!(db_config.merkle_tree.memtable_capacity_mb, 256);
         assert_eq!(db_config.merkle_tree.stalled_writes_timeout_sec, 30);
@@ -143,7 +214,31 @@ mod tests {
             128
         );
         assert_eq!(db_config.experimental.state_keeper_db_max_open_files, None);
+        assert_eq!(
+            db_config
+                .experimental
+                .state_keeper_db_state_cache_capacity_mb,
+            None
+        );
+        assert_eq!(
+            db_config
+                .experimental
+                .state_keeper_db_hard_pending_compaction_limit_mb,
+            None
+        );
         assert!(!db_config.experimental.merkle_tree_repair_stale_keys);
+        assert_eq!(
+            db_config
+                .experimental
+                .merkle_tree_consistency_check_interval_sec,
+            None
+        );
+        assert_eq!(
+            db_config
+                .experimental
+                .merkle_tree_consistency_check_subtree_delay_ms,
+            100
+        );
 
         // Check that new env variable for Merkle tree path is supported
         lock.set_env("DATABASE_MERKLE_TREE_PATH=/db/tree/main");
@@ -26,6 +26,16 @@ impl FromEnv for L1Secrets {
             gateway_rpc_url: std::env::var("ETH_CLIENT_GATEWAY_WEB3_URL")
                 .ok()
                 .map(|url| url.parse().expect("ETH_CLIENT_GATEWAY_WEB3_URL")),
+            fee_source_rpc_urls: std::env::var("ETH_CLIENT_FEE_SOURCE_WEB3_URLS")
+                .ok()
+                .map(|urls| {
+                    urls.split(',')
+                        .map(|url| url.trim().parse())
+                        .collect::<Result<_, _>>()
+                })
+                .transpose()
+                .context("ETH_CLIENT_FEE_SOURCE_WEB3_URLS")?
+                .unwrap_or_default(),
         })
     }
 }
@@ -77,6 +87,16 @@ mod tests {
                     time_in_mempool_in_l1_blocks_cap: 2000,
                     is_verifier_pre_fflonk: true,
                     gas_limit_mode: Default::default(),
+                    pubdata_auto_selection: true,
+                    pubdata_auto_selection_max_blob_base_fee: Some(10_000_000_000),
+                    pubdata_auto_selection_max_calldata_size: 100_000,
+                    operator_takeover_after_l1_blocks: Some(20),
+                    high_gas_price_aggregation_threshold: Some(50_000_000_000),
+                    max_aggregated_blocks_on_high_gas_price: 5,
+                    fee_bump_interval_in_l1_blocks: Some(3),
+                    fee_bump_interval_sec: Some(60),
+                    fee_bump_percent: 25,
+                    max_fee_per_gas_cap: Some(500_000_000_000),
                 }),
                 Some(GasAdjusterConfig {
                     default_priority_fee_per_gas: 20000000000,
@@ -91,15 +111,28 @@ mod tests {
                     num_samples_for_blob_base_fee_estimate: 10,
                     internal_pubdata_pricing_multiplier: 1.0,
                     max_blob_base_fee: None,
+                    fee_sources_quorum: Some(2),
+                    fee_sources_max_deviation: 0.3,
                 }),
                 Some(EthWatchConfig {
                     confirmations_for_eth_event: Some(0),
                     eth_node_poll_interval: 300,
+                    reorg_check_depth_in_l1_blocks: None,
+                    l2_to_l1_message_tracking_enabled: false,
+                    l2_to_l1_message_tracking_interval_ms:
+                        EthWatchConfig::default_l2_to_l1_message_tracking_interval_ms(),
+                    l2_to_l1_message_tracking_first_l1_batch: None,
+                    l2_to_l1_message_retention_l1_batches:
+                        EthWatchConfig::default_l2_to_l1_message_retention_l1_batches(),
                 }),
             ),
             L1Secrets {
                 l1_rpc_url: "http://127.0.0.1:8545".to_string().parse().unwrap(),
                 gateway_rpc_url: Some("http://127.0.0.1:8547".to_string().parse().unwrap()),
+                fee_source_rpc_urls: vec![
+                    "http://127.0.0.1:8548".parse().unwrap(),
+                    "http://127.0.0.1:8549".parse().unwrap(),
+                ],
             },
         )
     }
@@ -126,6 +159,8 @@ mod tests {
             ETH_SENDER_GAS_ADJUSTER_MAX_L1_GAS_PRICE="100000000"
             ETH_SENDER_GAS_ADJUSTER_MAX_BLOB_BASE_FEE_SAMPLES="10"
             ETH_SENDER_GAS_ADJUSTER_INTERNAL_PUBDATA_PRICING_MULTIPLIER="1.0"
+            ETH_SENDER_GAS_ADJUSTER_FEE_SOURCES_QUORUM="2"
+            ETH_SENDER_GAS_ADJUSTER_FEE_SOURCES_MAX_DEVIATION="0.3"
             ETH_SENDER_WAIT_FOR_PROOFS="false"
             ETH_SENDER_SENDER_MAX_AGGREGATED_BLOCKS_TO_COMMIT="3"
             ETH_SENDER_SENDER_MAX_AGGREGATED_BLOCKS_TO_EXECUTE="4"
@@ -140,10 +175,21 @@ mod tests {
             ETH_SENDER_SENDER_MAX_ACCEPTABLE_PRIORITY_FEE_IN_GWEI="100000000000"
             ETH_SENDER_SENDER_PUBDATA_SENDING_MODE="Calldata"
             ETH_SENDER_SENDER_is_verifier_pre_fflonk="true"
+            ETH_SENDER_SENDER_PUBDATA_AUTO_SELECTION="true"
+            ETH_SENDER_SENDER_PUBDATA_AUTO_SELECTION_MAX_BLOB_BASE_FEE="10000000000"
+            ETH_SENDER_SENDER_PUBDATA_AUTO_SELECTION_MAX_CALLDATA_SIZE="100000"
+            ETH_SENDER_SENDER_OPERATOR_TAKEOVER_AFTER_L1_BLOCKS="20"
+            ETH_SENDER_SENDER_HIGH_GAS_PRICE_AGGREGATION_THRESHOLD="50000000000"
+            ETH_SENDER_SENDER_MAX_AGGREGATED_BLOCKS_ON_HIGH_GAS_PRICE="5"
+            ETH_SENDER_SENDER_FEE_BUMP_INTERVAL_IN_L1_BLOCKS="3"
+            ETH_SENDER_SENDER_FEE_BUMP_INTERVAL_SEC="60"
+            ETH_SENDER_SENDER_FEE_BUMP_PERCENT="25"
+            ETH_SENDER_SENDER_MAX_FEE_PER_GAS_CAP="500000000000"
             ETH_WATCH_CONFIRMATIONS_FOR_ETH_EVENT="0"
             ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
             ETH_CLIENT_WEB3_URL="http://127.0.0.1:8545"
             ETH_CLIENT_GATEWAY_WEB3_URL="http://127.0.0.1:8547"
+            ETH_CLIENT_FEE_SOURCE_WEB3_URLS="http://127.0.0.1:8548,http://127.0.0.1:8549"
 
         "#;
         lock.set_env(config);
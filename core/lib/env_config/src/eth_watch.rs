@@ -19,6 +19,11 @@ mod tests {
         EthWatchConfig {
             confirmations_for_eth_event: Some(0),
             eth_node_poll_interval: 300,
+            reorg_check_depth_in_l1_blocks: Some(64),
+            l2_to_l1_message_tracking_enabled: true,
+            l2_to_l1_message_tracking_interval_ms: 5_000,
+            l2_to_l1_message_tracking_first_l1_batch: Some(10),
+            l2_to_l1_message_retention_l1_batches: 1_000,
         }
     }
 
@@ -28,6 +33,11 @@ mod tests {
         let config = r#"
             ETH_WATCH_CONFIRMATIONS_FOR_ETH_EVENT="0"
             ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
+            ETH_WATCH_REORG_CHECK_DEPTH_IN_L1_BLOCKS="64"
+            ETH_WATCH_L2_TO_L1_MESSAGE_TRACKING_ENABLED="true"
+            ETH_WATCH_L2_TO_L1_MESSAGE_TRACKING_INTERVAL_MS="5000"
+            ETH_WATCH_L2_TO_L1_MESSAGE_TRACKING_FIRST_L1_BATCH="10"
+            ETH_WATCH_L2_TO_L1_MESSAGE_RETENTION_L1_BATCHES="1000"
         "#;
         lock.set_env(config);
 
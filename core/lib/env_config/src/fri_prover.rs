@@ -12,7 +12,10 @@ impl FromEnv for FriProverConfig {
 
 #[cfg(test)]
 mod tests {
-    use zksync_config::{configs::object_store::ObjectStoreMode, ObjectStoreConfig};
+    use zksync_config::{
+        configs::object_store::{ContentAddressingMode, ObjectStoreMode},
+        ObjectStoreConfig,
+    };
 
     use super::*;
     use crate::test_utils::EnvMutex;
@@ -32,6 +35,7 @@ mod tests {
                 },
                 max_retries: 5,
                 local_mirror_path: None,
+                content_addressing: ContentAddressingMode::Disabled,
             }),
         }
     }
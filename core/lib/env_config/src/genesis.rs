@@ -107,6 +107,7 @@ impl FromEnv for GenesisConfig {
             dummy_verifier: false,
             l1_batch_commit_data_generator_mode: state_keeper.l1_batch_commit_data_generator_mode,
             custom_genesis_state_path: custom_genesis_state_config.path,
+            circuit_geometry: None,
         })
     }
 }
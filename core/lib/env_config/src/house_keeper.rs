@@ -18,6 +18,9 @@ mod tests {
     fn expected_config() -> HouseKeeperConfig {
         HouseKeeperConfig {
             l1_batch_metrics_reporting_interval_ms: 10_000,
+            inclusion_stats_reporting_interval_ms: Some(60_000),
+            inclusion_stats_window_secs: Some(7_200),
+            contract_usage_aggregation_interval_ms: Some(30_000),
         }
     }
 
@@ -26,6 +29,9 @@ mod tests {
         let mut lock = MUTEX.lock();
         let config = r#"
             HOUSE_KEEPER_L1_BATCH_METRICS_REPORTING_INTERVAL_MS="10000"
+            HOUSE_KEEPER_INCLUSION_STATS_REPORTING_INTERVAL_MS="60000"
+            HOUSE_KEEPER_INCLUSION_STATS_WINDOW_SECS="7200"
+            HOUSE_KEEPER_CONTRACT_USAGE_AGGREGATION_INTERVAL_MS="30000"
         "#;
         lock.set_env(config);
 
@@ -19,6 +19,7 @@ mod snapshots_creator;
 mod utils;
 
 mod base_token_adjuster;
+mod batch_exporter;
 mod da_dispatcher;
 mod external_price_api_client;
 mod external_proof_integration_api;
@@ -31,6 +32,7 @@ mod wallets;
 
 pub mod da_client;
 mod timestamp_asserter;
+mod tx_policy;
 
 pub trait FromEnv: Sized {
     fn from_env() -> anyhow::Result<Self>;
@@ -31,7 +31,10 @@ impl FromEnv for SnapshotsObjectStoreConfig {
 
 #[cfg(test)]
 mod tests {
-    use zksync_config::{configs::object_store::ObjectStoreMode, ObjectStoreConfig};
+    use zksync_config::{
+        configs::object_store::{ContentAddressingMode, ObjectStoreMode},
+        ObjectStoreConfig,
+    };
 
     use super::*;
     use crate::test_utils::EnvMutex;
@@ -46,6 +49,7 @@ mod tests {
             },
             max_retries: 5,
             local_mirror_path: Some("/var/cache".to_owned()),
+            content_addressing: ContentAddressingMode::Disabled,
         }
     }
 
@@ -30,7 +30,14 @@ mod tests {
                 first_tee_processed_batch: L1BatchNumber(1337),
                 tee_proof_generation_timeout_in_secs: 600,
                 tee_batch_permanently_ignored_timeout_in_hours: 240,
+                attestation_policy_enabled: true,
+                sgx_mrenclave_allowlist: vec!["aa".repeat(32), "bb".repeat(32)],
+                sgx_mrsigner_allowlist: vec![],
+                tdx_mrtd_allowlist: vec!["cc".repeat(48)],
             },
+            proof_sampling_rate: Some(0.1),
+            proof_sampling_always_prove_ranges: vec!["1-100".to_owned(), "1000-1000".to_owned()],
+            proof_sampling_never_prove_ranges: vec![],
         }
     }
 
@@ -43,6 +50,11 @@ mod tests {
             PROOF_DATA_HANDLER_FIRST_TEE_PROCESSED_BATCH="1337"
             PROOF_DATA_HANDLER_TEE_PROOF_GENERATION_TIMEOUT_IN_SECS="600"
             PROOF_DATA_HANDLER_TEE_BATCH_PERMANENTLY_IGNORED_TIMEOUT_IN_HOURS="240"
+            PROOF_DATA_HANDLER_ATTESTATION_POLICY_ENABLED="true"
+            PROOF_DATA_HANDLER_SGX_MRENCLAVE_ALLOWLIST="aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa,bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"
+            PROOF_DATA_HANDLER_PROOF_SAMPLING_RATE="0.1"
+            PROOF_DATA_HANDLER_PROOF_SAMPLING_ALWAYS_PROVE_RANGES="1-100,1000-1000"
+            PROOF_DATA_HANDLER_TDX_MRTD_ALLOWLIST="cccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc"
         "#;
         let mut lock = MUTEX.lock();
         lock.set_env(config);
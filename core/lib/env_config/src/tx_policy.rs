@@ -0,0 +1,43 @@
+use zksync_config::configs::TxPolicyConfig;
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for TxPolicyConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("tx_policy", "TX_POLICY_")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{addr, EnvMutex};
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    #[test]
+    fn from_env_tx_policy() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            TX_POLICY_ALLOWED_INITIATORS="0x0000000000000000000000000000000000000001,0x0000000000000000000000000000000000000002"
+            TX_POLICY_DENIED_ADDRESSES="0x0000000000000000000000000000000000000003"
+            TX_POLICY_ALLOWED_DEPLOYERS="0x0000000000000000000000000000000000000001"
+            TX_POLICY_MAX_TXS_PER_ADDRESS_PER_MINUTE=60
+        "#;
+        lock.set_env(config);
+
+        let actual = TxPolicyConfig::from_env().unwrap();
+        assert_eq!(
+            actual,
+            TxPolicyConfig {
+                allowed_initiators: vec![
+                    addr("0x0000000000000000000000000000000000000001"),
+                    addr("0x0000000000000000000000000000000000000002"),
+                ],
+                denied_addresses: vec![addr("0x0000000000000000000000000000000000000003")],
+                allowed_deployers: Some(vec![addr("0x0000000000000000000000000000000000000001")]),
+                max_txs_per_address_per_minute: Some(60),
+            }
+        );
+    }
+}
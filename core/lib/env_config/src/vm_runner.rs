@@ -27,6 +27,8 @@ impl FromEnv for ExperimentalVmConfig {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use zksync_basic_types::{vm::FastVmMode, L1BatchNumber};
 
     use super::*;
@@ -50,12 +52,43 @@ mod tests {
         assert_eq!(config.first_processed_batch, L1BatchNumber(123));
     }
 
+    #[test]
+    fn protective_reads_config_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            VM_RUNNER_PROTECTIVE_READS_DB_PATH=/db/protective_reads
+            VM_RUNNER_PROTECTIVE_READS_WINDOW_SIZE=3
+            VM_RUNNER_PROTECTIVE_READS_FIRST_PROCESSED_BATCH=10
+            VM_RUNNER_PROTECTIVE_READS_FAST_VM_MODE=shadow
+            VM_RUNNER_PROTECTIVE_READS_FAIL_ON_DIVERGENCE=true
+        "#;
+        lock.set_env(config);
+
+        let config = ProtectiveReadsWriterConfig::from_env().unwrap();
+        assert_eq!(config.db_path, "/db/protective_reads");
+        assert_eq!(config.window_size, 3);
+        assert_eq!(config.first_processed_batch, L1BatchNumber(10));
+        assert_eq!(config.fast_vm_mode, FastVmMode::Shadow);
+        assert!(config.fail_on_divergence);
+
+        lock.remove_env(&[
+            "VM_RUNNER_PROTECTIVE_READS_FAST_VM_MODE",
+            "VM_RUNNER_PROTECTIVE_READS_FAIL_ON_DIVERGENCE",
+        ]);
+        let config = ProtectiveReadsWriterConfig::from_env().unwrap();
+        assert_eq!(config.fast_vm_mode, FastVmMode::Old);
+        assert!(!config.fail_on_divergence);
+    }
+
     #[test]
     fn experimental_vm_config_from_env() {
         let mut lock = MUTEX.lock();
         let config = r#"
             EXPERIMENTAL_VM_STATE_KEEPER_FAST_VM_MODE=new
             EXPERIMENTAL_VM_API_FAST_VM_MODE=shadow
+            EXPERIMENTAL_VM_API_VM_WARM_POOL_SIZE=4
+            EXPERIMENTAL_VM_API_VM_MAX_MEMORY_MB=512
+            EXPERIMENTAL_VM_API_VM_MAX_CPU_TIME_MS=2000
             EXPERIMENTAL_VM_PLAYGROUND_FAST_VM_MODE=shadow
             EXPERIMENTAL_VM_PLAYGROUND_DB_PATH=/db/vm_playground
             EXPERIMENTAL_VM_PLAYGROUND_FIRST_PROCESSED_BATCH=123
@@ -66,6 +99,11 @@ mod tests {
         let config = ExperimentalVmConfig::from_env().unwrap();
         assert_eq!(config.state_keeper_fast_vm_mode, FastVmMode::New);
         assert_eq!(config.api_fast_vm_mode, FastVmMode::Shadow);
+        assert_eq!(config.api_vm_warm_pool_size, Some(4));
+        assert!(!config.api_vm_resource_accounting);
+        assert!(config.api_vm_resource_accounting_enabled());
+        assert_eq!(config.api_vm_max_memory(), Some(512 << 20));
+        assert_eq!(config.api_vm_max_cpu_time(), Some(Duration::from_secs(2)));
         assert_eq!(config.playground.fast_vm_mode, FastVmMode::Shadow);
         assert_eq!(config.playground.db_path.unwrap(), "/db/vm_playground");
         assert_eq!(config.playground.first_processed_batch, L1BatchNumber(123));
@@ -3,7 +3,8 @@ use std::str::FromStr;
 use anyhow::Context;
 use zksync_basic_types::{Address, H256};
 use zksync_config::configs::wallets::{
-    AddressWallet, EthSender, StateKeeper, TokenMultiplierSetter, Wallet, Wallets,
+    AddressWallet, EthSender, SoftConfirmationSigner, StateKeeper, TokenMultiplierSetter, Wallet,
+    Wallets,
 };
 
 use crate::FromEnv;
@@ -34,9 +35,26 @@ impl FromEnv for Wallets {
                 None
             };
 
+            let additional_operators =
+                match std::env::var("ETH_SENDER_SENDER_ADDITIONAL_OPERATOR_PRIVATE_KEYS") {
+                    Ok(keys) => keys
+                        .split(',')
+                        .filter(|pk| !pk.trim().is_empty())
+                        .map(|pk| {
+                            let pk = pk
+                                .trim()
+                                .parse::<H256>()
+                                .context("Malformed additional operator pk")?;
+                            Wallet::from_private_key_bytes(pk, None)
+                        })
+                        .collect::<anyhow::Result<_>>()?,
+                    Err(_) => vec![],
+                };
+
             Some(EthSender {
                 operator,
                 blob_operator,
+                additional_operators,
             })
         } else {
             None
@@ -62,10 +80,20 @@ impl FromEnv for Wallets {
                 None
             };
 
+        let soft_confirmation_signer_pk = pk_from_env(
+            "SOFT_CONFIRMATION_SIGNER_PRIVATE_KEY",
+            "Malformed soft confirmation signer pk",
+        )?;
+        let soft_confirmation_signer = soft_confirmation_signer_pk
+            .map(|pk| Wallet::from_private_key_bytes(pk, None))
+            .transpose()?
+            .map(|wallet| SoftConfirmationSigner { wallet });
+
         Ok(Self {
             eth_sender,
             state_keeper,
             token_multiplier_setter,
+            soft_confirmation_signer,
         })
     }
 }
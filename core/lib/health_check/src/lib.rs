@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet},
     fmt,
     sync::{Arc, Mutex},
     thread,
@@ -45,6 +45,16 @@ impl HealthStatus {
         matches!(self, Self::Ready | Self::Affected)
     }
 
+    /// Returns the readiness level of a component with this status, disregarding its dependencies.
+    pub fn readiness_level(self) -> ReadinessLevel {
+        match self {
+            Self::Ready => ReadinessLevel::Ready,
+            Self::Affected => ReadinessLevel::Degraded,
+            Self::NotReady | Self::ShuttingDown => ReadinessLevel::Live,
+            Self::ShutDown | Self::Panicked => ReadinessLevel::Down,
+        }
+    }
+
     fn priority_for_aggregation(self) -> usize {
         match self {
             Self::Ready => 0,
@@ -57,6 +67,116 @@ impl HealthStatus {
     }
 }
 
+/// Readiness level of a component taking into account the health of its dependencies. Levels are ordered
+/// from the best to the worst one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadinessLevel {
+    /// Component and all its dependencies are ready.
+    Ready,
+    /// Component is operational, but it or some of its dependencies are affected or unhealthy.
+    Degraded,
+    /// Component is not operational (e.g., is initializing or shutting down), but hasn't failed.
+    Live,
+    /// Component is shut down or has panicked.
+    Down,
+}
+
+/// Structured reason for a component not being fully ready.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReadinessReason {
+    /// Name of the component causing the issue; either the component itself, or one of its (maybe transitive) dependencies.
+    pub component: &'static str,
+    /// Health status of the component causing the issue.
+    pub status: HealthStatus,
+}
+
+/// Readiness of a single component.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ComponentReadiness {
+    pub level: ReadinessLevel,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reasons: Vec<ReadinessReason>,
+}
+
+/// Aggregated readiness of an application. Unlike [`AppHealth`], accounts for dependencies among components.
+#[derive(Debug, Clone, Serialize)]
+pub struct AppReadiness {
+    /// Worst readiness level among all components.
+    pub level: ReadinessLevel,
+    pub components: BTreeMap<&'static str, ComponentReadiness>,
+}
+
+impl AppReadiness {
+    fn new(
+        components: &HashMap<&'static str, Health>,
+        dependencies: &HashMap<&'static str, Vec<&'static str>>,
+    ) -> Self {
+        let components: BTreeMap<_, _> = components
+            .iter()
+            .map(|(&name, health)| {
+                let readiness = Self::component_readiness(name, health, components, dependencies);
+                (name, readiness)
+            })
+            .collect();
+        let level = components
+            .values()
+            .map(|readiness| readiness.level)
+            .max()
+            .unwrap_or(ReadinessLevel::Ready);
+        Self { level, components }
+    }
+
+    fn component_readiness(
+        name: &'static str,
+        health: &Health,
+        components: &HashMap<&'static str, Health>,
+        dependencies: &HashMap<&'static str, Vec<&'static str>>,
+    ) -> ComponentReadiness {
+        let mut level = health.status.readiness_level();
+        let mut reasons = vec![];
+        if level != ReadinessLevel::Ready {
+            reasons.push(ReadinessReason {
+                component: name,
+                status: health.status,
+            });
+        }
+
+        // Traverse transitive dependencies; dependencies without a registered health check are ignored.
+        let mut visited = HashSet::from([name]);
+        let mut stack = dependencies.get(name).cloned().unwrap_or_default();
+        while let Some(dependency) = stack.pop() {
+            if !visited.insert(dependency) {
+                continue;
+            }
+            if let Some(dependency_health) = components.get(dependency) {
+                if dependency_health.status.readiness_level() != ReadinessLevel::Ready {
+                    // An unhealthy dependency degrades the component, but doesn't make it non-operational by itself.
+                    level = level.max(ReadinessLevel::Degraded);
+                    reasons.push(ReadinessReason {
+                        component: dependency,
+                        status: dependency_health.status,
+                    });
+                }
+            }
+            if let Some(transitive_dependencies) = dependencies.get(dependency) {
+                stack.extend_from_slice(transitive_dependencies);
+            }
+        }
+        ComponentReadiness { level, reasons }
+    }
+
+    /// Checks whether the application can serve traffic, possibly in a degraded mode.
+    pub fn is_ready(&self) -> bool {
+        self.level <= ReadinessLevel::Degraded
+    }
+
+    /// Checks whether the application is alive, i.e. none of its components are shut down or panicked.
+    pub fn is_live(&self) -> bool {
+        self.level < ReadinessLevel::Down
+    }
+}
+
 /// Health of a single component.
 #[derive(Debug, Clone, Serialize, PartialEq)]
 pub struct Health {
@@ -114,6 +234,8 @@ struct AppHealthCheckInner {
     /// Application-level health details.
     app_details: Option<serde_json::Value>,
     components: Vec<Arc<dyn CheckHealth>>,
+    /// Dependencies among components, keyed by the dependent component name.
+    dependencies: HashMap<&'static str, Vec<&'static str>>,
     slow_time_limit: Duration,
     hard_time_limit: Duration,
 }
@@ -135,6 +257,7 @@ impl AppHealthCheck {
 
         let inner = AppHealthCheckInner {
             components: Vec::default(),
+            dependencies: HashMap::new(),
             app_details: None,
             slow_time_limit,
             hard_time_limit,
@@ -222,11 +345,23 @@ impl AppHealthCheck {
         Ok(())
     }
 
+    /// Declares that `component` depends on `dependency` (e.g., an API server depends on the database).
+    /// Unhealthy dependencies degrade readiness of the dependent component as reported by [`AppHealth::readiness()`].
+    /// Components are identified by their names; it's not necessary for a component to be inserted beforehand.
+    pub fn add_dependency(&self, component: &'static str, dependency: &'static str) {
+        let mut guard = self.inner.lock().expect("`AppHealthCheck` is poisoned");
+        let component_dependencies = guard.dependencies.entry(component).or_default();
+        if !component_dependencies.contains(&dependency) {
+            component_dependencies.push(dependency);
+        }
+    }
+
     /// Checks the overall application health. This will query all component checks concurrently.
     pub async fn check_health(&self) -> AppHealth {
         // Clone `inner` so that we don't hold a lock for them across a wait point.
         let AppHealthCheckInner {
             components,
+            dependencies,
             app_details,
             slow_time_limit,
             hard_time_limit,
@@ -249,7 +384,12 @@ impl AppHealthCheck {
         let mut inner = Health::from(aggregated_status);
         inner.details = app_details.clone();
 
-        let health = AppHealth { inner, components };
+        let readiness = AppReadiness::new(&components, &dependencies);
+        let health = AppHealth {
+            inner,
+            components,
+            readiness,
+        };
         if !health.inner.status.is_healthy() {
             // Only log non-ready application health so that logs are not spammed without a reason.
             tracing::debug!("Aggregated application health: {health:?}");
@@ -329,6 +469,8 @@ pub struct AppHealth {
     #[serde(flatten)]
     inner: Health,
     components: HashMap<&'static str, Health>,
+    #[serde(skip)]
+    readiness: AppReadiness,
 }
 
 impl AppHealth {
@@ -345,6 +487,11 @@ impl AppHealth {
     pub fn components(&self) -> &HashMap<&'static str, Health> {
         &self.components
     }
+
+    /// Returns the readiness of the application and its components, taking into account dependencies among components.
+    pub fn readiness(&self) -> &AppReadiness {
+        &self.readiness
+    }
 }
 
 /// Interface to be used for health checks.
@@ -84,6 +84,7 @@ async fn aggregating_health_checks() {
     let inner = AppHealthCheckInner {
         app_details: None,
         components: vec![Arc::new(first_check), Arc::new(second_check)],
+        dependencies: HashMap::new(),
         slow_time_limit: AppHealthCheck::DEFAULT_SLOW_TIME_LIMIT,
         hard_time_limit: AppHealthCheck::DEFAULT_HARD_TIME_LIMIT,
     };
@@ -140,6 +141,71 @@ async fn aggregating_health_checks() {
     );
 }
 
+#[tokio::test]
+async fn readiness_accounts_for_dependencies() {
+    let checks = AppHealthCheck::default();
+    let (db_check, db_updater) = ReactiveHealthCheck::new("database");
+    let (state_keeper_check, state_keeper_updater) = ReactiveHealthCheck::new("state_keeper");
+    let (api_check, api_updater) = ReactiveHealthCheck::new("api");
+    checks.insert_component(db_check).unwrap();
+    checks.insert_component(state_keeper_check).unwrap();
+    checks.insert_component(api_check).unwrap();
+    checks.add_dependency("state_keeper", "database");
+    checks.add_dependency("api", "state_keeper");
+    // Dependencies without health checks are ignored.
+    checks.add_dependency("api", "unknown");
+
+    db_updater.update(HealthStatus::Ready.into());
+    state_keeper_updater.update(HealthStatus::Ready.into());
+    api_updater.update(HealthStatus::Ready.into());
+    let readiness = checks.check_health().await.readiness().clone();
+    assert_eq!(readiness.level, ReadinessLevel::Ready);
+    assert!(readiness.is_ready());
+    assert!(readiness.components.values().all(|component| {
+        component.level == ReadinessLevel::Ready && component.reasons.is_empty()
+    }));
+
+    db_updater.update(HealthStatus::NotReady.into());
+    let readiness = checks.check_health().await.readiness().clone();
+    assert_eq!(readiness.level, ReadinessLevel::Live);
+    assert!(!readiness.is_ready());
+    assert!(readiness.is_live());
+    assert_eq!(readiness.components["database"].level, ReadinessLevel::Live);
+    let expected_reasons = [ReadinessReason {
+        component: "database",
+        status: HealthStatus::NotReady,
+    }];
+    assert_eq!(
+        readiness.components["state_keeper"].level,
+        ReadinessLevel::Degraded
+    );
+    assert_eq!(
+        readiness.components["state_keeper"].reasons,
+        expected_reasons
+    );
+    // The API depends on the database transitively.
+    assert_eq!(readiness.components["api"].level, ReadinessLevel::Degraded);
+    assert_eq!(readiness.components["api"].reasons, expected_reasons);
+
+    db_updater.update(HealthStatus::Ready.into());
+    drop(state_keeper_updater);
+    let readiness = checks.check_health().await.readiness().clone();
+    assert_eq!(readiness.level, ReadinessLevel::Down);
+    assert!(!readiness.is_live());
+    assert_eq!(
+        readiness.components["database"].level,
+        ReadinessLevel::Ready
+    );
+    assert_eq!(readiness.components["api"].level, ReadinessLevel::Degraded);
+    assert_eq!(
+        readiness.components["api"].reasons,
+        [ReadinessReason {
+            component: "state_keeper",
+            status: HealthStatus::ShutDown,
+        }]
+    );
+}
+
 #[test]
 fn adding_duplicate_component() {
     let checks = AppHealthCheck::default();
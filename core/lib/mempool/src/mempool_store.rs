@@ -242,6 +242,11 @@ impl MempoolStore {
         }
     }
 
+    /// Removes all transactions from the mempool and resets the next expected priority operation ID.
+    pub fn clear(&mut self, next_priority_id: PriorityOpId) {
+        *self = Self::new(next_priority_id, self.capacity);
+    }
+
     pub fn get_mempool_info(&mut self) -> MempoolInfo {
         MempoolInfo {
             stashed_accounts: std::mem::take(&mut self.stashed_accounts),
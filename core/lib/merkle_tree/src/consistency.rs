@@ -7,7 +7,7 @@ use rayon::prelude::*;
 use crate::{
     errors::DeserializeError,
     hasher::{HashTree, HasherWithStats},
-    types::{LeafNode, Nibbles, Node, NodeKey, Root},
+    types::{ChildRef, InternalNode, LeafNode, Nibbles, Node, NodeKey, Root},
     Database, Key, MerkleTree, ValueHash,
 };
 
@@ -106,6 +106,52 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
         Ok(())
     }
 
+    /// Verifies consistency of a single subtree of the tree at the specified `version`, i.e., the subtree rooted
+    /// at the root child with the specified `nibble`. This allows splitting verification of large trees into smaller steps.
+    /// If the tree root is a leaf, the entire tree is verified for `nibble == 0`.
+    ///
+    /// Leaf indices are validated only if `leaf_data` is provided; in this case,
+    /// [`LeafConsistencyData::validate_count()`] should be called once all subtrees are verified.
+    pub(crate) fn verify_subtree_consistency(
+        &self,
+        version: u64,
+        nibble: u8,
+        leaf_data: Option<&LeafConsistencyData>,
+    ) -> Result<(), ConsistencyError> {
+        let manifest = self.db.try_manifest()?;
+        let manifest = manifest.ok_or(ConsistencyError::MissingVersion(version))?;
+        if version >= manifest.version_count {
+            return Err(ConsistencyError::MissingVersion(version));
+        }
+
+        let root = self
+            .db
+            .try_root(version)?
+            .ok_or(ConsistencyError::MissingRoot(version))?;
+        let Root::Filled {
+            node: root_node, ..
+        } = root
+        else {
+            return Ok(());
+        };
+
+        let root_key = Nibbles::EMPTY.with_version(version);
+        match &root_node {
+            Node::Leaf(_) => {
+                if nibble == 0 {
+                    self.validate_node(&root_node, root_key, leaf_data)?;
+                }
+            }
+            Node::Internal(node) => {
+                Self::validate_internal_node_version(node, root_key)?;
+                if let Some(child_ref) = node.child_ref(nibble) {
+                    self.validate_child(root_key, nibble, child_ref, leaf_data)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn validate_node(
         &self,
         node: &Node,
@@ -127,20 +173,7 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
             }
 
             Node::Internal(node) => {
-                let expected_version = node.child_refs().map(|child_ref| child_ref.version).max();
-                let Some(expected_version) = expected_version else {
-                    return Err(ConsistencyError::EmptyInternalNode { key });
-                };
-                if !key.is_empty() && expected_version != key.version {
-                    return Err(ConsistencyError::KeyVersionMismatch {
-                        key,
-                        expected_version,
-                    });
-                } else if key.is_empty() && expected_version > key.version {
-                    return Err(ConsistencyError::RootVersionMismatch {
-                        max_child_version: expected_version,
-                    });
-                }
+                Self::validate_internal_node_version(node, key)?;
 
                 // `.into_par_iter()` below is the only place where `rayon`-based parallelism
                 // is used in tree verification.
@@ -148,32 +181,7 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
                 children
                     .into_par_iter()
                     .try_for_each(|(nibble, child_ref)| {
-                        let child_key = key
-                            .nibbles
-                            .push(nibble)
-                            .ok_or(ConsistencyError::TerminalInternalNode { key })?;
-                        let child_key = child_key.with_version(child_ref.version);
-                        let child = self
-                            .db
-                            .try_tree_node(&child_key, child_ref.is_leaf)?
-                            .ok_or(ConsistencyError::MissingNode {
-                                key: child_key,
-                                is_leaf: child_ref.is_leaf,
-                            })?;
-
-                        // Recursion here is OK; the tree isn't that deep (approximately 8 nibbles for a tree with
-                        // approximately 1B entries).
-                        let child_hash = self.validate_node(&child, child_key, leaf_data)?;
-                        if child_hash == child_ref.hash {
-                            Ok(())
-                        } else {
-                            Err(ConsistencyError::HashMismatch {
-                                key,
-                                nibble,
-                                expected: child_ref.hash,
-                                actual: child_hash,
-                            })
-                        }
+                        self.validate_child(key, nibble, child_ref, leaf_data)
                     })?;
             }
         }
@@ -181,10 +189,66 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
         let level = key.nibbles.nibble_count() * 4;
         Ok(node.hash(&mut HasherWithStats::new(&self.hasher), level))
     }
+
+    fn validate_internal_node_version(
+        node: &InternalNode,
+        key: NodeKey,
+    ) -> Result<(), ConsistencyError> {
+        let expected_version = node.child_refs().map(|child_ref| child_ref.version).max();
+        let Some(expected_version) = expected_version else {
+            return Err(ConsistencyError::EmptyInternalNode { key });
+        };
+        if !key.is_empty() && expected_version != key.version {
+            return Err(ConsistencyError::KeyVersionMismatch {
+                key,
+                expected_version,
+            });
+        } else if key.is_empty() && expected_version > key.version {
+            return Err(ConsistencyError::RootVersionMismatch {
+                max_child_version: expected_version,
+            });
+        }
+        Ok(())
+    }
+
+    fn validate_child(
+        &self,
+        key: NodeKey,
+        nibble: u8,
+        child_ref: &ChildRef,
+        leaf_data: Option<&LeafConsistencyData>,
+    ) -> Result<(), ConsistencyError> {
+        let child_key = key
+            .nibbles
+            .push(nibble)
+            .ok_or(ConsistencyError::TerminalInternalNode { key })?;
+        let child_key = child_key.with_version(child_ref.version);
+        let child = self
+            .db
+            .try_tree_node(&child_key, child_ref.is_leaf)?
+            .ok_or(ConsistencyError::MissingNode {
+                key: child_key,
+                is_leaf: child_ref.is_leaf,
+            })?;
+
+        // Recursion here is OK; the tree isn't that deep (approximately 8 nibbles for a tree with
+        // approximately 1B entries).
+        let child_hash = self.validate_node(&child, child_key, leaf_data)?;
+        if child_hash == child_ref.hash {
+            Ok(())
+        } else {
+            Err(ConsistencyError::HashMismatch {
+                key,
+                nibble,
+                expected: child_ref.hash,
+                actual: child_hash,
+            })
+        }
+    }
 }
 
 #[derive(Debug)]
-struct LeafConsistencyData {
+pub(crate) struct LeafConsistencyData {
     expected_leaf_count: u64,
     actual_leaf_count: AtomicU64,
     leaf_indices_set: AtomicBitSet,
@@ -192,14 +256,24 @@ struct LeafConsistencyData {
 
 #[allow(clippy::cast_possible_truncation)] // expected leaf count is quite small
 impl LeafConsistencyData {
-    fn new(expected_leaf_count: u64) -> Self {
+    pub(crate) fn new(expected_leaf_count: u64) -> Self {
+        Self::resumed(expected_leaf_count, 0)
+    }
+
+    /// Creates data for a verification resumed after `checked_leaf_count` leaves were already checked
+    /// (e.g., by a previous process). Uniqueness of indices is not checked for these leaves.
+    pub(crate) fn resumed(expected_leaf_count: u64, checked_leaf_count: u64) -> Self {
         Self {
             expected_leaf_count,
-            actual_leaf_count: AtomicU64::new(0),
+            actual_leaf_count: AtomicU64::new(checked_leaf_count),
             leaf_indices_set: AtomicBitSet::new(expected_leaf_count as usize),
         }
     }
 
+    pub(crate) fn checked_leaf_count(&self) -> u64 {
+        self.actual_leaf_count.load(Ordering::Relaxed)
+    }
+
     fn insert_leaf(&self, leaf: &LeafNode) -> Result<(), ConsistencyError> {
         if leaf.leaf_index == 0 {
             return Err(ConsistencyError::ZeroIndex {
@@ -225,7 +299,7 @@ impl LeafConsistencyData {
         Ok(())
     }
 
-    fn validate_count(mut self) -> Result<(), ConsistencyError> {
+    pub(crate) fn validate_count(mut self) -> Result<(), ConsistencyError> {
         let actual_leaf_count = *self.actual_leaf_count.get_mut();
         if actual_leaf_count == self.expected_leaf_count {
             Ok(())
@@ -13,7 +13,7 @@ use crate::{
         ValueHash, TREE_DEPTH,
     },
     BlockOutput, HashTree, MerkleTree, MerkleTreePruner, MerkleTreePrunerHandle, NoVersionError,
-    PruneDatabase,
+    PruneDatabase, TreeEntriesWithMultiProof,
 };
 
 impl TreeInstruction<StorageKey> {
@@ -453,6 +453,21 @@ impl ZkSyncTreeReader {
         self.0.entries_with_proofs(version, keys)
     }
 
+    /// Reads entries with the specified keys from the tree together with a single multiproof for all entries.
+    /// The entries are returned in the same order as requested.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    pub fn entries_with_multiproof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        keys: &[Key],
+    ) -> Result<TreeEntriesWithMultiProof, NoVersionError> {
+        let version = u64::from(l1_batch_number.0);
+        self.0.entries_with_multiproof(version, keys)
+    }
+
     /// Returns raw nodes for the specified `keys`.
     pub fn raw_nodes(&self, keys: &[NodeKey]) -> Vec<Option<RawNode>> {
         let raw_nodes = self.0.db.raw_nodes(keys).into_iter();
@@ -4,7 +4,10 @@ use crate::{
     hasher::HasherWithStats,
     recovery::MerkleTreeRecovery,
     storage::{LoadAncestorsResult, SortedKeys, WorkingPatchSet},
-    types::{Nibbles, Node, ProfiledTreeOperation, TreeEntry, TreeEntryWithProof},
+    types::{
+        Nibbles, Node, ProfiledTreeOperation, TreeEntriesWithMultiProof, TreeEntry,
+        TreeEntryWithProof, TreeMultiProof,
+    },
     Database, HashTree, Key, MerkleTree, NoVersionError, PruneDatabase, ValueHash,
 };
 
@@ -59,6 +62,28 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
             },
         )
     }
+
+    /// Reads entries with the specified keys from the tree together with a single [`TreeMultiProof`]
+    /// proving all entries at once. The entries are returned in the same order as requested.
+    ///
+    /// Unlike [`Self::entries_with_proofs()`], sibling hashes shared by the entries are only included
+    /// into the proof once, which makes the proof much more compact for large batches of keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    pub fn entries_with_multiproof(
+        &self,
+        version: u64,
+        leaf_keys: &[Key],
+    ) -> Result<TreeEntriesWithMultiProof, NoVersionError> {
+        let proofs = self.entries_with_proofs(version, leaf_keys)?;
+        let proof = TreeMultiProof::from_proofs(&self.hasher, &proofs);
+        Ok(TreeEntriesWithMultiProof {
+            entries: proofs.into_iter().map(|entry| entry.base).collect(),
+            proof,
+        })
+    }
 }
 
 fn load_and_transform_entries<T>(
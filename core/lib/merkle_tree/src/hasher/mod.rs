@@ -12,6 +12,7 @@ use crate::{
     types::{TreeEntry, ValueHash, TREE_DEPTH},
 };
 
+mod multiproof;
 mod nodes;
 mod proofs;
 
@@ -0,0 +1,354 @@
+//! Merkle multiproofs, i.e., authenticity proofs for multiple tree entries sharing sibling hashes.
+
+use std::convert::Infallible;
+
+use anyhow::ensure;
+
+use crate::{
+    hasher::HashTree,
+    types::{
+        Key, TreeEntriesWithMultiProof, TreeEntry, TreeEntryWithProof, TreeMultiProof, ValueHash,
+        HASH_SIZE, TREE_DEPTH,
+    },
+};
+
+/// Folds leaf-level `nodes` sorted by key into the root node. Returns `None` if `nodes` are empty.
+///
+/// On each tree level, adjacent sibling nodes are combined using `merge`. For a node without a sibling among `nodes`,
+/// `merge_with_sibling` is called with the depth of the level and a flag whether the node is the right child
+/// of its parent. The traversal order is the same for building and verifying multiproofs, which is what makes
+/// the order of hashes in a [`TreeMultiProof`] well-defined.
+fn fold_levels<T, E>(
+    mut nodes: Vec<(Key, T)>,
+    mut merge: impl FnMut(usize, T, T) -> T,
+    mut merge_with_sibling: impl FnMut(usize, bool, T) -> Result<T, E>,
+) -> Result<Option<T>, E> {
+    for depth in 0..TREE_DEPTH {
+        let mut parent_nodes = Vec::with_capacity(nodes.len());
+        let mut nodes_iter = nodes.into_iter().peekable();
+        while let Some((index, node)) = nodes_iter.next() {
+            let is_right = index.bit(0);
+            let parent = if !is_right
+                && nodes_iter
+                    .peek()
+                    .is_some_and(|(next, _)| *next == index + 1)
+            {
+                let (_, right) = nodes_iter.next().unwrap();
+                merge(depth, node, right)
+            } else {
+                merge_with_sibling(depth, is_right, node)?
+            };
+            parent_nodes.push((index >> 1, parent));
+        }
+        nodes = parent_nodes;
+    }
+    debug_assert!(nodes.len() <= 1);
+    Ok(nodes.pop().map(|(_, root)| root))
+}
+
+impl TreeMultiProof {
+    /// Builds a multiproof from Merkle proofs for individual entries, which must all be obtained
+    /// for the same tree version.
+    pub(crate) fn from_proofs(hasher: &dyn HashTree, proofs: &[TreeEntryWithProof]) -> Self {
+        let mut leaves: Vec<_> = proofs.iter().map(|proof| (proof.base.key, proof)).collect();
+        leaves.sort_unstable_by_key(|(key, _)| *key);
+        leaves.dedup_by_key(|(key, _)| *key);
+
+        let mut multiproof = Self::default();
+        let root = fold_levels(
+            leaves,
+            |_, left, _| left, // Any proof from the subtree can be used to get sibling hashes for upper levels
+            |depth, _, proof| {
+                let empty_hash_count = TREE_DEPTH - proof.merkle_path.len();
+                let sibling_hash = depth
+                    .checked_sub(empty_hash_count)
+                    .map(|idx| proof.merkle_path[idx])
+                    .filter(|hash| *hash != hasher.empty_subtree_hash(depth));
+                multiproof.sibling_flags.push(sibling_hash.is_some());
+                multiproof.hashes.extend(sibling_hash);
+                Ok::<_, Infallible>(proof)
+            },
+        );
+        if let Err(never) = root {
+            match never {}
+        }
+        multiproof
+    }
+
+    /// Computes the root hash of the tree based on the provided `entries` and this proof.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `entries` are empty, contain conflicting entries for the same key,
+    /// or if this proof doesn't correspond to the entries.
+    pub fn root_hash(
+        &self,
+        hasher: &dyn HashTree,
+        entries: &[TreeEntry],
+    ) -> anyhow::Result<ValueHash> {
+        let mut entries = entries.to_vec();
+        entries.sort_unstable_by_key(|entry| entry.key);
+        for window in entries.windows(2) {
+            ensure!(
+                window[0].key != window[1].key || window[0] == window[1],
+                "Conflicting entries for key {:0>64x}",
+                window[0].key
+            );
+        }
+        entries.dedup();
+
+        let mut leaves = Vec::with_capacity(entries.len());
+        for entry in entries {
+            if entry.leaf_index == 0 {
+                ensure!(
+                    entry.value.is_zero(),
+                    "Invalid missing value specification for key {:0>64x}: leaf index is zero, but value is non-default",
+                    entry.key
+                );
+            }
+            leaves.push((entry.key, hasher.hash_leaf(&entry.value, entry.leaf_index)));
+        }
+
+        let mut sibling_flags = self.sibling_flags.iter().copied();
+        let mut hashes = self.hashes.iter();
+        let root_hash = fold_levels(
+            leaves,
+            |_, left, right| hasher.hash_branch(&left, &right),
+            |depth, is_right, hash| {
+                let is_non_empty = sibling_flags
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("Proof is too short"))?;
+                let sibling_hash = if is_non_empty {
+                    *hashes
+                        .next()
+                        .ok_or_else(|| anyhow::anyhow!("Proof has too few hashes"))?
+                } else {
+                    hasher.empty_subtree_hash(depth)
+                };
+                anyhow::Ok(if is_right {
+                    hasher.hash_branch(&sibling_hash, &hash)
+                } else {
+                    hasher.hash_branch(&hash, &sibling_hash)
+                })
+            },
+        )?;
+
+        ensure!(sibling_flags.next().is_none(), "Proof is too long");
+        ensure!(hashes.next().is_none(), "Proof has too many hashes");
+        root_hash.ok_or_else(|| anyhow::anyhow!("No entries provided"))
+    }
+
+    /// Serializes this proof in a compact binary format consumable by external verifiers:
+    ///
+    /// - Number of sibling flags as a big-endian `u32`
+    /// - Sibling flags packed into bytes, 8 flags per byte starting from the most significant bit.
+    ///   The last byte is padded with zero bits.
+    /// - Hashes of non-empty siblings, 32 bytes each
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of hashes doesn't match sibling flags, or if there are more than `u32::MAX` flags.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let non_empty_count = self.sibling_flags.iter().filter(|&&flag| flag).count();
+        assert_eq!(
+            non_empty_count,
+            self.hashes.len(),
+            "Number of hashes doesn't match sibling flags"
+        );
+        let flag_count = u32::try_from(self.sibling_flags.len()).expect("too many sibling flags");
+
+        let mut bytes = Vec::with_capacity(
+            4 + self.sibling_flags.len().div_ceil(8) + self.hashes.len() * HASH_SIZE,
+        );
+        bytes.extend_from_slice(&flag_count.to_be_bytes());
+        for chunk in self.sibling_flags.chunks(8) {
+            let byte = chunk
+                .iter()
+                .enumerate()
+                .fold(0_u8, |acc, (i, &flag)| acc | (u8::from(flag) << (7 - i)));
+            bytes.push(byte);
+        }
+        for hash in &self.hashes {
+            bytes.extend_from_slice(hash.as_bytes());
+        }
+        bytes
+    }
+
+    /// Deserializes a proof from the format produced by [`Self::to_bytes()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is malformed.
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        ensure!(bytes.len() >= 4, "Proof is too short");
+        let (flag_count, bytes) = bytes.split_at(4);
+        let flag_count = u32::from_be_bytes(flag_count.try_into().unwrap()) as usize;
+        let flag_byte_count = flag_count.div_ceil(8);
+        ensure!(bytes.len() >= flag_byte_count, "Proof is too short");
+        let (flag_bytes, hash_bytes) = bytes.split_at(flag_byte_count);
+
+        let sibling_flags: Vec<_> = (0..flag_count)
+            .map(|i| flag_bytes[i / 8] & (1 << (7 - i % 8)) != 0)
+            .collect();
+        if flag_count % 8 != 0 {
+            let padding_mask = (1_u8 << (8 - flag_count % 8)) - 1;
+            ensure!(
+                flag_bytes[flag_byte_count - 1] & padding_mask == 0,
+                "Non-zero padding in sibling flags"
+            );
+        }
+
+        let hash_count = sibling_flags.iter().filter(|&&flag| flag).count();
+        ensure!(
+            hash_bytes.len() == hash_count * HASH_SIZE,
+            "Unexpected proof length: expected {} bytes of hashes, got {}",
+            hash_count * HASH_SIZE,
+            hash_bytes.len()
+        );
+        let hashes = hash_bytes
+            .chunks(HASH_SIZE)
+            .map(ValueHash::from_slice)
+            .collect();
+        Ok(Self {
+            sibling_flags,
+            hashes,
+        })
+    }
+}
+
+impl TreeEntriesWithMultiProof {
+    /// Verifies this multiproof.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error <=> proof is invalid.
+    pub fn verify(
+        &self,
+        hasher: &dyn HashTree,
+        trusted_root_hash: ValueHash,
+    ) -> anyhow::Result<()> {
+        let root_hash = self.proof.root_hash(hasher, &self.entries)?;
+        ensure!(
+            root_hash == trusted_root_hash,
+            "Root hash mismatch: got {root_hash}, want {trusted_root_hash}"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
+
+    use super::*;
+    use crate::{MerkleTree, PatchSet};
+
+    fn create_tree(entries: &[TreeEntry]) -> (MerkleTree<PatchSet>, ValueHash) {
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        let output = tree.extend(entries.to_vec()).unwrap();
+        (tree, output.root_hash)
+    }
+
+    #[test]
+    fn multiproof_for_single_entry() {
+        let entry = TreeEntry::new(Key::from(123), 1, ValueHash::repeat_byte(1));
+        let (tree, root_hash) = create_tree(&[entry]);
+
+        let output = tree.entries_with_multiproof(0, &[entry.key]).unwrap();
+        assert_eq!(output.entries, [entry]);
+        // All siblings in a single-leaf tree are empty.
+        assert_eq!(output.proof.sibling_flags, [false; TREE_DEPTH]);
+        assert!(output.proof.hashes.is_empty());
+        output.verify(&Blake2Hasher, root_hash).unwrap();
+    }
+
+    #[test]
+    fn multiproof_for_adjacent_entries() {
+        let entries = [
+            TreeEntry::new(Key::from(2), 1, ValueHash::repeat_byte(1)),
+            TreeEntry::new(Key::from(3), 2, ValueHash::repeat_byte(2)),
+        ];
+        let (tree, root_hash) = create_tree(&entries);
+
+        let keys = [entries[1].key, entries[0].key, entries[1].key];
+        let output = tree.entries_with_multiproof(0, &keys).unwrap();
+        assert_eq!(output.entries, [entries[1], entries[0], entries[1]]);
+        // Leaves are siblings, so no sibling is requested on the leaf level.
+        assert_eq!(output.proof.sibling_flags, [false; TREE_DEPTH - 1]);
+        output.verify(&Blake2Hasher, root_hash).unwrap();
+    }
+
+    #[test]
+    fn multiproof_with_missing_keys() {
+        let entries = [
+            TreeEntry::new(Key::from(2), 1, ValueHash::repeat_byte(1)),
+            TreeEntry::new(Key::MAX, 2, ValueHash::repeat_byte(2)),
+        ];
+        let (tree, root_hash) = create_tree(&entries);
+
+        let keys = [Key::from(5), entries[0].key, Key::MAX - 1];
+        let output = tree.entries_with_multiproof(0, &keys).unwrap();
+        assert!(output.entries[0].is_empty());
+        assert_eq!(output.entries[1], entries[0]);
+        assert!(output.entries[2].is_empty());
+        assert!(!output.proof.hashes.is_empty());
+        output.verify(&Blake2Hasher, root_hash).unwrap();
+    }
+
+    #[test]
+    fn invalid_multiproofs_are_rejected() {
+        let entries = [
+            TreeEntry::new(Key::from(2), 1, ValueHash::repeat_byte(1)),
+            TreeEntry::new(Key::MAX, 2, ValueHash::repeat_byte(2)),
+        ];
+        let (tree, root_hash) = create_tree(&entries);
+        let output = tree.entries_with_multiproof(0, &[entries[0].key]).unwrap();
+
+        let mut invalid_output = output.clone();
+        invalid_output.entries[0].value = ValueHash::zero();
+        let err = invalid_output.verify(&Blake2Hasher, root_hash).unwrap_err();
+        assert!(err.to_string().contains("Root hash mismatch"), "{err}");
+
+        let mut invalid_output = output.clone();
+        invalid_output
+            .entries
+            .push(entries[0].with_value(ValueHash::zero()));
+        let err = invalid_output.verify(&Blake2Hasher, root_hash).unwrap_err();
+        assert!(err.to_string().contains("Conflicting entries"), "{err}");
+
+        let mut invalid_output = output.clone();
+        invalid_output.proof.sibling_flags.push(false);
+        let err = invalid_output.verify(&Blake2Hasher, root_hash).unwrap_err();
+        assert!(err.to_string().contains("too long"), "{err}");
+
+        let mut invalid_output = output;
+        invalid_output.proof.hashes.clear();
+        let err = invalid_output.verify(&Blake2Hasher, root_hash).unwrap_err();
+        assert!(err.to_string().contains("too few hashes"), "{err}");
+    }
+
+    #[test]
+    fn multiproof_serialization_roundtrip() {
+        let proof = TreeMultiProof {
+            sibling_flags: vec![
+                true, false, false, true, false, false, false, false, true, false,
+            ],
+            hashes: vec![
+                ValueHash::repeat_byte(1),
+                ValueHash::repeat_byte(2),
+                ValueHash::repeat_byte(3),
+            ],
+        };
+        let bytes = proof.to_bytes();
+        assert_eq!(bytes.len(), 4 + 2 + 3 * HASH_SIZE);
+        assert_eq!(bytes[..6], [0, 0, 0, 10, 0b_1001_0000, 0b_1000_0000]);
+        assert_eq!(TreeMultiProof::from_bytes(&bytes).unwrap(), proof);
+
+        let err = TreeMultiProof::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert!(err.to_string().contains("Unexpected proof length"), "{err}");
+        let mut invalid_bytes = bytes;
+        invalid_bytes[5] |= 1;
+        let err = TreeMultiProof::from_bytes(&invalid_bytes).unwrap_err();
+        assert!(err.to_string().contains("padding"), "{err}");
+    }
+}
@@ -54,3 +54,26 @@ fn recovering_tree_with_single_node() {
     );
     tree.verify_consistency(42, true).unwrap();
 }
+
+#[test]
+fn recovering_tree_with_single_node_in_parallel() {
+    let mut recovery = MerkleTreeRecovery::new(PatchSet::default(), 42).unwrap();
+    let recovery_entry = TreeEntry::new(Key::from(123), 1, ValueHash::repeat_byte(1));
+    recovery.extend_parallel(vec![recovery_entry]).unwrap();
+    let tree = MerkleTree::new(recovery.finalize().unwrap()).unwrap();
+
+    let mut hasher = HasherWithStats::new(&Blake2Hasher);
+    assert_eq!(
+        tree.latest_root_hash(),
+        LeafNode::new(recovery_entry).hash(&mut hasher, 0)
+    );
+    tree.verify_consistency(42, true).unwrap();
+}
+
+#[test]
+fn parallel_recovery_for_empty_chunk() {
+    let mut recovery = MerkleTreeRecovery::new(PatchSet::default(), 42).unwrap();
+    recovery.extend_parallel(vec![]).unwrap();
+    let tree = MerkleTree::new(recovery.finalize().unwrap()).unwrap();
+    assert_eq!(tree.root(42), Some(Root::Empty));
+}
@@ -10,8 +10,9 @@ use anyhow::Context as _;
 use rayon::prelude::*;
 
 use crate::{
-    types::{NodeKey, StaleNodeKey},
-    Database, PruneDatabase, RocksDBWrapper,
+    consistency::{ConsistencyError, LeafConsistencyData},
+    types::{NodeKey, Root, StaleNodeKey},
+    Database, MerkleTree, PruneDatabase, RocksDBWrapper,
 };
 
 /// Persisted information about stale keys repair progress.
@@ -280,14 +281,328 @@ impl StaleKeysRepairTask {
     }
 }
 
+/// Persisted information about consistency check progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ConsistencyCheckData {
+    /// Tree version being checked.
+    pub version: u64,
+    /// Nibble of the next root subtree to check. Equals [`ConsistencyCheckTask::SUBTREE_COUNT`]
+    /// if the version is fully checked.
+    pub next_nibble: u8,
+    /// Number of leaves in the already checked subtrees.
+    pub checked_leaf_count: u64,
+}
+
+impl ConsistencyCheckData {
+    fn new(version: u64) -> Self {
+        Self {
+            version,
+            next_nibble: 0,
+            checked_leaf_count: 0,
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.next_nibble >= ConsistencyCheckTask::SUBTREE_COUNT
+    }
+}
+
+/// [`ConsistencyCheckTask`] progress stats.
+#[derive(Debug, Clone, Default)]
+pub struct ConsistencyCheckStats {
+    /// Number of tree versions fully checked by the task.
+    pub checked_version_count: u64,
+    /// Latest fully checked tree version, or `None` if no versions have been checked.
+    pub last_checked_version: Option<u64>,
+    /// Tree version currently being checked.
+    pub current_version: Option<u64>,
+    /// Number of root subtrees checked for [`Self::current_version`].
+    pub checked_subtree_count: u8,
+    /// Latest tree version with a detected inconsistency.
+    pub inconsistent_version: Option<u64>,
+    /// Latest detected inconsistency.
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug)]
+enum ConsistencyCheckOutcome {
+    /// A single root subtree was checked.
+    CheckedSubtree {
+        version: u64,
+        checked_subtree_count: u8,
+    },
+    /// The tree version has been fully checked.
+    CheckedVersion(u64),
+    /// An inconsistency was detected at the specified tree version.
+    Inconsistent(u64, ConsistencyError),
+    /// The checked tree version was pruned or truncated; the check was restarted with the latest version.
+    Restarted,
+    /// No new tree versions to check.
+    Idle,
+}
+
+/// Handle for a [`ConsistencyCheckTask`] allowing to abort its operation.
+///
+/// The task is aborted once the handle is dropped.
+#[must_use = "Paired `ConsistencyCheckTask` is aborted once handle is dropped"]
+#[derive(Debug)]
+pub struct ConsistencyCheckHandle {
+    stats: Arc<Mutex<ConsistencyCheckStats>>,
+    _aborted_sender: mpsc::Sender<()>,
+}
+
+impl ConsistencyCheckHandle {
+    /// Returns stats for the paired task.
+    #[allow(clippy::missing_panics_doc)] // mutex poisoning shouldn't happen
+    pub fn stats(&self) -> ConsistencyCheckStats {
+        self.stats.lock().expect("stats mutex poisoned").clone()
+    }
+}
+
+/// Task that periodically verifies consistency of the latest tree version on a live tree.
+///
+/// Verification recomputes hashes for all tree nodes and checks that leaf indices are unique and continuous,
+/// i.e., it's equivalent to [`MerkleTree::verify_consistency()`]. Unlike it, verification is split into steps,
+/// each checking a single subtree of the tree root, with an optional delay between steps to limit the load
+/// on the node. Progress is persisted after each step, so the check is resumed after the node restart.
+#[derive(Debug)]
+pub struct ConsistencyCheckTask {
+    tree: MerkleTree<RocksDBWrapper>,
+    subtree_delay: Duration,
+    poll_interval: Duration,
+    leaf_data: Option<(u64, LeafConsistencyData)>,
+    stats: Arc<Mutex<ConsistencyCheckStats>>,
+    aborted_receiver: mpsc::Receiver<()>,
+}
+
+impl ConsistencyCheckTask {
+    const SUBTREE_COUNT: u8 = 16;
+
+    /// Creates a new task.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the tree in `db` has an incompatible configuration.
+    pub fn new(db: RocksDBWrapper) -> anyhow::Result<(Self, ConsistencyCheckHandle)> {
+        let (aborted_sender, aborted_receiver) = mpsc::channel();
+        let stats = Arc::<Mutex<ConsistencyCheckStats>>::default();
+        let this = Self {
+            tree: MerkleTree::new(db)?,
+            subtree_delay: Duration::ZERO,
+            poll_interval: Duration::from_secs(60),
+            leaf_data: None,
+            stats: stats.clone(),
+            aborted_receiver,
+        };
+        let handle = ConsistencyCheckHandle {
+            stats,
+            _aborted_sender: aborted_sender,
+        };
+        Ok((this, handle))
+    }
+
+    /// Sets the delay between checking root subtrees. Can be used to limit I/O and CPU load.
+    pub fn set_subtree_delay(&mut self, delay: Duration) {
+        self.subtree_delay = delay;
+    }
+
+    /// Sets the poll interval for this task, i.e., the interval between checks of the latest tree version.
+    pub fn set_poll_interval(&mut self, poll_interval: Duration) {
+        self.poll_interval = poll_interval;
+    }
+
+    fn version_exists(&self, version: u64) -> anyhow::Result<bool> {
+        let manifest = self.tree.db.try_manifest()?;
+        if manifest.map_or(true, |manifest| version >= manifest.version_count) {
+            return Ok(false);
+        }
+        Ok(self.tree.db.try_root(version)?.is_some())
+    }
+
+    fn restart(&mut self) -> anyhow::Result<ConsistencyCheckOutcome> {
+        self.leaf_data = None;
+        let Some(latest_version) = self.tree.latest_version() else {
+            return Ok(ConsistencyCheckOutcome::Idle);
+        };
+        self.tree
+            .db
+            .set_consistency_check_data(&ConsistencyCheckData::new(latest_version))?;
+        Ok(ConsistencyCheckOutcome::Restarted)
+    }
+
+    fn step(&mut self) -> anyhow::Result<ConsistencyCheckOutcome> {
+        let check_data = self
+            .tree
+            .db
+            .consistency_check_data()
+            .context("failed getting consistency check data")?;
+        let mut check_data = match check_data {
+            Some(data) if !data.is_finished() => data,
+            _ => {
+                let Some(latest_version) = self.tree.latest_version() else {
+                    tracing::debug!("Tree is empty, nothing to check");
+                    return Ok(ConsistencyCheckOutcome::Idle);
+                };
+                if check_data.map_or(false, |data| data.version >= latest_version) {
+                    tracing::debug!(latest_version, "No new tree versions to check");
+                    return Ok(ConsistencyCheckOutcome::Idle);
+                }
+                ConsistencyCheckData::new(latest_version)
+            }
+        };
+        let version = check_data.version;
+
+        let root = if self.version_exists(version)? {
+            self.tree.db.try_root(version)?
+        } else {
+            None
+        };
+        let Some(root) = root else {
+            tracing::info!(
+                version,
+                "Checked tree version was pruned or truncated; restarting the check"
+            );
+            return self.restart();
+        };
+        let leaf_count = match root {
+            Root::Empty => 0,
+            Root::Filled { leaf_count, .. } => leaf_count.get(),
+        };
+
+        if self.leaf_data.as_ref().map(|(v, _)| *v) != Some(version) {
+            let leaf_data = LeafConsistencyData::resumed(leaf_count, check_data.checked_leaf_count);
+            self.leaf_data = Some((version, leaf_data));
+        }
+        let (_, leaf_data) = self.leaf_data.as_ref().unwrap();
+
+        let nibble = check_data.next_nibble;
+        tracing::debug!(version, nibble, "Checking tree subtree");
+        let mut result = self
+            .tree
+            .verify_subtree_consistency(version, nibble, Some(leaf_data));
+        check_data.next_nibble += 1;
+        check_data.checked_leaf_count = leaf_data.checked_leaf_count();
+        if result.is_ok() && check_data.is_finished() {
+            let (_, leaf_data) = self.leaf_data.take().unwrap();
+            result = leaf_data.validate_count();
+        }
+
+        let outcome = match result {
+            Ok(()) if check_data.is_finished() => ConsistencyCheckOutcome::CheckedVersion(version),
+            Ok(()) => ConsistencyCheckOutcome::CheckedSubtree {
+                version,
+                checked_subtree_count: check_data.next_nibble,
+            },
+            Err(err) => {
+                // Nodes could be removed by the pruner or truncation concurrently with the check.
+                if !self.version_exists(version)? {
+                    tracing::info!(
+                        version,
+                        "Checked tree version was pruned or truncated; restarting the check"
+                    );
+                    return self.restart();
+                }
+                // Do not recheck the version; the inconsistency is reported to the caller.
+                self.leaf_data = None;
+                check_data.next_nibble = Self::SUBTREE_COUNT;
+                ConsistencyCheckOutcome::Inconsistent(version, err)
+            }
+        };
+        self.tree
+            .db
+            .set_consistency_check_data(&check_data)
+            .context("failed updating consistency check data")?;
+        Ok(outcome)
+    }
+
+    fn wait_for_abort(&mut self, timeout: Duration) -> bool {
+        match self.aborted_receiver.recv_timeout(timeout) {
+            Ok(()) | Err(mpsc::RecvTimeoutError::Disconnected) => true,
+            Err(mpsc::RecvTimeoutError::Timeout) => false,
+        }
+    }
+
+    fn update_stats(&self, outcome: &ConsistencyCheckOutcome) {
+        let mut stats = self.stats.lock().expect("stats mutex poisoned");
+        match outcome {
+            ConsistencyCheckOutcome::CheckedSubtree {
+                version,
+                checked_subtree_count,
+            } => {
+                stats.current_version = Some(*version);
+                stats.checked_subtree_count = *checked_subtree_count;
+            }
+            ConsistencyCheckOutcome::CheckedVersion(version) => {
+                stats.checked_version_count += 1;
+                stats.last_checked_version = Some(*version);
+                stats.current_version = None;
+                stats.checked_subtree_count = 0;
+            }
+            ConsistencyCheckOutcome::Inconsistent(version, err) => {
+                stats.inconsistent_version = Some(*version);
+                stats.last_error = Some(err.to_string());
+                stats.current_version = None;
+                stats.checked_subtree_count = 0;
+            }
+            ConsistencyCheckOutcome::Restarted | ConsistencyCheckOutcome::Idle => {
+                stats.current_version = None;
+                stats.checked_subtree_count = 0;
+            }
+        }
+    }
+
+    /// Runs this task indefinitely.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB I/O errors. Detected tree inconsistencies are logged and reported via [`ConsistencyCheckHandle`],
+    /// but do not terminate the task.
+    pub fn run(mut self) -> anyhow::Result<()> {
+        let check_data = self
+            .tree
+            .db
+            .consistency_check_data()
+            .context("failed getting consistency check data")?;
+        tracing::info!(
+            subtree_delay = ?self.subtree_delay,
+            poll_interval = ?self.poll_interval,
+            ?check_data,
+            "Starting consistency check task"
+        );
+
+        let mut wait_interval = Duration::ZERO;
+        while !self.wait_for_abort(wait_interval) {
+            let outcome = self.step()?;
+            wait_interval = match &outcome {
+                ConsistencyCheckOutcome::CheckedSubtree { .. } => self.subtree_delay,
+                ConsistencyCheckOutcome::CheckedVersion(version) => {
+                    tracing::info!(version, "Tree version is consistent");
+                    self.poll_interval
+                }
+                ConsistencyCheckOutcome::Inconsistent(version, err) => {
+                    tracing::error!(version, %err, "Tree version is inconsistent");
+                    self.poll_interval
+                }
+                ConsistencyCheckOutcome::Restarted => Duration::ZERO,
+                ConsistencyCheckOutcome::Idle => self.poll_interval,
+            };
+            self.update_stats(&outcome);
+        }
+        tracing::info!("Stop signal received, consistency check is shut down");
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::thread;
 
+    use assert_matches::assert_matches;
+
     use super::*;
     use crate::{
-        utils::testonly::setup_tree_with_stale_keys, Key, MerkleTree, MerkleTreePruner, TreeEntry,
-        ValueHash,
+        utils::testonly::setup_tree_with_stale_keys, Key, MerkleTree, MerkleTreeColumnFamily,
+        MerkleTreePruner, TreeEntry, ValueHash,
     };
 
     #[test]
@@ -373,4 +688,151 @@ mod tests {
         let bogus_stale_keys = StaleKeysRepairTask::bogus_stale_keys(&db, 1);
         assert!(bogus_stale_keys.is_empty());
     }
+
+    fn setup_tree_for_consistency_check(db: &mut RocksDBWrapper) {
+        let mut tree = MerkleTree::new(db).unwrap();
+        for chunk in [0_u64..50, 50..100] {
+            let kvs: Vec<_> = chunk
+                .map(|i| {
+                    TreeEntry::new(
+                        Key::MAX / 100 * Key::from(i),
+                        i + 1,
+                        ValueHash::repeat_byte(1),
+                    )
+                })
+                .collect();
+            tree.extend(kvs).unwrap();
+        }
+    }
+
+    fn check_version(task: &mut ConsistencyCheckTask) -> ConsistencyCheckOutcome {
+        for _ in 0..ConsistencyCheckTask::SUBTREE_COUNT - 1 {
+            let outcome = task.step().unwrap();
+            assert_matches!(outcome, ConsistencyCheckOutcome::CheckedSubtree { .. });
+        }
+        task.step().unwrap()
+    }
+
+    #[test]
+    fn consistency_check_basics() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut db = RocksDBWrapper::new(temp_dir.path()).unwrap();
+        let (mut task, _handle) = ConsistencyCheckTask::new(db.clone()).unwrap();
+        assert_matches!(task.step().unwrap(), ConsistencyCheckOutcome::Idle);
+
+        setup_tree_for_consistency_check(&mut db);
+        let outcome = check_version(&mut task);
+        assert_matches!(outcome, ConsistencyCheckOutcome::CheckedVersion(1));
+        // The latest version is already checked.
+        assert_matches!(task.step().unwrap(), ConsistencyCheckOutcome::Idle);
+
+        let check_data = db.consistency_check_data().unwrap().unwrap();
+        assert_eq!(check_data.version, 1);
+        assert!(check_data.is_finished());
+        assert_eq!(check_data.checked_leaf_count, 100);
+    }
+
+    #[test]
+    fn consistency_check_is_resumed() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut db = RocksDBWrapper::new(temp_dir.path()).unwrap();
+        setup_tree_for_consistency_check(&mut db);
+
+        let (mut task, _handle) = ConsistencyCheckTask::new(db.clone()).unwrap();
+        for _ in 0..5 {
+            task.step().unwrap();
+        }
+        drop(task);
+        let check_data = db.consistency_check_data().unwrap().unwrap();
+        assert_eq!(check_data.version, 1);
+        assert_eq!(check_data.next_nibble, 5);
+        assert!(check_data.checked_leaf_count > 0);
+
+        let (mut task, _handle) = ConsistencyCheckTask::new(db.clone()).unwrap();
+        for _ in 5..ConsistencyCheckTask::SUBTREE_COUNT - 1 {
+            let outcome = task.step().unwrap();
+            assert_matches!(
+                outcome,
+                ConsistencyCheckOutcome::CheckedSubtree { version: 1, .. }
+            );
+        }
+        let outcome = task.step().unwrap();
+        assert_matches!(outcome, ConsistencyCheckOutcome::CheckedVersion(1));
+    }
+
+    #[test]
+    fn consistency_check_detects_missing_nodes() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut db = RocksDBWrapper::new(temp_dir.path()).unwrap();
+        setup_tree_for_consistency_check(&mut db);
+
+        // Remove a non-root node for the latest version.
+        let raw_db = db.clone().into_inner();
+        let (node_key, _) = raw_db
+            .prefix_iterator_cf(MerkleTreeColumnFamily::Tree, &1_u64.to_be_bytes())
+            .find(|(key, _)| key.len() > 9)
+            .expect("no non-root nodes");
+        let mut batch = raw_db.new_write_batch();
+        batch.delete_cf(MerkleTreeColumnFamily::Tree, &node_key);
+        raw_db.write(batch).unwrap();
+
+        let (mut task, handle) = ConsistencyCheckTask::new(db.clone()).unwrap();
+        let outcome = loop {
+            let outcome = task.step().unwrap();
+            task.update_stats(&outcome);
+            if !matches!(outcome, ConsistencyCheckOutcome::CheckedSubtree { .. }) {
+                break outcome;
+            }
+        };
+        assert_matches!(
+            outcome,
+            ConsistencyCheckOutcome::Inconsistent(1, ConsistencyError::MissingNode { .. })
+        );
+        let stats = handle.stats();
+        assert_eq!(stats.inconsistent_version, Some(1));
+        assert!(stats.last_error.is_some());
+
+        // The inconsistent version should not be rechecked.
+        assert_matches!(task.step().unwrap(), ConsistencyCheckOutcome::Idle);
+    }
+
+    #[test]
+    fn consistency_check_is_restarted_after_pruning() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut db = RocksDBWrapper::new(temp_dir.path()).unwrap();
+        setup_tree_for_consistency_check(&mut db);
+        db.set_consistency_check_data(&ConsistencyCheckData::new(0))
+            .unwrap();
+
+        let (mut pruner, _) = MerkleTreePruner::new(&mut db);
+        pruner.prune_up_to(1).unwrap().expect("tree was not pruned");
+
+        let (mut task, _handle) = ConsistencyCheckTask::new(db.clone()).unwrap();
+        assert_matches!(task.step().unwrap(), ConsistencyCheckOutcome::Restarted);
+        let check_data = db.consistency_check_data().unwrap().unwrap();
+        assert_eq!(check_data, ConsistencyCheckData::new(1));
+        let outcome = check_version(&mut task);
+        assert_matches!(outcome, ConsistencyCheckOutcome::CheckedVersion(1));
+    }
+
+    #[test]
+    fn full_consistency_check_task_workflow() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut db = RocksDBWrapper::new(temp_dir.path()).unwrap();
+        setup_tree_for_consistency_check(&mut db);
+
+        let (task, handle) = ConsistencyCheckTask::new(db.clone()).unwrap();
+        let task_thread = thread::spawn(|| task.run());
+        while handle.stats().last_checked_version.is_none() {
+            thread::sleep(Duration::from_millis(50));
+        }
+        let stats = handle.stats();
+        assert_eq!(stats.last_checked_version, Some(1));
+        assert_eq!(stats.checked_version_count, 1);
+        assert_eq!(stats.inconsistent_version, None);
+
+        assert!(!task_thread.is_finished());
+        drop(handle);
+        task_thread.join().unwrap().unwrap();
+    }
 }
@@ -1,5 +1,8 @@
 //! Storage-related logic.
 
+use rayon::prelude::*;
+
+use self::proofs::SUBTREE_COUNT;
 pub use self::{
     database::{Database, NodeKeys, Patched, PruneDatabase, PrunePatchSet},
     parallel::PersistenceThreadHandle,
@@ -370,6 +373,66 @@ impl<'a, DB: Database + ?Sized> Storage<'a, DB> {
         patch
     }
 
+    /// Same as [`Self::extend_during_random_recovery()`], but traverses the tree in parallel. Entries are split
+    /// by the first key nibble, and each group is inserted into a separate subtree with the root at level 4 (= 1 nibble),
+    /// similarly to [`Self::extend_with_proofs()`]. The root node is then assembled from the child refs
+    /// produced for each subtree.
+    pub fn extend_during_parallel_recovery(mut self, recovery_entries: Vec<TreeEntry>) -> PatchSet {
+        let load_nodes_latency = BLOCK_TIMINGS.load_nodes.start();
+        let sorted_keys = SortedKeys::new(recovery_entries.iter().map(|entry| entry.key));
+        let parent_nibbles = self.updater.load_ancestors(&sorted_keys, self.db);
+        let load_nodes_latency = load_nodes_latency.observe();
+        tracing::debug!("Load stage took {load_nodes_latency:?}");
+
+        let extend_patch_latency = BLOCK_TIMINGS.extend_patch.start();
+        self.leaf_count += recovery_entries.len() as u64;
+        let mut entry_parts = [(); SUBTREE_COUNT].map(|()| vec![]);
+        for (entry, parent_nibbles) in recovery_entries.into_iter().zip(parent_nibbles) {
+            let first_nibble = Nibbles::nibble(&entry.key, 0);
+            entry_parts[usize::from(first_nibble)].push((entry, parent_nibbles));
+        }
+
+        let mut root = self.updater.patch_set.ensure_internal_root_node();
+        let initial_metrics = self.updater.metrics;
+        let updater_parts = self.updater.split();
+        // `into_par_iter()` below uses `rayon` to parallelize tree traversal. Each part only changes nodes
+        // in its subtree and its own copy of the root node.
+        let updater_parts: Vec<_> = updater_parts
+            .into_par_iter()
+            .zip_eq(entry_parts)
+            .map(|(mut updater, entries)| {
+                for (entry, parent_nibbles) in entries {
+                    updater.insert(entry, &parent_nibbles);
+                }
+                updater
+            })
+            .collect();
+
+        for (subtree_idx, updater) in updater_parts.iter().enumerate() {
+            let nibble = u8::try_from(subtree_idx).unwrap();
+            if let Some(child_ref) = updater.patch_set.child_ref(&Nibbles::EMPTY, nibble) {
+                root.insert_child_ref(nibble, *child_ref);
+            }
+        }
+        self.updater = updater_parts
+            .into_iter()
+            .reduce(TreeUpdater::merge)
+            .unwrap();
+        // ^ `unwrap()` is safe: `updater_parts` is non-empty
+        self.updater.metrics += initial_metrics;
+        if root.child_count() == 0 {
+            // The tree is empty; see `TreeUpdater::finalize_logs()` for the reasoning.
+            self.updater.patch_set.take_root();
+        } else {
+            self.updater.set_root_node(root.into());
+        }
+        let extend_patch_latency = extend_patch_latency.observe();
+        tracing::debug!("Parallel tree traversal stage took {extend_patch_latency:?}");
+
+        let (_, patch) = self.finalize();
+        patch
+    }
+
     fn finalize(self) -> (ValueHash, PatchSet) {
         tracing::debug!(
             "Finished updating tree; total leaf count: {}, stats: {:?}",
@@ -207,6 +207,11 @@ pub(crate) struct WorkingPatchSet {
 }
 
 impl WorkingPatchSet {
+    /// Minimum number of changed nodes on a tree level for the level to be hashed in parallel.
+    /// Upper levels of the tree contain few nodes, so distributing their hashing among threads
+    /// costs more than it saves.
+    const MIN_NODES_FOR_PARALLEL_HASHING: usize = 32;
+
     pub fn new(root_version: u64, root: Root) -> Self {
         let changes_by_nibble_count = match root {
             Root::Filled { node, .. } => {
@@ -375,17 +380,26 @@ impl WorkingPatchSet {
             |nibble_count, level_changes| {
                 let started_at = Instant::now();
                 let tree_level = nibble_count * 4;
-                // `into_par_iter()` below uses `rayon` to parallelize hash computations.
-                let output = level_changes
-                    .into_par_iter()
-                    .map_init(
-                        || hasher.with_stats(&stats),
-                        |hasher, (nibbles, node)| {
-                            let nibbles = Nibbles::from_parts(nibbles, nibble_count);
-                            (nibbles, Some(node.inner.hash(hasher, tree_level)), node)
-                        },
-                    )
-                    .collect::<Vec<_>>();
+                let hash_node =
+                    |hasher: &mut HasherWithStats<'_>,
+                     (nibbles, node): (NibblesBytes, WorkingNode)| {
+                        let nibbles = Nibbles::from_parts(nibbles, nibble_count);
+                        (nibbles, Some(node.inner.hash(hasher, tree_level)), node)
+                    };
+                let output = if level_changes.len() < Self::MIN_NODES_FOR_PARALLEL_HASHING {
+                    let mut hasher = hasher.with_stats(&stats);
+                    level_changes
+                        .into_iter()
+                        .map(|entry| hash_node(&mut hasher, entry))
+                        .collect::<Vec<_>>()
+                } else {
+                    // `into_par_iter()` below uses `rayon` to parallelize hash computations. The output
+                    // doesn't depend on the number of threads since nodes on the same level are hashed independently.
+                    level_changes
+                        .into_par_iter()
+                        .map_init(|| hasher.with_stats(&stats), hash_node)
+                        .collect::<Vec<_>>()
+                };
                 stats.hashing_duration += started_at.elapsed();
                 output
             },
@@ -22,7 +22,7 @@ use zksync_storage::{
 use crate::{
     errors::{DeserializeError, ErrorContext},
     metrics::ApplyPatchStats,
-    repair::StaleKeysRepairData,
+    repair::{ConsistencyCheckData, StaleKeysRepairData},
     storage::{
         database::{PruneDatabase, PrunePatchSet},
         Database, NodeKeys, PatchSet,
@@ -116,6 +116,8 @@ impl RocksDBWrapper {
 
     const STALE_KEYS_REPAIR_KEY: &'static [u8] = &[0, 0];
 
+    const CONSISTENCY_CHECK_KEY: &'static [u8] = &[0, 0, 0];
+
     /// Creates a new wrapper, initializing RocksDB at the specified directory.
     ///
     /// # Errors
@@ -270,6 +272,33 @@ impl RocksDBWrapper {
         StaleKeysRepairData::deserialize(&raw_value).map(Some)
     }
 
+    pub(crate) fn set_consistency_check_data(
+        &self,
+        data: &ConsistencyCheckData,
+    ) -> anyhow::Result<()> {
+        let mut raw_value = vec![];
+        data.serialize(&mut raw_value);
+
+        let mut write_batch = self.db.new_write_batch();
+        write_batch.put_cf(
+            MerkleTreeColumnFamily::Tree,
+            Self::CONSISTENCY_CHECK_KEY,
+            &raw_value,
+        );
+        self.db
+            .write(write_batch)
+            .context("Failed writing a batch to RocksDB")
+    }
+
+    pub(crate) fn consistency_check_data(
+        &self,
+    ) -> Result<Option<ConsistencyCheckData>, DeserializeError> {
+        let Some(raw_value) = self.raw_node(Self::CONSISTENCY_CHECK_KEY) else {
+            return Ok(None);
+        };
+        ConsistencyCheckData::deserialize(&raw_value).map(Some)
+    }
+
     /// Returns the wrapped RocksDB instance.
     pub fn into_inner(self) -> RocksDB<MerkleTreeColumnFamily> {
         self.db
@@ -4,7 +4,7 @@ use std::{collections::HashMap, str};
 
 use crate::{
     errors::{DeserializeError, DeserializeErrorKind, ErrorContext},
-    repair::StaleKeysRepairData,
+    repair::{ConsistencyCheckData, StaleKeysRepairData},
     types::{
         ChildRef, InternalNode, Key, LeafNode, Manifest, Node, RawNode, Root, TreeTags, ValueHash,
         HASH_SIZE, KEY_SIZE,
@@ -368,6 +368,28 @@ impl StaleKeysRepairData {
     }
 }
 
+impl ConsistencyCheckData {
+    pub(super) fn deserialize(mut bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let version = leb128::read::unsigned(&mut bytes).map_err(DeserializeErrorKind::Leb128)?;
+        let (&next_nibble, mut bytes) = bytes
+            .split_first()
+            .ok_or(DeserializeErrorKind::UnexpectedEof)?;
+        let checked_leaf_count =
+            leb128::read::unsigned(&mut bytes).map_err(DeserializeErrorKind::Leb128)?;
+        Ok(Self {
+            version,
+            next_nibble,
+            checked_leaf_count,
+        })
+    }
+
+    pub(super) fn serialize(&self, buffer: &mut Vec<u8>) {
+        leb128::write::unsigned(buffer, self.version).unwrap();
+        buffer.push(self.next_nibble);
+        leb128::write::unsigned(buffer, self.checked_leaf_count).unwrap();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use zksync_types::H256;
@@ -116,6 +116,33 @@ pub struct TreeEntryWithProof {
     pub merkle_path: Vec<ValueHash>,
 }
 
+/// Proof of authenticity shared by multiple entries in a Merkle tree.
+///
+/// To verify a multiproof, entries are sorted by key and folded level by level starting from the leaf level.
+/// On each level, a node whose sibling isn't computed from the proven entries requests the sibling hash
+/// from the proof. The proof lists hashes of such siblings in the order they are requested; hashes of empty subtrees
+/// are skipped and are only marked in [`Self::sibling_flags`]. Thus, the proof size scales with the number
+/// of *non-empty* siblings, which for a batch of keys is significantly smaller than the total size
+/// of the individual Merkle paths.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TreeMultiProof {
+    /// For each requested sibling in the order of requests, specifies whether the sibling hash is included
+    /// into [`Self::hashes`] (`true`) or the sibling is an empty subtree (`false`).
+    pub sibling_flags: Vec<bool>,
+    /// Hashes of non-empty siblings in the order of requests.
+    pub hashes: Vec<ValueHash>,
+}
+
+/// Entries in a Merkle tree together with a shared proof of authenticity.
+#[derive(Debug, Clone)]
+pub struct TreeEntriesWithMultiProof {
+    /// Entries in a Merkle tree in the same order as requested. Missing keys correspond
+    /// to [empty](TreeEntry::is_empty()) entries.
+    pub entries: Vec<TreeEntry>,
+    /// Proof of authenticity for all `entries`.
+    pub proof: TreeMultiProof,
+}
+
 /// Output of inserting a block of entries into a Merkle tree.
 #[derive(Debug, PartialEq, Eq)]
 pub struct BlockOutput {
@@ -126,6 +126,29 @@ fn basic_workflow_multiblock() {
     assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(12));
 }
 
+#[test]
+fn hashing_is_deterministic_regardless_of_thread_count() {
+    let logs = gen_storage_logs();
+    let outputs = [1, 4].map(|thread_count| {
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let db = RocksDB::new(temp_dir.as_ref()).unwrap();
+        let mut tree = ZkSyncTree::new(db.into()).unwrap();
+        tree.use_dedicated_thread_pool(thread_count);
+        let metadata: Vec<_> = logs
+            .chunks(40)
+            .map(|batch| {
+                let metadata = tree.process_l1_batch(batch).unwrap();
+                (metadata.root_hash, metadata.witness.unwrap())
+            })
+            .collect();
+        tree.save().unwrap();
+        metadata
+    });
+
+    let [single_threaded, multi_threaded] = outputs;
+    assert_eq!(single_threaded, multi_threaded);
+}
+
 #[test]
 fn tree_with_single_leaf_works_correctly() {
     let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
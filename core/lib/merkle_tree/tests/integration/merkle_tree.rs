@@ -7,7 +7,7 @@ use test_casing::test_casing;
 use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
 use zksync_merkle_tree::{
     Database, HashTree, MerkleTree, PatchSet, Patched, PruneDatabase, TreeEntry, TreeInstruction,
-    TreeLogEntry, TreeRangeDigest,
+    TreeLogEntry, TreeMultiProof, TreeRangeDigest,
 };
 use zksync_types::{AccountTreeId, Address, StorageKey, H256, U256};
 
@@ -244,6 +244,43 @@ fn entry_proofs_are_computed_correctly_with_intermediate_commits(chunk_size: usi
     }
 }
 
+#[test_casing(3, [10, 17, 42])]
+fn multiproofs_are_computed_correctly_for_historical_versions(chunk_size: usize) {
+    let (kvs, _) = &*ENTRIES_AND_HASH;
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    let root_hashes: Vec<_> = kvs
+        .chunks(chunk_size)
+        .map(|chunk| tree.extend(chunk.to_vec()).unwrap().root_hash)
+        .collect();
+
+    let mut rng = StdRng::seed_from_u64(chunk_size as u64);
+    for (version, root_hash) in root_hashes.into_iter().enumerate() {
+        let mut keys: Vec<_> = kvs
+            .choose_multiple(&mut rng, 30)
+            .map(|entry| entry.key)
+            .collect();
+        // Add some missing keys.
+        keys.extend((0..5).map(|_| U256([rng.gen(), rng.gen(), rng.gen(), rng.gen()])));
+
+        let output = tree.entries_with_multiproof(version as u64, &keys).unwrap();
+        assert_eq!(output.entries.len(), keys.len());
+        output.verify(&Blake2Hasher, root_hash).unwrap();
+
+        let single_proofs = tree.entries_with_proofs(version as u64, &keys).unwrap();
+        let single_proofs_len: usize = single_proofs
+            .iter()
+            .map(|entry| entry.merkle_path.len())
+            .sum();
+        assert!(output.proof.hashes.len() < single_proofs_len);
+        for (entry, single_proof) in output.entries.iter().zip(single_proofs) {
+            assert_eq!(*entry, single_proof.base);
+        }
+
+        let proof = TreeMultiProof::from_bytes(&output.proof.to_bytes()).unwrap();
+        assert_eq!(proof, output.proof);
+    }
+}
+
 fn test_accumulated_commits<DB: Database>(db: DB, chunk_size: usize) -> DB {
     let (kvs, expected_hash) = &*ENTRIES_AND_HASH;
     let mut db = Patched::new(db);
@@ -13,10 +13,11 @@ use crate::common::{convert_to_writes, generate_key_value_pairs, TreeMap, ENTRIE
 enum RecoveryKind {
     Linear,
     Random,
+    Parallel,
 }
 
 impl RecoveryKind {
-    const ALL: [Self; 2] = [Self::Linear, Self::Random];
+    const ALL: [Self; 3] = [Self::Linear, Self::Random, Self::Parallel];
 }
 
 #[test]
@@ -55,6 +56,7 @@ fn test_recovery_in_chunks(mut db: impl PruneDatabase, kind: RecoveryKind, chunk
         match kind {
             RecoveryKind::Linear => recovery.extend_linear(chunk.to_vec()).unwrap(),
             RecoveryKind::Random => recovery.extend_random(chunk.to_vec()).unwrap(),
+            RecoveryKind::Parallel => recovery.extend_parallel(chunk.to_vec()).unwrap(),
         }
         if i % 3 == 1 {
             recovery = MerkleTreeRecovery::new(&mut db, recovered_version).unwrap();
@@ -136,6 +138,7 @@ where
         match kind {
             RecoveryKind::Linear => recovery.extend_linear(chunk.to_vec()).unwrap(),
             RecoveryKind::Random => recovery.extend_random(chunk.to_vec()).unwrap(),
+            RecoveryKind::Parallel => recovery.extend_parallel(chunk.to_vec()).unwrap(),
         }
         if i % 3 == 1 {
             // need this to ensure that the old persistence thread doesn't corrupt DB
@@ -152,7 +155,7 @@ where
     test_tree_after_recovery(&mut tree, recovered_version, *expected_hash);
 }
 
-#[test_casing(8, test_casing::Product((RecoveryKind::ALL, [6, 10, 17, 42])))]
+#[test_casing(12, test_casing::Product((RecoveryKind::ALL, [6, 10, 17, 42])))]
 fn recovery_in_chunks(kind: RecoveryKind, chunk_size: usize) {
     test_recovery_in_chunks(PatchSet::default(), kind, chunk_size);
 }
@@ -163,14 +166,14 @@ mod rocksdb {
 
     use super::*;
 
-    #[test_casing(8, test_casing::Product((RecoveryKind::ALL, [6, 10, 17, 42])))]
+    #[test_casing(12, test_casing::Product((RecoveryKind::ALL, [6, 10, 17, 42])))]
     fn recovery_in_chunks(kind: RecoveryKind, chunk_size: usize) {
         let temp_dir = TempDir::new().unwrap();
         let db = RocksDBWrapper::new(temp_dir.path()).unwrap();
         test_recovery_in_chunks(db, kind, chunk_size);
     }
 
-    #[test_casing(8, test_casing::Product((RecoveryKind::ALL, [6, 10, 17, 42])))]
+    #[test_casing(12, test_casing::Product((RecoveryKind::ALL, [6, 10, 17, 42])))]
     fn parallel_recovery_in_chunks(kind: RecoveryKind, chunk_size: usize) {
         let temp_dir = TempDir::new().unwrap();
         let db = RocksDBWrapper::new(temp_dir.path()).unwrap();
@@ -6,6 +6,10 @@
 //! - "Private glue", aka type conversions from current to the "past" and vice versa.
 //!
 //! The "private glue" lies in the `types` module.
+//!
+//! Most of the glued types have the same representation in all VM versions, so conversions between them are infallible.
+//! For types that can genuinely mismatch (e.g., a log query with an aux byte unknown to the target VM version),
+//! there are validating [`TryGlueFrom`] conversions returning [`GlueError`].
 
 pub(crate) mod history_mode;
 pub mod tracers;
@@ -45,3 +49,116 @@ impl<T> GlueFrom<T> for T {
         this
     }
 }
+
+/// Error returned by validating glue conversions.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum GlueError {
+    #[error("aux byte {aux_byte} is not supported by zk_evm {version}")]
+    UnsupportedAuxByte { aux_byte: u8, version: &'static str },
+    #[error("timestamp {timestamp} precedes the starting VM timestamp {starting_timestamp}")]
+    TimestampBeforeStart {
+        timestamp: u32,
+        starting_timestamp: u32,
+    },
+    #[error("fat pointer offset {offset} exceeds its length {length}")]
+    FatPointerOffsetOutOfBounds { offset: u32, length: u32 },
+    #[error("fat pointer [{start}, {start} + {length}) overflows the memory page")]
+    FatPointerOverflow { start: u32, length: u32 },
+}
+
+/// Validating version of [`GlueFrom`] for types that may be incompatible between VM versions.
+pub trait TryGlueFrom<T>: Sized {
+    fn try_glue_from(value: T) -> Result<Self, GlueError>;
+}
+
+/// See the description of [`TryGlueFrom`] trait above.
+pub trait TryGlueInto<T>: Sized {
+    fn try_glue_into(self) -> Result<T, GlueError>;
+}
+
+// Blanket `TryGlueInto` impl for any type that implements `TryGlueFrom`.
+impl<T, U> TryGlueInto<U> for T
+where
+    U: TryGlueFrom<T>,
+{
+    fn try_glue_into(self) -> Result<U, GlueError> {
+        U::try_glue_from(self)
+    }
+}
+
+/// Checks that a timestamp could be produced by a VM starting from `starting_timestamp`. Zero timestamps are allowed;
+/// they are used for formal queries not produced by VM execution.
+fn validate_timestamp(timestamp: u32, starting_timestamp: u32) -> Result<(), GlueError> {
+    if timestamp != 0 && timestamp < starting_timestamp {
+        return Err(GlueError::TimestampBeforeStart {
+            timestamp,
+            starting_timestamp,
+        });
+    }
+    Ok(())
+}
+
+fn validate_aux_byte(
+    aux_byte: u8,
+    supported_aux_bytes: &[u8],
+    version: &'static str,
+) -> Result<(), GlueError> {
+    if supported_aux_bytes.contains(&aux_byte) {
+        Ok(())
+    } else {
+        Err(GlueError::UnsupportedAuxByte { aux_byte, version })
+    }
+}
+
+/// Checks fat pointer bounds; the VM assumes that `offset <= length` and that the pointed slice doesn't overflow.
+fn validate_fat_pointer(offset: u32, start: u32, length: u32) -> Result<(), GlueError> {
+    if offset > length {
+        return Err(GlueError::FatPointerOffsetOutOfBounds { offset, length });
+    }
+    if start.checked_add(length).is_none() {
+        return Err(GlueError::FatPointerOverflow { start, length });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validating_timestamps() {
+        validate_timestamp(0, 1_024).unwrap();
+        validate_timestamp(1_024, 1_024).unwrap();
+        let err = validate_timestamp(10, 1_024).unwrap_err();
+        assert_eq!(
+            err,
+            GlueError::TimestampBeforeStart {
+                timestamp: 10,
+                starting_timestamp: 1_024
+            }
+        );
+    }
+
+    #[test]
+    fn validating_fat_pointers() {
+        validate_fat_pointer(0, 0, 0).unwrap();
+        validate_fat_pointer(32, 64, 32).unwrap();
+        let err = validate_fat_pointer(33, 64, 32).unwrap_err();
+        assert_eq!(
+            err,
+            GlueError::FatPointerOffsetOutOfBounds {
+                offset: 33,
+                length: 32
+            }
+        );
+        let err = validate_fat_pointer(0, u32::MAX, 1).unwrap_err();
+        assert_eq!(
+            err,
+            GlueError::FatPointerOverflow {
+                start: u32::MAX,
+                length: 1
+            }
+        );
+    }
+}
@@ -64,6 +64,7 @@ impl GlueFrom<crate::vm_m5::vm_instance::VmBlockResult> for crate::interface::Fi
             final_bootloader_memory: None,
             pubdata_input: None,
             state_diffs: None,
+            precompile_inputs: None,
         }
     }
 }
@@ -115,6 +116,7 @@ impl GlueFrom<crate::vm_m6::vm_instance::VmBlockResult> for crate::interface::Fi
             final_bootloader_memory: None,
             pubdata_input: None,
             state_diffs: None,
+            precompile_inputs: None,
         }
     }
 }
@@ -172,6 +174,7 @@ impl GlueFrom<crate::vm_1_3_2::vm_instance::VmBlockResult> for crate::interface:
             final_bootloader_memory: None,
             pubdata_input: None,
             state_diffs: None,
+            precompile_inputs: None,
         }
     }
 }
@@ -1,13 +1,22 @@
 use zk_evm_1_4_1::{
     aux_structures::{LogQuery as LogQuery_1_4_1, Timestamp as Timestamp_1_4_1},
-    zkevm_opcode_defs::FarCallOpcode as FarCallOpcode_1_4_1,
+    zkevm_opcode_defs::{
+        system_params::{
+            EVENT_AUX_BYTE, L1_MESSAGE_AUX_BYTE, PRECOMPILE_AUX_BYTE, STORAGE_AUX_BYTE,
+        },
+        FarCallOpcode as FarCallOpcode_1_4_1, FatPointer as FatPointer_1_4_1, STARTING_TIMESTAMP,
+    },
 };
 use zksync_types::{
     u256_to_h256,
     zk_evm_types::{FarCallOpcode, LogQuery, Timestamp},
+    U256,
 };
 
-use crate::glue::{GlueFrom, GlueInto};
+use crate::glue::{
+    validate_aux_byte, validate_fat_pointer, validate_timestamp, GlueError, GlueFrom, GlueInto,
+    TryGlueFrom,
+};
 
 impl GlueFrom<FarCallOpcode_1_4_1> for FarCallOpcode {
     fn glue_from(value: FarCallOpcode_1_4_1) -> Self {
@@ -81,3 +90,34 @@ impl GlueFrom<zk_evm_1_4_1::reference_impls::event_sink::EventMessage>
         }
     }
 }
+
+/// Aux bytes of log queries supported by `zk_evm@1.4.1` (transient storage was introduced in `zk_evm@1.5.0`).
+const SUPPORTED_AUX_BYTES: [u8; 4] = [
+    STORAGE_AUX_BYTE,
+    EVENT_AUX_BYTE,
+    L1_MESSAGE_AUX_BYTE,
+    PRECOMPILE_AUX_BYTE,
+];
+
+impl TryGlueFrom<Timestamp> for Timestamp_1_4_1 {
+    fn try_glue_from(value: Timestamp) -> Result<Self, GlueError> {
+        validate_timestamp(value.0, STARTING_TIMESTAMP)?;
+        Ok(value.glue_into())
+    }
+}
+
+impl TryGlueFrom<LogQuery> for LogQuery_1_4_1 {
+    fn try_glue_from(value: LogQuery) -> Result<Self, GlueError> {
+        validate_aux_byte(value.aux_byte, &SUPPORTED_AUX_BYTES, "1.4.1")?;
+        validate_timestamp(value.timestamp.0, STARTING_TIMESTAMP)?;
+        Ok(value.glue_into())
+    }
+}
+
+impl TryGlueFrom<U256> for FatPointer_1_4_1 {
+    fn try_glue_from(value: U256) -> Result<Self, GlueError> {
+        let pointer = Self::from_u256(value);
+        validate_fat_pointer(pointer.offset, pointer.start, pointer.length)?;
+        Ok(pointer)
+    }
+}
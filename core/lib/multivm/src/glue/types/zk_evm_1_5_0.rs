@@ -1,6 +1,16 @@
-use zksync_types::u256_to_h256;
+use zk_evm_1_5_0::zkevm_opcode_defs::{
+    system_params::{
+        EVENT_AUX_BYTE, L1_MESSAGE_AUX_BYTE, PRECOMPILE_AUX_BYTE, STORAGE_AUX_BYTE,
+        TRANSIENT_STORAGE_AUX_BYTE,
+    },
+    STARTING_TIMESTAMP,
+};
+use zksync_types::{u256_to_h256, U256};
 
-use crate::glue::{GlueFrom, GlueInto};
+use crate::glue::{
+    validate_aux_byte, validate_fat_pointer, validate_timestamp, GlueError, GlueFrom, GlueInto,
+    TryGlueFrom,
+};
 
 impl GlueFrom<zk_evm_1_5_0::aux_structures::Timestamp> for zksync_types::zk_evm_types::Timestamp {
     fn glue_from(timestamp: zk_evm_1_5_0::aux_structures::Timestamp) -> Self {
@@ -88,3 +98,37 @@ impl GlueFrom<zk_evm_1_5_0::reference_impls::event_sink::EventMessage>
         }
     }
 }
+
+/// Aux bytes of log queries supported by `zk_evm@1.5.0` (including transient storage).
+const SUPPORTED_AUX_BYTES: [u8; 5] = [
+    STORAGE_AUX_BYTE,
+    EVENT_AUX_BYTE,
+    L1_MESSAGE_AUX_BYTE,
+    PRECOMPILE_AUX_BYTE,
+    TRANSIENT_STORAGE_AUX_BYTE,
+];
+
+impl TryGlueFrom<zksync_types::zk_evm_types::Timestamp>
+    for zk_evm_1_5_0::aux_structures::Timestamp
+{
+    fn try_glue_from(value: zksync_types::zk_evm_types::Timestamp) -> Result<Self, GlueError> {
+        validate_timestamp(value.0, STARTING_TIMESTAMP)?;
+        Ok(value.glue_into())
+    }
+}
+
+impl TryGlueFrom<zksync_types::zk_evm_types::LogQuery> for zk_evm_1_5_0::aux_structures::LogQuery {
+    fn try_glue_from(value: zksync_types::zk_evm_types::LogQuery) -> Result<Self, GlueError> {
+        validate_aux_byte(value.aux_byte, &SUPPORTED_AUX_BYTES, "1.5.0")?;
+        validate_timestamp(value.timestamp.0, STARTING_TIMESTAMP)?;
+        Ok(value.glue_into())
+    }
+}
+
+impl TryGlueFrom<U256> for zk_evm_1_5_0::zkevm_opcode_defs::FatPointer {
+    fn try_glue_from(value: U256) -> Result<Self, GlueError> {
+        let pointer = Self::from_u256(value);
+        validate_fat_pointer(pointer.offset, pointer.start, pointer.length)?;
+        Ok(pointer)
+    }
+}
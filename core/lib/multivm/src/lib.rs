@@ -11,6 +11,7 @@ pub use crate::{
     glue::{
         history_mode::HistoryMode,
         tracers::{IntoOldVmTracer, MultiVmTracer, MultiVmTracerPointer},
+        GlueError,
     },
     versions::{
         vm_1_3_2, vm_1_4_1, vm_1_4_2, vm_boojum_integration, vm_fast, vm_latest, vm_m5, vm_m6,
@@ -34,7 +34,7 @@ impl PubdataBuilder for FullPubdataBuilder {
     ) -> Vec<u8> {
         if protocol_version.is_pre_gateway() {
             let mut operator_input = vec![];
-            extend_from_pubdata_input(&mut operator_input, input);
+            extend_from_pubdata_input(&mut operator_input, input, protocol_version);
 
             // Extend with uncompressed state diffs.
             operator_input.extend((input.state_diffs.len() as u32).to_be_bytes());
@@ -45,7 +45,7 @@ impl PubdataBuilder for FullPubdataBuilder {
             operator_input
         } else {
             let mut pubdata = vec![];
-            extend_from_pubdata_input(&mut pubdata, input);
+            extend_from_pubdata_input(&mut pubdata, input, protocol_version);
 
             // Extend with uncompressed state diffs.
             pubdata.extend((input.state_diffs.len() as u32).to_be_bytes());
@@ -86,10 +86,10 @@ impl PubdataBuilder for FullPubdataBuilder {
     fn settlement_layer_pubdata(
         &self,
         input: &PubdataInput,
-        _protocol_version: ProtocolVersionId,
+        protocol_version: ProtocolVersionId,
     ) -> Vec<u8> {
         let mut pubdata = vec![];
-        extend_from_pubdata_input(&mut pubdata, input);
+        extend_from_pubdata_input(&mut pubdata, input, protocol_version);
 
         pubdata
     }
@@ -1,5 +1,7 @@
 use zksync_mini_merkle_tree::MiniMerkleTree;
-use zksync_types::{bytecode::BytecodeHash, web3::keccak256, writes::compress_state_diffs};
+use zksync_types::{
+    bytecode::BytecodeHash, web3::keccak256, writes::StateDiffCompressionVersion, ProtocolVersionId,
+};
 
 use crate::interface::pubdata::{L1MessengerL2ToL1Log, PubdataInput};
 
@@ -69,7 +71,11 @@ pub(crate) fn encode_user_logs(user_logs: &[L1MessengerL2ToL1Log]) -> Vec<u8> {
     result
 }
 
-pub(crate) fn extend_from_pubdata_input(buffer: &mut Vec<u8>, pubdata_input: &PubdataInput) {
+pub(crate) fn extend_from_pubdata_input(
+    buffer: &mut Vec<u8>,
+    pubdata_input: &PubdataInput,
+    protocol_version: ProtocolVersionId,
+) {
     let PubdataInput {
         user_logs,
         l2_to_l1_messages,
@@ -96,6 +102,7 @@ pub(crate) fn extend_from_pubdata_input(buffer: &mut Vec<u8>, pubdata_input: &Pu
     }
     // Encoding state diffs
     // Format: `[size of compressed state diffs u32 || compressed state diffs || (# state diffs: intial + repeated) as u32 || sorted state diffs by <index, address, key>]`
-    let state_diffs_compressed = compress_state_diffs(state_diffs.clone());
+    let compression_version = StateDiffCompressionVersion::for_protocol_version(protocol_version);
+    let state_diffs_compressed = compression_version.compress(state_diffs.clone());
     buffer.extend(state_diffs_compressed);
 }
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use once_cell::sync::OnceCell;
+
+use crate::{
+    glue::tracers::IntoOldVmTracer,
+    interface::{BootloaderMemoryDump, BootloaderMemoryDumpConfig, BootloaderMemoryDumpPoint},
+    vm_latest::MultiVmSubversion,
+};
+
+pub mod vm_1_4_1;
+pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Tracer dumping selected bootloader memory regions (transaction descriptions, operator refunds,
+/// pubdata pointers etc.) at configurable execution points. Allows debugging bootloader-level failures
+/// without adding debug logs to the bootloader and rebuilding system contracts.
+///
+/// Dumps are placed into the provided cell once VM execution stops. Dumps are only collected by the latest VM version;
+/// for older VM versions, the tracer is a no-op.
+#[derive(Debug, Clone)]
+pub struct BootloaderMemoryTracer {
+    config: BootloaderMemoryDumpConfig,
+    /// Execution point observed on the previous VM cycle; the dump will be taken at the end of the current cycle.
+    pending_point: Option<BootloaderMemoryDumpPoint>,
+    subversion: Option<MultiVmSubversion>,
+    dumps: Vec<BootloaderMemoryDump>,
+    result: Arc<OnceCell<Vec<BootloaderMemoryDump>>>,
+}
+
+impl BootloaderMemoryTracer {
+    pub fn new(
+        config: BootloaderMemoryDumpConfig,
+        result: Arc<OnceCell<Vec<BootloaderMemoryDump>>>,
+    ) -> Self {
+        Self {
+            config,
+            pending_point: None,
+            subversion: None,
+            dumps: vec![],
+            result,
+        }
+    }
+
+    fn should_dump_at(&self, point: BootloaderMemoryDumpPoint) -> bool {
+        !self.config.regions.is_empty() && self.config.points.contains(&point)
+    }
+}
+
+impl IntoOldVmTracer for BootloaderMemoryTracer {}
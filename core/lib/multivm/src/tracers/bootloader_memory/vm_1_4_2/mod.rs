@@ -0,0 +1,10 @@
+use crate::{
+    interface::storage::WriteStorage,
+    tracers::{dynamic::vm_1_4_1::DynTracer, BootloaderMemoryTracer},
+    vm_1_4_2::{HistoryMode, SimpleMemory, VmTracer},
+};
+
+// Bootloader memory dumps are not supported for this VM version.
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for BootloaderMemoryTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for BootloaderMemoryTracer {}
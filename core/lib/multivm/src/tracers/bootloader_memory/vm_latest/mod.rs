@@ -0,0 +1,140 @@
+use std::ops;
+
+use zk_evm_1_5_0::tracing::{BeforeExecutionData, VmLocalStateData};
+
+use crate::{
+    interface::{
+        storage::{StoragePtr, WriteStorage},
+        tracer::{TracerExecutionStatus, VmExecutionStopReason},
+        BootloaderMemoryDump, BootloaderMemoryDumpPoint, BootloaderMemoryRegion,
+        BootloaderMemoryRegionDump,
+    },
+    tracers::{dynamic::vm_1_5_0::DynTracer, BootloaderMemoryTracer},
+    vm_latest::{
+        constants::{
+            get_bootloader_tx_description_offset,
+            get_operator_provided_l1_messenger_pubdata_offset, get_operator_refunds_offset,
+            BOOTLOADER_HEAP_PAGE, BOOTLOADER_TX_DESCRIPTION_SIZE,
+        },
+        BootloaderState, HistoryMode, MultiVmSubversion, SimpleMemory, VmHook, VmTracer,
+        ZkSyncVmState,
+    },
+};
+
+/// Number of words holding pointers to the operator-provided L1 messenger pubdata
+/// (the call selector, calldata offset and pubdata length).
+const PUBDATA_POINTER_SLOTS: usize = 3;
+
+fn region_slots(
+    region: BootloaderMemoryRegion,
+    subversion: MultiVmSubversion,
+    tx_count: usize,
+) -> ops::Range<usize> {
+    let (start, len) = match region {
+        BootloaderMemoryRegion::TxDescriptions => (
+            get_bootloader_tx_description_offset(subversion),
+            BOOTLOADER_TX_DESCRIPTION_SIZE * tx_count,
+        ),
+        BootloaderMemoryRegion::OperatorRefunds => {
+            (get_operator_refunds_offset(subversion), tx_count)
+        }
+        BootloaderMemoryRegion::PubdataPointers => (
+            get_operator_provided_l1_messenger_pubdata_offset(subversion),
+            PUBDATA_POINTER_SLOTS,
+        ),
+    };
+    start..start + len
+}
+
+impl BootloaderMemoryTracer {
+    fn take_dump<S: WriteStorage, H: HistoryMode>(
+        &mut self,
+        point: BootloaderMemoryDumpPoint,
+        state: &ZkSyncVmState<S, H>,
+        bootloader_state: &BootloaderState,
+    ) {
+        let subversion = bootloader_state.get_vm_subversion();
+        let tx_count = bootloader_state.free_tx_index();
+        let regions = self
+            .config
+            .regions
+            .iter()
+            .map(|&region| {
+                let slots = region_slots(region, subversion, tx_count);
+                BootloaderMemoryRegionDump {
+                    region,
+                    start_slot: slots.start,
+                    words: state.memory.dump_page_content_as_u256_words(
+                        BOOTLOADER_HEAP_PAGE,
+                        slots.start as u32..slots.end as u32,
+                    ),
+                }
+            })
+            .collect();
+
+        self.dumps.push(BootloaderMemoryDump {
+            point,
+            tx_index: bootloader_state.try_current_tx(),
+            timestamp: state.local_state.timestamp,
+            regions,
+        });
+    }
+}
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for BootloaderMemoryTracer {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: BeforeExecutionData,
+        _memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        // The hook position depends on the VM subversion, which is only known after the first VM cycle.
+        // This is fine since the first executed instruction is never a hook.
+        let Some(subversion) = self.subversion else {
+            return;
+        };
+        let hook = VmHook::from_opcode_memory(&state, &data, subversion);
+        let point = match hook {
+            Some(VmHook::AccountValidationEntered) => BootloaderMemoryDumpPoint::ValidationStarted,
+            Some(VmHook::AskOperatorForRefund) => BootloaderMemoryDumpPoint::RefundRequested,
+            Some(VmHook::TxHasEnded) => BootloaderMemoryDumpPoint::TxEnded,
+            Some(VmHook::PubdataRequested) => BootloaderMemoryDumpPoint::PubdataRequested,
+            _ => return,
+        };
+        if self.should_dump_at(point) {
+            self.pending_point = Some(point);
+        }
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for BootloaderMemoryTracer {
+    fn finish_cycle(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        bootloader_state: &mut BootloaderState,
+    ) -> TracerExecutionStatus {
+        self.subversion = Some(bootloader_state.get_vm_subversion());
+        if let Some(point) = self.pending_point.take() {
+            self.take_dump(point, state, bootloader_state);
+        }
+        TracerExecutionStatus::Continue
+    }
+
+    fn after_vm_execution(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        if self.should_dump_at(BootloaderMemoryDumpPoint::ExecutionEnded) {
+            self.take_dump(
+                BootloaderMemoryDumpPoint::ExecutionEnded,
+                state,
+                bootloader_state,
+            );
+        }
+        let dumps = std::mem::take(&mut self.dumps);
+        self.result.set(dumps).ok();
+    }
+}
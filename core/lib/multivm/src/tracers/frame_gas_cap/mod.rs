@@ -0,0 +1,133 @@
+use zksync_types::Address;
+
+use crate::glue::tracers::IntoOldVmTracer;
+
+pub mod vm_1_4_1;
+pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Tracer limiting the amount of gas a contract can forward to a single far call frame, and halting the transaction
+/// if the limit is violated. Useful as an anti-griefing measure.
+///
+/// Two independent rules are supported:
+///
+/// - **Forwarding divisor** (the EIP-150 "all but 1/N" rule): a contract can forward at most
+///   `available - available / divisor` gas to a call, where `available` is the gas the contract had before the call.
+///   Note that EraVM already enforces this rule with the divisor 64, so only smaller divisors have an effect.
+/// - **Absolute cap** on the gas forwarded to a single call frame.
+///
+/// The rules only apply to calls made by user contracts; calls made by the bootloader, system contracts,
+/// and by accounts / paymasters invoked by the bootloader are never limited.
+///
+/// Only the latest VM version enforces the limit; for older VM versions, the tracer is a no-op.
+#[derive(Debug, Clone, Default)]
+pub struct FrameGasCap {
+    forwarding_divisor: Option<u32>,
+    max_frame_gas: Option<u32>,
+    violation: Option<String>,
+}
+
+impl FrameGasCap {
+    /// Creates a tracer with the specified forwarding divisor and absolute cap. If both are `None`, the tracer is a no-op.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `forwarding_divisor` is 0.
+    pub fn new(forwarding_divisor: Option<u32>, max_frame_gas: Option<u32>) -> Self {
+        assert_ne!(
+            forwarding_divisor,
+            Some(0),
+            "forwarding divisor must be positive"
+        );
+        Self {
+            forwarding_divisor,
+            max_frame_gas,
+            violation: None,
+        }
+    }
+
+    /// Checks whether this tracer limits forwarded gas at all.
+    pub fn is_enabled(&self) -> bool {
+        self.forwarding_divisor.is_some() || self.max_frame_gas.is_some()
+    }
+
+    /// Returns the maximum amount of gas that can be forwarded to a call frame given `available` gas in the caller.
+    pub fn max_forwarded_gas(&self, available: u32) -> u32 {
+        let by_divisor = self
+            .forwarding_divisor
+            .map_or(available, |divisor| available - available / divisor);
+        self.max_frame_gas
+            .map_or(by_divisor, |max_gas| by_divisor.min(max_gas))
+    }
+
+    /// Records a far call forwarding `forwarded` gas out of `available` gas in the caller.
+    fn check_far_call(&mut self, caller: Address, forwarded: u32, available: u32) {
+        if self.violation.is_some() {
+            return;
+        }
+        let max_forwarded = self.max_forwarded_gas(available);
+        if forwarded > max_forwarded {
+            self.violation = Some(format!(
+                "Call frame gas cap exceeded: {caller:?} forwarded {forwarded} gas out of {available} available, \
+                 while at most {max_forwarded} is allowed"
+            ));
+        }
+    }
+}
+
+impl IntoOldVmTracer for FrameGasCap {}
+
+/// Checks whether the address is in the kernel space (i.e., belongs to the bootloader or a system contract).
+fn is_kernel_space(address: Address) -> bool {
+    address.as_bytes()[..18].iter().all(|&byte| byte == 0)
+}
+
+/// Checks whether a far call from the specified frame is subject to the gas cap. `caller` is the address
+/// of the frame making the call, and `caller_sender` is `msg.sender` of this frame.
+fn is_capped_call(caller: Address, caller_sender: Address) -> bool {
+    // Accounts and paymasters are called by the bootloader; they must be able to forward all their gas.
+    !is_kernel_space(caller) && !is_kernel_space(caller_sender)
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_system_constants::{BOOTLOADER_ADDRESS, CONTRACT_DEPLOYER_ADDRESS};
+
+    use super::*;
+
+    #[test]
+    fn computing_max_forwarded_gas() {
+        let cap = FrameGasCap::default();
+        assert!(!cap.is_enabled());
+        assert_eq!(cap.max_forwarded_gas(1_000), 1_000);
+
+        let cap = FrameGasCap::new(Some(64), None);
+        assert_eq!(cap.max_forwarded_gas(6_400), 6_300);
+        let cap = FrameGasCap::new(Some(2), Some(1_000));
+        assert!(cap.is_enabled());
+        assert_eq!(cap.max_forwarded_gas(1_500), 750);
+        assert_eq!(cap.max_forwarded_gas(10_000), 1_000);
+    }
+
+    #[test]
+    fn recording_violations() {
+        let caller = Address::repeat_byte(1);
+        let mut cap = FrameGasCap::new(None, Some(1_000));
+        cap.check_far_call(caller, 1_000, 5_000);
+        assert!(cap.violation.is_none());
+        cap.check_far_call(caller, 1_001, 5_000);
+        let violation = cap.violation.clone().unwrap();
+        assert!(violation.contains("forwarded 1001 gas"), "{violation}");
+    }
+
+    #[test]
+    fn determining_capped_calls() {
+        let user = Address::repeat_byte(1);
+        assert!(is_capped_call(user, Address::repeat_byte(2)));
+        assert!(!is_capped_call(user, BOOTLOADER_ADDRESS));
+        assert!(!is_capped_call(CONTRACT_DEPLOYER_ADDRESS, user));
+    }
+}
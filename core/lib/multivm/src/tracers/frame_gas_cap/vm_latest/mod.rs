@@ -0,0 +1,56 @@
+use zk_evm_1_5_0::{
+    tracing::{AfterExecutionData, VmLocalStateData},
+    zkevm_opcode_defs::Opcode,
+};
+
+use super::is_capped_call;
+use crate::{
+    interface::{
+        storage::{StoragePtr, WriteStorage},
+        tracer::{TracerExecutionStatus, TracerExecutionStopReason},
+        Halt,
+    },
+    tracers::{dynamic::vm_1_5_0::DynTracer, FrameGasCap},
+    vm_latest::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
+};
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for FrameGasCap {
+    fn after_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: AfterExecutionData,
+        _memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        if !matches!(data.opcode.variant.opcode, Opcode::FarCall(_)) {
+            return;
+        }
+
+        // After the far call, the current frame is the callee frame, and the caller frame is the last one in the stack.
+        let callstack = &state.vm_local_state.callstack;
+        let Some(caller) = callstack.inner.last() else {
+            return;
+        };
+        if !is_capped_call(caller.this_address, caller.msg_sender) {
+            return;
+        }
+        let forwarded = callstack.current.ergs_remaining;
+        let available = caller.ergs_remaining.saturating_add(forwarded);
+        self.check_far_call(caller.this_address, forwarded, available);
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for FrameGasCap {
+    fn finish_cycle(
+        &mut self,
+        _state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &mut BootloaderState,
+    ) -> TracerExecutionStatus {
+        if let Some(violation) = &self.violation {
+            return TracerExecutionStatus::Stop(TracerExecutionStopReason::Abort(
+                Halt::TracerCustom(violation.clone()),
+            ));
+        }
+        TracerExecutionStatus::Continue
+    }
+}
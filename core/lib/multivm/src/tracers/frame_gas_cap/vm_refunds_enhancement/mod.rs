@@ -0,0 +1,10 @@
+use crate::{
+    interface::storage::WriteStorage,
+    tracers::{dynamic::vm_1_3_3::DynTracer, FrameGasCap},
+    vm_refunds_enhancement::{HistoryMode, SimpleMemory, VmTracer},
+};
+
+// Frame gas cap is not enforced for this VM version.
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for FrameGasCap {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for FrameGasCap {}
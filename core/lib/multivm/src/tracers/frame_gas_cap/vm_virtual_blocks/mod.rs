@@ -0,0 +1,16 @@
+use crate::{
+    interface::storage::WriteStorage,
+    tracers::{dynamic::vm_1_3_3::DynTracer, FrameGasCap},
+    vm_virtual_blocks::{
+        ExecutionEndTracer, ExecutionProcessing, HistoryMode, SimpleMemory, VmTracer,
+    },
+};
+
+// Frame gas cap is not enforced for this VM version.
+impl<H: HistoryMode> ExecutionEndTracer<H> for FrameGasCap {}
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for FrameGasCap {}
+
+impl<S: WriteStorage, H: HistoryMode> ExecutionProcessing<S, H> for FrameGasCap {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for FrameGasCap {}
@@ -1,15 +1,21 @@
 pub use self::{
+    bootloader_memory::BootloaderMemoryTracer,
     call_tracer::CallTracer,
+    frame_gas_cap::FrameGasCap,
     multivm_dispatcher::TracerDispatcher,
     prestate_tracer::PrestateTracer,
+    resource_usage::{LimitedResource, ResourceLimits, ResourceUsage, ResourceUsageTracer},
     storage_invocation::StorageInvocations,
     validator::{ValidationTracer, TIMESTAMP_ASSERTER_FUNCTION_SELECTOR},
 };
 
+mod bootloader_memory;
 mod call_tracer;
 pub mod dynamic;
+mod frame_gas_cap;
 mod multivm_dispatcher;
 pub mod old;
 mod prestate_tracer;
+mod resource_usage;
 mod storage_invocation;
 mod validator;
@@ -0,0 +1,169 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::OnceCell;
+
+use crate::glue::tracers::IntoOldVmTracer;
+
+pub mod vm_1_4_1;
+pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Limits on resources consumed by a single VM execution enforced by [`ResourceUsageTracer`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Maximum estimated memory consumed by VM oracles, in bytes.
+    pub max_memory: Option<usize>,
+    /// Maximum CPU time spent on the execution. Since the VM is executed on a dedicated thread, CPU time
+    /// is approximated by the wall-clock execution time.
+    pub max_cpu_time: Option<Duration>,
+}
+
+/// Resource that can be limited by [`ResourceLimits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LimitedResource {
+    Memory,
+    CpuTime,
+}
+
+/// Resources consumed by a VM execution as measured by [`ResourceUsageTracer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceUsage {
+    /// Peak estimated memory consumed by VM oracles, in bytes. The estimate is based on the sizes of oracles' state
+    /// and history, and is sampled every [`ResourceUsageTracer::CHECK_INTERVAL`] cycles.
+    pub peak_memory: usize,
+    /// Approximate CPU time spent on the execution.
+    pub cpu_time: Duration,
+    /// Limit that has caused the execution to halt, if any.
+    pub exceeded_limit: Option<LimitedResource>,
+}
+
+/// Tracer accounting memory and CPU time consumed by a VM execution, and halting the execution if [`ResourceLimits`]
+/// are exceeded. Useful as an anti-DoS measure for executions requested via the API (e.g., call tracing),
+/// since a single pathological transaction can consume gigabytes of memory.
+///
+/// Only the latest VM version is accounted; for older VM versions, the tracer is a no-op.
+#[derive(Debug, Clone)]
+pub struct ResourceUsageTracer {
+    limits: ResourceLimits,
+    started_at: Option<Instant>,
+    cycles: u64,
+    peak_memory: usize,
+    violation: Option<(LimitedResource, String)>,
+    result: Arc<OnceCell<ResourceUsage>>,
+}
+
+impl ResourceUsageTracer {
+    /// Interval (in VM cycles) between resource usage checks. Estimating memory usage is relatively expensive,
+    /// so it's not performed on each cycle.
+    pub const CHECK_INTERVAL: u64 = 4_096;
+
+    /// Creates a tracer with the specified limits. Resource usage will be stored in `result` after the execution.
+    pub fn new(limits: ResourceLimits, result: Arc<OnceCell<ResourceUsage>>) -> Self {
+        Self {
+            limits,
+            started_at: None,
+            cycles: 0,
+            peak_memory: 0,
+            violation: None,
+            result,
+        }
+    }
+
+    /// Returns the CPU time elapsed since the start of the execution.
+    fn cpu_time(&self) -> Duration {
+        self.started_at.map_or(Duration::ZERO, |ts| ts.elapsed())
+    }
+
+    /// Records the current memory usage and checks the limits. Only the first violated limit is recorded.
+    fn check(&mut self, memory: usize) {
+        self.peak_memory = self.peak_memory.max(memory);
+        if self.violation.is_some() {
+            return;
+        }
+
+        if let Some(max_memory) = self.limits.max_memory {
+            if self.peak_memory > max_memory {
+                let message = format!(
+                    "VM memory limit exceeded: estimated usage is {} bytes, while at most {max_memory} bytes is allowed",
+                    self.peak_memory
+                );
+                self.violation = Some((LimitedResource::Memory, message));
+                return;
+            }
+        }
+        if let Some(max_cpu_time) = self.limits.max_cpu_time {
+            let cpu_time = self.cpu_time();
+            if cpu_time > max_cpu_time {
+                let message = format!(
+                    "VM CPU time limit exceeded: execution took {cpu_time:?}, while at most {max_cpu_time:?} is allowed"
+                );
+                self.violation = Some((LimitedResource::CpuTime, message));
+            }
+        }
+    }
+
+    fn store_result(&self) {
+        let usage = ResourceUsage {
+            peak_memory: self.peak_memory,
+            cpu_time: self.cpu_time(),
+            exceeded_limit: self.violation.as_ref().map(|(resource, _)| *resource),
+        };
+        // The result may already be set if the VM is run multiple times with the same tracer, e.g.
+        // when a transaction is re-executed without bytecode compression.
+        self.result.set(usage).ok();
+    }
+}
+
+impl IntoOldVmTracer for ResourceUsageTracer {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checking_resource_limits() {
+        let limits = ResourceLimits {
+            max_memory: Some(1_000),
+            max_cpu_time: None,
+        };
+        let mut tracer = ResourceUsageTracer::new(limits, Arc::default());
+        tracer.check(500);
+        tracer.check(200);
+        assert_eq!(tracer.peak_memory, 500);
+        assert!(tracer.violation.is_none());
+
+        tracer.check(1_001);
+        let (resource, message) = tracer.violation.clone().unwrap();
+        assert_eq!(resource, LimitedResource::Memory);
+        assert!(message.contains("memory limit exceeded"), "{message}");
+        // Only the first violation is recorded.
+        tracer.check(2_000);
+        assert_eq!(tracer.violation.as_ref().unwrap().1, message);
+
+        tracer.store_result();
+        let usage = *tracer.result.get().unwrap();
+        assert_eq!(usage.peak_memory, 2_000);
+        assert_eq!(usage.exceeded_limit, Some(LimitedResource::Memory));
+    }
+
+    #[test]
+    fn checking_cpu_time_limit() {
+        let limits = ResourceLimits {
+            max_memory: None,
+            max_cpu_time: Some(Duration::ZERO),
+        };
+        let mut tracer = ResourceUsageTracer::new(limits, Arc::default());
+        tracer.started_at = Some(Instant::now());
+        std::thread::sleep(Duration::from_millis(1));
+        tracer.check(0);
+        let (resource, message) = tracer.violation.clone().unwrap();
+        assert_eq!(resource, LimitedResource::CpuTime);
+        assert!(message.contains("CPU time limit exceeded"), "{message}");
+    }
+}
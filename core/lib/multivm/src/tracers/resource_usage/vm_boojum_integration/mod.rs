@@ -0,0 +1,10 @@
+use crate::{
+    interface::storage::WriteStorage,
+    tracers::{dynamic::vm_1_4_0::DynTracer, ResourceUsageTracer},
+    vm_boojum_integration::{HistoryMode, SimpleMemory, VmTracer},
+};
+
+// Resource usage is not accounted for this VM version.
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for ResourceUsageTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for ResourceUsageTracer {}
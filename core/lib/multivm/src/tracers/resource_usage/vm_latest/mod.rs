@@ -0,0 +1,59 @@
+use std::time::Instant;
+
+use crate::{
+    interface::{
+        storage::WriteStorage,
+        tracer::{TracerExecutionStatus, TracerExecutionStopReason, VmExecutionStopReason},
+        Halt,
+    },
+    tracers::{dynamic::vm_1_5_0::DynTracer, ResourceUsageTracer},
+    vm_latest::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
+};
+
+/// Estimates memory consumed by the VM oracles, including their history.
+fn estimate_memory<S: WriteStorage, H: HistoryMode>(state: &ZkSyncVmState<S, H>) -> usize {
+    state.event_sink.get_size()
+        + state.event_sink.get_history_size()
+        + state.memory.get_size()
+        + state.memory.get_history_size()
+        + state.decommittment_processor.get_size()
+        + state.decommittment_processor.get_history_size()
+        + state.storage.get_size()
+        + state.storage.get_history_size()
+}
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for ResourceUsageTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for ResourceUsageTracer {
+    fn initialize_tracer(&mut self, _state: &mut ZkSyncVmState<S, H>) {
+        self.started_at.get_or_insert_with(Instant::now);
+    }
+
+    fn finish_cycle(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &mut BootloaderState,
+    ) -> TracerExecutionStatus {
+        self.cycles += 1;
+        if self.cycles % Self::CHECK_INTERVAL == 0 {
+            self.check(estimate_memory(state));
+        }
+
+        if let Some((_, message)) = &self.violation {
+            return TracerExecutionStatus::Stop(TracerExecutionStopReason::Abort(
+                Halt::TracerCustom(message.clone()),
+            ));
+        }
+        TracerExecutionStatus::Continue
+    }
+
+    fn after_vm_execution(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.peak_memory = self.peak_memory.max(estimate_memory(state));
+        self.store_result();
+    }
+}
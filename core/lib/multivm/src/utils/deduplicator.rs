@@ -47,6 +47,11 @@ impl StorageWritesDeduplicator {
         self.metrics
     }
 
+    /// Returns storage slots modified so far together with their current values.
+    pub fn modified_key_values(&self) -> &HashMap<StorageKey, ModifiedSlot> {
+        &self.modified_key_values
+    }
+
     pub fn into_modified_key_values(self) -> HashMap<StorageKey, ModifiedSlot> {
         self.modified_key_values
     }
@@ -0,0 +1,199 @@
+//! Disassembler for EraVM bytecodes.
+
+use std::collections::BTreeSet;
+
+use zk_evm_1_5_0::zkevm_opcode_defs::{
+    decoding::{EncodingModeProduction, VmEncodingMode},
+    ImmMemHandlerFlags, Opcode, Operand,
+};
+use zksync_types::{
+    api::{BasicBlock, DisassembledBytecode, DisassembledInstruction},
+    bytecode::{validate_bytecode, InvalidBytecodeError},
+};
+
+/// Size of a single encoded instruction in bytes.
+const INSTRUCTION_SIZE: usize = 8;
+/// PC is a 16-bit value, so instructions beyond this limit are unreachable and are not decoded.
+const MAX_INSTRUCTION_COUNT: usize = 1 << 16;
+/// Offset of the 3-bit condition in the instruction encoding.
+const CONDITION_SHIFT: u32 = 13;
+/// Condition names indexed by their encoding.
+const CONDITION_NAMES: [&str; 8] = ["always", "gt", "lt", "eq", "ge", "le", "ne", "gtOrLt"];
+
+/// Effect of an instruction on control flow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ControlFlow {
+    /// Control proceeds to the next instruction.
+    Next,
+    /// Jump to a static address, or to an address computed at runtime (`None`).
+    Jump(Option<u16>),
+    /// Near call of a function with the specified exception handler.
+    NearCall { callee: u16, exception_handler: u16 },
+    /// Return (normal, revert or panic) from a near or far call.
+    Return,
+}
+
+#[derive(Debug)]
+struct DecodedInstruction {
+    instruction: DisassembledInstruction,
+    control_flow: ControlFlow,
+    is_conditional: bool,
+}
+
+impl DecodedInstruction {
+    fn new(pc: u16, raw: u64) -> Self {
+        let (variant, _) =
+            EncodingModeProduction::parse_preliminary_variant_and_absolute_number(raw);
+        let condition = ((raw >> CONDITION_SHIFT) & 0b111) as usize;
+        let imm0 = (raw >> 32) as u16;
+        let imm1 = (raw >> 48) as u16;
+
+        let control_flow = match variant.opcode {
+            Opcode::Jump(_) => {
+                let is_static =
+                    variant.src0_operand_type == Operand::Full(ImmMemHandlerFlags::UseImm16Only);
+                ControlFlow::Jump(is_static.then_some(imm0))
+            }
+            Opcode::NearCall(_) => ControlFlow::NearCall {
+                callee: imm0,
+                exception_handler: imm1,
+            },
+            Opcode::Ret(_) => ControlFlow::Return,
+            _ => ControlFlow::Next,
+        };
+
+        Self {
+            instruction: DisassembledInstruction {
+                pc,
+                raw: raw.into(),
+                opcode: format!("{:?}", variant.opcode),
+                condition: CONDITION_NAMES[condition].to_owned(),
+                src0: ((raw >> 16) & 0xf) as u8,
+                src1: ((raw >> 20) & 0xf) as u8,
+                dst0: ((raw >> 24) & 0xf) as u8,
+                dst1: ((raw >> 28) & 0xf) as u8,
+                imm0,
+                imm1,
+            },
+            control_flow,
+            is_conditional: condition != 0,
+        }
+    }
+
+    /// Returns PCs of instructions that control can statically flow to after this instruction.
+    fn successors(&self) -> Vec<u16> {
+        let next_pc = self.instruction.pc.checked_add(1);
+        let mut successors = match self.control_flow {
+            ControlFlow::Next => return next_pc.into_iter().collect(),
+            ControlFlow::Jump(target) => target.into_iter().collect(),
+            // Control returns to the next instruction after the called function completes.
+            ControlFlow::NearCall {
+                callee,
+                exception_handler,
+            } => [Some(callee), Some(exception_handler), next_pc]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ControlFlow::Return => vec![],
+        };
+        if self.is_conditional {
+            successors.extend(next_pc);
+        }
+        successors.sort_unstable();
+        successors.dedup();
+        successors
+    }
+}
+
+/// Disassembles an EraVM bytecode into instructions and splits them into basic blocks.
+///
+/// EraVM bytecodes don't separate code from constants, so the constant pool at the end of the bytecode
+/// is decoded as instructions as well. Constants are normally not reachable from the code, so they can be distinguished
+/// by following block successors starting from PC 0.
+pub fn disassemble_bytecode(bytecode: &[u8]) -> Result<DisassembledBytecode, InvalidBytecodeError> {
+    validate_bytecode(bytecode)?;
+
+    let decoded: Vec<_> = bytecode
+        .chunks_exact(INSTRUCTION_SIZE)
+        .take(MAX_INSTRUCTION_COUNT)
+        .enumerate()
+        .map(|(pc, raw)| {
+            let raw = u64::from_be_bytes(raw.try_into().unwrap());
+            DecodedInstruction::new(pc as u16, raw)
+        })
+        .collect();
+
+    // Block leaders are the entry point, static control flow targets and instructions following control flow changes.
+    let mut leaders = BTreeSet::from([0_u16]);
+    for instruction in &decoded {
+        if instruction.control_flow != ControlFlow::Next {
+            leaders.extend(instruction.successors());
+            leaders.extend(instruction.instruction.pc.checked_add(1));
+        }
+    }
+    let instruction_count = decoded.len();
+    leaders.retain(|&pc| usize::from(pc) < instruction_count);
+
+    let leaders: Vec<_> = leaders.into_iter().collect();
+    let basic_blocks = leaders
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let end = leaders
+                .get(i + 1)
+                .map_or(instruction_count - 1, |&next_start| {
+                    usize::from(next_start) - 1
+                });
+            let mut successors = decoded[end].successors();
+            successors.retain(|&pc| usize::from(pc) < instruction_count);
+            BasicBlock {
+                start,
+                end: end as u16,
+                successors,
+            }
+        })
+        .collect();
+
+    Ok(DisassembledBytecode {
+        instructions: decoded
+            .into_iter()
+            .map(|decoded| decoded.instruction)
+            .collect(),
+        basic_blocks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn disassembling_nop_bytecode() {
+        let nop = EncodingModeProduction::nop_encoding().to_be_bytes();
+        let bytecode: Vec<u8> = nop.repeat(12);
+
+        let disassembled = disassemble_bytecode(&bytecode).unwrap();
+        assert_eq!(disassembled.instructions.len(), 12);
+        for (pc, instruction) in disassembled.instructions.iter().enumerate() {
+            assert_eq!(usize::from(instruction.pc), pc);
+            assert!(instruction.opcode.starts_with("Nop"), "{instruction:?}");
+            assert_eq!(instruction.condition, "always");
+        }
+        assert_eq!(
+            disassembled.basic_blocks,
+            [BasicBlock {
+                start: 0,
+                end: 11,
+                successors: vec![],
+            }]
+        );
+    }
+
+    #[test]
+    fn disassembling_invalid_bytecode() {
+        let err = disassemble_bytecode(&[0; 64]).unwrap_err();
+        assert_matches!(err, InvalidBytecodeError::BytecodeLengthInWordsIsEven);
+    }
+}
@@ -0,0 +1,190 @@
+//! Circuit geometry used to estimate circuit usage of the latest VM versions.
+
+use circuit_sequencer_api::geometry_config::{GeometryConfig, ProtocolGeometry};
+use once_cell::sync::OnceCell;
+
+/// Overrides for the circuit geometry of the latest VM versions. Unset fields fall back to
+/// [`ProtocolGeometry::latest()`] and the default maximum number of base layer circuits in a batch.
+///
+/// Overrides should only be used if the prover is built with a matching geometry; otherwise, sealed batches
+/// may be unprovable.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CircuitGeometryOverrides {
+    pub cycles_per_vm_snapshot: Option<u32>,
+    pub cycles_per_ram_permutation: Option<u32>,
+    pub cycles_per_storage_application: Option<u32>,
+    pub cycles_per_storage_sorter: Option<u32>,
+    pub cycles_per_code_decommitter: Option<u32>,
+    pub cycles_code_decommitter_sorter: Option<u32>,
+    pub cycles_per_log_demuxer: Option<u32>,
+    pub cycles_per_events_or_l1_messages_sorter: Option<u32>,
+    pub cycles_per_keccak256_circuit: Option<u32>,
+    pub cycles_per_ecrecover_circuit: Option<u32>,
+    pub cycles_per_sha256_circuit: Option<u32>,
+    pub cycles_per_secp256r1_verify_circuit: Option<u32>,
+    pub cycles_per_transient_storage_sorter: Option<u32>,
+    pub max_base_layer_circuits: Option<usize>,
+}
+
+impl CircuitGeometryOverrides {
+    fn apply(self, config: &mut GeometryConfig) {
+        let fields = [
+            (
+                self.cycles_per_vm_snapshot,
+                &mut config.cycles_per_vm_snapshot,
+            ),
+            (
+                self.cycles_per_ram_permutation,
+                &mut config.cycles_per_ram_permutation,
+            ),
+            (
+                self.cycles_per_storage_application,
+                &mut config.cycles_per_storage_application,
+            ),
+            (
+                self.cycles_per_storage_sorter,
+                &mut config.cycles_per_storage_sorter,
+            ),
+            (
+                self.cycles_per_code_decommitter,
+                &mut config.cycles_per_code_decommitter,
+            ),
+            (
+                self.cycles_code_decommitter_sorter,
+                &mut config.cycles_code_decommitter_sorter,
+            ),
+            (
+                self.cycles_per_log_demuxer,
+                &mut config.cycles_per_log_demuxer,
+            ),
+            (
+                self.cycles_per_events_or_l1_messages_sorter,
+                &mut config.cycles_per_events_or_l1_messages_sorter,
+            ),
+            (
+                self.cycles_per_keccak256_circuit,
+                &mut config.cycles_per_keccak256_circuit,
+            ),
+            (
+                self.cycles_per_ecrecover_circuit,
+                &mut config.cycles_per_ecrecover_circuit,
+            ),
+            (
+                self.cycles_per_sha256_circuit,
+                &mut config.cycles_per_sha256_circuit,
+            ),
+            (
+                self.cycles_per_secp256r1_verify_circuit,
+                &mut config.cycles_per_secp256r1_verify_circuit,
+            ),
+            (
+                self.cycles_per_transient_storage_sorter,
+                &mut config.cycles_per_transient_storage_sorter,
+            ),
+        ];
+        for (value, target) in fields {
+            if let Some(value) = value {
+                *target = value;
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct CircuitGeometry {
+    pub config: GeometryConfig,
+    pub max_base_layer_circuits: usize,
+}
+
+impl CircuitGeometry {
+    fn new(overrides: CircuitGeometryOverrides) -> Self {
+        let mut config = ProtocolGeometry::latest().config();
+        overrides.apply(&mut config);
+        Self {
+            config,
+            max_base_layer_circuits: overrides
+                .max_base_layer_circuits
+                .unwrap_or(crate::vm_latest::constants::MAX_BASE_LAYER_CIRCUITS),
+        }
+    }
+}
+
+static CIRCUIT_GEOMETRY: OnceCell<CircuitGeometry> = OnceCell::new();
+
+/// Sets process-wide circuit geometry overrides for the latest VM versions. Must be called before any VM
+/// is instantiated.
+///
+/// # Errors
+///
+/// Returns an error if the geometry is already initialized (i.e., this method was called before, or the geometry
+/// was already used by a VM) with different values, or if any of the overrides is zero.
+pub fn set_circuit_geometry_overrides(overrides: CircuitGeometryOverrides) -> anyhow::Result<()> {
+    let geometry = CircuitGeometry::new(overrides);
+    anyhow::ensure!(
+        geometry.max_base_layer_circuits > 0,
+        "maximum number of base layer circuits must be positive"
+    );
+    anyhow::ensure!(
+        [
+            geometry.config.cycles_per_vm_snapshot,
+            geometry.config.cycles_per_ram_permutation,
+            geometry.config.cycles_per_storage_application,
+            geometry.config.cycles_per_storage_sorter,
+            geometry.config.cycles_per_code_decommitter,
+            geometry.config.cycles_code_decommitter_sorter,
+            geometry.config.cycles_per_log_demuxer,
+            geometry.config.cycles_per_events_or_l1_messages_sorter,
+            geometry.config.cycles_per_keccak256_circuit,
+            geometry.config.cycles_per_ecrecover_circuit,
+            geometry.config.cycles_per_sha256_circuit,
+            geometry.config.cycles_per_secp256r1_verify_circuit,
+            geometry.config.cycles_per_transient_storage_sorter,
+        ]
+        .iter()
+        .all(|&cycles| cycles > 0),
+        "circuit geometry cycles must be positive: {:?}",
+        geometry.config
+    );
+
+    if let Err(geometry) = CIRCUIT_GEOMETRY.set(geometry) {
+        let initialized = circuit_geometry();
+        anyhow::ensure!(
+            initialized.config == geometry.config
+                && initialized.max_base_layer_circuits == geometry.max_base_layer_circuits,
+            "circuit geometry is already initialized with different values: {initialized:?}"
+        );
+    }
+    Ok(())
+}
+
+/// Returns the circuit geometry for the latest VM versions, taking overrides into account.
+pub(crate) fn circuit_geometry() -> &'static CircuitGeometry {
+    CIRCUIT_GEOMETRY.get_or_init(|| CircuitGeometry::new(CircuitGeometryOverrides::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applying_geometry_overrides() {
+        let default_geometry = CircuitGeometry::new(CircuitGeometryOverrides::default());
+        assert_eq!(default_geometry.config, ProtocolGeometry::latest().config());
+        assert_eq!(
+            default_geometry.max_base_layer_circuits,
+            crate::vm_latest::constants::MAX_BASE_LAYER_CIRCUITS
+        );
+
+        let geometry = CircuitGeometry::new(CircuitGeometryOverrides {
+            cycles_per_keccak256_circuit: Some(100),
+            max_base_layer_circuits: Some(1_000),
+            ..CircuitGeometryOverrides::default()
+        });
+        assert_eq!(geometry.config.cycles_per_keccak256_circuit, 100);
+        assert_eq!(
+            geometry.config.cycles_per_vm_snapshot,
+            default_geometry.config.cycles_per_vm_snapshot
+        );
+        assert_eq!(geometry.max_base_layer_circuits, 1_000);
+    }
+}
@@ -1,18 +1,27 @@
+use std::collections::HashSet;
+
 use zksync_types::{
     fee_model::{BatchFeeInput, L1PeggedBatchFeeModelInput, PubdataIndependentBatchFeeModelInput},
     vm::VmVersion,
     U256,
 };
 
-pub use self::deduplicator::{ModifiedSlot, StorageWritesDeduplicator};
+pub(crate) use self::geometry::circuit_geometry;
+pub use self::{
+    deduplicator::{ModifiedSlot, StorageWritesDeduplicator},
+    disassembler::disassemble_bytecode,
+    geometry::{set_circuit_geometry_overrides, CircuitGeometryOverrides},
+};
 use crate::{
-    glue::{GlueFrom, GlueInto},
+    glue::{GlueError, GlueFrom, GlueInto, TryGlueFrom},
     interface::L1BatchEnv,
 };
 
 pub(crate) mod bytecode;
 mod deduplicator;
+mod disassembler;
 pub(crate) mod events;
+mod geometry;
 
 /// Allows to convert `LogQuery` between two different versions, even if they don't provide
 /// direct conversion between each other.
@@ -27,6 +36,30 @@ where
     R::glue_from(l.glue_into())
 }
 
+/// Validating version of [`glue_log_query()`] that should be used at VM version boundaries. Returns an error
+/// if the query cannot be represented in the target version (e.g., it has an aux byte unknown to it).
+pub fn try_glue_log_query<L, R>(l: L) -> Result<R, GlueError>
+where
+    L: GlueInto<zksync_types::zk_evm_types::LogQuery>,
+    R: TryGlueFrom<zksync_types::zk_evm_types::LogQuery>,
+{
+    R::try_glue_from(l.glue_into())
+}
+
+/// Estimates the total size of compressed bytecodes published by the VM for the provided factory dependencies,
+/// assuming that none of them is known yet. Uses the same compression algorithm as the VM; bytecodes
+/// that cannot be compressed are accounted with their original size.
+pub fn estimate_compressed_bytecodes_size(bytecodes: &[Vec<u8>]) -> usize {
+    let unique_bytecodes: HashSet<_> = bytecodes.iter().collect();
+    unique_bytecodes
+        .into_iter()
+        .map(|bytecode| {
+            bytecode::compress(bytecode.clone())
+                .map_or(bytecode.len(), |info| info.compressed.len())
+        })
+        .sum()
+}
+
 /// Calculates the base fee and gas per pubdata for the given L1 gas price.
 pub fn derive_base_fee_and_gas_per_pubdata(
     batch_fee_input: BatchFeeInput,
@@ -557,7 +590,7 @@ pub fn get_max_batch_base_layer_circuits(version: VmVersion) -> usize {
         VmVersion::Vm1_5_0SmallBootloaderMemory
         | VmVersion::Vm1_5_0IncreasedBootloaderMemory
         | VmVersion::VmGateway
-        | VmVersion::VmEvmEmulator => crate::vm_latest::constants::MAX_BASE_LAYER_CIRCUITS,
+        | VmVersion::VmEvmEmulator => circuit_geometry().max_base_layer_circuits,
     }
 }
 
@@ -15,7 +15,7 @@ use crate::{
         FinishedL1Batch, L1BatchEnv, L2BlockEnv, PushTransactionResult, SystemEnv, VmExecutionMode,
         VmExecutionResultAndLogs, VmFactory, VmInterface, VmInterfaceHistoryEnabled,
     },
-    utils::{events::extract_l2tol1logs_from_l1_messenger, glue_log_query},
+    utils::{events::extract_l2tol1logs_from_l1_messenger, try_glue_log_query},
     vm_1_4_1::{
         bootloader_state::BootloaderState,
         old_vm::events::merge_events,
@@ -60,12 +60,12 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
 
         let storage_log_queries = self.state.storage.get_final_log_queries();
 
-        let deduped_storage_log_queries = sort_storage_access_queries(
-            storage_log_queries
-                .iter()
-                .map(|log| glue_log_query(log.log_query)),
-        )
-        .1;
+        let deduped_storage_log_queries =
+            sort_storage_access_queries(storage_log_queries.iter().map(|log| {
+                try_glue_log_query(log.log_query)
+                    .unwrap_or_else(|err| panic!("Cannot sort storage log queries: {err}"))
+            }))
+            .1;
 
         CurrentExecutionState {
             events,
@@ -154,6 +154,7 @@ impl<S: WriteStorage, H: HistoryMode> VmInterface for Vm<S, H> {
                     .build_pubdata(false),
             ),
             state_diffs: None,
+            precompile_inputs: None,
         }
     }
 }
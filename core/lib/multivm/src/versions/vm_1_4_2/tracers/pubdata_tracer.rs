@@ -24,7 +24,7 @@ use crate::{
             extract_bytecode_publication_requests_from_l1_messenger,
             extract_l2tol1logs_from_l1_messenger,
         },
-        glue_log_query,
+        try_glue_log_query,
     },
     vm_1_4_2::{
         bootloader_state::{utils::apply_pubdata_to_memory, BootloaderState},
@@ -151,7 +151,10 @@ impl<S: WriteStorage> PubdataTracer<S> {
             storage
                 .storage_log_queries_after_timestamp(Timestamp(0))
                 .iter()
-                .map(|log| glue_log_query(log.log_query)),
+                .map(|log| {
+                    try_glue_log_query(log.log_query)
+                        .unwrap_or_else(|err| panic!("Cannot sort storage log queries: {err}"))
+                }),
         )
         .1
         .into_iter()
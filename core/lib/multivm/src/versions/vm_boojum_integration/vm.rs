@@ -151,6 +151,7 @@ impl<S: WriteStorage, H: HistoryMode> VmInterface for Vm<S, H> {
                     .build_pubdata(false),
             ),
             state_diffs: None,
+            precompile_inputs: None,
         }
     }
 }
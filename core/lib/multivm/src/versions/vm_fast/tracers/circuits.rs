@@ -1,10 +1,9 @@
-use circuit_sequencer_api::geometry_config::{GeometryConfig, ProtocolGeometry};
 use zksync_vm2::interface::{
     CycleStats, GlobalStateInterface, Opcode, OpcodeType, ShouldStop, Tracer,
 };
 use zksync_vm_interface::CircuitStatistic;
 
-use crate::vm_latest::tracers::circuits_capacity::*;
+use crate::{utils::circuit_geometry, vm_latest::tracers::circuits_capacity::*};
 
 /// VM tracer tracking [`CircuitStatistic`]s. Statistics generally depend on the number of time some opcodes were invoked,
 /// and, for precompiles, invocation complexity (e.g., how many hashing cycles `keccak256` required).
@@ -135,34 +134,30 @@ impl Tracer for CircuitsTracer {
 impl CircuitsTracer {
     /// Obtains the current circuit stats from this tracer.
     pub fn circuit_statistic(&self) -> CircuitStatistic {
+        let geometry = &circuit_geometry().config;
         CircuitStatistic {
-            main_vm: self.main_vm_cycles as f32 / GEOMETRY_CONFIG.cycles_per_vm_snapshot as f32,
+            main_vm: self.main_vm_cycles as f32 / geometry.cycles_per_vm_snapshot as f32,
             ram_permutation: self.ram_permutation_cycles as f32
-                / GEOMETRY_CONFIG.cycles_per_ram_permutation as f32,
+                / geometry.cycles_per_ram_permutation as f32,
             storage_application: self.storage_application_cycles as f32
-                / GEOMETRY_CONFIG.cycles_per_storage_application as f32,
+                / geometry.cycles_per_storage_application as f32,
             storage_sorter: self.storage_sorter_cycles as f32
-                / GEOMETRY_CONFIG.cycles_per_storage_sorter as f32,
+                / geometry.cycles_per_storage_sorter as f32,
             code_decommitter: self.code_decommitter_cycles as f32
-                / GEOMETRY_CONFIG.cycles_per_code_decommitter as f32,
+                / geometry.cycles_per_code_decommitter as f32,
             code_decommitter_sorter: self.code_decommitter_sorter_cycles as f32
-                / GEOMETRY_CONFIG.cycles_code_decommitter_sorter as f32,
-            log_demuxer: self.log_demuxer_cycles as f32
-                / GEOMETRY_CONFIG.cycles_per_log_demuxer as f32,
+                / geometry.cycles_code_decommitter_sorter as f32,
+            log_demuxer: self.log_demuxer_cycles as f32 / geometry.cycles_per_log_demuxer as f32,
             events_sorter: self.events_sorter_cycles as f32
-                / GEOMETRY_CONFIG.cycles_per_events_or_l1_messages_sorter as f32,
-            keccak256: self.keccak256_cycles as f32
-                / GEOMETRY_CONFIG.cycles_per_keccak256_circuit as f32,
-            ecrecover: self.ecrecover_cycles as f32
-                / GEOMETRY_CONFIG.cycles_per_ecrecover_circuit as f32,
-            sha256: self.sha256_cycles as f32 / GEOMETRY_CONFIG.cycles_per_sha256_circuit as f32,
+                / geometry.cycles_per_events_or_l1_messages_sorter as f32,
+            keccak256: self.keccak256_cycles as f32 / geometry.cycles_per_keccak256_circuit as f32,
+            ecrecover: self.ecrecover_cycles as f32 / geometry.cycles_per_ecrecover_circuit as f32,
+            sha256: self.sha256_cycles as f32 / geometry.cycles_per_sha256_circuit as f32,
             secp256k1_verify: self.secp256r1_verify_cycles as f32
-                / GEOMETRY_CONFIG.cycles_per_secp256r1_verify_circuit as f32,
+                / geometry.cycles_per_secp256r1_verify_circuit as f32,
             transient_storage_checker: self.transient_storage_checker_cycles as f32
-                / GEOMETRY_CONFIG.cycles_per_transient_storage_sorter as f32,
+                / geometry.cycles_per_transient_storage_sorter as f32,
             ..Default::default()
         }
     }
 }
-
-const GEOMETRY_CONFIG: GeometryConfig = ProtocolGeometry::latest().config();
@@ -853,6 +853,7 @@ where
                     .state_diffs
                     .to_vec(),
             ),
+            precompile_inputs: None,
         }
     }
 }
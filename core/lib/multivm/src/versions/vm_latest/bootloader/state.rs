@@ -238,6 +238,11 @@ impl BootloaderState {
         }
     }
 
+    /// Returns the id of current tx, or `None` if no transactions were executed yet.
+    pub(crate) fn try_current_tx(&self) -> Option<usize> {
+        self.tx_to_execute.checked_sub(1)
+    }
+
     /// Returns the id of current tx
     pub(crate) fn current_tx(&self) -> usize {
         self.tx_to_execute
@@ -11,7 +11,7 @@ pub use self::{
         dispatcher::TracerDispatcher,
         traits::{ToTracerPointer, TracerPointer, VmTracer},
     },
-    types::ZkSyncVmState,
+    types::{WarmVmState, ZkSyncVmState},
     utils::transaction_encoding::TransactionVmExt,
     vm::Vm,
 };
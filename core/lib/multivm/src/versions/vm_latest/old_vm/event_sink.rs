@@ -9,9 +9,12 @@ use zk_evm_1_5_0::{
     },
 };
 
-use crate::vm_latest::old_vm::{
-    history_recorder::{AppDataFrameManagerWithHistory, HistoryEnabled, HistoryMode},
-    oracles::OracleWithHistory,
+use crate::{
+    interface::utils::LogQuerySnapshot,
+    vm_latest::old_vm::{
+        history_recorder::{AppDataFrameManagerWithHistory, HistoryEnabled, HistoryMode},
+        oracles::OracleWithHistory,
+    },
 };
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -41,6 +44,24 @@ impl<H: HistoryMode> InMemoryEventSink<H> {
         Self::events_and_l1_messages_from_history(history)
     }
 
+    /// Returns snapshots of log queries in all frames (including rollbacks) in the order they were recorded.
+    pub(crate) fn log_query_snapshots(&self) -> Vec<LogQuerySnapshot> {
+        self.frames_stack
+            .forward()
+            .all_frames()
+            .iter()
+            .map(|query| LogQuerySnapshot {
+                timestamp: query.timestamp.0,
+                aux_byte: query.aux_byte,
+                address: query.address,
+                key: query.key,
+                value: query.written_value,
+                is_service: query.is_service,
+                rollback: query.rollback,
+            })
+            .collect()
+    }
+
     pub fn get_log_queries(&self) -> usize {
         self.frames_stack.forward().current_frame().len()
     }
@@ -399,6 +399,11 @@ impl<T> FramedStack<T> {
         &self.data[*self.frame_start_indices.last().unwrap()..self.data.len()]
     }
 
+    /// Returns items in all frames, starting from the outermost frame.
+    pub fn all_frames(&self) -> &[T] {
+        &self.data
+    }
+
     fn len(&self) -> usize {
         self.frame_start_indices.len()
     }
@@ -553,6 +558,22 @@ impl MemoryPage {
             * PAGE_SUBDIVISION_LEN
             * std::mem::size_of::<PrimitiveValue>()
     }
+
+    /// Iterates over non-empty slots in the ascending slot order.
+    fn non_empty_slots(&self) -> impl Iterator<Item = (usize, PrimitiveValue)> + '_ {
+        self.root
+            .iter()
+            .enumerate()
+            .filter_map(|(root_index, leaf)| Some((root_index, leaf.as_ref()?)))
+            .flat_map(|(root_index, leaf)| {
+                leaf.iter()
+                    .enumerate()
+                    .filter(|(_, value)| **value != PRIMITIVE_VALUE_EMPTY)
+                    .map(move |(leaf_index, value)| {
+                        (root_index * PAGE_SUBDIVISION_LEN + leaf_index, *value)
+                    })
+            })
+    }
 }
 
 impl PartialEq for MemoryPage {
@@ -626,6 +647,17 @@ impl MemoryWrapper {
     pub fn get_size(&self) -> usize {
         self.memory.iter().map(|page| page.get_size()).sum()
     }
+
+    /// Iterates over non-empty memory words as `(page, slot, value)` tuples ordered by page and slot.
+    pub fn non_empty_words(&self) -> impl Iterator<Item = (usize, usize, PrimitiveValue)> + '_ {
+        self.memory
+            .iter()
+            .enumerate()
+            .flat_map(|(page_number, page)| {
+                page.non_empty_slots()
+                    .map(move |(slot, value)| (page_number, slot, value))
+            })
+    }
 }
 
 impl WithHistory for MemoryWrapper {
@@ -86,6 +86,13 @@ impl<H: HistoryMode> SimpleMemory<H> {
         self.memory.inner().read_slot(page, slot)
     }
 
+    /// Iterates over non-empty memory words as `(page, slot, value)` tuples ordered by page and slot.
+    pub(crate) fn non_empty_words(
+        &self,
+    ) -> impl Iterator<Item = (usize, usize, PrimitiveValue)> + '_ {
+        self.memory.inner().non_empty_words()
+    }
+
     // This method should be used with relatively small lengths, since
     // we don't heavily optimize here for cases with long lengths
     pub fn read_unaligned_bytes(&self, page: usize, start: usize, length: usize) -> Vec<u8> {
@@ -7,9 +7,13 @@ use zk_evm_1_5_0::{
         ecrecover, keccak256, secp256r1_verify, sha256, PrecompileAddress,
     },
 };
+use zksync_types::U256;
 
 use super::OracleWithHistory;
-use crate::vm_latest::old_vm::history_recorder::{HistoryEnabled, HistoryMode, HistoryRecorder};
+use crate::{
+    interface::PrecompileInputs,
+    vm_latest::old_vm::history_recorder::{HistoryEnabled, HistoryMode, HistoryRecorder},
+};
 
 /// Wrap of DefaultPrecompilesProcessor that store queue
 /// of timestamp when precompiles are called to be executed.
@@ -20,6 +24,13 @@ use crate::vm_latest::old_vm::history_recorder::{HistoryEnabled, HistoryMode, Hi
 pub struct PrecompilesProcessorWithHistory<H: HistoryMode> {
     pub timestamp_history: HistoryRecorder<Vec<Timestamp>, H>,
     pub precompile_cycles_history: HistoryRecorder<Vec<(PrecompileAddress, usize)>, H>,
+    /// Memory words read by hashing precompile calls, concatenated in the call order.
+    pub hash_input_words_history: HistoryRecorder<Vec<U256>, H>,
+    /// Hashing precompile calls together with the number of memory words read by each call.
+    pub hash_input_calls_history: HistoryRecorder<Vec<(PrecompileAddress, usize)>, H>,
+    /// Whether inputs of hashing precompile calls are recorded. Disabled by default since recording
+    /// requires collecting memory queries for each call.
+    record_hash_inputs: bool,
 }
 
 impl<H: HistoryMode> Default for PrecompilesProcessorWithHistory<H> {
@@ -27,6 +38,9 @@ impl<H: HistoryMode> Default for PrecompilesProcessorWithHistory<H> {
         Self {
             timestamp_history: Default::default(),
             precompile_cycles_history: Default::default(),
+            hash_input_words_history: Default::default(),
+            hash_input_calls_history: Default::default(),
+            record_hash_inputs: false,
         }
     }
 }
@@ -36,6 +50,10 @@ impl OracleWithHistory for PrecompilesProcessorWithHistory<HistoryEnabled> {
         self.timestamp_history.rollback_to_timestamp(timestamp);
         self.precompile_cycles_history
             .rollback_to_timestamp(timestamp);
+        self.hash_input_words_history
+            .rollback_to_timestamp(timestamp);
+        self.hash_input_calls_history
+            .rollback_to_timestamp(timestamp);
     }
 }
 
@@ -47,6 +65,46 @@ impl<H: HistoryMode> PrecompilesProcessorWithHistory<H> {
     pub fn delete_history(&mut self) {
         self.timestamp_history.delete_history();
         self.precompile_cycles_history.delete_history();
+        self.hash_input_words_history.delete_history();
+        self.hash_input_calls_history.delete_history();
+    }
+
+    /// Enables recording inputs of hashing precompile calls. Should be called before any transactions are executed.
+    pub fn enable_hash_inputs_recording(&mut self) {
+        self.record_hash_inputs = true;
+    }
+
+    /// Returns inputs of all hashing precompile calls laid out in contiguous buffers, or `None` if recording
+    /// is not enabled.
+    pub fn hash_inputs(&self) -> Option<PrecompileInputs> {
+        if !self.record_hash_inputs {
+            return None;
+        }
+
+        let mut inputs = PrecompileInputs::default();
+        let mut words = self.hash_input_words_history.inner().iter().copied();
+        for &(precompile_address, words_count) in self.hash_input_calls_history.inner() {
+            let call_words = words.by_ref().take(words_count);
+            match precompile_address {
+                PrecompileAddress::Keccak256 => inputs.keccak256.push_call(call_words),
+                PrecompileAddress::SHA256 => inputs.sha256.push_call(call_words),
+                _ => unreachable!("only hashing precompile calls are recorded"),
+            }
+        }
+        Some(inputs)
+    }
+
+    fn record_hash_inputs(
+        &mut self,
+        precompile_address: PrecompileAddress,
+        reads: &[MemoryQuery],
+        timestamp: Timestamp,
+    ) {
+        for read in reads {
+            self.hash_input_words_history.push(read.value, timestamp);
+        }
+        self.hash_input_calls_history
+            .push((precompile_address, reads.len()), timestamp);
     }
 }
 
@@ -74,21 +132,45 @@ impl<H: HistoryMode> PrecompilesProcessor for PrecompilesProcessorWithHistory<H>
             let rounds = match precompile_address {
                 PrecompileAddress::Keccak256 => {
                     // pure function call, non-revertable
-                    keccak256::keccak256_rounds_function::<M, false>(
-                        monotonic_cycle_counter,
-                        query,
-                        memory,
-                    )
-                    .0
+                    if self.record_hash_inputs {
+                        let (rounds, witness) = keccak256::keccak256_rounds_function::<M, true>(
+                            monotonic_cycle_counter,
+                            query,
+                            memory,
+                        );
+                        if let Some((reads, _, _)) = witness {
+                            self.record_hash_inputs(precompile_address, &reads, query.timestamp);
+                        }
+                        rounds
+                    } else {
+                        keccak256::keccak256_rounds_function::<M, false>(
+                            monotonic_cycle_counter,
+                            query,
+                            memory,
+                        )
+                        .0
+                    }
                 }
                 PrecompileAddress::SHA256 => {
                     // pure function call, non-revertable
-                    sha256::sha256_rounds_function::<M, false>(
-                        monotonic_cycle_counter,
-                        query,
-                        memory,
-                    )
-                    .0
+                    if self.record_hash_inputs {
+                        let (rounds, witness) = sha256::sha256_rounds_function::<M, true>(
+                            monotonic_cycle_counter,
+                            query,
+                            memory,
+                        );
+                        if let Some((reads, _, _)) = witness {
+                            self.record_hash_inputs(precompile_address, &reads, query.timestamp);
+                        }
+                        rounds
+                    } else {
+                        sha256::sha256_rounds_function::<M, false>(
+                            monotonic_cycle_counter,
+                            query,
+                            memory,
+                        )
+                        .0
+                    }
                 }
                 PrecompileAddress::Ecrecover => {
                     // pure function call, non-revertable
@@ -21,7 +21,10 @@ use zksync_types::{
 
 use crate::{
     glue::GlueInto,
-    interface::storage::{StoragePtr, WriteStorage},
+    interface::{
+        storage::{StoragePtr, WriteStorage},
+        utils::StorageSlotSnapshot,
+    },
     vm_latest::{
         old_vm::{
             history_recorder::{
@@ -332,6 +335,41 @@ impl<S: WriteStorage, H: HistoryMode> StorageOracle<S, H> {
             .unwrap_or(&[])
     }
 
+    /// Returns storage slots modified during VM execution, ordered by address and key.
+    pub(crate) fn modified_slots_snapshot(&self) -> Vec<StorageSlotSnapshot> {
+        let mut slots: Vec<_> = self
+            .storage
+            .inner()
+            .get_modified_storage_keys()
+            .into_iter()
+            .map(|(key, value)| StorageSlotSnapshot {
+                address: *key.address(),
+                key: *key.key(),
+                value,
+            })
+            .collect();
+        slots.sort_unstable_by_key(|slot| (slot.address, slot.key));
+        slots
+    }
+
+    /// Returns non-zero transient storage slots, ordered by address and key.
+    pub(crate) fn transient_slots_snapshot(&self) -> Vec<StorageSlotSnapshot> {
+        let mut slots: Vec<_> = self
+            .transient_storage
+            .inner()
+            .inner()
+            .iter()
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(key, value)| StorageSlotSnapshot {
+                address: *key.address(),
+                key: *key.key(),
+                value: u256_to_h256(*value),
+            })
+            .collect();
+        slots.sort_unstable_by_key(|slot| (slot.address, slot.key));
+        slots
+    }
+
     pub(crate) fn get_final_log_queries(&self) -> Vec<StorageLogQuery> {
         assert_eq!(
             self.storage_frames_stack.len(),
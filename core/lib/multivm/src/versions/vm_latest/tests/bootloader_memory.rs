@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use once_cell::sync::OnceCell;
+use zksync_test_contracts::TxType;
+
+use super::TestedLatestVm;
+use crate::{
+    interface::{
+        BootloaderMemoryDump, BootloaderMemoryDumpConfig, BootloaderMemoryDumpPoint,
+        BootloaderMemoryRegion, InspectExecutionMode, TxExecutionMode, VmInterface,
+    },
+    tracers::BootloaderMemoryTracer,
+    versions::testonly::VmTesterBuilder,
+    vm_latest::{constants::BATCH_COMPUTATIONAL_GAS_LIMIT, ToTracerPointer},
+};
+
+#[test]
+fn dumping_bootloader_memory() {
+    let mut vm = VmTesterBuilder::new()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .build::<TestedLatestVm>();
+
+    vm.deploy_test_contract();
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_test_contract_transaction(
+        vm.test_contract.unwrap(),
+        false,
+        Default::default(),
+        false,
+        TxType::L2,
+    );
+    vm.vm.push_transaction(tx);
+
+    let dumps = Arc::new(OnceCell::new());
+    let tracer = BootloaderMemoryTracer::new(BootloaderMemoryDumpConfig::all(), dumps.clone())
+        .into_tracer_pointer();
+    let res = vm
+        .vm
+        .inspect(&mut tracer.into(), InspectExecutionMode::OneTx);
+    assert!(!res.result.is_failed(), "{:?}", res.result);
+
+    let dumps = Arc::try_unwrap(dumps).unwrap().take().unwrap();
+    let points: Vec<_> = dumps.iter().map(|dump| dump.point).collect();
+    assert!(
+        points.contains(&BootloaderMemoryDumpPoint::ValidationStarted),
+        "{points:?}"
+    );
+    assert!(
+        points.contains(&BootloaderMemoryDumpPoint::RefundRequested),
+        "{points:?}"
+    );
+    assert_eq!(
+        points.last(),
+        Some(&BootloaderMemoryDumpPoint::ExecutionEnded)
+    );
+
+    // The test contract was deployed in a separate transaction, so there are 2 transactions in the bootloader.
+    let last_dump = dumps.last().unwrap();
+    assert_eq!(last_dump.tx_index, Some(1));
+    assert_eq!(last_dump.regions.len(), BootloaderMemoryRegion::ALL.len());
+    let tx_descriptions = &last_dump.regions[0];
+    assert_eq!(
+        tx_descriptions.region,
+        BootloaderMemoryRegion::TxDescriptions
+    );
+    assert_eq!(tx_descriptions.words.len(), 4);
+    // Metadata must be set for both transactions.
+    assert!(!tx_descriptions.words[0].is_zero());
+    assert!(!tx_descriptions.words[2].is_zero());
+    let refunds = &last_dump.regions[1];
+    assert_eq!(refunds.region, BootloaderMemoryRegion::OperatorRefunds);
+    assert_eq!(refunds.words.len(), 2);
+
+    let serialized = serde_json::to_string(&dumps).unwrap();
+    let restored: Vec<BootloaderMemoryDump> = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(restored, dumps);
+}
@@ -41,6 +41,7 @@ use crate::{
 };
 
 mod bootloader;
+mod bootloader_memory;
 mod default_aa;
 // TODO - fix this test
 // `mod invalid_bytecode;`
@@ -67,6 +68,7 @@ mod require_eip712;
 mod rollbacks;
 mod secp256r1;
 mod simple_execution;
+mod state_snapshot;
 mod storage;
 mod tracing_execution_error;
 mod transfer;
@@ -1,6 +1,17 @@
+use zksync_test_contracts::TestContract;
+use zksync_types::{Address, Execute, Transaction};
+
+use super::TestedLatestVm;
 use crate::{
-    versions::testonly::precompiles::{test_ecrecover, test_keccak, test_sha256},
-    vm_latest::{HistoryEnabled, Vm},
+    interface::{
+        InspectExecutionMode, TxExecutionMode, VmInterface, VmInterfaceExt,
+        VmInterfaceHistoryEnabled,
+    },
+    versions::testonly::{
+        precompiles::{test_ecrecover, test_keccak, test_sha256},
+        ContractToDeploy, VmTester, VmTesterBuilder,
+    },
+    vm_latest::{constants::BATCH_COMPUTATIONAL_GAS_LIMIT, HistoryEnabled, Vm},
 };
 
 #[test]
@@ -17,3 +28,69 @@ fn sha256() {
 fn ecrecover() {
     test_ecrecover::<Vm<_, HistoryEnabled>>();
 }
+
+fn setup_keccak_tx() -> (VmTester<TestedLatestVm>, Transaction) {
+    let contract = TestContract::precompiles_test().bytecode.to_vec();
+    let address = Address::repeat_byte(1);
+    let mut vm = VmTesterBuilder::new()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![ContractToDeploy::account(contract, address)])
+        .build::<Vm<_, HistoryEnabled>>();
+
+    // calldata for `doKeccak(10)`.
+    let keccak10_calldata =
+        "370f20ac000000000000000000000000000000000000000000000000000000000000000a";
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(address),
+            calldata: hex::decode(keccak10_calldata).unwrap(),
+            value: 0.into(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+    (vm, tx)
+}
+
+#[test]
+fn hash_precompile_inputs_are_not_recorded_by_default() {
+    let (mut vm, tx) = setup_keccak_tx();
+    vm.vm.push_transaction(tx);
+    let exec_result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(!exec_result.result.is_failed(), "{exec_result:#?}");
+    assert_eq!(vm.vm.state.precompiles_processor.hash_inputs(), None);
+}
+
+#[test]
+fn hash_precompile_inputs_are_recorded() {
+    let (mut vm, tx) = setup_keccak_tx();
+    vm.vm.record_precompile_inputs();
+
+    let inputs_before_tx = vm.vm.state.precompiles_processor.hash_inputs().unwrap();
+    vm.vm.make_snapshot();
+    vm.vm.push_transaction(tx.clone());
+    let exec_result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(!exec_result.result.is_failed(), "{exec_result:#?}");
+    // Inputs must be rolled back together with the transaction.
+    vm.vm.rollback_to_the_latest_snapshot();
+    assert_eq!(
+        vm.vm.state.precompiles_processor.hash_inputs().unwrap(),
+        inputs_before_tx
+    );
+    let keccak_calls_before_tx = inputs_before_tx.keccak256.len();
+
+    vm.vm.push_transaction(tx);
+    let exec_result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(!exec_result.result.is_failed(), "{exec_result:#?}");
+
+    let inputs = vm.vm.state.precompiles_processor.hash_inputs().unwrap();
+    assert!(
+        inputs.keccak256.len() >= keccak_calls_before_tx + 10,
+        "{inputs:?}"
+    );
+    let recorded_words: usize = inputs.keccak256.calls().map(<[_]>::len).sum();
+    assert_eq!(recorded_words, inputs.keccak256.words().len());
+}
@@ -0,0 +1,54 @@
+use zksync_test_contracts::TxType;
+
+use super::TestedLatestVm;
+use crate::{
+    interface::{InspectExecutionMode, TxExecutionMode, VmInterface, VmInterfaceExt},
+    versions::testonly::VmTesterBuilder,
+    vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
+};
+
+#[test]
+fn diffing_state_snapshots() {
+    let mut vm = VmTesterBuilder::new()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .build::<TestedLatestVm>();
+
+    vm.deploy_test_contract();
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_test_contract_transaction(
+        vm.test_contract.unwrap(),
+        false,
+        Default::default(),
+        false,
+        TxType::L2,
+    );
+
+    let snapshot_before = vm.vm.state_snapshot();
+    assert_eq!(snapshot_before.diff(&vm.vm.state_snapshot()), []);
+    assert!(!snapshot_before.memory.is_empty());
+
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(!res.result.is_failed(), "{:?}", res.result);
+
+    let snapshot_after = vm.vm.state_snapshot();
+    assert!(snapshot_after.timestamp > snapshot_before.timestamp);
+    assert!(snapshot_after.log_queries.len() > snapshot_before.log_queries.len());
+    let diff = snapshot_before.diff(&snapshot_after);
+    let paths: Vec<_> = diff.iter().map(|diff| diff.path.as_str()).collect();
+    assert!(paths.contains(&"timestamp"), "{paths:?}");
+    assert!(
+        paths.iter().any(|path| path.starts_with("storage[")),
+        "{paths:?}"
+    );
+    assert!(
+        paths.iter().any(|path| path.starts_with("memory[")),
+        "{paths:?}"
+    );
+
+    let serialized = serde_json::to_string(&snapshot_after).unwrap();
+    let restored = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(snapshot_after.diff(&restored), []);
+}
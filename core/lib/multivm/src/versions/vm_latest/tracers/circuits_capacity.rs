@@ -1,6 +1,7 @@
-use circuit_sequencer_api::geometry_config::{GeometryConfig, ProtocolGeometry};
-
-use crate::{interface::CircuitStatistic, utils::CircuitCycleStatistic};
+use crate::{
+    interface::CircuitStatistic,
+    utils::{circuit_geometry, CircuitCycleStatistic},
+};
 
 // "Rich addressing" opcodes are opcodes that can write their return value/read the input onto the stack
 // and so take 1-2 RAM permutations more than an average opcode.
@@ -52,34 +53,30 @@ pub(crate) const PRECOMPILE_LOG_DEMUXER_CYCLES: u32 = 1;
 pub(crate) const LOG_DECOMMIT_RAM_CYCLES: u32 = 1;
 pub(crate) const LOG_DECOMMIT_DECOMMITTER_SORTER_CYCLES: u32 = 1;
 
-const GEOMETRY_CONFIG: GeometryConfig = ProtocolGeometry::latest().config();
-
 pub(crate) fn circuit_statistic_from_cycles(cycles: CircuitCycleStatistic) -> CircuitStatistic {
+    let geometry = &circuit_geometry().config;
     CircuitStatistic {
-        main_vm: cycles.main_vm_cycles as f32 / GEOMETRY_CONFIG.cycles_per_vm_snapshot as f32,
+        main_vm: cycles.main_vm_cycles as f32 / geometry.cycles_per_vm_snapshot as f32,
         ram_permutation: cycles.ram_permutation_cycles as f32
-            / GEOMETRY_CONFIG.cycles_per_ram_permutation as f32,
+            / geometry.cycles_per_ram_permutation as f32,
         storage_application: cycles.storage_application_cycles as f32
-            / GEOMETRY_CONFIG.cycles_per_storage_application as f32,
+            / geometry.cycles_per_storage_application as f32,
         storage_sorter: cycles.storage_sorter_cycles as f32
-            / GEOMETRY_CONFIG.cycles_per_storage_sorter as f32,
+            / geometry.cycles_per_storage_sorter as f32,
         code_decommitter: cycles.code_decommitter_cycles as f32
-            / GEOMETRY_CONFIG.cycles_per_code_decommitter as f32,
+            / geometry.cycles_per_code_decommitter as f32,
         code_decommitter_sorter: cycles.code_decommitter_sorter_cycles as f32
-            / GEOMETRY_CONFIG.cycles_code_decommitter_sorter as f32,
-        log_demuxer: cycles.log_demuxer_cycles as f32
-            / GEOMETRY_CONFIG.cycles_per_log_demuxer as f32,
+            / geometry.cycles_code_decommitter_sorter as f32,
+        log_demuxer: cycles.log_demuxer_cycles as f32 / geometry.cycles_per_log_demuxer as f32,
         events_sorter: cycles.events_sorter_cycles as f32
-            / GEOMETRY_CONFIG.cycles_per_events_or_l1_messages_sorter as f32,
-        keccak256: cycles.keccak256_cycles as f32
-            / GEOMETRY_CONFIG.cycles_per_keccak256_circuit as f32,
-        ecrecover: cycles.ecrecover_cycles as f32
-            / GEOMETRY_CONFIG.cycles_per_ecrecover_circuit as f32,
-        sha256: cycles.sha256_cycles as f32 / GEOMETRY_CONFIG.cycles_per_sha256_circuit as f32,
+            / geometry.cycles_per_events_or_l1_messages_sorter as f32,
+        keccak256: cycles.keccak256_cycles as f32 / geometry.cycles_per_keccak256_circuit as f32,
+        ecrecover: cycles.ecrecover_cycles as f32 / geometry.cycles_per_ecrecover_circuit as f32,
+        sha256: cycles.sha256_cycles as f32 / geometry.cycles_per_sha256_circuit as f32,
         secp256k1_verify: cycles.secp256k1_verify_cycles as f32
-            / GEOMETRY_CONFIG.cycles_per_secp256r1_verify_circuit as f32,
+            / geometry.cycles_per_secp256r1_verify_circuit as f32,
         transient_storage_checker: cycles.transient_storage_checker_cycles as f32
-            / GEOMETRY_CONFIG.cycles_per_transient_storage_sorter as f32,
+            / geometry.cycles_per_transient_storage_sorter as f32,
         ..Default::default()
     }
 }
@@ -3,7 +3,7 @@ mod snapshot;
 mod transaction_data;
 mod vm_state;
 
-pub use self::vm_state::ZkSyncVmState;
+pub use self::vm_state::{WarmVmState, ZkSyncVmState};
 pub(crate) use self::{
     hook::VmHook, snapshot::VmSnapshot, transaction_data::TransactionData, vm_state::new_vm_state,
 };
@@ -10,8 +10,9 @@ use zk_evm_1_5_0::{
         STARTING_BASE_PAGE, STARTING_TIMESTAMP,
     },
 };
+use zksync_contracts::{BaseSystemContracts, BaseSystemContractsHashes};
 use zksync_system_constants::BOOTLOADER_ADDRESS;
-use zksync_types::{block::L2BlockHasher, h256_to_u256, Address, L2BlockNumber};
+use zksync_types::{block::L2BlockHasher, h256_to_u256, Address, L2BlockNumber, U256};
 
 use crate::{
     interface::{
@@ -45,6 +46,53 @@ pub type ZkSyncVmState<S, H> = VmState<
     DummyTracer,
 >;
 
+/// Part of the VM state depending only on the base system contracts: the bootloader code loaded into memory
+/// and default account / EVM emulator bytecodes ready to be decommitted.
+///
+/// Can be prepared in advance (e.g., on a background thread) to reduce VM initialization latency.
+/// Each state can be used to initialize a single VM.
+#[derive(Debug)]
+pub struct WarmVmState<H: HistoryMode> {
+    base_system_contracts: BaseSystemContractsHashes,
+    memory: SimpleMemory<H>,
+    initial_bytecodes: Vec<(U256, Vec<U256>)>,
+}
+
+impl<H: HistoryMode> WarmVmState<H> {
+    pub fn new(base_system_contracts: &BaseSystemContracts) -> Self {
+        let mut initial_bytecodes = vec![(
+            h256_to_u256(base_system_contracts.default_aa.hash),
+            bytes_to_be_words(&base_system_contracts.default_aa.code),
+        )];
+        if let Some(evm_emulator) = &base_system_contracts.evm_emulator {
+            initial_bytecodes.push((
+                h256_to_u256(evm_emulator.hash),
+                bytes_to_be_words(&evm_emulator.code),
+            ));
+        }
+
+        let mut memory = SimpleMemory::default();
+        memory.populate(
+            vec![(
+                BOOTLOADER_CODE_PAGE,
+                bytes_to_be_words(&base_system_contracts.bootloader.code),
+            )],
+            Timestamp(0),
+        );
+
+        Self {
+            base_system_contracts: base_system_contracts.hashes(),
+            memory,
+            initial_bytecodes,
+        }
+    }
+
+    /// Returns hashes of the base system contracts this state was prepared for.
+    pub fn base_system_contracts(&self) -> BaseSystemContractsHashes {
+        self.base_system_contracts
+    }
+}
+
 fn formal_calldata_abi() -> PrimitiveValue {
     let fat_pointer = FatPointer {
         offset: 0,
@@ -65,7 +113,14 @@ pub(crate) fn new_vm_state<S: WriteStorage, H: HistoryMode>(
     system_env: &SystemEnv,
     l1_batch_env: &L1BatchEnv,
     subversion: MultiVmSubversion,
+    warm_state: WarmVmState<H>,
 ) -> (ZkSyncVmState<S, H>, BootloaderState) {
+    assert_eq!(
+        warm_state.base_system_contracts,
+        system_env.base_system_smart_contracts.hashes(),
+        "Warm VM state was prepared for other base system contracts"
+    );
+
     let last_l2_block = if let Some(last_l2_block) = load_last_l2_block(&storage) {
         last_l2_block
     } else {
@@ -81,31 +136,13 @@ pub(crate) fn new_vm_state<S: WriteStorage, H: HistoryMode>(
     assert_next_block(&last_l2_block, &l1_batch_env.first_l2_block);
     let first_l2_block = l1_batch_env.first_l2_block;
     let storage_oracle: StorageOracle<S, H> = StorageOracle::new(storage.clone());
-    let mut memory = SimpleMemory::default();
+    let mut memory = warm_state.memory;
     let event_sink = InMemoryEventSink::default();
     let precompiles_processor = PrecompilesProcessorWithHistory::<H>::default();
 
     let mut decommittment_processor: DecommitterOracle<false, S, H> =
         DecommitterOracle::new(storage);
-    let mut initial_bytecodes = vec![(
-        h256_to_u256(system_env.base_system_smart_contracts.default_aa.hash),
-        bytes_to_be_words(&system_env.base_system_smart_contracts.default_aa.code),
-    )];
-    if let Some(evm_emulator) = &system_env.base_system_smart_contracts.evm_emulator {
-        initial_bytecodes.push((
-            h256_to_u256(evm_emulator.hash),
-            bytes_to_be_words(&evm_emulator.code),
-        ));
-    }
-    decommittment_processor.populate(initial_bytecodes, Timestamp(0));
-
-    memory.populate(
-        vec![(
-            BOOTLOADER_CODE_PAGE,
-            bytes_to_be_words(&system_env.base_system_smart_contracts.bootloader.code),
-        )],
-        Timestamp(0),
-    );
+    decommittment_processor.populate(warm_state.initial_bytecodes, Timestamp(0));
 
     let bootloader_initial_memory = BootloaderState::initial_memory(l1_batch_env);
     memory.populate_page(
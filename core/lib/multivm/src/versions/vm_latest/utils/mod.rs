@@ -7,6 +7,7 @@ pub mod l2_blocks;
 pub(crate) mod logs;
 pub mod overhead;
 pub(crate) mod refund;
+pub mod state_snapshot;
 pub mod transaction_encoding;
 pub mod v26_upgrade;
 
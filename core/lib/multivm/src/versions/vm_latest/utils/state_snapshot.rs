@@ -0,0 +1,87 @@
+//! Capturing serializable VM state snapshots.
+
+use std::collections::BTreeMap;
+
+use zk_evm_1_5_0::vm_state::PrimitiveValue;
+use zksync_types::{u256_to_h256, U256};
+
+use crate::{
+    interface::{
+        storage::WriteStorage,
+        utils::{CallFrameSnapshot, VmFlagsSnapshot, VmStateSnapshot, VmWordSnapshot},
+    },
+    vm_latest::{HistoryMode, ZkSyncVmState},
+};
+
+fn word_snapshot(value: &PrimitiveValue) -> VmWordSnapshot {
+    VmWordSnapshot {
+        value: value.value,
+        is_pointer: value.is_pointer,
+    }
+}
+
+/// Captures a snapshot of the full VM state. Can be called at arbitrary points of execution, e.g. from a custom tracer.
+pub fn capture_state_snapshot<S: WriteStorage, H: HistoryMode>(
+    state: &ZkSyncVmState<S, H>,
+) -> VmStateSnapshot {
+    let local_state = &state.local_state;
+    let callstack = &local_state.callstack;
+    let callstack = callstack
+        .inner
+        .iter()
+        .chain([&callstack.current])
+        .map(|frame| CallFrameSnapshot {
+            this_address: frame.this_address,
+            msg_sender: frame.msg_sender,
+            code_address: frame.code_address,
+            base_memory_page: frame.base_memory_page.0,
+            code_page: frame.code_page.0,
+            pc: frame.pc,
+            sp: frame.sp,
+            ergs_remaining: frame.ergs_remaining,
+            context_value: U256::from(frame.context_u128_value),
+            is_static: frame.is_static,
+            is_local_frame: frame.is_local_frame,
+        })
+        .collect();
+
+    let mut memory = BTreeMap::<_, BTreeMap<_, _>>::new();
+    for (page, slot, value) in state.memory.non_empty_words() {
+        memory
+            .entry(page as u32)
+            .or_default()
+            .insert(slot as u32, word_snapshot(&value));
+    }
+
+    let mut decommitted_code_hashes: Vec<_> = state
+        .decommittment_processor
+        .get_decommitted_code_hashes_with_history()
+        .inner()
+        .iter()
+        .filter(|(_, page)| page.is_some())
+        .map(|(hash, _)| u256_to_h256(*hash))
+        .collect();
+    decommitted_code_hashes.sort_unstable();
+
+    VmStateSnapshot {
+        timestamp: local_state.timestamp,
+        monotonic_cycle_counter: local_state.monotonic_cycle_counter,
+        registers: local_state.registers.iter().map(word_snapshot).collect(),
+        flags: VmFlagsSnapshot {
+            overflow_or_less_than: local_state.flags.overflow_or_less_than_flag,
+            equal: local_state.flags.equality_flag,
+            greater_than: local_state.flags.greater_than_flag,
+        },
+        callstack,
+        memory,
+        storage: state.storage.modified_slots_snapshot(),
+        transient_storage: state.storage.transient_slots_snapshot(),
+        log_queries: state.event_sink.log_query_snapshots(),
+        decommitted_code_hashes,
+        precompile_calls: state
+            .precompiles_processor
+            .precompile_cycles_history
+            .inner()
+            .len(),
+    }
+}
@@ -14,6 +14,7 @@ use crate::{
     glue::GlueInto,
     interface::{
         storage::{StoragePtr, WriteStorage},
+        utils::VmStateSnapshot,
         BytecodeCompressionError, BytecodeCompressionResult, CurrentExecutionState,
         FinishedL1Batch, L1BatchEnv, L2BlockEnv, PushTransactionResult, SystemEnv, VmExecutionMode,
         VmExecutionResultAndLogs, VmFactory, VmInterface, VmInterfaceHistoryEnabled,
@@ -24,7 +25,8 @@ use crate::{
         bootloader::BootloaderState,
         old_vm::{events::merge_events, history_recorder::HistoryEnabled},
         tracers::{dispatcher::TracerDispatcher, PubdataTracer},
-        types::{new_vm_state, VmSnapshot, ZkSyncVmState},
+        types::{new_vm_state, VmSnapshot, WarmVmState, ZkSyncVmState},
+        utils::state_snapshot::capture_state_snapshot,
     },
     HistoryMode,
 };
@@ -230,6 +232,7 @@ impl<S: WriteStorage, H: HistoryMode> VmInterface for Vm<S, H> {
                     .state_diffs
                     .clone(),
             ),
+            precompile_inputs: self.state.precompiles_processor.hash_inputs(),
         }
     }
 }
@@ -253,8 +256,26 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
         storage: StoragePtr<S>,
         subversion: MultiVmSubversion,
     ) -> Self {
-        let (state, bootloader_state) =
-            new_vm_state(storage.clone(), &system_env, &batch_env, subversion);
+        let warm_state = WarmVmState::new(&system_env.base_system_smart_contracts);
+        Self::new_with_warm_state(batch_env, system_env, storage, subversion, warm_state)
+    }
+
+    /// Creates a VM using the provided pre-initialized state, which must correspond to the base system contracts
+    /// in `system_env`.
+    pub(crate) fn new_with_warm_state(
+        batch_env: L1BatchEnv,
+        system_env: SystemEnv,
+        storage: StoragePtr<S>,
+        subversion: MultiVmSubversion,
+        warm_state: WarmVmState<H::Vm1_5_0>,
+    ) -> Self {
+        let (state, bootloader_state) = new_vm_state(
+            storage.clone(),
+            &system_env,
+            &batch_env,
+            subversion,
+            warm_state,
+        );
         Self {
             bootloader_state,
             state,
@@ -266,6 +287,21 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
             _phantom: Default::default(),
         }
     }
+
+    /// Enables recording inputs of hashing precompile calls, which are then returned in [`FinishedL1Batch`].
+    /// Recording is only necessary for witness generation and is disabled by default. Should be called before
+    /// any transactions are executed.
+    pub fn record_precompile_inputs(&mut self) {
+        self.state
+            .precompiles_processor
+            .enable_hash_inputs_recording();
+    }
+
+    /// Captures a snapshot of the full VM state (registers, callstack, memory and oracles). Snapshots captured
+    /// at different points or by different VM builds can be compared using [`VmStateSnapshot::diff()`].
+    pub fn state_snapshot(&self) -> VmStateSnapshot {
+        capture_state_snapshot(&self.state)
+    }
 }
 
 impl<S: WriteStorage> VmInterfaceHistoryEnabled for Vm<S, HistoryEnabled> {
@@ -140,6 +140,7 @@ impl<S: WriteStorage, H: HistoryMode> VmInterface for Vm<S, H> {
             final_bootloader_memory: Some(bootloader_memory),
             pubdata_input: None,
             state_diffs: None,
+            precompile_inputs: None,
         }
     }
 }
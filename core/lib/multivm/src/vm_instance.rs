@@ -228,6 +228,51 @@ impl<S: ReadStorage, H: HistoryMode> LegacyVmInstance<S, H> {
         }
     }
 
+    /// Creates a VM using the provided pre-initialized state. The state is only used by VM versions based on
+    /// [`vm_latest`] and is dropped for older versions; it must correspond to the base system contracts in `system_env`.
+    pub fn new_with_warm_state(
+        l1_batch_env: L1BatchEnv,
+        system_env: SystemEnv,
+        storage_view: StoragePtr<StorageView<S>>,
+        vm_version: VmVersion,
+        warm_state: vm_latest::WarmVmState<H::Vm1_5_0>,
+    ) -> Self {
+        let subversion = match vm_version {
+            VmVersion::Vm1_5_0SmallBootloaderMemory => {
+                vm_latest::MultiVmSubversion::SmallBootloaderMemory
+            }
+            VmVersion::Vm1_5_0IncreasedBootloaderMemory => {
+                vm_latest::MultiVmSubversion::IncreasedBootloaderMemory
+            }
+            VmVersion::VmGateway => vm_latest::MultiVmSubversion::Gateway,
+            VmVersion::VmEvmEmulator => vm_latest::MultiVmSubversion::EvmEmulator,
+            _ => {
+                return Self::new_with_specific_version(
+                    l1_batch_env,
+                    system_env,
+                    storage_view,
+                    vm_version,
+                );
+            }
+        };
+        let vm = vm_latest::Vm::new_with_warm_state(
+            l1_batch_env,
+            system_env,
+            storage_view,
+            subversion,
+            warm_state,
+        );
+        Self::Vm1_5_0(vm)
+    }
+
+    /// Enables recording inputs of hashing precompile calls. Only supported by VM versions based on [`vm_latest`];
+    /// no-op for older versions.
+    pub fn record_precompile_inputs(&mut self) {
+        if let Self::Vm1_5_0(vm) = self {
+            vm.record_precompile_inputs();
+        }
+    }
+
     /// Returns memory-related oracle metrics.
     pub fn record_vm_memory_metrics(&self) -> VmMemoryMetrics {
         dispatch_legacy_vm!(self.record_vm_memory_metrics())
@@ -0,0 +1,154 @@
+//! Parquet encoding of cold storage archives. Parquet is used so that archives can be inspected and queried
+//! by standard analytics tooling without going through the node.
+
+use std::sync::Arc;
+
+use arrow_array::{Array, BinaryArray, FixedSizeBinaryArray, RecordBatch, UInt32Array};
+use arrow_schema::{DataType, Field, Schema};
+use bytes::Bytes;
+use parquet::{
+    arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter},
+    basic::{Compression, ZstdLevel},
+    file::properties::WriterProperties,
+    format::KeyValue,
+};
+use zksync_types::{
+    cold_storage::{ArchivedCallTrace, ArchivedEvent, CallTracesArchive, EventsArchive},
+    L1BatchNumber, L2BlockNumber, H256,
+};
+
+use crate::raw::BoxedError;
+
+/// Key of the file metadata entry holding the archived L1 batch number. The number is stored in the metadata
+/// rather than in a column so that it's available for archives without rows.
+const L1_BATCH_NUMBER_KEY: &str = "l1_batch_number";
+
+fn write_parquet(
+    l1_batch_number: L1BatchNumber,
+    batch: &RecordBatch,
+) -> Result<Vec<u8>, BoxedError> {
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::ZSTD(ZstdLevel::default()))
+        .set_key_value_metadata(Some(vec![KeyValue::new(
+            L1_BATCH_NUMBER_KEY.to_owned(),
+            l1_batch_number.0.to_string(),
+        )]))
+        .build();
+    let mut writer = ArrowWriter::try_new(Vec::new(), batch.schema(), Some(properties))?;
+    writer.write(batch)?;
+    writer.into_inner().map_err(From::from)
+}
+
+fn read_parquet(bytes: Vec<u8>) -> Result<(L1BatchNumber, Vec<RecordBatch>), BoxedError> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(bytes))?;
+    let l1_batch_number = builder
+        .metadata()
+        .file_metadata()
+        .key_value_metadata()
+        .into_iter()
+        .flatten()
+        .find(|entry| entry.key == L1_BATCH_NUMBER_KEY)
+        .and_then(|entry| entry.value.as_deref())
+        .ok_or("archive metadata doesn't contain L1 batch number")?
+        .parse()?;
+    let batches = builder.build()?.collect::<Result<_, _>>()?;
+    Ok((L1BatchNumber(l1_batch_number), batches))
+}
+
+fn column<'a, T: Array + 'static>(batch: &'a RecordBatch, name: &str) -> Result<&'a T, BoxedError> {
+    batch
+        .column_by_name(name)
+        .and_then(|column| column.as_any().downcast_ref::<T>())
+        .ok_or_else(|| format!("archive has missing or malformed column `{name}`").into())
+}
+
+pub(crate) fn serialize_call_traces(archive: &CallTracesArchive) -> Result<Vec<u8>, BoxedError> {
+    let schema = Schema::new(vec![
+        Field::new("tx_hash", DataType::FixedSizeBinary(32), false),
+        Field::new("call_trace", DataType::Binary, false),
+    ]);
+    let tx_hashes = FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+        archive
+            .call_traces
+            .iter()
+            .map(|trace| Some(trace.tx_hash.as_bytes())),
+        32,
+    )?;
+    let call_traces = BinaryArray::from_iter_values(
+        archive
+            .call_traces
+            .iter()
+            .map(|trace| trace.call_trace.as_slice()),
+    );
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![Arc::new(tx_hashes), Arc::new(call_traces)],
+    )?;
+    write_parquet(archive.l1_batch_number, &batch)
+}
+
+pub(crate) fn deserialize_call_traces(bytes: Vec<u8>) -> Result<CallTracesArchive, BoxedError> {
+    let (l1_batch_number, batches) = read_parquet(bytes)?;
+    let mut call_traces = vec![];
+    for batch in &batches {
+        let tx_hashes: &FixedSizeBinaryArray = column(batch, "tx_hash")?;
+        if tx_hashes.value_length() != 32 {
+            return Err("archive has malformed column `tx_hash`".into());
+        }
+        let traces: &BinaryArray = column(batch, "call_trace")?;
+        call_traces.extend((0..batch.num_rows()).map(|i| ArchivedCallTrace {
+            tx_hash: H256::from_slice(tx_hashes.value(i)),
+            call_trace: traces.value(i).to_vec(),
+        }));
+    }
+    Ok(CallTracesArchive {
+        l1_batch_number,
+        call_traces,
+    })
+}
+
+pub(crate) fn serialize_events(archive: &EventsArchive) -> Result<Vec<u8>, BoxedError> {
+    let schema = Schema::new(vec![
+        Field::new("l2_block_number", DataType::UInt32, false),
+        Field::new("event_index_in_block", DataType::UInt32, false),
+        Field::new("value", DataType::Binary, false),
+    ]);
+    let l2_block_numbers =
+        UInt32Array::from_iter_values(archive.events.iter().map(|event| event.l2_block_number.0));
+    let indices = UInt32Array::from_iter_values(
+        archive
+            .events
+            .iter()
+            .map(|event| event.event_index_in_block),
+    );
+    let values =
+        BinaryArray::from_iter_values(archive.events.iter().map(|event| event.value.as_slice()));
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(l2_block_numbers),
+            Arc::new(indices),
+            Arc::new(values),
+        ],
+    )?;
+    write_parquet(archive.l1_batch_number, &batch)
+}
+
+pub(crate) fn deserialize_events(bytes: Vec<u8>) -> Result<EventsArchive, BoxedError> {
+    let (l1_batch_number, batches) = read_parquet(bytes)?;
+    let mut events = vec![];
+    for batch in &batches {
+        let l2_block_numbers: &UInt32Array = column(batch, "l2_block_number")?;
+        let indices: &UInt32Array = column(batch, "event_index_in_block")?;
+        let values: &BinaryArray = column(batch, "value")?;
+        events.extend((0..batch.num_rows()).map(|i| ArchivedEvent {
+            l2_block_number: L2BlockNumber(l2_block_numbers.value(i)),
+            event_index_in_block: indices.value(i),
+            value: values.value(i).to_vec(),
+        }));
+    }
+    Ok(EventsArchive {
+        l1_batch_number,
+        events,
+    })
+}
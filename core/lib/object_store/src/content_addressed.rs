@@ -0,0 +1,261 @@
+//! Content-addressed object store.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use zksync_config::configs::object_store::ContentAddressingMode;
+
+use crate::{
+    metrics::{ContentAddressingEvent, CONTENT_ADDRESSING_METRICS},
+    raw::{Bucket, ObjectStore, ObjectStoreError},
+};
+
+/// Prefix of pointer objects stored under logical keys.
+const POINTER_PREFIX: &[u8] = b"zksync-content-address:sha256:";
+/// Length of a hex-encoded SHA-256 digest.
+const DIGEST_HEX_LEN: usize = 64;
+
+type ContentDigest = [u8; 32];
+
+fn content_digest(value: &[u8]) -> ContentDigest {
+    Sha256::digest(value).into()
+}
+
+fn content_key(digest: &ContentDigest) -> String {
+    format!("content_sha256_{}", hex::encode(digest))
+}
+
+fn encode_pointer(digest: &ContentDigest) -> Vec<u8> {
+    let mut pointer = POINTER_PREFIX.to_vec();
+    pointer.extend_from_slice(hex::encode(digest).as_bytes());
+    pointer
+}
+
+/// Parses a pointer object. Returns `None` if the object is not a pointer (i.e., it's a legacy object
+/// stored under its logical key).
+fn decode_pointer(object: &[u8]) -> Option<ContentDigest> {
+    if object.len() != POINTER_PREFIX.len() + DIGEST_HEX_LEN {
+        return None;
+    }
+    let hex_digest = object.strip_prefix(POINTER_PREFIX)?;
+    let mut digest = ContentDigest::default();
+    hex::decode_to_slice(hex_digest, &mut digest).ok()?;
+    Some(digest)
+}
+
+/// [`ObjectStore`] wrapper storing payloads under keys derived from their SHA-256 digest.
+///
+/// A logical key (i.e., the key supplied to the store methods) is associated with a small pointer object containing
+/// the payload digest. On each read, the payload digest is verified against the pointer, so that a corrupted payload
+/// results in an error rather than being returned. Identical payloads are stored only once.
+///
+/// Removing an object only removes the pointer, since the payload may be shared with other logical keys.
+#[derive(Debug)]
+pub(crate) struct ContentAddressedObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    mode: ContentAddressingMode,
+}
+
+impl ContentAddressedObjectStore {
+    pub fn new(inner: Arc<dyn ObjectStore>, mode: ContentAddressingMode) -> Self {
+        assert_ne!(
+            mode,
+            ContentAddressingMode::Disabled,
+            "content-addressed store must not be created with disabled content addressing"
+        );
+        tracing::info!("Initializing content addressing for store {inner:?} in mode {mode:?}");
+        Self { inner, mode }
+    }
+
+    async fn put_content(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        let digest = content_digest(&value);
+        self.inner
+            .put_raw(bucket, &content_key(&digest), value)
+            .await?;
+        // Only put the pointer once the payload is stored, so that pointers are never dangling.
+        self.inner
+            .put_raw(bucket, key, encode_pointer(&digest))
+            .await
+    }
+
+    async fn get_legacy(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        object: Vec<u8>,
+    ) -> Result<Vec<u8>, ObjectStoreError> {
+        if self.mode == ContentAddressingMode::Strict {
+            CONTENT_ADDRESSING_METRICS.observe(bucket, ContentAddressingEvent::LegacyRejected);
+            return Err(ObjectStoreError::Other {
+                source: format!(
+                    "object `{key}` in bucket {bucket} is not content-addressed, which is disallowed in strict mode"
+                )
+                .into(),
+                is_retriable: false,
+            });
+        }
+
+        CONTENT_ADDRESSING_METRICS.observe(bucket, ContentAddressingEvent::LegacyRead);
+        if let Err(err) = self.put_content(bucket, key, object.clone()).await {
+            tracing::warn!(
+                "Failed migrating object `{key}` in bucket {bucket} to content-addressed form: {:#}",
+                anyhow::Error::from(err)
+            );
+        } else {
+            tracing::debug!("Migrated object `{key}` in bucket {bucket} to content-addressed form");
+            CONTENT_ADDRESSING_METRICS.observe(bucket, ContentAddressingEvent::Migrated);
+        }
+        Ok(object)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for ContentAddressedObjectStore {
+    #[tracing::instrument(name = "ContentAddressedObjectStore::get_raw", skip(self))]
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let object = self.inner.get_raw(bucket, key).await?;
+        let Some(expected_digest) = decode_pointer(&object) else {
+            return self.get_legacy(bucket, key, object).await;
+        };
+
+        let content_key = content_key(&expected_digest);
+        let value = self.inner.get_raw(bucket, &content_key).await?;
+        let actual_digest = content_digest(&value);
+        if actual_digest != expected_digest {
+            CONTENT_ADDRESSING_METRICS.observe(bucket, ContentAddressingEvent::DigestMismatch);
+            return Err(ObjectStoreError::Other {
+                source: format!(
+                    "integrity check failed for object `{key}` in bucket {bucket}: expected SHA-256 digest {}, got {}",
+                    hex::encode(expected_digest),
+                    hex::encode(actual_digest)
+                )
+                .into(),
+                is_retriable: false,
+            });
+        }
+        CONTENT_ADDRESSING_METRICS.observe(bucket, ContentAddressingEvent::Verified);
+        Ok(value)
+    }
+
+    #[tracing::instrument(
+        name = "ContentAddressedObjectStore::put_raw",
+        skip(self, value),
+        fields(value.len = value.len())
+    )]
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        self.put_content(bucket, key, value).await
+    }
+
+    #[tracing::instrument(name = "ContentAddressedObjectStore::remove_raw", skip(self))]
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        self.inner.remove_raw(bucket, key).await
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.inner.storage_prefix_raw(bucket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::MockObjectStore;
+
+    #[test]
+    fn pointer_roundtrip() {
+        let digest = content_digest(b"test");
+        let pointer = encode_pointer(&digest);
+        assert_eq!(decode_pointer(&pointer), Some(digest));
+
+        assert_eq!(decode_pointer(b"test"), None);
+        let mut invalid_pointer = pointer.clone();
+        *invalid_pointer.last_mut().unwrap() = b'x';
+        assert_eq!(decode_pointer(&invalid_pointer), None);
+    }
+
+    #[tokio::test]
+    async fn content_addressed_store_basics() {
+        let inner = MockObjectStore::arc();
+        let store = ContentAddressedObjectStore::new(inner.clone(), ContentAddressingMode::Enabled);
+        store
+            .put_raw(Bucket::WitnessInput, "first", vec![1, 2, 3])
+            .await
+            .unwrap();
+        store
+            .put_raw(Bucket::WitnessInput, "second", vec![1, 2, 3])
+            .await
+            .unwrap();
+
+        let object = store.get_raw(Bucket::WitnessInput, "first").await.unwrap();
+        assert_eq!(object, [1, 2, 3]);
+        let pointer = inner.get_raw(Bucket::WitnessInput, "first").await.unwrap();
+        let digest = decode_pointer(&pointer).unwrap();
+        let second_pointer = inner.get_raw(Bucket::WitnessInput, "second").await.unwrap();
+        assert_eq!(pointer, second_pointer);
+
+        // Emulate payload corruption.
+        inner
+            .put_raw(Bucket::WitnessInput, &content_key(&digest), vec![1, 2, 4])
+            .await
+            .unwrap();
+        let err = store
+            .get_raw(Bucket::WitnessInput, "second")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("integrity check failed"), "{err}");
+        assert!(!err.is_retriable());
+
+        store
+            .remove_raw(Bucket::WitnessInput, "first")
+            .await
+            .unwrap();
+        let err = store
+            .get_raw(Bucket::WitnessInput, "first")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+    }
+
+    #[tokio::test]
+    async fn legacy_objects_are_migrated() {
+        let inner = MockObjectStore::arc();
+        inner
+            .put_raw(Bucket::ProofsFri, "legacy", vec![3, 2, 1])
+            .await
+            .unwrap();
+
+        let strict_store =
+            ContentAddressedObjectStore::new(inner.clone(), ContentAddressingMode::Strict);
+        let err = strict_store
+            .get_raw(Bucket::ProofsFri, "legacy")
+            .await
+            .unwrap_err();
+        assert!(!err.is_retriable());
+
+        let store = ContentAddressedObjectStore::new(inner.clone(), ContentAddressingMode::Enabled);
+        let object = store.get_raw(Bucket::ProofsFri, "legacy").await.unwrap();
+        assert_eq!(object, [3, 2, 1]);
+        let pointer = inner.get_raw(Bucket::ProofsFri, "legacy").await.unwrap();
+        assert_eq!(decode_pointer(&pointer), Some(content_digest(&[3, 2, 1])));
+
+        // After migration, the object should be readable in the strict mode.
+        let object = strict_store
+            .get_raw(Bucket::ProofsFri, "legacy")
+            .await
+            .unwrap();
+        assert_eq!(object, [3, 2, 1]);
+    }
+}
@@ -2,15 +2,18 @@ use std::sync::Arc;
 
 use anyhow::Context as _;
 use tokio::sync::OnceCell;
-use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
+use zksync_config::configs::object_store::{
+    ContentAddressingMode, ObjectStoreConfig, ObjectStoreMode,
+};
 
 use crate::{
+    content_addressed::ContentAddressedObjectStore,
     file::FileBackedObjectStore,
     gcs::{GoogleCloudStore, GoogleCloudStoreAuthMode},
     mirror::MirroringObjectStore,
     raw::{ObjectStore, ObjectStoreError},
     retries::StoreWithRetries,
-    s3::{S3Store, S3StoreAuthMode},
+    s3::{S3Store, S3StoreAuthMode, S3UploadConfig},
 };
 
 /// Factory of [`ObjectStore`]s that caches the store instance once it's created. Used mainly for legacy reasons.
@@ -62,6 +65,16 @@ impl ObjectStoreFactory {
         config: &ObjectStoreConfig,
     ) -> Result<Arc<dyn ObjectStore>, ObjectStoreError> {
         tracing::trace!("Initializing object store with configuration {config:?}");
+        let store = Self::create_base_store(config).await?;
+        Ok(match config.content_addressing {
+            ContentAddressingMode::Disabled => store,
+            mode => Arc::new(ContentAddressedObjectStore::new(store, mode)),
+        })
+    }
+
+    async fn create_base_store(
+        config: &ObjectStoreConfig,
+    ) -> Result<Arc<dyn ObjectStore>, ObjectStoreError> {
         match &config.mode {
             ObjectStoreMode::GCS { bucket_base_url } => {
                 let store = StoreWithRetries::try_new(config.max_retries, || {
@@ -104,7 +117,20 @@ impl ObjectStoreFactory {
                 s3_credential_file_path,
                 endpoint,
                 region,
+                multipart_part_size_mb,
+                server_side_encryption,
+                sse_kms_key_id,
             } => {
+                let mut upload_config = S3UploadConfig {
+                    server_side_encryption: server_side_encryption.clone(),
+                    sse_kms_key_id: sse_kms_key_id.clone(),
+                    ..S3UploadConfig::default()
+                };
+                if let Some(part_size_mb) = multipart_part_size_mb {
+                    upload_config.multipart_part_size = usize::try_from(*part_size_mb << 20)
+                        .context("multipart part size is too large")?;
+                }
+
                 let store = StoreWithRetries::try_new(config.max_retries, || {
                     S3Store::new(
                         S3StoreAuthMode::AuthenticatedWithCredentialFile(
@@ -113,6 +139,7 @@ impl ObjectStoreFactory {
                         bucket_base_url.clone(),
                         endpoint.clone(),
                         region.clone(),
+                        upload_config.clone(),
                     )
                 })
                 .await?;
@@ -129,6 +156,7 @@ impl ObjectStoreFactory {
                         bucket_base_url.clone(),
                         endpoint.clone(),
                         region.clone(),
+                        S3UploadConfig::default(),
                     )
                 })
                 .await?;
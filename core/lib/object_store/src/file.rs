@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, path::Path};
 
 use async_trait::async_trait;
 use tokio::{fs, io};
@@ -43,6 +43,7 @@ impl FileBackedObjectStore {
             Bucket::ProofsFri,
             Bucket::StorageSnapshot,
             Bucket::VmDumps,
+            Bucket::ColdStorage,
         ] {
             let bucket_path = format!("{base_dir}/{bucket}");
             fs::create_dir_all(&bucket_path).await?;
@@ -69,6 +70,10 @@ impl ObjectStore for FileBackedObjectStore {
         value: Vec<u8>,
     ) -> Result<(), ObjectStoreError> {
         let filename = self.filename(bucket, key);
+        // Keys may contain path separators (e.g., if they are prefixed), so we need to create the parent dir if necessary.
+        if let Some(parent) = Path::new(&filename).parent() {
+            fs::create_dir_all(parent).await?;
+        }
         fs::write(filename, value).await.map_err(From::from)
     }
 
@@ -5,6 +5,10 @@
 //! - [GCS-based store](GoogleCloudStore)
 //! - [Mock in-memory store](MockObjectStore)
 //!
+//! Any of these stores can be switched to the content-addressed mode, in which objects are stored under keys derived from
+//! their SHA-256 digest, and each read verifies the object integrity.
+//! A store can also be [shared](PrefixedObjectStore) among multiple tenants by prefixing keys of each tenant.
+//!
 //! Normally, these implementations are not used directly. Instead, a store trait object (`Arc<dyn ObjectStore>`)
 //! can be constructed using an [`ObjectStoreFactory`] based on the configuration.
 //! This trait object is what should be used for dependency injection.
@@ -23,6 +27,8 @@
     clippy::doc_markdown
 )]
 
+mod cold_storage;
+mod content_addressed;
 mod factory;
 mod file;
 mod gcs;
@@ -30,6 +36,7 @@ mod metrics;
 mod mirror;
 mod mock;
 mod objects;
+mod prefixed;
 mod raw;
 mod retries;
 mod s3;
@@ -48,5 +55,6 @@ pub use self::{
     gcs::{GoogleCloudStore, GoogleCloudStoreAuthMode},
     mock::MockObjectStore,
     objects::StoredObject,
+    prefixed::PrefixedObjectStore,
     raw::{Bucket, ObjectStore, ObjectStoreError},
 };
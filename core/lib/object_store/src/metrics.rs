@@ -2,7 +2,10 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, Histogram, LabeledFamily, LatencyObserver, Metrics};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, LabeledFamily,
+    LatencyObserver, Metrics,
+};
 
 use crate::Bucket;
 
@@ -29,3 +32,45 @@ impl ObjectStoreMetrics {
 
 #[vise::register]
 pub(crate) static OBJECT_STORE_METRICS: vise::Global<ObjectStoreMetrics> = vise::Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(crate) enum ContentAddressingEvent {
+    /// Content-addressed object was read and successfully verified.
+    Verified,
+    /// Content-addressed object failed integrity verification.
+    DigestMismatch,
+    /// Legacy (not content-addressed) object was read.
+    LegacyRead,
+    /// Legacy object was migrated to the content-addressed form.
+    Migrated,
+    /// Legacy object was rejected in the strict mode.
+    LegacyRejected,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
+struct ContentAddressingLabels {
+    bucket: &'static str,
+    event: ContentAddressingEvent,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_object_store_content_addressing")]
+pub(crate) struct ContentAddressingMetrics {
+    /// Number of content addressing events grouped by the bucket and event kind.
+    events: Family<ContentAddressingLabels, Counter>,
+}
+
+impl ContentAddressingMetrics {
+    pub fn observe(&self, bucket: Bucket, event: ContentAddressingEvent) {
+        let labels = ContentAddressingLabels {
+            bucket: bucket.as_str(),
+            event,
+        };
+        self.events[&labels].inc();
+    }
+}
+
+#[vise::register]
+pub(crate) static CONTENT_ADDRESSING_METRICS: vise::Global<ContentAddressingMetrics> =
+    vise::Global::new();
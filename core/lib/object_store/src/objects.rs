@@ -7,13 +7,17 @@ use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use prost::Message;
 use zksync_protobuf::{decode, ProtoFmt};
 use zksync_types::{
+    cold_storage::{CallTracesArchive, EventsArchive},
     snapshots::{
         SnapshotFactoryDependencies, SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
     },
     L1BatchNumber,
 };
 
-use crate::raw::{BoxedError, Bucket, ObjectStore, ObjectStoreError};
+use crate::{
+    cold_storage,
+    raw::{BoxedError, Bucket, ObjectStore, ObjectStoreError},
+};
 
 /// Object that can be stored in an [`ObjectStore`].
 pub trait StoredObject: Sized {
@@ -120,6 +124,40 @@ where
     }
 }
 
+impl StoredObject for CallTracesArchive {
+    const BUCKET: Bucket = Bucket::ColdStorage;
+    type Key<'a> = L1BatchNumber;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!("l1_batch_{key}_call_traces.parquet")
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, BoxedError> {
+        cold_storage::serialize_call_traces(self)
+    }
+
+    fn deserialize(bytes: Vec<u8>) -> Result<Self, BoxedError> {
+        cold_storage::deserialize_call_traces(bytes)
+    }
+}
+
+impl StoredObject for EventsArchive {
+    const BUCKET: Bucket = Bucket::ColdStorage;
+    type Key<'a> = L1BatchNumber;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!("l1_batch_{key}_events.parquet")
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, BoxedError> {
+        cold_storage::serialize_events(self)
+    }
+
+    fn deserialize(bytes: Vec<u8>) -> Result<Self, BoxedError> {
+        cold_storage::deserialize_events(bytes)
+    }
+}
+
 impl dyn ObjectStore + '_ {
     /// Fetches the value for the given key if it exists.
     ///
@@ -208,9 +246,10 @@ impl dyn ObjectStore + '_ {
 #[cfg(test)]
 mod tests {
     use zksync_types::{
+        cold_storage::{ArchivedCallTrace, ArchivedEvent},
         snapshots::{SnapshotFactoryDependency, SnapshotStorageLog},
         web3::Bytes,
-        H256,
+        L2BlockNumber, H256,
     };
 
     use super::*;
@@ -292,4 +331,72 @@ mod tests {
         let reconstructed_factory_deps = store.get(key).await.unwrap();
         assert_eq!(factory_deps, reconstructed_factory_deps);
     }
+
+    #[tokio::test]
+    async fn cold_storage_archives_can_be_serialized_and_deserialized() {
+        let store = MockObjectStore::arc();
+        let key = L1BatchNumber(42);
+        let call_traces = CallTracesArchive {
+            l1_batch_number: key,
+            call_traces: vec![ArchivedCallTrace {
+                tx_hash: H256::repeat_byte(1),
+                call_trace: vec![1, 2, 3],
+            }],
+        };
+        let events = EventsArchive {
+            l1_batch_number: key,
+            events: vec![
+                ArchivedEvent {
+                    l2_block_number: L2BlockNumber(100),
+                    event_index_in_block: 0,
+                    value: vec![4, 5],
+                },
+                ArchivedEvent {
+                    l2_block_number: L2BlockNumber(101),
+                    event_index_in_block: 3,
+                    value: vec![],
+                },
+            ],
+        };
+
+        let call_traces_key = store.put(key, &call_traces).await.unwrap();
+        let events_key = store.put(key, &events).await.unwrap();
+        assert_eq!(call_traces_key, "l1_batch_42_call_traces.parquet");
+        assert_eq!(events_key, "l1_batch_42_events.parquet");
+
+        let restored_call_traces: CallTracesArchive = store.get(key).await.unwrap();
+        assert_eq!(restored_call_traces, call_traces);
+        assert_eq!(
+            restored_call_traces.get(H256::repeat_byte(1)),
+            Some([1, 2, 3].as_slice())
+        );
+        let restored_events: EventsArchive = store.get(key).await.unwrap();
+        assert_eq!(restored_events, events);
+        assert_eq!(
+            restored_events.get(L2BlockNumber(100), 0),
+            Some([4, 5].as_slice())
+        );
+        assert_eq!(restored_events.get(L2BlockNumber(100), 1), None);
+    }
+
+    #[tokio::test]
+    async fn empty_cold_storage_archives_can_be_serialized_and_deserialized() {
+        let store = MockObjectStore::arc();
+        let key = L1BatchNumber(42);
+        let call_traces = CallTracesArchive {
+            l1_batch_number: key,
+            call_traces: vec![],
+        };
+        let events = EventsArchive {
+            l1_batch_number: key,
+            events: vec![],
+        };
+        store.put(key, &call_traces).await.unwrap();
+        store.put(key, &events).await.unwrap();
+
+        let restored_call_traces: CallTracesArchive = store.get(key).await.unwrap();
+        assert_eq!(restored_call_traces, call_traces);
+        let restored_events: EventsArchive = store.get(key).await.unwrap();
+        assert_eq!(restored_events, events);
+    }
 }
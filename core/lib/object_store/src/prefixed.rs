@@ -0,0 +1,107 @@
+//! Object store with prefixed keys.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+
+/// [`ObjectStore`] wrapper that prepends a fixed prefix to all keys, so that objects are stored as `{prefix}/{key}`
+/// in each bucket of the underlying store.
+///
+/// This allows multiple tenants (e.g., multiple chains served by a single node) to share a store
+/// without key collisions.
+#[derive(Debug)]
+pub struct PrefixedObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    prefix: String,
+}
+
+impl PrefixedObjectStore {
+    /// Wraps the provided store. `prefix` must be non-empty and must not contain `/` chars.
+    pub fn new(inner: Arc<dyn ObjectStore>, prefix: impl Into<String>) -> Self {
+        let prefix = prefix.into();
+        assert!(
+            !prefix.is_empty() && !prefix.contains('/'),
+            "invalid object store key prefix: {prefix:?}"
+        );
+        Self { inner, prefix }
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}/{key}", self.prefix)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for PrefixedObjectStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        self.inner.get_raw(bucket, &self.key(key)).await
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        self.inner.put_raw(bucket, &self.key(key), value).await
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        self.inner.remove_raw(bucket, &self.key(key)).await
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        format!("{}/{}", self.inner.storage_prefix_raw(bucket), self.prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::MockObjectStore;
+
+    #[tokio::test]
+    async fn prefixed_stores_do_not_collide() {
+        let inner = MockObjectStore::arc();
+        let first_store = PrefixedObjectStore::new(inner.clone(), "chain_270");
+        let second_store = PrefixedObjectStore::new(inner.clone(), "chain_271");
+
+        first_store
+            .put_raw(Bucket::WitnessInput, "test", vec![1, 2, 3])
+            .await
+            .unwrap();
+        second_store
+            .put_raw(Bucket::WitnessInput, "test", vec![4, 5])
+            .await
+            .unwrap();
+
+        let object = first_store
+            .get_raw(Bucket::WitnessInput, "test")
+            .await
+            .unwrap();
+        assert_eq!(object, [1, 2, 3]);
+        let object = inner
+            .get_raw(Bucket::WitnessInput, "chain_271/test")
+            .await
+            .unwrap();
+        assert_eq!(object, [4, 5]);
+        let err = inner
+            .get_raw(Bucket::WitnessInput, "test")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+
+        second_store
+            .remove_raw(Bucket::WitnessInput, "test")
+            .await
+            .unwrap();
+        first_store
+            .get_raw(Bucket::WitnessInput, "test")
+            .await
+            .unwrap();
+    }
+}
@@ -1,21 +1,146 @@
 //! S3-based [`ObjectStore`] implementation.
 
-use std::fmt;
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::Mutex,
+};
 
 use anyhow::Context;
 use async_trait::async_trait;
 use aws_config::{meta::region::RegionProviderChain, BehaviorVersion, ConfigLoader, Region};
 use aws_runtime::env_config::file::{EnvConfigFileKind, EnvConfigFiles};
-use aws_sdk_s3::{error::SdkError, primitives::ByteStreamError, Client};
+use aws_sdk_s3::{
+    error::SdkError,
+    primitives::{ByteStream, ByteStreamError},
+    types::{CompletedMultipartUpload, CompletedPart, ServerSideEncryption},
+    Client,
+};
 use http::StatusCode;
 
 use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
 
-/// [`ObjectStore`] implementation based on AWS S3.
+/// Minimum size of a part in a multipart upload (except for the last part) allowed by S3.
+const MIN_MULTIPART_PART_SIZE: usize = 5 << 20;
+/// Maximum number of parts in a multipart upload allowed by S3.
+const MAX_MULTIPART_PARTS: usize = 10_000;
+
+/// Upload settings for [`S3Store`].
+#[derive(Debug, Clone)]
+pub struct S3UploadConfig {
+    /// Size of a single part in multipart uploads. Objects not exceeding this size are uploaded with a single request.
+    pub multipart_part_size: usize,
+    /// Server-side encryption algorithm set for uploaded objects (e.g., `AES256` or `aws:kms`).
+    pub server_side_encryption: Option<String>,
+    /// KMS key ID used for server-side encryption.
+    pub sse_kms_key_id: Option<String>,
+}
+
+impl Default for S3UploadConfig {
+    fn default() -> Self {
+        Self {
+            multipart_part_size: 64 << 20,
+            server_side_encryption: None,
+            sse_kms_key_id: None,
+        }
+    }
+}
+
+impl S3UploadConfig {
+    fn validate(&self) -> Result<(), ObjectStoreError> {
+        if self.multipart_part_size < MIN_MULTIPART_PART_SIZE {
+            return Err(ObjectStoreError::Initialization {
+                source: format!(
+                    "multipart part size {} is less than the minimum allowed size {MIN_MULTIPART_PART_SIZE}",
+                    self.multipart_part_size
+                )
+                .into(),
+                is_retriable: false,
+            });
+        }
+        if self.sse_kms_key_id.is_some() && self.server_side_encryption.is_none() {
+            return Err(ObjectStoreError::Initialization {
+                source: "KMS key ID is specified without server-side encryption algorithm".into(),
+                is_retriable: false,
+            });
+        }
+        Ok(())
+    }
+
+    fn server_side_encryption(&self) -> Option<ServerSideEncryption> {
+        self.server_side_encryption
+            .as_deref()
+            .map(ServerSideEncryption::from)
+    }
+}
+
+/// Fingerprint of an uploaded object used to check that a multipart upload is resumed for the same object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ObjectFingerprint {
+    len: usize,
+    hash: u64,
+}
+
+impl ObjectFingerprint {
+    fn new(value: &[u8]) -> Self {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        Self {
+            len: value.len(),
+            hash: hasher.finish(),
+        }
+    }
+}
+
+/// Multipart upload that has failed with a retriable error and can be resumed.
+#[derive(Debug)]
+struct PendingUpload {
+    upload_id: String,
+    fingerprint: ObjectFingerprint,
+    /// Successfully uploaded parts, ordered by the part number.
+    completed_parts: Vec<CompletedPart>,
+}
+
+/// Multipart uploads that can be resumed, keyed by the object key. This allows to not re-upload parts
+/// when retrying a failed upload (e.g., by [`StoreWithRetries`](crate::retries::StoreWithRetries)).
+#[derive(Debug, Default)]
+struct PendingUploads(Mutex<HashMap<String, PendingUpload>>);
+
+impl PendingUploads {
+    /// Removes a pending upload for the specified key. Returns `None` if there's no upload, or the upload
+    /// was started for another object; in the latter case, the stale upload is returned in the `Err` variant
+    /// so that it can be aborted.
+    fn take(
+        &self,
+        key: &str,
+        fingerprint: ObjectFingerprint,
+    ) -> Result<Option<PendingUpload>, PendingUpload> {
+        let upload = self.0.lock().expect("poisoned").remove(key);
+        match upload {
+            Some(upload) if upload.fingerprint == fingerprint => Ok(Some(upload)),
+            Some(upload) => Err(upload),
+            None => Ok(None),
+        }
+    }
+
+    fn insert(&self, key: String, upload: PendingUpload) {
+        self.0.lock().expect("poisoned").insert(key, upload);
+    }
+}
+
+/// [`ObjectStore`] implementation based on AWS S3 or an S3-compatible API (e.g., MinIO or Cloudflare R2).
+///
+/// Objects larger than [`S3UploadConfig::multipart_part_size`] are uploaded using multipart upload. If such an upload
+/// fails with a retriable error, the upload is not aborted; instead, the next upload for the same object resumes it,
+/// only uploading the remaining parts. An upload that is never resumed is not aborted, so it's recommended to configure
+/// a bucket lifecycle rule removing incomplete multipart uploads.
 pub struct S3Store {
     endpoint: String,
     bucket_prefix: String,
     client: Client,
+    upload_config: S3UploadConfig,
+    pending_uploads: PendingUploads,
 }
 
 impl fmt::Debug for S3Store {
@@ -24,6 +149,7 @@ impl fmt::Debug for S3Store {
             .debug_struct("S3Store")
             .field("bucket_prefix", &self.bucket_prefix)
             .field("endpoint", &self.endpoint)
+            .field("upload_config", &self.upload_config)
             // Skip `client` as its representation may contain sensitive info
             .finish_non_exhaustive()
     }
@@ -41,12 +167,18 @@ pub enum S3StoreAuthMode {
 
 impl S3Store {
     /// Creates a new S3 store.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `upload_config` is invalid.
     pub async fn new(
         auth_mode: S3StoreAuthMode,
         bucket_prefix: String,
         endpoint: Option<String>,
         region: Option<String>,
+        upload_config: S3UploadConfig,
     ) -> Result<Self, ObjectStoreError> {
+        upload_config.validate()?;
         let region_provider = RegionProviderChain::first_try(region.map(Region::new))
             .or_default_provider()
             .or_else(Region::new("auto"));
@@ -62,6 +194,8 @@ impl S3Store {
             endpoint: endpoint.unwrap_or_default(),
             bucket_prefix,
             client,
+            upload_config,
+            pending_uploads: PendingUploads::default(),
         })
     }
 
@@ -82,6 +216,151 @@ impl S3Store {
     fn filename(bucket: &str, filename: &str) -> String {
         format!("{bucket}/{filename}")
     }
+
+    async fn put_single(&self, filename: String, value: Vec<u8>) -> Result<(), ObjectStoreError> {
+        let length = i64::try_from(value.len()).context("Object is way too big")?;
+        self.client
+            .put_object()
+            .bucket(self.bucket_prefix.clone())
+            .key(filename)
+            .body(value.into())
+            .content_length(length)
+            .set_server_side_encryption(self.upload_config.server_side_encryption())
+            .set_ssekms_key_id(self.upload_config.sse_kms_key_id.clone())
+            .send()
+            .await?;
+        Ok(())
+    }
+
+    async fn put_multipart(
+        &self,
+        filename: String,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        let part_size = self.upload_config.multipart_part_size;
+        let part_count = value.len().div_ceil(part_size);
+        if part_count > MAX_MULTIPART_PARTS {
+            return Err(ObjectStoreError::Other {
+                source: format!(
+                    "object with size {} requires {part_count} parts, more than the maximum {MAX_MULTIPART_PARTS}; \
+                     increase multipart part size",
+                    value.len()
+                )
+                .into(),
+                is_retriable: false,
+            });
+        }
+
+        let fingerprint = ObjectFingerprint::new(&value);
+        let pending_upload = match self.pending_uploads.take(&filename, fingerprint) {
+            Ok(upload) => upload,
+            Err(stale_upload) => {
+                self.abort_multipart(&filename, &stale_upload.upload_id)
+                    .await;
+                None
+            }
+        };
+        let mut upload = if let Some(upload) = pending_upload {
+            tracing::info!(
+                "Resuming multipart upload for key {filename} with {}/{part_count} completed parts",
+                upload.completed_parts.len()
+            );
+            upload
+        } else {
+            let output = self
+                .client
+                .create_multipart_upload()
+                .bucket(self.bucket_prefix.clone())
+                .key(filename.clone())
+                .set_server_side_encryption(self.upload_config.server_side_encryption())
+                .set_ssekms_key_id(self.upload_config.sse_kms_key_id.clone())
+                .send()
+                .await?;
+            let upload_id = output
+                .upload_id()
+                .context("S3 didn't return multipart upload ID")?;
+            PendingUpload {
+                upload_id: upload_id.to_owned(),
+                fingerprint,
+                completed_parts: Vec::with_capacity(part_count),
+            }
+        };
+
+        match self.upload_parts(&filename, &value, &mut upload).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.is_retriable() => {
+                self.pending_uploads.insert(filename, upload);
+                Err(err)
+            }
+            Err(err) => {
+                self.abort_multipart(&filename, &upload.upload_id).await;
+                Err(err)
+            }
+        }
+    }
+
+    /// Uploads remaining parts for a multipart upload and completes it.
+    async fn upload_parts(
+        &self,
+        filename: &str,
+        value: &[u8],
+        upload: &mut PendingUpload,
+    ) -> Result<(), ObjectStoreError> {
+        let chunks = value.chunks(self.upload_config.multipart_part_size);
+        for (i, chunk) in chunks.enumerate().skip(upload.completed_parts.len()) {
+            // Part numbers are 1-based.
+            let part_number = i32::try_from(i + 1).context("too many parts")?;
+            let length = i64::try_from(chunk.len()).context("Part is way too big")?;
+            let output = self
+                .client
+                .upload_part()
+                .bucket(self.bucket_prefix.clone())
+                .key(filename)
+                .upload_id(upload.upload_id.clone())
+                .part_number(part_number)
+                .body(ByteStream::from(chunk.to_vec()))
+                .content_length(length)
+                .send()
+                .await?;
+            let e_tag = output.e_tag().context("S3 didn't return part ETag")?;
+            upload.completed_parts.push(
+                CompletedPart::builder()
+                    .e_tag(e_tag)
+                    .part_number(part_number)
+                    .build(),
+            );
+        }
+
+        let completed_upload = CompletedMultipartUpload::builder()
+            .set_parts(Some(upload.completed_parts.clone()))
+            .build();
+        self.client
+            .complete_multipart_upload()
+            .bucket(self.bucket_prefix.clone())
+            .key(filename)
+            .upload_id(upload.upload_id.clone())
+            .multipart_upload(completed_upload)
+            .send()
+            .await?;
+        Ok(())
+    }
+
+    /// Aborts a multipart upload so that its parts don't occupy storage. Errors are logged and ignored.
+    async fn abort_multipart(&self, filename: &str, upload_id: &str) {
+        let result = self
+            .client
+            .abort_multipart_upload()
+            .bucket(self.bucket_prefix.clone())
+            .key(filename)
+            .upload_id(upload_id)
+            .send()
+            .await;
+        if let Err(err) = result {
+            tracing::warn!(
+                "Failed aborting multipart upload {upload_id} for key {filename}: {err}"
+            );
+        }
+    }
 }
 
 impl From<ByteStreamError> for ObjectStoreError {
@@ -187,16 +466,11 @@ impl ObjectStore for S3Store {
             self.bucket_prefix
         );
 
-        let length = i64::try_from(value.len()).context("Object is way too big")?;
-        self.client
-            .put_object()
-            .bucket(self.bucket_prefix.clone())
-            .key(filename)
-            .body(value.into())
-            .content_length(length)
-            .send()
-            .await?;
-        Ok(())
+        if value.len() > self.upload_config.multipart_part_size {
+            self.put_multipart(filename, value).await
+        } else {
+            self.put_single(filename, value).await
+        }
     }
 
     async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
@@ -224,3 +498,69 @@ impl ObjectStore for S3Store {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn validating_upload_config() {
+        S3UploadConfig::default().validate().unwrap();
+
+        let config = S3UploadConfig {
+            multipart_part_size: 1 << 20,
+            ..S3UploadConfig::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert_matches!(
+            err,
+            ObjectStoreError::Initialization {
+                is_retriable: false,
+                ..
+            }
+        );
+
+        let config = S3UploadConfig {
+            sse_kms_key_id: Some("key".to_owned()),
+            ..S3UploadConfig::default()
+        };
+        config.validate().unwrap_err();
+        let config = S3UploadConfig {
+            server_side_encryption: Some("aws:kms".to_owned()),
+            sse_kms_key_id: Some("key".to_owned()),
+            ..S3UploadConfig::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(
+            config.server_side_encryption(),
+            Some(ServerSideEncryption::AwsKms)
+        );
+    }
+
+    #[test]
+    fn pending_upload_is_only_resumed_for_same_object() {
+        let uploads = PendingUploads::default();
+        let fingerprint = ObjectFingerprint::new(&[1; 32]);
+        let new_upload = |upload_id: &str| PendingUpload {
+            upload_id: upload_id.to_owned(),
+            fingerprint,
+            completed_parts: vec![CompletedPart::builder().part_number(1).e_tag("0").build()],
+        };
+
+        assert!(uploads.take("key", fingerprint).unwrap().is_none());
+        uploads.insert("key".to_owned(), new_upload("1"));
+        let upload = uploads.take("key", fingerprint).unwrap().unwrap();
+        assert_eq!(upload.upload_id, "1");
+        assert_eq!(upload.completed_parts.len(), 1);
+        assert!(uploads.take("key", fingerprint).unwrap().is_none());
+
+        uploads.insert("key".to_owned(), new_upload("2"));
+        let other_fingerprint = ObjectFingerprint::new(&[2; 32]);
+        assert_ne!(other_fingerprint, fingerprint);
+        let stale_upload = uploads.take("key", other_fingerprint).unwrap_err();
+        assert_eq!(stale_upload.upload_id, "2");
+        assert!(uploads.take("key", fingerprint).unwrap().is_none());
+    }
+}
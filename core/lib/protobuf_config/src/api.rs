@@ -69,6 +69,11 @@ impl ProtoRepr for proto::Web3JsonRpc {
             ws_port: required(&self.ws_port)
                 .and_then(|p| Ok((*p).try_into()?))
                 .context("ws_port")?,
+            admin_port: self
+                .admin_port
+                .map(|p| p.try_into())
+                .transpose()
+                .context("admin_port")?,
             req_entities_limit: self.req_entities_limit,
             filters_disabled: self.filters_disabled.unwrap_or(false),
             filters_limit: self.filters_limit,
@@ -87,6 +92,16 @@ impl ProtoRepr for proto::Web3JsonRpc {
             max_tx_size: required(&self.max_tx_size)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_tx_size")?,
+            max_tx_abi_encoded_size: self
+                .max_tx_abi_encoded_size
+                .map(|x| x.try_into())
+                .transpose()
+                .context("max_tx_abi_encoded_size")?,
+            max_tx_compressed_bytecodes_size: self
+                .max_tx_compressed_bytecodes_size
+                .map(|x| x.try_into())
+                .transpose()
+                .context("max_tx_compressed_bytecodes_size")?,
             vm_execution_cache_misses_limit: self
                 .vm_execution_cache_misses_limit
                 .map(|x| x.try_into())
@@ -117,6 +132,7 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|x| x.try_into())
                 .transpose()
                 .context("latest_values_max_block_lag")?,
+            storage_read_batching_window_ms: self.storage_read_batching_window_ms,
             fee_history_limit: self.fee_history_limit,
             max_batch_request_size: self
                 .max_batch_request_size
@@ -129,6 +145,11 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .transpose()
                 .context("max_response_body_size_mb")?,
             max_response_body_size_overrides_mb,
+            max_trace_size_mb: self
+                .max_trace_size_mb
+                .map(|x| x.try_into())
+                .transpose()
+                .context("max_trace_size_mb")?,
             websocket_requests_per_minute_limit: self
                 .websocket_requests_per_minute_limit
                 .map(|x| x.try_into())
@@ -141,6 +162,14 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|x| x.try_into())
                 .transpose()
                 .context("mempool_cache_size")?,
+            eth_call_cache_size: self
+                .eth_call_cache_size
+                .map(|x| x.try_into())
+                .transpose()
+                .context("eth_call_cache_size")?,
+            load_shedding_vm_queue_latency_ms: self.load_shedding_vm_queue_latency_ms,
+            load_shedding_pool_utilization: self.load_shedding_pool_utilization,
+            load_shedding_retry_after_ms: self.load_shedding_retry_after_ms,
             whitelisted_tokens_for_aa: self
                 .whitelisted_tokens_for_aa
                 .iter()
@@ -159,10 +188,15 @@ impl ProtoRepr for proto::Web3JsonRpc {
         Self {
             http_port: Some(this.http_port.into()),
             ws_port: Some(this.ws_port.into()),
+            admin_port: this.admin_port.map(Into::into),
             req_entities_limit: this.req_entities_limit,
             filters_disabled: Some(this.filters_disabled),
             mempool_cache_update_interval: this.mempool_cache_update_interval,
             mempool_cache_size: this.mempool_cache_size.map(|x| x.try_into().unwrap()),
+            eth_call_cache_size: this.eth_call_cache_size.map(|x| x.try_into().unwrap()),
+            load_shedding_vm_queue_latency_ms: this.load_shedding_vm_queue_latency_ms,
+            load_shedding_pool_utilization: this.load_shedding_pool_utilization,
+            load_shedding_retry_after_ms: this.load_shedding_retry_after_ms,
             filters_limit: this.filters_limit,
             subscriptions_limit: this.subscriptions_limit,
             pubsub_polling_interval: this.pubsub_polling_interval,
@@ -174,6 +208,10 @@ impl ProtoRepr for proto::Web3JsonRpc {
             ),
             estimate_gas_optimize_search: Some(this.estimate_gas_optimize_search),
             max_tx_size: Some(this.max_tx_size.try_into().unwrap()),
+            max_tx_abi_encoded_size: this.max_tx_abi_encoded_size.map(|x| x.try_into().unwrap()),
+            max_tx_compressed_bytecodes_size: this
+                .max_tx_compressed_bytecodes_size
+                .map(|x| x.try_into().unwrap()),
             vm_execution_cache_misses_limit: this
                 .vm_execution_cache_misses_limit
                 .map(|x| x.try_into().unwrap()),
@@ -188,6 +226,7 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .latest_values_cache_size_mb
                 .map(|x| x.try_into().unwrap()),
             latest_values_max_block_lag: this.latest_values_max_block_lag.map(NonZeroU32::get),
+            storage_read_batching_window_ms: this.storage_read_batching_window_ms,
             fee_history_limit: this.fee_history_limit,
             max_batch_request_size: this.max_batch_request_size.map(|x| x.try_into().unwrap()),
             max_response_body_size_mb: this
@@ -205,6 +244,7 @@ impl ProtoRepr for proto::Web3JsonRpc {
                     },
                 })
                 .collect(),
+            max_trace_size_mb: this.max_trace_size_mb.map(|x| x.try_into().unwrap()),
             websocket_requests_per_minute_limit: this
                 .websocket_requests_per_minute_limit
                 .map(|x| x.into()),
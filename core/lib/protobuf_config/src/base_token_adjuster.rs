@@ -1,3 +1,4 @@
+use anyhow::Context as _;
 use zksync_config::configs::{self};
 use zksync_protobuf::ProtoRepr;
 
@@ -7,6 +8,18 @@ impl ProtoRepr for proto::BaseTokenAdjuster {
     type Type = configs::base_token_adjuster::BaseTokenAdjusterConfig;
 
     fn read(&self) -> anyhow::Result<Self::Type> {
+        let base_token_decimals = self
+            .base_token_decimals
+            .map(u8::try_from)
+            .transpose()
+            .context("base_token_decimals")?
+            .unwrap_or(Self::Type::default_base_token_decimals());
+        anyhow::ensure!(
+            base_token_decimals <= Self::Type::MAX_BASE_TOKEN_DECIMALS,
+            "base_token_decimals ({base_token_decimals}) must not exceed {}",
+            Self::Type::MAX_BASE_TOKEN_DECIMALS
+        );
+
         Ok(configs::base_token_adjuster::BaseTokenAdjusterConfig {
             price_polling_interval_ms: self
                 .price_polling_interval_ms
@@ -45,6 +58,7 @@ impl ProtoRepr for proto::BaseTokenAdjuster {
             l1_update_deviation_percentage: self
                 .l1_update_deviation_percentage
                 .unwrap_or(Self::Type::default_l1_update_deviation_percentage()),
+            base_token_decimals,
         })
     }
 
@@ -63,6 +77,7 @@ impl ProtoRepr for proto::BaseTokenAdjuster {
             default_priority_fee_per_gas: Some(this.default_priority_fee_per_gas),
             max_acceptable_priority_fee_in_gwei: Some(this.max_acceptable_priority_fee_in_gwei),
             halt_on_error: Some(this.halt_on_error),
+            base_token_decimals: Some(this.base_token_decimals.into()),
         }
     }
 }
@@ -0,0 +1,48 @@
+use anyhow::Context as _;
+use zksync_config::configs::{batch_exporter::BatchExportFormat, BatchExporterConfig};
+use zksync_protobuf::{required, ProtoRepr};
+
+use crate::proto::batch_exporter as proto;
+
+impl proto::BatchExportFormat {
+    fn new(x: &BatchExportFormat) -> Self {
+        match x {
+            BatchExportFormat::Json => Self::Json,
+            BatchExportFormat::Parquet => Self::Parquet,
+        }
+    }
+
+    fn parse(&self) -> BatchExportFormat {
+        match self {
+            Self::Json => BatchExportFormat::Json,
+            Self::Parquet => BatchExportFormat::Parquet,
+        }
+    }
+}
+
+impl ProtoRepr for proto::BatchExporter {
+    type Type = BatchExporterConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            output_path: required(&self.output_path).context("output_path")?.clone(),
+            format: self
+                .format
+                .map(proto::BatchExportFormat::try_from)
+                .transpose()
+                .context("format")?
+                .map_or_else(BatchExportFormat::default, |format| format.parse()),
+            poll_interval_ms: self.poll_interval_ms,
+            max_batches_per_iteration: self.max_batches_per_iteration,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            output_path: Some(this.output_path.clone()),
+            format: Some(proto::BatchExportFormat::new(&this.format).into()),
+            poll_interval_ms: this.poll_interval_ms,
+            max_batches_per_iteration: this.max_batches_per_iteration,
+        }
+    }
+}
@@ -22,6 +22,26 @@ impl proto::FeeModelVersion {
     }
 }
 
+impl proto::TimestampPolicy {
+    fn new(kind: &configs::chain::TimestampPolicyKind) -> Self {
+        use configs::chain::TimestampPolicyKind as From;
+        match kind {
+            From::RealTime => Self::RealTime,
+            From::FixedIncrement => Self::FixedIncrement,
+            From::MedianOfL1 => Self::MedianOfL1,
+        }
+    }
+
+    fn parse(&self) -> configs::chain::TimestampPolicyKind {
+        use configs::chain::TimestampPolicyKind as To;
+        match self {
+            Self::RealTime => To::RealTime,
+            Self::FixedIncrement => To::FixedIncrement,
+            Self::MedianOfL1 => To::MedianOfL1,
+        }
+    }
+}
+
 impl ProtoRepr for proto::StateKeeper {
     type Type = configs::chain::StateKeeperConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
@@ -72,15 +92,69 @@ impl ProtoRepr for proto::StateKeeper {
                 .and_then(|x| Ok(proto::FeeModelVersion::try_from(*x)?))
                 .context("fee_model_version")?
                 .parse(),
+            fee_congestion_enabled: self.fee_congestion_enabled.unwrap_or_default(),
+            fee_congestion_compute_target: self
+                .fee_congestion_compute_target
+                .unwrap_or(Self::Type::default_fee_congestion_compute_target()),
+            fee_congestion_pubdata_target: self
+                .fee_congestion_pubdata_target
+                .unwrap_or(Self::Type::default_fee_congestion_pubdata_target()),
+            fee_congestion_max_change_denominator: self
+                .fee_congestion_max_change_denominator
+                .unwrap_or(Self::Type::default_fee_congestion_max_change_denominator()),
+            fee_congestion_max_multiplier: self
+                .fee_congestion_max_multiplier
+                .unwrap_or(Self::Type::default_fee_congestion_max_multiplier()),
+            fee_congestion_window_batches: self
+                .fee_congestion_window_batches
+                .unwrap_or(Self::Type::default_fee_congestion_window_batches()),
             validation_computational_gas_limit: *required(&self.validation_computational_gas_limit)
                 .context("validation_computational_gas_limit")?,
             save_call_traces: *required(&self.save_call_traces).context("save_call_traces")?,
             max_circuits_per_batch: required(&self.max_circuits_per_batch)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_circuits_per_batch")?,
+            geometry_adjustment_enabled: self.geometry_adjustment_enabled.unwrap_or_default(),
+            geometry_adjustment_target_proving_time_sec: self
+                .geometry_adjustment_target_proving_time_sec
+                .unwrap_or(Self::Type::default_geometry_adjustment_target_proving_time_sec()),
+            geometry_adjustment_min_percentage: self
+                .geometry_adjustment_min_percentage
+                .unwrap_or(Self::Type::default_geometry_adjustment_min_percentage()),
+            geometry_adjustment_max_percentage: self
+                .geometry_adjustment_max_percentage
+                .unwrap_or(Self::Type::default_geometry_adjustment_max_percentage()),
+            geometry_adjustment_window_batches: self
+                .geometry_adjustment_window_batches
+                .unwrap_or(Self::Type::default_geometry_adjustment_window_batches()),
             protective_reads_persistence_enabled: self
                 .protective_reads_persistence_enabled
                 .unwrap_or_default(),
+            witness_inputs_pregeneration_enabled: self
+                .witness_inputs_pregeneration_enabled
+                .unwrap_or_default(),
+            storage_slot_writers_enabled: self.storage_slot_writers_enabled.unwrap_or_default(),
+            pending_batch_state_enabled: self.pending_batch_state_enabled.unwrap_or_default(),
+            timestamp_policy: self
+                .timestamp_policy
+                .map(proto::TimestampPolicy::try_from)
+                .transpose()
+                .context("timestamp_policy")?
+                .map_or_else(Default::default, |policy| policy.parse()),
+            timestamp_increment_sec: self
+                .timestamp_increment_sec
+                .unwrap_or(Self::Type::default_timestamp_increment_sec()),
+            l1_timestamp_window: self
+                .l1_timestamp_window
+                .unwrap_or(Self::Type::default_l1_timestamp_window()),
+            dev_mode: self.dev_mode.unwrap_or_default(),
+            sequencer_instance_id: self.sequencer_instance_id.clone(),
+            sequencer_lease_ttl_ms: self
+                .sequencer_lease_ttl_ms
+                .unwrap_or(Self::Type::default_sequencer_lease_ttl_ms()),
+            frame_gas_forwarding_divisor: self.frame_gas_forwarding_divisor,
+            max_frame_gas: self.max_frame_gas,
+            batch_checkpoint_interval: self.batch_checkpoint_interval,
 
             // We need these values only for instantiating configs from environmental variables, so it's not
             // needed during the initialization from files
@@ -116,10 +190,35 @@ impl ProtoRepr for proto::StateKeeper {
             max_gas_per_batch: Some(this.max_gas_per_batch),
             max_pubdata_per_batch: Some(this.max_pubdata_per_batch),
             fee_model_version: Some(proto::FeeModelVersion::new(&this.fee_model_version).into()),
+            fee_congestion_enabled: Some(this.fee_congestion_enabled),
+            fee_congestion_compute_target: Some(this.fee_congestion_compute_target),
+            fee_congestion_pubdata_target: Some(this.fee_congestion_pubdata_target),
+            fee_congestion_max_change_denominator: Some(this.fee_congestion_max_change_denominator),
+            fee_congestion_max_multiplier: Some(this.fee_congestion_max_multiplier),
+            fee_congestion_window_batches: Some(this.fee_congestion_window_batches),
             validation_computational_gas_limit: Some(this.validation_computational_gas_limit),
             save_call_traces: Some(this.save_call_traces),
             max_circuits_per_batch: Some(this.max_circuits_per_batch.try_into().unwrap()),
+            geometry_adjustment_enabled: Some(this.geometry_adjustment_enabled),
+            geometry_adjustment_target_proving_time_sec: Some(
+                this.geometry_adjustment_target_proving_time_sec,
+            ),
+            geometry_adjustment_min_percentage: Some(this.geometry_adjustment_min_percentage),
+            geometry_adjustment_max_percentage: Some(this.geometry_adjustment_max_percentage),
+            geometry_adjustment_window_batches: Some(this.geometry_adjustment_window_batches),
             protective_reads_persistence_enabled: Some(this.protective_reads_persistence_enabled),
+            witness_inputs_pregeneration_enabled: Some(this.witness_inputs_pregeneration_enabled),
+            storage_slot_writers_enabled: Some(this.storage_slot_writers_enabled),
+            pending_batch_state_enabled: Some(this.pending_batch_state_enabled),
+            timestamp_policy: Some(proto::TimestampPolicy::new(&this.timestamp_policy).into()),
+            timestamp_increment_sec: Some(this.timestamp_increment_sec),
+            l1_timestamp_window: Some(this.l1_timestamp_window),
+            dev_mode: Some(this.dev_mode),
+            sequencer_instance_id: this.sequencer_instance_id.clone(),
+            sequencer_lease_ttl_ms: Some(this.sequencer_lease_ttl_ms),
+            frame_gas_forwarding_divisor: this.frame_gas_forwarding_divisor,
+            max_frame_gas: this.max_frame_gas,
+            batch_checkpoint_interval: this.batch_checkpoint_interval,
         }
     }
 }
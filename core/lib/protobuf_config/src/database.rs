@@ -1,3 +1,5 @@
+use std::num::NonZeroU32;
+
 use anyhow::Context as _;
 use zksync_config::configs;
 use zksync_protobuf::{
@@ -48,6 +50,24 @@ impl ProtoRepr for proto::MerkleTree {
             max_l1_batches_per_iter: required(&self.max_l1_batches_per_iter)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_l1_batches_per_iter")?,
+            hashing_thread_count: self
+                .hashing_thread_count
+                .map(|x| x.try_into())
+                .transpose()
+                .context("hashing_thread_count")?,
+            pruning_retained_l1_batches: self
+                .pruning_retained_l1_batches
+                .map(|count| NonZeroU32::new(count).context("cannot be 0"))
+                .transpose()
+                .context("pruning_retained_l1_batches")?,
+            pruning_retain_unproven_l1_batches: self
+                .pruning_retain_unproven_l1_batches
+                .unwrap_or(Self::Type::default_pruning_retain_unproven_l1_batches()),
+            pruning_poll_interval_sec: self
+                .pruning_poll_interval_sec
+                .unwrap_or(Self::Type::default_pruning_poll_interval_sec()),
+            in_memory: self.in_memory.unwrap_or(false),
+            in_memory_persistence_interval_sec: self.in_memory_persistence_interval_sec,
         })
     }
 
@@ -60,6 +80,12 @@ impl ProtoRepr for proto::MerkleTree {
             memtable_capacity_mb: Some(this.memtable_capacity_mb.try_into().unwrap()),
             stalled_writes_timeout_sec: Some(this.stalled_writes_timeout_sec),
             max_l1_batches_per_iter: Some(this.max_l1_batches_per_iter.try_into().unwrap()),
+            hashing_thread_count: this.hashing_thread_count.map(|x| x.try_into().unwrap()),
+            pruning_retained_l1_batches: this.pruning_retained_l1_batches.map(NonZeroU32::get),
+            pruning_retain_unproven_l1_batches: Some(this.pruning_retain_unproven_l1_batches),
+            pruning_poll_interval_sec: Some(this.pruning_poll_interval_sec),
+            in_memory: Some(this.in_memory),
+            in_memory_persistence_interval_sec: this.in_memory_persistence_interval_sec,
         }
     }
 }
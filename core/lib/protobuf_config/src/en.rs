@@ -1,5 +1,5 @@
 use std::{
-    num::{NonZeroU64, NonZeroUsize},
+    num::{NonZeroU32, NonZeroU64, NonZeroUsize},
     str::FromStr,
 };
 
@@ -37,6 +37,16 @@ impl ProtoRepr for proto::ExternalNode {
                 .bridge_addresses_refresh_interval_sec
                 .and_then(NonZeroU64::new),
             gateway_chain_id: self.gateway_chain_id.map(SLChainId),
+            cross_verification_enabled: self.cross_verification_enabled.unwrap_or(false),
+            max_auto_rollback_l1_batches: self.max_auto_rollback_l1_batches,
+            response_checker_enabled: self.response_checker_enabled.unwrap_or(false),
+            response_checker_peer_url: self
+                .response_checker_peer_url
+                .as_deref()
+                .map(SensitiveUrl::from_str)
+                .transpose()
+                .context("response_checker_peer_url")?,
+            response_checker_stride: self.response_checker_stride.and_then(NonZeroU32::new),
         })
     }
 
@@ -56,6 +66,14 @@ impl ProtoRepr for proto::ExternalNode {
                 .bridge_addresses_refresh_interval_sec
                 .map(|a| a.get()),
             gateway_chain_id: this.gateway_chain_id.map(|c| c.0),
+            cross_verification_enabled: Some(this.cross_verification_enabled),
+            max_auto_rollback_l1_batches: this.max_auto_rollback_l1_batches,
+            response_checker_enabled: Some(this.response_checker_enabled),
+            response_checker_peer_url: this
+                .response_checker_peer_url
+                .as_ref()
+                .map(|url| url.expose_str().to_string()),
+            response_checker_stride: this.response_checker_stride.map(NonZeroU32::get),
         }
     }
 }
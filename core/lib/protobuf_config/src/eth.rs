@@ -138,6 +138,25 @@ impl ProtoRepr for proto::Sender {
                 .context("gas_limit_mode")?
                 .map(|a| a.parse())
                 .unwrap_or(Self::Type::default_gas_limit_mode()),
+            pubdata_auto_selection: self.pubdata_auto_selection.unwrap_or(false),
+            pubdata_auto_selection_max_blob_base_fee: self.pubdata_auto_selection_max_blob_base_fee,
+            pubdata_auto_selection_max_calldata_size: self
+                .pubdata_auto_selection_max_calldata_size
+                .map(|x| x.try_into())
+                .transpose()
+                .context("pubdata_auto_selection_max_calldata_size")?
+                .unwrap_or(Self::Type::default_pubdata_auto_selection_max_calldata_size()),
+            operator_takeover_after_l1_blocks: self.operator_takeover_after_l1_blocks,
+            high_gas_price_aggregation_threshold: self.high_gas_price_aggregation_threshold,
+            max_aggregated_blocks_on_high_gas_price: self
+                .max_aggregated_blocks_on_high_gas_price
+                .unwrap_or(Self::Type::default_max_aggregated_blocks_on_high_gas_price()),
+            fee_bump_interval_in_l1_blocks: self.fee_bump_interval_in_l1_blocks,
+            fee_bump_interval_sec: self.fee_bump_interval_sec,
+            fee_bump_percent: self
+                .fee_bump_percent
+                .unwrap_or(Self::Type::default_fee_bump_percent()),
+            max_fee_per_gas_cap: self.max_fee_per_gas_cap,
         })
     }
 
@@ -168,6 +187,22 @@ impl ProtoRepr for proto::Sender {
             time_in_mempool_in_l1_blocks_cap: Some(this.time_in_mempool_in_l1_blocks_cap),
             is_verifier_pre_fflonk: Some(this.is_verifier_pre_fflonk),
             gas_limit_mode: Some(proto::GasLimitMode::new(&this.gas_limit_mode).into()),
+            pubdata_auto_selection: Some(this.pubdata_auto_selection),
+            pubdata_auto_selection_max_blob_base_fee: this.pubdata_auto_selection_max_blob_base_fee,
+            pubdata_auto_selection_max_calldata_size: Some(
+                this.pubdata_auto_selection_max_calldata_size
+                    .try_into()
+                    .unwrap(),
+            ),
+            operator_takeover_after_l1_blocks: this.operator_takeover_after_l1_blocks,
+            high_gas_price_aggregation_threshold: this.high_gas_price_aggregation_threshold,
+            max_aggregated_blocks_on_high_gas_price: Some(
+                this.max_aggregated_blocks_on_high_gas_price,
+            ),
+            fee_bump_interval_in_l1_blocks: this.fee_bump_interval_in_l1_blocks,
+            fee_bump_interval_sec: this.fee_bump_interval_sec,
+            fee_bump_percent: Some(this.fee_bump_percent),
+            max_fee_per_gas_cap: this.max_fee_per_gas_cap,
         }
     }
 }
@@ -201,6 +236,14 @@ impl ProtoRepr for proto::GasAdjuster {
             )
             .context("internal_pubdata_pricing_multiplier")?,
             max_blob_base_fee: self.max_blob_base_fee,
+            fee_sources_quorum: self
+                .fee_sources_quorum
+                .map(|x| x.try_into())
+                .transpose()
+                .context("fee_sources_quorum")?,
+            fee_sources_max_deviation: self
+                .fee_sources_max_deviation
+                .unwrap_or(Self::Type::default_fee_sources_max_deviation()),
         })
     }
 
@@ -222,6 +265,8 @@ impl ProtoRepr for proto::GasAdjuster {
             ),
             internal_pubdata_pricing_multiplier: Some(this.internal_pubdata_pricing_multiplier),
             max_blob_base_fee: this.max_blob_base_fee,
+            fee_sources_quorum: this.fee_sources_quorum.map(|x| x.try_into().unwrap()),
+            fee_sources_max_deviation: Some(this.fee_sources_max_deviation),
         }
     }
 }
@@ -234,6 +279,17 @@ impl ProtoRepr for proto::EthWatch {
             confirmations_for_eth_event: self.confirmations_for_eth_event,
             eth_node_poll_interval: *required(&self.eth_node_poll_interval)
                 .context("eth_node_poll_interval")?,
+            reorg_check_depth_in_l1_blocks: self.reorg_check_depth_in_l1_blocks,
+            l2_to_l1_message_tracking_enabled: self
+                .l2_to_l1_message_tracking_enabled
+                .unwrap_or_default(),
+            l2_to_l1_message_tracking_interval_ms: self
+                .l2_to_l1_message_tracking_interval_ms
+                .unwrap_or_else(Self::Type::default_l2_to_l1_message_tracking_interval_ms),
+            l2_to_l1_message_tracking_first_l1_batch: self.l2_to_l1_message_tracking_first_l1_batch,
+            l2_to_l1_message_retention_l1_batches: self
+                .l2_to_l1_message_retention_l1_batches
+                .unwrap_or_else(Self::Type::default_l2_to_l1_message_retention_l1_batches),
         })
     }
 
@@ -241,6 +297,11 @@ impl ProtoRepr for proto::EthWatch {
         Self {
             confirmations_for_eth_event: this.confirmations_for_eth_event,
             eth_node_poll_interval: Some(this.eth_node_poll_interval),
+            reorg_check_depth_in_l1_blocks: this.reorg_check_depth_in_l1_blocks,
+            l2_to_l1_message_tracking_enabled: Some(this.l2_to_l1_message_tracking_enabled),
+            l2_to_l1_message_tracking_interval_ms: Some(this.l2_to_l1_message_tracking_interval_ms),
+            l2_to_l1_message_tracking_first_l1_batch: this.l2_to_l1_message_tracking_first_l1_batch,
+            l2_to_l1_message_retention_l1_batches: Some(this.l2_to_l1_message_retention_l1_batches),
         }
     }
 }
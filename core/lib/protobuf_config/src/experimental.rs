@@ -7,7 +7,7 @@ use zksync_protobuf::{repr::ProtoRepr, required};
 
 use crate::{proto::experimental as proto, read_optional_repr};
 
-fn parse_vm_mode(raw: Option<i32>) -> anyhow::Result<FastVmMode> {
+pub(crate) fn parse_vm_mode(raw: Option<i32>) -> anyhow::Result<FastVmMode> {
     Ok(raw
         .map(proto::FastVmMode::try_from)
         .transpose()
@@ -30,12 +30,32 @@ impl ProtoRepr for proto::Db {
                 .map(|count| NonZeroU32::new(count).context("cannot be 0"))
                 .transpose()
                 .context("state_keeper_db_max_open_files")?,
+            state_keeper_db_state_cache_capacity_mb: self
+                .state_keeper_db_state_cache_capacity_mb
+                .map(|capacity| capacity.try_into())
+                .transpose()
+                .context("state_keeper_db_state_cache_capacity_mb")?,
+            state_keeper_db_soft_pending_compaction_limit_mb: self
+                .state_keeper_db_soft_pending_compaction_limit_mb
+                .map(|limit| limit.try_into())
+                .transpose()
+                .context("state_keeper_db_soft_pending_compaction_limit_mb")?,
+            state_keeper_db_hard_pending_compaction_limit_mb: self
+                .state_keeper_db_hard_pending_compaction_limit_mb
+                .map(|limit| limit.try_into())
+                .transpose()
+                .context("state_keeper_db_hard_pending_compaction_limit_mb")?,
             protective_reads_persistence_enabled: self.reads_persistence_enabled.unwrap_or(false),
             processing_delay_ms: self.processing_delay_ms.unwrap_or_default(),
             include_indices_and_filters_in_block_cache: self
                 .include_indices_and_filters_in_block_cache
                 .unwrap_or(false),
             merkle_tree_repair_stale_keys: self.merkle_tree_repair_stale_keys.unwrap_or(false),
+            merkle_tree_consistency_check_interval_sec: self
+                .merkle_tree_consistency_check_interval_sec,
+            merkle_tree_consistency_check_subtree_delay_ms: self
+                .merkle_tree_consistency_check_subtree_delay_ms
+                .unwrap_or(Self::Type::default_merkle_tree_consistency_check_subtree_delay_ms()),
         })
     }
 
@@ -49,18 +69,32 @@ impl ProtoRepr for proto::Db {
             state_keeper_db_max_open_files: this
                 .state_keeper_db_max_open_files
                 .map(NonZeroU32::get),
+            state_keeper_db_state_cache_capacity_mb: this
+                .state_keeper_db_state_cache_capacity_mb
+                .map(|capacity| capacity.try_into().unwrap()),
+            state_keeper_db_soft_pending_compaction_limit_mb: this
+                .state_keeper_db_soft_pending_compaction_limit_mb
+                .map(|limit| limit.try_into().unwrap()),
+            state_keeper_db_hard_pending_compaction_limit_mb: this
+                .state_keeper_db_hard_pending_compaction_limit_mb
+                .map(|limit| limit.try_into().unwrap()),
             reads_persistence_enabled: Some(this.protective_reads_persistence_enabled),
             processing_delay_ms: Some(this.processing_delay_ms),
             include_indices_and_filters_in_block_cache: Some(
                 this.include_indices_and_filters_in_block_cache,
             ),
             merkle_tree_repair_stale_keys: Some(this.merkle_tree_repair_stale_keys),
+            merkle_tree_consistency_check_interval_sec: this
+                .merkle_tree_consistency_check_interval_sec,
+            merkle_tree_consistency_check_subtree_delay_ms: Some(
+                this.merkle_tree_consistency_check_subtree_delay_ms,
+            ),
         }
     }
 }
 
 impl proto::FastVmMode {
-    fn new(source: FastVmMode) -> Self {
+    pub(crate) fn new(source: FastVmMode) -> Self {
         match source {
             FastVmMode::Old => Self::Old,
             FastVmMode::New => Self::New,
@@ -115,6 +149,18 @@ impl ProtoRepr for proto::Vm {
             playground: read_optional_repr(&self.playground).unwrap_or_default(),
             state_keeper_fast_vm_mode: parse_vm_mode(self.state_keeper_fast_vm_mode)?,
             api_fast_vm_mode: parse_vm_mode(self.api_fast_vm_mode)?,
+            api_vm_warm_pool_size: self
+                .api_vm_warm_pool_size
+                .map(|x| x.try_into())
+                .transpose()
+                .context("api_vm_warm_pool_size")?,
+            api_vm_resource_accounting: self.api_vm_resource_accounting.unwrap_or(false),
+            api_vm_max_memory_mb: self
+                .api_vm_max_memory_mb
+                .map(|x| x.try_into())
+                .transpose()
+                .context("api_vm_max_memory_mb")?,
+            api_vm_max_cpu_time_ms: self.api_vm_max_cpu_time_ms,
         })
     }
 
@@ -125,6 +171,10 @@ impl ProtoRepr for proto::Vm {
                 proto::FastVmMode::new(this.state_keeper_fast_vm_mode).into(),
             ),
             api_fast_vm_mode: Some(proto::FastVmMode::new(this.api_fast_vm_mode).into()),
+            api_vm_warm_pool_size: this.api_vm_warm_pool_size.map(|x| x.try_into().unwrap()),
+            api_vm_resource_accounting: Some(this.api_vm_resource_accounting),
+            api_vm_max_memory_mb: this.api_vm_max_memory_mb.map(|x| x.try_into().unwrap()),
+            api_vm_max_cpu_time_ms: this.api_vm_max_cpu_time_ms,
         }
     }
 }
@@ -45,6 +45,8 @@ impl ProtoRepr for proto::GeneralConfig {
             experimental_vm_config: read_optional_repr(&self.experimental_vm),
             prover_job_monitor_config: read_optional_repr(&self.prover_job_monitor),
             timestamp_asserter_config: read_optional_repr(&self.timestamp_asserter),
+            tx_policy_config: read_optional_repr(&self.tx_policy),
+            batch_exporter_config: read_optional_repr(&self.batch_exporter),
         })
     }
 
@@ -107,6 +109,8 @@ impl ProtoRepr for proto::GeneralConfig {
                 .timestamp_asserter_config
                 .as_ref()
                 .map(ProtoRepr::build),
+            tx_policy: this.tx_policy_config.as_ref().map(ProtoRepr::build),
+            batch_exporter: this.batch_exporter_config.as_ref().map(ProtoRepr::build),
         }
     }
 }
@@ -8,7 +8,7 @@ use zksync_basic_types::{
 use zksync_config::configs;
 use zksync_protobuf::{repr::ProtoRepr, required};
 
-use crate::{parse_h160, parse_h256, proto::genesis as proto};
+use crate::{parse_h160, parse_h256, proto::genesis as proto, read_optional_repr};
 
 impl proto::L1BatchCommitDataGeneratorMode {
     pub(crate) fn new(n: &L1BatchCommitmentMode) -> Self {
@@ -105,6 +105,7 @@ impl ProtoRepr for proto::Genesis {
             .context("l1_batch_commit_data_generator_mode")?
             .parse(),
             custom_genesis_state_path: self.custom_genesis_state_path.clone(),
+            circuit_geometry: read_optional_repr(&prover.circuit_geometry),
         })
     }
 
@@ -128,6 +129,7 @@ impl ProtoRepr for proto::Genesis {
                 fflonk_snark_wrapper_vk_hash: this
                     .fflonk_snark_wrapper_vk_hash
                     .map(|x| format!("{:?}", x)),
+                circuit_geometry: this.circuit_geometry.as_ref().map(ProtoRepr::build),
             }),
             l1_batch_commit_data_generator_mode: Some(
                 proto::L1BatchCommitDataGeneratorMode::new(
@@ -139,3 +141,45 @@ impl ProtoRepr for proto::Genesis {
         }
     }
 }
+
+impl ProtoRepr for proto::CircuitGeometry {
+    type Type = configs::CircuitGeometryConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            cycles_per_vm_snapshot: self.cycles_per_vm_snapshot,
+            cycles_per_ram_permutation: self.cycles_per_ram_permutation,
+            cycles_per_storage_application: self.cycles_per_storage_application,
+            cycles_per_storage_sorter: self.cycles_per_storage_sorter,
+            cycles_per_code_decommitter: self.cycles_per_code_decommitter,
+            cycles_code_decommitter_sorter: self.cycles_code_decommitter_sorter,
+            cycles_per_log_demuxer: self.cycles_per_log_demuxer,
+            cycles_per_events_or_l1_messages_sorter: self.cycles_per_events_or_l1_messages_sorter,
+            cycles_per_keccak256_circuit: self.cycles_per_keccak256_circuit,
+            cycles_per_ecrecover_circuit: self.cycles_per_ecrecover_circuit,
+            cycles_per_sha256_circuit: self.cycles_per_sha256_circuit,
+            cycles_per_secp256r1_verify_circuit: self.cycles_per_secp256r1_verify_circuit,
+            cycles_per_transient_storage_sorter: self.cycles_per_transient_storage_sorter,
+            max_base_layer_circuits: self.max_base_layer_circuits,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            cycles_per_vm_snapshot: this.cycles_per_vm_snapshot,
+            cycles_per_ram_permutation: this.cycles_per_ram_permutation,
+            cycles_per_storage_application: this.cycles_per_storage_application,
+            cycles_per_storage_sorter: this.cycles_per_storage_sorter,
+            cycles_per_code_decommitter: this.cycles_per_code_decommitter,
+            cycles_code_decommitter_sorter: this.cycles_code_decommitter_sorter,
+            cycles_per_log_demuxer: this.cycles_per_log_demuxer,
+            cycles_per_events_or_l1_messages_sorter: this.cycles_per_events_or_l1_messages_sorter,
+            cycles_per_keccak256_circuit: this.cycles_per_keccak256_circuit,
+            cycles_per_ecrecover_circuit: this.cycles_per_ecrecover_circuit,
+            cycles_per_sha256_circuit: this.cycles_per_sha256_circuit,
+            cycles_per_secp256r1_verify_circuit: this.cycles_per_secp256r1_verify_circuit,
+            cycles_per_transient_storage_sorter: this.cycles_per_transient_storage_sorter,
+            max_base_layer_circuits: this.max_base_layer_circuits,
+        }
+    }
+}
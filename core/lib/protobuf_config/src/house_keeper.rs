@@ -12,6 +12,9 @@ impl ProtoRepr for proto::HouseKeeper {
                 &self.l1_batch_metrics_reporting_interval_ms,
             )
             .context("l1_batch_metrics_reporting_interval_ms")?,
+            inclusion_stats_reporting_interval_ms: self.inclusion_stats_reporting_interval_ms,
+            inclusion_stats_window_secs: self.inclusion_stats_window_secs,
+            contract_usage_aggregation_interval_ms: self.contract_usage_aggregation_interval_ms,
         })
     }
 
@@ -20,6 +23,9 @@ impl ProtoRepr for proto::HouseKeeper {
             l1_batch_metrics_reporting_interval_ms: Some(
                 this.l1_batch_metrics_reporting_interval_ms,
             ),
+            inclusion_stats_reporting_interval_ms: this.inclusion_stats_reporting_interval_ms,
+            inclusion_stats_window_secs: this.inclusion_stats_window_secs,
+            contract_usage_aggregation_interval_ms: this.contract_usage_aggregation_interval_ms,
         }
     }
 }
@@ -6,6 +6,7 @@
 
 mod api;
 mod base_token_adjuster;
+mod batch_exporter;
 mod chain;
 mod circuit_breaker;
 mod commitment_generator;
@@ -36,6 +37,7 @@ mod snapshots_creator;
 #[cfg(test)]
 mod tests;
 mod timestamp_asserter;
+mod tx_policy;
 mod utils;
 mod vm_runner;
 mod wallets;
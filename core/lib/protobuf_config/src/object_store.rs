@@ -1,9 +1,29 @@
 use anyhow::Context as _;
-use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
+use zksync_config::configs::object_store::{
+    ContentAddressingMode, ObjectStoreConfig, ObjectStoreMode,
+};
 use zksync_protobuf::{repr::ProtoRepr, required};
 
 use crate::proto::object_store as proto;
 
+impl proto::ContentAddressingMode {
+    fn new(x: &ContentAddressingMode) -> Self {
+        match x {
+            ContentAddressingMode::Disabled => Self::Disabled,
+            ContentAddressingMode::Enabled => Self::Enabled,
+            ContentAddressingMode::Strict => Self::Strict,
+        }
+    }
+
+    fn parse(&self) -> ContentAddressingMode {
+        match self {
+            Self::Disabled => ContentAddressingMode::Disabled,
+            Self::Enabled => ContentAddressingMode::Enabled,
+            Self::Strict => ContentAddressingMode::Strict,
+        }
+    }
+}
+
 impl ProtoRepr for proto::ObjectStore {
     type Type = ObjectStoreConfig;
 
@@ -42,6 +62,9 @@ impl ProtoRepr for proto::ObjectStore {
                         .clone(),
                     endpoint: mode.endpoint.clone(),
                     region: mode.region.clone(),
+                    multipart_part_size_mb: mode.multipart_part_size_mb,
+                    server_side_encryption: mode.server_side_encryption.clone(),
+                    sse_kms_key_id: mode.sse_kms_key_id.clone(),
                 }
             }
             proto::object_store::Mode::S3AnonymousReadOnly(mode) => {
@@ -66,6 +89,12 @@ impl ProtoRepr for proto::ObjectStore {
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_retries")?,
             local_mirror_path: self.local_mirror_path.clone(),
+            content_addressing: self
+                .content_addressing
+                .map(proto::ContentAddressingMode::try_from)
+                .transpose()
+                .context("content_addressing")?
+                .map_or_else(ContentAddressingMode::default, |mode| mode.parse()),
         })
     }
 
@@ -97,12 +126,18 @@ impl ProtoRepr for proto::ObjectStore {
                 s3_credential_file_path,
                 endpoint,
                 region,
+                multipart_part_size_mb,
+                server_side_encryption,
+                sse_kms_key_id,
             } => proto::object_store::Mode::S3WithCredentialFile(
                 proto::object_store::S3WithCredentialFile {
                     bucket_base_url: Some(bucket_base_url.clone()),
                     s3_credential_file_path: Some(s3_credential_file_path.clone()),
                     endpoint: endpoint.clone(),
                     region: region.clone(),
+                    multipart_part_size_mb: *multipart_part_size_mb,
+                    server_side_encryption: server_side_encryption.clone(),
+                    sse_kms_key_id: sse_kms_key_id.clone(),
                 },
             ),
             ObjectStoreMode::S3AnonymousReadOnly {
@@ -127,6 +162,9 @@ impl ProtoRepr for proto::ObjectStore {
             mode: Some(mode),
             max_retries: Some(this.max_retries.into()),
             local_mirror_path: this.local_mirror_path.clone(),
+            content_addressing: Some(
+                proto::ContentAddressingMode::new(&this.content_addressing).into(),
+            ),
         }
     }
 }
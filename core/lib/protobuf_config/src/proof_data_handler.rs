@@ -35,7 +35,14 @@ impl ProtoRepr for proto::ProofDataHandler {
                     .unwrap_or_else(
                         configs::TeeConfig::default_tee_batch_permanently_ignored_timeout_in_hours,
                     ),
+                attestation_policy_enabled: self.attestation_policy_enabled.unwrap_or(false),
+                sgx_mrenclave_allowlist: self.sgx_mrenclave_allowlist.clone(),
+                sgx_mrsigner_allowlist: self.sgx_mrsigner_allowlist.clone(),
+                tdx_mrtd_allowlist: self.tdx_mrtd_allowlist.clone(),
             },
+            proof_sampling_rate: self.proof_sampling_rate,
+            proof_sampling_always_prove_ranges: self.proof_sampling_always_prove_ranges.clone(),
+            proof_sampling_never_prove_ranges: self.proof_sampling_never_prove_ranges.clone(),
         })
     }
 
@@ -53,6 +60,13 @@ impl ProtoRepr for proto::ProofDataHandler {
                     .tee_batch_permanently_ignored_timeout_in_hours
                     .into(),
             ),
+            attestation_policy_enabled: Some(this.tee_config.attestation_policy_enabled),
+            sgx_mrenclave_allowlist: this.tee_config.sgx_mrenclave_allowlist.clone(),
+            sgx_mrsigner_allowlist: this.tee_config.sgx_mrsigner_allowlist.clone(),
+            tdx_mrtd_allowlist: this.tee_config.tdx_mrtd_allowlist.clone(),
+            proof_sampling_rate: this.proof_sampling_rate,
+            proof_sampling_always_prove_ranges: this.proof_sampling_always_prove_ranges.clone(),
+            proof_sampling_never_prove_ranges: this.proof_sampling_never_prove_ranges.clone(),
         }
     }
 }
@@ -1,5 +1,6 @@
 use std::num::NonZeroU64;
 
+use anyhow::Context as _;
 use zksync_config::configs::PruningConfig;
 use zksync_protobuf::ProtoRepr;
 
@@ -14,6 +15,16 @@ impl ProtoRepr for proto::Pruning {
             chunk_size: self.chunk_size,
             removal_delay_sec: self.removal_delay_sec.and_then(NonZeroU64::new),
             data_retention_sec: self.data_retention_sec,
+            call_traces_retention_sec: self.call_traces_retention_sec,
+            events_retention_sec: self.events_retention_sec,
+            dry_run: self.dry_run.unwrap_or_default(),
+            cold_storage_archive_after_sec: self.cold_storage_archive_after_sec,
+            cold_storage_object_store: self
+                .cold_storage_object_store
+                .as_ref()
+                .map(ProtoRepr::read)
+                .transpose()
+                .context("cold_storage_object_store")?,
         })
     }
 
@@ -23,6 +34,14 @@ impl ProtoRepr for proto::Pruning {
             chunk_size: this.chunk_size,
             removal_delay_sec: this.removal_delay_sec.map(|a| a.get()),
             data_retention_sec: this.data_retention_sec,
+            call_traces_retention_sec: this.call_traces_retention_sec,
+            events_retention_sec: this.events_retention_sec,
+            dry_run: Some(this.dry_run),
+            cold_storage_archive_after_sec: this.cold_storage_archive_after_sec,
+            cold_storage_object_store: this
+                .cold_storage_object_store
+                .as_ref()
+                .map(ProtoRepr::build),
         }
     }
 }
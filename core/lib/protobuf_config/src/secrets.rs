@@ -10,7 +10,7 @@ use zksync_config::configs::{
     consensus::{AttesterSecretKey, ConsensusSecrets, NodeSecretKey, ValidatorSecretKey},
     da_client::{avail::AvailSecrets, celestia::CelestiaSecrets, eigen::EigenSecrets},
     secrets::{DataAvailabilitySecrets, Secrets},
-    ContractVerifierSecrets, DatabaseSecrets, L1Secrets,
+    AdminApiSecrets, ContractVerifierSecrets, DatabaseSecrets, L1Secrets,
 };
 use zksync_protobuf::{required, ProtoRepr};
 
@@ -29,6 +29,7 @@ impl ProtoRepr for proto::Secrets {
             l1: read_optional_repr(&self.l1),
             data_availability: read_optional_repr(&self.da),
             contract_verifier: read_optional_repr(&self.contract_verifier),
+            admin_api: read_optional_repr(&self.admin_api),
         })
     }
 
@@ -39,6 +40,7 @@ impl ProtoRepr for proto::Secrets {
             consensus: this.consensus.as_ref().map(ProtoRepr::build),
             da: this.data_availability.as_ref().map(ProtoRepr::build),
             contract_verifier: this.contract_verifier.as_ref().map(ProtoRepr::build),
+            admin_api: this.admin_api.as_ref().map(ProtoRepr::build),
         }
     }
 }
@@ -94,6 +96,12 @@ impl ProtoRepr for proto::L1Secrets {
                 .map(|url| SensitiveUrl::from_str(&url))
                 .transpose()
                 .context("gateway_rpc_url")?,
+            fee_source_rpc_urls: self
+                .fee_source_rpc_urls
+                .iter()
+                .map(|url| SensitiveUrl::from_str(url))
+                .collect::<Result<_, _>>()
+                .context("fee_source_rpc_urls")?,
         })
     }
 
@@ -104,6 +112,11 @@ impl ProtoRepr for proto::L1Secrets {
                 .gateway_rpc_url
                 .as_ref()
                 .map(|url| url.expose_url().to_string()),
+            fee_source_rpc_urls: this
+                .fee_source_rpc_urls
+                .iter()
+                .map(|url| url.expose_str().to_string())
+                .collect(),
         }
     }
 }
@@ -271,3 +284,19 @@ impl ProtoRepr for proto::ContractVerifierSecrets {
         Self { etherscan_api_key }
     }
 }
+
+impl ProtoRepr for proto::AdminApiSecrets {
+    type Type = AdminApiSecrets;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(AdminApiSecrets {
+            auth_token: APIKey::from(required(&self.auth_token).context("auth_token")?.as_str()),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            auth_token: Some(this.auth_token.0.expose_secret().to_string()),
+        }
+    }
+}
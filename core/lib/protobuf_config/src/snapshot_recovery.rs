@@ -44,9 +44,15 @@ impl ProtoRepr for proto::SnapshotRecovery {
                             .map(|a| NonZeroUsize::new(a as usize))
                     })
                     .flatten();
+                let parallel_extension = self
+                    .experimental
+                    .as_ref()
+                    .and_then(|a| a.tree_recovery_parallel_extension)
+                    .unwrap_or_default();
                 TreeRecoveryConfig {
                     chunk_size,
                     parallel_persistence_buffer,
+                    parallel_extension,
                 }
             })
             .unwrap_or_default();
@@ -62,6 +68,11 @@ impl ProtoRepr for proto::SnapshotRecovery {
                 .as_ref()
                 .and_then(|experimental| experimental.drop_storage_key_preimages)
                 .unwrap_or_default(),
+            apply_differential_snapshots: self
+                .experimental
+                .as_ref()
+                .and_then(|experimental| experimental.apply_differential_snapshots)
+                .unwrap_or_default(),
         })
     }
 
@@ -79,6 +90,8 @@ impl ProtoRepr for proto::SnapshotRecovery {
                         .parallel_persistence_buffer
                         .map(|a| a.get() as u64),
                     drop_storage_key_preimages: Some(this.drop_storage_key_preimages),
+                    tree_recovery_parallel_extension: Some(this.tree.parallel_extension),
+                    apply_differential_snapshots: Some(this.apply_differential_snapshots),
                 }),
             )
         };
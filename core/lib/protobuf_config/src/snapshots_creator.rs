@@ -24,6 +24,7 @@ impl ProtoRepr for proto::SnapshotsCreator {
                 .context("storage_logs_chunk_size")?,
             concurrent_queries_count: *required(&self.concurrent_queries_count)
                 .context("concurrent_queries_count")?,
+            max_differential_snapshots: self.max_differential_snapshots.unwrap_or_default(),
             object_store,
         })
     }
@@ -34,6 +35,7 @@ impl ProtoRepr for proto::SnapshotsCreator {
             l1_batch_number: this.l1_batch_number.map(|num| num.0),
             storage_logs_chunk_size: Some(this.storage_logs_chunk_size),
             concurrent_queries_count: Some(this.concurrent_queries_count),
+            max_differential_snapshots: Some(this.max_differential_snapshots),
             object_store: this.object_store.as_ref().map(ProtoRepr::build),
         }
     }
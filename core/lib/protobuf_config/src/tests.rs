@@ -52,6 +52,8 @@ fn test_encoding() {
     test_encode_all_formats::<ReprConv<proto::external_price_api_client::ExternalPriceApiClient>>(
         rng,
     );
+    test_encode_all_formats::<ReprConv<proto::tx_policy::TxPolicy>>(rng);
+    test_encode_all_formats::<ReprConv<proto::batch_exporter::BatchExporter>>(rng);
     test_encode_all_formats::<ReprConv<proto::general::GeneralConfig>>(rng);
 }
 
@@ -0,0 +1,53 @@
+use anyhow::Context as _;
+use zksync_config::configs::TxPolicyConfig;
+use zksync_protobuf::ProtoRepr;
+use zksync_types::Address;
+
+use crate::{parse_h160, proto::tx_policy as proto};
+
+fn read_addresses(addresses: &[String]) -> anyhow::Result<Vec<Address>> {
+    addresses
+        .iter()
+        .enumerate()
+        .map(|(i, address)| parse_h160(address).context(i))
+        .collect()
+}
+
+fn build_addresses(addresses: &[Address]) -> Vec<String> {
+    addresses
+        .iter()
+        .map(|address| format!("{address:?}"))
+        .collect()
+}
+
+impl ProtoRepr for proto::TxPolicy {
+    type Type = TxPolicyConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            allowed_initiators: read_addresses(&self.allowed_initiators)
+                .context("allowed_initiators")?,
+            denied_addresses: read_addresses(&self.denied_addresses).context("denied_addresses")?,
+            allowed_deployers: self
+                .allowed_deployers
+                .as_ref()
+                .map(|list| read_addresses(&list.addresses))
+                .transpose()
+                .context("allowed_deployers")?,
+            max_txs_per_address_per_minute: self.max_txs_per_address_per_minute,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            allowed_initiators: build_addresses(&this.allowed_initiators),
+            denied_addresses: build_addresses(&this.denied_addresses),
+            allowed_deployers: this.allowed_deployers.as_deref().map(|addresses| {
+                proto::AddressList {
+                    addresses: build_addresses(addresses),
+                }
+            }),
+            max_txs_per_address_per_minute: this.max_txs_per_address_per_minute,
+        }
+    }
+}
@@ -3,7 +3,10 @@ use zksync_basic_types::L1BatchNumber;
 use zksync_config::configs;
 use zksync_protobuf::{required, ProtoRepr};
 
-use crate::proto::vm_runner as proto;
+use crate::{
+    experimental::parse_vm_mode,
+    proto::{experimental as experimental_proto, vm_runner as proto},
+};
 
 impl ProtoRepr for proto::ProtectiveReadsWriter {
     type Type = configs::ProtectiveReadsWriterConfig;
@@ -15,6 +18,8 @@ impl ProtoRepr for proto::ProtectiveReadsWriter {
             first_processed_batch: L1BatchNumber(
                 *required(&self.first_processed_batch).context("first_batch")? as u32,
             ),
+            fast_vm_mode: parse_vm_mode(self.fast_vm_mode)?,
+            fail_on_divergence: self.fail_on_divergence.unwrap_or(false),
         })
     }
 
@@ -23,6 +28,8 @@ impl ProtoRepr for proto::ProtectiveReadsWriter {
             db_path: Some(this.db_path.clone()),
             window_size: Some(this.window_size as u64),
             first_processed_batch: Some(this.first_processed_batch.0 as u64),
+            fast_vm_mode: Some(experimental_proto::FastVmMode::new(this.fast_vm_mode).into()),
+            fail_on_divergence: Some(this.fail_on_divergence),
         }
     }
 }
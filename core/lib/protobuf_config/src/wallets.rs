@@ -1,7 +1,10 @@
 use anyhow::Context;
 use zksync_config::configs::{
     self,
-    wallets::{AddressWallet, EthSender, StateKeeper, TokenMultiplierSetter, Wallet},
+    wallets::{
+        AddressWallet, EthSender, SoftConfirmationSigner, StateKeeper, TokenMultiplierSetter,
+        Wallet,
+    },
 };
 use zksync_protobuf::{required, ProtoRepr};
 use zksync_types::{Address, K256PrivateKey};
@@ -34,9 +37,23 @@ impl ProtoRepr for proto::Wallets {
                     .and_then(|a| parse_h160(a).ok()),
             )?;
 
+            let additional_operators = self
+                .additional_operators
+                .iter()
+                .enumerate()
+                .map(|(i, wallet)| {
+                    Wallet::from_private_key_bytes(
+                        parse_h256(required(&wallet.private_key).context("private_key")?)?,
+                        wallet.address.as_ref().and_then(|a| parse_h160(a).ok()),
+                    )
+                    .with_context(|| format!("additional_operators[{i}]"))
+                })
+                .collect::<anyhow::Result<_>>()?;
+
             Some(EthSender {
                 operator,
                 blob_operator,
+                additional_operators,
             })
         } else {
             None
@@ -71,10 +88,28 @@ impl ProtoRepr for proto::Wallets {
                 None
             };
 
+        let soft_confirmation_signer =
+            if let Some(soft_confirmation_signer) = &self.soft_confirmation_signer {
+                let wallet = Wallet::from_private_key_bytes(
+                    parse_h256(
+                        required(&soft_confirmation_signer.private_key)
+                            .context("soft_confirmation_signer")?,
+                    )?,
+                    soft_confirmation_signer
+                        .address
+                        .as_ref()
+                        .and_then(|a| parse_h160(a).ok()),
+                )?;
+                Some(SoftConfirmationSigner { wallet })
+            } else {
+                None
+            };
+
         Ok(Self::Type {
             eth_sender,
             state_keeper,
             token_multiplier_setter,
+            soft_confirmation_signer,
         })
     }
 
@@ -86,21 +121,28 @@ impl ProtoRepr for proto::Wallets {
             }
         };
 
-        let (operator, blob_operator) = if let Some(eth_sender) = &this.eth_sender {
-            let blob = eth_sender
-                .blob_operator
-                .as_ref()
-                .map(|blob| create_pk_wallet(blob.address(), blob.private_key()));
-            (
-                Some(create_pk_wallet(
-                    eth_sender.operator.address(),
-                    eth_sender.operator.private_key(),
-                )),
-                blob,
-            )
-        } else {
-            (None, None)
-        };
+        let (operator, blob_operator, additional_operators) =
+            if let Some(eth_sender) = &this.eth_sender {
+                let blob = eth_sender
+                    .blob_operator
+                    .as_ref()
+                    .map(|blob| create_pk_wallet(blob.address(), blob.private_key()));
+                let additional_operators = eth_sender
+                    .additional_operators
+                    .iter()
+                    .map(|wallet| create_pk_wallet(wallet.address(), wallet.private_key()))
+                    .collect();
+                (
+                    Some(create_pk_wallet(
+                        eth_sender.operator.address(),
+                        eth_sender.operator.private_key(),
+                    )),
+                    blob,
+                    additional_operators,
+                )
+            } else {
+                (None, None, vec![])
+            };
 
         let fee_account = this
             .state_keeper
@@ -119,11 +161,18 @@ impl ProtoRepr for proto::Wallets {
                     )
                 });
 
+        let soft_confirmation_signer = this
+            .soft_confirmation_signer
+            .as_ref()
+            .map(|signer| create_pk_wallet(signer.wallet.address(), signer.wallet.private_key()));
+
         Self {
             blob_operator,
             operator,
             fee_account,
             token_multiplier_setter,
+            soft_confirmation_signer,
+            additional_operators,
         }
     }
 }
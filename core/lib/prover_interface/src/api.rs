@@ -6,7 +6,8 @@ use serde_with::{hex::Hex, serde_as};
 use zksync_types::{
     protocol_version::{L1VerifierConfig, ProtocolSemanticVersion},
     tee_types::TeeType,
-    L1BatchNumber,
+    web3::keccak256,
+    L1BatchNumber, PackedEthSignature, H256,
 };
 
 use crate::{
@@ -32,8 +33,59 @@ pub enum ProofGenerationDataResponse {
     Error(String),
 }
 
+/// Response with TEE verifier input. For backward compatibility, the input is flattened into the response,
+/// so that responses without auxiliary data can be deserialized as [`TeeVerifierInput`].
 #[derive(Debug, Serialize, Deserialize)]
-pub struct TeeProofGenerationDataResponse(pub Box<TeeVerifierInput>);
+pub struct TeeProofGenerationDataResponse {
+    #[serde(flatten)]
+    pub input: Box<TeeVerifierInput>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auxiliary_data: Option<SignedTeeAuxiliaryData>,
+}
+
+/// Kind of auxiliary data attached to a TEE proof generation job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TeeAuxiliaryDataKind {
+    /// Proof of inclusion of the batch pubdata into the DA layer (for validium chains).
+    DaInclusionProof,
+}
+
+impl TeeAuxiliaryDataKind {
+    fn discriminant(self) -> u8 {
+        match self {
+            Self::DaInclusionProof => 0,
+        }
+    }
+}
+
+/// Auxiliary data for an L1 batch that cannot be derived from the TEE verifier input, signed by the operator.
+/// The TEE prover checks the signature before signing the batch.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedTeeAuxiliaryData {
+    pub l1_batch_number: L1BatchNumber,
+    pub kind: TeeAuxiliaryDataKind,
+    #[serde_as(as = "Hex")]
+    pub data: Vec<u8>,
+    pub signature: PackedEthSignature,
+}
+
+impl SignedTeeAuxiliaryData {
+    /// Returns the message signed by the operator: Keccak-256 hash of the L1 batch number (4 bytes, big-endian),
+    /// the data kind discriminant (1 byte) and the data.
+    pub fn signed_message(
+        l1_batch_number: L1BatchNumber,
+        kind: TeeAuxiliaryDataKind,
+        data: &[u8],
+    ) -> H256 {
+        let mut preimage = Vec::with_capacity(5 + data.len());
+        preimage.extend_from_slice(&l1_batch_number.0.to_be_bytes());
+        preimage.push(kind.discriminant());
+        preimage.extend_from_slice(data);
+        H256(keccak256(&preimage))
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum SubmitProofResponse {
@@ -41,6 +93,11 @@ pub enum SubmitProofResponse {
     Error(String),
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub enum SubmitProvingStatsResponse {
+    Success,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum SubmitTeeProofResponse {
     Success,
@@ -51,6 +108,38 @@ pub enum RegisterTeeAttestationResponse {
     Success,
 }
 
+/// Response to any request to the admin API of the proof data handler.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ProofAdminActionResponse {
+    Success,
+}
+
+/// Reason for a proof sampling decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofSamplingReason {
+    /// The decision is derived from the seed and the sampling rate.
+    Rate,
+    /// The batch is in a range of batches that are always proven.
+    AlwaysProveRange,
+    /// The batch is in a range of batches that are never proven.
+    NeverProveRange,
+}
+
+/// Proof sampling decision for an L1 batch. For decisions with [`ProofSamplingReason::Rate`], the batch is sampled
+/// iff the first 8 bytes of `seed` interpreted as a big-endian integer and divided by 2^64 are less than `sampling_rate`,
+/// where `seed` is the Keccak-256 hash of `commitment` concatenated with the big-endian 4-byte L1 batch number.
+/// Thus, the decision can be independently verified given the batch commitment published on L1.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProofSamplingDecisionResponse {
+    pub l1_batch_number: L1BatchNumber,
+    pub sampled: bool,
+    pub reason: ProofSamplingReason,
+    pub commitment: H256,
+    pub seed: H256,
+    pub sampling_rate: f64,
+}
+
 // Structs to hold data necessary for making HTTP requests
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -68,6 +157,16 @@ pub enum SubmitProofRequest {
     SkippedProofGeneration,
 }
 
+/// Statistics reported by the prover subsystem for a proven L1 batch. Used by the sequencer to estimate
+/// witness complexity of recent batches.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SubmitProvingStatsRequest {
+    /// Number of base layer circuits generated for the batch.
+    pub base_layer_circuits: u32,
+    /// Total time spent by provers on all proving jobs of the batch (including recursion layers), in milliseconds.
+    pub proving_time_ms: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct VerifyProofRequest(pub Box<JsonL1BatchProofForL1>);
 
@@ -82,3 +181,25 @@ pub struct RegisterTeeAttestationRequest {
     #[serde_as(as = "Hex")]
     pub pubkey: Vec<u8>,
 }
+
+// Requests to the admin API of the proof data handler. All of these requests must be authenticated
+// and are recorded in the audit log.
+
+/// Returns a batch picked by a prover (or previously skipped) to the proving queue.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RequeueBatchRequest {
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Marks a batch that isn't proven yet as skipped, so that it's not picked by provers.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SkipBatchRequest {
+    pub reason: String,
+}
+
+/// Discards a submitted proof for a batch that wasn't sent to L1 yet and returns the batch to the proving queue.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InvalidateProofRequest {
+    pub reason: String,
+}
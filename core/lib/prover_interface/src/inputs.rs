@@ -1,13 +1,19 @@
-use std::{collections::HashMap, convert::TryInto, fmt::Debug};
+use std::{collections::HashMap, convert::TryInto, fmt, fmt::Debug};
 
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, Bytes};
+use zksync_contracts::BaseSystemContractsHashes;
 use zksync_object_store::{Bucket, StoredObject, _reexports::BoxedError};
 use zksync_types::{
-    basic_fri_types::Eip4844Blobs, block::L2BlockExecutionData, commitment::PubdataParams,
-    witness_block_state::WitnessStorageState, L1BatchNumber, ProtocolVersionId, H256, U256,
+    basic_fri_types::Eip4844Blobs,
+    block::L2BlockExecutionData,
+    bytecode::{validate_bytecode, BytecodeHash},
+    commitment::PubdataParams,
+    u256_to_h256,
+    witness_block_state::WitnessStorageState,
+    L1BatchNumber, ProtocolVersionId, H256, U256,
 };
-use zksync_vm_interface::{L1BatchEnv, SystemEnv};
+use zksync_vm_interface::{L1BatchEnv, PrecompileInputs, SystemEnv};
 
 use crate::{FormatMarker, CBOR};
 
@@ -171,11 +177,89 @@ pub struct VMRunWitnessInputData<FM: FormatMarker = CBOR> {
     pub storage_refunds: Vec<u32>,
     pub pubdata_costs: Vec<i32>,
     pub witness_block_state: WitnessStorageState,
+    /// Hashes of the base system contracts the batch was executed with. Provers should cross-check them
+    /// against the code used for witness generation; see [`Self::check_base_system_contracts()`].
+    /// May be missing for inputs produced by older server versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_system_contracts_hashes: Option<BaseSystemContractsHashes>,
+    /// Inputs of hashing precompile calls in the batch laid out in contiguous buffers, so that they can be consumed
+    /// by hash circuits directly. May be missing for inputs produced by older server versions or by the fast VM.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precompile_inputs: Option<PrecompileInputs>,
 
     #[serde(skip)]
     pub _marker: std::marker::PhantomData<FM>,
 }
 
+impl<FM: FormatMarker> VMRunWitnessInputData<FM> {
+    /// Checks that the bootloader code, default account and EVM emulator hashes in this input correspond
+    /// to the base system contracts the batch was executed with. Inputs without embedded hashes are not checked.
+    pub fn check_base_system_contracts(&self) -> Result<(), BaseSystemContractsMismatch> {
+        let Some(expected) = &self.base_system_contracts_hashes else {
+            return Ok(());
+        };
+
+        let bootloader_code = self.bootloader_code.concat();
+        let bootloader_hash = validate_bytecode(&bootloader_code)
+            .ok()
+            .map(|()| BytecodeHash::for_bytecode(&bootloader_code).value());
+        BaseSystemContractsMismatch::check(
+            "bootloader",
+            Some(expected.bootloader),
+            bootloader_hash,
+        )?;
+        BaseSystemContractsMismatch::check(
+            "default account",
+            expected.default_aa,
+            u256_to_h256(self.default_account_code_hash),
+        )?;
+        BaseSystemContractsMismatch::check(
+            "EVM emulator",
+            expected.evm_emulator,
+            self.evm_emulator_code_hash.map(u256_to_h256),
+        )
+    }
+}
+
+/// Error returned by [`VMRunWitnessInputData::check_base_system_contracts()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BaseSystemContractsMismatch {
+    pub contract: &'static str,
+    pub expected: Option<H256>,
+    pub actual: Option<H256>,
+}
+
+impl BaseSystemContractsMismatch {
+    fn check<T: Into<Option<H256>>>(
+        contract: &'static str,
+        expected: T,
+        actual: T,
+    ) -> Result<(), Self> {
+        let (expected, actual) = (expected.into(), actual.into());
+        if expected == actual {
+            Ok(())
+        } else {
+            Err(Self {
+                contract,
+                expected,
+                actual,
+            })
+        }
+    }
+}
+
+impl fmt::Display for BaseSystemContractsMismatch {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "{} hash mismatch: batch was executed with {:?}, but witness input contains {:?}",
+            self.contract, self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for BaseSystemContractsMismatch {}
+
 impl StoredObject for VMRunWitnessInputData<CBOR> {
     const BUCKET: Bucket = Bucket::WitnessInput;
 
@@ -271,6 +355,52 @@ impl V1TeeVerifierInput {
             pubdata_params,
         }
     }
+
+    /// Splits this input into chunks that can be verified incrementally. The first chunk is always
+    /// [`TeeVerifierInputChunk::Header`]; it's followed by [`TeeVerifierInputChunk::L2Blocks`] chunks, each containing
+    /// at most `max_l2_blocks_per_chunk` L2 blocks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_l2_blocks_per_chunk` is 0.
+    pub fn into_chunks(self, max_l2_blocks_per_chunk: usize) -> Vec<TeeVerifierInputChunk> {
+        assert!(max_l2_blocks_per_chunk > 0, "chunk size must be positive");
+
+        let header = TeeVerifierInputHeader {
+            vm_run_data: self.vm_run_data,
+            merkle_paths: self.merkle_paths,
+            l1_batch_env: self.l1_batch_env,
+            system_env: self.system_env,
+            pubdata_params: self.pubdata_params,
+        };
+        let mut chunks = vec![TeeVerifierInputChunk::Header(header)];
+        let mut blocks = self.l2_blocks_execution_data.into_iter().peekable();
+        while blocks.peek().is_some() {
+            let chunk = blocks.by_ref().take(max_l2_blocks_per_chunk).collect();
+            chunks.push(TeeVerifierInputChunk::L2Blocks(chunk));
+        }
+        chunks
+    }
+}
+
+/// Batch-wide part of [`V1TeeVerifierInput`], i.e. everything except for the executed L2 blocks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeeVerifierInputHeader {
+    pub vm_run_data: VMRunWitnessInputData,
+    pub merkle_paths: WitnessInputMerklePaths,
+    pub l1_batch_env: L1BatchEnv,
+    pub system_env: SystemEnv,
+    pub pubdata_params: PubdataParams,
+}
+
+/// Chunk of [`V1TeeVerifierInput`] used for incremental verification of large L1 batches.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum TeeVerifierInputChunk {
+    /// Batch-wide data. Must be the first chunk for a batch.
+    Header(TeeVerifierInputHeader),
+    /// Consecutive L2 blocks of the batch, including the trailing fictive block.
+    L2Blocks(Vec<L2BlockExecutionData>),
 }
 
 /// Data used as input for the TEE verifier.
@@ -327,4 +457,55 @@ mod tests {
         let logs_from_job: Vec<_> = job.into_merkle_paths().collect();
         assert_eq!(logs_from_job, logs);
     }
+
+    #[test]
+    fn checking_base_system_contracts() {
+        let bootloader_code = vec![[1_u8; 32], [2_u8; 32], [3_u8; 32]];
+        let bootloader_hash = BytecodeHash::for_bytecode(&bootloader_code.concat()).value();
+        let default_aa_hash = H256::repeat_byte(0x0a);
+        let mut data = VMRunWitnessInputData::<CBOR> {
+            l1_batch_number: L1BatchNumber(1),
+            used_bytecodes: HashMap::new(),
+            initial_heap_content: vec![],
+            protocol_version: ProtocolVersionId::latest(),
+            bootloader_code,
+            default_account_code_hash: zksync_types::h256_to_u256(default_aa_hash),
+            evm_emulator_code_hash: None,
+            storage_refunds: vec![],
+            pubdata_costs: vec![],
+            witness_block_state: WitnessStorageState::default(),
+            base_system_contracts_hashes: None,
+            precompile_inputs: None,
+            _marker: std::marker::PhantomData,
+        };
+        data.check_base_system_contracts().unwrap();
+
+        let hashes = BaseSystemContractsHashes {
+            bootloader: bootloader_hash,
+            default_aa: default_aa_hash,
+            evm_emulator: None,
+        };
+        data.base_system_contracts_hashes = Some(hashes);
+        data.check_base_system_contracts().unwrap();
+
+        data.base_system_contracts_hashes = Some(BaseSystemContractsHashes {
+            evm_emulator: Some(H256::repeat_byte(0x0e)),
+            ..hashes
+        });
+        let err = data.check_base_system_contracts().unwrap_err();
+        assert_eq!(err.contract, "EVM emulator");
+
+        data.base_system_contracts_hashes = Some(hashes);
+        data.bootloader_code.pop();
+        let err = data.check_base_system_contracts().unwrap_err();
+        assert_eq!(err.contract, "bootloader");
+        assert_eq!(err.actual, None); // even number of words is an invalid bytecode
+
+        // Check that the hashes survive (de)serialization.
+        data.bootloader_code.push([3; 32]);
+        let serialized = StoredObject::serialize(&data).unwrap();
+        let deserialized =
+            <VMRunWitnessInputData as StoredObject>::deserialize(serialized).unwrap();
+        assert_eq!(deserialized, data);
+    }
 }
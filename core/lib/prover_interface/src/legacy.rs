@@ -64,6 +64,8 @@ impl From<VMRunWitnessInputDataLegacy> for VMRunWitnessInputData<Bincode> {
             storage_refunds: value.storage_refunds,
             pubdata_costs: value.pubdata_costs,
             witness_block_state: value.witness_block_state,
+            base_system_contracts_hashes: None,
+            precompile_inputs: None,
             _marker: std::marker::PhantomData,
         }
     }
@@ -82,6 +84,8 @@ impl From<VMRunWitnessInputData<Bincode>> for VMRunWitnessInputData<CBOR> {
             pubdata_costs: value.pubdata_costs,
             witness_block_state: value.witness_block_state,
             evm_emulator_code_hash: value.evm_emulator_code_hash,
+            base_system_contracts_hashes: value.base_system_contracts_hashes,
+            precompile_inputs: value.precompile_inputs,
             _marker: std::marker::PhantomData,
         }
     }
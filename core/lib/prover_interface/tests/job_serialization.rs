@@ -4,7 +4,9 @@ use bellman::plonk::better_better_cs::proof::Proof;
 use tokio::fs;
 use zksync_object_store::{Bucket, MockObjectStore, StoredObject};
 use zksync_prover_interface::{
-    api::{SubmitProofRequest, SubmitTeeProofRequest},
+    api::{
+        SignedTeeAuxiliaryData, SubmitProofRequest, SubmitTeeProofRequest, TeeAuxiliaryDataKind,
+    },
     inputs::{StorageLogMetadata, WitnessInputMerklePaths},
     outputs::{
         L1BatchProofForL1, L1BatchTeeProofForL1, PlonkL1BatchProofForL1, TypedL1BatchProofForL1,
@@ -12,7 +14,8 @@ use zksync_prover_interface::{
     Bincode, CBOR,
 };
 use zksync_types::{
-    protocol_version::ProtocolSemanticVersion, tee_types::TeeType, L1BatchNumber, ProtocolVersionId,
+    protocol_version::ProtocolSemanticVersion, tee_types::TeeType, K256PrivateKey, L1BatchNumber,
+    PackedEthSignature, ProtocolVersionId, H256,
 };
 
 /// Tests compatibility of the `PrepareBasicCircuitsJob` serialization to the previously used
@@ -215,3 +218,34 @@ fn test_tee_proof_request_serialization() {
     }));
     assert_eq!(tee_proof_result, tee_proof_expected);
 }
+
+#[test]
+fn test_tee_auxiliary_data_serialization() {
+    let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(0x11)).unwrap();
+    let l1_batch_number = L1BatchNumber(42);
+    let kind = TeeAuxiliaryDataKind::DaInclusionProof;
+    let data = vec![1, 2, 3];
+    let message = SignedTeeAuxiliaryData::signed_message(l1_batch_number, kind, &data);
+    let auxiliary_data = SignedTeeAuxiliaryData {
+        l1_batch_number,
+        kind,
+        data,
+        signature: PackedEthSignature::sign_raw(&private_key, &message).unwrap(),
+    };
+
+    let json = serde_json::to_value(&auxiliary_data).unwrap();
+    assert_eq!(json["kind"], "da_inclusion_proof");
+    assert_eq!(json["data"], "010203");
+    let restored: SignedTeeAuxiliaryData = serde_json::from_value(json).unwrap();
+    assert_eq!(restored, auxiliary_data);
+
+    let signer = restored
+        .signature
+        .signature_recover_signer(&message)
+        .unwrap();
+    assert_eq!(signer, private_key.address());
+    // The message must depend on the batch number.
+    let other_message =
+        SignedTeeAuxiliaryData::signed_message(L1BatchNumber(43), kind, &restored.data);
+    assert_ne!(other_message, message);
+}
@@ -15,8 +15,9 @@ use zksync_types::{
     api,
     bytecode::{BytecodeHash, BytecodeMarker},
     snapshots::{
-        SnapshotFactoryDependencies, SnapshotHeader, SnapshotRecoveryStatus, SnapshotStorageLog,
-        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey, SnapshotVersion,
+        uniform_hashed_keys_chunk, SnapshotFactoryDependencies, SnapshotHeader, SnapshotManifest,
+        SnapshotRecoveryStatus, SnapshotStorageLog, SnapshotStorageLogsChunk,
+        SnapshotStorageLogsChunkIndex, SnapshotStorageLogsStorageKey, SnapshotVersion,
     },
     tokens::TokenInfo,
     L1BatchNumber, L2BlockNumber, StorageKey, H256,
@@ -136,6 +137,12 @@ pub trait SnapshotsApplierMainNodeClient: fmt::Debug + Send + Sync {
         l1_batch_number: L1BatchNumber,
     ) -> EnrichedClientResult<Option<SnapshotHeader>>;
 
+    /// Fetches a chain of snapshots for the specified L1 batch, or for the newest snapshot if the L1 batch is not specified.
+    async fn fetch_snapshot_manifest(
+        &self,
+        l1_batch_number: Option<L1BatchNumber>,
+    ) -> EnrichedClientResult<Option<SnapshotManifest>>;
+
     async fn fetch_tokens(
         &self,
         at_l2_block: L2BlockNumber,
@@ -184,6 +191,16 @@ impl SnapshotsApplierMainNodeClient for Box<DynClient<L2>> {
             .await
     }
 
+    async fn fetch_snapshot_manifest(
+        &self,
+        l1_batch_number: Option<L1BatchNumber>,
+    ) -> EnrichedClientResult<Option<SnapshotManifest>> {
+        self.get_snapshot_manifest(l1_batch_number)
+            .rpc_context("get_snapshot_manifest")
+            .with_arg("number", &l1_batch_number)
+            .await
+    }
+
     async fn fetch_tokens(
         &self,
         at_l2_block: L2BlockNumber,
@@ -255,6 +272,7 @@ pub struct SnapshotApplierTaskStats {
 pub struct SnapshotsApplierTask {
     snapshot_l1_batch: Option<L1BatchNumber>,
     drop_storage_key_preimages: bool,
+    apply_differential_snapshots: bool,
     config: SnapshotsApplierConfig,
     health_updater: HealthUpdater,
     connection_pool: ConnectionPool<Core>,
@@ -272,6 +290,7 @@ impl SnapshotsApplierTask {
         Self {
             snapshot_l1_batch: None,
             drop_storage_key_preimages: false,
+            apply_differential_snapshots: false,
             config,
             health_updater: ReactiveHealthCheck::new("snapshot_recovery").1,
             connection_pool,
@@ -330,6 +349,13 @@ impl SnapshotsApplierTask {
         self.drop_storage_key_preimages = true;
     }
 
+    /// Enables recovery from differential snapshots. If enabled, the applier fetches a snapshot manifest from the main node
+    /// and applies the full snapshot together with all differential snapshots based on it. Requires the main node
+    /// to support the `snapshots_getSnapshotManifest` method.
+    pub fn apply_differential_snapshots(&mut self) {
+        self.apply_differential_snapshots = true;
+    }
+
     /// Returns the health check for snapshot recovery.
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
@@ -407,13 +433,90 @@ impl SnapshotsApplierTask {
     }
 }
 
+/// Snapshots applied during recovery.
+#[derive(Debug, Clone)]
+struct SnapshotChain {
+    /// Version of the full snapshot at the start of the chain. Differential snapshots always have version 1.
+    version: SnapshotVersion,
+    /// L1 batch numbers of the full snapshot followed by differential snapshots (if any), in the ascending order.
+    l1_batch_numbers: Vec<L1BatchNumber>,
+    /// Indexes of storage log chunks in the full snapshot ordered by chunk ID. Empty if the snapshot version
+    /// doesn't support chunk indexes.
+    chunk_indexes: Vec<SnapshotStorageLogsChunkIndex>,
+}
+
+impl SnapshotChain {
+    fn full(version: SnapshotVersion, header: &SnapshotHeader) -> anyhow::Result<Self> {
+        let chunk_indexes = if version.has_chunk_indexes() {
+            let chunk_count = header.storage_logs_chunks.len() as u64;
+            header
+                .storage_logs_chunks
+                .iter()
+                .enumerate()
+                .map(|(i, chunk)| {
+                    anyhow::ensure!(
+                        chunk.chunk_id == i as u64,
+                        "storage log chunks in snapshot header are not ordered by chunk ID"
+                    );
+                    let index = chunk.index.with_context(|| {
+                        format!("snapshot header doesn't contain index for chunk {i}")
+                    })?;
+                    let expected_range = uniform_hashed_keys_chunk(chunk.chunk_id, chunk_count);
+                    anyhow::ensure!(
+                        index.hashed_keys_range() == expected_range,
+                        "unexpected hashed key range for chunk {i}: expected {expected_range:?}, got {:?}",
+                        index.hashed_keys_range()
+                    );
+                    Ok(index)
+                })
+                .collect::<anyhow::Result<_>>()?
+        } else {
+            vec![]
+        };
+
+        Ok(Self {
+            version,
+            l1_batch_numbers: vec![header.l1_batch_number],
+            chunk_indexes,
+        })
+    }
+
+    fn from_manifest(manifest: &SnapshotManifest) -> anyhow::Result<Self> {
+        manifest.validate().context("invalid snapshot manifest")?;
+        let version = SnapshotRecoveryStrategy::check_snapshot_version(manifest.base.version)?;
+        if manifest.deltas.is_empty() {
+            return Self::full(version, &manifest.base);
+        }
+        for delta in &manifest.deltas {
+            let delta_version = SnapshotRecoveryStrategy::check_snapshot_version(delta.version)?;
+            anyhow::ensure!(
+                delta_version == SnapshotVersion::Version1 && version == SnapshotVersion::Version1,
+                "differential snapshot for L1 batch #{} and its base must have version 1",
+                delta.l1_batch_number
+            );
+        }
+        Ok(Self {
+            version,
+            l1_batch_numbers: manifest
+                .snapshots()
+                .map(|header| header.l1_batch_number)
+                .collect(),
+            chunk_indexes: vec![],
+        })
+    }
+
+    fn differential_l1_batches(&self) -> &[L1BatchNumber] {
+        &self.l1_batch_numbers[1..]
+    }
+}
+
 /// Strategy determining how snapshot recovery should proceed.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 enum SnapshotRecoveryStrategy {
     /// Snapshot recovery should proceed from scratch with the specified params.
-    New(SnapshotVersion),
+    New(SnapshotChain),
     /// Snapshot recovery should continue with the specified params.
-    Resumed(SnapshotVersion),
+    Resumed(SnapshotChain),
     /// Snapshot recovery has already been completed.
     Completed,
 }
@@ -423,6 +526,7 @@ impl SnapshotRecoveryStrategy {
         storage: &mut Connection<'_, Core>,
         main_node_client: &dyn SnapshotsApplierMainNodeClient,
         snapshot_l1_batch: Option<L1BatchNumber>,
+        apply_differential_snapshots: bool,
     ) -> Result<(Self, SnapshotRecoveryStatus), SnapshotsApplierError> {
         let latency =
             METRICS.initial_stage_duration[&InitialStage::FetchMetadataFromMainNode].start();
@@ -438,19 +542,23 @@ impl SnapshotRecoveryStrategy {
             }
 
             let l1_batch_number = applied_snapshot_status.l1_batch_number;
-            let snapshot_header = main_node_client
-                .fetch_snapshot(l1_batch_number)
-                .await?
-                .with_context(|| {
-                    format!("snapshot for L1 batch #{l1_batch_number} is no longer present on main node")
-                })?;
+            let (_, chain) = Self::fetch_snapshot(
+                main_node_client,
+                Some(l1_batch_number),
+                apply_differential_snapshots,
+            )
+            .await?
+            .with_context(|| {
+                format!(
+                    "snapshot for L1 batch #{l1_batch_number} is no longer present on main node"
+                )
+            })?;
             // Old snapshots can theoretically be removed by the node, but in this case the snapshot data may be removed as well,
             // so returning an error looks appropriate here.
-            let snapshot_version = Self::check_snapshot_version(snapshot_header.version)?;
 
             let latency = latency.observe();
             tracing::info!("Re-initialized snapshots applier after reset/failure in {latency:?}");
-            Ok((Self::Resumed(snapshot_version), applied_snapshot_status))
+            Ok((Self::Resumed(chain), applied_snapshot_status))
         } else {
             let is_genesis_needed = storage.blocks_dal().is_genesis_needed().await?;
             if !is_genesis_needed {
@@ -460,8 +568,12 @@ impl SnapshotRecoveryStrategy {
                 return Err(SnapshotsApplierError::Fatal(err));
             }
 
-            let (recovery_status, snapshot_version) =
-                Self::create_fresh_recovery_status(main_node_client, snapshot_l1_batch).await?;
+            let (recovery_status, chain) = Self::create_fresh_recovery_status(
+                main_node_client,
+                snapshot_l1_batch,
+                apply_differential_snapshots,
+            )
+            .await?;
 
             let storage_logs_count = storage
                 .storage_logs_dal()
@@ -478,14 +590,42 @@ impl SnapshotRecoveryStrategy {
 
             let latency = latency.observe();
             tracing::info!("Initialized fresh snapshots applier in {latency:?}");
-            Ok((Self::New(snapshot_version), recovery_status))
+            Ok((Self::New(chain), recovery_status))
         }
     }
 
-    async fn create_fresh_recovery_status(
+    /// Fetches the snapshot header for the last snapshot in the chain, together with the chain itself.
+    async fn fetch_snapshot(
         main_node_client: &dyn SnapshotsApplierMainNodeClient,
         snapshot_l1_batch: Option<L1BatchNumber>,
-    ) -> Result<(SnapshotRecoveryStatus, SnapshotVersion), SnapshotsApplierError> {
+        apply_differential_snapshots: bool,
+    ) -> Result<Option<(SnapshotHeader, SnapshotChain)>, SnapshotsApplierError> {
+        if apply_differential_snapshots {
+            let Some(manifest) = main_node_client
+                .fetch_snapshot_manifest(snapshot_l1_batch)
+                .await?
+            else {
+                return Ok(None);
+            };
+            let chain = SnapshotChain::from_manifest(&manifest)?;
+            if let Some(l1_batch_number) = snapshot_l1_batch {
+                let target_l1_batch_number = manifest.target().l1_batch_number;
+                if target_l1_batch_number != l1_batch_number {
+                    let err = anyhow::anyhow!(
+                        "main node returned snapshot manifest for L1 batch #{target_l1_batch_number}, \
+                         while L1 batch #{l1_batch_number} was requested"
+                    );
+                    return Err(err.into());
+                }
+            }
+            tracing::info!(
+                "Fetched snapshot manifest with full snapshot for L1 batch #{} and {} differential snapshot(s)",
+                manifest.base.l1_batch_number,
+                manifest.deltas.len()
+            );
+            return Ok(Some((manifest.target().clone(), chain)));
+        }
+
         let l1_batch_number = match snapshot_l1_batch {
             Some(num) => num,
             None => main_node_client
@@ -493,11 +633,33 @@ impl SnapshotRecoveryStrategy {
                 .await?
                 .context("no snapshots on main node; snapshot recovery is impossible")?,
         };
-        let snapshot_response = main_node_client.fetch_snapshot(l1_batch_number).await?;
+        let Some(snapshot) = main_node_client.fetch_snapshot(l1_batch_number).await? else {
+            return Ok(None);
+        };
+        let snapshot_version = Self::check_snapshot_version(snapshot.version)?;
+        let chain = SnapshotChain::full(snapshot_version, &snapshot)?;
+        Ok(Some((snapshot, chain)))
+    }
 
-        let snapshot = snapshot_response.with_context(|| {
-            format!("snapshot for L1 batch #{l1_batch_number} is not present on main node")
+    async fn create_fresh_recovery_status(
+        main_node_client: &dyn SnapshotsApplierMainNodeClient,
+        snapshot_l1_batch: Option<L1BatchNumber>,
+        apply_differential_snapshots: bool,
+    ) -> Result<(SnapshotRecoveryStatus, SnapshotChain), SnapshotsApplierError> {
+        let snapshot_response = Self::fetch_snapshot(
+            main_node_client,
+            snapshot_l1_batch,
+            apply_differential_snapshots,
+        )
+        .await?;
+        let (snapshot, chain) = snapshot_response.with_context(|| {
+            if let Some(l1_batch_number) = snapshot_l1_batch {
+                format!("snapshot for L1 batch #{l1_batch_number} is not present on main node")
+            } else {
+                "no snapshots on main node; snapshot recovery is impossible".to_owned()
+            }
         })?;
+        let l1_batch_number = snapshot.l1_batch_number;
         let l2_block_number = snapshot.l2_block_number;
         tracing::info!(
             "Found snapshot with data up to L1 batch #{l1_batch_number}, L2 block #{l2_block_number}, \
@@ -505,7 +667,6 @@ impl SnapshotRecoveryStrategy {
             version = snapshot.version,
             chunk_count = snapshot.storage_logs_chunks.len()
         );
-        let snapshot_version = Self::check_snapshot_version(snapshot.version)?;
 
         let l1_batch = main_node_client
             .fetch_l1_batch_details(l1_batch_number)
@@ -543,7 +704,7 @@ impl SnapshotRecoveryStrategy {
             protocol_version,
             storage_logs_chunks_processed: vec![false; snapshot.storage_logs_chunks.len()],
         };
-        Ok((status, snapshot_version))
+        Ok((status, chain))
     }
 
     fn check_snapshot_version(raw_version: u16) -> anyhow::Result<SnapshotVersion> {
@@ -553,9 +714,16 @@ impl SnapshotRecoveryStrategy {
             )
         })?;
         anyhow::ensure!(
-            matches!(version, SnapshotVersion::Version0 | SnapshotVersion::Version1),
+            matches!(
+                version,
+                SnapshotVersion::Version0 | SnapshotVersion::Version1 | SnapshotVersion::Version2
+            ),
             "Cannot recover from a snapshot with version {version:?}; the only supported versions are {:?}",
-            [SnapshotVersion::Version0, SnapshotVersion::Version1]
+            [
+                SnapshotVersion::Version0,
+                SnapshotVersion::Version1,
+                SnapshotVersion::Version2
+            ]
         );
         Ok(version)
     }
@@ -579,7 +747,7 @@ impl StorageLogs {
                 let logs: SnapshotStorageLogsChunk<StorageKey> = blob_store.get(key).await?;
                 Ok(Self::V0(logs.storage_logs))
             }
-            SnapshotVersion::Version1 => {
+            SnapshotVersion::Version1 | SnapshotVersion::Version2 => {
                 let logs: SnapshotStorageLogsChunk = blob_store.get(key).await?;
                 Ok(Self::V1(logs.storage_logs))
             }
@@ -618,6 +786,35 @@ impl StorageLogs {
         Ok(())
     }
 
+    /// Applies storage logs from the corresponding chunk of a differential snapshot.
+    fn apply_diff(&mut self, diff: Vec<SnapshotStorageLog>) -> anyhow::Result<()> {
+        let Self::V1(logs) = self else {
+            anyhow::bail!("differential snapshots can only be applied to version 1 snapshots");
+        };
+
+        let mut log_positions: HashMap<_, _> = logs
+            .iter()
+            .enumerate()
+            .map(|(i, log)| (log.key, i))
+            .collect();
+        for log in diff {
+            if let Some(&position) = log_positions.get(&log.key) {
+                let existing_log = &mut logs[position];
+                anyhow::ensure!(
+                    existing_log.enumeration_index == log.enumeration_index
+                        && existing_log.l1_batch_number_of_initial_write
+                            == log.l1_batch_number_of_initial_write,
+                    "differential storage log {log:?} is inconsistent with the base log {existing_log:?}"
+                );
+                existing_log.value = log.value;
+            } else {
+                log_positions.insert(log.key, logs.len());
+                logs.push(log);
+            }
+        }
+        Ok(())
+    }
+
     fn drop_key_preimages(&mut self) {
         match self {
             Self::V0(logs) => {
@@ -651,7 +848,7 @@ struct SnapshotsApplier<'a> {
     blob_store: &'a dyn ObjectStore,
     applied_snapshot_status: SnapshotRecoveryStatus,
     health_updater: &'a HealthUpdater,
-    snapshot_version: SnapshotVersion,
+    snapshot_chain: SnapshotChain,
     max_concurrency: usize,
     drop_storage_key_preimages: bool,
     factory_deps_recovered: bool,
@@ -681,13 +878,14 @@ impl<'a> SnapshotsApplier<'a> {
             &mut storage_transaction,
             main_node_client,
             task.snapshot_l1_batch,
+            task.apply_differential_snapshots,
         )
         .await?;
         tracing::info!("Chosen snapshot recovery strategy: {strategy:?} with status: {applied_snapshot_status:?}");
-        let (created_from_scratch, snapshot_version) = match strategy {
+        let (created_from_scratch, snapshot_chain) = match &strategy {
             SnapshotRecoveryStrategy::Completed => return Ok((strategy, applied_snapshot_status)),
-            SnapshotRecoveryStrategy::New(version) => (true, version),
-            SnapshotRecoveryStrategy::Resumed(version) => (false, version),
+            SnapshotRecoveryStrategy::New(chain) => (true, chain.clone()),
+            SnapshotRecoveryStrategy::Resumed(chain) => (false, chain.clone()),
         };
 
         let mut this = Self {
@@ -696,7 +894,7 @@ impl<'a> SnapshotsApplier<'a> {
             blob_store: task.blob_store.as_ref(),
             applied_snapshot_status,
             health_updater,
-            snapshot_version,
+            snapshot_chain,
             max_concurrency: task.config.max_concurrency.get(),
             drop_storage_key_preimages: task.drop_storage_key_preimages,
             factory_deps_recovered: !created_from_scratch,
@@ -783,8 +981,27 @@ impl<'a> SnapshotsApplier<'a> {
     ) -> Result<(), SnapshotsApplierError> {
         let latency = METRICS.initial_stage_duration[&InitialStage::ApplyFactoryDeps].start();
 
-        tracing::debug!("Fetching factory dependencies from object store");
-        let l1_batch_number = self.applied_snapshot_status.l1_batch_number;
+        // Differential snapshots only contain factory deps added after the base snapshot, so we need to collect
+        // factory deps from all snapshots in the chain.
+        for &l1_batch_number in &self.snapshot_chain.l1_batch_numbers {
+            self.recover_factory_deps_for_snapshot(storage, l1_batch_number)
+                .await?;
+        }
+
+        let latency = latency.observe();
+        tracing::info!("Applied factory dependencies in {latency:?}");
+
+        Ok(())
+    }
+
+    async fn recover_factory_deps_for_snapshot(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<(), SnapshotsApplierError> {
+        tracing::debug!(
+            "Fetching factory dependencies for snapshot L1 batch #{l1_batch_number} from object store"
+        );
         let factory_deps: SnapshotFactoryDependencies =
             self.blob_store.get(l1_batch_number).await.map_err(|err| {
                 let context = format!(
@@ -845,10 +1062,6 @@ impl<'a> SnapshotsApplier<'a> {
                 )
                 .await?;
         }
-
-        let latency = latency.observe();
-        tracing::info!("Applied factory dependencies in {latency:?}");
-
         Ok(())
     }
 
@@ -893,6 +1106,58 @@ impl<'a> SnapshotsApplier<'a> {
         Ok(())
     }
 
+    async fn load_storage_logs(
+        &self,
+        storage_key: SnapshotStorageLogsStorageKey,
+        version: SnapshotVersion,
+    ) -> Result<StorageLogs, SnapshotsApplierError> {
+        StorageLogs::load(self.blob_store, storage_key, version)
+            .await
+            .map_err(|err| {
+                let context =
+                    format!("cannot fetch storage logs {storage_key:?} from object store");
+                SnapshotsApplierError::object_store(err, context)
+            })
+    }
+
+    /// Loads a storage logs chunk from a full snapshot and verifies it against the chunk index. Verification
+    /// doesn't depend on other chunks, so it's performed concurrently for all processed chunks. If verification fails,
+    /// the chunk is re-fetched several times so that a corrupted download doesn't fail the entire recovery.
+    async fn load_verified_storage_logs(
+        &self,
+        storage_key: SnapshotStorageLogsStorageKey,
+        index: &SnapshotStorageLogsChunkIndex,
+    ) -> Result<StorageLogs, SnapshotsApplierError> {
+        const MAX_ATTEMPTS: usize = 3;
+
+        let mut attempt = 1;
+        loop {
+            let chunk: SnapshotStorageLogsChunk =
+                self.blob_store.get(storage_key).await.map_err(|err| {
+                    let context =
+                        format!("cannot fetch storage logs {storage_key:?} from object store");
+                    SnapshotsApplierError::object_store(err, context)
+                })?;
+            let err = match index.verify(&chunk) {
+                Ok(()) => return Ok(StorageLogs::V1(chunk.storage_logs)),
+                Err(err) => err,
+            };
+
+            METRICS.storage_logs_chunks_verification_failures.inc();
+            if attempt == MAX_ATTEMPTS {
+                let err = err.context(format!(
+                    "storage logs {storage_key:?} failed verification after {MAX_ATTEMPTS} attempts; \
+                     the snapshot may be corrupted"
+                ));
+                return Err(SnapshotsApplierError::Fatal(err));
+            }
+            tracing::warn!(
+                "Storage logs {storage_key:?} failed verification (attempt {attempt}/{MAX_ATTEMPTS}): {err:#}; re-fetching"
+            );
+            attempt += 1;
+        }
+    }
+
     #[tracing::instrument(level = "debug", err, skip(self, semaphore))]
     async fn recover_storage_logs_single_chunk(
         &self,
@@ -908,18 +1173,29 @@ impl<'a> SnapshotsApplier<'a> {
 
         let storage_key = SnapshotStorageLogsStorageKey {
             chunk_id,
-            l1_batch_number: self.applied_snapshot_status.l1_batch_number,
+            l1_batch_number: self.snapshot_chain.l1_batch_numbers[0],
+        };
+        let chunk_index = self.snapshot_chain.chunk_indexes.get(chunk_id as usize);
+        let mut storage_logs = if let Some(index) = chunk_index {
+            self.load_verified_storage_logs(storage_key, index).await?
+        } else {
+            self.load_storage_logs(storage_key, self.snapshot_chain.version)
+                .await?
         };
-        let mut storage_logs =
-            StorageLogs::load(self.blob_store, storage_key, self.snapshot_version)
-                .await
-                .map_err(|err| {
-                    let context =
-                        format!("cannot fetch storage logs {storage_key:?} from object store");
-                    SnapshotsApplierError::object_store(err, context)
-                })?;
-
         storage_logs.validate(&self.applied_snapshot_status)?;
+
+        for &l1_batch_number in self.snapshot_chain.differential_l1_batches() {
+            let storage_key = SnapshotStorageLogsStorageKey {
+                chunk_id,
+                l1_batch_number,
+            };
+            let diff = self
+                .load_storage_logs(storage_key, SnapshotVersion::Version1)
+                .await?;
+            diff.validate(&self.applied_snapshot_status)?;
+            storage_logs.apply_diff(diff.without_preimages())?;
+        }
+
         if self.drop_storage_key_preimages {
             storage_logs.drop_key_preimages();
         }
@@ -979,11 +1255,17 @@ impl<'a> SnapshotsApplier<'a> {
             .map(|(chunk_id, _)| {
                 self.recover_storage_logs_single_chunk(&semaphore, chunk_id as u64)
             });
-        let job_completion = futures::future::try_join_all(tasks);
+        // Chunks are processed independently, so a failure in one chunk shouldn't abort processing other chunks;
+        // otherwise, their progress would be lost. Chunks processed successfully will be skipped on retry.
+        let job_completion = futures::future::join_all(tasks);
 
         tokio::select! {
-            res = job_completion => {
-                res?;
+            results = job_completion => {
+                let failed_chunk_count = results.iter().filter(|res| res.is_err()).count();
+                if let Some(err) = results.into_iter().find_map(Result::err) {
+                    tracing::warn!("Failed processing {failed_chunk_count} storage log chunk(s)");
+                    return Err(err);
+                }
             },
             _ = stop_receiver.changed() => {
                 return Err(SnapshotsApplierError::Canceled);
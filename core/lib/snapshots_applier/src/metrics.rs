@@ -2,7 +2,9 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "stage", rename_all = "snake_case")]
@@ -38,6 +40,9 @@ pub(crate) struct SnapshotsApplierMetrics {
     /// Latency of storage log chunk processing split by stage.
     #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
     pub storage_logs_chunks_duration: Family<StorageLogsChunksStage, Histogram<Duration>>,
+
+    /// Number of storage log chunks that failed verification against their index.
+    pub storage_logs_chunks_verification_failures: Counter,
 }
 
 #[vise::register]
@@ -17,8 +17,9 @@ use zksync_types::{
 };
 
 use self::utils::{
-    mock_l2_block_header, mock_recovery_status, mock_snapshot_header, mock_tokens, prepare_clients,
-    random_storage_logs, MockMainNodeClient, ObjectStoreWithErrors,
+    add_differential_snapshot, mock_l2_block_header, mock_recovery_status, mock_snapshot_header,
+    mock_tokens, prepare_clients, prepare_clients_for_v2_snapshot, random_storage_logs,
+    MockMainNodeClient, ObjectStoreWithErrors,
 };
 use super::*;
 use crate::tests::utils::{mock_factory_deps, HangingObjectStore};
@@ -418,6 +419,160 @@ async fn snapshot_applier_recovers_after_stopping() {
     assert_eq!(all_storage_logs.len(), storage_logs.len());
 }
 
+#[tokio::test]
+async fn recovering_from_differential_snapshot() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let base_status = SnapshotRecoveryStatus {
+        l1_batch_number: expected_status.l1_batch_number - 10,
+        l1_batch_root_hash: H256::random(),
+        l2_block_number: expected_status.l2_block_number - 20,
+        l2_block_hash: H256::random(),
+        ..mock_recovery_status()
+    };
+    let factory_deps = mock_factory_deps(None);
+    let base_logs = random_storage_logs::<H256>(base_status.l1_batch_number, 200);
+    let (object_store, mut client) = prepare_clients(&base_status, &factory_deps, &base_logs).await;
+
+    // Base logs are split into 2 chunks of 100 logs each. Each differential chunk updates some of the existing logs
+    // and adds new ones.
+    let mut expected_logs: HashMap<_, _> =
+        base_logs.iter().map(|log| (log.key, log.clone())).collect();
+    let mut next_enumeration_index = base_logs.len() as u64 + 1;
+    let diff_chunks = base_logs.chunks(100).map(|base_chunk| {
+        let updated_logs = base_chunk.iter().step_by(3).map(|log| SnapshotStorageLog {
+            value: H256::random(),
+            ..log.clone()
+        });
+        let mut diff_chunk: Vec<_> = updated_logs.collect();
+        for _ in 0..10 {
+            diff_chunk.push(SnapshotStorageLog {
+                key: H256::random(),
+                value: H256::random(),
+                l1_batch_number_of_initial_write: expected_status.l1_batch_number,
+                enumeration_index: next_enumeration_index,
+            });
+            next_enumeration_index += 1;
+        }
+        expected_logs.extend(diff_chunk.iter().map(|log| (log.key, log.clone())));
+        diff_chunk
+    });
+    let diff_chunks: Vec<_> = diff_chunks.collect();
+    add_differential_snapshot(&*object_store, &mut client, &expected_status, diff_chunks).await;
+
+    let mut task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client),
+        object_store,
+    );
+    task.apply_differential_snapshots();
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let stats = task.run(stop_receiver).await.unwrap();
+    assert!(stats.done_work);
+
+    let mut storage = pool.connection().await.unwrap();
+    let current_db_status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap();
+    assert_eq!(current_db_status.unwrap(), expected_status);
+
+    let all_initial_writes = storage
+        .storage_logs_dedup_dal()
+        .dump_all_initial_writes_for_tests()
+        .await;
+    assert_eq!(all_initial_writes.len(), expected_logs.len());
+    for initial_write in all_initial_writes {
+        let log = &expected_logs[&initial_write.hashed_key];
+        assert_eq!(
+            initial_write.l1_batch_number,
+            log.l1_batch_number_of_initial_write
+        );
+        assert_eq!(initial_write.index, log.enumeration_index);
+    }
+
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), expected_logs.len());
+    for db_log in all_storage_logs {
+        let expected_log = &expected_logs[&db_log.hashed_key];
+        assert_eq!(db_log.value, expected_log.value);
+        assert_eq!(db_log.l2_block_number, expected_status.l2_block_number);
+    }
+}
+
+#[tokio::test]
+async fn resuming_v2_snapshot_recovery_after_chunk_verification_failure() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let storage_logs = random_storage_logs::<H256>(expected_status.l1_batch_number, 200);
+    let (object_store, client) =
+        prepare_clients_for_v2_snapshot(&expected_status, &mock_factory_deps(None), &storage_logs)
+            .await;
+
+    let corrupted_key = SnapshotStorageLogsStorageKey {
+        l1_batch_number: expected_status.l1_batch_number,
+        chunk_id: 1,
+    };
+    let original_chunk: SnapshotStorageLogsChunk = object_store.get(corrupted_key).await.unwrap();
+    let mut corrupted_chunk = original_chunk.clone();
+    corrupted_chunk.storage_logs[0].value = H256::random();
+    object_store
+        .put(corrupted_key, &corrupted_chunk)
+        .await
+        .unwrap();
+
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client.clone()),
+        object_store.clone(),
+    );
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let err = task.run(stop_receiver.clone()).await.unwrap_err();
+    let err = format!("{err:#}");
+    assert!(err.contains("failed verification"), "{err}");
+
+    // The intact chunk must be persisted despite the failure.
+    let mut storage = pool.connection().await.unwrap();
+    let status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap()
+        .expect("no recovery status");
+    assert_eq!(status.storage_logs_chunks_processed, [true, false]);
+
+    object_store
+        .put(corrupted_key, &original_chunk)
+        .await
+        .unwrap();
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client),
+        object_store,
+    );
+    let stats = task.run(stop_receiver).await.unwrap();
+    assert!(stats.done_work);
+
+    let status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap();
+    assert_eq!(status.unwrap(), expected_status);
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), storage_logs.len());
+}
+
 #[tokio::test]
 async fn health_status_immediately_after_task_start() {
     #[derive(Debug, Clone)]
@@ -456,6 +611,14 @@ async fn health_status_immediately_after_task_start() {
             future::pending().await
         }
 
+        async fn fetch_snapshot_manifest(
+            &self,
+            _l1_batch_number: Option<L1BatchNumber>,
+        ) -> EnrichedClientResult<Option<SnapshotManifest>> {
+            self.0.wait().await;
+            future::pending().await
+        }
+
         async fn fetch_tokens(
             &self,
             _at_l2_block: L2BlockNumber,
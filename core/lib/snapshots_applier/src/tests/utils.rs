@@ -14,9 +14,10 @@ use zksync_types::{
     block::L2BlockHeader,
     bytecode::{BytecodeHash, BytecodeMarker},
     snapshots::{
-        SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotHeader,
-        SnapshotRecoveryStatus, SnapshotStorageLog, SnapshotStorageLogsChunk,
-        SnapshotStorageLogsChunkMetadata, SnapshotStorageLogsStorageKey, SnapshotVersion,
+        uniform_hashed_keys_chunk, SnapshotFactoryDependencies, SnapshotFactoryDependency,
+        SnapshotHeader, SnapshotManifest, SnapshotRecoveryStatus, SnapshotStorageLog,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsChunkIndex, SnapshotStorageLogsChunkMetadata,
+        SnapshotStorageLogsStorageKey, SnapshotVersion,
     },
     tokens::{TokenInfo, TokenMetadata},
     web3::Bytes,
@@ -54,6 +55,7 @@ pub(super) struct MockMainNodeClient {
     pub fetch_l1_batch_responses: HashMap<L1BatchNumber, api::L1BatchDetails>,
     pub fetch_l2_block_responses: HashMap<L2BlockNumber, api::BlockDetails>,
     pub fetch_newest_snapshot_response: Option<SnapshotHeader>,
+    pub fetch_snapshot_manifest_response: Option<SnapshotManifest>,
     pub tokens_response: Vec<TokenInfo>,
     pub tokens_response_error: Arc<RwLock<Option<EnrichedClientError>>>,
 }
@@ -105,6 +107,21 @@ impl SnapshotsApplierMainNodeClient for MockMainNodeClient {
             .filter(|response| response.l1_batch_number == l1_batch_number))
     }
 
+    async fn fetch_snapshot_manifest(
+        &self,
+        l1_batch_number: Option<L1BatchNumber>,
+    ) -> EnrichedClientResult<Option<SnapshotManifest>> {
+        let manifest = self.fetch_snapshot_manifest_response.clone().or_else(|| {
+            Some(SnapshotManifest {
+                base: self.fetch_newest_snapshot_response.clone()?,
+                deltas: vec![],
+            })
+        });
+        Ok(manifest.filter(|manifest| {
+            l1_batch_number.map_or(true, |number| manifest.target().l1_batch_number == number)
+        }))
+    }
+
     async fn fetch_tokens(
         &self,
         _at_l2_block: L2BlockNumber,
@@ -228,6 +245,7 @@ fn l1_batch_details(number: L1BatchNumber, root_hash: H256) -> api::L1BatchDetai
     api::L1BatchDetails {
         number,
         base: block_details_base(root_hash),
+        logs_bloom: None,
     }
 }
 
@@ -311,9 +329,11 @@ pub(super) fn mock_snapshot_header(
             .map(|chunk_id| SnapshotStorageLogsChunkMetadata {
                 chunk_id,
                 filepath: format!("file{chunk_id}"),
+                index: None,
             })
             .collect(),
         factory_deps_filepath: "some_filepath".to_string(),
+        base_l1_batch_number: None,
     }
 }
 
@@ -368,6 +388,92 @@ where
     (object_store, client)
 }
 
+/// Prepares clients for a version 2 snapshot. Unlike [`prepare_clients()`], storage logs are split into chunks
+/// by hashed key ranges, and chunk indexes are included into the snapshot header.
+pub(super) async fn prepare_clients_for_v2_snapshot(
+    status: &SnapshotRecoveryStatus,
+    factory_deps: &SnapshotFactoryDependencies,
+    logs: &[SnapshotStorageLog],
+) -> (Arc<dyn ObjectStore>, MockMainNodeClient) {
+    let (object_store, mut client) = prepare_clients(status, factory_deps, logs).await;
+    let chunk_count = status.storage_logs_chunks_processed.len() as u64;
+    let mut header = mock_snapshot_header(SnapshotVersion::Version2.into(), status);
+    for chunk_metadata in &mut header.storage_logs_chunks {
+        let chunk_id = chunk_metadata.chunk_id;
+        let hashed_keys_range = uniform_hashed_keys_chunk(chunk_id, chunk_count);
+        let storage_logs = logs
+            .iter()
+            .filter(|log| hashed_keys_range.contains(&log.key))
+            .cloned()
+            .collect();
+        let chunk = SnapshotStorageLogsChunk { storage_logs };
+        chunk_metadata.index = Some(SnapshotStorageLogsChunkIndex::new(
+            chunk_id,
+            chunk_count,
+            chunk.checksum(),
+        ));
+
+        let chunk_key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: status.l1_batch_number,
+            chunk_id,
+        };
+        object_store.put(chunk_key, &chunk).await.unwrap();
+    }
+    client.fetch_newest_snapshot_response = Some(header);
+    (object_store, client)
+}
+
+/// Adds a differential snapshot for `status` based on the snapshot currently returned by `client`.
+pub(super) async fn add_differential_snapshot(
+    object_store: &dyn ObjectStore,
+    client: &mut MockMainNodeClient,
+    status: &SnapshotRecoveryStatus,
+    chunks: Vec<Vec<SnapshotStorageLog>>,
+) {
+    let base = client
+        .fetch_newest_snapshot_response
+        .clone()
+        .expect("no base snapshot");
+    assert_eq!(chunks.len(), status.storage_logs_chunks_processed.len());
+
+    let factory_deps = SnapshotFactoryDependencies {
+        factory_deps: vec![],
+    };
+    object_store
+        .put(status.l1_batch_number, &factory_deps)
+        .await
+        .unwrap();
+    for (chunk_id, storage_logs) in chunks.into_iter().enumerate() {
+        let chunk_key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: status.l1_batch_number,
+            chunk_id: chunk_id as u64,
+        };
+        object_store
+            .put(chunk_key, &SnapshotStorageLogsChunk { storage_logs })
+            .await
+            .unwrap();
+    }
+
+    let mut header = mock_snapshot_header(SnapshotVersion::Version1.into(), status);
+    header.base_l1_batch_number = Some(base.l1_batch_number);
+    client.fetch_snapshot_manifest_response = Some(SnapshotManifest {
+        base,
+        deltas: vec![header],
+    });
+    client.fetch_l1_batch_responses.insert(
+        status.l1_batch_number,
+        l1_batch_details(status.l1_batch_number, status.l1_batch_root_hash),
+    );
+    client.fetch_l2_block_responses.insert(
+        status.l2_block_number,
+        l2_block_details(
+            status.l2_block_number,
+            status.l1_batch_number,
+            status.l2_block_hash,
+        ),
+    );
+}
+
 /// Object store wrapper that hangs up after processing the specified number of requests.
 /// Used to emulate the snapshot applier being restarted since, if it's configured to have concurrency 1,
 /// the applier will request an object from the store strictly after fully processing all previously requested objects.
@@ -0,0 +1,224 @@
+//! [`ReadStorage`] implementation forking the state of a remote L2 node.
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use tokio::runtime::Handle;
+use zksync_types::{
+    api::{BlockIdVariant, BlockNumber},
+    h256_to_u256, L2BlockNumber, StorageKey, StorageValue, H256,
+};
+use zksync_vm_interface::storage::ReadStorage;
+use zksync_web3_decl::{
+    client::{DynClient, L2},
+    error::{ClientRpcContext, EnrichedClientResult},
+    namespaces::{EthNamespaceClient, ZksNamespaceClient},
+};
+
+/// [`ReadStorage`] implementation lazily fetching storage slots and factory dependencies from a remote L2 node
+/// at a pinned L2 block. Fetched data is cached locally, so each slot / dependency is requested at most once.
+///
+/// Since account code hashes, nonces and balances are stored in system contract slots, they are fetched
+/// in the same way as other storage slots.
+///
+/// The remote node doesn't expose enumeration indices of storage keys, so the storage emulates them:
+/// a key is considered present in the forked state (i.e., a write to it is not initial) iff its value is non-zero,
+/// and present keys are assigned sequential enumeration indices in the order they are accessed. This only affects
+/// pubdata accounting for repeated writes.
+#[derive(Debug)]
+pub struct ForkStorage {
+    rt_handle: Handle,
+    client: Box<DynClient<L2>>,
+    l2_block: L2BlockNumber,
+    values: HashMap<StorageKey, StorageValue>,
+    enumeration_indices: HashMap<StorageKey, u64>,
+    factory_deps: HashMap<H256, Option<Vec<u8>>>,
+}
+
+impl ForkStorage {
+    /// Creates a storage forking the state of the remote node after the specified L2 block. If the block
+    /// is not specified, the latest sealed block of the remote node is used.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RPC errors, and errors if the remote node doesn't have the specified L2 block.
+    pub async fn new(
+        rt_handle: Handle,
+        client: Box<DynClient<L2>>,
+        l2_block: Option<L2BlockNumber>,
+    ) -> anyhow::Result<Self> {
+        let client = client.for_component("fork_storage");
+        let latest_l2_block = client
+            .get_block_number()
+            .rpc_context("get_block_number")
+            .await?;
+        let latest_l2_block = u32::try_from(latest_l2_block)
+            .map_err(|err| anyhow::anyhow!("invalid L2 block number {latest_l2_block}: {err}"))?;
+        let latest_l2_block = L2BlockNumber(latest_l2_block);
+        let l2_block = l2_block.unwrap_or(latest_l2_block);
+        anyhow::ensure!(
+            l2_block <= latest_l2_block,
+            "cannot fork from L2 block #{l2_block}: the latest L2 block on the remote node is #{latest_l2_block}"
+        );
+        tracing::info!("Forking remote node state at L2 block #{l2_block}");
+
+        Ok(Self {
+            rt_handle,
+            client,
+            l2_block,
+            values: HashMap::new(),
+            enumeration_indices: HashMap::new(),
+            factory_deps: HashMap::new(),
+        })
+    }
+
+    /// Returns the L2 block the state is forked at.
+    pub fn l2_block(&self) -> L2BlockNumber {
+        self.l2_block
+    }
+
+    fn block_id(&self) -> BlockIdVariant {
+        BlockIdVariant::BlockNumber(BlockNumber::Number(self.l2_block.0.into()))
+    }
+
+    async fn fetch_value(&self, key: &StorageKey) -> EnrichedClientResult<StorageValue> {
+        self.client
+            .get_storage_at(
+                *key.address(),
+                h256_to_u256(*key.key()),
+                Some(self.block_id()),
+            )
+            .rpc_context("get_storage_at")
+            .with_arg("key", key)
+            .await
+    }
+
+    async fn fetch_factory_dep(&self, hash: H256) -> EnrichedClientResult<Option<Vec<u8>>> {
+        self.client
+            .get_bytecode_by_hash(hash)
+            .rpc_context("get_bytecode_by_hash")
+            .with_arg("hash", &hash)
+            .await
+    }
+}
+
+impl ReadStorage for ForkStorage {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        if let Some(value) = self.values.get(key) {
+            return *value;
+        }
+        let value = self
+            .rt_handle
+            .block_on(self.fetch_value(key))
+            .with_context(|| format!("failed fetching value for {key:?} from the forked node"))
+            .unwrap();
+        self.values.insert(*key, value);
+        value
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        self.read_value(key).is_zero()
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        if let Some(dep) = self.factory_deps.get(&hash) {
+            return dep.clone();
+        }
+        let dep = self
+            .rt_handle
+            .block_on(self.fetch_factory_dep(hash))
+            .with_context(|| {
+                format!("failed fetching factory dependency {hash:?} from the forked node")
+            })
+            .unwrap();
+        self.factory_deps.insert(hash, dep.clone());
+        dep
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        if self.is_write_initial(key) {
+            return None;
+        }
+        let next_index = self.enumeration_indices.len() as u64 + 1;
+        Some(*self.enumeration_indices.entry(*key).or_insert(next_index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use zksync_types::{u256_to_h256, AccountTreeId, Address, U256, U64};
+    use zksync_web3_decl::client::MockClient;
+
+    use super::*;
+
+    fn mock_client(storage_requests: Arc<AtomicUsize>) -> MockClient<L2> {
+        MockClient::builder(L2::default())
+            .method("eth_blockNumber", || Ok(U64::from(10)))
+            .method(
+                "eth_getStorageAt",
+                move |_address: Address, idx: U256, block: Option<BlockIdVariant>| {
+                    storage_requests.fetch_add(1, Ordering::Relaxed);
+                    assert_eq!(
+                        block,
+                        Some(BlockIdVariant::BlockNumber(BlockNumber::Number(5.into())))
+                    );
+                    Ok(u256_to_h256(idx))
+                },
+            )
+            .method("zks_getBytecodeByHash", |hash: H256| {
+                Ok((hash == H256::repeat_byte(1)).then(|| vec![1_u8; 32]))
+            })
+            .build()
+    }
+
+    #[tokio::test]
+    async fn fork_storage_basics() {
+        let storage_requests = Arc::new(AtomicUsize::new(0));
+        let client = mock_client(storage_requests.clone());
+        let err = ForkStorage::new(
+            Handle::current(),
+            Box::new(client.clone()),
+            Some(L2BlockNumber(11)),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("latest L2 block"), "{err:#}");
+
+        let mut storage =
+            ForkStorage::new(Handle::current(), Box::new(client), Some(L2BlockNumber(5)))
+                .await
+                .unwrap();
+        assert_eq!(storage.l2_block(), L2BlockNumber(5));
+
+        tokio::task::spawn_blocking(move || {
+            let account = AccountTreeId::new(Address::repeat_byte(0x23));
+            let zero_key = StorageKey::new(account, H256::zero());
+            let key = StorageKey::new(account, H256::from_low_u64_be(42));
+            let other_key = StorageKey::new(account, H256::from_low_u64_be(23));
+
+            assert_eq!(storage.read_value(&key), H256::from_low_u64_be(42));
+            assert_eq!(storage.read_value(&key), H256::from_low_u64_be(42));
+            assert!(!storage.is_write_initial(&key));
+            assert!(storage.is_write_initial(&zero_key));
+            assert_eq!(storage_requests.load(Ordering::Relaxed), 2);
+
+            assert_eq!(storage.get_enumeration_index(&zero_key), None);
+            assert_eq!(storage.get_enumeration_index(&other_key), Some(1));
+            assert_eq!(storage.get_enumeration_index(&key), Some(2));
+            assert_eq!(storage.get_enumeration_index(&other_key), Some(1));
+
+            assert_eq!(
+                storage.load_factory_dep(H256::repeat_byte(1)),
+                Some(vec![1; 32])
+            );
+            assert_eq!(storage.load_factory_dep(H256::repeat_byte(2)), None);
+        })
+        .await
+        .unwrap();
+    }
+}
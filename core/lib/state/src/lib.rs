@@ -9,24 +9,30 @@
     clippy::doc_markdown // frequent false positive: RocksDB
 )]
 
+pub use zksync_storage::CompactionOptions;
 pub use zksync_vm_interface::storage as interface;
 
 pub use self::{
     cache::sequential_cache::SequentialCache,
-    catchup::{AsyncCatchupTask, RocksdbCell},
-    postgres::{PostgresStorage, PostgresStorageCaches, PostgresStorageCachesTask},
+    catchup::{AsyncCatchupTask, KeepUpdatedTask, RocksdbCell},
+    fork_storage::ForkStorage,
+    postgres::{
+        PostgresStorage, PostgresStorageCaches, PostgresStorageCachesTask,
+        PostgresStorageReadBatcherTask,
+    },
     rocksdb::{
         RocksdbStorage, RocksdbStorageBuilder, RocksdbStorageOptions, StateKeeperColumnFamily,
     },
     shadow_storage::ShadowStorage,
     storage_factory::{
         BatchDiff, BatchDiffs, CommonStorage, OwnedStorage, ReadStorageFactory, RocksdbWithMemory,
-        SnapshotStorage,
+        SnapshotStorage, SnapshotStorageOverlay,
     },
 };
 
 mod cache;
 mod catchup;
+mod fork_storage;
 mod postgres;
 mod rocksdb;
 mod shadow_storage;
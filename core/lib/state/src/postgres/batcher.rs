@@ -0,0 +1,177 @@
+//! Batching of storage slot reads from multiple [`PostgresStorage`](super::PostgresStorage) instances.
+
+use std::{collections::HashMap, time::Duration};
+
+use tokio::sync::{mpsc, oneshot, watch};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::{L2BlockNumber, StorageValue, H256};
+
+use super::metrics::{BatchedReadOutcome, READ_BATCHER_METRICS};
+
+#[derive(Debug)]
+struct ReadRequest {
+    hashed_key: H256,
+    l2_block_number: L2BlockNumber,
+    response_sender: oneshot::Sender<StorageValue>,
+}
+
+/// Handle allowing to send read requests to [`PostgresStorageReadBatcherTask`].
+#[derive(Debug, Clone)]
+pub(super) struct ReadBatcher {
+    request_sender: mpsc::UnboundedSender<ReadRequest>,
+}
+
+impl ReadBatcher {
+    /// Enqueues a read request. Returns `None` if the batcher task is not running.
+    pub(super) fn read_value(
+        &self,
+        hashed_key: H256,
+        l2_block_number: L2BlockNumber,
+    ) -> Option<oneshot::Receiver<StorageValue>> {
+        let (response_sender, response_receiver) = oneshot::channel();
+        let request = ReadRequest {
+            hashed_key,
+            l2_block_number,
+            response_sender,
+        };
+        self.request_sender.send(request).ok()?;
+        Some(response_receiver)
+    }
+}
+
+/// Asynchronous task that coalesces storage slot reads from concurrently executing VMs into batched Postgres queries.
+///
+/// Reads are collected during a short time window after the first request in a batch is received. Requests in the batch
+/// are then grouped by the L2 block, and each group is served with a single query.
+#[derive(Debug)]
+pub struct PostgresStorageReadBatcherTask {
+    connection_pool: ConnectionPool<Core>,
+    window: Duration,
+    request_receiver: mpsc::UnboundedReceiver<ReadRequest>,
+}
+
+impl PostgresStorageReadBatcherTask {
+    /// Maximum number of read requests in a single batch.
+    const MAX_BATCH_SIZE: usize = 1_024;
+
+    pub(super) fn new(
+        connection_pool: ConnectionPool<Core>,
+        window: Duration,
+    ) -> (Self, ReadBatcher) {
+        let (request_sender, request_receiver) = mpsc::unbounded_channel();
+        let this = Self {
+            connection_pool,
+            window,
+            request_receiver,
+        };
+        (this, ReadBatcher { request_sender })
+    }
+
+    /// Runs the task.
+    ///
+    /// ## Errors
+    ///
+    /// Only returns an error if the stop signal sender is dropped. Postgres errors are not propagated; if serving
+    /// a batch fails, requesting storage instances fall back to querying Postgres directly.
+    #[tracing::instrument(name = "PostgresStorageReadBatcherTask::run", skip_all)]
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(window = ?self.window, "Starting task");
+
+        loop {
+            let first_request = tokio::select! {
+                _ = stop_receiver.changed() => break,
+                request = self.request_receiver.recv() => {
+                    if let Some(request) = request {
+                        request
+                    } else {
+                        // All storage caches have been dropped, which means that we must receive the stop signal soon.
+                        stop_receiver.changed().await?;
+                        break;
+                    }
+                }
+            };
+
+            let mut batch = vec![first_request];
+            let deadline = tokio::time::Instant::now() + self.window;
+            while batch.len() < Self::MAX_BATCH_SIZE {
+                match tokio::time::timeout_at(deadline, self.request_receiver.recv()).await {
+                    Ok(Some(request)) => batch.push(request),
+                    Ok(None) | Err(_) => break,
+                }
+            }
+            self.serve_batch(batch).await;
+        }
+        tracing::info!("Stop signal received, read batcher is shutting down");
+        Ok(())
+    }
+
+    async fn serve_batch(&self, batch: Vec<ReadRequest>) {
+        READ_BATCHER_METRICS.batch_size.observe(batch.len());
+
+        let mut requests_by_block = HashMap::<_, HashMap<_, Vec<_>>>::new();
+        for request in batch {
+            requests_by_block
+                .entry(request.l2_block_number)
+                .or_default()
+                .entry(request.hashed_key)
+                .or_default()
+                .push(request.response_sender);
+        }
+        READ_BATCHER_METRICS
+            .queries_per_batch
+            .observe(requests_by_block.len());
+
+        let mut connection = match self
+            .connection_pool
+            .connection_tagged("storage_read_batcher")
+            .await
+        {
+            Ok(connection) => connection,
+            Err(err) => {
+                tracing::warn!("Failed acquiring connection for batched reads: {err}");
+                READ_BATCHER_METRICS.outcome[&BatchedReadOutcome::Failed]
+                    .inc_by(requests_by_block.len() as u64);
+                return; // Dropping response senders will make requesters fall back to direct reads
+            }
+        };
+
+        for (l2_block_number, requests) in requests_by_block {
+            let hashed_keys: Vec<_> = requests.keys().copied().collect();
+            READ_BATCHER_METRICS
+                .keys_per_query
+                .observe(hashed_keys.len());
+
+            let latency = READ_BATCHER_METRICS.query_latency.start();
+            let values = connection
+                .storage_logs_dal()
+                .get_storage_values(&hashed_keys, l2_block_number)
+                .await;
+            latency.observe();
+            let values = match values {
+                Ok(values) => values,
+                Err(err) => {
+                    tracing::warn!(
+                        %l2_block_number,
+                        "Failed batch-reading {} storage values: {err}",
+                        hashed_keys.len()
+                    );
+                    READ_BATCHER_METRICS.outcome[&BatchedReadOutcome::Failed].inc();
+                    continue;
+                }
+            };
+            READ_BATCHER_METRICS.outcome[&BatchedReadOutcome::Succeeded].inc();
+
+            for (hashed_key, response_senders) in requests {
+                let value = values.get(&hashed_key).copied().flatten();
+                let value = value.unwrap_or_default();
+                READ_BATCHER_METRICS
+                    .served_reads
+                    .inc_by(response_senders.len() as u64);
+                for sender in response_senders {
+                    // The requester may have been dropped in the meantime; this is fine.
+                    sender.send(value).ok();
+                }
+            }
+        }
+    }
+}
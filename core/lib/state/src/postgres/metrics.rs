@@ -60,3 +60,38 @@ pub(super) struct PostgresStorageMetrics {
 
 #[vise::register]
 pub(super) static STORAGE_METRICS: vise::Global<PostgresStorageMetrics> = vise::Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
+#[metrics(label = "outcome", rename_all = "snake_case")]
+pub(super) enum BatchedReadOutcome {
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "state_postgres_read_batcher")]
+pub(super) struct ReadBatcherMetrics {
+    /// Number of read requests in a single batch.
+    #[metrics(buckets = Buckets::exponential(1.0..=1_024.0, 2.0))]
+    pub batch_size: Histogram<usize>,
+    /// Number of Postgres queries (i.e., distinct L2 blocks) necessary to serve a single batch.
+    #[metrics(buckets = Buckets::exponential(1.0..=1_024.0, 2.0))]
+    pub queries_per_batch: Histogram<usize>,
+    /// Number of distinct storage keys read in a single query.
+    #[metrics(buckets = Buckets::exponential(1.0..=1_024.0, 2.0))]
+    pub keys_per_query: Histogram<usize>,
+    /// Latency of a batched Postgres query.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub query_latency: Histogram<Duration>,
+    /// Number of batched queries by outcome.
+    pub outcome: Family<BatchedReadOutcome, Counter>,
+    /// Total number of read requests served by batched queries. Together with `outcome`, can be used to estimate
+    /// coalescing efficiency (i.e., the average number of reads served by a single query).
+    pub served_reads: Counter,
+    /// Number of read requests that fell back to querying Postgres directly because the batcher failed
+    /// to serve them.
+    pub fallback_reads: Counter,
+}
+
+#[vise::register]
+pub(super) static READ_BATCHER_METRICS: vise::Global<ReadBatcherMetrics> = vise::Global::new();
@@ -11,9 +11,14 @@ use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_types::{L1BatchNumber, L2BlockNumber, StorageKey, StorageValue, H256};
 use zksync_vm_interface::storage::ReadStorage;
 
-use self::metrics::{Method, ValuesUpdateStage, CACHE_METRICS, STORAGE_METRICS};
+pub use self::batcher::PostgresStorageReadBatcherTask;
+use self::{
+    batcher::ReadBatcher,
+    metrics::{Method, ValuesUpdateStage, CACHE_METRICS, READ_BATCHER_METRICS, STORAGE_METRICS},
+};
 use crate::cache::{lru_cache::LruCache, CacheValue};
 
+mod batcher;
 mod metrics;
 #[cfg(test)]
 mod tests;
@@ -279,6 +284,7 @@ pub struct PostgresStorageCaches {
     // it wasn't written to at the point that interests us.
     negative_initial_writes: InitialWritesCache,
     values: Option<ValuesCacheAndUpdater>,
+    read_batcher: Option<ReadBatcher>,
 }
 
 impl PostgresStorageCaches {
@@ -300,6 +306,7 @@ impl PostgresStorageCaches {
                 initial_writes_capacity / 2,
             ),
             values: None,
+            read_batcher: None,
         }
     }
 
@@ -342,6 +349,23 @@ impl PostgresStorageCaches {
         }
     }
 
+    /// Configures batching of storage slot reads that miss the caches. If enabled, reads from all storage instances
+    /// using these caches are collected during `window` and served with a single Postgres query per L2 block.
+    /// This trades off a slightly increased latency for individual reads for a greatly reduced number of Postgres queries
+    /// under high load (e.g., many concurrent `eth_call`s).
+    ///
+    /// The returned task must be spawned on the Tokio runtime; otherwise, reads will fall back to direct Postgres queries.
+    pub fn configure_read_batching(
+        &mut self,
+        window: Duration,
+        connection_pool: ConnectionPool<Core>,
+    ) -> PostgresStorageReadBatcherTask {
+        tracing::debug!("Initializing storage read batching with {window:?} window");
+        let (task, batcher) = PostgresStorageReadBatcherTask::new(connection_pool, window);
+        self.read_batcher = Some(batcher);
+        task
+    }
+
     /// Schedules an update of the VM storage values cache to the specified L2 block. If the values cache is not configured,
     /// this is a no-op.
     ///
@@ -537,6 +561,16 @@ impl<'a> PostgresStorage<'a> {
         Some(&self.caches.as_ref()?.values.as_ref()?.cache)
     }
 
+    fn read_value_batched(&self, hashed_key: H256) -> Option<StorageValue> {
+        let batcher = self.caches.as_ref()?.read_batcher.as_ref()?;
+        let response = batcher.read_value(hashed_key, self.l2_block_number);
+        let value = response.and_then(|response| self.rt_handle.block_on(response).ok());
+        if value.is_none() {
+            READ_BATCHER_METRICS.fallback_reads.inc();
+        }
+        value
+    }
+
     /// Returns the wrapped connection.
     pub fn into_inner(self) -> Connection<'a, Core> {
         self.connection
@@ -555,6 +589,13 @@ impl ReadStorage for PostgresStorage<'_> {
             const RETRY_INTERVAL: Duration = Duration::from_millis(500);
             const MAX_TRIES: usize = 20;
 
+            if let Some(value) = self.read_value_batched(hashed_key) {
+                if let Some(cache) = self.values_cache() {
+                    cache.insert(self.l2_block_number, hashed_key, value);
+                }
+                return value;
+            }
+
             let mut dal = self.connection.storage_web3_dal();
             let value = (|| {
                 self.rt_handle
@@ -12,7 +12,10 @@ use zksync_dal::ConnectionPool;
 use zksync_types::StorageLog;
 
 use super::*;
-use crate::test_utils::{create_l1_batch, create_l2_block, gen_storage_logs, prepare_postgres};
+use crate::test_utils::{
+    create_l1_batch, create_l2_block, gen_storage_logs, prepare_postgres,
+    prepare_postgres_with_log_count,
+};
 
 fn test_postgres_storage_basics(
     pool: &ConnectionPool<Core>,
@@ -586,6 +589,63 @@ async fn using_values_cache() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn using_read_batching() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut connection = pool.connection().await.unwrap();
+    let genesis_logs = prepare_postgres_with_log_count(&mut connection, 20).await;
+    let updated_key = genesis_logs[0].key;
+    let new_logs = [StorageLog::new_write_log(updated_key, H256::repeat_byte(1))];
+    create_l2_block(&mut connection, L2BlockNumber(1), &new_logs).await;
+    drop(connection);
+
+    let mut caches = PostgresStorageCaches::new(1_024, 1_024);
+    let task = caches.configure_read_batching(Duration::from_millis(10), pool.clone());
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let task_handle = tokio::spawn(task.run(stop_receiver));
+
+    let read_all_values = move |pool: ConnectionPool<Core>,
+                                caches: PostgresStorageCaches,
+                                l2_block_number: L2BlockNumber| {
+        let rt_handle = Handle::current();
+        let connection = rt_handle.block_on(pool.connection()).unwrap();
+        let mut storage =
+            PostgresStorage::new(rt_handle, connection, l2_block_number, true).with_caches(caches);
+        for log in &genesis_logs {
+            let expected_value = if log.key == updated_key && l2_block_number == L2BlockNumber(1) {
+                H256::repeat_byte(1)
+            } else {
+                log.value
+            };
+            assert_eq!(storage.read_value(&log.key), expected_value);
+        }
+        let non_existing_key = gen_storage_logs(100..101)[0].key;
+        assert_eq!(storage.read_value(&non_existing_key), StorageValue::zero());
+    };
+
+    // Concurrently read values for different L2 blocks, so that batches contain reads for both of them.
+    let read_tasks: Vec<_> = (0..4)
+        .map(|i| {
+            let read_all_values = read_all_values.clone();
+            let (pool, caches) = (pool.clone(), caches.clone());
+            tokio::task::spawn_blocking(move || {
+                read_all_values(pool, caches, L2BlockNumber(i % 2));
+            })
+        })
+        .collect();
+    for task in read_tasks {
+        task.await.unwrap();
+    }
+
+    stop_sender.send_replace(true);
+    task_handle.await.unwrap().unwrap();
+
+    // Reads should fall back to direct Postgres queries after the batcher is stopped.
+    tokio::task::spawn_blocking(move || read_all_values(pool, caches, L2BlockNumber(1)))
+        .await
+        .unwrap();
+}
+
 /// (Sort of) fuzzes [`ValuesCache`] by comparing outputs of [`PostgresStorage`] with and without caching
 /// on randomly generated `read_value()` queries.
 fn mini_fuzz_values_cache_inner(
@@ -31,7 +31,7 @@ use anyhow::Context as _;
 use itertools::{Either, Itertools};
 use tokio::sync::watch;
 use zksync_dal::{Connection, Core, CoreDal, DalError};
-use zksync_storage::{db::NamedColumnFamily, RocksDB, RocksDBOptions};
+use zksync_storage::{db::NamedColumnFamily, CompactionOptions, RocksDB, RocksDBOptions};
 use zksync_types::{L1BatchNumber, StorageKey, StorageValue, H256};
 use zksync_vm_interface::storage::ReadStorage;
 
@@ -75,6 +75,10 @@ impl NamedColumnFamily for StateKeeperColumnFamily {
             Self::FactoryDeps => "factory_deps",
         }
     }
+
+    fn requires_tuning(&self) -> bool {
+        matches!(self, Self::State)
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -133,6 +137,14 @@ pub struct RocksdbStorageOptions {
     /// Number of open files that can be simultaneously opened by RocksDB. Default is `None`, for no limit.
     /// Can be used to restrict memory usage of RocksDB.
     pub max_open_files: Option<NonZeroU32>,
+    /// Size of the dedicated block cache for the state column family in bytes. If set, storage slot reads
+    /// will not compete with bytecode reads for the block cache (bytecodes are much larger than slot values,
+    /// and can evict hot slots from the shared cache). Default is `None`, meaning that the state column family
+    /// uses the shared block cache.
+    pub state_block_cache_capacity: Option<usize>,
+    /// Compaction options for the state column family. Can be used to reduce the frequency of write stalls
+    /// for large states.
+    pub state_compaction: CompactionOptions,
 }
 
 impl Default for RocksdbStorageOptions {
@@ -140,6 +152,8 @@ impl Default for RocksdbStorageOptions {
         Self {
             block_cache_capacity: 128 << 20,
             max_open_files: None,
+            state_block_cache_capacity: None,
+            state_compaction: CompactionOptions::default(),
         }
     }
 }
@@ -149,6 +163,8 @@ impl RocksdbStorageOptions {
         RocksDBOptions {
             block_cache_capacity: Some(self.block_cache_capacity),
             max_open_files: self.max_open_files,
+            large_cf_block_cache_capacity: self.state_block_cache_capacity,
+            large_cf_compaction: self.state_compaction,
             ..RocksDBOptions::default()
         }
     }
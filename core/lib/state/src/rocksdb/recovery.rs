@@ -1,15 +1,18 @@
 //! Logic for [`RocksdbStorage`] related to snapshot recovery.
 
-use std::{num::NonZeroU32, ops};
+use std::{collections::HashMap, num::NonZeroU32, ops};
 
 use anyhow::Context as _;
 use tokio::sync::watch;
 use zksync_dal::{storage_logs_dal::StorageRecoveryLogEntry, Connection, Core, CoreDal};
-use zksync_types::{snapshots::uniform_hashed_keys_chunk, L1BatchNumber, L2BlockNumber, H256};
+use zksync_types::{
+    snapshots::{uniform_hashed_keys_chunk, SnapshotStorageLog},
+    L1BatchNumber, L2BlockNumber, H256,
+};
 
 use super::{
     metrics::{ChunkRecoveryStage, RecoveryStage, RECOVERY_METRICS},
-    RocksdbStorage, RocksdbSyncError, StateValue,
+    RocksdbStorage, RocksdbStorageBuilder, RocksdbSyncError, StateValue,
 };
 
 #[derive(Debug)]
@@ -299,3 +302,53 @@ impl RocksdbStorage {
         Ok(())
     }
 }
+
+/// Recovery from snapshot files in an object store. Unlike recovery in [`RocksdbStorageBuilder::ensure_ready()`],
+/// it doesn't require a Postgres instance with the recovered snapshot; the caller is responsible for loading
+/// snapshot data.
+impl RocksdbStorageBuilder {
+    /// Returns the storage if it's initialized (i.e., has a recovered snapshot or is synchronized with Postgres),
+    /// without synchronizing it.
+    pub async fn into_initialized(self) -> Option<RocksdbStorage> {
+        self.0.l1_batch_number().await?;
+        Some(self.0)
+    }
+
+    /// Saves factory dependencies from snapshot files.
+    pub async fn save_snapshot_factory_deps(
+        &mut self,
+        factory_deps: HashMap<H256, Vec<u8>>,
+    ) -> anyhow::Result<()> {
+        self.0.pending_patch.factory_deps = factory_deps;
+        self.0
+            .save(None)
+            .await
+            .context("failed saving factory deps")
+    }
+
+    /// Saves a chunk of storage logs from snapshot files. Values for the already saved keys are overwritten,
+    /// so chunks of differential snapshots can be saved after the corresponding chunk of the full snapshot.
+    pub async fn save_snapshot_storage_logs(
+        &mut self,
+        storage_logs: Vec<SnapshotStorageLog>,
+    ) -> anyhow::Result<()> {
+        self.0.pending_patch.state = storage_logs
+            .into_iter()
+            .map(|log| (log.key, (log.value, log.enumeration_index)))
+            .collect();
+        self.0
+            .save(None)
+            .await
+            .context("failed saving storage logs chunk")
+    }
+
+    /// Marks recovery from snapshot files as complete. Until this method is called, the storage is considered
+    /// uninitialized, so an interrupted recovery should be restarted from scratch.
+    pub async fn finish_snapshot_recovery(
+        mut self,
+        snapshot_l1_batch: L1BatchNumber,
+    ) -> anyhow::Result<RocksdbStorage> {
+        self.0.save(Some(snapshot_l1_batch + 1)).await?;
+        Ok(self.0)
+    }
+}
@@ -7,7 +7,7 @@ use tempfile::TempDir;
 use test_casing::test_casing;
 use tokio::sync::RwLock;
 use zksync_dal::{ConnectionPool, Core};
-use zksync_types::{L2BlockNumber, StorageLog};
+use zksync_types::{snapshots::SnapshotStorageLog, AccountTreeId, L2BlockNumber, StorageLog};
 
 use super::*;
 use crate::test_utils::{
@@ -46,12 +46,31 @@ fn hash_storage_log_keys(logs: &HashMap<StorageKey, H256>) -> HashMap<H256, H256
         .collect()
 }
 
+#[test_casing(2, [false, true])]
 #[tokio::test]
-async fn rocksdb_storage_basics() {
+async fn rocksdb_storage_basics(tuned_state_cf: bool) {
     let dir = TempDir::new().expect("cannot create temporary dir for state keeper");
-    let mut storage = RocksdbStorage::new(dir.path().into(), RocksdbStorageOptions::default())
+    let mut options = RocksdbStorageOptions::default();
+    if tuned_state_cf {
+        options.state_block_cache_capacity = Some(16 << 20);
+        options.state_compaction = CompactionOptions {
+            level0_slowdown_writes_trigger: NonZeroU32::new(40),
+            level0_stop_writes_trigger: NonZeroU32::new(60),
+            soft_pending_compaction_bytes_limit: Some(128 << 30),
+            hard_pending_compaction_bytes_limit: Some(512 << 30),
+            max_subcompactions: NonZeroU32::new(2),
+        };
+    }
+    let mut storage = RocksdbStorage::new(dir.path().into(), options)
         .await
         .unwrap();
+    assert_eq!(
+        storage
+            .db
+            .dedicated_block_cache_stats(StateKeeperColumnFamily::State)
+            .is_some(),
+        tuned_state_cf
+    );
     let mut storage_logs: HashMap<_, _> = gen_storage_logs(0..20)
         .into_iter()
         .map(|log| (log.key, log.value))
@@ -333,6 +352,62 @@ async fn recovering_factory_deps_from_snapshot() {
     }
 }
 
+#[tokio::test]
+async fn recovering_from_snapshot_files() {
+    let dir = TempDir::new().expect("cannot create temporary dir for state keeper");
+    let mut builder = RocksdbStorage::builder(dir.path()).await.unwrap();
+    let snapshot_l1_batch = L1BatchNumber(23);
+    let storage_keys: Vec<_> = (0..10_u64)
+        .map(|i| StorageKey::new(AccountTreeId::default(), H256::from_low_u64_be(i)))
+        .collect();
+    let storage_logs = storage_keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| SnapshotStorageLog {
+            key: key.hashed_key(),
+            value: H256::repeat_byte(1),
+            l1_batch_number_of_initial_write: L1BatchNumber(1),
+            enumeration_index: i as u64 + 1,
+        });
+    let bytecode_hash = H256::repeat_byte(0xff);
+    builder
+        .save_snapshot_factory_deps(HashMap::from([(bytecode_hash, vec![1; 32])]))
+        .await
+        .unwrap();
+    builder
+        .save_snapshot_storage_logs(storage_logs.clone().collect())
+        .await
+        .unwrap();
+    // Emulate a differential snapshot chunk overwriting some of the logs.
+    let diff_logs = storage_logs
+        .step_by(2)
+        .map(|log| SnapshotStorageLog {
+            value: H256::repeat_byte(2),
+            ..log
+        })
+        .collect();
+    builder.save_snapshot_storage_logs(diff_logs).await.unwrap();
+    assert_eq!(builder.l1_batch_number().await, None);
+
+    let mut storage = builder
+        .finish_snapshot_recovery(snapshot_l1_batch)
+        .await
+        .unwrap();
+    assert_eq!(storage.l1_batch_number().await, Some(snapshot_l1_batch + 1));
+    for (i, key) in storage_keys.iter().enumerate() {
+        let expected_value = H256::repeat_byte(if i % 2 == 0 { 2 } else { 1 });
+        assert_eq!(storage.read_value(key), expected_value);
+        assert_eq!(storage.get_enumeration_index(key), Some(i as u64 + 1));
+    }
+    assert_eq!(storage.load_factory_dep(bytecode_hash), Some(vec![1; 32]));
+
+    // The recovered storage should be returned as is after reopening.
+    drop(storage);
+    let builder = RocksdbStorage::builder(dir.path()).await.unwrap();
+    let storage = builder.into_initialized().await.unwrap();
+    assert_eq!(storage.l1_batch_number().await, Some(snapshot_l1_batch + 1));
+}
+
 #[tokio::test]
 async fn recovering_from_snapshot_and_following_logs() {
     let pool = ConnectionPool::<Core>::test_pool().await;
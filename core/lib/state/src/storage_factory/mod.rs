@@ -10,12 +10,14 @@ use zksync_vm_interface::storage::{ReadStorage, StorageSnapshot};
 
 use self::metrics::{SnapshotStage, SNAPSHOT_METRICS};
 pub use self::{
+    overlay::SnapshotStorageOverlay,
     rocksdb_with_memory::{BatchDiff, BatchDiffs, RocksdbWithMemory},
     snapshot::SnapshotStorage,
 };
 use crate::{PostgresStorage, RocksdbStorage, RocksdbStorageBuilder, StateKeeperColumnFamily};
 
 mod metrics;
+mod overlay;
 mod rocksdb_with_memory;
 mod snapshot;
 
@@ -0,0 +1,293 @@
+use std::sync::Arc;
+
+use zksync_types::{StorageKey, StorageValue, H256};
+use zksync_vm_interface::storage::ReadStorage;
+
+use super::BatchDiff;
+
+/// Immutable layer of [`SnapshotStorageOverlay`]. Layers form a singly linked list from the newest
+/// to the oldest diff, so that overlays branching from the same point share all their common layers.
+#[derive(Debug)]
+struct OverlayLayer {
+    diff: BatchDiff,
+    parent: Option<Arc<OverlayLayer>>,
+}
+
+/// Storage overlay that stacks uncommitted batch diffs over a base storage view (e.g., [`RocksdbStorage`](crate::RocksdbStorage)
+/// or [`PostgresStorage`](crate::PostgresStorage)).
+///
+/// Cloning an overlay is cheap: diffs are shared among all clones, and only the base storage is cloned.
+/// Pushing a diff to a clone doesn't affect the original overlay or other clones. Thus, multiple speculative
+/// executions can branch from the same state without copying diffs.
+///
+/// Diffs are looked up from the newest to the oldest one on each access, so an overlay with many diffs
+/// should be periodically [squashed](Self::squash()).
+#[derive(Debug, Clone)]
+pub struct SnapshotStorageOverlay<S> {
+    base: S,
+    head: Option<Arc<OverlayLayer>>,
+    depth: usize,
+}
+
+impl<S: ReadStorage> SnapshotStorageOverlay<S> {
+    /// Creates an overlay without any diffs over the provided storage.
+    pub fn new(base: S) -> Self {
+        Self {
+            base,
+            head: None,
+            depth: 0,
+        }
+    }
+
+    /// Pushes diffs on top of this overlay, from the oldest to the newest one.
+    #[must_use]
+    pub fn with_diffs(mut self, diffs: impl IntoIterator<Item = BatchDiff>) -> Self {
+        for diff in diffs {
+            self.push_diff(diff);
+        }
+        self
+    }
+
+    /// Branches this overlay, using another handle to the same base storage state. Can be used if the base storage
+    /// cannot be cloned (e.g., for [`PostgresStorage`](crate::PostgresStorage), which holds a DB connection).
+    ///
+    /// It is the caller's responsibility to ensure that `base` provides the same state as the base of this overlay.
+    pub fn branch_with_base<T: ReadStorage>(&self, base: T) -> SnapshotStorageOverlay<T> {
+        SnapshotStorageOverlay {
+            base,
+            head: self.head.clone(),
+            depth: self.depth,
+        }
+    }
+
+    /// Pushes a diff on top of this overlay. The diff will take precedence over all existing diffs.
+    pub fn push_diff(&mut self, diff: BatchDiff) {
+        self.head = Some(Arc::new(OverlayLayer {
+            diff,
+            parent: self.head.take(),
+        }));
+        self.depth += 1;
+    }
+
+    /// Returns the number of diffs in this overlay.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns a reference to the base storage.
+    pub fn base(&self) -> &S {
+        &self.base
+    }
+
+    /// Merges all diffs in this overlay into a single diff. This speeds up subsequent reads, but copies the diff data
+    /// if the diffs are shared with other overlays.
+    pub fn squash(&mut self) {
+        if self.depth <= 1 {
+            return;
+        }
+
+        let mut squashed = BatchDiff::default();
+        // Iterate from the newest diff to the oldest one, so newer entries take precedence.
+        for diff in self.diffs() {
+            for (key, value) in &diff.state_diff {
+                squashed.state_diff.entry(*key).or_insert(*value);
+            }
+            for (key, index) in &diff.enum_index_diff {
+                squashed.enum_index_diff.entry(*key).or_insert(*index);
+            }
+            for (hash, bytecode) in &diff.factory_dep_diff {
+                squashed
+                    .factory_dep_diff
+                    .entry(*hash)
+                    .or_insert_with(|| bytecode.clone());
+            }
+        }
+        self.head = Some(Arc::new(OverlayLayer {
+            diff: squashed,
+            parent: None,
+        }));
+        self.depth = 1;
+    }
+
+    /// Iterates over diffs from the newest to the oldest one.
+    fn diffs(&self) -> impl Iterator<Item = &BatchDiff> + '_ {
+        let mut layer = self.head.as_deref();
+        std::iter::from_fn(move || {
+            let current = layer?;
+            layer = current.parent.as_deref();
+            Some(&current.diff)
+        })
+    }
+}
+
+impl<S: ReadStorage> ReadStorage for SnapshotStorageOverlay<S> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        let hashed_key = key.hashed_key();
+        let value = self
+            .diffs()
+            .find_map(|diff| diff.state_diff.get(&hashed_key))
+            .copied();
+        value.unwrap_or_else(|| self.base.read_value(key))
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        let hashed_key = key.hashed_key();
+        if self
+            .diffs()
+            .any(|diff| diff.enum_index_diff.contains_key(&hashed_key))
+        {
+            return false;
+        }
+        self.base.is_write_initial(key)
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        let bytecode = self
+            .diffs()
+            .find_map(|diff| diff.factory_dep_diff.get(&hash))
+            .cloned();
+        bytecode.or_else(|| self.base.load_factory_dep(hash))
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        let hashed_key = key.hashed_key();
+        let index = self
+            .diffs()
+            .find_map(|diff| diff.enum_index_diff.get(&hashed_key))
+            .copied();
+        index.or_else(|| self.base.get_enumeration_index(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{AccountTreeId, Address};
+    use zksync_vm_interface::storage::InMemoryStorage;
+
+    use super::*;
+
+    fn storage_key(i: u64) -> StorageKey {
+        StorageKey::new(
+            AccountTreeId::new(Address::repeat_byte(1)),
+            H256::from_low_u64_be(i),
+        )
+    }
+
+    /// Creates a diff with the specified `(key, value)` updates and `(key, enum_index)` initial writes.
+    fn diff_with_values(values: &[(u64, u64)], initial_writes: &[(u64, u64)]) -> BatchDiff {
+        let state_diff = values
+            .iter()
+            .map(|&(key, value)| (storage_key(key).hashed_key(), H256::from_low_u64_be(value)))
+            .collect();
+        let enum_index_diff = initial_writes
+            .iter()
+            .map(|&(key, index)| (storage_key(key).hashed_key(), index))
+            .collect();
+        BatchDiff {
+            state_diff,
+            enum_index_diff,
+            factory_dep_diff: Default::default(),
+        }
+    }
+
+    fn base_storage() -> InMemoryStorage {
+        let mut storage = InMemoryStorage::default();
+        storage.set_value(storage_key(0), H256::from_low_u64_be(100));
+        storage.set_value(storage_key(1), H256::from_low_u64_be(101));
+        storage
+    }
+
+    #[test]
+    fn overlay_basics() {
+        let mut overlay = SnapshotStorageOverlay::new(base_storage()).with_diffs([
+            diff_with_values(&[(1, 1), (2, 2)], &[(2, 10)]),
+            diff_with_values(&[(2, 3), (3, 4)], &[(3, 11)]),
+        ]);
+        assert_eq!(overlay.depth(), 2);
+
+        assert_eq!(
+            overlay.read_value(&storage_key(0)),
+            H256::from_low_u64_be(100)
+        );
+        assert_eq!(
+            overlay.read_value(&storage_key(1)),
+            H256::from_low_u64_be(1)
+        );
+        assert_eq!(
+            overlay.read_value(&storage_key(2)),
+            H256::from_low_u64_be(3)
+        );
+        assert_eq!(
+            overlay.read_value(&storage_key(3)),
+            H256::from_low_u64_be(4)
+        );
+        assert_eq!(overlay.read_value(&storage_key(4)), H256::zero());
+
+        assert!(!overlay.is_write_initial(&storage_key(0)));
+        assert!(!overlay.is_write_initial(&storage_key(3)));
+        assert!(overlay.is_write_initial(&storage_key(4)));
+        assert_eq!(overlay.get_enumeration_index(&storage_key(2)), Some(10));
+        assert_eq!(overlay.get_enumeration_index(&storage_key(3)), Some(11));
+        assert_eq!(overlay.get_enumeration_index(&storage_key(4)), None);
+    }
+
+    #[test]
+    fn branching_overlays() {
+        let mut overlay = SnapshotStorageOverlay::new(base_storage())
+            .with_diffs([diff_with_values(&[(1, 1)], &[])]);
+        let mut branch = overlay.clone();
+        branch.push_diff(diff_with_values(&[(1, 2), (2, 3)], &[(2, 10)]));
+        let mut other_branch = overlay.branch_with_base(base_storage());
+        other_branch.push_diff(diff_with_values(&[(2, 5)], &[(2, 10)]));
+
+        assert_eq!(overlay.depth(), 1);
+        assert_eq!(
+            overlay.read_value(&storage_key(1)),
+            H256::from_low_u64_be(1)
+        );
+        assert_eq!(overlay.read_value(&storage_key(2)), H256::zero());
+        assert_eq!(branch.depth(), 2);
+        assert_eq!(branch.read_value(&storage_key(1)), H256::from_low_u64_be(2));
+        assert_eq!(branch.read_value(&storage_key(2)), H256::from_low_u64_be(3));
+        assert_eq!(
+            other_branch.read_value(&storage_key(1)),
+            H256::from_low_u64_be(1)
+        );
+        assert_eq!(
+            other_branch.read_value(&storage_key(2)),
+            H256::from_low_u64_be(5)
+        );
+
+        // Diffs must be shared among branches.
+        let shared_layer = overlay.head.as_ref().unwrap();
+        let branch_parent = branch.head.as_ref().unwrap().parent.as_ref().unwrap();
+        assert!(Arc::ptr_eq(shared_layer, branch_parent));
+    }
+
+    #[test]
+    fn squashing_overlay() {
+        let mut overlay = SnapshotStorageOverlay::new(base_storage()).with_diffs([
+            diff_with_values(&[(1, 1), (2, 2)], &[(2, 10)]),
+            diff_with_values(&[(2, 3), (3, 4)], &[(3, 11)]),
+        ]);
+        let mut branch = overlay.clone();
+        overlay.squash();
+        assert_eq!(overlay.depth(), 1);
+
+        for i in 0..5 {
+            let key = storage_key(i);
+            assert_eq!(overlay.read_value(&key), branch.read_value(&key), "{i}");
+            assert_eq!(
+                overlay.is_write_initial(&key),
+                branch.is_write_initial(&key),
+                "{i}"
+            );
+            assert_eq!(
+                overlay.get_enumeration_index(&key),
+                branch.get_enumeration_index(&key),
+                "{i}"
+            );
+        }
+        assert_eq!(overlay.get_enumeration_index(&storage_key(3)), Some(11));
+    }
+}
@@ -0,0 +1,239 @@
+//! Reconstruction of the L2 state from pubdata committed on L1.
+//!
+//! [`StateReconstructor`] consumes pubdata of consecutive L1 batches (see [`Pubdata`] for the format) and applies state diffs
+//! to a Merkle tree, so that the resulting root hash can be compared with one committed on L1. This allows recovering
+//! the chain state without trusting the main node.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Context as _;
+use zksync_merkle_tree::{Database, Key, MerkleTree, PatchSet, TreeEntry};
+use zksync_types::{
+    bytecode::BytecodeHash,
+    h256_to_u256, u256_to_h256,
+    writes::{DecompressedStateDiff, StateDiffKey},
+    L1BatchNumber, H256, U256,
+};
+
+pub use crate::pubdata::{pubdata_from_blobs, Pubdata};
+
+mod pubdata;
+#[cfg(test)]
+mod tests;
+
+/// Information about an L1 batch applied by [`StateReconstructor`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconstructedL1Batch {
+    pub number: L1BatchNumber,
+    /// Root hash of the Merkle tree after applying the batch.
+    pub root_hash: H256,
+    /// Number of leaves in the Merkle tree after applying the batch.
+    pub leaf_count: u64,
+    pub initial_writes: usize,
+    pub repeated_writes: usize,
+    pub published_bytecodes: usize,
+}
+
+/// Reconstructs the L2 state from pubdata of consecutive L1 batches.
+///
+/// Since storage writes made at genesis are not published, the reconstructor must be initialized with the tree state
+/// preceding the first applied batch (e.g., the genesis state or a trusted snapshot).
+///
+/// Besides the Merkle tree, the reconstructor keeps tree keys and values of all slots in memory indexed by the enumeration
+/// index, since repeated writes in pubdata refer to slots by their index and can be compressed relative to the previous
+/// slot value.
+#[derive(Debug)]
+pub struct StateReconstructor<DB = PatchSet> {
+    tree: MerkleTree<DB>,
+    slots: HashMap<u64, (Key, U256)>,
+    bytecodes: HashMap<H256, Vec<u8>>,
+    next_l1_batch: L1BatchNumber,
+}
+
+impl StateReconstructor {
+    /// Creates a reconstructor with an in-memory Merkle tree.
+    ///
+    /// # Errors
+    ///
+    /// Errors in the same situations as [`Self::with_db()`].
+    pub fn new(
+        initial_entries: Vec<TreeEntry>,
+        next_l1_batch: L1BatchNumber,
+    ) -> anyhow::Result<Self> {
+        Self::with_db(PatchSet::default(), initial_entries, next_l1_batch)
+    }
+}
+
+impl<DB: Database> StateReconstructor<DB> {
+    /// Creates a reconstructor with the Merkle tree persisted in the provided database, which must be empty.
+    /// `initial_entries` define the tree state preceding `next_l1_batch`; their leaf indices must be `1..=n`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database is not empty, or if the initial entries are invalid.
+    pub fn with_db(
+        db: DB,
+        initial_entries: Vec<TreeEntry>,
+        next_l1_batch: L1BatchNumber,
+    ) -> anyhow::Result<Self> {
+        let mut tree = MerkleTree::new(db)?;
+        anyhow::ensure!(
+            tree.latest_version().is_none(),
+            "Merkle tree used for state reconstruction must be empty"
+        );
+
+        let slots: HashMap<_, _> = initial_entries
+            .iter()
+            .map(|entry| (entry.leaf_index, (entry.key, h256_to_u256(entry.value))))
+            .collect();
+        let entry_count = initial_entries.len() as u64;
+        anyhow::ensure!(
+            slots.len() as u64 == entry_count
+                && slots
+                    .keys()
+                    .all(|&index| (1..=entry_count).contains(&index)),
+            "leaf indices of initial tree entries must be 1..={entry_count}"
+        );
+        let output = tree.extend(initial_entries)?;
+        anyhow::ensure!(
+            output.leaf_count == entry_count,
+            "initial tree entries contain duplicate keys"
+        );
+        tracing::info!(
+            "Initialized state reconstruction with {entry_count} slots and root hash {:?}; next L1 batch is #{next_l1_batch}",
+            output.root_hash
+        );
+
+        Ok(Self {
+            tree,
+            slots,
+            bytecodes: HashMap::new(),
+            next_l1_batch,
+        })
+    }
+
+    /// Returns the number of the next L1 batch expected by [`Self::apply_l1_batch()`].
+    pub fn next_l1_batch(&self) -> L1BatchNumber {
+        self.next_l1_batch
+    }
+
+    /// Returns the current root hash of the Merkle tree.
+    pub fn root_hash(&self) -> H256 {
+        self.tree.latest_root_hash()
+    }
+
+    /// Returns the current value of a storage slot by its tree key (i.e., [`StorageKey::hashed_key_u256()`]).
+    ///
+    /// [`StorageKey::hashed_key_u256()`]: zksync_types::StorageKey::hashed_key_u256()
+    pub fn value(&self, key: Key) -> anyhow::Result<H256> {
+        let version = self.tree.latest_version().context("Merkle tree is empty")?;
+        let entries = self.tree.entries(version, &[key])?;
+        Ok(entries[0].value)
+    }
+
+    /// Returns a bytecode published in one of the applied batches.
+    pub fn bytecode(&self, hash: H256) -> Option<&[u8]> {
+        self.bytecodes.get(&hash).map(Vec::as_slice)
+    }
+
+    /// Applies pubdata of the next L1 batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if pubdata is malformed or inconsistent with the current state (e.g., refers to an unknown
+    /// enumeration index), or if the tree cannot be updated. If pubdata is invalid, the reconstructor state is not changed.
+    pub fn apply_l1_batch(&mut self, pubdata: &[u8]) -> anyhow::Result<ReconstructedL1Batch> {
+        let number = self.next_l1_batch;
+        let pubdata = Pubdata::parse(pubdata, |index| {
+            let (_, value) = self
+                .slots
+                .get(&index)
+                .with_context(|| format!("unknown enumeration index {index}"))?;
+            Ok(*value)
+        })
+        .with_context(|| format!("failed parsing pubdata for L1 batch #{number}"))?;
+
+        let (entries, initial_writes) = self
+            .tree_entries(&pubdata.state_diffs)
+            .with_context(|| format!("invalid state diffs for L1 batch #{number}"))?;
+        let output = self.tree.extend(entries.clone())?;
+
+        for entry in entries {
+            self.slots
+                .insert(entry.leaf_index, (entry.key, h256_to_u256(entry.value)));
+        }
+
+        let published_bytecodes = pubdata.published_bytecodes.len();
+        for bytecode in pubdata.published_bytecodes {
+            let hash = BytecodeHash::for_bytecode(&bytecode).value();
+            self.bytecodes.insert(hash, bytecode);
+        }
+        self.next_l1_batch += 1;
+
+        let batch = ReconstructedL1Batch {
+            number,
+            root_hash: output.root_hash,
+            leaf_count: output.leaf_count,
+            initial_writes,
+            repeated_writes: pubdata.state_diffs.len() - initial_writes,
+            published_bytecodes,
+        };
+        tracing::debug!("Applied pubdata for L1 batch: {batch:?}");
+        Ok(batch)
+    }
+
+    /// Converts state diffs to tree entries. New leaf indices are assigned to initial writes sequentially
+    /// in the publication order, in the same way as it's done by the state keeper.
+    fn tree_entries(
+        &self,
+        state_diffs: &[DecompressedStateDiff],
+    ) -> anyhow::Result<(Vec<TreeEntry>, usize)> {
+        let mut next_index = self.slots.len() as u64 + 1;
+        let mut initial_writes = 0;
+        let entries: Vec<_> = state_diffs
+            .iter()
+            .map(|diff| {
+                let value = u256_to_h256(diff.final_value);
+                match diff.key {
+                    StateDiffKey::Initial(derived_key) => {
+                        initial_writes += 1;
+                        next_index += 1;
+                        let key = Key::from_little_endian(&derived_key);
+                        Ok(TreeEntry::new(key, next_index - 1, value))
+                    }
+                    StateDiffKey::Repeated(index) => {
+                        let (key, _) = self
+                            .slots
+                            .get(&index)
+                            .with_context(|| format!("unknown enumeration index {index}"))?;
+                        Ok(TreeEntry::new(*key, index, value))
+                    }
+                }
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        let initial_keys: Vec<_> = entries[..initial_writes]
+            .iter()
+            .map(|entry| entry.key)
+            .collect();
+        let unique_keys: HashSet<_> = initial_keys.iter().collect();
+        anyhow::ensure!(
+            unique_keys.len() == initial_keys.len(),
+            "initial writes contain duplicate keys"
+        );
+        if let Some(version) = self.tree.latest_version() {
+            let existing_entries = self.tree.entries(version, &initial_keys)?;
+            let existing = initial_keys
+                .iter()
+                .zip(&existing_entries)
+                .find(|(_, entry)| !entry.is_empty());
+            if let Some((key, entry)) = existing {
+                anyhow::bail!(
+                    "initial write to key {key:?}, which is already present in the tree with leaf index {}",
+                    entry.leaf_index
+                );
+            }
+        }
+        Ok((entries, initial_writes))
+    }
+}
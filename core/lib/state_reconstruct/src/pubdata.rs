@@ -0,0 +1,101 @@
+//! Parsing of L1 batch pubdata.
+
+use anyhow::Context as _;
+use zksync_types::{
+    commitment::SerializeCommitment,
+    l2_to_l1_log::L2ToL1Log,
+    writes::{decompress_state_diffs, DecompressedStateDiff},
+    U256,
+};
+
+/// Pubdata of a single L1 batch as published on L1.
+///
+/// # Format
+///
+/// ```text
+/// number of user L2-to-L1 logs (u32) || logs (88 bytes each)
+///     || number of L2-to-L1 messages (u32) || (message length (u32) || message)*
+///     || number of published bytecodes (u32) || (bytecode length (u32) || bytecode)*
+///     || compressed state diffs (incl. the compression header)
+/// ```
+///
+/// Pubdata may be followed by zero padding (e.g., if it is read from blobs).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pubdata {
+    pub user_logs: Vec<L2ToL1Log>,
+    pub l2_to_l1_messages: Vec<Vec<u8>>,
+    pub published_bytecodes: Vec<Vec<u8>>,
+    /// Decompressed state diffs: initial writes followed by repeated writes.
+    pub state_diffs: Vec<DecompressedStateDiff>,
+}
+
+impl Pubdata {
+    /// Parses pubdata. Since state diffs may be compressed relative to previous slot values, the caller must provide
+    /// current values of slots by their enumeration index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if pubdata is malformed, or propagates errors returned by `value_by_index`.
+    pub fn parse(
+        mut data: &[u8],
+        value_by_index: impl FnMut(u64) -> anyhow::Result<U256>,
+    ) -> anyhow::Result<Self> {
+        let data = &mut data;
+        let logs_count = read_u32(data).context("failed reading number of user logs")?;
+        let user_logs = (0..logs_count)
+            .map(|_| {
+                let log = read_bytes(data, L2ToL1Log::SERIALIZED_SIZE)?;
+                Ok(L2ToL1Log::from_slice(log))
+            })
+            .collect::<anyhow::Result<_>>()
+            .context("failed reading user logs")?;
+        let l2_to_l1_messages =
+            read_length_prefixed_items(data).context("failed reading L2-to-L1 messages")?;
+        let published_bytecodes =
+            read_length_prefixed_items(data).context("failed reading published bytecodes")?;
+        let state_diffs = decompress_state_diffs(data, value_by_index)
+            .context("failed decompressing state diffs")?;
+        anyhow::ensure!(
+            data.iter().all(|&byte| byte == 0),
+            "pubdata has {} unexpected trailing bytes",
+            data.len()
+        );
+
+        Ok(Self {
+            user_logs,
+            l2_to_l1_messages,
+            published_bytecodes,
+            state_diffs,
+        })
+    }
+}
+
+/// Concatenates pubdata published in blobs. Zero padding at the end of the last blob is allowed by [`Pubdata::parse()`].
+pub fn pubdata_from_blobs<'a>(blobs: impl IntoIterator<Item = &'a [u8]>) -> Vec<u8> {
+    blobs.into_iter().flatten().copied().collect()
+}
+
+fn read_bytes<'a>(data: &mut &'a [u8], len: usize) -> anyhow::Result<&'a [u8]> {
+    anyhow::ensure!(
+        data.len() >= len,
+        "unexpected end of pubdata: expected {len} bytes, got {}",
+        data.len()
+    );
+    let (head, tail) = data.split_at(len);
+    *data = tail;
+    Ok(head)
+}
+
+fn read_u32(data: &mut &[u8]) -> anyhow::Result<u32> {
+    Ok(u32::from_be_bytes(read_bytes(data, 4)?.try_into().unwrap()))
+}
+
+fn read_length_prefixed_items(data: &mut &[u8]) -> anyhow::Result<Vec<Vec<u8>>> {
+    let count = read_u32(data)?;
+    (0..count)
+        .map(|_| {
+            let len = read_u32(data)?;
+            Ok(read_bytes(data, len as usize)?.to_vec())
+        })
+        .collect()
+}
@@ -0,0 +1,232 @@
+//! Tests for state reconstruction.
+
+use zksync_multivm::{
+    interface::pubdata::{L1MessengerL2ToL1Log, PubdataBuilder, PubdataInput},
+    pubdata_builders::FullPubdataBuilder,
+};
+use zksync_types::{
+    writes::StateDiffRecord, AccountTreeId, Address, ProtocolVersionId, StorageKey,
+};
+
+use super::*;
+
+fn storage_key(index: u64) -> StorageKey {
+    StorageKey::new(
+        AccountTreeId::new(Address::repeat_byte(0x11)),
+        H256::from_low_u64_be(index),
+    )
+}
+
+fn state_diff(
+    key: StorageKey,
+    enumeration_index: u64,
+    initial_value: u64,
+    final_value: u64,
+) -> StateDiffRecord {
+    StateDiffRecord {
+        address: *key.address(),
+        key: h256_to_u256(*key.key()),
+        derived_key: StorageKey::raw_hashed_key(key.address(), key.key()),
+        enumeration_index,
+        initial_value: initial_value.into(),
+        final_value: final_value.into(),
+    }
+}
+
+fn pubdata(state_diffs: Vec<StateDiffRecord>, published_bytecodes: Vec<Vec<u8>>) -> Vec<u8> {
+    let input = PubdataInput {
+        user_logs: vec![L1MessengerL2ToL1Log {
+            sender: Address::repeat_byte(1),
+            key: 1.into(),
+            value: 2.into(),
+            ..L1MessengerL2ToL1Log::default()
+        }],
+        l2_to_l1_messages: vec![vec![1; 10]],
+        published_bytecodes,
+        state_diffs,
+    };
+    FullPubdataBuilder::new(Address::zero())
+        .settlement_layer_pubdata(&input, ProtocolVersionId::latest())
+}
+
+fn genesis_entry() -> TreeEntry {
+    TreeEntry::new(
+        storage_key(0).hashed_key_u256(),
+        1,
+        H256::from_low_u64_be(1),
+    )
+}
+
+#[test]
+fn parsing_pubdata() {
+    let bytecode = vec![0; 64];
+    let pubdata = pubdata(
+        vec![state_diff(storage_key(1), 0, 0, 10)],
+        vec![bytecode.clone()],
+    );
+    let pubdata = Pubdata::parse(&pubdata, |_| unreachable!()).unwrap();
+
+    assert_eq!(pubdata.user_logs.len(), 1);
+    assert_eq!(pubdata.user_logs[0].sender, Address::repeat_byte(1));
+    assert_eq!(pubdata.l2_to_l1_messages, [vec![1; 10]]);
+    assert_eq!(pubdata.published_bytecodes, [bytecode]);
+    assert_eq!(
+        pubdata.state_diffs,
+        [DecompressedStateDiff {
+            key: StateDiffKey::Initial(StorageKey::raw_hashed_key(
+                storage_key(1).address(),
+                storage_key(1).key()
+            )),
+            final_value: 10.into(),
+        }]
+    );
+}
+
+#[test]
+fn parsing_pubdata_from_blobs() {
+    let pubdata = pubdata(vec![state_diff(storage_key(1), 0, 0, 10)], vec![]);
+    let mut blob = pubdata.clone();
+    blob.resize(pubdata.len() + 100, 0);
+    let blobs = [&blob[..50], &blob[50..]];
+    let blob_pubdata = pubdata_from_blobs(blobs);
+    assert_eq!(
+        Pubdata::parse(&blob_pubdata, |_| unreachable!()).unwrap(),
+        Pubdata::parse(&pubdata, |_| unreachable!()).unwrap()
+    );
+
+    blob.push(1);
+    let err = Pubdata::parse(&blob, |_| unreachable!()).unwrap_err();
+    assert!(err.to_string().contains("trailing bytes"), "{err:#}");
+}
+
+#[test]
+fn reconstructing_state() {
+    let mut reconstructor =
+        StateReconstructor::new(vec![genesis_entry()], L1BatchNumber(1)).unwrap();
+    let mut expected_tree = MerkleTree::new(PatchSet::default()).unwrap();
+    expected_tree.extend(vec![genesis_entry()]).unwrap();
+    assert_eq!(reconstructor.root_hash(), expected_tree.latest_root_hash());
+
+    // Initial writes are assigned leaf indices in the `(address, key)` order.
+    let bytecode = vec![1; 64];
+    let batch = reconstructor
+        .apply_l1_batch(&pubdata(
+            vec![
+                state_diff(storage_key(2), 0, 0, 20),
+                state_diff(storage_key(1), 0, 0, 10),
+            ],
+            vec![bytecode.clone()],
+        ))
+        .unwrap();
+    let expected_output = expected_tree
+        .extend(vec![
+            TreeEntry::new(
+                storage_key(1).hashed_key_u256(),
+                2,
+                H256::from_low_u64_be(10),
+            ),
+            TreeEntry::new(
+                storage_key(2).hashed_key_u256(),
+                3,
+                H256::from_low_u64_be(20),
+            ),
+        ])
+        .unwrap();
+    assert_eq!(batch.number, L1BatchNumber(1));
+    assert_eq!(batch.root_hash, expected_output.root_hash);
+    assert_eq!(batch.leaf_count, 3);
+    assert_eq!(batch.initial_writes, 2);
+    assert_eq!(batch.repeated_writes, 0);
+    assert_eq!(batch.published_bytecodes, 1);
+    let bytecode_hash = BytecodeHash::for_bytecode(&bytecode).value();
+    assert_eq!(
+        reconstructor.bytecode(bytecode_hash),
+        Some(bytecode.as_slice())
+    );
+
+    // Repeated writes are compressed relative to the previous values.
+    let batch = reconstructor
+        .apply_l1_batch(&pubdata(
+            vec![
+                state_diff(storage_key(0), 1, 1, 2),
+                state_diff(storage_key(2), 3, 20, 15),
+                state_diff(storage_key(3), 0, 0, u64::MAX),
+            ],
+            vec![],
+        ))
+        .unwrap();
+    let expected_output = expected_tree
+        .extend(vec![
+            TreeEntry::new(
+                storage_key(0).hashed_key_u256(),
+                1,
+                H256::from_low_u64_be(2),
+            ),
+            TreeEntry::new(
+                storage_key(2).hashed_key_u256(),
+                3,
+                H256::from_low_u64_be(15),
+            ),
+            TreeEntry::new(
+                storage_key(3).hashed_key_u256(),
+                4,
+                H256::from_low_u64_be(u64::MAX),
+            ),
+        ])
+        .unwrap();
+    assert_eq!(batch.number, L1BatchNumber(2));
+    assert_eq!(batch.root_hash, expected_output.root_hash);
+    assert_eq!(batch.initial_writes, 1);
+    assert_eq!(batch.repeated_writes, 2);
+    assert_eq!(reconstructor.next_l1_batch(), L1BatchNumber(3));
+    assert_eq!(
+        reconstructor
+            .value(storage_key(2).hashed_key_u256())
+            .unwrap(),
+        H256::from_low_u64_be(15)
+    );
+}
+
+#[test]
+fn invalid_pubdata_does_not_change_state() {
+    let mut reconstructor =
+        StateReconstructor::new(vec![genesis_entry()], L1BatchNumber(1)).unwrap();
+    let root_hash = reconstructor.root_hash();
+
+    let err = reconstructor
+        .apply_l1_batch(&pubdata(vec![state_diff(storage_key(1), 5, 0, 1)], vec![]))
+        .unwrap_err();
+    assert!(
+        format!("{err:#}").contains("unknown enumeration index 5"),
+        "{err:#}"
+    );
+
+    let err = reconstructor
+        .apply_l1_batch(&pubdata(vec![state_diff(storage_key(0), 0, 0, 1)], vec![]))
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("already present"), "{err:#}");
+
+    assert_eq!(reconstructor.root_hash(), root_hash);
+    assert_eq!(reconstructor.next_l1_batch(), L1BatchNumber(1));
+}
+
+#[test]
+fn invalid_initial_entries() {
+    let entry = genesis_entry();
+    let err = StateReconstructor::new(
+        vec![TreeEntry {
+            leaf_index: 2,
+            ..entry
+        }],
+        L1BatchNumber(1),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("leaf indices"), "{err:#}");
+
+    let duplicate_entry = TreeEntry {
+        leaf_index: 2,
+        ..entry
+    };
+    let err = StateReconstructor::new(vec![entry, duplicate_entry], L1BatchNumber(1)).unwrap_err();
+    assert!(err.to_string().contains("duplicate keys"), "{err:#}");
+}
@@ -4,6 +4,7 @@ use std::{
     ffi::CStr,
     fmt, iter,
     marker::PhantomData,
+    mem,
     num::NonZeroU32,
     ops,
     path::Path,
@@ -87,6 +88,8 @@ impl<CF: NamedColumnFamily> WriteBatch<'_, CF> {
 struct RocksDBCaches {
     /// LRU block cache shared among all column families.
     shared: Option<Cache>,
+    /// Dedicated LRU block caches for large column families, keyed by the CF name.
+    dedicated: HashMap<String, Cache>,
 }
 
 impl fmt::Debug for RocksDBCaches {
@@ -100,7 +103,40 @@ impl fmt::Debug for RocksDBCaches {
 impl RocksDBCaches {
     fn new(capacity: Option<usize>) -> Self {
         let shared = capacity.map(Cache::new_lru_cache);
-        Self { shared }
+        Self {
+            shared,
+            dedicated: HashMap::new(),
+        }
+    }
+
+    fn cache_for_cf(&mut self, cf_name: &str, dedicated_capacity: Option<usize>) -> Option<&Cache> {
+        if let Some(capacity) = dedicated_capacity {
+            Some(
+                self.dedicated
+                    .entry(cf_name.to_owned())
+                    .or_insert_with(|| Cache::new_lru_cache(capacity)),
+            )
+        } else {
+            self.shared.as_ref()
+        }
+    }
+}
+
+/// Usage statistics for a RocksDB block cache. All sizes are measured in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockCacheStats {
+    /// Total memory usage of the cache.
+    pub usage: usize,
+    /// Memory usage of the entries pinned in the cache (e.g., ones currently being read).
+    pub pinned_usage: usize,
+}
+
+impl BlockCacheStats {
+    fn new(cache: &Cache) -> Self {
+        Self {
+            usage: cache.get_usage(),
+            pinned_usage: cache.get_pinned_usage(),
+        }
     }
 }
 
@@ -123,7 +159,7 @@ pub(crate) struct RocksDBInner {
     _registry_entry: RegistryEntry,
     // Importantly, `Cache`s must be dropped after `DB`, so we place them as the last field
     // (fields in a struct are dropped in the declaration order).
-    _caches: RocksDBCaches,
+    caches: RocksDBCaches,
 }
 
 impl RocksDBInner {
@@ -163,6 +199,14 @@ impl RocksDBInner {
             if let Some(pending_compactions) = pending_compactions {
                 metrics.pending_compactions.set(pending_compactions);
             }
+            let compaction_pending = self.int_property(cf, properties::COMPACTION_PENDING);
+            if let Some(compaction_pending) = compaction_pending {
+                metrics.compaction_pending.set(compaction_pending);
+            }
+            let delayed_write_rate = self.int_property(cf, properties::ACTUAL_DELAYED_WRITE_RATE);
+            if let Some(delayed_write_rate) = delayed_write_rate {
+                metrics.delayed_write_rate.set(delayed_write_rate);
+            }
 
             let size_stats = self.size_stats(cf);
             metrics.live_data_size.set(size_stats.live_data_size);
@@ -315,6 +359,49 @@ impl StalledWritesRetries {
     }
 }
 
+/// Compaction-related options applied to large CFs (as defined in [`NamedColumnFamily::requires_tuning()`]).
+/// Unset options retain their default RocksDB values.
+///
+/// These options control when RocksDB starts throttling (slowing down) and stopping writes because compaction
+/// cannot keep up with them. Raising the limits trades off read amplification and disk usage for fewer write stalls.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompactionOptions {
+    /// Number of level-0 SST files at which writes are slowed down.
+    pub level0_slowdown_writes_trigger: Option<NonZeroU32>,
+    /// Number of level-0 SST files at which writes are stopped.
+    pub level0_stop_writes_trigger: Option<NonZeroU32>,
+    /// Estimated number of bytes pending compaction at which writes are slowed down.
+    pub soft_pending_compaction_bytes_limit: Option<usize>,
+    /// Estimated number of bytes pending compaction at which writes are stopped.
+    pub hard_pending_compaction_bytes_limit: Option<usize>,
+    /// Maximum number of threads that a single compaction job can be split into.
+    pub max_subcompactions: Option<NonZeroU32>,
+}
+
+impl CompactionOptions {
+    fn apply(&self, options: &mut Options) {
+        if let Some(trigger) = self.level0_slowdown_writes_trigger {
+            options.set_level_zero_slowdown_writes_trigger(Self::to_i32(trigger));
+        }
+        if let Some(trigger) = self.level0_stop_writes_trigger {
+            options.set_level_zero_stop_writes_trigger(Self::to_i32(trigger));
+        }
+        if let Some(limit) = self.soft_pending_compaction_bytes_limit {
+            options.set_soft_pending_compaction_bytes_limit(limit);
+        }
+        if let Some(limit) = self.hard_pending_compaction_bytes_limit {
+            options.set_hard_pending_compaction_bytes_limit(limit);
+        }
+        if let Some(count) = self.max_subcompactions {
+            options.set_max_subcompactions(count.get());
+        }
+    }
+
+    fn to_i32(value: NonZeroU32) -> i32 {
+        i32::try_from(value.get()).unwrap_or(i32::MAX)
+    }
+}
+
 /// [`RocksDB`] options.
 #[derive(Debug, Clone, Copy)]
 pub struct RocksDBOptions {
@@ -330,11 +417,20 @@ pub struct RocksDBOptions {
     /// Setting this to a reasonably large value (order of 512 MiB) is helpful for large DBs that experience
     /// write stalls. If not set, large CFs will not be configured specially.
     pub large_memtable_capacity: Option<usize>,
+    /// Byte capacity of dedicated block caches set for large CFs (as defined in [`NamedColumnFamily::requires_tuning()`]).
+    /// If set, each large CF gets a separate block cache with this capacity, so that reads from other CFs
+    /// do not evict its blocks from the shared cache. If not set, large CFs use the shared block cache.
+    pub large_cf_block_cache_capacity: Option<usize>,
+    /// Compaction options for large CFs.
+    pub large_cf_compaction: CompactionOptions,
     /// Timeout to wait for the database to run compaction on stalled writes during startup or
     /// when the corresponding RocksDB error is encountered.
     pub stalled_writes_retries: StalledWritesRetries,
     /// Number of open files that can be used by the DB. Default is None, for no limit.
     pub max_open_files: Option<NonZeroU32>,
+    /// If set, the DB will be backed by an in-memory environment instead of the filesystem. The DB path
+    /// is still used to identify the DB within the environment, but nothing is written to disk.
+    pub in_memory: bool,
 }
 
 impl Default for RocksDBOptions {
@@ -343,8 +439,11 @@ impl Default for RocksDBOptions {
             block_cache_capacity: None,
             include_indices_and_filters_in_block_cache: false,
             large_memtable_capacity: None,
+            large_cf_block_cache_capacity: None,
+            large_cf_compaction: CompactionOptions::default(),
             stalled_writes_retries: StalledWritesRetries::new(Duration::from_secs(10)),
             max_open_files: None,
+            in_memory: false,
         }
     }
 }
@@ -377,7 +476,7 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
     }
 
     pub fn with_options(path: &Path, options: RocksDBOptions) -> Result<Self, rocksdb::Error> {
-        let caches = RocksDBCaches::new(options.block_cache_capacity);
+        let mut caches = RocksDBCaches::new(options.block_cache_capacity);
         let mut db_options = Self::rocksdb_options(None, None);
         let max_open_files = if let Some(non_zero) = options.max_open_files {
             i32::try_from(non_zero.get()).unwrap_or(i32::MAX)
@@ -385,14 +484,20 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
             -1
         };
         db_options.set_max_open_files(max_open_files);
-        let existing_cfs = DB::list_cf(&db_options, path).unwrap_or_else(|err| {
-            tracing::warn!(
-                "Failed getting column families for RocksDB `{}` at `{}`, assuming CFs are empty; {err}",
-                CF::DB_NAME,
-                path.display()
-            );
+        let existing_cfs = if options.in_memory {
+            db_options.set_env(&rocksdb::Env::mem_env()?);
+            // A fresh in-memory environment never contains any CFs.
             vec![]
-        });
+        } else {
+            DB::list_cf(&db_options, path).unwrap_or_else(|err| {
+                tracing::warn!(
+                    "Failed getting column families for RocksDB `{}` at `{}`, assuming CFs are empty; {err}",
+                    CF::DB_NAME,
+                    path.display()
+                );
+                vec![]
+            })
+        };
 
         let cfs_and_options: HashMap<_, _> = CF::ALL
             .iter()
@@ -426,7 +531,10 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
         let cfs = all_cfs_and_options.map(|(cf_name, requires_tuning)| {
             let mut block_based_options = BlockBasedOptions::default();
             block_based_options.set_bloom_filter(10.0, false);
-            if let Some(cache) = &caches.shared {
+            let dedicated_cache_capacity = options
+                .large_cf_block_cache_capacity
+                .filter(|_| requires_tuning);
+            if let Some(cache) = caches.cache_for_cf(cf_name, dedicated_cache_capacity) {
                 block_based_options.set_block_cache(cache);
             }
             if options.include_indices_and_filters_in_block_cache {
@@ -435,7 +543,11 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
             }
 
             let memtable_capacity = options.large_memtable_capacity.filter(|_| requires_tuning);
-            let cf_options = Self::rocksdb_options(memtable_capacity, Some(block_based_options));
+            let mut cf_options =
+                Self::rocksdb_options(memtable_capacity, Some(block_based_options));
+            if requires_tuning {
+                options.large_cf_compaction.apply(&mut cf_options);
+            }
             ColumnFamilyDescriptor::new(cf_name, cf_options)
         });
 
@@ -445,7 +557,7 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
             db_name: CF::DB_NAME,
             cf_names,
             _registry_entry: RegistryEntry::new(),
-            _caches: caches,
+            caches,
         });
         RocksdbSizeMetrics::register(CF::DB_NAME, Arc::downgrade(&inner));
 
@@ -505,6 +617,36 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
         }
     }
 
+    /// Returns usage statistics for the block cache shared among CFs, or `None` if the DB uses default
+    /// RocksDB cache options.
+    pub fn block_cache_stats(&self) -> Option<BlockCacheStats> {
+        self.inner.caches.shared.as_ref().map(BlockCacheStats::new)
+    }
+
+    /// Returns usage statistics for the dedicated block cache of the specified CF, or `None` if the CF
+    /// doesn't have a dedicated cache (see [`RocksDBOptions::large_cf_block_cache_capacity`]).
+    pub fn dedicated_block_cache_stats(&self, cf: CF) -> Option<BlockCacheStats> {
+        let cache = self.inner.caches.dedicated.get(cf.name())?;
+        Some(BlockCacheStats::new(cache))
+    }
+
+    /// Changes the byte capacity of the shared block cache. If the new capacity is lower than the current cache usage,
+    /// RocksDB will evict entries from the cache to fit into it. Does nothing if the DB uses default RocksDB cache options.
+    pub fn set_block_cache_capacity(&self, capacity: usize) {
+        if let Some(cache) = &self.inner.caches.shared {
+            // `Cache` is a ref-counted handle, so changing capacity for a clone affects the original cache.
+            cache.clone().set_capacity(capacity);
+        }
+    }
+
+    /// Changes the byte capacity of the dedicated block cache for the specified CF. Does nothing
+    /// if the CF doesn't have a dedicated cache.
+    pub fn set_dedicated_block_cache_capacity(&self, cf: CF, capacity: usize) {
+        if let Some(cache) = self.inner.caches.dedicated.get(cf.name()) {
+            cache.clone().set_capacity(capacity);
+        }
+    }
+
     pub fn estimated_number_of_entries(&self, cf: CF) -> u64 {
         const ERROR_MSG: &str = "failed to get estimated number of entries";
 
@@ -587,6 +729,32 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
         }
     }
 
+    /// Copies all entries from a consistent snapshot of `source` into this DB. Existing entries
+    /// with keys not present in `source` are retained.
+    ///
+    /// This is mostly useful to load an on-disk DB into an in-memory one (or vice versa).
+    pub fn copy_from(&self, source: &Self) -> Result<(), rocksdb::Error> {
+        const BATCH_SIZE: usize = 10_000;
+
+        let snapshot = source.inner.db.snapshot();
+        for &cf in CF::ALL {
+            let source_cf = source.column_family(cf);
+            let target_cf = self.column_family(cf);
+            let mut batch = rocksdb::WriteBatch::default();
+            for entry in snapshot.iterator_cf(source_cf, IteratorMode::Start) {
+                let (key, value) = entry?;
+                batch.put_cf(target_cf, key, value);
+                if batch.len() >= BATCH_SIZE {
+                    self.write_inner(mem::take(&mut batch))?;
+                }
+            }
+            if !batch.is_empty() {
+                self.write_inner(batch)?;
+            }
+        }
+        Ok(())
+    }
+
     fn write_inner(&self, raw_batch: rocksdb::WriteBatch) -> Result<(), rocksdb::Error> {
         if self.sync_writes {
             let mut options = WriteOptions::new();
@@ -1032,6 +1200,80 @@ mod tests {
         }
     }
 
+    #[derive(Debug, Clone, Copy)]
+    enum TunedColumnFamilies {
+        Default,
+        Large,
+    }
+
+    impl NamedColumnFamily for TunedColumnFamilies {
+        const DB_NAME: &'static str = "test";
+        const ALL: &'static [Self] = &[Self::Default, Self::Large];
+
+        fn name(&self) -> &'static str {
+            match self {
+                Self::Default => "default",
+                Self::Large => "large",
+            }
+        }
+
+        fn requires_tuning(&self) -> bool {
+            matches!(self, Self::Large)
+        }
+    }
+
+    #[test]
+    fn dedicated_block_caches_for_large_column_families() {
+        let temp_dir = TempDir::new().unwrap();
+        let options = RocksDBOptions {
+            block_cache_capacity: Some(1 << 20),
+            large_cf_block_cache_capacity: Some(1 << 20),
+            large_cf_compaction: CompactionOptions {
+                level0_slowdown_writes_trigger: NonZeroU32::new(40),
+                level0_stop_writes_trigger: NonZeroU32::new(60),
+                soft_pending_compaction_bytes_limit: Some(128 << 30),
+                hard_pending_compaction_bytes_limit: Some(512 << 30),
+                max_subcompactions: NonZeroU32::new(2),
+            },
+            ..RocksDBOptions::default()
+        };
+        let db = RocksDB::<TunedColumnFamilies>::with_options(temp_dir.path(), options).unwrap();
+
+        assert!(db.block_cache_stats().is_some());
+        assert!(db
+            .dedicated_block_cache_stats(TunedColumnFamilies::Default)
+            .is_none());
+        assert!(db
+            .dedicated_block_cache_stats(TunedColumnFamilies::Large)
+            .is_some());
+
+        let mut batch = db.new_write_batch();
+        for i in 0_u32..1_000 {
+            batch.put_cf(TunedColumnFamilies::Large, &i.to_be_bytes(), &[1; 64]);
+        }
+        db.write(batch).unwrap();
+        // Flush the memtable so that reads go through the block cache.
+        let cf = db.column_family(TunedColumnFamilies::Large);
+        db.inner.db.flush_cf(cf).unwrap();
+        for i in 0_u32..1_000 {
+            let value = db
+                .get_cf(TunedColumnFamilies::Large, &i.to_be_bytes())
+                .unwrap();
+            assert_eq!(value.as_deref(), Some([1; 64].as_slice()));
+        }
+        let stats = db
+            .dedicated_block_cache_stats(TunedColumnFamilies::Large)
+            .unwrap();
+        assert!(stats.usage > 0, "{stats:?}");
+
+        db.set_dedicated_block_cache_capacity(TunedColumnFamilies::Large, 0);
+        db.set_block_cache_capacity(2 << 20);
+        let value = db
+            .get_cf(TunedColumnFamilies::Large, &0_u32.to_be_bytes())
+            .unwrap();
+        assert_eq!(value.as_deref(), Some([1; 64].as_slice()));
+    }
+
     #[test]
     fn changing_column_families() {
         let temp_dir = TempDir::new().unwrap();
@@ -1048,6 +1290,52 @@ mod tests {
         assert_eq!(value.unwrap(), b"value");
     }
 
+    #[test]
+    fn copying_db_to_and_from_memory() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = RocksDB::<NewColumnFamilies>::new(temp_dir.path())
+            .unwrap()
+            .with_sync_writes();
+        let mut batch = db.new_write_batch();
+        for i in 0_u32..100 {
+            batch.put_cf(NewColumnFamilies::Default, &i.to_be_bytes(), b"value");
+            batch.put_cf(NewColumnFamilies::Other, &i.to_be_bytes(), b"other");
+        }
+        db.write(batch).unwrap();
+
+        let in_memory_options = RocksDBOptions {
+            in_memory: true,
+            ..RocksDBOptions::default()
+        };
+        let mem_dir = temp_dir.path().join("mem");
+        let mem_db =
+            RocksDB::<NewColumnFamilies>::with_options(&mem_dir, in_memory_options).unwrap();
+        assert!(!mem_dir.exists());
+        mem_db.copy_from(&db).unwrap();
+        drop(db);
+
+        for cf in [NewColumnFamilies::Default, NewColumnFamilies::Other] {
+            let entries: Vec<_> = mem_db.prefix_iterator_cf(cf, &[]).collect();
+            assert_eq!(entries.len(), 100);
+        }
+        let value = mem_db
+            .get_cf(NewColumnFamilies::Other, &42_u32.to_be_bytes())
+            .unwrap();
+        assert_eq!(value.unwrap(), b"other");
+
+        let copy_dir = TempDir::new().unwrap();
+        let copied_db = RocksDB::<NewColumnFamilies>::new(copy_dir.path())
+            .unwrap()
+            .with_sync_writes();
+        copied_db.copy_from(&mem_db).unwrap();
+        drop(copied_db);
+        let copied_db = RocksDB::<NewColumnFamilies>::new(copy_dir.path()).unwrap();
+        let value = copied_db
+            .get_cf(NewColumnFamilies::Default, &99_u32.to_be_bytes())
+            .unwrap();
+        assert_eq!(value.unwrap(), b"value");
+    }
+
     #[derive(Debug, Clone, Copy)]
     struct JunkColumnFamily;
 
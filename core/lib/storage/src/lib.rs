@@ -1,5 +1,7 @@
 pub mod db;
 mod metrics;
 
-pub use db::{RocksDB, RocksDBOptions, StalledWritesRetries, WeakRocksDB};
+pub use db::{
+    BlockCacheStats, CompactionOptions, RocksDB, RocksDBOptions, StalledWritesRetries, WeakRocksDB,
+};
 pub use rocksdb;
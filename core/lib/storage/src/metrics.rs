@@ -76,6 +76,11 @@ pub(crate) struct RocksdbSizeMetrics {
     /// Estimated number of bytes for pending compactions.
     #[metrics(unit = Unit::Bytes)]
     pub pending_compactions: Gauge<u64>,
+    /// Boolean gauge indicating whether at least one compaction is pending for the column family.
+    pub compaction_pending: Gauge<u64>,
+    /// Current rate (bytes per second) to which writes are throttled because of pending compactions.
+    /// Zero if writes are not throttled.
+    pub delayed_write_rate: Gauge<u64>,
 
     /// Estimated size of all live data in the column family of a RocksDB instance.
     pub live_data_size: Gauge<u64>,
@@ -0,0 +1,279 @@
+//! Incremental verification of L1 batches.
+
+use anyhow::Context as _;
+use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
+use zksync_merkle_tree::TreeInstruction;
+use zksync_multivm::{
+    interface::{
+        storage::{StoragePtr, StorageSnapshot, StorageView},
+        L2BlockEnv, VmFactory, VmInterface,
+    },
+    pubdata_builders::pubdata_params_to_builder,
+    vm_latest::HistoryEnabled,
+    LegacyVmInstance,
+};
+use zksync_prover_interface::inputs::{
+    StorageLogMetadata, TeeVerifierInputChunk, TeeVerifierInputHeader,
+};
+use zksync_types::{
+    block::L2BlockExecutionData, commitment::PubdataParams, u256_to_h256, L1BatchNumber,
+    L2BlockNumber, H256,
+};
+
+use crate::{
+    execute_tx, generate_tree_instructions, get_bowp, VerificationResult, VerifiedWitnessStorage,
+};
+
+type VerifierStorage = VerifiedWitnessStorage<StorageSnapshot>;
+
+/// Verifier for an L1 batch that consumes the batch input in chunks.
+///
+/// The batch-wide data (witness storage, Merkle paths and VM environment) is provided upfront in
+/// [`TeeVerifierInputHeader`]; L2 blocks are executed as they arrive via [`Self::process_l2_blocks()`] and can be dropped
+/// afterwards. Thus, only a single L2 block needs to be held in memory at a time, rather than all transactions
+/// in the batch. The root hash is checked in [`Self::finalize()`] once all blocks are processed.
+#[derive(Debug)]
+pub struct IncrementalVerifier {
+    batch_number: L1BatchNumber,
+    old_root_hash: H256,
+    enumeration_index: u64,
+    merkle_paths: Vec<StorageLogMetadata>,
+    pubdata_params: PubdataParams,
+    storage_view: StoragePtr<StorageView<VerifierStorage>>,
+    vm: LegacyVmInstance<VerifierStorage, HistoryEnabled>,
+    expected_l2_block: L2BlockNumber,
+    /// Last received L2 block. Its transactions are executed once the next block arrives, since the last block
+    /// in the batch is the fictive one.
+    pending_l2_block: Option<L2BlockExecutionData>,
+    executed_tx_count: usize,
+}
+
+impl IncrementalVerifier {
+    /// Initializes the verifier from the batch-wide input data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Merkle paths for storage reads are invalid.
+    pub fn new(header: TeeVerifierInputHeader) -> anyhow::Result<Self> {
+        let old_root_hash = header
+            .l1_batch_env
+            .previous_batch_hash
+            .context("previous batch hash is not set in L1 batch env")?;
+        let enumeration_index = header.merkle_paths.next_enumeration_index();
+        let batch_number = header.l1_batch_env.number;
+        let expected_l2_block = L2BlockNumber(header.l1_batch_env.first_l2_block.number);
+
+        let read_storage_ops = header
+            .vm_run_data
+            .witness_block_state
+            .read_storage_key
+            .into_iter();
+
+        let initial_writes_ops = header
+            .vm_run_data
+            .witness_block_state
+            .is_write_initial
+            .into_iter();
+
+        // We need to define storage slots read during batch execution, and their initial state;
+        // hence, the use of both read_storage_ops and initial_writes_ops.
+        // StorageSnapshot also requires providing enumeration indices,
+        // but they only matter at the end of execution when creating pubdata for the batch,
+        // which is irrelevant in this case. Thus, enumeration indices are set to dummy values.
+        let storage = read_storage_ops
+            .enumerate()
+            .map(|(i, (hash, bytes))| (hash.hashed_key(), Some((bytes, i as u64 + 1u64))))
+            .chain(initial_writes_ops.filter_map(|(key, initial_write)| {
+                initial_write.then_some((key.hashed_key(), None))
+            }))
+            .collect();
+
+        let factory_deps = header
+            .vm_run_data
+            .used_bytecodes
+            .into_iter()
+            .map(|(hash, bytes)| (u256_to_h256(hash), bytes.into_flattened()))
+            .collect();
+
+        let storage_snapshot = StorageSnapshot::new(storage, factory_deps);
+        // Values in the snapshot are provided by the prover and must not be trusted; instead, they are checked
+        // against Merkle paths, which are in turn checked against the trusted root hash.
+        let merkle_paths: Vec<_> = header.merkle_paths.into_merkle_paths().collect();
+        let storage = VerifiedWitnessStorage::from_merkle_paths(
+            storage_snapshot,
+            old_root_hash,
+            &merkle_paths,
+        )
+        .context("failed verifying Merkle paths for storage reads")?;
+        let storage_view = StorageView::new(storage).to_rc_ptr();
+        let vm =
+            LegacyVmInstance::new(header.l1_batch_env, header.system_env, storage_view.clone());
+
+        Ok(Self {
+            batch_number,
+            old_root_hash,
+            enumeration_index,
+            merkle_paths,
+            pubdata_params: header.pubdata_params,
+            storage_view,
+            vm,
+            expected_l2_block,
+            pending_l2_block: None,
+            executed_tx_count: 0,
+        })
+    }
+
+    /// Returns the number of the L1 batch being verified.
+    pub fn batch_number(&self) -> L1BatchNumber {
+        self.batch_number
+    }
+
+    /// Returns the number of transactions executed so far.
+    pub fn executed_tx_count(&self) -> usize {
+        self.executed_tx_count
+    }
+
+    /// Processes a chunk of the batch input. The first chunk must be the header, which is consumed by [`Self::new()`];
+    /// thus, this method only accepts [`TeeVerifierInputChunk::L2Blocks`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chunk is a header, or if processing L2 blocks fails.
+    pub fn process_chunk(&mut self, chunk: TeeVerifierInputChunk) -> anyhow::Result<()> {
+        match chunk {
+            TeeVerifierInputChunk::Header(_) => {
+                anyhow::bail!(
+                    "unexpected header chunk for L1 batch #{}",
+                    self.batch_number
+                )
+            }
+            TeeVerifierInputChunk::L2Blocks(blocks) => self.process_l2_blocks(blocks),
+        }
+    }
+
+    /// Executes the provided L2 blocks. Blocks must be consecutive and continue the previously processed ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if blocks are out of order, or if executing a transaction fails.
+    pub fn process_l2_blocks(
+        &mut self,
+        blocks: impl IntoIterator<Item = L2BlockExecutionData>,
+    ) -> anyhow::Result<()> {
+        for block in blocks {
+            anyhow::ensure!(
+                block.number == self.expected_l2_block,
+                "unexpected L2 block #{} in L1 batch #{}; expected #{}",
+                block.number,
+                self.batch_number,
+                self.expected_l2_block
+            );
+            self.expected_l2_block += 1;
+
+            if let Some(prev_block) = self.pending_l2_block.take() {
+                self.execute_l2_block(prev_block, &block)?;
+            }
+            self.pending_l2_block = Some(block);
+        }
+        Ok(())
+    }
+
+    fn execute_l2_block(
+        &mut self,
+        l2_block_data: L2BlockExecutionData,
+        next_l2_block_data: &L2BlockExecutionData,
+    ) -> anyhow::Result<()> {
+        tracing::trace!(
+            "Started execution of l2_block: {:?}, executing {:?} transactions",
+            l2_block_data.number,
+            l2_block_data.txs.len(),
+        );
+        for tx in &l2_block_data.txs {
+            tracing::trace!("Started execution of tx: {tx:?}");
+            execute_tx(tx, &mut self.vm)
+                .context("failed to execute transaction in TeeVerifierInputProducer")?;
+            self.executed_tx_count += 1;
+            tracing::trace!("Finished execution of tx: {tx:?}");
+        }
+
+        tracing::trace!("finished l2_block {l2_block_data:?}");
+        tracing::trace!("about to vm.start_new_l2_block {next_l2_block_data:?}");
+
+        self.vm
+            .start_new_l2_block(L2BlockEnv::from_l2_block_data(next_l2_block_data));
+
+        tracing::trace!("Finished execution of l2_block: {:?}", l2_block_data.number);
+        Ok(())
+    }
+
+    /// Finishes the batch and checks that it produces the expected root hash, verifying the Merkle paths
+    /// of all touched storage slots against the VM output.
+    ///
+    /// # Errors
+    ///
+    /// Returns a verbose error of the failure, because any error is not actionable.
+    pub fn finalize(mut self) -> anyhow::Result<VerificationResult> {
+        let batch_number = self.batch_number;
+        tracing::trace!("about to vm.finish_batch()");
+        let vm_out = self
+            .vm
+            .finish_batch(pubdata_params_to_builder(self.pubdata_params));
+
+        let unproven_key_count = self
+            .storage_view
+            .borrow_mut()
+            .inner_mut()
+            .unproven_keys()
+            .len();
+        if unproven_key_count > 0 {
+            tracing::warn!(
+                "{unproven_key_count} storage slots accessed during execution of L1 batch #{batch_number} \
+                 are not covered by Merkle paths"
+            );
+        }
+
+        let block_output_with_proofs = get_bowp(self.merkle_paths)?;
+
+        let instructions: Vec<TreeInstruction> =
+            generate_tree_instructions(self.enumeration_index, &block_output_with_proofs, vm_out)?;
+
+        block_output_with_proofs
+            .verify_proofs(&Blake2Hasher, self.old_root_hash, &instructions)
+            .context("Failed to verify_proofs {l1_batch_number} correctly!")?;
+
+        Ok(VerificationResult {
+            value_hash: block_output_with_proofs.root_hash().unwrap(),
+            batch_number,
+        })
+    }
+}
+
+/// Verifies an L1 batch from a sequence of input chunks, e.g. ones lazily deserialized from a stream.
+/// Chunks must start with [`TeeVerifierInputChunk::Header`], followed by [`TeeVerifierInputChunk::L2Blocks`] chunks.
+///
+/// # Errors
+///
+/// Returns an error if the chunk sequence is malformed, or if verification fails.
+pub fn verify_chunks(
+    chunks: impl IntoIterator<Item = TeeVerifierInputChunk>,
+) -> anyhow::Result<VerificationResult> {
+    let mut chunks = chunks.into_iter();
+    let header = match chunks.next() {
+        Some(TeeVerifierInputChunk::Header(header)) => header,
+        Some(TeeVerifierInputChunk::L2Blocks(_)) => {
+            anyhow::bail!("first chunk of TEE verifier input must be a header")
+        }
+        None => anyhow::bail!("TEE verifier input has no chunks"),
+    };
+
+    let mut verifier = IncrementalVerifier::new(header)?;
+    for chunk in chunks {
+        verifier.process_chunk(chunk)?;
+    }
+    tracing::debug!(
+        "Executed {} transactions in L1 batch #{}",
+        verifier.executed_tx_count(),
+        verifier.batch_number()
+    );
+    verifier.finalize()
+}
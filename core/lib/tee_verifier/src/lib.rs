@@ -4,29 +4,28 @@
 //! executing the VM and verifying all the accessed memory slots by their
 //! merkle path.
 
-use anyhow::{bail, Context, Result};
-use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
+use anyhow::{bail, Result};
 use zksync_merkle_tree::{
     BlockOutputWithProofs, TreeInstruction, TreeLogEntry, TreeLogEntryWithProof, ValueHash,
 };
 use zksync_multivm::{
-    interface::{
-        storage::{ReadStorage, StorageSnapshot, StorageView},
-        FinishedL1Batch, L2BlockEnv, VmFactory, VmInterface, VmInterfaceExt,
-        VmInterfaceHistoryEnabled,
-    },
-    pubdata_builders::pubdata_params_to_builder,
+    interface::{storage::ReadStorage, FinishedL1Batch, VmInterfaceExt, VmInterfaceHistoryEnabled},
     vm_latest::HistoryEnabled,
     LegacyVmInstance,
 };
 use zksync_prover_interface::inputs::{
-    StorageLogMetadata, V1TeeVerifierInput, WitnessInputMerklePaths,
+    StorageLogMetadata, TeeVerifierInputHeader, V1TeeVerifierInput,
 };
-use zksync_types::{
-    block::L2BlockExecutionData, commitment::PubdataParams, u256_to_h256, L1BatchNumber,
-    StorageLog, StorageValue, Transaction, H256,
+use zksync_types::{L1BatchNumber, StorageLog, StorageValue, Transaction, H256};
+
+pub use self::{
+    incremental::{verify_chunks, IncrementalVerifier},
+    storage::VerifiedWitnessStorage,
 };
 
+mod incremental;
+mod storage;
+
 /// A structure to hold the result of verification.
 pub struct VerificationResult {
     /// The root hash of the batch that was verified.
@@ -50,67 +49,24 @@ impl Verify for V1TeeVerifierInput {
     /// Returns a verbose error of the failure, because any error is
     /// not actionable.
     fn verify(self) -> anyhow::Result<VerificationResult> {
-        let old_root_hash = self.l1_batch_env.previous_batch_hash.unwrap();
-        let enumeration_index = self.merkle_paths.next_enumeration_index();
-        let batch_number = self.l1_batch_env.number;
-
-        let read_storage_ops = self
-            .vm_run_data
-            .witness_block_state
-            .read_storage_key
-            .into_iter();
-
-        let initial_writes_ops = self
-            .vm_run_data
-            .witness_block_state
-            .is_write_initial
-            .into_iter();
-
-        // We need to define storage slots read during batch execution, and their initial state;
-        // hence, the use of both read_storage_ops and initial_writes_ops.
-        // StorageSnapshot also requires providing enumeration indices,
-        // but they only matter at the end of execution when creating pubdata for the batch,
-        // which is irrelevant in this case. Thus, enumeration indices are set to dummy values.
-        let storage = read_storage_ops
-            .enumerate()
-            .map(|(i, (hash, bytes))| (hash.hashed_key(), Some((bytes, i as u64 + 1u64))))
-            .chain(initial_writes_ops.filter_map(|(key, initial_write)| {
-                initial_write.then_some((key.hashed_key(), None))
-            }))
-            .collect();
-
-        let factory_deps = self
-            .vm_run_data
-            .used_bytecodes
-            .into_iter()
-            .map(|(hash, bytes)| (u256_to_h256(hash), bytes.into_flattened()))
-            .collect();
-
-        let storage_snapshot = StorageSnapshot::new(storage, factory_deps);
-        let storage_view = StorageView::new(storage_snapshot).to_rc_ptr();
-        let vm = LegacyVmInstance::new(self.l1_batch_env, self.system_env, storage_view);
-        let vm_out = execute_vm(self.l2_blocks_execution_data, vm, self.pubdata_params)?;
-
-        let block_output_with_proofs = get_bowp(self.merkle_paths)?;
-
-        let instructions: Vec<TreeInstruction> =
-            generate_tree_instructions(enumeration_index, &block_output_with_proofs, vm_out)?;
-
-        block_output_with_proofs
-            .verify_proofs(&Blake2Hasher, old_root_hash, &instructions)
-            .context("Failed to verify_proofs {l1_batch_number} correctly!")?;
-
-        Ok(VerificationResult {
-            value_hash: block_output_with_proofs.root_hash().unwrap(),
-            batch_number,
-        })
+        let l2_blocks_execution_data = self.l2_blocks_execution_data;
+        let header = TeeVerifierInputHeader {
+            vm_run_data: self.vm_run_data,
+            merkle_paths: self.merkle_paths,
+            l1_batch_env: self.l1_batch_env,
+            system_env: self.system_env,
+            pubdata_params: self.pubdata_params,
+        };
+        let mut verifier = IncrementalVerifier::new(header)?;
+        verifier.process_l2_blocks(l2_blocks_execution_data)?;
+        verifier.finalize()
     }
 }
 
 /// Sets the initial storage values and returns `BlockOutputWithProofs`
-fn get_bowp(witness_input_merkle_paths: WitnessInputMerklePaths) -> Result<BlockOutputWithProofs> {
-    let logs_result: Result<_, _> = witness_input_merkle_paths
-        .into_merkle_paths()
+fn get_bowp(merkle_paths: Vec<StorageLogMetadata>) -> Result<BlockOutputWithProofs> {
+    let logs_result: Result<_, _> = merkle_paths
+        .into_iter()
         .map(
             |StorageLogMetadata {
                  root_hash,
@@ -175,42 +131,6 @@ fn get_bowp(witness_input_merkle_paths: WitnessInputMerklePaths) -> Result<Block
     })
 }
 
-/// Executes the VM and returns `FinishedL1Batch` on success.
-fn execute_vm<S: ReadStorage>(
-    l2_blocks_execution_data: Vec<L2BlockExecutionData>,
-    mut vm: LegacyVmInstance<S, HistoryEnabled>,
-    pubdata_params: PubdataParams,
-) -> anyhow::Result<FinishedL1Batch> {
-    let next_l2_blocks_data = l2_blocks_execution_data.iter().skip(1);
-
-    let l2_blocks_data = l2_blocks_execution_data.iter().zip(next_l2_blocks_data);
-
-    for (l2_block_data, next_l2_block_data) in l2_blocks_data {
-        tracing::trace!(
-            "Started execution of l2_block: {:?}, executing {:?} transactions",
-            l2_block_data.number,
-            l2_block_data.txs.len(),
-        );
-        for tx in &l2_block_data.txs {
-            tracing::trace!("Started execution of tx: {tx:?}");
-            execute_tx(tx, &mut vm)
-                .context("failed to execute transaction in TeeVerifierInputProducer")?;
-            tracing::trace!("Finished execution of tx: {tx:?}");
-        }
-
-        tracing::trace!("finished l2_block {l2_block_data:?}");
-        tracing::trace!("about to vm.start_new_l2_block {next_l2_block_data:?}");
-
-        vm.start_new_l2_block(L2BlockEnv::from_l2_block_data(next_l2_block_data));
-
-        tracing::trace!("Finished execution of l2_block: {:?}", l2_block_data.number);
-    }
-
-    tracing::trace!("about to vm.finish_batch()");
-
-    Ok(vm.finish_batch(pubdata_params_to_builder(pubdata_params)))
-}
-
 /// Map `LogQuery` and `TreeLogEntry` to a `TreeInstruction`
 fn map_log_tree(
     storage_log: &StorageLog,
@@ -302,14 +222,16 @@ fn execute_tx<S: ReadStorage>(
 #[cfg(test)]
 mod tests {
     use zksync_contracts::{BaseSystemContracts, SystemContractCode};
-    use zksync_multivm::interface::{L1BatchEnv, SystemEnv, TxExecutionMode};
-    use zksync_prover_interface::inputs::{TeeVerifierInput, VMRunWitnessInputData};
+    use zksync_multivm::interface::{L1BatchEnv, L2BlockEnv, SystemEnv, TxExecutionMode};
+    use zksync_prover_interface::inputs::{
+        TeeVerifierInput, TeeVerifierInputChunk, VMRunWitnessInputData, WitnessInputMerklePaths,
+    };
+    use zksync_types::{block::L2BlockExecutionData, L2BlockNumber};
 
     use super::*;
 
-    #[test]
-    fn test_v1_serialization() {
-        let tvi = V1TeeVerifierInput::new(
+    fn mock_input(l2_blocks_execution_data: Vec<L2BlockExecutionData>) -> V1TeeVerifierInput {
+        V1TeeVerifierInput::new(
             VMRunWitnessInputData {
                 l1_batch_number: Default::default(),
                 used_bytecodes: Default::default(),
@@ -321,10 +243,12 @@ mod tests {
                 storage_refunds: vec![],
                 pubdata_costs: vec![],
                 witness_block_state: Default::default(),
+                base_system_contracts_hashes: None,
+                precompile_inputs: None,
                 _marker: std::marker::PhantomData,
             },
             WitnessInputMerklePaths::new(0),
-            vec![],
+            l2_blocks_execution_data,
             L1BatchEnv {
                 previous_batch_hash: Some(H256([1; 32])),
                 number: Default::default(),
@@ -359,12 +283,51 @@ mod tests {
                 chain_id: Default::default(),
             },
             Default::default(),
-        );
-        let tvi = TeeVerifierInput::new(tvi);
+        )
+    }
+
+    fn mock_l2_block(number: u32) -> L2BlockExecutionData {
+        L2BlockExecutionData {
+            number: L2BlockNumber(number),
+            timestamp: number.into(),
+            prev_block_hash: H256::repeat_byte(number as u8),
+            virtual_blocks: 1,
+            txs: vec![],
+        }
+    }
+
+    #[test]
+    fn test_v1_serialization() {
+        let tvi = TeeVerifierInput::new(mock_input(vec![]));
         let serialized = bincode::serialize(&tvi).expect("Failed to serialize TeeVerifierInput.");
         let deserialized: TeeVerifierInput =
             bincode::deserialize(&serialized).expect("Failed to deserialize TeeVerifierInput.");
 
         assert_eq!(tvi, deserialized);
     }
+
+    #[test]
+    fn test_chunks_serialization() {
+        let blocks = (0..5).map(mock_l2_block).collect();
+        let chunks = mock_input(blocks).into_chunks(2);
+        assert_eq!(chunks.len(), 4);
+
+        for chunk in &chunks {
+            let serialized =
+                bincode::serialize(chunk).expect("Failed to serialize TeeVerifierInputChunk.");
+            let deserialized: TeeVerifierInputChunk = bincode::deserialize(&serialized)
+                .expect("Failed to deserialize TeeVerifierInputChunk.");
+            assert_eq!(*chunk, deserialized);
+        }
+    }
+
+    #[test]
+    fn verifying_chunks_requires_header() {
+        let err = verify_chunks(Vec::new()).err().unwrap();
+        assert!(err.to_string().contains("no chunks"), "{err:#}");
+
+        let chunks = [TeeVerifierInputChunk::L2Blocks(vec![mock_l2_block(0)])];
+        let err = verify_chunks(chunks).err().unwrap();
+        assert!(err.to_string().contains("must be a header"), "{err:#}");
+    }
 }
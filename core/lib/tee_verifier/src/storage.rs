@@ -0,0 +1,330 @@
+//! Storage with values verified against Merkle proofs.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{ensure, Context as _};
+use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
+use zksync_merkle_tree::{Key, TreeEntry, TreeEntryWithProof, ValueHash};
+use zksync_multivm::interface::storage::ReadStorage;
+use zksync_prover_interface::inputs::StorageLogMetadata;
+use zksync_types::{StorageKey, StorageValue, H256};
+
+/// Storage slot state proven by a Merkle path.
+#[derive(Debug, Clone, Copy)]
+struct ProvenEntry {
+    value: StorageValue,
+    leaf_index: u64,
+}
+
+impl From<TreeEntry> for ProvenEntry {
+    fn from(entry: TreeEntry) -> Self {
+        Self {
+            value: entry.value,
+            leaf_index: entry.leaf_index,
+        }
+    }
+}
+
+/// [`ReadStorage`] wrapper checking values returned by the wrapped storage against Merkle proofs.
+///
+/// All proofs are verified when the storage is created. Afterwards, each storage slot value returned by the wrapped storage
+/// is compared with the proven value, and a mismatch results in a panic (similar to other complete storage snapshots,
+/// e.g. [`StorageSnapshot`](zksync_multivm::interface::storage::StorageSnapshot)). Enumeration indices and initial write
+/// flags are taken from the proofs; thus, the wrapped storage may contain placeholder indices.
+///
+/// Slots not covered by proofs are read from the wrapped storage as-is and are recorded, so that the caller can decide
+/// how to treat them; see [`Self::unproven_keys()`]. Factory dependencies are always read from the wrapped storage.
+#[derive(Debug)]
+pub struct VerifiedWitnessStorage<S> {
+    inner: S,
+    entries: HashMap<Key, ProvenEntry>,
+    unproven_keys: HashSet<StorageKey>,
+}
+
+impl<S: ReadStorage> VerifiedWitnessStorage<S> {
+    /// Creates storage with entries proven against a single trusted root hash of the Merkle tree, e.g. ones returned by
+    /// the Merkle tree API.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the proofs is invalid, or if proofs for the same key are provided multiple times.
+    pub fn new(
+        inner: S,
+        trusted_root_hash: ValueHash,
+        entries: impl IntoIterator<Item = TreeEntryWithProof>,
+    ) -> anyhow::Result<Self> {
+        let mut proven_entries = HashMap::new();
+        for entry in entries {
+            let key = entry.base.key;
+            entry
+                .verify(&Blake2Hasher, trusted_root_hash)
+                .with_context(|| format!("invalid proof for key {key:0>64x}"))?;
+            let prev_entry = proven_entries.insert(key, entry.base.into());
+            ensure!(prev_entry.is_none(), "duplicate proof for key {key:0>64x}");
+        }
+
+        Ok(Self {
+            inner,
+            entries: proven_entries,
+            unproven_keys: HashSet::new(),
+        })
+    }
+
+    /// Creates storage from Merkle paths produced by the Merkle tree when processing an L1 batch (e.g., included
+    /// into witness inputs). Paths must be fully expanded, i.e. obtained via
+    /// [`WitnessInputMerklePaths::into_merkle_paths()`](zksync_prover_interface::inputs::WitnessInputMerklePaths::into_merkle_paths()).
+    ///
+    /// Paths are chained: each path is checked against the root hash after applying the previous storage log,
+    /// starting from the trusted `old_root_hash`. Thus, values read before the first write in the batch are fully
+    /// verified by this method. Root hashes after writes additionally depend on written values, which are only
+    /// known after the batch is executed; hence, the caller must verify the paths against the VM output as well,
+    /// e.g. using [`BlockOutputWithProofs::verify_proofs()`](zksync_merkle_tree::BlockOutputWithProofs::verify_proofs()).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the paths is invalid or inconsistent with the previous paths.
+    pub fn from_merkle_paths(
+        inner: S,
+        old_root_hash: ValueHash,
+        merkle_paths: &[StorageLogMetadata],
+    ) -> anyhow::Result<Self> {
+        let mut root_hash = old_root_hash;
+        let mut proven_entries = HashMap::with_capacity(merkle_paths.len());
+        for (i, path) in merkle_paths.iter().enumerate() {
+            let key = path.leaf_hashed_key;
+            let base = match (path.is_write, path.first_write, path.leaf_enumeration_index) {
+                (false, true, _) => {
+                    anyhow::bail!("read #{i} for key {key:0>64x} is marked as a first write")
+                }
+                (false, false, 0) | (true, true, _) => TreeEntry::new(key, 0, H256::zero()),
+                (_, false, leaf_index) => TreeEntry::new(key, leaf_index, H256(path.value_read)),
+            };
+            let proof = TreeEntryWithProof {
+                base,
+                merkle_path: path.merkle_paths.iter().copied().map(H256).collect(),
+            };
+            proof
+                .verify(&Blake2Hasher, root_hash)
+                .with_context(|| format!("invalid Merkle path #{i} for key {key:0>64x}"))?;
+
+            let next_root_hash = H256(path.root_hash);
+            ensure!(
+                path.is_write || next_root_hash == root_hash,
+                "read #{i} for key {key:0>64x} changes root hash: {root_hash:?} -> {next_root_hash:?}"
+            );
+            root_hash = next_root_hash;
+
+            let prev_entry = proven_entries.insert(key, base.into());
+            ensure!(
+                prev_entry.is_none(),
+                "duplicate Merkle path for key {key:0>64x}"
+            );
+        }
+
+        Ok(Self {
+            inner,
+            entries: proven_entries,
+            unproven_keys: HashSet::new(),
+        })
+    }
+
+    /// Returns storage keys accessed so far that are not covered by the provided proofs.
+    pub fn unproven_keys(&self) -> &HashSet<StorageKey> {
+        &self.unproven_keys
+    }
+
+    /// Returns the wrapped storage.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn proven_entry(&mut self, key: &StorageKey) -> Option<ProvenEntry> {
+        let entry = self.entries.get(&key.hashed_key_u256()).copied();
+        if entry.is_none() && self.unproven_keys.insert(*key) {
+            tracing::debug!("Storage slot {key:?} is not covered by Merkle proofs");
+        }
+        entry
+    }
+}
+
+impl<S: ReadStorage> ReadStorage for VerifiedWitnessStorage<S> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        let value = self.inner.read_value(key);
+        if let Some(entry) = self.proven_entry(key) {
+            assert_eq!(
+                value, entry.value,
+                "value for storage slot {key:?} doesn't match its Merkle proof"
+            );
+        }
+        value
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        match self.proven_entry(key) {
+            Some(entry) => entry.leaf_index == 0,
+            None => self.inner.is_write_initial(key),
+        }
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        self.inner.load_factory_dep(hash)
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        match self.proven_entry(key) {
+            Some(entry) => (entry.leaf_index > 0).then_some(entry.leaf_index),
+            None => self.inner.get_enumeration_index(key),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_merkle_tree::{MerkleTree, PatchSet, TreeInstruction, TreeLogEntry};
+    use zksync_multivm::interface::storage::InMemoryStorage;
+    use zksync_types::{AccountTreeId, Address};
+
+    use super::*;
+
+    fn storage_key(i: u64) -> StorageKey {
+        StorageKey::new(
+            AccountTreeId::new(Address::repeat_byte(0x11)),
+            H256::from_low_u64_be(i),
+        )
+    }
+
+    /// Creates a tree with keys 0..5 and a storage with the same state.
+    fn create_tree_and_storage() -> (MerkleTree<PatchSet>, InMemoryStorage) {
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        let mut storage = InMemoryStorage::default();
+        let entries = (0..5).map(|i| {
+            let key = storage_key(i);
+            let value = H256::from_low_u64_be(i + 100);
+            storage.set_value(key, value);
+            TreeEntry::new(key.hashed_key_u256(), i + 1, value)
+        });
+        tree.extend(entries.collect()).unwrap();
+        (tree, storage)
+    }
+
+    #[test]
+    fn verifying_storage_with_tree_proofs() {
+        let (tree, storage) = create_tree_and_storage();
+        let root_hash = tree.root_hash(0).unwrap();
+        let keys = [0, 1, 10].map(|i| storage_key(i).hashed_key_u256());
+        let proofs = tree.entries_with_proofs(0, &keys).unwrap();
+
+        let err =
+            VerifiedWitnessStorage::new(storage.clone(), H256::zero(), proofs.clone()).unwrap_err();
+        assert!(err.to_string().contains("invalid proof"), "{err:#}");
+
+        let mut storage = VerifiedWitnessStorage::new(storage, root_hash, proofs).unwrap();
+        assert_eq!(
+            storage.read_value(&storage_key(0)),
+            H256::from_low_u64_be(100)
+        );
+        assert_eq!(storage.get_enumeration_index(&storage_key(1)), Some(2));
+        assert!(storage.is_write_initial(&storage_key(10)));
+        assert!(storage.unproven_keys().is_empty());
+
+        assert_eq!(
+            storage.read_value(&storage_key(3)),
+            H256::from_low_u64_be(103)
+        );
+        assert_eq!(*storage.unproven_keys(), HashSet::from([storage_key(3)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't match its Merkle proof")]
+    fn tampered_value_is_detected() {
+        let (tree, mut storage) = create_tree_and_storage();
+        let root_hash = tree.root_hash(0).unwrap();
+        let proofs = tree
+            .entries_with_proofs(0, &[storage_key(0).hashed_key_u256()])
+            .unwrap();
+        storage.set_value(storage_key(0), H256::repeat_byte(0xff));
+
+        let mut storage = VerifiedWitnessStorage::new(storage, root_hash, proofs).unwrap();
+        storage.read_value(&storage_key(0));
+    }
+
+    #[test]
+    fn verifying_storage_with_batch_merkle_paths() {
+        let (mut tree, storage) = create_tree_and_storage();
+        let old_root_hash = tree.root_hash(0).unwrap();
+        let instructions = vec![
+            TreeInstruction::Read(storage_key(0).hashed_key_u256()),
+            TreeInstruction::Write(TreeEntry::new(
+                storage_key(1).hashed_key_u256(),
+                2,
+                H256::repeat_byte(1),
+            )),
+            TreeInstruction::Read(storage_key(10).hashed_key_u256()),
+            TreeInstruction::Write(TreeEntry::new(
+                storage_key(11).hashed_key_u256(),
+                6,
+                H256::repeat_byte(2),
+            )),
+        ];
+        let output = tree.extend_with_proofs(instructions.clone()).unwrap();
+
+        let merkle_paths: Vec<_> = output
+            .logs
+            .iter()
+            .zip(&instructions)
+            .map(|(log, instruction)| {
+                let (leaf_enumeration_index, value_read) = match log.base {
+                    TreeLogEntry::Read { leaf_index, value }
+                    | TreeLogEntry::Updated {
+                        leaf_index,
+                        previous_value: value,
+                    } => (leaf_index, value.0),
+                    TreeLogEntry::Inserted | TreeLogEntry::ReadMissingKey => (0, [0; 32]),
+                };
+                StorageLogMetadata {
+                    root_hash: log.root_hash.0,
+                    is_write: matches!(
+                        log.base,
+                        TreeLogEntry::Inserted | TreeLogEntry::Updated { .. }
+                    ),
+                    first_write: matches!(log.base, TreeLogEntry::Inserted),
+                    merkle_paths: log.merkle_path.iter().map(|hash| hash.0).collect(),
+                    leaf_hashed_key: instruction.key(),
+                    leaf_enumeration_index,
+                    value_written: [0; 32],
+                    value_read,
+                }
+            })
+            .collect();
+
+        let mut storage =
+            VerifiedWitnessStorage::from_merkle_paths(storage, old_root_hash, &merkle_paths)
+                .unwrap();
+        assert_eq!(
+            storage.read_value(&storage_key(0)),
+            H256::from_low_u64_be(100)
+        );
+        assert_eq!(
+            storage.read_value(&storage_key(1)),
+            H256::from_low_u64_be(101)
+        );
+        assert!(!storage.is_write_initial(&storage_key(1)));
+        assert_eq!(storage.read_value(&storage_key(10)), H256::zero());
+        assert!(storage.is_write_initial(&storage_key(11)));
+        assert!(storage.unproven_keys().is_empty());
+
+        // Tamper with a value read in the batch.
+        let mut tampered_paths = merkle_paths;
+        tampered_paths[0].value_read = [0xff; 32];
+        let err = VerifiedWitnessStorage::from_merkle_paths(
+            InMemoryStorage::default(),
+            old_root_hash,
+            &tampered_paths,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("invalid Merkle path #0"),
+            "{err:#}"
+        );
+    }
+}
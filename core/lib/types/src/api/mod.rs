@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use derive_more::Display;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
@@ -15,9 +15,11 @@ pub use crate::transaction_request::{
 };
 use crate::{
     debug_flat_call::{DebugCallFlat, ResultDebugCallFlat},
+    fee::Fee,
+    fee_model::BatchFeeInput,
     protocol_version::L1VerifierConfig,
     tee_types::TeeType,
-    Address, L2BlockNumber, ProtocolVersionId,
+    Address, L2BlockNumber, PackedEthSignature, ProtocolVersionId,
 };
 
 pub mod en;
@@ -274,6 +276,51 @@ pub struct TransactionReceipt {
     /// Effective gas price
     #[serde(rename = "effectiveGasPrice")]
     pub effective_gas_price: Option<U256>,
+    /// Paymaster context; `None` if the transaction doesn't use a paymaster.
+    #[serde(
+        rename = "paymasterInfo",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub paymaster_info: Option<PaymasterReceiptInfo>,
+    /// Settlement layer transactions for the L1 batch this transaction was included within;
+    /// `None` if the batch is not committed yet.
+    #[serde(
+        rename = "l1Finality",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub l1_finality: Option<ReceiptL1Finality>,
+}
+
+/// Settlement layer transactions for the L1 batch of a transaction included into [`TransactionReceipt`].
+/// Allows to check finality of a transaction without additional calls. Fields are set once the corresponding
+/// settlement layer transaction is confirmed.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptL1Finality {
+    pub commit_tx_hash: Option<H256>,
+    pub committed_at: Option<DateTime<Utc>>,
+    pub prove_tx_hash: Option<H256>,
+    pub proven_at: Option<DateTime<Utc>>,
+    pub execute_tx_hash: Option<H256>,
+    pub executed_at: Option<DateTime<Utc>>,
+}
+
+/// Paymaster context of a transaction included into [`TransactionReceipt`].
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymasterReceiptInfo {
+    /// Paymaster address.
+    pub paymaster: Address,
+    /// First 4 bytes of the paymaster input identifying the paymaster flow (e.g., `general` or `approvalBased`).
+    /// `None` if the paymaster input is shorter than 4 bytes.
+    pub paymaster_input_selector: Option<Bytes>,
+    /// Fee in the base token paid by the paymaster (i.e., gas used multiplied by the effective gas price).
+    pub sponsored_fee: U256,
+    /// Fee in the base token paid by the transaction initiator. Note that the initiator may still compensate
+    /// the paymaster in other tokens (e.g., in the `approvalBased` flow); such transfers are not accounted for here.
+    pub user_paid_fee: U256,
 }
 
 /// The block type returned from RPC calls.
@@ -644,6 +691,9 @@ pub struct GetLogsFilter {
     pub to_block: L2BlockNumber,
     pub addresses: Vec<Address>,
     pub topics: Vec<(u32, Vec<H256>)>,
+    /// If set, restricts the filter to the specified L2 blocks from the block range (e.g., ones that may contain
+    /// matching logs according to log blooms).
+    pub l2_blocks: Option<Vec<L2BlockNumber>>,
 }
 
 /// Result of debugging block
@@ -677,6 +727,21 @@ pub struct DebugCall {
     pub error: Option<String>,
     pub revert_reason: Option<String>,
     pub calls: Vec<DebugCall>,
+    /// Parts of the call dropped because the trace exceeded the size limit.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub truncated: Vec<TruncatedTraceField>,
+}
+
+/// Part of a call trace that can be dropped if the trace exceeds the size limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TruncatedTraceField {
+    /// Data returned by the call.
+    Output,
+    /// Call data.
+    Input,
+    /// Nested calls.
+    Calls,
 }
 
 // TODO (PLA-965): remove deprecated fields from the struct. It is currently in a "migration" phase
@@ -769,6 +834,39 @@ impl ProtocolVersion {
     }
 }
 
+/// Protocol version known to the node together with its activation metadata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolVersionActivation {
+    /// Minor version of the protocol.
+    pub minor_version: u16,
+    /// Timestamp at which upgrade should be performed.
+    pub timestamp: u64,
+    /// First L1 batch executed with this protocol version. `None` if the version is not activated yet.
+    pub first_l1_batch: Option<L1BatchNumber>,
+    /// Timestamp of the first L1 batch executed with this protocol version.
+    pub first_l1_batch_timestamp: Option<u64>,
+    /// Bootloader code hash.
+    pub bootloader_code_hash: H256,
+    /// Default account code hash.
+    pub default_account_code_hash: H256,
+    /// EVM emulator code hash.
+    pub evm_emulator_code_hash: Option<H256>,
+    /// L2 upgrade transaction hash.
+    pub l2_system_upgrade_tx_hash: Option<H256>,
+    /// Patches of the protocol version in the ascending order.
+    pub patches: Vec<ProtocolVersionPatch>,
+}
+
+/// Patch of a protocol version.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolVersionPatch {
+    pub patch: u32,
+    /// Verifier configuration used by the patch.
+    pub verification_keys_hashes: L1VerifierConfig,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
 pub enum SupportedTracers {
@@ -801,6 +899,108 @@ impl Default for TracerConfig {
     }
 }
 
+impl TracerConfig {
+    /// Creates a config for the call tracer returning a tree of calls.
+    pub fn call_tracer() -> Self {
+        Self::default()
+    }
+
+    /// Creates a config for the flat call tracer returning a flat list of calls (similar to Parity traces).
+    pub fn flat_call_tracer() -> Self {
+        Self {
+            tracer: SupportedTracers::FlatCallTracer,
+            tracer_config: CallTracerConfig::default(),
+        }
+    }
+
+    /// Makes the tracer return only the top-level call.
+    #[must_use]
+    pub fn only_top_call(mut self) -> Self {
+        self.tracer_config.only_top_call = true;
+        self
+    }
+}
+
+/// Override of the batch fee input for simulation methods (`eth_estimateGas`, `zks_estimateFee` and `debug_traceCall`),
+/// e.g. to estimate transaction costs under different L1 gas prices. The override is an optional trailing parameter
+/// of these methods, so callers that don't need it may omit it. Unset fields are taken from the fee input
+/// that would be used without the override.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeInputOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_gas_price: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fair_l2_gas_price: Option<u64>,
+    /// Ignored for L1-pegged fee inputs (i.e., for protocol versions before 1.4.1), for which the pubdata price
+    /// is derived from the L1 gas price.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fair_pubdata_price: Option<u64>,
+}
+
+impl FeeInputOverride {
+    /// Applies this override to the provided fee input. The fee input variant is preserved.
+    pub fn apply(&self, fee_input: BatchFeeInput) -> BatchFeeInput {
+        match fee_input {
+            BatchFeeInput::L1Pegged(input) => BatchFeeInput::l1_pegged(
+                self.l1_gas_price.unwrap_or(input.l1_gas_price),
+                self.fair_l2_gas_price.unwrap_or(input.fair_l2_gas_price),
+            ),
+            BatchFeeInput::PubdataIndependent(input) => BatchFeeInput::pubdata_independent(
+                self.l1_gas_price.unwrap_or(input.l1_gas_price),
+                self.fair_l2_gas_price.unwrap_or(input.fair_l2_gas_price),
+                self.fair_pubdata_price.unwrap_or(input.fair_pubdata_price),
+            ),
+        }
+    }
+}
+
+/// Fee estimate together with its sensitivity to the gas per pubdata byte price, as returned by `zks_estimateFeeWithSensitivity`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeWithSensitivity {
+    /// Fee estimate for the current gas per pubdata price; same as the output of `zks_estimateFee`.
+    #[serde(flatten)]
+    pub fee: Fee,
+    pub gas_per_pubdata_sensitivity: GasPerPubdataSensitivity,
+    /// Usage of precompiles by the transaction executed with the estimated gas limit. Only precompiles called
+    /// at least once are included.
+    #[serde(default)]
+    pub precompile_usage: Vec<PrecompileUsage>,
+}
+
+/// Usage of a single precompile by a transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrecompileUsage {
+    /// Human-readable precompile name, e.g. `p256Verify`.
+    pub name: String,
+    pub address: Address,
+    /// Number of calls to the precompile.
+    pub call_count: u32,
+    /// Total gas used by all calls to the precompile.
+    pub gas_used: U256,
+}
+
+/// Dependency of the gas limit required by a transaction on the gas per pubdata byte price.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasPerPubdataSensitivity {
+    /// Number of pubdata bytes published by the transaction.
+    pub pubdata_published: u32,
+    /// Gas limits for a range of gas per pubdata prices, in the ascending price order.
+    pub points: Vec<GasPerPubdataSensitivityPoint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasPerPubdataSensitivityPoint {
+    pub gas_per_pubdata: U256,
+    /// Required gas limit (including the operator overhead and the estimation scale factor).
+    /// `None` if the transaction wouldn't fit into a batch with this gas per pubdata price.
+    pub gas_limit: Option<U256>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum BlockStatus {
@@ -899,6 +1099,9 @@ pub struct L1BatchDetails {
     pub number: L1BatchNumber,
     #[serde(flatten)]
     pub base: BlockDetailsBase,
+    /// Bloom filter for logs emitted in all L2 blocks of the batch. `None` if the bloom is not computed
+    /// (e.g., for old batches).
+    pub logs_bloom: Option<Bloom>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -917,6 +1120,26 @@ pub struct Proof {
     pub storage_proof: Vec<StorageProof>,
 }
 
+/// Storage slot value proven by a [`MultiProof`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageMultiProofEntry {
+    pub key: H256,
+    pub value: H256,
+    pub index: u64,
+}
+
+/// Values of multiple storage slots of an account together with a single Merkle multiproof for all of them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiProof {
+    pub address: Address,
+    /// Storage slots in the same order as requested.
+    pub storage: Vec<StorageMultiProofEntry>,
+    /// Multiproof for all storage slots in the compact binary format.
+    pub proof: Bytes,
+}
+
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -988,6 +1211,322 @@ pub struct L1ToL2TxsStatus {
     pub l1_to_l2_txs_paused: bool,
 }
 
+/// Consumption status of an L2-to-L1 message on L1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum L2ToL1MessageConsumption {
+    /// Consumption of the message is not tracked. Only messages sent by the bridge contracts in L1 batches
+    /// executed on L1 are tracked.
+    Untracked,
+    /// The message wasn't consumed on L1 yet.
+    Pending,
+    /// The message was consumed on L1 (e.g., the corresponding withdrawal was finalized).
+    Consumed,
+}
+
+/// Status of an L2-to-L1 message sent via the `L1Messenger` system contract.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L2ToL1MessageStatus {
+    pub transaction_hash: H256,
+    pub block_number: L2BlockNumber,
+    pub sender: Address,
+    /// Keccak-256 hash of the message.
+    pub message_hash: H256,
+    /// L1 batch containing the message, or `None` if the batch is not sealed yet.
+    pub l1_batch_number: Option<L1BatchNumber>,
+    /// Index of the message log in the L2-to-L1 logs Merkle tree of the L1 batch.
+    pub merkle_index: Option<u32>,
+    /// Whether the Merkle proof for the message can be obtained using `zks_getL2ToL1LogProof`.
+    pub proof_available: bool,
+    /// Whether the L1 batch is executed on L1, i.e. the message can be consumed on L1.
+    pub l1_batch_executed: bool,
+    pub consumption: L2ToL1MessageConsumption,
+    /// L1 block at which the message was observed to be consumed.
+    pub consumed_at_l1_block: Option<u64>,
+}
+
+/// Sequencer-signed commitment to the inclusion of a transaction at a specific position of an L2 block.
+/// The signature is produced over [`SoftConfirmationCommitment::digest()`](crate::soft_confirmation::SoftConfirmationCommitment::digest()).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoftConfirmation {
+    pub transaction_hash: H256,
+    pub block_number: L2BlockNumber,
+    pub transaction_index: u32,
+    pub signer: Address,
+    pub signature: PackedEthSignature,
+    pub signed_at: DateTime<Utc>,
+}
+
+/// Way in which an account is involved in a transaction or an L2 block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AccountActivityKind {
+    /// Account is the transaction initiator.
+    Sender,
+    /// Account is the transaction recipient.
+    Receiver,
+    /// Account storage was modified.
+    StorageWrite,
+}
+
+/// Entry in the account activity history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountActivityEntry {
+    pub block_number: L2BlockNumber,
+    /// Transaction hash. Storage writes are tracked per L2 block rather than per transaction, so this field
+    /// is `None` for entries only containing [`AccountActivityKind::StorageWrite`].
+    pub transaction_hash: Option<H256>,
+    pub transaction_index: Option<u32>,
+    pub kinds: Vec<AccountActivityKind>,
+}
+
+/// Account activity history in a range of L2 blocks, returned by `zks_getAccountActivity`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountActivity {
+    pub from_block: L2BlockNumber,
+    /// Last L2 block covered by `entries`. If the number of entries in the requested range exceeds the server limit,
+    /// this is less than the end of the requested range; the remaining entries can be requested starting from `to_block + 1`.
+    pub to_block: L2BlockNumber,
+    /// Entries ordered by the L2 block number and then by the transaction index. Within an L2 block, the storage write entry
+    /// (if any) goes last.
+    pub entries: Vec<AccountActivityEntry>,
+}
+
+/// Nonce details of an account, returned by `zks_getAccountNonceDetails`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountNonceDetails {
+    /// Account nonce as of the latest sealed L2 block, i.e., the nonce of the next transaction to be executed.
+    pub committed_nonce: U256,
+    /// Nonce to be used by the next submitted transaction, taking into account transactions pending in the mempool.
+    /// This is the same value as returned by `eth_getTransactionCount` for the pending block.
+    pub pending_nonce: U256,
+    /// Nonces of transactions from the account pending in the mempool, in the ascending order.
+    pub mempool_nonces: Vec<U256>,
+    /// Nonces between `committed_nonce` and the greatest mempool nonce without a pending transaction. Transactions
+    /// with nonces after the first gap cannot be executed until the gap is filled.
+    pub nonce_gaps: Vec<U256>,
+    /// Deployment nonce of the account as of the latest sealed L2 block.
+    pub deployment_nonce: U256,
+}
+
+/// Class of transactions for which inclusion stats are tracked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxInclusionClass {
+    /// Priority (L1 -> L2) transactions.
+    L1,
+    /// Transactions submitted via the L2 API.
+    L2,
+}
+
+impl TxInclusionClass {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::L1 => "l1",
+            Self::L2 => "l2",
+        }
+    }
+}
+
+/// Transaction processing stage for which inclusion stats are tracked. Latencies are measured from the moment
+/// the transaction was received by the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxInclusionStage {
+    /// Inclusion into an L2 block.
+    Inclusion,
+    /// Execution of the containing L1 batch on L1.
+    Finality,
+}
+
+impl TxInclusionStage {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Inclusion => "inclusion",
+            Self::Finality => "finality",
+        }
+    }
+}
+
+/// Latency percentiles for a certain class of transactions and processing stage, returned by `zks_getInclusionStats`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionStats {
+    pub tx_class: TxInclusionClass,
+    pub stage: TxInclusionStage,
+    /// Number of transactions the percentiles are computed for.
+    pub sample_count: u64,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    /// Stats cover transactions received since this moment.
+    pub window_start: DateTime<Utc>,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Resource by which contracts are ranked in `zks_getTopContracts`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize
+)]
+#[serde(rename_all = "camelCase")]
+pub enum ContractUsageMetric {
+    /// Gas used by transactions calling the contract.
+    #[default]
+    Gas,
+    /// Pubdata published by transactions calling the contract.
+    Pubdata,
+}
+
+/// Resources consumed by transactions calling a certain contract during a day (UTC), returned by `zks_getTopContracts`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractUsage {
+    /// Address of the contract called by transactions (i.e., the `to` address).
+    pub address: Address,
+    pub day: NaiveDate,
+    /// Number of transactions calling the contract.
+    pub tx_count: u64,
+    pub gas_used: u64,
+    pub pubdata_published: u64,
+}
+
+/// Report on simulated L1 batch sealing for pending mempool transactions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SealCriteriaSimulation {
+    /// Number of pending transactions considered by the simulation.
+    pub pending_transactions: usize,
+    /// Number of transactions that would be included into the batch.
+    pub included_transactions: usize,
+    /// Seal criterion triggered by the pending transactions, or `None` if all of them fit into the batch.
+    pub seal: Option<SimulatedSeal>,
+    /// Transactions that would be rejected by the state keeper as unexecutable.
+    pub unexecutable_transactions: Vec<SimulatedTxRejection>,
+    /// Transactions that have failed in the sandbox (e.g., because of a nonce gap) and were skipped.
+    pub skipped_transactions: Vec<H256>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedSeal {
+    /// Name of the triggered seal criterion.
+    pub criterion: String,
+    /// Transaction that has triggered the criterion.
+    pub transaction_hash: H256,
+    /// Whether the triggering transaction would be included into the sealed batch.
+    pub transaction_included: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedTxRejection {
+    pub transaction_hash: H256,
+    pub criterion: String,
+}
+
+/// Transaction rejected by the transaction policy engine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxPolicyDecision {
+    pub transaction_hash: H256,
+    pub initiator_address: Address,
+    /// Stage at which the transaction was rejected: `submission` or `inclusion`.
+    pub stage: String,
+    /// Name of the violated policy rule.
+    pub rule: String,
+    /// Human-readable rejection reason.
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Change of the deployed bytecode at a certain address caused by a protocol upgrade transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemContractDiff {
+    pub address: Address,
+    /// Bytecode hash before the upgrade; zero if there was no contract at the address.
+    pub previous_code_hash: H256,
+    pub new_code_hash: H256,
+}
+
+/// Result of a protocol upgrade transaction dry run executed against the latest sealed state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolUpgradeDryRun {
+    pub protocol_version: ProtocolVersionId,
+    pub upgrade_tx_hash: H256,
+    /// Last sealed L1 batch at the time of the dry run; the upgrade transaction was executed on top of its state.
+    pub base_l1_batch_number: L1BatchNumber,
+    /// Error if the upgrade transaction (or the batch containing it) has failed; `None` if the dry run was successful.
+    pub error: Option<String>,
+    pub system_contract_diffs: Vec<SystemContractDiff>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// State of the queues processed by the node components, as reported by the `admin` namespace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentQueues {
+    /// Whether the API server rejects new transactions.
+    pub tx_intake_paused: bool,
+    /// Whether a forced seal of the current L1 batch is pending. `None` if the state keeper doesn't run
+    /// in the same process as the API server.
+    pub l1_batch_seal_requested: Option<bool>,
+    pub last_sealed_l1_batch: Option<L1BatchNumber>,
+    pub last_committed_l1_batch: Option<L1BatchNumber>,
+    pub last_proven_l1_batch: Option<L1BatchNumber>,
+    pub last_executed_l1_batch: Option<L1BatchNumber>,
+    /// Number of Ethereum transactions sent by the operator that are not confirmed yet.
+    pub unconfirmed_eth_txs: usize,
+    /// Oldest L1 batch that has proof generation data but is not picked by a prover yet.
+    pub oldest_unpicked_proof_batch: Option<L1BatchNumber>,
+    /// Oldest L1 batch without a generated proof.
+    pub oldest_unproven_batch: Option<L1BatchNumber>,
+}
+
+/// Lease allowing a sequencer instance to seal L2 blocks and L1 batches, as reported by the `admin` namespace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SequencerLease {
+    /// Identifier of the sequencer instance holding the lease.
+    pub instance_id: String,
+    /// Fencing epoch incremented each time the lease changes hands.
+    pub epoch: u64,
+    /// Expiration time of the lease unless it's renewed by the holder.
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Entry of the audit log recording privileged actions performed by node operators.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogEntry {
+    /// Sequential ID of the entry.
+    pub id: u64,
+    /// Performed action, e.g. `admin_pauseTxIntake` or `revert_l1_batches`.
+    pub action: String,
+    /// Identity of the operator or process that performed the action.
+    pub actor: String,
+    /// State affected by the action before it was performed, if known.
+    pub before: Option<Value>,
+    /// State affected by the action after it was performed, if known.
+    pub after: Option<Value>,
+    /// Time when the action was performed.
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct EcosystemContracts {
     pub bridgehub_proxy_addr: Address,
@@ -1001,6 +1540,64 @@ pub struct EcosystemContracts {
     pub server_notifier_addr: Option<Address>,
 }
 
+/// Latest write to a storage slot, as returned by `zks_getStorageSlotWriter`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageSlotWriter {
+    /// L2 block in which the slot was last modified.
+    pub l2_block_number: L2BlockNumber,
+    /// Hash of the transaction that last modified the slot. `None` if the slot was modified outside
+    /// of a transaction (e.g., at genesis), or if the node didn't record storage slot writers for the block.
+    pub tx_hash: Option<H256>,
+    /// Value of the slot after the write.
+    pub value: H256,
+}
+
+/// Disassembled EraVM bytecode, as returned by `zks_disassembleBytecode`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisassembledBytecode {
+    /// Decoded instructions in the program order. Since EraVM bytecodes don't separate code from the constant pool,
+    /// trailing constants are decoded as instructions as well.
+    pub instructions: Vec<DisassembledInstruction>,
+    /// Basic blocks covering all instructions.
+    pub basic_blocks: Vec<BasicBlock>,
+}
+
+/// Single decoded EraVM instruction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisassembledInstruction {
+    /// Instruction index in the bytecode (i.e., its byte offset divided by 8).
+    pub pc: u16,
+    /// Raw instruction encoding.
+    pub raw: U64,
+    /// Opcode together with its variant, e.g. `Binop(Xor)`.
+    pub opcode: String,
+    /// Execution condition, e.g. `always` or `eq`.
+    pub condition: String,
+    pub src0: u8,
+    pub src1: u8,
+    pub dst0: u8,
+    pub dst1: u8,
+    pub imm0: u16,
+    pub imm1: u16,
+}
+
+/// Basic block of EraVM instructions, i.e. a sequence of instructions with a single entry point
+/// and control flow changes only at its end.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BasicBlock {
+    /// PC of the first instruction in the block.
+    pub start: u16,
+    /// PC of the last instruction in the block (inclusive).
+    pub end: u16,
+    /// Starting PCs of blocks that control can be statically determined to flow to from this block.
+    /// Jumps to addresses computed at runtime are not included.
+    pub successors: Vec<u16>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1053,4 +1650,22 @@ mod tests {
         let block_number = BlockNumber::Number(U64::from(42));
         assert_eq!(format!("{}", block_number), "42");
     }
+
+    #[test]
+    fn applying_fee_input_override() {
+        let fee_input_override: FeeInputOverride =
+            serde_json::from_str(r#"{ "l1GasPrice": 100, "fairPubdataPrice": 5000 }"#).unwrap();
+
+        let fee_input = BatchFeeInput::pubdata_independent(10, 20, 30);
+        assert_eq!(
+            fee_input_override.apply(fee_input),
+            BatchFeeInput::pubdata_independent(100, 20, 5000)
+        );
+        let fee_input = BatchFeeInput::l1_pegged(10, 20);
+        assert_eq!(
+            fee_input_override.apply(fee_input),
+            BatchFeeInput::l1_pegged(100, 20)
+        );
+        assert_eq!(FeeInputOverride::default().apply(fee_input), fee_input);
+    }
 }
@@ -0,0 +1,73 @@
+//! Artifacts of sealed L1 batches exported to external systems (e.g., for analytics).
+
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::{Address, L1BatchNumber, L2BlockNumber, H256, U256};
+
+/// Execution result of a transaction included into an exported L1 batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedTransaction {
+    pub hash: H256,
+    pub l2_block_number: L2BlockNumber,
+    pub index_in_l2_block: u32,
+    pub is_priority: bool,
+    pub initiator_address: Address,
+    /// Transaction recipient; `None` for some L1 and upgrade transactions.
+    pub to: Option<Address>,
+    /// Nonce of the initiator; `None` for L1 and upgrade transactions.
+    pub nonce: Option<u64>,
+    /// Revert reason; `None` if the transaction succeeded.
+    pub error: Option<String>,
+    pub gas_limit: U256,
+    pub gas_used: U256,
+    pub effective_gas_price: U256,
+}
+
+/// Storage slot written to in an exported L1 batch, together with its final value in the batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedStorageDiff {
+    pub address: Address,
+    pub key: H256,
+    pub value: H256,
+}
+
+/// Event emitted in an exported L1 batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedEvent {
+    pub tx_hash: H256,
+    pub l2_block_number: L2BlockNumber,
+    pub index_in_l2_block: u32,
+    pub address: Address,
+    pub topics: Vec<H256>,
+    pub data: Vec<u8>,
+}
+
+/// Pubdata-related statistics of an exported L1 batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedPubdataStats {
+    /// Size of the pubdata input in bytes; `None` for L1 batches produced by old protocol versions.
+    pub pubdata_input_size: Option<usize>,
+    pub storage_diff_count: usize,
+    pub user_l2_to_l1_log_count: usize,
+    pub l2_to_l1_message_count: usize,
+    /// Total size of L2-to-L1 messages in bytes.
+    pub l2_to_l1_messages_size: usize,
+}
+
+/// All artifacts of a single sealed L1 batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchArtifacts {
+    pub l1_batch_number: L1BatchNumber,
+    pub timestamp: u64,
+    /// Transactions ordered by the L2 block number and index in block.
+    pub transactions: Vec<ExportedTransaction>,
+    /// Storage diffs ordered by the address and key.
+    pub storage_diffs: Vec<ExportedStorageDiff>,
+    /// Events ordered by the L2 block number and index in block.
+    pub events: Vec<ExportedEvent>,
+    pub pubdata: ExportedPubdataStats,
+}
@@ -0,0 +1,79 @@
+//! Types used to move old call traces and events from Postgres to cold storage (i.e., an object store).
+
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::{L1BatchNumber, L2BlockNumber, H256};
+
+/// Kind of data that can be moved to cold storage. Postgres rows for archived data are retained as stubs
+/// (i.e., with their payload cleared), so that filtering by indexed columns keeps working.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ColdStorageDataKind {
+    /// Call traces returned by `debug_traceTransaction` and similar methods. Archived rows have empty `call_trace`.
+    CallTraces,
+    /// Events emitted by transactions. Archived rows have empty `value`, but retain addresses and topics.
+    Events,
+}
+
+impl ColdStorageDataKind {
+    pub const ALL: [Self; 2] = [Self::CallTraces, Self::Events];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CallTraces => "call_traces",
+            Self::Events => "events",
+        }
+    }
+}
+
+/// Call trace moved to cold storage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedCallTrace {
+    pub tx_hash: H256,
+    /// Serialized call trace in the same format as it was stored in Postgres.
+    pub call_trace: Vec<u8>,
+}
+
+/// All call traces for a single L1 batch moved to cold storage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CallTracesArchive {
+    pub l1_batch_number: L1BatchNumber,
+    pub call_traces: Vec<ArchivedCallTrace>,
+}
+
+impl CallTracesArchive {
+    /// Returns the serialized call trace for the specified transaction.
+    pub fn get(&self, tx_hash: H256) -> Option<&[u8]> {
+        self.call_traces
+            .iter()
+            .find(|trace| trace.tx_hash == tx_hash)
+            .map(|trace| trace.call_trace.as_slice())
+    }
+}
+
+/// Payload of an event moved to cold storage. The event is identified by its L2 block and index in this block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedEvent {
+    pub l2_block_number: L2BlockNumber,
+    pub event_index_in_block: u32,
+    pub value: Vec<u8>,
+}
+
+/// All events for a single L1 batch moved to cold storage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventsArchive {
+    pub l1_batch_number: L1BatchNumber,
+    /// Events ordered by the L2 block number and index in block.
+    pub events: Vec<ArchivedEvent>,
+}
+
+impl EventsArchive {
+    /// Returns the payload of the specified event.
+    pub fn get(&self, l2_block_number: L2BlockNumber, event_index_in_block: u32) -> Option<&[u8]> {
+        let idx = self
+            .events
+            .binary_search_by_key(&(l2_block_number, event_index_in_block), |event| {
+                (event.l2_block_number, event.event_index_in_block)
+            })
+            .ok()?;
+        Some(&self.events[idx].value)
+    }
+}
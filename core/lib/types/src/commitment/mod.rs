@@ -29,7 +29,7 @@ use crate::{
     u256_to_h256,
     web3::keccak256,
     writes::{
-        compress_state_diffs, InitialStorageWrite, RepeatedStorageWrite, StateDiffRecord,
+        InitialStorageWrite, RepeatedStorageWrite, StateDiffCompressionVersion, StateDiffRecord,
         PADDED_ENCODED_STORAGE_DIFF_LEN_BYTES,
     },
     ProtocolVersionId, H256,
@@ -407,7 +407,10 @@ impl L1BatchAuxiliaryOutput {
 
                 let state_diffs_packed = serialize_commitments(&state_diffs);
                 let state_diffs_hash = H256::from(keccak256(&(state_diffs_packed)));
-                let state_diffs_compressed = compress_state_diffs(state_diffs);
+                let state_diffs_compressed = StateDiffCompressionVersion::for_protocol_version(
+                    common_input.protocol_version,
+                )
+                .compress(state_diffs);
 
                 // Sanity checks. System logs are empty for the genesis batch, so we can't do checks for it.
                 if !system_logs.is_empty() {
@@ -19,6 +19,8 @@ pub enum SourceCodeData {
     StandardJsonInput(serde_json::Map<String, serde_json::Value>),
     #[serde(rename = "vyper-multi-file")]
     VyperMultiFile(HashMap<String, String>),
+    #[serde(rename = "vyper-single-file")]
+    VyperSingleFile(String),
     #[serde(rename = "yul-single-file")]
     YulSingleFile(String),
 }
@@ -29,7 +31,9 @@ impl SourceCodeData {
             SourceCodeData::SolSingleFile(_)
             | SourceCodeData::StandardJsonInput(_)
             | SourceCodeData::YulSingleFile(_) => CompilerType::Solc,
-            SourceCodeData::VyperMultiFile(_) => CompilerType::Vyper,
+            SourceCodeData::VyperMultiFile(_) | SourceCodeData::VyperSingleFile(_) => {
+                CompilerType::Vyper
+            }
         }
     }
 }
@@ -112,6 +116,17 @@ impl<'de> Visitor<'de> for SourceCodeVisitor {
                     .map_err(|_| A::Error::custom("invalid object"))?;
                 SourceCodeData::VyperMultiFile(sources)
             }
+            Some("vyper-single-file") => {
+                let value = source_code.ok_or_else(|| A::Error::missing_field("source_code"))?;
+                SourceCodeData::VyperSingleFile(
+                    value
+                        .as_str()
+                        .ok_or_else(|| {
+                            A::Error::invalid_type(Unexpected::Other(&value.to_string()), &self)
+                        })?
+                        .to_string(),
+                )
+            }
             Some(x) => {
                 return Err(A::Error::unknown_variant(
                     x,
@@ -120,6 +135,7 @@ impl<'de> Visitor<'de> for SourceCodeVisitor {
                         "solidity-standard-json-input",
                         "yul-single-file",
                         "vyper-multi-file",
+                        "vyper-single-file",
                     ],
                 ))
             }
@@ -151,6 +167,91 @@ pub struct VerificationIncomingRequest {
     pub evm_specific: VerificationEvmSettings,
 }
 
+impl VerificationIncomingRequest {
+    /// Validates and normalizes the standard JSON input (if any) so that the stored request is self-contained
+    /// and can be used to reproduce the compilation:
+    ///
+    /// - All sources must be provided inline; `urls` and other source fields are dropped.
+    /// - `settings.outputSelection` is dropped since it's always overridden by the verifier; other settings
+    ///   (remappings, libraries, `viaIR`, optimizer settings etc.) are preserved as is.
+    /// - Unknown top-level fields are dropped.
+    /// - The contract name is qualified with the source path (`path/to/File.sol:Name`) if it's not qualified
+    ///   already and the source file can be determined unambiguously.
+    pub fn normalize_standard_json_input(&mut self) -> Result<(), StandardJsonInputError> {
+        let SourceCodeData::StandardJsonInput(input) = &mut self.source_code_data else {
+            return Ok(());
+        };
+
+        let extension = match input.get("language").and_then(serde_json::Value::as_str) {
+            Some("Solidity") => "sol",
+            Some("Yul") => "yul",
+            _ => return Err(StandardJsonInputError::UnsupportedLanguage),
+        };
+        let sources = input
+            .get_mut("sources")
+            .and_then(serde_json::Value::as_object_mut)
+            .filter(|sources| !sources.is_empty())
+            .ok_or(StandardJsonInputError::NoSources)?;
+        for (path, source) in sources.iter_mut() {
+            let content = source
+                .get("content")
+                .and_then(serde_json::Value::as_str)
+                .ok_or_else(|| StandardJsonInputError::NoSourceContent(path.clone()))?;
+            *source = serde_json::json!({ "content": content });
+        }
+
+        if let Some((path, _)) = self.contract_name.rsplit_once(':') {
+            if !sources.contains_key(path) {
+                return Err(StandardJsonInputError::MissingContractSource(
+                    path.to_owned(),
+                ));
+            }
+        } else {
+            let file_name = format!("{}.{extension}", self.contract_name);
+            let mut candidates = sources.keys().filter(|path| {
+                sources.len() == 1 || path.rsplit('/').next() == Some(file_name.as_str())
+            });
+            let path = match (candidates.next(), candidates.next()) {
+                (Some(path), None) => path.clone(),
+                _ => {
+                    return Err(StandardJsonInputError::AmbiguousContractName(
+                        self.contract_name.clone(),
+                    ))
+                }
+            };
+            self.contract_name = format!("{path}:{}", self.contract_name);
+        }
+
+        if let Some(settings) = input
+            .get_mut("settings")
+            .and_then(serde_json::Value::as_object_mut)
+        {
+            settings.remove("outputSelection");
+        }
+        input.retain(|key, _| matches!(key.as_str(), "language" | "sources" | "settings"));
+        Ok(())
+    }
+}
+
+/// Errors produced by [`VerificationIncomingRequest::normalize_standard_json_input()`].
+#[derive(Debug, thiserror::Error)]
+pub enum StandardJsonInputError {
+    #[error("standard JSON input must have `language` set to `Solidity` or `Yul`")]
+    UnsupportedLanguage,
+    #[error("standard JSON input has no sources")]
+    NoSources,
+    #[error("source `{0}` has no inline `content`; sources referenced by URLs are not supported")]
+    NoSourceContent(String),
+    #[error(
+        "source `{0}` referenced in the contract name is missing from the standard JSON input"
+    )]
+    MissingContractSource(String),
+    #[error(
+        "cannot determine the source file for contract `{0}`; specify the contract name as `path/to/File.sol:{0}`"
+    )]
+    AmbiguousContractName(String),
+}
+
 /// Settings for EVM verification, used only if
 /// `SourceCodeData` is `SolSingleFile`.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
@@ -269,6 +370,16 @@ impl VerificationInfo {
         self.verification_problems.is_empty()
     }
 
+    /// Returns the status of the verification request that resulted in this info. Partial matches
+    /// (e.g., ones with metadata-stripped bytecode equality) have a distinct status.
+    pub fn request_status(&self) -> &'static str {
+        if self.is_perfect_match() {
+            VerificationRequestStatus::SUCCESSFUL
+        } else {
+            VerificationRequestStatus::PARTIAL_MATCH
+        }
+    }
+
     pub fn bytecode_marker(&self) -> BytecodeMarker {
         // Deployed bytecode is only present for EVM contracts.
         if self.artifacts.deployed_bytecode.is_some() {
@@ -289,11 +400,19 @@ pub struct VerificationRequestStatus {
     pub compilation_errors: Option<Vec<String>>,
 }
 
+impl VerificationRequestStatus {
+    /// Status of a request that resulted in a full bytecode match.
+    pub const SUCCESSFUL: &'static str = "successful";
+    /// Status of a request that resulted in a partial bytecode match, i.e. bytecodes are equal
+    /// after stripping metadata.
+    pub const PARTIAL_MATCH: &'static str = "partial_match";
+}
+
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
 
-    use super::SourceCodeData;
+    use super::*;
 
     #[test]
     fn source_code_deserialization() {
@@ -309,6 +428,14 @@ mod tests {
             Ok(SourceCodeData::StandardJsonInput(_))
         );
 
+        let vyper_single_file_str = r#"{"codeFormat": "vyper-single-file", "sourceCode": "text"}"#;
+        let vyper_single_file_result =
+            serde_json::from_str::<SourceCodeData>(vyper_single_file_str);
+        assert_matches!(
+            vyper_single_file_result,
+            Ok(SourceCodeData::VyperSingleFile(code)) if code == "text"
+        );
+
         let type_not_specified_str = r#"{"sourceCode": "text"}"#;
         let type_not_specified_result =
             serde_json::from_str::<SourceCodeData>(type_not_specified_str);
@@ -322,4 +449,110 @@ mod tests {
             serde_json::from_str::<SourceCodeData>(type_not_specified_object_str);
         assert!(type_not_specified_object_result.is_err());
     }
+
+    fn standard_json_request(
+        contract_name: &str,
+        input: serde_json::Value,
+    ) -> VerificationIncomingRequest {
+        serde_json::from_value(serde_json::json!({
+            "contractAddress": Address::repeat_byte(0x23),
+            "codeFormat": "solidity-standard-json-input",
+            "sourceCode": input,
+            "contractName": contract_name,
+            "compilerSolcVersion": "0.8.28",
+            "optimizationUsed": true,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn normalizing_standard_json_input() {
+        let input = serde_json::json!({
+            "language": "Solidity",
+            "sources": {
+                "contracts/Counter.sol": {
+                    "content": "import \"lib/Math.sol\"; contract Counter {}",
+                    "keccak256": "0x00",
+                },
+                "lib/Math.sol": { "content": "library Math {}" },
+            },
+            "settings": {
+                "remappings": ["@oz/=lib/oz/"],
+                "libraries": { "lib/Math.sol": { "Math": "0x0000000000000000000000000000000000000001" } },
+                "viaIR": true,
+                "outputSelection": { "*": { "*": ["abi"] } },
+            },
+            "extra": 1,
+        });
+        let mut request = standard_json_request("Counter", input);
+        request.normalize_standard_json_input().unwrap();
+
+        assert_eq!(request.contract_name, "contracts/Counter.sol:Counter");
+        let SourceCodeData::StandardJsonInput(input) = &request.source_code_data else {
+            panic!(
+                "unexpected source code data: {:?}",
+                request.source_code_data
+            );
+        };
+        assert_eq!(
+            serde_json::Value::Object(input.clone()),
+            serde_json::json!({
+                "language": "Solidity",
+                "sources": {
+                    "contracts/Counter.sol": {
+                        "content": "import \"lib/Math.sol\"; contract Counter {}",
+                    },
+                    "lib/Math.sol": { "content": "library Math {}" },
+                },
+                "settings": {
+                    "remappings": ["@oz/=lib/oz/"],
+                    "libraries": { "lib/Math.sol": { "Math": "0x0000000000000000000000000000000000000001" } },
+                    "viaIR": true,
+                },
+            })
+        );
+
+        // Normalization is idempotent.
+        let normalized = request.clone();
+        request.normalize_standard_json_input().unwrap();
+        assert_eq!(request.contract_name, normalized.contract_name);
+    }
+
+    #[test]
+    fn normalizing_invalid_standard_json_input() {
+        let sources = serde_json::json!({
+            "a/Test.sol": { "content": "contract Test {}" },
+            "b/Test.sol": { "content": "contract Test {}" },
+        });
+        let mut request = standard_json_request(
+            "Test",
+            serde_json::json!({ "language": "Solidity", "sources": sources }),
+        );
+        let err = request.normalize_standard_json_input().unwrap_err();
+        assert_matches!(err, StandardJsonInputError::AmbiguousContractName(name) if name == "Test");
+
+        let mut request = standard_json_request(
+            "c/Test.sol:Test",
+            serde_json::json!({ "language": "Solidity", "sources": sources }),
+        );
+        let err = request.normalize_standard_json_input().unwrap_err();
+        assert_matches!(err, StandardJsonInputError::MissingContractSource(path) if path == "c/Test.sol");
+
+        let mut request = standard_json_request(
+            "Test",
+            serde_json::json!({ "language": "Vyper", "sources": sources }),
+        );
+        let err = request.normalize_standard_json_input().unwrap_err();
+        assert_matches!(err, StandardJsonInputError::UnsupportedLanguage);
+
+        let mut request = standard_json_request(
+            "Test",
+            serde_json::json!({
+                "language": "Solidity",
+                "sources": { "Test.sol": { "urls": ["ipfs://test"] } },
+            }),
+        );
+        let err = request.normalize_standard_json_input().unwrap_err();
+        assert_matches!(err, StandardJsonInputError::NoSourceContent(path) if path == "Test.sol");
+    }
 }
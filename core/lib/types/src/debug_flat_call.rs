@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 use zksync_basic_types::{web3::Bytes, U256};
 
-use crate::{api::DebugCallType, Address, H256};
+use crate::{
+    api::{DebugCallType, TruncatedTraceField},
+    Address, H256,
+};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -23,6 +26,10 @@ pub struct DebugCallFlat {
     pub block_number: u32,
     pub block_hash: H256,
     pub r#type: DebugCallType,
+    /// Parts of the call dropped because the trace exceeded the size limit. Dropped nested calls
+    /// are still accounted for in `subtraces`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub truncated: Vec<TruncatedTraceField>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
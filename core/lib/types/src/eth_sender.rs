@@ -82,6 +82,7 @@ pub struct TxHistory {
     pub signed_raw_tx: Vec<u8>,
     pub sent_at_block: Option<u32>,
     pub max_gas_per_pubdata: Option<u64>,
+    pub created_at_timestamp: u64,
 }
 
 #[derive(Clone, Debug)]
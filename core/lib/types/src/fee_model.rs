@@ -3,7 +3,17 @@ use std::num::NonZeroU64;
 use serde::{Deserialize, Serialize};
 use zksync_system_constants::L1_GAS_PER_PUBDATA_BYTE;
 
-use crate::{ceil_div_u256, ProtocolVersionId, U256};
+use crate::{ceil_div_u256, L1BatchNumber, ProtocolVersionId, U256};
+
+/// Resource utilization of a sealed L1 batch. Used by the congestion-based adjustment of the fair L2 gas price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct L1BatchUtilization {
+    pub l1_batch_number: L1BatchNumber,
+    /// L2 gas used by transactions in the batch (i.e., gas limit minus refunds).
+    pub gas_used: u64,
+    /// Size of the pubdata published by the batch in bytes.
+    pub pubdata_size: u64,
+}
 
 /// Fee input to be provided into the VM. It contains two options:
 /// - `L1Pegged`: L1 gas price is provided to the VM, and the pubdata price is derived from it. Using this option is required for the
@@ -287,25 +297,22 @@ impl FeeParamsV2 {
 
     /// Converts the fee param to the base token.
     fn convert_to_base_token(&self, price_in_wei: u64) -> u64 {
-        let converted_price = u128::from(price_in_wei)
-            * u128::from(self.conversion_ratio.numerator.get())
-            / u128::from(self.conversion_ratio.denominator.get());
+        let (numerator, denominator) = self.conversion_ratio.to_smallest_units();
+        let mut converted_price = U256::from(price_in_wei) * numerator / denominator;
+        // For base tokens with fewer decimals than ETH, a non-zero price must not be rounded down to zero.
+        // Conversion for other base tokens is intentionally left as is.
+        if self.conversion_ratio.has_fewer_decimals_than_eth() && price_in_wei > 0 {
+            converted_price = converted_price.max(U256::one());
+        }
 
         // Match on the converted price to ensure it can be represented as a u64
         match converted_price.try_into() {
             Ok(converted_price) => converted_price,
             Err(_) => {
-                if converted_price > u128::from(u64::MAX) {
-                    tracing::warn!(
-                        "Conversion to base token price failed: converted price is too large: {}. Using u64::MAX instead.",
-                        converted_price
-                    );
-                } else {
-                    panic!(
-                        "Conversion to base token price failed: converted price is not a valid u64: {}",
-                        converted_price
-                    );
-                }
+                tracing::warn!(
+                    "Conversion to base token price failed: converted price is too large: {}. Using u64::MAX instead.",
+                    converted_price
+                );
                 u64::MAX
             }
         }
@@ -313,10 +320,20 @@ impl FeeParamsV2 {
 }
 
 /// The struct that represents the BaseToken<->ETH conversion ratio.
+///
+/// The ratio is specified for whole units (i.e., `numerator / denominator` base tokens are worth 1 ETH);
+/// `base_token_decimals` is used to convert it to the ratio between the smallest units of the tokens.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct BaseTokenConversionRatio {
     pub numerator: NonZeroU64,
     pub denominator: NonZeroU64,
+    /// Number of decimals of the base token. Defaults to 18 (same as ETH) for backward compatibility; the default value
+    /// is not serialized, so that the serialized ratio is unchanged for such base tokens.
+    #[serde(
+        default = "BaseTokenConversionRatio::default_base_token_decimals",
+        skip_serializing_if = "BaseTokenConversionRatio::is_default_base_token_decimals"
+    )]
+    pub base_token_decimals: u8,
 }
 
 impl Default for BaseTokenConversionRatio {
@@ -324,6 +341,38 @@ impl Default for BaseTokenConversionRatio {
         Self {
             numerator: NonZeroU64::new(1).unwrap(),
             denominator: NonZeroU64::new(1).unwrap(),
+            base_token_decimals: Self::ETH_DECIMALS,
+        }
+    }
+}
+
+impl BaseTokenConversionRatio {
+    /// Number of decimals of ETH.
+    pub const ETH_DECIMALS: u8 = 18;
+
+    const fn default_base_token_decimals() -> u8 {
+        Self::ETH_DECIMALS
+    }
+
+    fn is_default_base_token_decimals(decimals: &u8) -> bool {
+        *decimals == Self::ETH_DECIMALS
+    }
+
+    fn has_fewer_decimals_than_eth(&self) -> bool {
+        self.base_token_decimals < Self::ETH_DECIMALS
+    }
+
+    /// Returns the numerator and denominator of the ratio converting WEI to the smallest units of the base token.
+    pub fn to_smallest_units(&self) -> (U256, U256) {
+        let numerator = U256::from(self.numerator.get());
+        let denominator = U256::from(self.denominator.get());
+        let decimals = self.base_token_decimals;
+        if decimals >= Self::ETH_DECIMALS {
+            let scale = U256::exp10(usize::from(decimals - Self::ETH_DECIMALS));
+            (numerator * scale, denominator)
+        } else {
+            let scale = U256::exp10(usize::from(Self::ETH_DECIMALS - decimals));
+            (numerator, denominator * scale)
         }
     }
 }
@@ -772,6 +821,7 @@ mod tests {
             BaseTokenConversionRatio {
                 numerator: NonZeroU64::new(3_000_000).unwrap(),
                 denominator: NonZeroU64::new(1).unwrap(),
+                base_token_decimals: 18,
             },
         );
 
@@ -802,9 +852,95 @@ mod tests {
             BaseTokenConversionRatio {
                 numerator: NonZeroU64::new(u64::MAX).unwrap(),
                 denominator: NonZeroU64::new(u64::MAX).unwrap(),
+                base_token_decimals: 18,
             },
         );
         assert_eq!(params.l1_gas_price(), u64::MAX);
         assert_eq!(params.l1_pubdata_price(), u64::MAX - 1);
     }
+
+    #[test]
+    fn test_fee_params_v2_base_token_with_custom_decimals() {
+        let config = FeeModelConfigV2 {
+            minimal_l2_gas_price: GWEI,
+            compute_overhead_part: 0.5,
+            pubdata_overhead_part: 0.5,
+            batch_overhead_l1_gas: 700_000,
+            max_gas_per_batch: 500_000_000,
+            max_pubdata_per_batch: 100_000,
+        };
+        // 1 ETH is worth 2,000 base tokens with 8 decimals, i.e. 1 WEI is worth `2 * 10^-7` base token units.
+        let ratio = BaseTokenConversionRatio {
+            numerator: NonZeroU64::new(2_000).unwrap(),
+            denominator: NonZeroU64::new(1).unwrap(),
+            base_token_decimals: 8,
+        };
+
+        let params = FeeParamsV2::new(config, 100 * GWEI, 3 * GWEI, ratio);
+        assert_eq!(params.l1_gas_price(), 20_000);
+        assert_eq!(params.l1_pubdata_price(), 600);
+        // The minimal gas price (1 gwei) is worth 0.2 base token units, but must not be rounded down to zero.
+        assert_eq!(params.config().minimal_l2_gas_price, 1);
+
+        let params = FeeParamsV2::new(config, 0, 0, ratio);
+        assert_eq!(params.l1_gas_price(), 0);
+
+        // Base tokens with more decimals than ETH are supported as well.
+        let ratio = BaseTokenConversionRatio {
+            base_token_decimals: 24,
+            ..ratio
+        };
+        let params = FeeParamsV2::new(config, GWEI, GWEI, ratio);
+        assert_eq!(params.l1_gas_price(), 2_000 * GWEI * 1_000_000);
+    }
+
+    #[test]
+    fn test_fee_params_v2_base_token_with_eth_decimals_is_not_rounded_up() {
+        let config = FeeModelConfigV2 {
+            minimal_l2_gas_price: 1,
+            compute_overhead_part: 0.5,
+            pubdata_overhead_part: 0.5,
+            batch_overhead_l1_gas: 700_000,
+            max_gas_per_batch: 500_000_000,
+            max_pubdata_per_batch: 100_000,
+        };
+        let ratio = BaseTokenConversionRatio {
+            numerator: NonZeroU64::new(1).unwrap(),
+            denominator: NonZeroU64::new(2).unwrap(),
+            base_token_decimals: 18,
+        };
+
+        let params = FeeParamsV2::new(config, 1, 3, ratio);
+        assert_eq!(params.config().minimal_l2_gas_price, 0);
+        assert_eq!(params.l1_gas_price(), 0);
+        assert_eq!(params.l1_pubdata_price(), 1);
+    }
+
+    #[test]
+    fn serializing_base_token_conversion_ratio() {
+        let ratio = BaseTokenConversionRatio {
+            numerator: NonZeroU64::new(3).unwrap(),
+            denominator: NonZeroU64::new(2).unwrap(),
+            base_token_decimals: 18,
+        };
+        let json = serde_json::to_value(ratio).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "numerator": 3, "denominator": 2 })
+        );
+        let restored: BaseTokenConversionRatio = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.base_token_decimals, 18);
+
+        let ratio = BaseTokenConversionRatio {
+            base_token_decimals: 6,
+            ..ratio
+        };
+        let json = serde_json::to_value(ratio).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "numerator": 3, "denominator": 2, "base_token_decimals": 6 })
+        );
+        let restored: BaseTokenConversionRatio = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.base_token_decimals, 6);
+    }
 }
@@ -29,8 +29,10 @@ pub type SerialId = u64;
 
 pub mod abi;
 pub mod aggregated_operations;
+pub mod batch_export;
 pub mod blob;
 pub mod block;
+pub mod cold_storage;
 pub mod commitment;
 #[cfg(feature = "contract-verification")]
 pub mod contract_verification;
@@ -43,6 +45,7 @@ pub mod l2_to_l1_log;
 pub mod priority_op_onchain_data;
 pub mod protocol_upgrade;
 pub mod snapshots;
+pub mod soft_confirmation;
 pub mod storage;
 pub mod system_contracts;
 pub mod tokens;
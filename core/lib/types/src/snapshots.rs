@@ -1,10 +1,14 @@
-use std::ops;
+use std::{iter, ops};
 
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use serde::{Deserialize, Serialize};
 use zksync_basic_types::{L1BatchNumber, L2BlockNumber, H256};
 
-use crate::{u256_to_h256, utils, web3::Bytes, ProtocolVersionId, StorageKey, StorageValue, U256};
+use crate::{
+    u256_to_h256, utils,
+    web3::{keccak256, Bytes},
+    ProtocolVersionId, StorageKey, StorageValue, U256,
+};
 
 /// Information about all snapshots persisted by the node.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +29,21 @@ pub enum SnapshotVersion {
     /// Snapshot version made compatible with L1 recovery. Differs from `Version0` by including
     /// hashed keys in storage logs instead of `(address, key)` pairs.
     Version1 = 1,
+    /// Uses the same storage log format as `Version1`, but each storage logs chunk is accompanied by
+    /// a [`SnapshotStorageLogsChunkIndex`], which allows to verify chunks independently during recovery.
+    Version2 = 2,
+}
+
+impl SnapshotVersion {
+    /// Checks whether storage logs in this snapshot version use hashed keys.
+    pub fn has_hashed_keys(self) -> bool {
+        matches!(self, Self::Version1 | Self::Version2)
+    }
+
+    /// Checks whether storage log chunks in this snapshot version have [indexes](SnapshotStorageLogsChunkIndex).
+    pub fn has_chunk_indexes(self) -> bool {
+        matches!(self, Self::Version2)
+    }
 }
 
 /// Storage snapshot metadata. Used in DAL to fetch certain snapshot data.
@@ -33,11 +52,17 @@ pub struct SnapshotMetadata {
     pub version: SnapshotVersion,
     /// L1 batch for the snapshot. The data in the snapshot captures node storage at the end of this batch.
     pub l1_batch_number: L1BatchNumber,
+    /// For differential snapshots, L1 batch of the snapshot this snapshot is based on. Differential snapshots only contain
+    /// storage logs and factory dependencies changed after the base snapshot. `None` for full snapshots.
+    pub base_l1_batch_number: Option<L1BatchNumber>,
     /// Path to the factory dependencies blob.
     pub factory_deps_filepath: String,
     /// Paths to the storage log blobs. Ordered by the chunk ID. If a certain chunk is not produced yet,
     /// the corresponding path is `None`.
     pub storage_logs_filepaths: Vec<Option<String>>,
+    /// Checksums of storage log chunks for snapshots with version 2. Ordered by the chunk ID. If a certain chunk
+    /// is not produced yet, the corresponding checksum is `None`. Empty for snapshots with older versions.
+    pub storage_logs_checksums: Vec<Option<H256>>,
 }
 
 impl SnapshotMetadata {
@@ -45,6 +70,11 @@ impl SnapshotMetadata {
     pub fn is_complete(&self) -> bool {
         self.storage_logs_filepaths.iter().all(Option::is_some)
     }
+
+    /// Checks whether this is a differential snapshot.
+    pub fn is_differential(&self) -> bool {
+        self.base_l1_batch_number.is_some()
+    }
 }
 
 /// Snapshot data returned by using JSON-RPC API.
@@ -61,6 +91,69 @@ pub struct SnapshotHeader {
     /// Ordered by chunk IDs.
     pub storage_logs_chunks: Vec<SnapshotStorageLogsChunkMetadata>,
     pub factory_deps_filepath: String,
+    /// For differential snapshots, L1 batch of the snapshot this snapshot is based on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_l1_batch_number: Option<L1BatchNumber>,
+}
+
+/// Chain of snapshots allowing to recover node storage at the end of a certain L1 batch, returned by using JSON-RPC API.
+///
+/// Storage logs in a differential snapshot are split into the same number of chunks as in the full snapshot
+/// at the start of the chain, so that a chunk with a certain ID covers the same range of hashed keys in all snapshots.
+/// Thus, the storage state for a chunk can be recovered by applying the corresponding chunks in the order
+/// of the snapshots in the chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotManifest {
+    /// Full snapshot at the start of the chain.
+    pub base: SnapshotHeader,
+    /// Differential snapshots ordered by ascending L1 batch number. Each snapshot is based on the previous one
+    /// (or on [`Self::base`] for the first snapshot).
+    pub deltas: Vec<SnapshotHeader>,
+}
+
+impl SnapshotManifest {
+    /// Returns the last snapshot in the chain.
+    pub fn target(&self) -> &SnapshotHeader {
+        self.deltas.last().unwrap_or(&self.base)
+    }
+
+    /// Returns all snapshots in the chain, starting from the base one.
+    pub fn snapshots(&self) -> impl Iterator<Item = &SnapshotHeader> + '_ {
+        iter::once(&self.base).chain(&self.deltas)
+    }
+
+    /// Checks that snapshots in this manifest are properly chained.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.base.base_l1_batch_number.is_none(),
+            "base snapshot for L1 batch #{} is differential",
+            self.base.l1_batch_number
+        );
+        let chunk_count = self.base.storage_logs_chunks.len();
+        let mut prev = &self.base;
+        for delta in &self.deltas {
+            anyhow::ensure!(
+                delta.base_l1_batch_number == Some(prev.l1_batch_number),
+                "differential snapshot for L1 batch #{} is not based on the preceding snapshot for L1 batch #{}",
+                delta.l1_batch_number,
+                prev.l1_batch_number
+            );
+            anyhow::ensure!(
+                delta.l1_batch_number > prev.l1_batch_number,
+                "differential snapshot for L1 batch #{} precedes its base snapshot",
+                delta.l1_batch_number
+            );
+            anyhow::ensure!(
+                delta.storage_logs_chunks.len() == chunk_count,
+                "differential snapshot for L1 batch #{} has {} storage log chunks, while the base snapshot has {chunk_count}",
+                delta.l1_batch_number,
+                delta.storage_logs_chunks.len()
+            );
+            prev = delta;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -69,6 +162,60 @@ pub struct SnapshotStorageLogsChunkMetadata {
     pub chunk_id: u64,
     // can be either be a file available under HTTP(s) or local filesystem path
     pub filepath: String,
+    /// Chunk index. Only present for snapshots with version 2.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<SnapshotStorageLogsChunkIndex>,
+}
+
+/// Index of a storage logs chunk allowing to verify the chunk contents without relying on other chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotStorageLogsChunkIndex {
+    /// Start of the hashed key range covered by the chunk (inclusive).
+    pub hashed_keys_start: H256,
+    /// End of the hashed key range covered by the chunk (inclusive).
+    pub hashed_keys_end: H256,
+    /// Checksum of the chunk contents as computed by [`SnapshotStorageLogsChunk::checksum()`].
+    pub checksum: H256,
+}
+
+impl SnapshotStorageLogsChunkIndex {
+    /// Creates an index for a chunk produced by [`uniform_hashed_keys_chunk()`].
+    pub fn new(chunk_id: u64, chunk_count: u64, checksum: H256) -> Self {
+        let hashed_keys_range = uniform_hashed_keys_chunk(chunk_id, chunk_count);
+        Self {
+            hashed_keys_start: *hashed_keys_range.start(),
+            hashed_keys_end: *hashed_keys_range.end(),
+            checksum,
+        }
+    }
+
+    /// Returns the hashed key range covered by the chunk.
+    pub fn hashed_keys_range(&self) -> ops::RangeInclusive<H256> {
+        self.hashed_keys_start..=self.hashed_keys_end
+    }
+
+    /// Verifies that the provided chunk corresponds to this index.
+    pub fn verify(&self, chunk: &SnapshotStorageLogsChunk) -> anyhow::Result<()> {
+        let range = self.hashed_keys_range();
+        if let Some(log) = chunk
+            .storage_logs
+            .iter()
+            .find(|log| !range.contains(&log.key))
+        {
+            anyhow::bail!(
+                "storage log with hashed key {:?} is outside the chunk range {range:?}",
+                log.key
+            );
+        }
+        let checksum = chunk.checksum();
+        anyhow::ensure!(
+            checksum == self.checksum,
+            "chunk checksum {checksum:?} doesn't match the expected checksum {:?}",
+            self.checksum
+        );
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -83,6 +230,23 @@ pub struct SnapshotStorageLogsChunk<K = H256> {
     pub storage_logs: Vec<SnapshotStorageLog<K>>,
 }
 
+impl SnapshotStorageLogsChunk {
+    /// Computes the checksum of this chunk. The checksum depends only on the ordered storage logs in the chunk
+    /// and not on their serialization.
+    pub fn checksum(&self) -> H256 {
+        const LOG_ENCODING_LEN: usize = 32 + 32 + 4 + 8;
+
+        let mut buffer = Vec::with_capacity(self.storage_logs.len() * LOG_ENCODING_LEN);
+        for log in &self.storage_logs {
+            buffer.extend_from_slice(log.key.as_bytes());
+            buffer.extend_from_slice(log.value.as_bytes());
+            buffer.extend_from_slice(&log.l1_batch_number_of_initial_write.0.to_be_bytes());
+            buffer.extend_from_slice(&log.enumeration_index.to_be_bytes());
+        }
+        H256(keccak256(&buffer))
+    }
+}
+
 /// Storage log record in a storage snapshot.
 ///
 /// Version 0 and version 1 snapshots differ in the key type; version 0 uses full [`StorageKey`]s (i.e., storage key preimages),
@@ -387,4 +551,72 @@ mod tests {
             assert!(max_chunk_size - min_chunk_size < U256::from(chunks_count));
         }
     }
+
+    fn mock_header(l1_batch_number: u32, base_l1_batch_number: Option<u32>) -> SnapshotHeader {
+        SnapshotHeader {
+            version: SnapshotVersion::Version1.into(),
+            l1_batch_number: L1BatchNumber(l1_batch_number),
+            l2_block_number: L2BlockNumber(l1_batch_number * 2),
+            storage_logs_chunks: (0..2)
+                .map(|chunk_id| SnapshotStorageLogsChunkMetadata {
+                    chunk_id,
+                    filepath: format!("storage_logs/{l1_batch_number}/{chunk_id}"),
+                    index: None,
+                })
+                .collect(),
+            factory_deps_filepath: format!("factory_deps/{l1_batch_number}"),
+            base_l1_batch_number: base_l1_batch_number.map(L1BatchNumber),
+        }
+    }
+
+    #[test]
+    fn validating_snapshot_manifest() {
+        let mut manifest = SnapshotManifest {
+            base: mock_header(10, None),
+            deltas: vec![mock_header(20, Some(10)), mock_header(30, Some(20))],
+        };
+        manifest.validate().unwrap();
+        assert_eq!(manifest.target().l1_batch_number, L1BatchNumber(30));
+        let l1_batch_numbers: Vec<_> = manifest.snapshots().map(|s| s.l1_batch_number.0).collect();
+        assert_eq!(l1_batch_numbers, [10, 20, 30]);
+
+        manifest.deltas[1].base_l1_batch_number = Some(L1BatchNumber(10));
+        let err = manifest.validate().unwrap_err().to_string();
+        assert!(err.contains("not based on the preceding snapshot"), "{err}");
+
+        manifest.deltas[1] = mock_header(30, Some(20));
+        manifest.deltas[1].storage_logs_chunks.pop();
+        let err = manifest.validate().unwrap_err().to_string();
+        assert!(err.contains("storage log chunks"), "{err}");
+
+        manifest.base.base_l1_batch_number = Some(L1BatchNumber(5));
+        let err = manifest.validate().unwrap_err().to_string();
+        assert!(err.contains("is differential"), "{err}");
+    }
+
+    #[test]
+    fn verifying_chunk_with_index() {
+        let chunk_range = uniform_hashed_keys_chunk(1, 4);
+        let mut chunk = SnapshotStorageLogsChunk {
+            storage_logs: (1..=10)
+                .map(|i| SnapshotStorageLog {
+                    key: u256_to_h256(h256_to_u256(*chunk_range.start()) + i),
+                    value: H256::repeat_byte(i as u8),
+                    l1_batch_number_of_initial_write: L1BatchNumber(1),
+                    enumeration_index: i,
+                })
+                .collect(),
+        };
+        let index = SnapshotStorageLogsChunkIndex::new(1, 4, chunk.checksum());
+        assert_eq!(index.hashed_keys_range(), chunk_range);
+        index.verify(&chunk).unwrap();
+
+        chunk.storage_logs[3].value = H256::zero();
+        let err = index.verify(&chunk).unwrap_err().to_string();
+        assert!(err.contains("checksum"), "{err}");
+
+        let other_index = SnapshotStorageLogsChunkIndex::new(0, 4, chunk.checksum());
+        let err = other_index.verify(&chunk).unwrap_err().to_string();
+        assert!(err.contains("outside the chunk range"), "{err}");
+    }
 }
@@ -0,0 +1,68 @@
+//! Soft confirmations, i.e. sequencer-signed commitments to the position of a transaction in an L2 block.
+
+use anyhow::Context as _;
+use zksync_basic_types::{web3::keccak256, Address, L2BlockNumber, H256};
+
+use crate::{K256PrivateKey, PackedEthSignature};
+
+/// Commitment to the inclusion of a transaction at a specific position of an L2 block.
+///
+/// The commitment is signed by the sequencer when the L2 block is sealed, so that a soft confirmation
+/// that does not match the finalized chain serves as a proof of sequencer misbehavior.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SoftConfirmationCommitment {
+    pub tx_hash: H256,
+    pub l2_block_number: L2BlockNumber,
+    pub index_in_block: u32,
+}
+
+impl SoftConfirmationCommitment {
+    /// Returns the digest signed by the sequencer. The digest is defined as
+    /// `keccak256(tx_hash ++ be_bytes(l2_block_number: u32) ++ be_bytes(index_in_block: u32))`.
+    pub fn digest(&self) -> H256 {
+        let mut bytes = Vec::with_capacity(40);
+        bytes.extend_from_slice(self.tx_hash.as_bytes());
+        bytes.extend_from_slice(&self.l2_block_number.0.to_be_bytes());
+        bytes.extend_from_slice(&self.index_in_block.to_be_bytes());
+        H256(keccak256(&bytes))
+    }
+
+    /// Signs this commitment with the provided key.
+    pub fn sign(&self, private_key: &K256PrivateKey) -> anyhow::Result<PackedEthSignature> {
+        PackedEthSignature::sign_raw(private_key, &self.digest())
+            .context("failed signing soft confirmation")
+    }
+
+    /// Recovers the address of the account that has signed this commitment.
+    pub fn recover_signer(&self, signature: &PackedEthSignature) -> anyhow::Result<Address> {
+        signature
+            .signature_recover_signer(&self.digest())
+            .context("failed recovering soft confirmation signer")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn soft_confirmation_signature_roundtrip() {
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(0x42)).unwrap();
+        let commitment = SoftConfirmationCommitment {
+            tx_hash: H256::repeat_byte(1),
+            l2_block_number: L2BlockNumber(10),
+            index_in_block: 3,
+        };
+        let signature = commitment.sign(&private_key).unwrap();
+        let signer = commitment.recover_signer(&signature).unwrap();
+        assert_eq!(signer, private_key.address());
+
+        let other_commitment = SoftConfirmationCommitment {
+            index_in_block: 4,
+            ..commitment
+        };
+        assert_ne!(other_commitment.digest(), commitment.digest());
+        let other_signer = other_commitment.recover_signer(&signature).unwrap();
+        assert_ne!(other_signer, private_key.address());
+    }
+}
@@ -164,7 +164,7 @@ fn default_passes(prev_value: U256, new_value: U256) -> Vec<Box<dyn CompressionM
 /// The metadata byte is structured as:
 /// First 5 bits: length of the compressed value
 /// Last 3 bits: operation id corresponding to the given compression used.
-fn metadata_byte(output_size: usize, operation_id: usize) -> u8 {
+pub(super) fn metadata_byte(output_size: usize, operation_id: usize) -> u8 {
     ((output_size << 3) | operation_id) as u8
 }
 
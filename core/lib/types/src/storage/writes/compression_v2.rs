@@ -0,0 +1,334 @@
+//! Second version of state diff compression.
+//!
+//! In addition to per-value compression strategies used in the first version (see [`compress_with_best_strategy()`]),
+//! this version uses:
+//!
+//! - A batch-level dictionary of values written multiple times in a batch (e.g., addresses or timestamps).
+//!   Such values are published once and are referenced by their dictionary index afterwards.
+//! - A dedicated zero-length strategy for values incremented by one (e.g., counters and nonces).
+//!
+//! # Format
+//!
+//! ```text
+//! header: version (u8) || size of compressed state diffs (u24) || number of bytes used for enumeration index (u8)
+//! compressed state diffs: dictionary size (u16) || dictionary values (32 bytes each)
+//!     || num_initial writes (u16) || compressed initial writes || compressed repeated writes
+//! ```
+//!
+//! Initial and repeated writes are encoded in the same way as in the first version. Values are encoded as a metadata byte
+//! (5 bits for the payload length and 3 bits for the operation ID) followed by the payload. Besides operations 0..=3
+//! from the first version, the following operations are supported:
+//!
+//! - 4: reference to the dictionary; the payload is a big-endian dictionary index (1 or 2 bytes).
+//! - 5: increment by one; the payload is empty.
+
+use std::collections::HashMap;
+
+use zksync_basic_types::{ProtocolVersionId, U256};
+
+use super::{
+    compress_state_diffs, compress_with_best_strategy, compression::metadata_byte, prepend_header,
+    StateDiffRecord,
+};
+
+/// Version number for the second version of state diff compression.
+pub const COMPRESSION_V2_VERSION_NUMBER: u8 = 2;
+
+pub(super) const DICTIONARY_OPERATION_ID: usize = 4;
+pub(super) const INCREMENT_OPERATION_ID: usize = 5;
+/// Size of a dictionary value in bytes.
+pub(super) const DICTIONARY_VALUE_SIZE: usize = 32;
+/// Maximum size of a dictionary reference (incl. the metadata byte). Used to estimate savings from adding a value
+/// to the dictionary.
+const MAX_DICTIONARY_REFERENCE_SIZE: usize = 3;
+const MAX_DICTIONARY_LEN: usize = u16::MAX as usize;
+
+/// Version of state diff compression used in pubdata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateDiffCompressionVersion {
+    /// Per-value compression; see [`compress_state_diffs()`].
+    V1,
+    /// Per-value compression with a batch-level value dictionary; see [`compress_state_diffs_v2()`].
+    V2,
+}
+
+impl StateDiffCompressionVersion {
+    /// Returns the compression version used by the specified protocol version.
+    pub fn for_protocol_version(protocol_version: ProtocolVersionId) -> Self {
+        if protocol_version.is_pre_state_diff_compression_v2() {
+            Self::V1
+        } else {
+            Self::V2
+        }
+    }
+
+    /// Compresses state diffs using this compression version.
+    pub fn compress(self, state_diffs: Vec<StateDiffRecord>) -> Vec<u8> {
+        match self {
+            Self::V1 => compress_state_diffs(state_diffs),
+            Self::V2 => compress_state_diffs_v2(state_diffs),
+        }
+    }
+}
+
+/// Dictionary of frequently written values in a batch.
+#[derive(Debug, Default)]
+struct ValueDictionary {
+    values: Vec<U256>,
+    indices: HashMap<U256, usize>,
+}
+
+impl ValueDictionary {
+    /// Selects values for which publishing the value once and referencing it is cheaper than compressing each occurrence.
+    /// Values are ordered by decreasing savings, so that the most beneficial values get 1-byte indices.
+    fn new(state_diffs: &[StateDiffRecord]) -> Self {
+        let mut savings = HashMap::<U256, usize>::new();
+        for state_diff in state_diffs {
+            let compressed_len = compress_value(state_diff, None).len();
+            *savings.entry(state_diff.final_value).or_default() +=
+                compressed_len.saturating_sub(MAX_DICTIONARY_REFERENCE_SIZE);
+        }
+
+        let mut values: Vec<_> = savings
+            .into_iter()
+            .filter(|&(_, savings)| savings > DICTIONARY_VALUE_SIZE)
+            .collect();
+        values.sort_unstable_by(|(value, savings), (other_value, other_savings)| {
+            other_savings
+                .cmp(savings)
+                .then_with(|| value.cmp(other_value))
+        });
+        values.truncate(MAX_DICTIONARY_LEN);
+
+        let values: Vec<_> = values.into_iter().map(|(value, _)| value).collect();
+        let indices = values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (*value, i))
+            .collect();
+        Self { values, indices }
+    }
+
+    fn reference(&self, value: &U256) -> Option<Vec<u8>> {
+        let index = *self.indices.get(value)?;
+        let index_bytes = (index as u16).to_be_bytes();
+        let index_bytes = if index <= u8::MAX.into() {
+            &index_bytes[1..]
+        } else {
+            &index_bytes[..]
+        };
+
+        let mut reference = vec![metadata_byte(index_bytes.len(), DICTIONARY_OPERATION_ID)];
+        reference.extend_from_slice(index_bytes);
+        Some(reference)
+    }
+}
+
+/// Compresses the final value of a state diff using the most efficient strategy.
+fn compress_value(state_diff: &StateDiffRecord, dictionary: Option<&ValueDictionary>) -> Vec<u8> {
+    let (prev_value, new_value) = (state_diff.initial_value, state_diff.final_value);
+    if new_value == prev_value.overflowing_add(U256::one()).0 {
+        // No other strategy can be more efficient.
+        return vec![metadata_byte(0, INCREMENT_OPERATION_ID)];
+    }
+
+    let compressed = compress_with_best_strategy(prev_value, new_value);
+    match dictionary.and_then(|dictionary| dictionary.reference(&new_value)) {
+        Some(reference) if reference.len() < compressed.len() => reference,
+        _ => compressed,
+    }
+}
+
+fn compress_state_diff(state_diff: &StateDiffRecord, dictionary: &ValueDictionary) -> Vec<u8> {
+    let mut compressed = match state_diff.enumeration_index {
+        0 => state_diff.derived_key.to_vec(),
+        enumeration_index if enumeration_index <= u32::MAX.into() => {
+            (enumeration_index as u32).to_be_bytes().to_vec()
+        }
+        enumeration_index => panic!("enumeration_index is too large: {}", enumeration_index),
+    };
+    compressed.extend(compress_value(state_diff, Some(dictionary)));
+    compressed
+}
+
+/// Compresses a vector of state diff records according to the following:
+/// dictionary size (u16) || dictionary values || num_initial writes (u16) || compressed initial writes || compressed repeated writes
+pub fn compress_state_diffs_v2(mut state_diffs: Vec<StateDiffRecord>) -> Vec<u8> {
+    // IMPORTANT: Sorting here is determined by the order expected in the circuits.
+    state_diffs.sort_by_key(|rec| (rec.address, rec.key));
+    let dictionary = ValueDictionary::new(&state_diffs);
+
+    let mut res = vec![];
+    res.extend((dictionary.values.len() as u16).to_be_bytes());
+    for value in &dictionary.values {
+        let mut buffer = [0_u8; DICTIONARY_VALUE_SIZE];
+        value.to_big_endian(&mut buffer);
+        res.extend(buffer);
+    }
+
+    let (initial_writes, repeated_writes): (Vec<_>, Vec<_>) = state_diffs
+        .iter()
+        .partition(|rec| rec.enumeration_index == 0);
+
+    res.extend((initial_writes.len() as u16).to_be_bytes());
+    for state_diff in initial_writes {
+        res.extend(compress_state_diff(state_diff, &dictionary));
+    }
+    for state_diff in repeated_writes {
+        res.extend(compress_state_diff(state_diff, &dictionary));
+    }
+
+    prepend_header(COMPRESSION_V2_VERSION_NUMBER, res)
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_basic_types::Address;
+
+    use super::*;
+    use crate::writes::{BYTES_PER_DERIVED_KEY, BYTES_PER_ENUMERATION_INDEX};
+
+    fn read_bytes<'a>(data: &mut &'a [u8], len: usize) -> &'a [u8] {
+        let (head, tail) = data.split_at(len);
+        *data = tail;
+        head
+    }
+
+    /// Decompresses state diffs, returning `(derived key or enumeration index, final value)` tuples.
+    fn decompress(
+        mut data: &[u8],
+        initial_values: &HashMap<Vec<u8>, U256>,
+    ) -> Vec<(Vec<u8>, U256)> {
+        let header = read_bytes(&mut data, 5);
+        assert_eq!(header[0], COMPRESSION_V2_VERSION_NUMBER);
+        let compressed_len = u32::from_be_bytes([0, header[1], header[2], header[3]]);
+        assert_eq!(compressed_len as usize, data.len());
+        assert_eq!(header[4], BYTES_PER_ENUMERATION_INDEX);
+
+        let dictionary_len = u16::from_be_bytes(read_bytes(&mut data, 2).try_into().unwrap());
+        let dictionary: Vec<_> = (0..dictionary_len)
+            .map(|_| U256::from_big_endian(read_bytes(&mut data, DICTIONARY_VALUE_SIZE)))
+            .collect();
+        let initial_writes_count = u16::from_be_bytes(read_bytes(&mut data, 2).try_into().unwrap());
+
+        let mut diffs = vec![];
+        let mut i = 0;
+        while !data.is_empty() {
+            let key_len = if i < initial_writes_count {
+                BYTES_PER_DERIVED_KEY
+            } else {
+                BYTES_PER_ENUMERATION_INDEX
+            };
+            let key = read_bytes(&mut data, key_len.into()).to_vec();
+            let initial_value = initial_values[&key];
+
+            let metadata = read_bytes(&mut data, 1)[0];
+            let (len, operation_id) = (usize::from(metadata >> 3), usize::from(metadata & 7));
+            let len = if operation_id == 0 { 32 } else { len };
+            let payload = U256::from_big_endian(read_bytes(&mut data, len));
+            let final_value = match operation_id {
+                0 | 3 => payload,
+                1 => initial_value.overflowing_add(payload).0,
+                2 => initial_value.overflowing_sub(payload).0,
+                DICTIONARY_OPERATION_ID => dictionary[payload.as_usize()],
+                INCREMENT_OPERATION_ID => initial_value + 1,
+                _ => panic!("invalid operation ID: {operation_id}"),
+            };
+            diffs.push((key, final_value));
+            i += 1;
+        }
+        diffs
+    }
+
+    fn state_diffs() -> Vec<StateDiffRecord> {
+        let owner = U256::from_big_endian(Address::repeat_byte(0xaa).as_bytes());
+        let timestamp = U256::from(1_700_000_000_u64);
+        (0..300_u64)
+            .map(|i| {
+                let (initial_value, final_value) = match i % 5 {
+                    // Counter
+                    0 => (U256::from(i), U256::from(i + 1)),
+                    // Balance
+                    1 => (U256::from(i) << 64, (U256::from(i) << 64) - 12_345),
+                    2 => (U256::zero(), owner),
+                    3 => (U256::from(i), timestamp),
+                    _ => (U256::zero(), U256::MAX - i),
+                };
+                let mut derived_key = [0_u8; 32];
+                derived_key[..8].copy_from_slice(&i.to_be_bytes());
+                StateDiffRecord {
+                    address: Address::repeat_byte(1),
+                    key: i.into(),
+                    derived_key,
+                    enumeration_index: if i % 3 == 0 { 0 } else { i + 1 },
+                    initial_value,
+                    final_value,
+                }
+            })
+            .collect()
+    }
+
+    fn diff_key(state_diff: &StateDiffRecord) -> Vec<u8> {
+        if state_diff.enumeration_index == 0 {
+            state_diff.derived_key.to_vec()
+        } else {
+            (state_diff.enumeration_index as u32).to_be_bytes().to_vec()
+        }
+    }
+
+    #[test]
+    fn compression_roundtrip() {
+        let state_diffs = state_diffs();
+        let compressed = compress_state_diffs_v2(state_diffs.clone());
+
+        let initial_values = state_diffs
+            .iter()
+            .map(|diff| (diff_key(diff), diff.initial_value))
+            .collect();
+        let decompressed: HashMap<_, _> = decompress(&compressed, &initial_values)
+            .into_iter()
+            .collect();
+        assert_eq!(decompressed.len(), state_diffs.len());
+        for diff in &state_diffs {
+            assert_eq!(decompressed[&diff_key(diff)], diff.final_value, "{diff:?}");
+        }
+    }
+
+    #[test]
+    fn compression_v2_is_more_efficient() {
+        let state_diffs = state_diffs();
+        let compressed_v1 = compress_state_diffs(state_diffs.clone());
+        let compressed_v2 = compress_state_diffs_v2(state_diffs);
+        assert!(
+            compressed_v2.len() < compressed_v1.len(),
+            "v1: {}, v2: {}",
+            compressed_v1.len(),
+            compressed_v2.len()
+        );
+    }
+
+    #[test]
+    fn dictionary_is_not_used_for_unique_values() {
+        let state_diffs: Vec<_> = state_diffs()
+            .into_iter()
+            .filter(|diff| diff.enumeration_index != 0)
+            .map(|diff| StateDiffRecord {
+                final_value: U256::MAX - diff.key,
+                ..diff
+            })
+            .collect();
+        let compressed_v1 = compress_state_diffs(state_diffs.clone());
+        let compressed_v2 = compress_state_diffs_v2(state_diffs);
+        // The only difference should be the dictionary size and the version.
+        assert_eq!(compressed_v2.len(), compressed_v1.len() + 2);
+        assert_eq!(compressed_v2[7..], compressed_v1[5..]);
+    }
+
+    #[test]
+    fn compression_version_for_protocol_version() {
+        assert_eq!(
+            StateDiffCompressionVersion::for_protocol_version(ProtocolVersionId::latest()),
+            StateDiffCompressionVersion::V1
+        );
+    }
+}
@@ -0,0 +1,216 @@
+//! Decompression of state diffs published as a part of pubdata. This is the inverse of
+//! [`compress_state_diffs()`](super::compress_state_diffs()) and [`compress_state_diffs_v2()`](super::compress_state_diffs_v2()).
+
+use anyhow::Context as _;
+use zksync_basic_types::U256;
+
+use super::{
+    compression_v2::{DICTIONARY_OPERATION_ID, DICTIONARY_VALUE_SIZE, INCREMENT_OPERATION_ID},
+    BYTES_PER_DERIVED_KEY, BYTES_PER_ENUMERATION_INDEX, COMPRESSION_V2_VERSION_NUMBER,
+    COMPRESSION_VERSION_NUMBER,
+};
+
+/// Key of a state diff as published in pubdata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StateDiffKey {
+    /// Initial write identified by the derived key of the slot (i.e., `Blake2s(bytes32(address), key)`).
+    Initial([u8; 32]),
+    /// Repeated write identified by the enumeration index of the slot.
+    Repeated(u64),
+}
+
+/// State diff decompressed from pubdata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecompressedStateDiff {
+    pub key: StateDiffKey,
+    pub final_value: U256,
+}
+
+fn read_bytes<'a>(data: &mut &'a [u8], len: usize) -> anyhow::Result<&'a [u8]> {
+    anyhow::ensure!(
+        data.len() >= len,
+        "unexpected end of data: expected {len} bytes, got {}",
+        data.len()
+    );
+    let (head, tail) = data.split_at(len);
+    *data = tail;
+    Ok(head)
+}
+
+fn read_u16(data: &mut &[u8]) -> anyhow::Result<u16> {
+    Ok(u16::from_be_bytes(read_bytes(data, 2)?.try_into().unwrap()))
+}
+
+/// Decompresses state diffs (including the header) from the start of `data`, advancing it past the decompressed data.
+/// Initial writes are returned first, followed by repeated writes; within each group, diffs are in the publication order.
+///
+/// Since values may be compressed relative to the previous slot value, the caller must provide current values of slots
+/// by their enumeration index (`value_by_index`). Initial writes are always relative to the zero value.
+///
+/// # Errors
+///
+/// Returns an error if the data is malformed, or propagates errors returned by `value_by_index`.
+pub fn decompress_state_diffs(
+    data: &mut &[u8],
+    mut value_by_index: impl FnMut(u64) -> anyhow::Result<U256>,
+) -> anyhow::Result<Vec<DecompressedStateDiff>> {
+    let header = read_bytes(data, 5).context("failed reading state diffs header")?;
+    let version = header[0];
+    let compressed_len = u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+    anyhow::ensure!(
+        header[4] == BYTES_PER_ENUMERATION_INDEX,
+        "unsupported number of bytes per enumeration index: {}",
+        header[4]
+    );
+    let mut compressed = read_bytes(data, compressed_len).context("state diffs are truncated")?;
+
+    let dictionary = match version {
+        COMPRESSION_VERSION_NUMBER => vec![],
+        COMPRESSION_V2_VERSION_NUMBER => {
+            let len = read_u16(&mut compressed).context("failed reading dictionary size")?;
+            (0..len)
+                .map(|_| {
+                    let value = read_bytes(&mut compressed, DICTIONARY_VALUE_SIZE)?;
+                    Ok(U256::from_big_endian(value))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+                .context("failed reading dictionary")?
+        }
+        _ => anyhow::bail!("unsupported state diff compression version: {version}"),
+    };
+    let initial_writes_count =
+        usize::from(read_u16(&mut compressed).context("failed reading number of initial writes")?);
+
+    let mut diffs = vec![];
+    while !compressed.is_empty() {
+        let (key, initial_value) = if diffs.len() < initial_writes_count {
+            let derived_key = read_bytes(&mut compressed, BYTES_PER_DERIVED_KEY.into())?;
+            let key = StateDiffKey::Initial(derived_key.try_into().unwrap());
+            (key, U256::zero())
+        } else {
+            let index = read_bytes(&mut compressed, BYTES_PER_ENUMERATION_INDEX.into())?;
+            let index = u32::from_be_bytes(index.try_into().unwrap()).into();
+            let value = value_by_index(index)
+                .with_context(|| format!("failed getting value for enumeration index {index}"))?;
+            (StateDiffKey::Repeated(index), value)
+        };
+
+        let metadata = read_bytes(&mut compressed, 1)?[0];
+        let (len, operation_id) = (usize::from(metadata >> 3), usize::from(metadata & 7));
+        let len = if operation_id == 0 { 32 } else { len };
+        let payload = U256::from_big_endian(read_bytes(&mut compressed, len)?);
+        let final_value = match operation_id {
+            0 | 3 => payload,
+            1 => initial_value.overflowing_add(payload).0,
+            2 => initial_value.overflowing_sub(payload).0,
+            DICTIONARY_OPERATION_ID if version == COMPRESSION_V2_VERSION_NUMBER => {
+                let index = payload.low_u64() as usize;
+                *dictionary
+                    .get(index)
+                    .with_context(|| format!("dictionary index {index} is out of bounds"))?
+            }
+            INCREMENT_OPERATION_ID if version == COMPRESSION_V2_VERSION_NUMBER => {
+                initial_value.overflowing_add(U256::one()).0
+            }
+            _ => anyhow::bail!(
+                "invalid operation ID {operation_id} for compression version {version}"
+            ),
+        };
+        diffs.push(DecompressedStateDiff { key, final_value });
+    }
+
+    anyhow::ensure!(
+        diffs.len() >= initial_writes_count,
+        "expected at least {initial_writes_count} initial writes, got {}",
+        diffs.len()
+    );
+    Ok(diffs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use zksync_basic_types::Address;
+
+    use super::*;
+    use crate::writes::{StateDiffCompressionVersion, StateDiffRecord};
+
+    fn state_diffs() -> Vec<StateDiffRecord> {
+        let timestamp = U256::from(1_700_000_000_u64);
+        (0..100_u64)
+            .map(|i| {
+                let (initial_value, final_value) = match i % 4 {
+                    0 => (U256::from(i), U256::from(i + 1)),
+                    1 => (U256::from(i) << 64, (U256::from(i) << 64) - 12_345),
+                    2 => (U256::from(i), timestamp),
+                    _ => (U256::from(i), U256::MAX - i),
+                };
+                let is_initial = i % 3 == 0;
+                let mut derived_key = [0_u8; 32];
+                derived_key[..8].copy_from_slice(&i.to_be_bytes());
+                StateDiffRecord {
+                    address: Address::repeat_byte(1),
+                    key: i.into(),
+                    derived_key,
+                    enumeration_index: if is_initial { 0 } else { i + 1 },
+                    initial_value: if is_initial {
+                        U256::zero()
+                    } else {
+                        initial_value
+                    },
+                    final_value,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn decompression_roundtrip() {
+        let state_diffs = state_diffs();
+        let values_by_index: HashMap<_, _> = state_diffs
+            .iter()
+            .filter(|diff| !diff.is_write_initial())
+            .map(|diff| (diff.enumeration_index, diff.initial_value))
+            .collect();
+
+        for version in [
+            StateDiffCompressionVersion::V1,
+            StateDiffCompressionVersion::V2,
+        ] {
+            let mut compressed = version.compress(state_diffs.clone());
+            compressed.extend([0; 10]); // simulate padding
+            let mut data = compressed.as_slice();
+            let decompressed = decompress_state_diffs(&mut data, |index| {
+                values_by_index
+                    .get(&index)
+                    .copied()
+                    .context("unknown index")
+            })
+            .unwrap();
+            assert_eq!(data, [0; 10]);
+
+            assert_eq!(decompressed.len(), state_diffs.len());
+            let decompressed: HashMap<_, _> = decompressed
+                .into_iter()
+                .map(|diff| (diff.key, diff.final_value))
+                .collect();
+            for diff in &state_diffs {
+                let key = if diff.is_write_initial() {
+                    StateDiffKey::Initial(diff.derived_key)
+                } else {
+                    StateDiffKey::Repeated(diff.enumeration_index)
+                };
+                assert_eq!(decompressed[&key], diff.final_value, "{diff:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn decompressing_truncated_data() {
+        let compressed = StateDiffCompressionVersion::V2.compress(state_diffs());
+        let mut data = &compressed[..compressed.len() - 1];
+        let err = decompress_state_diffs(&mut data, |_| Ok(U256::zero())).unwrap_err();
+        assert!(err.to_string().contains("truncated"), "{err:#}");
+    }
+}
@@ -4,9 +4,17 @@ use serde::{de, ser::SerializeTuple, Deserialize, Deserializer, Serialize, Seria
 use zksync_basic_types::{Address, U256};
 
 pub(crate) use self::compression::{compress_with_best_strategy, COMPRESSION_VERSION_NUMBER};
+pub use self::{
+    compression_v2::{
+        compress_state_diffs_v2, StateDiffCompressionVersion, COMPRESSION_V2_VERSION_NUMBER,
+    },
+    decompression::{decompress_state_diffs, DecompressedStateDiff, StateDiffKey},
+};
 use crate::H256;
 
 pub mod compression;
+mod compression_v2;
+mod decompression;
 
 /// The number of bytes being used for state diff enumeration indices. Applicable to repeated writes.
 pub const BYTES_PER_ENUMERATION_INDEX: u8 = 4;
@@ -171,15 +179,15 @@ pub fn compress_state_diffs(mut state_diffs: Vec<StateDiffRecord>) -> Vec<u8> {
         res.extend(state_diff.compress());
     }
 
-    prepend_header(res)
+    prepend_header(COMPRESSION_VERSION_NUMBER, res)
 }
 
 /// Adds the header to the beginning of the compressed state diffs so it can be used as part of the overall
 /// pubdata. Need to prepend: compression version || number of compressed state diffs || number of bytes used for
 /// enumeration index.
-fn prepend_header(compressed_state_diffs: Vec<u8>) -> Vec<u8> {
+fn prepend_header(compression_version: u8, compressed_state_diffs: Vec<u8>) -> Vec<u8> {
     let mut res = vec![0u8; 5];
-    res[0] = COMPRESSION_VERSION_NUMBER;
+    res[0] = compression_version;
 
     res[1..4].copy_from_slice(&(compressed_state_diffs.len() as u32).to_be_bytes()[1..4]);
 
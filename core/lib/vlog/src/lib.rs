@@ -135,7 +135,7 @@ impl ObservabilityBuilder {
 
         // For now we use logs filter as a global filter for subscriber.
         // Later we may want to enforce each layer to have its own filter.
-        let global_filter = logs.build_filter();
+        let (global_filter, reloadable_filter) = logs.build_reloadable_filter();
 
         let logs_layer = logs.into_layer();
         let (otlp_tracing_provider, otlp_tracing_layer) = self
@@ -155,6 +155,7 @@ impl ObservabilityBuilder {
             .with(otlp_logging_layer)
             .try_init()
             .context("failed installing global tracer / logger")?;
+        reloadable_filter.install();
 
         let sentry_guard = self.sentry.map(|sentry| sentry.install());
 
@@ -1,7 +1,8 @@
-use std::{backtrace::Backtrace, str::FromStr};
+use std::{backtrace::Backtrace, str::FromStr, sync::OnceLock};
 
+use anyhow::Context as _;
 use serde::Deserialize;
-use tracing_subscriber::{fmt, registry::LookupSpan, EnvFilter, Layer};
+use tracing_subscriber::{fmt, registry::LookupSpan, reload, EnvFilter, Layer, Registry};
 
 mod layer;
 
@@ -72,17 +73,22 @@ impl Logs {
     ///
     /// [1]: https://docs.rs/tracing-subscriber/0.3.18/tracing_subscriber/filter/targets/struct.Targets.html#filtering-with-targets
     pub(super) fn build_filter(&self) -> EnvFilter {
-        let mut directives = if self.disable_default_logs {
-            "".to_string()
-        } else {
-            "zksync=info,".to_string()
-        };
-        if let Some(log_directives) = &self.log_directives {
-            directives.push_str(log_directives);
-        } else if let Ok(env_directives) = std::env::var(EnvFilter::DEFAULT_ENV) {
-            directives.push_str(&env_directives);
+        let env_directives = std::env::var(EnvFilter::DEFAULT_ENV).ok();
+        let log_directives = self.log_directives.as_deref().or(env_directives.as_deref());
+        EnvFilter::new(filter_directives(self.disable_default_logs, log_directives))
+    }
+
+    /// Wraps the filter built by [`Self::build_filter()`] so that it can be changed at runtime
+    /// using [`reload_log_directives()`].
+    pub(super) fn build_reloadable_filter(
+        &self,
+    ) -> (reload::Layer<EnvFilter, Registry>, ReloadableFilter) {
+        let (filter, handle) = reload::Layer::new(self.build_filter());
+        let reloadable = ReloadableFilter {
+            handle,
+            disable_default_logs: self.disable_default_logs,
         };
-        EnvFilter::new(directives)
+        (filter, reloadable)
     }
 
     pub fn with_log_directives(mut self, log_directives: Option<String>) -> Self {
@@ -129,6 +135,62 @@ impl Logs {
     }
 }
 
+fn filter_directives(disable_default_logs: bool, log_directives: Option<&str>) -> String {
+    let mut directives = if disable_default_logs {
+        "".to_string()
+    } else {
+        "zksync=info,".to_string()
+    };
+    if let Some(log_directives) = log_directives {
+        directives.push_str(log_directives);
+    }
+    directives
+}
+
+/// Handle to the global log filter installed by [`ObservabilityBuilder`](crate::ObservabilityBuilder).
+#[derive(Debug)]
+pub(super) struct ReloadableFilter {
+    handle: reload::Handle<EnvFilter, Registry>,
+    disable_default_logs: bool,
+}
+
+static RELOADABLE_FILTER: OnceLock<ReloadableFilter> = OnceLock::new();
+
+impl ReloadableFilter {
+    pub(super) fn install(self) {
+        // The observability stack can only be installed once per process, so this can only fail in tests.
+        RELOADABLE_FILTER.set(self).ok();
+    }
+}
+
+/// Checks that the provided log directives (in the `RUST_LOG` format) are valid.
+pub fn validate_log_directives(log_directives: &str) -> anyhow::Result<()> {
+    EnvFilter::try_new(log_directives)
+        .with_context(|| format!("invalid log directives: {log_directives:?}"))?;
+    Ok(())
+}
+
+/// Replaces log directives of the installed observability stack. The default `zksync=info` directive is retained
+/// unless it was disabled via [`Logs::disable_default_logs()`]. If `log_directives` is `None`, only the default
+/// directive will be used.
+///
+/// # Errors
+///
+/// Returns an error if the directives are invalid, or if the observability stack is not installed.
+pub fn reload_log_directives(log_directives: Option<&str>) -> anyhow::Result<()> {
+    if let Some(log_directives) = log_directives {
+        validate_log_directives(log_directives)?;
+    }
+    let filter = RELOADABLE_FILTER
+        .get()
+        .context("observability stack is not installed")?;
+    let directives = filter_directives(filter.disable_default_logs, log_directives);
+    filter
+        .handle
+        .reload(EnvFilter::new(directives))
+        .context("failed reloading log filter")
+}
+
 #[allow(deprecated)] // Not available yet on stable, so we can't switch right now.
 fn json_panic_handler(panic_info: &std::panic::PanicInfo) {
     let backtrace = Backtrace::force_capture();
@@ -14,7 +14,7 @@ use zksync_multivm::{
     },
     is_supported_by_fast_vm,
     pubdata_builders::pubdata_params_to_builder,
-    tracers::CallTracer,
+    tracers::{CallTracer, FrameGasCap},
     vm_fast,
     vm_fast::FastValidationTracer,
     vm_latest::HistoryEnabled,
@@ -91,6 +91,8 @@ pub struct MainBatchExecutorFactory<Tr> {
     observe_storage_metrics: bool,
     skip_signature_verification: bool,
     divergence_handler: Option<DivergenceHandler>,
+    frame_gas_cap: Option<FrameGasCap>,
+    record_precompile_inputs: bool,
     _tracer: PhantomData<Tr>,
 }
 
@@ -102,6 +104,8 @@ impl<Tr: BatchTracer> MainBatchExecutorFactory<Tr> {
             observe_storage_metrics: false,
             skip_signature_verification: false,
             divergence_handler: None,
+            frame_gas_cap: None,
+            record_precompile_inputs: false,
             _tracer: PhantomData,
         }
     }
@@ -123,6 +127,12 @@ impl<Tr: BatchTracer> MainBatchExecutorFactory<Tr> {
         self.observe_storage_metrics = true;
     }
 
+    /// Enables recording inputs of hashing precompile calls, which are returned in [`FinishedL1Batch`].
+    /// Inputs are only necessary for witness generation; they are only recorded by the legacy VM.
+    pub fn record_precompile_inputs(&mut self) {
+        self.record_precompile_inputs = true;
+    }
+
     pub fn set_divergence_handler(&mut self, handler: DivergenceHandler) {
         tracing::info!("Set VM divergence handler");
         self.divergence_handler = Some(handler);
@@ -136,6 +146,24 @@ impl<Tr: BatchTracer> MainBatchExecutorFactory<Tr> {
     pub fn skip_signature_verification(&mut self) {
         self.skip_signature_verification = true;
     }
+
+    /// Sets the cap on gas forwarded to a single call frame. Transactions violating the cap will be halted.
+    ///
+    /// The cap is only enforced by the legacy VM, so it must not be combined with the fast VM; callers are responsible
+    /// for validating this.
+    pub fn set_frame_gas_cap(&mut self, frame_gas_cap: FrameGasCap) {
+        if !frame_gas_cap.is_enabled() {
+            return;
+        }
+        tracing::info!("Set frame gas cap: {frame_gas_cap:?}");
+        if !matches!(self.fast_vm_mode, FastVmMode::Old) {
+            tracing::warn!(
+                "Frame gas cap is not enforced by the fast VM; with mode {:?}, it may be ignored or lead to VM divergence",
+                self.fast_vm_mode
+            );
+        }
+        self.frame_gas_cap = Some(frame_gas_cap);
+    }
 }
 
 impl<S: ReadStorage + Send + 'static, Tr: BatchTracer> BatchExecutorFactory<S>
@@ -157,6 +185,8 @@ impl<S: ReadStorage + Send + 'static, Tr: BatchTracer> BatchExecutorFactory<S>
             observe_storage_metrics: self.observe_storage_metrics,
             skip_signature_verification: self.skip_signature_verification,
             divergence_handler: self.divergence_handler.clone(),
+            frame_gas_cap: self.frame_gas_cap.clone(),
+            record_precompile_inputs: self.record_precompile_inputs,
             commands: commands_receiver,
             _storage: PhantomData,
             _tracer: PhantomData::<Tr>,
@@ -239,13 +269,17 @@ impl<S: ReadStorage, Tr: BatchTracer> BatchVm<S, Tr> {
         &mut self,
         tx: Transaction,
         with_compression: bool,
+        frame_gas_cap: Option<&FrameGasCap>,
     ) -> BatchTransactionExecutionResult {
         let legacy_tracer_result = Arc::new(OnceCell::default());
-        let legacy_tracer = if Tr::TRACE_CALLS {
+        let mut legacy_tracer = if Tr::TRACE_CALLS {
             vec![CallTracer::new(legacy_tracer_result.clone()).into_tracer_pointer()]
         } else {
             vec![]
         };
+        if let Some(frame_gas_cap) = frame_gas_cap {
+            legacy_tracer.push(frame_gas_cap.clone().into_tracer_pointer());
+        }
         let mut legacy_tracer = legacy_tracer.into();
         let mut fast_traces = vec![];
 
@@ -309,6 +343,8 @@ struct CommandReceiver<S, Tr> {
     observe_storage_metrics: bool,
     skip_signature_verification: bool,
     divergence_handler: Option<DivergenceHandler>,
+    frame_gas_cap: Option<FrameGasCap>,
+    record_precompile_inputs: bool,
     commands: mpsc::Receiver<Command>,
     _storage: PhantomData<S>,
     _tracer: PhantomData<Tr>,
@@ -337,6 +373,11 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
                 vm.skip_signature_verification();
             }
         }
+        if self.record_precompile_inputs {
+            if let BatchVm::Legacy(vm) = &mut vm {
+                vm.record_precompile_inputs();
+            }
+        }
         let mut batch_finished = false;
         let mut prev_storage_stats = StorageViewStats::default();
 
@@ -466,7 +507,7 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
         // it means that there is no sense in polluting the space of compressed bytecodes,
         // and so we re-execute the transaction, but without compression.
 
-        let res = vm.inspect_transaction(tx.clone(), true);
+        let res = vm.inspect_transaction(tx.clone(), true, self.frame_gas_cap.as_ref());
         if res.compression_result.is_ok() {
             return Ok(BatchTransactionExecutionResult {
                 tx_result: res.tx_result,
@@ -480,7 +521,7 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
         vm.rollback_to_the_latest_snapshot();
         vm.make_snapshot();
 
-        let res = vm.inspect_transaction(tx.clone(), false);
+        let res = vm.inspect_transaction(tx.clone(), false, self.frame_gas_cap.as_ref());
         res.compression_result
             .context("compression failed when it wasn't applied")?;
         Ok(BatchTransactionExecutionResult {
@@ -497,7 +538,7 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
         tx: &Transaction,
         vm: &mut BatchVm<S, Tr>,
     ) -> anyhow::Result<BatchTransactionExecutionResult> {
-        let res = vm.inspect_transaction(tx.clone(), true);
+        let res = vm.inspect_transaction(tx.clone(), true, self.frame_gas_cap.as_ref());
         if res.compression_result.is_ok() {
             Ok(BatchTransactionExecutionResult {
                 tx_result: res.tx_result,
@@ -100,4 +100,22 @@ impl OneshotEnvParameters<CallOrExecute> {
         )
         .await
     }
+
+    /// Prepares environment for executing an L1 -> L2 transaction. Unlike [`Self::to_execute_env()`], the base fee
+    /// is derived from `fee_input` in the same way as in the state keeper.
+    pub async fn to_l1_tx_execute_env(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        resolved_block_info: &ResolvedBlockInfo,
+        fee_input: BatchFeeInput,
+    ) -> anyhow::Result<OneshotEnv> {
+        self.to_env_inner(
+            connection,
+            TxExecutionMode::VerifyExecute,
+            resolved_block_info,
+            fee_input,
+            None,
+        )
+        .await
+    }
 }
@@ -1,7 +1,12 @@
 use std::time::Duration;
 
-use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics};
-use zksync_multivm::interface::{storage::StorageViewStats, VmMemoryMetrics};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
+};
+use zksync_multivm::{
+    interface::{storage::StorageViewStats, VmMemoryMetrics},
+    tracers::{LimitedResource, ResourceUsage},
+};
 
 use crate::shared::STORAGE_METRICS;
 
@@ -80,3 +85,74 @@ pub(super) fn report_vm_storage_metrics(
 ) {
     STORAGE_METRICS.observe(&format!("Tx {tx_id}"), vm_execution_took, storage_stats);
 }
+
+/// Outcome of taking a state from the VM warm pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "outcome", rename_all = "snake_case")]
+pub(super) enum WarmPoolOutcome {
+    /// A pre-initialized state was taken from the pool.
+    Hit,
+    /// The pool had no states for the requested base system contracts.
+    Miss,
+    /// The execution uses an older protocol version than the one pooled states are prepared for.
+    OldProtocolVersion,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "api_vm_warm_pool")]
+pub(super) struct VmWarmPoolMetrics {
+    /// Number of attempts to take a state from the pool, grouped by outcome.
+    pub take: Family<WarmPoolOutcome, Counter>,
+    /// Number of times pooled states were invalidated because of a protocol version or base system contracts change.
+    pub invalidations: Counter,
+    /// Number of states in the pool after the latest refill step.
+    pub size: Gauge<usize>,
+}
+
+#[vise::register]
+pub(super) static WARM_POOL_METRICS: vise::Global<VmWarmPoolMetrics> = vise::Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "resource", rename_all = "snake_case")]
+enum ResourceLabel {
+    Memory,
+    CpuTime,
+}
+
+impl From<LimitedResource> for ResourceLabel {
+    fn from(resource: LimitedResource) -> Self {
+        match resource {
+            LimitedResource::Memory => Self::Memory,
+            LimitedResource::CpuTime => Self::CpuTime,
+        }
+    }
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "api_vm_resource_usage")]
+struct VmResourceUsageMetrics {
+    /// Peak estimated memory consumed by VM oracles during an execution.
+    #[metrics(buckets = MEMORY_SIZE_BUCKETS)]
+    peak_memory: Histogram<usize>,
+    /// Approximate CPU time spent on an execution.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    cpu_time: Histogram<Duration>,
+    /// Number of executions halted because of exceeding a resource limit.
+    halted: Family<ResourceLabel, Counter>,
+}
+
+#[vise::register]
+static RESOURCE_USAGE_METRICS: vise::Global<VmResourceUsageMetrics> = vise::Global::new();
+
+pub(super) fn report_resource_usage(usage: &ResourceUsage) {
+    RESOURCE_USAGE_METRICS
+        .peak_memory
+        .observe(usage.peak_memory);
+    RESOURCE_USAGE_METRICS.cpu_time.observe(usage.cpu_time);
+    if let Some(resource) = usage.exceeded_limit {
+        tracing::info!(
+            "VM execution was halted because of exceeding {resource:?} limit; usage: {usage:?}"
+        );
+        RESOURCE_USAGE_METRICS.halted[&resource.into()].inc();
+    }
+}
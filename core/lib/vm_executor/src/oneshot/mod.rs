@@ -14,6 +14,7 @@ use std::{
 use anyhow::Context;
 use async_trait::async_trait;
 use once_cell::sync::OnceCell;
+pub use zksync_multivm::tracers::{FrameGasCap, ResourceLimits};
 use zksync_multivm::{
     interface::{
         executor::{OneshotExecutor, TransactionValidator},
@@ -25,10 +26,13 @@ use zksync_multivm::{
         VmFactory, VmInterface,
     },
     is_supported_by_fast_vm,
-    tracers::{CallTracer, StorageInvocations, TracerDispatcher, ValidationTracer},
+    tracers::{
+        CallTracer, FrameGasCap, ResourceUsage, ResourceUsageTracer, StorageInvocations,
+        TracerDispatcher, ValidationTracer,
+    },
     utils::adjust_pubdata_price_for_tx,
     vm_fast::{self, FastValidationTracer, StorageInvocationsTracer},
-    vm_latest::{HistoryDisabled, HistoryEnabled},
+    vm_latest::{HistoryDisabled, HistoryEnabled, WarmVmState},
     zk_evm_latest::ethereum_types::U256,
     FastVmInstance, HistoryMode, LegacyVmInstance, MultiVmTracer, VmVersion,
 };
@@ -43,6 +47,7 @@ use zksync_types::{
     SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION, SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION,
 };
 
+use self::warm_pool::VmWarmPool;
 pub use self::{
     block::{BlockInfo, ResolvedBlockInfo},
     contracts::{
@@ -60,6 +65,7 @@ mod metrics;
 mod mock;
 #[cfg(test)]
 mod tests;
+mod warm_pool;
 
 /// Main [`OneshotExecutor`] implementation used by the API server.
 #[derive(Debug)]
@@ -68,6 +74,9 @@ pub struct MainOneshotExecutor {
     vm_divergence_handler: DivergenceHandler,
     missed_storage_invocation_limit: usize,
     execution_latency_histogram: Option<&'static vise::Histogram<Duration>>,
+    warm_pool: Option<VmWarmPool>,
+    resource_limits: Option<ResourceLimits>,
+    frame_gas_cap: Option<FrameGasCap>,
 }
 
 impl MainOneshotExecutor {
@@ -81,6 +90,9 @@ impl MainOneshotExecutor {
             }),
             missed_storage_invocation_limit,
             execution_latency_histogram: None,
+            warm_pool: None,
+            resource_limits: None,
+            frame_gas_cap: None,
         }
     }
 
@@ -108,6 +120,43 @@ impl MainOneshotExecutor {
         self.execution_latency_histogram = Some(histogram);
     }
 
+    /// Sets the number of pre-initialized VM states kept for each set of base system contracts. States are prepared
+    /// in the background and allow to exclude VM setup from execution latency. Only used by the legacy VM
+    /// for the latest protocol version; `0` disables the pool.
+    pub fn set_warm_pool_size(&mut self, size: usize) {
+        self.warm_pool = (size > 0).then(|| VmWarmPool::new(size));
+    }
+
+    /// Enables resource accounting for calls and gas estimations. Consumed memory and CPU time are reported
+    /// as metrics, and the execution is halted if any of the specified `limits` is exceeded. Only applied
+    /// to the legacy VM; transaction validation is not affected.
+    pub fn set_resource_limits(&mut self, limits: ResourceLimits) {
+        self.resource_limits = Some(limits);
+    }
+
+    /// Sets the cap on gas forwarded to a single call frame, so that transactions violating the cap are halted
+    /// in the same way as by the state keeper. Applied to all executions, including transaction validation.
+    ///
+    /// The cap is only enforced by the legacy VM; it must not be combined with the fast VM.
+    pub fn set_frame_gas_cap(&mut self, frame_gas_cap: FrameGasCap) {
+        if frame_gas_cap.is_enabled() {
+            self.frame_gas_cap = Some(frame_gas_cap);
+        }
+    }
+
+    fn take_warm_state(
+        &self,
+        env: &OneshotEnv,
+        fast_vm_mode: FastVmMode,
+    ) -> Option<WarmVmState<HistoryDisabled>> {
+        if !matches!(fast_vm_mode, FastVmMode::Old) {
+            return None;
+        }
+        self.warm_pool
+            .as_ref()?
+            .take(env.system.version, &env.system.base_system_smart_contracts)
+    }
+
     fn select_fast_vm_mode(
         &self,
         env: &OneshotEnv,
@@ -133,15 +182,21 @@ where
         args: TxExecutionArgs,
         tracing_params: OneshotTracingParams,
     ) -> anyhow::Result<OneshotTransactionExecutionResult> {
-        let missed_storage_invocation_limit = match env.system.execution_mode {
-            // storage accesses are not limited for tx validation
-            TxExecutionMode::VerifyExecute => usize::MAX,
+        let (missed_storage_invocation_limit, resource_limits) = match env.system.execution_mode {
+            // storage accesses and resources are not limited for tx validation
+            TxExecutionMode::VerifyExecute => (usize::MAX, None),
             TxExecutionMode::EthCall | TxExecutionMode::EstimateFee => {
-                self.missed_storage_invocation_limit
+                (self.missed_storage_invocation_limit, self.resource_limits)
             }
         };
+        let resource_usage = Arc::<OnceCell<ResourceUsage>>::default();
+        let resource_tracer =
+            resource_limits.map(|limits| ResourceUsageTracer::new(limits, resource_usage.clone()));
+        let fast_vm_mode = self.select_fast_vm_mode(&env, &tracing_params);
+        let frame_gas_cap = self.frame_gas_cap.clone();
         let sandbox = VmSandbox {
-            fast_vm_mode: self.select_fast_vm_mode(&env, &tracing_params),
+            fast_vm_mode,
+            warm_state: self.take_warm_state(&env, fast_vm_mode),
             vm_divergence_handler: self.vm_divergence_handler.clone(),
             storage,
             env,
@@ -150,11 +205,13 @@ where
         };
 
         let current_span = tracing::Span::current();
-        tokio::task::spawn_blocking(move || {
+        let result = tokio::task::spawn_blocking(move || {
             let _entered_span = current_span.entered();
             sandbox.execute_in_vm(|vm, transaction| {
                 vm.inspect_transaction_with_bytecode_compression(
                     missed_storage_invocation_limit,
+                    resource_tracer,
+                    frame_gas_cap,
                     tracing_params,
                     transaction,
                     true,
@@ -162,7 +219,12 @@ where
             })
         })
         .await
-        .context("VM execution panicked")
+        .context("VM execution panicked")?;
+
+        if let Some(usage) = resource_usage.get() {
+            metrics::report_resource_usage(usage);
+        }
+        Ok(result)
     }
 }
 
@@ -185,12 +247,15 @@ where
         );
 
         let l1_batch_env = env.l1_batch.clone();
+        let fast_vm_mode = if !is_supported_by_fast_vm(env.system.version) {
+            FastVmMode::Old // the fast VM doesn't support old protocol versions
+        } else {
+            self.fast_vm_mode
+        };
+        let frame_gas_cap = self.frame_gas_cap.clone();
         let sandbox = VmSandbox {
-            fast_vm_mode: if !is_supported_by_fast_vm(env.system.version) {
-                FastVmMode::Old // the fast VM doesn't support old protocol versions
-            } else {
-                self.fast_vm_mode
-            },
+            fast_vm_mode,
+            warm_state: self.take_warm_state(&env, fast_vm_mode),
             vm_divergence_handler: self.vm_divergence_handler.clone(),
             storage,
             env,
@@ -207,7 +272,13 @@ where
             sandbox.execute_in_vm(|vm, transaction| match vm {
                 Vm::Legacy(vm) => {
                     vm.push_transaction(transaction);
-                    validate_legacy(vm, version, validation_params, batch_timestamp)
+                    validate_legacy(
+                        vm,
+                        version,
+                        validation_params,
+                        batch_timestamp,
+                        frame_gas_cap,
+                    )
                 }
 
                 Vm::Fast(_, FastVmInstance::Fast(vm)) => {
@@ -223,6 +294,7 @@ where
                             version,
                             validation_params.clone(),
                             batch_timestamp,
+                            None,
                         ),
                         ShadowMut::Shadow(vm) => {
                             validate_fast(vm, validation_params.clone(), batch_timestamp)
@@ -246,6 +318,8 @@ impl<S: ReadStorage> Vm<S, StorageInvocationsTracer<StorageView<S>>, FastValidat
     fn inspect_transaction_with_bytecode_compression(
         &mut self,
         missed_storage_invocation_limit: usize,
+        resource_tracer: Option<ResourceUsageTracer>,
+        frame_gas_cap: Option<FrameGasCap>,
         params: OneshotTracingParams,
         tx: Transaction,
         with_compression: bool,
@@ -256,6 +330,8 @@ impl<S: ReadStorage> Vm<S, StorageInvocationsTracer<StorageView<S>>, FastValidat
                 let mut tracers = Self::create_legacy_tracers(
                     missed_storage_invocation_limit,
                     params.trace_calls.then(|| calls_result.clone()),
+                    resource_tracer,
+                    frame_gas_cap,
                 );
                 vm.inspect_transaction_with_bytecode_compression(&mut tracers, tx, with_compression)
             }
@@ -264,9 +340,13 @@ impl<S: ReadStorage> Vm<S, StorageInvocationsTracer<StorageView<S>>, FastValidat
                     !params.trace_calls,
                     "Call tracing is not supported by fast VM yet"
                 );
+                // Resources are not accounted and the frame gas cap is not enforced for the fast VM,
+                // so the shadow VM doesn't do either.
                 let legacy_tracers = Self::create_legacy_tracers::<HistoryEnabled>(
                     missed_storage_invocation_limit,
                     None,
+                    None,
+                    None,
                 );
                 let tracer =
                     StorageInvocationsTracer::new(storage.clone(), missed_storage_invocation_limit);
@@ -303,6 +383,8 @@ impl<S: ReadStorage> Vm<S, StorageInvocationsTracer<StorageView<S>>, FastValidat
     fn create_legacy_tracers<H: HistoryMode>(
         missed_storage_invocation_limit: usize,
         calls_result: Option<Arc<OnceCell<Vec<Call>>>>,
+        resource_tracer: Option<ResourceUsageTracer>,
+        frame_gas_cap: Option<FrameGasCap>,
     ) -> TracerDispatcher<StorageView<S>, H> {
         let mut tracers = vec![];
         if let Some(calls_result) = calls_result {
@@ -310,6 +392,12 @@ impl<S: ReadStorage> Vm<S, StorageInvocationsTracer<StorageView<S>>, FastValidat
         }
         tracers
             .push(StorageInvocations::new(missed_storage_invocation_limit).into_tracer_pointer());
+        if let Some(resource_tracer) = resource_tracer {
+            tracers.push(resource_tracer.into_tracer_pointer());
+        }
+        if let Some(frame_gas_cap) = frame_gas_cap {
+            tracers.push(frame_gas_cap.into_tracer_pointer());
+        }
         tracers.into()
     }
 }
@@ -340,17 +428,23 @@ fn validate_legacy<S, H>(
     version: VmVersion,
     validation_params: ValidationParams,
     batch_timestamp: u64,
+    frame_gas_cap: Option<FrameGasCap>,
 ) -> Result<ValidationTraces, ValidationError>
 where
     S: WriteStorage,
     H: 'static + HistoryMode,
     ValidationTracer<H>: MultiVmTracer<S, H>,
+    FrameGasCap: MultiVmTracer<S, H>,
 {
     let validation_tracer = ValidationTracer::<H>::new(validation_params, version, batch_timestamp);
     let mut validation_result = validation_tracer.get_result();
     let validation_traces = validation_tracer.get_traces();
-    let validation_tracer: Box<dyn MultiVmTracer<_, H>> = validation_tracer.into_tracer_pointer();
-    let tracers = TracerDispatcher::from(validation_tracer);
+    let mut tracers: Vec<Box<dyn MultiVmTracer<_, H>>> =
+        vec![validation_tracer.into_tracer_pointer()];
+    if let Some(frame_gas_cap) = frame_gas_cap {
+        tracers.push(frame_gas_cap.into_tracer_pointer());
+    }
+    let tracers = TracerDispatcher::from(tracers);
 
     let exec_result = vm.inspect(&mut tracers.into(), InspectExecutionMode::OneTx);
 
@@ -369,6 +463,8 @@ where
 #[derive(Debug)]
 struct VmSandbox<S> {
     fast_vm_mode: FastVmMode,
+    /// Pre-initialized state for the legacy VM, if any.
+    warm_state: Option<WarmVmState<HistoryDisabled>>,
     vm_divergence_handler: DivergenceHandler,
     storage: StorageWithOverrides<S>,
     env: OneshotEnv,
@@ -459,12 +555,22 @@ impl<S: ReadStorage> VmSandbox<S> {
 
         let storage_view = StorageView::new(self.storage).to_rc_ptr();
         let mut vm = match self.fast_vm_mode {
-            FastVmMode::Old => Vm::Legacy(LegacyVmInstance::new_with_specific_version(
-                self.env.l1_batch,
-                self.env.system,
-                storage_view.clone(),
-                protocol_version.into_api_vm_version(),
-            )),
+            FastVmMode::Old => Vm::Legacy(if let Some(warm_state) = self.warm_state {
+                LegacyVmInstance::new_with_warm_state(
+                    self.env.l1_batch,
+                    self.env.system,
+                    storage_view.clone(),
+                    protocol_version.into_api_vm_version(),
+                    warm_state,
+                )
+            } else {
+                LegacyVmInstance::new_with_specific_version(
+                    self.env.l1_batch,
+                    self.env.system,
+                    storage_view.clone(),
+                    protocol_version.into_api_vm_version(),
+                )
+            }),
             FastVmMode::New => Vm::Fast(
                 storage_view.clone(),
                 FastVmInstance::fast(self.env.l1_batch, self.env.system, storage_view.clone()),
@@ -104,3 +104,75 @@ async fn inspecting_transfer(exec_mode: TxExecutionMode, fast_vm_mode: FastVmMod
     let exec_result = result.tx_result.result;
     assert!(!exec_result.is_failed(), "{exec_result:?}");
 }
+
+async fn wait_for_warm_pool(pool: &VmWarmPool, env: &OneshotEnv, expected_len: usize) {
+    let base_system_contracts = &env.system.base_system_smart_contracts;
+    while pool.available_states(env.system.version, base_system_contracts) < expected_len {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+#[tokio::test]
+async fn inspecting_transfer_with_warm_pool() {
+    let tx = create_l2_transaction(1_000_000_000.into(), Nonce(0));
+    let mut storage = InMemoryStorage::with_system_contracts();
+    storage.set_value(
+        storage_key_for_eth_balance(&tx.initiator_account()),
+        u256_to_h256(u64::MAX.into()),
+    );
+
+    let l1_batch = default_l1_batch_env(1);
+    let env = OneshotEnv {
+        system: default_system_env(TxExecutionMode::EthCall),
+        current_block: Some(StoredL2BlockEnv {
+            number: l1_batch.first_l2_block.number - 1,
+            timestamp: l1_batch.first_l2_block.timestamp - 1,
+            txs_rolling_hash: H256::zero(),
+        }),
+        l1_batch,
+    };
+
+    let mut executor = MainOneshotExecutor::new(usize::MAX);
+    executor.set_warm_pool_size(2);
+    let pool = executor.warm_pool.clone().unwrap();
+    // The first execution misses the pool and triggers its refill.
+    assert!(executor.take_warm_state(&env, FastVmMode::Old).is_none());
+    wait_for_warm_pool(&pool, &env, 2).await;
+
+    let result = executor
+        .inspect_transaction_with_bytecode_compression(
+            StorageWithOverrides::new(storage),
+            env.clone(),
+            TxExecutionArgs::for_gas_estimate(tx.into()),
+            OneshotTracingParams::default(),
+        )
+        .await
+        .unwrap();
+    result.compression_result.unwrap();
+    let exec_result = result.tx_result.result;
+    assert!(!exec_result.is_failed(), "{exec_result:?}");
+    // The taken state should be replaced in the background.
+    wait_for_warm_pool(&pool, &env, 2).await;
+
+    // Executions for older protocol versions don't use or invalidate the pool.
+    let mut old_env = env.clone();
+    old_env.system.version = ProtocolVersionId::Version22;
+    assert!(executor
+        .take_warm_state(&old_env, FastVmMode::Old)
+        .is_none());
+    assert_eq!(
+        pool.available_states(env.system.version, &env.system.base_system_smart_contracts),
+        2
+    );
+
+    // A newer protocol version invalidates the pool.
+    let mut new_env = env.clone();
+    new_env.system.version = ProtocolVersionId::next();
+    assert!(executor
+        .take_warm_state(&new_env, FastVmMode::Old)
+        .is_none());
+    assert_eq!(
+        pool.available_states(env.system.version, &env.system.base_system_smart_contracts),
+        0
+    );
+}
@@ -0,0 +1,169 @@
+//! Pool of pre-initialized VM states used to exclude VM setup from oneshot execution latency.
+
+use std::sync::{Arc, Mutex};
+
+use zksync_contracts::{BaseSystemContracts, BaseSystemContractsHashes};
+use zksync_multivm::vm_latest::{HistoryDisabled, WarmVmState};
+use zksync_types::ProtocolVersionId;
+
+use super::metrics::{WarmPoolOutcome, WARM_POOL_METRICS};
+
+#[derive(Debug)]
+struct PoolEntry {
+    base_system_contracts: BaseSystemContractsHashes,
+    states: Vec<WarmVmState<HistoryDisabled>>,
+    is_refilling: bool,
+}
+
+#[derive(Debug, Default)]
+struct PoolState {
+    protocol_version: Option<ProtocolVersionId>,
+    entries: Vec<PoolEntry>,
+}
+
+impl PoolState {
+    fn entry_mut(
+        &mut self,
+        protocol_version: ProtocolVersionId,
+        base_system_contracts: &BaseSystemContractsHashes,
+    ) -> Option<&mut PoolEntry> {
+        if self.protocol_version != Some(protocol_version) {
+            return None;
+        }
+        self.entries
+            .iter_mut()
+            .find(|entry| entry.base_system_contracts == *base_system_contracts)
+    }
+}
+
+/// Pool of warm legacy VM states for the latest protocol version.
+///
+/// States are prepared on blocking threads in the background. The pool is invalidated once a newer protocol version
+/// is observed; states for older protocol versions (e.g., for calls on historical blocks) are not pooled.
+/// Within a protocol version, states are kept for a limited number of base system contracts sets, so that a change
+/// of base system contracts evicts stale states.
+#[derive(Debug, Clone)]
+pub(super) struct VmWarmPool {
+    size: usize,
+    state: Arc<Mutex<PoolState>>,
+}
+
+impl VmWarmPool {
+    /// Maximum number of base system contracts sets to keep states for. The API server uses distinct contracts
+    /// for gas estimation and for calls / transaction execution.
+    const MAX_CONTRACT_SETS: usize = 2;
+
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            state: Arc::default(),
+        }
+    }
+
+    /// Takes a warm state for the specified protocol version and base system contracts and schedules refilling
+    /// the pool in the background. Returns `None` if the pool has no suitable state.
+    pub fn take(
+        &self,
+        protocol_version: ProtocolVersionId,
+        base_system_contracts: &BaseSystemContracts,
+    ) -> Option<WarmVmState<HistoryDisabled>> {
+        let hashes = base_system_contracts.hashes();
+        let mut state = self.state.lock().unwrap();
+        match state.protocol_version {
+            Some(version) if version > protocol_version => {
+                WARM_POOL_METRICS.take[&WarmPoolOutcome::OldProtocolVersion].inc();
+                return None;
+            }
+            Some(version) if version == protocol_version => { /* pool is up to date */ }
+            prev_version => {
+                if let Some(prev_version) = prev_version {
+                    tracing::info!(
+                        "Invalidating warm VM states for protocol version {prev_version:?}; new version is {protocol_version:?}"
+                    );
+                    WARM_POOL_METRICS.invalidations.inc();
+                }
+                state.protocol_version = Some(protocol_version);
+                state.entries.clear();
+            }
+        }
+
+        if state.entry_mut(protocol_version, &hashes).is_none() {
+            if state.entries.len() >= Self::MAX_CONTRACT_SETS {
+                let evicted = state.entries.remove(0);
+                tracing::info!(
+                    "Evicting warm VM states for base system contracts {:?}",
+                    evicted.base_system_contracts
+                );
+                WARM_POOL_METRICS.invalidations.inc();
+            }
+            state.entries.push(PoolEntry {
+                base_system_contracts: hashes,
+                states: vec![],
+                is_refilling: false,
+            });
+        }
+        let entry = state.entry_mut(protocol_version, &hashes).unwrap();
+        let warm_state = entry.states.pop();
+        let should_refill = !entry.is_refilling && entry.states.len() < self.size;
+        entry.is_refilling |= should_refill;
+        drop(state);
+
+        let outcome = if warm_state.is_some() {
+            WarmPoolOutcome::Hit
+        } else {
+            WarmPoolOutcome::Miss
+        };
+        WARM_POOL_METRICS.take[&outcome].inc();
+
+        if should_refill {
+            let pool = self.clone();
+            let base_system_contracts = base_system_contracts.clone();
+            tokio::task::spawn_blocking(move || {
+                pool.refill(protocol_version, &base_system_contracts);
+            });
+        }
+        warm_state
+    }
+
+    /// This method is blocking.
+    fn refill(
+        &self,
+        protocol_version: ProtocolVersionId,
+        base_system_contracts: &BaseSystemContracts,
+    ) {
+        let hashes = base_system_contracts.hashes();
+        loop {
+            {
+                let mut state = self.state.lock().unwrap();
+                let Some(entry) = state.entry_mut(protocol_version, &hashes) else {
+                    return; // The entry was invalidated
+                };
+                if entry.states.len() >= self.size {
+                    entry.is_refilling = false;
+                    return;
+                }
+            }
+
+            // Initialize the state without holding the lock.
+            let warm_state = WarmVmState::new(base_system_contracts);
+            let mut state = self.state.lock().unwrap();
+            let Some(entry) = state.entry_mut(protocol_version, &hashes) else {
+                return;
+            };
+            entry.states.push(warm_state);
+            WARM_POOL_METRICS.size.set(entry.states.len());
+        }
+    }
+
+    #[cfg(test)]
+    pub fn available_states(
+        &self,
+        protocol_version: ProtocolVersionId,
+        base_system_contracts: &BaseSystemContracts,
+    ) -> usize {
+        let mut state = self.state.lock().unwrap();
+        state
+            .entry_mut(protocol_version, &base_system_contracts.hashes())
+            .map_or(0, |entry| entry.states.len())
+    }
+}
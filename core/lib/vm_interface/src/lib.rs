@@ -28,9 +28,11 @@ pub use crate::{
             StoredL2BlockEnv, SystemEnv, TxExecutionArgs, TxExecutionMode, VmExecutionMode,
         },
         outputs::{
-            BatchTransactionExecutionResult, BootloaderMemory, Call, CallType, CircuitStatistic,
-            CompressedBytecodeInfo, CurrentExecutionState, DeduplicatedWritesMetrics,
-            ExecutionResult, FinishedL1Batch, L2Block, OneshotTransactionExecutionResult,
+            BatchTransactionExecutionResult, BootloaderMemory, BootloaderMemoryDump,
+            BootloaderMemoryDumpConfig, BootloaderMemoryDumpPoint, BootloaderMemoryRegion,
+            BootloaderMemoryRegionDump, Call, CallType, CircuitStatistic, CompressedBytecodeInfo,
+            CurrentExecutionState, DeduplicatedWritesMetrics, ExecutionResult, FinishedL1Batch,
+            L2Block, OneshotTransactionExecutionResult, PrecompileInputs, PrecompileInputsBuffer,
             PushTransactionResult, Refunds, TransactionExecutionMetrics,
             TransactionExecutionResult, TxExecutionStatus, VmEvent, VmExecutionLogs,
             VmExecutionMetrics, VmExecutionResultAndLogs, VmExecutionStatistics, VmMemoryMetrics,
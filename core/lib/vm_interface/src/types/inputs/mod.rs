@@ -1,5 +1,5 @@
 use zksync_types::{
-    l2::L2Tx, ExecuteTransactionCommon, Nonce, PackedEthSignature, Transaction, U256,
+    l1::L1Tx, l2::L2Tx, ExecuteTransactionCommon, Nonce, PackedEthSignature, Transaction, U256,
 };
 
 pub use self::{
@@ -69,6 +69,17 @@ impl TxExecutionArgs {
         }
     }
 
+    /// Creates arguments to execute an L1 -> L2 transaction in the same way as the state keeper does it,
+    /// i.e. without any nonce / balance overrides or gas price adjustments.
+    pub fn for_l1_tx(tx: L1Tx) -> Self {
+        Self {
+            enforced_nonce: None,
+            added_balance: U256::zero(),
+            adjust_pubdata_price: false,
+            transaction: tx.into(),
+        }
+    }
+
     pub fn for_gas_estimate(transaction: Transaction) -> Self {
         // For L2 transactions we need to explicitly put enough balance into the account of the users
         // while for L1->L2 transactions the `to_mint` field plays this role
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use zksync_types::U256;
+
+/// Region of the bootloader heap that can be dumped for debugging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BootloaderMemoryRegion {
+    /// Descriptions of transactions pushed to the bootloader: for each transaction, its metadata
+    /// (execution mode flags) and the offset of the transaction encoding.
+    TxDescriptions,
+    /// Refunds provided by the operator for each transaction pushed to the bootloader.
+    OperatorRefunds,
+    /// Pointers to the operator-provided L1 messenger pubdata: the call selector, calldata offset and pubdata length.
+    PubdataPointers,
+}
+
+impl BootloaderMemoryRegion {
+    pub const ALL: [Self; 3] = [
+        Self::TxDescriptions,
+        Self::OperatorRefunds,
+        Self::PubdataPointers,
+    ];
+}
+
+/// Point of bootloader execution at which memory regions are dumped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BootloaderMemoryDumpPoint {
+    /// The bootloader has started validating a transaction.
+    ValidationStarted,
+    /// The bootloader has asked the operator for a transaction refund.
+    RefundRequested,
+    /// The bootloader has finished processing a transaction.
+    TxEnded,
+    /// The bootloader has requested pubdata from the operator.
+    PubdataRequested,
+    /// VM execution has stopped.
+    ExecutionEnded,
+}
+
+impl BootloaderMemoryDumpPoint {
+    pub const ALL: [Self; 5] = [
+        Self::ValidationStarted,
+        Self::RefundRequested,
+        Self::TxEnded,
+        Self::PubdataRequested,
+        Self::ExecutionEnded,
+    ];
+}
+
+/// Configuration of bootloader memory dumps: which regions to dump and at which execution points.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BootloaderMemoryDumpConfig {
+    pub points: Vec<BootloaderMemoryDumpPoint>,
+    pub regions: Vec<BootloaderMemoryRegion>,
+}
+
+impl BootloaderMemoryDumpConfig {
+    /// Creates a config dumping all supported regions at all supported execution points.
+    pub fn all() -> Self {
+        Self {
+            points: BootloaderMemoryDumpPoint::ALL.to_vec(),
+            regions: BootloaderMemoryRegion::ALL.to_vec(),
+        }
+    }
+}
+
+/// Contents of a single bootloader memory region.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BootloaderMemoryRegionDump {
+    pub region: BootloaderMemoryRegion,
+    /// Index of the first dumped word in the bootloader heap.
+    pub start_slot: usize,
+    pub words: Vec<U256>,
+}
+
+/// Snapshot of selected bootloader memory regions taken at a certain execution point.
+///
+/// A sequence of dumps is serializable and can be saved as a debug artifact, e.g. as JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BootloaderMemoryDump {
+    pub point: BootloaderMemoryDumpPoint,
+    /// Index of the transaction being processed by the bootloader, if any.
+    pub tx_index: Option<usize>,
+    /// VM timestamp at which the dump was taken.
+    pub timestamp: u32,
+    pub regions: Vec<BootloaderMemoryRegionDump>,
+}
@@ -1,6 +1,6 @@
 use zksync_types::writes::StateDiffRecord;
 
-use super::{BootloaderMemory, CurrentExecutionState, VmExecutionResultAndLogs};
+use super::{BootloaderMemory, CurrentExecutionState, PrecompileInputs, VmExecutionResultAndLogs};
 
 /// State of the VM after the batch execution.
 #[derive(Debug, Clone)]
@@ -15,6 +15,9 @@ pub struct FinishedL1Batch {
     pub pubdata_input: Option<Vec<u8>>,
     /// List of state diffs. Could be none for old versions of the VM.
     pub state_diffs: Option<Vec<StateDiffRecord>>,
+    /// Inputs of hashing precompile calls used for witness generation. Could be none for old versions of the VM,
+    /// for the fast VM, and if recording precompile inputs was not requested.
+    pub precompile_inputs: Option<PrecompileInputs>,
 }
 
 impl FinishedL1Batch {
@@ -33,6 +36,7 @@ impl FinishedL1Batch {
             final_bootloader_memory: Some(vec![]),
             pubdata_input: Some(vec![]),
             state_diffs: Some(vec![]),
+            precompile_inputs: Some(PrecompileInputs::default()),
         }
     }
 }
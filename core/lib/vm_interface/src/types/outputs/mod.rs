@@ -1,6 +1,10 @@
 use std::borrow::Cow;
 
 pub use self::{
+    bootloader_memory_dump::{
+        BootloaderMemoryDump, BootloaderMemoryDumpConfig, BootloaderMemoryDumpPoint,
+        BootloaderMemoryRegion, BootloaderMemoryRegionDump,
+    },
     bytecode::CompressedBytecodeInfo,
     execution_result::{
         BatchTransactionExecutionResult, Call, CallType, ExecutionResult,
@@ -10,17 +14,20 @@ pub use self::{
     execution_state::{BootloaderMemory, CurrentExecutionState},
     finished_l1batch::FinishedL1Batch,
     l2_block::L2Block,
+    precompile_inputs::{PrecompileInputs, PrecompileInputsBuffer},
     statistic::{
         CircuitStatistic, DeduplicatedWritesMetrics, TransactionExecutionMetrics,
         VmExecutionMetrics, VmExecutionStatistics, VmMemoryMetrics,
     },
 };
 
+mod bootloader_memory_dump;
 mod bytecode;
 mod execution_result;
 mod execution_state;
 mod finished_l1batch;
 mod l2_block;
+mod precompile_inputs;
 mod statistic;
 
 /// Result of pushing a transaction to the VM state without executing it.
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use zksync_types::U256;
+
+/// Inputs of hashing precompile calls performed during the batch, in the order of execution.
+///
+/// Inputs for each precompile are laid out in a single contiguous buffer of memory words, so that hash circuits
+/// can consume them without re-parsing memory queries for each call.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrecompileInputs {
+    pub keccak256: PrecompileInputsBuffer,
+    pub sha256: PrecompileInputsBuffer,
+}
+
+/// Contiguous buffer with memory words read by calls to a certain precompile.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrecompileInputsBuffer {
+    /// Memory words read by all calls, concatenated in the call order.
+    words: Vec<U256>,
+    /// Start offset of each call in `words`. The call ends at the start of the next call, or at the end of `words`.
+    offsets: Vec<usize>,
+}
+
+impl PrecompileInputsBuffer {
+    /// Appends inputs of a single precompile call.
+    pub fn push_call(&mut self, words: impl IntoIterator<Item = U256>) {
+        self.offsets.push(self.words.len());
+        self.words.extend(words);
+    }
+
+    /// Returns the number of calls in this buffer.
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Returns memory words read by all calls.
+    pub fn words(&self) -> &[U256] {
+        &self.words
+    }
+
+    /// Returns start offsets of calls in [`Self::words()`].
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    /// Returns inputs of the call with the specified index.
+    pub fn call(&self, index: usize) -> Option<&[U256]> {
+        let start = *self.offsets.get(index)?;
+        let end = self
+            .offsets
+            .get(index + 1)
+            .copied()
+            .unwrap_or(self.words.len());
+        Some(&self.words[start..end])
+    }
+
+    /// Iterates over inputs of all calls in the call order.
+    pub fn calls(&self) -> impl Iterator<Item = &[U256]> + '_ {
+        (0..self.len()).map(|index| self.call(index).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn precompile_inputs_buffer_basics() {
+        let mut buffer = PrecompileInputsBuffer::default();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.call(0), None);
+
+        buffer.push_call([U256::from(1), U256::from(2)]);
+        buffer.push_call([]);
+        buffer.push_call([U256::from(3)]);
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.words(), [1, 2, 3].map(U256::from));
+        assert_eq!(buffer.offsets(), [0, 2, 2]);
+
+        let calls: Vec<_> = buffer.calls().collect();
+        assert_eq!(
+            calls,
+            [&[U256::from(1), U256::from(2)][..], &[], &[U256::from(3)]]
+        );
+        assert_eq!(buffer.call(3), None);
+    }
+}
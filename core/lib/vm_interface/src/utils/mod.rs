@@ -5,7 +5,12 @@ pub use self::{
     shadow::{
         CheckDivergence, DivergenceErrors, DivergenceHandler, ShadowMut, ShadowRef, ShadowVm,
     },
+    state_snapshot::{
+        CallFrameSnapshot, LogQuerySnapshot, StorageSlotSnapshot, VmFlagsSnapshot,
+        VmStateDifference, VmStateSnapshot, VmWordSnapshot,
+    },
 };
 
 mod dump;
 mod shadow;
+mod state_snapshot;
@@ -0,0 +1,286 @@
+//! Serializable snapshots of the full VM state and their diffing.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use serde::{Deserialize, Serialize};
+use zksync_types::{Address, H256, U256};
+
+/// Value of a register or a memory word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VmWordSnapshot {
+    pub value: U256,
+    pub is_pointer: bool,
+}
+
+/// VM flags set by the last executed instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VmFlagsSnapshot {
+    pub overflow_or_less_than: bool,
+    pub equal: bool,
+    pub greater_than: bool,
+}
+
+/// Snapshot of a single (far or near) call frame.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallFrameSnapshot {
+    pub this_address: Address,
+    pub msg_sender: Address,
+    pub code_address: Address,
+    pub base_memory_page: u32,
+    pub code_page: u32,
+    pub pc: u16,
+    pub sp: u16,
+    pub ergs_remaining: u32,
+    pub context_value: U256,
+    pub is_static: bool,
+    pub is_local_frame: bool,
+}
+
+/// Storage slot modified during VM execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageSlotSnapshot {
+    pub address: Address,
+    pub key: H256,
+    pub value: H256,
+}
+
+/// Log query (an event or an L2-to-L1 message) recorded by the event sink oracle, including rollbacks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogQuerySnapshot {
+    pub timestamp: u32,
+    pub aux_byte: u8,
+    pub address: Address,
+    pub key: U256,
+    pub value: U256,
+    pub is_service: bool,
+    pub rollback: bool,
+}
+
+/// Serializable snapshot of the full VM state: registers, call stack, memory pages and oracle states.
+///
+/// Snapshots can be taken at arbitrary points of VM execution and compared using [`Self::diff()`], e.g. to bisect
+/// behavioral changes between VM versions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VmStateSnapshot {
+    pub timestamp: u32,
+    pub monotonic_cycle_counter: u32,
+    pub registers: Vec<VmWordSnapshot>,
+    pub flags: VmFlagsSnapshot,
+    /// Call frames ordered from the outermost one to the current one.
+    pub callstack: Vec<CallFrameSnapshot>,
+    /// Non-empty memory words keyed by the page number and the word index.
+    pub memory: BTreeMap<u32, BTreeMap<u32, VmWordSnapshot>>,
+    /// Storage slots modified during execution, ordered by address and key.
+    pub storage: Vec<StorageSlotSnapshot>,
+    /// Non-zero transient storage slots, ordered by address and key.
+    pub transient_storage: Vec<StorageSlotSnapshot>,
+    /// Log queries in the order they were recorded.
+    pub log_queries: Vec<LogQuerySnapshot>,
+    /// Hashes of bytecodes decommitted during execution, in the ascending order.
+    pub decommitted_code_hashes: Vec<H256>,
+    pub precompile_calls: usize,
+}
+
+/// Difference between two [`VmStateSnapshot`]s.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VmStateDifference {
+    /// Path to the differing value, e.g. `registers[3]` or `memory[8][120]`.
+    pub path: String,
+    /// Debug representation of the value in the first snapshot, or `None` if the value is missing.
+    pub left: Option<String>,
+    /// Debug representation of the value in the second snapshot, or `None` if the value is missing.
+    pub right: Option<String>,
+}
+
+impl fmt::Display for VmStateDifference {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let left = self.left.as_deref().unwrap_or("(missing)");
+        let right = self.right.as_deref().unwrap_or("(missing)");
+        write!(formatter, "{}: {left} -> {right}", self.path)
+    }
+}
+
+#[derive(Debug, Default)]
+struct DiffCollector(Vec<VmStateDifference>);
+
+impl DiffCollector {
+    fn check<T: fmt::Debug + PartialEq>(
+        &mut self,
+        path: String,
+        left: Option<&T>,
+        right: Option<&T>,
+    ) {
+        if left != right {
+            self.0.push(VmStateDifference {
+                path,
+                left: left.map(|value| format!("{value:?}")),
+                right: right.map(|value| format!("{value:?}")),
+            });
+        }
+    }
+
+    fn check_value<T: fmt::Debug + PartialEq>(&mut self, path: &str, left: &T, right: &T) {
+        self.check(path.to_owned(), Some(left), Some(right));
+    }
+
+    fn check_seq<T: fmt::Debug + PartialEq>(&mut self, path: &str, left: &[T], right: &[T]) {
+        for i in 0..left.len().max(right.len()) {
+            self.check(format!("{path}[{i}]"), left.get(i), right.get(i));
+        }
+    }
+
+    fn check_map<K: Ord + fmt::Debug, V: fmt::Debug + PartialEq>(
+        &mut self,
+        path: &str,
+        left: &BTreeMap<K, V>,
+        right: &BTreeMap<K, V>,
+    ) {
+        let keys: BTreeSet<_> = left.keys().chain(right.keys()).collect();
+        for key in keys {
+            self.check(format!("{path}[{key:?}]"), left.get(key), right.get(key));
+        }
+    }
+
+    fn check_storage(
+        &mut self,
+        path: &str,
+        left: &[StorageSlotSnapshot],
+        right: &[StorageSlotSnapshot],
+    ) {
+        let to_map = |slots: &[StorageSlotSnapshot]| -> BTreeMap<_, _> {
+            slots
+                .iter()
+                .map(|slot| ((slot.address, slot.key), slot.value))
+                .collect()
+        };
+        self.check_map(path, &to_map(left), &to_map(right));
+    }
+}
+
+impl VmStateSnapshot {
+    /// Compares this snapshot with another one and returns all found differences. Collections are compared
+    /// element-wise, so that differences can be pinpointed to a specific register, frame, memory word etc.
+    pub fn diff(&self, other: &Self) -> Vec<VmStateDifference> {
+        let mut diff = DiffCollector::default();
+        diff.check_value("timestamp", &self.timestamp, &other.timestamp);
+        diff.check_value(
+            "monotonic_cycle_counter",
+            &self.monotonic_cycle_counter,
+            &other.monotonic_cycle_counter,
+        );
+        diff.check_seq("registers", &self.registers, &other.registers);
+        diff.check_value("flags", &self.flags, &other.flags);
+        diff.check_seq("callstack", &self.callstack, &other.callstack);
+
+        let empty_page = BTreeMap::new();
+        let pages: BTreeSet<_> = self.memory.keys().chain(other.memory.keys()).collect();
+        for page in pages {
+            diff.check_map(
+                &format!("memory[{page}]"),
+                self.memory.get(page).unwrap_or(&empty_page),
+                other.memory.get(page).unwrap_or(&empty_page),
+            );
+        }
+
+        diff.check_storage("storage", &self.storage, &other.storage);
+        diff.check_storage(
+            "transient_storage",
+            &self.transient_storage,
+            &other.transient_storage,
+        );
+        diff.check_seq("log_queries", &self.log_queries, &other.log_queries);
+        diff.check_value(
+            "decommitted_code_hashes",
+            &self.decommitted_code_hashes,
+            &other.decommitted_code_hashes,
+        );
+        diff.check_value(
+            "precompile_calls",
+            &self.precompile_calls,
+            &other.precompile_calls,
+        );
+        diff.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(value: u64) -> VmWordSnapshot {
+        VmWordSnapshot {
+            value: value.into(),
+            is_pointer: false,
+        }
+    }
+
+    fn mock_snapshot() -> VmStateSnapshot {
+        VmStateSnapshot {
+            timestamp: 1_024,
+            monotonic_cycle_counter: 100,
+            registers: vec![word(0); 15],
+            flags: VmFlagsSnapshot {
+                overflow_or_less_than: false,
+                equal: true,
+                greater_than: false,
+            },
+            callstack: vec![],
+            memory: BTreeMap::from([(8, BTreeMap::from([(0, word(1)), (5, word(2))]))]),
+            storage: vec![StorageSlotSnapshot {
+                address: Address::repeat_byte(1),
+                key: H256::zero(),
+                value: H256::repeat_byte(0xff),
+            }],
+            transient_storage: vec![],
+            log_queries: vec![],
+            decommitted_code_hashes: vec![H256::repeat_byte(2)],
+            precompile_calls: 0,
+        }
+    }
+
+    #[test]
+    fn diffing_identical_snapshots() {
+        let snapshot = mock_snapshot();
+        assert_eq!(snapshot.diff(&snapshot.clone()), []);
+    }
+
+    #[test]
+    fn diffing_snapshots() {
+        let left = mock_snapshot();
+        let mut right = left.clone();
+        right.registers[3] = word(42);
+        right.memory.get_mut(&8).unwrap().remove(&5);
+        right.memory.insert(10, BTreeMap::from([(1, word(3))]));
+        right.storage[0].value = H256::zero();
+
+        let diff = left.diff(&right);
+        let paths: Vec<_> = diff.iter().map(|diff| diff.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "registers[3]",
+                "memory[8][5]",
+                "memory[10][1]",
+                "storage[(0x0101010101010101010101010101010101010101, 0x0000000000000000000000000000000000000000000000000000000000000000)]"
+            ]
+        );
+        assert_eq!(diff[1].right, None);
+        assert_eq!(diff[2].left, None);
+        assert!(
+            diff[0].to_string().starts_with("registers[3]: "),
+            "{}",
+            diff[0]
+        );
+    }
+
+    #[test]
+    fn snapshot_serialization_roundtrip() {
+        let snapshot = mock_snapshot();
+        let serialized = serde_json::to_string(&snapshot).unwrap();
+        let restored: VmStateSnapshot = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(restored, snapshot);
+    }
+}
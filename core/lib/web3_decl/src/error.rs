@@ -9,6 +9,7 @@ use std::{
     mem,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use jsonrpsee::{core::ClientError, types::error::ErrorCode};
@@ -25,6 +26,10 @@ pub enum Web3Error {
     PrunedBlock(L2BlockNumber),
     #[error("L1 batch with such an ID is pruned; the first retained L1 batch is {0}")]
     PrunedL1Batch(L1BatchNumber),
+    /// Data of the specified kind (e.g., call traces) was removed for the requested block by data retention,
+    /// although the block itself is retained.
+    #[error("{0} for the requested block are pruned; {0} are retained starting from L1 batch {1}")]
+    PrunedData(&'static str, L1BatchNumber),
     #[error("{}", _0.as_ref())]
     ProxyError(#[from] EnrichedClientError),
     #[error("{0}")]
@@ -50,6 +55,10 @@ pub enum Web3Error {
     InternalError(#[from] anyhow::Error),
     #[error("Server is shutting down")]
     ServerShuttingDown,
+    /// Low-priority request was rejected because the server is overloaded. The client should retry
+    /// after the specified delay.
+    #[error("Server is overloaded; retry after {0:?}")]
+    ServerOverloaded(Duration),
 }
 
 /// Client RPC error with additional details: the method name and arguments of the called method.
@@ -0,0 +1,85 @@
+#[cfg_attr(not(feature = "server"), allow(unused_imports))]
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use zksync_types::{
+    api::{
+        AuditLogEntry, ComponentQueues, SealCriteriaSimulation, SequencerLease, TxPolicyDecision,
+    },
+    Address, L1BatchNumber,
+};
+
+use crate::client::{ForWeb3Network, L2};
+
+/// Control actions for node operators. This namespace is only served by a separately bound server
+/// requiring authentication, and must never be exposed publicly.
+#[cfg_attr(
+    feature = "server",
+    rpc(server, client, namespace = "admin", client_bounds(Self: ForWeb3Network<Net = L2>))
+)]
+#[cfg_attr(
+    not(feature = "server"),
+    rpc(client, namespace = "admin", client_bounds(Self: ForWeb3Network<Net = L2>))
+)]
+pub trait AdminNamespace {
+    /// Stops accepting new L2 transactions via the API servers of this node. Returns `false`
+    /// if the intake was already paused.
+    #[method(name = "pauseTxIntake")]
+    async fn pause_tx_intake(&self) -> RpcResult<bool>;
+
+    /// Resumes accepting new L2 transactions. Returns `false` if the intake wasn't paused.
+    #[method(name = "resumeTxIntake")]
+    async fn resume_tx_intake(&self) -> RpcResult<bool>;
+
+    /// Requests the state keeper to seal the current L1 batch as soon as it contains at least one transaction.
+    #[method(name = "sealL1Batch")]
+    async fn seal_l1_batch(&self) -> RpcResult<()>;
+
+    /// Sets the priority of proof generation for the specified L1 batch. Batches with higher priority
+    /// are handed out to provers first. Returns `false` if the batch is not queued for proof generation.
+    #[method(name = "setProofPriority")]
+    async fn set_proof_priority(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        priority: i32,
+    ) -> RpcResult<bool>;
+
+    /// Returns the state of the queues processed by the node components.
+    #[method(name = "getComponentQueues")]
+    async fn get_component_queues(&self) -> RpcResult<ComponentQueues>;
+
+    /// Returns the current sequencer lease, or `None` if no sequencer instance has acquired it yet.
+    #[method(name = "getSequencerLease")]
+    async fn get_sequencer_lease(&self) -> RpcResult<Option<SequencerLease>>;
+
+    /// Transfers the sequencer lease to the specified sequencer instance, e.g. a standby one. The current holder
+    /// stops sealing blocks immediately and shuts down. Returns the updated lease.
+    #[method(name = "promoteSequencer")]
+    async fn promote_sequencer(&self, instance_id: String) -> RpcResult<SequencerLease>;
+
+    /// Returns entries of the audit log of privileged actions, starting from the entry with the specified ID.
+    /// At most `limit` entries are returned (1,000 if not specified, which is also the upper bound).
+    #[method(name = "getAuditLog")]
+    async fn get_audit_log(
+        &self,
+        from_id: u64,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<AuditLogEntry>>;
+
+    /// Simulates which seal criterion would be triggered by pending mempool transactions, and after how many
+    /// of them. Doesn't affect the live L1 batch. At most `limit` pending transactions are considered
+    /// (100 if not specified, which is also the upper bound); each of them is executed in the sandbox.
+    #[method(name = "simulateBatchSealing")]
+    async fn simulate_batch_sealing(
+        &self,
+        limit: Option<usize>,
+    ) -> RpcResult<SealCriteriaSimulation>;
+
+    /// Returns up to `limit` latest transactions rejected by the transaction policy, optionally filtered
+    /// by the initiator address. At most 1,000 decisions are returned (100 if `limit` is not specified).
+    #[method(name = "getTxPolicyDecisions")]
+    async fn get_tx_policy_decisions(
+        &self,
+        address: Option<Address>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<TxPolicyDecision>>;
+}
@@ -0,0 +1,333 @@
+//! Builder-style helpers for `zks_` and `debug_` methods with optional params.
+//!
+//! The helpers are provided as extension traits for the corresponding namespace clients, so they are available
+//! for all clients (including [`DynClient`](crate::client::DynClient) and [`MockClient`](crate::client::MockClient)).
+//! Optional params that are not set are sent as `null`s, i.e., the server uses their default values.
+
+use chrono::NaiveDate;
+use jsonrpsee::core::ClientError;
+use zksync_types::{
+    api::{
+        state_override::StateOverride, BlockId, CallTracerBlockResult, CallTracerResult,
+        ContractUsage, ContractUsageMetric, FeeInputOverride, FeeWithSensitivity, TracerConfig,
+    },
+    fee::Fee,
+    transaction_request::CallRequest,
+};
+
+use super::{DebugNamespaceClient, ZksNamespaceClient};
+use crate::client::{ForWeb3Network, L2};
+
+/// Builder for `zks_estimateFee` and `zks_estimateFeeWithSensitivity` calls.
+#[derive(Debug)]
+#[must_use = "requests must be sent using `send()` or `send_with_sensitivity()`"]
+pub struct EstimateFeeRequest<'a, C: ?Sized> {
+    client: &'a C,
+    request: CallRequest,
+    state_override: Option<StateOverride>,
+    fee_input_override: Option<FeeInputOverride>,
+}
+
+impl<C> EstimateFeeRequest<'_, C>
+where
+    C: ZksNamespaceClient + ForWeb3Network<Net = L2> + ?Sized,
+{
+    /// Sets the state override applied when estimating the fee.
+    pub fn with_state_override(mut self, state_override: StateOverride) -> Self {
+        self.state_override = Some(state_override);
+        self
+    }
+
+    /// Sets the batch fee input override applied when estimating the fee.
+    pub fn with_fee_input_override(mut self, fee_input_override: FeeInputOverride) -> Self {
+        self.fee_input_override = Some(fee_input_override);
+        self
+    }
+
+    /// Sends the request using `zks_estimateFee`.
+    pub async fn send(self) -> Result<Fee, ClientError> {
+        self.client
+            .estimate_fee(self.request, self.state_override, self.fee_input_override)
+            .await
+    }
+
+    /// Sends the request using `zks_estimateFeeWithSensitivity`.
+    pub async fn send_with_sensitivity(self) -> Result<FeeWithSensitivity, ClientError> {
+        self.client
+            .estimate_fee_with_sensitivity(
+                self.request,
+                self.state_override,
+                self.fee_input_override,
+            )
+            .await
+    }
+}
+
+/// Builder for `zks_getTopContracts` calls.
+#[derive(Debug)]
+#[must_use = "requests must be sent using `send()`"]
+pub struct TopContractsRequest<'a, C: ?Sized> {
+    client: &'a C,
+    day: Option<NaiveDate>,
+    metric: Option<ContractUsageMetric>,
+    limit: Option<usize>,
+}
+
+impl<C> TopContractsRequest<'_, C>
+where
+    C: ZksNamespaceClient + ForWeb3Network<Net = L2> + ?Sized,
+{
+    /// Sets the day (UTC) to return contracts for. By default, the last complete day is used.
+    pub fn for_day(mut self, day: NaiveDate) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    /// Sets the metric to rank contracts by.
+    pub fn by_metric(mut self, metric: ContractUsageMetric) -> Self {
+        self.metric = Some(metric);
+        self
+    }
+
+    /// Sets the maximum number of returned contracts.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sends the request.
+    pub async fn send(self) -> Result<Vec<ContractUsage>, ClientError> {
+        self.client
+            .get_top_contracts(self.day, self.metric, self.limit)
+            .await
+    }
+}
+
+/// Builder-style helpers for `zks_` methods.
+pub trait ZksNamespaceClientExt: ZksNamespaceClient + ForWeb3Network<Net = L2> {
+    /// Starts building a fee estimation request for the specified transaction.
+    fn estimate_fee_request(&self, request: CallRequest) -> EstimateFeeRequest<'_, Self> {
+        EstimateFeeRequest {
+            client: self,
+            request,
+            state_override: None,
+            fee_input_override: None,
+        }
+    }
+
+    /// Starts building a request for top contracts by resource usage.
+    fn top_contracts_request(&self) -> TopContractsRequest<'_, Self> {
+        TopContractsRequest {
+            client: self,
+            day: None,
+            metric: None,
+            limit: None,
+        }
+    }
+}
+
+impl<C> ZksNamespaceClientExt for C where C: ZksNamespaceClient + ForWeb3Network<Net = L2> + ?Sized {}
+
+/// Builder for `debug_traceBlockByNumber` and `debug_traceBlockByHash` calls.
+#[derive(Debug)]
+#[must_use = "requests must be sent using `send()`"]
+pub struct TraceBlockRequest<'a, C: ?Sized> {
+    client: &'a C,
+    block: BlockId,
+    tracer: Option<TracerConfig>,
+}
+
+impl<C> TraceBlockRequest<'_, C>
+where
+    C: DebugNamespaceClient + ForWeb3Network<Net = L2> + ?Sized,
+{
+    /// Sets the tracer configuration. By default, the call tracer is used.
+    pub fn with_tracer(mut self, tracer: TracerConfig) -> Self {
+        self.tracer = Some(tracer);
+        self
+    }
+
+    /// Sends the request, choosing the method based on the block ID.
+    pub async fn send(self) -> Result<CallTracerBlockResult, ClientError> {
+        match self.block {
+            BlockId::Number(number) => self.client.trace_block_by_number(number, self.tracer).await,
+            BlockId::Hash(hash) => self.client.trace_block_by_hash(hash, self.tracer).await,
+        }
+    }
+}
+
+/// Builder for `debug_traceCall` calls.
+#[derive(Debug)]
+#[must_use = "requests must be sent using `send()`"]
+pub struct TraceCallRequest<'a, C: ?Sized> {
+    client: &'a C,
+    request: CallRequest,
+    block: Option<BlockId>,
+    tracer: Option<TracerConfig>,
+    fee_input_override: Option<FeeInputOverride>,
+}
+
+impl<C> TraceCallRequest<'_, C>
+where
+    C: DebugNamespaceClient + ForWeb3Network<Net = L2> + ?Sized,
+{
+    /// Sets the block to execute the call on. By default, the pending block is used.
+    pub fn at_block(mut self, block: BlockId) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Sets the tracer configuration. By default, the call tracer is used.
+    pub fn with_tracer(mut self, tracer: TracerConfig) -> Self {
+        self.tracer = Some(tracer);
+        self
+    }
+
+    /// Sets the batch fee input override applied when executing the call.
+    pub fn with_fee_input_override(mut self, fee_input_override: FeeInputOverride) -> Self {
+        self.fee_input_override = Some(fee_input_override);
+        self
+    }
+
+    /// Sends the request.
+    pub async fn send(self) -> Result<CallTracerResult, ClientError> {
+        self.client
+            .trace_call(
+                self.request,
+                self.block,
+                self.tracer,
+                self.fee_input_override,
+            )
+            .await
+    }
+}
+
+/// Builder-style helpers for `debug_` methods.
+pub trait DebugNamespaceClientExt: DebugNamespaceClient + ForWeb3Network<Net = L2> {
+    /// Starts building a request to trace all transactions in the specified block.
+    fn trace_block(&self, block: BlockId) -> TraceBlockRequest<'_, Self> {
+        TraceBlockRequest {
+            client: self,
+            block,
+            tracer: None,
+        }
+    }
+
+    /// Starts building a request to trace the specified call.
+    fn trace_call_request(&self, request: CallRequest) -> TraceCallRequest<'_, Self> {
+        TraceCallRequest {
+            client: self,
+            request,
+            block: None,
+            tracer: None,
+            fee_input_override: None,
+        }
+    }
+}
+
+impl<C> DebugNamespaceClientExt for C where
+    C: DebugNamespaceClient + ForWeb3Network<Net = L2> + ?Sized
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use zksync_types::{api, H256};
+
+    use super::*;
+    use crate::client::{DynClient, MockClient};
+
+    #[tokio::test]
+    async fn trace_block_request_chooses_method() {
+        let client = MockClient::builder(L2::default())
+            .method(
+                "debug_traceBlockByNumber",
+                |number: api::BlockNumber, tracer: Option<TracerConfig>| {
+                    assert_eq!(number, api::BlockNumber::Latest);
+                    assert!(tracer.is_none());
+                    Ok(CallTracerBlockResult::CallTrace(vec![]))
+                },
+            )
+            .method(
+                "debug_traceBlockByHash",
+                |hash: H256, tracer: Option<TracerConfig>| {
+                    assert_eq!(hash, H256::repeat_byte(1));
+                    let tracer = tracer.unwrap();
+                    assert_matches!(tracer.tracer, api::SupportedTracers::FlatCallTracer);
+                    assert!(tracer.tracer_config.only_top_call);
+                    Ok(CallTracerBlockResult::FlatCallTrace(vec![]))
+                },
+            )
+            .build();
+        let client = Box::new(client) as Box<DynClient<L2>>;
+
+        let traces = client
+            .trace_block(BlockId::Number(api::BlockNumber::Latest))
+            .send()
+            .await
+            .unwrap();
+        assert!(traces.unwrap_default().is_empty());
+
+        let traces = client
+            .trace_block(BlockId::Hash(H256::repeat_byte(1)))
+            .with_tracer(TracerConfig::flat_call_tracer().only_top_call())
+            .send()
+            .await
+            .unwrap();
+        assert!(traces.unwrap_flat().is_empty());
+    }
+
+    #[tokio::test]
+    async fn trace_call_request_sends_optional_params() {
+        let client = MockClient::builder(L2::default())
+            .method(
+                "debug_traceCall",
+                |_: CallRequest,
+                 block: Option<BlockId>,
+                 tracer: Option<TracerConfig>,
+                 fee_input_override: Option<FeeInputOverride>| {
+                    assert_eq!(block, Some(BlockId::Hash(H256::repeat_byte(2))));
+                    assert!(tracer.is_none());
+                    assert!(fee_input_override.is_none());
+                    Ok(CallTracerResult::CallTrace(api::DebugCall::default()))
+                },
+            )
+            .build();
+
+        let trace = client
+            .trace_call_request(CallRequest::default())
+            .at_block(BlockId::Hash(H256::repeat_byte(2)))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(trace.unwrap_default(), api::DebugCall::default());
+    }
+
+    #[tokio::test]
+    async fn top_contracts_request_sends_optional_params() {
+        let day = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let client = MockClient::builder(L2::default())
+            .method(
+                "zks_getTopContracts",
+                move |req_day: Option<NaiveDate>,
+                      metric: Option<ContractUsageMetric>,
+                      limit: Option<usize>| {
+                    assert_eq!(req_day, Some(day));
+                    assert_eq!(metric, None);
+                    assert_eq!(limit, Some(5));
+                    Ok(Vec::<ContractUsage>::new())
+                },
+            )
+            .build();
+
+        let contracts = client
+            .top_contracts_request()
+            .for_day(day)
+            .with_limit(5)
+            .send()
+            .await
+            .unwrap();
+        assert!(contracts.is_empty());
+    }
+}
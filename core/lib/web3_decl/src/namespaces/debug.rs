@@ -2,7 +2,10 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
-    api::{BlockId, BlockNumber, CallTracerBlockResult, CallTracerResult, TracerConfig},
+    api::{
+        BlockId, BlockNumber, CallTracerBlockResult, CallTracerResult, FeeInputOverride,
+        TracerConfig,
+    },
     transaction_request::CallRequest,
 };
 
@@ -40,6 +43,7 @@ pub trait DebugNamespace {
         request: CallRequest,
         block: Option<BlockId>,
         options: Option<TracerConfig>,
+        fee_input_override: Option<FeeInputOverride>,
     ) -> RpcResult<CallTracerResult>;
 
     #[method(name = "traceTransaction")]
@@ -4,7 +4,7 @@ use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
         state_override::StateOverride, BlockId, BlockIdVariant, BlockNumber, FeeHistory,
-        Transaction, TransactionVariant,
+        FeeInputOverride, Transaction, TransactionVariant,
     },
     transaction_request::CallRequest,
     Address, H256,
@@ -47,6 +47,7 @@ pub trait EthNamespace {
         req: CallRequest,
         _block: Option<BlockNumber>,
         state_override: Option<StateOverride>,
+        fee_input_override: Option<FeeInputOverride>,
     ) -> RpcResult<U256>;
 
     #[method(name = "gasPrice")]
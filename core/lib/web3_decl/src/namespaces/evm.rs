@@ -0,0 +1,35 @@
+#[cfg_attr(not(feature = "server"), allow(unused_imports))]
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use zksync_types::U64;
+
+use crate::client::{ForWeb3Network, L2};
+
+/// Methods compatible with popular development nodes allowing to control block production. Only available
+/// if the state keeper runs in the development mode.
+#[cfg_attr(
+    feature = "server",
+    rpc(server, client, namespace = "evm", client_bounds(Self: ForWeb3Network<Net = L2>))
+)]
+#[cfg_attr(
+    not(feature = "server"),
+    rpc(client, namespace = "evm", client_bounds(Self: ForWeb3Network<Net = L2>))
+)]
+pub trait EvmNamespace {
+    /// Seals the pending L1 batch (which produces a new L2 block) and returns the number of the last sealed L2 block.
+    #[method(name = "mine")]
+    async fn mine(&self) -> RpcResult<U64>;
+
+    /// Shifts timestamps of the following blocks by the specified number of seconds. Returns the total time offset.
+    #[method(name = "increaseTime")]
+    async fn increase_time(&self, seconds: u64) -> RpcResult<u64>;
+
+    /// Takes a snapshot of the chain state and returns its ID.
+    #[method(name = "snapshot")]
+    async fn snapshot(&self) -> RpcResult<U64>;
+
+    /// Reverts the chain state to the snapshot with the specified ID. Returns `false` if the snapshot is unknown
+    /// or cannot be reverted to (e.g., because the L1 batch it was taken in is sealed).
+    #[method(name = "revert")]
+    async fn revert(&self, snapshot_id: U64) -> RpcResult<bool>;
+}
@@ -0,0 +1,31 @@
+#[cfg_attr(not(feature = "server"), allow(unused_imports))]
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use zksync_types::{api::L2ToL1MessageStatus, L1BatchNumber, H256};
+
+use crate::client::{ForWeb3Network, L2};
+
+/// Introspection of the outgoing L2-to-L1 message queue, i.e. messages sent via the `L1Messenger` system contract.
+#[cfg_attr(
+    feature = "server",
+    rpc(server, client, namespace = "interop", client_bounds(Self: ForWeb3Network<Net = L2>))
+)]
+#[cfg_attr(
+    not(feature = "server"),
+    rpc(client, namespace = "interop", client_bounds(Self: ForWeb3Network<Net = L2>))
+)]
+pub trait InteropNamespace {
+    /// Returns all L2-to-L1 messages sent by the specified transaction.
+    #[method(name = "getL2ToL1MessagesByTxHash")]
+    async fn get_l2_to_l1_messages_by_tx_hash(
+        &self,
+        tx_hash: H256,
+    ) -> RpcResult<Vec<L2ToL1MessageStatus>>;
+
+    /// Returns all L2-to-L1 messages in the specified L1 batch ordered by their index in the L2-to-L1 logs Merkle tree.
+    #[method(name = "getL2ToL1MessagesByL1Batch")]
+    async fn get_l2_to_l1_messages_by_l1_batch(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Vec<L2ToL1MessageStatus>>;
+}
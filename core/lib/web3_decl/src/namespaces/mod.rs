@@ -1,18 +1,35 @@
 pub use self::{
-    debug::DebugNamespaceClient, en::EnNamespaceClient, eth::EthNamespaceClient,
-    net::NetNamespaceClient, snapshots::SnapshotsNamespaceClient,
-    unstable::UnstableNamespaceClient, web3::Web3NamespaceClient, zks::ZksNamespaceClient,
+    admin::AdminNamespaceClient,
+    builders::{
+        DebugNamespaceClientExt, EstimateFeeRequest, TopContractsRequest, TraceBlockRequest,
+        TraceCallRequest, ZksNamespaceClientExt,
+    },
+    debug::DebugNamespaceClient,
+    en::EnNamespaceClient,
+    eth::EthNamespaceClient,
+    evm::EvmNamespaceClient,
+    interop::InteropNamespaceClient,
+    net::NetNamespaceClient,
+    snapshots::SnapshotsNamespaceClient,
+    unstable::UnstableNamespaceClient,
+    web3::Web3NamespaceClient,
+    zks::ZksNamespaceClient,
 };
 #[cfg(feature = "server")]
 pub use self::{
-    debug::DebugNamespaceServer, en::EnNamespaceServer, eth::EthNamespaceServer,
-    eth::EthPubSubServer, net::NetNamespaceServer, snapshots::SnapshotsNamespaceServer,
+    admin::AdminNamespaceServer, debug::DebugNamespaceServer, en::EnNamespaceServer,
+    eth::EthNamespaceServer, eth::EthPubSubServer, evm::EvmNamespaceServer,
+    interop::InteropNamespaceServer, net::NetNamespaceServer, snapshots::SnapshotsNamespaceServer,
     unstable::UnstableNamespaceServer, web3::Web3NamespaceServer, zks::ZksNamespaceServer,
 };
 
+mod admin;
+mod builders;
 mod debug;
 mod en;
 mod eth;
+mod evm;
+mod interop;
 mod net;
 mod snapshots;
 mod unstable;
@@ -2,7 +2,7 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
-    snapshots::{AllSnapshots, SnapshotHeader},
+    snapshots::{AllSnapshots, SnapshotHeader, SnapshotManifest},
     L1BatchNumber,
 };
 
@@ -20,9 +20,19 @@ pub trait SnapshotsNamespace {
     #[method(name = "getAllSnapshots")]
     async fn get_all_snapshots(&self) -> RpcResult<AllSnapshots>;
 
+    /// Returns a full snapshot for the specified L1 batch. Differential snapshots are not returned.
     #[method(name = "getSnapshot")]
     async fn get_snapshot_by_l1_batch_number(
         &self,
         l1_batch_number: L1BatchNumber,
     ) -> RpcResult<Option<SnapshotHeader>>;
+
+    /// Returns a chain of snapshots (a full snapshot followed by zero or more differential snapshots) allowing
+    /// to recover storage at the specified L1 batch. If the L1 batch is not specified, returns the manifest
+    /// for the newest complete snapshot.
+    #[method(name = "getSnapshotManifest")]
+    async fn get_snapshot_manifest(
+        &self,
+        l1_batch_number: Option<L1BatchNumber>,
+    ) -> RpcResult<Option<SnapshotManifest>>;
 }
@@ -3,7 +3,8 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
-        ChainAggProof, DataAvailabilityDetails, L1ToL2TxsStatus, TeeProof, TransactionExecutionInfo,
+        ChainAggProof, DataAvailabilityDetails, L1ToL2TxsStatus, ProtocolUpgradeDryRun, TeeProof,
+        TransactionExecutionInfo,
     },
     tee_types::TeeType,
     L1BatchNumber, L2ChainId, H256,
@@ -55,4 +56,12 @@ pub trait UnstableNamespace {
 
     #[method(name = "l1ToL2TxsStatus")]
     async fn l1_to_l2_txs_status(&self) -> RpcResult<L1ToL2TxsStatus>;
+
+    /// Returns the result of dry-running the upgrade transaction for the specified protocol version, or `null`
+    /// if the upgrade wasn't dry-run (e.g., because it was activated before it could be processed).
+    #[method(name = "getProtocolUpgradeDryRun")]
+    async fn get_protocol_upgrade_dry_run(
+        &self,
+        version_id: u16,
+    ) -> RpcResult<Option<ProtocolUpgradeDryRun>>;
 }
@@ -1,12 +1,16 @@
 use std::collections::HashMap;
 
+use chrono::NaiveDate;
 #[cfg_attr(not(feature = "server"), allow(unused_imports))]
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
-        state_override::StateOverride, BlockDetails, BridgeAddresses, L1BatchDetails,
-        L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetailedResult, TransactionDetails,
+        state_override::StateOverride, AccountActivity, AccountNonceDetails, BlockDetails,
+        BlockIdVariant, BridgeAddresses, ContractUsage, ContractUsageMetric, DisassembledBytecode,
+        FeeInputOverride, FeeWithSensitivity, InclusionStats, L1BatchDetails, L2ToL1LogProof,
+        MultiProof, Proof, ProtocolVersion, ProtocolVersionActivation, SoftConfirmation,
+        StorageSlotWriter, TransactionDetailedResult, TransactionDetails, TransactionReceipt,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -33,8 +37,24 @@ pub trait ZksNamespace {
         &self,
         req: CallRequest,
         state_override: Option<StateOverride>,
+        fee_input_override: Option<FeeInputOverride>,
     ) -> RpcResult<Fee>;
 
+    /// Same as `zks_estimateFee`, but additionally returns gas limits required by the transaction
+    /// for a range of gas per pubdata byte prices, and usage of precompiles by the transaction.
+    ///
+    /// If the transaction has no signature, a mock ECDSA signature is used during estimation. Custom accounts
+    /// verifying signatures of another kind (e.g., passkey-based accounts using the `P256Verify` precompile) should
+    /// provide a properly formatted dummy signature; precompile usage allows checking that the validation logic
+    /// was fully exercised.
+    #[method(name = "estimateFeeWithSensitivity")]
+    async fn estimate_fee_with_sensitivity(
+        &self,
+        req: CallRequest,
+        state_override: Option<StateOverride>,
+        fee_input_override: Option<FeeInputOverride>,
+    ) -> RpcResult<FeeWithSensitivity>;
+
     #[method(name = "estimateGasL1ToL2")]
     async fn estimate_gas_l1_to_l2(
         &self,
@@ -42,6 +62,16 @@ pub trait ZksNamespace {
         state_override: Option<StateOverride>,
     ) -> RpcResult<U256>;
 
+    /// Simulates execution of an L1 -> L2 transaction on top of the pending block in the same way as the state keeper
+    /// would execute it, and returns the would-be L2 receipt. The request is interpreted in the same way
+    /// as for `zks_estimateGasL1ToL2`; `from` is the L1 sender (aliased if it's a contract).
+    #[method(name = "simulateL1ToL2Transaction")]
+    async fn simulate_l1_to_l2_transaction(
+        &self,
+        req: CallRequest,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<TransactionReceipt>;
+
     #[method(name = "getBridgehubContract")]
     async fn get_bridgehub_contract(&self) -> RpcResult<Option<Address>>;
 
@@ -117,6 +147,21 @@ pub trait ZksNamespace {
     #[method(name = "getBytecodeByHash")]
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>>;
 
+    /// Returns the latest write to the specified storage slot at or before the specified block (by default,
+    /// the pending one). The writing transaction is only returned if the node records storage slot writers.
+    #[method(name = "getStorageSlotWriter")]
+    async fn get_storage_slot_writer(
+        &self,
+        address: Address,
+        key: H256,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<Option<StorageSlotWriter>>;
+
+    /// Disassembles an EraVM bytecode with the specified hash into instructions split into basic blocks.
+    /// Returns `null` if the bytecode is unknown or is not an EraVM bytecode.
+    #[method(name = "disassembleBytecode")]
+    async fn disassemble_bytecode(&self, hash: H256) -> RpcResult<Option<DisassembledBytecode>>;
+
     #[method(name = "getL1GasPrice")]
     async fn get_l1_gas_price(&self) -> RpcResult<U64>;
 
@@ -129,6 +174,11 @@ pub trait ZksNamespace {
         version_id: Option<u16>,
     ) -> RpcResult<Option<ProtocolVersion>>;
 
+    /// Returns all protocol versions known to the node in the ascending order, together with
+    /// their activation L1 batches, base system contracts and verifier keys.
+    #[method(name = "getProtocolVersions")]
+    async fn get_protocol_versions(&self) -> RpcResult<Vec<ProtocolVersionActivation>>;
+
     #[method(name = "getProof")]
     async fn get_proof(
         &self,
@@ -137,6 +187,14 @@ pub trait ZksNamespace {
         l1_batch_number: L1BatchNumber,
     ) -> RpcResult<Option<Proof>>;
 
+    #[method(name = "getMultiProof")]
+    async fn get_multi_proof(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<MultiProof>>;
+
     #[method(name = "getBatchFeeInput")]
     async fn get_batch_fee_input(&self) -> RpcResult<PubdataIndependentBatchFeeModelInput>;
 
@@ -145,4 +203,29 @@ pub trait ZksNamespace {
         &self,
         tx_bytes: Bytes,
     ) -> RpcResult<TransactionDetailedResult>;
+
+    #[method(name = "getSoftConfirmation")]
+    async fn get_soft_confirmation(&self, tx_hash: H256) -> RpcResult<Option<SoftConfirmation>>;
+
+    #[method(name = "getAccountActivity")]
+    async fn get_account_activity(
+        &self,
+        address: Address,
+        from_block: L2BlockNumber,
+        to_block: L2BlockNumber,
+    ) -> RpcResult<AccountActivity>;
+
+    #[method(name = "getAccountNonceDetails")]
+    async fn get_account_nonce_details(&self, address: Address) -> RpcResult<AccountNonceDetails>;
+
+    #[method(name = "getInclusionStats")]
+    async fn get_inclusion_stats(&self) -> RpcResult<Vec<InclusionStats>>;
+
+    #[method(name = "getTopContracts")]
+    async fn get_top_contracts(
+        &self,
+        day: Option<NaiveDate>,
+        metric: Option<ContractUsageMetric>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<ContractUsage>>;
 }
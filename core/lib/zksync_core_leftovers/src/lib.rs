@@ -62,6 +62,14 @@ pub enum Component {
     ExternalProofIntegrationApi,
     /// VM runner-based component that allows to test experimental VM features. Doesn't save any data to Postgres.
     VmPlayground,
+    /// VM runner-based component that dry-runs protocol upgrade transactions before the upgrades are activated.
+    ProtocolUpgradeDryRun,
+    /// Component removing old call traces and events from Postgres according to retention policies.
+    DataRetention,
+    /// Component moving old call traces and events from Postgres to an object store.
+    ColdStorage,
+    /// Component exporting artifacts of sealed L1 batches to an external sink, e.g. for analytics.
+    BatchExporter,
 }
 
 #[derive(Debug)]
@@ -104,9 +112,13 @@ impl FromStr for Components {
             }
             "vm_runner_bwip" => Ok(Components(vec![Component::VmRunnerBwip])),
             "vm_playground" => Ok(Components(vec![Component::VmPlayground])),
+            "protocol_upgrade_dry_run" => Ok(Components(vec![Component::ProtocolUpgradeDryRun])),
             "external_proof_integration_api" => {
                 Ok(Components(vec![Component::ExternalProofIntegrationApi]))
             }
+            "data_retention" => Ok(Components(vec![Component::DataRetention])),
+            "cold_storage" => Ok(Components(vec![Component::ColdStorage])),
+            "batch_exporter" => Ok(Components(vec![Component::BatchExporter])),
             other => Err(format!("{} is not a valid component name", other)),
         }
     }
@@ -10,12 +10,15 @@ use zksync_config::{
         },
         house_keeper::HouseKeeperConfig,
         vm_runner::BasicWitnessInputProducerConfig,
-        wallets::{AddressWallet, EthSender, StateKeeper, TokenMultiplierSetter, Wallet, Wallets},
-        CommitmentGeneratorConfig, DatabaseSecrets, ExperimentalVmConfig,
+        wallets::{
+            AddressWallet, EthSender, SoftConfirmationSigner, StateKeeper, TokenMultiplierSetter,
+            Wallet, Wallets,
+        },
+        BatchExporterConfig, CommitmentGeneratorConfig, DatabaseSecrets, ExperimentalVmConfig,
         ExternalPriceApiClientConfig, FriProofCompressorConfig, FriProverConfig,
         FriProverGatewayConfig, FriWitnessGeneratorConfig, GeneralConfig, ObservabilityConfig,
         PrometheusConfig, ProofDataHandlerConfig, ProtectiveReadsWriterConfig,
-        ProverJobMonitorConfig, PruningConfig, SnapshotRecoveryConfig,
+        ProverJobMonitorConfig, PruningConfig, SnapshotRecoveryConfig, TxPolicyConfig,
     },
     ApiConfig, BaseTokenAdjusterConfig, ContractVerifierConfig, DAClientConfig, DADispatcherConfig,
     DBConfig, EthConfig, EthWatchConfig, ExternalProofIntegrationApiConfig, GasAdjusterConfig,
@@ -79,6 +82,9 @@ pub struct TempConfigStore {
     pub experimental_vm_config: Option<ExperimentalVmConfig>,
     pub prover_job_monitor_config: Option<ProverJobMonitorConfig>,
     pub timestamp_asserter_config: Option<TimestampAsserterConfig>,
+    pub tx_policy_config: Option<TxPolicyConfig>,
+    pub batch_exporter_config: Option<BatchExporterConfig>,
+    pub soft_confirmation_signer: Option<SoftConfirmationSigner>,
 }
 
 impl TempConfigStore {
@@ -119,6 +125,8 @@ impl TempConfigStore {
             experimental_vm_config: self.experimental_vm_config.clone(),
             prover_job_monitor_config: self.prover_job_monitor_config.clone(),
             timestamp_asserter_config: self.timestamp_asserter_config.clone(),
+            tx_policy_config: self.tx_policy_config.clone(),
+            batch_exporter_config: self.batch_exporter_config.clone(),
         }
     }
 
@@ -134,6 +142,7 @@ impl TempConfigStore {
             Some(EthSender {
                 operator,
                 blob_operator,
+                additional_operators: vec![],
             })
         });
         let state_keeper = self
@@ -155,6 +164,7 @@ impl TempConfigStore {
             eth_sender,
             state_keeper,
             token_multiplier_setter,
+            soft_confirmation_signer: self.soft_confirmation_signer.clone(),
         }
     }
 }
@@ -198,6 +208,11 @@ fn load_env_config() -> anyhow::Result<TempConfigStore> {
         experimental_vm_config: ExperimentalVmConfig::from_env().ok(),
         prover_job_monitor_config: ProverJobMonitorConfig::from_env().ok(),
         timestamp_asserter_config: TimestampAsserterConfig::from_env().ok(),
+        tx_policy_config: TxPolicyConfig::from_env().ok(),
+        batch_exporter_config: BatchExporterConfig::from_env().ok(),
+        soft_confirmation_signer: Wallets::from_env()
+            .ok()
+            .and_then(|wallets| wallets.soft_confirmation_signer),
     })
 }
 
@@ -223,3 +238,18 @@ pub fn load_database_secrets(path: Option<PathBuf>) -> anyhow::Result<DatabaseSe
         None => DatabaseSecrets::from_env(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wallets_include_soft_confirmation_signer() {
+        let signer = Wallets::for_tests().soft_confirmation_signer.unwrap();
+        let store = TempConfigStore {
+            soft_confirmation_signer: Some(signer.clone()),
+            ..TempConfigStore::default()
+        };
+        assert_eq!(store.wallets().soft_confirmation_signer, Some(signer));
+    }
+}
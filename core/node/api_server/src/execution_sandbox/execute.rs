@@ -27,19 +27,24 @@ use zksync_multivm::{
 use zksync_object_store::{Bucket, ObjectStore};
 use zksync_state::{PostgresStorage, PostgresStorageCaches};
 use zksync_types::{
-    api::state_override::StateOverride, fee_model::BatchFeeInput, l2::L2Tx, vm::FastVmMode,
-    StorageLog, Transaction,
+    api::state_override::StateOverride, fee_model::BatchFeeInput, l1::L1Tx, l2::L2Tx,
+    l2_to_l1_log::UserL2ToL1Log, vm::FastVmMode, StorageLog, Transaction,
 };
 use zksync_vm_executor::oneshot::{MainOneshotExecutor, MockOneshotExecutor};
 
 use super::{vm_metrics::SandboxStage, BlockArgs, VmPermit, SANDBOX_METRICS};
-use crate::{execution_sandbox::storage::apply_state_override, tx_sender::SandboxExecutorOptions};
+use crate::{
+    execution_sandbox::storage::{apply_pending_state, apply_state_override},
+    tx_sender::SandboxExecutorOptions,
+};
 
 /// Action that can be executed by [`SandboxExecutor`].
 #[derive(Debug)]
 pub(crate) enum SandboxAction {
     /// Execute a transaction.
     Execution { tx: L2Tx, fee_input: BatchFeeInput },
+    /// Execute an L1 -> L2 transaction in the same way as the state keeper would do it.
+    L1TxExecution { tx: L1Tx, fee_input: BatchFeeInput },
     /// Execute a call, possibly with tracing.
     Call {
         call: L2Tx,
@@ -47,11 +52,12 @@ pub(crate) enum SandboxAction {
         enforced_base_fee: Option<u64>,
         tracing_params: OneshotTracingParams,
     },
-    /// Estimate gas for a transaction.
+    /// Estimate gas for a transaction, possibly with tracing.
     GasEstimation {
         tx: Transaction,
         fee_input: BatchFeeInput,
         base_fee: u64,
+        tracing_params: OneshotTracingParams,
     },
 }
 
@@ -62,10 +68,13 @@ impl SandboxAction {
                 TxExecutionArgs::for_validation(tx),
                 OneshotTracingParams::default(),
             ),
-            Self::GasEstimation { tx, .. } => (
-                TxExecutionArgs::for_gas_estimate(tx),
+            Self::L1TxExecution { tx, .. } => (
+                TxExecutionArgs::for_l1_tx(tx),
                 OneshotTracingParams::default(),
             ),
+            Self::GasEstimation {
+                tx, tracing_params, ..
+            } => (TxExecutionArgs::for_gas_estimate(tx), tracing_params),
             Self::Call {
                 call,
                 tracing_params,
@@ -84,6 +93,8 @@ pub struct SandboxExecutionOutput {
     pub write_logs: Vec<StorageLog>,
     /// Events produced by the VM.
     pub events: Vec<VmEvent>,
+    /// User L2 -> L1 logs produced by the VM.
+    pub l2_to_l1_logs: Vec<UserL2ToL1Log>,
     /// Traced calls if requested.
     pub call_traces: Vec<Call>,
     /// Execution metrics.
@@ -98,6 +109,7 @@ impl SandboxExecutionOutput {
             result: ExecutionResult::Success { output: Vec::new() },
             write_logs: Vec::new(),
             events: Vec::new(),
+            l2_to_l1_logs: Vec::new(),
             call_traces: Vec::new(),
             metrics: TransactionExecutionMetrics {
                 writes: DeduplicatedWritesMetrics::default(),
@@ -161,6 +173,7 @@ where
                 .filter_map(|log| log.log.is_write().then_some(log.log))
                 .collect(),
             events: tx_result.logs.events,
+            l2_to_l1_logs: tx_result.logs.user_l2_to_l1_logs,
             call_traces: result.call_traces,
             metrics,
             are_published_bytecodes_ok: result.compression_result.is_ok(),
@@ -187,6 +200,11 @@ impl SandboxExecutor {
     ) -> Self {
         let mut executor = MainOneshotExecutor::new(missed_storage_invocation_limit);
         executor.set_fast_vm_mode(options.fast_vm_mode);
+        executor.set_warm_pool_size(options.vm_warm_pool_size);
+        if let Some(limits) = options.vm_resource_limits {
+            executor.set_resource_limits(limits);
+        }
+        executor.set_frame_gas_cap(options.frame_gas_cap.clone());
 
         let vm_divergence_counter = Arc::<AtomicUsize>::default();
         if cfg!(test) {
@@ -302,13 +320,20 @@ impl SandboxExecutor {
             .prepare_env_and_storage(connection, block_args, &action)
             .await?;
 
+        let mut storage = StorageWithOverrides::new(storage);
+        if let Some(pending_state) = block_args.pending_state() {
+            apply_pending_state(&mut storage, pending_state);
+        }
         let storage = if let Some(state_override) = state_override {
-            tokio::task::spawn_blocking(|| apply_state_override(storage, state_override))
-                .await
-                .context("applying state override panicked")?
+            tokio::task::spawn_blocking(|| {
+                apply_state_override(&mut storage, state_override);
+                storage
+            })
+            .await
+            .context("applying state override panicked")?
         } else {
             // Do not spawn a new thread in the most frequent case.
-            StorageWithOverrides::new(storage)
+            storage
         };
 
         let (execution_args, tracing_params) = action.into_parts();
@@ -339,6 +364,12 @@ impl SandboxExecutor {
                     .to_execute_env(&mut connection, resolved_block_info, *fee_input, tx)
                     .await?
             }
+            &SandboxAction::L1TxExecution { fee_input, .. } => {
+                self.options
+                    .eth_call
+                    .to_l1_tx_execute_env(&mut connection, resolved_block_info, fee_input)
+                    .await?
+            }
             &SandboxAction::Call {
                 fee_input,
                 enforced_base_fee,
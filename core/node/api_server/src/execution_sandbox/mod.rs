@@ -1,5 +1,9 @@
 use std::{
-    sync::{Arc, RwLock},
+    cell::Cell,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, RwLock,
+    },
     time::{Duration, Instant},
 };
 
@@ -7,6 +11,7 @@ use anyhow::Context as _;
 use rand::{thread_rng, Rng};
 use zksync_dal::{pruning_dal::PruningInfo, Connection, Core, CoreDal, DalError};
 use zksync_multivm::utils::get_eth_call_gas_limit;
+use zksync_state_keeper::PendingBatchState;
 use zksync_types::{
     api, fee_model::BatchFeeInput, L1BatchNumber, L2BlockNumber, ProtocolVersionId, U256,
 };
@@ -17,7 +22,7 @@ pub(crate) use self::{
     error::SandboxExecutionError,
     execute::{SandboxAction, SandboxExecutionOutput, SandboxExecutor},
     validate::ValidationError,
-    vm_metrics::{SubmitTxStage, SANDBOX_METRICS},
+    vm_metrics::{MulticallFastPathOutcome, OverloadReason, SubmitTxStage, SANDBOX_METRICS},
 };
 
 // Note: keep the modules private, and instead re-export functions that make public interface.
@@ -92,6 +97,100 @@ impl VmConcurrencyBarrier {
 pub struct VmConcurrencyLimiter {
     /// Semaphore that limits the number of concurrent VM executions.
     limiter: Arc<tokio::sync::Semaphore>,
+    queue_latency: QueueLatencyTracker,
+}
+
+/// Tracks the exponential moving average of the latency of waiting for a VM permit.
+#[derive(Debug, Default)]
+struct QueueLatencyTracker {
+    waiting: AtomicUsize,
+    /// Average latency together with the time it was last updated.
+    average: Mutex<Option<(Duration, Instant)>>,
+}
+
+impl QueueLatencyTracker {
+    /// Weight of a new observation in the moving average.
+    const SMOOTHING_FACTOR: f64 = 0.2;
+    /// If no permits were acquired for this long and no one waits for a permit, the queue is considered empty.
+    const STALE_AFTER: Duration = Duration::from_secs(1);
+
+    fn observe(&self, latency: Duration) {
+        let mut average = self.average.lock().expect("VM queue latency is poisoned");
+        let new_average = match *average {
+            Some((prev, _)) => {
+                prev.mul_f64(1.0 - Self::SMOOTHING_FACTOR) + latency.mul_f64(Self::SMOOTHING_FACTOR)
+            }
+            None => latency,
+        };
+        *average = Some((new_average, Instant::now()));
+    }
+
+    fn get(&self) -> Duration {
+        let average = *self.average.lock().expect("VM queue latency is poisoned");
+        let Some((average, updated_at)) = average else {
+            return Duration::ZERO;
+        };
+        let is_stale = updated_at.elapsed() > Self::STALE_AFTER;
+        if is_stale && self.waiting.load(Ordering::Relaxed) == 0 {
+            Duration::ZERO
+        } else {
+            average
+        }
+    }
+}
+
+/// Time spent waiting for VM permits by a JSON-RPC method call.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct VmPermitWait {
+    /// Number of acquired permits.
+    pub permits: u32,
+    /// Total time spent waiting for the permits.
+    pub total: Duration,
+}
+
+thread_local! {
+    /// VM permit waits for the method call currently polled on this thread, if any.
+    static VM_PERMIT_WAIT: Cell<Option<VmPermitWait>> = const { Cell::new(None) };
+}
+
+impl VmPermitWait {
+    /// Starts tracking VM permit waits on the current thread, continuing from `self`. Returns the previously tracked value,
+    /// which must be passed to [`Self::exit()`].
+    pub(crate) fn enter(self) -> Option<Self> {
+        VM_PERMIT_WAIT.replace(Some(self))
+    }
+
+    /// Stops tracking VM permit waits on the current thread and returns the tracked value.
+    pub(crate) fn exit(prev: Option<Self>) -> Self {
+        VM_PERMIT_WAIT.replace(prev).unwrap_or_default()
+    }
+
+    fn observe(latency: Duration) {
+        VM_PERMIT_WAIT.with(|cell| {
+            if let Some(mut wait) = cell.get() {
+                wait.permits += 1;
+                wait.total += latency;
+                cell.set(Some(wait));
+            }
+        });
+    }
+}
+
+/// Guard tracking the number of tasks waiting for a VM permit.
+#[derive(Debug)]
+struct WaitingGuard<'a>(&'a AtomicUsize);
+
+impl<'a> WaitingGuard<'a> {
+    fn new(waiting: &'a AtomicUsize) -> Self {
+        waiting.fetch_add(1, Ordering::Relaxed);
+        Self(waiting)
+    }
+}
+
+impl Drop for WaitingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
 }
 
 impl VmConcurrencyLimiter {
@@ -104,6 +203,7 @@ impl VmConcurrencyLimiter {
 
         let this = Self {
             limiter: Arc::clone(&limiter),
+            queue_latency: QueueLatencyTracker::default(),
         };
         let barrier = VmConcurrencyBarrier {
             limiter,
@@ -121,8 +221,12 @@ impl VmConcurrencyLimiter {
             .observe(available_permits);
 
         let latency = SANDBOX_METRICS.sandbox[&SandboxStage::VmConcurrencyLimiterAcquire].start();
+        let waiting_guard = WaitingGuard::new(&self.queue_latency.waiting);
         let permit = Arc::clone(&self.limiter).acquire_owned().await.ok()?;
+        drop(waiting_guard);
         let elapsed = latency.observe();
+        self.queue_latency.observe(elapsed);
+        VmPermitWait::observe(elapsed);
         // We don't want to emit too many logs.
         if elapsed > Duration::from_millis(10) {
             tracing::debug!(
@@ -134,6 +238,11 @@ impl VmConcurrencyLimiter {
             _permit: Arc::new(permit),
         })
     }
+
+    /// Returns the moving average of the latency of waiting for a VM permit.
+    pub(crate) fn queue_latency(&self) -> Duration {
+        self.queue_latency.get()
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -290,6 +399,9 @@ pub(crate) struct BlockArgs {
     inner: BlockInfo,
     resolved: ResolvedBlockInfo,
     block_id: api::BlockId,
+    fee_input_override: Option<api::FeeInputOverride>,
+    /// Changes produced by unsealed transactions; only set for the pending block.
+    pending_state: Option<Arc<PendingBatchState>>,
 }
 
 impl BlockArgs {
@@ -300,6 +412,8 @@ impl BlockArgs {
             inner,
             resolved,
             block_id: api::BlockId::Number(api::BlockNumber::Pending),
+            fee_input_override: None,
+            pending_state: None,
         })
     }
 
@@ -342,14 +456,50 @@ impl BlockArgs {
             inner,
             resolved: inner.resolve(connection).await?,
             block_id,
+            fee_input_override: None,
+            pending_state: None,
         })
     }
 
+    /// Sets the override for the batch fee input used when executing transactions on top of this block.
+    pub fn with_fee_input_override(
+        mut self,
+        fee_input_override: Option<api::FeeInputOverride>,
+    ) -> Self {
+        self.fee_input_override = fee_input_override;
+        self
+    }
+
+    /// Sets changes produced by unsealed transactions, which will be applied on top of the persisted state
+    /// when executing transactions on top of this block. Should only be called for the pending block.
+    pub fn with_pending_state(mut self, pending_state: Option<Arc<PendingBatchState>>) -> Self {
+        debug_assert!(self.is_pending() || pending_state.is_none());
+        self.pending_state = pending_state;
+        self
+    }
+
+    pub(crate) fn pending_state(&self) -> Option<&PendingBatchState> {
+        self.pending_state.as_deref()
+    }
+
+    /// Applies the fee input override (if any) to the provided fee input.
+    pub fn override_fee_input(&self, fee_input: BatchFeeInput) -> BatchFeeInput {
+        match &self.fee_input_override {
+            Some(fee_input_override) => fee_input_override.apply(fee_input),
+            None => fee_input,
+        }
+    }
+
     pub fn resolved_block_number(&self) -> L2BlockNumber {
         self.inner.block_number()
     }
 
-    fn is_pending(&self) -> bool {
+    /// Returns the number of the L2 block whose state (as persisted in Postgres) is used for execution.
+    pub fn state_l2_block_number(&self) -> L2BlockNumber {
+        self.resolved.state_l2_block_number()
+    }
+
+    pub fn is_pending(&self) -> bool {
         matches!(
             self.block_id,
             api::BlockId::Number(api::BlockNumber::Pending)
@@ -1,22 +1,37 @@
 //! VM storage functionality specifically used in the VM sandbox.
 
 use zksync_multivm::interface::storage::{ReadStorage, StorageWithOverrides};
+use zksync_state_keeper::PendingBatchState;
 use zksync_types::{
     api::state_override::{BytecodeOverride, OverrideState, StateOverride},
     bytecode::{pad_evm_bytecode, BytecodeHash, BytecodeMarker},
     get_code_key, get_evm_code_hash_key, get_known_code_key, get_nonce_key, h256_to_u256,
     u256_to_h256,
     utils::{decompose_full_nonce, nonces_to_full_nonce, storage_key_for_eth_balance},
-    web3, AccountTreeId, StorageKey, H256,
+    web3, AccountTreeId, StorageKey, H256, SYSTEM_CONTEXT_ADDRESS,
 };
 
+/// Applies changes produced by unsealed transactions. Writes to the system context are skipped, so that
+/// the block environment provided to the VM remains consistent with the persisted state.
+pub(super) fn apply_pending_state<S: ReadStorage>(
+    storage: &mut StorageWithOverrides<S>,
+    pending_state: &PendingBatchState,
+) {
+    for (key, value) in pending_state.storage_writes() {
+        if *key.address() != SYSTEM_CONTEXT_ADDRESS {
+            storage.set_value(key, value);
+        }
+    }
+    for (hash, bytecode) in pending_state.factory_deps() {
+        storage.store_factory_dep(hash, bytecode.to_vec());
+    }
+}
+
 /// This method is blocking.
 pub(super) fn apply_state_override<S: ReadStorage>(
-    storage: S,
+    storage: &mut StorageWithOverrides<S>,
     state_override: StateOverride,
-) -> StorageWithOverrides<S> {
-    let mut storage = StorageWithOverrides::new(storage);
-
+) {
     for (account, overrides) in state_override {
         if let Some(balance) = overrides.balance {
             let balance_key = storage_key_for_eth_balance(&account);
@@ -73,7 +88,6 @@ pub(super) fn apply_state_override<S: ReadStorage>(
             None => { /* do nothing */ }
         }
     }
-    storage
 }
 
 #[cfg(test)]
@@ -139,7 +153,8 @@ mod tests {
         storage.set_value(retained_key, H256::repeat_byte(0xfe));
         let erased_key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(5)), H256::zero());
         storage.set_value(erased_key, H256::repeat_byte(1));
-        let mut storage = apply_state_override(storage, overrides);
+        let mut storage = StorageWithOverrides::new(storage);
+        apply_state_override(&mut storage, overrides);
 
         let balance = storage.read_value(&storage_key_for_eth_balance(&Address::repeat_byte(1)));
         assert_eq!(balance, H256::from_low_u64_be(1));
@@ -1,5 +1,6 @@
 use tokio::runtime::Handle;
 use zksync_dal::{Connection, Core, CoreDal};
+use zksync_multivm::interface::storage::StorageWithOverrides;
 use zksync_state::PostgresStorage;
 use zksync_types::{
     api::state_override::StateOverride, AccountTreeId, L2BlockNumber, StorageKey, StorageLog, H256,
@@ -21,10 +22,13 @@ pub(crate) async fn apply_state_overrides(
     let state = PostgresStorage::new_async(Handle::current(), connection, latest_block, false)
         .await
         .unwrap();
-    let state_with_overrides =
-        tokio::task::spawn_blocking(|| apply_state_override(state, state_override))
-            .await
-            .unwrap();
+    let state_with_overrides = tokio::task::spawn_blocking(|| {
+        let mut state = StorageWithOverrides::new(state);
+        apply_state_override(&mut state, state_override);
+        state
+    })
+    .await
+    .unwrap();
     let (state, overrides) = state_with_overrides.into_parts();
 
     let mut connection = state.into_inner();
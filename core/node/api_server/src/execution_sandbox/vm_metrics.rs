@@ -80,6 +80,26 @@ impl Drop for SubmitTxLatencyObserver<'_> {
     }
 }
 
+/// Outcome of the gas estimation fast path for Multicall3 transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "outcome", rename_all = "snake_case")]
+pub(crate) enum MulticallFastPathOutcome {
+    /// Optimistic gas limit was sufficient, so the binary search was skipped.
+    Accepted,
+    /// Optimistic gas limit was insufficient; fell back to the binary search.
+    FellBack,
+}
+
+/// Reason for rejecting a low-priority request because of overload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "reason", rename_all = "snake_case")]
+pub(crate) enum OverloadReason {
+    /// Average latency of waiting for a VM permit exceeds the threshold.
+    VmQueueLatency,
+    /// Utilization of the replica connection pool exceeds the threshold.
+    PoolUtilization,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
 #[metrics(rename_all = "snake_case")]
 enum OverrideKind {
@@ -146,6 +166,10 @@ pub(crate) struct SandboxMetrics {
     /// is (as expected) greater than the final gas estimate.
     #[metrics(buckets = Buckets::linear(-0.05..=0.15, 0.01))]
     pub estimate_gas_optimistic_gas_limit_relative_diff: Histogram<f64>,
+    /// Number of gas estimations for Multicall3 transactions that took the fast path, grouped by outcome.
+    pub estimate_gas_multicall_fast_path: Family<MulticallFastPathOutcome, Counter>,
+    /// Number of low-priority requests rejected because of overload, grouped by the overload reason.
+    pub shed_requests: Family<OverloadReason, Counter>,
     /// Statistics on state overrides.
     state_overrides: Family<StateOverrideLabels, Counter>,
     /// Statistics on bytecode kinds supplied in overrides.
@@ -2,7 +2,7 @@ use std::{net::SocketAddr, sync::Arc, time::Duration};
 
 use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
 use tokio::sync::watch;
-use zksync_health_check::{AppHealth, AppHealthCheck};
+use zksync_health_check::{AppHealth, AppHealthCheck, AppReadiness};
 
 async fn check_health(
     app_health_check: State<Arc<AppHealthCheck>>,
@@ -16,6 +16,32 @@ async fn check_health(
     (response_code, Json(response))
 }
 
+/// Readiness probe: the node is ready if all components are operational, possibly in a degraded mode.
+async fn check_readiness(
+    app_health_check: State<Arc<AppHealthCheck>>,
+) -> (StatusCode, Json<AppReadiness>) {
+    let readiness = app_health_check.check_health().await.readiness().clone();
+    let response_code = if readiness.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (response_code, Json(readiness))
+}
+
+/// Liveness probe: the node is live if none of its components are shut down or panicked.
+async fn check_liveness(
+    app_health_check: State<Arc<AppHealthCheck>>,
+) -> (StatusCode, Json<AppReadiness>) {
+    let readiness = app_health_check.check_health().await.readiness().clone();
+    let response_code = if readiness.is_live() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (response_code, Json(readiness))
+}
+
 async fn run_server(
     bind_address: &SocketAddr,
     app_health_check: Arc<AppHealthCheck>,
@@ -28,6 +54,8 @@ async fn run_server(
     app_health_check.expose_metrics();
     let app = Router::new()
         .route("/health", get(check_health))
+        .route("/health/ready", get(check_readiness))
+        .route("/health/live", get(check_liveness))
         .with_state(app_health_check);
     let listener = tokio::net::TcpListener::bind(bind_address)
         .await
@@ -3,20 +3,26 @@ use std::{ops, time::Instant};
 use anyhow::Context;
 use zksync_dal::CoreDal;
 use zksync_multivm::{
-    interface::{ExecutionResult, TransactionExecutionMetrics},
+    interface::{Call, ExecutionResult, OneshotTracingParams, TransactionExecutionMetrics},
     utils::{
         adjust_pubdata_price_for_tx, derive_base_fee_and_gas_per_pubdata, derive_overhead,
-        get_max_batch_gas_limit,
+        get_max_batch_gas_limit, get_max_gas_per_pubdata_byte,
     },
 };
-use zksync_system_constants::MAX_L2_TX_GAS_LIMIT;
+use zksync_system_constants::{MAX_L2_TX_GAS_LIMIT, PRECOMPILES};
 use zksync_types::{
-    api::state_override::StateOverride, fee::Fee, fee_model::BatchFeeInput, get_code_key,
-    ExecuteTransactionCommon, PackedEthSignature, ProtocolVersionId, Transaction, H256,
+    api::{self, state_override::StateOverride},
+    fee::Fee,
+    fee_model::BatchFeeInput,
+    get_code_key, ExecuteTransactionCommon, PackedEthSignature, ProtocolVersionId, Transaction,
+    H256, U256,
 };
 
-use super::{result::ApiCallResult, SubmitTxError, TxSender};
-use crate::execution_sandbox::{BlockArgs, SandboxAction, VmPermit, SANDBOX_METRICS};
+use super::{multicall::multicall3_call_count, result::ApiCallResult, SubmitTxError, TxSender};
+use crate::execution_sandbox::{
+    BlockArgs, MulticallFastPathOutcome, SandboxAction, SandboxExecutionOutput, VmPermit,
+    SANDBOX_METRICS,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum BinarySearchKind {
@@ -36,6 +42,10 @@ impl BinarySearchKind {
     }
 }
 
+/// Multipliers applied to the current gas per pubdata byte price to get prices for the sensitivity analysis.
+/// Must be sorted in the ascending order.
+const SENSITIVITY_GAS_PER_PUBDATA_MULTIPLIERS: [f64; 4] = [0.5, 1.0, 2.0, 4.0];
+
 impl TxSender {
     #[tracing::instrument(level = "debug", skip_all, fields(
         initiator = ?tx.initiator_account(),
@@ -50,6 +60,81 @@ impl TxSender {
         state_override: Option<StateOverride>,
         kind: BinarySearchKind,
     ) -> Result<Fee, SubmitTxError> {
+        let (estimator, unscaled_gas_limit) = self
+            .estimate_gas_limit(
+                tx,
+                block_args,
+                acceptable_overestimation,
+                state_override,
+                kind,
+            )
+            .await?;
+        let suggested_gas_limit = (unscaled_gas_limit as f64 * estimated_fee_scale_factor) as u64;
+        estimator
+            .finalize(suggested_gas_limit, estimated_fee_scale_factor)
+            .await
+    }
+
+    /// Same as [`Self::get_txs_fee_in_wei()`], but additionally estimates how the required gas limit depends
+    /// on the gas per pubdata byte price.
+    #[tracing::instrument(level = "debug", skip_all, fields(
+        initiator = ?tx.initiator_account(),
+        nonce = ?tx.nonce(),
+    ))]
+    pub(crate) async fn get_txs_fee_with_sensitivity(
+        &self,
+        tx: Transaction,
+        block_args: BlockArgs,
+        estimated_fee_scale_factor: f64,
+        acceptable_overestimation: u64,
+        state_override: Option<StateOverride>,
+        kind: BinarySearchKind,
+    ) -> Result<api::FeeWithSensitivity, SubmitTxError> {
+        let (estimator, unscaled_gas_limit) = self
+            .estimate_gas_limit(
+                tx,
+                block_args,
+                acceptable_overestimation,
+                state_override,
+                kind,
+            )
+            .await?;
+        let gas_per_pubdata_sensitivity = estimator
+            .gas_per_pubdata_sensitivity(
+                unscaled_gas_limit,
+                estimated_fee_scale_factor,
+                acceptable_overestimation,
+            )
+            .await?;
+        let suggested_gas_limit = (unscaled_gas_limit as f64 * estimated_fee_scale_factor) as u64;
+        // Trace calls in the final run to report precompile usage. This doesn't influence the estimate, but allows
+        // to check whether the transaction exercises the same precompiles as the real one (e.g., the `P256Verify`
+        // precompile for passkey-based accounts, which only call it if provided with a properly formatted signature).
+        let tracing_params = OneshotTracingParams { trace_calls: true };
+        let (fee, call_traces) = estimator
+            .finalize_with_traces(
+                suggested_gas_limit,
+                estimated_fee_scale_factor,
+                tracing_params,
+            )
+            .await?;
+        Ok(api::FeeWithSensitivity {
+            fee,
+            gas_per_pubdata_sensitivity,
+            precompile_usage: aggregate_precompile_usage(&call_traces),
+        })
+    }
+
+    /// Returns the minimum passing gas limit for the transaction without the operator overhead and scaling.
+    async fn estimate_gas_limit(
+        &self,
+        tx: Transaction,
+        block_args: BlockArgs,
+        acceptable_overestimation: u64,
+        state_override: Option<StateOverride>,
+        kind: BinarySearchKind,
+    ) -> Result<(GasEstimator<'_>, u64), SubmitTxError> {
+        self.shed_low_priority_load()?;
         let estimation_started_at = Instant::now();
         let mut estimator = GasEstimator::new(self, tx, block_args, state_override).await?;
         estimator.adjust_transaction_fee();
@@ -65,7 +150,7 @@ impl TxSender {
         // If the transaction succeeds, it will discard most of the search space at once.
         let optimistic_gas_limit = initial_estimate.optimistic_gas_limit_without_overhead();
 
-        let (bounds, initial_pivot) = match kind {
+        let (mut bounds, mut initial_pivot) = match kind {
             BinarySearchKind::Full => {
                 let lower_bound = initial_estimate.gas_charged_for_pubdata;
                 let upper_bound = MAX_L2_TX_GAS_LIMIT + initial_estimate.gas_charged_for_pubdata;
@@ -83,9 +168,41 @@ impl TxSender {
             }
         };
 
-        let (unscaled_gas_limit, iteration_count) =
-            Self::binary_search(&estimator, bounds, initial_pivot, acceptable_overestimation)
-                .await?;
+        // Multicall3 sub-calls are executed sequentially in a single VM run with shared (warm) state, so the initial estimate
+        // already aggregates their gas usage, and each sub-call only retains 1/64 of the remaining gas per the 63/64 rule,
+        // which is covered by the optimistic gas limit. Thus, a single check of the optimistic gas limit is sufficient
+        // in most cases, and allows to skip the binary search, which is expensive for large batches.
+        let mut fast_path_gas_limit = None;
+        let multicall_len = multicall3_call_count(&estimator.transaction.execute.calldata);
+        if let (BinarySearchKind::Optimized, Some(call_count), Some(gas_limit)) =
+            (kind, multicall_len, optimistic_gas_limit)
+        {
+            let gas_limit = gas_limit.clamp(*bounds.start(), *bounds.end());
+            let (result, _) = estimator.step(gas_limit).await?;
+            let outcome = if result.is_failed() {
+                bounds = (gas_limit + 1).min(*bounds.end())..=*bounds.end();
+                initial_pivot = None;
+                MulticallFastPathOutcome::FellBack
+            } else {
+                fast_path_gas_limit = Some(gas_limit);
+                MulticallFastPathOutcome::Accepted
+            };
+            tracing::debug!(
+                call_count,
+                gas_limit,
+                ?outcome,
+                "Used Multicall3 gas estimation fast path"
+            );
+            SANDBOX_METRICS.estimate_gas_multicall_fast_path[&outcome].inc();
+        }
+
+        let (unscaled_gas_limit, iteration_count) = match fast_path_gas_limit {
+            Some(gas_limit) => (gas_limit, 1),
+            None => {
+                Self::binary_search(&estimator, bounds, initial_pivot, acceptable_overestimation)
+                    .await?
+            }
+        };
         // Metrics are intentionally reported regardless of the binary search mode, so that the collected stats can be used to adjust
         // optimized binary search params (e.g., the initial pivot multiplier).
         if let Some(lower_bound) = optimized_lower_bound {
@@ -112,11 +229,7 @@ impl TxSender {
             iteration_count,
             "Finished estimating gas limit for transaction"
         );
-
-        let suggested_gas_limit = (unscaled_gas_limit as f64 * estimated_fee_scale_factor) as u64;
-        estimator
-            .finalize(suggested_gas_limit, estimated_fee_scale_factor)
-            .await
+        Ok((estimator, unscaled_gas_limit))
     }
 
     async fn binary_search(
@@ -290,7 +403,7 @@ impl InitialGasEstimate {
 /// Encapsulates gas estimation process for a specific transaction.
 ///
 /// Public for testing purposes.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(super) struct GasEstimator<'a> {
     sender: &'a TxSender,
     transaction: Transaction,
@@ -314,8 +427,9 @@ impl<'a> GasEstimator<'a> {
         let protocol_version = block_args.protocol_version();
 
         let max_gas_limit = get_max_batch_gas_limit(protocol_version.into());
+        let fee_input = block_args.override_fee_input(sender.scaled_batch_fee_input().await?);
         let fee_input = adjust_pubdata_price_for_tx(
-            sender.scaled_batch_fee_input().await?,
+            fee_input,
             transaction.gas_per_pubdata_byte_limit(),
             // We do not have to adjust the params to the `gasPrice` of the transaction, since
             // its gas price will be amended later on to suit the `fee_input`
@@ -369,6 +483,97 @@ impl<'a> GasEstimator<'a> {
         }
     }
 
+    /// Returns a copy of this estimator with the batch fee input adjusted to the specified gas per pubdata byte price.
+    fn with_gas_per_pubdata(&self, gas_per_pubdata: u64) -> Self {
+        let mut fee_input = self.fee_input.into_pubdata_independent();
+        fee_input.fair_pubdata_price = gas_per_pubdata * self.base_fee;
+        let fee_input = BatchFeeInput::PubdataIndependent(fee_input);
+        let (base_fee, gas_per_pubdata_byte) =
+            derive_base_fee_and_gas_per_pubdata(fee_input, self.protocol_version.into());
+
+        let mut this = Self {
+            fee_input,
+            base_fee,
+            gas_per_pubdata_byte,
+            ..self.clone()
+        };
+        this.adjust_transaction_fee();
+        this
+    }
+
+    /// Estimates gas limits required by the transaction for a range of gas per pubdata byte prices.
+    ///
+    /// Pubdata published by a transaction doesn't depend on the pubdata price, so the gas limit for each price is predicted
+    /// by re-pricing pubdata and is then checked with a single VM run in the common case. A binary search is only performed
+    /// if the predicted limit is insufficient.
+    async fn gas_per_pubdata_sensitivity(
+        &self,
+        unscaled_gas_limit: u64,
+        estimated_fee_scale_factor: f64,
+        acceptable_overestimation: u64,
+    ) -> Result<api::GasPerPubdataSensitivity, SubmitTxError> {
+        let (result, metrics) = self.step(unscaled_gas_limit).await?;
+        result.check_api_call_result()?;
+        let pubdata_published = metrics.vm.pubdata_published;
+        let gas_without_pubdata = unscaled_gas_limit
+            .saturating_sub(u64::from(pubdata_published) * self.gas_per_pubdata_byte);
+
+        // Prices higher than the transaction limit cannot be used with the transaction.
+        let max_gas_per_pubdata = self
+            .transaction
+            .gas_per_pubdata_byte_limit()
+            .min(get_max_gas_per_pubdata_byte(self.protocol_version.into()).into())
+            .as_u64();
+        let mut prices: Vec<_> = SENSITIVITY_GAS_PER_PUBDATA_MULTIPLIERS
+            .iter()
+            .map(|&multiplier| (self.gas_per_pubdata_byte as f64 * multiplier) as u64)
+            .filter(|&price| price <= max_gas_per_pubdata)
+            .collect();
+        prices.dedup();
+
+        let mut points = Vec::with_capacity(prices.len());
+        for price in prices {
+            let (estimator, unscaled_gas_limit) = if price == self.gas_per_pubdata_byte {
+                (self.clone(), unscaled_gas_limit)
+            } else {
+                let estimator = self.with_gas_per_pubdata(price);
+                let gas_for_pubdata = u64::from(pubdata_published) * estimator.gas_per_pubdata_byte;
+                let predicted_gas_limit = gas_without_pubdata + gas_for_pubdata;
+                let (result, _) = estimator.step(predicted_gas_limit).await?;
+                let gas_limit = if result.is_failed() {
+                    let upper_bound = MAX_L2_TX_GAS_LIMIT + gas_for_pubdata;
+                    let lower_bound = (predicted_gas_limit + 1).min(upper_bound);
+                    TxSender::binary_search(
+                        &estimator,
+                        lower_bound..=upper_bound,
+                        None,
+                        acceptable_overestimation,
+                    )
+                    .await?
+                    .0
+                } else {
+                    predicted_gas_limit
+                };
+                (estimator, gas_limit)
+            };
+
+            let suggested_gas_limit =
+                (unscaled_gas_limit as f64 * estimated_fee_scale_factor) as u64;
+            let full_gas_limit = suggested_gas_limit
+                .checked_add(estimator.tx_overhead(suggested_gas_limit))
+                .filter(|&gas_limit| gas_limit <= self.max_gas_limit);
+            points.push(api::GasPerPubdataSensitivityPoint {
+                gas_per_pubdata: estimator.gas_per_pubdata_byte.into(),
+                gas_limit: full_gas_limit.map(U256::from),
+            });
+        }
+
+        Ok(api::GasPerPubdataSensitivity {
+            pubdata_published,
+            points,
+        })
+    }
+
     pub(super) async fn initialize(&self) -> Result<InitialGasEstimate, SubmitTxError> {
         let operator_overhead = self.tx_overhead(self.max_gas_limit);
 
@@ -422,22 +627,37 @@ impl<'a> GasEstimator<'a> {
         .into()
     }
 
+    /// Returns the gas limit (including the operator overhead) that the transaction should be executed with.
+    fn forced_gas_limit(&self, tx_gas_limit: u64) -> u64 {
+        let gas_limit_with_overhead = tx_gas_limit + self.tx_overhead(tx_gas_limit);
+        // We need to ensure that we never use a gas limit that is higher than the maximum allowed
+        gas_limit_with_overhead.min(get_max_batch_gas_limit(self.protocol_version.into()))
+    }
+
     #[tracing::instrument(level = "trace", skip(self))]
     async fn step(
         &self,
         tx_gas_limit: u64,
     ) -> Result<(ExecutionResult, TransactionExecutionMetrics), SubmitTxError> {
-        let gas_limit_with_overhead = tx_gas_limit + self.tx_overhead(tx_gas_limit);
-        // We need to ensure that we never use a gas limit that is higher than the maximum allowed
-        let forced_gas_limit =
-            gas_limit_with_overhead.min(get_max_batch_gas_limit(self.protocol_version.into()));
-        self.unadjusted_step(forced_gas_limit).await
+        self.unadjusted_step(self.forced_gas_limit(tx_gas_limit))
+            .await
     }
 
     pub(super) async fn unadjusted_step(
         &self,
         forced_gas_limit: u64,
     ) -> Result<(ExecutionResult, TransactionExecutionMetrics), SubmitTxError> {
+        let output = self
+            .execute(forced_gas_limit, OneshotTracingParams::default())
+            .await?;
+        Ok((output.result, output.metrics))
+    }
+
+    async fn execute(
+        &self,
+        forced_gas_limit: u64,
+        tracing_params: OneshotTracingParams,
+    ) -> Result<SandboxExecutionOutput, SubmitTxError> {
         let mut tx = self.transaction.clone();
         match &mut tx.common_data {
             ExecuteTransactionCommon::L1(l1_common_data) => {
@@ -465,10 +685,11 @@ impl<'a> GasEstimator<'a> {
             tx,
             fee_input: self.fee_input,
             base_fee: self.base_fee,
+            tracing_params,
         };
         let connection = self.sender.acquire_replica_connection().await?;
         let executor = &self.sender.0.executor;
-        let execution_output = executor
+        Ok(executor
             .execute_in_sandbox(
                 self.vm_permit.clone(),
                 connection,
@@ -476,8 +697,7 @@ impl<'a> GasEstimator<'a> {
                 &self.block_args,
                 self.state_override.clone(),
             )
-            .await?;
-        Ok((execution_output.result, execution_output.metrics))
+            .await?)
     }
 
     async fn finalize(
@@ -485,8 +705,30 @@ impl<'a> GasEstimator<'a> {
         suggested_gas_limit: u64,
         estimated_fee_scale_factor: f64,
     ) -> Result<Fee, SubmitTxError> {
-        let (result, tx_metrics) = self.step(suggested_gas_limit).await?;
-        result.into_api_call_result()?;
+        let tracing_params = OneshotTracingParams::default();
+        let (fee, _) = self
+            .finalize_with_traces(
+                suggested_gas_limit,
+                estimated_fee_scale_factor,
+                tracing_params,
+            )
+            .await?;
+        Ok(fee)
+    }
+
+    /// Same as [`Self::finalize()`], but additionally returns call traces of the final run collected according
+    /// to `tracing_params`.
+    async fn finalize_with_traces(
+        self,
+        suggested_gas_limit: u64,
+        estimated_fee_scale_factor: f64,
+        tracing_params: OneshotTracingParams,
+    ) -> Result<(Fee, Vec<Call>), SubmitTxError> {
+        let output = self
+            .execute(self.forced_gas_limit(suggested_gas_limit), tracing_params)
+            .await?;
+        let tx_metrics = output.metrics;
+        output.result.into_api_call_result()?;
         self.sender
             .ensure_tx_executable(&self.transaction, tx_metrics, false)?;
 
@@ -539,11 +781,43 @@ impl<'a> GasEstimator<'a> {
             self.transaction.gas_per_pubdata_byte_limit(),
         );
 
-        Ok(Fee {
+        let fee = Fee {
             max_fee_per_gas: self.base_fee.into(),
             max_priority_fee_per_gas: 0u32.into(),
             gas_limit: full_gas_limit.into(),
             gas_per_pubdata_limit,
-        })
+        };
+        Ok((fee, output.call_traces))
     }
 }
+
+/// Aggregates usage of [known precompiles](PRECOMPILES) across all (possibly nested) calls. Returned usage
+/// is ordered in the same way as precompiles in the registry.
+pub(super) fn aggregate_precompile_usage(call_traces: &[Call]) -> Vec<api::PrecompileUsage> {
+    let mut usage = vec![(0_u32, 0_u64); PRECOMPILES.len()];
+    let mut pending_calls: Vec<_> = call_traces.iter().collect();
+    while let Some(call) = pending_calls.pop() {
+        pending_calls.extend(&call.calls);
+        let precompile_idx = PRECOMPILES
+            .iter()
+            .position(|&(_, address)| address == call.to);
+        if let Some(idx) = precompile_idx {
+            usage[idx].0 += 1;
+            usage[idx].1 += call.gas_used;
+        }
+    }
+
+    PRECOMPILES
+        .iter()
+        .zip(usage)
+        .filter(|(_, (call_count, _))| *call_count > 0)
+        .map(
+            |(&(name, address), (call_count, gas_used))| api::PrecompileUsage {
+                name: name.to_owned(),
+                address,
+                call_count,
+                gas_used: gas_used.into(),
+            },
+        )
+        .collect()
+}
@@ -0,0 +1,127 @@
+//! Load shedding for low-priority requests processed by [`TxSender`](super::TxSender).
+
+use std::time::Duration;
+
+use zksync_config::configs::api::Web3JsonRpcConfig;
+use zksync_dal::{ConnectionPool, Core};
+
+use super::SubmitTxError;
+use crate::execution_sandbox::{OverloadReason, VmConcurrencyLimiter, SANDBOX_METRICS};
+
+/// Thresholds for rejecting low-priority requests (`eth_call`, gas estimation, call tracing) when the server
+/// is overloaded. Transaction submission is never rejected because of overload.
+///
+/// By default, load shedding is disabled.
+#[derive(Debug, Clone)]
+pub struct LoadSheddingConfig {
+    /// Average latency of waiting for a VM permit above which requests are rejected.
+    pub vm_queue_latency_threshold: Option<Duration>,
+    /// Share of connections in use in the replica connection pool (from 0 to 1) above which requests are rejected.
+    pub pool_utilization_threshold: Option<f64>,
+    /// Delay that clients are advised to wait before retrying a rejected request.
+    pub retry_after: Duration,
+}
+
+impl Default for LoadSheddingConfig {
+    fn default() -> Self {
+        Self {
+            vm_queue_latency_threshold: None,
+            pool_utilization_threshold: None,
+            retry_after: Duration::from_secs(1),
+        }
+    }
+}
+
+impl LoadSheddingConfig {
+    pub fn new(web3_json_config: &Web3JsonRpcConfig) -> Self {
+        Self {
+            vm_queue_latency_threshold: web3_json_config.load_shedding_vm_queue_latency(),
+            pool_utilization_threshold: web3_json_config.load_shedding_pool_utilization,
+            retry_after: web3_json_config.load_shedding_retry_after(),
+        }
+    }
+
+    /// Checks whether the server is overloaded based on the current state of the VM concurrency limiter
+    /// and the replica connection pool.
+    pub(super) fn check(
+        &self,
+        vm_concurrency_limiter: &VmConcurrencyLimiter,
+        replica_pool: &ConnectionPool<Core>,
+    ) -> Result<(), SubmitTxError> {
+        let reason = if self
+            .vm_queue_latency_threshold
+            .is_some_and(|threshold| vm_concurrency_limiter.queue_latency() > threshold)
+        {
+            OverloadReason::VmQueueLatency
+        } else if self
+            .pool_utilization_threshold
+            .is_some_and(|threshold| replica_pool.utilization() > threshold)
+        {
+            OverloadReason::PoolUtilization
+        } else {
+            return Ok(());
+        };
+
+        tracing::debug!("Shedding low-priority request because of overload: {reason:?}");
+        SANDBOX_METRICS.shed_requests[&reason].inc();
+        Err(SubmitTxError::ServerOverloaded(self.retry_after))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    const RETRY_AFTER: Duration = Duration::from_millis(500);
+
+    #[tokio::test]
+    async fn shedding_load_on_pool_utilization() {
+        let pool = ConnectionPool::<Core>::constrained_test_pool(2).await;
+        let (vm_concurrency_limiter, _) = VmConcurrencyLimiter::new(1);
+        let config = LoadSheddingConfig {
+            pool_utilization_threshold: Some(0.5),
+            retry_after: RETRY_AFTER,
+            ..LoadSheddingConfig::default()
+        };
+        config.check(&vm_concurrency_limiter, &pool).unwrap();
+
+        let _connections = [
+            pool.connection().await.unwrap(),
+            pool.connection().await.unwrap(),
+        ];
+        let err = config.check(&vm_concurrency_limiter, &pool).unwrap_err();
+        assert_matches!(err, SubmitTxError::ServerOverloaded(delay) if delay == RETRY_AFTER);
+        // Load shedding is disabled by default.
+        LoadSheddingConfig::default()
+            .check(&vm_concurrency_limiter, &pool)
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn shedding_load_on_vm_queue_latency() {
+        let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+        let (vm_concurrency_limiter, _) = VmConcurrencyLimiter::new(1);
+        let vm_concurrency_limiter = std::sync::Arc::new(vm_concurrency_limiter);
+        let config = LoadSheddingConfig {
+            vm_queue_latency_threshold: Some(Duration::from_millis(10)),
+            retry_after: RETRY_AFTER,
+            ..LoadSheddingConfig::default()
+        };
+
+        let permit = vm_concurrency_limiter.acquire().await.unwrap();
+        config.check(&vm_concurrency_limiter, &pool).unwrap();
+        let waiting_task = tokio::spawn({
+            let vm_concurrency_limiter = vm_concurrency_limiter.clone();
+            async move { vm_concurrency_limiter.acquire().await.is_some() }
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        drop(permit);
+        assert!(waiting_task.await.unwrap());
+
+        assert!(vm_concurrency_limiter.queue_latency() >= Duration::from_millis(10));
+        let err = config.check(&vm_concurrency_limiter, &pool).unwrap_err();
+        assert_matches!(err, SubmitTxError::ServerOverloaded(delay) if delay == RETRY_AFTER);
+    }
+}
@@ -2,7 +2,7 @@
 
 use std::{
     sync::{
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc,
     },
     time::Duration,
@@ -19,24 +19,28 @@ use zksync_dal::{
 use zksync_health_check::{CheckHealth, Health, HealthStatus};
 use zksync_multivm::{
     interface::{
-        tracer::TimestampAsserterParams as TracerTimestampAsserterParams, OneshotTracingParams,
-        TransactionExecutionMetrics,
+        tracer::TimestampAsserterParams as TracerTimestampAsserterParams, ExecutionResult,
+        OneshotTracingParams, TransactionExecutionMetrics,
     },
     utils::{
-        derive_base_fee_and_gas_per_pubdata, get_max_batch_gas_limit, get_max_new_factory_deps,
+        derive_base_fee_and_gas_per_pubdata, estimate_compressed_bytecodes_size,
+        get_max_batch_gas_limit, get_max_new_factory_deps,
     },
 };
 use zksync_node_fee_model::{ApiFeeInputProvider, BatchFeeModelInputProvider};
 use zksync_object_store::ObjectStore;
 use zksync_state::PostgresStorageCaches;
 use zksync_state_keeper::{
-    seal_criteria::{ConditionalSealer, NoopSealer, SealData},
+    seal_criteria::{
+        ConditionalSealer, NoopSealer, SealData, SealSimulation, SimulatedTxResolution,
+    },
     SequencerSealer,
 };
 use zksync_types::{
-    api::state_override::StateOverride,
+    api::{self, state_override::StateOverride},
     fee_model::BatchFeeInput,
     get_intrinsic_constants, h256_to_u256,
+    l1::L1Tx,
     l2::{error::TxCheckError::TxDuplication, L2Tx},
     transaction_request::CallOverrides,
     utils::storage_key_for_eth_balance,
@@ -44,18 +48,25 @@ use zksync_types::{
     AccountTreeId, Address, L2ChainId, Nonce, ProtocolVersionId, Transaction, H160, H256, U256,
 };
 use zksync_vm_executor::oneshot::{
-    CallOrExecute, EstimateGas, MultiVmBaseSystemContracts, OneshotEnvParameters,
+    CallOrExecute, EstimateGas, FrameGasCap, MultiVmBaseSystemContracts, OneshotEnvParameters,
+    ResourceLimits,
 };
 
 pub(super) use self::{gas_estimation::BinarySearchKind, result::SubmitTxError};
-use self::{master_pool_sink::MasterPoolSink, result::ApiCallResult, tx_sink::TxSink};
+use self::{
+    load_shedding::LoadSheddingConfig, master_pool_sink::MasterPoolSink, result::ApiCallResult,
+    tx_sink::TxSink,
+};
 use crate::execution_sandbox::{
-    BlockArgs, SandboxAction, SandboxExecutionOutput, SandboxExecutor, SubmitTxStage,
-    VmConcurrencyBarrier, VmConcurrencyLimiter, SANDBOX_METRICS,
+    BlockArgs, SandboxAction, SandboxExecutionError, SandboxExecutionOutput, SandboxExecutor,
+    SubmitTxStage, VmConcurrencyBarrier, VmConcurrencyLimiter, SANDBOX_METRICS,
 };
 
 mod gas_estimation;
+pub mod load_shedding;
 pub mod master_pool_sink;
+mod multicall;
+pub mod policy;
 pub mod proxy;
 mod result;
 #[cfg(test)]
@@ -106,6 +117,9 @@ pub async fn build_tx_sender(
 pub struct SandboxExecutorOptions {
     pub(crate) fast_vm_mode: FastVmMode,
     pub(crate) vm_dump_store: Option<Arc<dyn ObjectStore>>,
+    pub(crate) vm_warm_pool_size: usize,
+    pub(crate) vm_resource_limits: Option<ResourceLimits>,
+    pub(crate) frame_gas_cap: FrameGasCap,
     /// Env parameters to be used when estimating gas.
     pub(crate) estimate_gas: OneshotEnvParameters<EstimateGas>,
     /// Env parameters to be used when performing `eth_call` requests.
@@ -133,6 +147,9 @@ impl SandboxExecutorOptions {
         Ok(Self {
             fast_vm_mode: FastVmMode::Old,
             vm_dump_store: None,
+            vm_warm_pool_size: 0,
+            vm_resource_limits: None,
+            frame_gas_cap: FrameGasCap::default(),
             estimate_gas: OneshotEnvParameters::new(
                 Arc::new(estimate_gas_contracts),
                 chain_id,
@@ -157,6 +174,23 @@ impl SandboxExecutorOptions {
         self.vm_dump_store = Some(store);
     }
 
+    /// Sets the number of pre-initialized VM states kept for each set of base system contracts. `0` (the default)
+    /// disables the warm pool.
+    pub fn set_vm_warm_pool_size(&mut self, size: usize) {
+        self.vm_warm_pool_size = size;
+    }
+
+    /// Enables resource accounting for VM executions with the specified limits. Disabled by default.
+    pub fn set_vm_resource_limits(&mut self, limits: ResourceLimits) {
+        self.vm_resource_limits = Some(limits);
+    }
+
+    /// Sets the cap on gas forwarded to a single call frame. Should match the cap enforced by the state keeper,
+    /// so that transactions violating it are rejected during validation and fail during gas estimation.
+    pub fn set_frame_gas_cap(&mut self, frame_gas_cap: FrameGasCap) {
+        self.frame_gas_cap = frame_gas_cap;
+    }
+
     pub(crate) async fn mock() -> Self {
         Self::new(L2ChainId::default(), AccountTreeId::default(), u32::MAX)
             .await
@@ -242,6 +276,7 @@ impl TxSenderBuilder {
             whitelisted_tokens_for_aa_cache,
             sealer,
             executor,
+            tx_intake_paused: AtomicBool::new(false),
         }))
     }
 }
@@ -257,11 +292,14 @@ pub struct TxSenderConfig {
     pub gas_price_scale_factor: f64,
     pub max_nonce_ahead: u32,
     pub max_allowed_l2_tx_gas_limit: u64,
+    pub max_tx_abi_encoded_size: Option<usize>,
+    pub max_tx_compressed_bytecodes_size: Option<usize>,
     pub vm_execution_cache_misses_limit: Option<usize>,
     pub validation_computational_gas_limit: u32,
     pub chain_id: L2ChainId,
     pub whitelisted_tokens_for_aa: Vec<Address>,
     pub timestamp_asserter_params: Option<TimestampAsserterParams>,
+    pub load_shedding: LoadSheddingConfig,
 }
 
 #[derive(Debug, Clone)]
@@ -282,12 +320,15 @@ impl TxSenderConfig {
             gas_price_scale_factor: web3_json_config.gas_price_scale_factor,
             max_nonce_ahead: web3_json_config.max_nonce_ahead,
             max_allowed_l2_tx_gas_limit: state_keeper_config.max_allowed_l2_tx_gas_limit,
+            max_tx_abi_encoded_size: web3_json_config.max_tx_abi_encoded_size,
+            max_tx_compressed_bytecodes_size: web3_json_config.max_tx_compressed_bytecodes_size,
             vm_execution_cache_misses_limit: web3_json_config.vm_execution_cache_misses_limit,
             validation_computational_gas_limit: state_keeper_config
                 .validation_computational_gas_limit,
             chain_id,
             whitelisted_tokens_for_aa: web3_json_config.whitelisted_tokens_for_aa.clone(),
             timestamp_asserter_params: None,
+            load_shedding: LoadSheddingConfig::new(web3_json_config),
         }
     }
 
@@ -314,6 +355,8 @@ pub struct TxSenderInner {
     /// Batch sealer used to check whether transaction can be executed by the sequencer.
     pub(super) sealer: Arc<dyn ConditionalSealer>,
     pub(super) executor: SandboxExecutor,
+    /// Set by the node operator via the `admin` namespace.
+    pub(super) tx_intake_paused: AtomicBool,
 }
 
 /// Health check details for [`TxSender`].
@@ -380,10 +423,28 @@ impl TxSender {
         }
     }
 
+    /// Pauses or resumes accepting new transactions. Returns the previous state.
+    pub fn set_tx_intake_paused(&self, paused: bool) -> bool {
+        self.0.tx_intake_paused.swap(paused, Ordering::Relaxed)
+    }
+
+    pub fn is_tx_intake_paused(&self) -> bool {
+        self.0.tx_intake_paused.load(Ordering::Relaxed)
+    }
+
     pub(crate) fn vm_concurrency_limiter(&self) -> Arc<VmConcurrencyLimiter> {
         Arc::clone(&self.0.vm_concurrency_limiter)
     }
 
+    /// Rejects a low-priority request (e.g., `eth_call` or gas estimation) if the server is overloaded,
+    /// so that transaction submission is not starved.
+    pub(crate) fn shed_low_priority_load(&self) -> Result<(), SubmitTxError> {
+        self.0.sender_config.load_shedding.check(
+            &self.0.vm_concurrency_limiter,
+            &self.0.replica_connection_pool,
+        )
+    }
+
     pub(crate) async fn read_whitelisted_tokens_for_aa_cache(&self) -> Vec<Address> {
         self.0.whitelisted_tokens_for_aa_cache.read().await.clone()
     }
@@ -402,6 +463,10 @@ impl TxSender {
         tx: L2Tx,
         block_args: BlockArgs,
     ) -> Result<SandboxExecutionOutput, SubmitTxError> {
+        if self.is_tx_intake_paused() {
+            return Err(SubmitTxError::TxIntakePaused);
+        }
+
         let tx_hash = tx.hash();
         let stage_latency = SANDBOX_METRICS.start_tx_submit_stage(tx_hash, SubmitTxStage::Validate);
         self.validate_tx(&tx, block_args.protocol_version()).await?;
@@ -562,6 +627,7 @@ impl TxSender {
                 max_new_factory_deps,
             ));
         }
+        self.validate_tx_size(tx)?;
 
         let intrinsic_consts = get_intrinsic_constants();
         assert!(
@@ -582,6 +648,35 @@ impl TxSender {
         Ok(())
     }
 
+    fn validate_tx_size(&self, tx: &L2Tx) -> Result<(), SubmitTxError> {
+        if let Some(max_size) = self.0.sender_config.max_tx_abi_encoded_size {
+            // `abi_encoding_len()` returns the length in 32-byte words
+            let encoded_size = tx.abi_encoding_len() * 32;
+            if encoded_size > max_size {
+                tracing::info!(
+                    "Submitted Tx is Unexecutable {:?} because of TxTooLarge {encoded_size}",
+                    tx.hash()
+                );
+                return Err(SubmitTxError::TxTooLarge(encoded_size, max_size));
+            }
+        }
+
+        if let Some(max_size) = self.0.sender_config.max_tx_compressed_bytecodes_size {
+            let compressed_size = estimate_compressed_bytecodes_size(&tx.execute.factory_deps);
+            if compressed_size > max_size {
+                tracing::info!(
+                    "Submitted Tx is Unexecutable {:?} because of CompressedBytecodesTooLarge {compressed_size}",
+                    tx.hash()
+                );
+                return Err(SubmitTxError::CompressedBytecodesTooLarge(
+                    compressed_size,
+                    max_size,
+                ));
+            }
+        }
+        Ok(())
+    }
+
     async fn validate_account_nonce(&self, tx: &L2Tx) -> Result<(), SubmitTxError> {
         let Nonce(expected_nonce) = self
             .get_expected_nonce(tx.initiator_account())
@@ -686,6 +781,7 @@ impl TxSender {
         call: L2Tx,
         state_override: Option<StateOverride>,
     ) -> Result<Vec<u8>, SubmitTxError> {
+        self.shed_low_priority_load()?;
         let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
         let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
 
@@ -718,6 +814,111 @@ impl TxSender {
         result.result.into_api_call_result()
     }
 
+    /// Simulates execution of an L1 -> L2 transaction on top of the state defined by `block_args` in the same way
+    /// as the state keeper would execute it. Unlike with L2 transactions, a reverted L1 -> L2 transaction is not an error
+    /// since it would still be included into a batch.
+    pub(crate) async fn simulate_l1_tx(
+        &self,
+        tx: L1Tx,
+        block_args: BlockArgs,
+        state_override: Option<StateOverride>,
+    ) -> Result<SandboxExecutionOutput, SubmitTxError> {
+        self.shed_low_priority_load()?;
+        // **Important.** The fee input must be obtained before acquiring a connection; see `submit_tx()`.
+        let fee_input = self
+            .0
+            .batch_fee_input_provider
+            .get_batch_fee_input()
+            .await
+            .context("cannot get batch fee input")?;
+
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+        let connection = self.acquire_replica_connection().await?;
+        let action = SandboxAction::L1TxExecution { tx, fee_input };
+        let output = self
+            .0
+            .executor
+            .execute_in_sandbox(vm_permit, connection, action, &block_args, state_override)
+            .await?;
+        if let ExecutionResult::Halt { reason } = &output.result {
+            return Err(SandboxExecutionError::from(reason.clone()).into());
+        }
+        Ok(output)
+    }
+
+    /// Simulates sealing of an L1 batch consisting of the provided `transactions` without affecting the live batch.
+    ///
+    /// Each transaction is executed in the sandbox on top of the state defined by `block_args` in isolation,
+    /// so transactions depending on preceding ones (e.g., with a nonce gap) are skipped.
+    pub(crate) async fn simulate_batch_sealing(
+        &self,
+        transactions: Vec<Transaction>,
+        block_args: BlockArgs,
+    ) -> Result<api::SealCriteriaSimulation, SubmitTxError> {
+        self.shed_low_priority_load()?;
+        // **Important.** The fee input must be obtained before acquiring a connection; see `submit_tx()`.
+        let fee_input = self
+            .0
+            .batch_fee_input_provider
+            .get_batch_fee_input()
+            .await
+            .context("cannot get batch fee input")?;
+
+        let mut simulation =
+            SealSimulation::new(self.0.sealer.as_ref(), block_args.protocol_version());
+        let mut report = api::SealCriteriaSimulation {
+            pending_transactions: transactions.len(),
+            ..api::SealCriteriaSimulation::default()
+        };
+        for transaction in transactions {
+            let tx_hash = transaction.hash();
+            let Ok(tx) = L2Tx::try_from(transaction.clone()) else {
+                report.skipped_transactions.push(tx_hash);
+                continue;
+            };
+
+            let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+            let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+            let connection = self.acquire_replica_connection().await?;
+            let action = SandboxAction::Execution { fee_input, tx };
+            let execution_output = self
+                .0
+                .executor
+                .execute_in_sandbox(vm_permit, connection, action, &block_args, None)
+                .await?;
+            if matches!(execution_output.result, ExecutionResult::Halt { .. }) {
+                report.skipped_transactions.push(tx_hash);
+                continue;
+            }
+
+            let seal_data = SealData::for_transaction(&transaction, execution_output.metrics);
+            let (criterion, transaction_included) =
+                match simulation.push_transaction(false, seal_data) {
+                    SimulatedTxResolution::Included => continue,
+                    SimulatedTxResolution::Unexecutable(criterion) => {
+                        report
+                            .unexecutable_transactions
+                            .push(api::SimulatedTxRejection {
+                                transaction_hash: tx_hash,
+                                criterion: criterion.to_owned(),
+                            });
+                        continue;
+                    }
+                    SimulatedTxResolution::IncludedAndSealed(criterion) => (criterion, true),
+                    SimulatedTxResolution::ExcludedAndSealed(criterion) => (criterion, false),
+                };
+            report.seal = Some(api::SimulatedSeal {
+                criterion: criterion.to_owned(),
+                transaction_hash: tx_hash,
+                transaction_included,
+            });
+            break;
+        }
+        report.included_transactions = simulation.tx_count();
+        Ok(report)
+    }
+
     pub async fn gas_price(&self) -> anyhow::Result<u64> {
         let mut connection = self.acquire_replica_connection().await?;
         let protocol_version = connection
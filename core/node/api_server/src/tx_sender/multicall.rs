@@ -0,0 +1,48 @@
+//! Recognition of [Multicall3](https://github.com/mds1/multicall) calls used by the gas estimation fast path.
+
+use once_cell::sync::Lazy;
+use zksync_types::ethabi::{self, ParamType};
+
+/// Multicall3 entry points that execute a batch of sub-calls: `(selector, ABI of the arguments)`.
+static MULTICALL3_METHODS: Lazy<Vec<([u8; 4], Vec<ParamType>)>> = Lazy::new(|| {
+    let call = ParamType::Tuple(vec![ParamType::Address, ParamType::Bytes]);
+    let call3 = ParamType::Tuple(vec![ParamType::Address, ParamType::Bool, ParamType::Bytes]);
+    let call3_value = ParamType::Tuple(vec![
+        ParamType::Address,
+        ParamType::Bool,
+        ParamType::Uint(256),
+        ParamType::Bytes,
+    ]);
+    let calls = ParamType::Array(Box::new(call));
+    let methods = [
+        ("aggregate", vec![calls.clone()]),
+        ("blockAndAggregate", vec![calls.clone()]),
+        ("tryAggregate", vec![ParamType::Bool, calls.clone()]),
+        ("tryBlockAndAggregate", vec![ParamType::Bool, calls]),
+        ("aggregate3", vec![ParamType::Array(Box::new(call3))]),
+        (
+            "aggregate3Value",
+            vec![ParamType::Array(Box::new(call3_value))],
+        ),
+    ];
+    methods
+        .into_iter()
+        .map(|(name, params)| (ethabi::short_signature(name, &params), params))
+        .collect()
+});
+
+/// Returns the number of sub-calls if `calldata` is a well-formed call to one of Multicall3 batch methods.
+///
+/// The contract address is intentionally not checked since Multicall3 is deployed at different addresses
+/// on different chains. Recognition only affects gas estimation performance, not its correctness,
+/// since the estimate is always validated by executing the transaction.
+pub(super) fn multicall3_call_count(calldata: &[u8]) -> Option<usize> {
+    let (selector, args) = calldata.split_first_chunk::<4>()?;
+    let (_, params) = MULTICALL3_METHODS
+        .iter()
+        .find(|(method_selector, _)| method_selector == selector)?;
+    let tokens = ethabi::decode(params, args).ok()?;
+    // The sub-calls array is always the last argument.
+    let calls = tokens.into_iter().last()?.into_array()?;
+    Some(calls.len())
+}
@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use zksync_dal::{
+    transactions_dal::L2TxSubmissionResult, Connection, ConnectionPool, Core, CoreDal,
+};
+use zksync_multivm::interface::tracer::ValidationTraces;
+use zksync_state_keeper::tx_policy::{
+    deployer_addresses, TxPolicy, TxPolicyStage, TxPolicyViolation,
+};
+use zksync_types::{
+    api::{Transaction, TransactionDetails, TransactionId},
+    l2::L2Tx,
+    Address, Nonce, H256,
+};
+use zksync_web3_decl::error::Web3Error;
+
+use super::{tx_sink::TxSink, SubmitTxError};
+use crate::execution_sandbox::SandboxExecutionOutput;
+
+/// Wrapper around another [`TxSink`] enforcing the [`TxPolicy`] before submitting transactions.
+///
+/// Rejections are persisted to Postgres so that they can be queried via the admin API; see [`TxPolicy::persist_decision()`]
+/// for the details.
+#[derive(Debug)]
+pub struct PolicyEnforcingSink {
+    inner: Box<dyn TxSink>,
+    policy: Arc<TxPolicy>,
+    master_pool: ConnectionPool<Core>,
+}
+
+impl PolicyEnforcingSink {
+    pub fn new(
+        inner: impl TxSink,
+        policy: Arc<TxPolicy>,
+        master_pool: ConnectionPool<Core>,
+    ) -> Self {
+        Self {
+            inner: Box::new(inner),
+            policy,
+            master_pool,
+        }
+    }
+
+    fn check(
+        &self,
+        tx: &L2Tx,
+        execution_output: &SandboxExecutionOutput,
+    ) -> Result<(), TxPolicyViolation> {
+        self.policy
+            .check_tx(tx.initiator_account(), tx.recipient_account())?;
+        for deployer_address in deployer_addresses(&execution_output.events) {
+            self.policy.check_deployer(deployer_address)?;
+        }
+        self.policy.check_rate_limit(tx.initiator_account())
+    }
+
+    async fn persist_rejection(
+        &self,
+        tx: &L2Tx,
+        violation: &TxPolicyViolation,
+    ) -> anyhow::Result<()> {
+        let mut storage = self.master_pool.connection_tagged("api_tx_policy").await?;
+        self.policy
+            .persist_decision(
+                &mut storage,
+                tx.hash(),
+                tx.initiator_account(),
+                TxPolicyStage::Submission,
+                violation,
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl TxSink for PolicyEnforcingSink {
+    async fn submit_tx(
+        &self,
+        tx: &L2Tx,
+        execution_output: &SandboxExecutionOutput,
+        validation_traces: ValidationTraces,
+    ) -> Result<L2TxSubmissionResult, SubmitTxError> {
+        if let Err(violation) = self.check(tx, execution_output) {
+            tracing::info!("Rejecting tx {:?}: {violation}", tx.hash());
+            if let Err(err) = self.persist_rejection(tx, &violation).await {
+                tracing::warn!(
+                    "Failed persisting policy rejection for tx {:?}: {err:#}",
+                    tx.hash()
+                );
+            }
+            return Err(SubmitTxError::PolicyViolation(violation));
+        }
+
+        self.inner
+            .submit_tx(tx, execution_output, validation_traces)
+            .await
+    }
+
+    async fn lookup_pending_nonce(
+        &self,
+        account_address: Address,
+        last_known_nonce: u32,
+    ) -> Result<Option<Nonce>, Web3Error> {
+        self.inner
+            .lookup_pending_nonce(account_address, last_known_nonce)
+            .await
+    }
+
+    async fn lookup_tx(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        id: TransactionId,
+    ) -> Result<Option<Transaction>, Web3Error> {
+        self.inner.lookup_tx(storage, id).await
+    }
+
+    async fn lookup_tx_details(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        hash: H256,
+    ) -> Result<Option<TransactionDetails>, Web3Error> {
+        self.inner.lookup_tx_details(storage, hash).await
+    }
+}
@@ -97,8 +97,12 @@ impl TxCacheInner {
 }
 
 impl TxCache {
-    async fn push(&self, tx: L2Tx) {
+    /// Adds a transaction to the cache. Returns `false` if the transaction is already present in the cache.
+    async fn push(&self, tx: L2Tx) -> bool {
         let mut inner = self.inner.write().await;
+        if inner.transactions_by_hash.contains_key(&tx.hash()) {
+            return false;
+        }
         inner
             .nonces_by_account
             .entry(tx.initiator_account())
@@ -110,6 +114,7 @@ impl TxCache {
             .or_default()
             .insert(tx.hash());
         inner.transactions_by_hash.insert(tx.hash(), tx);
+        true
     }
 
     async fn get(&self, tx_hash: H256) -> Option<L2Tx> {
@@ -207,7 +212,12 @@ impl TxCache {
 }
 
 /// Used by external node to proxy transaction to the main node
-/// and store them while they're not synced back yet
+/// and store them while they're not synced back yet.
+///
+/// The proxy itself performs no validation; it only deduplicates transactions. A transaction that is already
+/// in the cache (i.e., was recently forwarded to the main node and is not synced back yet) is not forwarded again;
+/// [`L2TxSubmissionResult::Duplicate`] is returned for it instead. Errors returned by the main node are surfaced
+/// to the caller as is.
 #[derive(Debug)]
 pub struct TxProxy {
     tx_cache: TxCache,
@@ -315,7 +325,10 @@ impl TxSink for TxProxy {
         // We're running an external node: we have to proxy the transaction to the main node.
         // But before we do that, save the tx to cache in case someone will request it
         // Before it reaches the main node.
-        self.tx_cache.push(tx.clone()).await;
+        if !self.tx_cache.push(tx.clone()).await {
+            tracing::debug!("Transaction {:?} was already proxied; skipping", tx.hash());
+            return Ok(L2TxSubmissionResult::Duplicate);
+        }
         if let Err(err) = self.submit_tx_impl(tx).await {
             // Remove the transaction from the cache on failure so that it doesn't occupy space in the cache indefinitely.
             self.tx_cache.remove(tx.hash()).await;
@@ -385,7 +398,7 @@ impl TxSink for TxProxy {
 
 #[cfg(test)]
 mod tests {
-    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
     use test_casing::test_casing;
     use zksync_node_genesis::{insert_genesis_batch, mock_genesis_config, GenesisParams};
@@ -543,6 +556,44 @@ mod tests {
         assert!(found_tx.is_none(), "{found_tx:?}");
     }
 
+    #[tokio::test]
+    async fn duplicate_transaction_is_not_proxied() {
+        let tx = create_l2_transaction(10, 100);
+        let send_tx_count = Arc::new(AtomicUsize::new(0));
+        let main_node_client = MockClient::builder(L2::default())
+            .method("eth_sendRawTransaction", {
+                let send_tx_count = send_tx_count.clone();
+                let tx_hash = tx.hash();
+                move |_bytes: Bytes| {
+                    send_tx_count.fetch_add(1, Ordering::Relaxed);
+                    Ok(tx_hash)
+                }
+            })
+            .build();
+
+        let proxy = TxProxy::new(Box::new(main_node_client));
+        let result = proxy
+            .submit_tx(
+                &tx,
+                &SandboxExecutionOutput::mock_success(),
+                ValidationTraces::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result, L2TxSubmissionResult::Proxied);
+
+        let result = proxy
+            .submit_tx(
+                &tx,
+                &SandboxExecutionOutput::mock_success(),
+                ValidationTraces::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result, L2TxSubmissionResult::Duplicate);
+        assert_eq!(send_tx_count.load(Ordering::Relaxed), 1);
+    }
+
     #[derive(Debug, Clone, Copy)]
     enum CacheUpdateMethod {
         BackgroundTask,
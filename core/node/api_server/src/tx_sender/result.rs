@@ -1,5 +1,8 @@
+use std::time::Duration;
+
 use thiserror::Error;
 use zksync_multivm::interface::ExecutionResult;
+use zksync_state_keeper::tx_policy::TxPolicyViolation;
 use zksync_types::{l2::error::TxCheckError, Address, U256};
 use zksync_web3_decl::error::EnrichedClientError;
 
@@ -26,6 +29,10 @@ pub enum SubmitTxError {
     Unexecutable(String),
     #[error("server shutting down")]
     ServerShuttingDown,
+    #[error("transaction intake is paused by the operator")]
+    TxIntakePaused,
+    #[error("server is overloaded; retry after {0:?}")]
+    ServerOverloaded(Duration),
     #[error("failed to include transaction in the system. reason: {0}")]
     BootloaderFailure(String),
     #[error("failed to validate the transaction. reason: {0}")]
@@ -51,6 +58,14 @@ pub enum SubmitTxError {
         "too many factory dependencies in the transaction. {0} provided, while only {1} allowed"
     )]
     TooManyFactoryDependencies(usize, usize),
+    #[error(
+        "transaction is too large. ABI-encoded size is {0} bytes, while only {1} bytes allowed"
+    )]
+    TxTooLarge(usize, usize),
+    #[error(
+        "compressed factory dependencies are too large. {0} bytes provided, while only {1} bytes allowed"
+    )]
+    CompressedBytecodesTooLarge(usize, usize),
     /// IntrinsicGas is returned if the transaction is specified to use less gas
     /// than required to start the invocation.
     #[error("intrinsic gas too low")]
@@ -71,6 +86,8 @@ pub enum SubmitTxError {
     Internal(#[from] anyhow::Error),
     #[error("contract deployer address {0} is not in the allow list")]
     DeployerNotInAllowList(Address),
+    #[error("transaction policy violation: {0}")]
+    PolicyViolation(TxPolicyViolation),
 }
 
 impl SubmitTxError {
@@ -85,6 +102,8 @@ impl SubmitTxError {
             Self::GasLimitIsTooBig => "gas-limit-is-too-big",
             Self::Unexecutable(_) => "unexecutable",
             Self::ServerShuttingDown => "shutting-down",
+            Self::TxIntakePaused => "tx-intake-paused",
+            Self::ServerOverloaded(_) => "server-overloaded",
             Self::BootloaderFailure(_) => "bootloader-failure",
             Self::ValidationFailed(_) => "validation-failed",
             Self::FailedToChargeFee(_) => "failed-too-charge-fee",
@@ -95,6 +114,8 @@ impl SubmitTxError {
             Self::MaxPriorityFeeGreaterThanMaxFee => "max-priority-fee-greater-than-max-fee",
             Self::UnexpectedVMBehavior(_) => "unexpected-vm-behavior",
             Self::TooManyFactoryDependencies(_, _) => "too-many-factory-dependencies",
+            Self::TxTooLarge(_, _) => "tx-too-large",
+            Self::CompressedBytecodesTooLarge(_, _) => "compressed-bytecodes-too-large",
             Self::IntrinsicGas => "intrinsic-gas",
             Self::FailedToPublishCompressedBytecodes => "failed-to-publish-compressed-bytecodes",
             Self::MintedAmountOverflow => "minted-amount-overflow",
@@ -102,6 +123,7 @@ impl SubmitTxError {
             Self::ProxyError(_) => "proxy-error",
             Self::Internal(_) => "internal",
             Self::DeployerNotInAllowList(_) => "deployer-not-in-allow-list",
+            Self::PolicyViolation(_) => "policy-violation",
         }
     }
 
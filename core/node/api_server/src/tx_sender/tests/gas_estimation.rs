@@ -4,19 +4,27 @@ use std::collections::HashMap;
 
 use assert_matches::assert_matches;
 use test_casing::{test_casing, Product};
-use zksync_system_constants::CODE_ORACLE_ADDRESS;
-use zksync_test_contracts::Account;
+use zksync_multivm::interface::Call;
+use zksync_system_constants::{
+    CODE_ORACLE_ADDRESS, KECCAK256_PRECOMPILE_ADDRESS, SECP256R1_VERIFY_PRECOMPILE_ADDRESS,
+    SHA256_PRECOMPILE_ADDRESS,
+};
+use zksync_test_contracts::{Account, TestContract};
 use zksync_types::{
     api::state_override::{OverrideAccount, OverrideState},
     bytecode::BytecodeHash,
-    u256_to_h256,
+    ethabi, u256_to_h256,
     web3::keccak256,
+    Execute,
 };
 
 use super::*;
 use crate::{
-    testonly::{StateBuilder, TestAccount},
-    tx_sender::gas_estimation::GasEstimator,
+    testonly::{default_fee, Call3Value, StateBuilder, TestAccount},
+    tx_sender::{
+        gas_estimation::{aggregate_precompile_usage, GasEstimator},
+        multicall::multicall3_call_count,
+    },
 };
 
 /// Initial pivot multiplier empirically sufficient for most tx types.
@@ -444,6 +452,125 @@ async fn estimating_gas_for_expensive_txs(write_count: usize) {
     test_estimating_gas(state_override, tx, 0).await;
 }
 
+#[test_casing(2, [10, 100])]
+#[tokio::test]
+async fn estimating_gas_per_pubdata_sensitivity(write_count: usize) {
+    let mut alice = Account::random();
+    let state_override = StateBuilder::default().with_expensive_contract().build();
+    let tx = alice.create_expensive_tx(write_count);
+
+    let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let tx_sender = create_real_tx_sender(pool).await;
+    let block_args = pending_block_args(&tx_sender).await;
+    let fee = tx_sender
+        .get_txs_fee_with_sensitivity(
+            tx.into(),
+            block_args,
+            1.0,
+            0,
+            Some(state_override),
+            BinarySearchKind::Full,
+        )
+        .await
+        .unwrap();
+
+    let sensitivity = &fee.gas_per_pubdata_sensitivity;
+    assert!(sensitivity.pubdata_published > 0, "{sensitivity:?}");
+    assert!(sensitivity.points.len() > 1, "{sensitivity:?}");
+    let current_point = sensitivity
+        .points
+        .iter()
+        .find(|point| point.gas_per_pubdata == fee.fee.gas_per_pubdata_limit)
+        .unwrap();
+    assert_eq!(current_point.gas_limit, Some(fee.fee.gas_limit));
+
+    // Gas limits must grow with the gas per pubdata price.
+    let gas_limits: Vec<_> = sensitivity
+        .points
+        .iter()
+        .filter_map(|point| point.gas_limit)
+        .collect();
+    assert!(
+        gas_limits.windows(2).all(|window| window[0] < window[1]),
+        "{sensitivity:?}"
+    );
+}
+
+#[test]
+fn aggregating_precompile_usage() {
+    let precompile_call = |address, gas_used| Call {
+        to: address,
+        gas_used,
+        ..Call::default()
+    };
+    let call_traces = [
+        Call {
+            to: Address::repeat_byte(1),
+            calls: vec![
+                precompile_call(SECP256R1_VERIFY_PRECOMPILE_ADDRESS, 100),
+                Call {
+                    to: Address::repeat_byte(2),
+                    calls: vec![precompile_call(SECP256R1_VERIFY_PRECOMPILE_ADDRESS, 200)],
+                    ..Call::default()
+                },
+            ],
+            ..Call::default()
+        },
+        precompile_call(KECCAK256_PRECOMPILE_ADDRESS, 50),
+    ];
+
+    let usage = aggregate_precompile_usage(&call_traces);
+    assert_eq!(usage.len(), 2, "{usage:?}");
+    assert_eq!(usage[0].name, "p256Verify");
+    assert_eq!(usage[0].address, SECP256R1_VERIFY_PRECOMPILE_ADDRESS);
+    assert_eq!(usage[0].call_count, 2);
+    assert_eq!(usage[0].gas_used, 300.into());
+    assert_eq!(usage[1].name, "keccak256");
+    assert_eq!(usage[1].call_count, 1);
+    assert_eq!(usage[1].gas_used, 50.into());
+}
+
+#[tokio::test]
+async fn estimating_fee_reports_precompile_usage() {
+    let mut alice = Account::random();
+    let state_override = StateBuilder::default().with_precompiles_contract().build();
+    let calldata = TestContract::precompiles_test()
+        .function("doSha256")
+        .encode_input(&[ethabi::Token::Uint(3.into())])
+        .unwrap();
+    let execute = Execute {
+        contract_address: Some(StateBuilder::PRECOMPILES_CONTRACT_ADDRESS),
+        calldata,
+        value: 0.into(),
+        factory_deps: vec![],
+    };
+    let tx = alice.get_l2_tx_for_execute(execute, Some(default_fee()));
+
+    let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let tx_sender = create_real_tx_sender(pool).await;
+    let block_args = pending_block_args(&tx_sender).await;
+    let fee = tx_sender
+        .get_txs_fee_with_sensitivity(
+            tx,
+            block_args,
+            1.0,
+            0,
+            Some(state_override),
+            BinarySearchKind::Full,
+        )
+        .await
+        .unwrap();
+
+    let sha256_usage = fee
+        .precompile_usage
+        .iter()
+        .find(|usage| usage.address == SHA256_PRECOMPILE_ADDRESS)
+        .unwrap_or_else(|| panic!("{:?}", fee.precompile_usage));
+    assert_eq!(sha256_usage.name, "sha256");
+    assert_eq!(sha256_usage.call_count, 3);
+    assert!(sha256_usage.gas_used > U256::zero());
+}
+
 #[tokio::test]
 async fn estimating_gas_for_code_oracle_tx() {
     let mut alice = Account::random();
@@ -573,3 +700,80 @@ async fn estimating_gas_for_evm_deployment() {
     // At the time of writing the test, `evm_gas_limit` is ~926k.
     assert!((100_000..10_000_000).contains(&evm_gas_limit));
 }
+
+#[test]
+fn recognizing_multicall3_calls() {
+    let mut alice = Account::random();
+    let calls: Vec<Call3Value> = (1..=3)
+        .map(|i| alice.create_counter_tx(i.into(), false).into())
+        .collect();
+    let multicall = alice.multicall_with_value(0.into(), &calls);
+    assert_eq!(multicall3_call_count(&multicall.data.unwrap().0), Some(3));
+
+    let counter_tx = alice.create_counter_tx(1.into(), false);
+    assert_eq!(multicall3_call_count(&counter_tx.execute.calldata), None);
+    assert_eq!(multicall3_call_count(&[]), None);
+    // Selector without well-formed arguments
+    let aggregate3_selector = ethabi::short_signature(
+        "aggregate3",
+        &[ethabi::ParamType::Array(Box::new(
+            ethabi::ParamType::Tuple(vec![
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Bool,
+                ethabi::ParamType::Bytes,
+            ]),
+        ))],
+    );
+    assert_eq!(multicall3_call_count(&aggregate3_selector), None);
+}
+
+#[tokio::test]
+async fn estimating_gas_for_multicall() {
+    let mut alice = Account::random();
+    let state_override = StateBuilder::default()
+        .with_multicall3_contract()
+        .with_counter_contract(None)
+        .build();
+    let calls: Vec<Call3Value> = (1..=10)
+        .map(|i| alice.create_counter_tx(i.into(), false).into())
+        .collect();
+    let multicall = alice.multicall_with_value(0.into(), &calls);
+    let execute = Execute {
+        contract_address: multicall.to,
+        calldata: multicall.data.unwrap().0,
+        value: 0.into(),
+        factory_deps: vec![],
+    };
+    let tx: L2Tx = alice
+        .get_l2_tx_for_execute(execute, Some(default_fee()))
+        .try_into()
+        .unwrap();
+
+    let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let tx_sender = create_real_tx_sender(pool).await;
+    let block_args = pending_block_args(&tx_sender).await;
+    let mut gas_limits = vec![];
+    for kind in [BinarySearchKind::Full, BinarySearchKind::Optimized] {
+        let fee = tx_sender
+            .get_txs_fee_in_wei(
+                tx.clone().into(),
+                block_args.clone(),
+                1.0,
+                0,
+                Some(state_override.clone()),
+                kind,
+            )
+            .await
+            .unwrap();
+        gas_limits.push(u64::try_from(fee.gas_limit).unwrap());
+    }
+
+    // The fast path skips the binary search, so the estimate may be slightly greater than the minimum one.
+    let [full_gas_limit, fast_path_gas_limit] = gas_limits[..] else {
+        unreachable!();
+    };
+    assert!(
+        (full_gas_limit..=full_gas_limit * 11 / 10).contains(&fast_path_gas_limit),
+        "full={full_gas_limit}, fast_path={fast_path_gas_limit}"
+    );
+}
@@ -5,13 +5,18 @@ use std::ops::Range;
 use assert_matches::assert_matches;
 use chrono::NaiveDateTime;
 use test_casing::test_casing;
+use zksync_config::configs::TxPolicyConfig;
 use zksync_multivm::interface::{tracer::ValidationTraces, ExecutionResult};
 use zksync_node_fee_model::{BatchFeeModelInputProvider, MockBatchFeeParamsProvider};
 use zksync_node_test_utils::create_l2_transaction;
+use zksync_state_keeper::tx_policy::{TxPolicy, TxPolicyViolation};
 use zksync_test_contracts::Account;
 
 use super::*;
-use crate::testonly::{StateBuilder, TestAccount};
+use crate::{
+    testonly::{StateBuilder, TestAccount},
+    tx_sender::policy::PolicyEnforcingSink,
+};
 
 #[tokio::test]
 async fn submitting_tx_requires_one_connection() {
@@ -183,6 +188,72 @@ async fn fee_validation_errors() {
     }
 }
 
+#[tokio::test]
+async fn size_validation_errors() {
+    let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let l2_chain_id = L2ChainId::default();
+    let tx_executor = SandboxExecutor::mock(MockOneshotExecutor::default()).await;
+    let (mut tx_sender, _) = create_test_tx_sender(pool.clone(), l2_chain_id, tx_executor).await;
+    let sender_config = &mut Arc::get_mut(&mut tx_sender.0).unwrap().sender_config;
+    sender_config.max_tx_abi_encoded_size = Some(2_000);
+    sender_config.max_tx_compressed_bytecodes_size = Some(1_000);
+
+    let fee_params_provider: &dyn BatchFeeModelInputProvider =
+        &MockBatchFeeParamsProvider::default();
+    let fee_input = fee_params_provider.get_batch_fee_input().await.unwrap();
+    let (base_fee, gas_per_pubdata) =
+        derive_base_fee_and_gas_per_pubdata(fee_input, ProtocolVersionId::latest().into());
+    let tx = create_l2_transaction(base_fee, gas_per_pubdata);
+
+    StateBuilder::default()
+        .with_balance(tx.initiator_account(), u64::MAX.into())
+        .apply(storage)
+        .await;
+
+    tx_sender
+        .validate_tx(&tx, ProtocolVersionId::latest())
+        .await
+        .unwrap();
+
+    {
+        let mut tx = tx.clone();
+        tx.execute.calldata = vec![1; 4_000];
+        let err = tx_sender
+            .validate_tx(&tx, ProtocolVersionId::latest())
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            SubmitTxError::TxTooLarge(size, 2_000) if size > 4_000
+        );
+    }
+    {
+        let mut tx = tx.clone();
+        // Bytecode with all words distinct cannot be compressed efficiently.
+        let bytecode: Vec<u8> = (0..65_u64)
+            .flat_map(|i| {
+                [i.to_be_bytes(), (i + 1_000).to_be_bytes()]
+                    .concat()
+                    .repeat(2)
+            })
+            .collect();
+        tx.execute.factory_deps = vec![bytecode.clone(), bytecode];
+        let err = tx_sender
+            .validate_tx(&tx, ProtocolVersionId::latest())
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            SubmitTxError::CompressedBytecodesTooLarge(size, 1_000) if size > 1_000
+        );
+    }
+}
+
 #[tokio::test]
 async fn sending_transfer() {
     let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
@@ -202,6 +273,32 @@ async fn sending_transfer() {
     assert!(!vm_result.result.is_failed(), "{vm_result:?}");
 }
 
+#[tokio::test]
+async fn sending_transfer_with_paused_tx_intake() {
+    let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let tx_sender = create_real_tx_sender(pool).await;
+    let block_args = pending_block_args(&tx_sender).await;
+    let mut alice = Account::random();
+
+    let storage = tx_sender.acquire_replica_connection().await.unwrap();
+    StateBuilder::default()
+        .with_balance(alice.address(), u64::MAX.into())
+        .apply(storage)
+        .await;
+
+    assert!(!tx_sender.set_tx_intake_paused(true));
+    let transfer = alice.create_transfer(1_000_000_000.into());
+    let err = tx_sender
+        .submit_tx(transfer.clone(), block_args.clone())
+        .await
+        .unwrap_err();
+    assert_matches!(err, SubmitTxError::TxIntakePaused);
+
+    assert!(tx_sender.set_tx_intake_paused(false));
+    let vm_result = tx_sender.submit_tx(transfer, block_args).await.unwrap();
+    assert!(!vm_result.result.is_failed(), "{vm_result:?}");
+}
+
 #[tokio::test]
 async fn sending_transfer_with_insufficient_balance() {
     let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
@@ -419,3 +516,88 @@ async fn submitting_evm_deployment() {
     let vm_result = tx_sender.submit_tx(tx, block_args).await.unwrap();
     assert_matches!(&vm_result.result, ExecutionResult::Success { .. });
 }
+
+#[tokio::test]
+async fn policy_enforcing_sink_rejects_and_persists_violations() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let tx = create_l2_transaction(10, 100);
+    let initiator = tx.initiator_account();
+    let policy = TxPolicy::new(&TxPolicyConfig {
+        denied_addresses: vec![initiator],
+        ..TxPolicyConfig::default()
+    });
+    let sink = PolicyEnforcingSink::new(
+        MasterPoolSink::new(pool.clone()),
+        Arc::new(policy),
+        pool.clone(),
+    );
+
+    let execution_output = SandboxExecutionOutput::mock_success();
+    let err = sink
+        .submit_tx(&tx, &execution_output, ValidationTraces::default())
+        .await
+        .unwrap_err();
+    assert_matches!(
+        err,
+        SubmitTxError::PolicyViolation(TxPolicyViolation::AddressDenied(address))
+            if address == initiator
+    );
+
+    let mut storage = pool.connection().await.unwrap();
+    let decisions = storage
+        .tx_policy_decisions_dal()
+        .get_latest_decisions(Some(initiator), 10)
+        .await
+        .unwrap();
+    assert_eq!(decisions.len(), 1);
+    assert_eq!(decisions[0].transaction_hash, tx.hash());
+    assert_eq!(decisions[0].stage, "submission");
+    assert_eq!(decisions[0].rule, "address_denied");
+    let stored_tx = storage
+        .transactions_dal()
+        .get_storage_tx_by_hash(tx.hash())
+        .await
+        .unwrap();
+    assert!(stored_tx.is_none());
+}
+
+#[tokio::test]
+async fn policy_enforcing_sink_rate_limits_initiators() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let policy = TxPolicy::new(&TxPolicyConfig {
+        max_txs_per_address_per_minute: Some(1),
+        ..TxPolicyConfig::default()
+    });
+    let sink = PolicyEnforcingSink::new(
+        MasterPoolSink::new(pool.clone()),
+        Arc::new(policy),
+        pool.clone(),
+    );
+
+    let execution_output = SandboxExecutionOutput::mock_success();
+    let tx = create_l2_transaction(10, 100);
+    sink.submit_tx(&tx, &execution_output, ValidationTraces::default())
+        .await
+        .unwrap();
+    // Use a transaction with the same initiator.
+    let mut next_tx = tx.clone();
+    next_tx.common_data.nonce = Nonce(1);
+    next_tx.set_input(vec![1], H256::repeat_byte(1));
+    let err = sink
+        .submit_tx(&next_tx, &execution_output, ValidationTraces::default())
+        .await
+        .unwrap_err();
+    assert_matches!(
+        err,
+        SubmitTxError::PolicyViolation(TxPolicyViolation::RateLimitExceeded(_, 1))
+    );
+
+    // Rate limit violations are not persisted.
+    let mut storage = pool.connection().await.unwrap();
+    let decisions = storage
+        .tx_policy_decisions_dal()
+        .get_latest_decisions(None, 10)
+        .await
+        .unwrap();
+    assert!(decisions.is_empty());
+}
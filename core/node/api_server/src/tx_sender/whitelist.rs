@@ -5,8 +5,9 @@ use serde::Deserialize;
 use tokio::sync::{watch, RwLock};
 use zksync_config::configs::api::DeploymentAllowlist;
 use zksync_dal::transactions_dal::L2TxSubmissionResult;
-use zksync_multivm::interface::{tracer::ValidationTraces, VmEvent};
-use zksync_types::{h256_to_address, l2::L2Tx, Address, CONTRACT_DEPLOYER_ADDRESS};
+use zksync_multivm::interface::tracer::ValidationTraces;
+use zksync_state_keeper::tx_policy::deployer_addresses;
+use zksync_types::{l2::L2Tx, Address};
 
 use crate::{
     execution_sandbox::SandboxExecutionOutput,
@@ -43,17 +44,7 @@ impl TxSink for WhitelistedDeployPoolSink {
         //   event ContractDeployed(address indexed deployerAddress, bytes32 indexed bytecodeHash, address indexed contractAddress);
         // We extract the deployer address from topic[1] and verify it is whitelisted.
 
-        let deployer_addresses = execution_output.events.iter().filter_map(|event| {
-            let is_contract_deployed = event.address == CONTRACT_DEPLOYER_ADDRESS
-                && event.indexed_topics.first() == Some(&VmEvent::DEPLOY_EVENT_SIGNATURE);
-            if is_contract_deployed {
-                event.indexed_topics.get(1).map(h256_to_address)
-            } else {
-                None
-            }
-        });
-
-        for deployer_address in deployer_addresses {
+        for deployer_address in deployer_addresses(&execution_output.events) {
             if !self
                 .shared_allow_list
                 .is_address_allowed(&deployer_address)
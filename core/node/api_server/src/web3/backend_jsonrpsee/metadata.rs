@@ -9,7 +9,7 @@ use zksync_web3_decl::{error::Web3Error, jsonrpsee::MethodResponse};
 #[cfg(test)]
 use super::testonly::RecordedMethodCalls;
 use crate::{
-    execution_sandbox::SANDBOX_METRICS,
+    execution_sandbox::{VmPermitWait, SANDBOX_METRICS},
     web3::metrics::{ObservedRpcParams, API_METRICS},
 };
 
@@ -24,26 +24,38 @@ pub(crate) struct MethodMetadata {
     pub block_diff: Option<u32>,
     /// Did this call return an app-level error?
     pub has_app_error: bool,
+    /// Identity of the operator performing the call. Only set for authenticated servers.
+    pub operator: Option<Arc<str>>,
+    /// Time spent by the call waiting for VM permits.
+    pub vm_permit_wait: VmPermitWait,
 }
 
 impl MethodMetadata {
-    fn new(name: &'static str) -> Self {
+    fn new(name: &'static str, operator: Option<Arc<str>>) -> Self {
         Self {
             name,
             started_at: Instant::now(),
             block_id: None,
             block_diff: None,
             has_app_error: false,
+            operator,
+            vm_permit_wait: VmPermitWait::default(),
         }
     }
 }
 
+/// Identity of the operator calling methods on an authenticated server. Attached to HTTP requests as an extension
+/// once they are authenticated.
+#[derive(Debug, Clone)]
+pub(crate) struct OperatorIdentity(pub Arc<str>);
+
 type CurrentMethodInner = RefCell<Option<MethodMetadata>>;
 
 #[must_use = "guard will reset method metadata on drop"]
 #[derive(Debug)]
 pub(super) struct CurrentMethodGuard<'a> {
     prev: Option<MethodMetadata>,
+    prev_vm_permit_wait: Option<VmPermitWait>,
     current: &'a mut MethodMetadata,
     thread_local: &'a ThreadLocal<CurrentMethodInner>,
 }
@@ -52,6 +64,7 @@ impl Drop for CurrentMethodGuard<'_> {
     fn drop(&mut self) {
         let cell = self.thread_local.get_or_default();
         *self.current = mem::replace(&mut *cell.borrow_mut(), self.prev.take()).unwrap();
+        self.current.vm_permit_wait = VmPermitWait::exit(self.prev_vm_permit_wait.take());
     }
 }
 
@@ -96,15 +109,23 @@ impl MethodTracer {
         }
     }
 
+    /// Returns the identity of the operator performing the current JSON-RPC method call, if the call was authenticated.
+    pub(crate) fn operator(&self) -> Option<Arc<str>> {
+        let cell = self.inner.get_or_default();
+        let metadata = cell.borrow();
+        metadata.as_ref()?.operator.clone()
+    }
+
     pub(super) fn new_call<'a>(
         self: &Arc<Self>,
         name: &'static str,
         raw_params: ObservedRpcParams<'a>,
+        operator: Option<Arc<str>>,
     ) -> MethodCall<'a> {
         MethodCall {
             tracer: self.clone(),
             params: raw_params,
-            meta: MethodMetadata::new(name),
+            meta: MethodMetadata::new(name, operator),
             is_completed: false,
         }
     }
@@ -151,8 +172,10 @@ impl MethodCall<'_> {
         let meta = &mut self.meta;
         let cell = self.tracer.inner.get_or_default();
         let prev = mem::replace(&mut *cell.borrow_mut(), Some(meta.clone()));
+        let prev_vm_permit_wait = meta.vm_permit_wait.enter();
         CurrentMethodGuard {
             prev,
+            prev_vm_permit_wait,
             current: meta,
             thread_local: &self.tracer.inner,
         }
@@ -162,7 +185,8 @@ impl MethodCall<'_> {
         self.is_completed = true;
         let meta = &self.meta;
         let params = &self.params;
-        match response.as_error_code() {
+        let error_code = response.as_error_code();
+        match error_code {
             None => {
                 API_METRICS.observe_response_size(meta.name, params, response.as_result().len());
             }
@@ -175,7 +199,7 @@ impl MethodCall<'_> {
                 );
             }
         }
-        API_METRICS.observe_latency(meta, params);
+        API_METRICS.observe_latency(meta, params, error_code.is_some());
         #[cfg(test)]
         self.tracer.recorder.observe_response(meta, response);
     }
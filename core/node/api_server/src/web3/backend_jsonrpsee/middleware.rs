@@ -29,7 +29,7 @@ use zksync_web3_decl::jsonrpsee::{
     MethodResponse,
 };
 
-use super::metadata::{MethodCall, MethodTracer};
+use super::metadata::{MethodCall, MethodTracer, OperatorIdentity};
 use crate::web3::metrics::{ObservedRpcParams, API_METRICS};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
@@ -142,7 +142,13 @@ where
         } else {
             ObservedRpcParams::Unknown
         };
-        let call = self.method_tracer.new_call(method_name, observed_params);
+        let operator = request
+            .extensions()
+            .get::<OperatorIdentity>()
+            .map(|identity| identity.0.clone());
+        let call = self
+            .method_tracer
+            .new_call(method_name, observed_params, operator);
         WithMethodCall::new(self.inner.call(request), call)
     }
 }
@@ -340,6 +346,7 @@ mod tests {
     use zksync_web3_decl::jsonrpsee::{types::Id, ResponsePayload};
 
     use super::*;
+    use crate::execution_sandbox::VmConcurrencyLimiter;
 
     #[test_casing(4, Product(([false, true], [false, true])))]
     #[tokio::test(flavor = "multi_thread")]
@@ -375,7 +382,7 @@ mod tests {
 
             WithMethodCall::new(
                 inner,
-                method_tracer.new_call("test", ObservedRpcParams::None),
+                method_tracer.new_call("test", ObservedRpcParams::None, None),
             )
         });
 
@@ -398,6 +405,43 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn metadata_middleware_tracks_vm_permit_wait() {
+        let method_tracer = Arc::new(MethodTracer::default());
+        let (limiter, _barrier) = VmConcurrencyLimiter::new(1);
+        let limiter = Arc::new(limiter);
+        let permit = limiter.acquire().await.unwrap();
+
+        let inner = {
+            let limiter = limiter.clone();
+            async move {
+                let _permit = limiter.acquire().await.unwrap();
+                MethodResponse::response(
+                    Id::Number(1),
+                    ResponsePayload::success("{}".to_string()),
+                    usize::MAX,
+                )
+            }
+        };
+        let call = WithMethodCall::new(
+            inner,
+            method_tracer.new_call("test", ObservedRpcParams::None, None),
+        );
+        let call = tokio::spawn(call);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        drop(permit);
+        call.await.unwrap();
+
+        let calls = method_tracer.recorded_calls().take();
+        assert_eq!(calls.len(), 1);
+        let vm_permit_wait = calls[0].metadata.vm_permit_wait;
+        assert_eq!(vm_permit_wait.permits, 1);
+        assert!(
+            vm_permit_wait.total >= Duration::from_millis(10),
+            "{vm_permit_wait:?}"
+        );
+    }
+
     #[tokio::test]
     async fn traffic_tracker_basics() {
         let traffic_tracker = TrafficTracker::default();
@@ -4,11 +4,14 @@
 /// namespace structures defined in `zksync_core`.
 use zksync_web3_decl::{
     error::Web3Error,
-    jsonrpsee::types::{error::ErrorCode, ErrorObjectOwned},
+    jsonrpsee::{
+        core::ClientError,
+        types::{error::ErrorCode, ErrorObjectOwned},
+    },
 };
 
 pub(crate) use self::{
-    metadata::{MethodMetadata, MethodTracer},
+    metadata::{MethodMetadata, MethodTracer, OperatorIdentity},
     middleware::{
         CorrelationMiddleware, LimitMiddleware, MetadataLayer, ShutdownMiddleware, TrafficTracker,
     },
@@ -25,9 +28,22 @@ impl MethodTracer {
     pub(crate) fn map_err(&self, err: Web3Error) -> ErrorObjectOwned {
         self.observe_error(&err);
 
+        if let Web3Error::ProxyError(proxy_err) = &err {
+            // Surface errors returned by the main node verbatim, so that the caller gets the same error code, message
+            // and data (e.g., revert reason) as if it has called the main node directly.
+            if let ClientError::Call(main_node_err) = proxy_err.as_ref() {
+                return main_node_err.clone();
+            }
+        }
+
         let data = match &err {
-            Web3Error::SubmitTransactionError(_, data) => Some(format!("0x{}", hex::encode(data))),
-            Web3Error::ProxyError(_) => Some("0x".to_owned()),
+            Web3Error::SubmitTransactionError(_, data) => {
+                Some(format!("0x{}", hex::encode(data)).into())
+            }
+            Web3Error::ProxyError(_) => Some("0x".into()),
+            Web3Error::ServerOverloaded(retry_after) => Some(serde_json::json!({
+                "retryAfterMs": retry_after.as_millis() as u64,
+            })),
             _ => None,
         };
         let code = match err {
@@ -36,6 +52,7 @@ impl MethodTracer {
             Web3Error::NoBlock
             | Web3Error::PrunedBlock(_)
             | Web3Error::PrunedL1Batch(_)
+            | Web3Error::PrunedData(..)
             | Web3Error::TooManyTopics
             | Web3Error::FilterNotFound
             | Web3Error::InvalidFilterBlockHash
@@ -44,7 +61,9 @@ impl MethodTracer {
             | Web3Error::SerializationError(_)
             | Web3Error::ProxyError(_) => 3,
             Web3Error::TreeApiUnavailable => 6,
-            Web3Error::ServerShuttingDown => ErrorCode::ServerIsBusy.code(),
+            Web3Error::ServerShuttingDown | Web3Error::ServerOverloaded(_) => {
+                ErrorCode::ServerIsBusy.code()
+            }
         };
         let message = match err {
             // Do not expose internal error details to the client.
@@ -64,6 +83,7 @@ impl From<SubmitTxError> for Web3Error {
             SubmitTxError::Internal(err) => Self::InternalError(err),
             SubmitTxError::ProxyError(err) => Self::ProxyError(err),
             SubmitTxError::ServerShuttingDown => Self::ServerShuttingDown,
+            SubmitTxError::ServerOverloaded(retry_after) => Self::ServerOverloaded(retry_after),
             _ => Self::SubmitTransactionError(err.to_string(), err.data()),
         }
     }
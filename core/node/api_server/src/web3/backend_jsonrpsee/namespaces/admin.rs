@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use zksync_types::{
+    api::{
+        AuditLogEntry, ComponentQueues, SealCriteriaSimulation, SequencerLease, TxPolicyDecision,
+    },
+    Address, L1BatchNumber,
+};
+use zksync_web3_decl::{jsonrpsee::core::RpcResult, namespaces::AdminNamespaceServer};
+
+use crate::web3::namespaces::AdminNamespace;
+
+#[async_trait]
+impl AdminNamespaceServer for AdminNamespace {
+    async fn pause_tx_intake(&self) -> RpcResult<bool> {
+        self.pause_tx_intake_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn resume_tx_intake(&self) -> RpcResult<bool> {
+        self.resume_tx_intake_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn seal_l1_batch(&self) -> RpcResult<()> {
+        self.seal_l1_batch_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn set_proof_priority(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        priority: i32,
+    ) -> RpcResult<bool> {
+        self.set_proof_priority_impl(l1_batch_number, priority)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_component_queues(&self) -> RpcResult<ComponentQueues> {
+        self.get_component_queues_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_sequencer_lease(&self) -> RpcResult<Option<SequencerLease>> {
+        self.get_sequencer_lease_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn promote_sequencer(&self, instance_id: String) -> RpcResult<SequencerLease> {
+        self.promote_sequencer_impl(instance_id)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_audit_log(
+        &self,
+        from_id: u64,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<AuditLogEntry>> {
+        self.get_audit_log_impl(from_id, limit)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn simulate_batch_sealing(
+        &self,
+        limit: Option<usize>,
+    ) -> RpcResult<SealCriteriaSimulation> {
+        self.simulate_batch_sealing_impl(limit)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_tx_policy_decisions(
+        &self,
+        address: Option<Address>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<TxPolicyDecision>> {
+        self.get_tx_policy_decisions_impl(address, limit)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+}
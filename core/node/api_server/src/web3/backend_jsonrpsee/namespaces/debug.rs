@@ -1,5 +1,8 @@
 use zksync_types::{
-    api::{BlockId, BlockNumber, CallTracerBlockResult, CallTracerResult, TracerConfig},
+    api::{
+        BlockId, BlockNumber, CallTracerBlockResult, CallTracerResult, FeeInputOverride,
+        TracerConfig,
+    },
     transaction_request::CallRequest,
     H256,
 };
@@ -37,8 +40,9 @@ impl DebugNamespaceServer for DebugNamespace {
         request: CallRequest,
         block: Option<BlockId>,
         options: Option<TracerConfig>,
+        fee_input_override: Option<FeeInputOverride>,
     ) -> RpcResult<CallTracerResult> {
-        self.debug_trace_call_impl(request, block, options)
+        self.debug_trace_call_impl(request, block, options, fee_input_override)
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
@@ -1,7 +1,7 @@
 use zksync_types::{
     api::{
         state_override::StateOverride, Block, BlockId, BlockIdVariant, BlockNumber, FeeHistory,
-        Log, Transaction, TransactionId, TransactionReceipt, TransactionVariant,
+        FeeInputOverride, Log, Transaction, TransactionId, TransactionReceipt, TransactionVariant,
     },
     transaction_request::CallRequest,
     web3::{Bytes, Index, SyncState, U64Number},
@@ -43,8 +43,9 @@ impl EthNamespaceServer for EthNamespace {
         req: CallRequest,
         block: Option<BlockNumber>,
         state_override: Option<StateOverride>,
+        fee_input_override: Option<FeeInputOverride>,
     ) -> RpcResult<U256> {
-        self.estimate_gas_impl(req, block, state_override)
+        self.estimate_gas_impl(req, block, state_override, fee_input_override)
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
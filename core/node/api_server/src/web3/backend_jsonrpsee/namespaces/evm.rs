@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use zksync_types::U64;
+use zksync_web3_decl::{jsonrpsee::core::RpcResult, namespaces::EvmNamespaceServer};
+
+use crate::web3::namespaces::EvmNamespace;
+
+#[async_trait]
+impl EvmNamespaceServer for EvmNamespace {
+    async fn mine(&self) -> RpcResult<U64> {
+        self.mine_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn increase_time(&self, seconds: u64) -> RpcResult<u64> {
+        self.increase_time_impl(seconds)
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn snapshot(&self) -> RpcResult<U64> {
+        self.snapshot_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn revert(&self, snapshot_id: U64) -> RpcResult<bool> {
+        self.revert_impl(snapshot_id)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+}
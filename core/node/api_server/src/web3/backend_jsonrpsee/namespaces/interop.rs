@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use zksync_types::{api::L2ToL1MessageStatus, L1BatchNumber, H256};
+use zksync_web3_decl::{jsonrpsee::core::RpcResult, namespaces::InteropNamespaceServer};
+
+use crate::web3::namespaces::InteropNamespace;
+
+#[async_trait]
+impl InteropNamespaceServer for InteropNamespace {
+    async fn get_l2_to_l1_messages_by_tx_hash(
+        &self,
+        tx_hash: H256,
+    ) -> RpcResult<Vec<L2ToL1MessageStatus>> {
+        self.get_l2_to_l1_messages_by_tx_hash_impl(tx_hash)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_l2_to_l1_messages_by_l1_batch(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Vec<L2ToL1MessageStatus>> {
+        self.get_l2_to_l1_messages_by_l1_batch_impl(l1_batch_number)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+}
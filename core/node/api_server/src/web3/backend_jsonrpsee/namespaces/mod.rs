@@ -1,6 +1,9 @@
+pub mod admin;
 pub mod debug;
 pub mod en;
 pub mod eth;
+pub mod evm;
+pub mod interop;
 pub mod net;
 pub mod snapshots;
 pub mod unstable;
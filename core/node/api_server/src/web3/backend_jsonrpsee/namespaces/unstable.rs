@@ -1,6 +1,7 @@
 use zksync_types::{
     api::{
-        ChainAggProof, DataAvailabilityDetails, L1ToL2TxsStatus, TeeProof, TransactionExecutionInfo,
+        ChainAggProof, DataAvailabilityDetails, L1ToL2TxsStatus, ProtocolUpgradeDryRun, TeeProof,
+        TransactionExecutionInfo,
     },
     tee_types::TeeType,
     L1BatchNumber, L2ChainId, H256,
@@ -67,4 +68,13 @@ impl UnstableNamespaceServer for UnstableNamespace {
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn get_protocol_upgrade_dry_run(
+        &self,
+        version_id: u16,
+    ) -> RpcResult<Option<ProtocolUpgradeDryRun>> {
+        self.get_protocol_upgrade_dry_run_impl(version_id)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
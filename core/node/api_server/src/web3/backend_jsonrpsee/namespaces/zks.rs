@@ -1,9 +1,13 @@
 use std::collections::HashMap;
 
+use chrono::NaiveDate;
 use zksync_types::{
     api::{
-        state_override::StateOverride, BlockDetails, BridgeAddresses, L1BatchDetails,
-        L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetailedResult, TransactionDetails,
+        state_override::StateOverride, AccountActivity, AccountNonceDetails, BlockDetails,
+        BlockIdVariant, BridgeAddresses, ContractUsage, ContractUsageMetric, DisassembledBytecode,
+        FeeInputOverride, FeeWithSensitivity, InclusionStats, L1BatchDetails, L2ToL1LogProof,
+        MultiProof, Proof, ProtocolVersion, ProtocolVersionActivation, SoftConfirmation,
+        StorageSlotWriter, TransactionDetailedResult, TransactionDetails, TransactionReceipt,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -24,8 +28,20 @@ impl ZksNamespaceServer for ZksNamespace {
         &self,
         req: CallRequest,
         state_override: Option<StateOverride>,
+        fee_input_override: Option<FeeInputOverride>,
     ) -> RpcResult<Fee> {
-        self.estimate_fee_impl(req, state_override)
+        self.estimate_fee_impl(req, state_override, fee_input_override)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn estimate_fee_with_sensitivity(
+        &self,
+        req: CallRequest,
+        state_override: Option<StateOverride>,
+        fee_input_override: Option<FeeInputOverride>,
+    ) -> RpcResult<FeeWithSensitivity> {
+        self.estimate_fee_with_sensitivity_impl(req, state_override, fee_input_override)
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
@@ -40,6 +56,16 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn simulate_l1_to_l2_transaction(
+        &self,
+        req: CallRequest,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<TransactionReceipt> {
+        self.simulate_l1_to_l2_transaction_impl(req, state_override)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_bridgehub_contract(&self) -> RpcResult<Option<Address>> {
         Ok(self.get_bridgehub_contract_impl())
     }
@@ -152,6 +178,23 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_storage_slot_writer(
+        &self,
+        address: Address,
+        key: H256,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<Option<StorageSlotWriter>> {
+        self.get_storage_slot_writer_impl(address, key, block.map(Into::into))
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn disassemble_bytecode(&self, hash: H256) -> RpcResult<Option<DisassembledBytecode>> {
+        self.disassemble_bytecode_impl(hash)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     // to be removed in favor of `get_batch_fee_input`
     async fn get_l1_gas_price(&self) -> RpcResult<U64> {
         match self.get_batch_fee_input_impl().await {
@@ -179,6 +222,12 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_protocol_versions(&self) -> RpcResult<Vec<ProtocolVersionActivation>> {
+        self.get_protocol_versions_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_proof(
         &self,
         address: Address,
@@ -190,6 +239,17 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_multi_proof(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<MultiProof>> {
+        self.get_multi_proof_impl(address, keys, l1_batch_number)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_base_token_l1_address(&self) -> RpcResult<Address> {
         self.get_base_token_l1_address_impl()
             .map_err(|err| self.current_method().map_err(err))
@@ -208,4 +268,44 @@ impl ZksNamespaceServer for ZksNamespace {
         self.get_l2_multicall3_impl()
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn get_soft_confirmation(&self, tx_hash: H256) -> RpcResult<Option<SoftConfirmation>> {
+        self.get_soft_confirmation_impl(tx_hash)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_account_activity(
+        &self,
+        address: Address,
+        from_block: L2BlockNumber,
+        to_block: L2BlockNumber,
+    ) -> RpcResult<AccountActivity> {
+        self.get_account_activity_impl(address, from_block, to_block)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_account_nonce_details(&self, address: Address) -> RpcResult<AccountNonceDetails> {
+        self.get_account_nonce_details_impl(address)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_inclusion_stats(&self) -> RpcResult<Vec<InclusionStats>> {
+        self.get_inclusion_stats_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_top_contracts(
+        &self,
+        day: Option<NaiveDate>,
+        metric: Option<ContractUsageMetric>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<ContractUsage>> {
+        self.get_top_contracts_impl(day, metric, limit)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
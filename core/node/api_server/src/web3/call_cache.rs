@@ -0,0 +1,145 @@
+//! Cache for `eth_call` results.
+
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use lru::LruCache;
+use serde::Serialize;
+use zksync_types::{api::state_override::StateOverride, transaction_request::CallRequest, H256};
+
+use super::metrics::{EthCallCacheResult, ETH_CALL_CACHE_METRICS};
+
+/// Key of a cached `eth_call` result.
+///
+/// The key includes the hash of the block the call is executed on (rather than its number), so that cached results
+/// are never served for a block replaced during a reorg.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct EthCallCacheKey {
+    block_hash: H256,
+    request_hash: H256,
+    state_override_hash: Option<H256>,
+}
+
+impl EthCallCacheKey {
+    pub fn new(
+        block_hash: H256,
+        request: &CallRequest,
+        state_override: Option<&StateOverride>,
+    ) -> Self {
+        Self {
+            block_hash,
+            request_hash: canonical_hash(request),
+            state_override_hash: state_override.map(canonical_hash),
+        }
+    }
+
+    pub fn block_hash(&self) -> H256 {
+        self.block_hash
+    }
+}
+
+/// Hashes the JSON representation of the value. The value is first converted to [`serde_json::Value`] so that
+/// map keys are sorted, which makes the hash independent of the iteration order of `HashMap`s (e.g., in [`StateOverride`]).
+fn canonical_hash(value: &impl Serialize) -> H256 {
+    let value = serde_json::to_value(value).expect("failed serializing value");
+    let bytes = serde_json::to_vec(&value).expect("failed serializing value");
+    H256(zksync_types::web3::keccak256(&bytes))
+}
+
+/// LRU cache for results of `eth_call` executed on sealed L2 blocks. Since such calls are deterministic,
+/// their results can be reused across requests. Calls on the pending block must not be cached.
+#[derive(Debug, Clone)]
+pub struct EthCallCache(Arc<Mutex<LruCache<EthCallCacheKey, Vec<u8>>>>);
+
+impl EthCallCache {
+    /// Creates a cache with the specified max number of entries.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self(Arc::new(Mutex::new(LruCache::new(capacity))))
+    }
+
+    pub(crate) fn get(&self, key: &EthCallCacheKey) -> Option<Vec<u8>> {
+        let output = self
+            .0
+            .lock()
+            .expect("eth_call cache is poisoned")
+            .get(key)
+            .cloned();
+        let result = if output.is_some() {
+            EthCallCacheResult::Hit
+        } else {
+            EthCallCacheResult::Miss
+        };
+        ETH_CALL_CACHE_METRICS.lookups[&result].inc();
+        output
+    }
+
+    pub(crate) fn insert(&self, key: EthCallCacheKey, output: Vec<u8>) {
+        let mut cache = self.0.lock().expect("eth_call cache is poisoned");
+        cache.put(key, output);
+        ETH_CALL_CACHE_METRICS.len.set(cache.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use zksync_types::{
+        api::state_override::{OverrideAccount, OverrideState},
+        Address,
+    };
+
+    use super::*;
+
+    fn state_override(slots: impl Iterator<Item = u64>) -> StateOverride {
+        let state = slots
+            .map(|i| (H256::from_low_u64_be(i), H256::repeat_byte(1)))
+            .collect();
+        let account = OverrideAccount {
+            state: Some(OverrideState::State(state)),
+            ..OverrideAccount::default()
+        };
+        StateOverride::new(HashMap::from([(Address::repeat_byte(1), account)]))
+    }
+
+    #[test]
+    fn cache_key_does_not_depend_on_map_ordering() {
+        let request = CallRequest {
+            to: Some(Address::repeat_byte(2)),
+            ..CallRequest::default()
+        };
+        let block_hash = H256::repeat_byte(3);
+        let key = EthCallCacheKey::new(block_hash, &request, Some(&state_override(0..100)));
+        let reversed_key =
+            EthCallCacheKey::new(block_hash, &request, Some(&state_override((0..100).rev())));
+        assert_eq!(key, reversed_key);
+
+        let other_key = EthCallCacheKey::new(block_hash, &request, Some(&state_override(0..99)));
+        assert_ne!(key, other_key);
+        let no_override_key = EthCallCacheKey::new(block_hash, &request, None);
+        assert_ne!(key, no_override_key);
+        let other_block_key = EthCallCacheKey::new(
+            H256::repeat_byte(4),
+            &request,
+            Some(&state_override(0..100)),
+        );
+        assert_ne!(key, other_block_key);
+    }
+
+    #[test]
+    fn cache_basics() {
+        let cache = EthCallCache::new(NonZeroUsize::new(1).unwrap());
+        let request = CallRequest::default();
+        let key = EthCallCacheKey::new(H256::repeat_byte(1), &request, None);
+        assert_eq!(cache.get(&key), None);
+        cache.insert(key, vec![1, 2, 3]);
+        assert_eq!(cache.get(&key), Some(vec![1, 2, 3]));
+
+        let other_key = EthCallCacheKey::new(H256::repeat_byte(2), &request, None);
+        cache.insert(other_key, vec![4]);
+        assert_eq!(cache.get(&key), None);
+        assert_eq!(cache.get(&other_key), Some(vec![4]));
+    }
+}
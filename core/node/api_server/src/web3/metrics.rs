@@ -158,6 +158,19 @@ impl From<&MethodMetadata> for MethodLabels {
         }
     }
 }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+enum CallStatus {
+    Success,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
+struct MethodStatusLabels {
+    method: &'static str,
+    status: CallStatus,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
 #[metrics(rename_all = "snake_case")]
 enum Web3ErrorKind {
@@ -171,6 +184,7 @@ enum Web3ErrorKind {
     LogsLimitExceeded,
     InvalidFilterBlockHash,
     TreeApiUnavailable,
+    Overloaded,
     Internal,
 }
 
@@ -178,7 +192,9 @@ impl Web3ErrorKind {
     fn new(err: &Web3Error) -> Self {
         match err {
             Web3Error::NoBlock => Self::NoBlock,
-            Web3Error::PrunedBlock(_) | Web3Error::PrunedL1Batch(_) => Self::Pruned,
+            Web3Error::PrunedBlock(_) | Web3Error::PrunedL1Batch(_) | Web3Error::PrunedData(..) => {
+                Self::Pruned
+            }
             Web3Error::SubmitTransactionError(..) => Self::SubmitTransaction,
             Web3Error::ProxyError(_) => Self::Proxy,
             Web3Error::SerializationError(_) => Self::TransactionSerialization,
@@ -187,6 +203,7 @@ impl Web3ErrorKind {
             Web3Error::LogsLimitExceeded(..) => Self::LogsLimitExceeded,
             Web3Error::InvalidFilterBlockHash => Self::InvalidFilterBlockHash,
             Web3Error::TreeApiUnavailable => Self::TreeApiUnavailable,
+            Web3Error::ServerOverloaded(_) => Self::Overloaded,
             Web3Error::InternalError(_)
             | Web3Error::MethodNotImplemented
             | Web3Error::ServerShuttingDown => Self::Internal,
@@ -249,6 +266,14 @@ pub(crate) struct ApiMetrics {
     web3_call: Family<MethodLabels, Histogram<Duration>>,
     #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
     web3_dropped_call_latency: Family<MethodLabels, Histogram<Duration>>,
+    /// Latency of a Web3 call grouped by the method name and call status (i.e., whether the call resulted in an error).
+    /// Unlike `web3_call`, doesn't have block-related labels, which makes it easier to compare method latencies
+    /// across deployments.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    web3_method_latency: Family<MethodStatusLabels, Histogram<Duration>>,
+    /// Total time a Web3 call spent waiting for VM permits. Only recorded for calls that have acquired at least one permit.
+    #[metrics(buckets = Buckets::LATENCIES, labels = ["method"], unit = Unit::Seconds)]
+    web3_call_vm_permit_wait: LabeledFamily<&'static str, Histogram<Duration>>,
     /// Difference between the latest sealed L2 block and the resolved L2 block for a web3 call.
     #[metrics(buckets = BLOCK_DIFF_BUCKETS, labels = ["method"])]
     web3_call_block_diff: LabeledFamily<&'static str, Histogram<u64>>,
@@ -307,15 +332,28 @@ impl ApiMetrics {
         &self,
         meta: &MethodMetadata,
         raw_params: &ObservedRpcParams<'_>,
+        is_error: bool,
     ) {
         static FILTER: ReportFilter = report_filter!(Duration::from_secs(1));
         const MIN_REPORTED_LATENCY: Duration = Duration::from_secs(5);
 
         let latency = meta.started_at.elapsed();
         self.web3_call[&MethodLabels::from(meta)].observe(latency);
+        let status_labels = MethodStatusLabels {
+            method: meta.name,
+            status: if is_error {
+                CallStatus::Error
+            } else {
+                CallStatus::Success
+            },
+        };
+        self.web3_method_latency[&status_labels].observe(latency);
         if let Some(block_diff) = meta.block_diff {
             self.web3_call_block_diff[&meta.name].observe(block_diff.into());
         }
+        if meta.vm_permit_wait.permits > 0 {
+            self.web3_call_vm_permit_wait[&meta.name].observe(meta.vm_permit_wait.total);
+        }
         if latency >= MIN_REPORTED_LATENCY && FILTER.should_report() {
             tracing::info!("Long call to `{}`{raw_params}: {latency:?}", meta.name);
         }
@@ -507,6 +545,28 @@ pub(super) struct MempoolCacheMetrics {
 #[vise::register]
 pub(super) static MEMPOOL_CACHE_METRICS: vise::Global<MempoolCacheMetrics> = vise::Global::new();
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "result", rename_all = "snake_case")]
+pub(super) enum EthCallCacheResult {
+    Hit,
+    Miss,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_eth_call_cache")]
+pub(super) struct EthCallCacheMetrics {
+    /// Number of cache lookups for `eth_call` requests grouped by the lookup result.
+    pub lookups: Family<EthCallCacheResult, Counter>,
+    /// Number of `eth_call` results not inserted into the cache because the block they were executed on
+    /// was reverted during execution.
+    pub skipped_inserts: Counter,
+    /// Current number of entries in the cache.
+    pub len: Gauge<usize>,
+}
+
+#[vise::register]
+pub(super) static ETH_CALL_CACHE_METRICS: vise::Global<EthCallCacheMetrics> = vise::Global::new();
+
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
@@ -3,44 +3,51 @@ use std::{collections::HashSet, net::SocketAddr, num::NonZeroU32, sync::Arc, tim
 use anyhow::Context as _;
 use chrono::NaiveDateTime;
 use futures::future;
+use secrecy::ExposeSecret;
 use serde::Deserialize;
 use tokio::{
     sync::{mpsc, oneshot, watch, Mutex},
     task::JoinHandle,
 };
-use tower_http::{cors::CorsLayer, metrics::InFlightRequestsLayer};
+use tower_http::{
+    cors::CorsLayer, metrics::InFlightRequestsLayer, validate_request::ValidateRequestHeaderLayer,
+};
 use zksync_config::configs::api::{MaxResponseSize, MaxResponseSizeOverrides};
 use zksync_dal::{helpers::wait_for_l1_batch, ConnectionPool, Core};
 use zksync_health_check::{HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_metadata_calculator::api_server::TreeApiClient;
 use zksync_node_sync::SyncState;
-use zksync_types::L2BlockNumber;
+use zksync_object_store::ObjectStore;
+use zksync_state_keeper::{DevModeControl, L1BatchSealRequest, PendingBatchStateReader};
+use zksync_types::{secrets::APIKey, L2BlockNumber};
 use zksync_web3_decl::{
     client::{DynClient, L2},
     jsonrpsee::{
         server::{
-            middleware::rpc::either::Either, BatchRequestConfig, RpcServiceBuilder, ServerBuilder,
+            middleware::rpc::either::Either, BatchRequestConfig, HttpBody, HttpRequest,
+            HttpResponse, RpcServiceBuilder, ServerBuilder,
         },
         MethodCallback, Methods, RpcModule,
     },
     namespaces::{
-        DebugNamespaceServer, EnNamespaceServer, EthNamespaceServer, EthPubSubServer,
-        NetNamespaceServer, SnapshotsNamespaceServer, UnstableNamespaceServer, Web3NamespaceServer,
-        ZksNamespaceServer,
+        AdminNamespaceServer, DebugNamespaceServer, EnNamespaceServer, EthNamespaceServer,
+        EthPubSubServer, EvmNamespaceServer, InteropNamespaceServer, NetNamespaceServer,
+        SnapshotsNamespaceServer, UnstableNamespaceServer, Web3NamespaceServer, ZksNamespaceServer,
     },
     types::Filter,
 };
 
 use self::{
     backend_jsonrpsee::{
-        CorrelationMiddleware, LimitMiddleware, MetadataLayer, MethodTracer, ShutdownMiddleware,
-        TrafficTracker,
+        CorrelationMiddleware, LimitMiddleware, MetadataLayer, MethodTracer, OperatorIdentity,
+        ShutdownMiddleware, TrafficTracker,
     },
+    call_cache::EthCallCache,
     mempool_cache::MempoolCache,
     metrics::API_METRICS,
     namespaces::{
-        DebugNamespace, EnNamespace, EthNamespace, NetNamespace, SnapshotsNamespace,
-        UnstableNamespace, Web3Namespace, ZksNamespace,
+        AdminNamespace, DebugNamespace, EnNamespace, EthNamespace, EvmNamespace, InteropNamespace,
+        NetNamespace, SnapshotsNamespace, UnstableNamespace, Web3Namespace, ZksNamespace,
     },
     pubsub::{EthSubscribe, EthSubscriptionIdProvider, PubSubEvent},
     state::{Filters, InternalApiConfig, RpcState, SealedL2BlockNumber},
@@ -52,6 +59,7 @@ use crate::{
 };
 
 pub mod backend_jsonrpsee;
+pub mod call_cache;
 pub mod mempool_cache;
 pub(super) mod metrics;
 pub mod namespaces;
@@ -60,6 +68,7 @@ pub mod state;
 pub mod testonly;
 #[cfg(test)]
 pub(crate) mod tests;
+mod trace_truncation;
 
 /// Timeout for graceful shutdown logic within API servers.
 const GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
@@ -71,6 +80,10 @@ const NO_REQUESTS_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
 /// Time interval with no requests sent to the API server to declare that traffic to the server is ceased,
 /// and start gracefully shutting down the server.
 const SHUTDOWN_INTERVAL_WITHOUT_REQUESTS: Duration = Duration::from_millis(500);
+/// HTTP header identifying the operator calling an authenticated server.
+pub(crate) const OPERATOR_HEADER: &str = "x-operator";
+/// Operator identity recorded for authenticated calls without the [`OPERATOR_HEADER`].
+const DEFAULT_OPERATOR: &str = "admin";
 
 /// Represents all kinds of `Filter`.
 #[derive(Debug, Clone)]
@@ -102,6 +115,14 @@ pub enum Namespace {
     Pubsub,
     Snapshots,
     Unstable,
+    /// Introspection of the outgoing L2-to-L1 message queue.
+    Interop,
+    /// Control actions for node operators. Can only be served by a separate HTTP server
+    /// that requires authentication (see [`ApiBuilder::with_admin_auth_token()`]).
+    Admin,
+    /// Block production control compatible with development nodes. Only functional if the state keeper
+    /// runs in the development mode (see [`ApiBuilder::with_dev_mode_control()`]).
+    Evm,
 }
 
 impl Namespace {
@@ -134,11 +155,18 @@ struct OptionalApiParams {
     batch_request_size_limit: Option<usize>,
     response_body_size_limit: Option<MaxResponseSize>,
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    websocket_requests_per_minute_limit_updates: Option<watch::Receiver<Option<NonZeroU32>>>,
     tree_api: Option<Arc<dyn TreeApiClient>>,
     mempool_cache: Option<MempoolCache>,
+    eth_call_cache: Option<EthCallCache>,
     extended_tracing: bool,
     pub_sub_events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
     l2_l1_log_proof_handler: Option<Box<DynClient<L2>>>,
+    cold_storage: Option<Arc<dyn ObjectStore>>,
+    admin_auth_token: Option<APIKey>,
+    l1_batch_seal_request: Option<L1BatchSealRequest>,
+    dev_mode_control: Option<DevModeControl>,
+    pending_batch_state: Option<PendingBatchStateReader>,
 }
 
 /// Structure capable of spawning a configured Web3 API server along with all the required
@@ -246,6 +274,17 @@ impl ApiBuilder {
         self
     }
 
+    /// Allows changing the requests per minute limit for WebSocket sessions at runtime. The limit provided
+    /// by the receiver takes precedence over [`Self::with_websocket_requests_per_minute_limit()`].
+    /// A changed limit only applies to sessions opened after the change.
+    pub fn with_websocket_requests_per_minute_limit_updates(
+        mut self,
+        updates: watch::Receiver<Option<NonZeroU32>>,
+    ) -> Self {
+        self.optional.websocket_requests_per_minute_limit_updates = Some(updates);
+        self
+    }
+
     pub fn with_sync_state(mut self, sync_state: SyncState) -> Self {
         self.optional.sync_state = Some(sync_state);
         self
@@ -277,6 +316,12 @@ impl ApiBuilder {
         self
     }
 
+    /// Enables caching results of `eth_call` requests executed on sealed L2 blocks.
+    pub fn with_eth_call_cache(mut self, cache: EthCallCache) -> Self {
+        self.optional.eth_call_cache = Some(cache);
+        self
+    }
+
     pub fn with_extended_tracing(mut self, extended_tracing: bool) -> Self {
         self.optional.extended_tracing = extended_tracing;
         self
@@ -306,6 +351,44 @@ impl ApiBuilder {
         self
     }
 
+    /// Sets the object store with call traces and events archived by the cold storage archiver.
+    /// If not set, requests touching archived data will fail.
+    pub fn with_cold_storage(mut self, cold_storage: Arc<dyn ObjectStore>) -> Self {
+        tracing::info!("Using cold storage: {cold_storage:?}");
+        self.optional.cold_storage = Some(cold_storage);
+        self
+    }
+
+    /// Sets the bearer token required from clients of the server. Must be set if the `admin` namespace
+    /// is enabled. Clients may identify the operator performing calls via the `X-Operator` HTTP header;
+    /// the identity is recorded in the audit log.
+    pub fn with_admin_auth_token(mut self, token: APIKey) -> Self {
+        self.optional.admin_auth_token = Some(token);
+        self
+    }
+
+    /// Sets the handle used by the `admin` namespace to force sealing the current L1 batch. If not set
+    /// (e.g., if the state keeper runs in another process), the corresponding method is not available.
+    pub fn with_l1_batch_seal_request(mut self, seal_request: L1BatchSealRequest) -> Self {
+        self.optional.l1_batch_seal_request = Some(seal_request);
+        self
+    }
+
+    /// Sets the handle used by the `evm` namespace to control block production. If not set (e.g., if the state keeper
+    /// doesn't run in the development mode), methods in the namespace are not available.
+    pub fn with_dev_mode_control(mut self, control: DevModeControl) -> Self {
+        self.optional.dev_mode_control = Some(control);
+        self
+    }
+
+    /// Sets the handle to the state of the L1 batch processed by the state keeper. If set, the `pending` block
+    /// reflects transactions that are not sealed yet; otherwise (e.g., if the state keeper runs in another process),
+    /// the `pending` block state is equivalent to the latest sealed L2 block.
+    pub fn with_pending_batch_state(mut self, pending_state: PendingBatchStateReader) -> Self {
+        self.optional.pending_batch_state = Some(pending_state);
+        self
+    }
+
     // Intended for tests only.
     #[doc(hidden)]
     fn with_pub_sub_events(mut self, sender: mpsc::UnboundedSender<PubSubEvent>) -> Self {
@@ -324,7 +407,27 @@ impl ApiBuilder {
 impl ApiBuilder {
     pub fn build(self) -> anyhow::Result<ApiServer> {
         let transport = self.transport.context("API transport not set")?;
+        let is_admin = self
+            .namespaces
+            .as_ref()
+            .is_some_and(|namespaces| namespaces.contains(&Namespace::Admin));
+        if is_admin {
+            anyhow::ensure!(
+                matches!(transport, ApiTransport::Http(_)),
+                "`admin` namespace is only supported for HTTP transport"
+            );
+            anyhow::ensure!(
+                self.namespaces.as_deref() == Some(&[Namespace::Admin]),
+                "`admin` namespace must be served by a separate server without other namespaces"
+            );
+            anyhow::ensure!(
+                self.optional.admin_auth_token.is_some(),
+                "`admin` namespace requires an auth token"
+            );
+        }
+
         let health_check_name = match &transport {
+            ApiTransport::Http(_) if is_admin => "admin_api",
             ApiTransport::Http(_) => "http_api",
             ApiTransport::WebSocket(_) => "ws_api",
         };
@@ -386,10 +489,13 @@ impl ApiServer {
             api_config: self.config,
             start_info,
             mempool_cache: self.optional.mempool_cache,
+            eth_call_cache: self.optional.eth_call_cache,
             last_sealed_l2_block: self.sealed_l2_block_handle,
             bridge_addresses_handle: self.bridge_addresses_handle,
             tree_api: self.optional.tree_api,
             l2_l1_log_proof_handler: self.optional.l2_l1_log_proof_handler,
+            cold_storage: self.optional.cold_storage,
+            pending_batch_state: self.optional.pending_batch_state,
         })
     }
 
@@ -399,6 +505,8 @@ impl ApiServer {
     ) -> anyhow::Result<RpcModule<()>> {
         let namespaces = self.namespaces.clone();
         let zksync_network_id = self.config.l2_chain_id;
+        let l1_batch_seal_request = self.optional.l1_batch_seal_request.clone();
+        let dev_mode_control = self.optional.dev_mode_control.clone();
         let rpc_state = self.build_rpc_state().await?;
 
         // Collect all the methods into a single RPC module.
@@ -437,9 +545,21 @@ impl ApiServer {
                 .context("cannot merge snapshots namespace")?;
         }
         if namespaces.contains(&Namespace::Unstable) {
-            rpc.merge(UnstableNamespace::new(rpc_state).into_rpc())
+            rpc.merge(UnstableNamespace::new(rpc_state.clone()).into_rpc())
                 .context("cannot merge unstable namespace")?;
         }
+        if namespaces.contains(&Namespace::Interop) {
+            rpc.merge(InteropNamespace::new(rpc_state.clone()).into_rpc())
+                .context("cannot merge interop namespace")?;
+        }
+        if namespaces.contains(&Namespace::Evm) {
+            rpc.merge(EvmNamespace::new(rpc_state.clone(), dev_mode_control).into_rpc())
+                .context("cannot merge evm namespace")?;
+        }
+        if namespaces.contains(&Namespace::Admin) {
+            rpc.merge(AdminNamespace::new(rpc_state, l1_batch_seal_request).into_rpc())
+                .context("cannot merge admin namespace")?;
+        }
         Ok(rpc)
     }
 
@@ -636,11 +756,19 @@ impl ApiServer {
             } else {
                 (u32::MAX, MaxResponseSizeOverrides::empty())
             };
-        let websocket_requests_per_minute_limit = self.optional.websocket_requests_per_minute_limit;
+        let websocket_requests_per_minute_limit = self
+            .optional
+            .websocket_requests_per_minute_limit_updates
+            .clone()
+            .unwrap_or_else(|| {
+                // The sender is dropped immediately, so the limit will never change.
+                watch::channel(self.optional.websocket_requests_per_minute_limit).1
+            });
         let subscriptions_limit = self.optional.subscriptions_limit;
         let vm_barrier = self.optional.vm_barrier.clone();
         let health_updater = self.health_updater.clone();
         let method_tracer = self.method_tracer.clone();
+        let admin_auth_token = self.optional.admin_auth_token.clone();
 
         let extended_tracing = self.optional.extended_tracing;
         if extended_tracing {
@@ -672,10 +800,34 @@ impl ApiServer {
                 future::ready(())
             }),
         );
+        // Setup authentication. Only HTTP servers can be authenticated, which is checked when building the server.
+        let auth = admin_auth_token.map(|token| {
+            let expected_header = format!("Bearer {}", token.0.expose_secret());
+            ValidateRequestHeaderLayer::custom(move |request: &mut HttpRequest| {
+                let header = request.headers().get(http::header::AUTHORIZATION);
+                if header.is_some_and(|header| header.as_bytes() == expected_header.as_bytes()) {
+                    let operator = request
+                        .headers()
+                        .get(OPERATOR_HEADER)
+                        .and_then(|header| header.to_str().ok())
+                        .filter(|operator| !operator.is_empty())
+                        .unwrap_or(DEFAULT_OPERATOR);
+                    request
+                        .extensions_mut()
+                        .insert(OperatorIdentity(operator.into()));
+                    Ok(())
+                } else {
+                    let mut response = HttpResponse::new(HttpBody::empty());
+                    *response.status_mut() = http::StatusCode::UNAUTHORIZED;
+                    Err(response)
+                }
+            })
+        });
         // Assemble server middleware.
         let middleware = tower::ServiceBuilder::new()
             .layer(in_flight_requests)
-            .option_layer(cors);
+            .option_layer(cors)
+            .option_layer(auth);
 
         // Settings shared by HTTP and WS servers.
         let max_connections = !is_http
@@ -706,7 +858,7 @@ impl ApiServer {
             // We want to capture limit middleware errors with `metadata_layer`; hence, `LimitMiddleware` is placed after it.
             .option_layer((!is_http).then(|| {
                 tower::layer::layer_fn(move |svc| {
-                    LimitMiddleware::new(svc, websocket_requests_per_minute_limit)
+                    LimitMiddleware::new(svc, *websocket_requests_per_minute_limit.borrow())
                 })
             }));
 
@@ -0,0 +1,316 @@
+use std::time::Duration;
+
+use serde_json::json;
+use zksync_dal::{Connection, Core, CoreDal, DalError};
+use zksync_state_keeper::L1BatchSealRequest;
+use zksync_types::{
+    api::{
+        AuditLogEntry, ComponentQueues, SealCriteriaSimulation, SequencerLease, TxPolicyDecision,
+    },
+    Address, L1BatchNumber,
+};
+use zksync_web3_decl::error::Web3Error;
+
+use crate::{
+    execution_sandbox::BlockArgs,
+    web3::{backend_jsonrpsee::MethodTracer, state::RpcState},
+};
+
+/// Control actions for node operators. Served only by a dedicated server requiring authentication.
+#[derive(Debug, Clone)]
+pub(crate) struct AdminNamespace {
+    state: RpcState,
+    l1_batch_seal_request: Option<L1BatchSealRequest>,
+}
+
+impl AdminNamespace {
+    /// TTL of the lease transferred via [`Self::promote_sequencer_impl()`]. The promoted instance renews the lease
+    /// with its own TTL once it observes the transfer; if it doesn't run, the lease can be taken over after this TTL.
+    const PROMOTED_LEASE_TTL: Duration = Duration::from_secs(30);
+    /// Maximum number of entries returned by [`Self::get_audit_log_impl()`].
+    const MAX_AUDIT_LOG_ENTRIES: usize = 1_000;
+    /// Maximum number of pending transactions considered by [`Self::simulate_batch_sealing_impl()`]. Each transaction
+    /// is executed in the sandbox, so the limit must be small.
+    const MAX_SIMULATED_TRANSACTIONS: usize = 100;
+    /// Number of decisions returned by [`Self::get_tx_policy_decisions_impl()`] if the limit is not specified.
+    const DEFAULT_TX_POLICY_DECISIONS: usize = 100;
+    /// Maximum number of decisions returned by [`Self::get_tx_policy_decisions_impl()`].
+    const MAX_TX_POLICY_DECISIONS: usize = 1_000;
+
+    pub fn new(state: RpcState, l1_batch_seal_request: Option<L1BatchSealRequest>) -> Self {
+        Self {
+            state,
+            l1_batch_seal_request,
+        }
+    }
+
+    pub(crate) fn current_method(&self) -> &MethodTracer {
+        &self.state.current_method
+    }
+
+    /// Records a privileged action performed by the current operator in the audit log.
+    async fn record_action(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        action: &str,
+        before: Option<serde_json::Value>,
+        after: Option<serde_json::Value>,
+    ) -> Result<(), Web3Error> {
+        let operator = self.current_method().operator();
+        let actor = operator.as_deref().unwrap_or("unknown");
+        storage
+            .audit_log_dal()
+            .insert_entry(action, actor, before.as_ref(), after.as_ref())
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(())
+    }
+
+    pub async fn pause_tx_intake_impl(&self) -> Result<bool, Web3Error> {
+        let was_paused = self.state.tx_sender.set_tx_intake_paused(true);
+        if !was_paused {
+            tracing::warn!("Transaction intake was paused by the operator");
+        }
+        let mut storage = self.state.acquire_connection().await?;
+        self.record_action(
+            &mut storage,
+            "admin_pauseTxIntake",
+            Some(json!({ "txIntakePaused": was_paused })),
+            Some(json!({ "txIntakePaused": true })),
+        )
+        .await?;
+        Ok(!was_paused)
+    }
+
+    pub async fn resume_tx_intake_impl(&self) -> Result<bool, Web3Error> {
+        let was_paused = self.state.tx_sender.set_tx_intake_paused(false);
+        if was_paused {
+            tracing::info!("Transaction intake was resumed by the operator");
+        }
+        let mut storage = self.state.acquire_connection().await?;
+        self.record_action(
+            &mut storage,
+            "admin_resumeTxIntake",
+            Some(json!({ "txIntakePaused": was_paused })),
+            Some(json!({ "txIntakePaused": false })),
+        )
+        .await?;
+        Ok(was_paused)
+    }
+
+    pub async fn seal_l1_batch_impl(&self) -> Result<(), Web3Error> {
+        // The state keeper may run in a different process, in which case we cannot reach it.
+        let seal_request = self
+            .l1_batch_seal_request
+            .as_ref()
+            .ok_or(Web3Error::MethodNotImplemented)?;
+        tracing::info!("Operator requested to seal the current L1 batch");
+        let was_requested = seal_request.is_requested();
+        seal_request.request();
+        let mut storage = self.state.acquire_connection().await?;
+        self.record_action(
+            &mut storage,
+            "admin_sealL1Batch",
+            Some(json!({ "l1BatchSealRequested": was_requested })),
+            Some(json!({ "l1BatchSealRequested": true })),
+        )
+        .await
+    }
+
+    pub async fn set_proof_priority_impl(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        priority: i32,
+    ) -> Result<bool, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(DalError::generalize)?;
+        let prev_priority = transaction
+            .proof_generation_dal()
+            .get_priority(l1_batch_number)
+            .await
+            .map_err(DalError::generalize)?;
+        let updated = transaction
+            .proof_generation_dal()
+            .set_priority(l1_batch_number, priority)
+            .await
+            .map_err(DalError::generalize)?;
+        if updated {
+            self.record_action(
+                &mut transaction,
+                "admin_setProofPriority",
+                Some(json!({ "l1BatchNumber": l1_batch_number, "priority": prev_priority })),
+                Some(json!({ "l1BatchNumber": l1_batch_number, "priority": priority })),
+            )
+            .await?;
+        }
+        transaction.commit().await.map_err(DalError::generalize)?;
+
+        if updated {
+            tracing::info!(
+                "Operator set proof generation priority for L1 batch #{l1_batch_number} to {priority}"
+            );
+        }
+        Ok(updated)
+    }
+
+    pub async fn get_component_queues_impl(&self) -> Result<ComponentQueues, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let last_sealed_l1_batch = storage
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await
+            .map_err(DalError::generalize)?;
+        let last_committed_l1_batch = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_committed_on_eth()
+            .await
+            .map_err(DalError::generalize)?;
+        let last_proven_l1_batch = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_proven_on_eth()
+            .await
+            .map_err(DalError::generalize)?;
+        let last_executed_l1_batch = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await
+            .map_err(DalError::generalize)?;
+        let unconfirmed_eth_txs = storage
+            .eth_sender_dal()
+            .get_unconfirmed_txs_count()
+            .await
+            .map_err(DalError::generalize)?;
+        let oldest_unpicked_proof_batch = storage
+            .proof_generation_dal()
+            .get_oldest_unpicked_batch()
+            .await
+            .map_err(DalError::generalize)?;
+        let oldest_unproven_batch = storage
+            .proof_generation_dal()
+            .get_oldest_not_generated_batch()
+            .await
+            .map_err(DalError::generalize)?;
+
+        Ok(ComponentQueues {
+            tx_intake_paused: self.state.tx_sender.is_tx_intake_paused(),
+            l1_batch_seal_requested: self
+                .l1_batch_seal_request
+                .as_ref()
+                .map(L1BatchSealRequest::is_requested),
+            last_sealed_l1_batch,
+            last_committed_l1_batch,
+            last_proven_l1_batch,
+            last_executed_l1_batch,
+            unconfirmed_eth_txs,
+            oldest_unpicked_proof_batch,
+            oldest_unproven_batch,
+        })
+    }
+
+    pub async fn get_sequencer_lease_impl(&self) -> Result<Option<SequencerLease>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        Ok(storage
+            .sequencer_lease_dal()
+            .get_lease()
+            .await
+            .map_err(DalError::generalize)?)
+    }
+
+    pub async fn promote_sequencer_impl(
+        &self,
+        instance_id: String,
+    ) -> Result<SequencerLease, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(DalError::generalize)?;
+        let prev_lease = transaction
+            .sequencer_lease_dal()
+            .get_lease()
+            .await
+            .map_err(DalError::generalize)?;
+        let epoch = transaction
+            .sequencer_lease_dal()
+            .transfer_lease(&instance_id, Self::PROMOTED_LEASE_TTL)
+            .await
+            .map_err(DalError::generalize)?;
+        let lease = transaction
+            .sequencer_lease_dal()
+            .get_lease()
+            .await
+            .map_err(DalError::generalize)?
+            .ok_or_else(|| anyhow::anyhow!("sequencer lease disappeared after transfer"))?;
+        self.record_action(
+            &mut transaction,
+            "admin_promoteSequencer",
+            prev_lease.map(|lease| json!(lease)),
+            Some(json!(lease)),
+        )
+        .await?;
+        transaction.commit().await.map_err(DalError::generalize)?;
+
+        tracing::warn!(
+            "Operator transferred the sequencer lease to instance `{instance_id}` (epoch {epoch})"
+        );
+        Ok(lease)
+    }
+
+    pub async fn get_audit_log_impl(
+        &self,
+        from_id: u64,
+        limit: Option<usize>,
+    ) -> Result<Vec<AuditLogEntry>, Web3Error> {
+        let limit = limit
+            .unwrap_or(Self::MAX_AUDIT_LOG_ENTRIES)
+            .min(Self::MAX_AUDIT_LOG_ENTRIES);
+        let mut storage = self.state.acquire_connection().await?;
+        Ok(storage
+            .audit_log_dal()
+            .get_entries(from_id, limit)
+            .await
+            .map_err(DalError::generalize)?)
+    }
+
+    pub async fn simulate_batch_sealing_impl(
+        &self,
+        limit: Option<usize>,
+    ) -> Result<SealCriteriaSimulation, Web3Error> {
+        let limit = limit
+            .unwrap_or(Self::MAX_SIMULATED_TRANSACTIONS)
+            .min(Self::MAX_SIMULATED_TRANSACTIONS);
+        let mut connection = self.state.acquire_connection().await?;
+        let transactions = connection
+            .transactions_dal()
+            .get_pending_l2_transactions(limit)
+            .await
+            .map_err(DalError::generalize)?;
+        let block_args = BlockArgs::pending(&mut connection).await?;
+        drop(connection);
+
+        Ok(self
+            .state
+            .tx_sender
+            .simulate_batch_sealing(transactions, block_args)
+            .await?)
+    }
+
+    pub async fn get_tx_policy_decisions_impl(
+        &self,
+        address: Option<Address>,
+        limit: Option<usize>,
+    ) -> Result<Vec<TxPolicyDecision>, Web3Error> {
+        let limit = limit
+            .unwrap_or(Self::DEFAULT_TX_POLICY_DECISIONS)
+            .min(Self::MAX_TX_POLICY_DECISIONS);
+        let mut storage = self.state.acquire_connection().await?;
+        Ok(storage
+            .tx_policy_decisions_dal()
+            .get_latest_decisions(address, limit)
+            .await
+            .map_err(DalError::generalize)?)
+    }
+}
@@ -1,24 +1,29 @@
 use anyhow::Context as _;
-use zksync_dal::{CoreDal, DalError};
+use zksync_dal::{pruning_dal::RetainedDataKind, CoreDal, DalError};
 use zksync_multivm::interface::{Call, CallType, ExecutionResult, OneshotTracingParams};
 use zksync_system_constants::MAX_ENCODED_TX_SIZE;
 use zksync_types::{
     api::{
         BlockId, BlockNumber, CallTracerBlockResult, CallTracerResult, DebugCall, DebugCallType,
-        ResultDebugCall, SupportedTracers, TracerConfig,
+        FeeInputOverride, ResultDebugCall, SupportedTracers, TracerConfig,
     },
+    cold_storage::ColdStorageDataKind,
     debug_flat_call::{Action, CallResult, CallTraceMeta, DebugCallFlat, ResultDebugCallFlat},
     l2::L2Tx,
     transaction_request::CallRequest,
     web3,
     zk_evm_types::FarCallOpcode,
-    H256, U256,
+    L2BlockNumber, H256, U256,
 };
 use zksync_web3_decl::error::Web3Error;
 
 use crate::{
     execution_sandbox::SandboxAction,
-    web3::{backend_jsonrpsee::MethodTracer, state::RpcState},
+    web3::{
+        backend_jsonrpsee::MethodTracer,
+        state::RpcState,
+        trace_truncation::{truncate_trace, TruncatableTrace},
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -90,6 +95,7 @@ impl DebugNamespace {
             error: call.error.or(internal_error),
             revert_reason: call.revert_reason,
             calls,
+            truncated: vec![],
         }
     }
 
@@ -153,6 +159,7 @@ impl DebugNamespace {
             block_number: meta.block_number,
             block_hash: meta.block_hash,
             r#type: DebugCallType::Call,
+            truncated: vec![],
         });
 
         if !only_top_call {
@@ -168,6 +175,17 @@ impl DebugNamespace {
         &self.state.current_method
     }
 
+    /// Truncates the trace if it exceeds the configured size limit.
+    fn truncate_trace<T: TruncatableTrace>(&self, mut trace: T) -> T {
+        let max_size = self.state.api_config.max_trace_size;
+        if let Some(stage) = truncate_trace(&mut trace, max_size) {
+            tracing::debug!(
+                "Truncated trace exceeding {max_size} bytes; last applied stage: {stage:?}"
+            );
+        }
+        trace
+    }
+
     pub async fn debug_trace_block_impl(
         &self,
         block_id: BlockId,
@@ -190,11 +208,30 @@ impl DebugNamespace {
         self.current_method()
             .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
 
-        let call_traces = connection
-            .blocks_web3_dal()
-            .get_traces_for_l2_block(block_number)
+        let archived_l1_batch = connection
+            .cold_storage_dal()
+            .get_archived_l1_batch_for_l2_block(ColdStorageDataKind::CallTraces, block_number)
             .await
             .map_err(DalError::generalize)?;
+        let call_traces = if let Some(l1_batch_number) = archived_l1_batch {
+            let archive = self
+                .state
+                .load_archived_call_traces(l1_batch_number)
+                .await?;
+            connection
+                .blocks_web3_dal()
+                .get_archived_traces_for_l2_block(block_number, &archive)
+                .await
+        } else {
+            self.state
+                .ensure_data_retained(&mut connection, RetainedDataKind::CallTraces, block_number)
+                .await?;
+            connection
+                .blocks_web3_dal()
+                .get_traces_for_l2_block(block_number)
+                .await
+        };
+        let call_traces = call_traces.map_err(DalError::generalize)?;
 
         let options = options.unwrap_or_default();
         let result = match options.tracer {
@@ -232,7 +269,7 @@ impl DebugNamespace {
                 CallTracerBlockResult::FlatCallTrace(res)
             }
         };
-        Ok(result)
+        Ok(self.truncate_trace(result))
     }
 
     pub async fn debug_trace_transaction_impl(
@@ -241,13 +278,46 @@ impl DebugNamespace {
         options: Option<TracerConfig>,
     ) -> Result<Option<CallTracerResult>, Web3Error> {
         let mut connection = self.state.acquire_connection().await?;
-        let call_trace = connection
-            .transactions_dal()
-            .get_call_trace(tx_hash)
+        let archived_l1_batch = connection
+            .cold_storage_dal()
+            .get_archived_l1_batch_for_transaction(ColdStorageDataKind::CallTraces, tx_hash)
             .await
             .map_err(DalError::generalize)?;
+        let call_trace = if let Some(l1_batch_number) = archived_l1_batch {
+            let archive = self
+                .state
+                .load_archived_call_traces(l1_batch_number)
+                .await?;
+            connection
+                .transactions_dal()
+                .get_archived_call_trace(tx_hash, &archive)
+                .await
+        } else {
+            connection.transactions_dal().get_call_trace(tx_hash).await
+        };
+        let call_trace = call_trace.map_err(DalError::generalize)?;
+        if call_trace.is_none() && archived_l1_batch.is_none() {
+            // Distinguish between an unknown transaction and a transaction with pruned call traces.
+            let tx_block_number = connection
+                .transactions_dal()
+                .get_storage_tx_by_hash(tx_hash)
+                .await
+                .map_err(DalError::generalize)?
+                .and_then(|tx| tx.miniblock_number);
+            if let Some(block_number) = tx_block_number {
+                let block_number = L2BlockNumber(block_number as u32);
+                self.state
+                    .ensure_data_retained(
+                        &mut connection,
+                        RetainedDataKind::CallTraces,
+                        block_number,
+                    )
+                    .await?;
+            }
+        }
         Ok(call_trace.map(|(call_trace, meta)| {
-            Self::map_call(call_trace, meta, options.unwrap_or_default())
+            let trace = Self::map_call(call_trace, meta, options.unwrap_or_default());
+            self.truncate_trace(trace)
         }))
     }
 
@@ -256,9 +326,11 @@ impl DebugNamespace {
         mut request: CallRequest,
         block_id: Option<BlockId>,
         options: Option<TracerConfig>,
+        fee_input_override: Option<FeeInputOverride>,
     ) -> Result<CallTracerResult, Web3Error> {
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         self.current_method().set_block_id(block_id);
+        self.state.tx_sender.shed_low_priority_load()?;
 
         let options = options.unwrap_or_default();
 
@@ -271,7 +343,8 @@ impl DebugNamespace {
         let block_args = self
             .state
             .resolve_block_args(&mut connection, block_id)
-            .await?;
+            .await?
+            .with_fee_input_override(fee_input_override);
         self.current_method().set_block_diff(
             self.state
                 .last_sealed_l2_block
@@ -291,6 +364,7 @@ impl DebugNamespace {
             drop(connection);
             fee_input
         };
+        let fee_input = block_args.override_fee_input(fee_input);
 
         let call_overrides = request.get_call_overrides()?;
         let call = L2Tx::from_request(
@@ -354,6 +428,6 @@ impl DebugNamespace {
             // It's a call request, it's safe to everything as default
             ..Default::default()
         };
-        Ok(Self::map_call(call, meta, options))
+        Ok(self.truncate_trace(Self::map_call(call, meta, options)))
     }
 }
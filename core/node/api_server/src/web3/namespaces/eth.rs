@@ -1,18 +1,19 @@
 use anyhow::Context as _;
-use zksync_dal::{CoreDal, DalError};
+use zksync_dal::{pruning_dal::RetainedDataKind, Connection, Core, CoreDal, DalError};
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        state_override::StateOverride, BlockId, BlockNumber, FeeHistory, GetLogsFilter,
-        Transaction, TransactionId, TransactionReceipt, TransactionVariant,
+        state_override::StateOverride, BlockId, BlockNumber, FeeHistory, FeeInputOverride,
+        GetLogsFilter, Transaction, TransactionId, TransactionReceipt, TransactionVariant,
     },
     bytecode::{trim_padded_evm_bytecode, BytecodeHash, BytecodeMarker},
+    h256_to_u256,
     l2::{L2Tx, TransactionType},
     transaction_request::CallRequest,
     u256_to_h256,
-    utils::decompose_full_nonce,
+    utils::{decompose_full_nonce, storage_key_for_eth_balance},
     web3::{self, Bytes, SyncInfo, SyncState},
-    AccountTreeId, L2BlockNumber, StorageKey, H256, L2_BASE_TOKEN_ADDRESS, U256,
+    AccountTreeId, Bloom, BloomInput, L2BlockNumber, StorageKey, H256, L2_BASE_TOKEN_ADDRESS, U256,
 };
 use zksync_web3_decl::{
     error::Web3Error,
@@ -23,7 +24,13 @@ use crate::{
     execution_sandbox::BlockArgs,
     tx_sender::BinarySearchKind,
     utils::{fill_transaction_receipts, open_readonly_transaction},
-    web3::{backend_jsonrpsee::MethodTracer, metrics::API_METRICS, state::RpcState, TypedFilter},
+    web3::{
+        backend_jsonrpsee::MethodTracer,
+        call_cache::EthCallCacheKey,
+        metrics::{API_METRICS, ETH_CALL_CACHE_METRICS},
+        state::RpcState,
+        TypedFilter,
+    },
 };
 
 pub const EVENT_TOPIC_NUMBER_LIMIT: usize = 4;
@@ -78,8 +85,23 @@ impl EthNamespace {
         if request.gas.is_none() {
             request.gas = Some(block_args.default_eth_call_gas(&mut connection).await?);
         }
+        let cache_key = self
+            .eth_call_cache_key(
+                &mut connection,
+                &block_args,
+                &request,
+                state_override.as_ref(),
+            )
+            .await?;
         drop(connection);
 
+        if let (Some(cache), Some(key)) = (&self.state.eth_call_cache, &cache_key) {
+            if let Some(output) = cache.get(key) {
+                return Ok(output.into());
+            }
+        }
+
+        let block_number = block_args.resolved_block_number();
         let call_overrides = request.get_call_overrides()?;
         let tx = L2Tx::from_request(
             request.into(),
@@ -93,14 +115,64 @@ impl EthNamespace {
             .tx_sender
             .eth_call(block_args, call_overrides, tx, state_override)
             .await?;
+        if let Some(key) = cache_key {
+            self.cache_eth_call_result(key, block_number, &call_result)
+                .await?;
+        }
         Ok(call_result.into())
     }
 
+    /// Returns the cache key for an `eth_call`, or `None` if the call result should not be cached (e.g., because
+    /// the call is executed on the pending block).
+    async fn eth_call_cache_key(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        block_args: &BlockArgs,
+        request: &CallRequest,
+        state_override: Option<&StateOverride>,
+    ) -> Result<Option<EthCallCacheKey>, Web3Error> {
+        if self.state.eth_call_cache.is_none() || block_args.is_pending() {
+            return Ok(None);
+        }
+        let block_hash = connection
+            .blocks_web3_dal()
+            .get_l2_block_hash(block_args.resolved_block_number())
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(block_hash.map(|hash| EthCallCacheKey::new(hash, request, state_override)))
+    }
+
+    async fn cache_eth_call_result(
+        &self,
+        key: EthCallCacheKey,
+        block_number: L2BlockNumber,
+        output: &[u8],
+    ) -> Result<(), Web3Error> {
+        let Some(cache) = &self.state.eth_call_cache else {
+            return Ok(());
+        };
+        // The block may have been reverted while the call was executing, in which case the call may have used
+        // the state of the new block with the same number.
+        let mut connection = self.state.acquire_connection().await?;
+        let block_hash = connection
+            .blocks_web3_dal()
+            .get_l2_block_hash(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        if block_hash == Some(key.block_hash()) {
+            cache.insert(key, output.to_vec());
+        } else {
+            ETH_CALL_CACHE_METRICS.skipped_inserts.inc();
+        }
+        Ok(())
+    }
+
     pub async fn estimate_gas_impl(
         &self,
         request: CallRequest,
         _block: Option<BlockNumber>,
         state_override: Option<StateOverride>,
+        fee_input_override: Option<FeeInputOverride>,
     ) -> Result<U256, Web3Error> {
         self.current_method()
             .observe_state_override(state_override.as_ref());
@@ -120,7 +192,9 @@ impl EthNamespace {
             .eip712_meta
             .is_some();
         let mut connection = self.state.acquire_connection().await?;
-        let block_args = BlockArgs::pending(&mut connection).await?;
+        let block_args = BlockArgs::pending(&mut connection)
+            .await?
+            .with_fee_input_override(fee_input_override);
         drop(connection);
         let mut tx: L2Tx = L2Tx::from_request(
             request_with_gas_per_pubdata_overridden.into(),
@@ -177,6 +251,12 @@ impl EthNamespace {
         let mut connection = self.state.acquire_connection().await?;
         let block_number = self.state.resolve_block(&mut connection, block_id).await?;
 
+        let balance_key = storage_key_for_eth_balance(&address);
+        if let Some(balance) = self.pending_storage_value(block_id, block_number, &balance_key) {
+            self.set_block_diff(block_number);
+            return Ok(h256_to_u256(balance));
+        }
+
         let balance = connection
             .storage_web3_dal()
             .standard_token_historical_balance(
@@ -191,6 +271,22 @@ impl EthNamespace {
         Ok(balance)
     }
 
+    /// Returns the value of a storage slot written by unsealed transactions if `block_id` refers to the pending block,
+    /// which was resolved to `block_number`.
+    fn pending_storage_value(
+        &self,
+        block_id: BlockId,
+        block_number: L2BlockNumber,
+        key: &StorageKey,
+    ) -> Option<H256> {
+        if !matches!(block_id, BlockId::Number(BlockNumber::Pending)) {
+            return None;
+        }
+        self.state
+            .pending_batch_state(block_number)?
+            .storage_value(key)
+    }
+
     fn set_block_diff(&self, block_number: L2BlockNumber) {
         let diff = self.state.last_sealed_l2_block.diff(block_number);
         self.current_method().set_block_diff(diff);
@@ -376,13 +472,22 @@ impl EthNamespace {
             return Ok(None);
         };
         self.set_block_diff(block_number); // only report block diff for existing L2 blocks
+        self.state
+            .ensure_data_retained(&mut storage, RetainedDataKind::Events, block_number)
+            .await?;
 
         let receipts = storage
             .transactions_web3_dal()
             .get_transaction_receipts(&block.transactions)
             .await
             .with_context(|| format!("get_transaction_receipts({block_number})"))?;
-        let receipts = fill_transaction_receipts(&mut storage, receipts).await?;
+        let mut receipts = fill_transaction_receipts(&mut storage, receipts).await?;
+        self.state
+            .fill_archived_logs(
+                &mut storage,
+                receipts.iter_mut().flat_map(|receipt| &mut receipt.logs),
+            )
+            .await?;
         Ok(Some(receipts))
     }
 
@@ -448,6 +553,9 @@ impl EthNamespace {
         let mut connection = self.state.acquire_connection().await?;
         let block_number = self.state.resolve_block(&mut connection, block_id).await?;
         self.set_block_diff(block_number);
+        if let Some(value) = self.pending_storage_value(block_id, block_number, &storage_key) {
+            return Ok(value);
+        }
         let value = connection
             .storage_web3_dal()
             .get_historical_value_unchecked(storage_key.hashed_key(), block_number)
@@ -559,7 +667,19 @@ impl EthNamespace {
             .get_transaction_receipts(&[hash])
             .await
             .context("get_transaction_receipts")?;
-        let receipts = fill_transaction_receipts(&mut storage, receipts).await?;
+        if let Some(receipt) = receipts.first() {
+            let block_number = L2BlockNumber(receipt.inner.block_number.as_u32());
+            self.state
+                .ensure_data_retained(&mut storage, RetainedDataKind::Events, block_number)
+                .await?;
+        }
+        let mut receipts = fill_transaction_receipts(&mut storage, receipts).await?;
+        self.state
+            .fill_archived_logs(
+                &mut storage,
+                receipts.iter_mut().flat_map(|receipt| &mut receipt.logs),
+            )
+            .await?;
         Ok(receipts.into_iter().next())
     }
 
@@ -841,14 +961,28 @@ impl EthNamespace {
                     );
                 }
 
-                let get_logs_filter = GetLogsFilter {
+                let mut get_logs_filter = GetLogsFilter {
                     from_block: *from_block,
                     to_block,
                     addresses,
                     topics,
+                    l2_blocks: None,
                 };
 
                 let mut storage = self.state.acquire_connection().await?;
+                self.state
+                    .ensure_data_retained(&mut storage, RetainedDataKind::Events, *from_block)
+                    .await?;
+                get_logs_filter.l2_blocks =
+                    Self::select_l2_blocks_by_blooms(&mut storage, &get_logs_filter).await?;
+                if get_logs_filter
+                    .l2_blocks
+                    .as_ref()
+                    .is_some_and(|l2_blocks| l2_blocks.is_empty())
+                {
+                    *from_block = to_block + 1;
+                    return Ok(FilterChanges::Logs(vec![]));
+                }
 
                 // Check if there is more than one block in range and there are more than `req_entities_limit` logs that satisfies filter.
                 // In this case we should return error and suggest requesting logs with smaller block range.
@@ -870,23 +1004,85 @@ impl EthNamespace {
                     }
                 }
 
-                let logs = storage
+                let mut logs = storage
                     .events_web3_dal()
                     .get_logs(get_logs_filter, i32::MAX as usize)
                     .await
                     .map_err(DalError::generalize)?;
+                self.state
+                    .fill_archived_logs(&mut storage, &mut logs)
+                    .await?;
                 *from_block = to_block + 1;
                 FilterChanges::Logs(logs)
             }
         })
     }
 
+    /// Uses L1 batch and L2 block log blooms to select L2 blocks that may contain logs matching the filter.
+    /// Returns `None` if blooms cannot narrow down the block range.
+    async fn select_l2_blocks_by_blooms(
+        storage: &mut Connection<'_, Core>,
+        filter: &GetLogsFilter,
+    ) -> Result<Option<Vec<L2BlockNumber>>, Web3Error> {
+        if filter.addresses.is_empty() && filter.topics.iter().all(|(_, topics)| topics.is_empty())
+        {
+            return Ok(None);
+        }
+        if filter.from_block > filter.to_block {
+            return Ok(None);
+        }
+
+        let l2_block_range = filter.from_block..=filter.to_block;
+        let l1_batch_blooms = storage
+            .events_web3_dal()
+            .get_l1_batch_logs_blooms(l2_block_range.clone())
+            .await
+            .map_err(DalError::generalize)?;
+        let candidate_l1_batches: Vec<_> = l1_batch_blooms
+            .into_iter()
+            .filter(|(_, bloom)| bloom.map_or(true, |bloom| bloom_may_match(&bloom, filter)))
+            .map(|(number, _)| number)
+            .collect();
+
+        let l2_block_blooms = storage
+            .events_web3_dal()
+            .get_l2_block_logs_blooms(l2_block_range, &candidate_l1_batches)
+            .await
+            .map_err(DalError::generalize)?;
+        let block_count = filter.to_block.0 - filter.from_block.0 + 1;
+        let candidate_l2_blocks: Vec<_> = l2_block_blooms
+            .into_iter()
+            .filter(|(_, bloom)| bloom.map_or(true, |bloom| bloom_may_match(&bloom, filter)))
+            .map(|(number, _)| number)
+            .collect();
+        if candidate_l2_blocks.len() >= block_count as usize {
+            return Ok(None); // blooms didn't filter out any blocks
+        }
+        Ok(Some(candidate_l2_blocks))
+    }
+
     pub fn max_priority_fee_per_gas_impl(&self) -> U256 {
         // ZKsync does not require priority fee.
         0u64.into()
     }
 }
 
+/// Checks whether a block or batch with the specified log bloom may contain logs matching the filter.
+fn bloom_may_match(bloom: &Bloom, filter: &GetLogsFilter) -> bool {
+    let address_matches = filter.addresses.is_empty()
+        || filter
+            .addresses
+            .iter()
+            .any(|address| bloom.contains_input(BloomInput::Raw(address.as_bytes())));
+    address_matches
+        && filter.topics.iter().all(|(_, topics)| {
+            topics.is_empty()
+                || topics
+                    .iter()
+                    .any(|topic| bloom.contains_input(BloomInput::Raw(topic.as_bytes())))
+        })
+}
+
 // Bogus methods.
 // They are moved into a separate `impl` block so they don't make the actual implementation noisy.
 // This `impl` block contains methods that we *have* to implement for compliance, but don't really
@@ -0,0 +1,64 @@
+use zksync_state_keeper::DevModeControl;
+use zksync_types::U64;
+use zksync_web3_decl::error::Web3Error;
+
+use crate::web3::{backend_jsonrpsee::MethodTracer, state::RpcState};
+
+/// Controls block production if the state keeper runs in the development mode.
+#[derive(Debug, Clone)]
+pub(crate) struct EvmNamespace {
+    state: RpcState,
+    dev_mode_control: Option<DevModeControl>,
+}
+
+impl EvmNamespace {
+    pub fn new(state: RpcState, dev_mode_control: Option<DevModeControl>) -> Self {
+        Self {
+            state,
+            dev_mode_control,
+        }
+    }
+
+    pub(crate) fn current_method(&self) -> &MethodTracer {
+        &self.state.current_method
+    }
+
+    fn control(&self) -> Result<&DevModeControl, Web3Error> {
+        // The state keeper may run in a different process or not in the dev mode, in which case we cannot reach it.
+        self.dev_mode_control
+            .as_ref()
+            .ok_or(Web3Error::MethodNotImplemented)
+    }
+
+    pub async fn mine_impl(&self) -> Result<U64, Web3Error> {
+        let l2_block_number = self.control()?.mine().await.map_err(anyhow::Error::from)?;
+        Ok(l2_block_number.0.into())
+    }
+
+    pub fn increase_time_impl(&self, seconds: u64) -> Result<u64, Web3Error> {
+        let total_offset = self.control()?.increase_time(seconds);
+        tracing::info!("Increased dev mode time offset by {seconds}s to {total_offset}s");
+        Ok(total_offset)
+    }
+
+    pub async fn snapshot_impl(&self) -> Result<U64, Web3Error> {
+        let snapshot_id = self
+            .control()?
+            .snapshot()
+            .await
+            .map_err(anyhow::Error::from)?;
+        Ok(snapshot_id.into())
+    }
+
+    pub async fn revert_impl(&self, snapshot_id: U64) -> Result<bool, Web3Error> {
+        let reverted = self
+            .control()?
+            .revert(snapshot_id.as_u64())
+            .await
+            .map_err(anyhow::Error::from)?;
+        if reverted {
+            tracing::info!("Reverted chain state to dev mode snapshot #{snapshot_id}");
+        }
+        Ok(reverted)
+    }
+}
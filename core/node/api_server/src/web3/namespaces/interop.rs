@@ -0,0 +1,48 @@
+use zksync_dal::{CoreDal, DalError};
+use zksync_types::{api::L2ToL1MessageStatus, L1BatchNumber, H256};
+use zksync_web3_decl::error::Web3Error;
+
+use crate::web3::{backend_jsonrpsee::MethodTracer, RpcState};
+
+#[derive(Debug)]
+pub(crate) struct InteropNamespace {
+    state: RpcState,
+}
+
+impl InteropNamespace {
+    pub fn new(state: RpcState) -> Self {
+        Self { state }
+    }
+
+    pub(crate) fn current_method(&self) -> &MethodTracer {
+        &self.state.current_method
+    }
+
+    pub async fn get_l2_to_l1_messages_by_tx_hash_impl(
+        &self,
+        tx_hash: H256,
+    ) -> Result<Vec<L2ToL1MessageStatus>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        Ok(storage
+            .l2_to_l1_messages_dal()
+            .get_messages_by_tx_hash(tx_hash)
+            .await
+            .map_err(DalError::generalize)?)
+    }
+
+    pub async fn get_l2_to_l1_messages_by_l1_batch_impl(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Vec<L2ToL1MessageStatus>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(l1_batch_number, &mut storage)
+            .await?;
+        Ok(storage
+            .l2_to_l1_messages_dal()
+            .get_messages_for_l1_batch(l1_batch_number)
+            .await
+            .map_err(DalError::generalize)?)
+    }
+}
@@ -1,9 +1,12 @@
 //! Actual implementation of Web3 API namespaces logic, not tied to the backend
 //! used to create a JSON RPC server.
 
+mod admin;
 mod debug;
 mod en;
 pub(crate) mod eth;
+mod evm;
+mod interop;
 mod net;
 mod snapshots;
 mod unstable;
@@ -11,7 +14,7 @@ mod web3;
 mod zks;
 
 pub(super) use self::{
-    debug::DebugNamespace, en::EnNamespace, eth::EthNamespace, net::NetNamespace,
-    snapshots::SnapshotsNamespace, unstable::UnstableNamespace, web3::Web3Namespace,
-    zks::ZksNamespace,
+    admin::AdminNamespace, debug::DebugNamespace, en::EnNamespace, eth::EthNamespace,
+    evm::EvmNamespace, interop::InteropNamespace, net::NetNamespace, snapshots::SnapshotsNamespace,
+    unstable::UnstableNamespace, web3::Web3Namespace, zks::ZksNamespace,
 };
@@ -1,7 +1,10 @@
 use anyhow::Context as _;
-use zksync_dal::{CoreDal, DalError};
+use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_types::{
-    snapshots::{AllSnapshots, SnapshotHeader, SnapshotStorageLogsChunkMetadata},
+    snapshots::{
+        AllSnapshots, SnapshotHeader, SnapshotManifest, SnapshotMetadata,
+        SnapshotStorageLogsChunkIndex, SnapshotStorageLogsChunkMetadata,
+    },
     L1BatchNumber,
 };
 use zksync_web3_decl::error::Web3Error;
@@ -45,7 +48,64 @@ impl SnapshotsNamespace {
         let Some(snapshot_metadata) = snapshot_metadata else {
             return Ok(None);
         };
+        if snapshot_metadata.is_differential() {
+            // Differential snapshots are only returned as a part of a manifest, so that clients unaware of them
+            // don't try to recover from a differential snapshot alone.
+            return Ok(None);
+        }
+        Self::snapshot_header(&mut storage_processor, snapshot_metadata).await
+    }
+
+    pub async fn get_snapshot_manifest_impl(
+        &self,
+        l1_batch_number: Option<L1BatchNumber>,
+    ) -> Result<Option<SnapshotManifest>, Web3Error> {
+        let mut storage_processor = self.state.acquire_connection().await?;
+        let mut dal = storage_processor.snapshots_dal();
+        let snapshot_metadata = if let Some(l1_batch_number) = l1_batch_number {
+            dal.get_snapshot_metadata(l1_batch_number).await
+        } else {
+            dal.get_newest_complete_snapshot_metadata().await
+        };
+        let Some(mut snapshot_metadata) = snapshot_metadata.map_err(DalError::generalize)? else {
+            return Ok(None);
+        };
+
+        let mut deltas = vec![];
+        while let Some(base_l1_batch_number) = snapshot_metadata.base_l1_batch_number {
+            let l1_batch_number = snapshot_metadata.l1_batch_number;
+            let Some(header) =
+                Self::snapshot_header(&mut storage_processor, snapshot_metadata).await?
+            else {
+                return Ok(None);
+            };
+            deltas.push(header);
+
+            snapshot_metadata = storage_processor
+                .snapshots_dal()
+                .get_snapshot_metadata(base_l1_batch_number)
+                .await
+                .map_err(DalError::generalize)?
+                .with_context(|| {
+                    format!(
+                        "snapshot for L1 batch #{base_l1_batch_number} referenced as a base for snapshot \
+                         for L1 batch #{l1_batch_number} is missing"
+                    )
+                })?;
+        }
+        let Some(base) = Self::snapshot_header(&mut storage_processor, snapshot_metadata).await?
+        else {
+            return Ok(None);
+        };
+        deltas.reverse();
+        Ok(Some(SnapshotManifest { base, deltas }))
+    }
 
+    /// Returns `None` for incomplete snapshots.
+    async fn snapshot_header(
+        storage_processor: &mut Connection<'_, Core>,
+        snapshot_metadata: SnapshotMetadata,
+    ) -> Result<Option<SnapshotHeader>, Web3Error> {
         let snapshot_files = snapshot_metadata.storage_logs_filepaths;
         let is_complete = snapshot_files.iter().all(Option::is_some);
         if !is_complete {
@@ -53,16 +113,24 @@ impl SnapshotsNamespace {
             return Ok(None);
         }
 
+        let chunk_count = snapshot_files.len() as u64;
+        let checksums = &snapshot_metadata.storage_logs_checksums;
         let chunks = snapshot_files
             .into_iter()
             .enumerate()
             .filter_map(|(chunk_id, filepath)| {
+                let checksum = checksums.get(chunk_id).copied().flatten();
+                let chunk_id = chunk_id as u64;
                 Some(SnapshotStorageLogsChunkMetadata {
-                    chunk_id: chunk_id as u64,
+                    chunk_id,
                     filepath: filepath?,
+                    index: checksum.map(|checksum| {
+                        SnapshotStorageLogsChunkIndex::new(chunk_id, chunk_count, checksum)
+                    }),
                 })
             })
             .collect();
+        let l1_batch_number = snapshot_metadata.l1_batch_number;
         let (_, l2_block_number) = storage_processor
             .blocks_dal()
             .get_l2_block_range_of_l1_batch(l1_batch_number)
@@ -72,10 +140,11 @@ impl SnapshotsNamespace {
 
         Ok(Some(SnapshotHeader {
             version: snapshot_metadata.version.into(),
-            l1_batch_number: snapshot_metadata.l1_batch_number,
+            l1_batch_number,
             l2_block_number,
             storage_logs_chunks: chunks,
             factory_deps_filepath: snapshot_metadata.factory_deps_filepath,
+            base_l1_batch_number: snapshot_metadata.base_l1_batch_number,
         }))
     }
 }
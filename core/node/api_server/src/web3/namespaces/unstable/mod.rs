@@ -8,10 +8,11 @@ use zksync_dal::{CoreDal, DalError};
 use zksync_mini_merkle_tree::MiniMerkleTree;
 use zksync_types::{
     api::{
-        ChainAggProof, DataAvailabilityDetails, L1ToL2TxsStatus, TeeProof, TransactionExecutionInfo,
+        ChainAggProof, DataAvailabilityDetails, L1ToL2TxsStatus, ProtocolUpgradeDryRun, TeeProof,
+        TransactionExecutionInfo,
     },
     tee_types::TeeType,
-    L1BatchNumber, L2ChainId,
+    L1BatchNumber, L2ChainId, ProtocolVersionId,
 };
 use zksync_web3_decl::{error::Web3Error, types::H256};
 
@@ -194,4 +195,19 @@ impl UnstableNamespace {
             l1_to_l2_txs_in_mempool,
         })
     }
+
+    pub async fn get_protocol_upgrade_dry_run_impl(
+        &self,
+        version_id: u16,
+    ) -> Result<Option<ProtocolUpgradeDryRun>, Web3Error> {
+        let Ok(version_id) = ProtocolVersionId::try_from(version_id) else {
+            return Ok(None);
+        };
+        let mut storage = self.state.acquire_connection().await?;
+        Ok(storage
+            .protocol_upgrade_dry_runs_dal()
+            .get_dry_run(version_id)
+            .await
+            .map_err(DalError::generalize)?)
+    }
 }
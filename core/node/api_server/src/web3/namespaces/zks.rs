@@ -1,19 +1,22 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, iter};
 
 use anyhow::Context as _;
+use chrono::{NaiveDate, Utc};
 use zksync_crypto_primitives::hasher::{keccak::KeccakHasher, Hasher};
 use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_metadata_calculator::api_server::TreeApiError;
 use zksync_mini_merkle_tree::MiniMerkleTree;
-use zksync_multivm::interface::VmEvent;
+use zksync_multivm::{interface::VmEvent, utils::disassemble_bytecode};
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
-    address_to_h256,
+    abi, address_to_h256,
     api::{
         self, state_override::StateOverride, BlockDetails, BridgeAddresses, GetLogsFilter,
-        L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion, StorageProof,
-        TransactionDetailedResult, TransactionDetails,
+        L1BatchDetails, L2ToL1LogProof, MultiProof, Proof, ProtocolVersion, SoftConfirmation,
+        StorageMultiProofEntry, StorageProof, TransactionDetailedResult, TransactionDetails,
     },
+    block::build_bloom,
+    bytecode::{BytecodeHash, BytecodeMarker},
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
     h256_to_u256,
@@ -22,11 +25,12 @@ use zksync_types::{
     l2_to_l1_log::{l2_to_l1_logs_tree_size, L2ToL1Log, LOG_PROOF_SUPPORTED_METADATA_VERSION},
     tokens::ETHEREUM_ADDRESS,
     transaction_request::CallRequest,
-    utils::storage_key_for_standard_token_balance,
+    utils::{decompose_full_nonce, storage_key_for_standard_token_balance},
     web3,
     web3::Bytes,
-    AccountTreeId, L1BatchNumber, L2BlockNumber, ProtocolVersionId, StorageKey, Transaction,
-    L1_MESSENGER_ADDRESS, L2_BASE_TOKEN_ADDRESS, REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE, U256, U64,
+    AccountTreeId, BloomInput, L1BatchNumber, L2BlockNumber, PriorityOpId, ProtocolVersionId,
+    StorageKey, Transaction, L1_MESSENGER_ADDRESS, L2_BASE_TOKEN_ADDRESS,
+    PRIORITY_OPERATION_L2_TX_TYPE, REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE, U256, U64,
 };
 use zksync_web3_decl::{
     error::{ClientRpcContext, Web3Error},
@@ -47,6 +51,10 @@ pub(crate) struct ZksNamespace {
 }
 
 impl ZksNamespace {
+    /// Maximum number of contracts returned by `zks_getTopContracts`.
+    const MAX_TOP_CONTRACTS: usize = 100;
+    const DEFAULT_TOP_CONTRACTS: usize = 10;
+
     pub fn new(state: RpcState) -> Self {
         Self { state }
     }
@@ -59,10 +67,54 @@ impl ZksNamespace {
         &self,
         request: CallRequest,
         state_override: Option<StateOverride>,
+        fee_input_override: Option<api::FeeInputOverride>,
     ) -> Result<Fee, Web3Error> {
         self.current_method()
             .observe_state_override(state_override.as_ref());
 
+        let (tx, block_args) = self
+            .prepare_l2_tx_for_fee_estimation(request, fee_input_override)
+            .await?;
+        self.estimate_fee(tx.into(), block_args, state_override)
+            .await
+    }
+
+    pub async fn estimate_fee_with_sensitivity_impl(
+        &self,
+        request: CallRequest,
+        state_override: Option<StateOverride>,
+        fee_input_override: Option<api::FeeInputOverride>,
+    ) -> Result<api::FeeWithSensitivity, Web3Error> {
+        self.current_method()
+            .observe_state_override(state_override.as_ref());
+
+        let (tx, block_args) = self
+            .prepare_l2_tx_for_fee_estimation(request, fee_input_override)
+            .await?;
+        let scale_factor = self.state.api_config.estimate_gas_scale_factor;
+        let acceptable_overestimation =
+            self.state.api_config.estimate_gas_acceptable_overestimation;
+        let search_kind = BinarySearchKind::new(self.state.api_config.estimate_gas_optimize_search);
+
+        Ok(self
+            .state
+            .tx_sender
+            .get_txs_fee_with_sensitivity(
+                tx.into(),
+                block_args,
+                scale_factor,
+                acceptable_overestimation as u64,
+                state_override,
+                search_kind,
+            )
+            .await?)
+    }
+
+    async fn prepare_l2_tx_for_fee_estimation(
+        &self,
+        request: CallRequest,
+        fee_input_override: Option<api::FeeInputOverride>,
+    ) -> Result<(L2Tx, BlockArgs), Web3Error> {
         let mut request_with_gas_per_pubdata_overridden = request;
         self.state
             .set_nonce_for_call_request(&mut request_with_gas_per_pubdata_overridden)
@@ -73,7 +125,9 @@ impl ZksNamespace {
         }
 
         let mut connection = self.state.acquire_connection().await?;
-        let block_args = BlockArgs::pending(&mut connection).await?;
+        let block_args = BlockArgs::pending(&mut connection)
+            .await?
+            .with_fee_input_override(fee_input_override);
         drop(connection);
         let mut tx = L2Tx::from_request(
             request_with_gas_per_pubdata_overridden.into(),
@@ -85,8 +139,7 @@ impl ZksNamespace {
         // not consider provided ones.
         tx.common_data.fee.max_priority_fee_per_gas = 0u64.into();
         tx.common_data.fee.gas_per_pubdata_limit = U256::from(DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE);
-        self.estimate_fee(tx.into(), block_args, state_override)
-            .await
+        Ok((tx, block_args))
     }
 
     pub async fn estimate_l1_to_l2_gas_impl(
@@ -121,6 +174,111 @@ impl ZksNamespace {
         Ok(fee.gas_limit)
     }
 
+    pub async fn simulate_l1_to_l2_transaction_impl(
+        &self,
+        request: CallRequest,
+        state_override: Option<StateOverride>,
+    ) -> Result<api::TransactionReceipt, Web3Error> {
+        self.current_method()
+            .observe_state_override(state_override.as_ref());
+
+        let mut request = request;
+        if let Some(ref mut eip712_meta) = request.eip712_meta {
+            if eip712_meta.gas_per_pubdata == U256::zero() {
+                eip712_meta.gas_per_pubdata = REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE.into();
+            }
+        }
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = BlockArgs::pending(&mut connection).await?;
+        let serial_id = connection
+            .transactions_dal()
+            .last_priority_id()
+            .await
+            .map_err(DalError::generalize)?
+            .map_or(PriorityOpId(0), |id| id + 1);
+        drop(connection);
+
+        // L1 -> L2 transactions submitted via the mailbox always have a target.
+        let mut tx = L1Tx::from_request(request, false).map_err(Web3Error::SerializationError)?;
+        tx.common_data.serial_id = serial_id;
+        // Mirrors the L1 contract, which refunds the sender if no refund recipient is specified.
+        tx.common_data.refund_recipient = tx.common_data.sender;
+        tx.common_data.canonical_tx_hash =
+            abi::NewPriorityRequest::from(tx.clone()).transaction.hash();
+        let tx_hash = tx.hash();
+        let from = tx.common_data.sender;
+        let to = tx.execute.contract_address;
+        let gas_limit = tx.common_data.gas_limit;
+        let effective_gas_price = tx.common_data.max_fee_per_gas;
+        let block_number = block_args.resolved_block_number();
+
+        let output = self
+            .state
+            .tx_sender
+            .simulate_l1_tx(tx, block_args, state_override)
+            .await?;
+
+        let logs: Vec<_> = output
+            .events
+            .into_iter()
+            .enumerate()
+            .map(|(i, event)| api::Log {
+                block_number: Some(block_number.0.into()),
+                log_index: Some((i as u64).into()),
+                transaction_log_index: Some((i as u64).into()),
+                ..map_event(event, tx_hash)
+            })
+            .collect();
+        let logs_bloom = build_bloom(logs.iter().flat_map(|log| {
+            log.topics
+                .iter()
+                .map(|topic| BloomInput::Raw(topic.as_bytes()))
+                .chain(iter::once(BloomInput::Raw(log.address.as_bytes())))
+        }));
+        let l2_to_l1_logs = output
+            .l2_to_l1_logs
+            .into_iter()
+            .enumerate()
+            .map(|(i, log)| api::L2ToL1Log {
+                block_hash: None,
+                block_number: block_number.0.into(),
+                l1_batch_number: None,
+                log_index: (i as u64).into(),
+                transaction_index: u64::from(log.0.tx_number_in_block).into(),
+                transaction_hash: tx_hash,
+                transaction_log_index: (i as u64).into(),
+                tx_index_in_l1_batch: Some(u64::from(log.0.tx_number_in_block).into()),
+                shard_id: log.0.shard_id.into(),
+                is_service: log.0.is_service,
+                sender: log.0.sender,
+                key: log.0.key,
+                value: log.0.value,
+            })
+            .collect();
+        let gas_used = gas_limit.saturating_sub(output.metrics.gas_refunded.into());
+
+        Ok(api::TransactionReceipt {
+            transaction_hash: tx_hash,
+            block_number: block_number.0.into(),
+            from,
+            to,
+            gas_used: Some(gas_used),
+            cumulative_gas_used: gas_used,
+            effective_gas_price: Some(effective_gas_price),
+            logs,
+            l2_to_l1_logs,
+            status: if output.result.is_failed() {
+                U64::zero()
+            } else {
+                U64::one()
+            },
+            logs_bloom,
+            transaction_type: Some(PRIORITY_OPERATION_L2_TX_TYPE.into()),
+            ..api::TransactionReceipt::default()
+        })
+    }
+
     async fn estimate_fee(
         &self,
         tx: Transaction,
@@ -291,6 +449,7 @@ impl ZksNamespace {
                         to_block: block_number,
                         addresses: vec![L1_MESSENGER_ADDRESS],
                         topics: vec![(2, vec![address_to_h256(&sender)]), (3, vec![msg])],
+                        l2_blocks: None,
                     },
                     self.state.api_config.req_entities_limit,
                 )
@@ -557,6 +716,139 @@ impl ZksNamespace {
         Ok(tx_details)
     }
 
+    pub async fn get_soft_confirmation_impl(
+        &self,
+        tx_hash: H256,
+    ) -> Result<Option<SoftConfirmation>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        Ok(storage
+            .soft_confirmations_dal()
+            .get_soft_confirmation(tx_hash)
+            .await
+            .map_err(DalError::generalize)?)
+    }
+
+    pub async fn get_account_activity_impl(
+        &self,
+        address: Address,
+        from_block: L2BlockNumber,
+        to_block: L2BlockNumber,
+    ) -> Result<api::AccountActivity, Web3Error> {
+        if from_block > to_block {
+            return Ok(api::AccountActivity {
+                from_block,
+                to_block,
+                entries: vec![],
+            });
+        }
+
+        let mut storage = self.state.acquire_connection().await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(from_block, &mut storage)
+            .await?;
+
+        let limit = self.state.api_config.req_entities_limit;
+        let activity = storage
+            .transactions_web3_dal()
+            .get_account_activity(address, from_block..=to_block, limit)
+            .await
+            .map_err(DalError::generalize)?;
+        activity.ok_or(Web3Error::LogsLimitExceeded(
+            limit,
+            from_block.0,
+            from_block.0,
+        ))
+    }
+
+    pub async fn get_account_nonce_details_impl(
+        &self,
+        address: Address,
+    ) -> Result<api::AccountNonceDetails, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let latest_block_id = api::BlockId::Number(api::BlockNumber::Latest);
+        let latest_block_number = self
+            .state
+            .resolve_block(&mut storage, latest_block_id)
+            .await?;
+        let full_nonce = storage
+            .storage_web3_dal()
+            .get_address_historical_nonce(address, latest_block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        let (committed_nonce, deployment_nonce) = decompose_full_nonce(full_nonce);
+        let committed_nonce = u64::try_from(committed_nonce)
+            .map_err(|err| anyhow::anyhow!("nonce conversion failed: {err}"))?;
+
+        let mempool_nonces = storage
+            .transactions_web3_dal()
+            .get_mempool_nonces_by_initiator_account(address, committed_nonce)
+            .await
+            .map_err(DalError::generalize)?;
+        drop(storage);
+
+        let pending_nonce = if let Some(nonce) = self
+            .state
+            .tx_sink()
+            .lookup_pending_nonce(address, committed_nonce as u32)
+            .await?
+        {
+            nonce.0.into()
+        } else {
+            // Pending nonce is the first gap in mempool nonces, same as in `eth_getTransactionCount`.
+            let mut pending_nonce = committed_nonce;
+            for &nonce in &mempool_nonces {
+                if nonce != pending_nonce {
+                    break;
+                }
+                pending_nonce += 1;
+            }
+            pending_nonce
+        };
+        let limit = self.state.api_config.req_entities_limit;
+        let nonce_gaps = nonce_gaps(committed_nonce, &mempool_nonces, limit);
+
+        Ok(api::AccountNonceDetails {
+            committed_nonce: committed_nonce.into(),
+            pending_nonce: pending_nonce.into(),
+            mempool_nonces: mempool_nonces.into_iter().map(U256::from).collect(),
+            nonce_gaps: nonce_gaps.into_iter().map(U256::from).collect(),
+            deployment_nonce,
+        })
+    }
+
+    /// Returns the latest transaction inclusion stats computed by the house keeper. The returned list is empty
+    /// if stats reporting is disabled or no transactions were included during the configured window.
+    pub async fn get_inclusion_stats_impl(&self) -> Result<Vec<api::InclusionStats>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let stats = storage
+            .inclusion_stats_dal()
+            .get_inclusion_stats()
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(stats)
+    }
+
+    /// Returns contracts with the largest gas or pubdata usage during the specified UTC day (by default, today),
+    /// as aggregated by the house keeper.
+    pub async fn get_top_contracts_impl(
+        &self,
+        day: Option<NaiveDate>,
+        metric: Option<api::ContractUsageMetric>,
+        limit: Option<usize>,
+    ) -> Result<Vec<api::ContractUsage>, Web3Error> {
+        let day = day.unwrap_or_else(|| Utc::now().date_naive());
+        let limit = limit
+            .unwrap_or(Self::DEFAULT_TOP_CONTRACTS)
+            .min(Self::MAX_TOP_CONTRACTS);
+        let mut storage = self.state.acquire_connection().await?;
+        Ok(storage
+            .contract_usage_dal()
+            .get_top_contracts(day, metric.unwrap_or_default(), limit)
+            .await
+            .map_err(DalError::generalize)?)
+    }
+
     pub async fn get_l1_batch_details_impl(
         &self,
         batch_number: L1BatchNumber,
@@ -586,6 +878,42 @@ impl ZksNamespace {
             .map_err(DalError::generalize)?)
     }
 
+    pub async fn get_storage_slot_writer_impl(
+        &self,
+        address: Address,
+        key: H256,
+        block_id: Option<api::BlockId>,
+    ) -> Result<Option<api::StorageSlotWriter>, Web3Error> {
+        let block_id = block_id.unwrap_or(api::BlockId::Number(api::BlockNumber::Pending));
+        self.current_method().set_block_id(block_id);
+
+        let storage_key = StorageKey::new(AccountTreeId::new(address), key);
+        let mut storage = self.state.acquire_connection().await?;
+        let block_number = self.state.resolve_block(&mut storage, block_id).await?;
+        Ok(storage
+            .storage_web3_dal()
+            .get_storage_slot_writer(storage_key.hashed_key(), block_number)
+            .await
+            .map_err(DalError::generalize)?)
+    }
+
+    pub async fn disassemble_bytecode_impl(
+        &self,
+        hash: H256,
+    ) -> Result<Option<api::DisassembledBytecode>, Web3Error> {
+        let is_era_vm_bytecode =
+            BytecodeHash::try_from(hash).is_ok_and(|hash| hash.marker() == BytecodeMarker::EraVm);
+        if !is_era_vm_bytecode {
+            return Ok(None);
+        }
+        let Some(bytecode) = self.get_bytecode_by_hash_impl(hash).await? else {
+            return Ok(None);
+        };
+        let disassembled = disassemble_bytecode(&bytecode)
+            .with_context(|| format!("failed disassembling bytecode {hash:?}"))?;
+        Ok(Some(disassembled))
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn get_fee_params_impl(&self) -> FeeParams {
         self.state
@@ -618,6 +946,17 @@ impl ZksNamespace {
         Ok(protocol_version)
     }
 
+    pub async fn get_protocol_versions_impl(
+        &self,
+    ) -> Result<Vec<api::ProtocolVersionActivation>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        Ok(storage
+            .protocol_versions_web3_dal()
+            .get_protocol_version_activations()
+            .await
+            .map_err(DalError::generalize)?)
+    }
+
     pub async fn get_proofs_impl(
         &self,
         address: Address,
@@ -641,23 +980,7 @@ impl ZksNamespace {
         let proofs_result = tree_api.get_proofs(l1_batch_number, hashed_keys).await;
         let proofs = match proofs_result {
             Ok(proofs) => proofs,
-            Err(TreeApiError::NotReady(_)) => return Err(Web3Error::TreeApiUnavailable),
-            Err(TreeApiError::NoVersion(err)) => {
-                return if err.missing_version > err.version_count {
-                    Ok(None)
-                } else {
-                    Err(Web3Error::InternalError(anyhow::anyhow!(
-                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
-                    )))
-                };
-            }
-            Err(TreeApiError::Internal(err)) => return Err(Web3Error::InternalError(err)),
-            Err(_) => {
-                // This branch is not expected to be executed, but has to be provided since the error is non-exhaustive.
-                return Err(Web3Error::InternalError(anyhow::anyhow!(
-                    "Unspecified tree API error"
-                )));
-            }
+            Err(err) => return Self::handle_tree_api_error(err, l1_batch_number),
         };
 
         let storage_proof = proofs
@@ -677,6 +1000,76 @@ impl ZksNamespace {
         }))
     }
 
+    pub async fn get_multi_proof_impl(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Option<MultiProof>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(l1_batch_number, &mut storage)
+            .await?;
+        drop(storage);
+
+        let hashed_keys = keys
+            .iter()
+            .map(|key| StorageKey::new(AccountTreeId::new(address), *key).hashed_key_u256())
+            .collect();
+        let tree_api = self
+            .state
+            .tree_api
+            .as_deref()
+            .ok_or(Web3Error::MethodNotImplemented)?;
+        let output = match tree_api.get_multiproof(l1_batch_number, hashed_keys).await {
+            Ok(output) => output,
+            Err(err) => return Self::handle_tree_api_error(err, l1_batch_number),
+        };
+
+        let entries = output
+            .entries
+            .into_iter()
+            .zip(keys)
+            .map(|(entry, key)| StorageMultiProofEntry {
+                key,
+                value: entry.value,
+                index: entry.index,
+            })
+            .collect();
+        Ok(Some(MultiProof {
+            address,
+            storage: entries,
+            proof: output.proof,
+        }))
+    }
+
+    /// Converts a tree API error to the API response. An L1 batch not yet processed by the tree results in `Ok(None)`.
+    fn handle_tree_api_error<T>(
+        err: TreeApiError,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Option<T>, Web3Error> {
+        match err {
+            TreeApiError::NotReady(_) => Err(Web3Error::TreeApiUnavailable),
+            TreeApiError::NoVersion(err) => {
+                if err.missing_version > err.version_count {
+                    Ok(None)
+                } else {
+                    Err(Web3Error::InternalError(anyhow::anyhow!(
+                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
+                    )))
+                }
+            }
+            TreeApiError::Internal(err) => Err(Web3Error::InternalError(err)),
+            _ => {
+                // This branch is not expected to be executed, but has to be provided since the error is non-exhaustive.
+                Err(Web3Error::InternalError(anyhow::anyhow!(
+                    "Unspecified tree API error"
+                )))
+            }
+        }
+    }
+
     pub fn get_base_token_l1_address_impl(&self) -> Result<Address, Web3Error> {
         self.state
             .api_config
@@ -756,3 +1149,15 @@ fn map_event(vm_event: VmEvent, tx_hash: H256) -> api::Log {
         block_timestamp: None,
     }
 }
+
+/// Returns nonces in `committed_nonce..max(mempool_nonces)` missing from `mempool_nonces` (which must be sorted),
+/// capped to `limit` entries.
+fn nonce_gaps(committed_nonce: u64, mempool_nonces: &[u64], limit: usize) -> Vec<u64> {
+    let mut gaps = vec![];
+    let mut expected_nonce = committed_nonce;
+    for &nonce in mempool_nonces {
+        gaps.extend((expected_nonce..nonce).take(limit - gaps.len()));
+        expected_nonce = expected_nonce.max(nonce + 1);
+    }
+    gaps
+}
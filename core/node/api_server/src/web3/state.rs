@@ -23,12 +23,20 @@ use zksync_config::{
     },
     GenesisConfig,
 };
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
+use zksync_dal::{
+    pruning_dal::RetainedDataKind, Connection, ConnectionPool, Core, CoreDal, DalError,
+};
 use zksync_metadata_calculator::api_server::TreeApiClient;
 use zksync_node_sync::SyncState;
+use zksync_object_store::{ObjectStore, StoredObject};
+use zksync_state_keeper::{PendingBatchState, PendingBatchStateReader};
 use zksync_types::{
-    api, commitment::L1BatchCommitmentMode, l2::L2Tx, transaction_request::CallRequest, Address,
-    L1BatchNumber, L1ChainId, L2BlockNumber, L2ChainId, H256, U256, U64,
+    api,
+    cold_storage::{CallTracesArchive, ColdStorageDataKind, EventsArchive},
+    commitment::L1BatchCommitmentMode,
+    l2::L2Tx,
+    transaction_request::CallRequest,
+    Address, L1BatchNumber, L1ChainId, L2BlockNumber, L2ChainId, H256, U256, U64,
 };
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -38,6 +46,7 @@ use zksync_web3_decl::{
 
 use super::{
     backend_jsonrpsee::MethodTracer,
+    call_cache::EthCallCache,
     mempool_cache::MempoolCache,
     metrics::{FilterType, FILTER_METRICS},
     TypedFilter,
@@ -117,6 +126,8 @@ pub struct InternalApiConfigBase {
     pub estimate_gas_optimize_search: bool,
     pub req_entities_limit: usize,
     pub fee_history_limit: u64,
+    /// Maximum size of a trace returned by `debug_trace*` methods in bytes.
+    pub max_trace_size: usize,
     pub filters_disabled: bool,
     pub l1_to_l2_txs_paused: bool,
 }
@@ -135,6 +146,7 @@ impl InternalApiConfigBase {
             estimate_gas_optimize_search: web3_config.estimate_gas_optimize_search,
             req_entities_limit: web3_config.req_entities_limit(),
             fee_history_limit: web3_config.fee_history_limit(),
+            max_trace_size: web3_config.max_trace_size(),
             filters_disabled: web3_config.filters_disabled,
             l1_to_l2_txs_paused: false,
         }
@@ -168,6 +180,8 @@ pub struct InternalApiConfig {
     pub l2_testnet_paymaster_addr: Option<Address>,
     pub req_entities_limit: usize,
     pub fee_history_limit: u64,
+    /// Maximum size of a trace returned by `debug_trace*` methods in bytes. Larger traces are progressively truncated.
+    pub max_trace_size: usize,
     pub base_token_address: Option<Address>,
     pub filters_disabled: bool,
     pub dummy_verifier: bool,
@@ -210,6 +224,7 @@ impl InternalApiConfig {
             l2_testnet_paymaster_addr: l2_contracts.testnet_paymaster_addr,
             req_entities_limit: base.req_entities_limit,
             fee_history_limit: base.fee_history_limit,
+            max_trace_size: base.max_trace_size,
             base_token_address: Some(l1_ecosystem_contracts.base_token_address),
             filters_disabled: base.filters_disabled,
             dummy_verifier: base.dummy_verifier,
@@ -317,9 +332,15 @@ pub(crate) struct RpcState {
     /// from a snapshot.
     pub(super) start_info: BlockStartInfo,
     pub(super) mempool_cache: Option<MempoolCache>,
+    /// Cache for `eth_call` results on sealed L2 blocks. If not set, results are not cached.
+    pub(super) eth_call_cache: Option<EthCallCache>,
     pub(super) last_sealed_l2_block: SealedL2BlockNumber,
     pub(super) bridge_addresses_handle: BridgeAddressesHandle,
     pub(super) l2_l1_log_proof_handler: Option<Box<DynClient<L2>>>,
+    /// Object store with call traces and events moved out of Postgres by the cold storage archiver.
+    pub(super) cold_storage: Option<Arc<dyn ObjectStore>>,
+    /// State of the L1 batch processed by the state keeper, if the state keeper runs in the same process.
+    pub(super) pending_batch_state: Option<PendingBatchStateReader>,
 }
 
 impl RpcState {
@@ -410,13 +431,40 @@ impl RpcState {
         connection: &mut Connection<'_, Core>,
         block: api::BlockId,
     ) -> Result<BlockArgs, Web3Error> {
-        BlockArgs::new(connection, block, &self.start_info)
+        let block_args = BlockArgs::new(connection, block, &self.start_info)
             .await
             .map_err(|err| match err {
                 BlockArgsError::Pruned(number) => Web3Error::PrunedBlock(number),
                 BlockArgsError::Missing => Web3Error::NoBlock,
                 BlockArgsError::Database(err) => Web3Error::InternalError(err),
-            })
+            })?;
+        Ok(if block_args.is_pending() {
+            let pending_state = self.pending_batch_state(block_args.state_l2_block_number() + 1);
+            block_args.with_pending_state(pending_state)
+        } else {
+            block_args
+        })
+    }
+
+    /// Returns the state of the L1 batch processed by the state keeper if it can be applied on top of Postgres state
+    /// with the specified first non-persisted (i.e., pending) L2 block. Returns `None` if the state keeper doesn't run
+    /// in the same process, or if the persisted state has caught up with the state keeper.
+    pub(crate) fn pending_batch_state(
+        &self,
+        pending_l2_block: L2BlockNumber,
+    ) -> Option<Arc<PendingBatchState>> {
+        let snapshot = self.pending_batch_state.as_ref()?.snapshot()?;
+        let sealed_l2_block = L2BlockNumber(pending_l2_block.0.checked_sub(1)?);
+        if snapshot.is_applicable_to(sealed_l2_block) {
+            Some(snapshot)
+        } else {
+            tracing::trace!(
+                "Pending state for L1 batch #{} (open L2 block #{}) is not applicable on top of L2 block #{sealed_l2_block}",
+                snapshot.l1_batch(),
+                snapshot.open_l2_block()
+            );
+            None
+        }
     }
 
     pub async fn resolve_filter_block_number(
@@ -502,6 +550,116 @@ impl RpcState {
         call_request.nonce = Some(address_historical_nonce);
         Ok(())
     }
+
+    /// Returns an error if data of the specified kind was removed for the specified L2 block by data retention.
+    /// Without this check, the API would silently return empty call traces or logs for such blocks.
+    pub(crate) async fn ensure_data_retained(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        kind: RetainedDataKind,
+        l2_block_number: L2BlockNumber,
+    ) -> Result<(), Web3Error> {
+        let Some(last_pruned_l1_batch) = connection
+            .pruning_dal()
+            .get_last_retention_pruned_l1_batch(kind)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(());
+        };
+        let last_pruned_l2_block = connection
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(last_pruned_l1_batch)
+            .await
+            .map_err(DalError::generalize)?;
+        // If the L1 batch is missing, it was removed by the DB pruner, which is checked separately.
+        let Some((_, last_pruned_l2_block)) = last_pruned_l2_block else {
+            return Ok(());
+        };
+        if l2_block_number <= last_pruned_l2_block {
+            return Err(Web3Error::PrunedData(
+                kind.as_str(),
+                last_pruned_l1_batch + 1,
+            ));
+        }
+        Ok(())
+    }
+
+    async fn load_cold_storage_archive<T: for<'a> StoredObject<Key<'a> = L1BatchNumber>>(
+        &self,
+        kind: ColdStorageDataKind,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<T, Web3Error> {
+        let cold_storage = self.cold_storage.as_ref().with_context(|| {
+            format!(
+                "{} for L1 batch #{l1_batch_number} are archived in cold storage, which is not configured",
+                kind.as_str()
+            )
+        })?;
+        let archive = cold_storage.get(l1_batch_number).await.with_context(|| {
+            format!(
+                "failed loading {} for L1 batch #{l1_batch_number} from cold storage",
+                kind.as_str()
+            )
+        })?;
+        Ok(archive)
+    }
+
+    /// Loads call traces for the specified L1 batch from cold storage.
+    pub(crate) async fn load_archived_call_traces(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<CallTracesArchive, Web3Error> {
+        self.load_cold_storage_archive(ColdStorageDataKind::CallTraces, l1_batch_number)
+            .await
+    }
+
+    /// Fills payloads of `logs` moved to cold storage. Logs from non-archived L1 batches are left as is.
+    pub(crate) async fn fill_archived_logs(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        logs: impl IntoIterator<Item = &mut api::Log>,
+    ) -> Result<(), Web3Error> {
+        let mut logs: Vec<_> = logs.into_iter().collect();
+        let mut l1_batch_numbers: Vec<_> = logs
+            .iter()
+            .filter_map(|log| Some(L1BatchNumber(log.l1_batch_number?.as_u32())))
+            .collect();
+        l1_batch_numbers.sort_unstable();
+        l1_batch_numbers.dedup();
+        if l1_batch_numbers.is_empty() {
+            return Ok(());
+        }
+
+        let archived_l1_batches = connection
+            .cold_storage_dal()
+            .filter_archived_l1_batches(ColdStorageDataKind::Events, &l1_batch_numbers)
+            .await
+            .map_err(DalError::generalize)?;
+        for l1_batch_number in archived_l1_batches {
+            let archive: EventsArchive = self
+                .load_cold_storage_archive(ColdStorageDataKind::Events, l1_batch_number)
+                .await?;
+            let batch_logs = logs
+                .iter_mut()
+                .filter(|log| log.l1_batch_number == Some(U64::from(l1_batch_number.0)));
+            for log in batch_logs {
+                let (Some(block_number), Some(log_index)) = (log.block_number, log.log_index)
+                else {
+                    continue;
+                };
+                let value = archive
+                    .get(L2BlockNumber(block_number.as_u32()), log_index.as_u32())
+                    .with_context(|| {
+                        format!(
+                            "event #{log_index} in L2 block #{block_number} is missing from cold storage archive"
+                        )
+                    })?;
+                log.data = value.to_vec().into();
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Contains mapping from index to `Filter`s with optional location.
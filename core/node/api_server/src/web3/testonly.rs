@@ -108,6 +108,7 @@ pub struct TestServerBuilder {
     tx_executor: MockOneshotExecutor,
     executor_options: Option<SandboxExecutorOptions>,
     method_tracer: Arc<MethodTracer>,
+    pending_batch_state: Option<PendingBatchStateReader>,
 }
 
 impl TestServerBuilder {
@@ -119,6 +120,7 @@ impl TestServerBuilder {
             tx_executor: MockOneshotExecutor::default(),
             executor_options: None,
             method_tracer: Arc::default(),
+            pending_batch_state: None,
         }
     }
 
@@ -142,6 +144,13 @@ impl TestServerBuilder {
         self
     }
 
+    /// Sets the state of the L1 batch processed by the state keeper.
+    #[must_use]
+    pub fn with_pending_batch_state(mut self, pending_state: PendingBatchStateReader) -> Self {
+        self.pending_batch_state = Some(pending_state);
+        self
+    }
+
     /// Builds an HTTP server.
     pub async fn build_http(self, stop_receiver: watch::Receiver<bool>) -> ApiServerHandles {
         self.spawn_server(ApiTransportLabel::Http, None, stop_receiver)
@@ -175,6 +184,7 @@ impl TestServerBuilder {
             pool,
             api_config,
             method_tracer,
+            pending_batch_state,
         } = self;
 
         let tx_executor = if let Some(options) = executor_options {
@@ -187,12 +197,17 @@ impl TestServerBuilder {
         let (pub_sub_events_sender, pub_sub_events_receiver) = mpsc::unbounded_channel();
 
         let mut namespaces = Namespace::DEFAULT.to_vec();
-        namespaces.extend([Namespace::Debug, Namespace::Snapshots, Namespace::Unstable]);
+        namespaces.extend([
+            Namespace::Debug,
+            Namespace::Snapshots,
+            Namespace::Unstable,
+            Namespace::Interop,
+        ]);
         let sealed_l2_block_handle = SealedL2BlockNumber::default();
         let bridge_addresses_handle =
             BridgeAddressesHandle::new(api_config.bridge_addresses.clone());
 
-        let server_builder = match transport {
+        let mut server_builder = match transport {
             ApiTransportLabel::Http => ApiBuilder::jsonrpsee_backend(api_config, pool).http(0),
             ApiTransportLabel::Ws => {
                 let mut builder = ApiBuilder::jsonrpsee_backend(api_config, pool)
@@ -208,6 +223,9 @@ impl TestServerBuilder {
                 builder
             }
         };
+        if let Some(pending_state) = pending_batch_state {
+            server_builder = server_builder.with_pending_batch_state(pending_state);
+        }
         let server_handles = server_builder
             .with_polling_interval(POLL_INTERVAL)
             .with_tx_sender(tx_sender)
@@ -0,0 +1,210 @@
+//! Tests for the `admin` Web3 namespace.
+
+use http::{header, HeaderMap, HeaderValue};
+use zksync_state_keeper::L1BatchSealRequest;
+
+use super::*;
+use crate::{
+    execution_sandbox::SandboxExecutor,
+    web3::{testonly::create_test_tx_sender, OPERATOR_HEADER},
+};
+
+const AUTH_TOKEN: &str = "correct-horse-battery-staple";
+
+async fn admin_api_builder(pool: ConnectionPool<Core>) -> ApiBuilder {
+    let contracts_config = ContractsConfig::for_tests();
+    let web3_config = Web3JsonRpcConfig::for_tests();
+    let genesis = GenesisConfig::for_tests();
+    let api_config = InternalApiConfig::new(
+        &web3_config,
+        &contracts_config.settlement_layer_specific_contracts(),
+        &contracts_config.l1_specific_contracts(),
+        &contracts_config.l2_contracts(),
+        &genesis,
+        false,
+    );
+    let tx_executor = SandboxExecutor::mock(MockOneshotExecutor::default()).await;
+    let (tx_sender, _) =
+        create_test_tx_sender(pool.clone(), api_config.l2_chain_id, tx_executor).await;
+    let bridge_addresses_handle = BridgeAddressesHandle::new(api_config.bridge_addresses.clone());
+
+    ApiBuilder::jsonrpsee_backend(api_config, pool)
+        .http(0)
+        .with_polling_interval(POLL_INTERVAL)
+        .with_tx_sender(tx_sender)
+        .with_sealed_l2_block_handle(SealedL2BlockNumber::default())
+        .with_bridge_addresses_handle(bridge_addresses_handle)
+        .enable_api_namespaces(vec![Namespace::Admin])
+}
+
+#[tokio::test]
+async fn admin_server_requires_dedicated_authenticated_server() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+
+    let err = admin_api_builder(pool.clone()).await.build().unwrap_err();
+    assert!(err.to_string().contains("auth token"), "{err}");
+
+    let err = admin_api_builder(pool.clone())
+        .await
+        .with_admin_auth_token(AUTH_TOKEN.into())
+        .enable_api_namespaces(vec![Namespace::Admin, Namespace::Eth])
+        .build()
+        .unwrap_err();
+    assert!(err.to_string().contains("separate server"), "{err}");
+
+    let err = admin_api_builder(pool)
+        .await
+        .with_admin_auth_token(AUTH_TOKEN.into())
+        .ws(0)
+        .build()
+        .unwrap_err();
+    assert!(err.to_string().contains("HTTP"), "{err}");
+}
+
+#[tokio::test]
+async fn admin_server_basics() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    StorageInitialization::genesis()
+        .prepare_storage(&mut storage)
+        .await
+        .unwrap();
+    drop(storage);
+
+    let seal_request = L1BatchSealRequest::default();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let mut server_handles = admin_api_builder(pool)
+        .await
+        .with_admin_auth_token(AUTH_TOKEN.into())
+        .with_l1_batch_seal_request(seal_request.clone())
+        .build()
+        .unwrap()
+        .run(stop_receiver)
+        .await
+        .unwrap();
+    let local_addr = server_handles.wait_until_ready().await;
+    let url = format!("http://{local_addr}/");
+
+    let unauthenticated_client = <HttpClient>::builder().build(&url).unwrap();
+    let err = unauthenticated_client
+        .request::<bool, _>("admin_pauseTxIntake", rpc_params![])
+        .await
+        .unwrap_err();
+    assert_matches!(err, ClientError::Transport(_));
+
+    let mut headers = HeaderMap::new();
+    let auth_header = format!("Bearer {AUTH_TOKEN}");
+    headers.insert(
+        header::AUTHORIZATION,
+        HeaderValue::from_str(&auth_header).unwrap(),
+    );
+    headers.insert(OPERATOR_HEADER, HeaderValue::from_static("alice"));
+    let client = <HttpClient>::builder()
+        .set_headers(headers)
+        .build(&url)
+        .unwrap();
+
+    let paused: bool = client
+        .request("admin_pauseTxIntake", rpc_params![])
+        .await
+        .unwrap();
+    assert!(paused);
+    let paused: bool = client
+        .request("admin_pauseTxIntake", rpc_params![])
+        .await
+        .unwrap();
+    assert!(!paused);
+
+    let queues: api::ComponentQueues = client
+        .request("admin_getComponentQueues", rpc_params![])
+        .await
+        .unwrap();
+    assert!(queues.tx_intake_paused);
+    assert_eq!(queues.l1_batch_seal_requested, Some(false));
+    assert_eq!(queues.last_sealed_l1_batch, Some(L1BatchNumber(0)));
+    assert_eq!(queues.unconfirmed_eth_txs, 0);
+
+    let resumed: bool = client
+        .request("admin_resumeTxIntake", rpc_params![])
+        .await
+        .unwrap();
+    assert!(resumed);
+
+    client
+        .request::<(), _>("admin_sealL1Batch", rpc_params![])
+        .await
+        .unwrap();
+    assert!(seal_request.is_requested());
+
+    let updated: bool = client
+        .request("admin_setProofPriority", rpc_params![L1BatchNumber(1), 10])
+        .await
+        .unwrap();
+    assert!(!updated); // the batch doesn't exist
+
+    let lease: Option<api::SequencerLease> = client
+        .request("admin_getSequencerLease", rpc_params![])
+        .await
+        .unwrap();
+    assert_eq!(lease, None);
+    let lease: api::SequencerLease = client
+        .request("admin_promoteSequencer", rpc_params!["standby"])
+        .await
+        .unwrap();
+    assert_eq!(lease.instance_id, "standby");
+    assert_eq!(lease.epoch, 1);
+    let lease: api::SequencerLease = client
+        .request("admin_promoteSequencer", rpc_params!["primary"])
+        .await
+        .unwrap();
+    assert_eq!(lease.epoch, 2);
+    let current_lease: Option<api::SequencerLease> = client
+        .request("admin_getSequencerLease", rpc_params![])
+        .await
+        .unwrap();
+    assert_eq!(current_lease, Some(lease.clone()));
+
+    let audit_log: Vec<api::AuditLogEntry> = client
+        .request("admin_getAuditLog", rpc_params![0, ()])
+        .await
+        .unwrap();
+    let actions: Vec<_> = audit_log
+        .iter()
+        .map(|entry| entry.action.as_str())
+        .collect();
+    // `admin_setProofPriority` didn't change anything, so it's not recorded.
+    assert_eq!(
+        actions,
+        [
+            "admin_pauseTxIntake",
+            "admin_pauseTxIntake",
+            "admin_resumeTxIntake",
+            "admin_sealL1Batch",
+            "admin_promoteSequencer",
+            "admin_promoteSequencer",
+        ]
+    );
+    assert!(audit_log.iter().all(|entry| entry.actor == "alice"));
+    assert_eq!(
+        audit_log[0].before,
+        Some(serde_json::json!({ "txIntakePaused": false }))
+    );
+    assert_eq!(
+        audit_log[0].after,
+        Some(serde_json::json!({ "txIntakePaused": true }))
+    );
+    assert_eq!(audit_log[4].before, None);
+    assert_eq!(
+        audit_log[5].after,
+        Some(serde_json::to_value(&lease).unwrap())
+    );
+
+    let tail: Vec<api::AuditLogEntry> = client
+        .request("admin_getAuditLog", rpc_params![audit_log[4].id, 1])
+        .await
+        .unwrap();
+    assert_eq!(tail, [audit_log[4].clone()]);
+
+    stop_sender.send_replace(true);
+    server_handles.shutdown().await;
+}
@@ -183,6 +183,64 @@ async fn log_filter_changes_after_snapshot_recovery() {
     .await;
 }
 
+#[derive(Debug)]
+struct LogFilterChangesWithBloomsTest;
+
+#[async_trait]
+impl HttpTest for LogFilterChangesWithBloomsTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let all_logs_filter_id = client.new_filter(Filter::default()).await?;
+        let address_filter = Filter {
+            address: Some(Address::repeat_byte(23).into()),
+            ..Filter::default()
+        };
+        let address_filter_id = client.new_filter(address_filter).await?;
+
+        let mut storage = pool.connection().await?;
+        let (_, events) = store_events(&mut storage, 1, 0).await?;
+        // Store an L2 block with events, but with an empty log bloom. The bloom is authoritative,
+        // so the block should be skipped by filters with address / topic constraints.
+        let (_, unindexed_events) = store_events(&mut storage, 2, 4).await?;
+        storage
+            .blocks_dal()
+            .range_update_logs_bloom(L2BlockNumber(2), &[Bloom::zero()])
+            .await?;
+        drop(storage);
+        let events: Vec<_> = events.iter().collect();
+        let all_events: Vec<_> = events
+            .iter()
+            .copied()
+            .chain(unindexed_events.iter())
+            .collect();
+
+        let all_logs = client.get_filter_changes(all_logs_filter_id).await?;
+        let FilterChanges::Logs(all_logs) = all_logs else {
+            panic!("Unexpected getFilterChanges output: {:?}", all_logs);
+        };
+        assert_logs_match(&all_logs, &all_events);
+
+        let address_logs = client.get_filter_changes(address_filter_id).await?;
+        let FilterChanges::Logs(address_logs) = address_logs else {
+            panic!("Unexpected getFilterChanges output: {:?}", address_logs);
+        };
+        assert_logs_match(&address_logs, &[events[0], events[3]]);
+
+        // The skipped block must not be returned on subsequent polls either.
+        let new_address_logs = client.get_filter_changes(address_filter_id).await?;
+        assert_matches!(new_address_logs, FilterChanges::Hashes(hashes) if hashes.is_empty());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn log_filter_changes_with_blooms() {
+    test_http_server(LogFilterChangesWithBloomsTest).await;
+}
+
 #[derive(Debug)]
 struct LogFilterChangesWithBlockBoundariesTest;
 
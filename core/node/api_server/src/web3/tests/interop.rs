@@ -0,0 +1,71 @@
+//! Tests for the `interop` Web3 namespace.
+
+use zksync_system_constants::{L1_MESSENGER_ADDRESS, L2_BASE_TOKEN_ADDRESS};
+use zksync_types::{
+    address_to_h256,
+    api::L2ToL1MessageConsumption,
+    l2_to_l1_log::{L2ToL1Log, UserL2ToL1Log},
+};
+use zksync_web3_decl::namespaces::InteropNamespaceClient;
+
+use super::*;
+
+#[derive(Debug)]
+struct L2ToL1MessagesTest;
+
+#[async_trait]
+impl HttpTest for L2ToL1MessagesTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await?;
+        let l2_block = store_l2_block(&mut storage, L2BlockNumber(1), &[]).await?;
+        let tx_hash = H256::repeat_byte(1);
+        let tx_location = IncludedTxLocation {
+            tx_hash,
+            tx_index_in_l2_block: 0,
+        };
+        let message_log = UserL2ToL1Log(L2ToL1Log {
+            shard_id: 0,
+            is_service: true,
+            tx_number_in_block: 0,
+            sender: L1_MESSENGER_ADDRESS,
+            key: address_to_h256(&L2_BASE_TOKEN_ADDRESS),
+            value: H256::repeat_byte(2),
+        });
+        storage
+            .events_dal()
+            .save_user_l2_to_l1_logs(l2_block.number, &[(tx_location, vec![&message_log])])
+            .await?;
+
+        let messages = client.get_l2_to_l1_messages_by_tx_hash(tx_hash).await?;
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].sender, L2_BASE_TOKEN_ADDRESS);
+        assert_eq!(messages[0].message_hash, H256::repeat_byte(2));
+        assert_eq!(messages[0].l1_batch_number, None);
+        assert!(!messages[0].proof_available);
+
+        seal_l1_batch(&mut storage, L1BatchNumber(1)).await?;
+        let messages = client
+            .get_l2_to_l1_messages_by_l1_batch(L1BatchNumber(1))
+            .await?;
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].transaction_hash, tx_hash);
+        assert_eq!(messages[0].l1_batch_number, Some(L1BatchNumber(1)));
+        assert_eq!(messages[0].merkle_index, Some(0));
+        assert!(messages[0].proof_available);
+        assert!(!messages[0].l1_batch_executed);
+        assert_eq!(messages[0].consumption, L2ToL1MessageConsumption::Untracked);
+
+        let messages = client.get_l2_to_l1_messages_by_tx_hash(tx_hash).await?;
+        assert_eq!(messages[0].merkle_index, Some(0));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_l2_to_l1_messages() {
+    test_http_server(L2ToL1MessagesTest).await;
+}
@@ -27,7 +27,7 @@ use zksync_system_constants::{
 };
 use zksync_types::{
     api,
-    block::{pack_block_info, L2BlockHasher, L2BlockHeader, UnsealedL1BatchHeader},
+    block::{build_bloom, pack_block_info, L2BlockHasher, L2BlockHeader, UnsealedL1BatchHeader},
     bytecode::{
         testonly::{PADDED_EVM_BYTECODE, PROCESSED_EVM_BYTECODE},
         BytecodeHash,
@@ -39,8 +39,11 @@ use zksync_types::{
     tokens::{TokenInfo, TokenMetadata},
     tx::IncludedTxLocation,
     u256_to_h256,
-    utils::{storage_key_for_eth_balance, storage_key_for_standard_token_balance},
-    AccountTreeId, Address, L1BatchNumber, Nonce, StorageKey, StorageLog, H256, U256, U64,
+    utils::{
+        nonces_to_full_nonce, storage_key_for_eth_balance, storage_key_for_standard_token_balance,
+    },
+    AccountTreeId, Address, Bloom, BloomInput, L1BatchNumber, Nonce, StorageKey, StorageLog, H256,
+    U256, U64,
 };
 use zksync_vm_executor::oneshot::MockOneshotExecutor;
 use zksync_web3_decl::{
@@ -64,8 +67,10 @@ use crate::{
     web3::testonly::TestServerBuilder,
 };
 
+mod admin;
 mod debug;
 mod filters;
+mod interop;
 mod snapshots;
 mod unstable;
 mod vm;
@@ -154,6 +159,11 @@ trait HttpTest: Send + Sync {
         Arc::default()
     }
 
+    /// Returns the state of the L1 batch processed by the (mock) state keeper.
+    fn pending_batch_state(&self) -> Option<PendingBatchStateReader> {
+        None
+    }
+
     async fn test(&self, client: &DynClient<L2>, pool: &ConnectionPool<Core>)
         -> anyhow::Result<()>;
 
@@ -292,6 +302,9 @@ async fn test_http_server(test: impl HttpTest) {
     if let Some(executor_options) = test.executor_options() {
         server_builder = server_builder.with_executor_options(executor_options);
     }
+    if let Some(pending_state) = test.pending_batch_state() {
+        server_builder = server_builder.with_pending_batch_state(pending_state);
+    }
     let mut server_handles = server_builder.build_http(stop_receiver).await;
 
     let local_addr = server_handles.wait_until_ready().await;
@@ -379,9 +392,8 @@ async fn store_events(
     l2_block_number: u32,
     start_idx: u32,
 ) -> anyhow::Result<(IncludedTxLocation, Vec<VmEvent>)> {
-    let new_l2_block = create_l2_block(l2_block_number);
+    let mut new_l2_block = create_l2_block(l2_block_number);
     let l1_batch_number = L1BatchNumber(l2_block_number);
-    storage.blocks_dal().insert_l2_block(&new_l2_block).await?;
     let tx_location = IncludedTxLocation {
         tx_hash: H256::repeat_byte(1),
         tx_index_in_l2_block: 0,
@@ -416,6 +428,14 @@ async fn store_events(
             value: (start_idx + 3).to_le_bytes().to_vec(),
         },
     ];
+    new_l2_block.logs_bloom = build_bloom(events.iter().flat_map(|event| {
+        event
+            .indexed_topics
+            .iter()
+            .map(|topic| BloomInput::Raw(topic.as_bytes()))
+            .chain([BloomInput::Raw(event.address.as_bytes())])
+    }));
+    storage.blocks_dal().insert_l2_block(&new_l2_block).await?;
     storage
         .events_dal()
         .save_events(
@@ -681,6 +701,78 @@ async fn storage_access_with_snapshot_recovery() {
     test_http_server(StorageAccessWithSnapshotRecovery).await;
 }
 
+#[derive(Debug)]
+struct StorageAccessWithPendingState;
+
+impl StorageAccessWithPendingState {
+    const ADDRESS: Address = Address::repeat_byte(1);
+
+    fn storage_key() -> StorageKey {
+        StorageKey::new(AccountTreeId::new(Self::ADDRESS), H256::zero())
+    }
+}
+
+#[async_trait]
+impl HttpTest for StorageAccessWithPendingState {
+    fn pending_batch_state(&self) -> Option<PendingBatchStateReader> {
+        let storage_writes = HashMap::from([
+            (
+                storage_key_for_eth_balance(&Self::ADDRESS),
+                H256::from_low_u64_be(123),
+            ),
+            (Self::storage_key(), H256::repeat_byte(0xff)),
+        ]);
+        // Genesis L2 block is persisted, so the first L2 block is open.
+        Some(PendingBatchStateReader::mock(
+            L1BatchNumber(1),
+            L2BlockNumber(1),
+            storage_writes,
+        ))
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let pending = api::BlockIdVariant::BlockNumber(api::BlockNumber::Pending);
+        let latest = api::BlockIdVariant::BlockNumber(api::BlockNumber::Latest);
+
+        let balance = client.get_balance(Self::ADDRESS, Some(pending)).await?;
+        assert_eq!(balance, 123.into());
+        let storage_value = client
+            .get_storage_at(Self::ADDRESS, 0.into(), Some(pending))
+            .await?;
+        assert_eq!(storage_value, H256::repeat_byte(0xff));
+
+        // Unsealed changes must not be visible in the latest block.
+        let balance = client.get_balance(Self::ADDRESS, Some(latest)).await?;
+        assert_eq!(balance, 0.into());
+        let storage_value = client
+            .get_storage_at(Self::ADDRESS, 0.into(), Some(latest))
+            .await?;
+        assert_eq!(storage_value, H256::zero());
+
+        // After the open L2 block is persisted, the pending state becomes stale and must be ignored.
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &[]).await?;
+        drop(storage);
+
+        let balance = client.get_balance(Self::ADDRESS, Some(pending)).await?;
+        assert_eq!(balance, 0.into());
+        let storage_value = client
+            .get_storage_at(Self::ADDRESS, 0.into(), Some(pending))
+            .await?;
+        assert_eq!(storage_value, H256::zero());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn storage_access_with_pending_state() {
+    test_http_server(StorageAccessWithPendingState).await;
+}
+
 #[derive(Debug)]
 struct TransactionCountTest;
 
@@ -777,6 +869,74 @@ async fn getting_transaction_count_for_account() {
     test_http_server(TransactionCountTest).await;
 }
 
+#[derive(Debug)]
+struct AccountNonceDetailsTest;
+
+#[async_trait]
+impl HttpTest for AccountNonceDetailsTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let test_address = Address::repeat_byte(11);
+        let details = client.get_account_nonce_details(test_address).await?;
+        assert_eq!(details.committed_nonce, 0.into());
+        assert_eq!(details.pending_nonce, 0.into());
+        assert!(details.mempool_nonces.is_empty());
+        assert!(details.nonce_gaps.is_empty());
+        assert_eq!(details.deployment_nonce, 0.into());
+
+        let mut storage = pool.connection().await?;
+        let mut committed_tx = create_l2_transaction(10, 200);
+        committed_tx.common_data.initiator_address = test_address;
+        committed_tx.common_data.nonce = Nonce(0);
+        store_l2_block(
+            &mut storage,
+            L2BlockNumber(1),
+            &[mock_execute_transaction(committed_tx.into())],
+        )
+        .await?;
+        let full_nonce = nonces_to_full_nonce(1.into(), 3.into());
+        let nonce_log =
+            StorageLog::new_write_log(get_nonce_key(&test_address), u256_to_h256(full_nonce));
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(1), &[nonce_log])
+            .await?;
+
+        for nonce in [1, 2, 5, 7] {
+            let mut pending_tx = create_l2_transaction(10, 200);
+            pending_tx.common_data.initiator_address = test_address;
+            pending_tx.common_data.nonce = Nonce(nonce);
+            storage
+                .transactions_dal()
+                .insert_transaction_l2(
+                    &pending_tx,
+                    TransactionExecutionMetrics::default(),
+                    ValidationTraces::default(),
+                )
+                .await?;
+        }
+
+        let details = client.get_account_nonce_details(test_address).await?;
+        assert_eq!(details.committed_nonce, 1.into());
+        assert_eq!(details.pending_nonce, 3.into());
+        assert_eq!(details.mempool_nonces, [1_u64, 2, 5, 7].map(U256::from));
+        assert_eq!(details.nonce_gaps, [3_u64, 4, 6].map(U256::from));
+        assert_eq!(details.deployment_nonce, 3.into());
+
+        let pending_count = client.get_transaction_count(test_address, None).await?;
+        assert_eq!(pending_count, details.pending_nonce);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_account_nonce_details() {
+    test_http_server(AccountNonceDetailsTest).await;
+}
+
 #[derive(Debug)]
 struct TransactionCountAfterSnapshotRecoveryTest;
 
@@ -1234,6 +1394,55 @@ async fn getting_bytecodes() {
     test_http_server(GetBytecodeTest).await;
 }
 
+#[derive(Debug)]
+struct DisassembleBytecodeTest;
+
+#[async_trait]
+impl HttpTest for DisassembleBytecodeTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let evm_address = Address::repeat_byte(1);
+        let mut connection = pool.connection().await?;
+        GetBytecodeTest::insert_evm_bytecode(&mut connection, L2BlockNumber(0), evm_address)
+            .await?;
+
+        let contract = &get_system_smart_contracts()[0];
+        let bytecode_hash = BytecodeHash::for_bytecode(&contract.bytecode).value();
+        let disassembled = client
+            .disassemble_bytecode(bytecode_hash)
+            .await?
+            .context("no disassembled bytecode")?;
+        assert_eq!(
+            disassembled.instructions.len(),
+            (contract.bytecode.len() / 8).min(1 << 16)
+        );
+        assert_eq!(disassembled.basic_blocks[0].start, 0);
+        let last_block = disassembled.basic_blocks.last().unwrap();
+        assert_eq!(
+            usize::from(last_block.end),
+            disassembled.instructions.len() - 1
+        );
+
+        let evm_bytecode_hash =
+            BytecodeHash::for_evm_bytecode(PROCESSED_EVM_BYTECODE.len(), PADDED_EVM_BYTECODE)
+                .value();
+        let disassembled = client.disassemble_bytecode(evm_bytecode_hash).await?;
+        assert_eq!(disassembled, None);
+        let unknown_hash = BytecodeHash::for_bytecode(&[0; 32]).value();
+        let disassembled = client.disassemble_bytecode(unknown_hash).await?;
+        assert_eq!(disassembled, None);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn disassembling_bytecodes() {
+    test_http_server(DisassembleBytecodeTest).await;
+}
+
 #[derive(Debug)]
 struct FeeHistoryTest;
 
@@ -1352,3 +1561,39 @@ impl HttpTest for FeeHistoryTest {
 async fn getting_fee_history() {
     test_http_server(FeeHistoryTest).await;
 }
+
+#[derive(Debug)]
+struct ProtocolVersionsTest;
+
+#[async_trait]
+impl HttpTest for ProtocolVersionsTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let latest_version = client.get_protocol_version(None).await?.unwrap();
+        let versions = client.get_protocol_versions().await?;
+        let genesis_version = versions
+            .iter()
+            .find(|version| version.first_l1_batch == Some(L1BatchNumber(0)))
+            .context("no version for genesis batch")?;
+        assert_eq!(
+            Some(genesis_version.minor_version),
+            latest_version.minor_version()
+        );
+        assert_eq!(
+            Some(genesis_version.bootloader_code_hash),
+            latest_version.bootloader_code_hash()
+        );
+        assert!(genesis_version.first_l1_batch_timestamp.is_some());
+        assert_eq!(genesis_version.patches.len(), 1, "{genesis_version:?}");
+        assert_eq!(genesis_version.patches[0].patch, 0);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_protocol_versions() {
+    test_http_server(ProtocolVersionsTest).await;
+}
@@ -2,7 +2,7 @@
 
 use std::collections::HashSet;
 
-use zksync_types::snapshots::SnapshotVersion;
+use zksync_types::snapshots::{SnapshotStorageLogsChunkIndex, SnapshotVersion};
 use zksync_web3_decl::namespaces::SnapshotsNamespaceClient;
 
 use super::*;
@@ -109,3 +109,159 @@ async fn snapshot_with_some_chunks() {
 async fn snapshot_with_all_chunks() {
     test_http_server(SnapshotBasicsTest::new(0..SnapshotBasicsTest::CHUNK_COUNT)).await;
 }
+
+#[derive(Debug)]
+struct DifferentialSnapshotTest;
+
+impl DifferentialSnapshotTest {
+    const CHUNK_COUNT: u64 = 2;
+}
+
+#[async_trait]
+impl HttpTest for DifferentialSnapshotTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await.unwrap();
+        for number in 1..=2 {
+            store_l2_block(
+                &mut storage,
+                L2BlockNumber(number),
+                &[mock_execute_transaction(create_l2_transaction(1, 2).into())],
+            )
+            .await?;
+            seal_l1_batch(&mut storage, L1BatchNumber(number)).await?;
+        }
+
+        storage
+            .snapshots_dal()
+            .add_snapshot(
+                SnapshotVersion::Version1,
+                L1BatchNumber(1),
+                Self::CHUNK_COUNT,
+                "file:///factory_deps",
+            )
+            .await?;
+        storage
+            .snapshots_dal()
+            .add_differential_snapshot(
+                SnapshotVersion::Version1,
+                L1BatchNumber(2),
+                L1BatchNumber(1),
+                Self::CHUNK_COUNT,
+                "file:///factory_deps_delta",
+            )
+            .await?;
+        for l1_batch_number in [L1BatchNumber(1), L1BatchNumber(2)] {
+            for chunk_id in 0..Self::CHUNK_COUNT {
+                let path = format!("file:///storage_logs/{l1_batch_number}/chunk{chunk_id}");
+                storage
+                    .snapshots_dal()
+                    .add_storage_logs_filepath_for_snapshot(l1_batch_number, chunk_id, &path)
+                    .await?;
+            }
+        }
+
+        let all_snapshots = client.get_all_snapshots().await?;
+        assert_eq!(all_snapshots.snapshots_l1_batch_numbers, [L1BatchNumber(1)]);
+        let snapshot_header = client
+            .get_snapshot_by_l1_batch_number(L1BatchNumber(2))
+            .await?;
+        assert!(snapshot_header.is_none(), "{snapshot_header:?}");
+
+        let manifest = client
+            .get_snapshot_manifest(None)
+            .await?
+            .context("no snapshot manifest")?;
+        manifest.validate()?;
+        assert_eq!(manifest.base.l1_batch_number, L1BatchNumber(1));
+        assert_eq!(manifest.deltas.len(), 1);
+        let target = manifest.target();
+        assert_eq!(target.l1_batch_number, L1BatchNumber(2));
+        assert_eq!(target.l2_block_number, L2BlockNumber(2));
+        assert_eq!(target.base_l1_batch_number, Some(L1BatchNumber(1)));
+        assert_eq!(target.factory_deps_filepath, "file:///factory_deps_delta");
+
+        let manifest = client
+            .get_snapshot_manifest(Some(L1BatchNumber(1)))
+            .await?
+            .context("no snapshot manifest")?;
+        assert_eq!(manifest.base.l1_batch_number, L1BatchNumber(1));
+        assert!(manifest.deltas.is_empty());
+
+        let manifest = client.get_snapshot_manifest(Some(L1BatchNumber(3))).await?;
+        assert!(manifest.is_none(), "{manifest:?}");
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn differential_snapshot_manifest() {
+    test_http_server(DifferentialSnapshotTest).await;
+}
+
+#[derive(Debug)]
+struct SnapshotWithChunkIndexesTest;
+
+#[async_trait]
+impl HttpTest for SnapshotWithChunkIndexesTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        const CHUNK_COUNT: u64 = 3;
+
+        let mut storage = pool.connection().await.unwrap();
+        store_l2_block(
+            &mut storage,
+            L2BlockNumber(1),
+            &[mock_execute_transaction(create_l2_transaction(1, 2).into())],
+        )
+        .await?;
+        seal_l1_batch(&mut storage, L1BatchNumber(1)).await?;
+        storage
+            .snapshots_dal()
+            .add_snapshot(
+                SnapshotVersion::Version2,
+                L1BatchNumber(1),
+                CHUNK_COUNT,
+                "file:///factory_deps",
+            )
+            .await?;
+        for chunk_id in 0..CHUNK_COUNT {
+            let path = format!("file:///storage_logs/chunk{chunk_id}");
+            let checksum = H256::repeat_byte(chunk_id as u8 + 1);
+            storage
+                .snapshots_dal()
+                .add_storage_logs_chunk_for_snapshot(L1BatchNumber(1), chunk_id, &path, checksum)
+                .await?;
+        }
+
+        let snapshot_header = client
+            .get_snapshot_by_l1_batch_number(L1BatchNumber(1))
+            .await?
+            .context("no snapshot for L1 batch #1")?;
+        assert_eq!(snapshot_header.version, 2);
+        assert_eq!(
+            snapshot_header.storage_logs_chunks.len(),
+            CHUNK_COUNT as usize
+        );
+        for chunk in &snapshot_header.storage_logs_chunks {
+            let expected_index = SnapshotStorageLogsChunkIndex::new(
+                chunk.chunk_id,
+                CHUNK_COUNT,
+                H256::repeat_byte(chunk.chunk_id as u8 + 1),
+            );
+            assert_eq!(chunk.index, Some(expected_index));
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn snapshot_with_chunk_indexes() {
+    test_http_server(SnapshotWithChunkIndexesTest).await;
+}
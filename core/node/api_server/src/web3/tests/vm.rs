@@ -12,12 +12,14 @@ use api::state_override::{OverrideAccount, StateOverride};
 use test_casing::test_casing;
 use zksync_contracts::{BaseSystemContracts, BaseSystemContractsHashes};
 use zksync_multivm::interface::{
-    ExecutionResult, OneshotEnv, VmExecutionLogs, VmExecutionResultAndLogs, VmRevertReason,
+    ExecutionResult, OneshotEnv, Refunds, TxExecutionMode, VmExecutionLogs,
+    VmExecutionResultAndLogs, VmRevertReason,
 };
 use zksync_types::{
     api::ApiStorageLog, fee_model::BatchFeeInput, get_intrinsic_constants,
     transaction_request::CallRequest, u256_to_h256, vm::FastVmMode, K256PrivateKey, L2ChainId,
-    PackedEthSignature, StorageLogKind, StorageLogWithPreviousValue, Transaction, U256,
+    PackedEthSignature, StorageLogKind, StorageLogWithPreviousValue, Transaction,
+    PRIORITY_OPERATION_L2_TX_TYPE, U256,
 };
 use zksync_vm_executor::oneshot::{
     BaseSystemContractsProvider, ContractsKind, MockOneshotExecutor, OneshotEnvParameters,
@@ -93,6 +95,8 @@ fn executor_options_with_evm_emulator() -> SandboxExecutorOptions {
     SandboxExecutorOptions {
         fast_vm_mode: FastVmMode::Old,
         vm_dump_store: None,
+        vm_warm_pool_size: 0,
+        vm_resource_limits: None,
         estimate_gas: OneshotEnvParameters::new(
             base_contracts.clone(),
             L2ChainId::default(),
@@ -717,6 +721,98 @@ async fn send_raw_transaction_with_detailed_output() {
     test_http_server(SendTransactionWithDetailedOutputTest).await;
 }
 
+#[derive(Debug)]
+struct SimulateL1ToL2TransactionTest;
+
+impl SimulateL1ToL2TransactionTest {
+    const GAS_REFUNDED: u64 = 10_000;
+}
+
+#[async_trait]
+impl HttpTest for SimulateL1ToL2TransactionTest {
+    fn transaction_executor(&self) -> MockOneshotExecutor {
+        let mut tx_executor = MockOneshotExecutor::default();
+        tx_executor.set_full_tx_responses(|tx, env| {
+            assert!(tx.is_l1(), "{tx:?}");
+            assert_eq!(env.system.execution_mode, TxExecutionMode::VerifyExecute);
+            assert_eq!(env.l1_batch.first_l2_block.number, 1);
+
+            let result = if tx.execute.calldata().is_empty() {
+                ExecutionResult::Revert {
+                    output: VmRevertReason::VmError,
+                }
+            } else {
+                ExecutionResult::Success { output: vec![] }
+            };
+            VmExecutionResultAndLogs {
+                logs: VmExecutionLogs {
+                    events: vec![VmEvent {
+                        location: (L1BatchNumber(1), 0),
+                        address: Address::repeat_byte(1),
+                        indexed_topics: vec![H256::repeat_byte(2)],
+                        value: vec![],
+                    }],
+                    ..VmExecutionLogs::default()
+                },
+                refunds: Refunds {
+                    gas_refunded: Self::GAS_REFUNDED,
+                    operator_suggested_refund: 0,
+                },
+                ..VmExecutionResultAndLogs::mock(result)
+            }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let l1_sender = Address::repeat_byte(0x11);
+        let mut call_request = CallRequest::from(create_l2_transaction(10, 100));
+        call_request.from = Some(l1_sender);
+        call_request.data = Some(b"test".to_vec().into());
+        let gas_limit = call_request.gas.unwrap();
+
+        let receipt = client
+            .simulate_l1_to_l2_transaction(call_request.clone(), None)
+            .await?;
+        assert_ne!(receipt.transaction_hash, H256::zero());
+        assert_eq!(receipt.status, 1.into());
+        assert_eq!(receipt.from, l1_sender);
+        assert_eq!(receipt.to, call_request.to);
+        assert_eq!(receipt.block_number, 1.into());
+        assert_eq!(
+            receipt.gas_used,
+            Some(gas_limit - U256::from(Self::GAS_REFUNDED))
+        );
+        assert_eq!(
+            receipt.transaction_type,
+            Some(PRIORITY_OPERATION_L2_TX_TYPE.into())
+        );
+        assert_eq!(receipt.logs.len(), 1);
+        assert_eq!(receipt.logs[0].address, Address::repeat_byte(1));
+        assert_eq!(
+            receipt.logs[0].transaction_hash,
+            Some(receipt.transaction_hash)
+        );
+
+        // Reverted L1 -> L2 transactions are still included into a batch, so the simulation returns a failed receipt.
+        call_request.data = None;
+        let receipt = client
+            .simulate_l1_to_l2_transaction(call_request, None)
+            .await?;
+        assert_eq!(receipt.status, 0.into());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn simulating_l1_to_l2_transaction() {
+    test_http_server(SimulateL1ToL2TransactionTest).await;
+}
+
 #[derive(Debug, Default)]
 struct TraceCallTest {
     fee_input: ExpectedFeeInput,
@@ -762,13 +858,13 @@ impl HttpTest for TraceCallTest {
         self.fee_input.expect_default(Self::FEE_SCALE);
         let call_request = CallTest::call_request(b"pending");
         let call_result = client
-            .trace_call(call_request.clone(), None, None)
+            .trace_call(call_request.clone(), None, None, None)
             .await?
             .unwrap_default();
         Self::assert_debug_call(&call_request, &call_result);
         let pending_block_number = api::BlockId::Number(api::BlockNumber::Pending);
         let call_result = client
-            .trace_call(call_request.clone(), Some(pending_block_number), None)
+            .trace_call(call_request.clone(), Some(pending_block_number), None, None)
             .await?
             .unwrap_default();
         Self::assert_debug_call(&call_request, &call_result);
@@ -782,6 +878,7 @@ impl HttpTest for TraceCallTest {
                     call_request.clone(),
                     Some(api::BlockId::Number(number)),
                     None,
+                    None,
                 )
                 .await?
                 .unwrap_default();
@@ -794,6 +891,7 @@ impl HttpTest for TraceCallTest {
                 CallTest::call_request(b"100"),
                 Some(api::BlockId::Number(invalid_block_number)),
                 None,
+                None,
             )
             .await
             .unwrap_err();
@@ -815,13 +913,16 @@ impl HttpTest for TraceCallTest {
         // Fee input is not scaled further as per `ApiFeeInputProvider` implementation
         self.fee_input.expect_custom(batch_header.fee_input);
         let call_request = CallTest::call_request(b"block=2");
-        let call_result = client.trace_call(call_request.clone(), None, None).await?;
+        let call_result = client
+            .trace_call(call_request.clone(), None, None, None)
+            .await?;
         Self::assert_debug_call(&call_request, &call_result.unwrap_default());
         let call_result = client
             .trace_call(
                 call_request.clone(),
                 Some(api::BlockId::Number(api::BlockNumber::Pending)),
                 None,
+                None,
             )
             .await?;
         Self::assert_debug_call(&call_request, &call_result.unwrap_default());
@@ -834,6 +935,7 @@ impl HttpTest for TraceCallTest {
                 call_request.clone(),
                 Some(api::BlockId::Number(api::BlockNumber::Latest)),
                 None,
+                None,
             )
             .await?;
         Self::assert_debug_call(&call_request, &call_result.unwrap_default());
@@ -881,13 +983,13 @@ impl HttpTest for TraceCallTestAfterSnapshotRecovery {
         self.fee_input.expect_default(TraceCallTest::FEE_SCALE);
         let call_request = CallTest::call_request(b"pending");
         let call_result = client
-            .trace_call(call_request.clone(), None, None)
+            .trace_call(call_request.clone(), None, None, None)
             .await?
             .unwrap_default();
         TraceCallTest::assert_debug_call(&call_request, &call_result);
         let pending_block_number = api::BlockId::Number(api::BlockNumber::Pending);
         let call_result = client
-            .trace_call(call_request.clone(), Some(pending_block_number), None)
+            .trace_call(call_request.clone(), Some(pending_block_number), None, None)
             .await?
             .unwrap_default();
         TraceCallTest::assert_debug_call(&call_request, &call_result);
@@ -910,7 +1012,7 @@ impl HttpTest for TraceCallTestAfterSnapshotRecovery {
                 .expect_for_block(number, TraceCallTest::FEE_SCALE);
             let number = api::BlockId::Number(number);
             let call_result = client
-                .trace_call(call_request.clone(), Some(number), None)
+                .trace_call(call_request.clone(), Some(number), None, None)
                 .await?
                 .unwrap_default();
             TraceCallTest::assert_debug_call(&call_request, &call_result);
@@ -960,7 +1062,7 @@ impl HttpTest for TraceCallTestWithEvmEmulator {
         seal_l1_batch(&mut connection, L1BatchNumber(1)).await?;
 
         client
-            .trace_call(CallTest::call_request(&[]), None, None)
+            .trace_call(CallTest::call_request(&[]), None, None, None)
             .await?;
 
         let call_request_without_target = CallRequest {
@@ -968,7 +1070,7 @@ impl HttpTest for TraceCallTestWithEvmEmulator {
             ..CallTest::call_request(b"no_target")
         };
         client
-            .trace_call(call_request_without_target, None, None)
+            .trace_call(call_request_without_target, None, None, None)
             .await?;
         Ok(())
     }
@@ -995,9 +1097,9 @@ impl EstimateMethod {
 
     async fn query(self, client: &DynClient<L2>, req: CallRequest) -> Result<U256, ClientError> {
         match self {
-            Self::EthEstimateGas => client.estimate_gas(req, None, None).await,
+            Self::EthEstimateGas => client.estimate_gas(req, None, None, None).await,
             Self::ZksEstimateFee => client
-                .estimate_fee(req, None)
+                .estimate_fee(req, None, None)
                 .await
                 .map(|fee| fee.gas_limit),
             Self::ZksEstimateGasL1ToL2 => client.estimate_gas_l1_to_l2(req, None).await,
@@ -1161,8 +1263,20 @@ impl HttpTest for EstimateGasWithStateOverrideTest {
         let state_override = StateOverride::new(state_override);
 
         client
-            .estimate_gas(call_request.clone(), None, Some(state_override))
+            .estimate_gas(
+                call_request.clone(),
+                None,
+                Some(state_override.clone()),
+                None,
+            )
             .await?;
+        // The fee input override is a trailing optional param, so it can be omitted by callers.
+        ClientT::request::<U256, _>(
+            &client,
+            "eth_estimateGas",
+            rpc_params![call_request.clone(), "latest", state_override],
+        )
+        .await?;
 
         // Transaction that should fail without balance override
         let l2_transaction = create_l2_transaction(10, 100);
@@ -1171,7 +1285,7 @@ impl HttpTest for EstimateGasWithStateOverrideTest {
         call_request.value = Some(1_000_000.into());
 
         let error = client
-            .estimate_gas(call_request.clone(), None, None)
+            .estimate_gas(call_request.clone(), None, None, None)
             .await
             .unwrap_err();
 
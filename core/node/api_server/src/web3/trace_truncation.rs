@@ -0,0 +1,357 @@
+//! Progressive truncation of call traces exceeding the size limit.
+//!
+//! Instead of failing the entire request, traces are truncated in stages: first, return data of all calls is dropped,
+//! then call data, and finally deeply nested calls (the depth limit is halved on each step). Truncated calls are marked
+//! with the dropped fields, so that clients can distinguish truncated data from empty one.
+
+use std::{io, iter};
+
+use serde::Serialize;
+use zksync_types::{
+    api::{CallTracerBlockResult, CallTracerResult, DebugCall, TruncatedTraceField},
+    debug_flat_call::DebugCallFlat,
+    web3,
+};
+
+/// Stage of trace truncation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TruncationStage {
+    /// Return data of all calls is dropped.
+    Outputs,
+    /// Call data of all calls is dropped.
+    Inputs,
+    /// Calls nested deeper than the specified depth are dropped. The top-level call has zero depth.
+    MaxDepth(usize),
+}
+
+/// Call trace that can be truncated.
+pub(crate) trait TruncatableTrace: Serialize {
+    /// Returns the maximum depth of calls in the trace.
+    fn max_depth(&self) -> usize;
+
+    /// Applies the specified truncation stage to the trace.
+    fn truncate(&mut self, stage: TruncationStage);
+}
+
+fn mark_truncated(truncated: &mut Vec<TruncatedTraceField>, field: TruncatedTraceField) {
+    if !truncated.contains(&field) {
+        truncated.push(field);
+    }
+}
+
+/// Clears the bytes, returning whether they were non-empty.
+fn take_bytes(bytes: &mut web3::Bytes) -> bool {
+    !std::mem::take(&mut bytes.0).is_empty()
+}
+
+fn call_max_depth(call: &DebugCall) -> usize {
+    call.calls
+        .iter()
+        .map(|call| call_max_depth(call) + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+fn truncate_call(call: &mut DebugCall, stage: TruncationStage, depth: usize) {
+    match stage {
+        TruncationStage::Outputs => {
+            if take_bytes(&mut call.output) {
+                mark_truncated(&mut call.truncated, TruncatedTraceField::Output);
+            }
+        }
+        TruncationStage::Inputs => {
+            if take_bytes(&mut call.input) {
+                mark_truncated(&mut call.truncated, TruncatedTraceField::Input);
+            }
+        }
+        TruncationStage::MaxDepth(max_depth) => {
+            if depth >= max_depth {
+                if !call.calls.is_empty() {
+                    call.calls = vec![];
+                    mark_truncated(&mut call.truncated, TruncatedTraceField::Calls);
+                }
+                return;
+            }
+        }
+    }
+
+    for nested_call in &mut call.calls {
+        truncate_call(nested_call, stage, depth + 1);
+    }
+}
+
+/// The first element of the trace address is the transaction index in the block.
+fn flat_call_depth(call: &DebugCallFlat) -> usize {
+    call.trace_address.len().saturating_sub(1)
+}
+
+fn flat_calls_max_depth(calls: &[DebugCallFlat]) -> usize {
+    calls.iter().map(flat_call_depth).max().unwrap_or(0)
+}
+
+fn truncate_flat_calls(calls: &mut Vec<DebugCallFlat>, stage: TruncationStage) {
+    match stage {
+        TruncationStage::Outputs => {
+            for call in calls {
+                let Some(result) = &mut call.result else {
+                    continue;
+                };
+                if take_bytes(&mut result.output) {
+                    mark_truncated(&mut call.truncated, TruncatedTraceField::Output);
+                }
+            }
+        }
+        TruncationStage::Inputs => {
+            for call in calls {
+                if take_bytes(&mut call.action.input) {
+                    mark_truncated(&mut call.truncated, TruncatedTraceField::Input);
+                }
+            }
+        }
+        TruncationStage::MaxDepth(max_depth) => {
+            calls.retain(|call| flat_call_depth(call) <= max_depth);
+            for call in calls {
+                if flat_call_depth(call) == max_depth && call.subtraces > 0 {
+                    mark_truncated(&mut call.truncated, TruncatedTraceField::Calls);
+                }
+            }
+        }
+    }
+}
+
+impl TruncatableTrace for CallTracerResult {
+    fn max_depth(&self) -> usize {
+        match self {
+            Self::CallTrace(call) => call_max_depth(call),
+            Self::FlatCallTrace(calls) => flat_calls_max_depth(calls),
+        }
+    }
+
+    fn truncate(&mut self, stage: TruncationStage) {
+        match self {
+            Self::CallTrace(call) => truncate_call(call, stage, 0),
+            Self::FlatCallTrace(calls) => truncate_flat_calls(calls, stage),
+        }
+    }
+}
+
+impl TruncatableTrace for CallTracerBlockResult {
+    fn max_depth(&self) -> usize {
+        let max_depth = match self {
+            Self::CallTrace(traces) => traces
+                .iter()
+                .map(|trace| call_max_depth(&trace.result))
+                .max(),
+            Self::FlatCallTrace(traces) => traces
+                .iter()
+                .map(|trace| flat_calls_max_depth(&trace.result))
+                .max(),
+        };
+        max_depth.unwrap_or(0)
+    }
+
+    fn truncate(&mut self, stage: TruncationStage) {
+        match self {
+            Self::CallTrace(traces) => {
+                for trace in traces {
+                    truncate_call(&mut trace.result, stage, 0);
+                }
+            }
+            Self::FlatCallTrace(traces) => {
+                for trace in traces {
+                    truncate_flat_calls(&mut trace.result, stage);
+                }
+            }
+        }
+    }
+}
+
+/// Counts bytes written to it.
+#[derive(Debug, Default)]
+struct ByteCounter(usize);
+
+impl io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn serialized_size(value: &impl Serialize) -> usize {
+    let mut counter = ByteCounter::default();
+    serde_json::to_writer(&mut counter, value).expect("failed serializing trace");
+    counter.0
+}
+
+/// Progressively truncates the trace until its JSON serialization fits into `max_size` bytes. Returns the last applied
+/// truncation stage, or `None` if the trace wasn't truncated.
+///
+/// If the trace doesn't fit even after all stages are applied (i.e., only top-level calls without call / return data remain),
+/// the truncated trace is returned as is.
+pub(crate) fn truncate_trace(
+    trace: &mut impl TruncatableTrace,
+    max_size: usize,
+) -> Option<TruncationStage> {
+    if serialized_size(trace) <= max_size {
+        return None;
+    }
+
+    let max_depth = trace.max_depth();
+    let depth_stages = iter::successors((max_depth > 0).then_some(max_depth / 2), |&depth| {
+        (depth > 0).then_some(depth / 2)
+    });
+    let stages = [TruncationStage::Outputs, TruncationStage::Inputs]
+        .into_iter()
+        .chain(depth_stages.map(TruncationStage::MaxDepth));
+
+    let mut last_stage = None;
+    for stage in stages {
+        trace.truncate(stage);
+        last_stage = Some(stage);
+        if serialized_size(trace) <= max_size {
+            break;
+        }
+    }
+    last_stage
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        api::DebugCallType,
+        debug_flat_call::{Action, CallResult},
+        Address, H256, U256,
+    };
+
+    use super::*;
+
+    fn call_with_depth(depth: usize) -> DebugCall {
+        DebugCall {
+            input: web3::Bytes(vec![1; 100]),
+            output: web3::Bytes(vec![2; 100]),
+            calls: if depth == 0 {
+                vec![]
+            } else {
+                vec![call_with_depth(depth - 1), call_with_depth(depth - 1)]
+            },
+            ..DebugCall::default()
+        }
+    }
+
+    fn flat_call(trace_address: Vec<usize>, subtraces: usize) -> DebugCallFlat {
+        DebugCallFlat {
+            action: Action {
+                call_type: DebugCallType::Call,
+                from: Address::zero(),
+                to: Address::zero(),
+                gas: U256::zero(),
+                value: U256::zero(),
+                input: web3::Bytes(vec![1; 100]),
+            },
+            result: Some(CallResult {
+                output: web3::Bytes(vec![2; 100]),
+                gas_used: U256::zero(),
+            }),
+            subtraces,
+            error: None,
+            trace_address,
+            transaction_position: 0,
+            transaction_hash: H256::zero(),
+            block_number: 0,
+            block_hash: H256::zero(),
+            r#type: DebugCallType::Call,
+            truncated: vec![],
+        }
+    }
+
+    #[test]
+    fn small_trace_is_not_truncated() {
+        let mut trace = CallTracerResult::CallTrace(call_with_depth(3));
+        let size = serialized_size(&trace);
+        assert_eq!(truncate_trace(&mut trace, size), None);
+        assert_eq!(trace.unwrap_default(), call_with_depth(3));
+    }
+
+    #[test]
+    fn truncating_call_trace() {
+        let original_trace = CallTracerResult::CallTrace(call_with_depth(4));
+        assert_eq!(original_trace.max_depth(), 4);
+        let size = serialized_size(&original_trace);
+
+        let mut trace = original_trace.clone();
+        let stage = truncate_trace(&mut trace, size - 1);
+        assert_eq!(stage, Some(TruncationStage::Outputs));
+        let call = trace.unwrap_default();
+        assert!(call.output.0.is_empty());
+        assert_eq!(call.input.0.len(), 100);
+        assert_eq!(call.truncated, [TruncatedTraceField::Output]);
+        assert_eq!(
+            call.calls[1].calls[0].truncated,
+            [TruncatedTraceField::Output]
+        );
+
+        // Only top-level calls without data must fit into the limit.
+        let mut trace = original_trace;
+        let stage = truncate_trace(&mut trace, 500);
+        assert_eq!(stage, Some(TruncationStage::MaxDepth(0)));
+        let call = trace.unwrap_default();
+        assert!(call.input.0.is_empty() && call.output.0.is_empty());
+        assert!(call.calls.is_empty());
+        assert_eq!(
+            call.truncated,
+            [
+                TruncatedTraceField::Output,
+                TruncatedTraceField::Input,
+                TruncatedTraceField::Calls
+            ]
+        );
+        let serialized = serde_json::to_value(&call).unwrap();
+        assert_eq!(
+            serialized["truncated"],
+            serde_json::json!(["output", "input", "calls"])
+        );
+    }
+
+    #[test]
+    fn truncating_call_trace_by_depth() {
+        let mut trace = CallTracerResult::CallTrace(call_with_depth(4));
+        trace.truncate(TruncationStage::MaxDepth(2));
+        assert_eq!(trace.max_depth(), 2);
+        let call = trace.unwrap_default();
+        assert!(call.truncated.is_empty());
+        assert!(call.calls[0].truncated.is_empty());
+        let deepest_call = &call.calls[0].calls[1];
+        assert!(deepest_call.calls.is_empty());
+        assert_eq!(deepest_call.truncated, [TruncatedTraceField::Calls]);
+        assert_eq!(deepest_call.input.0.len(), 100);
+    }
+
+    #[test]
+    fn truncating_flat_call_trace() {
+        let calls = vec![
+            flat_call(vec![0], 1),
+            flat_call(vec![0, 0], 2),
+            flat_call(vec![0, 0, 0], 0),
+            flat_call(vec![0, 0, 1], 0),
+        ];
+        let mut trace = CallTracerResult::FlatCallTrace(calls);
+        assert_eq!(trace.max_depth(), 2);
+
+        trace.truncate(TruncationStage::Outputs);
+        trace.truncate(TruncationStage::MaxDepth(1));
+        let calls = trace.unwrap_flat();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].truncated, [TruncatedTraceField::Output]);
+        assert_eq!(
+            calls[1].truncated,
+            [TruncatedTraceField::Output, TruncatedTraceField::Calls]
+        );
+        assert_eq!(calls[1].subtraces, 2);
+        assert!(calls[1].result.as_ref().unwrap().output.0.is_empty());
+        assert_eq!(calls[1].action.input.0.len(), 100);
+    }
+}
@@ -13,6 +13,7 @@ use zksync_node_fee_model::l1_gas_price::TxParamsProvider;
 use zksync_types::{
     base_token_ratio::BaseTokenAPIRatio,
     ethabi::{Contract, Token},
+    fee_model::BaseTokenConversionRatio,
     web3::{contract::Tokenize, BlockNumber},
     Address, U256,
 };
@@ -164,12 +165,19 @@ impl BaseTokenL1Behaviour {
             .function("setTokenMultiplier")
             .context("`setTokenMultiplier` function must be present in the ChainAdmin contract")?;
 
+        // The L1 multiplier converts WEI to the smallest units of the base token, so it must account for its decimals.
+        let (numerator, denominator) = BaseTokenConversionRatio {
+            numerator: api_ratio.numerator,
+            denominator: api_ratio.denominator,
+            base_token_decimals: l1_params.config.base_token_decimals,
+        }
+        .to_smallest_units();
         let calldata = fn_set_token_multiplier
             .encode_input(
                 &(
                     Token::Address(l1_params.diamond_proxy_contract_address),
-                    Token::Uint(api_ratio.numerator.get().into()),
-                    Token::Uint(api_ratio.denominator.get().into()),
+                    Token::Uint(numerator),
+                    Token::Uint(denominator),
                 )
                     .into_tokens(),
             )
@@ -261,7 +269,23 @@ impl BaseTokenL1Behaviour {
             )
             .call((*l1_params.eth_client).as_ref())
             .await?;
-        Ok(BigDecimal::from(numerator.as_u128()).div(BigDecimal::from(denominator.as_u128())))
+        let numerator = u256_to_u128(numerator).context("L1 multiplier numerator")?;
+        let denominator = u256_to_u128(denominator).context("L1 multiplier denominator")?;
+        anyhow::ensure!(denominator != 0, "L1 multiplier denominator is zero");
+        let l1_ratio = BigDecimal::from(numerator).div(BigDecimal::from(denominator));
+
+        // Convert the L1 multiplier (which is denominated in the smallest units) to whole units used by API ratios.
+        let (unit_numerator, unit_denominator) = BaseTokenConversionRatio {
+            base_token_decimals: l1_params.config.base_token_decimals,
+            ..BaseTokenConversionRatio::default()
+        }
+        .to_smallest_units();
+        let unit_numerator = u256_to_u128(unit_numerator).context("base token unit numerator")?;
+        let unit_denominator =
+            u256_to_u128(unit_denominator).context("base token unit denominator")?;
+        Ok(l1_ratio
+            .mul(BigDecimal::from(unit_denominator))
+            .div(BigDecimal::from(unit_numerator)))
     }
 
     fn get_eth_fees(
@@ -308,13 +332,19 @@ impl BaseTokenL1Behaviour {
     }
 }
 
+fn u256_to_u128(value: U256) -> anyhow::Result<u128> {
+    anyhow::ensure!(value.bits() <= 128, "value {value} does not fit into u128");
+    Ok(value.low_u128())
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::Div;
 
     use bigdecimal::{BigDecimal, Zero};
+    use zksync_types::U256;
 
-    use crate::base_token_l1_behaviour::BaseTokenL1Behaviour;
+    use crate::base_token_l1_behaviour::{u256_to_u128, BaseTokenL1Behaviour};
 
     #[test]
     fn test_compute_deviation() {
@@ -335,4 +365,11 @@ mod tests {
         let deviation = BaseTokenL1Behaviour::compute_deviation(prev_ratio, current_ratio);
         assert_eq!(deviation, BigDecimal::from(100));
     }
+
+    #[test]
+    fn converting_u256_to_u128() {
+        assert_eq!(u256_to_u128(U256::from(42)).unwrap(), 42);
+        assert_eq!(u256_to_u128(U256::from(u128::MAX)).unwrap(), u128::MAX);
+        u256_to_u128(U256::from(u128::MAX) + 1).unwrap_err();
+    }
 }
@@ -24,6 +24,12 @@ impl DBBaseTokenRatioProvider {
         pool: ConnectionPool<Core>,
         config: BaseTokenAdjusterConfig,
     ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            config.base_token_decimals <= BaseTokenAdjusterConfig::MAX_BASE_TOKEN_DECIMALS,
+            "base token decimals ({}) must not exceed {}",
+            config.base_token_decimals,
+            BaseTokenAdjusterConfig::MAX_BASE_TOKEN_DECIMALS
+        );
         let fetcher = Self {
             pool,
             latest_ratio: Arc::default(),
@@ -75,6 +81,7 @@ impl DBBaseTokenRatioProvider {
             Ok(Some(latest_storage_price)) => BaseTokenConversionRatio {
                 numerator: latest_storage_price.numerator,
                 denominator: latest_storage_price.denominator,
+                base_token_decimals: self.config.base_token_decimals,
             },
             Ok(None) => {
                 // TODO(PE-136): Insert initial ratio from genesis.
@@ -82,7 +89,10 @@ impl DBBaseTokenRatioProvider {
                 // to have no ratios in the DB right after genesis. Having initial ratios in the DB
                 // from the genesis stage will eliminate this possibility.
                 tracing::warn!("No latest price found in the database. Using default ratio.");
-                BaseTokenConversionRatio::default()
+                BaseTokenConversionRatio {
+                    base_token_decimals: self.config.base_token_decimals,
+                    ..BaseTokenConversionRatio::default()
+                }
             }
             Err(err) => anyhow::bail!("Failed to get latest base token ratio: {:?}", err),
         };
@@ -117,6 +127,7 @@ impl Default for NoOpRatioProvider {
             latest_ratio: BaseTokenConversionRatio {
                 numerator: NonZeroU64::new(1).unwrap(),
                 denominator: NonZeroU64::new(1).unwrap(),
+                base_token_decimals: BaseTokenConversionRatio::ETH_DECIMALS,
             },
         }
     }
@@ -0,0 +1,243 @@
+//! Exporter streaming artifacts of sealed L1 batches (transaction results, storage diffs, events and pubdata stats)
+//! to external systems, e.g. for analytics.
+
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_types::{
+    batch_export::{ExportedPubdataStats, ExportedStorageDiff, L1BatchArtifacts},
+    L1BatchNumber,
+};
+
+use crate::metrics::METRICS;
+pub use crate::{
+    parquet_sink::ParquetSink,
+    sink::{BatchArtifactsSink, FileSink},
+};
+
+mod metrics;
+mod parquet_sink;
+mod sink;
+#[cfg(test)]
+mod tests;
+
+/// Configuration of [`BatchExporter`].
+#[derive(Debug, Clone)]
+pub struct BatchExporterConfig {
+    /// Interval between iterations if the previous iteration didn't export anything.
+    pub poll_interval: Duration,
+    /// Maximum number of L1 batches exported during a single iteration.
+    pub max_batches_per_iteration: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct BatchExporterHealth {
+    sink: &'static str,
+    last_exported_l1_batch: Option<L1BatchNumber>,
+}
+
+/// Exports artifacts of sealed L1 batches to a [`BatchArtifactsSink`], one L1 batch at a time in order.
+///
+/// Export progress is persisted in Postgres after each L1 batch is exported, so the exporter resumes
+/// from where it stopped after a restart. Since the cursor is advanced only after the sink has accepted
+/// an L1 batch, delivery is at-least-once.
+#[derive(Debug)]
+pub struct BatchExporter {
+    config: BatchExporterConfig,
+    pool: ConnectionPool<Core>,
+    sink: Arc<dyn BatchArtifactsSink>,
+    health_updater: HealthUpdater,
+}
+
+impl BatchExporter {
+    pub fn new(
+        config: BatchExporterConfig,
+        pool: ConnectionPool<Core>,
+        sink: Arc<dyn BatchArtifactsSink>,
+    ) -> Self {
+        Self {
+            config,
+            pool,
+            sink,
+            health_updater: ReactiveHealthCheck::new("batch_exporter").1,
+        }
+    }
+
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    async fn next_l1_batch_to_export(
+        &self,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<Option<L1BatchNumber>> {
+        let Some(earliest_l1_batch) = storage.blocks_dal().get_earliest_l1_batch_number().await?
+        else {
+            return Ok(None);
+        };
+        let cursor = storage
+            .batch_export_dal()
+            .get_cursor(self.sink.id())
+            .await?;
+        // L1 batches may have been pruned since the last export; they cannot be exported anymore.
+        Ok(Some(cursor.map_or(earliest_l1_batch, |number| {
+            (number + 1).max(earliest_l1_batch)
+        })))
+    }
+
+    /// Loads artifacts of a sealed L1 batch from Postgres.
+    pub async fn load_artifacts(
+        storage: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<L1BatchArtifacts>> {
+        let Some(header) = storage
+            .blocks_dal()
+            .get_l1_batch_header(l1_batch_number)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let l2_block_range = storage
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(l1_batch_number)
+            .await?
+            .with_context(|| format!("L1 batch #{l1_batch_number} has no L2 blocks"))?;
+
+        let transactions = storage
+            .batch_export_dal()
+            .get_transactions_for_export(l1_batch_number)
+            .await?;
+        let events = storage
+            .batch_export_dal()
+            .get_events_for_export(l2_block_range.0..=l2_block_range.1)
+            .await?;
+        let touched_slots = storage
+            .storage_logs_dal()
+            .get_touched_slots_for_executed_l1_batch(l1_batch_number)
+            .await?;
+        let mut storage_diffs: Vec<_> = touched_slots
+            .into_iter()
+            .map(|(key, value)| ExportedStorageDiff {
+                address: *key.address(),
+                key: *key.key(),
+                value,
+            })
+            .collect();
+        storage_diffs.sort_unstable_by_key(|diff| (diff.address, diff.key));
+
+        let pubdata = ExportedPubdataStats {
+            pubdata_input_size: header.pubdata_input.as_ref().map(Vec::len),
+            storage_diff_count: storage_diffs.len(),
+            user_l2_to_l1_log_count: header.l2_to_l1_logs.len(),
+            l2_to_l1_message_count: header.l2_to_l1_messages.len(),
+            l2_to_l1_messages_size: header.l2_to_l1_messages.iter().map(Vec::len).sum(),
+        };
+        Ok(Some(L1BatchArtifacts {
+            l1_batch_number,
+            timestamp: header.timestamp,
+            transactions,
+            storage_diffs,
+            events,
+            pubdata,
+        }))
+    }
+
+    /// Returns the number of exported L1 batches.
+    async fn run_single_iteration(
+        &self,
+        stop_receiver: &watch::Receiver<bool>,
+        health: &mut BatchExporterHealth,
+    ) -> anyhow::Result<u32> {
+        let sink_id = self.sink.id();
+        let mut storage = self.pool.connection_tagged("batch_exporter").await?;
+        let Some(mut next_l1_batch) = self.next_l1_batch_to_export(&mut storage).await? else {
+            return Ok(0);
+        };
+        let Some(sealed_l1_batch) = storage.blocks_dal().get_sealed_l1_batch_number().await? else {
+            return Ok(0);
+        };
+
+        let mut exported_l1_batches = 0;
+        while exported_l1_batches < self.config.max_batches_per_iteration
+            && next_l1_batch <= sealed_l1_batch
+        {
+            if *stop_receiver.borrow() {
+                break;
+            }
+
+            let latency = METRICS.load_latency.start();
+            let artifacts = Self::load_artifacts(&mut storage, next_l1_batch)
+                .await?
+                .with_context(|| format!("sealed L1 batch #{next_l1_batch} is missing"))?;
+            latency.observe();
+
+            let latency = METRICS.export_latency[&sink_id].start();
+            self.sink.export(&artifacts).await.with_context(|| {
+                format!("failed exporting L1 batch #{next_l1_batch} to sink `{sink_id}`")
+            })?;
+            latency.observe();
+            // The cursor is advanced only after the sink has accepted the L1 batch, which guarantees
+            // at-least-once delivery.
+            storage
+                .batch_export_dal()
+                .set_cursor(sink_id, next_l1_batch)
+                .await?;
+
+            tracing::debug!("Exported L1 batch #{next_l1_batch} to sink `{sink_id}`");
+            METRICS.observe_export(sink_id, &artifacts);
+            health.last_exported_l1_batch = Some(next_l1_batch);
+            exported_l1_batches += 1;
+            next_l1_batch += 1;
+        }
+        Ok(exported_l1_batches)
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting batch exporter to sink `{}` with configuration {:?}",
+            self.sink.id(),
+            self.config
+        );
+        let mut health = BatchExporterHealth {
+            sink: self.sink.id(),
+            last_exported_l1_batch: None,
+        };
+
+        while !*stop_receiver.borrow_and_update() {
+            let should_sleep = match self.run_single_iteration(&stop_receiver, &mut health).await {
+                Ok(exported_l1_batches) => {
+                    self.health_updater
+                        .update(Health::from(HealthStatus::Ready).with_details(&health));
+                    exported_l1_batches == 0
+                }
+                Err(err) => {
+                    // Errors are not fatal; the failed L1 batch will be exported on the next iteration.
+                    tracing::warn!(
+                        "Batch exporter error, retrying in {:?}, error was: {err:?}",
+                        self.config.poll_interval
+                    );
+                    let health =
+                        Health::from(HealthStatus::Affected).with_details(serde_json::json!({
+                            "error": err.to_string(),
+                        }));
+                    self.health_updater.update(health);
+                    true
+                }
+            };
+
+            if should_sleep
+                && tokio::time::timeout(self.config.poll_interval, stop_receiver.changed())
+                    .await
+                    .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, shutting down batch exporter");
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use vise::{Buckets, Gauge, Histogram, LabeledFamily, Metrics, Unit};
+use zksync_types::batch_export::L1BatchArtifacts;
+
+const ITEM_COUNT_BUCKETS: Buckets = Buckets::values(&[
+    1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0, 1_000.0, 2_000.0, 5_000.0, 10_000.0,
+    20_000.0, 50_000.0, 100_000.0,
+]);
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "batch_exporter")]
+pub(crate) struct BatchExporterMetrics {
+    /// Number of the last L1 batch exported to a sink.
+    #[metrics(labels = ["sink"])]
+    last_exported_l1_batch: LabeledFamily<&'static str, Gauge<u64>>,
+    /// Latency of loading artifacts of a single L1 batch from Postgres.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub load_latency: Histogram<Duration>,
+    /// Latency of exporting artifacts of a single L1 batch to a sink.
+    #[metrics(labels = ["sink"], buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub export_latency: LabeledFamily<&'static str, Histogram<Duration>>,
+    /// Number of transactions in exported L1 batches.
+    #[metrics(buckets = ITEM_COUNT_BUCKETS)]
+    exported_transactions: Histogram<usize>,
+    /// Number of storage diffs in exported L1 batches.
+    #[metrics(buckets = ITEM_COUNT_BUCKETS)]
+    exported_storage_diffs: Histogram<usize>,
+    /// Number of events in exported L1 batches.
+    #[metrics(buckets = ITEM_COUNT_BUCKETS)]
+    exported_events: Histogram<usize>,
+}
+
+impl BatchExporterMetrics {
+    pub fn observe_export(&self, sink: &'static str, artifacts: &L1BatchArtifacts) {
+        self.last_exported_l1_batch[&sink].set(artifacts.l1_batch_number.0.into());
+        self.exported_transactions
+            .observe(artifacts.transactions.len());
+        self.exported_storage_diffs
+            .observe(artifacts.storage_diffs.len());
+        self.exported_events.observe(artifacts.events.len());
+    }
+}
+
+#[vise::register]
+pub(crate) static METRICS: vise::Global<BatchExporterMetrics> = vise::Global::new();
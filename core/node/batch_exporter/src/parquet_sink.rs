@@ -0,0 +1,320 @@
+//! Sink writing L1 batch artifacts as Parquet files.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Context as _;
+use arrow_array::{
+    builder::{FixedSizeBinaryBuilder, ListBuilder},
+    ArrayRef, BinaryArray, BooleanArray, FixedSizeBinaryArray, RecordBatch, StringArray,
+    UInt32Array, UInt64Array,
+};
+use async_trait::async_trait;
+use parquet::{
+    arrow::ArrowWriter,
+    basic::{Compression, ZstdLevel},
+    file::properties::WriterProperties,
+};
+use zksync_types::{batch_export::L1BatchArtifacts, L1BatchNumber};
+
+use crate::sink::BatchArtifactsSink;
+
+/// Sink writing artifacts of each L1 batch as Parquet files in a local directory.
+///
+/// Each kind of artifacts is written to a separate subdirectory (`transactions`, `storage_diffs`, `events`
+/// and `batches` for timestamps and pubdata stats), with one file per L1 batch. Thus, each subdirectory can be
+/// queried as a table by analytics tools; all rows contain the L1 batch number to join tables on.
+/// Similarly to [`FileSink`](crate::FileSink), files are written atomically, and repeated exports overwrite
+/// the previously written files. The file in the `batches` subdirectory is written last, so its presence
+/// signals that all artifacts of the L1 batch are written.
+#[derive(Debug)]
+pub struct ParquetSink {
+    output_dir: PathBuf,
+}
+
+impl ParquetSink {
+    pub const TABLES: [&'static str; 4] = ["transactions", "storage_diffs", "events", "batches"];
+
+    pub fn new(output_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            output_dir: output_dir.into(),
+        }
+    }
+
+    /// Returns the path to the file with artifacts of the specified kind (one of [`Self::TABLES`])
+    /// for the specified L1 batch.
+    pub fn file_path(&self, table: &str, l1_batch_number: L1BatchNumber) -> PathBuf {
+        // Padding ensures that lexicographical ordering of files corresponds to the ordering of L1 batches.
+        self.output_dir
+            .join(table)
+            .join(format!("l1_batch_{:010}.parquet", l1_batch_number.0))
+    }
+
+    fn encode(batch: &RecordBatch) -> anyhow::Result<Vec<u8>> {
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .build();
+        let mut writer = ArrowWriter::try_new(Vec::new(), batch.schema(), Some(properties))?;
+        writer.write(batch)?;
+        Ok(writer.into_inner()?)
+    }
+
+    fn l1_batch_numbers(l1_batch_number: L1BatchNumber, len: usize) -> ArrayRef {
+        Arc::new(UInt32Array::from(vec![l1_batch_number.0; len]))
+    }
+
+    fn transactions(artifacts: &L1BatchArtifacts) -> anyhow::Result<RecordBatch> {
+        let txs = &artifacts.transactions;
+        let hashes = FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+            txs.iter().map(|tx| Some(tx.hash.as_bytes())),
+            32,
+        )?;
+        let initiators = FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+            txs.iter().map(|tx| Some(tx.initiator_address.as_bytes())),
+            20,
+        )?;
+        let recipients = FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+            txs.iter().map(|tx| tx.to.as_ref().map(|to| to.as_bytes())),
+            20,
+        )?;
+        // `U256` values don't fit into any Parquet integer type, so they are stored as decimal strings.
+        let u256_column = |values: Vec<String>| -> ArrayRef { Arc::new(StringArray::from(values)) };
+
+        let columns: [(&str, ArrayRef, bool); 12] = [
+            (
+                "l1_batch_number",
+                Self::l1_batch_numbers(artifacts.l1_batch_number, txs.len()),
+                false,
+            ),
+            ("hash", Arc::new(hashes), false),
+            (
+                "l2_block_number",
+                Arc::new(UInt32Array::from_iter_values(
+                    txs.iter().map(|tx| tx.l2_block_number.0),
+                )),
+                false,
+            ),
+            (
+                "index_in_l2_block",
+                Arc::new(UInt32Array::from_iter_values(
+                    txs.iter().map(|tx| tx.index_in_l2_block),
+                )),
+                false,
+            ),
+            (
+                "is_priority",
+                Arc::new(BooleanArray::from_iter(
+                    txs.iter().map(|tx| Some(tx.is_priority)),
+                )),
+                false,
+            ),
+            ("initiator_address", Arc::new(initiators), false),
+            ("to", Arc::new(recipients), true),
+            (
+                "nonce",
+                Arc::new(UInt64Array::from_iter(txs.iter().map(|tx| tx.nonce))),
+                true,
+            ),
+            (
+                "error",
+                Arc::new(StringArray::from_iter(
+                    txs.iter().map(|tx| tx.error.as_deref()),
+                )),
+                true,
+            ),
+            (
+                "gas_limit",
+                u256_column(txs.iter().map(|tx| tx.gas_limit.to_string()).collect()),
+                false,
+            ),
+            (
+                "gas_used",
+                u256_column(txs.iter().map(|tx| tx.gas_used.to_string()).collect()),
+                false,
+            ),
+            (
+                "effective_gas_price",
+                u256_column(
+                    txs.iter()
+                        .map(|tx| tx.effective_gas_price.to_string())
+                        .collect(),
+                ),
+                false,
+            ),
+        ];
+        Ok(RecordBatch::try_from_iter_with_nullable(columns)?)
+    }
+
+    fn storage_diffs(artifacts: &L1BatchArtifacts) -> anyhow::Result<RecordBatch> {
+        let diffs = &artifacts.storage_diffs;
+        let addresses = FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+            diffs.iter().map(|diff| Some(diff.address.as_bytes())),
+            20,
+        )?;
+        let keys = FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+            diffs.iter().map(|diff| Some(diff.key.as_bytes())),
+            32,
+        )?;
+        let values = FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+            diffs.iter().map(|diff| Some(diff.value.as_bytes())),
+            32,
+        )?;
+
+        let columns: [(&str, ArrayRef, bool); 4] = [
+            (
+                "l1_batch_number",
+                Self::l1_batch_numbers(artifacts.l1_batch_number, diffs.len()),
+                false,
+            ),
+            ("address", Arc::new(addresses), false),
+            ("key", Arc::new(keys), false),
+            ("value", Arc::new(values), false),
+        ];
+        Ok(RecordBatch::try_from_iter_with_nullable(columns)?)
+    }
+
+    fn events(artifacts: &L1BatchArtifacts) -> anyhow::Result<RecordBatch> {
+        let events = &artifacts.events;
+        let tx_hashes = FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+            events.iter().map(|event| Some(event.tx_hash.as_bytes())),
+            32,
+        )?;
+        let addresses = FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+            events.iter().map(|event| Some(event.address.as_bytes())),
+            20,
+        )?;
+        let mut topics = ListBuilder::new(FixedSizeBinaryBuilder::new(32));
+        for event in events {
+            for topic in &event.topics {
+                topics.values().append_value(topic.as_bytes())?;
+            }
+            topics.append(true);
+        }
+
+        let columns: [(&str, ArrayRef, bool); 7] = [
+            (
+                "l1_batch_number",
+                Self::l1_batch_numbers(artifacts.l1_batch_number, events.len()),
+                false,
+            ),
+            ("tx_hash", Arc::new(tx_hashes), false),
+            (
+                "l2_block_number",
+                Arc::new(UInt32Array::from_iter_values(
+                    events.iter().map(|event| event.l2_block_number.0),
+                )),
+                false,
+            ),
+            (
+                "index_in_l2_block",
+                Arc::new(UInt32Array::from_iter_values(
+                    events.iter().map(|event| event.index_in_l2_block),
+                )),
+                false,
+            ),
+            ("address", Arc::new(addresses), false),
+            ("topics", Arc::new(topics.finish()), false),
+            (
+                "data",
+                Arc::new(BinaryArray::from_iter_values(
+                    events.iter().map(|event| event.data.as_slice()),
+                )),
+                false,
+            ),
+        ];
+        Ok(RecordBatch::try_from_iter_with_nullable(columns)?)
+    }
+
+    fn batches(artifacts: &L1BatchArtifacts) -> anyhow::Result<RecordBatch> {
+        let pubdata = &artifacts.pubdata;
+        let count_column =
+            |value: usize| -> ArrayRef { Arc::new(UInt64Array::from(vec![value as u64])) };
+
+        let columns: [(&str, ArrayRef, bool); 7] = [
+            (
+                "l1_batch_number",
+                Self::l1_batch_numbers(artifacts.l1_batch_number, 1),
+                false,
+            ),
+            (
+                "timestamp",
+                Arc::new(UInt64Array::from(vec![artifacts.timestamp])),
+                false,
+            ),
+            (
+                "pubdata_input_size",
+                Arc::new(UInt64Array::from(vec![pubdata
+                    .pubdata_input_size
+                    .map(|size| size as u64)])),
+                true,
+            ),
+            (
+                "storage_diff_count",
+                count_column(pubdata.storage_diff_count),
+                false,
+            ),
+            (
+                "user_l2_to_l1_log_count",
+                count_column(pubdata.user_l2_to_l1_log_count),
+                false,
+            ),
+            (
+                "l2_to_l1_message_count",
+                count_column(pubdata.l2_to_l1_message_count),
+                false,
+            ),
+            (
+                "l2_to_l1_messages_size",
+                count_column(pubdata.l2_to_l1_messages_size),
+                false,
+            ),
+        ];
+        Ok(RecordBatch::try_from_iter_with_nullable(columns)?)
+    }
+
+    async fn write_file(path: &Path, contents: &[u8]) -> anyhow::Result<()> {
+        let dir = path.parent().context("file path has no parent")?;
+        tokio::fs::create_dir_all(dir)
+            .await
+            .with_context(|| format!("failed creating output dir {dir:?}"))?;
+        let tmp_path = path.with_extension("parquet.tmp");
+        tokio::fs::write(&tmp_path, contents)
+            .await
+            .with_context(|| format!("failed writing {tmp_path:?}"))?;
+        // Sync the file before renaming, so that the file is durable once the cursor is advanced.
+        tokio::fs::File::open(&tmp_path)
+            .await
+            .with_context(|| format!("failed opening {tmp_path:?}"))?
+            .sync_all()
+            .await
+            .with_context(|| format!("failed syncing {tmp_path:?}"))?;
+        tokio::fs::rename(&tmp_path, path)
+            .await
+            .with_context(|| format!("failed renaming {tmp_path:?} to {path:?}"))
+    }
+}
+
+#[async_trait]
+impl BatchArtifactsSink for ParquetSink {
+    fn id(&self) -> &'static str {
+        "parquet"
+    }
+
+    async fn export(&self, artifacts: &L1BatchArtifacts) -> anyhow::Result<()> {
+        let tables = [
+            Self::transactions(artifacts).context("transactions")?,
+            Self::storage_diffs(artifacts).context("storage_diffs")?,
+            Self::events(artifacts).context("events")?,
+            Self::batches(artifacts).context("batches")?,
+        ];
+        for (table, batch) in Self::TABLES.into_iter().zip(&tables) {
+            let contents =
+                Self::encode(batch).with_context(|| format!("failed encoding `{table}`"))?;
+            let path = self.file_path(table, artifacts.l1_batch_number);
+            Self::write_file(&path, &contents).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+//! Sinks receiving exported L1 batch artifacts.
+
+use std::{fmt, path::PathBuf};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use zksync_types::{batch_export::L1BatchArtifacts, L1BatchNumber};
+
+/// Destination of exported L1 batch artifacts.
+///
+/// Artifacts are delivered at least once: if the exporter is stopped after exporting an L1 batch,
+/// but before persisting its cursor, the batch will be exported again on restart. Thus, implementations
+/// must handle repeated exports of the same L1 batch (e.g., by overwriting the previously exported data,
+/// or by deduplicating on the consumer side using [`L1BatchArtifacts::l1_batch_number`]).
+#[async_trait]
+pub trait BatchArtifactsSink: fmt::Debug + Send + Sync + 'static {
+    /// Returns a stable identifier of the sink. The identifier is used to persist export progress in Postgres,
+    /// so changing it will restart the export from the earliest L1 batch.
+    fn id(&self) -> &'static str;
+
+    /// Exports artifacts of a single L1 batch. Once this method returns successfully, the exported data
+    /// must be durable.
+    async fn export(&self, artifacts: &L1BatchArtifacts) -> anyhow::Result<()>;
+}
+
+/// Sink writing artifacts of each L1 batch to a separate JSON file in a local directory.
+///
+/// Files are written atomically (first to a temporary file, which is then renamed), so consumers never observe
+/// partially written artifacts. Repeated exports overwrite the previously written file.
+#[derive(Debug)]
+pub struct FileSink {
+    output_dir: PathBuf,
+}
+
+impl FileSink {
+    pub fn new(output_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            output_dir: output_dir.into(),
+        }
+    }
+
+    /// Returns the path to the file with artifacts for the specified L1 batch.
+    pub fn file_path(&self, l1_batch_number: L1BatchNumber) -> PathBuf {
+        // Padding ensures that lexicographical ordering of files corresponds to the ordering of L1 batches.
+        self.output_dir
+            .join(format!("l1_batch_{:010}.json", l1_batch_number.0))
+    }
+}
+
+#[async_trait]
+impl BatchArtifactsSink for FileSink {
+    fn id(&self) -> &'static str {
+        "file"
+    }
+
+    async fn export(&self, artifacts: &L1BatchArtifacts) -> anyhow::Result<()> {
+        let path = self.file_path(artifacts.l1_batch_number);
+        let tmp_path = path.with_extension("json.tmp");
+        let serialized =
+            serde_json::to_vec(artifacts).context("failed serializing L1 batch artifacts")?;
+
+        tokio::fs::create_dir_all(&self.output_dir)
+            .await
+            .with_context(|| format!("failed creating output dir {:?}", self.output_dir))?;
+        tokio::fs::write(&tmp_path, &serialized)
+            .await
+            .with_context(|| format!("failed writing {tmp_path:?}"))?;
+        // Sync the file before renaming, so that the file is durable once the cursor is advanced.
+        tokio::fs::File::open(&tmp_path)
+            .await
+            .with_context(|| format!("failed opening {tmp_path:?}"))?
+            .sync_all()
+            .await
+            .with_context(|| format!("failed syncing {tmp_path:?}"))?;
+        tokio::fs::rename(&tmp_path, &path)
+            .await
+            .with_context(|| format!("failed renaming {tmp_path:?} to {path:?}"))?;
+        Ok(())
+    }
+}
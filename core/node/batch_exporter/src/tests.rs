@@ -0,0 +1,240 @@
+use std::{ops, sync::Mutex};
+
+use arrow_array::{Array, UInt32Array};
+use async_trait::async_trait;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use test_log::test;
+use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+use zksync_node_test_utils::{create_l1_batch, create_l2_block};
+
+use super::*;
+
+#[derive(Debug, Default)]
+struct MockSink {
+    exported: Mutex<Vec<L1BatchArtifacts>>,
+    fail: bool,
+}
+
+#[async_trait]
+impl BatchArtifactsSink for MockSink {
+    fn id(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn export(&self, artifacts: &L1BatchArtifacts) -> anyhow::Result<()> {
+        anyhow::ensure!(!self.fail, "sink is unavailable");
+        self.exported.lock().unwrap().push(artifacts.clone());
+        Ok(())
+    }
+}
+
+const CONFIG: BatchExporterConfig = BatchExporterConfig {
+    poll_interval: Duration::from_millis(10),
+    max_batches_per_iteration: 2,
+};
+
+async fn seal_l1_batches(storage: &mut Connection<'_, Core>, numbers: ops::RangeInclusive<u32>) {
+    for number in numbers {
+        storage
+            .blocks_dal()
+            .insert_l2_block(&create_l2_block(number))
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch(number))
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .mark_l2_blocks_as_executed_in_l1_batch(L1BatchNumber(number))
+            .await
+            .unwrap();
+    }
+}
+
+fn health_for(exporter: &BatchExporter) -> BatchExporterHealth {
+    BatchExporterHealth {
+        sink: exporter.sink.id(),
+        last_exported_l1_batch: None,
+    }
+}
+
+#[test(tokio::test)]
+async fn exporting_l1_batches() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    seal_l1_batches(&mut storage, 1..=2).await;
+
+    let sink = Arc::new(MockSink::default());
+    let exporter = BatchExporter::new(CONFIG, pool.clone(), sink.clone());
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let mut health = health_for(&exporter);
+
+    let exported_l1_batches = exporter
+        .run_single_iteration(&stop_receiver, &mut health)
+        .await
+        .unwrap();
+    assert_eq!(exported_l1_batches, 2);
+    assert_eq!(health.last_exported_l1_batch, Some(L1BatchNumber(1)));
+    let cursor = storage.batch_export_dal().get_cursor("mock").await.unwrap();
+    assert_eq!(cursor, Some(L1BatchNumber(1)));
+
+    let exported_l1_batches = exporter
+        .run_single_iteration(&stop_receiver, &mut health)
+        .await
+        .unwrap();
+    assert_eq!(exported_l1_batches, 1);
+    let exported_l1_batches = exporter
+        .run_single_iteration(&stop_receiver, &mut health)
+        .await
+        .unwrap();
+    assert_eq!(exported_l1_batches, 0);
+
+    let exported = sink.exported.lock().unwrap();
+    let exported_numbers: Vec<_> = exported.iter().map(|batch| batch.l1_batch_number).collect();
+    assert_eq!(exported_numbers, [0, 1, 2].map(L1BatchNumber));
+    // The genesis batch initializes system contracts' storage.
+    let genesis_artifacts = &exported[0];
+    assert!(!genesis_artifacts.storage_diffs.is_empty());
+    assert_eq!(
+        genesis_artifacts.pubdata.storage_diff_count,
+        genesis_artifacts.storage_diffs.len()
+    );
+    assert!(genesis_artifacts.storage_diffs.windows(2).all(|diffs| (
+        diffs[0].address,
+        diffs[0].key
+    ) < (
+        diffs[1].address,
+        diffs[1].key
+    )));
+}
+
+#[test(tokio::test)]
+async fn cursor_is_not_advanced_on_sink_error() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    seal_l1_batches(&mut storage, 1..=1).await;
+
+    let failing_sink = Arc::new(MockSink {
+        fail: true,
+        ..MockSink::default()
+    });
+    let exporter = BatchExporter::new(CONFIG, pool.clone(), failing_sink);
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let mut health = health_for(&exporter);
+    let err = exporter
+        .run_single_iteration(&stop_receiver, &mut health)
+        .await
+        .unwrap_err();
+    assert!(
+        format!("{err:#}").contains("sink is unavailable"),
+        "{err:#}"
+    );
+    let cursor = storage.batch_export_dal().get_cursor("mock").await.unwrap();
+    assert_eq!(cursor, None);
+
+    let sink = Arc::new(MockSink::default());
+    let exporter = BatchExporter::new(CONFIG, pool.clone(), sink.clone());
+    let exported_l1_batches = exporter
+        .run_single_iteration(&stop_receiver, &mut health)
+        .await
+        .unwrap();
+    assert_eq!(exported_l1_batches, 2);
+    assert_eq!(
+        sink.exported.lock().unwrap()[0].l1_batch_number,
+        L1BatchNumber(0)
+    );
+}
+
+#[test(tokio::test)]
+async fn file_sink_overwrites_exported_batches() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let artifacts = BatchExporter::load_artifacts(&mut storage, L1BatchNumber(0))
+        .await
+        .unwrap()
+        .expect("no genesis artifacts");
+    assert!(
+        BatchExporter::load_artifacts(&mut storage, L1BatchNumber(1))
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let sink = FileSink::new(temp_dir.path().join("exports"));
+    sink.export(&artifacts).await.unwrap();
+    // Repeated exports must be idempotent.
+    sink.export(&artifacts).await.unwrap();
+
+    let path = sink.file_path(L1BatchNumber(0));
+    assert!(path.ends_with("l1_batch_0000000000.json"), "{path:?}");
+    let contents = tokio::fs::read(&path).await.unwrap();
+    let restored: L1BatchArtifacts = serde_json::from_slice(&contents).unwrap();
+    assert_eq!(restored, artifacts);
+    assert!(!path.with_extension("json.tmp").exists());
+}
+
+#[test(tokio::test)]
+async fn parquet_sink_writes_tables() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let artifacts = BatchExporter::load_artifacts(&mut storage, L1BatchNumber(0))
+        .await
+        .unwrap()
+        .expect("no genesis artifacts");
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let sink = ParquetSink::new(temp_dir.path().join("exports"));
+    sink.export(&artifacts).await.unwrap();
+    // Repeated exports must be idempotent.
+    sink.export(&artifacts).await.unwrap();
+
+    let expected_row_counts = [
+        artifacts.transactions.len(),
+        artifacts.storage_diffs.len(),
+        artifacts.events.len(),
+        1,
+    ];
+    assert!(!artifacts.storage_diffs.is_empty());
+    for (table, expected_row_count) in ParquetSink::TABLES.into_iter().zip(expected_row_counts) {
+        let path = sink.file_path(table, L1BatchNumber(0));
+        assert!(
+            path.ends_with(format!("{table}/l1_batch_0000000000.parquet")),
+            "{path:?}"
+        );
+        assert!(!path.with_extension("parquet.tmp").exists());
+
+        let file = std::fs::File::open(&path).unwrap();
+        let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut row_count = 0;
+        for batch in reader {
+            let batch = batch.unwrap();
+            let l1_batch_numbers = batch
+                .column_by_name("l1_batch_number")
+                .unwrap()
+                .as_any()
+                .downcast_ref::<UInt32Array>()
+                .unwrap();
+            assert!(l1_batch_numbers.iter().all(|number| number == Some(0)));
+            row_count += batch.num_rows();
+        }
+        assert_eq!(row_count, expected_row_count, "{table}");
+    }
+}
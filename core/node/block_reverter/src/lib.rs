@@ -11,6 +11,9 @@ pub use zksync_eth_client as eth_client;
 use zksync_eth_client::{BoundEthInterface, CallFunctionArgs, EthInterface, Options};
 use zksync_merkle_tree::domain::ZkSyncTree;
 use zksync_object_store::{ObjectStore, ObjectStoreError};
+use zksync_prover_interface::inputs::{
+    VMRunWitnessInputData, WitnessInputData, WitnessInputMerklePaths,
+};
 use zksync_state::RocksdbStorage;
 use zksync_storage::RocksDB;
 use zksync_types::{
@@ -22,7 +25,7 @@ use zksync_types::{
         SnapshotStorageLogsStorageKey,
     },
     web3::BlockNumber,
-    Address, L1BatchNumber, L2ChainId, H160, H256, U256,
+    Address, L1BatchNumber, L2BlockNumber, L2ChainId, H160, H256, U256,
 };
 
 #[cfg(test)]
@@ -34,6 +37,8 @@ mod tests;
 const GATEWAY_DEFAULT_GAS: usize = 50_000_000;
 /// The amount of gas to be used for transactions on top of L1 chains.
 const L1_DEFAULT_GAS: usize = 5_000_000;
+/// Maximum number of concurrent requests to the object store when removing artifacts.
+const CONCURRENT_REMOVE_REQUESTS: usize = 20;
 
 #[derive(Debug)]
 pub struct BlockReverterEthConfig {
@@ -90,8 +95,15 @@ pub enum NodeRole {
 /// - State of the Merkle tree
 /// - State of the RocksDB storage cache
 /// - Object store for protocol snapshots
+/// - Object store for witness inputs of the reverted batches
+///
+/// Rows of the reverted batches in dependent Postgres tables (e.g., proof generation details and data availability
+/// dispatch records) are removed together with the batches. Artifacts affected by the rollback are listed
+/// in the returned [`RollbackReport`].
 ///
 /// In addition, it can revert the state of the Ethereum contract (if the reverted L1 batches were committed).
+///
+/// If enabled with [`Self::enable_audit_log()`], performed actions are recorded in the operator audit log.
 #[derive(Debug)]
 pub struct BlockReverter {
     /// Role affects the interactions with the consensus state.
@@ -103,6 +115,8 @@ pub struct BlockReverter {
     storage_cache_paths: Vec<String>,
     merkle_tree_path: Option<String>,
     snapshots_object_store: Option<Arc<dyn ObjectStore>>,
+    witness_inputs_object_store: Option<Arc<dyn ObjectStore>>,
+    audit_actor: Option<String>,
 }
 
 impl BlockReverter {
@@ -115,6 +129,8 @@ impl BlockReverter {
             storage_cache_paths: Vec::new(),
             merkle_tree_path: None,
             snapshots_object_store: None,
+            witness_inputs_object_store: None,
+            audit_actor: None,
         }
     }
 
@@ -151,8 +167,71 @@ impl BlockReverter {
         self
     }
 
-    /// Rolls back previously enabled DBs (Postgres + RocksDB) and the snapshot object store to a previous state.
-    pub async fn roll_back(&self, last_l1_batch_to_keep: L1BatchNumber) -> anyhow::Result<()> {
+    /// Enables removing witness inputs of the reverted L1 batches from the provided object store.
+    /// Only has effect if Postgres is rolled back as well.
+    pub fn enable_rolling_back_witness_inputs(
+        &mut self,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> &mut Self {
+        self.witness_inputs_object_store = Some(object_store);
+        self
+    }
+
+    /// Enables recording performed actions in the operator audit log on behalf of the specified actor.
+    /// Should be used for reversions initiated by node operators; automatic rollbacks (e.g., on reorgs detected
+    /// by the external node) are not recorded.
+    pub fn enable_audit_log(&mut self, actor: String) -> &mut Self {
+        self.audit_actor = Some(actor);
+        self
+    }
+
+    async fn record_action(
+        &self,
+        action: &str,
+        before: Option<serde_json::Value>,
+        after: Option<serde_json::Value>,
+    ) -> anyhow::Result<()> {
+        let Some(actor) = &self.audit_actor else {
+            return Ok(());
+        };
+        self.connection_pool
+            .connection()
+            .await?
+            .audit_log_dal()
+            .insert_entry(action, actor, before.as_ref(), after.as_ref())
+            .await
+            .context("failed recording action in the audit log")?;
+        Ok(())
+    }
+
+    /// Rolls back previously enabled DBs (Postgres + RocksDB) and object stores to a previous state.
+    /// Returns a report listing the artifacts affected by the rollback.
+    pub async fn roll_back(
+        &self,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> anyhow::Result<RollbackReport> {
+        let state_before = if self.audit_actor.is_some() {
+            let mut storage = self.connection_pool.connection().await?;
+            let last_sealed_l1_batch = storage.blocks_dal().get_sealed_l1_batch_number().await?;
+            let last_sealed_l2_block = storage.blocks_dal().get_sealed_l2_block_number().await?;
+            Some(serde_json::json!({
+                "last_sealed_l1_batch": last_sealed_l1_batch,
+                "last_sealed_l2_block": last_sealed_l2_block,
+            }))
+        } else {
+            None
+        };
+        let report = self.roll_back_inner(last_l1_batch_to_keep).await?;
+        let report_json = serde_json::to_value(&report).context("failed serializing report")?;
+        self.record_action("roll_back_l1_batches", state_before, Some(report_json))
+            .await?;
+        Ok(report)
+    }
+
+    async fn roll_back_inner(
+        &self,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> anyhow::Result<RollbackReport> {
         if !self.allow_rolling_back_executed_batches {
             let mut storage = self.connection_pool.connection().await?;
             let last_executed_l1_batch = storage
@@ -168,14 +247,24 @@ impl BlockReverter {
         // Tree needs to be rolled back first to keep the state recoverable
         self.roll_back_rocksdb_instances(last_l1_batch_to_keep)
             .await?;
+        let mut report = RollbackReport {
+            last_l1_batch_to_keep,
+            ..RollbackReport::default()
+        };
         let deleted_snapshots = if self.should_roll_back_postgres {
-            self.roll_back_postgres(last_l1_batch_to_keep).await?
+            self.roll_back_postgres(last_l1_batch_to_keep, &mut report)
+                .await?
         } else {
             vec![]
         };
+        report.deleted_snapshots = deleted_snapshots
+            .iter()
+            .map(|snapshot| snapshot.l1_batch_number)
+            .collect();
 
         if let Some(object_store) = self.snapshots_object_store.as_deref() {
             Self::delete_snapshot_files(object_store, &deleted_snapshots).await?;
+            report.removed_snapshot_files = true;
         } else if !deleted_snapshots.is_empty() {
             tracing::info!(
                 "Did not remove snapshot files in object store since it was not provided; \
@@ -183,7 +272,24 @@ impl BlockReverter {
             );
         }
 
-        Ok(())
+        if let Some(object_store) = self.witness_inputs_object_store.as_deref() {
+            Self::delete_witness_inputs(object_store, &report.reverted_witness_inputs).await?;
+            report.removed_witness_inputs = true;
+        } else if !report.reverted_witness_inputs.is_empty() {
+            tracing::info!(
+                "Did not remove witness inputs in object store since it was not provided; \
+                 L1 batches with reverted witness inputs: {:?}",
+                report.reverted_witness_inputs
+            );
+        }
+
+        if !report.dispatched_da_blobs.is_empty() {
+            tracing::warn!(
+                "Reverted L1 batches were dispatched to the DA layer; the dispatched blobs are left dangling: {:?}",
+                report.dispatched_da_blobs
+            );
+        }
+        Ok(report)
     }
 
     async fn roll_back_rocksdb_instances(
@@ -300,6 +406,7 @@ impl BlockReverter {
     async fn roll_back_postgres(
         &self,
         last_l1_batch_to_keep: L1BatchNumber,
+        report: &mut RollbackReport,
     ) -> anyhow::Result<Vec<SnapshotMetadata>> {
         tracing::info!("Rolling back Postgres data");
         let mut storage = self.connection_pool.connection().await?;
@@ -313,6 +420,31 @@ impl BlockReverter {
                 format!("L1 batch #{last_l1_batch_to_keep} doesn't contain L2 blocks")
             })?;
 
+        report.last_l2_block_to_keep = Some(last_l2_block_to_keep);
+
+        // Proof generation details and data availability records are removed together with L1 batches,
+        // so we need to collect them beforehand.
+        report.reverted_witness_inputs = transaction
+            .proof_generation_dal()
+            .get_l1_batches_with_proof_generation_details_after(last_l1_batch_to_keep)
+            .await?;
+        report.dispatched_da_blobs = transaction
+            .data_availability_dal()
+            .get_dispatched_blobs_after(last_l1_batch_to_keep)
+            .await?
+            .into_iter()
+            .map(|(l1_batch_number, blob_id)| DispatchedDaBlob {
+                l1_batch_number,
+                blob_id,
+            })
+            .collect();
+
+        // Aggregated contract usage is computed from transactions, so it must be rolled back before them.
+        tracing::info!("Rolling back aggregated contract usage");
+        transaction
+            .contract_usage_dal()
+            .roll_back(last_l2_block_to_keep)
+            .await?;
         tracing::info!("Rolling back transactions state");
         transaction
             .transactions_dal()
@@ -328,6 +460,16 @@ impl BlockReverter {
             .events_dal()
             .roll_back_l2_to_l1_logs(last_l2_block_to_keep)
             .await?;
+        tracing::info!("Rolling back soft confirmations");
+        transaction
+            .soft_confirmations_dal()
+            .delete_soft_confirmations_after(last_l2_block_to_keep)
+            .await?;
+        tracing::info!("Rolling back unsealed batch checkpoints");
+        transaction
+            .unsealed_batch_checkpoints_dal()
+            .delete_checkpoints_starting_from(last_l2_block_to_keep + 1)
+            .await?;
         tracing::info!("Rolling back created tokens");
         transaction
             .tokens_dal()
@@ -395,25 +537,6 @@ impl BlockReverter {
         object_store: &dyn ObjectStore,
         deleted_snapshots: &[SnapshotMetadata],
     ) -> anyhow::Result<()> {
-        const CONCURRENT_REMOVE_REQUESTS: usize = 20;
-
-        fn ignore_not_found_errors(err: ObjectStoreError) -> Result<(), ObjectStoreError> {
-            match err {
-                ObjectStoreError::KeyNotFound(err) => {
-                    tracing::debug!("Ignoring 'not found' object store error: {err}");
-                    Ok(())
-                }
-                _ => Err(err),
-            }
-        }
-
-        fn combine_results(output: &mut anyhow::Result<()>, result: anyhow::Result<()>) {
-            if let Err(err) = result {
-                tracing::warn!("{err:?}");
-                *output = Err(err);
-            }
-        }
-
         if deleted_snapshots.is_empty() {
             return Ok(());
         }
@@ -482,6 +605,54 @@ impl BlockReverter {
         overall_result
     }
 
+    async fn delete_witness_inputs(
+        object_store: &dyn ObjectStore,
+        l1_batch_numbers: &[L1BatchNumber],
+    ) -> anyhow::Result<()> {
+        let remove_semaphore = &Semaphore::new(CONCURRENT_REMOVE_REQUESTS);
+        let remove_futures = l1_batch_numbers.iter().map(|&l1_batch_number| async move {
+            let _permit = remove_semaphore
+                .acquire()
+                .await
+                .context("semaphore is never closed")?;
+
+            tracing::info!("Removing witness inputs for L1 batch #{l1_batch_number}");
+            let mut result = Ok(());
+            let vm_run_data_result = object_store
+                .remove::<VMRunWitnessInputData>(l1_batch_number)
+                .await
+                .or_else(ignore_not_found_errors)
+                .with_context(|| {
+                    format!("failed removing VM run data for L1 batch #{l1_batch_number}")
+                });
+            combine_results(&mut result, vm_run_data_result);
+            let merkle_paths_result = object_store
+                .remove::<WitnessInputMerklePaths>(l1_batch_number)
+                .await
+                .or_else(ignore_not_found_errors)
+                .with_context(|| {
+                    format!("failed removing Merkle paths for L1 batch #{l1_batch_number}")
+                });
+            combine_results(&mut result, merkle_paths_result);
+            let witness_inputs_result = object_store
+                .remove::<WitnessInputData>(l1_batch_number)
+                .await
+                .or_else(ignore_not_found_errors)
+                .with_context(|| {
+                    format!("failed removing witness inputs for L1 batch #{l1_batch_number}")
+                });
+            combine_results(&mut result, witness_inputs_result);
+            result
+        });
+        let remove_results = futures::future::join_all(remove_futures).await;
+
+        let mut overall_result = Ok(());
+        for result in remove_results {
+            combine_results(&mut overall_result, result);
+        }
+        overall_result
+    }
+
     /// Sends a revert transaction to L1.
     pub async fn send_ethereum_revert_transaction(
         &self,
@@ -543,7 +714,14 @@ impl BlockReverter {
                     receipt.status
                 );
                 tracing::info!("Revert transaction has completed");
-                return Ok(());
+                let after = serde_json::json!({
+                    "last_l1_batch_to_keep": last_l1_batch_to_keep,
+                    "nonce": nonce,
+                    "tx_hash": hash,
+                });
+                return self
+                    .record_action("send_revert_transaction", None, Some(after))
+                    .await;
             } else {
                 tracing::info!("waiting for L1 transaction confirmation...");
                 tokio::time::sleep(Duration::from_secs(5)).await;
@@ -630,10 +808,56 @@ impl BlockReverter {
             .eth_sender_dal()
             .clear_failed_transactions()
             .await?;
-        Ok(())
+        self.record_action("clear_failed_l1_transactions", None, None)
+            .await
     }
 }
 
+fn ignore_not_found_errors(err: ObjectStoreError) -> Result<(), ObjectStoreError> {
+    match err {
+        ObjectStoreError::KeyNotFound(err) => {
+            tracing::debug!("Ignoring 'not found' object store error: {err}");
+            Ok(())
+        }
+        _ => Err(err),
+    }
+}
+
+fn combine_results(output: &mut anyhow::Result<()>, result: anyhow::Result<()>) {
+    if let Err(err) = result {
+        tracing::warn!("{err:?}");
+        *output = Err(err);
+    }
+}
+
+/// Machine-readable report on a rollback performed by [`BlockReverter::roll_back()`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RollbackReport {
+    /// Last L1 batch retained after the rollback.
+    pub last_l1_batch_to_keep: L1BatchNumber,
+    /// Last L2 block retained after the rollback. Only set if Postgres was rolled back.
+    pub last_l2_block_to_keep: Option<L2BlockNumber>,
+    /// L1 batch numbers of deleted protocol snapshots.
+    pub deleted_snapshots: Vec<L1BatchNumber>,
+    /// Whether files of deleted snapshots were removed from the object store.
+    pub removed_snapshot_files: bool,
+    /// Reverted L1 batches that had proof generation details, i.e. may have witness inputs persisted in the object store.
+    pub reverted_witness_inputs: Vec<L1BatchNumber>,
+    /// Whether witness inputs of reverted L1 batches were removed from the object store.
+    pub removed_witness_inputs: bool,
+    /// Reverted L1 batches that were dispatched to the DA layer. Dispatch records are removed from Postgres,
+    /// but blobs cannot be removed from the DA layer.
+    pub dispatched_da_blobs: Vec<DispatchedDaBlob>,
+}
+
+/// Blob dispatched to the DA layer for a reverted L1 batch.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DispatchedDaBlob {
+    pub l1_batch_number: L1BatchNumber,
+    /// ID of the blob; `None` if the DA layer hasn't assigned an ID yet.
+    pub blob_id: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct SuggestedRevertValues {
     pub last_executed_l1_batch_number: L1BatchNumber,
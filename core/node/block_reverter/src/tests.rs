@@ -6,15 +6,18 @@ use assert_matches::assert_matches;
 use async_trait::async_trait;
 use test_casing::test_casing;
 use tokio::sync::watch;
-use zksync_dal::Connection;
+use zksync_dal::{unsealed_batch_checkpoints_dal::UnsealedBatchCheckpoint, Connection};
 use zksync_merkle_tree::TreeInstruction;
-use zksync_object_store::{Bucket, MockObjectStore};
+use zksync_object_store::{Bucket, MockObjectStore, StoredObject};
 use zksync_state::interface::ReadStorage;
 use zksync_types::{
     block::{L1BatchHeader, L2BlockHeader},
+    commitment::PubdataType,
     fee_model::BatchFeeInput,
     snapshots::SnapshotVersion,
-    AccountTreeId, L2BlockNumber, ProtocolVersion, ProtocolVersionId, StorageKey, StorageLog,
+    soft_confirmation::SoftConfirmationCommitment,
+    AccountTreeId, K256PrivateKey, L2BlockNumber, ProtocolVersion, ProtocolVersionId, StorageKey,
+    StorageLog,
 };
 
 use super::*;
@@ -206,6 +209,210 @@ async fn block_reverter_basics(sync_merkle_tree: bool) {
     }
 }
 
+#[tokio::test]
+async fn reverting_soft_confirmations() {
+    let storage_logs = gen_storage_logs();
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &storage_logs).await;
+
+    let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(0x11)).unwrap();
+    let confirmations: Vec<_> = (1..=7_u32)
+        .map(|number| {
+            let commitment = SoftConfirmationCommitment {
+                tx_hash: H256::from_low_u64_be(number.into()),
+                l2_block_number: L2BlockNumber(number),
+                index_in_block: 0,
+            };
+            (commitment, commitment.sign(&private_key).unwrap())
+        })
+        .collect();
+    storage
+        .soft_confirmations_dal()
+        .insert_soft_confirmations(private_key.address(), &confirmations)
+        .await
+        .unwrap();
+
+    BlockReverter::new(NodeRole::External, pool.clone())
+        .enable_rolling_back_postgres()
+        .roll_back(L1BatchNumber(5))
+        .await
+        .unwrap();
+
+    for (commitment, _) in &confirmations {
+        let confirmation = storage
+            .soft_confirmations_dal()
+            .get_soft_confirmation(commitment.tx_hash)
+            .await
+            .unwrap();
+        assert_eq!(
+            confirmation.is_some(),
+            commitment.l2_block_number <= L2BlockNumber(5),
+            "{commitment:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn reverting_unsealed_batch_checkpoints() {
+    let storage_logs = gen_storage_logs();
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &storage_logs).await;
+
+    for number in 4..=7 {
+        let checkpoint = UnsealedBatchCheckpoint {
+            l1_batch_number: L1BatchNumber(number),
+            l2_block_number: L2BlockNumber(number),
+            tx_count: 1,
+            storage_logs_checksum: H256::repeat_byte(number as u8),
+        };
+        storage
+            .unsealed_batch_checkpoints_dal()
+            .insert_checkpoint(&checkpoint)
+            .await
+            .unwrap();
+    }
+
+    BlockReverter::new(NodeRole::External, pool.clone())
+        .enable_rolling_back_postgres()
+        .roll_back(L1BatchNumber(5))
+        .await
+        .unwrap();
+
+    for number in 4..=7 {
+        let checkpoints = storage
+            .unsealed_batch_checkpoints_dal()
+            .get_checkpoints(L1BatchNumber(number))
+            .await
+            .unwrap();
+        assert_eq!(checkpoints.len(), usize::from(number <= 5), "{number}");
+    }
+}
+
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn reverting_witness_inputs_and_da_records(remove_objects: bool) {
+    let storage_logs = gen_storage_logs();
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &storage_logs).await;
+
+    let object_store = MockObjectStore::arc();
+    for number in [4, 7] {
+        let l1_batch_number = L1BatchNumber(number);
+        storage
+            .proof_generation_dal()
+            .insert_proof_generation_details(l1_batch_number)
+            .await
+            .unwrap();
+        let key = <VMRunWitnessInputData as StoredObject>::encode_key(l1_batch_number);
+        object_store
+            .put_raw(Bucket::WitnessInput, &key, vec![1, 2, 3])
+            .await
+            .unwrap();
+        storage
+            .data_availability_dal()
+            .insert_l1_batch_da(
+                l1_batch_number,
+                &format!("blob-{number}"),
+                Default::default(),
+                PubdataType::Avail,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    let mut block_reverter = BlockReverter::new(NodeRole::External, pool.clone());
+    block_reverter.enable_rolling_back_postgres();
+    if remove_objects {
+        block_reverter.enable_rolling_back_witness_inputs(object_store.clone());
+    }
+    let report = block_reverter.roll_back(L1BatchNumber(5)).await.unwrap();
+
+    assert_eq!(report.last_l1_batch_to_keep, L1BatchNumber(5));
+    assert_eq!(report.last_l2_block_to_keep, Some(L2BlockNumber(5)));
+    assert_eq!(report.reverted_witness_inputs, [L1BatchNumber(7)]);
+    assert_eq!(report.removed_witness_inputs, remove_objects);
+    assert_eq!(
+        report.dispatched_da_blobs,
+        [DispatchedDaBlob {
+            l1_batch_number: L1BatchNumber(7),
+            blob_id: Some("blob-7".to_owned()),
+        }]
+    );
+
+    let da_details = storage
+        .data_availability_dal()
+        .get_da_details_by_batch_number(L1BatchNumber(7))
+        .await
+        .unwrap();
+    assert!(da_details.is_none());
+    let da_details = storage
+        .data_availability_dal()
+        .get_da_details_by_batch_number(L1BatchNumber(4))
+        .await
+        .unwrap();
+    assert!(da_details.is_some());
+
+    let retained_key = <VMRunWitnessInputData as StoredObject>::encode_key(L1BatchNumber(4));
+    object_store
+        .get_raw(Bucket::WitnessInput, &retained_key)
+        .await
+        .unwrap();
+    let reverted_key = <VMRunWitnessInputData as StoredObject>::encode_key(L1BatchNumber(7));
+    let reverted_result = object_store
+        .get_raw(Bucket::WitnessInput, &reverted_key)
+        .await;
+    if remove_objects {
+        assert_matches!(
+            reverted_result.unwrap_err(),
+            ObjectStoreError::KeyNotFound(_)
+        );
+    } else {
+        reverted_result.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn rollback_is_recorded_in_audit_log() {
+    let storage_logs = gen_storage_logs();
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &storage_logs).await;
+
+    // Rollbacks are not recorded unless the audit log is enabled.
+    BlockReverter::new(NodeRole::External, pool.clone())
+        .enable_rolling_back_postgres()
+        .roll_back(L1BatchNumber(7))
+        .await
+        .unwrap();
+    let entries = storage.audit_log_dal().get_entries(0, 10).await.unwrap();
+    assert!(entries.is_empty());
+
+    let report = BlockReverter::new(NodeRole::External, pool.clone())
+        .enable_rolling_back_postgres()
+        .enable_audit_log("alice".to_owned())
+        .roll_back(L1BatchNumber(5))
+        .await
+        .unwrap();
+    let entries = storage.audit_log_dal().get_entries(0, 10).await.unwrap();
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry.action, "roll_back_l1_batches");
+    assert_eq!(entry.actor, "alice");
+    assert_eq!(
+        entry.before,
+        Some(serde_json::json!({
+            "last_sealed_l1_batch": 7,
+            "last_sealed_l2_block": 7,
+        }))
+    );
+    assert_eq!(entry.after, Some(serde_json::to_value(&report).unwrap()));
+}
+
 async fn create_mock_snapshot(
     storage: &mut Connection<'_, Core>,
     object_store: &dyn ObjectStore,
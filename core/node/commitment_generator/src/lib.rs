@@ -5,28 +5,29 @@ use itertools::Itertools;
 use tokio::{sync::watch, task::JoinHandle};
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
-use zksync_l1_contract_interface::i_executor::commit::kzg::pubdata_to_blob_commitments;
 use zksync_multivm::zk_evm_latest::ethereum_types::U256;
 use zksync_types::{
-    blob::num_blobs_required,
     commitment::{
-        AuxCommitments, BlobHash, CommitmentCommonInput, CommitmentInput, L1BatchAuxiliaryOutput,
-        L1BatchCommitment, L1BatchCommitmentArtifacts, L1BatchCommitmentMode,
+        AuxCommitments, CommitmentCommonInput, CommitmentInput, L1BatchCommitment,
+        L1BatchCommitmentArtifacts, L1BatchCommitmentMode,
     },
     h256_to_u256,
     writes::{InitialStorageWrite, RepeatedStorageWrite, StateDiffRecord},
     L1BatchNumber, ProtocolVersionId, StorageKey, H256,
 };
 
+pub use crate::recalculation::{recalculate_l1_batch_commitment, L1BatchCommitmentData};
 use crate::{
     metrics::{CommitmentStage, METRICS},
+    recalculation::{blob_hashes, post_process_commitment, tweak_input},
     utils::{
-        convert_vm_events_to_log_queries, pubdata_to_blob_linear_hashes, read_aggregation_root,
-        CommitmentComputer, RealCommitmentComputer,
+        convert_vm_events_to_log_queries, read_aggregation_root, CommitmentComputer,
+        RealCommitmentComputer,
     },
 };
 
 mod metrics;
+mod recalculation;
 #[cfg(test)]
 mod tests;
 mod utils;
@@ -267,32 +268,11 @@ impl CommitmentGenerator {
             }
             state_diffs.sort_unstable_by_key(|rec| (rec.address, rec.key));
 
-            let blob_hashes = if protocol_version.is_post_1_4_2() {
-                let pubdata_input = header.pubdata_input.with_context(|| {
-                    format!("`pubdata_input` is missing for L1 batch #{l1_batch_number}")
+            let blob_hashes =
+                blob_hashes(protocol_version, header.pubdata_input).with_context(|| {
+                    format!("failed computing blob hashes for L1 batch #{l1_batch_number}")
                 })?;
 
-                let commitments = pubdata_to_blob_commitments(
-                    num_blobs_required(&protocol_version),
-                    &pubdata_input,
-                );
-                let linear_hashes = pubdata_to_blob_linear_hashes(
-                    num_blobs_required(&protocol_version),
-                    pubdata_input,
-                );
-
-                commitments
-                    .into_iter()
-                    .zip(linear_hashes)
-                    .map(|(commitment, linear_hash)| BlobHash {
-                        commitment,
-                        linear_hash,
-                    })
-                    .collect::<Vec<_>>()
-            } else {
-                vec![Default::default(); num_blobs_required(&protocol_version)]
-            };
-
             let aggregation_root = if protocol_version.is_pre_gateway() {
                 H256::zero()
             } else {
@@ -313,7 +293,7 @@ impl CommitmentGenerator {
             }
         };
 
-        self.tweak_input(&mut input);
+        tweak_input(self.commitment_mode, &mut input);
         Ok(input)
     }
 
@@ -331,7 +311,7 @@ impl CommitmentGenerator {
         let latency =
             METRICS.generate_commitment_latency_stage[&CommitmentStage::Calculate].start();
         let mut commitment = L1BatchCommitment::new(input);
-        self.post_process_commitment(&mut commitment);
+        post_process_commitment(self.commitment_mode, &mut commitment);
         let artifacts = commitment.artifacts();
         let latency = latency.observe();
         tracing::debug!(
@@ -383,32 +363,6 @@ impl CommitmentGenerator {
         Ok(())
     }
 
-    fn tweak_input(&self, input: &mut CommitmentInput) {
-        match (self.commitment_mode, input) {
-            (L1BatchCommitmentMode::Rollup, _) => {
-                // Do nothing
-            }
-            (L1BatchCommitmentMode::Validium, CommitmentInput::PostBoojum { blob_hashes, .. }) => {
-                for hashes in blob_hashes {
-                    hashes.commitment = H256::zero();
-                }
-            }
-            (L1BatchCommitmentMode::Validium, _) => { /* Do nothing */ }
-        }
-    }
-
-    fn post_process_commitment(&self, commitment: &mut L1BatchCommitment) {
-        match (self.commitment_mode, &mut commitment.auxiliary_output) {
-            (
-                L1BatchCommitmentMode::Validium,
-                L1BatchAuxiliaryOutput::PostBoojum { blob_hashes, .. },
-            ) => {
-                blob_hashes.fill(Default::default());
-            }
-            _ => { /* Do nothing */ }
-        }
-    }
-
     #[tracing::instrument(skip(self))]
     async fn next_batch_range(&self) -> anyhow::Result<Option<ops::RangeInclusive<L1BatchNumber>>> {
         let mut connection = self
@@ -0,0 +1,141 @@
+//! Standalone recalculation of L1 batch commitments, e.g. for audits or external verifiers.
+
+use anyhow::Context;
+use zksync_l1_contract_interface::i_executor::commit::kzg::pubdata_to_blob_commitments;
+use zksync_multivm::interface::VmEvent;
+use zksync_types::{
+    blob::num_blobs_required,
+    commitment::{
+        AuxCommitments, BlobHash, CommitmentCommonInput, CommitmentInput, L1BatchAuxiliaryOutput,
+        L1BatchCommitment, L1BatchCommitmentMode,
+    },
+    l2_to_l1_log::SystemL2ToL1Log,
+    writes::StateDiffRecord,
+    ProtocolVersionId, H256, U256,
+};
+
+use crate::utils::{
+    convert_vm_events_to_log_queries, pubdata_to_blob_linear_hashes, CommitmentComputer,
+    RealCommitmentComputer,
+};
+
+/// Data required to recalculate the commitment of a post-Boojum L1 batch.
+#[derive(Debug, Clone)]
+pub struct L1BatchCommitmentData {
+    /// Batch metadata: user L2-to-L1 logs, Merkle tree data, base system contract hashes and the protocol version.
+    pub common: CommitmentCommonInput,
+    /// System L2-to-L1 logs emitted in the batch.
+    pub system_logs: Vec<SystemL2ToL1Log>,
+    /// State diffs produced by the batch. Can be provided in any order.
+    pub state_diffs: Vec<StateDiffRecord>,
+    /// VM events emitted in the batch.
+    pub events: Vec<VmEvent>,
+    /// Non-zero words of the initial bootloader heap, as persisted by the state keeper.
+    pub initial_bootloader_heap: Vec<(usize, U256)>,
+    /// Pubdata of the batch. Required for protocol versions starting from 1.4.2.
+    pub pubdata: Option<Vec<u8>>,
+    /// Aggregation root of the batch. Ignored for pre-gateway protocol versions.
+    pub aggregation_root: H256,
+}
+
+/// Recalculates the commitment of an L1 batch exactly as it is computed by [`CommitmentGenerator`](crate::CommitmentGenerator)
+/// and committed on L1. The commitment hash can be obtained via [`L1BatchCommitment::hash()`].
+///
+/// # Errors
+///
+/// Returns an error if the batch uses a pre-Boojum protocol version, or if the data is incomplete for the protocol version
+/// (e.g., pubdata is missing).
+pub fn recalculate_l1_batch_commitment(
+    data: L1BatchCommitmentData,
+    commitment_mode: L1BatchCommitmentMode,
+) -> anyhow::Result<L1BatchCommitment> {
+    let protocol_version = data.common.protocol_version;
+    anyhow::ensure!(
+        !protocol_version.is_pre_boojum(),
+        "recalculating commitments is not supported for pre-Boojum protocol version {protocol_version:?}"
+    );
+
+    let computer = RealCommitmentComputer;
+    let events_queue = convert_vm_events_to_log_queries(&data.events);
+    let aux_commitments = AuxCommitments {
+        events_queue_commitment: computer
+            .events_queue_commitment(&events_queue, protocol_version)
+            .context("failed computing events queue commitment")?,
+        bootloader_initial_content_commitment: computer
+            .bootloader_initial_content_commitment(&data.initial_bootloader_heap, protocol_version)
+            .context("failed computing bootloader initial content commitment")?,
+    };
+
+    let mut state_diffs = data.state_diffs;
+    state_diffs.sort_unstable_by_key(|rec| (rec.address, rec.key));
+    let aggregation_root = if protocol_version.is_pre_gateway() {
+        H256::zero()
+    } else {
+        data.aggregation_root
+    };
+
+    let mut input = CommitmentInput::PostBoojum {
+        common: data.common,
+        system_logs: data.system_logs,
+        state_diffs,
+        aux_commitments,
+        blob_hashes: blob_hashes(protocol_version, data.pubdata)?,
+        aggregation_root,
+    };
+    tweak_input(commitment_mode, &mut input);
+    let mut commitment = L1BatchCommitment::new(input);
+    post_process_commitment(commitment_mode, &mut commitment);
+    Ok(commitment)
+}
+
+/// Computes blob hashes for a post-Boojum batch from its pubdata.
+pub(crate) fn blob_hashes(
+    protocol_version: ProtocolVersionId,
+    pubdata: Option<Vec<u8>>,
+) -> anyhow::Result<Vec<BlobHash>> {
+    let blobs_required = num_blobs_required(&protocol_version);
+    if !protocol_version.is_post_1_4_2() {
+        return Ok(vec![Default::default(); blobs_required]);
+    }
+
+    let pubdata = pubdata.context("pubdata is missing")?;
+    let commitments = pubdata_to_blob_commitments(blobs_required, &pubdata);
+    let linear_hashes = pubdata_to_blob_linear_hashes(blobs_required, pubdata);
+    Ok(commitments
+        .into_iter()
+        .zip(linear_hashes)
+        .map(|(commitment, linear_hash)| BlobHash {
+            commitment,
+            linear_hash,
+        })
+        .collect())
+}
+
+pub(crate) fn tweak_input(commitment_mode: L1BatchCommitmentMode, input: &mut CommitmentInput) {
+    match (commitment_mode, input) {
+        (L1BatchCommitmentMode::Rollup, _) => {
+            // Do nothing
+        }
+        (L1BatchCommitmentMode::Validium, CommitmentInput::PostBoojum { blob_hashes, .. }) => {
+            for hashes in blob_hashes {
+                hashes.commitment = H256::zero();
+            }
+        }
+        (L1BatchCommitmentMode::Validium, _) => { /* Do nothing */ }
+    }
+}
+
+pub(crate) fn post_process_commitment(
+    commitment_mode: L1BatchCommitmentMode,
+    commitment: &mut L1BatchCommitment,
+) {
+    match (commitment_mode, &mut commitment.auxiliary_output) {
+        (
+            L1BatchCommitmentMode::Validium,
+            L1BatchAuxiliaryOutput::PostBoojum { blob_hashes, .. },
+        ) => {
+            blob_hashes.fill(Default::default());
+        }
+        _ => { /* Do nothing */ }
+    }
+}
@@ -302,6 +302,68 @@ async fn commitment_generator_with_tree_emulation() {
     generator_handle.await.unwrap().unwrap();
 }
 
+#[tokio::test]
+async fn recalculating_commitment() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let l1_batch_number = L1BatchNumber(1);
+    seal_l1_batch(&mut storage, l1_batch_number).await;
+    save_l1_batch_tree_data(&mut storage, l1_batch_number).await;
+
+    for commitment_mode in [
+        L1BatchCommitmentMode::Rollup,
+        L1BatchCommitmentMode::Validium,
+    ] {
+        // Uses the real commitment computer, so that auxiliary commitments are comparable.
+        let generator = CommitmentGenerator::new(pool.clone(), commitment_mode);
+        let expected_artifacts = generator.process_batch(l1_batch_number).await.unwrap();
+        let CommitmentInput::PostBoojum {
+            common,
+            system_logs,
+            state_diffs,
+            aggregation_root,
+            ..
+        } = generator.prepare_input(l1_batch_number).await.unwrap()
+        else {
+            panic!("unexpected commitment input");
+        };
+
+        let events = storage
+            .events_dal()
+            .get_vm_events_for_l1_batch(l1_batch_number)
+            .await
+            .unwrap()
+            .unwrap();
+        let initial_bootloader_heap = storage
+            .blocks_dal()
+            .get_initial_bootloader_heap(l1_batch_number)
+            .await
+            .unwrap()
+            .unwrap();
+        let header = storage
+            .blocks_dal()
+            .get_l1_batch_header(l1_batch_number)
+            .await
+            .unwrap()
+            .unwrap();
+        let data = L1BatchCommitmentData {
+            common,
+            system_logs,
+            state_diffs,
+            events,
+            initial_bootloader_heap,
+            pubdata: header.pubdata_input,
+            aggregation_root,
+        };
+
+        let commitment = recalculate_l1_batch_commitment(data, commitment_mode).unwrap();
+        assert_eq!(commitment.hash(), expected_artifacts.commitment_hash);
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct SerdeVmEvent {
     location: (L1BatchNumber, u32),
@@ -31,6 +31,8 @@ pub(super) struct EN {
     pub(super) pool: ConnectionPool,
     pub(super) sync_state: SyncState,
     pub(super) client: Box<DynClient<L2>>,
+    /// Whether to run the fallback fetcher alongside the consensus node.
+    pub(super) gap_fallback: bool,
 }
 
 impl EN {
@@ -112,15 +114,17 @@ impl EN {
 
             // Run the temporary fetcher until the certificates are backfilled.
             // Temporary fetcher should be removed once json RPC syncing is fully deprecated.
-            s.spawn_bg({
-                let store = store.clone();
-                async {
-                    let store = store;
-                    self.fallback_block_fetcher(ctx, &store)
-                        .await
-                        .wrap("fallback_block_fetcher()")
-                }
-            });
+            if self.gap_fallback {
+                s.spawn_bg({
+                    let store = store.clone();
+                    async {
+                        let store = store;
+                        self.fallback_block_fetcher(ctx, &store)
+                            .await
+                            .wrap("fallback_block_fetcher()")
+                    }
+                });
+            }
 
             let (block_store, runner) = BlockStore::new(ctx, Box::new(store.clone()))
                 .await
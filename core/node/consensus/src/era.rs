@@ -37,10 +37,12 @@ pub async fn run_main_node(
 
 /// Runs the consensus node for the external node.
 /// If `cfg` is `None`, it will just fetch blocks from the main node
-/// using JSON RPC, without starting the consensus node.
+/// using JSON RPC, without starting the consensus node. If `gap_fallback` is set, the consensus node
+/// additionally fetches blocks using JSON RPC whenever it lags behind the main node.
 pub async fn run_external_node(
     ctx: &ctx::Ctx,
     cfg: Option<(ConsensusConfig, ConsensusSecrets)>,
+    gap_fallback: bool,
     pool: zksync_dal::ConnectionPool<Core>,
     sync_state: SyncState,
     main_node_client: Box<DynClient<L2>>,
@@ -51,6 +53,7 @@ pub async fn run_external_node(
         pool: ConnectionPool(pool),
         sync_state: sync_state.clone(),
         client: main_node_client.for_component("block_fetcher"),
+        gap_fallback,
     };
     let res = match cfg {
         Some((cfg, secrets)) => {
@@ -407,6 +407,7 @@ impl StateKeeper {
             pool: self.pool,
             client,
             sync_state: self.sync_state.clone(),
+            gap_fallback: false,
         }
         .run_fetcher(ctx, self.actions_sender)
         .await
@@ -423,6 +424,7 @@ impl StateKeeper {
             pool: self.pool,
             client,
             sync_state: self.sync_state.clone(),
+            gap_fallback: true,
         }
         .run(
             ctx,
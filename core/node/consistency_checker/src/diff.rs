@@ -0,0 +1,339 @@
+//! Structured diffs between locally reproduced L1 batch commitments and the ones obtained from L1.
+
+use std::fmt;
+
+use serde::Serialize;
+use zksync_types::{
+    commitment::{L1BatchCommitmentMode, SerializeCommitment},
+    ethabi::Token,
+    l2_to_l1_log::L2ToL1Log,
+    ProtocolVersionId,
+};
+
+/// Names of `CommitBatchInfo` fields for pre-Boojum batches.
+const PRE_BOOJUM_FIELDS: &[&str] = &[
+    "batchNumber",
+    "timestamp",
+    "indexRepeatedStorageChanges",
+    "newStateRoot",
+    "numberOfLayer1Txs",
+    "l2LogsTreeRoot",
+    "priorityOperationsHash",
+    "initialStorageChanges",
+    "repeatedStorageChanges",
+    "l2Logs",
+    "l2ArbitraryLengthMessages",
+    "factoryDeps",
+];
+/// Names of `CommitBatchInfo` fields for post-Boojum batches. The last field is named differently
+/// depending on the protocol version; see [`da_input_field_name()`].
+const POST_BOOJUM_FIELDS: &[&str] = &[
+    "batchNumber",
+    "timestamp",
+    "indexRepeatedStorageChanges",
+    "newStateRoot",
+    "numberOfLayer1Txs",
+    "priorityOperationsHash",
+    "bootloaderHeapInitialContentsHash",
+    "eventsQueueStateHash",
+    "systemLogs",
+];
+const SYSTEM_LOGS_FIELD_INDEX: usize = 8;
+/// Byte slices not longer than this are output in full.
+const MAX_DISPLAYED_BYTES: usize = 64;
+
+fn da_input_field_name(protocol_version: ProtocolVersionId) -> &'static str {
+    if protocol_version.is_pre_gateway() {
+        "pubdataCommitments"
+    } else {
+        "operatorDAInput"
+    }
+}
+
+/// Mismatch in a top-level field of the commitment.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldDiff {
+    pub field: &'static str,
+    pub local: String,
+    pub reference: String,
+}
+
+/// Mismatch in a system log committed as a part of the `systemLogs` field. Logs are matched by their index.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SystemLogDiff {
+    pub index: usize,
+    /// `None` if the log is missing locally.
+    pub local: Option<L2ToL1Log>,
+    /// `None` if the log is missing in the reference.
+    pub reference: Option<L2ToL1Log>,
+}
+
+/// Mismatch in a section of the pubdata / DA input field of the commitment.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PubdataSectionDiff {
+    pub section: &'static str,
+    pub local_len: usize,
+    pub reference_len: usize,
+    /// Offset of the first mismatched byte in the section, if the mismatch is not only in the section length.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_mismatch_offset: Option<usize>,
+    /// Local section contents; only output for short sections.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local: Option<String>,
+    /// Reference section contents; only output for short sections.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+}
+
+/// Structured diff between a locally reproduced L1 batch commitment and the reference commitment from L1.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CommitmentDiff {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldDiff>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub system_logs: Vec<SystemLogDiff>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pubdata_sections: Vec<PubdataSectionDiff>,
+}
+
+impl CommitmentDiff {
+    /// Computes the diff between `local` and `reference` commitment tokens produced by `CommitBatchInfo::into_token()`.
+    pub fn new(
+        protocol_version: ProtocolVersionId,
+        commitment_mode: L1BatchCommitmentMode,
+        local: &Token,
+        reference: &Token,
+    ) -> Self {
+        let (Token::Tuple(local_fields), Token::Tuple(reference_fields)) = (local, reference)
+        else {
+            return Self {
+                fields: vec![FieldDiff {
+                    field: "(commitment)",
+                    local: format_token(local),
+                    reference: format_token(reference),
+                }],
+                ..Self::default()
+            };
+        };
+
+        let is_pre_boojum = protocol_version.is_pre_boojum();
+        let field_name = |idx: usize| {
+            if is_pre_boojum {
+                PRE_BOOJUM_FIELDS.get(idx).copied().unwrap_or("(unknown)")
+            } else if idx < POST_BOOJUM_FIELDS.len() {
+                POST_BOOJUM_FIELDS[idx]
+            } else if idx == POST_BOOJUM_FIELDS.len() {
+                da_input_field_name(protocol_version)
+            } else {
+                "(unknown)"
+            }
+        };
+
+        let mut this = Self::default();
+        let field_count = local_fields.len().max(reference_fields.len());
+        for idx in 0..field_count {
+            let local_field = local_fields.get(idx);
+            let reference_field = reference_fields.get(idx);
+            if local_field == reference_field {
+                continue;
+            }
+            this.fields.push(FieldDiff {
+                field: field_name(idx),
+                local: local_field.map_or_else(|| "(missing)".to_owned(), format_token),
+                reference: reference_field.map_or_else(|| "(missing)".to_owned(), format_token),
+            });
+
+            let (Some(Token::Bytes(local_bytes)), Some(Token::Bytes(reference_bytes))) =
+                (local_field, reference_field)
+            else {
+                continue;
+            };
+            if is_pre_boojum {
+                continue;
+            }
+            if idx == SYSTEM_LOGS_FIELD_INDEX {
+                this.system_logs = diff_system_logs(local_bytes, reference_bytes);
+            } else if idx == POST_BOOJUM_FIELDS.len() {
+                this.pubdata_sections = diff_pubdata_sections(
+                    protocol_version,
+                    commitment_mode,
+                    local_bytes,
+                    reference_bytes,
+                );
+            }
+        }
+        this
+    }
+}
+
+impl fmt::Display for CommitmentDiff {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields: Vec<_> = self.fields.iter().map(|diff| diff.field).collect();
+        write!(formatter, "mismatched fields: {fields:?}")?;
+        if !self.system_logs.is_empty() {
+            let indices: Vec<_> = self.system_logs.iter().map(|diff| diff.index).collect();
+            write!(formatter, "; mismatched system logs: {indices:?}")?;
+        }
+        if !self.pubdata_sections.is_empty() {
+            let sections: Vec<_> = self
+                .pubdata_sections
+                .iter()
+                .map(|diff| diff.section)
+                .collect();
+            write!(formatter, "; mismatched pubdata sections: {sections:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned by the consistency checker if the locally reproduced commitment differs from the one on L1.
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Locally reproduced commitment differs from the reference obtained from L1 ({diff}); \
+     local: {local:?}, reference: {reference:?}"
+)]
+pub struct CommitmentMismatch {
+    pub diff: CommitmentDiff,
+    local: Token,
+    reference: Token,
+}
+
+impl CommitmentMismatch {
+    pub(crate) fn new(diff: CommitmentDiff, local: Token, reference: Token) -> Self {
+        Self {
+            diff,
+            local,
+            reference,
+        }
+    }
+}
+
+fn format_bytes(bytes: &[u8]) -> String {
+    if bytes.len() <= MAX_DISPLAYED_BYTES {
+        format!("0x{}", hex::encode(bytes))
+    } else {
+        format!(
+            "0x{}... ({} bytes)",
+            hex::encode(&bytes[..MAX_DISPLAYED_BYTES]),
+            bytes.len()
+        )
+    }
+}
+
+fn format_token(token: &Token) -> String {
+    match token {
+        Token::Uint(value) | Token::Int(value) => value.to_string(),
+        Token::FixedBytes(bytes) | Token::Bytes(bytes) => format_bytes(bytes),
+        Token::Array(items) | Token::FixedArray(items) | Token::Tuple(items) => {
+            format!("({} items)", items.len())
+        }
+        other => format!("{other:?}"),
+    }
+}
+
+fn diff_system_logs(local: &[u8], reference: &[u8]) -> Vec<SystemLogDiff> {
+    let parse_logs = |bytes: &[u8]| -> Vec<L2ToL1Log> {
+        bytes
+            .chunks_exact(L2ToL1Log::SERIALIZED_SIZE)
+            .map(L2ToL1Log::from_slice)
+            .collect()
+    };
+    let local = parse_logs(local);
+    let reference = parse_logs(reference);
+
+    let log_count = local.len().max(reference.len());
+    (0..log_count)
+        .filter_map(|index| {
+            let local = local.get(index);
+            let reference = reference.get(index);
+            (local != reference).then(|| SystemLogDiff {
+                index,
+                local: local.cloned(),
+                reference: reference.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// Splits off up to `len` bytes from the start of `bytes`.
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> &'a [u8] {
+    let (head, tail) = bytes.split_at(len.min(bytes.len()));
+    *bytes = tail;
+    head
+}
+
+/// Splits the pubdata / DA input field into logical sections. The layout mirrors `CommitBatchInfo::into_token()`.
+fn pubdata_sections(
+    protocol_version: ProtocolVersionId,
+    commitment_mode: L1BatchCommitmentMode,
+    mut bytes: &[u8],
+) -> Vec<(&'static str, &[u8])> {
+    if protocol_version.is_pre_1_4_2() {
+        return vec![("pubdata", bytes)];
+    }
+    if protocol_version.is_pre_gateway() {
+        let source = take(&mut bytes, 1);
+        return vec![("pubdataSource", source), ("pubdataCommitments", bytes)];
+    }
+
+    let state_diff_hash = take(&mut bytes, 32);
+    match commitment_mode {
+        L1BatchCommitmentMode::Validium => {
+            vec![
+                ("stateDiffHash", state_diff_hash),
+                ("daInclusionData", bytes),
+            ]
+        }
+        L1BatchCommitmentMode::Rollup => {
+            let full_pubdata_hash = take(&mut bytes, 32);
+            let blob_count = take(&mut bytes, 1);
+            let blob_hashes_len = blob_count
+                .first()
+                .map_or(0, |&count| 32 * usize::from(count));
+            let blob_hashes = take(&mut bytes, blob_hashes_len);
+            let source = take(&mut bytes, 1);
+            vec![
+                ("stateDiffHash", state_diff_hash),
+                ("fullPubdataHash", full_pubdata_hash),
+                ("blobCount", blob_count),
+                ("blobLinearHashes", blob_hashes),
+                ("pubdataSource", source),
+                ("pubdataCommitments", bytes),
+            ]
+        }
+    }
+}
+
+fn diff_pubdata_sections(
+    protocol_version: ProtocolVersionId,
+    commitment_mode: L1BatchCommitmentMode,
+    local: &[u8],
+    reference: &[u8],
+) -> Vec<PubdataSectionDiff> {
+    let local_sections = pubdata_sections(protocol_version, commitment_mode, local);
+    let reference_sections = pubdata_sections(protocol_version, commitment_mode, reference);
+    // Section layouts are the same for local and reference data, so sections can be zipped.
+    local_sections
+        .into_iter()
+        .zip(reference_sections)
+        .filter_map(|((section, local), (_, reference))| {
+            if local == reference {
+                return None;
+            }
+            let first_mismatch_offset = local
+                .iter()
+                .zip(reference)
+                .position(|(local_byte, reference_byte)| local_byte != reference_byte);
+            let is_short =
+                local.len() <= MAX_DISPLAYED_BYTES && reference.len() <= MAX_DISPLAYED_BYTES;
+            Some(PubdataSectionDiff {
+                section,
+                local_len: local.len(),
+                reference_len: reference.len(),
+                first_mismatch_offset,
+                local: is_short.then(|| format_bytes(local)),
+                reference: is_short.then(|| format_bytes(reference)),
+            })
+        })
+        .collect()
+}
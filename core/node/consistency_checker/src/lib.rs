@@ -26,6 +26,11 @@ use zksync_types::{
     Address, L1BatchNumber, ProtocolVersionId, SLChainId, H256, U256,
 };
 
+pub use self::diff::{
+    CommitmentDiff, CommitmentMismatch, FieldDiff, PubdataSectionDiff, SystemLogDiff,
+};
+
+mod diff;
 #[cfg(test)]
 mod tests;
 
@@ -74,6 +79,9 @@ struct ConsistencyCheckerDetails {
     last_checked_batch: Option<L1BatchNumber>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     inconsistent_batches: Vec<L1BatchNumber>,
+    /// Structured diff for the last inconsistent batch, if the inconsistency is caused by a commitment mismatch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_commitment_diff: Option<CommitmentDiff>,
 }
 
 impl ConsistencyCheckerDetails {
@@ -126,6 +134,13 @@ impl HandleConsistencyCheckerEvent for ConsistencyCheckerHealthUpdater {
     fn report_inconsistent_batch(&mut self, number: L1BatchNumber, err: &anyhow::Error) {
         tracing::warn!("L1 batch #{number} is inconsistent with L1: {err:?}");
         self.current_details.inconsistent_batches.push(number);
+        if let Some(mismatch) = err.downcast_ref::<CommitmentMismatch>() {
+            tracing::error!(
+                "Commitment diff for L1 batch #{number}: {:#?}",
+                mismatch.diff
+            );
+            self.current_details.last_commitment_diff = Some(mismatch.diff.clone());
+        }
         self.inner.update(self.current_details.health());
     }
 }
@@ -236,11 +251,15 @@ impl LocalL1BatchCommitData {
 
         let local_token =
             CommitBatchInfo::new(self.commitment_mode, &self.l1_batch, da).into_token();
-        anyhow::ensure!(
-            local_token == *reference,
-            "Locally reproduced commitment differs from the reference obtained from L1; \
-             local: {local_token:?}, reference: {reference:?}"
-        );
+        if local_token != *reference {
+            let diff = CommitmentDiff::new(
+                protocol_version,
+                self.commitment_mode,
+                &local_token,
+                reference,
+            );
+            return Err(CommitmentMismatch::new(diff, local_token, reference.clone()).into());
+        }
         Ok(())
     }
 }
@@ -8,15 +8,19 @@ use tokio::sync::mpsc;
 use zksync_config::GenesisConfig;
 use zksync_dal::Connection;
 use zksync_eth_client::{clients::MockSettlementLayer, EthInterface, Options};
+use zksync_health_check::CheckHealth;
 use zksync_l1_contract_interface::{i_executor::methods::CommitBatches, Tokenizable, Tokenize};
 use zksync_node_genesis::{insert_genesis_batch, mock_genesis_config, GenesisParams};
 use zksync_node_test_utils::{
     create_l1_batch, create_l1_batch_metadata, l1_batch_metadata_to_commitment_artifacts,
 };
 use zksync_types::{
-    aggregated_operations::AggregatedActionType, commitment::L1BatchWithMetadata,
-    protocol_version::ProtocolSemanticVersion, web3::Log, ProtocolVersion, ProtocolVersionId, H256,
-    L2_BRIDGEHUB_ADDRESS,
+    aggregated_operations::AggregatedActionType,
+    commitment::L1BatchWithMetadata,
+    l2_to_l1_log::{L2ToL1Log, SystemL2ToL1Log},
+    protocol_version::ProtocolSemanticVersion,
+    web3::Log,
+    ProtocolVersion, ProtocolVersionId, H256, L2_BRIDGEHUB_ADDRESS,
 };
 
 use super::*;
@@ -852,3 +856,126 @@ async fn checker_detects_incorrect_tx_data_after_snapshot_recovery(
     )
     .await;
 }
+
+fn commitment_token(l1_batch: &L1BatchWithMetadata, mode: L1BatchCommitmentMode) -> Token {
+    CommitBatchInfo::new(mode, l1_batch, PubdataSendingMode::Calldata).into_token()
+}
+
+#[test]
+fn commitment_diff_pinpoints_mismatched_fields_and_system_logs() {
+    let mut l1_batch = create_l1_batch_with_metadata(1);
+    let system_log = L2ToL1Log {
+        shard_id: 0,
+        is_service: true,
+        tx_number_in_block: 0,
+        sender: Address::repeat_byte(0x80),
+        key: H256::from_low_u64_be(1),
+        value: H256::repeat_byte(1),
+    };
+    l1_batch.header.system_logs = vec![SystemL2ToL1Log(system_log.clone()); 2];
+    let reference = commitment_token(&l1_batch, L1BatchCommitmentMode::Rollup);
+
+    l1_batch.header.timestamp += 1;
+    l1_batch.header.system_logs[1].0.value = H256::repeat_byte(2);
+    let local = commitment_token(&l1_batch, L1BatchCommitmentMode::Rollup);
+
+    let diff = CommitmentDiff::new(
+        ProtocolVersionId::latest(),
+        L1BatchCommitmentMode::Rollup,
+        &local,
+        &reference,
+    );
+    let mismatched_fields: Vec<_> = diff.fields.iter().map(|diff| diff.field).collect();
+    assert_eq!(mismatched_fields, ["timestamp", "systemLogs"]);
+    assert_eq!(diff.fields[0].local, "2");
+    assert_eq!(diff.fields[0].reference, "1");
+
+    assert_eq!(diff.system_logs.len(), 1);
+    let log_diff = &diff.system_logs[0];
+    assert_eq!(log_diff.index, 1);
+    assert_eq!(log_diff.local.as_ref().unwrap().value, H256::repeat_byte(2));
+    assert_eq!(log_diff.reference.as_ref(), Some(&system_log));
+    assert!(diff.pubdata_sections.is_empty());
+}
+
+#[test_casing(2, COMMITMENT_MODES)]
+#[test]
+fn commitment_diff_pinpoints_pubdata_sections(commitment_mode: L1BatchCommitmentMode) {
+    let mut l1_batch = create_l1_batch_with_metadata(1);
+    l1_batch.header.pubdata_input = Some(vec![1; 100]);
+    let reference = commitment_token(&l1_batch, commitment_mode);
+
+    l1_batch.metadata.state_diff_hash = Some(H256::repeat_byte(0xff));
+    l1_batch.header.pubdata_input = Some(vec![1; 101]);
+    let local = commitment_token(&l1_batch, commitment_mode);
+
+    let diff = CommitmentDiff::new(
+        ProtocolVersionId::latest(),
+        commitment_mode,
+        &local,
+        &reference,
+    );
+    let mismatched_fields: Vec<_> = diff.fields.iter().map(|diff| diff.field).collect();
+    assert_eq!(mismatched_fields, ["operatorDAInput"]);
+
+    let mismatched_sections: Vec<_> = diff
+        .pubdata_sections
+        .iter()
+        .map(|diff| diff.section)
+        .collect();
+    match commitment_mode {
+        L1BatchCommitmentMode::Rollup => {
+            assert_eq!(
+                mismatched_sections,
+                [
+                    "stateDiffHash",
+                    "fullPubdataHash",
+                    "blobLinearHashes",
+                    "pubdataCommitments"
+                ]
+            );
+            let payload_diff = diff.pubdata_sections.last().unwrap();
+            assert_eq!(payload_diff.local_len, payload_diff.reference_len + 1);
+            assert!(payload_diff.local.is_none());
+        }
+        L1BatchCommitmentMode::Validium => {
+            // Pubdata isn't published for validiums.
+            assert_eq!(mismatched_sections, ["stateDiffHash"]);
+        }
+    }
+    let state_diff_hash_diff = &diff.pubdata_sections[0];
+    assert_eq!(state_diff_hash_diff.first_mismatch_offset, Some(0));
+    assert_eq!(
+        state_diff_hash_diff.local.as_deref(),
+        Some(format!("0x{}", hex::encode([0xff; 32])).as_str())
+    );
+}
+
+#[tokio::test]
+async fn commitment_mismatch_is_reported_in_health_details() {
+    let l1_batch = create_l1_batch_with_metadata(1);
+    let reference = commitment_token(&l1_batch, L1BatchCommitmentMode::Rollup);
+    let mut local_batch = l1_batch.clone();
+    local_batch.header.l1_tx_count += 1;
+    let local = LocalL1BatchCommitData {
+        l1_batch: local_batch,
+        commit_tx_hash: H256::zero(),
+        commit_chain_id: None,
+        commitment_mode: L1BatchCommitmentMode::Rollup,
+    };
+
+    let err = local.verify_commitment(&reference, false).unwrap_err();
+    let mismatch = err.downcast_ref::<CommitmentMismatch>().unwrap();
+    let mismatched_fields: Vec<_> = mismatch.diff.fields.iter().map(|diff| diff.field).collect();
+    assert_eq!(mismatched_fields, ["numberOfLayer1Txs"]);
+
+    let (health_check, mut health_updater) = ConsistencyCheckerHealthUpdater::new();
+    health_updater.report_inconsistent_batch(L1BatchNumber(1), &err);
+    let health = health_check.check_health().await;
+    assert_matches!(health.status(), HealthStatus::Affected);
+    let details = health.details().unwrap();
+    assert_eq!(
+        details["last_commitment_diff"]["fields"][0]["field"],
+        "numberOfLayer1Txs"
+    );
+}
@@ -12,8 +12,8 @@ use zksync_types::{
     bytecode::{trim_bytecode, BytecodeHash, BytecodeMarker},
     contract_verification::{
         api::{
-            CompilerVersions, SourceCodeData, VerificationIncomingRequest, VerificationInfo,
-            VerificationProblem, VerificationRequestStatus,
+            CompilerVersions, SourceCodeData, StandardJsonInputError, VerificationIncomingRequest,
+            VerificationInfo, VerificationProblem, VerificationRequestStatus,
         },
         contract_identifier::ContractIdentifier,
     },
@@ -25,6 +25,7 @@ use super::{api_decl::RestApi, metrics::METRICS};
 #[derive(Debug)]
 pub(crate) enum ApiError {
     IncorrectCompilerVersions,
+    InvalidStandardJsonInput(StandardJsonInputError),
     UnsupportedCompilerVersions,
     MissingZkCompilerVersion,
     BogusZkCompilerVersion,
@@ -52,6 +53,7 @@ impl ApiError {
     pub fn message(&self) -> String {
         match self {
             Self::IncorrectCompilerVersions => "incorrect compiler versions".into(),
+            Self::InvalidStandardJsonInput(err) => format!("invalid standard JSON input: {err}"),
             Self::UnsupportedCompilerVersions => "unsupported compiler versions".into(),
             Self::MissingZkCompilerVersion => {
                 "missing zk compiler version for EraVM bytecode".into()
@@ -73,6 +75,7 @@ impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let status_code = match &self {
             Self::IncorrectCompilerVersions
+            | Self::InvalidStandardJsonInput(_)
             | Self::UnsupportedCompilerVersions
             | Self::MissingZkCompilerVersion
             | Self::BogusZkCompilerVersion
@@ -124,10 +127,13 @@ impl RestApi {
     #[tracing::instrument(skip(self_, request))]
     pub async fn verification(
         State(self_): State<Arc<Self>>,
-        Json(request): Json<VerificationIncomingRequest>,
+        Json(mut request): Json<VerificationIncomingRequest>,
     ) -> ApiResult<usize> {
         let method_latency = METRICS.call[&"contract_verification"].start();
         Self::validate_contract_verification_query(&request)?;
+        request
+            .normalize_standard_json_input()
+            .map_err(ApiError::InvalidStandardJsonInput)?;
 
         let is_compilation_supported = self_
             .supported_compilers
@@ -1,11 +1,14 @@
 //! Tests for contract verification API server.
 
-use std::{str, vec};
+use std::{str, time::Duration, vec};
 
 use test_casing::test_casing;
 use utils::{mock_verification_info, MockApiClient, MockContractVerifier};
+use zksync_dal::CoreDal;
 use zksync_types::{
-    bytecode::BytecodeMarker, contract_verification::api::VerificationProblem, Address,
+    bytecode::BytecodeMarker,
+    contract_verification::api::{SourceCodeData, StandardJsonInputError, VerificationProblem},
+    Address,
 };
 
 use super::*;
@@ -80,7 +83,7 @@ async fn submitting_request(bytecode_kind: BytecodeMarker) {
     contract_verifier.verify_contract(verification_info).await;
 
     let status = client.verification_status(id).await;
-    assert_eq!(status.status, "successful");
+    assert_eq!(status.status, "partial_match");
 
     // We should be able to fetch verification info
     let info = client.verification_info(address).await;
@@ -253,6 +256,68 @@ async fn submitting_request_with_unsupported_zksolc() {
         .await;
 }
 
+#[tokio::test]
+async fn submitting_standard_json_request() {
+    let pool = ConnectionPool::test_pool().await;
+    let client = MockApiClient::new(pool.clone());
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+
+    let address = Address::repeat_byte(0x23);
+    mock_deploy_contract(&mut storage, address, BytecodeMarker::EraVm).await;
+
+    let mut verification_request = serde_json::json!({
+        "contractAddress": address,
+        "codeFormat": "solidity-standard-json-input",
+        "sourceCode": {
+            "language": "Solidity",
+            "sources": {
+                "contracts/Test.sol": { "content": "import \"lib/Lib.sol\"; contract Test {}" },
+                "lib/Lib.sol": { "content": "library Lib {}" },
+            },
+            "settings": {
+                "remappings": ["@lib/=lib/"],
+                "viaIR": true,
+                "outputSelection": { "*": { "*": ["abi"] } },
+            },
+        },
+        "contractName": "Missing",
+        "compilerZksolcVersion": ZKSOLC_VERSION,
+        "compilerSolcVersion": SOLC_VERSION,
+        "optimizationUsed": true,
+    });
+    client
+        .assert_verification_request_error(
+            &verification_request,
+            ApiError::InvalidStandardJsonInput(StandardJsonInputError::AmbiguousContractName(
+                "Missing".to_owned(),
+            )),
+        )
+        .await;
+
+    verification_request["contractName"] = "Test".into();
+    let id = client
+        .send_verification_request(&verification_request)
+        .await;
+
+    // The normalized input should be stored.
+    let request = storage
+        .contract_verification_dal()
+        .get_next_queued_verification_request(Duration::from_secs(600))
+        .await
+        .unwrap()
+        .expect("no request");
+    assert_eq!(request.id, id);
+    assert_eq!(request.req.contract_name, "contracts/Test.sol:Test");
+    let SourceCodeData::StandardJsonInput(input) = &request.req.source_code_data else {
+        panic!("unexpected source code: {:?}", request.req.source_code_data);
+    };
+    assert_eq!(
+        input["settings"],
+        serde_json::json!({ "remappings": ["@lib/=lib/"], "viaIR": true })
+    );
+}
+
 #[tokio::test]
 async fn querying_missing_request() {
     let pool = ConnectionPool::test_pool().await;
@@ -0,0 +1,228 @@
+//! Cold storage component moving old call traces and events from Postgres to an object store.
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_object_store::ObjectStore;
+use zksync_types::{
+    cold_storage::{CallTracesArchive, ColdStorageDataKind, EventsArchive},
+    L1BatchNumber,
+};
+
+use crate::{metrics::COLD_STORAGE_METRICS, prune_conditions::is_l1_batch_data_removable};
+
+/// Maximum number of L1 batches archived for a single data kind during an iteration.
+const MAX_L1_BATCHES_PER_ITERATION: u32 = 100;
+
+/// Configuration of [`ColdStorageArchiver`].
+#[derive(Debug, Clone)]
+pub struct ColdStorageConfig {
+    /// Minimum age of an L1 batch (measured from its timestamp) for its data to be moved to cold storage.
+    /// Additionally, data is only moved after the L1 batch has its commitment generated, is executed on L1,
+    /// and is not referenced by incomplete snapshots.
+    pub archive_after: Duration,
+    /// Data kinds moved to cold storage.
+    pub data_kinds: Vec<ColdStorageDataKind>,
+    /// Interval between iterations if the previous iteration didn't archive anything.
+    pub poll_interval: Duration,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct ColdStorageHealth {
+    pub(crate) last_archived_l1_batches: HashMap<&'static str, L1BatchNumber>,
+}
+
+/// Moves call traces and events for old L1 batches to an object store, one L1 batch at a time. Archives
+/// are stored in the Parquet format.
+///
+/// Unlike [`DataRetentionManager`](crate::DataRetentionManager), data is not lost: the payload is uploaded
+/// to the object store before Postgres rows are stubbed, and the API server transparently fetches archived data
+/// on demand. Rows are retained so that filtering (e.g., by event topics) is still performed by Postgres.
+#[derive(Debug)]
+pub struct ColdStorageArchiver {
+    config: ColdStorageConfig,
+    pool: ConnectionPool<Core>,
+    object_store: Arc<dyn ObjectStore>,
+    health_updater: HealthUpdater,
+}
+
+impl ColdStorageArchiver {
+    pub fn new(
+        config: ColdStorageConfig,
+        pool: ConnectionPool<Core>,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> Self {
+        Self {
+            config,
+            pool,
+            object_store,
+            health_updater: ReactiveHealthCheck::new("cold_storage").1,
+        }
+    }
+
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    async fn next_l1_batch_to_archive(
+        storage: &mut Connection<'_, Core>,
+        kind: ColdStorageDataKind,
+    ) -> anyhow::Result<Option<L1BatchNumber>> {
+        let Some(earliest_l1_batch) = storage.blocks_dal().get_earliest_l1_batch_number().await?
+        else {
+            return Ok(None);
+        };
+        let last_archived = storage
+            .cold_storage_dal()
+            .get_last_archived_l1_batch(kind)
+            .await?;
+        Ok(Some(last_archived.map_or(earliest_l1_batch, |number| {
+            (number + 1).max(earliest_l1_batch)
+        })))
+    }
+
+    async fn archive_l1_batch(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        kind: ColdStorageDataKind,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        let l2_block_range = storage
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(l1_batch_number)
+            .await?
+            .with_context(|| format!("L1 batch #{l1_batch_number} has no L2 blocks"))?;
+        let l2_block_range = l2_block_range.0..=l2_block_range.1;
+
+        // The object is uploaded before stubbing Postgres rows, so that data is never lost. If the node stops
+        // in between, the object will be overwritten on the next attempt.
+        let mut dal = storage.cold_storage_dal();
+        let (object_key, archived_count) = match kind {
+            ColdStorageDataKind::CallTraces => {
+                let call_traces = dal
+                    .get_call_traces_for_archive(l2_block_range.clone())
+                    .await?;
+                let archived_count = call_traces.len();
+                let archive = CallTracesArchive {
+                    l1_batch_number,
+                    call_traces,
+                };
+                let key = self.object_store.put(l1_batch_number, &archive).await?;
+                (key, archived_count)
+            }
+            ColdStorageDataKind::Events => {
+                let events = dal.get_events_for_archive(l2_block_range.clone()).await?;
+                let archived_count = events.len();
+                let archive = EventsArchive {
+                    l1_batch_number,
+                    events,
+                };
+                let key = self.object_store.put(l1_batch_number, &archive).await?;
+                (key, archived_count)
+            }
+        };
+
+        let mut transaction = storage.start_transaction().await?;
+        transaction
+            .cold_storage_dal()
+            .stub_archived_data(kind, l2_block_range)
+            .await?;
+        transaction
+            .cold_storage_dal()
+            .insert_archive(kind, l1_batch_number, &object_key, archived_count)
+            .await?;
+        transaction.commit().await?;
+
+        tracing::debug!(
+            "Moved {archived_count} entries of {kind:?} for L1 batch #{l1_batch_number} to cold storage (`{object_key}`)"
+        );
+        COLD_STORAGE_METRICS.observe_archiving(kind, l1_batch_number, archived_count);
+        Ok(())
+    }
+
+    /// Returns the number of L1 batches processed across all data kinds.
+    pub(crate) async fn run_single_iteration(
+        &self,
+        stop_receiver: &watch::Receiver<bool>,
+        health: &mut ColdStorageHealth,
+    ) -> anyhow::Result<u32> {
+        let mut storage = self.pool.connection_tagged("cold_storage").await?;
+        let mut processed_l1_batches = 0;
+        for &kind in &self.config.data_kinds {
+            let Some(mut next_l1_batch) =
+                Self::next_l1_batch_to_archive(&mut storage, kind).await?
+            else {
+                continue;
+            };
+
+            for _ in 0..MAX_L1_BATCHES_PER_ITERATION {
+                if *stop_receiver.borrow() {
+                    return Ok(processed_l1_batches);
+                }
+                // Stubbing removes data read by the commitment generator, so the batch must be fully processed first.
+                if !is_l1_batch_data_removable(
+                    &mut storage,
+                    self.config.archive_after,
+                    next_l1_batch,
+                )
+                .await?
+                {
+                    break;
+                }
+                self.archive_l1_batch(&mut storage, kind, next_l1_batch)
+                    .await?;
+                health
+                    .last_archived_l1_batches
+                    .insert(kind.as_str(), next_l1_batch);
+                processed_l1_batches += 1;
+                next_l1_batch += 1;
+            }
+        }
+        Ok(processed_l1_batches)
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting cold storage archiver with configuration {:?}",
+            self.config
+        );
+        let mut health = ColdStorageHealth::default();
+
+        while !*stop_receiver.borrow_and_update() {
+            let should_sleep = match self.run_single_iteration(&stop_receiver, &mut health).await {
+                Ok(processed_l1_batches) => {
+                    self.health_updater
+                        .update(Health::from(HealthStatus::Ready).with_details(&health));
+                    processed_l1_batches == 0
+                }
+                Err(err) => {
+                    // Similarly to the DB pruner, errors are not fatal.
+                    tracing::warn!(
+                        "Cold storage error, retrying in {:?}, error was: {err:?}",
+                        self.config.poll_interval
+                    );
+                    let health =
+                        Health::from(HealthStatus::Affected).with_details(serde_json::json!({
+                            "error": err.to_string(),
+                        }));
+                    self.health_updater.update(health);
+                    true
+                }
+            };
+
+            if should_sleep
+                && tokio::time::timeout(self.config.poll_interval, stop_receiver.changed())
+                    .await
+                    .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, shutting down cold storage archiver");
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 //! Postgres pruning component.
 
 use std::{
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 
@@ -15,16 +15,23 @@ use zksync_dal::{
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_types::{L1BatchNumber, L2BlockNumber};
 
+pub use self::{
+    cold_storage::{ColdStorageArchiver, ColdStorageConfig},
+    retention::{DataRetentionConfig, DataRetentionManager},
+};
 use self::{
     metrics::{ConditionOutcome, PruneType, METRICS},
     prune_conditions::{
-        ConsistencyCheckerProcessedBatch, L1BatchExistsCondition, L1BatchOlderThanPruneCondition,
+        ConsistencyCheckerProcessedBatch, L1BatchExistsCondition,
+        L1BatchNotReferencedBySnapshotsCondition, L1BatchOlderThanPruneCondition,
         NextL1BatchHasMetadataCondition, NextL1BatchWasExecutedCondition, PruneCondition,
     },
 };
 
+mod cold_storage;
 mod metrics;
 mod prune_conditions;
+mod retention;
 #[cfg(test)]
 mod tests;
 
@@ -39,6 +46,31 @@ pub struct DbPrunerConfig {
     /// Minimum age of an L1 batch in order for it to be eligible for pruning. Setting this to zero
     /// will effectively disable this pruning criterion.
     pub minimum_l1_batch_age: Duration,
+    /// If set, the pruner only evaluates prune conditions and reports what would be pruned
+    /// (via logs and the health check) without modifying Postgres.
+    pub dry_run: bool,
+}
+
+/// Outcome of evaluating prune conditions for a single L1 batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PruneConditionsReport {
+    l1_batch: L1BatchNumber,
+    successful_conditions: Vec<String>,
+    failed_conditions: Vec<String>,
+    errored_conditions: Vec<String>,
+}
+
+impl PruneConditionsReport {
+    fn is_prunable(&self) -> bool {
+        self.failed_conditions.is_empty() && self.errored_conditions.is_empty()
+    }
+}
+
+/// Pruning progress simulated by the pruner in the dry-run mode.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct DryRunPruningInfo {
+    l1_batch: L1BatchNumber,
+    l2_block: L2BlockNumber,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -51,6 +83,12 @@ struct DbPrunerHealth {
     last_hard_pruned_l1_batch: Option<L1BatchNumber>,
     #[serde(skip_serializing_if = "Option::is_none")]
     last_hard_pruned_l2_block: Option<L2BlockNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_conditions_report: Option<PruneConditionsReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dry_run_pruned_l1_batch: Option<L1BatchNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dry_run_pruned_l2_block: Option<L2BlockNumber>,
 }
 
 impl From<PruningInfo> for DbPrunerHealth {
@@ -60,6 +98,9 @@ impl From<PruningInfo> for DbPrunerHealth {
             last_soft_pruned_l2_block: info.last_soft_pruned.map(|info| info.l2_block),
             last_hard_pruned_l1_batch: info.last_hard_pruned.map(|info| info.l1_batch),
             last_hard_pruned_l2_block: info.last_hard_pruned.map(|info| info.l2_block),
+            last_conditions_report: None,
+            dry_run_pruned_l1_batch: None,
+            dry_run_pruned_l2_block: None,
         }
     }
 }
@@ -82,6 +123,8 @@ pub struct DbPruner {
     connection_pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
     prune_conditions: Vec<Arc<dyn PruneCondition>>,
+    last_conditions_report: Mutex<Option<PruneConditionsReport>>,
+    dry_run_progress: Mutex<Option<DryRunPruningInfo>>,
 }
 
 impl DbPruner {
@@ -99,6 +142,9 @@ impl DbPruner {
             Arc::new(ConsistencyCheckerProcessedBatch {
                 pool: connection_pool.clone(),
             }),
+            Arc::new(L1BatchNotReferencedBySnapshotsCondition {
+                pool: connection_pool.clone(),
+            }),
         ];
         if config.minimum_l1_batch_age > Duration::ZERO {
             // Do not add a condition if it's trivial in order to not clutter logs.
@@ -121,6 +167,8 @@ impl DbPruner {
             connection_pool,
             health_updater: ReactiveHealthCheck::new("db_pruner").1,
             prune_conditions,
+            last_conditions_report: Mutex::new(None),
+            dry_run_progress: Mutex::new(None),
         }
     }
 
@@ -129,6 +177,25 @@ impl DbPruner {
     }
 
     async fn is_l1_batch_prunable(&self, l1_batch_number: L1BatchNumber) -> bool {
+        let report = self.check_prune_conditions(l1_batch_number).await;
+        let result = report.is_prunable();
+        if !result {
+            tracing::debug!(
+                "Pruning L1 batch {l1_batch_number} is not possible, \
+                 successful conditions: {:?}, failed conditions: {:?}, errored conditions: {:?}",
+                report.successful_conditions,
+                report.failed_conditions,
+                report.errored_conditions
+            );
+        }
+        *self.last_conditions_report.lock().unwrap() = Some(report);
+        result
+    }
+
+    async fn check_prune_conditions(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> PruneConditionsReport {
         let mut successful_conditions = vec![];
         let mut failed_conditions = vec![];
         let mut errored_conditions = vec![];
@@ -152,16 +219,12 @@ impl DbPruner {
             METRICS.observe_condition(condition.as_ref(), outcome);
         }
 
-        let result = failed_conditions.is_empty() && errored_conditions.is_empty();
-        if !result {
-            tracing::debug!(
-                "Pruning L1 batch {l1_batch_number} is not possible, \
-                 successful conditions: {successful_conditions:?}, \
-                 failed conditions: {failed_conditions:?}, \
-                 errored conditions: {errored_conditions:?}"
-            );
+        PruneConditionsReport {
+            l1_batch: l1_batch_number,
+            successful_conditions,
+            failed_conditions,
+            errored_conditions,
         }
-        result
     }
 
     async fn update_l1_batches_metric(&self) -> anyhow::Result<()> {
@@ -182,10 +245,51 @@ impl DbPruner {
     }
 
     fn update_health(&self, info: PruningInfo) {
-        let health = Health::from(HealthStatus::Ready).with_details(DbPrunerHealth::from(info));
+        let mut details = DbPrunerHealth::from(info);
+        details.last_conditions_report = self.last_conditions_report.lock().unwrap().clone();
+        if let Some(dry_run_progress) = *self.dry_run_progress.lock().unwrap() {
+            details.dry_run_pruned_l1_batch = Some(dry_run_progress.l1_batch);
+            details.dry_run_pruned_l2_block = Some(dry_run_progress.l2_block);
+        }
+        let health = Health::from(HealthStatus::Ready).with_details(details);
         self.health_updater.update(health);
     }
 
+    /// Simulates soft pruning without modifying Postgres. The simulated progress starts from the actual
+    /// soft pruning info and is kept in memory only.
+    async fn dry_run_soft_prune(&self, storage: &mut Connection<'_, Core>) -> anyhow::Result<bool> {
+        let current_pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        let last_pruned_l1_batch = self
+            .dry_run_progress
+            .lock()
+            .unwrap()
+            .map(|info| info.l1_batch)
+            .or(current_pruning_info
+                .last_soft_pruned
+                .map(|info| info.l1_batch));
+        let next_l1_batch_to_prune =
+            last_pruned_l1_batch.unwrap_or(L1BatchNumber(0)) + self.config.pruned_batch_chunk_size;
+        if !self.is_l1_batch_prunable(next_l1_batch_to_prune).await {
+            self.update_health(current_pruning_info);
+            return Ok(false);
+        }
+
+        let (_, next_l2_block_to_prune) = storage
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(next_l1_batch_to_prune)
+            .await?
+            .with_context(|| format!("L1 batch #{next_l1_batch_to_prune} is ready to be pruned, but has no L2 blocks"))?;
+        tracing::info!(
+            "Dry run: would prune data up to L1 batch #{next_l1_batch_to_prune} / L2 block #{next_l2_block_to_prune}"
+        );
+        *self.dry_run_progress.lock().unwrap() = Some(DryRunPruningInfo {
+            l1_batch: next_l1_batch_to_prune,
+            l2_block: next_l2_block_to_prune,
+        });
+        self.update_health(current_pruning_info);
+        Ok(true)
+    }
+
     async fn soft_prune(&self, storage: &mut Connection<'_, Core>) -> anyhow::Result<bool> {
         let start = Instant::now();
         let mut transaction = storage.start_transaction().await?;
@@ -292,6 +396,15 @@ impl DbPruner {
         stop_receiver: &mut watch::Receiver<bool>,
     ) -> anyhow::Result<PruningIterationOutcome> {
         let mut storage = self.connection_pool.connection_tagged("db_pruner").await?;
+        if self.config.dry_run {
+            let would_prune = self.dry_run_soft_prune(&mut storage).await?;
+            return Ok(if would_prune {
+                PruningIterationOutcome::Pruned
+            } else {
+                PruningIterationOutcome::NoOp
+            });
+        }
+
         let current_pruning_info = storage.pruning_dal().get_pruning_info().await?;
         self.update_health(current_pruning_info);
 
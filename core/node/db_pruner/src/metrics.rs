@@ -3,7 +3,8 @@ use std::time::Duration;
 use vise::{
     Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
 };
-use zksync_dal::pruning_dal::HardPruningStats;
+use zksync_dal::pruning_dal::{HardPruningStats, RetainedDataKind};
+use zksync_types::{cold_storage::ColdStorageDataKind, L1BatchNumber};
 
 use crate::prune_conditions::PruneCondition;
 
@@ -24,6 +25,7 @@ enum PrunedEntityType {
     Event,
     L2ToL1Log,
     CallTrace,
+    SoftConfirmation,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
@@ -69,11 +71,13 @@ impl DbPrunerMetrics {
             deleted_events,
             deleted_call_traces,
             deleted_l2_to_l1_logs,
+            deleted_soft_confirmations,
         } = stats;
         tracing::info!(
             "Performed pruning of database, deleted {deleted_l1_batches} L1 batches, {deleted_l2_blocks} L2 blocks, \
              {deleted_storage_logs} storage logs, \
-             {deleted_events} events, {deleted_call_traces} call traces, {deleted_l2_to_l1_logs} L2-to-L1 logs"
+             {deleted_events} events, {deleted_call_traces} call traces, {deleted_l2_to_l1_logs} L2-to-L1 logs, \
+             {deleted_soft_confirmations} soft confirmations"
         );
 
         self.deleted_entities[&PrunedEntityType::L1Batch].observe(deleted_l1_batches);
@@ -82,6 +86,8 @@ impl DbPrunerMetrics {
         self.deleted_entities[&PrunedEntityType::Event].observe(deleted_events);
         self.deleted_entities[&PrunedEntityType::L2ToL1Log].observe(deleted_l2_to_l1_logs);
         self.deleted_entities[&PrunedEntityType::CallTrace].observe(deleted_call_traces);
+        self.deleted_entities[&PrunedEntityType::SoftConfirmation]
+            .observe(deleted_soft_confirmations);
     }
 
     pub fn observe_condition(&self, condition: &dyn PruneCondition, outcome: ConditionOutcome) {
@@ -95,3 +101,70 @@ impl DbPrunerMetrics {
 
 #[vise::register]
 pub(super) static METRICS: vise::Global<DbPrunerMetrics> = vise::Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
+struct RetainedDataLabels {
+    kind: &'static str,
+}
+
+impl From<RetainedDataKind> for RetainedDataLabels {
+    fn from(kind: RetainedDataKind) -> Self {
+        Self {
+            kind: kind.as_str(),
+        }
+    }
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "data_retention")]
+pub(super) struct DataRetentionMetrics {
+    /// Number of entities removed for a single L1 batch, grouped by data kind.
+    #[metrics(buckets = ENTITY_COUNT_BUCKETS)]
+    pruned_entities: Family<RetainedDataLabels, Histogram<u64>>,
+    /// Last L1 batch for which data was removed, grouped by data kind.
+    last_pruned_l1_batch: Family<RetainedDataLabels, Gauge<u64>>,
+}
+
+impl DataRetentionMetrics {
+    pub fn observe_pruning(
+        &self,
+        kind: RetainedDataKind,
+        l1_batch_number: L1BatchNumber,
+        pruned_count: u64,
+    ) {
+        let labels = RetainedDataLabels::from(kind);
+        self.pruned_entities[&labels].observe(pruned_count);
+        self.last_pruned_l1_batch[&labels].set(l1_batch_number.0.into());
+    }
+}
+
+#[vise::register]
+pub(super) static RETENTION_METRICS: vise::Global<DataRetentionMetrics> = vise::Global::new();
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "cold_storage")]
+pub(super) struct ColdStorageMetrics {
+    /// Number of entities moved to cold storage for a single L1 batch, grouped by data kind.
+    #[metrics(buckets = ENTITY_COUNT_BUCKETS)]
+    archived_entities: Family<RetainedDataLabels, Histogram<u64>>,
+    /// Last L1 batch for which data was moved to cold storage, grouped by data kind.
+    last_archived_l1_batch: Family<RetainedDataLabels, Gauge<u64>>,
+}
+
+impl ColdStorageMetrics {
+    pub fn observe_archiving(
+        &self,
+        kind: ColdStorageDataKind,
+        l1_batch_number: L1BatchNumber,
+        archived_count: usize,
+    ) {
+        let labels = RetainedDataLabels {
+            kind: kind.as_str(),
+        };
+        self.archived_entities[&labels].observe(archived_count as u64);
+        self.last_archived_l1_batch[&labels].set(l1_batch_number.0.into());
+    }
+}
+
+#[vise::register]
+pub(super) static COLD_STORAGE_METRICS: vise::Global<ColdStorageMetrics> = vise::Global::new();
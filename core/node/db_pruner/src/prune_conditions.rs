@@ -2,7 +2,7 @@ use std::{fmt, time::Duration};
 
 use async_trait::async_trait;
 use chrono::Utc;
-use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_types::L1BatchNumber;
 
 #[async_trait]
@@ -12,6 +12,51 @@ pub(crate) trait PruneCondition: fmt::Debug + fmt::Display + Send + Sync + 'stat
     async fn is_batch_prunable(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<bool>;
 }
 
+/// Checks whether call traces and events for the specified L1 batch can be removed from Postgres (or replaced
+/// with stubs). Besides the batch age, requires all components reading this data from Postgres to have processed
+/// the batch:
+///
+/// - The commitment generator reads VM events of the batch.
+/// - The batch must be executed on L1 so that it cannot be reverted and re-executed.
+/// - Incomplete snapshots must not reference the batch.
+pub(crate) async fn is_l1_batch_data_removable(
+    storage: &mut Connection<'_, Core>,
+    minimum_age: Duration,
+    l1_batch_number: L1BatchNumber,
+) -> anyhow::Result<bool> {
+    let Some(header) = storage
+        .blocks_dal()
+        .get_l1_batch_header(l1_batch_number)
+        .await?
+    else {
+        return Ok(false);
+    };
+    let age_sec = (Utc::now().timestamp() as u64).saturating_sub(header.timestamp);
+    if age_sec < minimum_age.as_secs() {
+        return Ok(false);
+    }
+
+    if !storage
+        .blocks_dal()
+        .has_l1_batch_commitment(l1_batch_number)
+        .await?
+    {
+        return Ok(false);
+    }
+    let last_executed_l1_batch = storage
+        .blocks_dal()
+        .get_number_of_last_l1_batch_executed_on_eth()
+        .await?;
+    if last_executed_l1_batch.map_or(true, |executed| executed < l1_batch_number) {
+        return Ok(false);
+    }
+    let earliest_referenced_l1_batch = storage
+        .snapshots_dal()
+        .get_earliest_l1_batch_referenced_by_incomplete_snapshots()
+        .await?;
+    Ok(earliest_referenced_l1_batch.map_or(true, |referenced| l1_batch_number < referenced))
+}
+
 #[derive(Debug)]
 pub(super) struct L1BatchOlderThanPruneCondition {
     pub minimum_age: Duration,
@@ -171,3 +216,31 @@ impl PruneCondition for ConsistencyCheckerProcessedBatch {
         Ok(l1_batch_number <= last_processed_l1_batch)
     }
 }
+
+#[derive(Debug)]
+pub(super) struct L1BatchNotReferencedBySnapshotsCondition {
+    pub pool: ConnectionPool<Core>,
+}
+
+impl fmt::Display for L1BatchNotReferencedBySnapshotsCondition {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("L1 batch is not referenced by incomplete snapshots")
+    }
+}
+
+#[async_trait]
+impl PruneCondition for L1BatchNotReferencedBySnapshotsCondition {
+    fn metric_label(&self) -> &'static str {
+        "l1_batch_not_referenced_by_snapshots"
+    }
+
+    async fn is_batch_prunable(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<bool> {
+        let mut storage = self.pool.connection_tagged("db_pruner").await?;
+        let earliest_referenced_l1_batch = storage
+            .snapshots_dal()
+            .get_earliest_l1_batch_referenced_by_incomplete_snapshots()
+            .await?;
+        // A snapshot for L1 batch N reads storage logs up to and including N, so pruning must stop strictly before it.
+        Ok(earliest_referenced_l1_batch.map_or(true, |referenced| l1_batch_number < referenced))
+    }
+}
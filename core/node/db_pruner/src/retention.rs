@@ -0,0 +1,180 @@
+//! Data retention component removing old call traces and events from Postgres.
+
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::Context as _;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_dal::{pruning_dal::RetainedDataKind, Connection, ConnectionPool, Core, CoreDal};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_types::L1BatchNumber;
+
+use crate::{metrics::RETENTION_METRICS, prune_conditions::is_l1_batch_data_removable};
+
+/// Maximum number of L1 batches processed for a single data kind during an iteration. Limits the time
+/// other data kinds need to wait if there's a large backlog for one of them.
+const MAX_L1_BATCHES_PER_ITERATION: u32 = 100;
+
+/// Configuration of [`DataRetentionManager`].
+#[derive(Debug, Clone)]
+pub struct DataRetentionConfig {
+    /// Retention periods for each kind of data, measured from the timestamp of the containing L1 batch.
+    /// Data kinds missing from this map are retained indefinitely. Regardless of the retention period, data is only
+    /// removed after the L1 batch has its commitment generated, is executed on L1, and is not referenced
+    /// by incomplete snapshots.
+    pub retention_periods: HashMap<RetainedDataKind, Duration>,
+    /// Interval between iterations if the previous iteration didn't prune anything.
+    pub poll_interval: Duration,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct DataRetentionHealth {
+    pub(crate) last_pruned_l1_batches: HashMap<&'static str, L1BatchNumber>,
+}
+
+/// Removes data from Postgres according to per-kind retention periods.
+///
+/// Unlike [`DbPruner`](crate::DbPruner), the manager doesn't remove L1 batches or L2 blocks, so the node
+/// retains its full block history. Data is removed one L1 batch at a time; progress is persisted for each data kind
+/// separately, so that retention periods can be changed between node restarts.
+#[derive(Debug)]
+pub struct DataRetentionManager {
+    config: DataRetentionConfig,
+    pool: ConnectionPool<Core>,
+    health_updater: HealthUpdater,
+}
+
+impl DataRetentionManager {
+    pub fn new(config: DataRetentionConfig, pool: ConnectionPool<Core>) -> Self {
+        Self {
+            config,
+            pool,
+            health_updater: ReactiveHealthCheck::new("data_retention").1,
+        }
+    }
+
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    async fn next_l1_batch_to_prune(
+        storage: &mut Connection<'_, Core>,
+        kind: RetainedDataKind,
+    ) -> anyhow::Result<Option<L1BatchNumber>> {
+        let Some(earliest_l1_batch) = storage.blocks_dal().get_earliest_l1_batch_number().await?
+        else {
+            return Ok(None);
+        };
+        let last_pruned = storage
+            .pruning_dal()
+            .get_last_retention_pruned_l1_batch(kind)
+            .await?;
+        // The earliest L1 batch may be greater than the last pruned one if the node is pruned or was recovered from a snapshot.
+        Ok(Some(last_pruned.map_or(earliest_l1_batch, |number| {
+            (number + 1).max(earliest_l1_batch)
+        })))
+    }
+
+    async fn prune_l1_batch(
+        storage: &mut Connection<'_, Core>,
+        kind: RetainedDataKind,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        let mut transaction = storage.start_transaction().await?;
+        let l2_block_range = transaction
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(l1_batch_number)
+            .await?
+            .with_context(|| format!("L1 batch #{l1_batch_number} has no L2 blocks"))?;
+        let pruned_count = transaction
+            .pruning_dal()
+            .prune_retained_data(kind, l2_block_range.0..=l2_block_range.1)
+            .await?;
+        transaction
+            .pruning_dal()
+            .insert_retention_log(kind, l1_batch_number)
+            .await?;
+        transaction.commit().await?;
+
+        tracing::debug!(
+            "Removed {pruned_count} entries of {kind:?} for L1 batch #{l1_batch_number}"
+        );
+        RETENTION_METRICS.observe_pruning(kind, l1_batch_number, pruned_count);
+        Ok(())
+    }
+
+    /// Returns the number of L1 batches processed across all data kinds.
+    pub(crate) async fn run_single_iteration(
+        &self,
+        stop_receiver: &watch::Receiver<bool>,
+        health: &mut DataRetentionHealth,
+    ) -> anyhow::Result<u32> {
+        let mut storage = self.pool.connection_tagged("data_retention").await?;
+        let mut processed_l1_batches = 0;
+        for (&kind, &retention_period) in &self.config.retention_periods {
+            let Some(mut next_l1_batch) = Self::next_l1_batch_to_prune(&mut storage, kind).await?
+            else {
+                continue;
+            };
+
+            for _ in 0..MAX_L1_BATCHES_PER_ITERATION {
+                if *stop_receiver.borrow() {
+                    return Ok(processed_l1_batches);
+                }
+                if !is_l1_batch_data_removable(&mut storage, retention_period, next_l1_batch)
+                    .await?
+                {
+                    break;
+                }
+                Self::prune_l1_batch(&mut storage, kind, next_l1_batch).await?;
+                health
+                    .last_pruned_l1_batches
+                    .insert(kind.as_str(), next_l1_batch);
+                processed_l1_batches += 1;
+                next_l1_batch += 1;
+            }
+        }
+        Ok(processed_l1_batches)
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting data retention with configuration {:?}",
+            self.config
+        );
+        let mut health = DataRetentionHealth::default();
+
+        while !*stop_receiver.borrow_and_update() {
+            let should_sleep = match self.run_single_iteration(&stop_receiver, &mut health).await {
+                Ok(processed_l1_batches) => {
+                    self.health_updater
+                        .update(Health::from(HealthStatus::Ready).with_details(&health));
+                    processed_l1_batches == 0
+                }
+                Err(err) => {
+                    // Similarly to the DB pruner, errors are not fatal.
+                    tracing::warn!(
+                        "Data retention error, retrying in {:?}, error was: {err:?}",
+                        self.config.poll_interval
+                    );
+                    let health =
+                        Health::from(HealthStatus::Affected).with_details(serde_json::json!({
+                            "error": err.to_string(),
+                        }));
+                    self.health_updater.update(health);
+                    true
+                }
+            };
+
+            if should_sleep
+                && tokio::time::timeout(self.config.poll_interval, stop_receiver.changed())
+                    .await
+                    .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, shutting down data retention");
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ use std::{collections::HashMap, fmt};
 use assert_matches::assert_matches;
 use async_trait::async_trait;
 use test_log::test;
-use zksync_dal::pruning_dal::PruningInfo;
+use zksync_dal::pruning_dal::{PruningInfo, RetainedDataKind};
 use zksync_db_connection::connection::Connection;
 use zksync_health_check::CheckHealth;
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
@@ -11,8 +11,12 @@ use zksync_node_test_utils::{
     create_l1_batch, create_l1_batch_metadata, create_l2_block,
     l1_batch_metadata_to_commitment_artifacts,
 };
+use zksync_object_store::MockObjectStore;
 use zksync_types::{
-    aggregated_operations::AggregatedActionType, L2BlockNumber, ProtocolVersion, H256,
+    aggregated_operations::AggregatedActionType,
+    cold_storage::{CallTracesArchive, ColdStorageDataKind, EventsArchive},
+    snapshots::SnapshotVersion,
+    L2BlockNumber, ProtocolVersion, H256,
 };
 
 use super::*;
@@ -78,6 +82,7 @@ async fn is_l1_batch_prunable_works() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 1,
             minimum_l1_batch_age: Duration::ZERO,
+            dry_run: false,
         },
         ConnectionPool::test_pool().await,
         vec![failing_check, other_failing_check],
@@ -148,6 +153,7 @@ async fn hard_pruning_ignores_conditions_checks() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 5,
             minimum_l1_batch_age: Duration::ZERO,
+            dry_run: false,
         },
         pool.clone(),
         vec![nothing_prunable_check],
@@ -197,6 +203,7 @@ async fn pruner_catches_up_with_hard_pruning_up_to_soft_pruning_boundary_ignorin
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 5,
             minimum_l1_batch_age: Duration::ZERO,
+            dry_run: false,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -235,6 +242,7 @@ async fn unconstrained_pruner_with_fresh_database() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            dry_run: false,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -275,6 +283,7 @@ async fn pruning_blocked_after_first_chunk() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            dry_run: false,
         },
         pool.clone(),
         vec![first_chunk_prunable_check],
@@ -315,6 +324,7 @@ async fn pruner_is_resistant_to_errors() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            dry_run: false,
         },
         pool.clone(),
         vec![erroneous_condition],
@@ -473,6 +483,92 @@ async fn real_conditions_work_as_expected() {
     );
 }
 
+#[tokio::test]
+async fn snapshot_condition_works_as_expected() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let condition = L1BatchNotReferencedBySnapshotsCondition { pool: pool.clone() };
+    assert!(condition
+        .is_batch_prunable(L1BatchNumber(10))
+        .await
+        .unwrap());
+
+    storage
+        .snapshots_dal()
+        .add_snapshot(SnapshotVersion::Version0, L1BatchNumber(3), 1, "deps")
+        .await
+        .unwrap();
+    assert!(condition.is_batch_prunable(L1BatchNumber(2)).await.unwrap());
+    assert!(!condition.is_batch_prunable(L1BatchNumber(3)).await.unwrap());
+    assert!(!condition
+        .is_batch_prunable(L1BatchNumber(10))
+        .await
+        .unwrap());
+
+    // Complete snapshots must not block pruning.
+    storage
+        .snapshots_dal()
+        .add_storage_logs_filepath_for_snapshot(L1BatchNumber(3), 0, "logs")
+        .await
+        .unwrap();
+    assert!(condition
+        .is_batch_prunable(L1BatchNumber(10))
+        .await
+        .unwrap());
+}
+
+#[test(tokio::test)]
+async fn dry_run_pruning_does_not_modify_database() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_l2_blocks(&mut conn, 10, 2).await;
+
+    let condition = Arc::new(
+        ConditionMock::name("some passing")
+            .with_response(L1BatchNumber(3), true)
+            .with_response(L1BatchNumber(6), false),
+    );
+    let pruner = DbPruner::with_conditions(
+        DbPrunerConfig {
+            removal_delay: Duration::ZERO,
+            pruned_batch_chunk_size: 3,
+            minimum_l1_batch_age: Duration::ZERO,
+            dry_run: true,
+        },
+        pool.clone(),
+        vec![condition],
+    );
+    let health_check = pruner.health_check();
+
+    let (_stop_sender, mut stop_receiver) = watch::channel(false);
+    let outcome = pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_matches!(outcome, PruningIterationOutcome::Pruned);
+    let outcome = pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_matches!(outcome, PruningIterationOutcome::NoOp);
+
+    assert_eq!(
+        conn.pruning_dal().get_pruning_info().await.unwrap(),
+        PruningInfo::default()
+    );
+    let health = health_check.check_health().await;
+    assert_matches!(health.status(), HealthStatus::Ready);
+    let details: DbPrunerHealth =
+        serde_json::from_value(health.details().unwrap().clone()).unwrap();
+    assert_eq!(details.last_soft_pruned_l1_batch, None);
+    assert_eq!(details.dry_run_pruned_l1_batch, Some(L1BatchNumber(3)));
+    assert_eq!(details.dry_run_pruned_l2_block, Some(L2BlockNumber(7)));
+    let report = details.last_conditions_report.unwrap();
+    assert_eq!(report.l1_batch, L1BatchNumber(6));
+    assert_eq!(report.failed_conditions, ["some passing"]);
+    assert!(!report.is_prunable());
+}
+
 #[tokio::test]
 async fn pruner_with_real_conditions() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -485,6 +581,7 @@ async fn pruner_with_real_conditions() {
         removal_delay: Duration::from_millis(10), // non-zero to not have a tight loop in `DbPruner::run()`
         pruned_batch_chunk_size: 1,
         minimum_l1_batch_age: Duration::ZERO,
+        dry_run: false,
     };
     let pruner = DbPruner::new(config, pool.clone());
     let mut health_check = pruner.health_check();
@@ -541,6 +638,7 @@ async fn pruning_iteration_timely_shuts_down() {
             removal_delay: Duration::MAX, // intentionally chosen so that pruning iterations stuck
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            dry_run: false,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -570,6 +668,7 @@ async fn pruner_timely_shuts_down() {
             removal_delay: Duration::MAX, // intentionally chosen so that pruning iterations stuck
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            dry_run: false,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -585,3 +684,149 @@ async fn pruner_timely_shuts_down() {
     stop_sender.send_replace(true);
     pruner_handle.await.unwrap().unwrap();
 }
+
+#[test(tokio::test)]
+async fn data_retention_respects_retention_periods() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_l2_blocks(&mut conn, 5, 2).await;
+
+    let config = DataRetentionConfig {
+        retention_periods: HashMap::from([
+            (RetainedDataKind::Events, Duration::ZERO),
+            (RetainedDataKind::CallTraces, Duration::MAX),
+        ]),
+        poll_interval: Duration::from_millis(10),
+    };
+    let manager = DataRetentionManager::new(config, pool.clone());
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let mut health = Default::default();
+    // L1 batches don't have commitments and are not executed yet.
+    let processed_l1_batches = manager
+        .run_single_iteration(&stop_receiver, &mut health)
+        .await
+        .unwrap();
+    assert_eq!(processed_l1_batches, 0);
+
+    for number in 0..5 {
+        save_l1_batch_metadata(&mut conn, number).await;
+    }
+    for number in 0..3 {
+        mark_l1_batch_as_executed(&mut conn, number).await;
+    }
+    let processed_l1_batches = manager
+        .run_single_iteration(&stop_receiver, &mut health)
+        .await
+        .unwrap();
+    assert_eq!(processed_l1_batches, 3);
+    let last_pruned = conn
+        .pruning_dal()
+        .get_last_retention_pruned_l1_batch(RetainedDataKind::Events)
+        .await
+        .unwrap();
+    assert_eq!(last_pruned, Some(L1BatchNumber(2)));
+
+    for number in 3..5 {
+        mark_l1_batch_as_executed(&mut conn, number).await;
+    }
+    let processed_l1_batches = manager
+        .run_single_iteration(&stop_receiver, &mut health)
+        .await
+        .unwrap();
+    assert_eq!(processed_l1_batches, 2);
+
+    let mut dal = conn.pruning_dal();
+    let last_pruned = dal
+        .get_last_retention_pruned_l1_batch(RetainedDataKind::Events)
+        .await
+        .unwrap();
+    assert_eq!(last_pruned, Some(L1BatchNumber(4)));
+    let last_pruned = dal
+        .get_last_retention_pruned_l1_batch(RetainedDataKind::CallTraces)
+        .await
+        .unwrap();
+    assert_eq!(last_pruned, None);
+
+    // All L1 batches are already processed.
+    let processed_l1_batches = manager
+        .run_single_iteration(&stop_receiver, &mut health)
+        .await
+        .unwrap();
+    assert_eq!(processed_l1_batches, 0);
+    assert_eq!(
+        health.last_pruned_l1_batches,
+        HashMap::from([("events", L1BatchNumber(4))])
+    );
+}
+
+#[test(tokio::test)]
+async fn cold_storage_archives_old_l1_batches() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_l2_blocks(&mut conn, 3, 2).await;
+
+    let object_store = MockObjectStore::arc();
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let mut health = Default::default();
+    // L1 batches are not old enough.
+    let config = ColdStorageConfig {
+        archive_after: Duration::MAX,
+        data_kinds: ColdStorageDataKind::ALL.to_vec(),
+        poll_interval: Duration::from_millis(10),
+    };
+    let archiver = ColdStorageArchiver::new(config, pool.clone(), object_store.clone());
+    let processed_l1_batches = archiver
+        .run_single_iteration(&stop_receiver, &mut health)
+        .await
+        .unwrap();
+    assert_eq!(processed_l1_batches, 0);
+
+    let config = ColdStorageConfig {
+        archive_after: Duration::ZERO,
+        data_kinds: ColdStorageDataKind::ALL.to_vec(),
+        poll_interval: Duration::from_millis(10),
+    };
+    let archiver = ColdStorageArchiver::new(config, pool.clone(), object_store.clone());
+    // L1 batches don't have commitments and are not executed yet.
+    let processed_l1_batches = archiver
+        .run_single_iteration(&stop_receiver, &mut health)
+        .await
+        .unwrap();
+    assert_eq!(processed_l1_batches, 0);
+
+    for number in 0..3 {
+        save_l1_batch_metadata(&mut conn, number).await;
+        mark_l1_batch_as_executed(&mut conn, number).await;
+    }
+    let processed_l1_batches = archiver
+        .run_single_iteration(&stop_receiver, &mut health)
+        .await
+        .unwrap();
+    assert_eq!(processed_l1_batches, 6);
+
+    for kind in ColdStorageDataKind::ALL {
+        let last_archived = conn
+            .cold_storage_dal()
+            .get_last_archived_l1_batch(kind)
+            .await
+            .unwrap();
+        assert_eq!(last_archived, Some(L1BatchNumber(2)));
+        assert_eq!(
+            health.last_archived_l1_batches[kind.as_str()],
+            L1BatchNumber(2)
+        );
+    }
+    for number in 0..3 {
+        let call_traces: CallTracesArchive = object_store.get(L1BatchNumber(number)).await.unwrap();
+        assert_eq!(call_traces.l1_batch_number, L1BatchNumber(number));
+        let events: EventsArchive = object_store.get(L1BatchNumber(number)).await.unwrap();
+        assert_eq!(events.l1_batch_number, L1BatchNumber(number));
+    }
+
+    // All L1 batches are already archived.
+    let processed_l1_batches = archiver
+        .run_single_iteration(&stop_receiver, &mut health)
+        .await
+        .unwrap();
+    assert_eq!(processed_l1_batches, 0);
+}
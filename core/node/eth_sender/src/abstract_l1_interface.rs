@@ -16,6 +16,9 @@ use zksync_types::{
 
 use crate::EthSenderError;
 
+/// Gas limit of a plain value transfer.
+const CANCELLATION_TX_GAS: u64 = 21_000;
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct OperatorNonce {
     // Nonce on finalized block
@@ -70,10 +73,16 @@ pub(super) trait AbstractL1Interface: 'static + Sync + Send + fmt::Debug {
 
     fn get_blobs_operator_account(&self) -> Option<Address>;
 
+    /// Returns accounts of the additional keys in the non-blob operator pool.
+    fn get_additional_operator_accounts(&self) -> Vec<Address>;
+
+    /// `operator_address` selects a key from the non-blob operator pool; `None` corresponds
+    /// to the default key of `operator_type`.
     async fn get_operator_nonce(
         &self,
         block_numbers: L1BlockNumbers,
         operator_type: OperatorType,
+        operator_address: Option<Address>,
     ) -> Result<Option<OperatorNonce>, EthSenderError>;
 
     #[allow(clippy::too_many_arguments)]
@@ -86,7 +95,17 @@ pub(super) trait AbstractL1Interface: 'static + Sync + Send + fmt::Debug {
         max_aggregated_tx_gas: U256,
         operator_type: OperatorType,
         pubdata_limit: Option<U256>,
-    ) -> SignedCallResult;
+    ) -> Result<SignedCallResult, EthSenderError>;
+
+    /// Signs a transaction transferring 0 wei from a key of the non-blob operator pool to itself. Such a transaction
+    /// replaces a pending transaction with the same nonce, so that the latter cannot be mined.
+    async fn sign_cancellation_tx(
+        &self,
+        operator_address: Option<Address>,
+        nonce: Nonce,
+        base_fee_per_gas: u64,
+        priority_fee_per_gas: u64,
+    ) -> Result<SignedCallResult, EthSenderError>;
 
     async fn get_l1_block_numbers(
         &self,
@@ -98,6 +117,8 @@ pub(super) trait AbstractL1Interface: 'static + Sync + Send + fmt::Debug {
 pub(super) struct RealL1Interface {
     pub ethereum_client: Option<Box<dyn BoundEthInterface>>,
     pub ethereum_client_blobs: Option<Box<dyn BoundEthInterface>>,
+    /// Additional keys forming the non-blob operator pool together with `ethereum_client`.
+    pub additional_operators: Vec<Box<dyn BoundEthInterface>>,
     pub sl_client: Option<Box<dyn BoundEthInterface>>,
     pub wait_confirmations: Option<u64>,
 }
@@ -118,6 +139,24 @@ impl RealL1Interface {
             OperatorType::Gateway => self.sl_client.as_deref().unwrap(),
         }
     }
+
+    fn bound_operator_client(
+        &self,
+        operator_type: OperatorType,
+        operator_address: Option<Address>,
+    ) -> Result<&dyn BoundEthInterface, EthSenderError> {
+        Ok(match (operator_type, operator_address) {
+            (OperatorType::NonBlob, Some(address)) => {
+                let client = self
+                    .additional_operators
+                    .iter()
+                    .find(|client| client.sender_account() == address)
+                    .ok_or(EthSenderError::UnknownOperator(address))?;
+                &**client
+            }
+            _ => self.bound_query_client(operator_type),
+        })
+    }
 }
 
 #[async_trait]
@@ -184,20 +223,27 @@ impl AbstractL1Interface for RealL1Interface {
             .map(|s| s.sender_account())
     }
 
+    fn get_additional_operator_accounts(&self) -> Vec<Address> {
+        self.additional_operators
+            .iter()
+            .map(|client| client.sender_account())
+            .collect()
+    }
+
     async fn get_operator_nonce(
         &self,
         block_numbers: L1BlockNumbers,
         operator_type: OperatorType,
+        operator_address: Option<Address>,
     ) -> Result<Option<OperatorNonce>, EthSenderError> {
-        let finalized = self
-            .bound_query_client(operator_type)
+        let client = self.bound_operator_client(operator_type, operator_address)?;
+        let finalized = client
             .nonce_at(block_numbers.finalized.0.into())
             .await?
             .as_u32()
             .into();
 
-        let latest = self
-            .bound_query_client(operator_type)
+        let latest = client
             .nonce_at(block_numbers.latest.0.into())
             .await?
             .as_u32()
@@ -214,8 +260,9 @@ impl AbstractL1Interface for RealL1Interface {
         gas: U256,
         operator_type: OperatorType,
         max_gas_per_pubdata: Option<U256>,
-    ) -> SignedCallResult {
-        self.bound_query_client(operator_type)
+    ) -> Result<SignedCallResult, EthSenderError> {
+        let signed_tx = self
+            .bound_operator_client(operator_type, tx.from_addr)?
             .sign_prepared_tx_for_addr(
                 tx.raw_tx.clone(),
                 tx.contract_address,
@@ -248,7 +295,33 @@ impl AbstractL1Interface for RealL1Interface {
                 }),
             )
             .await
-            .expect("Failed to sign transaction")
+            .expect("Failed to sign transaction");
+        Ok(signed_tx)
+    }
+
+    async fn sign_cancellation_tx(
+        &self,
+        operator_address: Option<Address>,
+        nonce: Nonce,
+        base_fee_per_gas: u64,
+        priority_fee_per_gas: u64,
+    ) -> Result<SignedCallResult, EthSenderError> {
+        let client = self.bound_operator_client(OperatorType::NonBlob, operator_address)?;
+        let signed_tx = client
+            .sign_prepared_tx_for_addr(
+                vec![],
+                client.sender_account(),
+                Options::with(|opt| {
+                    opt.gas = Some(U256::from(CANCELLATION_TX_GAS));
+                    opt.max_fee_per_gas = Some(U256::from(base_fee_per_gas + priority_fee_per_gas));
+                    opt.max_priority_fee_per_gas = Some(U256::from(priority_fee_per_gas));
+                    opt.nonce = Some(nonce.0.into());
+                    opt.transaction_type = Some(EIP_1559_TX_TYPE.into());
+                }),
+            )
+            .await
+            .expect("Failed to sign transaction");
+        Ok(signed_tx)
     }
 
     async fn get_l1_block_numbers(
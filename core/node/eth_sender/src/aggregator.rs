@@ -1,10 +1,12 @@
 use std::sync::Arc;
 
+use anyhow::Context as _;
 use zksync_config::configs::eth_sender::{ProofSendingMode, SenderConfig};
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_l1_contract_interface::i_executor::methods::{ExecuteBatches, ProveBatches};
 use zksync_mini_merkle_tree::MiniMerkleTree;
+use zksync_node_fee_model::l1_gas_price::TxParamsProvider;
 use zksync_object_store::{ObjectStore, ObjectStoreError};
 use zksync_prover_interface::{outputs::L1BatchProofForL1, Bincode};
 use zksync_types::{
@@ -22,8 +24,8 @@ use zksync_types::{
 use super::{
     aggregated_operations::AggregatedOperation,
     publish_criterion::{
-        GasCriterionKind, L1BatchPublishCriterion, L1GasCriterion, NumberCriterion,
-        TimestampDeadlineCriterion,
+        DataSizeCriterion, GasCriterionKind, GasPriceAwareNumberCriterion, L1BatchPublishCriterion,
+        L1GasCriterion, NumberCriterion, TimestampDeadlineCriterion,
     },
 };
 use crate::EthSenderError;
@@ -46,6 +48,34 @@ pub struct Aggregator {
     commitment_mode: L1BatchCommitmentMode,
     priority_merkle_tree: Option<MiniMerkleTree<L1Tx>>,
     settlement_layer: SettlementLayer,
+    /// Set if aggregation depends on L1 gas prices; see [`GasPriceAwareNumberCriterion`].
+    high_gas_price_aggregation: Option<HighGasPriceAggregation>,
+}
+
+#[derive(Debug)]
+struct HighGasPriceAggregation {
+    threshold: u64,
+    limit: u32,
+    fee_provider: Arc<dyn TxParamsProvider>,
+}
+
+impl HighGasPriceAggregation {
+    fn number_criterion(
+        this: Option<&Self>,
+        op: AggregatedActionType,
+        limit: u32,
+    ) -> Box<dyn L1BatchPublishCriterion> {
+        match this {
+            Some(this) => Box::new(GasPriceAwareNumberCriterion {
+                op,
+                limit,
+                high_gas_price_limit: this.limit,
+                high_gas_price_threshold: this.threshold,
+                fee_provider: this.fee_provider.clone(),
+            }),
+            None => Box::new(NumberCriterion { op, limit }),
+        }
+    }
 }
 
 /// Denotes whether there are any restrictions on sending either
@@ -110,9 +140,20 @@ impl Aggregator {
         commitment_mode: L1BatchCommitmentMode,
         pool: ConnectionPool<Core>,
         settlement_layer: SettlementLayer,
+        fee_provider: Option<Arc<dyn TxParamsProvider>>,
     ) -> anyhow::Result<Self> {
         let operate_4844_mode: bool =
             custom_commit_sender_addr.is_some() && !settlement_layer.is_gateway();
+        // L1 batches are never aggregated when settling on gateway, so there's no point in watching L1 gas prices.
+        let high_gas_price_aggregation = match config.high_gas_price_aggregation_threshold {
+            Some(threshold) if !settlement_layer.is_gateway() => Some(HighGasPriceAggregation {
+                threshold,
+                limit: config.max_aggregated_blocks_on_high_gas_price,
+                fee_provider: fee_provider
+                    .context("high gas price aggregation requires an L1 fee provider")?,
+            }),
+            _ => None,
+        };
 
         // We do not have a reliable lower bound for gas needed to execute batches on gateway so we do not aggregate.
         let execute_criteria: Vec<Box<dyn L1BatchPublishCriterion>> = if settlement_layer
@@ -132,10 +173,11 @@ impl Aggregator {
             })]
         } else {
             vec![
-                Box::from(NumberCriterion {
-                    op: AggregatedActionType::Execute,
-                    limit: config.max_aggregated_blocks_to_execute,
-                }),
+                HighGasPriceAggregation::number_criterion(
+                    high_gas_price_aggregation.as_ref(),
+                    AggregatedActionType::Execute,
+                    config.max_aggregated_blocks_to_execute,
+                ),
                 Box::from(TimestampDeadlineCriterion {
                     op: AggregatedActionType::Execute,
                     deadline_seconds: config.aggregated_block_execute_deadline,
@@ -153,10 +195,33 @@ impl Aggregator {
             if !settlement_layer.is_gateway() && commitment_mode == L1BatchCommitmentMode::Validium
             {
                 vec![
-                    Box::from(NumberCriterion {
+                    HighGasPriceAggregation::number_criterion(
+                        high_gas_price_aggregation.as_ref(),
+                        AggregatedActionType::Commit,
+                        config.max_aggregated_blocks_to_commit,
+                    ),
+                    Box::from(TimestampDeadlineCriterion {
                         op: AggregatedActionType::Commit,
-                        limit: config.max_aggregated_blocks_to_commit,
+                        deadline_seconds: config.aggregated_block_commit_deadline,
+                        max_allowed_lag: Some(config.timestamp_criteria_max_allowed_lag),
                     }),
+                    Box::from(L1GasCriterion::new(
+                        config.max_aggregated_tx_gas,
+                        GasCriterionKind::CommitValidium,
+                    )),
+                ]
+            } else if high_gas_price_aggregation.is_some()
+                && config.pubdata_sending_mode == PubdataSendingMode::Calldata
+            {
+                // Rollup L1 batches can only be committed together if pubdata is sent via calldata:
+                // the blob sidecar only covers a single L1 batch. Unlike for validium chains, L1 batches are committed
+                // one by one unless L1 gas is expensive.
+                vec![
+                    HighGasPriceAggregation::number_criterion(
+                        high_gas_price_aggregation.as_ref(),
+                        AggregatedActionType::Commit,
+                        1,
+                    ),
                     Box::from(TimestampDeadlineCriterion {
                         op: AggregatedActionType::Commit,
                         deadline_seconds: config.aggregated_block_commit_deadline,
@@ -166,6 +231,10 @@ impl Aggregator {
                         config.max_aggregated_tx_gas,
                         GasCriterionKind::CommitValidium,
                     )),
+                    Box::from(DataSizeCriterion {
+                        op: AggregatedActionType::Commit,
+                        data_limit: config.max_eth_tx_data_size,
+                    }),
                 ]
             } else {
                 if config.max_aggregated_blocks_to_commit > 1 {
@@ -181,12 +250,31 @@ impl Aggregator {
                 })]
             };
 
+        // Only dummy proofs may be aggregated; real proofs are always sent one by one.
+        let proof_criteria: Vec<Box<dyn L1BatchPublishCriterion>> =
+            if high_gas_price_aggregation.is_some() {
+                vec![
+                    HighGasPriceAggregation::number_criterion(
+                        high_gas_price_aggregation.as_ref(),
+                        AggregatedActionType::PublishProofOnchain,
+                        1,
+                    ),
+                    Box::from(TimestampDeadlineCriterion {
+                        op: AggregatedActionType::PublishProofOnchain,
+                        deadline_seconds: config.aggregated_block_prove_deadline,
+                        max_allowed_lag: Some(config.timestamp_criteria_max_allowed_lag),
+                    }),
+                ]
+            } else {
+                vec![Box::from(NumberCriterion {
+                    op: AggregatedActionType::PublishProofOnchain,
+                    limit: 1,
+                })]
+            };
+
         Ok(Self {
             commit_criteria,
-            proof_criteria: vec![Box::from(NumberCriterion {
-                op: AggregatedActionType::PublishProofOnchain,
-                limit: 1,
-            })],
+            proof_criteria,
             execute_criteria,
             pubdata_da: config.pubdata_sending_mode,
             config,
@@ -196,9 +284,20 @@ impl Aggregator {
             priority_merkle_tree: None,
             pool,
             settlement_layer,
+            high_gas_price_aggregation,
         })
     }
 
+    /// Returns the max number of L1 batches to load from the storage for an operation that packs
+    /// at most `limit` L1 batches if L1 gas price is low.
+    fn l1_batches_to_load(&self, limit: u32) -> usize {
+        let high_gas_price_limit = self
+            .high_gas_price_aggregation
+            .as_ref()
+            .map_or(0, |aggregation| aggregation.limit);
+        limit.max(high_gas_price_limit) as usize
+    }
+
     pub(crate) async fn get_next_ready_operation(
         &mut self,
         storage: &mut Connection<'_, Core>,
@@ -220,7 +319,7 @@ impl Aggregator {
         if let Some(op) = restrictions.filter_execute_op(
             self.get_execute_operations(
                 storage,
-                self.config.max_aggregated_blocks_to_execute as usize,
+                self.l1_batches_to_load(self.config.max_aggregated_blocks_to_execute),
                 last_sealed_l1_batch_number,
                 priority_tree_start_index,
             )
@@ -236,7 +335,7 @@ impl Aggregator {
             Ok(restrictions.filter_commit_op(
                 self.get_commit_operation(
                     storage,
-                    self.config.max_aggregated_blocks_to_commit as usize,
+                    self.l1_batches_to_load(self.config.max_aggregated_blocks_to_commit),
                     last_sealed_l1_batch_number,
                     base_system_contracts_hashes,
                     protocol_version_id,
@@ -425,11 +524,12 @@ impl Aggregator {
 
     async fn load_dummy_proof_operations(
         storage: &mut Connection<'_, Core>,
+        limit: usize,
         is_4844_mode: bool,
     ) -> Vec<L1BatchWithMetadata> {
         let mut ready_for_proof_l1_batches = storage
             .blocks_dal()
-            .get_ready_for_dummy_proof_l1_batches(1)
+            .get_ready_for_dummy_proof_l1_batches(limit)
             .await
             .unwrap();
 
@@ -614,8 +714,12 @@ impl Aggregator {
             }
 
             ProofSendingMode::SkipEveryProof => {
-                let ready_for_proof_l1_batches =
-                    Self::load_dummy_proof_operations(storage, self.operate_4844_mode).await;
+                let ready_for_proof_l1_batches = Self::load_dummy_proof_operations(
+                    storage,
+                    self.l1_batches_to_load(1),
+                    self.operate_4844_mode,
+                )
+                .await;
                 self.prepare_dummy_proof_operation(
                     storage,
                     ready_for_proof_l1_batches,
@@ -638,7 +742,7 @@ impl Aggregator {
                 } else {
                     let ready_for_proof_batches = storage
                         .blocks_dal()
-                        .get_skipped_for_proof_l1_batches(1)
+                        .get_skipped_for_proof_l1_batches(self.l1_batches_to_load(1))
                         .await
                         .unwrap();
                     self.prepare_dummy_proof_operation(
@@ -1,5 +1,6 @@
+use zksync_dal::DalError;
 use zksync_eth_client::{ContractCallError, EnrichedClientError};
-use zksync_types::web3::contract;
+use zksync_types::{web3::contract, Address};
 
 #[derive(Debug, thiserror::Error)]
 pub enum EthSenderError {
@@ -9,6 +10,10 @@ pub enum EthSenderError {
     ContractCall(#[from] ContractCallError),
     #[error("Token parsing error: {0}")]
     Parse(#[from] contract::Error),
+    #[error("Database error: {0}")]
+    Dal(#[from] DalError),
+    #[error("Operator {0:?} is not in the operator pool")]
+    UnknownOperator(Address),
 }
 
 impl EthSenderError {
@@ -8,7 +8,7 @@ use zksync_eth_client::{ClientError, EnrichedClientError};
 use zksync_node_fee_model::l1_gas_price::TxParamsProvider;
 use zksync_types::eth_sender::TxHistory;
 
-use crate::{abstract_l1_interface::OperatorType, EthSenderError};
+use crate::{abstract_l1_interface::OperatorType, metrics::METRICS, EthSenderError};
 
 #[derive(Debug)]
 pub(crate) struct EthFees {
@@ -32,9 +32,19 @@ pub(crate) struct GasAdjusterFeesOracle {
     pub gas_adjuster: Arc<dyn TxParamsProvider>,
     pub max_acceptable_priority_fee_in_gwei: u64,
     pub time_in_mempool_in_l1_blocks_cap: u32,
+    /// Percentage by which fees are increased when replacing a pending transaction.
+    pub fee_bump_percent: u64,
+    pub max_fee_per_gas_cap: Option<u64>,
 }
 
 impl GasAdjusterFeesOracle {
+    /// Minimal increase in fees required for blob transactions to be replaced.
+    const MIN_BLOB_TX_FEE_BUMP_PERCENT: u64 = 100;
+
+    fn bump_fee(value: u64, percent: u64) -> u64 {
+        value * (100 + percent) / 100
+    }
+
     fn assert_fee_is_not_zero(&self, value: u64, fee_type: &'static str) {
         if value == 0 {
             panic!(
@@ -55,15 +65,21 @@ impl GasAdjusterFeesOracle {
         let blob_base_fee_per_gas = Some(blob_base_fee_per_gas);
 
         if let Some(previous_sent_tx) = previous_sent_tx {
-            // for blob transactions on re-sending need to double all gas prices
+            // for blob transactions on re-sending need to (at least) double all gas prices
+            let percent = max(self.fee_bump_percent, Self::MIN_BLOB_TX_FEE_BUMP_PERCENT);
             return Ok(EthFees {
-                base_fee_per_gas: max(previous_sent_tx.base_fee_per_gas * 2, base_fee_per_gas),
+                base_fee_per_gas: max(
+                    Self::bump_fee(previous_sent_tx.base_fee_per_gas, percent),
+                    base_fee_per_gas,
+                ),
                 priority_fee_per_gas: max(
-                    previous_sent_tx.priority_fee_per_gas * 2,
+                    Self::bump_fee(previous_sent_tx.priority_fee_per_gas, percent),
                     priority_fee_per_gas,
                 ),
                 blob_base_fee_per_gas: max(
-                    previous_sent_tx.blob_base_fee_per_gas.map(|v| v * 2),
+                    previous_sent_tx
+                        .blob_base_fee_per_gas
+                        .map(|v| Self::bump_fee(v, percent)),
                     blob_base_fee_per_gas,
                 ),
                 max_gas_per_pubdata_price: None,
@@ -102,17 +118,18 @@ impl GasAdjusterFeesOracle {
         let mut priority_fee_per_gas = self.gas_adjuster.get_priority_fee();
 
         if let Some(previous_sent_tx) = previous_sent_tx {
-            // Increase `priority_fee_per_gas` by at least 20% to prevent "replacement transaction under-priced" error.
+            // Increase `priority_fee_per_gas` by at least `fee_bump_percent` (20% by default)
+            // to prevent "replacement transaction under-priced" error.
             priority_fee_per_gas = max(
                 priority_fee_per_gas,
-                (previous_sent_tx.priority_fee_per_gas * 6) / 5 + 1,
+                Self::bump_fee(previous_sent_tx.priority_fee_per_gas, self.fee_bump_percent) + 1,
             );
 
             // same for base_fee_per_gas, we theoretically only need to increase it by 10%, but
-            // we increase it by 20% to have priority_fee not growing faster than base fee
+            // we increase it by the same percentage to have priority_fee not growing faster than base fee
             base_fee_per_gas = max(
                 base_fee_per_gas,
-                (previous_sent_tx.base_fee_per_gas * 6) / 5 + 1,
+                Self::bump_fee(previous_sent_tx.base_fee_per_gas, self.fee_bump_percent) + 1,
             );
         }
 
@@ -160,16 +177,19 @@ impl GasAdjusterFeesOracle {
             .get_gateway_price_per_pubdata(capped_time_in_mempool_in_l1_blocks);
 
         if let Some(previous_sent_tx) = previous_sent_tx {
-            // Increase `gas_per_pubdata_fee`. Increase by at least 20%  for having the same behaviour as for L1
+            // Increase `base_fee_per_gas` by at least `fee_bump_percent` for having the same behaviour as for L1
             base_fee_per_gas = max(
                 base_fee_per_gas,
-                (previous_sent_tx.base_fee_per_gas * 6) / 5 + 1,
+                Self::bump_fee(previous_sent_tx.base_fee_per_gas, self.fee_bump_percent) + 1,
             );
 
-            // Increase `gas_per_pubdata_fee`. Increase by at least 20%  for having the same behaviour as for L1
+            // Increase `gas_per_pubdata_fee` by at least `fee_bump_percent` for having the same behaviour as for L1
             gas_per_pubdata =
                 if let Some(prev_gas_per_pubdata) = previous_sent_tx.max_gas_per_pubdata {
-                    max(gas_per_pubdata, (prev_gas_per_pubdata * 6) / 5 + 1)
+                    max(
+                        gas_per_pubdata,
+                        Self::bump_fee(prev_gas_per_pubdata, self.fee_bump_percent) + 1,
+                    )
                 } else {
                     gas_per_pubdata
                 };
@@ -213,6 +233,32 @@ impl GasAdjusterFeesOracle {
         }
         Ok(())
     }
+
+    fn verify_fees_within_cap(
+        &self,
+        fees: &EthFees,
+        previous_sent_tx: &Option<TxHistory>,
+        operator_type: OperatorType,
+    ) -> Result<(), EthSenderError> {
+        let Some(max_fee_per_gas_cap) = self.max_fee_per_gas_cap else {
+            return Ok(());
+        };
+        let max_fee_per_gas = fees.base_fee_per_gas + fees.priority_fee_per_gas;
+        if max_fee_per_gas <= max_fee_per_gas_cap {
+            return Ok(());
+        }
+
+        METRICS.fee_cap_reached[&operator_type].inc();
+        tracing::error!(
+            "max_fee_per_gas {max_fee_per_gas} for {operator_type:?} tx exceeds the configured cap \
+             {max_fee_per_gas_cap}; previously sent attempt: {previous_sent_tx:?}"
+        );
+        let err = ClientError::Custom("max_fee_per_gas exceeds the configured cap".into());
+        let err = EnrichedClientError::new(err, "verify_fees_within_cap")
+            .with_arg("max_fee_per_gas", &max_fee_per_gas)
+            .with_arg("max_fee_per_gas_cap", &max_fee_per_gas_cap);
+        Err(err.into())
+    }
 }
 
 impl EthFeesOracle for GasAdjusterFeesOracle {
@@ -222,14 +268,15 @@ impl EthFeesOracle for GasAdjusterFeesOracle {
         time_in_mempool_in_l1_blocks: u32,
         operator_type: OperatorType,
     ) -> Result<EthFees, EthSenderError> {
-        match operator_type {
-            OperatorType::NonBlob => {
-                self.calculate_fees_no_blob_sidecar(previous_sent_tx, time_in_mempool_in_l1_blocks)
-            }
-            OperatorType::Blob => self.calculate_fees_with_blob_sidecar(previous_sent_tx),
-            OperatorType::Gateway => {
-                self.calculate_fees_for_gateway_tx(previous_sent_tx, time_in_mempool_in_l1_blocks)
-            }
-        }
+        let fees =
+            match operator_type {
+                OperatorType::NonBlob => self
+                    .calculate_fees_no_blob_sidecar(previous_sent_tx, time_in_mempool_in_l1_blocks),
+                OperatorType::Blob => self.calculate_fees_with_blob_sidecar(previous_sent_tx),
+                OperatorType::Gateway => self
+                    .calculate_fees_for_gateway_tx(previous_sent_tx, time_in_mempool_in_l1_blocks),
+            }?;
+        self.verify_fees_within_cap(&fees, previous_sent_tx, operator_type)?;
+        Ok(fees)
     }
 }
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use tokio::sync::watch;
 use zksync_config::configs::eth_sender::SenderConfig;
 use zksync_contracts::BaseSystemContractsHashes;
@@ -12,6 +14,7 @@ use zksync_l1_contract_interface::{
     multicall3::{Multicall3Call, Multicall3Result},
     Tokenizable, Tokenize,
 };
+use zksync_node_fee_model::l1_gas_price::TxParamsProvider;
 use zksync_shared_metrics::BlockL1Stage;
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
@@ -31,6 +34,7 @@ use crate::{
     aggregator::OperationSkippingRestrictions,
     health::{EthTxAggregatorHealthDetails, EthTxDetails},
     metrics::{PubdataKind, METRICS},
+    pubdata_mode_selector::PubdataModeSelector,
     publish_criterion::L1GasCriterion,
     zksync_functions::ZkSyncFunctions,
     Aggregator, EthSenderError,
@@ -79,11 +83,16 @@ pub struct EthTxAggregator {
     /// transactions. The `Some` then contains the address of this custom operator
     /// address.
     custom_commit_sender_addr: Option<Address>,
+    /// Additional keys of the non-blob operator pool. Transactions are only assigned to these keys
+    /// after the eth_tx_manager moves stuck transactions to them.
+    additional_operator_addrs: Vec<Address>,
     pool: ConnectionPool<Core>,
     sl_chain_id: SLChainId,
     health_updater: HealthUpdater,
     priority_tree_start_index: Option<usize>,
     settlement_layer: SettlementLayer,
+    /// Set if pubdata auto-selection is enabled; chooses between blobs and calldata for each commit.
+    pubdata_mode_selector: Option<PubdataModeSelector>,
 }
 
 struct TxData {
@@ -106,7 +115,9 @@ impl EthTxAggregator {
         state_transition_chain_contract: Address,
         rollup_chain_id: L2ChainId,
         custom_commit_sender_addr: Option<Address>,
+        additional_operator_addrs: Vec<Address>,
         settlement_layer: SettlementLayer,
+        fee_provider: Option<Arc<dyn TxParamsProvider>>,
     ) -> Self {
         let eth_client = eth_client.for_component("eth_tx_aggregator");
         let functions = ZkSyncFunctions::default();
@@ -125,6 +136,8 @@ impl EthTxAggregator {
         };
 
         let sl_chain_id = (*eth_client).as_ref().fetch_chain_id().await.unwrap();
+        let pubdata_mode_selector =
+            fee_provider.and_then(|fee_provider| PubdataModeSelector::new(&config, fee_provider));
 
         Self {
             config,
@@ -139,11 +152,13 @@ impl EthTxAggregator {
             base_nonce_custom_commit_sender,
             rollup_chain_id,
             custom_commit_sender_addr,
+            additional_operator_addrs,
             pool,
             sl_chain_id,
             health_updater: ReactiveHealthCheck::new("eth_tx_aggregator").1,
             priority_tree_start_index: None,
             settlement_layer,
+            pubdata_mode_selector,
         }
     }
 
@@ -603,7 +618,7 @@ impl EthTxAggregator {
             op_restrictions.execute_restriction = reason;
         }
 
-        if let Some(agg_op) = self
+        if let Some(mut agg_op) = self
             .aggregator
             .get_next_ready_operation(
                 storage,
@@ -616,6 +631,19 @@ impl EthTxAggregator {
             .await?
         {
             let is_gateway = self.settlement_layer.is_gateway();
+            if let (
+                Some(selector),
+                AggregatedOperation::Commit(_, l1_batches, pubdata_da @ PubdataSendingMode::Blobs),
+            ) = (&self.pubdata_mode_selector, &mut agg_op)
+            {
+                if !is_gateway {
+                    let pubdata_len = l1_batches
+                        .iter()
+                        .map(|batch| batch.header.pubdata_input.as_ref().map_or(0, Vec::len))
+                        .sum();
+                    *pubdata_da = selector.select(pubdata_len);
+                }
+            }
             let tx = self
                 .save_eth_tx(
                     storage,
@@ -700,12 +728,11 @@ impl EthTxAggregator {
                     &self.functions.post_gateway_commit
                 };
 
-                let l1_batch_for_sidecar =
-                    if PubdataSendingMode::Blobs == self.aggregator.pubdata_da() {
-                        Some(l1_batches[0].clone())
-                    } else {
-                        None
-                    };
+                let l1_batch_for_sidecar = if PubdataSendingMode::Blobs == *pubdata_da {
+                    Some(l1_batches[0].clone())
+                } else {
+                    None
+                };
 
                 Self::encode_commit_data(encoding_fn, &commit_data, l1_batch_for_sidecar)
             }
@@ -789,8 +816,11 @@ impl EthTxAggregator {
         // var whatever it actually is: a `None` for single-addr operator or `Some`
         // for multi-addr operator in 4844 mode.
         let sender_addr = match (op_type, is_gateway) {
-            (AggregatedActionType::Commit, false) => self.custom_commit_sender_addr,
-            (_, _) => None,
+            (AggregatedActionType::Commit, false) if self.custom_commit_sender_addr.is_some() => {
+                self.custom_commit_sender_addr
+            }
+            (_, false) => self.active_operator_addr(&mut transaction).await?,
+            (_, true) => None,
         };
         let nonce = self.get_next_nonce(&mut transaction, sender_addr).await?;
         let encoded_aggregated_op =
@@ -861,9 +891,13 @@ impl EthTxAggregator {
         // At the start we have to consider this fact and get the max nonce.
         let l1_nonce = if from_addr.is_none() {
             self.base_nonce
-        } else {
+        } else if from_addr == self.custom_commit_sender_addr {
             self.base_nonce_custom_commit_sender
                 .expect("custom base nonce is expected to be initialized; qed")
+        } else {
+            // Transactions are assigned to additional operator keys only after being taken over
+            // by eth_tx_manager, which initializes nonces from L1.
+            0
         };
         tracing::info!(
             "Next nonce from db: {}, nonce from L1: {} for address: {:?}",
@@ -874,6 +908,32 @@ impl EthTxAggregator {
         Ok(db_nonce.max(l1_nonce))
     }
 
+    /// Returns the key from the non-blob operator pool that new transactions should be sent from,
+    /// i.e. the key of the most recent non-blob transaction. `None` corresponds to the main operator key.
+    async fn active_operator_addr(
+        &self,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<Option<Address>, EthSenderError> {
+        if self.additional_operator_addrs.is_empty() {
+            return Ok(None);
+        }
+        let last_sender = storage
+            .eth_sender_dal()
+            .get_sender_of_last_eth_tx(self.custom_commit_sender_addr, false)
+            .await?;
+        Ok(match last_sender {
+            Some(addr) if self.additional_operator_addrs.contains(&addr) => Some(addr),
+            Some(addr) => {
+                tracing::warn!(
+                    "Last transaction was sent from {addr:?}, which is not in the operator pool; \
+                     falling back to the main operator"
+                );
+                None
+            }
+            None => None,
+        })
+    }
+
     /// Returns the health check for eth tx aggregator.
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
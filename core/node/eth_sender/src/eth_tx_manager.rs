@@ -13,8 +13,9 @@ use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthChe
 use zksync_node_fee_model::l1_gas_price::TxParamsProvider;
 use zksync_shared_metrics::BlockL1Stage;
 use zksync_types::{
-    aggregated_operations::AggregatedActionType, eth_sender::EthTx, Address, L1BlockNumber,
-    GATEWAY_CALLDATA_PROCESSING_ROLLUP_OVERHEAD_GAS, H256,
+    aggregated_operations::AggregatedActionType,
+    eth_sender::{EthTx, TxHistory},
+    Address, L1BlockNumber, GATEWAY_CALLDATA_PROCESSING_ROLLUP_OVERHEAD_GAS, H256,
     L1_CALLDATA_PROCESSING_ROLLUP_OVERHEAD_GAS, L1_GAS_PER_PUBDATA_BYTE, U256,
 };
 
@@ -41,6 +42,8 @@ pub struct EthTxManager {
     fees_oracle: Box<dyn EthFeesOracle>,
     pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
+    /// L1 block at which stuck transactions were last taken over by another key from the operator pool.
+    last_takeover_block: Option<L1BlockNumber>,
 }
 
 impl EthTxManager {
@@ -50,19 +53,27 @@ impl EthTxManager {
         gas_adjuster: Arc<dyn TxParamsProvider>,
         ethereum_client: Option<Box<dyn BoundEthInterface>>,
         ethereum_client_blobs: Option<Box<dyn BoundEthInterface>>,
+        additional_operators: Vec<Box<dyn BoundEthInterface>>,
         l2_client: Option<Box<dyn BoundEthInterface>>,
     ) -> Self {
         let ethereum_client = ethereum_client.map(|eth| eth.for_component("eth_tx_manager"));
         let ethereum_client_blobs =
             ethereum_client_blobs.map(|eth| eth.for_component("eth_tx_manager"));
+        let additional_operators = additional_operators
+            .into_iter()
+            .map(|eth| eth.for_component("eth_tx_manager"))
+            .collect();
         let fees_oracle = GasAdjusterFeesOracle {
             gas_adjuster,
             max_acceptable_priority_fee_in_gwei: config.max_acceptable_priority_fee_in_gwei,
             time_in_mempool_in_l1_blocks_cap: config.time_in_mempool_in_l1_blocks_cap,
+            fee_bump_percent: config.fee_bump_percent,
+            max_fee_per_gas_cap: config.max_fee_per_gas_cap,
         };
         let l1_interface = Box::new(RealL1Interface {
             ethereum_client,
             ethereum_client_blobs,
+            additional_operators,
             sl_client: l2_client,
             wait_confirmations: config.wait_confirmations,
         });
@@ -70,12 +81,19 @@ impl EthTxManager {
             "Started eth_tx_manager supporting {:?} operators",
             l1_interface.supported_operator_types()
         );
+        let additional_operator_accounts = l1_interface.get_additional_operator_accounts();
+        if !additional_operator_accounts.is_empty() {
+            tracing::info!(
+                "Non-blob operator pool contains additional keys {additional_operator_accounts:?}"
+            );
+        }
         Self {
             l1_interface,
             config,
             fees_oracle: Box::new(fees_oracle),
             pool,
             health_updater: ReactiveHealthCheck::new("eth_tx_manager").1,
+            last_takeover_block: None,
         }
     }
 
@@ -89,6 +107,9 @@ impl EthTxManager {
         storage: &mut Connection<'_, Core>,
         op: &EthTx,
     ) -> Result<Option<ExecutedTxStatus>, EthSenderError> {
+        // If the transaction was taken over by another operator key, attempts from both keys may be mined,
+        // with only one of them being successful. Thus, a failed attempt is only reported if there's no successful one.
+        let mut failed_status = None;
         // Checking history items, starting from most recently sent.
         for history_item in storage
             .eth_sender_dal()
@@ -104,8 +125,11 @@ impl EthTxManager {
                 .get_tx_status(history_item.tx_hash, self.operator_type(op))
                 .await
             {
-                Ok(Some(s)) => return Ok(Some(s)),
-                Ok(_) => continue,
+                Ok(Some(s)) if s.success => return Ok(Some(s)),
+                Ok(Some(s)) => {
+                    failed_status.get_or_insert(s);
+                }
+                Ok(None) => continue,
                 Err(err) => {
                     tracing::warn!(
                         "Can't check transaction {:?}: {:?}",
@@ -116,7 +140,7 @@ impl EthTxManager {
                 }
             }
         }
-        Ok(None)
+        Ok(failed_status)
     }
 
     pub(crate) async fn send_eth_tx(
@@ -216,7 +240,7 @@ impl EthTxManager {
                 operator_type,
                 max_gas_per_pubdata_price.map(Into::into),
             )
-            .await;
+            .await?;
 
         if let Some(blob_sidecar) = &tx.blob_sidecar {
             signed_tx.raw_tx = RawTransactionBytes::new_unchecked(encode_blob_tx_with_sidecar(
@@ -333,6 +357,22 @@ impl EthTxManager {
             None
         }
     }
+
+    /// Returns addresses of all keys used by the operator of the specified type. For the non-blob operator,
+    /// this is the main key (`None`) followed by the additional keys from the operator pool.
+    pub(crate) fn operator_addresses(&self, operator_type: OperatorType) -> Vec<Option<Address>> {
+        let mut addresses = vec![self.operator_address(operator_type)];
+        if operator_type == OperatorType::NonBlob {
+            addresses.extend(
+                self.l1_interface
+                    .get_additional_operator_accounts()
+                    .into_iter()
+                    .map(Some),
+            );
+        }
+        addresses
+    }
+
     // Monitors the in-flight transactions, marks mined ones as confirmed,
     // returns the one that has to be resent (if there is one).
     pub(super) async fn monitor_inflight_transactions_single_operator(
@@ -340,22 +380,22 @@ impl EthTxManager {
         storage: &mut Connection<'_, Core>,
         l1_block_numbers: L1BlockNumbers,
         operator_type: OperatorType,
+        operator_address: Option<Address>,
     ) -> Result<Option<(EthTx, u32)>, EthSenderError> {
         let operator_nonce = self
             .l1_interface
-            .get_operator_nonce(l1_block_numbers, operator_type)
+            .get_operator_nonce(l1_block_numbers, operator_type, operator_address)
             .await?;
 
         if let Some(operator_nonce) = operator_nonce {
             let inflight_txs = storage
                 .eth_sender_dal()
-                .get_inflight_txs(
-                    self.operator_address(operator_type),
-                    operator_type == OperatorType::Gateway,
-                )
+                .get_inflight_txs(operator_address, operator_type == OperatorType::Gateway)
                 .await
                 .unwrap();
-            METRICS.number_of_inflight_txs[&operator_type].set(inflight_txs.len());
+            if operator_address == self.operator_address(operator_type) {
+                METRICS.number_of_inflight_txs[&operator_type].set(inflight_txs.len());
+            }
 
             Ok(self
                 .apply_inflight_txs_statuses_and_get_first_to_resend(
@@ -504,10 +544,13 @@ impl EthTxManager {
     fn operator_type(&self, tx: &EthTx) -> OperatorType {
         if tx.is_gateway {
             OperatorType::Gateway
-        } else if tx.from_addr.is_none() {
-            OperatorType::NonBlob
-        } else {
+        } else if tx.from_addr.is_some()
+            && tx.from_addr == self.l1_interface.get_blobs_operator_account()
+        {
             OperatorType::Blob
+        } else {
+            // Either the main key, or one of the additional keys from the operator pool.
+            OperatorType::NonBlob
         }
     }
 
@@ -625,13 +668,11 @@ impl EthTxManager {
         storage: &mut Connection<'_, Core>,
         current_block: L1BlockNumber,
         operator_type: OperatorType,
+        operator_address: Option<Address>,
     ) {
         let number_inflight_txs = storage
             .eth_sender_dal()
-            .get_inflight_txs(
-                self.operator_address(operator_type),
-                operator_type == OperatorType::Gateway,
-            )
+            .get_inflight_txs(operator_address, operator_type == OperatorType::Gateway)
             .await
             .unwrap()
             .len();
@@ -646,7 +687,7 @@ impl EthTxManager {
                 .eth_sender_dal()
                 .get_new_eth_txs(
                     number_of_available_slots_for_eth_txs,
-                    &self.operator_address(operator_type),
+                    &operator_address,
                     operator_type == OperatorType::Gateway,
                 )
                 .await
@@ -678,14 +719,43 @@ impl EthTxManager {
         storage: &mut Connection<'_, Core>,
         l1_block_numbers: L1BlockNumbers,
         operator_type: OperatorType,
+        operator_address: Option<Address>,
     ) -> Result<(), EthSenderError> {
         if let Some((tx, sent_at_block)) = self
-            .monitor_inflight_transactions_single_operator(storage, l1_block_numbers, operator_type)
+            .monitor_inflight_transactions_single_operator(
+                storage,
+                l1_block_numbers,
+                operator_type,
+                operator_address,
+            )
             .await?
         {
             // New gas price depends on the time this tx spent in mempool.
             let time_in_mempool_in_l1_blocks = l1_block_numbers.latest.0 - sent_at_block;
 
+            if self.should_take_over(operator_type, sent_at_block, l1_block_numbers.latest) {
+                return self
+                    .take_over_stuck_txs(storage, &tx, l1_block_numbers)
+                    .await;
+            }
+
+            let last_attempt = storage
+                .eth_sender_dal()
+                .get_last_sent_eth_tx(tx.id)
+                .await
+                .unwrap();
+            if let Some(last_attempt) = &last_attempt {
+                if !self.is_fee_bump_due(last_attempt, l1_block_numbers.latest) {
+                    tracing::debug!(
+                        "Fee bump for tx {} (nonce {}) is not due yet; last attempt was sent at block {:?}",
+                        tx.id,
+                        tx.nonce,
+                        last_attempt.sent_at_block
+                    );
+                    return Ok(());
+                }
+            }
+
             // We don't want to return early in case resend does not succeed -
             // the error is logged anyway, but early returns will prevent
             // sending new operations.
@@ -701,6 +771,172 @@ impl EthTxManager {
         Ok(())
     }
 
+    /// Checks whether a pending transaction may be replaced according to the configured fee bump schedule.
+    fn is_fee_bump_due(&self, last_attempt: &TxHistory, current_block: L1BlockNumber) -> bool {
+        let due_by_blocks = self.config.fee_bump_interval_in_l1_blocks.map(|interval| {
+            last_attempt.sent_at_block.map_or(true, |sent_at_block| {
+                current_block.0 >= sent_at_block.saturating_add(interval)
+            })
+        });
+        let due_by_time = self.config.fee_bump_interval_sec.map(|interval| {
+            let now = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .expect("incorrect system time")
+                .as_secs();
+            now >= last_attempt.created_at_timestamp.saturating_add(interval)
+        });
+        match (due_by_blocks, due_by_time) {
+            (None, None) => true,
+            (Some(due), None) | (None, Some(due)) => due,
+            (Some(due_by_blocks), Some(due_by_time)) => due_by_blocks || due_by_time,
+        }
+    }
+
+    fn should_take_over(
+        &self,
+        operator_type: OperatorType,
+        sent_at_block: u32,
+        current_block: L1BlockNumber,
+    ) -> bool {
+        let Some(takeover_after) = self.config.operator_takeover_after_l1_blocks else {
+            return false;
+        };
+        if operator_type != OperatorType::NonBlob
+            || self
+                .l1_interface
+                .get_additional_operator_accounts()
+                .is_empty()
+        {
+            return false;
+        }
+        // Give the key that took over transactions the same time to get them mined.
+        let stuck_since = self
+            .last_takeover_block
+            .map_or(sent_at_block, |block| block.0.max(sent_at_block));
+        current_block.0.saturating_sub(stuck_since) >= takeover_after
+    }
+
+    /// Moves the stuck transaction and all subsequent unconfirmed transactions of its key
+    /// to the next key from the non-blob operator pool. Transactions already sent from the old key are replaced
+    /// with no-op transactions, so that they don't compete with the transactions sent from the new key.
+    async fn take_over_stuck_txs(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        stuck_tx: &EthTx,
+        l1_block_numbers: L1BlockNumbers,
+    ) -> Result<(), EthSenderError> {
+        let pool = self.operator_addresses(OperatorType::NonBlob);
+        let position = pool
+            .iter()
+            .position(|address| *address == stuck_tx.from_addr)
+            .unwrap_or(0);
+        let next_operator = pool[(position + 1) % pool.len()];
+
+        let Some(next_operator_nonce) = self
+            .l1_interface
+            .get_operator_nonce(l1_block_numbers, OperatorType::NonBlob, next_operator)
+            .await?
+        else {
+            return Ok(());
+        };
+        let db_nonce = storage
+            .eth_sender_dal()
+            .get_next_nonce(next_operator, false)
+            .await
+            .unwrap()
+            .unwrap_or(0);
+        let first_nonce = db_nonce.max(next_operator_nonce.latest.0.into());
+
+        let sent_txs = storage
+            .eth_sender_dal()
+            .get_inflight_txs(stuck_tx.from_addr, false)
+            .await
+            .unwrap();
+        let moved_txs = storage
+            .eth_sender_dal()
+            .reassign_unconfirmed_txs(
+                stuck_tx.from_addr,
+                stuck_tx.nonce.0.into(),
+                next_operator,
+                first_nonce,
+            )
+            .await
+            .unwrap();
+        self.last_takeover_block = Some(l1_block_numbers.latest);
+        METRICS.operator_takeovers.inc();
+        tracing::warn!(
+            "Tx {} (nonce {}) of operator {:?} is stuck; {moved_txs} unconfirmed txs are taken over \
+             by operator {next_operator:?} starting from nonce {first_nonce}",
+            stuck_tx.id,
+            stuck_tx.nonce,
+            stuck_tx.from_addr
+        );
+
+        for tx in sent_txs.iter().filter(|tx| tx.nonce >= stuck_tx.nonce) {
+            // Cancellation is best-effort: even if the original transaction gets mined, its attempts are kept
+            // in the history, so it will be confirmed as usual.
+            match self.cancel_tx(storage, tx, l1_block_numbers).await {
+                Ok(tx_hash) => tracing::info!(
+                    "Sent cancellation tx {tx_hash:?} for tx {} (nonce {}) of operator {:?}",
+                    tx.id,
+                    tx.nonce,
+                    tx.from_addr
+                ),
+                Err(err) => tracing::warn!(
+                    "Failed cancelling tx {} (nonce {}) of operator {:?}: {err}",
+                    tx.id,
+                    tx.nonce,
+                    tx.from_addr
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the pending transaction with a 0-value transfer at the same nonce and from the same key.
+    async fn cancel_tx(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        tx: &EthTx,
+        l1_block_numbers: L1BlockNumbers,
+    ) -> Result<H256, EthSenderError> {
+        let previous_sent_tx = storage
+            .eth_sender_dal()
+            .get_last_sent_eth_tx(tx.id)
+            .await
+            .unwrap();
+        let time_in_mempool_in_l1_blocks = previous_sent_tx
+            .as_ref()
+            .and_then(|sent_tx| sent_tx.sent_at_block)
+            .map_or(0, |sent_at_block| {
+                l1_block_numbers.latest.0.saturating_sub(sent_at_block)
+            });
+        // Fees are bumped compared to the previous attempt, so that the cancellation replaces it in the mempool.
+        let EthFees {
+            base_fee_per_gas,
+            priority_fee_per_gas,
+            ..
+        } = self.fees_oracle.calculate_fees(
+            &previous_sent_tx,
+            time_in_mempool_in_l1_blocks,
+            OperatorType::NonBlob,
+        )?;
+        let signed_tx = self
+            .l1_interface
+            .sign_cancellation_tx(
+                tx.from_addr,
+                tx.nonce,
+                base_fee_per_gas,
+                priority_fee_per_gas,
+            )
+            .await?;
+        let tx_hash = self
+            .l1_interface
+            .send_raw_tx(signed_tx.raw_tx, OperatorType::NonBlob)
+            .await?;
+        Ok(tx_hash)
+    }
+
     #[tracing::instrument(skip_all, name = "EthTxManager::loop_iteration")]
     pub async fn loop_iteration(&mut self, storage: &mut Connection<'_, Core>) {
         // We can treat blob and non-blob operators independently as they have different nonces and
@@ -716,19 +952,32 @@ impl EthTxManager {
                 "Loop iteration at block {} for {operator_type:?} operator",
                 l1_block_numbers.latest
             );
-            self.send_new_eth_txs(storage, l1_block_numbers.latest, operator_type)
-                .await;
-            let result = self
-                .update_statuses_and_resend_if_needed(storage, l1_block_numbers, operator_type)
+            // Each key of the operator pool has its own nonce, so keys are processed independently.
+            for operator_address in self.operator_addresses(operator_type) {
+                self.send_new_eth_txs(
+                    storage,
+                    l1_block_numbers.latest,
+                    operator_type,
+                    operator_address,
+                )
                 .await;
-
-            //We don't want an error in sending non-blob transactions interrupt sending blob txs
-            if let Err(error) = result {
-                // Web3 API request failures can cause this,
-                // and anything more important is already properly reported.
-                tracing::warn!("eth_sender error {:?}", error);
-                if error.is_retriable() {
-                    METRICS.l1_transient_errors.inc();
+                let result = self
+                    .update_statuses_and_resend_if_needed(
+                        storage,
+                        l1_block_numbers,
+                        operator_type,
+                        operator_address,
+                    )
+                    .await;
+
+                //We don't want an error in sending non-blob transactions interrupt sending blob txs
+                if let Err(error) = result {
+                    // Web3 API request failures can cause this,
+                    // and anything more important is already properly reported.
+                    tracing::warn!("eth_sender error {:?}", error);
+                    if error.is_retriable() {
+                        METRICS.l1_transient_errors.inc();
+                    }
                 }
             }
         }
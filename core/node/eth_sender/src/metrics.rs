@@ -42,6 +42,13 @@ pub(super) enum TransactionType {
     Regular,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
+#[metrics(label = "mode", rename_all = "snake_case")]
+pub(super) enum PubdataModeLabel {
+    Blobs,
+    Calldata,
+}
+
 impl From<AggregatedActionType> for ActionTypeLabel {
     fn from(action_type: AggregatedActionType) -> Self {
         Self(action_type)
@@ -110,6 +117,14 @@ pub(super) struct EthSenderMetrics {
     /// Number of L1 batches aggregated for publishing with a specific reason.
     pub block_aggregation_reason: Family<AggregationReasonLabels, Counter>,
     pub l1_transient_errors: Counter,
+    /// Number of commit operations for which a certain DA mode was selected by pubdata auto-selection.
+    pub pubdata_mode_selected: Family<PubdataModeLabel, Counter>,
+    /// Estimated savings (in gwei) from publishing pubdata via calldata instead of blobs.
+    pub pubdata_auto_selection_savings_gwei: Counter,
+    /// Number of times stuck transactions were taken over by another key from the operator pool.
+    pub operator_takeovers: Counter,
+    /// Number of times sending a transaction was skipped because its fees exceeded the configured cap.
+    pub fee_cap_reached: Family<OperatorType, Counter>,
 }
 
 impl EthSenderMetrics {
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use zksync_config::configs::eth_sender::SenderConfig;
+use zksync_l1_contract_interface::i_executor::commit::kzg::ZK_SYNC_BYTES_PER_BLOB;
+use zksync_node_fee_model::l1_gas_price::TxParamsProvider;
+use zksync_types::{pubdata_da::PubdataSendingMode, L1_GAS_PER_PUBDATA_BYTE};
+
+use crate::metrics::{PubdataModeLabel, METRICS};
+
+/// Blob gas consumed by a single blob (EIP-4844 `GAS_PER_BLOB`).
+const GAS_PER_BLOB: u64 = 1 << 17;
+
+/// Estimated L1 costs (in wei) of publishing pubdata of a certain size in either DA mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PubdataCosts {
+    pub blobs: u128,
+    pub calldata: u128,
+}
+
+impl PubdataCosts {
+    pub fn estimate(pubdata_len: usize, base_fee_per_gas: u64, blob_base_fee_per_gas: u64) -> Self {
+        let blob_count = pubdata_len.div_ceil(ZK_SYNC_BYTES_PER_BLOB).max(1) as u128;
+        Self {
+            blobs: blob_count * u128::from(GAS_PER_BLOB) * u128::from(blob_base_fee_per_gas),
+            calldata: pubdata_len as u128
+                * u128::from(L1_GAS_PER_PUBDATA_BYTE)
+                * u128::from(base_fee_per_gas),
+        }
+    }
+}
+
+/// Chooses between blobs and calldata for each commit operation based on the current L1 fees.
+///
+/// Only used if the node is configured to send pubdata using blobs and auto-selection is enabled.
+#[derive(Debug)]
+pub(crate) struct PubdataModeSelector {
+    fee_provider: Arc<dyn TxParamsProvider>,
+    max_blob_base_fee: Option<u64>,
+    max_calldata_size: usize,
+}
+
+impl PubdataModeSelector {
+    /// Returns `None` if auto-selection is not applicable for the provided config.
+    pub fn new(config: &SenderConfig, fee_provider: Arc<dyn TxParamsProvider>) -> Option<Self> {
+        if !config.pubdata_auto_selection
+            || config.pubdata_sending_mode != PubdataSendingMode::Blobs
+        {
+            return None;
+        }
+        Some(Self {
+            fee_provider,
+            max_blob_base_fee: config.pubdata_auto_selection_max_blob_base_fee,
+            max_calldata_size: config.pubdata_auto_selection_max_calldata_size,
+        })
+    }
+
+    /// Selects the DA mode for publishing `pubdata_len` bytes and reports the estimated savings
+    /// compared to always using blobs.
+    pub fn select(&self, pubdata_len: usize) -> PubdataSendingMode {
+        let base_fee_per_gas = self.fee_provider.get_blob_tx_base_fee();
+        let blob_base_fee_per_gas = self.fee_provider.get_blob_tx_blob_base_fee();
+        let costs = PubdataCosts::estimate(pubdata_len, base_fee_per_gas, blob_base_fee_per_gas);
+        let mode = self.select_for_costs(pubdata_len, blob_base_fee_per_gas, costs);
+
+        tracing::info!(
+            "Selected {mode:?} DA for commit with {pubdata_len} bytes of pubdata; \
+             base_fee_per_gas {base_fee_per_gas}, blob_base_fee_per_gas {blob_base_fee_per_gas}, \
+             estimated costs: {costs:?}"
+        );
+        if mode == PubdataSendingMode::Calldata {
+            METRICS.pubdata_mode_selected[&PubdataModeLabel::Calldata].inc();
+            let savings = costs.blobs.saturating_sub(costs.calldata);
+            METRICS
+                .pubdata_auto_selection_savings_gwei
+                .inc_by((savings / 1_000_000_000) as u64);
+        } else {
+            METRICS.pubdata_mode_selected[&PubdataModeLabel::Blobs].inc();
+        }
+        mode
+    }
+
+    pub(crate) fn select_for_costs(
+        &self,
+        pubdata_len: usize,
+        blob_base_fee_per_gas: u64,
+        costs: PubdataCosts,
+    ) -> PubdataSendingMode {
+        if pubdata_len > self.max_calldata_size {
+            return PubdataSendingMode::Blobs;
+        }
+        if let Some(max_blob_base_fee) = self.max_blob_base_fee {
+            if blob_base_fee_per_gas > max_blob_base_fee {
+                return PubdataSendingMode::Calldata;
+            }
+        }
+        if costs.calldata < costs.blobs {
+            PubdataSendingMode::Calldata
+        } else {
+            PubdataSendingMode::Blobs
+        }
+    }
+}
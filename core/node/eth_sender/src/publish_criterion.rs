@@ -1,8 +1,9 @@
-use std::{fmt, ops};
+use std::{fmt, ops, sync::Arc};
 
 use async_trait::async_trait;
 use chrono::Utc;
 use zksync_dal::{Connection, Core, CoreDal};
+use zksync_node_fee_model::l1_gas_price::TxParamsProvider;
 use zksync_types::{
     aggregated_operations::AggregatedActionType, commitment::L1BatchWithMetadata, L1BatchNumber,
 };
@@ -69,6 +70,120 @@ impl L1BatchPublishCriterion for NumberCriterion {
     }
 }
 
+/// Same as [`NumberCriterion`], but packs more L1 batches together while L1 gas is expensive.
+#[derive(Debug)]
+pub struct GasPriceAwareNumberCriterion {
+    pub op: AggregatedActionType,
+    /// Maximum number of L1 batches to be packed together if L1 gas price is below the threshold.
+    pub limit: u32,
+    /// Number of L1 batches to be packed together if L1 gas price exceeds the threshold.
+    pub high_gas_price_limit: u32,
+    /// L1 base fee per gas (in wei) above which `high_gas_price_limit` is used.
+    pub high_gas_price_threshold: u64,
+    pub fee_provider: Arc<dyn TxParamsProvider>,
+}
+
+impl GasPriceAwareNumberCriterion {
+    fn current_limit(&self) -> (u32, bool) {
+        let base_fee_per_gas = self.fee_provider.get_next_block_minimal_base_fee();
+        if base_fee_per_gas > self.high_gas_price_threshold {
+            (self.high_gas_price_limit.max(self.limit), true)
+        } else {
+            (self.limit, false)
+        }
+    }
+}
+
+#[async_trait]
+impl L1BatchPublishCriterion for GasPriceAwareNumberCriterion {
+    fn name(&self) -> &'static str {
+        "gas_price_aware_l1_batch_number"
+    }
+
+    async fn last_l1_batch_to_publish(
+        &mut self,
+        _storage: &mut Connection<'_, Core>,
+        consecutive_l1_batches: &[L1BatchWithMetadata],
+        _last_sealed_l1_batch: L1BatchNumber,
+        _is_gateway: bool,
+    ) -> Option<L1BatchNumber> {
+        let first = consecutive_l1_batches.first()?.header.number.0;
+        let last = consecutive_l1_batches.last()?.header.number.0;
+        let (limit, is_gas_price_high) = self.current_limit();
+        let batch_count = last - first + 1;
+        if batch_count >= limit {
+            let result = L1BatchNumber(first + limit - 1);
+            tracing::debug!(
+                "`{}` publish criterion (limit={limit}, high_gas_price={is_gas_price_high}) triggered for op {} \
+                 with L1 batch range {:?}",
+                self.name(),
+                self.op,
+                first..=result.0
+            );
+            let reason = if is_gas_price_high {
+                "high_gas_price_number"
+            } else {
+                "number"
+            };
+            METRICS.block_aggregation_reason[&(self.op, reason).into()].inc();
+            Some(result)
+        } else {
+            None
+        }
+    }
+}
+
+/// Limits the size of L1 batch data (currently, pubdata) sent in a single L1 transaction.
+#[derive(Debug)]
+pub struct DataSizeCriterion {
+    pub op: AggregatedActionType,
+    pub data_limit: usize,
+}
+
+impl DataSizeCriterion {
+    /// Rough estimate of the encoded size of batch fields other than pubdata (header, commitments, system logs).
+    const L1_BATCH_DATA_OVERHEAD: usize = 4_096;
+}
+
+#[async_trait]
+impl L1BatchPublishCriterion for DataSizeCriterion {
+    fn name(&self) -> &'static str {
+        "data_size"
+    }
+
+    async fn last_l1_batch_to_publish(
+        &mut self,
+        _storage: &mut Connection<'_, Core>,
+        consecutive_l1_batches: &[L1BatchWithMetadata],
+        _last_sealed_l1_batch: L1BatchNumber,
+        _is_gateway: bool,
+    ) -> Option<L1BatchNumber> {
+        let mut data_size_left = self.data_limit;
+        for (index, l1_batch) in consecutive_l1_batches.iter().enumerate() {
+            let l1_batch_size = Self::L1_BATCH_DATA_OVERHEAD
+                + l1_batch.header.pubdata_input.as_ref().map_or(0, Vec::len);
+            if l1_batch_size > data_size_left {
+                // A single L1 batch is always published, even if it exceeds the limit.
+                let last_l1_batch = if index == 0 {
+                    l1_batch.header.number
+                } else {
+                    l1_batch.header.number - 1
+                };
+                tracing::debug!(
+                    "`data_size` publish criterion (limit={}) triggered for op {} with L1 batch range {:?}",
+                    self.data_limit,
+                    self.op,
+                    consecutive_l1_batches[0].header.number.0..=last_l1_batch.0
+                );
+                METRICS.block_aggregation_reason[&(self.op, "data_size").into()].inc();
+                return Some(last_l1_batch);
+            }
+            data_size_left -= l1_batch_size;
+        }
+        None
+    }
+}
+
 #[derive(Debug)]
 pub struct TimestampDeadlineCriterion {
     pub op: AggregatedActionType,
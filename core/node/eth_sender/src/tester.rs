@@ -265,6 +265,7 @@ impl EthSenderTester {
             commitment_mode,
             connection_pool.clone(),
             SettlementLayer::L1(chain_id),
+            Some(gas_adjuster.clone()),
         )
         .await
         .unwrap();
@@ -286,7 +287,9 @@ impl EthSenderTester {
             STATE_TRANSITION_CONTRACT_ADDRESS,
             Default::default(),
             custom_commit_sender_addr,
+            vec![],
             SettlementLayer::L1(chain_id),
+            Some(gas_adjuster.clone()),
         )
         .await;
 
@@ -296,6 +299,7 @@ impl EthSenderTester {
             gas_adjuster.clone(),
             Some(gateway.clone()),
             Some(gateway_blobs.clone()),
+            vec![],
             None,
         );
 
@@ -335,6 +339,7 @@ impl EthSenderTester {
             self.gas_adjuster.clone(),
             None,
             None,
+            vec![],
             Some(self.l2_gateway.clone()),
         );
         self.settlement_layer = SettlementLayer::Gateway(10.into());
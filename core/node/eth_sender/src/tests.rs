@@ -1,5 +1,6 @@
 use assert_matches::assert_matches;
 use test_casing::{test_casing, Product};
+use zksync_config::configs::eth_sender::{EthConfig, SenderConfig};
 use zksync_contracts::hyperchain_contract;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_eth_client::{
@@ -18,17 +19,23 @@ use zksync_types::{
     commitment::{
         L1BatchCommitmentMode, L1BatchMetaParameters, L1BatchMetadata, L1BatchWithMetadata,
     },
+    eth_sender::TxHistory,
     ethabi::{self, Token},
     helpers::unix_timestamp_ms,
+    pubdata_da::PubdataSendingMode,
     settlement::SettlementLayer,
     web3::{self, contract::Error},
-    Address, K256PrivateKey, L2ChainId, ProtocolVersionId, SLChainId, H256, U256,
+    Address, K256PrivateKey, L1BatchNumber, L2ChainId, Nonce, ProtocolVersionId, SLChainId, H256,
+    U256,
 };
 use zksync_web3_decl::client::MockClient;
 
 use crate::{
     abstract_l1_interface::{AbstractL1Interface, OperatorType, RealL1Interface},
     aggregated_operations::AggregatedOperation,
+    eth_fees_oracle::{EthFeesOracle, GasAdjusterFeesOracle},
+    pubdata_mode_selector::{PubdataCosts, PubdataModeSelector},
+    publish_criterion::{GasPriceAwareNumberCriterion, L1BatchPublishCriterion},
     tester::{
         EthSenderTester, TestL1Batch, STATE_TRANSITION_CONTRACT_ADDRESS,
         STATE_TRANSITION_MANAGER_CONTRACT_ADDRESS,
@@ -299,6 +306,7 @@ async fn resend_each_block(commitment_mode: L1BatchCommitmentMode) -> anyhow::Re
             &mut tester.conn.connection().await.unwrap(),
             block_numbers,
             OperatorType::NonBlob,
+            None,
         )
         .await?
         .unwrap();
@@ -929,6 +937,7 @@ async fn test_signing_eip712_tx() {
     let l1_interface = RealL1Interface {
         ethereum_client: None,
         ethereum_client_blobs: None,
+        additional_operators: vec![],
         sl_client: Some(sign_client),
         wait_confirmations: Some(10),
     };
@@ -947,8 +956,280 @@ async fn test_signing_eip712_tx() {
             OperatorType::Gateway,
             Some(1.into()),
         )
-        .await;
+        .await
+        .unwrap();
     let (_tx_req, _tx_hash) =
         TransactionRequest::from_bytes(tx.raw_tx.as_ref(), L2ChainId::new(chain_id).unwrap())
             .unwrap();
 }
+
+#[test_log::test(tokio::test)]
+async fn pubdata_auto_selection() {
+    let tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        false,
+        true,
+        L1BatchCommitmentMode::Rollup,
+        SettlementLayer::L1(10.into()),
+    )
+    .await;
+    let config = SenderConfig {
+        pubdata_sending_mode: PubdataSendingMode::Blobs,
+        pubdata_auto_selection: true,
+        pubdata_auto_selection_max_blob_base_fee: Some(1_000),
+        pubdata_auto_selection_max_calldata_size: 100_000,
+        ..EthConfig::for_tests().sender.unwrap()
+    };
+    let selector = PubdataModeSelector::new(&config, tester.gas_adjuster.clone()).unwrap();
+
+    // Blobs are cheaper when blob gas is cheap.
+    let costs = PubdataCosts::estimate(50_000, 100, 1);
+    assert_eq!(costs.blobs, 131_072);
+    assert_eq!(costs.calldata, 50_000 * 17 * 100);
+    assert_eq!(
+        selector.select_for_costs(50_000, 1, costs),
+        PubdataSendingMode::Blobs
+    );
+
+    // Small pubdata is cheaper to publish via calldata during a blob fee spike.
+    let costs = PubdataCosts::estimate(1_000, 10, 500);
+    assert!(costs.calldata < costs.blobs);
+    assert_eq!(
+        selector.select_for_costs(1_000, 500, costs),
+        PubdataSendingMode::Calldata
+    );
+
+    // Blob base fee cap forces calldata regardless of the estimated costs.
+    let costs = PubdataCosts::estimate(50_000, 100, 2_000);
+    assert_eq!(
+        selector.select_for_costs(50_000, 2_000, costs),
+        PubdataSendingMode::Calldata
+    );
+
+    // Pubdata not fitting into calldata always uses blobs.
+    let costs = PubdataCosts::estimate(200_000, 1, 1_000_000);
+    assert_eq!(
+        selector.select_for_costs(200_000, 1_000_000, costs),
+        PubdataSendingMode::Blobs
+    );
+
+    let disabled_config = SenderConfig {
+        pubdata_auto_selection: false,
+        ..config.clone()
+    };
+    assert!(PubdataModeSelector::new(&disabled_config, tester.gas_adjuster.clone()).is_none());
+    let calldata_config = SenderConfig {
+        pubdata_sending_mode: PubdataSendingMode::Calldata,
+        ..config
+    };
+    assert!(PubdataModeSelector::new(&calldata_config, tester.gas_adjuster).is_none());
+}
+
+#[test_log::test(tokio::test)]
+async fn unconfirmed_txs_are_reassigned_to_another_operator() {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+        SettlementLayer::L1(10.into()),
+    )
+    .await;
+
+    let _genesis_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let first_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let second_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    first_l1_batch.commit(&mut tester, true).await;
+    let commit_tx = tester.save_commit_tx(second_l1_batch.number).await;
+    let prove_tx = tester.save_prove_tx(first_l1_batch.number).await;
+    assert_eq!((commit_tx.nonce.0, prove_tx.nonce.0), (1, 2));
+
+    let backup_operator = Address::repeat_byte(0x42);
+    let mut storage = tester.storage().await;
+    let moved_txs = storage
+        .eth_sender_dal()
+        .reassign_unconfirmed_txs(None, commit_tx.nonce.0.into(), Some(backup_operator), 7)
+        .await
+        .unwrap();
+    assert_eq!(moved_txs, 2);
+
+    let new_txs = storage
+        .eth_sender_dal()
+        .get_new_eth_txs(10, &Some(backup_operator), false)
+        .await
+        .unwrap();
+    let moved: Vec<_> = new_txs.iter().map(|tx| (tx.id, tx.nonce.0)).collect();
+    assert_eq!(moved, [(commit_tx.id, 7), (prove_tx.id, 8)]);
+
+    let last_sender = storage
+        .eth_sender_dal()
+        .get_sender_of_last_eth_tx(None, false)
+        .await
+        .unwrap();
+    assert_eq!(last_sender, Some(backup_operator));
+    let next_nonce = storage
+        .eth_sender_dal()
+        .get_next_nonce(Some(backup_operator), false)
+        .await
+        .unwrap();
+    assert_eq!(next_nonce, Some(9));
+    // The confirmed commit tx stays with the main operator.
+    let next_nonce = storage
+        .eth_sender_dal()
+        .get_next_nonce(None, false)
+        .await
+        .unwrap();
+    assert_eq!(next_nonce, Some(1));
+}
+
+#[test_log::test(tokio::test)]
+async fn more_l1_batches_are_aggregated_on_high_gas_price() {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+        SettlementLayer::L1(10.into()),
+    )
+    .await;
+
+    let _genesis_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    for _ in 0..3 {
+        TestL1Batch::sealed(&mut tester).await;
+    }
+    let pool = tester.conn.clone();
+    let mut storage = pool.connection().await.unwrap();
+    let mut l1_batches = vec![];
+    for number in 1..=3 {
+        let header = storage
+            .blocks_dal()
+            .get_l1_batch_header(L1BatchNumber(number))
+            .await
+            .unwrap()
+            .unwrap();
+        l1_batches.push(l1_batch_with_metadata(header));
+    }
+
+    // The mock L1 base fee is 100 wei, so the next block's base fee is estimated to be at least 87 wei.
+    for (threshold, l1_batch_count, expected) in
+        [(1_000, 3, Some(1)), (1_000, 1, Some(1)), (50, 3, None)]
+    {
+        let mut criterion = GasPriceAwareNumberCriterion {
+            op: AggregatedActionType::Commit,
+            limit: 1,
+            high_gas_price_limit: 4,
+            high_gas_price_threshold: threshold,
+            fee_provider: tester.gas_adjuster.clone(),
+        };
+        let last_l1_batch = criterion
+            .last_l1_batch_to_publish(
+                &mut storage,
+                &l1_batches[..l1_batch_count],
+                L1BatchNumber(3),
+                false,
+            )
+            .await;
+        assert_eq!(last_l1_batch, expected.map(L1BatchNumber), "{threshold}");
+    }
+
+    TestL1Batch::sealed(&mut tester).await;
+    let header = storage
+        .blocks_dal()
+        .get_l1_batch_header(L1BatchNumber(4))
+        .await
+        .unwrap()
+        .unwrap();
+    l1_batches.push(l1_batch_with_metadata(header));
+    let mut criterion = GasPriceAwareNumberCriterion {
+        op: AggregatedActionType::Commit,
+        limit: 1,
+        high_gas_price_limit: 4,
+        high_gas_price_threshold: 50,
+        fee_provider: tester.gas_adjuster.clone(),
+    };
+    let last_l1_batch = criterion
+        .last_l1_batch_to_publish(&mut storage, &l1_batches, L1BatchNumber(4), false)
+        .await;
+    assert_eq!(last_l1_batch, Some(L1BatchNumber(4)));
+}
+
+#[test_log::test(tokio::test)]
+async fn fee_bumps_use_configured_percent_and_cap() {
+    let tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        false,
+        true,
+        L1BatchCommitmentMode::Rollup,
+        SettlementLayer::L1(10.into()),
+    )
+    .await;
+    let mut fees_oracle = GasAdjusterFeesOracle {
+        gas_adjuster: tester.gas_adjuster.clone(),
+        max_acceptable_priority_fee_in_gwei: u64::MAX,
+        time_in_mempool_in_l1_blocks_cap: 0,
+        fee_bump_percent: 50,
+        max_fee_per_gas_cap: None,
+    };
+    let previous_sent_tx = Some(TxHistory {
+        id: 1,
+        eth_tx_id: 1,
+        base_fee_per_gas: 1_000,
+        priority_fee_per_gas: 2_000_000_000,
+        blob_base_fee_per_gas: None,
+        tx_hash: H256::zero(),
+        signed_raw_tx: vec![],
+        sent_at_block: Some(1),
+        max_gas_per_pubdata: None,
+        created_at_timestamp: 0,
+    });
+
+    let fees = fees_oracle
+        .calculate_fees(&previous_sent_tx, 0, OperatorType::NonBlob)
+        .unwrap();
+    assert_eq!(fees.base_fee_per_gas, 1_501);
+    assert_eq!(fees.priority_fee_per_gas, 3_000_000_001);
+
+    // Blob transactions are always bumped by at least 100%.
+    let previous_blob_tx = previous_sent_tx.clone().map(|tx| TxHistory {
+        base_fee_per_gas: 1_000_000_000_000,
+        blob_base_fee_per_gas: Some(1_000_000_000_000),
+        ..tx
+    });
+    let fees = fees_oracle
+        .calculate_fees(&previous_blob_tx, 0, OperatorType::Blob)
+        .unwrap();
+    assert_eq!(fees.base_fee_per_gas, 2_000_000_000_000);
+    assert_eq!(fees.priority_fee_per_gas, 4_000_000_000);
+    assert_eq!(fees.blob_base_fee_per_gas, Some(2_000_000_000_000));
+
+    fees_oracle.max_fee_per_gas_cap = Some(4_000_000_000);
+    fees_oracle
+        .calculate_fees(&previous_sent_tx, 0, OperatorType::NonBlob)
+        .unwrap();
+    fees_oracle.max_fee_per_gas_cap = Some(3_000_000_000);
+    let err = fees_oracle
+        .calculate_fees(&previous_sent_tx, 0, OperatorType::NonBlob)
+        .unwrap_err();
+    assert!(err.to_string().contains("cap"), "{err}");
+}
+
+#[tokio::test]
+async fn signing_for_unknown_operator_fails() {
+    let l1_interface = RealL1Interface {
+        ethereum_client: None,
+        ethereum_client_blobs: None,
+        additional_operators: vec![],
+        sl_client: None,
+        wait_confirmations: None,
+    };
+    let unknown_operator = Address::repeat_byte(0x42);
+    let err = l1_interface
+        .sign_cancellation_tx(Some(unknown_operator), Nonce(0), 100, 10)
+        .await
+        .unwrap_err();
+    assert_matches!(err, EthSenderError::UnknownOperator(addr) if addr == unknown_operator);
+}
@@ -1,9 +1,11 @@
 use std::{collections::HashMap, fmt, sync::Arc};
 
 use anyhow::Context;
+use tokio::sync::OnceCell;
 use zksync_contracts::{
-    bytecode_supplier_contract, getters_facet_contract, l1_asset_router_contract, l2_message_root,
-    state_transition_manager_contract, verifier_contract, wrapped_base_token_store_contract,
+    bytecode_supplier_contract, getters_facet_contract, l1_asset_router_contract,
+    l1_nullifier_contract, l2_message_root, state_transition_manager_contract, verifier_contract,
+    wrapped_base_token_store_contract,
 };
 use zksync_eth_client::{
     clients::{DynClient, L1},
@@ -80,6 +82,16 @@ pub trait EthClient: 'static + fmt::Debug + Send + Sync {
         block_number: U64,
         l2_chain_id: L2ChainId,
     ) -> Result<H256, ContractCallError>;
+
+    /// Checks whether the L2-to-L1 message with the specified index in the L2-to-L1 logs Merkle tree of an L1 batch
+    /// was consumed on L1 (i.e., the corresponding withdrawal was finalized) as of `block_number`.
+    /// Returns `None` if the L1 shared bridge is not known.
+    async fn is_withdrawal_finalized(
+        &self,
+        block_number: u64,
+        l1_batch_number: L1BatchNumber,
+        l2_message_index: u32,
+    ) -> Result<Option<bool>, ContractCallError>;
 }
 
 // This constant is used for reading auxiliary events
@@ -102,6 +114,8 @@ pub struct EthHttpQueryClient<Net: Network> {
     bytecode_supplier_addr: Option<Address>,
     wrapped_base_token_store: Option<Address>,
     l1_shared_bridge_addr: Option<Address>,
+    /// Address of the L1 nullifier referenced by the L1 shared bridge. Immutable, so it's fetched once and cached.
+    l1_nullifier_addr: OnceCell<Address>,
     // Only present for post-shared bridge chains.
     state_transition_manager_address: Option<Address>,
     server_notifier_address: Option<Address>,
@@ -110,6 +124,7 @@ pub struct EthHttpQueryClient<Net: Network> {
     getters_facet_contract_abi: Contract,
     message_root_abi: Contract,
     l1_asset_router_abi: Contract,
+    l1_nullifier_abi: Contract,
     wrapped_base_token_store_abi: Contract,
     confirmations_for_eth_event: Option<u64>,
     l2_chain_id: L2ChainId,
@@ -158,10 +173,12 @@ where
             getters_facet_contract_abi: getters_facet_contract(),
             message_root_abi: l2_message_root(),
             l1_asset_router_abi: l1_asset_router_contract(),
+            l1_nullifier_abi: l1_nullifier_contract(),
             wrapped_base_token_store_abi: wrapped_base_token_store_contract(),
             confirmations_for_eth_event,
             wrapped_base_token_store,
             l1_shared_bridge_addr,
+            l1_nullifier_addr: OnceCell::new(),
             l2_chain_id,
         }
     }
@@ -459,6 +476,40 @@ where
             .await
     }
 
+    async fn is_withdrawal_finalized(
+        &self,
+        block_number: u64,
+        l1_batch_number: L1BatchNumber,
+        l2_message_index: u32,
+    ) -> Result<Option<bool>, ContractCallError> {
+        let Some(l1_shared_bridge_addr) = self.l1_shared_bridge_addr else {
+            return Ok(None);
+        };
+
+        // Withdrawal finalization is recorded by the L1 nullifier, which is referenced by the L1 asset router.
+        let l1_nullifier_addr = *self
+            .l1_nullifier_addr
+            .get_or_try_init(|| async {
+                CallFunctionArgs::new("L1_NULLIFIER", ())
+                    .for_contract(l1_shared_bridge_addr, &self.l1_asset_router_abi)
+                    .call(&self.client)
+                    .await
+            })
+            .await?;
+        let block_id = BlockId::Number(block_number.into());
+        let args = (
+            U256::from(self.l2_chain_id.as_u64()),
+            U256::from(l1_batch_number.0),
+            U256::from(l2_message_index),
+        );
+        CallFunctionArgs::new("isWithdrawalFinalized", args)
+            .with_block(block_id)
+            .for_contract(l1_nullifier_addr, &self.l1_nullifier_abi)
+            .call(&self.client)
+            .await
+            .map(Some)
+    }
+
     async fn get_chain_gateway_upgrade_info(
         &self,
     ) -> Result<Option<ZkChainSpecificUpgradeData>, ContractCallError> {
@@ -2,13 +2,14 @@ use std::fmt;
 
 use zksync_dal::{eth_watcher_dal::EventType, Connection, Core};
 use zksync_eth_client::{ContractCallError, EnrichedClientError};
-use zksync_types::{api::Log, H256};
+use zksync_types::{api::Log, PriorityOpId, H256};
 
 pub(crate) use self::{
     appended_chain_batch_root::BatchRootProcessor,
     decentralized_upgrades::DecentralizedUpgradesEventProcessor,
     gateway_migration::GatewayMigrationProcessor, priority_ops::PriorityOpsEventProcessor,
 };
+use crate::client::EthClient;
 
 mod appended_chain_batch_root;
 mod decentralized_upgrades;
@@ -48,6 +49,20 @@ impl EventProcessorError {
     }
 }
 
+/// Details of an L1 reorg detected by an [`EventProcessor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct L1Reorg {
+    /// First L1 block containing a reverted event.
+    pub first_reverted_block: u64,
+    /// ID of the first reverted priority operation.
+    pub first_reverted_priority_op: PriorityOpId,
+    /// Number of rolled back priority operations.
+    pub reverted_priority_ops: usize,
+    /// Whether rolled back operations were loaded into the mempool, so that the node must be restarted
+    /// for the mempool to be rebuilt.
+    pub requires_restart: bool,
+}
+
 /// Processor for a single type of events emitted by the L1 contract. [`EthWatch`](crate::EthWatch)
 /// feeds events to all processors one-by-one.
 #[async_trait::async_trait]
@@ -60,6 +75,18 @@ pub(super) trait EventProcessor: 'static + fmt::Debug + Send + Sync {
         events: Vec<Log>,
     ) -> Result<usize, EventProcessorError>;
 
+    /// Checks whether events processed by this processor were reverted by an L1 reorg deeper than the confirmation
+    /// threshold, and rolls back the corresponding state. Returns the rollback details if anything was reverted;
+    /// in this case, events must be re-fetched starting from [`L1Reorg::first_reverted_block`].
+    async fn check_for_reorg(
+        &mut self,
+        _storage: &mut Connection<'_, Core>,
+        _client: &dyn EthClient,
+        _to_block: u64,
+    ) -> Result<Option<L1Reorg>, EventProcessorError> {
+        Ok(None)
+    }
+
     /// Relevant topic1 which defines what events to be processed
     fn topic1(&self) -> Option<H256>;
 
@@ -1,14 +1,16 @@
-use std::{convert::TryFrom, sync::Arc};
+use std::{collections::HashMap, convert::TryFrom, sync::Arc};
 
 use anyhow::Context;
 use zksync_contracts::hyperchain_contract;
 use zksync_dal::{eth_watcher_dal::EventType, Connection, Core, CoreDal, DalError};
 use zksync_shared_metrics::{TxStage, APP_METRICS};
-use zksync_types::{api::Log, l1::L1Tx, PriorityOpId, H256};
+use zksync_types::{
+    api::Log, l1::L1Tx, web3::BlockNumber as Web3BlockNumber, L1BlockNumber, PriorityOpId, H256,
+};
 
 use crate::{
-    client::EthClient,
-    event_processors::{EventProcessor, EventProcessorError, EventsSource},
+    client::{EthClient, RETRY_LIMIT},
+    event_processors::{EventProcessor, EventProcessorError, EventsSource, L1Reorg},
     metrics::{PollStage, METRICS},
 };
 
@@ -18,12 +20,14 @@ pub struct PriorityOpsEventProcessor {
     next_expected_priority_id: PriorityOpId,
     new_priority_request_signature: H256,
     sl_client: Arc<dyn EthClient>,
+    reorg_check_depth: Option<u64>,
 }
 
 impl PriorityOpsEventProcessor {
     pub fn new(
         next_expected_priority_id: PriorityOpId,
         sl_client: Arc<dyn EthClient>,
+        reorg_check_depth: Option<u64>,
     ) -> anyhow::Result<Self> {
         Ok(Self {
             next_expected_priority_id,
@@ -32,8 +36,15 @@ impl PriorityOpsEventProcessor {
                 .context("NewPriorityRequest event is missing in ABI")?
                 .signature(),
             sl_client,
+            reorg_check_depth,
         })
     }
+
+    fn parse_priority_op(&self, event: Log) -> Result<L1Tx, EventProcessorError> {
+        assert_eq!(event.topics[0], self.new_priority_request_signature); // guaranteed by the watcher
+        L1Tx::try_from(Into::<zksync_types::web3::Log>::into(event))
+            .map_err(|err| EventProcessorError::log_parse(err, "priority op"))
+    }
 }
 
 #[async_trait::async_trait]
@@ -46,10 +57,7 @@ impl EventProcessor for PriorityOpsEventProcessor {
         let mut priority_ops = Vec::new();
         let events_count = events.len();
         for event in events {
-            assert_eq!(event.topics[0], self.new_priority_request_signature); // guaranteed by the watcher
-            let tx = L1Tx::try_from(Into::<zksync_types::web3::Log>::into(event))
-                .map_err(|err| EventProcessorError::log_parse(err, "priority op"))?;
-            priority_ops.push(tx);
+            priority_ops.push(self.parse_priority_op(event)?);
         }
 
         if priority_ops.is_empty() {
@@ -109,6 +117,84 @@ impl EventProcessor for PriorityOpsEventProcessor {
         Ok(skipped_ops + ops_to_insert.len())
     }
 
+    async fn check_for_reorg(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        client: &dyn EthClient,
+        to_block: u64,
+    ) -> Result<Option<L1Reorg>, EventProcessorError> {
+        let Some(depth) = self.reorg_check_depth else {
+            return Ok(None);
+        };
+        let from_block = to_block.saturating_sub(depth);
+        let stored_ops = storage
+            .transactions_dal()
+            .get_priority_ops_received_since(L1BlockNumber(from_block as u32))
+            .await
+            .map_err(DalError::generalize)?;
+        if stored_ops.is_empty() {
+            return Ok(None);
+        }
+
+        let events = client
+            .get_events(
+                Web3BlockNumber::Number(from_block.into()),
+                Web3BlockNumber::Number(to_block.into()),
+                Some(self.new_priority_request_signature),
+                None,
+                RETRY_LIMIT,
+            )
+            .await?;
+        let mut canonical_hashes = HashMap::with_capacity(events.len());
+        for event in events {
+            let op = self.parse_priority_op(event)?;
+            canonical_hashes.insert(op.serial_id(), op.hash());
+        }
+
+        // Operations are ordered by ID, so all operations after the first reverted one are affected as well.
+        let Some(first_reverted_idx) = stored_ops
+            .iter()
+            .position(|op| canonical_hashes.get(&op.id) != Some(&op.hash))
+        else {
+            return Ok(None);
+        };
+        let reverted_ops = &stored_ops[first_reverted_idx..];
+        let first_reverted = &reverted_ops[0];
+        tracing::warn!(
+            "Detected L1 reorg reverting {} priority ops starting from #{} (L1 block {})",
+            reverted_ops.len(),
+            first_reverted.id,
+            first_reverted.l1_block_number
+        );
+
+        if let Some(included_op) = reverted_ops.iter().find(|op| op.is_included) {
+            return Err(EventProcessorError::Internal(anyhow::anyhow!(
+                "priority op #{} reverted by L1 reorg is already included into an L2 block; \
+                 manual intervention is required",
+                included_op.id
+            )));
+        }
+
+        let removed_count = storage
+            .transactions_dal()
+            .remove_priority_ops_since(first_reverted.id)
+            .await
+            .map_err(DalError::generalize)?;
+        self.next_expected_priority_id = first_reverted.id;
+        METRICS.reorged_priority_ops.inc_by(removed_count as u64);
+
+        Ok(Some(L1Reorg {
+            first_reverted_block: reverted_ops
+                .iter()
+                .map(|op| op.l1_block_number.0.into())
+                .min()
+                .unwrap_or(from_block),
+            first_reverted_priority_op: first_reverted.id,
+            reverted_priority_ops: removed_count,
+            requires_restart: reverted_ops.iter().any(|op| op.in_mempool),
+        }))
+    }
+
     fn topic1(&self) -> Option<H256> {
         Some(self.new_priority_request_signature)
     }
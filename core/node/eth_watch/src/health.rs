@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use zksync_health_check::{Health, HealthStatus};
+use zksync_types::PriorityOpId;
+
+use crate::event_processors::L1Reorg;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L1ReorgDetails {
+    pub first_reverted_l1_block: u64,
+    pub first_reverted_priority_op: PriorityOpId,
+    pub reverted_priority_ops: usize,
+}
+
+impl From<L1Reorg> for L1ReorgDetails {
+    fn from(reorg: L1Reorg) -> Self {
+        Self {
+            first_reverted_l1_block: reorg.first_reverted_block,
+            first_reverted_priority_op: reorg.first_reverted_priority_op,
+            reverted_priority_ops: reorg.reverted_priority_ops,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct EthWatchHealthDetails {
+    /// Last L1 reorg that led to priority operations being rolled back and replayed.
+    pub last_l1_reorg: Option<L1ReorgDetails>,
+}
+
+impl From<&EthWatchHealthDetails> for Health {
+    fn from(details: &EthWatchHealthDetails) -> Self {
+        Self::from(HealthStatus::Ready).with_details(details)
+    }
+}
@@ -7,6 +7,7 @@ use std::{sync::Arc, time::Duration};
 use anyhow::Context as _;
 use tokio::sync::watch;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
+use zksync_health_check::{Health, HealthUpdater, ReactiveHealthCheck};
 use zksync_mini_merkle_tree::MiniMerkleTree;
 use zksync_system_constants::PRIORITY_EXPIRATION;
 use zksync_types::{
@@ -14,12 +15,16 @@ use zksync_types::{
     web3::BlockNumber as Web3BlockNumber, L1BatchNumber, L2ChainId, PriorityOpId,
 };
 
-pub use self::client::{EthClient, EthHttpQueryClient, GetLogsClient, ZkSyncExtentionEthClient};
 use self::{
     client::RETRY_LIMIT,
     event_processors::{EventProcessor, EventProcessorError, PriorityOpsEventProcessor},
+    health::EthWatchHealthDetails,
     metrics::METRICS,
 };
+pub use self::{
+    client::{EthClient, EthHttpQueryClient, GetLogsClient, ZkSyncExtentionEthClient},
+    message_tracker::{L2ToL1MessageTracker, L2ToL1MessageTrackerConfig},
+};
 use crate::event_processors::{
     BatchRootProcessor, DecentralizedUpgradesEventProcessor, EventsSource,
     GatewayMigrationProcessor,
@@ -27,6 +32,8 @@ use crate::event_processors::{
 
 mod client;
 mod event_processors;
+mod health;
+mod message_tracker;
 mod metrics;
 #[cfg(test)]
 mod tests;
@@ -47,6 +54,7 @@ pub struct EthWatch {
     poll_interval: Duration,
     event_processors: Vec<Box<dyn EventProcessor>>,
     pool: ConnectionPool<Core>,
+    health_updater: HealthUpdater,
 }
 
 impl EthWatch {
@@ -58,6 +66,7 @@ impl EthWatch {
         pool: ConnectionPool<Core>,
         poll_interval: Duration,
         chain_id: L2ChainId,
+        reorg_check_depth: Option<u64>,
     ) -> anyhow::Result<Self> {
         let mut storage = pool.connection_tagged("eth_watch").await?;
         let l1_client: Arc<dyn EthClient> = l1_client.into();
@@ -69,8 +78,11 @@ impl EthWatch {
 
         drop(storage);
 
-        let priority_ops_processor =
-            PriorityOpsEventProcessor::new(state.next_expected_priority_id, sl_eth_client.clone())?;
+        let priority_ops_processor = PriorityOpsEventProcessor::new(
+            state.next_expected_priority_id,
+            sl_eth_client.clone(),
+            reorg_check_depth,
+        )?;
         let decentralized_upgrades_processor = DecentralizedUpgradesEventProcessor::new(
             state.last_seen_protocol_version,
             sl_eth_client.clone(),
@@ -100,9 +112,15 @@ impl EthWatch {
             poll_interval,
             event_processors,
             pool,
+            health_updater: ReactiveHealthCheck::new("eth_watch").1,
         })
     }
 
+    /// Returns the health check for the watcher.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
     #[tracing::instrument(name = "EthWatch::initialize_state", skip_all)]
     async fn initialize_state(
         storage: &mut Connection<'_, Core>,
@@ -146,6 +164,8 @@ impl EthWatch {
     pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let mut timer = tokio::time::interval(self.poll_interval);
         let pool = self.pool.clone();
+        self.health_updater
+            .update(Health::from(&EthWatchHealthDetails::default()));
 
         while !*stop_receiver.borrow_and_update() {
             tokio::select! {
@@ -193,6 +213,28 @@ impl EthWatch {
                 client.confirmed_block_number().await?
             };
 
+            if let Some(reorg) = processor.check_for_reorg(storage, client, to_block).await? {
+                storage
+                    .eth_watcher_dal()
+                    .update_next_block_to_process(
+                        processor.event_type(),
+                        chain_id,
+                        reorg.first_reverted_block,
+                    )
+                    .await
+                    .map_err(DalError::generalize)?;
+                self.health_updater
+                    .update(Health::from(&EthWatchHealthDetails {
+                        last_l1_reorg: Some(reorg.into()),
+                    }));
+                if reorg.requires_restart {
+                    return Err(EventProcessorError::Internal(anyhow::anyhow!(
+                        "priority ops reverted by L1 reorg were loaded into the mempool; \
+                         restarting so that they are replayed from L1"
+                    )));
+                }
+            }
+
             let from_block = storage
                 .eth_watcher_dal()
                 .get_or_set_next_block_to_process(
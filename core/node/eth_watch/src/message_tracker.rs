@@ -0,0 +1,194 @@
+//! Tracking of the outgoing L2-to-L1 message queue.
+
+use std::{sync::Arc, time::Duration};
+
+use tokio::sync::watch;
+use zksync_config::EthWatchConfig;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
+use zksync_system_constants::{L2_ASSET_ROUTER_ADDRESS, L2_BASE_TOKEN_ADDRESS};
+use zksync_types::{Address, L1BatchNumber, L1BlockNumber};
+
+use crate::{client::EthClient, event_processors::EventProcessorError, metrics::METRICS};
+
+/// Max number of L1 batches which messages are registered for tracking during a single iteration.
+const MAX_BATCHES_PER_ITERATION: u32 = 100;
+/// Max number of messages checked for consumption on L1 during a single iteration.
+const MAX_MESSAGES_PER_ITERATION: usize = 50;
+
+/// Configuration of [`L2ToL1MessageTracker`].
+#[derive(Debug, Clone)]
+pub struct L2ToL1MessageTrackerConfig {
+    /// Interval between tracker iterations.
+    pub poll_interval: Duration,
+    /// First L1 batch which messages are tracked. If `None`, tracking starts from the last L1 batch executed on L1
+    /// at the moment the tracker is first run.
+    pub first_l1_batch: Option<L1BatchNumber>,
+    /// Number of the latest tracked L1 batches for which message statuses are retained.
+    pub retention_l1_batches: u32,
+}
+
+impl L2ToL1MessageTrackerConfig {
+    pub fn new(config: &EthWatchConfig) -> Self {
+        Self {
+            poll_interval: config.l2_to_l1_message_tracking_interval(),
+            first_l1_batch: config
+                .l2_to_l1_message_tracking_first_l1_batch
+                .map(L1BatchNumber),
+            retention_l1_batches: config.l2_to_l1_message_retention_l1_batches,
+        }
+    }
+}
+
+/// Tracks consumption on L1 of the L2-to-L1 messages sent by the bridge contracts (i.e., finalization of withdrawals)
+/// in L1 batches executed on L1. The consumption status is persisted in Postgres and exposed via the API server.
+///
+/// The tracker runs independently of [`EthWatch`](crate::EthWatch), so that failures when checking messages
+/// do not affect processing of priority operations and upgrades.
+#[derive(Debug)]
+pub struct L2ToL1MessageTracker {
+    l1_client: Arc<dyn EthClient>,
+    pool: ConnectionPool<Core>,
+    config: L2ToL1MessageTrackerConfig,
+}
+
+impl L2ToL1MessageTracker {
+    /// Senders of the tracked messages.
+    pub(crate) const SENDERS: [Address; 2] = [L2_ASSET_ROUTER_ADDRESS, L2_BASE_TOKEN_ADDRESS];
+
+    pub fn new(
+        l1_client: Box<dyn EthClient>,
+        pool: ConnectionPool<Core>,
+        config: L2ToL1MessageTrackerConfig,
+    ) -> Self {
+        Self {
+            l1_client: l1_client.into(),
+            pool,
+            config,
+        }
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let mut timer = tokio::time::interval(self.config.poll_interval);
+        while !*stop_receiver.borrow_and_update() {
+            tokio::select! {
+                _ = timer.tick() => { /* continue iterations */ }
+                _ = stop_receiver.changed() => break,
+            }
+
+            let mut storage = self.pool.connection_tagged("eth_watch").await?;
+            match self.update(&mut storage).await {
+                Ok(()) => {}
+                Err(EventProcessorError::Internal(err)) => {
+                    tracing::error!("Internal error tracking L2-to-L1 messages: {err:?}");
+                    return Err(err);
+                }
+                Err(err) => {
+                    // Consumption statuses are informational, so transient errors are retried on the next iteration.
+                    tracing::warn!("Failed tracking L2-to-L1 messages: {err}");
+                }
+            }
+        }
+
+        tracing::info!("Stop signal received, L2-to-L1 message tracker is shutting down");
+        Ok(())
+    }
+
+    pub(crate) async fn update(
+        &self,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<(), EventProcessorError> {
+        let last_executed_batch = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await
+            .map_err(DalError::generalize)?;
+        if let Some(last_executed_batch) = last_executed_batch {
+            self.track_new_messages(storage, last_executed_batch)
+                .await?;
+        }
+
+        let messages = storage
+            .l2_to_l1_messages_dal()
+            .get_unconsumed_messages(MAX_MESSAGES_PER_ITERATION)
+            .await
+            .map_err(DalError::generalize)?;
+        if messages.is_empty() {
+            return Ok(());
+        }
+
+        let l1_block_number = self.l1_client.confirmed_block_number().await?;
+        let mut pending_messages = vec![];
+        let mut consumed_messages = vec![];
+        for (l1_batch_number, index) in messages {
+            let is_finalized = self
+                .l1_client
+                .is_withdrawal_finalized(l1_block_number, l1_batch_number, index)
+                .await?;
+            match is_finalized {
+                // The L1 shared bridge is unknown, so there's no way to check consumption.
+                None => return Ok(()),
+                Some(true) => consumed_messages.push((l1_batch_number, index)),
+                Some(false) => pending_messages.push((l1_batch_number, index)),
+            }
+        }
+
+        let mut dal = storage.l2_to_l1_messages_dal();
+        dal.mark_messages_checked(&pending_messages, None)
+            .await
+            .map_err(DalError::generalize)?;
+        let consumed_at = L1BlockNumber(l1_block_number as u32);
+        dal.mark_messages_checked(&consumed_messages, Some(consumed_at))
+            .await
+            .map_err(DalError::generalize)?;
+        METRICS
+            .consumed_l2_to_l1_messages
+            .inc_by(consumed_messages.len() as u64);
+        Ok(())
+    }
+
+    async fn track_new_messages(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        last_executed_batch: L1BatchNumber,
+    ) -> Result<(), EventProcessorError> {
+        let last_tracked_batch = storage
+            .l2_to_l1_messages_dal()
+            .get_last_tracked_l1_batch()
+            .await
+            .map_err(DalError::generalize)?;
+        let next_batch = match (last_tracked_batch, self.config.first_l1_batch) {
+            (Some(last_tracked), Some(first)) => (last_tracked + 1).max(first),
+            (Some(last_tracked), None) => last_tracked + 1,
+            (None, Some(first)) => first,
+            (None, None) => last_executed_batch,
+        };
+        if next_batch > last_executed_batch {
+            return Ok(());
+        }
+
+        let last_batch = last_executed_batch.min(next_batch + MAX_BATCHES_PER_ITERATION - 1);
+        let mut dal = storage.l2_to_l1_messages_dal();
+        let tracked_count = dal
+            .track_messages(next_batch..=last_batch, &Self::SENDERS)
+            .await
+            .map_err(DalError::generalize)?;
+        tracing::debug!(
+            "Started tracking {tracked_count} L2-to-L1 messages in L1 batches {next_batch}..={last_batch}"
+        );
+
+        if let Some(first_retained_batch) =
+            (last_batch.0 + 1).checked_sub(self.config.retention_l1_batches)
+        {
+            let pruned_count = dal
+                .prune_messages(L1BatchNumber(first_retained_batch))
+                .await
+                .map_err(DalError::generalize)?;
+            if pruned_count > 0 {
+                tracing::debug!(
+                    "Pruned {pruned_count} L2-to-L1 messages in L1 batches before #{first_retained_batch}"
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -19,6 +19,10 @@ pub(super) struct EthWatcherMetrics {
     /// Latency of polling and processing events split by stage.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub poll_eth_node: Family<PollStage, Histogram<Duration>>,
+    /// Number of priority operations rolled back because of L1 reorgs.
+    pub reorged_priority_ops: Counter,
+    /// Number of L2-to-L1 messages observed to be consumed on L1.
+    pub consumed_l2_to_l1_messages: Counter,
 }
 
 #[vise::register]
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, convert::TryInto, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryInto,
+    sync::Arc,
+};
 
 use tokio::sync::RwLock;
 use zksync_contracts::{
@@ -33,6 +37,7 @@ pub struct FakeEthClientData {
     batch_roots: HashMap<u64, Vec<Log>>,
     chain_roots: HashMap<u64, H256>,
     bytecode_preimages: HashMap<H256, Vec<u8>>,
+    finalized_withdrawals: HashSet<(L1BatchNumber, u32)>,
 }
 
 impl FakeEthClientData {
@@ -48,6 +53,7 @@ impl FakeEthClientData {
             batch_roots: Default::default(),
             chain_roots: Default::default(),
             bytecode_preimages: Default::default(),
+            finalized_withdrawals: Default::default(),
         }
     }
 
@@ -62,6 +68,19 @@ impl FakeEthClientData {
         }
     }
 
+    fn revert_transactions_since_block(&mut self, first_reverted_block: u64) {
+        let reverted_blocks: Vec<_> = self
+            .transactions
+            .keys()
+            .copied()
+            .filter(|&block| block >= first_reverted_block)
+            .collect();
+        for block in reverted_blocks {
+            let reverted = self.transactions.remove(&block).unwrap();
+            self.processed_priority_transactions_count -= reverted.len() as u64;
+        }
+    }
+
     fn add_upgrade_timestamp(&mut self, upgrades: &[(ProtocolUpgrade, u64)]) {
         for (upgrade, eth_block) in upgrades {
             self.upgrade_timestamp
@@ -138,6 +157,14 @@ impl MockEthClient {
         self.inner.write().await.add_transactions(transactions);
     }
 
+    /// Emulates an L1 reorg reverting all priority transactions starting from the specified block.
+    pub async fn revert_transactions_since_block(&mut self, first_reverted_block: u64) {
+        self.inner
+            .write()
+            .await
+            .revert_transactions_since_block(first_reverted_block);
+    }
+
     pub async fn add_upgrade_timestamp(&mut self, upgrades: &[(ProtocolUpgrade, u64)]) {
         self.inner.write().await.add_upgrade_timestamp(upgrades);
     }
@@ -184,6 +211,14 @@ impl MockEthClient {
             .await
             .add_chain_log_proofs(chain_log_proofs);
     }
+
+    pub async fn finalize_withdrawal(&mut self, l1_batch_number: L1BatchNumber, index: u32) {
+        self.inner
+            .write()
+            .await
+            .finalized_withdrawals
+            .insert((l1_batch_number, index));
+    }
 }
 
 #[async_trait::async_trait]
@@ -331,6 +366,18 @@ impl EthClient for MockEthClient {
     ) -> Result<Option<H256>, ContractCallError> {
         Ok(Some(H256::zero()))
     }
+
+    async fn is_withdrawal_finalized(
+        &self,
+        _block_number: u64,
+        l1_batch_number: L1BatchNumber,
+        l2_message_index: u32,
+    ) -> Result<Option<bool>, ContractCallError> {
+        let finalized_withdrawals = &self.inner.read().await.finalized_withdrawals;
+        Ok(Some(
+            finalized_withdrawals.contains(&(l1_batch_number, l2_message_index)),
+        ))
+    }
 }
 
 #[async_trait::async_trait]
@@ -1,22 +1,29 @@
 use std::convert::TryInto;
 
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_system_constants::{L1_MESSENGER_ADDRESS, L2_ASSET_ROUTER_ADDRESS};
 use zksync_types::{
-    abi,
+    abi, address_to_h256,
     aggregated_operations::AggregatedActionType,
-    api::ChainAggProof,
-    block::L1BatchHeader,
+    api::{ChainAggProof, L2ToL1MessageConsumption},
+    block::{L1BatchHeader, L2BlockHeader},
     commitment::L1BatchCommitmentArtifacts,
+    fee_model::BatchFeeInput,
     l1::{L1Tx, OpProcessingType, PriorityQueueType},
-    l2_to_l1_log::BatchAndChainMerklePath,
+    l2_to_l1_log::{BatchAndChainMerklePath, L2ToL1Log, UserL2ToL1Log},
     protocol_upgrade::{ProtocolUpgradeTx, ProtocolUpgradeTxCommonData},
     protocol_version::ProtocolSemanticVersion,
     settlement::SettlementLayer,
-    Address, Execute, L1BatchNumber, L1TxCommonData, L2ChainId, PriorityOpId, ProtocolUpgrade,
-    ProtocolVersion, ProtocolVersionId, SLChainId, Transaction, H256, U256,
+    tx::IncludedTxLocation,
+    Address, Execute, L1BatchNumber, L1BlockNumber, L1TxCommonData, L2BlockNumber, L2ChainId,
+    PriorityOpId, ProtocolUpgrade, ProtocolVersion, ProtocolVersionId, SLChainId, Transaction,
+    H256, U256,
 };
 
-use crate::{tests::client::MockEthClient, EthWatch, ZkSyncExtentionEthClient};
+use crate::{
+    tests::client::MockEthClient, EthWatch, L2ToL1MessageTracker, L2ToL1MessageTrackerConfig,
+    ZkSyncExtentionEthClient,
+};
 
 mod client;
 
@@ -110,6 +117,7 @@ async fn create_test_watcher(
         connection_pool,
         std::time::Duration::from_nanos(1),
         L2ChainId::default(),
+        None,
     )
     .await
     .unwrap();
@@ -170,6 +178,61 @@ async fn test_normal_operation_l1_txs() {
     assert_eq!(db_tx.common_data.serial_id.0, 2);
 }
 
+#[test_log::test(tokio::test)]
+async fn priority_ops_are_replayed_after_deep_l1_reorg() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let mut client = MockEthClient::new(SLChainId(42));
+    let mut watcher = EthWatch::new(
+        Box::new(client.clone()),
+        Box::new(client.clone()),
+        SettlementLayer::L1(SL_CHAIN_ID),
+        connection_pool.clone(),
+        std::time::Duration::from_nanos(1),
+        L2ChainId::default(),
+        Some(10),
+    )
+    .await
+    .unwrap();
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 14)])
+        .await;
+    client.set_last_finalized_block_number(20).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    let db_ops = storage
+        .transactions_dal()
+        .get_priority_ops_received_since(L1BlockNumber(0))
+        .await
+        .unwrap();
+    assert_eq!(db_ops.len(), 2);
+
+    // The reorg is deeper than the confirmation threshold, so the second op is reverted after it was processed.
+    client.revert_transactions_since_block(12).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    let db_ops = storage
+        .transactions_dal()
+        .get_priority_ops_received_since(L1BlockNumber(0))
+        .await
+        .unwrap();
+    assert_eq!(db_ops.len(), 1);
+    assert_eq!(db_ops[0].id, PriorityOpId(0));
+
+    // The reverted op is re-included into a later L1 block and is replayed.
+    client.add_transactions(&[build_l1_tx(1, 22)]).await;
+    client.set_last_finalized_block_number(25).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    let db_ops = storage
+        .transactions_dal()
+        .get_priority_ops_received_since(L1BlockNumber(0))
+        .await
+        .unwrap();
+    assert_eq!(db_ops.len(), 2);
+    assert_eq!(db_ops[1].id, PriorityOpId(1));
+    assert_eq!(db_ops[1].l1_block_number, L1BlockNumber(22));
+}
+
 #[test_log::test(tokio::test)]
 async fn test_gap_in_upgrade_timestamp() {
     let connection_pool = ConnectionPool::<Core>::test_pool().await;
@@ -217,6 +280,7 @@ async fn test_normal_operation_upgrade_timestamp() {
         connection_pool.clone(),
         std::time::Duration::from_nanos(1),
         L2ChainId::default(),
+        None,
     )
     .await
     .unwrap();
@@ -440,6 +504,178 @@ async fn test_transactions_get_gradually_processed_by_gateway() {
     assert_eq!(tx.common_data.serial_id.0, 3);
 }
 
+/// Creates an L1 batch executed on L1 with a single withdrawal message. Returns the hash of the withdrawal transaction.
+async fn setup_executed_batch_with_withdrawal(storage: &mut Connection<'_, Core>) -> H256 {
+    let protocol_version = (ProtocolVersionId::latest() as u16 - 1).try_into().unwrap();
+    let l2_block = L2BlockHeader {
+        number: L2BlockNumber(1),
+        timestamp: 1,
+        hash: H256::repeat_byte(1),
+        l1_tx_count: 0,
+        l2_tx_count: 1,
+        fee_account_address: Address::zero(),
+        base_fee_per_gas: 100,
+        batch_fee_input: BatchFeeInput::l1_pegged(100, 100),
+        gas_per_pubdata_limit: 100,
+        base_system_contracts_hashes: Default::default(),
+        protocol_version: Some(protocol_version),
+        virtual_blocks: 1,
+        gas_limit: 0,
+        logs_bloom: Default::default(),
+        pubdata_params: Default::default(),
+    };
+    storage
+        .blocks_dal()
+        .insert_l2_block(&l2_block)
+        .await
+        .unwrap();
+
+    let tx_hash = H256::repeat_byte(0x11);
+    let withdrawal_log = UserL2ToL1Log(L2ToL1Log {
+        shard_id: 0,
+        is_service: true,
+        tx_number_in_block: 0,
+        sender: L1_MESSENGER_ADDRESS,
+        key: address_to_h256(&L2_ASSET_ROUTER_ADDRESS),
+        value: H256::repeat_byte(0x22),
+    });
+    let tx_location = IncludedTxLocation {
+        tx_hash,
+        tx_index_in_l2_block: 0,
+    };
+    storage
+        .events_dal()
+        .save_user_l2_to_l1_logs(l2_block.number, &[(tx_location, vec![&withdrawal_log])])
+        .await
+        .unwrap();
+
+    let header = L1BatchHeader::new(L1BatchNumber(1), 1, Default::default(), protocol_version);
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&header)
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .mark_l2_blocks_as_executed_in_l1_batch(L1BatchNumber(1))
+        .await
+        .unwrap();
+
+    let eth_tx_id = storage
+        .eth_sender_dal()
+        .save_eth_tx(
+            0,
+            vec![],
+            AggregatedActionType::Execute,
+            Address::default(),
+            None,
+            None,
+            None,
+            false,
+        )
+        .await
+        .unwrap()
+        .id;
+    storage
+        .blocks_dal()
+        .set_eth_tx_id(
+            L1BatchNumber(1)..=L1BatchNumber(1),
+            eth_tx_id,
+            AggregatedActionType::Execute,
+        )
+        .await
+        .unwrap();
+    let eth_tx_hash = H256::repeat_byte(0x33);
+    storage
+        .eth_sender_dal()
+        .insert_tx_history(eth_tx_id, 0, 0, None, None, eth_tx_hash, &[], 0, None)
+        .await
+        .unwrap();
+    storage
+        .eth_sender_dal()
+        .confirm_tx(eth_tx_hash, U256::zero())
+        .await
+        .unwrap();
+    tx_hash
+}
+
+fn create_message_tracker(
+    connection_pool: ConnectionPool<Core>,
+    client: &MockEthClient,
+    first_l1_batch: Option<L1BatchNumber>,
+    retention_l1_batches: u32,
+) -> L2ToL1MessageTracker {
+    let config = L2ToL1MessageTrackerConfig {
+        poll_interval: std::time::Duration::from_millis(10),
+        first_l1_batch,
+        retention_l1_batches,
+    };
+    L2ToL1MessageTracker::new(Box::new(client.clone()), connection_pool, config)
+}
+
+#[test_log::test(tokio::test)]
+async fn tracking_l2_to_l1_message_consumption() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let mut connection = connection_pool.connection().await.unwrap();
+    let tx_hash = setup_executed_batch_with_withdrawal(&mut connection).await;
+    let (_, mut client) = create_l1_test_watcher(connection_pool.clone()).await;
+    let tracker = create_message_tracker(connection_pool.clone(), &client, None, 100);
+
+    tracker.update(&mut connection).await.unwrap();
+    let messages = connection
+        .l2_to_l1_messages_dal()
+        .get_messages_by_tx_hash(tx_hash)
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].sender, L2_ASSET_ROUTER_ADDRESS);
+    assert_eq!(messages[0].merkle_index, Some(0));
+    assert!(messages[0].l1_batch_executed);
+    assert_eq!(messages[0].consumption, L2ToL1MessageConsumption::Pending);
+
+    client.finalize_withdrawal(L1BatchNumber(1), 0).await;
+    client.set_last_finalized_block_number(5).await;
+    tracker.update(&mut connection).await.unwrap();
+    let messages = connection
+        .l2_to_l1_messages_dal()
+        .get_messages_by_tx_hash(tx_hash)
+        .await
+        .unwrap();
+    assert_eq!(messages[0].consumption, L2ToL1MessageConsumption::Consumed);
+    assert_eq!(messages[0].consumed_at_l1_block, Some(5));
+}
+
+async fn test_untracked_l2_to_l1_message(first_l1_batch: Option<L1BatchNumber>, retention: u32) {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let mut connection = connection_pool.connection().await.unwrap();
+    let tx_hash = setup_executed_batch_with_withdrawal(&mut connection).await;
+    let (_, client) = create_l1_test_watcher(connection_pool.clone()).await;
+    let tracker =
+        create_message_tracker(connection_pool.clone(), &client, first_l1_batch, retention);
+
+    tracker.update(&mut connection).await.unwrap();
+    let messages = connection
+        .l2_to_l1_messages_dal()
+        .get_messages_by_tx_hash(tx_hash)
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].consumption, L2ToL1MessageConsumption::Untracked);
+}
+
+#[test_log::test(tokio::test)]
+async fn l2_to_l1_messages_before_first_tracked_batch_are_not_tracked() {
+    test_untracked_l2_to_l1_message(Some(L1BatchNumber(2)), 100).await;
+}
+
+#[test_log::test(tokio::test)]
+async fn l2_to_l1_message_statuses_are_pruned() {
+    // All statuses are pruned immediately after being tracked.
+    test_untracked_l2_to_l1_message(None, 0).await;
+}
+
 #[test_log::test(tokio::test)]
 async fn test_batch_root_processor_from_genesis() {
     let connection_pool = ConnectionPool::<Core>::test_pool().await;
@@ -0,0 +1,182 @@
+//! Congestion-based (EIP-1559-style) adjustment of the fair L2 gas price.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use tokio::sync::watch;
+use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::fee_model::L1BatchUtilization;
+
+/// Parameters of the congestion adjustment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CongestionConfig {
+    pub max_gas_per_batch: u64,
+    pub max_pubdata_per_batch: u64,
+    pub compute_target: f64,
+    pub pubdata_target: f64,
+    pub max_change_denominator: u32,
+    pub max_multiplier: f64,
+    pub window_batches: u32,
+}
+
+impl CongestionConfig {
+    /// Extracts the congestion config from the state keeper config. Returns `None` if the adjustment is disabled.
+    pub fn from_state_keeper_config(config: &StateKeeperConfig) -> Option<Self> {
+        config.fee_congestion_enabled.then(|| Self {
+            max_gas_per_batch: config.max_gas_per_batch,
+            max_pubdata_per_batch: config.max_pubdata_per_batch,
+            compute_target: config.fee_congestion_compute_target,
+            pubdata_target: config.fee_congestion_pubdata_target,
+            max_change_denominator: config.fee_congestion_max_change_denominator,
+            max_multiplier: config.fee_congestion_max_multiplier,
+            window_batches: config.fee_congestion_window_batches,
+        })
+    }
+
+    /// Computes the fair L2 gas price multiplier after the specified batches (ordered by batch number).
+    ///
+    /// Starting from 1, the multiplier is updated for each batch as in EIP-1559: it's multiplied by
+    /// `1 + (utilization - target) / target / max_change_denominator`, where the utilization is taken for the resource
+    /// (compute or pubdata) that is the most congested relative to its target. The multiplier is clamped
+    /// to `[1, max_multiplier]` after each step, so the price never drops below `minimal_l2_gas_price`.
+    pub fn multiplier(&self, batches: &[L1BatchUtilization]) -> f64 {
+        let denominator = f64::from(self.max_change_denominator.max(1));
+        let max_multiplier = self.max_multiplier.max(1.0);
+        batches.iter().fold(1.0, |multiplier, batch| {
+            let compute_delta =
+                Self::relative_delta(batch.gas_used, self.max_gas_per_batch, self.compute_target);
+            let pubdata_delta = Self::relative_delta(
+                batch.pubdata_size,
+                self.max_pubdata_per_batch,
+                self.pubdata_target,
+            );
+            let delta = compute_delta.max(pubdata_delta);
+            (multiplier * (1.0 + delta / denominator)).clamp(1.0, max_multiplier)
+        })
+    }
+
+    /// Returns `(utilization - target) / target`, which lies in `[-1, 1 / target - 1]`.
+    fn relative_delta(used: u64, capacity: u64, target: f64) -> f64 {
+        if capacity == 0 || target <= 0.0 {
+            return -1.0;
+        }
+        let utilization = (used as f64 / capacity as f64).min(1.0);
+        (utilization - target) / target
+    }
+}
+
+/// Periodically recomputes the congestion multiplier for the fair L2 gas price based on utilization
+/// of the latest sealed L1 batches.
+#[derive(Debug)]
+pub struct CongestionFeeAdjuster {
+    pool: ConnectionPool<Core>,
+    config: CongestionConfig,
+    /// `f64` multiplier stored as bits.
+    multiplier: AtomicU64,
+}
+
+impl CongestionFeeAdjuster {
+    const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+    pub fn new(pool: ConnectionPool<Core>, config: CongestionConfig) -> Self {
+        Self {
+            pool,
+            config,
+            multiplier: AtomicU64::new(1.0_f64.to_bits()),
+        }
+    }
+
+    /// Returns the current multiplier for the fair L2 gas price. The multiplier is always at least 1.
+    pub fn multiplier(&self) -> f64 {
+        f64::from_bits(self.multiplier.load(Ordering::Relaxed))
+    }
+
+    async fn update(&self) -> anyhow::Result<()> {
+        let mut connection = self.pool.connection_tagged("fee_model").await?;
+        let batches = connection
+            .blocks_dal()
+            .get_latest_l1_batches_utilization(self.config.window_batches as usize)
+            .await?;
+        drop(connection);
+
+        let multiplier = self.config.multiplier(&batches);
+        let prev_multiplier = f64::from_bits(
+            self.multiplier
+                .swap(multiplier.to_bits(), Ordering::Relaxed),
+        );
+        if multiplier != prev_multiplier {
+            tracing::debug!(
+                "Updated congestion multiplier for fair L2 gas price: {prev_multiplier} -> {multiplier}"
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn run(&self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow() {
+            if let Err(err) = self.update().await {
+                tracing::warn!("Failed updating congestion multiplier: {err:#}");
+            }
+
+            if tokio::time::timeout(Self::POLL_INTERVAL, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, congestion fee adjuster is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::L1BatchNumber;
+
+    use super::*;
+
+    fn config() -> CongestionConfig {
+        CongestionConfig {
+            max_gas_per_batch: 1_000,
+            max_pubdata_per_batch: 100,
+            compute_target: 0.5,
+            pubdata_target: 0.5,
+            max_change_denominator: 8,
+            max_multiplier: 2.0,
+            window_batches: 64,
+        }
+    }
+
+    fn batch(gas_used: u64, pubdata_size: u64) -> L1BatchUtilization {
+        L1BatchUtilization {
+            l1_batch_number: L1BatchNumber(1),
+            gas_used,
+            pubdata_size,
+        }
+    }
+
+    #[test]
+    fn congestion_multiplier() {
+        let config = config();
+        assert_eq!(config.multiplier(&[]), 1.0);
+        // Batches at the target don't change the multiplier.
+        assert_eq!(config.multiplier(&[batch(500, 50); 3]), 1.0);
+        // Empty batches cannot push the price below the minimum.
+        assert_eq!(config.multiplier(&[batch(0, 0); 3]), 1.0);
+
+        // A full batch increases the multiplier by 1/8.
+        assert_eq!(config.multiplier(&[batch(1_000, 0)]), 1.125);
+        // The most congested resource is used.
+        assert_eq!(config.multiplier(&[batch(0, 100)]), 1.125);
+        // ...and the multiplier decays after the congestion is gone.
+        let multiplier = config.multiplier(&[batch(1_000, 0), batch(250, 0)]);
+        assert!(multiplier > 1.0 && multiplier < 1.125, "{multiplier}");
+
+        // The multiplier is capped.
+        assert_eq!(config.multiplier(&[batch(1_000, 100); 100]), 2.0);
+    }
+}
@@ -1,6 +1,6 @@
 //! Gas adjuster metrics.
 
-use vise::{Gauge, Metrics};
+use vise::{Counter, Gauge, Metrics};
 
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "server_gas_adjuster")]
@@ -13,6 +13,10 @@ pub(super) struct GasAdjusterMetrics {
     pub median_blob_base_fee: Gauge<u64>,
     pub median_l2_pubdata_price: Gauge<u64>,
     pub median_gas_per_pubdata_price: Gauge<u64>,
+    /// Number of errors returned by fee sources.
+    pub fee_source_errors: Counter,
+    /// Number of fee values rejected as outliers when combining data from multiple fee sources.
+    pub fee_source_outliers: Counter,
 }
 
 #[vise::register]
@@ -5,31 +5,40 @@ use std::{
     sync::{Arc, RwLock},
 };
 
+use futures::future;
 use tokio::sync::watch;
 use zksync_config::GasAdjusterConfig;
-use zksync_eth_client::EthFeeInterface;
+use zksync_eth_client::{BaseFees, EthFeeInterface};
 use zksync_types::{
     commitment::L1BatchCommitmentMode, pubdata_da::PubdataSendingMode, L1_GAS_PER_PUBDATA_BYTE,
     U256,
 };
 use zksync_web3_decl::client::{DynClient, L1, L2};
 
-use self::metrics::METRICS;
+use self::{metrics::METRICS, quorum::FeeSourcesQuorum};
 use super::TxParamsProvider;
 
 mod metrics;
+mod quorum;
 #[cfg(test)]
 mod tests;
 
+/// Client(s) used by [`GasAdjuster`] to fetch fee data.
+///
+/// By default, only the settlement layer client is used. If additional fee sources are configured, fee data
+/// is fetched from all sources and combined according to the quorum policy specified in [`GasAdjusterConfig`],
+/// so that a single flaky source cannot distort fees.
 #[derive(Debug)]
 pub struct GasAdjusterClient {
     inner: Box<dyn EthFeeInterface>,
+    additional_sources: Vec<Box<dyn EthFeeInterface>>,
 }
 
 impl From<Box<DynClient<L1>>> for GasAdjusterClient {
     fn from(inner: Box<DynClient<L1>>) -> Self {
         Self {
             inner: Box::new(inner.for_component("gas_adjuster")),
+            additional_sources: vec![],
         }
     }
 }
@@ -38,7 +47,81 @@ impl From<Box<DynClient<L2>>> for GasAdjusterClient {
     fn from(inner: Box<DynClient<L2>>) -> Self {
         Self {
             inner: Box::new(inner.for_component("gas_adjuster")),
+            additional_sources: vec![],
+        }
+    }
+}
+
+impl GasAdjusterClient {
+    /// Adds fee sources in addition to the main client. All sources must point to the same network.
+    pub fn with_additional_sources(
+        mut self,
+        sources: impl IntoIterator<Item = Box<DynClient<L1>>>,
+    ) -> Self {
+        self.additional_sources.extend(
+            sources
+                .into_iter()
+                .map(|source| Box::new(source.for_component("gas_adjuster")) as Box<_>),
+        );
+        self
+    }
+
+    fn sources(&self) -> impl Iterator<Item = &dyn EthFeeInterface> + '_ {
+        std::iter::once(self.inner.as_ref())
+            .chain(self.additional_sources.iter().map(AsRef::as_ref))
+    }
+
+    fn source_count(&self) -> usize {
+        1 + self.additional_sources.len()
+    }
+
+    async fn block_number(&self, quorum: &FeeSourcesQuorum) -> anyhow::Result<usize> {
+        if self.additional_sources.is_empty() {
+            return Ok(self.inner.block_number().await?.as_usize());
+        }
+
+        let results = future::join_all(self.sources().map(|source| source.block_number())).await;
+        let block_numbers = results
+            .into_iter()
+            .filter_map(|res| match res {
+                Ok(number) => Some(number.as_usize()),
+                Err(err) => {
+                    METRICS.fee_source_errors.inc();
+                    tracing::warn!("Fee source failed returning the latest block number: {err}");
+                    None
+                }
+            })
+            .collect();
+        quorum.combine_block_numbers(block_numbers)
+    }
+
+    async fn base_fee_history(
+        &self,
+        quorum: &FeeSourcesQuorum,
+        upto_block: usize,
+        block_count: usize,
+    ) -> anyhow::Result<Vec<BaseFees>> {
+        if self.additional_sources.is_empty() {
+            return Ok(self.inner.base_fee_history(upto_block, block_count).await?);
         }
+
+        let results = future::join_all(
+            self.sources()
+                .map(|source| source.base_fee_history(upto_block, block_count)),
+        )
+        .await;
+        let histories = results
+            .into_iter()
+            .filter_map(|res| match res {
+                Ok(history) => Some(history),
+                Err(err) => {
+                    METRICS.fee_source_errors.inc();
+                    tracing::warn!("Fee source failed returning fee history: {err}");
+                    None
+                }
+            })
+            .collect();
+        quorum.combine_fee_histories(histories, block_count)
     }
 }
 
@@ -63,6 +146,7 @@ pub struct GasAdjuster {
     pub(super) config: GasAdjusterConfig,
     pubdata_sending_mode: PubdataSendingMode,
     client: GasAdjusterClient,
+    fee_sources_quorum: FeeSourcesQuorum,
     commitment_mode: L1BatchCommitmentMode,
 }
 
@@ -73,18 +157,21 @@ impl GasAdjuster {
         pubdata_sending_mode: PubdataSendingMode,
         commitment_mode: L1BatchCommitmentMode,
     ) -> anyhow::Result<Self> {
+        let fee_sources_quorum = FeeSourcesQuorum::new(&config, client.source_count())?;
+
         // Subtracting 1 from the "latest" block number to prevent errors in case
         // the info about the latest block is not yet present on the node.
         // This sometimes happens on Infura.
         let current_block = client
-            .inner
-            .block_number()
+            .block_number(&fee_sources_quorum)
             .await?
-            .as_usize()
             .saturating_sub(1);
         let fee_history = client
-            .inner
-            .base_fee_history(current_block, config.max_base_fee_samples)
+            .base_fee_history(
+                &fee_sources_quorum,
+                current_block,
+                config.max_base_fee_samples,
+            )
             .await?;
 
         let base_fee_statistics = GasStatistics::new(
@@ -121,6 +208,7 @@ impl GasAdjuster {
             config,
             pubdata_sending_mode,
             client,
+            fee_sources_quorum,
             commitment_mode,
         })
     }
@@ -133,10 +221,8 @@ impl GasAdjuster {
         // This sometimes happens on Infura.
         let current_block = self
             .client
-            .inner
-            .block_number()
+            .block_number(&self.fee_sources_quorum)
             .await?
-            .as_usize()
             .saturating_sub(1);
 
         let last_processed_block = self.base_fee_statistics.last_processed_block();
@@ -145,8 +231,7 @@ impl GasAdjuster {
             let n_blocks = current_block - last_processed_block;
             let fee_data = self
                 .client
-                .inner
-                .base_fee_history(current_block, n_blocks)
+                .base_fee_history(&self.fee_sources_quorum, current_block, n_blocks)
                 .await?;
 
             // We shouldn't rely on L1 provider to return consistent results, so we check that we have at least one new sample.
@@ -0,0 +1,211 @@
+//! Combining fee data from multiple sources.
+
+use anyhow::Context as _;
+use zksync_config::GasAdjusterConfig;
+use zksync_eth_client::BaseFees;
+use zksync_types::U256;
+
+use super::metrics::METRICS;
+
+/// Fee value that can be combined across sources.
+pub(super) trait FeeValue: Copy + Ord + Default {
+    /// Lossy conversion to `f64` used to compute relative deviations.
+    fn to_f64(self) -> f64;
+}
+
+impl FeeValue for u64 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl FeeValue for U256 {
+    fn to_f64(self) -> f64 {
+        let bits = self.bits();
+        if bits <= 128 {
+            self.as_u128() as f64
+        } else {
+            let shift = bits - 128;
+            (self >> shift).as_u128() as f64 * 2.0_f64.powi(shift as i32)
+        }
+    }
+}
+
+/// Policy combining fee data returned by several sources.
+///
+/// Values are combined per block and per fee kind. First, the median across all responding sources is computed;
+/// values deviating from it by more than `max_deviation` (relative to the median) are rejected as outliers.
+/// If at least `quorum` values remain, their median is used; otherwise, the fee data is rejected altogether.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct FeeSourcesQuorum {
+    quorum: usize,
+    max_deviation: f64,
+}
+
+impl FeeSourcesQuorum {
+    pub fn new(config: &GasAdjusterConfig, source_count: usize) -> anyhow::Result<Self> {
+        let quorum = config.fee_sources_quorum.unwrap_or(1);
+        anyhow::ensure!(
+            (1..=source_count).contains(&quorum),
+            "fee sources quorum ({quorum}) must be in 1..={source_count}"
+        );
+        anyhow::ensure!(
+            config.fee_sources_max_deviation >= 0.0,
+            "max deviation for fee sources must be non-negative"
+        );
+        Ok(Self {
+            quorum,
+            max_deviation: config.fee_sources_max_deviation,
+        })
+    }
+
+    /// Returns the latest block that is available on at least `quorum` sources.
+    pub fn combine_block_numbers(&self, mut block_numbers: Vec<usize>) -> anyhow::Result<usize> {
+        block_numbers.sort_unstable_by(|a, b| b.cmp(a));
+        block_numbers
+            .get(self.quorum - 1)
+            .copied()
+            .with_context(|| {
+                format!(
+                    "only {} fee sources returned the latest block number, while quorum is {}",
+                    block_numbers.len(),
+                    self.quorum
+                )
+            })
+    }
+
+    /// Combines fee histories for the same block range returned by different sources. Histories with an unexpected length
+    /// are discarded.
+    pub fn combine_fee_histories(
+        &self,
+        histories: Vec<Vec<BaseFees>>,
+        block_count: usize,
+    ) -> anyhow::Result<Vec<BaseFees>> {
+        let histories: Vec<_> = histories
+            .into_iter()
+            .filter(|history| history.len() == block_count)
+            .collect();
+        anyhow::ensure!(
+            histories.len() >= self.quorum,
+            "only {} fee sources returned full fee history, while quorum is {}",
+            histories.len(),
+            self.quorum
+        );
+
+        (0..block_count)
+            .map(|i| {
+                let fees: Vec<_> = histories.iter().map(|history| &history[i]).collect();
+                Ok(BaseFees {
+                    base_fee_per_gas: self
+                        .combine_values(fees.iter().map(|fee| fee.base_fee_per_gas))
+                        .context("base_fee_per_gas")?,
+                    base_fee_per_blob_gas: self
+                        .combine_values(fees.iter().map(|fee| fee.base_fee_per_blob_gas))
+                        .context("base_fee_per_blob_gas")?,
+                    l2_pubdata_price: self
+                        .combine_values(fees.iter().map(|fee| fee.l2_pubdata_price))
+                        .context("l2_pubdata_price")?,
+                })
+            })
+            .collect()
+    }
+
+    fn combine_values<T: FeeValue>(&self, values: impl Iterator<Item = T>) -> anyhow::Result<T> {
+        let mut values: Vec<_> = values.collect();
+        let median = median(&mut values);
+        let max_abs_deviation = median.to_f64() * self.max_deviation;
+
+        let total_count = values.len();
+        values.retain(|value| (value.to_f64() - median.to_f64()).abs() <= max_abs_deviation);
+        let outlier_count = total_count - values.len();
+        if outlier_count > 0 {
+            METRICS.fee_source_outliers.inc_by(outlier_count as u64);
+        }
+        anyhow::ensure!(
+            values.len() >= self.quorum,
+            "only {} fee sources agree on the value (median: {}), while quorum is {}",
+            values.len(),
+            median.to_f64(),
+            self.quorum
+        );
+        Ok(median_of_sorted(&values))
+    }
+}
+
+fn median<T: FeeValue>(values: &mut [T]) -> T {
+    values.sort_unstable();
+    median_of_sorted(values)
+}
+
+fn median_of_sorted<T: FeeValue>(values: &[T]) -> T {
+    values.get(values.len() / 2).copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quorum(quorum: usize, max_deviation: f64) -> FeeSourcesQuorum {
+        FeeSourcesQuorum {
+            quorum,
+            max_deviation,
+        }
+    }
+
+    fn fees(base_fee_per_gas: u64, base_fee_per_blob_gas: u64) -> BaseFees {
+        BaseFees {
+            base_fee_per_gas,
+            base_fee_per_blob_gas: base_fee_per_blob_gas.into(),
+            l2_pubdata_price: 0.into(),
+        }
+    }
+
+    #[test]
+    fn quorum_defaults_to_single_source() {
+        let config = GasAdjusterConfig {
+            fee_sources_max_deviation: 0.5,
+            ..GasAdjusterConfig::default()
+        };
+        assert_eq!(FeeSourcesQuorum::new(&config, 1).unwrap().quorum, 1);
+        assert_eq!(FeeSourcesQuorum::new(&config, 3).unwrap().quorum, 1);
+        assert_eq!(FeeSourcesQuorum::new(&config, 4).unwrap().quorum, 1);
+
+        let config = GasAdjusterConfig {
+            fee_sources_quorum: Some(4),
+            ..config
+        };
+        FeeSourcesQuorum::new(&config, 3).unwrap_err();
+    }
+
+    #[test]
+    fn combining_block_numbers() {
+        let policy = quorum(2, 0.5);
+        assert_eq!(policy.combine_block_numbers(vec![10, 12, 11]).unwrap(), 11);
+        assert_eq!(policy.combine_block_numbers(vec![10, 12]).unwrap(), 10);
+        policy.combine_block_numbers(vec![10]).unwrap_err();
+    }
+
+    #[test]
+    fn combining_fee_histories_rejects_outliers() {
+        let policy = quorum(2, 0.5);
+        let histories = vec![
+            vec![fees(100, 10), fees(110, 10)],
+            vec![fees(105, 12), fees(1_000, 11)],
+            vec![fees(95, 11), fees(100, 9)],
+        ];
+        let combined = policy.combine_fee_histories(histories, 2).unwrap();
+        assert_eq!(combined, [fees(100, 11), fees(110, 10)]);
+    }
+
+    #[test]
+    fn combining_fee_histories_without_quorum() {
+        let policy = quorum(2, 0.1);
+        // Sources disagree too much.
+        let histories = vec![vec![fees(100, 10)], vec![fees(200, 10)]];
+        policy.combine_fee_histories(histories, 1).unwrap_err();
+
+        // One of the sources returned an incomplete history.
+        let histories = vec![vec![fees(100, 10)], vec![]];
+        policy.combine_fee_histories(histories, 1).unwrap_err();
+    }
+}
@@ -70,6 +70,8 @@ fn test_config() -> GasAdjusterConfig {
         num_samples_for_blob_base_fee_estimate: 3,
         internal_pubdata_pricing_multiplier: 1.0,
         max_blob_base_fee: None,
+        fee_sources_quorum: None,
+        fee_sources_max_deviation: 0.5,
     }
 }
 
@@ -208,3 +210,51 @@ async fn kept_updated_l2(commitment_mode: L1BatchCommitmentMode) {
         expected_median_blob_base_fee.into()
     );
 }
+
+/// Check that fees are combined across multiple sources and a single misbehaving source is ignored.
+#[tokio::test]
+async fn kept_updated_with_multiple_sources() {
+    let create_client = |multiplier: u64| {
+        let base_fees = TEST_BLOCK_FEES
+            .into_iter()
+            .zip(TEST_BLOB_FEES)
+            .map(|(block, blob)| BaseFees {
+                base_fee_per_gas: block * multiplier,
+                base_fee_per_blob_gas: (blob * multiplier).into(),
+                l2_pubdata_price: 0.into(),
+            })
+            .collect();
+        let eth_client = MockSettlementLayer::builder()
+            .with_fee_history(base_fees)
+            .build();
+        eth_client.advance_block_number(6);
+        eth_client
+    };
+    let eth_clients = [create_client(1), create_client(1), create_client(100)];
+    let [main_client, additional_clients @ ..] = eth_clients
+        .each_ref()
+        .map(|client| -> Box<DynClient<L1>> { Box::new(client.clone().into_client()) });
+
+    let config = test_config();
+    let adjuster = GasAdjuster::new(
+        GasAdjusterClient::from(main_client).with_additional_sources(additional_clients),
+        config,
+        PubdataSendingMode::Blobs,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await
+    .unwrap();
+
+    // The outlier values from the last source are rejected.
+    assert_eq!(read(&adjuster.base_fee_statistics).median(), 6);
+    assert_eq!(
+        read(&adjuster.blob_base_fee_statistics).median(),
+        (393216 * 2).into()
+    );
+
+    // Updates should succeed even if one of the sources lags behind.
+    eth_clients[0].advance_block_number(3);
+    eth_clients[1].advance_block_number(3);
+    adjuster.keep_updated().await.unwrap();
+    assert_eq!(read(&adjuster.base_fee_statistics).median(), 7);
+}
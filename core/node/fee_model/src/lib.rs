@@ -1,4 +1,8 @@
-use std::{fmt, fmt::Debug, sync::Arc};
+use std::{
+    fmt,
+    fmt::Debug,
+    sync::{Arc, RwLock},
+};
 
 use anyhow::Context;
 use async_trait::async_trait;
@@ -7,8 +11,9 @@ use zksync_types::fee_model::{
     BaseTokenConversionRatio, BatchFeeInput, FeeModelConfig, FeeParams, FeeParamsV1, FeeParamsV2,
 };
 
-use crate::l1_gas_price::GasAdjuster;
+use crate::{congestion::CongestionFeeAdjuster, l1_gas_price::GasAdjuster};
 
+pub mod congestion;
 pub mod l1_gas_price;
 
 /// Trait responsible for providing numerator and denominator for adjusting gas price that is denominated
@@ -51,13 +56,14 @@ impl dyn BatchFeeModelInputProvider {
 pub struct MainNodeFeeInputProvider {
     provider: Arc<GasAdjuster>,
     base_token_ratio_provider: Arc<dyn BaseTokenRatioProvider>,
-    config: FeeModelConfig,
+    config: RwLock<FeeModelConfig>,
+    congestion_adjuster: Option<Arc<CongestionFeeAdjuster>>,
 }
 
 #[async_trait]
 impl BatchFeeModelInputProvider for MainNodeFeeInputProvider {
     fn get_fee_model_params(&self) -> FeeParams {
-        match self.config {
+        match self.config_with_congestion() {
             FeeModelConfig::V1(config) => FeeParams::V1(FeeParamsV1 {
                 config,
                 l1_gas_price: self.provider.estimate_effective_gas_price(),
@@ -81,7 +87,42 @@ impl MainNodeFeeInputProvider {
         Self {
             provider,
             base_token_ratio_provider,
-            config,
+            config: RwLock::new(config),
+            congestion_adjuster: None,
+        }
+    }
+
+    /// Enables congestion-based adjustment of the fair L2 gas price.
+    pub fn with_congestion_adjuster(mut self, adjuster: Arc<CongestionFeeAdjuster>) -> Self {
+        self.congestion_adjuster = Some(adjuster);
+        self
+    }
+
+    /// Replaces the fee model config. The new config will be used for all subsequent fee input computations.
+    pub fn set_config(&self, config: FeeModelConfig) {
+        *self.config.write().expect("fee model config is poisoned") = config;
+    }
+
+    fn config(&self) -> FeeModelConfig {
+        *self.config.read().expect("fee model config is poisoned")
+    }
+
+    fn config_with_congestion(&self) -> FeeModelConfig {
+        let config = self.config();
+        let Some(adjuster) = &self.congestion_adjuster else {
+            return config;
+        };
+        let multiplier = adjuster.multiplier();
+        let scale = |price: u64| (price as f64 * multiplier) as u64;
+        match config {
+            FeeModelConfig::V1(mut config) => {
+                config.minimal_l2_gas_price = scale(config.minimal_l2_gas_price);
+                FeeModelConfig::V1(config)
+            }
+            FeeModelConfig::V2(mut config) => {
+                config.minimal_l2_gas_price = scale(config.minimal_l2_gas_price);
+                FeeModelConfig::V2(config)
+            }
         }
     }
 }
@@ -222,6 +263,7 @@ mod tests {
                 conversion_ratio: BaseTokenConversionRatio {
                     numerator: NonZeroU64::new(2).unwrap(),
                     denominator: NonZeroU64::new(1).unwrap(),
+                    base_token_decimals: 18,
                 },
                 input_minimal_l2_gas_price: 1000,
                 input_l1_gas_price: 2000,
@@ -235,6 +277,7 @@ mod tests {
                 conversion_ratio: BaseTokenConversionRatio {
                     numerator: NonZeroU64::new(1).unwrap(),
                     denominator: NonZeroU64::new(2).unwrap(),
+                    base_token_decimals: 18,
                 },
                 input_minimal_l2_gas_price: 1000,
                 input_l1_gas_price: 2000,
@@ -248,6 +291,7 @@ mod tests {
                 conversion_ratio: BaseTokenConversionRatio {
                     numerator: NonZeroU64::new(1).unwrap(),
                     denominator: NonZeroU64::new(1).unwrap(),
+                    base_token_decimals: 18,
                 },
                 input_minimal_l2_gas_price: 1000,
                 input_l1_gas_price: 2000,
@@ -261,6 +305,7 @@ mod tests {
                 conversion_ratio: BaseTokenConversionRatio {
                     numerator: NonZeroU64::new(1_000_000).unwrap(),
                     denominator: NonZeroU64::new(1).unwrap(),
+                    base_token_decimals: 18,
                 },
                 input_minimal_l2_gas_price: 1_000_000,
                 input_l1_gas_price: 2_000_000,
@@ -274,6 +319,7 @@ mod tests {
                 conversion_ratio: BaseTokenConversionRatio {
                     numerator: NonZeroU64::new(1).unwrap(),
                     denominator: NonZeroU64::new(1_000).unwrap(),
+                    base_token_decimals: 18,
                 },
                 input_minimal_l2_gas_price: 1_000_000,
                 input_l1_gas_price: 2_000_000,
@@ -287,6 +333,7 @@ mod tests {
                 conversion_ratio: BaseTokenConversionRatio {
                     numerator: NonZeroU64::new(1123456789).unwrap(),
                     denominator: NonZeroU64::new(1_000_000_000).unwrap(),
+                    base_token_decimals: 18,
                 },
                 input_minimal_l2_gas_price: 1_000_000,
                 input_l1_gas_price: 2_000_000,
@@ -295,11 +342,26 @@ mod tests {
                 expected_l1_gas_price: 2246913,
                 expected_l1_pubdata_price: 3370370,
             },
+            TestCase {
+                name: "1 ETH = 2_000 BaseToken with 8 decimals",
+                conversion_ratio: BaseTokenConversionRatio {
+                    numerator: NonZeroU64::new(2_000).unwrap(),
+                    denominator: NonZeroU64::new(1).unwrap(),
+                    base_token_decimals: 8,
+                },
+                input_minimal_l2_gas_price: 100_000_000,
+                input_l1_gas_price: 20_000_000_000,
+                input_l1_pubdata_price: 3,
+                expected_minimal_l2_gas_price: 20,
+                expected_l1_gas_price: 4_000,
+                expected_l1_pubdata_price: 1,
+            },
             TestCase {
                 name: "Conversion ratio too large so clamp down to u64::MAX",
                 conversion_ratio: BaseTokenConversionRatio {
                     numerator: NonZeroU64::new(u64::MAX).unwrap(),
                     denominator: NonZeroU64::new(1).unwrap(),
+                    base_token_decimals: 18,
                 },
                 input_minimal_l2_gas_price: 2,
                 input_l1_gas_price: 2,
@@ -0,0 +1,167 @@
+//! Builder for customized genesis states.
+
+use std::collections::{BTreeMap, HashMap};
+
+use zksync_contracts::BaseSystemContractsHashes;
+use zksync_dal::custom_genesis_export_dal::{FactoryDepRow, GenesisState, StorageLogRow};
+use zksync_system_constants::L2_BASE_TOKEN_ADDRESS;
+use zksync_types::{
+    block::DeployedContract, bytecode::BytecodeHash, u256_to_h256,
+    utils::storage_key_for_eth_balance, AccountTreeId, Address, ProtocolVersionId, StorageKey,
+    StorageLog, H256, U256,
+};
+
+use crate::{
+    make_genesis_batch_params,
+    utils::{get_deduped_log_queries, get_storage_logs},
+    GenesisBatchParams,
+};
+
+/// Storage slot of `totalSupply` in the `L2BaseToken` system contract.
+const BASE_TOKEN_TOTAL_SUPPLY_SLOT: u64 = 1;
+
+/// Builder of a customized genesis state, e.g. for forked chains.
+///
+/// Starts from the default set of system contracts and allows to override system contracts, predeploy additional
+/// contracts, and set initial base token balances and arbitrary storage slots. The built [`GenesisState`] can be
+/// serialized with `bincode` and referenced via `custom_genesis_state_path` in the genesis config;
+/// [`Self::genesis_batch_params()`] computes the matching root hash, leaf index and commitment for the genesis config.
+///
+/// The base token of the chain is not a part of the genesis state: on L2, it's always represented by
+/// the `L2BaseToken` system contract, and its L1 address is configured in the L1 contracts.
+/// Hashes of the bootloader and the default account are specified in the genesis config and are checked
+/// against the provided base system contracts by [`GenesisParams::from_genesis_config()`](crate::GenesisParams::from_genesis_config()).
+#[derive(Debug, Clone)]
+pub struct GenesisStateBuilder {
+    contracts: Vec<DeployedContract>,
+    balances: BTreeMap<Address, U256>,
+    storage_logs: Vec<StorageLog>,
+}
+
+impl GenesisStateBuilder {
+    /// Creates a builder with the provided system contracts.
+    pub fn new(system_contracts: Vec<DeployedContract>) -> Self {
+        Self {
+            contracts: system_contracts,
+            balances: BTreeMap::new(),
+            storage_logs: vec![],
+        }
+    }
+
+    /// Overrides the bytecode of a contract deployed at the same address, or adds a new contract
+    /// if there's no such contract yet.
+    pub fn with_contract(mut self, contract: DeployedContract) -> Self {
+        let existing = self
+            .contracts
+            .iter_mut()
+            .find(|existing| existing.account_id == contract.account_id);
+        match existing {
+            Some(existing) => *existing = contract,
+            None => self.contracts.push(contract),
+        }
+        self
+    }
+
+    /// Sets the initial base token balance of the specified account. The `totalSupply` of the base token
+    /// is set to the sum of all initial balances.
+    ///
+    /// Note that initial balances are not backed by the base token locked on L1; it's the responsibility
+    /// of the chain operator to make sure that the corresponding amount is accounted for on L1.
+    pub fn with_balance(mut self, address: Address, balance: U256) -> Self {
+        self.balances.insert(address, balance);
+        self
+    }
+
+    /// Sets an arbitrary storage slot, e.g. to initialize the state of a predeployed contract.
+    /// Takes precedence over storage slots set by other methods.
+    pub fn with_storage_log(mut self, log: StorageLog) -> Self {
+        self.storage_logs.push(log);
+        self
+    }
+
+    fn storage_logs(&self) -> Vec<StorageLog> {
+        let mut storage_logs = get_storage_logs(&self.contracts);
+        if !self.balances.is_empty() {
+            let mut total_supply = U256::zero();
+            for (address, &balance) in &self.balances {
+                total_supply = total_supply
+                    .checked_add(balance)
+                    .expect("base token total supply overflow");
+                storage_logs.push(StorageLog::new_write_log(
+                    storage_key_for_eth_balance(address),
+                    u256_to_h256(balance),
+                ));
+            }
+            let total_supply_key = StorageKey::new(
+                AccountTreeId::new(L2_BASE_TOKEN_ADDRESS),
+                H256::from_low_u64_be(BASE_TOKEN_TOTAL_SUPPLY_SLOT),
+            );
+            storage_logs.push(StorageLog::new_write_log(
+                total_supply_key,
+                u256_to_h256(total_supply),
+            ));
+        }
+        storage_logs.extend_from_slice(&self.storage_logs);
+
+        // Deduplicate logs keeping the last write for each key, so that the state is well-defined.
+        let mut deduped_logs = HashMap::with_capacity(storage_logs.len());
+        let mut keys = vec![];
+        for log in storage_logs {
+            if deduped_logs.insert(log.key, log).is_none() {
+                keys.push(log.key);
+            }
+        }
+        keys.into_iter().map(|key| deduped_logs[&key]).collect()
+    }
+
+    fn factory_deps(&self) -> HashMap<H256, Vec<u8>> {
+        self.contracts
+            .iter()
+            .map(|contract| {
+                let hash = BytecodeHash::for_bytecode(&contract.bytecode).value();
+                (hash, contract.bytecode.clone())
+            })
+            .collect()
+    }
+
+    /// Builds the genesis state.
+    pub fn build(&self) -> GenesisState {
+        let storage_logs = self
+            .storage_logs()
+            .into_iter()
+            .map(|log| StorageLogRow {
+                address: log.key.address().0,
+                key: log.key.key().0,
+                value: log.value.0,
+            })
+            .collect();
+        let factory_deps = self
+            .factory_deps()
+            .into_iter()
+            .map(|(bytecode_hash, bytecode)| FactoryDepRow {
+                bytecode_hash: bytecode_hash.0,
+                bytecode,
+            })
+            .collect();
+        GenesisState {
+            storage_logs,
+            factory_deps,
+        }
+    }
+
+    /// Computes genesis batch params (root hash, last leaf index and commitment) for the built genesis state.
+    /// These values must be specified in the genesis config.
+    pub fn genesis_batch_params(
+        &self,
+        base_system_contracts_hashes: BaseSystemContractsHashes,
+        protocol_version: ProtocolVersionId,
+    ) -> GenesisBatchParams {
+        let storage_logs = self.storage_logs();
+        let (params, _) = make_genesis_batch_params(
+            get_deduped_log_queries(&storage_logs),
+            base_system_contracts_hashes,
+            protocol_version,
+        );
+        params
+    }
+}
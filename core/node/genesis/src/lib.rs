@@ -30,12 +30,14 @@ use zksync_types::{
     ProtocolVersion, ProtocolVersionId, StorageKey, StorageLog, H256, U256,
 };
 
+pub use crate::builder::GenesisStateBuilder;
 use crate::utils::{
     add_eth_token, get_deduped_log_queries, get_storage_logs,
     insert_base_system_contracts_to_factory_deps, insert_deduplicated_writes_and_protective_reads,
     insert_factory_deps, insert_storage_logs, save_genesis_l1_batch_metadata,
 };
 
+mod builder;
 #[cfg(test)]
 mod tests;
 pub mod utils;
@@ -184,6 +186,7 @@ pub fn mock_genesis_config() -> GenesisConfig {
         dummy_verifier: false,
         l1_batch_commit_data_generator_mode: Default::default(),
         custom_genesis_state_path: None,
+        circuit_geometry: None,
     }
 }
 
@@ -64,3 +64,44 @@ async fn running_genesis_with_non_latest_protocol_version() {
     insert_genesis_batch(&mut conn, &params).await.unwrap();
     assert!(!conn.blocks_dal().is_genesis_needed().await.unwrap());
 }
+
+#[tokio::test]
+async fn running_genesis_with_custom_state() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    conn.blocks_dal().delete_genesis().await.unwrap();
+
+    let params = GenesisParams::mock();
+    let rich_account = Address::repeat_byte(0x11);
+    let balance = U256::from(10).pow(U256::from(24));
+    let predeployed_contract = DeployedContract {
+        account_id: AccountTreeId::new(Address::repeat_byte(0x22)),
+        bytecode: params.system_contracts()[0].bytecode.clone(),
+    };
+    let builder = GenesisStateBuilder::new(params.system_contracts().to_vec())
+        .with_balance(rich_account, balance)
+        .with_contract(predeployed_contract);
+    let expected_params = builder.genesis_batch_params(
+        params.base_system_contracts().hashes(),
+        params.minor_protocol_version(),
+    );
+
+    let batch_params =
+        insert_genesis_batch_with_custom_state(&mut conn, &params, Some(builder.build()))
+            .await
+            .unwrap();
+    assert_eq!(batch_params.root_hash, expected_params.root_hash);
+    assert_eq!(batch_params.commitment, expected_params.commitment);
+    assert_eq!(
+        batch_params.rollup_last_leaf_index,
+        expected_params.rollup_last_leaf_index
+    );
+
+    let balance_key = zksync_types::utils::storage_key_for_eth_balance(&rich_account);
+    let stored_balance = conn
+        .storage_web3_dal()
+        .get_value(&balance_key)
+        .await
+        .unwrap();
+    assert_eq!(stored_balance, u256_to_h256(balance));
+}
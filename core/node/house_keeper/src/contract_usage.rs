@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+
+use crate::{metrics::CONTRACT_USAGE_METRICS, periodic_job::PeriodicJob};
+
+/// Periodically aggregates gas and pubdata used by transactions per called contract and day for newly sealed L2 blocks.
+/// The aggregated usage is served by the `zks_getTopContracts` API method.
+#[derive(Debug)]
+pub struct ContractUsageAggregator {
+    aggregation_interval_ms: u64,
+    connection_pool: ConnectionPool<Core>,
+}
+
+impl ContractUsageAggregator {
+    /// Maximum number of L2 blocks aggregated in a single DB transaction.
+    const L2_BLOCKS_PER_CHUNK: u32 = 1_000;
+    /// Maximum number of chunks aggregated during a single run, so that the aggregator catches up gradually
+    /// and remains responsive to stop signals.
+    const MAX_CHUNKS_PER_RUN: usize = 10;
+
+    pub fn new(aggregation_interval_ms: u64, connection_pool: ConnectionPool<Core>) -> Self {
+        Self {
+            aggregation_interval_ms,
+            connection_pool,
+        }
+    }
+
+    async fn aggregate(&self) -> anyhow::Result<()> {
+        let mut conn = self
+            .connection_pool
+            .connection_tagged("house_keeper")
+            .await?;
+        for _ in 0..Self::MAX_CHUNKS_PER_RUN {
+            let Some(last_l2_block) = conn
+                .contract_usage_dal()
+                .aggregate_next_l2_blocks(Self::L2_BLOCKS_PER_CHUNK)
+                .await?
+            else {
+                break;
+            };
+            tracing::debug!("Aggregated contract usage up to L2 block #{last_l2_block}");
+            CONTRACT_USAGE_METRICS
+                .last_aggregated_l2_block
+                .set(last_l2_block.0.into());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PeriodicJob for ContractUsageAggregator {
+    const SERVICE_NAME: &'static str = "ContractUsageAggregator";
+
+    async fn run_routine_task(&mut self) -> anyhow::Result<()> {
+        self.aggregate().await
+    }
+
+    fn polling_interval_ms(&self) -> u64 {
+        self.aggregation_interval_ms
+    }
+}
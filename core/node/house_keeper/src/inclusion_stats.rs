@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use chrono::Utc;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+
+use crate::{
+    metrics::{InclusionLatencyLabels, InclusionStageLabels, Percentile, INCLUSION_STATS_METRICS},
+    periodic_job::PeriodicJob,
+};
+
+/// Periodically computes transaction inclusion / L1 finality latency percentiles over a sliding window,
+/// persists them for the `zks_getInclusionStats` API method and reports them as metrics.
+#[derive(Debug)]
+pub struct InclusionStatsReporter {
+    reporting_interval_ms: u64,
+    window: Duration,
+    connection_pool: ConnectionPool<Core>,
+}
+
+impl InclusionStatsReporter {
+    pub fn new(
+        reporting_interval_ms: u64,
+        window: Duration,
+        connection_pool: ConnectionPool<Core>,
+    ) -> Self {
+        Self {
+            reporting_interval_ms,
+            window,
+            connection_pool,
+        }
+    }
+
+    async fn report_stats(&self) -> anyhow::Result<()> {
+        let window = chrono::Duration::from_std(self.window).context("window is too large")?;
+        let window_start = Utc::now() - window;
+        let mut conn = self
+            .connection_pool
+            .connection_tagged("house_keeper")
+            .await?;
+        let stats = conn
+            .inclusion_stats_dal()
+            .compute_inclusion_stats(window_start)
+            .await?;
+        conn.inclusion_stats_dal()
+            .save_inclusion_stats(&stats)
+            .await?;
+
+        for stats in &stats {
+            let tx_class = stats.tx_class.as_str();
+            let stage = stats.stage.as_str();
+            INCLUSION_STATS_METRICS.sample_count[&InclusionStageLabels { tx_class, stage }]
+                .set(stats.sample_count);
+            let percentiles = [
+                (Percentile::P50, stats.p50_ms),
+                (Percentile::P90, stats.p90_ms),
+                (Percentile::P99, stats.p99_ms),
+            ];
+            for (percentile, value) in percentiles {
+                let labels = InclusionLatencyLabels {
+                    tx_class,
+                    stage,
+                    percentile,
+                };
+                INCLUSION_STATS_METRICS.latency_ms[&labels].set(value);
+            }
+        }
+        tracing::debug!("Updated transaction inclusion stats: {stats:?}");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PeriodicJob for InclusionStatsReporter {
+    const SERVICE_NAME: &'static str = "InclusionStatsReporter";
+
+    async fn run_routine_task(&mut self) -> anyhow::Result<()> {
+        self.report_stats().await
+    }
+
+    fn polling_interval_ms(&self) -> u64 {
+        self.reporting_interval_ms
+    }
+}
@@ -1,3 +1,5 @@
 pub mod blocks_state_reporter;
+pub mod contract_usage;
+pub mod inclusion_stats;
 mod metrics;
 pub mod periodic_job;
@@ -1,4 +1,4 @@
-use vise::{Gauge, Metrics};
+use vise::{EncodeLabelSet, EncodeLabelValue, Family, Gauge, Metrics};
 
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "fri_prover")]
@@ -9,3 +9,48 @@ pub(crate) struct FriProverMetrics {
 
 #[vise::register]
 pub(crate) static FRI_PROVER_METRICS: vise::Global<FriProverMetrics> = vise::Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(crate) enum Percentile {
+    P50,
+    P90,
+    P99,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(crate) struct InclusionLatencyLabels {
+    pub tx_class: &'static str,
+    pub stage: &'static str,
+    pub percentile: Percentile,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(crate) struct InclusionStageLabels {
+    pub tx_class: &'static str,
+    pub stage: &'static str,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_tx_inclusion")]
+pub(crate) struct InclusionStatsMetrics {
+    /// Latency percentiles (in milliseconds) from a transaction being received by the API server
+    /// to reaching the specified stage.
+    pub latency_ms: Family<InclusionLatencyLabels, Gauge<u64>>,
+    /// Number of transactions used to compute latency percentiles.
+    pub sample_count: Family<InclusionStageLabels, Gauge<u64>>,
+}
+
+#[vise::register]
+pub(crate) static INCLUSION_STATS_METRICS: vise::Global<InclusionStatsMetrics> =
+    vise::Global::new();
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_contract_usage")]
+pub(crate) struct ContractUsageMetrics {
+    /// Last L2 block aggregated into per-contract gas and pubdata usage.
+    pub last_aggregated_l2_block: Gauge<u64>,
+}
+
+#[vise::register]
+pub(crate) static CONTRACT_USAGE_METRICS: vise::Global<ContractUsageMetrics> = vise::Global::new();
@@ -10,13 +10,13 @@ use axum::{
     response::{IntoResponse, Response},
     routing, Json, Router,
 };
-use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde::{de, de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
 use tokio::sync::watch;
 use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
 use zksync_health_check::{CheckHealth, Health, HealthStatus};
 use zksync_merkle_tree::{
     unstable::{NodeKey, RawNode},
-    NoVersionError, ValueHash,
+    NoVersionError, TreeMultiProof, ValueHash,
 };
 use zksync_types::{u256_to_h256, web3, L1BatchNumber, H256, U256};
 
@@ -80,6 +80,62 @@ impl TreeEntryWithProof {
     }
 }
 
+/// Entry returned together with a [`TreeEntriesWithMultiProof`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MultiProofEntry {
+    #[serde(default, skip_serializing_if = "H256::is_zero")]
+    pub value: H256,
+    #[serde(default, skip_serializing_if = "TreeEntryWithProof::is_zero")]
+    pub index: u64,
+}
+
+/// Tree entries together with a single proof for all of them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TreeEntriesWithMultiProof {
+    /// Entries in the same order as the requested keys.
+    pub entries: Vec<MultiProofEntry>,
+    /// Multiproof in the compact binary format, as produced by [`TreeMultiProof::to_bytes()`].
+    pub proof: web3::Bytes,
+}
+
+impl TreeEntriesWithMultiProof {
+    fn new(src: zksync_merkle_tree::TreeEntriesWithMultiProof) -> Self {
+        Self {
+            entries: src
+                .entries
+                .into_iter()
+                .map(|entry| MultiProofEntry {
+                    value: entry.value,
+                    index: entry.leaf_index,
+                })
+                .collect(),
+            proof: web3::Bytes(src.proof.to_bytes()),
+        }
+    }
+
+    /// Verifies the entries. `keys` must be the keys requested for the proof, in the same order.
+    pub fn verify(&self, keys: &[U256], trusted_root_hash: H256) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            keys.len() == self.entries.len(),
+            "Number of keys ({}) doesn't match the number of entries ({})",
+            keys.len(),
+            self.entries.len()
+        );
+        let proof = TreeMultiProof::from_bytes(&self.proof.0).context("malformed proof")?;
+        let entries = keys
+            .iter()
+            .zip(&self.entries)
+            .map(|(&key, entry)| zksync_merkle_tree::TreeEntry {
+                key,
+                value: entry.value,
+                leaf_index: entry.index,
+            })
+            .collect();
+        zksync_merkle_tree::TreeEntriesWithMultiProof { entries, proof }
+            .verify(&Blake2Hasher, trusted_root_hash)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct HexNodeKey(NodeKey);
 
@@ -289,6 +345,13 @@ pub trait TreeApiClient: 'static + Send + Sync + fmt::Debug {
         l1_batch_number: L1BatchNumber,
         hashed_keys: Vec<U256>,
     ) -> Result<Vec<TreeEntryWithProof>, TreeApiError>;
+
+    /// Obtains a single multiproof for the specified `hashed_keys` at the specified tree version (= L1 batch number).
+    async fn get_multiproof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeEntriesWithMultiProof, TreeApiError>;
 }
 
 /// In-memory client implementation.
@@ -316,6 +379,21 @@ impl TreeApiClient for LazyAsyncTreeReader {
             Err(TreeApiError::NotReady(None))
         }
     }
+
+    async fn get_multiproof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeEntriesWithMultiProof, TreeApiError> {
+        if let Some(reader) = self.read() {
+            reader
+                .get_multiproof_inner(l1_batch_number, hashed_keys)
+                .await
+                .map_err(TreeApiError::NoVersion)
+        } else {
+            Err(TreeApiError::NotReady(None))
+        }
+    }
 }
 
 /// [`TreeApiClient`] implementation requesting data from a Merkle tree API server.
@@ -324,6 +402,7 @@ pub struct TreeApiHttpClient {
     inner: reqwest::Client,
     info_url: String,
     proofs_url: String,
+    multiproof_url: String,
 }
 
 impl TreeApiHttpClient {
@@ -338,8 +417,51 @@ impl TreeApiHttpClient {
             inner: client,
             info_url: url_base.to_owned(),
             proofs_url: format!("{url_base}/proofs"),
+            multiproof_url: format!("{url_base}/multiproof"),
         }
     }
+
+    /// Posts a request related to a specific tree version, parsing a `NoVersionError` from the response if necessary.
+    async fn post_for_version<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        request: &TreeProofsRequest,
+        request_description: &str,
+    ) -> Result<T, TreeApiError> {
+        let l1_batch_number = request.l1_batch_number;
+        let response = self
+            .inner
+            .post(url)
+            .json(request)
+            .send()
+            .await
+            .map_err(|err| {
+                TreeApiError::for_request(
+                    err,
+                    format_args!("{request_description} for L1 batch #{l1_batch_number}"),
+                )
+            })?;
+
+        let is_problem = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .map_or(false, |header| *header == PROBLEM_CONTENT_TYPE);
+        if response.status() == StatusCode::NOT_FOUND && is_problem {
+            // Try to parse `NoVersionError` from the response body.
+            let problem_data: NoVersionErrorData = response
+                .json()
+                .await
+                .context("failed parsing error response")?;
+            return Err(TreeApiError::NoVersion(problem_data.into()));
+        }
+
+        let response = response.error_for_status().with_context(|| {
+            format!("requesting {request_description} for L1 batch #{l1_batch_number} returned non-OK response")
+        })?;
+        Ok(response.json().await.with_context(|| {
+            format!("failed deserializing {request_description} for L1 batch #{l1_batch_number}")
+        })?)
+    }
 }
 
 #[async_trait]
@@ -384,43 +506,28 @@ impl TreeApiClient for TreeApiHttpClient {
         l1_batch_number: L1BatchNumber,
         hashed_keys: Vec<U256>,
     ) -> Result<Vec<TreeEntryWithProof>, TreeApiError> {
-        let response = self
-            .inner
-            .post(&self.proofs_url)
-            .json(&TreeProofsRequest {
-                l1_batch_number,
-                hashed_keys,
-            })
-            .send()
-            .await
-            .map_err(|err| {
-                TreeApiError::for_request(
-                    err,
-                    format_args!("proofs for L1 batch #{l1_batch_number}"),
-                )
-            })?;
-
-        let is_problem = response
-            .headers()
-            .get(header::CONTENT_TYPE)
-            .map_or(false, |header| *header == PROBLEM_CONTENT_TYPE);
-        if response.status() == StatusCode::NOT_FOUND && is_problem {
-            // Try to parse `NoVersionError` from the response body.
-            let problem_data: NoVersionErrorData = response
-                .json()
-                .await
-                .context("failed parsing error response")?;
-            return Err(TreeApiError::NoVersion(problem_data.into()));
-        }
-
-        let response = response.error_for_status().with_context(|| {
-            format!("requesting proofs for L1 batch #{l1_batch_number} returned non-OK response")
-        })?;
-        let response: TreeProofsResponse = response.json().await.with_context(|| {
-            format!("failed deserializing proofs for L1 batch #{l1_batch_number}")
-        })?;
+        let request = TreeProofsRequest {
+            l1_batch_number,
+            hashed_keys,
+        };
+        let response: TreeProofsResponse = self
+            .post_for_version(&self.proofs_url, &request, "proofs")
+            .await?;
         Ok(response.entries)
     }
+
+    async fn get_multiproof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeEntriesWithMultiProof, TreeApiError> {
+        let request = TreeProofsRequest {
+            l1_batch_number,
+            hashed_keys,
+        };
+        self.post_for_version(&self.multiproof_url, &request, "multiproof")
+            .await
+    }
 }
 
 impl AsyncTreeReader {
@@ -457,6 +564,31 @@ impl AsyncTreeReader {
         Ok(Json(response))
     }
 
+    async fn get_multiproof_inner(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeEntriesWithMultiProof, NoVersionError> {
+        let output = self
+            .clone()
+            .entries_with_multiproof(l1_batch_number, hashed_keys)
+            .await?;
+        Ok(TreeEntriesWithMultiProof::new(output))
+    }
+
+    async fn get_multiproof_handler(
+        State(this): State<Self>,
+        Json(request): Json<TreeProofsRequest>,
+    ) -> Result<Json<TreeEntriesWithMultiProof>, TreeApiServerError> {
+        let latency = API_METRICS.latency[&MerkleTreeApiMethod::GetMultiproof].start();
+        let response = this
+            .get_multiproof_inner(request.l1_batch_number, request.hashed_keys)
+            .await
+            .map_err(TreeApiServerError::NoTreeVersion)?;
+        latency.observe();
+        Ok(Json(response))
+    }
+
     async fn get_nodes_handler(
         State(this): State<Self>,
         Json(request): Json<TreeNodesRequest>,
@@ -507,6 +639,7 @@ impl AsyncTreeReader {
         let app = Router::new()
             .route("/", routing::get(Self::info_handler))
             .route("/proofs", routing::post(Self::get_proofs_handler))
+            .route("/multiproof", routing::post(Self::get_multiproof_handler))
             .route("/debug/nodes", routing::post(Self::get_nodes_handler))
             .route(
                 "/debug/stale-keys",
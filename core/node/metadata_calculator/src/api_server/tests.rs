@@ -8,7 +8,7 @@ use tokio::{
     io::AsyncWriteExt,
     net::{TcpListener, TcpSocket},
 };
-use zksync_dal::{ConnectionPool, Core};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
 
 use super::*;
 use crate::tests::{gen_storage_logs, reset_db_state, run_calculator, setup_calculator};
@@ -52,7 +52,7 @@ async fn merkle_tree_api() {
     hashed_keys.extend((0_u8..10).map(|byte| U256::from_big_endian(&[byte; 32])));
 
     let proofs = api_client
-        .get_proofs(L1BatchNumber(5), hashed_keys)
+        .get_proofs(L1BatchNumber(5), hashed_keys.clone())
         .await
         .unwrap();
     assert_eq!(proofs.len(), 20);
@@ -72,6 +72,32 @@ async fn merkle_tree_api() {
     assert_eq!(err.version_count, 6);
     assert_eq!(err.missing_version, 10);
 
+    let multiproof = api_client
+        .get_multiproof(L1BatchNumber(5), hashed_keys.clone())
+        .await
+        .unwrap();
+    assert_eq!(multiproof.entries.len(), 20);
+    for (i, entry) in multiproof.entries.iter().enumerate() {
+        assert_eq!(entry.index == 0, i >= 10);
+    }
+    let root_hash = pool
+        .connection()
+        .await
+        .unwrap()
+        .blocks_dal()
+        .get_l1_batch_state_root(L1BatchNumber(5))
+        .await
+        .unwrap()
+        .expect("no root hash");
+    multiproof.verify(&hashed_keys, root_hash).unwrap();
+    multiproof.verify(&hashed_keys, H256::zero()).unwrap_err();
+
+    let err = api_client
+        .get_multiproof(L1BatchNumber(10), vec![])
+        .await
+        .unwrap_err();
+    assert_matches!(err, TreeApiError::NoVersion(err) if err.missing_version == 10);
+
     let raw_nodes_response = api_client
         .inner
         .post(format!("http://{local_addr}/debug/nodes"))
@@ -0,0 +1,243 @@
+//! High-level wrapper for the tree consistency check task.
+
+use std::{
+    sync::{Arc, Weak},
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_health_check::{CheckHealth, Health, HealthStatus};
+use zksync_merkle_tree::repair;
+
+use crate::LazyAsyncTreeReader;
+
+#[derive(Debug, Serialize)]
+struct ConsistencyCheckHealthDetails {
+    checked_version_count: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_checked_version: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current_version: Option<u64>,
+    checked_subtree_count: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inconsistent_version: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_error: Option<String>,
+}
+
+impl From<repair::ConsistencyCheckStats> for ConsistencyCheckHealthDetails {
+    fn from(stats: repair::ConsistencyCheckStats) -> Self {
+        Self {
+            checked_version_count: stats.checked_version_count,
+            last_checked_version: stats.last_checked_version,
+            current_version: stats.current_version,
+            checked_subtree_count: stats.checked_subtree_count,
+            inconsistent_version: stats.inconsistent_version,
+            last_error: stats.last_error,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ConsistencyCheckHealthCheck {
+    handle: OnceCell<Weak<repair::ConsistencyCheckHandle>>,
+}
+
+#[async_trait]
+impl CheckHealth for ConsistencyCheckHealthCheck {
+    fn name(&self) -> &'static str {
+        "tree_consistency_check"
+    }
+
+    async fn check_health(&self) -> Health {
+        let Some(weak_handle) = self.handle.get() else {
+            return HealthStatus::Affected.into();
+        };
+        let Some(handle) = weak_handle.upgrade() else {
+            return HealthStatus::ShutDown.into();
+        };
+        let stats = handle.stats();
+        // An inconsistent tree doesn't prevent the node from operating, but it should be visible to the operator.
+        let status = if stats.inconsistent_version.is_some() {
+            HealthStatus::Affected
+        } else {
+            HealthStatus::Ready
+        };
+        Health::from(status).with_details(ConsistencyCheckHealthDetails::from(stats))
+    }
+}
+
+/// Task periodically verifying consistency of the latest Merkle tree version on a live node.
+#[derive(Debug)]
+#[must_use = "Task should `run()` in a managed Tokio task"]
+pub struct ConsistencyCheckTask {
+    tree_reader: LazyAsyncTreeReader,
+    health_check: Arc<ConsistencyCheckHealthCheck>,
+    poll_interval: Duration,
+    subtree_delay: Duration,
+}
+
+impl ConsistencyCheckTask {
+    pub(super) fn new(tree_reader: LazyAsyncTreeReader, poll_interval: Duration) -> Self {
+        Self {
+            tree_reader,
+            health_check: Arc::default(),
+            poll_interval,
+            subtree_delay: Duration::ZERO,
+        }
+    }
+
+    /// Sets the delay between checking each of 16 subtrees of the tree root. Can be used to limit
+    /// the load on the node caused by the check.
+    pub fn with_subtree_delay(mut self, delay: Duration) -> Self {
+        self.subtree_delay = delay;
+        self
+    }
+
+    pub fn health_check(&self) -> Arc<dyn CheckHealth> {
+        self.health_check.clone()
+    }
+
+    /// Runs this task indefinitely.
+    #[tracing::instrument(skip_all)]
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let db = tokio::select! {
+            res = self.tree_reader.wait() => {
+                match res {
+                    Some(reader) => reader.into_db(),
+                    None => {
+                        tracing::info!("Merkle tree dropped; shutting down consistency check");
+                        return Ok(());
+                    }
+                }
+            }
+            _ = stop_receiver.changed() => {
+                tracing::info!("Stop signal received before Merkle tree is initialized; shutting down consistency check");
+                return Ok(());
+            }
+        };
+
+        let (mut task, handle) = repair::ConsistencyCheckTask::new(db)?;
+        task.set_poll_interval(self.poll_interval);
+        task.set_subtree_delay(self.subtree_delay);
+        let handle = Arc::new(handle);
+        self.health_check
+            .handle
+            .set(Arc::downgrade(&handle))
+            .map_err(|_| anyhow::anyhow!("failed setting health check handle"))?;
+
+        let mut task = tokio::task::spawn_blocking(|| task.run());
+        tokio::select! {
+            res = &mut task => {
+                tracing::error!("Consistency check spontaneously stopped");
+                res.context("consistency check task panicked")?
+            },
+            _ = stop_receiver.changed() => {
+                tracing::info!("Stop signal received, consistency check is shutting down");
+                // This is the only strong reference to the handle, so dropping it should signal the task to stop.
+                drop(handle);
+                task.await.context("consistency check task panicked")?
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use tempfile::TempDir;
+    use zksync_dal::{ConnectionPool, Core};
+    use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+    use zksync_types::L1BatchNumber;
+
+    use super::*;
+    use crate::{
+        tests::{extend_db_state, gen_storage_logs, mock_config, reset_db_state},
+        MetadataCalculator,
+    };
+
+    const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+    async fn wait_for_health(
+        check: &dyn CheckHealth,
+        mut condition: impl FnMut(&Health) -> bool,
+    ) -> Health {
+        loop {
+            let health = check.check_health().await;
+            if condition(&health) {
+                return health;
+            } else if matches!(
+                health.status(),
+                HealthStatus::ShutDown | HealthStatus::Panicked
+            ) {
+                panic!("reached terminal health: {health:?}");
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn consistency_check_on_live_tree() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let config = mock_config(temp_dir.path());
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+        reset_db_state(&pool, 5).await;
+
+        let calculator = MetadataCalculator::new(config, None, pool.clone())
+            .await
+            .unwrap();
+        let reader = calculator.tree_reader();
+        let check_task = calculator
+            .consistency_check_task(POLL_INTERVAL)
+            .with_subtree_delay(Duration::from_millis(1));
+        let health_check = check_task.health_check();
+
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let calculator_handle = tokio::spawn(calculator.run(stop_receiver.clone()));
+        let check_task_handle = tokio::spawn(check_task.run(stop_receiver));
+
+        {
+            let reader = reader.wait().await.unwrap();
+            while reader.clone().info().await.next_l1_batch_number < L1BatchNumber(6) {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+        wait_for_health(&health_check, |health| {
+            let Some(details) = health.details() else {
+                return false;
+            };
+            details.get("last_checked_version") == Some(&5.into())
+        })
+        .await;
+
+        // Add more batches; the check should pick up the new latest version.
+        let logs = gen_storage_logs(200..300, 5);
+        extend_db_state(&mut storage, logs).await;
+        let health = wait_for_health(&health_check, |health| {
+            let Some(details) = health.details() else {
+                return false;
+            };
+            details.get("last_checked_version") == Some(&10.into())
+        })
+        .await;
+        assert_matches!(health.status(), HealthStatus::Ready);
+        let details = health.details().unwrap();
+        assert!(details.get("inconsistent_version").is_none());
+
+        stop_sender.send_replace(true);
+        calculator_handle.await.unwrap().unwrap();
+        check_task_handle.await.unwrap().unwrap();
+        wait_for_health(&health_check, |health| {
+            matches!(health.status(), HealthStatus::ShutDown)
+        })
+        .await;
+    }
+}
@@ -2,10 +2,11 @@
 
 use std::{
     collections::{BTreeMap, HashSet},
+    fs,
     future::Future,
-    path::Path,
+    path::{Path, PathBuf},
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use anyhow::Context as _;
@@ -24,11 +25,13 @@ use zksync_merkle_tree::{
     recovery::{MerkleTreeRecovery, PersistenceThreadHandle},
     repair::StaleKeysRepairTask,
     unstable::{NodeKey, RawNode},
-    Database, Key, MerkleTreeColumnFamily, NoVersionError, RocksDBWrapper, TreeEntry,
-    TreeEntryWithProof, TreeInstruction,
+    Database, Key, MerkleTreeColumnFamily, NoVersionError, RocksDBWrapper,
+    TreeEntriesWithMultiProof, TreeEntry, TreeEntryWithProof, TreeInstruction,
 };
 use zksync_shared_metrics::tree::{LoadChangesStage, TreeUpdateStage, METRICS};
-use zksync_storage::{RocksDB, RocksDBOptions, StalledWritesRetries, WeakRocksDB};
+use zksync_storage::{
+    CompactionOptions, RocksDB, RocksDBOptions, StalledWritesRetries, WeakRocksDB,
+};
 use zksync_types::{
     block::{L1BatchStatistics, L1BatchTreeData},
     writes::TreeWrite,
@@ -154,20 +157,25 @@ fn create_db_sync(config: &MetadataCalculatorConfig) -> anyhow::Result<RocksDBWr
         "Initializing Merkle tree database at `{path}` (max open files: {max_open_files:?}) with {multi_get_chunk_size} multi-get chunk size, \
          {block_cache_capacity}B block cache (indices & filters included: {include_indices_and_filters_in_block_cache:?}), \
          {memtable_capacity}B memtable capacity, \
-         {stalled_writes_timeout:?} stalled writes timeout",
-        path = path.display()
+         {stalled_writes_timeout:?} stalled writes timeout (in memory: {in_memory})",
+        path = path.display(),
+        in_memory = config.in_memory
     );
 
-    let mut db = RocksDB::with_options(
-        path,
-        RocksDBOptions {
-            block_cache_capacity: Some(block_cache_capacity),
-            include_indices_and_filters_in_block_cache,
-            large_memtable_capacity: Some(memtable_capacity),
-            stalled_writes_retries: StalledWritesRetries::new(stalled_writes_timeout),
-            max_open_files,
-        },
-    )?;
+    let options = RocksDBOptions {
+        block_cache_capacity: Some(block_cache_capacity),
+        include_indices_and_filters_in_block_cache,
+        large_memtable_capacity: Some(memtable_capacity),
+        large_cf_block_cache_capacity: None,
+        large_cf_compaction: CompactionOptions::default(),
+        stalled_writes_retries: StalledWritesRetries::new(stalled_writes_timeout),
+        max_open_files,
+        in_memory: config.in_memory,
+    };
+    let mut db = RocksDB::with_options(path, options)?;
+    if config.in_memory {
+        load_persisted_db(&db, path)?;
+    }
     if cfg!(test) {
         // We need sync writes for the unit tests to execute reliably. With the default config,
         // some writes to RocksDB may occur, but not be visible to the test code.
@@ -178,6 +186,90 @@ fn create_db_sync(config: &MetadataCalculatorConfig) -> anyhow::Result<RocksDBWr
     Ok(db)
 }
 
+/// Returns the path to the backup of a persisted in-memory tree, which is used while the persisted tree is being replaced.
+fn backup_path(path: &Path) -> PathBuf {
+    path.with_extension("old")
+}
+
+/// Loads the tree persisted at `path` (if any) into the in-memory `db`.
+fn load_persisted_db(db: &RocksDB<MerkleTreeColumnFamily>, path: &Path) -> anyhow::Result<()> {
+    let backup_path = backup_path(path);
+    let persisted_path = if path.exists() {
+        path
+    } else if backup_path.exists() {
+        // The node was terminated while replacing the persisted tree; the backup is complete.
+        backup_path.as_path()
+    } else {
+        tracing::info!(
+            "No persisted Merkle tree at `{}`; starting with an empty in-memory tree",
+            path.display()
+        );
+        return Ok(());
+    };
+
+    let started_at = Instant::now();
+    let persisted_db =
+        RocksDB::<MerkleTreeColumnFamily>::new(persisted_path).with_context(|| {
+            format!(
+                "failed opening persisted Merkle tree at `{}`",
+                persisted_path.display()
+            )
+        })?;
+    db.copy_from(&persisted_db)
+        .context("failed loading persisted Merkle tree into memory")?;
+    tracing::info!(
+        "Loaded persisted Merkle tree from `{}` into memory in {:?}",
+        persisted_path.display(),
+        started_at.elapsed()
+    );
+    Ok(())
+}
+
+/// Persists a consistent snapshot of the in-memory `db` to `path`, replacing the previously persisted tree.
+fn persist_db(db: &RocksDB<MerkleTreeColumnFamily>, path: &Path) -> anyhow::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    let backup_path = backup_path(path);
+    if tmp_path.exists() {
+        fs::remove_dir_all(&tmp_path)
+            .with_context(|| format!("failed removing `{}`", tmp_path.display()))?;
+    }
+
+    let persisted_db = RocksDB::<MerkleTreeColumnFamily>::new(&tmp_path)
+        .with_context(|| format!("failed creating RocksDB at `{}`", tmp_path.display()))?;
+    persisted_db
+        .copy_from(db)
+        .context("failed copying in-memory Merkle tree")?;
+    drop(persisted_db);
+
+    // The previously persisted tree is only removed after the new one is in place, so that there's always
+    // a complete persisted tree on disk.
+    if path.exists() {
+        if backup_path.exists() {
+            fs::remove_dir_all(&backup_path)
+                .with_context(|| format!("failed removing `{}`", backup_path.display()))?;
+        }
+        fs::rename(path, &backup_path).with_context(|| {
+            format!(
+                "failed moving `{}` to `{}`",
+                path.display(),
+                backup_path.display()
+            )
+        })?;
+    }
+    fs::rename(&tmp_path, path).with_context(|| {
+        format!(
+            "failed moving `{}` to `{}`",
+            tmp_path.display(),
+            path.display()
+        )
+    })?;
+    if backup_path.exists() {
+        fs::remove_dir_all(&backup_path)
+            .with_context(|| format!("failed removing `{}`", backup_path.display()))?;
+    }
+    Ok(())
+}
+
 pub(super) async fn create_readonly_db(
     config: MerkleTreeReaderConfig,
 ) -> anyhow::Result<RocksDBWrapper> {
@@ -257,6 +349,10 @@ impl AsyncTree {
         self.as_mut().pruner()
     }
 
+    pub fn use_dedicated_thread_pool(&mut self, thread_count: usize) {
+        self.as_mut().use_dedicated_thread_pool(thread_count);
+    }
+
     pub fn reader(&self) -> AsyncTreeReader {
         AsyncTreeReader {
             inner: self.inner.as_ref().expect(Self::INCONSISTENT_MSG).reader(),
@@ -333,6 +429,15 @@ impl AsyncTree {
     pub fn roll_back_logs(&mut self, last_l1_batch_to_keep: L1BatchNumber) -> anyhow::Result<()> {
         self.as_mut().roll_back_logs(last_l1_batch_to_keep)
     }
+
+    /// Persists a consistent snapshot of the tree to an on-disk RocksDB instance at `path`, replacing the previously
+    /// persisted tree. Only makes sense for in-memory trees.
+    pub async fn persist(&self, path: PathBuf) -> anyhow::Result<()> {
+        let db = self.as_ref().reader().db().clone().into_inner();
+        tokio::task::spawn_blocking(move || persist_db(&db, &path))
+            .await
+            .context("panicked persisting Merkle tree")?
+    }
 }
 
 /// Async version of [`ZkSyncTreeReader`].
@@ -410,6 +515,18 @@ impl AsyncTreeReader {
             .unwrap()
     }
 
+    pub async fn entries_with_multiproof(
+        self,
+        l1_batch_number: L1BatchNumber,
+        keys: Vec<Key>,
+    ) -> Result<TreeEntriesWithMultiProof, NoVersionError> {
+        tokio::task::spawn_blocking(move || {
+            self.inner.entries_with_multiproof(l1_batch_number, &keys)
+        })
+        .await
+        .unwrap()
+    }
+
     pub(crate) async fn raw_nodes(self, keys: Vec<NodeKey>) -> Vec<Option<RawNode>> {
         tokio::task::spawn_blocking(move || self.inner.raw_nodes(&keys))
             .await
@@ -479,6 +596,7 @@ impl LazyAsyncTreeReader {
 pub(super) struct AsyncTreeRecovery {
     inner: Option<MerkleTreeRecovery<RocksDBWrapper>>,
     mode: MerkleTreeMode,
+    parallel_extension: bool,
 }
 
 impl AsyncTreeRecovery {
@@ -509,6 +627,7 @@ impl AsyncTreeRecovery {
         let this = Self {
             inner: Some(recovery),
             mode,
+            parallel_extension: config.parallel_extension,
         };
         Ok((this, handle))
     }
@@ -577,8 +696,13 @@ impl AsyncTreeRecovery {
     /// Extends the tree with a chunk of recovery entries.
     pub async fn extend(&mut self, entries: Vec<TreeEntry>) -> anyhow::Result<()> {
         let mut tree = self.inner.take().expect(Self::INCONSISTENT_MSG);
+        let parallel_extension = self.parallel_extension;
         let tree = tokio::task::spawn_blocking(move || {
-            tree.extend_random(entries)?;
+            if parallel_extension {
+                tree.extend_parallel(entries)?;
+            } else {
+                tree.extend_random(entries)?;
+            }
             anyhow::Ok(tree)
         })
         .await
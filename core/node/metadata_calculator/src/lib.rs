@@ -18,19 +18,21 @@ use zksync_health_check::{CheckHealth, HealthUpdater, ReactiveHealthCheck};
 use zksync_object_store::ObjectStore;
 use zksync_shared_metrics::tree::METRICS;
 
+pub use self::{
+    consistency::ConsistencyCheckTask,
+    helpers::{AsyncTreeReader, LazyAsyncTreeReader, MerkleTreeInfo},
+    pruning::{MerkleTreePruningPolicy, MerkleTreePruningTask},
+    repair::StaleKeysRepairTask,
+};
 use self::{
     helpers::{create_db, Delayer, GenericAsyncTree, MerkleTreeHealth, MerkleTreeHealthCheck},
     pruning::PruningHandles,
     updater::TreeUpdater,
 };
-pub use self::{
-    helpers::{AsyncTreeReader, LazyAsyncTreeReader, MerkleTreeInfo},
-    pruning::MerkleTreePruningTask,
-    repair::StaleKeysRepairTask,
-};
 use crate::helpers::create_readonly_db;
 
 pub mod api_server;
+mod consistency;
 mod helpers;
 mod metrics;
 mod pruning;
@@ -53,6 +55,9 @@ pub struct MetadataCalculatorRecoveryConfig {
     ///
     /// If set to `None`, parallel persistence will be disabled.
     pub parallel_persistence_buffer: Option<NonZeroUsize>,
+    /// Whether to insert entries of each recovered chunk into the tree in parallel, with a separate writer
+    /// for each of 16 subtrees at the top of the tree. Speeds up recovery on multi-core machines.
+    pub parallel_extension: bool,
 }
 
 impl Default for MetadataCalculatorRecoveryConfig {
@@ -60,6 +65,7 @@ impl Default for MetadataCalculatorRecoveryConfig {
         Self {
             desired_chunk_size: 200_000,
             parallel_persistence_buffer: NonZeroUsize::new(4),
+            parallel_extension: false,
         }
     }
 }
@@ -92,10 +98,21 @@ pub struct MetadataCalculatorConfig {
     pub memtable_capacity: usize,
     /// Timeout to wait for the Merkle tree database to run compaction on stalled writes.
     pub stalled_writes_timeout: Duration,
+    /// Number of threads in a dedicated thread pool used for hashing. If not specified, the global `rayon`
+    /// thread pool is used.
+    pub hashing_thread_count: Option<usize>,
     /// Whether state keeper writes protective reads when it seals a batch.
     pub sealed_batches_have_protective_reads: bool,
     /// Configuration specific to the Merkle tree recovery.
     pub recovery: MetadataCalculatorRecoveryConfig,
+    /// Whether to keep the tree entirely in RAM. If set, the tree persisted at [`Self::db_path`] (if any)
+    /// is loaded into memory on startup. Since the tree is not shared via the filesystem, it cannot be read
+    /// by a [`TreeReaderTask`] running in a separate process.
+    pub in_memory: bool,
+    /// Interval between persisting the in-memory tree to [`Self::db_path`]. The tree is also persisted
+    /// on graceful shutdown. If not set, the in-memory tree is never persisted.
+    /// Has no effect if [`Self::in_memory`] is not set.
+    pub in_memory_persistence_interval: Option<Duration>,
 }
 
 impl MetadataCalculatorConfig {
@@ -115,10 +132,13 @@ impl MetadataCalculatorConfig {
             include_indices_and_filters_in_block_cache: false,
             memtable_capacity: merkle_tree_config.memtable_capacity(),
             stalled_writes_timeout: merkle_tree_config.stalled_writes_timeout(),
+            hashing_thread_count: merkle_tree_config.hashing_thread_count,
             sealed_batches_have_protective_reads: state_keeper_config
                 .protective_reads_persistence_enabled,
             // The main node isn't supposed to be recovered yet, so this value doesn't matter much
             recovery: MetadataCalculatorRecoveryConfig::default(),
+            in_memory: merkle_tree_config.in_memory,
+            in_memory_persistence_interval: merkle_tree_config.in_memory_persistence_interval(),
         }
     }
 }
@@ -196,8 +216,8 @@ impl MetadataCalculator {
         LazyAsyncTreeReader(self.tree_reader.subscribe())
     }
 
-    /// Returns a task that can be used to prune the Merkle tree according to the pruning logs in Postgres.
-    /// This method should be called once; only the latest returned task will do any job, all previous ones
+    /// Returns a task that can be used to prune the Merkle tree. By default, the task prunes the tree according
+    /// to the pruning logs in Postgres; this can be changed using [`MerkleTreePruningTask::with_policy()`]. This method should be called once; only the latest returned task will do any job, all previous ones
     /// will terminate immediately.
     pub fn pruning_task(&mut self, poll_interval: Duration) -> MerkleTreePruningTask {
         let (pruning_handles_sender, pruning_handles) = oneshot::channel();
@@ -210,6 +230,13 @@ impl MetadataCalculator {
         StaleKeysRepairTask::new(self.tree_reader())
     }
 
+    /// Returns a task that periodically verifies consistency of the latest tree version, checking for new versions
+    /// with the specified interval. The check is split into small steps, so it can run on a live node.
+    /// This method should be called once.
+    pub fn consistency_check_task(&self, poll_interval: Duration) -> ConsistencyCheckTask {
+        ConsistencyCheckTask::new(self.tree_reader(), poll_interval)
+    }
+
     async fn create_tree(&self) -> anyhow::Result<GenericAsyncTree> {
         self.health_updater
             .update(MerkleTreeHealth::Initialization.into());
@@ -244,6 +271,12 @@ impl MetadataCalculator {
         let Some(mut tree) = tree else {
             return Ok(()); // recovery was aborted because a stop signal was received
         };
+        if let Some(thread_count) = self.config.hashing_thread_count {
+            tracing::info!(
+                "Using dedicated thread pool with {thread_count} threads for Merkle tree hashing"
+            );
+            tree.use_dedicated_thread_pool(thread_count);
+        }
         // Set a tree reader before the tree is fully initialized to not wait for the first L1 batch to appear in Postgres.
         let tree_reader = tree.reader();
         self.tree_reader.send_replace(Some(tree_reader));
@@ -261,12 +294,18 @@ impl MetadataCalculator {
         self.health_updater
             .update(MerkleTreeHealth::MainLoop(tree_info).into());
 
-        let updater = TreeUpdater::new(
+        let mut updater = TreeUpdater::new(
             tree,
             self.max_l1_batches_per_iter,
             self.object_store,
             self.config.sealed_batches_have_protective_reads,
         );
+        if self.config.in_memory {
+            if let Some(interval) = self.config.in_memory_persistence_interval {
+                updater = updater
+                    .with_in_memory_persistence(self.config.db_path.clone().into(), interval);
+            }
+        }
         updater
             .loop_updating_tree(self.delayer, &self.pool, stop_receiver)
             .await
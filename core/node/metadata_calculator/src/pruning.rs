@@ -1,6 +1,6 @@
 //! Merkle tree pruning logic.
 
-use std::time::Duration;
+use std::{num::NonZeroU32, time::Duration};
 
 use anyhow::Context as _;
 use serde::Serialize;
@@ -36,7 +36,109 @@ impl From<MerkleTreePruningTaskHealth> for Health {
     }
 }
 
-/// Task performing Merkle tree pruning according to the pruning entries in Postgres.
+/// Policy determining which Merkle tree versions are retained by [`MerkleTreePruningTask`].
+///
+/// Tree versions correspond to L1 batches. The tree always retains a contiguous range of versions ending with
+/// the latest version, so the policy boils down to selecting the first retained version. It is computed as the greatest
+/// version allowed by the enabled pruning triggers, which is then capped by the enabled retention guards.
+///
+/// The default policy follows Postgres pruning and has no guards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleTreePruningPolicy {
+    /// Prune tree versions for L1 batches hard-pruned in Postgres.
+    pub follow_postgres_pruning: bool,
+    /// Retain versions for at least this number of latest L1 batches processed by the tree; prune older versions.
+    pub retained_l1_batches: Option<NonZeroU32>,
+    /// Never prune versions for L1 batches for which the proof isn't generated (or skipped) yet.
+    pub retain_unproven_l1_batches: bool,
+}
+
+impl Default for MerkleTreePruningPolicy {
+    fn default() -> Self {
+        Self {
+            follow_postgres_pruning: true,
+            retained_l1_batches: None,
+            retain_unproven_l1_batches: false,
+        }
+    }
+}
+
+impl MerkleTreePruningPolicy {
+    /// Creates a policy retaining versions for the specified number of latest L1 batches, which doesn't depend
+    /// on Postgres pruning.
+    pub fn retention_window(retained_l1_batches: NonZeroU32) -> Self {
+        Self {
+            follow_postgres_pruning: false,
+            retained_l1_batches: Some(retained_l1_batches),
+            retain_unproven_l1_batches: false,
+        }
+    }
+
+    /// Protects versions for L1 batches without a generated proof from pruning.
+    #[must_use]
+    pub fn retaining_unproven_l1_batches(mut self) -> Self {
+        self.retain_unproven_l1_batches = true;
+        self
+    }
+
+    fn target_retained_l1_batch(&self, state: &PruningState) -> Option<L1BatchNumber> {
+        let postgres_target = if self.follow_postgres_pruning {
+            state.last_hard_pruned_l1_batch.map(|number| number + 1)
+        } else {
+            None
+        };
+        let window_target = self
+            .retained_l1_batches
+            .zip(state.last_l1_batch_with_tree_data)
+            .and_then(|(retained, last)| (last.0 + 1).checked_sub(retained.get()))
+            .map(L1BatchNumber);
+
+        let target = postgres_target.max(window_target)?;
+        Some(match state.oldest_unproven_l1_batch {
+            Some(oldest_unproven) if self.retain_unproven_l1_batches => target.min(oldest_unproven),
+            _ => target,
+        })
+    }
+}
+
+/// Postgres state used to compute the target retained tree version.
+#[derive(Debug, Default)]
+struct PruningState {
+    last_hard_pruned_l1_batch: Option<L1BatchNumber>,
+    last_l1_batch_with_tree_data: Option<L1BatchNumber>,
+    oldest_unproven_l1_batch: Option<L1BatchNumber>,
+}
+
+impl PruningState {
+    async fn load(
+        pool: &ConnectionPool<Core>,
+        policy: &MerkleTreePruningPolicy,
+    ) -> anyhow::Result<Self> {
+        let mut storage = pool.connection_tagged("metadata_calculator").await?;
+        let mut state = Self::default();
+        if policy.follow_postgres_pruning {
+            let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+            state.last_hard_pruned_l1_batch =
+                pruning_info.last_hard_pruned.map(|info| info.l1_batch);
+        }
+        if policy.retained_l1_batches.is_some() {
+            state.last_l1_batch_with_tree_data = storage
+                .blocks_dal()
+                .get_last_l1_batch_number_with_tree_data()
+                .await?;
+        }
+        if policy.retain_unproven_l1_batches {
+            state.oldest_unproven_l1_batch = storage
+                .proof_generation_dal()
+                .get_oldest_not_generated_batch()
+                .await?;
+        }
+        Ok(state)
+    }
+}
+
+/// Task performing Merkle tree pruning according to a [`MerkleTreePruningPolicy`]. By default, the tree is pruned
+/// according to the pruning entries in Postgres.
 #[derive(Debug)]
 #[must_use = "Task should `run()` in a managed Tokio task"]
 pub struct MerkleTreePruningTask {
@@ -44,6 +146,7 @@ pub struct MerkleTreePruningTask {
     pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
     poll_interval: Duration,
+    policy: MerkleTreePruningPolicy,
 }
 
 impl MerkleTreePruningTask {
@@ -57,9 +160,17 @@ impl MerkleTreePruningTask {
             pool,
             health_updater: ReactiveHealthCheck::new("tree_pruner").1,
             poll_interval,
+            policy: MerkleTreePruningPolicy::default(),
         }
     }
 
+    /// Sets the policy determining retained tree versions.
+    #[must_use]
+    pub fn with_policy(mut self, policy: MerkleTreePruningPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
     }
@@ -97,12 +208,10 @@ impl MerkleTreePruningTask {
         let pruner_task_handle = tokio::task::spawn_blocking(|| pruner.run());
 
         while !*stop_receiver.borrow_and_update() {
-            let mut storage = self.pool.connection_tagged("metadata_calculator").await?;
-            let pruning_info = storage.pruning_dal().get_pruning_info().await?;
-            drop(storage);
-
-            if let Some(pruned) = pruning_info.last_hard_pruned {
-                let target_retained_l1_batch_number = pruned.l1_batch + 1;
+            let state = PruningState::load(&self.pool, &self.policy).await?;
+            if let Some(target_retained_l1_batch_number) =
+                self.policy.target_retained_l1_batch(&state)
+            {
                 let target_retained_version = u64::from(target_retained_l1_batch_number.0);
                 let Ok(prev_target_version) =
                     pruner_handle.set_target_retained_version(target_retained_version)
@@ -115,7 +224,8 @@ impl MerkleTreePruningTask {
                         .context("Merkle tree pruning thread panicked")?;
                 };
 
-                if prev_target_version != target_retained_version {
+                // The target may go back if a retention guard is triggered; the pruner ignores such updates.
+                if prev_target_version < target_retained_version {
                     let health = MerkleTreePruningTaskHealth::Pruning {
                         target_retained_l1_batch_number: Some(target_retained_l1_batch_number),
                     };
@@ -158,6 +268,111 @@ mod tests {
 
     const POLL_INTERVAL: Duration = Duration::from_millis(50);
 
+    #[test]
+    fn computing_target_retained_l1_batch() {
+        let state = PruningState {
+            last_hard_pruned_l1_batch: Some(L1BatchNumber(10)),
+            last_l1_batch_with_tree_data: Some(L1BatchNumber(100)),
+            oldest_unproven_l1_batch: Some(L1BatchNumber(50)),
+        };
+        let retained = NonZeroU32::new(20).unwrap();
+
+        let policy = MerkleTreePruningPolicy::default();
+        assert_eq!(
+            policy.target_retained_l1_batch(&state),
+            Some(L1BatchNumber(11))
+        );
+        let policy = MerkleTreePruningPolicy::retention_window(retained);
+        assert_eq!(
+            policy.target_retained_l1_batch(&state),
+            Some(L1BatchNumber(81))
+        );
+        let policy = policy.retaining_unproven_l1_batches();
+        assert_eq!(
+            policy.target_retained_l1_batch(&state),
+            Some(L1BatchNumber(50))
+        );
+
+        let policy = MerkleTreePruningPolicy {
+            retained_l1_batches: Some(retained),
+            ..MerkleTreePruningPolicy::default()
+        };
+        assert_eq!(
+            policy.target_retained_l1_batch(&state),
+            Some(L1BatchNumber(81))
+        );
+        let state_with_lagging_tree = PruningState {
+            last_l1_batch_with_tree_data: Some(L1BatchNumber(15)),
+            ..state
+        };
+        assert_eq!(
+            policy.target_retained_l1_batch(&state_with_lagging_tree),
+            Some(L1BatchNumber(11))
+        );
+    }
+
+    #[test]
+    fn target_retained_l1_batch_without_data() {
+        let policy = MerkleTreePruningPolicy::retention_window(NonZeroU32::new(20).unwrap())
+            .retaining_unproven_l1_batches();
+        assert_eq!(
+            policy.target_retained_l1_batch(&PruningState::default()),
+            None
+        );
+
+        let state = PruningState {
+            last_l1_batch_with_tree_data: Some(L1BatchNumber(10)),
+            ..PruningState::default()
+        };
+        assert_eq!(policy.target_retained_l1_batch(&state), None);
+        let state = PruningState {
+            last_l1_batch_with_tree_data: Some(L1BatchNumber(100)),
+            ..PruningState::default()
+        };
+        // All batches are proven.
+        assert_eq!(
+            policy.target_retained_l1_batch(&state),
+            Some(L1BatchNumber(81))
+        );
+    }
+
+    #[tokio::test]
+    async fn tree_pruning_with_retention_window() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let config = mock_config(temp_dir.path());
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+        reset_db_state(&pool, 5).await;
+
+        let mut calculator = MetadataCalculator::new(config, None, pool.clone())
+            .await
+            .unwrap();
+        let reader = calculator.tree_reader();
+        let policy = MerkleTreePruningPolicy::retention_window(NonZeroU32::new(2).unwrap());
+        let pruning_task = calculator.pruning_task(POLL_INTERVAL).with_policy(policy);
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let calculator_handle = tokio::spawn(calculator.run(stop_receiver.clone()));
+        let pruning_task_handle = tokio::spawn(pruning_task.run(stop_receiver));
+
+        let reader = reader.wait().await.unwrap();
+        // Only versions for L1 batches #4 and #5 should be retained.
+        loop {
+            let tree_info = reader.clone().info().await;
+            if tree_info.min_l1_batch_number == Some(L1BatchNumber(4)) {
+                break;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        reader.verify_consistency(L1BatchNumber(5)).await.unwrap();
+
+        stop_sender.send_replace(true);
+        calculator_handle.await.unwrap().unwrap();
+        pruning_task_handle.await.unwrap().unwrap();
+    }
+
     #[tokio::test]
     async fn basic_tree_pruning_workflow() {
         let pool = ConnectionPool::<Core>::test_pool().await;
@@ -62,14 +62,18 @@ async fn create_tree_recovery(
     AsyncTreeRecovery::with_handle(db, l1_batch.0.into(), MerkleTreeMode::Full, config).unwrap()
 }
 
+#[test_casing(2, [false, true])]
 #[tokio::test]
-async fn basic_recovery_workflow() {
+async fn basic_recovery_workflow(parallel_extension: bool) {
     let pool = ConnectionPool::<Core>::test_pool().await;
     let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
     let root_hash = prepare_storage_logs(pool.clone(), &temp_dir).await;
     prune_storage(&pool, L1BatchNumber(1)).await;
 
-    let config = MetadataCalculatorRecoveryConfig::default();
+    let config = MetadataCalculatorRecoveryConfig {
+        parallel_extension,
+        ..MetadataCalculatorRecoveryConfig::default()
+    };
     let init_params = InitParameters::new(&pool, &config)
         .await
         .unwrap()
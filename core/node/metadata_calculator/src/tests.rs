@@ -57,8 +57,11 @@ pub(super) fn mock_config(db_path: &Path) -> MetadataCalculatorConfig {
         include_indices_and_filters_in_block_cache: false,
         memtable_capacity: 16 << 20,            // 16 MiB
         stalled_writes_timeout: Duration::ZERO, // writes should never be stalled in tests
+        hashing_thread_count: None,
         sealed_batches_have_protective_reads: true,
         recovery: MetadataCalculatorRecoveryConfig::default(),
+        in_memory: false,
+        in_memory_persistence_interval: None,
     }
 }
 
@@ -293,6 +296,104 @@ async fn expected_tree_hash(pool: &ConnectionPool<Core>, sealed_protective_reads
     ZkSyncTree::process_genesis_batch(&all_logs).root_hash
 }
 
+#[tokio::test]
+async fn in_memory_tree_workflow() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (merkle_tree_config, operation_config) =
+        create_config(temp_dir.path(), MerkleTreeMode::Full);
+    let in_memory_config = MerkleTreeConfig {
+        in_memory: true,
+        in_memory_persistence_interval_sec: Some(3_600),
+        ..merkle_tree_config.clone()
+    };
+    let state_keeper_config = StateKeeperConfig {
+        protective_reads_persistence_enabled: true,
+        ..StateKeeperConfig::default()
+    };
+
+    let calculator = setup_calculator_with_options(
+        &in_memory_config,
+        &operation_config,
+        &state_keeper_config,
+        pool.clone(),
+        None,
+    )
+    .await;
+    reset_db_state(&pool, 5).await;
+    let tree_reader = calculator.tree_reader();
+    let merkle_tree_hash = run_calculator(calculator).await;
+    assert_eq!(merkle_tree_hash, expected_tree_hash(&pool, true).await);
+
+    let tree_reader = tree_reader.wait().await.unwrap();
+    for number in 0..=5 {
+        tree_reader
+            .clone()
+            .verify_consistency(L1BatchNumber(number))
+            .await
+            .unwrap();
+    }
+    // The tree must be persisted on shutdown.
+    assert!(Path::new(&merkle_tree_config.path).exists());
+
+    // Check that the persisted tree can be opened both on disk and in memory.
+    for config in [&merkle_tree_config, &in_memory_config] {
+        let calculator = setup_calculator_with_options(
+            config,
+            &operation_config,
+            &state_keeper_config,
+            pool.clone(),
+            None,
+        )
+        .await;
+        let tree = calculator.create_tree().await.unwrap();
+        let GenericAsyncTree::Ready(tree) = tree else {
+            panic!("Unexpected tree state: {tree:?}");
+        };
+        assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(6));
+        assert_eq!(tree.root_hash(), merkle_tree_hash);
+        tree.reader()
+            .verify_consistency(L1BatchNumber(5))
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn in_memory_tree_without_persistence() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (merkle_tree_config, operation_config) =
+        create_config(temp_dir.path(), MerkleTreeMode::Full);
+    let merkle_tree_config = MerkleTreeConfig {
+        in_memory: true,
+        ..merkle_tree_config
+    };
+
+    let calculator = setup_calculator_with_options(
+        &merkle_tree_config,
+        &operation_config,
+        &StateKeeperConfig::default(),
+        pool.clone(),
+        None,
+    )
+    .await;
+    reset_db_state(&pool, 3).await;
+    let tree_reader = calculator.tree_reader();
+    run_calculator(calculator).await;
+
+    let tree_reader = tree_reader.wait().await.unwrap();
+    assert_eq!(
+        tree_reader.clone().info().await.next_l1_batch_number,
+        L1BatchNumber(4)
+    );
+    tree_reader
+        .verify_consistency(L1BatchNumber(3))
+        .await
+        .unwrap();
+    assert!(!Path::new(&merkle_tree_config.path).exists());
+}
+
 #[test_casing(2, [false, true])]
 #[tokio::test]
 async fn status_receiver_has_correct_states(sealed_protective_reads: bool) {
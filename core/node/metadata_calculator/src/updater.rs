@@ -1,6 +1,11 @@
 //! Tree updater trait and its implementations.
 
-use std::{ops, sync::Arc, time::Instant};
+use std::{
+    ops,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::Context as _;
 use futures::{future, FutureExt};
@@ -17,12 +22,43 @@ use zksync_types::{
 
 use super::helpers::{AsyncTree, Delayer, L1BatchWithLogs};
 
+/// Periodic persistence of an in-memory tree.
+#[derive(Debug)]
+struct InMemoryTreePersistence {
+    path: PathBuf,
+    interval: Duration,
+    last_persisted_at: Instant,
+}
+
+impl InMemoryTreePersistence {
+    async fn persist(&mut self, tree: &AsyncTree) -> anyhow::Result<()> {
+        let started_at = Instant::now();
+        tree.persist(self.path.clone()).await?;
+        self.last_persisted_at = Instant::now();
+        tracing::info!(
+            "Persisted in-memory Merkle tree (next L1 batch: #{}) to `{}` in {:?}",
+            tree.next_l1_batch_number(),
+            self.path.display(),
+            started_at.elapsed()
+        );
+        Ok(())
+    }
+
+    async fn persist_if_due(&mut self, tree: &AsyncTree) -> anyhow::Result<()> {
+        if self.last_persisted_at.elapsed() >= self.interval {
+            self.persist(tree).await?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub(super) struct TreeUpdater {
     tree: AsyncTree,
     max_l1_batches_per_iter: usize,
     object_store: Option<Arc<dyn ObjectStore>>,
     sealed_batches_have_protective_reads: bool,
+    persistence: Option<InMemoryTreePersistence>,
 }
 
 impl TreeUpdater {
@@ -37,9 +73,20 @@ impl TreeUpdater {
             max_l1_batches_per_iter,
             object_store,
             sealed_batches_have_protective_reads,
+            persistence: None,
         }
     }
 
+    /// Enables persisting the (in-memory) tree to `path` with the specified interval and on shutdown.
+    pub fn with_in_memory_persistence(mut self, path: PathBuf, interval: Duration) -> Self {
+        self.persistence = Some(InMemoryTreePersistence {
+            path,
+            interval,
+            last_persisted_at: Instant::now(),
+        });
+        self
+    }
+
     async fn process_l1_batch(
         &mut self,
         l1_batch: L1BatchWithLogs,
@@ -260,6 +307,9 @@ impl TreeUpdater {
 
             let snapshot = *next_l1_batch_to_process;
             self.step(pool, &mut next_l1_batch_to_process).await?;
+            if let Some(persistence) = &mut self.persistence {
+                persistence.persist_if_due(&self.tree).await?;
+            }
             let delay = if snapshot == *next_l1_batch_to_process {
                 tracing::trace!(
                     "Metadata calculator (next L1 batch: #{next_l1_batch_to_process}) \
@@ -283,6 +333,10 @@ impl TreeUpdater {
                 () = delay => { /* The delay has passed */ }
             }
         }
+
+        if let Some(persistence) = &mut self.persistence {
+            persistence.persist(&self.tree).await?;
+        }
         Ok(())
     }
 }
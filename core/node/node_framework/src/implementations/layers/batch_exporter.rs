@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use zksync_batch_exporter::{
+    BatchArtifactsSink, BatchExporter, BatchExporterConfig, FileSink, ParquetSink,
+};
+use zksync_config::configs::{
+    batch_exporter::BatchExportFormat, BatchExporterConfig as BatchExporterFileConfig,
+};
+
+use crate::{
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource,
+        pools::{MasterPool, PoolResource},
+    },
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for the exporter of sealed L1 batch artifacts. Artifacts are written to JSON or Parquet files
+/// in the configured directory.
+#[derive(Debug)]
+pub struct BatchExporterLayer {
+    config: BatchExporterFileConfig,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub batch_exporter: BatchExporter,
+}
+
+impl BatchExporterLayer {
+    pub fn new(config: BatchExporterFileConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for BatchExporterLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "batch_exporter_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let main_pool = input.master_pool.get().await?;
+        let exporter_config = BatchExporterConfig {
+            poll_interval: self.config.poll_interval(),
+            max_batches_per_iteration: self.config.max_batches_per_iteration(),
+        };
+        let sink: Arc<dyn BatchArtifactsSink> = match self.config.format {
+            BatchExportFormat::Json => Arc::new(FileSink::new(&self.config.output_path)),
+            BatchExportFormat::Parquet => Arc::new(ParquetSink::new(&self.config.output_path)),
+        };
+        let batch_exporter = BatchExporter::new(exporter_config, main_pool, sink);
+
+        input
+            .app_health
+            .0
+            .insert_component(batch_exporter.health_check())
+            .map_err(WiringError::internal)?;
+        Ok(Output { batch_exporter })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for BatchExporter {
+    fn id(&self) -> TaskId {
+        "batch_exporter".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use zksync_config::ObjectStoreConfig;
+use zksync_node_db_pruner::{ColdStorageArchiver, ColdStorageConfig};
+use zksync_object_store::ObjectStoreFactory;
+use zksync_types::cold_storage::ColdStorageDataKind;
+
+use crate::{
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource,
+        pools::{MasterPool, PoolResource},
+    },
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for the cold storage archiver moving old call traces and events from Postgres to an object store.
+#[derive(Debug)]
+pub struct ColdStorageLayer {
+    config: ColdStorageConfig,
+    object_store_config: ObjectStoreConfig,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub cold_storage_archiver: ColdStorageArchiver,
+}
+
+impl ColdStorageLayer {
+    const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+    pub fn new(archive_after: Duration, object_store_config: ObjectStoreConfig) -> Self {
+        Self {
+            config: ColdStorageConfig {
+                archive_after,
+                data_kinds: ColdStorageDataKind::ALL.to_vec(),
+                poll_interval: Self::POLL_INTERVAL,
+            },
+            object_store_config,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ColdStorageLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "cold_storage_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let main_pool = input.master_pool.get().await?;
+        // A dedicated store is used instead of `ObjectStoreResource` so that archives can be placed
+        // separately from other node data.
+        let object_store = ObjectStoreFactory::new(self.object_store_config)
+            .create_store()
+            .await?;
+        let cold_storage_archiver = ColdStorageArchiver::new(self.config, main_pool, object_store);
+
+        input
+            .app_health
+            .0
+            .insert_component(cold_storage_archiver.health_check())
+            .map_err(WiringError::internal)?;
+        Ok(Output {
+            cold_storage_archiver,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for ColdStorageArchiver {
+    fn id(&self) -> TaskId {
+        "cold_storage_archiver".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
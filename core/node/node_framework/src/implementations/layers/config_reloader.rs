@@ -0,0 +1,488 @@
+use std::{
+    fmt,
+    num::NonZeroU32,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Context as _;
+use serde_json::json;
+use tokio::signal::unix::{signal, SignalKind};
+use zksync_config::configs::{chain::StateKeeperConfig, GeneralConfig};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+
+use crate::{
+    implementations::resources::{
+        pools::{MasterPool, PoolResource},
+        reloadable_config::ReloadableConfigResource,
+    },
+    service::StopReceiver,
+    task::{Task, TaskId, TaskKind},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Subset of the node configuration that can be changed at runtime without restarting the node.
+///
+/// - Fee model params: `minimal_l2_gas_price`, `compute_overhead_part`, `pubdata_overhead_part`
+///   and `batch_overhead_l1_gas` from the state keeper config.
+/// - Seal criteria limits: `transaction_slots`, `close_block_at_*_percentage` and `reject_tx_at_*_percentage`
+///   from the state keeper config.
+/// - Requests per minute limit for WebSocket API sessions.
+/// - Log directives.
+///
+/// Other state keeper config params must stay the same; a reload changing them is rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReloadableConfig {
+    pub state_keeper: StateKeeperConfig,
+    pub websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    pub log_directives: Option<String>,
+}
+
+impl ReloadableConfig {
+    pub fn from_general_config(config: &GeneralConfig) -> anyhow::Result<Self> {
+        let state_keeper = config
+            .state_keeper_config
+            .clone()
+            .context("missing state keeper config")?;
+        let websocket_requests_per_minute_limit = config
+            .api_config
+            .as_ref()
+            .and_then(|config| config.web3_json_rpc.websocket_requests_per_minute_limit);
+        let log_directives = config
+            .observability
+            .as_ref()
+            .and_then(|config| config.log_directives.clone());
+        Ok(Self {
+            state_keeper,
+            websocket_requests_per_minute_limit,
+            log_directives,
+        })
+    }
+
+    /// Returns reloadable params as JSON recorded in the audit log.
+    fn to_audit_json(&self) -> serde_json::Value {
+        let state_keeper = &self.state_keeper;
+        json!({
+            "minimal_l2_gas_price": state_keeper.minimal_l2_gas_price,
+            "compute_overhead_part": state_keeper.compute_overhead_part,
+            "pubdata_overhead_part": state_keeper.pubdata_overhead_part,
+            "batch_overhead_l1_gas": state_keeper.batch_overhead_l1_gas,
+            "transaction_slots": state_keeper.transaction_slots,
+            "close_block_at_geometry_percentage": state_keeper.close_block_at_geometry_percentage,
+            "close_block_at_eth_params_percentage": state_keeper.close_block_at_eth_params_percentage,
+            "close_block_at_gas_percentage": state_keeper.close_block_at_gas_percentage,
+            "reject_tx_at_geometry_percentage": state_keeper.reject_tx_at_geometry_percentage,
+            "reject_tx_at_eth_params_percentage": state_keeper.reject_tx_at_eth_params_percentage,
+            "reject_tx_at_gas_percentage": state_keeper.reject_tx_at_gas_percentage,
+            "websocket_requests_per_minute_limit": self.websocket_requests_per_minute_limit,
+            "log_directives": self.log_directives,
+        })
+    }
+
+    /// Checks that the config can replace the `current` one.
+    fn validate_update(&self, current: &Self) -> anyhow::Result<()> {
+        let new = &self.state_keeper;
+        let mut unchanged = new.clone();
+        let current_state_keeper = &current.state_keeper;
+        unchanged.minimal_l2_gas_price = current_state_keeper.minimal_l2_gas_price;
+        unchanged.compute_overhead_part = current_state_keeper.compute_overhead_part;
+        unchanged.pubdata_overhead_part = current_state_keeper.pubdata_overhead_part;
+        unchanged.batch_overhead_l1_gas = current_state_keeper.batch_overhead_l1_gas;
+        unchanged.transaction_slots = current_state_keeper.transaction_slots;
+        unchanged.close_block_at_geometry_percentage =
+            current_state_keeper.close_block_at_geometry_percentage;
+        unchanged.close_block_at_eth_params_percentage =
+            current_state_keeper.close_block_at_eth_params_percentage;
+        unchanged.close_block_at_gas_percentage =
+            current_state_keeper.close_block_at_gas_percentage;
+        unchanged.reject_tx_at_geometry_percentage =
+            current_state_keeper.reject_tx_at_geometry_percentage;
+        unchanged.reject_tx_at_eth_params_percentage =
+            current_state_keeper.reject_tx_at_eth_params_percentage;
+        unchanged.reject_tx_at_gas_percentage = current_state_keeper.reject_tx_at_gas_percentage;
+        anyhow::ensure!(
+            unchanged == *current_state_keeper,
+            "state keeper config contains changes that cannot be applied without a restart"
+        );
+
+        anyhow::ensure!(
+            new.minimal_l2_gas_price > 0,
+            "minimal_l2_gas_price must be positive"
+        );
+        for (name, value) in [
+            ("compute_overhead_part", new.compute_overhead_part),
+            ("pubdata_overhead_part", new.pubdata_overhead_part),
+        ] {
+            anyhow::ensure!(
+                (0.0..=1.0).contains(&value),
+                "{name} must be in [0, 1], got {value}"
+            );
+        }
+        anyhow::ensure!(
+            new.transaction_slots > 0,
+            "transaction_slots must be positive"
+        );
+        for (name, value) in [
+            (
+                "close_block_at_geometry_percentage",
+                new.close_block_at_geometry_percentage,
+            ),
+            (
+                "close_block_at_eth_params_percentage",
+                new.close_block_at_eth_params_percentage,
+            ),
+            (
+                "close_block_at_gas_percentage",
+                new.close_block_at_gas_percentage,
+            ),
+            (
+                "reject_tx_at_geometry_percentage",
+                new.reject_tx_at_geometry_percentage,
+            ),
+            (
+                "reject_tx_at_eth_params_percentage",
+                new.reject_tx_at_eth_params_percentage,
+            ),
+            (
+                "reject_tx_at_gas_percentage",
+                new.reject_tx_at_gas_percentage,
+            ),
+        ] {
+            anyhow::ensure!(
+                value > 0.0 && value <= 1.0,
+                "{name} must be in (0, 1], got {value}"
+            );
+        }
+
+        if let Some(log_directives) = &self.log_directives {
+            zksync_vlog::logs::validate_log_directives(log_directives)?;
+        }
+        Ok(())
+    }
+}
+
+type ConfigSubscriber = Box<dyn Fn(&ReloadableConfig) + Send + Sync>;
+
+struct ReloadableConfigInner {
+    current: ReloadableConfig,
+    subscribers: Vec<(&'static str, ConfigSubscriber)>,
+}
+
+/// Shared handle to the [`ReloadableConfig`].
+///
+/// Components that support reloading subscribe to the handle during wiring. A reloaded config is validated
+/// as a whole before being applied, and is then applied to all subscribers under a lock, so that components
+/// never observe a partially applied config.
+#[derive(Clone)]
+pub struct ReloadableConfigHandle(Arc<Mutex<ReloadableConfigInner>>);
+
+impl fmt::Debug for ReloadableConfigHandle {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.0.lock().expect("reloadable config is poisoned");
+        let subscribers: Vec<_> = inner.subscribers.iter().map(|(name, _)| *name).collect();
+        formatter
+            .debug_struct("ReloadableConfigHandle")
+            .field("current", &inner.current)
+            .field("subscribers", &subscribers)
+            .finish()
+    }
+}
+
+impl ReloadableConfigHandle {
+    pub fn new(config: ReloadableConfig) -> Self {
+        Self(Arc::new(Mutex::new(ReloadableConfigInner {
+            current: config,
+            subscribers: vec![],
+        })))
+    }
+
+    /// Returns the currently applied config.
+    pub fn current(&self) -> ReloadableConfig {
+        self.0
+            .lock()
+            .expect("reloadable config is poisoned")
+            .current
+            .clone()
+    }
+
+    /// Subscribes a component to config updates. `apply` is only called for reloaded configs, i.e., the component
+    /// is expected to be initialized with the [current](Self::current()) config; it must not block.
+    pub fn subscribe(
+        &self,
+        component: &'static str,
+        apply: impl Fn(&ReloadableConfig) + Send + Sync + 'static,
+    ) {
+        let mut inner = self.0.lock().expect("reloadable config is poisoned");
+        inner.subscribers.push((component, Box::new(apply)));
+    }
+
+    /// Validates and applies the provided config to all subscribed components. Returns `false` if the config
+    /// is the same as the current one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config is invalid; in this case, it's not applied to any component.
+    pub fn apply(&self, config: ReloadableConfig) -> anyhow::Result<bool> {
+        let mut inner = self.0.lock().expect("reloadable config is poisoned");
+        if config == inner.current {
+            return Ok(false);
+        }
+        config.validate_update(&inner.current)?;
+
+        for (component, apply) in &inner.subscribers {
+            tracing::debug!("Applying reloaded config to `{component}`");
+            apply(&config);
+        }
+        inner.current = config;
+        Ok(true)
+    }
+}
+
+type ConfigLoader = Box<dyn Fn() -> anyhow::Result<ReloadableConfig> + Send + Sync>;
+
+/// Wiring layer for reloading a subset of the node configuration ([`ReloadableConfig`]) at runtime.
+///
+/// The config is reloaded on receiving SIGHUP; if it's invalid, the error is logged and the node continues
+/// running with the previous config. Applied reloads are recorded in the audit log if the master pool is available.
+///
+/// This layer must be added before layers of the components supporting reloading.
+///
+/// ## Requests resources
+///
+/// - `PoolResource<MasterPool>` (optional)
+///
+/// ## Adds resources
+///
+/// - `ReloadableConfigResource`
+///
+/// ## Adds tasks
+///
+/// - `ConfigReloaderTask`
+pub struct ConfigReloaderLayer {
+    initial_config: ReloadableConfig,
+    loader: ConfigLoader,
+}
+
+impl fmt::Debug for ConfigReloaderLayer {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ConfigReloaderLayer")
+            .field("initial_config", &self.initial_config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ConfigReloaderLayer {
+    /// Creates a layer with the specified initial config. `loader` is invoked on each reload.
+    pub fn new(
+        initial_config: ReloadableConfig,
+        loader: impl Fn() -> anyhow::Result<ReloadableConfig> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            initial_config,
+            loader: Box::new(loader),
+        }
+    }
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: Option<PoolResource<MasterPool>>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub reloadable_config: ReloadableConfigResource,
+    #[context(task)]
+    pub task: ConfigReloaderTask,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ConfigReloaderLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "config_reloader_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let pool = match input.master_pool {
+            Some(pool) => Some(pool.get_singleton().await?),
+            None => None,
+        };
+        let handle = ReloadableConfigHandle::new(self.initial_config);
+        handle.subscribe("logs", |config| {
+            let log_directives = config.log_directives.as_deref();
+            if let Err(err) = zksync_vlog::logs::reload_log_directives(log_directives) {
+                tracing::warn!("Failed reloading log directives: {err:#}");
+            }
+        });
+
+        Ok(Output {
+            reloadable_config: ReloadableConfigResource(handle.clone()),
+            task: ConfigReloaderTask {
+                handle,
+                loader: self.loader,
+                pool,
+            },
+        })
+    }
+}
+
+pub struct ConfigReloaderTask {
+    handle: ReloadableConfigHandle,
+    loader: ConfigLoader,
+    pool: Option<ConnectionPool<Core>>,
+}
+
+impl fmt::Debug for ConfigReloaderTask {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ConfigReloaderTask")
+            .field("handle", &self.handle)
+            .field("pool", &self.pool)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ConfigReloaderTask {
+    /// Actor recorded in the audit log for config reloads.
+    const AUDIT_ACTOR: &'static str = "sighup";
+
+    async fn reload(&self) {
+        let config = match (self.loader)() {
+            Ok(config) => config,
+            Err(err) => {
+                tracing::error!("Failed loading config for reload: {err:#}");
+                return;
+            }
+        };
+        let prev_config = self.handle.current();
+        match self.handle.apply(config) {
+            Ok(true) => {
+                let config = self.handle.current();
+                tracing::info!("Applied reloaded config: {config:?}");
+                if let Err(err) = self.record_reload(&prev_config, &config).await {
+                    tracing::error!("Failed recording config reload in the audit log: {err:#}");
+                }
+            }
+            Ok(false) => tracing::info!("Reloaded config is unchanged"),
+            Err(err) => tracing::error!("Reloaded config is rejected: {err:#}"),
+        }
+    }
+
+    async fn record_reload(
+        &self,
+        prev_config: &ReloadableConfig,
+        config: &ReloadableConfig,
+    ) -> anyhow::Result<()> {
+        let Some(pool) = &self.pool else {
+            return Ok(());
+        };
+        let mut storage = pool.connection_tagged("config_reloader").await?;
+        storage
+            .audit_log_dal()
+            .insert_entry(
+                "reload_config",
+                Self::AUDIT_ACTOR,
+                Some(&prev_config.to_audit_json()),
+                Some(&config.to_audit_json()),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for ConfigReloaderTask {
+    fn kind(&self) -> TaskKind {
+        // Reloading doesn't depend on other tasks, and SIGHUP may be received at any time.
+        TaskKind::UnconstrainedTask
+    }
+
+    fn id(&self) -> TaskId {
+        "config_reloader".into()
+    }
+
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let mut sighup =
+            signal(SignalKind::hangup()).context("failed installing SIGHUP handler")?;
+        loop {
+            tokio::select! {
+                _ = sighup.recv() => {
+                    tracing::info!("Received SIGHUP signal; reloading config");
+                    self.reload().await;
+                }
+                _ = stop_receiver.0.changed() => break,
+            }
+        }
+        tracing::info!("Stop signal received, config reloader is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::*;
+
+    fn config() -> ReloadableConfig {
+        ReloadableConfig {
+            state_keeper: StateKeeperConfig::for_tests(),
+            websocket_requests_per_minute_limit: None,
+            log_directives: None,
+        }
+    }
+
+    #[test]
+    fn applying_reloaded_config() {
+        let handle = ReloadableConfigHandle::new(config());
+        let gas_price = Arc::new(AtomicU64::new(0));
+        handle.subscribe("test", {
+            let gas_price = gas_price.clone();
+            move |config| {
+                gas_price.store(config.state_keeper.minimal_l2_gas_price, Ordering::SeqCst);
+            }
+        });
+
+        assert!(!handle.apply(config()).unwrap());
+        assert_eq!(gas_price.load(Ordering::SeqCst), 0);
+
+        let mut new_config = config();
+        new_config.state_keeper.minimal_l2_gas_price *= 2;
+        new_config.websocket_requests_per_minute_limit = NonZeroU32::new(100);
+        new_config.log_directives = Some("zksync_node_framework=debug".to_owned());
+        assert!(handle.apply(new_config.clone()).unwrap());
+        assert_eq!(
+            gas_price.load(Ordering::SeqCst),
+            new_config.state_keeper.minimal_l2_gas_price
+        );
+        assert_eq!(handle.current(), new_config);
+    }
+
+    #[test]
+    fn invalid_config_is_not_applied() {
+        let handle = ReloadableConfigHandle::new(config());
+        handle.subscribe("test", |_| panic!("invalid config must not be applied"));
+
+        let mut new_config = config();
+        new_config.state_keeper.close_block_at_gas_percentage = 1.5;
+        let err = handle.apply(new_config).unwrap_err().to_string();
+        assert!(err.contains("close_block_at_gas_percentage"), "{err}");
+
+        let mut new_config = config();
+        new_config.state_keeper.max_pubdata_per_batch += 1;
+        let err = handle.apply(new_config).unwrap_err().to_string();
+        assert!(err.contains("restart"), "{err}");
+
+        let mut new_config = config();
+        new_config.log_directives = Some("zksync=not_a_level".to_owned());
+        handle.apply(new_config).unwrap_err();
+
+        assert_eq!(handle.current(), config());
+    }
+}
@@ -26,6 +26,8 @@ pub struct ExternalNodeConsensusLayer {
     pub build_version: semver::Version,
     pub config: Option<ConsensusConfig>,
     pub secrets: Option<ConsensusSecrets>,
+    /// Whether to fetch blocks via JSON-RPC if consensus-based syncing lags behind the main node.
+    pub gap_fallback: bool,
 }
 
 #[derive(Debug, FromContext)]
@@ -81,6 +83,7 @@ impl WiringLayer for ExternalNodeConsensusLayer {
         let consensus_task = ExternalNodeTask {
             build_version: self.build_version,
             config,
+            gap_fallback: self.gap_fallback,
             pool,
             main_node_client,
             sync_state,
@@ -94,6 +97,7 @@ impl WiringLayer for ExternalNodeConsensusLayer {
 pub struct ExternalNodeTask {
     build_version: semver::Version,
     config: Option<(ConsensusConfig, ConsensusSecrets)>,
+    gap_fallback: bool,
     pool: ConnectionPool<Core>,
     main_node_client: Box<DynClient<L2>>,
     sync_state: SyncState,
@@ -117,6 +121,7 @@ impl Task for ExternalNodeTask {
             s.spawn_bg(consensus::era::run_external_node(
                 ctx,
                 self.config,
+                self.gap_fallback,
                 self.pool,
                 self.sync_state,
                 self.main_node_client,
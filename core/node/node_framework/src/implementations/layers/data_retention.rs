@@ -0,0 +1,96 @@
+use std::{collections::HashMap, time::Duration};
+
+use zksync_dal::pruning_dal::RetainedDataKind;
+use zksync_node_db_pruner::{DataRetentionConfig, DataRetentionManager};
+
+use crate::{
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource,
+        pools::{MasterPool, PoolResource},
+    },
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for the data retention manager removing old call traces and events from Postgres.
+#[derive(Debug)]
+pub struct DataRetentionLayer {
+    config: DataRetentionConfig,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub data_retention_manager: DataRetentionManager,
+}
+
+impl DataRetentionLayer {
+    const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+    /// Creates a layer with the specified retention periods. Data with `None` retention period is retained indefinitely.
+    pub fn new(
+        call_traces_retention: Option<Duration>,
+        events_retention: Option<Duration>,
+    ) -> Self {
+        let retention_periods = [
+            (RetainedDataKind::CallTraces, call_traces_retention),
+            (RetainedDataKind::Events, events_retention),
+        ];
+        let retention_periods: HashMap<_, _> = retention_periods
+            .into_iter()
+            .filter_map(|(kind, period)| Some((kind, period?)))
+            .collect();
+        Self {
+            config: DataRetentionConfig {
+                retention_periods,
+                poll_interval: Self::POLL_INTERVAL,
+            },
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for DataRetentionLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "data_retention_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let main_pool = input.master_pool.get().await?;
+        let data_retention_manager = DataRetentionManager::new(self.config, main_pool);
+
+        input
+            .app_health
+            .0
+            .insert_component(data_retention_manager.health_check())
+            .map_err(WiringError::internal)?;
+        Ok(Output {
+            data_retention_manager,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for DataRetentionManager {
+    fn id(&self) -> TaskId {
+        "data_retention_manager".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -1,8 +1,11 @@
+use std::sync::Arc;
+
 use anyhow::Context;
 use zksync_circuit_breaker::l1_txs::FailedL1TransactionChecker;
 use zksync_config::configs::eth_sender::SenderConfig;
 use zksync_eth_client::BoundEthInterface;
 use zksync_eth_sender::{Aggregator, EthTxAggregator};
+use zksync_node_fee_model::l1_gas_price::TxParamsProvider;
 use zksync_types::{commitment::L1BatchCommitmentMode, L2ChainId};
 
 use crate::{
@@ -10,9 +13,10 @@ use crate::{
         circuit_breakers::CircuitBreakersResource,
         contracts::SettlementLayerContractsResource,
         eth_interface::{
-            BoundEthInterfaceForBlobsResource, BoundEthInterfaceForL2Resource,
-            BoundEthInterfaceResource,
+            BoundEthInterfaceForAdditionalOperatorsResource, BoundEthInterfaceForBlobsResource,
+            BoundEthInterfaceForL2Resource, BoundEthInterfaceResource,
         },
+        gas_adjuster::GasAdjusterResource,
         healthcheck::AppHealthCheckResource,
         object_store::ObjectStoreResource,
         pools::{MasterPool, PoolResource, ReplicaPool},
@@ -36,7 +40,9 @@ use crate::{
 /// - `PoolResource<ReplicaPool>`
 /// - `BoundEthInterfaceResource`
 /// - `BoundEthInterfaceForBlobsResource` (optional)
+/// - `BoundEthInterfaceForAdditionalOperatorsResource` (optional)
 /// - `ObjectStoreResource`
+/// - `GasAdjusterResource` (optional; required for pubdata auto-selection)
 /// - `CircuitBreakersResource` (adds a circuit breaker)
 ///
 /// ## Adds tasks
@@ -55,10 +61,13 @@ pub struct Input {
     pub replica_pool: PoolResource<ReplicaPool>,
     pub eth_client: Option<BoundEthInterfaceResource>,
     pub eth_client_blobs: Option<BoundEthInterfaceForBlobsResource>,
+    pub eth_clients_for_additional_operators:
+        Option<BoundEthInterfaceForAdditionalOperatorsResource>,
     pub eth_client_gateway: Option<BoundEthInterfaceForL2Resource>,
     pub object_store: ObjectStoreResource,
     pub settlement_mode: SettlementModeResource,
     pub sender_config: SenderConfig,
+    pub gas_adjuster: Option<GasAdjusterResource>,
     #[context(default)]
     pub circuit_breakers: CircuitBreakersResource,
     #[context(default)]
@@ -145,8 +154,25 @@ impl WiringLayer for EthTxAggregatorLayer {
         let eth_client_blobs_addr = eth_client_blobs
             .as_deref()
             .map(BoundEthInterface::sender_account);
+        let additional_operator_addrs = input
+            .eth_clients_for_additional_operators
+            .map(|c| c.0.iter().map(|client| client.sender_account()).collect())
+            .unwrap_or_default();
 
         let config = input.sender_config;
+        let gas_adjuster = input
+            .gas_adjuster
+            .map(|resource| resource.0 as Arc<dyn TxParamsProvider>);
+        if config.pubdata_auto_selection && gas_adjuster.is_none() {
+            return Err(WiringError::Configuration(
+                "pubdata auto-selection requires a gas adjuster".to_owned(),
+            ));
+        }
+        if config.high_gas_price_aggregation_threshold.is_some() && gas_adjuster.is_none() {
+            return Err(WiringError::Configuration(
+                "high gas price aggregation requires a gas adjuster".to_owned(),
+            ));
+        }
         let aggregator = Aggregator::new(
             config.clone(),
             object_store,
@@ -154,6 +180,7 @@ impl WiringLayer for EthTxAggregatorLayer {
             self.l1_batch_commit_data_generator_mode,
             replica_pool.clone(),
             input.settlement_mode.0,
+            gas_adjuster.clone(),
         )
         .await?;
 
@@ -168,7 +195,9 @@ impl WiringLayer for EthTxAggregatorLayer {
             diamond_proxy_addr,
             self.zksync_network_id,
             eth_client_blobs_addr,
+            additional_operator_addrs,
             input.settlement_mode.0,
+            gas_adjuster,
         )
         .await;
 
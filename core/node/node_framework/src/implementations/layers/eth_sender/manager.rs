@@ -6,8 +6,8 @@ use crate::{
     implementations::resources::{
         circuit_breakers::CircuitBreakersResource,
         eth_interface::{
-            BoundEthInterfaceForBlobsResource, BoundEthInterfaceForL2Resource,
-            BoundEthInterfaceResource,
+            BoundEthInterfaceForAdditionalOperatorsResource, BoundEthInterfaceForBlobsResource,
+            BoundEthInterfaceForL2Resource, BoundEthInterfaceResource,
         },
         gas_adjuster::GasAdjusterResource,
         healthcheck::AppHealthCheckResource,
@@ -30,6 +30,7 @@ use crate::{
 /// - `PoolResource<ReplicaPool>`
 /// - `BoundEthInterfaceResource`
 /// - `BoundEthInterfaceForBlobsResource` (optional)
+/// - `BoundEthInterfaceForAdditionalOperatorsResource` (optional)
 /// - `TxParamsResource`
 /// - `CircuitBreakersResource` (adds a circuit breaker)
 ///
@@ -46,6 +47,8 @@ pub struct Input {
     pub replica_pool: PoolResource<ReplicaPool>,
     pub eth_client: BoundEthInterfaceResource,
     pub eth_client_blobs: Option<BoundEthInterfaceForBlobsResource>,
+    pub eth_clients_for_additional_operators:
+        Option<BoundEthInterfaceForAdditionalOperatorsResource>,
     pub eth_client_gateway: Option<BoundEthInterfaceForL2Resource>,
     pub gas_adjuster: GasAdjusterResource,
     pub sender_config: SenderConfig,
@@ -78,6 +81,10 @@ impl WiringLayer for EthTxManagerLayer {
 
         let eth_client = input.eth_client.0.clone();
         let eth_client_blobs = input.eth_client_blobs.map(|c| c.0);
+        let additional_operators = input
+            .eth_clients_for_additional_operators
+            .map(|c| c.0)
+            .unwrap_or_default();
         let l2_client = input.eth_client_gateway.map(|c| c.0);
 
         let gas_adjuster = input.gas_adjuster.0;
@@ -88,6 +95,7 @@ impl WiringLayer for EthTxManagerLayer {
             gas_adjuster,
             Some(eth_client),
             eth_client_blobs,
+            additional_operators,
             l2_client,
         );
 
@@ -2,7 +2,10 @@ use zksync_config::{
     configs::contracts::{ecosystem::L1SpecificContracts, SettlementLayerSpecificContracts},
     EthWatchConfig,
 };
-use zksync_eth_watch::{EthHttpQueryClient, EthWatch, GetLogsClient, ZkSyncExtentionEthClient};
+use zksync_eth_watch::{
+    EthHttpQueryClient, EthWatch, GetLogsClient, L2ToL1MessageTracker, L2ToL1MessageTrackerConfig,
+    ZkSyncExtentionEthClient,
+};
 use zksync_types::L2ChainId;
 use zksync_web3_decl::client::{DynClient, Network};
 
@@ -15,6 +18,7 @@ use crate::{
         eth_interface::{
             EthInterfaceResource, SettlementLayerClient, SettlementLayerClientResource,
         },
+        healthcheck::AppHealthCheckResource,
         pools::{MasterPool, PoolResource},
         settlement_layer::SettlementModeResource,
     },
@@ -28,6 +32,7 @@ use crate::{
 ///
 /// Responsible for initializing and running of [`EthWatch`] component, that polls the Ethereum node for the relevant events,
 /// such as priority operations (aka L1 transactions), protocol upgrades etc.
+/// If enabled in the config, also runs [`L2ToL1MessageTracker`] as a separate task.
 #[derive(Debug)]
 pub struct EthWatchLayer {
     eth_watch_config: EthWatchConfig,
@@ -44,6 +49,8 @@ pub struct Input {
     pub eth_client: EthInterfaceResource,
     pub client: SettlementLayerClientResource,
     pub settlement_mode: SettlementModeResource,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
 }
 
 #[derive(Debug, IntoContext)]
@@ -51,6 +58,8 @@ pub struct Input {
 pub struct Output {
     #[context(task)]
     pub eth_watch: EthWatch,
+    #[context(task)]
+    pub message_tracker: Option<L2ToL1MessageTracker>,
 }
 
 impl EthWatchLayer {
@@ -128,6 +137,17 @@ impl WiringLayer for EthWatchLayer {
             )),
         };
 
+        let message_tracker = self
+            .eth_watch_config
+            .l2_to_l1_message_tracking_enabled
+            .then(|| {
+                L2ToL1MessageTracker::new(
+                    Box::new(l1_client.clone()),
+                    main_pool.clone(),
+                    L2ToL1MessageTrackerConfig::new(&self.eth_watch_config),
+                )
+            });
+
         let eth_watch = EthWatch::new(
             Box::new(l1_client),
             sl_l2_client,
@@ -135,10 +155,20 @@ impl WiringLayer for EthWatchLayer {
             main_pool,
             self.eth_watch_config.poll_interval(),
             self.chain_id,
+            self.eth_watch_config.reorg_check_depth_in_l1_blocks,
         )
         .await?;
 
-        Ok(Output { eth_watch })
+        input
+            .app_health
+            .0
+            .insert_component(eth_watch.health_check())
+            .map_err(WiringError::internal)?;
+
+        Ok(Output {
+            eth_watch,
+            message_tracker,
+        })
     }
 }
 
@@ -152,3 +182,14 @@ impl Task for EthWatch {
         (*self).run(stop_receiver.0).await
     }
 }
+
+#[async_trait::async_trait]
+impl Task for L2ToL1MessageTracker {
+    fn id(&self) -> TaskId {
+        "eth_watch/l2_to_l1_message_tracker".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
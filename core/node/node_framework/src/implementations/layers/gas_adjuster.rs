@@ -2,7 +2,9 @@ use std::sync::Arc;
 
 use anyhow::Context;
 use zksync_config::{configs::eth_sender::SenderConfig, GasAdjusterConfig, GenesisConfig};
-use zksync_node_fee_model::l1_gas_price::GasAdjuster;
+use zksync_node_fee_model::l1_gas_price::{GasAdjuster, GasAdjusterClient};
+use zksync_types::url::SensitiveUrl;
+use zksync_web3_decl::client::{Client, DynClient, ForWeb3Network, L1};
 
 use crate::{
     implementations::resources::{
@@ -21,6 +23,7 @@ use crate::{
 pub struct GasAdjusterLayer {
     gas_adjuster_config: GasAdjusterConfig,
     genesis_config: GenesisConfig,
+    fee_source_urls: Vec<SensitiveUrl>,
 }
 
 #[derive(Debug, FromContext)]
@@ -44,8 +47,15 @@ impl GasAdjusterLayer {
         Self {
             gas_adjuster_config,
             genesis_config,
+            fee_source_urls: vec![],
         }
     }
+
+    /// Adds L1 RPC endpoints used as additional fee sources. Ignored if the settlement layer is not L1.
+    pub fn with_fee_source_urls(mut self, urls: Vec<SensitiveUrl>) -> Self {
+        self.fee_source_urls = urls;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -59,8 +69,29 @@ impl WiringLayer for GasAdjusterLayer {
 
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
         let client = match input.client.0 {
-            SettlementLayerClient::L1(client) => client.into(),
-            SettlementLayerClient::L2(client) => client.into(),
+            SettlementLayerClient::L1(client) => {
+                let network = client.as_ref().network();
+                let additional_sources = self
+                    .fee_source_urls
+                    .into_iter()
+                    .map(|url| {
+                        let client = Client::http(url)
+                            .context("Client::new()")?
+                            .for_network(network)
+                            .build();
+                        anyhow::Ok(Box::new(client) as Box<DynClient<L1>>)
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                GasAdjusterClient::from(client).with_additional_sources(additional_sources)
+            }
+            SettlementLayerClient::L2(client) => {
+                if !self.fee_source_urls.is_empty() {
+                    tracing::warn!(
+                        "Additional fee sources are only supported for L1 settlement layer; ignoring them"
+                    );
+                }
+                client.into()
+            }
         };
 
         let adjuster = GasAdjuster::new(
@@ -1,10 +1,11 @@
 use zksync_config::configs::house_keeper::HouseKeeperConfig;
 use zksync_house_keeper::{
-    blocks_state_reporter::L1BatchMetricsReporter, periodic_job::PeriodicJob,
+    blocks_state_reporter::L1BatchMetricsReporter, contract_usage::ContractUsageAggregator,
+    inclusion_stats::InclusionStatsReporter, periodic_job::PeriodicJob,
 };
 
 use crate::{
-    implementations::resources::pools::{PoolResource, ReplicaPool},
+    implementations::resources::pools::{MasterPool, PoolResource, ReplicaPool},
     service::StopReceiver,
     task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
@@ -22,6 +23,7 @@ pub struct HouseKeeperLayer {
 #[context(crate = crate)]
 pub struct Input {
     pub replica_pool: PoolResource<ReplicaPool>,
+    pub master_pool: PoolResource<MasterPool>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -29,6 +31,10 @@ pub struct Input {
 pub struct Output {
     #[context(task)]
     pub l1_batch_metrics_reporter: L1BatchMetricsReporter,
+    #[context(task)]
+    pub inclusion_stats_reporter: Option<InclusionStatsReporter>,
+    #[context(task)]
+    pub contract_usage_aggregator: Option<ContractUsageAggregator>,
 }
 
 impl HouseKeeperLayer {
@@ -59,8 +65,40 @@ impl WiringLayer for HouseKeeperLayer {
             replica_pool,
         );
 
+        let inclusion_stats_reporter = match self
+            .house_keeper_config
+            .inclusion_stats_reporting_interval_ms
+        {
+            Some(reporting_interval_ms) => {
+                // The reporter persists computed stats, so it needs a master pool.
+                let master_pool = input.master_pool.get_singleton().await?;
+                Some(InclusionStatsReporter::new(
+                    reporting_interval_ms,
+                    self.house_keeper_config.inclusion_stats_window(),
+                    master_pool,
+                ))
+            }
+            None => None,
+        };
+
+        let contract_usage_aggregator = match self
+            .house_keeper_config
+            .contract_usage_aggregation_interval_ms
+        {
+            Some(aggregation_interval_ms) => {
+                let master_pool = input.master_pool.get_singleton().await?;
+                Some(ContractUsageAggregator::new(
+                    aggregation_interval_ms,
+                    master_pool,
+                ))
+            }
+            None => None,
+        };
+
         Ok(Output {
             l1_batch_metrics_reporter,
+            inclusion_stats_reporter,
+            contract_usage_aggregator,
         })
     }
 }
@@ -75,3 +113,25 @@ impl Task for L1BatchMetricsReporter {
         (*self).run(stop_receiver.0).await
     }
 }
+
+#[async_trait::async_trait]
+impl Task for InclusionStatsReporter {
+    fn id(&self) -> TaskId {
+        "inclusion_stats_reporter".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for ContractUsageAggregator {
+    fn id(&self) -> TaskId {
+        "contract_usage_aggregator".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
 use zksync_config::configs::chain::{FeeModelVersion, StateKeeperConfig};
-use zksync_node_fee_model::{ApiFeeInputProvider, MainNodeFeeInputProvider};
+use zksync_node_fee_model::{
+    congestion::{CongestionConfig, CongestionFeeAdjuster},
+    ApiFeeInputProvider, MainNodeFeeInputProvider,
+};
 use zksync_types::fee_model::{FeeModelConfig, FeeModelConfigV1, FeeModelConfigV2};
 
 use crate::{
@@ -11,7 +14,10 @@ use crate::{
         gas_adjuster::GasAdjusterResource,
         l1_tx_params::TxParamsResource,
         pools::{PoolResource, ReplicaPool},
+        reloadable_config::ReloadableConfigResource,
     },
+    service::StopReceiver,
+    task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
 };
@@ -21,6 +27,7 @@ use crate::{
 #[derive(Debug)]
 pub struct L1GasLayer {
     fee_model_config: FeeModelConfig,
+    congestion_config: Option<CongestionConfig>,
 }
 
 #[derive(Debug, FromContext)]
@@ -31,6 +38,8 @@ pub struct Input {
     /// If not provided, the base token assumed to be ETH, and the ratio will be constant.
     #[context(default)]
     pub base_token_ratio_provider: BaseTokenRatioProviderResource,
+    /// If provided, fee model params are updated when the config is reloaded.
+    pub reloadable_config: Option<ReloadableConfigResource>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -39,12 +48,15 @@ pub struct Output {
     pub sequencer_fee_input: SequencerFeeInputResource,
     pub api_fee_input: ApiFeeInputResource,
     pub l1_tx_params: TxParamsResource,
+    #[context(task)]
+    pub congestion_fee_adjuster: Option<CongestionFeeAdjusterTask>,
 }
 
 impl L1GasLayer {
     pub fn new(state_keeper_config: &StateKeeperConfig) -> Self {
         Self {
             fee_model_config: Self::map_config(state_keeper_config),
+            congestion_config: CongestionConfig::from_state_keeper_config(state_keeper_config),
         }
     }
 
@@ -77,13 +89,30 @@ impl WiringLayer for L1GasLayer {
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
         let ratio_provider = input.base_token_ratio_provider;
 
-        let main_fee_input_provider = Arc::new(MainNodeFeeInputProvider::new(
+        let replica_pool = input.replica_pool.get().await?;
+        let mut main_fee_input_provider = MainNodeFeeInputProvider::new(
             input.gas_adjuster.0.clone(),
             ratio_provider.0,
             self.fee_model_config,
-        ));
+        );
+        let congestion_fee_adjuster = self.congestion_config.map(|config| {
+            tracing::info!(
+                "Congestion-based adjustment of fair L2 gas price is enabled: {config:?}"
+            );
+            Arc::new(CongestionFeeAdjuster::new(replica_pool.clone(), config))
+        });
+        if let Some(adjuster) = &congestion_fee_adjuster {
+            main_fee_input_provider =
+                main_fee_input_provider.with_congestion_adjuster(adjuster.clone());
+        }
+        let main_fee_input_provider = Arc::new(main_fee_input_provider);
+        if let Some(ReloadableConfigResource(handle)) = input.reloadable_config {
+            let provider = main_fee_input_provider.clone();
+            handle.subscribe("fee_model", move |config| {
+                provider.set_config(Self::map_config(&config.state_keeper));
+            });
+        }
 
-        let replica_pool = input.replica_pool.get().await?;
         let api_fee_input_provider = Arc::new(ApiFeeInputProvider::new(
             main_fee_input_provider.clone(),
             replica_pool,
@@ -93,6 +122,21 @@ impl WiringLayer for L1GasLayer {
             sequencer_fee_input: main_fee_input_provider.into(),
             api_fee_input: api_fee_input_provider.into(),
             l1_tx_params: input.gas_adjuster.0.into(),
+            congestion_fee_adjuster: congestion_fee_adjuster.map(CongestionFeeAdjusterTask),
         })
     }
 }
+
+#[derive(Debug)]
+pub struct CongestionFeeAdjusterTask(Arc<CongestionFeeAdjuster>);
+
+#[async_trait::async_trait]
+impl Task for CongestionFeeAdjusterTask {
+    fn id(&self) -> TaskId {
+        "congestion_fee_adjuster".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.0.run(stop_receiver.0).await
+    }
+}
@@ -7,8 +7,9 @@ use std::{
 use anyhow::Context as _;
 use zksync_config::configs::{api::MerkleTreeApiConfig, database::MerkleTreeMode};
 use zksync_metadata_calculator::{
-    LazyAsyncTreeReader, MerkleTreePruningTask, MerkleTreeReaderConfig, MetadataCalculator,
-    MetadataCalculatorConfig, StaleKeysRepairTask, TreeReaderTask,
+    ConsistencyCheckTask, LazyAsyncTreeReader, MerkleTreePruningPolicy, MerkleTreePruningTask,
+    MerkleTreeReaderConfig, MetadataCalculator, MetadataCalculatorConfig, StaleKeysRepairTask,
+    TreeReaderTask,
 };
 use zksync_storage::RocksDB;
 
@@ -31,7 +32,10 @@ pub struct MetadataCalculatorLayer {
     config: MetadataCalculatorConfig,
     tree_api_config: Option<MerkleTreeApiConfig>,
     pruning_config: Option<Duration>,
+    pruning_policy: MerkleTreePruningPolicy,
     stale_keys_repair_enabled: bool,
+    /// Poll interval and subtree delay for the consistency check task.
+    consistency_check_config: Option<(Duration, Duration)>,
 }
 
 #[derive(Debug, FromContext)]
@@ -60,6 +64,9 @@ pub struct Output {
     /// Only provided if enabled in the config.
     #[context(task)]
     pub stale_keys_repair_task: Option<StaleKeysRepairTask>,
+    /// Only provided if enabled in the config.
+    #[context(task)]
+    pub consistency_check_task: Option<ConsistencyCheckTask>,
     pub rocksdb_shutdown_hook: ShutdownHook,
 }
 
@@ -69,7 +76,9 @@ impl MetadataCalculatorLayer {
             config,
             tree_api_config: None,
             pruning_config: None,
+            pruning_policy: MerkleTreePruningPolicy::default(),
             stale_keys_repair_enabled: false,
+            consistency_check_config: None,
         }
     }
 
@@ -83,10 +92,27 @@ impl MetadataCalculatorLayer {
         self
     }
 
+    /// Sets the policy for the pruning task. Only has effect if pruning is enabled via [`Self::with_pruning_config()`].
+    pub fn with_pruning_policy(mut self, policy: MerkleTreePruningPolicy) -> Self {
+        self.pruning_policy = policy;
+        self
+    }
+
     pub fn with_stale_keys_repair(mut self) -> Self {
         self.stale_keys_repair_enabled = true;
         self
     }
+
+    /// Enables the background consistency check for the tree, which checks the latest tree version
+    /// every `poll_interval` and waits for `subtree_delay` between checking subtrees of the tree root.
+    pub fn with_consistency_check(
+        mut self,
+        poll_interval: Duration,
+        subtree_delay: Duration,
+    ) -> Self {
+        self.consistency_check_config = Some((poll_interval, subtree_delay));
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -142,7 +168,9 @@ impl WiringLayer for MetadataCalculatorLayer {
             .pruning_config
             .map(
                 |pruning_removal_delay| -> Result<MerkleTreePruningTask, WiringError> {
-                    let pruning_task = metadata_calculator.pruning_task(pruning_removal_delay);
+                    let pruning_task = metadata_calculator
+                        .pruning_task(pruning_removal_delay)
+                        .with_policy(self.pruning_policy);
                     app_health
                         .insert_component(pruning_task.health_check())
                         .map_err(|err| WiringError::Internal(err.into()))?;
@@ -157,6 +185,21 @@ impl WiringLayer for MetadataCalculatorLayer {
             None
         };
 
+        let consistency_check_task = self
+            .consistency_check_config
+            .map(
+                |(poll_interval, subtree_delay)| -> Result<ConsistencyCheckTask, WiringError> {
+                    let task = metadata_calculator
+                        .consistency_check_task(poll_interval)
+                        .with_subtree_delay(subtree_delay);
+                    app_health
+                        .insert_component(task.health_check())
+                        .map_err(|err| WiringError::Internal(err.into()))?;
+                    Ok(task)
+                },
+            )
+            .transpose()?;
+
         let tree_api_client = TreeApiClientResource(Arc::new(metadata_calculator.tree_reader()));
 
         let rocksdb_shutdown_hook = ShutdownHook::new("rocksdb_terminaton", async {
@@ -172,6 +215,7 @@ impl WiringLayer for MetadataCalculatorLayer {
             tree_api_task,
             pruning_task,
             stale_keys_repair_task,
+            consistency_check_task,
             rocksdb_shutdown_hook,
         })
     }
@@ -224,6 +268,17 @@ impl Task for StaleKeysRepairTask {
     }
 }
 
+#[async_trait::async_trait]
+impl Task for ConsistencyCheckTask {
+    fn id(&self) -> TaskId {
+        "merkle_tree_consistency_check_task".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
+
 #[async_trait::async_trait]
 impl Task for MerkleTreePruningTask {
     fn id(&self) -> TaskId {
@@ -1,14 +1,18 @@
 pub mod base_token;
+pub mod batch_exporter;
 pub mod batch_status_updater;
 pub mod block_reverter;
 pub mod circuit_breaker_checker;
+pub mod cold_storage;
 pub mod commitment_generator;
+pub mod config_reloader;
 pub mod consensus;
 pub mod consistency_checker;
 pub mod contract_verification_api;
 pub mod da_clients;
 pub mod da_dispatcher;
 pub mod data_availability_fetcher;
+pub mod data_retention;
 pub mod eth_sender;
 pub mod eth_watch;
 pub mod external_proof_integration_api;
@@ -32,9 +36,12 @@ pub mod proof_data_handler;
 pub mod pruning;
 pub mod query_eth_client;
 pub mod reorg_detector;
+pub mod response_checker;
 pub mod settlement_layer_client;
 pub mod settlement_layer_data;
+pub mod shared_resource;
 pub mod sigint;
+pub mod snapshot_api;
 pub mod state_keeper;
 pub mod sync_state_updater;
 pub mod tree_data_fetcher;
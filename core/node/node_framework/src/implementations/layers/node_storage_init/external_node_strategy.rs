@@ -26,6 +26,10 @@ pub struct ExternalNodeInitStrategyLayer {
     pub l2_chain_id: L2ChainId,
     pub max_postgres_concurrency: NonZeroUsize,
     pub snapshot_recovery_config: Option<SnapshotRecoveryConfig>,
+    /// Whether to halt on divergence from the main node instead of reverting diverged L1 batches.
+    pub cross_verification: bool,
+    /// Maximum number of L1 batches that can be rolled back automatically; unbounded if not set.
+    pub max_rollback_depth: Option<u32>,
 }
 
 #[derive(Debug, FromContext)]
@@ -97,6 +101,8 @@ impl WiringLayer for ExternalNodeInitStrategyLayer {
             client,
             pool: pool.clone(),
             reverter: block_reverter,
+            cross_verification: self.cross_verification,
+            max_rollback_depth: self.max_rollback_depth,
         }) as Arc<dyn RevertStorage>);
         let strategy = NodeInitializationStrategy {
             genesis,
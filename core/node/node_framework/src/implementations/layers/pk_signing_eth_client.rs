@@ -1,13 +1,13 @@
 use zksync_config::{configs::wallets, GasAdjusterConfig};
-use zksync_eth_client::{clients::PKSigningClient, EthInterface};
+use zksync_eth_client::{clients::PKSigningClient, BoundEthInterface, EthInterface};
 
 use crate::{
     implementations::resources::{
         contracts::{L1ChainContractsResource, SettlementLayerContractsResource},
         eth_interface::{
-            BoundEthInterfaceForBlobsResource, BoundEthInterfaceForL2Resource,
-            BoundEthInterfaceResource, EthInterfaceResource, SettlementLayerClient,
-            SettlementLayerClientResource,
+            BoundEthInterfaceForAdditionalOperatorsResource, BoundEthInterfaceForBlobsResource,
+            BoundEthInterfaceForL2Resource, BoundEthInterfaceResource, EthInterfaceResource,
+            SettlementLayerClient, SettlementLayerClientResource,
         },
     },
     wiring_layer::{WiringError, WiringLayer},
@@ -36,6 +36,9 @@ pub struct Output {
     pub signing_client: BoundEthInterfaceResource,
     /// Only provided if the blob operator key is provided to the layer.
     pub signing_client_for_blobs: Option<BoundEthInterfaceForBlobsResource>,
+    /// Only provided if additional operator keys are provided to the layer.
+    pub signing_clients_for_additional_operators:
+        Option<BoundEthInterfaceForAdditionalOperatorsResource>,
     pub signing_client_for_gateway: Option<BoundEthInterfaceForL2Resource>,
 }
 
@@ -79,6 +82,32 @@ impl WiringLayer for PKSigningEthClientLayer {
         );
         let signing_client = BoundEthInterfaceResource(Box::new(signing_client));
 
+        let diamond_proxy_addr = input
+            .l1_contracts
+            .0
+            .chain_contracts_config
+            .diamond_proxy_addr;
+        let signing_clients_for_additional_operators =
+            (!self.wallets.additional_operators.is_empty()).then(|| {
+                let clients = self
+                    .wallets
+                    .additional_operators
+                    .iter()
+                    .map(|operator| {
+                        let client: Box<dyn BoundEthInterface> =
+                            Box::new(PKSigningClient::new_raw(
+                                operator.private_key().clone(),
+                                diamond_proxy_addr,
+                                gas_adjuster_config.default_priority_fee_per_gas,
+                                l1_chain_id,
+                                query_client.clone(),
+                            ));
+                        client
+                    })
+                    .collect();
+                BoundEthInterfaceForAdditionalOperatorsResource(clients)
+            });
+
         let signing_client_for_blobs = self.wallets.blob_operator.map(|blob_operator| {
             let private_key = blob_operator.private_key();
             let signing_client_for_blobs = PKSigningClient::new_raw(
@@ -119,6 +148,7 @@ impl WiringLayer for PKSigningEthClientLayer {
         Ok(Output {
             signing_client,
             signing_client_for_blobs,
+            signing_clients_for_additional_operators,
             signing_client_for_gateway: signing_client_for_l2_gateway,
         })
     }
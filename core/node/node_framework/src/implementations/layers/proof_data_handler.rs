@@ -3,7 +3,7 @@ use std::sync::Arc;
 use zksync_config::configs::ProofDataHandlerConfig;
 use zksync_dal::{ConnectionPool, Core};
 use zksync_object_store::ObjectStore;
-use zksync_types::{commitment::L1BatchCommitmentMode, L2ChainId};
+use zksync_types::{commitment::L1BatchCommitmentMode, secrets::APIKey, K256PrivateKey, L2ChainId};
 
 use crate::{
     implementations::resources::{
@@ -22,6 +22,8 @@ pub struct ProofDataHandlerLayer {
     proof_data_handler_config: ProofDataHandlerConfig,
     commitment_mode: L1BatchCommitmentMode,
     l2_chain_id: L2ChainId,
+    admin_auth_token: Option<APIKey>,
+    tee_auxiliary_data_signer: Option<K256PrivateKey>,
 }
 
 #[derive(Debug, FromContext)]
@@ -48,8 +50,24 @@ impl ProofDataHandlerLayer {
             proof_data_handler_config,
             commitment_mode,
             l2_chain_id,
+            admin_auth_token: None,
+            tee_auxiliary_data_signer: None,
         }
     }
+
+    /// Enables the admin API (requeueing / skipping L1 batches, invalidating proofs) authenticated
+    /// with the specified bearer token.
+    pub fn with_admin_auth_token(mut self, token: APIKey) -> Self {
+        self.admin_auth_token = Some(token);
+        self
+    }
+
+    /// Enables attaching auxiliary data (e.g., DA inclusion proofs) signed with the specified key
+    /// to TEE proof generation jobs.
+    pub fn with_tee_auxiliary_data_signer(mut self, signer: K256PrivateKey) -> Self {
+        self.tee_auxiliary_data_signer = Some(signer);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -71,6 +89,8 @@ impl WiringLayer for ProofDataHandlerLayer {
             main_pool,
             commitment_mode: self.commitment_mode,
             l2_chain_id: self.l2_chain_id,
+            admin_auth_token: self.admin_auth_token,
+            tee_auxiliary_data_signer: self.tee_auxiliary_data_signer,
         };
 
         Ok(Output { task })
@@ -84,6 +104,8 @@ pub struct ProofDataHandlerTask {
     main_pool: ConnectionPool<Core>,
     commitment_mode: L1BatchCommitmentMode,
     l2_chain_id: L2ChainId,
+    admin_auth_token: Option<APIKey>,
+    tee_auxiliary_data_signer: Option<K256PrivateKey>,
 }
 
 #[async_trait::async_trait]
@@ -99,6 +121,8 @@ impl Task for ProofDataHandlerTask {
             self.main_pool,
             self.commitment_mode,
             self.l2_chain_id,
+            self.admin_auth_token,
+            self.tee_auxiliary_data_signer,
             stop_receiver.0,
         )
         .await
@@ -19,6 +19,7 @@ pub struct PruningLayer {
     pruning_removal_delay: Duration,
     pruning_chunk_size: u32,
     minimum_l1_batch_age: Duration,
+    dry_run: bool,
 }
 
 #[derive(Debug, FromContext)]
@@ -46,8 +47,15 @@ impl PruningLayer {
             pruning_removal_delay,
             pruning_chunk_size,
             minimum_l1_batch_age,
+            dry_run: false,
         }
     }
+
+    /// Makes the pruner only report which L1 batches would be pruned without modifying Postgres.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -67,6 +75,7 @@ impl WiringLayer for PruningLayer {
                 removal_delay: self.pruning_removal_delay,
                 pruned_batch_chunk_size: self.pruning_chunk_size,
                 minimum_l1_batch_age: self.minimum_l1_batch_age,
+                dry_run: self.dry_run,
             },
             main_pool,
         );
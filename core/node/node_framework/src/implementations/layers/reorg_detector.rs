@@ -16,8 +16,27 @@ use crate::{
 /// This layer is responsible for detecting reorgs and shutting down the node if one is detected.
 ///
 /// This layer assumes that the node starts with the initialized state.
-#[derive(Debug)]
-pub struct ReorgDetectorLayer;
+#[derive(Debug, Default)]
+pub struct ReorgDetectorLayer {
+    cross_verification: bool,
+    max_rollback_depth: Option<u32>,
+}
+
+impl ReorgDetectorLayer {
+    /// Enables or disables the cross-verification mode of the reorg detector, in which the node halts
+    /// on divergence from the main node instead of reverting diverged L1 batches.
+    pub fn with_cross_verification(mut self, enabled: bool) -> Self {
+        self.cross_verification = enabled;
+        self
+    }
+
+    /// Sets the maximum number of L1 batches that can be rolled back automatically. If a detected reorg is deeper,
+    /// the node halts and must be reverted manually.
+    pub fn with_max_rollback_depth(mut self, max_depth: Option<u32>) -> Self {
+        self.max_rollback_depth = max_depth;
+        self
+    }
+}
 
 #[derive(Debug, FromContext)]
 #[context(crate = crate)]
@@ -48,7 +67,9 @@ impl WiringLayer for ReorgDetectorLayer {
         let MainNodeClientResource(main_node_client) = input.main_node_client;
         let pool = input.master_pool.get().await?;
 
-        let reorg_detector = ReorgDetector::new(main_node_client, pool);
+        let reorg_detector = ReorgDetector::new(main_node_client, pool)
+            .with_cross_verification(self.cross_verification)
+            .with_max_rollback_depth(self.max_rollback_depth);
 
         let AppHealthCheckResource(app_health) = input.app_health;
         app_health
@@ -0,0 +1,108 @@
+use std::num::NonZeroU32;
+
+use anyhow::Context as _;
+use zksync_node_sync::response_checker::{ResponseChecker, ResponseCheckerPeer};
+use zksync_types::{url::SensitiveUrl, L2ChainId};
+use zksync_web3_decl::client::{Client, DynClient, L2};
+
+use crate::{
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource,
+        main_node_client::MainNodeClientResource,
+        pools::{PoolResource, ReplicaPool},
+    },
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for [`ResponseChecker`].
+#[derive(Debug)]
+pub struct ResponseCheckerLayer {
+    /// URL of the archive peer to check responses against. If not set, the main node is used.
+    peer_url: Option<SensitiveUrl>,
+    l2_chain_id: L2ChainId,
+    stride: Option<NonZeroU32>,
+}
+
+impl ResponseCheckerLayer {
+    pub fn new(peer_url: Option<SensitiveUrl>, l2_chain_id: L2ChainId) -> Self {
+        Self {
+            peer_url,
+            l2_chain_id,
+            stride: None,
+        }
+    }
+
+    /// Sets the distance between consecutively checked L2 blocks.
+    pub fn with_stride(mut self, stride: Option<NonZeroU32>) -> Self {
+        self.stride = stride;
+        self
+    }
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub replica_pool: PoolResource<ReplicaPool>,
+    pub main_node_client: MainNodeClientResource,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub task: ResponseChecker,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ResponseCheckerLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "response_checker_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let pool = input.replica_pool.get().await?;
+        let peer: Box<DynClient<L2>> = if let Some(url) = self.peer_url {
+            let client = Client::http(url)
+                .context("failed creating JSON-RPC client for response checker peer")?
+                .for_network(self.l2_chain_id.into())
+                .build();
+            Box::new(client)
+        } else {
+            input.main_node_client.0
+        };
+        let peer: Box<dyn ResponseCheckerPeer> = Box::new(peer.for_component("response_checker"));
+
+        let mut task = ResponseChecker::new(peer, pool);
+        if let Some(stride) = self.stride {
+            task = task.with_stride(stride.get());
+        }
+
+        // Insert healthcheck
+        input
+            .app_health
+            .0
+            .insert_component(task.health_check())
+            .map_err(WiringError::internal)?;
+
+        Ok(Output { task })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for ResponseChecker {
+    fn id(&self) -> TaskId {
+        "response_checker".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
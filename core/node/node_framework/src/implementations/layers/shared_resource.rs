@@ -0,0 +1,36 @@
+use crate::{
+    resource::Resource,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Wiring layer injecting a pre-built resource into the service.
+///
+/// Primarily useful to share a resource instance (e.g., an L1 client or an object store) among services
+/// in a [`ZkStackServiceGroup`](crate::service::ZkStackServiceGroup). The resource should be created
+/// on the group runtime.
+#[derive(Debug)]
+pub struct SharedResourceLayer<R> {
+    name: &'static str,
+    resource: R,
+}
+
+impl<R: Resource + Clone> SharedResourceLayer<R> {
+    /// Creates a layer with the specified name. The name must be unique among the layers of the service.
+    pub fn new(name: &'static str, resource: R) -> Self {
+        Self { name, resource }
+    }
+}
+
+#[async_trait::async_trait]
+impl<R: Resource + Clone> WiringLayer for SharedResourceLayer<R> {
+    type Input = ();
+    type Output = R;
+
+    fn layer_name(&self) -> &'static str {
+        self.name
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        Ok(self.resource)
+    }
+}
@@ -0,0 +1,75 @@
+use zksync_node_snapshot_api::{SnapshotApiConfig, SnapshotApiServer};
+
+use crate::{
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource, main_node_client::MainNodeClientResource,
+        object_store::ObjectStoreResource,
+    },
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for the read-only JSON-RPC API served from a snapshot recovered from an object store.
+/// Unlike other API layers, it doesn't require Postgres.
+#[derive(Debug)]
+pub struct SnapshotApiLayer {
+    config: SnapshotApiConfig,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub main_node_client: MainNodeClientResource,
+    pub object_store: ObjectStoreResource,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub server: SnapshotApiServer,
+}
+
+impl SnapshotApiLayer {
+    pub fn new(config: SnapshotApiConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for SnapshotApiLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "snapshot_api_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let MainNodeClientResource(main_node_client) = input.main_node_client;
+        let ObjectStoreResource(object_store) = input.object_store;
+        let server = SnapshotApiServer::new(self.config, main_node_client, object_store);
+
+        input
+            .app_health
+            .0
+            .insert_component(server.health_check())
+            .map_err(WiringError::internal)?;
+        Ok(Output { server })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for SnapshotApiServer {
+    fn id(&self) -> TaskId {
+        "snapshot_api".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
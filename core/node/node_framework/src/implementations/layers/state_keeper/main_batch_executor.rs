@@ -1,5 +1,5 @@
 use zksync_types::vm::FastVmMode;
-use zksync_vm_executor::batch::{BatchTracer, MainBatchExecutorFactory, TraceCalls};
+use zksync_vm_executor::batch::{BatchTracer, FrameGasCap, MainBatchExecutorFactory, TraceCalls};
 
 use crate::{
     implementations::resources::state_keeper::BatchExecutorResource,
@@ -12,6 +12,8 @@ pub struct MainBatchExecutorLayer {
     save_call_traces: bool,
     optional_bytecode_compression: bool,
     fast_vm_mode: FastVmMode,
+    frame_gas_cap: FrameGasCap,
+    record_precompile_inputs: bool,
 }
 
 impl MainBatchExecutorLayer {
@@ -20,6 +22,8 @@ impl MainBatchExecutorLayer {
             save_call_traces,
             optional_bytecode_compression,
             fast_vm_mode: FastVmMode::default(),
+            frame_gas_cap: FrameGasCap::default(),
+            record_precompile_inputs: false,
         }
     }
 
@@ -28,9 +32,27 @@ impl MainBatchExecutorLayer {
         self
     }
 
+    /// Sets the cap on gas forwarded to a single call frame. Should not be set on external nodes, which must execute
+    /// all transactions included by the main node.
+    pub fn with_frame_gas_cap(mut self, frame_gas_cap: FrameGasCap) -> Self {
+        self.frame_gas_cap = frame_gas_cap;
+        self
+    }
+
+    /// Enables recording inputs of hashing precompile calls. Required if witness inputs are generated
+    /// by the state keeper.
+    pub fn with_precompile_inputs_recording(mut self, record_precompile_inputs: bool) -> Self {
+        self.record_precompile_inputs = record_precompile_inputs;
+        self
+    }
+
     fn create_executor<Tr: BatchTracer>(&self) -> BatchExecutorResource {
         let mut executor = MainBatchExecutorFactory::<Tr>::new(self.optional_bytecode_compression);
         executor.set_fast_vm_mode(self.fast_vm_mode);
+        executor.set_frame_gas_cap(self.frame_gas_cap.clone());
+        if self.record_precompile_inputs {
+            executor.record_precompile_inputs();
+        }
         executor.into()
     }
 }
@@ -45,6 +67,12 @@ impl WiringLayer for MainBatchExecutorLayer {
     }
 
     async fn wire(self, (): Self::Input) -> Result<Self::Output, WiringError> {
+        if self.frame_gas_cap.is_enabled() && !matches!(self.fast_vm_mode, FastVmMode::Old) {
+            return Err(WiringError::Configuration(format!(
+                "frame gas cap is not supported by the fast VM (mode: {:?})",
+                self.fast_vm_mode
+            )));
+        }
         Ok(if self.save_call_traces {
             self.create_executor::<TraceCalls>()
         } else {
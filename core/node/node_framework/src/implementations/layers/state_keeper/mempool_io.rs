@@ -1,17 +1,30 @@
+use std::sync::Arc;
+
 use anyhow::Context as _;
 use zksync_config::configs::{
     chain::{MempoolConfig, StateKeeperConfig},
-    wallets,
+    wallets, TxPolicyConfig,
+};
+use zksync_eth_client::EthInterface;
+use zksync_state_keeper::{
+    timestamp_policy::{timestamp_policy_from_config, MedianOfL1Updater},
+    tx_policy::TxPolicy,
+    GeometryAdjustmentConfig, GeometrySealAdjuster, MempoolFetcher, MempoolGuard, MempoolIO,
+    SequencerSealer,
 };
-use zksync_state_keeper::{MempoolFetcher, MempoolGuard, MempoolIO, SequencerSealer};
 use zksync_types::{commitment::PubdataType, L2ChainId};
 
 use crate::{
     implementations::resources::{
         contracts::{L2ContractsResource, SettlementLayerContractsResource},
+        eth_interface::EthInterfaceResource,
         fee_input::SequencerFeeInputResource,
         pools::{MasterPool, PoolResource},
-        state_keeper::{ConditionalSealerResource, StateKeeperIOResource},
+        reloadable_config::ReloadableConfigResource,
+        state_keeper::{
+            ConditionalSealerResource, DevModeControlResource, L1BatchSealRequestResource,
+            SequencerFenceResource, StateKeeperIOResource,
+        },
     },
     service::StopReceiver,
     task::{Task, TaskId},
@@ -25,15 +38,22 @@ use crate::{
 ///
 /// - `FeeInputResource`
 /// - `PoolResource<MasterPool>`
+/// - `EthInterfaceResource` (optional; required for the `median_of_l1` timestamp policy)
+/// - `ReloadableConfigResource` (optional; allows updating seal criteria limits at runtime)
+/// - `SequencerFenceResource` (optional; the mempool is only populated while the sequencer lease is held)
 ///
 /// ## Adds resources
 ///
 /// - `StateKeeperIOResource`
 /// - `ConditionalSealerResource`
+/// - `L1BatchSealRequestResource`
+/// - `DevModeControlResource` (only if the development mode is enabled)
 ///
 /// ## Adds tasks
 ///
 /// - `MempoolFetcherTask`
+/// - `GeometrySealAdjuster` (only if the geometry adjustment is enabled)
+/// - `MedianOfL1Updater` (only if the `median_of_l1` timestamp policy is used)
 #[derive(Debug)]
 pub struct MempoolIOLayer {
     zksync_network_id: L2ChainId,
@@ -41,6 +61,7 @@ pub struct MempoolIOLayer {
     mempool_config: MempoolConfig,
     wallets: wallets::StateKeeper,
     pubdata_type: PubdataType,
+    tx_policy_config: Option<TxPolicyConfig>,
 }
 
 #[derive(Debug, FromContext)]
@@ -50,6 +71,9 @@ pub struct Input {
     pub master_pool: PoolResource<MasterPool>,
     pub contracts_resource: SettlementLayerContractsResource,
     pub l2_contracts_resource: L2ContractsResource,
+    pub eth_client: Option<EthInterfaceResource>,
+    pub reloadable_config: Option<ReloadableConfigResource>,
+    pub sequencer_fence: Option<SequencerFenceResource>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -57,8 +81,14 @@ pub struct Input {
 pub struct Output {
     pub state_keeper_io: StateKeeperIOResource,
     pub conditional_sealer: ConditionalSealerResource,
+    pub l1_batch_seal_request: L1BatchSealRequestResource,
+    pub dev_mode_control: Option<DevModeControlResource>,
     #[context(task)]
     pub mempool_fetcher: MempoolFetcher,
+    #[context(task)]
+    pub geometry_seal_adjuster: Option<GeometrySealAdjuster>,
+    #[context(task)]
+    pub l1_timestamp_updater: Option<MedianOfL1Updater>,
 }
 
 impl MempoolIOLayer {
@@ -75,9 +105,16 @@ impl MempoolIOLayer {
             mempool_config,
             wallets,
             pubdata_type,
+            tx_policy_config: None,
         }
     }
 
+    /// Enforces the transaction policy when including transactions into blocks.
+    pub fn with_tx_policy(mut self, config: TxPolicyConfig) -> Self {
+        self.tx_policy_config = Some(config);
+        self
+    }
+
     async fn build_mempool_guard(
         &self,
         master_pool: &PoolResource<MasterPool>,
@@ -115,19 +152,22 @@ impl WiringLayer for MempoolIOLayer {
             .get_singleton()
             .await
             .context("Get master pool")?;
-        let mempool_fetcher = MempoolFetcher::new(
+        let mut mempool_fetcher = MempoolFetcher::new(
             mempool_guard.clone(),
             batch_fee_input_provider.clone(),
             &self.mempool_config,
             mempool_fetcher_pool,
         );
+        if let Some(SequencerFenceResource(fence)) = input.sequencer_fence {
+            mempool_fetcher = mempool_fetcher.with_sequencer_fence(fence);
+        }
 
         // Create mempool IO resource.
         let mempool_db_pool = master_pool
             .get_singleton()
             .await
             .context("Get master pool")?;
-        let io = MempoolIO::new(
+        let mut io = MempoolIO::new(
             mempool_guard,
             batch_fee_input_provider,
             mempool_db_pool,
@@ -138,14 +178,52 @@ impl WiringLayer for MempoolIOLayer {
             input.l2_contracts_resource.0.da_validator_addr,
             self.pubdata_type,
         )?;
+        if let Some(config) = &self.tx_policy_config {
+            io = io.with_tx_policy(Arc::new(TxPolicy::new(config)));
+        }
+        let l1_client = input.eth_client.map(|client| {
+            Box::new(client.0.for_component("state_keeper")) as Box<dyn EthInterface>
+        });
+        let (timestamp_policy, l1_timestamp_updater) =
+            timestamp_policy_from_config(&self.state_keeper_config, l1_client)?;
+        io = io.with_timestamp_policy(timestamp_policy);
+        if self.state_keeper_config.dev_mode {
+            tracing::warn!("State keeper runs in the development mode; this mode must not be used in production");
+            io = io.with_dev_mode();
+        }
+        let l1_batch_seal_request = L1BatchSealRequestResource(io.seal_request());
+        let dev_mode_control = io.dev_mode_control().map(DevModeControlResource);
 
         // Create sealer.
+        let geometry_adjustment_config =
+            GeometryAdjustmentConfig::from_state_keeper_config(&self.state_keeper_config)
+                .context("invalid geometry adjustment config")?;
         let sealer = SequencerSealer::new(self.state_keeper_config);
+        if let Some(ReloadableConfigResource(handle)) = input.reloadable_config {
+            let sealer_config = sealer.shared_config();
+            handle.subscribe("seal_criteria", move |config| {
+                sealer_config.set(config.state_keeper.clone());
+            });
+        }
+        let geometry_seal_adjuster = if let Some(config) = geometry_adjustment_config {
+            let pool = master_pool.get_singleton().await?;
+            Some(GeometrySealAdjuster::new(
+                pool,
+                config,
+                sealer.shared_config(),
+            ))
+        } else {
+            None
+        };
 
         Ok(Output {
             state_keeper_io: io.into(),
             conditional_sealer: sealer.into(),
+            l1_batch_seal_request,
+            dev_mode_control,
             mempool_fetcher,
+            geometry_seal_adjuster,
+            l1_timestamp_updater,
         })
     }
 }
@@ -160,3 +238,25 @@ impl Task for MempoolFetcher {
         (*self).run(stop_receiver.0).await
     }
 }
+
+#[async_trait::async_trait]
+impl Task for GeometrySealAdjuster {
+    fn id(&self) -> TaskId {
+        "state_keeper/geometry_seal_adjuster".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for MedianOfL1Updater {
+    fn id(&self) -> TaskId {
+        "state_keeper/median_l1_timestamp_updater".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -2,9 +2,9 @@ use std::sync::Arc;
 
 use anyhow::Context;
 use zksync_health_check::ReactiveHealthCheck;
-use zksync_state::AsyncCatchupTask;
-pub use zksync_state::RocksdbStorageOptions;
-use zksync_state_keeper::{AsyncRocksdbCache, ZkSyncStateKeeper};
+use zksync_state::{AsyncCatchupTask, KeepUpdatedTask};
+pub use zksync_state::{CompactionOptions, RocksdbStorageOptions};
+use zksync_state_keeper::{AsyncRocksdbCache, SequencerFence, ZkSyncStateKeeper};
 use zksync_storage::RocksDB;
 
 use crate::{
@@ -13,7 +13,7 @@ use crate::{
         pools::{MasterPool, PoolResource},
         state_keeper::{
             BatchExecutorResource, ConditionalSealerResource, OutputHandlerResource,
-            StateKeeperIOResource,
+            PendingBatchStateResource, SequencerFenceResource, StateKeeperIOResource,
         },
     },
     service::{ShutdownHook, StopReceiver},
@@ -26,12 +26,15 @@ pub mod external_io;
 pub mod main_batch_executor;
 pub mod mempool_io;
 pub mod output_handler;
+pub mod sequencer_lease;
 
 /// Wiring layer for the state keeper.
 #[derive(Debug)]
 pub struct StateKeeperLayer {
     state_keeper_db_path: String,
     rocksdb_options: RocksdbStorageOptions,
+    witness_inputs_pregeneration_enabled: bool,
+    pending_batch_state_enabled: bool,
 }
 
 #[derive(Debug, FromContext)]
@@ -42,6 +45,7 @@ pub struct Input {
     pub output_handler: OutputHandlerResource,
     pub conditional_sealer: ConditionalSealerResource,
     pub master_pool: PoolResource<MasterPool>,
+    pub sequencer_fence: Option<SequencerFenceResource>,
     #[context(default)]
     pub app_health: AppHealthCheckResource,
 }
@@ -54,6 +58,7 @@ pub struct Output {
     #[context(task)]
     pub rocksdb_catchup: AsyncCatchupTask,
     pub rocksdb_termination_hook: ShutdownHook,
+    pub pending_batch_state: Option<PendingBatchStateResource>,
 }
 
 impl StateKeeperLayer {
@@ -61,8 +66,27 @@ impl StateKeeperLayer {
         Self {
             state_keeper_db_path,
             rocksdb_options,
+            witness_inputs_pregeneration_enabled: false,
+            pending_batch_state_enabled: false,
         }
     }
+
+    /// Must be set if witness inputs pre-generation is enabled in the output handler, so that the state keeper
+    /// provides the storage view cache for sealed L1 batches.
+    pub fn with_witness_inputs_pregeneration_enabled(
+        mut self,
+        witness_inputs_pregeneration_enabled: bool,
+    ) -> Self {
+        self.witness_inputs_pregeneration_enabled = witness_inputs_pregeneration_enabled;
+        self
+    }
+
+    /// Makes the state keeper publish the state of the L1 batch being processed, so that it can be reflected
+    /// in the `pending` block by the API server.
+    pub fn with_pending_batch_state_enabled(mut self, pending_batch_state_enabled: bool) -> Self {
+        self.pending_batch_state_enabled = pending_batch_state_enabled;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -99,21 +123,35 @@ impl WiringLayer for StateKeeperLayer {
             self.rocksdb_options,
         );
 
+        // In the standby mode, the RocksDB cache is kept updated until the sequencer lease is acquired.
+        let standby = input
+            .sequencer_fence
+            .map(|SequencerFenceResource(fence)| (fence, storage_factory.keep_updated()));
         let state_keeper = ZkSyncStateKeeper::new(
             io,
             batch_executor_base,
             output_handler,
             sealer,
             Arc::new(storage_factory),
-        );
+        )
+        .with_storage_view_cache(self.witness_inputs_pregeneration_enabled)
+        .with_pending_batch_state(self.pending_batch_state_enabled);
+        let pending_batch_state = state_keeper.pending_state().map(PendingBatchStateResource);
 
-        let state_keeper = StateKeeperTask { state_keeper };
+        let state_keeper = StateKeeperTask {
+            state_keeper,
+            standby,
+        };
 
         input
             .app_health
             .0
             .insert_component(state_keeper.health_check())
             .map_err(WiringError::internal)?;
+        input
+            .app_health
+            .0
+            .add_dependency("state_keeper", "database");
 
         let rocksdb_termination_hook = ShutdownHook::new("rocksdb_terminaton", async {
             // Wait for all the instances of RocksDB to be destroyed.
@@ -125,6 +163,7 @@ impl WiringLayer for StateKeeperLayer {
             state_keeper,
             rocksdb_catchup,
             rocksdb_termination_hook,
+            pending_batch_state,
         })
     }
 }
@@ -132,6 +171,7 @@ impl WiringLayer for StateKeeperLayer {
 #[derive(Debug)]
 pub struct StateKeeperTask {
     state_keeper: ZkSyncStateKeeper,
+    standby: Option<(SequencerFence, KeepUpdatedTask)>,
 }
 
 impl StateKeeperTask {
@@ -147,7 +187,15 @@ impl Task for StateKeeperTask {
         "state_keeper".into()
     }
 
-    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        if let Some((fence, rocksdb_updater)) = self.standby {
+            if !fence
+                .run_standby(rocksdb_updater, &mut stop_receiver.0)
+                .await?
+            {
+                return Ok(());
+            }
+        }
         self.state_keeper.run(stop_receiver.0).await
     }
 }
@@ -1,16 +1,20 @@
+use std::num::NonZeroU32;
+
 use anyhow::Context as _;
 use zksync_node_framework_derive::FromContext;
 use zksync_state_keeper::{
-    io::seal_logic::l2_block_seal_subtasks::L2BlockSealProcess, L2BlockSealerTask, OutputHandler,
-    StateKeeperPersistence, TreeWritesPersistence,
+    io::seal_logic::l2_block_seal_subtasks::L2BlockSealProcess, BatchCheckpointsPersistence,
+    L2BlockSealerTask, OutputHandler, SoftConfirmationsPersistence, StateKeeperPersistence,
+    TreeWritesPersistence, WitnessInputsPersistence,
 };
-use zksync_types::L2_ASSET_ROUTER_ADDRESS;
+use zksync_types::{K256PrivateKey, L2_ASSET_ROUTER_ADDRESS};
 
 use crate::{
     implementations::resources::{
         contracts::{L2ContractsResource, SettlementLayerContractsResource},
+        object_store::ObjectStoreResource,
         pools::{MasterPool, PoolResource},
-        state_keeper::OutputHandlerResource,
+        state_keeper::{OutputHandlerResource, SequencerFenceResource},
         sync_state::SyncStateResource,
     },
     resource::Unique,
@@ -26,6 +30,8 @@ use crate::{
 ///
 /// - `PoolResource<MasterPool>`
 /// - `SyncStateResource` (optional)
+/// - `ObjectStoreResource` (required if witness inputs pre-generation is enabled)
+/// - `SequencerFenceResource` (optional)
 ///
 /// ## Adds resources
 ///
@@ -45,6 +51,14 @@ pub struct OutputHandlerLayer {
     /// May be set to `false` for nodes that do not participate in the sequencing process (e.g. external nodes)
     /// or run `vm_runner_protective_reads` component.
     protective_reads_persistence_enabled: bool,
+    /// Whether hashes of transactions that last modified storage slots should be persisted.
+    storage_slot_writers_enabled: bool,
+    /// Key used to sign soft confirmations for included transactions. If not set, soft confirmations are not issued.
+    soft_confirmation_signer: Option<K256PrivateKey>,
+    /// Whether VM run data for witness inputs should be generated when sealing L1 batches.
+    witness_inputs_pregeneration_enabled: bool,
+    /// Number of L2 blocks between checkpoints of the unsealed L1 batch. If not set, checkpoints are not persisted.
+    batch_checkpoint_interval: Option<u32>,
 }
 
 #[derive(Debug, FromContext)]
@@ -52,8 +66,10 @@ pub struct OutputHandlerLayer {
 pub struct Input {
     pub master_pool: PoolResource<MasterPool>,
     pub sync_state: Option<SyncStateResource>,
+    pub object_store: Option<ObjectStoreResource>,
     pub contracts: SettlementLayerContractsResource,
     pub l2_contracts_resource: L2ContractsResource,
+    pub sequencer_fence: Option<SequencerFenceResource>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -70,6 +86,10 @@ impl OutputHandlerLayer {
             l2_block_seal_queue_capacity,
             pre_insert_txs: false,
             protective_reads_persistence_enabled: false,
+            storage_slot_writers_enabled: false,
+            soft_confirmation_signer: None,
+            witness_inputs_pregeneration_enabled: false,
+            batch_checkpoint_interval: None,
         }
     }
 
@@ -85,6 +105,35 @@ impl OutputHandlerLayer {
         self.protective_reads_persistence_enabled = protective_reads_persistence_enabled;
         self
     }
+
+    pub fn with_storage_slot_writers_enabled(mut self, storage_slot_writers_enabled: bool) -> Self {
+        self.storage_slot_writers_enabled = storage_slot_writers_enabled;
+        self
+    }
+
+    pub fn with_soft_confirmation_signer(
+        mut self,
+        soft_confirmation_signer: Option<K256PrivateKey>,
+    ) -> Self {
+        self.soft_confirmation_signer = soft_confirmation_signer;
+        self
+    }
+
+    pub fn with_witness_inputs_pregeneration_enabled(
+        mut self,
+        witness_inputs_pregeneration_enabled: bool,
+    ) -> Self {
+        self.witness_inputs_pregeneration_enabled = witness_inputs_pregeneration_enabled;
+        self
+    }
+
+    pub fn with_batch_checkpoint_interval(
+        mut self,
+        batch_checkpoint_interval: Option<u32>,
+    ) -> Self {
+        self.batch_checkpoint_interval = batch_checkpoint_interval;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -126,10 +175,41 @@ impl WiringLayer for OutputHandlerLayer {
         if !self.protective_reads_persistence_enabled {
             persistence = persistence.without_protective_reads();
         }
+        if self.storage_slot_writers_enabled {
+            persistence = persistence.with_storage_slot_writers();
+        }
+        if let Some(SequencerFenceResource(fence)) = input.sequencer_fence {
+            persistence = persistence.with_sequencer_fence(fence);
+        }
 
-        let tree_writes_persistence = TreeWritesPersistence::new(persistence_pool);
+        let tree_writes_persistence = TreeWritesPersistence::new(persistence_pool.clone());
         let mut output_handler = OutputHandler::new(Box::new(persistence))
             .with_handler(Box::new(tree_writes_persistence));
+        if let Some(interval) = self.batch_checkpoint_interval {
+            let interval =
+                NonZeroU32::new(interval).context("batch checkpoint interval must be positive")?;
+            tracing::info!(
+                "Unsealed batch checkpoints are enabled; interval: {interval} L2 blocks"
+            );
+            let checkpoints = BatchCheckpointsPersistence::new(persistence_pool.clone(), interval);
+            output_handler = output_handler.with_handler(Box::new(checkpoints));
+        }
+        if let Some(private_key) = self.soft_confirmation_signer {
+            tracing::info!(
+                "Soft confirmations are enabled; signer: {:?}",
+                private_key.address()
+            );
+            let soft_confirmations =
+                SoftConfirmationsPersistence::new(persistence_pool.clone(), private_key);
+            output_handler = output_handler.with_handler(Box::new(soft_confirmations));
+        }
+        if self.witness_inputs_pregeneration_enabled {
+            let object_store = input.object_store.context(
+                "object store is required for witness inputs pre-generation, but it is not configured",
+            )?;
+            let witness_inputs = WitnessInputsPersistence::new(persistence_pool, object_store.0);
+            output_handler = output_handler.with_handler(Box::new(witness_inputs));
+        }
         if let Some(sync_state) = input.sync_state {
             output_handler = output_handler.with_handler(Box::new(sync_state.0));
         }
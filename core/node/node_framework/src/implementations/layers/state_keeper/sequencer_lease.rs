@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
+use zksync_state_keeper::SequencerLeaseTask;
+
+use crate::{
+    implementations::resources::{
+        pools::{MasterPool, PoolResource},
+        state_keeper::SequencerFenceResource,
+    },
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for the sequencer lease, which allows running standby sequencer instances.
+///
+/// ## Requests resources
+///
+/// - `PoolResource<MasterPool>`
+///
+/// ## Adds resources
+///
+/// - `SequencerFenceResource`
+///
+/// ## Adds tasks
+///
+/// - `SequencerLeaseTask`
+#[derive(Debug)]
+pub struct SequencerLeaseLayer {
+    instance_id: String,
+    ttl: Duration,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub sequencer_fence: SequencerFenceResource,
+    #[context(task)]
+    pub sequencer_lease_task: SequencerLeaseTask,
+}
+
+impl SequencerLeaseLayer {
+    pub fn new(instance_id: String, ttl: Duration) -> Self {
+        Self { instance_id, ttl }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for SequencerLeaseLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "sequencer_lease_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let pool = input
+            .master_pool
+            .get_singleton()
+            .await
+            .context("Get master pool")?;
+        let sequencer_lease_task = SequencerLeaseTask::new(pool, self.instance_id, self.ttl);
+        let sequencer_fence = SequencerFenceResource(sequencer_lease_task.fence());
+        Ok(Output {
+            sequencer_fence,
+            sequencer_lease_task,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for SequencerLeaseTask {
+    fn id(&self) -> TaskId {
+        "state_keeper/sequencer_lease".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
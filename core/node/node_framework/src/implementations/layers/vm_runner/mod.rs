@@ -8,6 +8,7 @@ use crate::{
 pub mod bwip;
 pub mod playground;
 pub mod protective_reads;
+pub mod upgrade_dry_run;
 
 #[async_trait::async_trait]
 impl<Io: VmRunnerIo> Task for StorageSyncTask<Io> {
@@ -79,6 +79,8 @@ impl WiringLayer for ProtectiveReadsWriterLayer {
             self.zksync_network_id,
             self.protective_reads_writer_config.first_processed_batch,
             self.protective_reads_writer_config.window_size,
+            self.protective_reads_writer_config.fast_vm_mode,
+            self.protective_reads_writer_config.fail_on_divergence,
         )
         .await?;
 
@@ -0,0 +1,71 @@
+use zksync_types::L2ChainId;
+use zksync_vm_runner::impls::ProtocolUpgradeDryRunner;
+
+use crate::{
+    implementations::resources::pools::{MasterPool, PoolResource},
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for [`ProtocolUpgradeDryRunner`].
+#[derive(Debug)]
+pub struct ProtocolUpgradeDryRunLayer {
+    zksync_network_id: L2ChainId,
+    validation_computational_gas_limit: u32,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub dry_runner: ProtocolUpgradeDryRunner,
+}
+
+impl ProtocolUpgradeDryRunLayer {
+    pub fn new(zksync_network_id: L2ChainId, validation_computational_gas_limit: u32) -> Self {
+        Self {
+            zksync_network_id,
+            validation_computational_gas_limit,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ProtocolUpgradeDryRunLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "vm_runner_protocol_upgrade_dry_run"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        // One connection is used to query upgrades and persist results, and another one backs the VM storage.
+        let pool = input.master_pool.get_custom(2).await?;
+        let dry_runner = ProtocolUpgradeDryRunner::new(
+            pool,
+            self.zksync_network_id,
+            self.validation_computational_gas_limit,
+        );
+        Ok(Output { dry_runner })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for ProtocolUpgradeDryRunner {
+    fn id(&self) -> TaskId {
+        "vm_runner/protocol_upgrade_dry_run".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -1,15 +1,25 @@
-use std::{num::NonZeroU32, time::Duration};
+use std::{
+    num::{NonZeroU32, NonZeroUsize},
+    time::Duration,
+};
 
 use anyhow::Context;
 use bridge_addresses::{L1UpdaterInner, MainNodeUpdaterInner};
-use tokio::{sync::oneshot, task::JoinHandle};
+use tokio::{
+    sync::{oneshot, watch},
+    task::JoinHandle,
+};
 use zksync_circuit_breaker::replication_lag::ReplicationLagChecker;
-use zksync_config::configs::api::MaxResponseSize;
+use zksync_config::{configs::api::MaxResponseSize, ObjectStoreConfig};
 use zksync_contracts::{bridgehub_contract, l1_asset_router_contract};
+use zksync_health_check::CheckHealth;
 use zksync_node_api_server::web3::{
+    call_cache::EthCallCache,
     state::{BridgeAddressesHandle, InternalApiConfig, InternalApiConfigBase, SealedL2BlockNumber},
     ApiBuilder, ApiServer, Namespace,
 };
+use zksync_object_store::ObjectStoreFactory;
+use zksync_types::secrets::APIKey;
 
 use crate::{
     implementations::{
@@ -26,6 +36,10 @@ use crate::{
             healthcheck::AppHealthCheckResource,
             main_node_client::MainNodeClientResource,
             pools::{PoolResource, ReplicaPool},
+            reloadable_config::ReloadableConfigResource,
+            state_keeper::{
+                DevModeControlResource, L1BatchSealRequestResource, PendingBatchStateResource,
+            },
             sync_state::SyncStateResource,
             web3_api::{MempoolCacheResource, TreeApiClientResource, TxSenderResource},
         },
@@ -56,6 +70,9 @@ pub struct Web3ServerOptionalConfig {
     // Used by the external node.
     pub bridge_addresses_refresh_interval: Option<Duration>,
     pub polling_interval: Option<Duration>,
+    // Used to serve call traces and events archived by the cold storage archiver.
+    pub cold_storage_object_store: Option<ObjectStoreConfig>,
+    pub eth_call_cache_size: Option<NonZeroUsize>,
 }
 
 impl Web3ServerOptionalConfig {
@@ -87,6 +104,9 @@ impl Web3ServerOptionalConfig {
             api_builder =
                 api_builder.with_pruning_info_refresh_interval(pruning_info_refresh_interval);
         }
+        if let Some(eth_call_cache_size) = self.eth_call_cache_size {
+            api_builder = api_builder.with_eth_call_cache(EthCallCache::new(eth_call_cache_size));
+        }
         api_builder = api_builder.with_extended_tracing(self.with_extended_tracing);
         api_builder
     }
@@ -97,6 +117,8 @@ impl Web3ServerOptionalConfig {
 enum Transport {
     Http,
     Ws,
+    /// HTTP server exclusively serving the authenticated `admin` namespace.
+    AdminHttp,
 }
 
 /// Wiring layer for Web3 JSON RPC server.
@@ -110,6 +132,10 @@ enum Transport {
 /// - `MempoolCacheResource`
 /// - `CircuitBreakersResource` (adds a circuit breaker)
 /// - `AppHealthCheckResource` (adds a health check)
+/// - `ReloadableConfigResource` (optional; allows updating the WebSocket rate limit at runtime)
+/// - `L1BatchSealRequestResource` (optional; used by the `admin` namespace to force L1 batch sealing)
+/// - `DevModeControlResource` (optional; used by the `evm` namespace to control block production)
+/// - `PendingBatchStateResource` (optional; used to reflect unsealed transactions in the `pending` block)
 ///
 /// ## Adds tasks
 ///
@@ -121,6 +147,7 @@ pub struct Web3ServerLayer {
     port: u16,
     optional_config: Web3ServerOptionalConfig,
     internal_api_config_base: InternalApiConfigBase,
+    admin_auth_token: Option<APIKey>,
 }
 
 #[derive(Debug, FromContext)]
@@ -141,6 +168,10 @@ pub struct Input {
     pub l1_contracts_resource: L1ChainContractsResource,
     pub l1_ecosystem_contracts_resource: L1EcosystemContractsResource,
     pub l2_contracts_resource: L2ContractsResource,
+    pub reloadable_config: Option<ReloadableConfigResource>,
+    pub l1_batch_seal_request: Option<L1BatchSealRequestResource>,
+    pub dev_mode_control: Option<DevModeControlResource>,
+    pub pending_batch_state: Option<PendingBatchStateResource>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -167,6 +198,7 @@ impl Web3ServerLayer {
             port,
             optional_config,
             internal_api_config_base,
+            admin_auth_token: None,
         }
     }
 
@@ -180,6 +212,25 @@ impl Web3ServerLayer {
             port,
             optional_config,
             internal_api_config_base,
+            admin_auth_token: None,
+        }
+    }
+
+    /// Creates a layer for an HTTP server serving only the `admin` namespace. All requests to the server
+    /// must provide the `auth_token` as a bearer token.
+    pub fn admin(
+        port: u16,
+        internal_api_config_base: InternalApiConfigBase,
+        mut optional_config: Web3ServerOptionalConfig,
+        auth_token: APIKey,
+    ) -> Self {
+        optional_config.namespaces = Some(vec![Namespace::Admin]);
+        Self {
+            transport: Transport::AdminHttp,
+            port,
+            optional_config,
+            internal_api_config_base,
+            admin_auth_token: Some(auth_token),
         }
     }
 }
@@ -193,10 +244,11 @@ impl WiringLayer for Web3ServerLayer {
         match self.transport {
             Transport::Http => "web3_http_server_layer",
             Transport::Ws => "web3_ws_server_layer",
+            Transport::AdminHttp => "web3_admin_server_layer",
         }
     }
 
-    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+    async fn wire(mut self, input: Self::Input) -> Result<Self::Output, WiringError> {
         // Get required resources.
         let replica_resource_pool = input.replica_pool;
         let updaters_pool = replica_resource_pool.get_custom(1).await?;
@@ -262,7 +314,32 @@ impl WiringLayer for Web3ServerLayer {
             }
             Transport::Ws => {
                 api_builder = api_builder.ws(self.port);
+                if let Some(ReloadableConfigResource(handle)) = input.reloadable_config {
+                    let (limit_sender, limit_receiver) =
+                        watch::channel(handle.current().websocket_requests_per_minute_limit);
+                    handle.subscribe("ws_api_rate_limit", move |config| {
+                        limit_sender.send_replace(config.websocket_requests_per_minute_limit);
+                    });
+                    api_builder = api_builder
+                        .with_websocket_requests_per_minute_limit_updates(limit_receiver);
+                }
             }
+            Transport::AdminHttp => {
+                api_builder = api_builder.http(self.port);
+                if let Some(token) = self.admin_auth_token.take() {
+                    api_builder = api_builder.with_admin_auth_token(token);
+                }
+                if let Some(L1BatchSealRequestResource(seal_request)) = input.l1_batch_seal_request
+                {
+                    api_builder = api_builder.with_l1_batch_seal_request(seal_request);
+                }
+            }
+        }
+        if let Some(DevModeControlResource(control)) = input.dev_mode_control {
+            api_builder = api_builder.with_dev_mode_control(control);
+        }
+        if let Some(PendingBatchStateResource(pending_state)) = input.pending_batch_state {
+            api_builder = api_builder.with_pending_batch_state(pending_state);
         }
         if let Some(sync_state) = sync_state {
             api_builder = api_builder.with_sync_state(sync_state);
@@ -270,6 +347,12 @@ impl WiringLayer for Web3ServerLayer {
         if let Some(main_node_client) = input.main_node_client {
             api_builder = api_builder.with_l2_l1_log_proof_handler(main_node_client.0)
         }
+        if let Some(object_store_config) = self.optional_config.cold_storage_object_store.take() {
+            let cold_storage = ObjectStoreFactory::new(object_store_config)
+                .create_store()
+                .await?;
+            api_builder = api_builder.with_cold_storage(cold_storage);
+        }
         let replication_lag_limit = self.optional_config.replication_lag_limit;
         api_builder = self.optional_config.apply(api_builder);
 
@@ -277,11 +360,17 @@ impl WiringLayer for Web3ServerLayer {
 
         // Insert healthcheck.
         let api_health_check = server.health_check();
+        let api_component = api_health_check.name();
         input
             .app_health
             .0
             .insert_component(api_health_check)
             .map_err(WiringError::internal)?;
+        input.app_health.0.add_dependency(api_component, "database");
+        input
+            .app_health
+            .0
+            .add_dependency(api_component, "state_keeper");
 
         // Insert circuit breaker.
         input
@@ -336,6 +425,7 @@ impl Task for Web3ApiTask {
         match self.transport {
             Transport::Http => "web3_http_server".into(),
             Transport::Ws => "web3_ws_server".into(),
+            Transport::AdminHttp => "web3_admin_server".into(),
         }
     }
 
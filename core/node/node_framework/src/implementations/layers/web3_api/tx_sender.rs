@@ -6,8 +6,11 @@ use zksync_node_api_server::{
     execution_sandbox::{VmConcurrencyBarrier, VmConcurrencyLimiter},
     tx_sender::{SandboxExecutorOptions, TimestampAsserterParams, TxSenderBuilder, TxSenderConfig},
 };
-use zksync_state::{PostgresStorageCaches, PostgresStorageCachesTask};
+use zksync_state::{
+    PostgresStorageCaches, PostgresStorageCachesTask, PostgresStorageReadBatcherTask,
+};
 use zksync_types::{vm::FastVmMode, AccountTreeId, Address};
+use zksync_vm_executor::oneshot::{FrameGasCap, ResourceLimits};
 use zksync_web3_decl::{
     client::{DynClient, L2},
     jsonrpsee,
@@ -37,6 +40,7 @@ pub struct PostgresStorageCachesConfig {
     pub initial_writes_cache_size: u64,
     pub latest_values_cache_size: u64,
     pub latest_values_max_block_lag: u32,
+    pub storage_read_batching_window: Option<Duration>,
 }
 
 /// Wiring layer for the `TxSender`.
@@ -56,6 +60,7 @@ pub struct PostgresStorageCachesConfig {
 /// ## Adds tasks
 ///
 /// - `PostgresStorageCachesTask`
+/// - `PostgresStorageReadBatcherTask` (optional)
 /// - `VmConcurrencyBarrierTask`
 /// - `WhitelistedTokensForAaUpdateTask` (optional)
 #[derive(Debug)]
@@ -64,6 +69,9 @@ pub struct TxSenderLayer {
     max_vm_concurrency: usize,
     whitelisted_tokens_for_aa_cache: bool,
     vm_mode: FastVmMode,
+    vm_warm_pool_size: usize,
+    vm_resource_limits: Option<ResourceLimits>,
+    frame_gas_cap: FrameGasCap,
     timestamp_asserter_config: Option<TimestampAsserterConfig>,
     tx_sender_config: TxSenderConfig,
 }
@@ -91,6 +99,8 @@ pub struct Output {
     #[context(task)]
     pub postgres_storage_caches_task: Option<PostgresStorageCachesTask>,
     #[context(task)]
+    pub postgres_storage_read_batcher_task: Option<PostgresStorageReadBatcherTask>,
+    #[context(task)]
     pub whitelisted_tokens_for_aa_update_task: Option<WhitelistedTokensForAaUpdateTask>,
 }
 
@@ -106,6 +116,9 @@ impl TxSenderLayer {
             max_vm_concurrency,
             whitelisted_tokens_for_aa_cache: false,
             vm_mode: FastVmMode::Old,
+            vm_warm_pool_size: 0,
+            vm_resource_limits: None,
+            frame_gas_cap: FrameGasCap::default(),
             timestamp_asserter_config,
             tx_sender_config,
         }
@@ -125,6 +138,25 @@ impl TxSenderLayer {
         self.vm_mode = mode;
         self
     }
+
+    /// Sets the number of pre-initialized VM states kept by the sandbox executor. Disabled (0) by default.
+    pub fn with_vm_warm_pool_size(mut self, size: usize) -> Self {
+        self.vm_warm_pool_size = size;
+        self
+    }
+
+    /// Enables resource accounting for sandbox VM executions with the specified limits. Disabled by default.
+    pub fn with_vm_resource_limits(mut self, limits: ResourceLimits) -> Self {
+        self.vm_resource_limits = Some(limits);
+        self
+    }
+
+    /// Sets the cap on gas forwarded to a single call frame for sandbox VM executions. Should match the cap
+    /// enforced by the state keeper. Disabled by default; not supported by the fast VM.
+    pub fn with_frame_gas_cap(mut self, frame_gas_cap: FrameGasCap) -> Self {
+        self.frame_gas_cap = frame_gas_cap;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -137,6 +169,13 @@ impl WiringLayer for TxSenderLayer {
     }
 
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        if self.frame_gas_cap.is_enabled() && !matches!(self.vm_mode, FastVmMode::Old) {
+            return Err(WiringError::Configuration(format!(
+                "frame gas cap is not supported by the fast VM (mode: {:?})",
+                self.vm_mode
+            )));
+        }
+
         // Get required resources.
         let tx_sink = input.tx_sink.0;
         let replica_pool = input.replica_pool.get().await?;
@@ -179,6 +218,10 @@ impl WiringLayer for TxSenderLayer {
         } else {
             None
         };
+        let postgres_storage_read_batcher_task = self
+            .postgres_storage_caches_config
+            .storage_read_batching_window
+            .map(|window| storage_caches.configure_read_batching(window, replica_pool.clone()));
 
         // Initialize `VmConcurrencyLimiter`.
         let (vm_concurrency_limiter, vm_concurrency_barrier) =
@@ -193,6 +236,11 @@ impl WiringLayer for TxSenderLayer {
         )
         .await?;
         executor_options.set_fast_vm_mode(self.vm_mode);
+        executor_options.set_vm_warm_pool_size(self.vm_warm_pool_size);
+        if let Some(limits) = self.vm_resource_limits {
+            executor_options.set_vm_resource_limits(limits);
+        }
+        executor_options.set_frame_gas_cap(self.frame_gas_cap);
 
         if let Some(store) = input.core_object_store {
             executor_options.set_vm_dump_object_store(store.0);
@@ -238,6 +286,7 @@ impl WiringLayer for TxSenderLayer {
         Ok(Output {
             tx_sender: tx_sender.into(),
             postgres_storage_caches_task,
+            postgres_storage_read_batcher_task,
             vm_concurrency_barrier,
             whitelisted_tokens_for_aa_update_task,
         })
@@ -255,6 +304,17 @@ impl Task for PostgresStorageCachesTask {
     }
 }
 
+#[async_trait::async_trait]
+impl Task for PostgresStorageReadBatcherTask {
+    fn id(&self) -> TaskId {
+        "postgres_storage_read_batcher".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
+
 #[async_trait::async_trait]
 impl Task for VmConcurrencyBarrier {
     fn id(&self) -> TaskId {
@@ -1,5 +1,6 @@
 pub use self::{master_pool_sink::MasterPoolSinkLayer, proxy_sink::ProxySinkLayer};
 
 pub mod master_pool_sink;
+pub mod policy;
 pub mod proxy_sink;
 pub mod whitelist;
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use zksync_config::configs::{api::DeploymentAllowlist, TxPolicyConfig};
+use zksync_node_api_server::tx_sender::{
+    master_pool_sink::MasterPoolSink,
+    policy::PolicyEnforcingSink,
+    whitelist::{AllowListTask, WhitelistedDeployPoolSink},
+};
+use zksync_state_keeper::tx_policy::TxPolicy;
+
+use crate::{
+    implementations::resources::{
+        pools::{MasterPool, PoolResource},
+        web3_api::TxSinkResource,
+    },
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for [`PolicyEnforcingSink`] that wraps a `MasterPoolSink` (optionally with a deployment allowlist)
+/// and enforces the transaction policy.
+pub struct PolicyEnforcingMasterPoolSinkLayer {
+    pub tx_policy: TxPolicyConfig,
+    pub deployment_allowlist: Option<DeploymentAllowlist>,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub tx_sink: TxSinkResource,
+    #[context(task)]
+    pub allow_list_task: Option<AllowListTask>,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for PolicyEnforcingMasterPoolSinkLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "policy_enforcing_master_pool_sink_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let pool = input.master_pool.get().await?;
+        let master_pool_sink = MasterPoolSink::new(pool.clone());
+        let policy = Arc::new(TxPolicy::new(&self.tx_policy));
+
+        let (tx_sink, allow_list_task) = match self.deployment_allowlist {
+            Some(deployment_allowlist) => {
+                let allow_list_task = AllowListTask::from_config(deployment_allowlist);
+                let inner =
+                    WhitelistedDeployPoolSink::new(master_pool_sink, allow_list_task.shared());
+                let tx_sink = PolicyEnforcingSink::new(inner, policy, pool);
+                (tx_sink, Some(allow_list_task))
+            }
+            None => (
+                PolicyEnforcingSink::new(master_pool_sink, policy, pool),
+                None,
+            ),
+        };
+
+        Ok(Output {
+            tx_sink: tx_sink.into(),
+            allow_list_task,
+        })
+    }
+}
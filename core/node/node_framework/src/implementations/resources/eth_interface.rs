@@ -71,6 +71,16 @@ impl Resource for BoundEthInterfaceForBlobsResource {
     }
 }
 
+/// Same as `BoundEthInterfaceResource`, but for the additional keys of the operator pool.
+#[derive(Debug, Clone)]
+pub struct BoundEthInterfaceForAdditionalOperatorsResource(pub Vec<Box<dyn BoundEthInterface>>);
+
+impl Resource for BoundEthInterfaceForAdditionalOperatorsResource {
+    fn name() -> String {
+        "common/bound_eth_interface_for_additional_operators".into()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BoundEthInterfaceForL2Resource(pub Box<dyn BoundEthInterface>);
 
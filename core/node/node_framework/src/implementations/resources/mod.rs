@@ -12,6 +12,7 @@ pub mod main_node_client;
 pub mod object_store;
 pub mod pools;
 pub mod price_api_client;
+pub mod reloadable_config;
 pub mod reverter;
 pub mod settlement_layer;
 pub mod state_keeper;
@@ -0,0 +1,15 @@
+pub use crate::implementations::layers::config_reloader::{
+    ReloadableConfig, ReloadableConfigHandle,
+};
+use crate::resource::Resource;
+
+/// A resource that provides [`ReloadableConfigHandle`] to the service. Components supporting config reloading
+/// should treat this resource as optional; if it's not provided, the config is static.
+#[derive(Debug, Clone)]
+pub struct ReloadableConfigResource(pub ReloadableConfigHandle);
+
+impl Resource for ReloadableConfigResource {
+    fn name() -> String {
+        "common/reloadable_config".into()
+    }
+}
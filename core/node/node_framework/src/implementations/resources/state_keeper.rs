@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
 use zksync_state::OwnedStorage;
-use zksync_state_keeper::{seal_criteria::ConditionalSealer, OutputHandler, StateKeeperIO};
+use zksync_state_keeper::{
+    seal_criteria::ConditionalSealer, DevModeControl, L1BatchSealRequest, OutputHandler,
+    PendingBatchStateReader, SequencerFence, StateKeeperIO,
+};
 use zksync_vm_executor::interface::BatchExecutorFactory;
 
 use crate::resource::{Resource, Unique};
@@ -78,3 +81,45 @@ where
         Self(Arc::new(sealer))
     }
 }
+
+/// A resource that allows requesting the state keeper to seal the current L1 batch.
+#[derive(Debug, Clone)]
+pub struct L1BatchSealRequestResource(pub L1BatchSealRequest);
+
+impl Resource for L1BatchSealRequestResource {
+    fn name() -> String {
+        "state_keeper/l1_batch_seal_request".into()
+    }
+}
+
+/// A resource that allows controlling block production if the state keeper runs in the development mode.
+#[derive(Debug, Clone)]
+pub struct DevModeControlResource(pub DevModeControl);
+
+impl Resource for DevModeControlResource {
+    fn name() -> String {
+        "state_keeper/dev_mode_control".into()
+    }
+}
+
+/// A resource providing read access to the state of the L1 batch being processed by the state keeper,
+/// including transactions in the unsealed L2 block.
+#[derive(Debug, Clone)]
+pub struct PendingBatchStateResource(pub PendingBatchStateReader);
+
+impl Resource for PendingBatchStateResource {
+    fn name() -> String {
+        "state_keeper/pending_batch_state".into()
+    }
+}
+
+/// A resource providing the fence for the sequencer lease. If present, the state keeper and the mempool fetcher
+/// only run while this instance holds the lease.
+#[derive(Debug, Clone)]
+pub struct SequencerFenceResource(pub SequencerFence);
+
+impl Resource for SequencerFenceResource {
+    fn name() -> String {
+        "state_keeper/sequencer_fence".into()
+    }
+}
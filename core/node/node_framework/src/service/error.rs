@@ -47,4 +47,6 @@ pub enum ZkStackServiceError {
     Wiring(Vec<(String, WiringError)>),
     #[error("One or more tasks failed: {0:?}")]
     Task(TaskErrors),
+    #[error("One or more services in the group failed: {0:?}")]
+    Group(Vec<(String, ZkStackServiceError)>),
 }
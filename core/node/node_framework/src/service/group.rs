@@ -0,0 +1,182 @@
+use std::{sync::mpsc, thread};
+
+use tokio::{runtime::Runtime, sync::watch};
+use zksync_vlog::ObservabilityGuard;
+
+use super::{StopReceiver, ZkStackService, ZkStackServiceBuilder, ZkStackServiceError};
+use crate::{
+    task::{Task, TaskId, TaskKind},
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+
+/// Group of [`ZkStackService`]s running in a single process on a shared Tokio runtime.
+///
+/// Each service has its own set of resources and tasks, so the services are isolated from each other
+/// unless they are explicitly provided with the same resource instances (e.g., via
+/// [`SharedResourceLayer`](crate::implementations::layers::shared_resource::SharedResourceLayer)).
+/// Once any service in the group exits, the stop signal is sent to all other services.
+#[derive(Debug)]
+pub struct ZkStackServiceGroup {
+    runtime: Runtime,
+    services: Vec<(String, ZkStackService)>,
+    stop_sender: watch::Sender<bool>,
+}
+
+impl ZkStackServiceGroup {
+    /// Creates a new group.
+    ///
+    /// Returns an error if called within a Tokio runtime context.
+    pub fn new() -> Result<Self, ZkStackServiceError> {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(ZkStackServiceError::RuntimeDetected);
+        }
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let (stop_sender, _) = watch::channel(false);
+        Ok(Self {
+            runtime,
+            services: Vec::new(),
+            stop_sender,
+        })
+    }
+
+    /// Returns a handle to the Tokio runtime shared by the services in the group.
+    pub fn runtime_handle(&self) -> tokio::runtime::Handle {
+        self.runtime.handle().clone()
+    }
+
+    /// Creates a builder for a service running on the group runtime.
+    pub fn service_builder(&self) -> ZkStackServiceBuilder {
+        ZkStackServiceBuilder::on_runtime_handle(self.runtime_handle())
+    }
+
+    /// Adds a service to the group. The builder must be obtained via [`Self::service_builder()`].
+    ///
+    /// `name` is used to distinguish services in logs and errors, so it should be unique within the group.
+    pub fn add_service(
+        &mut self,
+        name: impl Into<String>,
+        mut builder: ZkStackServiceBuilder,
+    ) -> &mut Self {
+        builder.add_layer(GroupStopLayer(self.stop_sender.subscribe()));
+        self.services.push((name.into(), builder.build()));
+        self
+    }
+
+    /// Runs all services in the group until any of them exits, then stops the remaining services.
+    ///
+    /// Errors of all services are collected and returned as [`ZkStackServiceError::Group`].
+    /// `observability_guard`, if provided, will be used to deinitialize the observability subsystem
+    /// after all services have exited.
+    pub fn run(
+        self,
+        observability_guard: impl Into<Option<ObservabilityGuard>>,
+    ) -> Result<(), ZkStackServiceError> {
+        let (exit_sender, exit_receiver) = mpsc::channel();
+        let service_threads: Vec<_> = self
+            .services
+            .into_iter()
+            .map(|(name, service)| {
+                let exit_sender = exit_sender.clone();
+                let thread = thread::Builder::new()
+                    .name(format!("service-{name}"))
+                    .spawn(move || {
+                        let result = service.run(None);
+                        exit_sender.send(()).ok();
+                        result
+                    })
+                    .expect("failed spawning service thread");
+                (name, thread)
+            })
+            .collect();
+        drop(exit_sender);
+
+        // Wait until the first service exits (`recv()` errors if there are no services), and stop the remaining ones.
+        exit_receiver.recv().ok();
+        tracing::info!("One of the services in the group has exited, shutting down the group");
+        self.stop_sender.send_replace(true);
+
+        let mut errors = Vec::new();
+        for (name, thread) in service_threads {
+            let result = thread
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+            match result {
+                Ok(()) => tracing::info!("Service {name} exited"),
+                Err(err) => {
+                    tracing::error!("Service {name} failed: {err}");
+                    errors.push((name, err));
+                }
+            }
+        }
+
+        tracing::info!("Exiting the service group");
+        if let Some(observability_guard) = &mut observability_guard.into() {
+            // Make sure that the shutdown happens in the `tokio` context.
+            let _guard = self.runtime.enter();
+            observability_guard.shutdown();
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ZkStackServiceError::Group(errors))
+        }
+    }
+}
+
+/// Wiring layer propagating the group stop signal to a service.
+#[derive(Debug)]
+struct GroupStopLayer(watch::Receiver<bool>);
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+struct GroupStopOutput {
+    #[context(task)]
+    task: GroupStopTask,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for GroupStopLayer {
+    type Input = ();
+    type Output = GroupStopOutput;
+
+    fn layer_name(&self) -> &'static str {
+        "group_stop_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        Ok(GroupStopOutput {
+            task: GroupStopTask(self.0),
+        })
+    }
+}
+
+/// Task that exits once the group stop signal is received, which makes the service shut down.
+#[derive(Debug)]
+struct GroupStopTask(watch::Receiver<bool>);
+
+#[async_trait::async_trait]
+impl Task for GroupStopTask {
+    fn kind(&self) -> TaskKind {
+        // The group may be stopped before the preconditions of the service are met.
+        TaskKind::UnconstrainedTask
+    }
+
+    fn id(&self) -> TaskId {
+        "group_stop".into()
+    }
+
+    async fn run(mut self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        tokio::select! {
+            _ = self.0.wait_for(|stop| *stop) => {
+                tracing::info!("Received group stop signal");
+            }
+            _ = stop_receiver.0.changed() => {}
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
 use std::{collections::HashMap, time::Duration};
 
 use futures::future::Fuse;
-use tokio::{runtime::Runtime, sync::watch, task::JoinHandle};
+use tokio::{
+    runtime::{Handle, Runtime},
+    sync::watch,
+    task::JoinHandle,
+};
 use zksync_utils::panic_extractor::try_extract_panic_message;
 use zksync_vlog::ObservabilityGuard;
 
@@ -9,6 +13,7 @@ pub use self::{
     context::ServiceContext,
     context_traits::{FromContext, IntoContext},
     error::{TaskError, ZkStackServiceError},
+    group::ZkStackServiceGroup,
     shutdown_hook::ShutdownHook,
     stop_receiver::StopReceiver,
 };
@@ -25,6 +30,7 @@ use crate::{
 mod context;
 mod context_traits;
 mod error;
+mod group;
 mod named_future;
 mod runnables;
 mod shutdown_hook;
@@ -35,6 +41,30 @@ mod tests;
 // A reasonable amount of time for any task to finish the shutdown process
 const TASK_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Tokio runtime used by a [`ZkStackService`]. The runtime is either owned by the service,
+/// or shared with other services (e.g., ones managed by a [`ZkStackServiceGroup`]).
+#[derive(Debug)]
+enum ServiceRuntime {
+    Owned(Runtime),
+    Shared(Handle),
+}
+
+impl ServiceRuntime {
+    fn handle(&self) -> &Handle {
+        match self {
+            Self::Owned(runtime) => runtime.handle(),
+            Self::Shared(handle) => handle,
+        }
+    }
+
+    fn block_on<F: std::future::Future>(&self, future: F) -> F::Output {
+        match self {
+            Self::Owned(runtime) => runtime.block_on(future),
+            Self::Shared(handle) => handle.block_on(future),
+        }
+    }
+}
+
 /// A builder for [`ZkStackService`].
 #[derive(Debug)]
 pub struct ZkStackServiceBuilder {
@@ -43,7 +73,7 @@ pub struct ZkStackServiceBuilder {
     // iterate through it matters.
     layers: Vec<(&'static str, WireFn)>,
     /// Tokio runtime used to spawn tasks.
-    runtime: Runtime,
+    runtime: ServiceRuntime,
 }
 
 impl ZkStackServiceBuilder {
@@ -69,12 +99,23 @@ impl ZkStackServiceBuilder {
     pub fn on_runtime(runtime: Runtime) -> Self {
         Self {
             layers: Vec::new(),
-            runtime,
+            runtime: ServiceRuntime::Owned(runtime),
+        }
+    }
+
+    /// Creates a new builder running on the runtime with the provided handle. The runtime must outlive the service.
+    ///
+    /// This method is used to run multiple services on a single runtime; see [`ZkStackServiceGroup`].
+    /// The built service must be run outside the runtime context (e.g., on a dedicated thread).
+    pub fn on_runtime_handle(handle: Handle) -> Self {
+        Self {
+            layers: Vec::new(),
+            runtime: ServiceRuntime::Shared(handle),
         }
     }
 
     /// Returns a handle to the Tokio runtime used by the service.
-    pub fn runtime_handle(&self) -> tokio::runtime::Handle {
+    pub fn runtime_handle(&self) -> Handle {
         self.runtime.handle().clone()
     }
 
@@ -128,7 +169,7 @@ pub struct ZkStackService {
     /// Sender used to stop the tasks.
     stop_sender: watch::Sender<bool>,
     /// Tokio runtime used to spawn tasks.
-    runtime: Runtime,
+    runtime: ServiceRuntime,
 
     /// Collector for the task errors met during the service execution.
     errors: Vec<TaskError>,
@@ -163,7 +204,7 @@ impl ZkStackService {
 
         if let Some(observability_guard) = &mut observability_guard.into() {
             // Make sure that the shutdown happens in the `tokio` context.
-            let _guard = self.runtime.enter();
+            let _guard = self.runtime.handle().enter();
             observability_guard.shutdown();
         }
 
@@ -5,7 +5,10 @@ use assert_matches::assert_matches;
 use tokio::{runtime::Runtime, sync::Barrier};
 
 use crate::{
-    service::{StopReceiver, WiringError, WiringLayer, ZkStackServiceBuilder, ZkStackServiceError},
+    service::{
+        StopReceiver, WiringError, WiringLayer, ZkStackServiceBuilder, ZkStackServiceError,
+        ZkStackServiceGroup,
+    },
     task::{Task, TaskId},
     IntoContext,
 };
@@ -252,3 +255,69 @@ fn test_task_run() {
     let res2 = *remaining_task_was_run.lock().unwrap();
     assert!(res2, "Incorrect resource value");
 }
+
+#[derive(Debug)]
+struct StoppableTaskLayer(Arc<Mutex<bool>>);
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+struct StoppableTaskLayerOutput {
+    #[context(task)]
+    task: StoppableTask,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for StoppableTaskLayer {
+    type Input = ();
+    type Output = StoppableTaskLayerOutput;
+
+    fn layer_name(&self) -> &'static str {
+        "stoppable_task_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        Ok(StoppableTaskLayerOutput {
+            task: StoppableTask(self.0),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct StoppableTask(Arc<Mutex<bool>>);
+
+#[async_trait::async_trait]
+impl Task for StoppableTask {
+    fn id(&self) -> TaskId {
+        "stoppable_task".into()
+    }
+
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        stop_receiver.0.changed().await?;
+        *self.0.lock().unwrap() = true;
+        Ok(())
+    }
+}
+
+// A failing service in `ZkStackServiceGroup` should stop other services in the group.
+#[test]
+fn test_service_group_stops_on_service_exit() {
+    let task_was_stopped = Arc::new(Mutex::new(false));
+    let mut group = ZkStackServiceGroup::new().unwrap();
+
+    let mut failing_service = group.service_builder();
+    failing_service.add_layer(TaskErrorLayer);
+    let mut stoppable_service = group.service_builder();
+    stoppable_service.add_layer(StoppableTaskLayer(task_was_stopped.clone()));
+    group
+        .add_service("failing", failing_service)
+        .add_service("stoppable", stoppable_service);
+
+    let err = group.run(None).unwrap_err();
+    let ZkStackServiceError::Group(errors) = err else {
+        panic!("Unexpected error: {err:?}");
+    };
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, "failing");
+    assert_matches!(errors[0].1, ZkStackServiceError::Task(_));
+    assert!(*task_was_stopped.lock().unwrap(), "Task was not stopped");
+}
@@ -11,7 +11,7 @@ use crate::{resource::ResourceId, service::ServiceContext, FromContext, IntoCont
 /// See [`WiringLayerExt`] trait for more context.
 #[allow(clippy::type_complexity)] // False positive, already a dedicated type.
 pub(crate) struct WireFn(
-    pub Box<dyn FnOnce(&runtime::Handle, &mut ServiceContext<'_>) -> Result<(), WiringError>>,
+    pub Box<dyn FnOnce(&runtime::Handle, &mut ServiceContext<'_>) -> Result<(), WiringError> + Send>,
 );
 
 impl fmt::Debug for WireFn {
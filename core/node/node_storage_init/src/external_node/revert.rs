@@ -13,6 +13,10 @@ pub struct ExternalNodeReverter {
     pub client: Box<DynClient<L2>>,
     pub pool: ConnectionPool<Core>,
     pub reverter: Option<BlockReverter>,
+    /// If set, diverged L1 batches are not reverted; instead, node initialization fails with a divergence report.
+    pub cross_verification: bool,
+    /// Maximum number of L1 batches that can be rolled back automatically; unbounded if not set.
+    pub max_rollback_depth: Option<u32>,
 }
 
 #[async_trait::async_trait]
@@ -44,7 +48,9 @@ impl RevertStorage for ExternalNodeReverter {
         &self,
         stop_receiver: watch::Receiver<bool>,
     ) -> anyhow::Result<Option<L1BatchNumber>> {
-        let mut reorg_detector = ReorgDetector::new(self.client.clone(), self.pool.clone());
+        let mut reorg_detector = ReorgDetector::new(self.client.clone(), self.pool.clone())
+            .with_cross_verification(self.cross_verification)
+            .with_max_rollback_depth(self.max_rollback_depth);
         let batch = match reorg_detector.run_once(stop_receiver).await {
             Ok(()) => {
                 // Even if stop signal was received, the node will shut down without launching any tasks.
@@ -65,6 +65,10 @@ impl InitializeStorage for ExternalNodeSnapshotRecovery {
             tracing::info!("Dropping storage key preimages for snapshot storage logs");
             snapshots_applier_task.drop_storage_key_preimages();
         }
+        if self.recovery_config.apply_differential_snapshots {
+            tracing::info!("Allowing recovery from differential snapshots");
+            snapshots_applier_task.apply_differential_snapshots();
+        }
         self.app_health
             .insert_component(snapshots_applier_task.health_check())?;
 
@@ -130,6 +134,7 @@ mod tests {
             recovery_config: SnapshotRecoveryConfig {
                 snapshot_l1_batch_override: None,
                 drop_storage_key_preimages: false,
+                apply_differential_snapshots: false,
                 object_store_config: None,
             },
             app_health,
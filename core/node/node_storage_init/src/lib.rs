@@ -16,6 +16,8 @@ pub struct SnapshotRecoveryConfig {
     /// If not specified, the latest snapshot will be used.
     pub snapshot_l1_batch_override: Option<L1BatchNumber>,
     pub drop_storage_key_preimages: bool,
+    /// Allows recovering from a chain of differential snapshots.
+    pub apply_differential_snapshots: bool,
     pub object_store_config: Option<ObjectStoreConfig>,
 }
 
@@ -123,6 +123,7 @@ impl MainNodeClient for Box<DynClient<L2>> {
             l1_batch_commit_data_generator_mode: dto.l1_batch_commit_data_generator_mode,
             // External node should initialise itself from a snapshot
             custom_genesis_state_path: None,
+            circuit_geometry: None,
         })
     }
 
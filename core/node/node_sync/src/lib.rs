@@ -5,6 +5,7 @@ pub mod external_io;
 pub mod fetcher;
 pub mod genesis;
 mod metrics;
+pub mod response_checker;
 pub mod sync_action;
 mod sync_state;
 pub mod testonly;
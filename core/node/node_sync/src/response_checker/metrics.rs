@@ -0,0 +1,21 @@
+//! Metrics for the response checker.
+
+use vise::{Counter, Family, Gauge, Metrics};
+
+use super::DivergenceKind;
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "external_node_response_checker")]
+pub(super) struct ResponseCheckerMetrics {
+    /// Number of L2 blocks checked against the peer.
+    pub checked_l2_blocks: Counter,
+    /// Last L2 block checked against the peer.
+    pub last_checked_l2_block: Gauge<u64>,
+    /// Number of detected divergences between the local node and the peer.
+    pub divergences: Family<DivergenceKind, Counter>,
+    /// Number of errors encountered during checks (e.g., network errors when querying the peer).
+    pub errors: Counter,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<ResponseCheckerMetrics> = vise::Global::new();
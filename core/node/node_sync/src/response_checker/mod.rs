@@ -0,0 +1,438 @@
+//! Response checker spot-checking data served by a (potentially pruned) node against a full archive peer.
+
+use std::{fmt, time::Duration};
+
+use async_trait::async_trait;
+use serde::Serialize;
+use tokio::sync::watch;
+use vise::{EncodeLabelSet, EncodeLabelValue};
+use zksync_dal::{pruning_dal::RetainedDataKind, ConnectionPool, Core, CoreDal};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_types::{
+    api::{self, GetLogsFilter},
+    Address, L1BatchNumber, L2BlockNumber, H256, U256,
+};
+use zksync_web3_decl::{
+    client::{DynClient, L2},
+    error::{ClientRpcContext, EnrichedClientError, EnrichedClientResult},
+    namespaces::EthNamespaceClient,
+    types::Filter,
+};
+
+use self::metrics::METRICS;
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+/// Maximum number of logs in an L2 block for which logs are compared. Blocks with more logs are only checked
+/// for header and transaction divergences, so that a check doesn't hit request limits on the peer.
+const MAX_LOGS_PER_L2_BLOCK: usize = 10_000;
+
+/// Log data compared by [`ResponseChecker`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogSnapshot {
+    pub address: Address,
+    pub topics: Vec<H256>,
+    pub data: Vec<u8>,
+    pub transaction_hash: Option<H256>,
+    pub log_index: Option<U256>,
+}
+
+impl From<api::Log> for LogSnapshot {
+    fn from(log: api::Log) -> Self {
+        Self {
+            address: log.address,
+            topics: log.topics,
+            data: log.data.0,
+            transaction_hash: log.transaction_hash,
+            log_index: log.log_index,
+        }
+    }
+}
+
+/// L2 block data compared by [`ResponseChecker`]. Only includes fields that are stable across node implementations
+/// and not affected by the node sync progress (e.g., the L1 batch number is not included since it's only known
+/// after the batch is sealed).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct L2BlockSnapshot {
+    pub hash: H256,
+    pub parent_hash: H256,
+    pub timestamp: u64,
+    pub transaction_hashes: Vec<H256>,
+    /// Logs emitted in the block. `None` if logs are not available (e.g., were removed by a data retention policy).
+    pub logs: Option<Vec<LogSnapshot>>,
+}
+
+impl L2BlockSnapshot {
+    fn new<Tx>(
+        block: api::Block<Tx>,
+        transaction_hashes: Vec<H256>,
+        logs: Option<Vec<api::Log>>,
+    ) -> Self {
+        Self {
+            hash: block.hash,
+            parent_hash: block.parent_hash,
+            timestamp: block.timestamp.as_u64(),
+            transaction_hashes,
+            logs: logs.map(|logs| logs.into_iter().map(LogSnapshot::from).collect()),
+        }
+    }
+}
+
+/// Peer providing reference responses for [`ResponseChecker`].
+#[async_trait]
+pub trait ResponseCheckerPeer: 'static + Send + Sync + fmt::Debug {
+    /// Fetches an L2 block with the specified number. If `with_logs` is set, the block logs must be fetched as well.
+    async fn fetch_l2_block(
+        &self,
+        number: L2BlockNumber,
+        with_logs: bool,
+    ) -> EnrichedClientResult<Option<L2BlockSnapshot>>;
+}
+
+#[async_trait]
+impl ResponseCheckerPeer for Box<DynClient<L2>> {
+    async fn fetch_l2_block(
+        &self,
+        number: L2BlockNumber,
+        with_logs: bool,
+    ) -> EnrichedClientResult<Option<L2BlockSnapshot>> {
+        let block_number = api::BlockNumber::Number(number.0.into());
+        let Some(block) = self
+            .get_block_by_number(block_number, false)
+            .rpc_context("get_block_by_number")
+            .with_arg("number", &number)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let transaction_hashes = block
+            .transactions
+            .iter()
+            .map(|tx| match tx {
+                api::TransactionVariant::Hash(hash) => Ok(*hash),
+                api::TransactionVariant::Full(_) => Err(EnrichedClientError::custom(
+                    "peer returned full transactions instead of hashes",
+                    "get_block_by_number",
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let logs = if with_logs {
+            let filter = Filter {
+                from_block: Some(block_number),
+                to_block: Some(block_number),
+                ..Filter::default()
+            };
+            let logs = self
+                .get_logs(filter)
+                .rpc_context("get_logs")
+                .with_arg("number", &number)
+                .await?;
+            Some(logs)
+        } else {
+            None
+        };
+        Ok(Some(L2BlockSnapshot::new(block, transaction_hashes, logs)))
+    }
+}
+
+/// Kind of divergence detected by [`ResponseChecker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DivergenceKind {
+    Hash,
+    ParentHash,
+    Timestamp,
+    Transactions,
+    Logs,
+}
+
+/// Divergence between the local node and the peer detected by [`ResponseChecker`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResponseDivergence {
+    pub l2_block: L2BlockNumber,
+    pub kind: DivergenceKind,
+    pub local: String,
+    pub remote: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ResponseCheckerHealthDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_checked_l2_block: Option<L2BlockNumber>,
+    checked_l2_blocks: u64,
+    divergences: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_divergence: Option<ResponseDivergence>,
+}
+
+impl From<&ResponseCheckerHealthDetails> for Health {
+    fn from(details: &ResponseCheckerHealthDetails) -> Self {
+        let status = if details.divergences > 0 {
+            HealthStatus::Affected
+        } else {
+            HealthStatus::Ready
+        };
+        Self::from(status).with_details(details)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum StepOutcome {
+    /// No L2 blocks in the local storage yet.
+    NoBlocks,
+    /// The selected L2 block was pruned or is not available on the peer yet.
+    Skipped(L2BlockNumber),
+    Checked(L2BlockNumber, Vec<ResponseDivergence>),
+}
+
+/// Component that periodically spot-checks the data served by the node's API against a full archive peer
+/// (e.g., the main node) and reports divergences via health checks and metrics.
+///
+/// The checker walks the range of L2 blocks retained by the node with a configurable stride, wrapping around
+/// once it reaches the latest sealed L2 block. This makes it suitable to continuously monitor correctness of pruned
+/// nodes, for which the retained range moves over time. Unlike the reorg detector, the checker never halts the node.
+#[derive(Debug)]
+pub struct ResponseChecker {
+    peer: Box<dyn ResponseCheckerPeer>,
+    pool: ConnectionPool<Core>,
+    health_updater: HealthUpdater,
+    health_details: ResponseCheckerHealthDetails,
+    poll_interval: Duration,
+    stride: u32,
+    next_l2_block: Option<L2BlockNumber>,
+}
+
+impl ResponseChecker {
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+    const DEFAULT_STRIDE: u32 = 100;
+
+    pub fn new(peer: Box<dyn ResponseCheckerPeer>, pool: ConnectionPool<Core>) -> Self {
+        Self {
+            peer,
+            pool,
+            health_updater: ReactiveHealthCheck::new("response_checker").1,
+            health_details: ResponseCheckerHealthDetails::default(),
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+            stride: Self::DEFAULT_STRIDE,
+            next_l2_block: None,
+        }
+    }
+
+    /// Sets the interval between consecutive checks.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets the distance between consecutively checked L2 blocks.
+    pub fn with_stride(mut self, stride: u32) -> Self {
+        self.stride = stride.max(1);
+        self
+    }
+
+    /// Returns a health check for this checker.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    /// Selects the next L2 block to check, and returns it together with the last L1 batch with pruned events (if any).
+    async fn select_l2_block(
+        &mut self,
+    ) -> anyhow::Result<Option<(L2BlockNumber, Option<L1BatchNumber>)>> {
+        let mut storage = self.pool.connection_tagged("response_checker").await?;
+        let Some(sealed_l2_block) = storage.blocks_dal().get_sealed_l2_block_number().await? else {
+            return Ok(None);
+        };
+        let Some(earliest_l2_block) = storage.blocks_dal().get_earliest_l2_block_number().await?
+        else {
+            return Ok(None);
+        };
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        let first_retained_l2_block = pruning_info
+            .last_soft_pruned
+            .map_or(earliest_l2_block, |info| info.l2_block + 1)
+            .max(earliest_l2_block);
+        let last_pruned_events_l1_batch = storage
+            .pruning_dal()
+            .get_last_retention_pruned_l1_batch(RetainedDataKind::Events)
+            .await?;
+        drop(storage);
+
+        let candidate = self.next_l2_block.unwrap_or(first_retained_l2_block);
+        let l2_block = if candidate < first_retained_l2_block || candidate > sealed_l2_block {
+            first_retained_l2_block
+        } else {
+            candidate
+        };
+        if l2_block > sealed_l2_block {
+            return Ok(None);
+        }
+        self.next_l2_block = Some(l2_block + self.stride);
+        Ok(Some((l2_block, last_pruned_events_l1_batch)))
+    }
+
+    async fn load_local_l2_block(
+        &self,
+        number: L2BlockNumber,
+        last_pruned_events_l1_batch: Option<L1BatchNumber>,
+    ) -> anyhow::Result<Option<L2BlockSnapshot>> {
+        let mut storage = self.pool.connection_tagged("response_checker").await?;
+        let Some(block) = storage.blocks_web3_dal().get_api_block(number).await? else {
+            return Ok(None);
+        };
+
+        let events_pruned = match (block.l1_batch_number, last_pruned_events_l1_batch) {
+            (Some(l1_batch), Some(last_pruned)) => l1_batch.as_u32() <= last_pruned.0,
+            _ => false,
+        };
+        let logs = if events_pruned {
+            None
+        } else {
+            let filter = GetLogsFilter {
+                from_block: number,
+                to_block: number,
+                addresses: vec![],
+                topics: vec![],
+                l2_blocks: None,
+            };
+            let logs = storage
+                .events_web3_dal()
+                .get_logs(filter, MAX_LOGS_PER_L2_BLOCK + 1)
+                .await?;
+            (logs.len() <= MAX_LOGS_PER_L2_BLOCK).then_some(logs)
+        };
+
+        let transaction_hashes = block.transactions.clone();
+        Ok(Some(L2BlockSnapshot::new(block, transaction_hashes, logs)))
+    }
+
+    fn compare(
+        number: L2BlockNumber,
+        local: &L2BlockSnapshot,
+        remote: &L2BlockSnapshot,
+    ) -> Vec<ResponseDivergence> {
+        let mut divergences = vec![];
+        let mut check = |kind, local: &dyn fmt::Debug, remote: &dyn fmt::Debug, matches| {
+            if !matches {
+                divergences.push(ResponseDivergence {
+                    l2_block: number,
+                    kind,
+                    local: format!("{local:?}"),
+                    remote: format!("{remote:?}"),
+                });
+            }
+        };
+
+        check(
+            DivergenceKind::Hash,
+            &local.hash,
+            &remote.hash,
+            local.hash == remote.hash,
+        );
+        check(
+            DivergenceKind::ParentHash,
+            &local.parent_hash,
+            &remote.parent_hash,
+            local.parent_hash == remote.parent_hash,
+        );
+        check(
+            DivergenceKind::Timestamp,
+            &local.timestamp,
+            &remote.timestamp,
+            local.timestamp == remote.timestamp,
+        );
+        check(
+            DivergenceKind::Transactions,
+            &local.transaction_hashes,
+            &remote.transaction_hashes,
+            local.transaction_hashes == remote.transaction_hashes,
+        );
+        if let (Some(local_logs), Some(remote_logs)) = (&local.logs, &remote.logs) {
+            check(
+                DivergenceKind::Logs,
+                local_logs,
+                remote_logs,
+                local_logs == remote_logs,
+            );
+        }
+        divergences
+    }
+
+    async fn step(&mut self) -> anyhow::Result<StepOutcome> {
+        let Some((number, last_pruned_events_l1_batch)) = self.select_l2_block().await? else {
+            return Ok(StepOutcome::NoBlocks);
+        };
+        let Some(local) = self
+            .load_local_l2_block(number, last_pruned_events_l1_batch)
+            .await?
+        else {
+            tracing::debug!("L2 block #{number} was pruned before it could be checked");
+            return Ok(StepOutcome::Skipped(number));
+        };
+
+        let with_logs = local.logs.is_some();
+        let Some(remote) = self.peer.fetch_l2_block(number, with_logs).await? else {
+            tracing::debug!("L2 block #{number} is not available on the peer yet");
+            return Ok(StepOutcome::Skipped(number));
+        };
+        Ok(StepOutcome::Checked(
+            number,
+            Self::compare(number, &local, &remote),
+        ))
+    }
+
+    fn update_state(&mut self, outcome: StepOutcome) {
+        let StepOutcome::Checked(number, divergences) = outcome else {
+            return;
+        };
+
+        METRICS.checked_l2_blocks.inc();
+        METRICS.last_checked_l2_block.set(number.0.into());
+        self.health_details.last_checked_l2_block = Some(number);
+        self.health_details.checked_l2_blocks += 1;
+        for divergence in divergences {
+            tracing::error!(
+                "Detected {:?} divergence for L2 block #{number}: local = {}, remote = {}",
+                divergence.kind,
+                divergence.local,
+                divergence.remote
+            );
+            METRICS.divergences[&divergence.kind].inc();
+            self.health_details.divergences += 1;
+            self.health_details.last_divergence = Some(divergence);
+        }
+        self.health_updater
+            .update(Health::from(&self.health_details));
+    }
+
+    /// Runs this checker until a stop signal is received. Errors (e.g., network errors when querying the peer)
+    /// are logged and retried after a delay.
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        self.health_updater
+            .update(Health::from(&self.health_details));
+
+        while !*stop_receiver.borrow_and_update() {
+            match self.step().await {
+                Ok(outcome) => self.update_state(outcome),
+                Err(err) => {
+                    tracing::warn!("Error checking responses, will retry after a delay: {err:?}");
+                    METRICS.errors.inc();
+                }
+            }
+
+            if tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received; response checker is shutting down");
+        Ok(())
+    }
+}
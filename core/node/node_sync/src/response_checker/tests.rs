@@ -0,0 +1,194 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use assert_matches::assert_matches;
+use zksync_dal::Connection;
+use zksync_health_check::CheckHealth;
+use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+use zksync_node_test_utils::{create_l1_batch, create_l2_block};
+
+use super::*;
+
+#[derive(Debug, Clone, Default)]
+struct MockPeer {
+    blocks: Arc<Mutex<HashMap<L2BlockNumber, L2BlockSnapshot>>>,
+}
+
+impl MockPeer {
+    fn insert(&self, number: L2BlockNumber, block: L2BlockSnapshot) {
+        self.blocks.lock().unwrap().insert(number, block);
+    }
+}
+
+#[async_trait]
+impl ResponseCheckerPeer for MockPeer {
+    async fn fetch_l2_block(
+        &self,
+        number: L2BlockNumber,
+        with_logs: bool,
+    ) -> EnrichedClientResult<Option<L2BlockSnapshot>> {
+        let block = self.blocks.lock().unwrap().get(&number).cloned();
+        Ok(block.map(|mut block| {
+            if !with_logs {
+                block.logs = None;
+            }
+            block
+        }))
+    }
+}
+
+async fn seal_l1_batch(storage: &mut Connection<'_, Core>, number: u32) {
+    storage
+        .blocks_dal()
+        .insert_l2_block(&create_l2_block(number))
+        .await
+        .unwrap();
+    let l1_batch = create_l1_batch(number);
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&l1_batch)
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .mark_l2_blocks_as_executed_in_l1_batch(l1_batch.number)
+        .await
+        .unwrap();
+}
+
+async fn prepare_storage(pool: &ConnectionPool<Core>, l1_batch_count: u32) {
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    for number in 1..=l1_batch_count {
+        seal_l1_batch(&mut storage, number).await;
+    }
+}
+
+/// Creates a peer mirroring the local storage.
+async fn mirror_storage(checker: &ResponseChecker, peer: &MockPeer, l2_block_count: u32) {
+    for number in 0..=l2_block_count {
+        let number = L2BlockNumber(number);
+        let block = checker
+            .load_local_l2_block(number, None)
+            .await
+            .unwrap()
+            .unwrap();
+        peer.insert(number, block);
+    }
+}
+
+fn mock_log() -> LogSnapshot {
+    LogSnapshot {
+        address: Address::repeat_byte(1),
+        topics: vec![H256::repeat_byte(2)],
+        data: vec![3; 32],
+        transaction_hash: Some(H256::repeat_byte(4)),
+        log_index: Some(0.into()),
+    }
+}
+
+#[tokio::test]
+async fn checker_detects_divergences() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&pool, 3).await;
+    let peer = MockPeer::default();
+    let mut checker = ResponseChecker::new(Box::new(peer.clone()), pool.clone()).with_stride(1);
+    mirror_storage(&checker, &peer, 3).await;
+
+    let mut diverged_block = peer.blocks.lock().unwrap()[&L2BlockNumber(2)].clone();
+    diverged_block.hash = H256::repeat_byte(0xff);
+    diverged_block.logs = Some(vec![mock_log()]);
+    peer.insert(L2BlockNumber(2), diverged_block);
+
+    let health_check = checker.health_check();
+    for number in 0..=1 {
+        let outcome = checker.step().await.unwrap();
+        assert_eq!(outcome, StepOutcome::Checked(L2BlockNumber(number), vec![]));
+        checker.update_state(outcome);
+    }
+    assert_matches!(
+        health_check.check_health().await.status(),
+        HealthStatus::Ready
+    );
+
+    let outcome = checker.step().await.unwrap();
+    let StepOutcome::Checked(number, divergences) = &outcome else {
+        panic!("Unexpected outcome: {outcome:?}");
+    };
+    assert_eq!(*number, L2BlockNumber(2));
+    let divergence_kinds: Vec<_> = divergences.iter().map(|div| div.kind).collect();
+    assert_eq!(
+        divergence_kinds,
+        [DivergenceKind::Hash, DivergenceKind::Logs]
+    );
+    checker.update_state(outcome);
+
+    let health = health_check.check_health().await;
+    assert_matches!(health.status(), HealthStatus::Affected);
+    let details = health.details().unwrap();
+    assert_eq!(details["divergences"], 2);
+    assert_eq!(details["last_checked_l2_block"], 2);
+    assert_eq!(details["last_divergence"]["kind"], "logs");
+}
+
+#[tokio::test]
+async fn checker_wraps_around_retained_range() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&pool, 5).await;
+    let peer = MockPeer::default();
+    let mut checker = ResponseChecker::new(Box::new(peer.clone()), pool.clone()).with_stride(2);
+    mirror_storage(&checker, &peer, 5).await;
+
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .pruning_dal()
+        .insert_soft_pruning_log(L1BatchNumber(1), L2BlockNumber(1))
+        .await
+        .unwrap();
+    drop(storage);
+
+    let mut checked_blocks = vec![];
+    for _ in 0..4 {
+        let outcome = checker.step().await.unwrap();
+        let StepOutcome::Checked(number, divergences) = outcome else {
+            panic!("Unexpected outcome: {outcome:?}");
+        };
+        assert!(divergences.is_empty(), "{divergences:?}");
+        checked_blocks.push(number.0);
+    }
+    assert_eq!(checked_blocks, [2, 4, 2, 4]);
+}
+
+#[tokio::test]
+async fn checker_skips_pruned_events_and_missing_peer_blocks() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&pool, 2).await;
+    let peer = MockPeer::default();
+    let mut checker = ResponseChecker::new(Box::new(peer.clone()), pool.clone()).with_stride(1);
+    mirror_storage(&checker, &peer, 1).await;
+
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .pruning_dal()
+        .insert_retention_log(RetainedDataKind::Events, L1BatchNumber(1))
+        .await
+        .unwrap();
+    drop(storage);
+
+    // Logs for the genesis and 1st L1 batches were removed locally, so they should not be compared.
+    let mut block = peer.blocks.lock().unwrap()[&L2BlockNumber(1)].clone();
+    block.logs = Some(vec![mock_log()]);
+    peer.insert(L2BlockNumber(1), block);
+
+    for number in 0..=1 {
+        let outcome = checker.step().await.unwrap();
+        assert_eq!(outcome, StepOutcome::Checked(L2BlockNumber(number), vec![]));
+    }
+    // L2 block #2 is not available on the peer.
+    let outcome = checker.step().await.unwrap();
+    assert_eq!(outcome, StepOutcome::Skipped(L2BlockNumber(2)));
+}
@@ -0,0 +1,117 @@
+use axum::{extract::Path, Json};
+use zksync_dal::{
+    proof_generation_dal::{ProofGenerationAdminAction, ProofGenerationJobStatus},
+    ConnectionPool, Core, CoreDal,
+};
+use zksync_prover_interface::api::{
+    InvalidateProofRequest, ProofAdminActionResponse, RequeueBatchRequest, SkipBatchRequest,
+};
+use zksync_types::L1BatchNumber;
+
+use crate::errors::RequestProcessorError;
+
+/// Processor for the admin API, allowing operators to manage proof generation jobs without manual SQL.
+#[derive(Clone)]
+pub(crate) struct AdminRequestProcessor {
+    pool: ConnectionPool<Core>,
+}
+
+impl AdminRequestProcessor {
+    pub(crate) fn new(pool: ConnectionPool<Core>) -> Self {
+        Self { pool }
+    }
+
+    pub(crate) async fn requeue_batch(
+        &self,
+        Path(l1_batch_number): Path<u32>,
+        Json(request): Json<RequeueBatchRequest>,
+    ) -> Result<Json<ProofAdminActionResponse>, RequestProcessorError> {
+        self.apply_action(
+            L1BatchNumber(l1_batch_number),
+            ProofGenerationAdminAction::Requeue,
+            request.reason.as_deref(),
+        )
+        .await
+    }
+
+    pub(crate) async fn skip_batch(
+        &self,
+        Path(l1_batch_number): Path<u32>,
+        Json(request): Json<SkipBatchRequest>,
+    ) -> Result<Json<ProofAdminActionResponse>, RequestProcessorError> {
+        self.apply_action(
+            L1BatchNumber(l1_batch_number),
+            ProofGenerationAdminAction::Skip,
+            Some(&request.reason),
+        )
+        .await
+    }
+
+    pub(crate) async fn invalidate_proof(
+        &self,
+        Path(l1_batch_number): Path<u32>,
+        Json(request): Json<InvalidateProofRequest>,
+    ) -> Result<Json<ProofAdminActionResponse>, RequestProcessorError> {
+        self.apply_action(
+            L1BatchNumber(l1_batch_number),
+            ProofGenerationAdminAction::InvalidateProof,
+            Some(&request.reason),
+        )
+        .await
+    }
+
+    async fn apply_action(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        action: ProofGenerationAdminAction,
+        reason: Option<&str>,
+    ) -> Result<Json<ProofAdminActionResponse>, RequestProcessorError> {
+        let reason = reason.map(str::trim);
+        if action != ProofGenerationAdminAction::Requeue && matches!(reason, None | Some("")) {
+            return Err(RequestProcessorError::BadRequest(format!(
+                "reason must be specified for `{action}`"
+            )));
+        }
+
+        let mut connection = self.pool.connection_tagged("proof_data_handler").await?;
+        let mut transaction = connection.start_transaction().await?;
+        let status = transaction
+            .proof_generation_dal()
+            .get_status_for_update(l1_batch_number)
+            .await?
+            .ok_or_else(|| {
+                RequestProcessorError::NotFound(format!(
+                    "L1 batch #{l1_batch_number} has no proof generation job"
+                ))
+            })?;
+        if !action.allowed_statuses().contains(&status) {
+            return Err(RequestProcessorError::Conflict(format!(
+                "cannot `{action}` proof generation job for L1 batch #{l1_batch_number} with status `{status}`"
+            )));
+        }
+        if status == ProofGenerationJobStatus::Generated {
+            // Once a proof is sent to L1, it cannot be replaced.
+            let last_l1_batch_with_prove_tx = transaction
+                .blocks_dal()
+                .get_last_l1_batch_with_prove_tx()
+                .await?;
+            if l1_batch_number <= last_l1_batch_with_prove_tx {
+                return Err(RequestProcessorError::Conflict(format!(
+                    "proof for L1 batch #{l1_batch_number} is already sent to L1"
+                )));
+            }
+        }
+
+        transaction
+            .proof_generation_dal()
+            .apply_admin_action(l1_batch_number, action, status, reason)
+            .await?;
+        transaction.commit().await?;
+
+        tracing::info!(
+            "Operator applied `{action}` to proof generation job for L1 batch #{l1_batch_number} \
+             (previous status: `{status}`, reason: {reason:?})"
+        );
+        Ok(Json(ProofAdminActionResponse::Success))
+    }
+}
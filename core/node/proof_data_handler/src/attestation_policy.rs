@@ -0,0 +1,324 @@
+//! Policy for attestation quotes registered by TEE provers.
+
+use std::collections::HashSet;
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use zksync_config::configs::TeeConfig;
+use zksync_types::tee_types::TeeType;
+
+/// Size of the quote header shared by SGX and TDX quotes.
+const QUOTE_HEADER_LEN: usize = 48;
+/// `tee_type` value in the quote header for SGX quotes.
+const SGX_TEE_TYPE: u32 = 0x00;
+/// `tee_type` value in the quote header for TDX quotes.
+const TDX_TEE_TYPE: u32 = 0x81;
+
+/// Size of the SGX enclave report body.
+const SGX_REPORT_BODY_LEN: usize = 384;
+/// `DEBUG` flag in SGX enclave attributes.
+const SGX_ATTRIBUTES_DEBUG: u64 = 0x02;
+
+/// Size of the TDX trust domain report body.
+const TDX_REPORT_BODY_LEN: usize = 584;
+/// `DEBUG` flag in TDX trust domain attributes.
+const TDX_ATTRIBUTES_DEBUG: u64 = 0x01;
+
+/// Claims extracted from an attestation quote. Measurements and report data are hex-encoded.
+/// Claims are only recorded after they've been accepted by [`AttestationPolicy`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "tee_type", rename_all = "lowercase")]
+pub(crate) enum AttestationClaims {
+    Sgx {
+        mrenclave: String,
+        mrsigner: String,
+        isv_prod_id: u16,
+        isv_svn: u16,
+        debug: bool,
+        report_data: String,
+    },
+    Tdx {
+        mrtd: String,
+        mrseam: String,
+        rtmrs: Vec<String>,
+        debug: bool,
+        report_data: String,
+    },
+}
+
+impl AttestationClaims {
+    /// Parses claims from a raw DCAP quote (SGX quote v3 / v4, or TDX quote v4). The quote signature
+    /// and its collateral are **not** verified by this method.
+    pub(crate) fn parse(quote: &[u8]) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            quote.len() >= QUOTE_HEADER_LEN,
+            "quote is too short: {} bytes",
+            quote.len()
+        );
+        let version = u16::from_le_bytes([quote[0], quote[1]]);
+        let tee_type = read_u32(quote, 4);
+        let body = &quote[QUOTE_HEADER_LEN..];
+
+        match (version, tee_type) {
+            (3 | 4, SGX_TEE_TYPE) => {
+                anyhow::ensure!(
+                    body.len() >= SGX_REPORT_BODY_LEN,
+                    "SGX quote is too short: {} bytes",
+                    quote.len()
+                );
+                let attributes = read_u64(body, 48);
+                Ok(Self::Sgx {
+                    mrenclave: hex::encode(&body[64..96]),
+                    mrsigner: hex::encode(&body[128..160]),
+                    isv_prod_id: u16::from_le_bytes([body[256], body[257]]),
+                    isv_svn: u16::from_le_bytes([body[258], body[259]]),
+                    debug: attributes & SGX_ATTRIBUTES_DEBUG != 0,
+                    report_data: hex::encode(&body[320..384]),
+                })
+            }
+            (4, TDX_TEE_TYPE) => {
+                anyhow::ensure!(
+                    body.len() >= TDX_REPORT_BODY_LEN,
+                    "TDX quote is too short: {} bytes",
+                    quote.len()
+                );
+                let td_attributes = read_u64(body, 120);
+                Ok(Self::Tdx {
+                    mrtd: hex::encode(&body[136..184]),
+                    mrseam: hex::encode(&body[16..64]),
+                    rtmrs: body[328..520].chunks(48).map(hex::encode).collect(),
+                    debug: td_attributes & TDX_ATTRIBUTES_DEBUG != 0,
+                    report_data: hex::encode(&body[520..584]),
+                })
+            }
+            _ => anyhow::bail!("unsupported quote version {version} / TEE type {tee_type:#x}"),
+        }
+    }
+
+    pub(crate) fn tee_type(&self) -> TeeType {
+        match self {
+            Self::Sgx { .. } => TeeType::Sgx,
+            Self::Tdx { .. } => TeeType::Tdx,
+        }
+    }
+
+    fn is_debug(&self) -> bool {
+        match self {
+            Self::Sgx { debug, .. } | Self::Tdx { debug, .. } => *debug,
+        }
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+/// Policy validating measurements in attestation quotes against per-TEE-type allowlists.
+///
+/// An SGX quote is accepted if either its MRENCLAVE or its MRSIGNER is allowlisted; a TDX quote
+/// is accepted if its MRTD is allowlisted. Quotes produced by debug-mode enclaves / trust domains
+/// are always rejected.
+#[derive(Debug, Clone)]
+pub(crate) struct AttestationPolicy {
+    sgx_mrenclaves: HashSet<String>,
+    sgx_mrsigners: HashSet<String>,
+    tdx_mrtds: HashSet<String>,
+}
+
+impl AttestationPolicy {
+    /// Creates a policy from the config. Returns `None` if the policy is disabled.
+    pub(crate) fn from_config(config: &TeeConfig) -> anyhow::Result<Option<Self>> {
+        if !config.attestation_policy_enabled {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            sgx_mrenclaves: parse_allowlist(&config.sgx_mrenclave_allowlist, 32)
+                .context("sgx_mrenclave_allowlist")?,
+            sgx_mrsigners: parse_allowlist(&config.sgx_mrsigner_allowlist, 32)
+                .context("sgx_mrsigner_allowlist")?,
+            tdx_mrtds: parse_allowlist(&config.tdx_mrtd_allowlist, 48)
+                .context("tdx_mrtd_allowlist")?,
+        }))
+    }
+
+    /// Checks the claims against this policy, returning a human-readable rejection reason on failure.
+    pub(crate) fn validate(&self, claims: &AttestationClaims) -> Result<(), String> {
+        if claims.is_debug() {
+            return Err(format!(
+                "{} quote is produced by a debug-mode enclave",
+                claims.tee_type()
+            ));
+        }
+
+        match claims {
+            AttestationClaims::Sgx {
+                mrenclave,
+                mrsigner,
+                ..
+            } => {
+                if self.sgx_mrenclaves.contains(mrenclave) || self.sgx_mrsigners.contains(mrsigner)
+                {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "neither MRENCLAVE {mrenclave} nor MRSIGNER {mrsigner} are allowlisted"
+                    ))
+                }
+            }
+            AttestationClaims::Tdx { mrtd, .. } => {
+                if self.tdx_mrtds.contains(mrtd) {
+                    Ok(())
+                } else {
+                    Err(format!("MRTD {mrtd} is not allowlisted"))
+                }
+            }
+        }
+    }
+}
+
+fn parse_allowlist(entries: &[String], expected_len: usize) -> anyhow::Result<HashSet<String>> {
+    entries
+        .iter()
+        .map(|entry| {
+            let entry = entry.strip_prefix("0x").unwrap_or(entry);
+            let bytes = hex::decode(entry).with_context(|| format!("invalid hex: {entry}"))?;
+            anyhow::ensure!(
+                bytes.len() == expected_len,
+                "measurement {entry} has unexpected length {}; expected {expected_len} bytes",
+                bytes.len()
+            );
+            Ok(hex::encode(bytes))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod testonly {
+    use super::*;
+
+    pub(crate) fn mock_sgx_quote(mrenclave: [u8; 32], mrsigner: [u8; 32], debug: bool) -> Vec<u8> {
+        let mut quote = vec![0_u8; QUOTE_HEADER_LEN + SGX_REPORT_BODY_LEN];
+        quote[0] = 3;
+        let body = &mut quote[QUOTE_HEADER_LEN..];
+        if debug {
+            body[48] = SGX_ATTRIBUTES_DEBUG as u8;
+        }
+        body[64..96].copy_from_slice(&mrenclave);
+        body[128..160].copy_from_slice(&mrsigner);
+        body[320..352].copy_from_slice(&[0xee; 32]);
+        quote
+    }
+
+    pub(crate) fn mock_tdx_quote(mrtd: [u8; 48], debug: bool) -> Vec<u8> {
+        let mut quote = vec![0_u8; QUOTE_HEADER_LEN + TDX_REPORT_BODY_LEN];
+        quote[0] = 4;
+        quote[4] = TDX_TEE_TYPE as u8;
+        let body = &mut quote[QUOTE_HEADER_LEN..];
+        if debug {
+            body[120] = TDX_ATTRIBUTES_DEBUG as u8;
+        }
+        body[136..184].copy_from_slice(&mrtd);
+        quote
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{testonly::*, *};
+
+    fn test_policy() -> AttestationPolicy {
+        let config = TeeConfig {
+            attestation_policy_enabled: true,
+            sgx_mrenclave_allowlist: vec![hex::encode([1; 32])],
+            sgx_mrsigner_allowlist: vec![format!("0x{}", hex::encode([2; 32]))],
+            tdx_mrtd_allowlist: vec![hex::encode([3; 48])],
+            ..TeeConfig::default()
+        };
+        AttestationPolicy::from_config(&config).unwrap().unwrap()
+    }
+
+    #[test]
+    fn parsing_sgx_quote() {
+        let quote = mock_sgx_quote([1; 32], [2; 32], false);
+        let claims = AttestationClaims::parse(&quote).unwrap();
+        assert_eq!(claims.tee_type(), TeeType::Sgx);
+        let AttestationClaims::Sgx {
+            mrenclave,
+            mrsigner,
+            debug,
+            report_data,
+            ..
+        } = &claims
+        else {
+            panic!("unexpected claims: {claims:?}");
+        };
+        assert_eq!(*mrenclave, hex::encode([1; 32]));
+        assert_eq!(*mrsigner, hex::encode([2; 32]));
+        assert!(!debug);
+        assert!(report_data.starts_with(&"ee".repeat(32)), "{report_data}");
+
+        let json = serde_json::to_value(&claims).unwrap();
+        assert_eq!(json["tee_type"], "sgx");
+        assert_eq!(json["mrenclave"], hex::encode([1; 32]));
+
+        let err = AttestationClaims::parse(&quote[..100]).unwrap_err();
+        assert!(err.to_string().contains("too short"), "{err:#}");
+    }
+
+    #[test]
+    fn validating_sgx_claims() {
+        let policy = test_policy();
+        let allowed_by_mrenclave = mock_sgx_quote([1; 32], [0; 32], false);
+        let allowed_by_mrsigner = mock_sgx_quote([0; 32], [2; 32], false);
+        for quote in [allowed_by_mrenclave, allowed_by_mrsigner] {
+            let claims = AttestationClaims::parse(&quote).unwrap();
+            policy.validate(&claims).unwrap();
+        }
+
+        let unknown = mock_sgx_quote([0; 32], [0; 32], false);
+        let claims = AttestationClaims::parse(&unknown).unwrap();
+        let err = policy.validate(&claims).unwrap_err();
+        assert!(err.contains("allowlisted"), "{err}");
+
+        let debug = mock_sgx_quote([1; 32], [2; 32], true);
+        let claims = AttestationClaims::parse(&debug).unwrap();
+        let err = policy.validate(&claims).unwrap_err();
+        assert!(err.contains("debug-mode"), "{err}");
+    }
+
+    #[test]
+    fn validating_tdx_claims() {
+        let policy = test_policy();
+        let quote = mock_tdx_quote([3; 48], false);
+        let claims = AttestationClaims::parse(&quote).unwrap();
+        assert_eq!(claims.tee_type(), TeeType::Tdx);
+        policy.validate(&claims).unwrap();
+
+        let quote = mock_tdx_quote([4; 48], false);
+        let claims = AttestationClaims::parse(&quote).unwrap();
+        policy.validate(&claims).unwrap_err();
+
+        let quote = mock_tdx_quote([3; 48], true);
+        let claims = AttestationClaims::parse(&quote).unwrap();
+        let err = policy.validate(&claims).unwrap_err();
+        assert!(err.contains("debug-mode"), "{err}");
+    }
+
+    #[test]
+    fn invalid_allowlist_entries() {
+        let config = TeeConfig {
+            attestation_policy_enabled: true,
+            sgx_mrenclave_allowlist: vec![hex::encode([1; 16])],
+            ..TeeConfig::default()
+        };
+        let err = AttestationPolicy::from_config(&config).unwrap_err();
+        assert!(format!("{err:#}").contains("unexpected length"), "{err:#}");
+
+        let config = TeeConfig::default();
+        assert!(AttestationPolicy::from_config(&config).unwrap().is_none());
+    }
+}
@@ -9,6 +9,10 @@ pub(crate) enum RequestProcessorError {
     GeneralError(String),
     ObjectStore(ObjectStoreError),
     Dal(DalError),
+    BadRequest(String),
+    Forbidden(String),
+    NotFound(String),
+    Conflict(String),
 }
 
 impl From<DalError> for RequestProcessorError {
@@ -41,6 +45,10 @@ impl IntoResponse for RequestProcessorError {
                     "Failed fetching/saving from db".to_owned(),
                 )
             }
+            Self::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+            Self::Forbidden(message) => (StatusCode::FORBIDDEN, message),
+            Self::NotFound(message) => (StatusCode::NOT_FOUND, message),
+            Self::Conflict(message) => (StatusCode::CONFLICT, message),
         };
         (status_code, message).into_response()
     }
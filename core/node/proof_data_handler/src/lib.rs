@@ -1,25 +1,38 @@
 use std::{net::SocketAddr, sync::Arc};
 
+use admin_request_processor::AdminRequestProcessor;
 use anyhow::Context as _;
-use axum::{extract::Path, http::StatusCode, response::IntoResponse, routing::post, Json, Router};
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
 use request_processor::RequestProcessor;
+use secrecy::ExposeSecret;
 use tee_request_processor::TeeRequestProcessor;
 use tokio::sync::watch;
+use tower_http::validate_request::ValidateRequestHeaderLayer;
 use zksync_config::configs::ProofDataHandlerConfig;
 use zksync_dal::{ConnectionPool, Core};
 use zksync_object_store::ObjectStore;
 use zksync_prover_interface::api::{
-    ProofGenerationDataRequest, RegisterTeeAttestationRequest, SubmitProofRequest,
+    InvalidateProofRequest, ProofGenerationDataRequest, RegisterTeeAttestationRequest,
+    RequeueBatchRequest, SkipBatchRequest, SubmitProofRequest, SubmitProvingStatsRequest,
     SubmitTeeProofRequest, TeeProofGenerationDataRequest,
 };
-use zksync_types::{commitment::L1BatchCommitmentMode, L2ChainId};
+use zksync_types::{commitment::L1BatchCommitmentMode, secrets::APIKey, K256PrivateKey, L2ChainId};
 
 #[cfg(test)]
 mod tests;
 
+mod admin_request_processor;
+mod attestation_policy;
 mod errors;
 mod metrics;
 mod request_processor;
+mod sampling_policy;
 mod tee_request_processor;
 
 pub async fn run_server(
@@ -28,6 +41,8 @@ pub async fn run_server(
     connection_pool: ConnectionPool<Core>,
     commitment_mode: L1BatchCommitmentMode,
     l2_chain_id: L2ChainId,
+    admin_auth_token: Option<APIKey>,
+    tee_auxiliary_data_signer: Option<K256PrivateKey>,
     mut stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<()> {
     let bind_address = SocketAddr::from(([0, 0, 0, 0], config.http_port));
@@ -38,7 +53,9 @@ pub async fn run_server(
         config,
         commitment_mode,
         l2_chain_id,
-    );
+        admin_auth_token,
+        tee_auxiliary_data_signer,
+    )?;
 
     let listener = tokio::net::TcpListener::bind(bind_address)
         .await
@@ -62,14 +79,18 @@ fn create_proof_processing_router(
     config: ProofDataHandlerConfig,
     commitment_mode: L1BatchCommitmentMode,
     l2_chain_id: L2ChainId,
-) -> Router {
+    admin_auth_token: Option<APIKey>,
+    tee_auxiliary_data_signer: Option<K256PrivateKey>,
+) -> anyhow::Result<Router> {
     let get_proof_gen_processor = RequestProcessor::new(
         blob_store.clone(),
         connection_pool.clone(),
         config.clone(),
         commitment_mode,
-    );
+    )?;
     let submit_proof_processor = get_proof_gen_processor.clone();
+    let proving_stats_processor = get_proof_gen_processor.clone();
+    let sampling_decision_processor = get_proof_gen_processor.clone();
     let mut router = Router::new()
         .route(
             "/proof_generation_data",
@@ -92,11 +113,39 @@ fn create_proof_processing_router(
                         .await
                 },
             ),
+        )
+        .route(
+            "/proving_stats/:l1_batch_number",
+            post(
+                move |l1_batch_number: Path<u32>, payload: Json<SubmitProvingStatsRequest>| async move {
+                    proving_stats_processor
+                        .submit_proving_stats(l1_batch_number, payload)
+                        .await
+                },
+            ),
+        )
+        .route(
+            "/proof_sampling/:l1_batch_number",
+            get(move |l1_batch_number: Path<u32>| async move {
+                sampling_decision_processor
+                    .get_sampling_decision(l1_batch_number)
+                    .await
+            }),
         );
 
+    // Admin endpoints are only served if the auth token is configured.
+    if let Some(token) = admin_auth_token {
+        router = router.merge(create_admin_router(connection_pool.clone(), &token));
+    }
+
     if config.tee_config.tee_support {
-        let get_tee_proof_gen_processor =
-            TeeRequestProcessor::new(blob_store, connection_pool, config.clone(), l2_chain_id);
+        let get_tee_proof_gen_processor = TeeRequestProcessor::new(
+            blob_store,
+            connection_pool,
+            config.clone(),
+            l2_chain_id,
+            tee_auxiliary_data_signer,
+        )?;
         let submit_tee_proof_processor = get_tee_proof_gen_processor.clone();
         let register_tee_attestation_processor = get_tee_proof_gen_processor.clone();
 
@@ -138,7 +187,46 @@ fn create_proof_processing_router(
         );
     }
 
-    router
+    Ok(router
         .layer(tower_http::compression::CompressionLayer::new())
-        .layer(tower_http::decompression::RequestDecompressionLayer::new().zstd(true))
+        .layer(tower_http::decompression::RequestDecompressionLayer::new().zstd(true)))
+}
+
+fn create_admin_router(connection_pool: ConnectionPool<Core>, auth_token: &APIKey) -> Router {
+    let requeue_processor = AdminRequestProcessor::new(connection_pool);
+    let skip_processor = requeue_processor.clone();
+    let invalidate_processor = requeue_processor.clone();
+
+    Router::new()
+        .route(
+            "/admin/requeue_batch/:l1_batch_number",
+            post(
+                move |l1_batch_number: Path<u32>, payload: Json<RequeueBatchRequest>| async move {
+                    requeue_processor
+                        .requeue_batch(l1_batch_number, payload)
+                        .await
+                },
+            ),
+        )
+        .route(
+            "/admin/skip_batch/:l1_batch_number",
+            post(
+                move |l1_batch_number: Path<u32>, payload: Json<SkipBatchRequest>| async move {
+                    skip_processor.skip_batch(l1_batch_number, payload).await
+                },
+            ),
+        )
+        .route(
+            "/admin/invalidate_proof/:l1_batch_number",
+            post(
+                move |l1_batch_number: Path<u32>, payload: Json<InvalidateProofRequest>| async move {
+                    invalidate_processor
+                        .invalidate_proof(l1_batch_number, payload)
+                        .await
+                },
+            ),
+        )
+        .route_layer(ValidateRequestHeaderLayer::bearer(
+            auth_token.0.expose_secret(),
+        ))
 }
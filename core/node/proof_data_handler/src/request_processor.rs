@@ -1,13 +1,14 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use axum::{extract::Path, Json};
 use zksync_config::configs::ProofDataHandlerConfig;
-use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_dal::{proof_generation_dal::ProofSamplingDecision, ConnectionPool, Core, CoreDal};
 use zksync_object_store::ObjectStore;
 use zksync_prover_interface::{
     api::{
         ProofGenerationData, ProofGenerationDataRequest, ProofGenerationDataResponse,
-        SubmitProofRequest, SubmitProofResponse,
+        ProofSamplingDecisionResponse, ProofSamplingReason, SubmitProofRequest,
+        SubmitProofResponse, SubmitProvingStatsRequest, SubmitProvingStatsResponse,
     },
     inputs::{
         L1BatchMetadataHashes, VMRunWitnessInputData, WitnessInputData, WitnessInputMerklePaths,
@@ -22,7 +23,9 @@ use zksync_types::{
     L1BatchNumber, ProtocolVersionId, H256, STATE_DIFF_HASH_KEY_PRE_GATEWAY,
 };
 
-use crate::{errors::RequestProcessorError, metrics::METRICS};
+use crate::{
+    errors::RequestProcessorError, metrics::METRICS, sampling_policy::ProofSamplingPolicy,
+};
 
 #[derive(Clone)]
 pub(crate) struct RequestProcessor {
@@ -30,6 +33,7 @@ pub(crate) struct RequestProcessor {
     pool: ConnectionPool<Core>,
     config: ProofDataHandlerConfig,
     commitment_mode: L1BatchCommitmentMode,
+    sampling_policy: Option<ProofSamplingPolicy>,
 }
 
 impl RequestProcessor {
@@ -38,13 +42,15 @@ impl RequestProcessor {
         pool: ConnectionPool<Core>,
         config: ProofDataHandlerConfig,
         commitment_mode: L1BatchCommitmentMode,
-    ) -> Self {
-        Self {
+    ) -> anyhow::Result<Self> {
+        let sampling_policy = ProofSamplingPolicy::from_config(&config)?;
+        Ok(Self {
             blob_store,
             pool,
             config,
             commitment_mode,
-        }
+            sampling_policy,
+        })
     }
 
     #[tracing::instrument(skip_all)]
@@ -54,9 +60,19 @@ impl RequestProcessor {
     ) -> Result<Json<ProofGenerationDataResponse>, RequestProcessorError> {
         tracing::info!("Received request for proof generation data: {:?}", request);
 
-        let l1_batch_number = match self.lock_batch_for_proving().await? {
-            Some(number) => number,
-            None => return Ok(Json(ProofGenerationDataResponse::Success(None))), // no batches pending to be proven
+        let l1_batch_number = loop {
+            let l1_batch_number = match self.lock_batch_for_proving().await? {
+                Some(number) => number,
+                None => return Ok(Json(ProofGenerationDataResponse::Success(None))), // no batches pending to be proven
+            };
+            match self.apply_sampling_policy(l1_batch_number).await {
+                Ok(true) => break l1_batch_number,
+                Ok(false) => continue, // the batch is skipped; try the next one
+                Err(err) => {
+                    self.unlock_batch(l1_batch_number).await?;
+                    return Err(err);
+                }
+            }
         };
 
         let proof_generation_data = self
@@ -87,6 +103,82 @@ impl RequestProcessor {
             .map_err(RequestProcessorError::Dal)
     }
 
+    /// Decides whether a real proof should be generated for a locked batch. If the batch isn't sampled,
+    /// it's marked as skipped and `false` is returned.
+    ///
+    /// The decision is made only once per batch. If a decision is already recorded, the batch was returned
+    /// to the proving queue (e.g., after a prover timeout or by the operator), so it's proven regardless of the decision.
+    async fn apply_sampling_policy(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<bool, RequestProcessorError> {
+        let Some(policy) = &self.sampling_policy else {
+            return Ok(true);
+        };
+        let mut conn = self.pool.connection().await?;
+        if conn
+            .proof_generation_dal()
+            .get_sampling_decision(l1_batch_number)
+            .await?
+            .is_some()
+        {
+            return Ok(true);
+        }
+
+        let commitment = conn
+            .blocks_dal()
+            .get_l1_batch_metadata(l1_batch_number)
+            .await?
+            .ok_or_else(|| {
+                RequestProcessorError::GeneralError(format!(
+                    "Missing metadata for L1 batch #{l1_batch_number}"
+                ))
+            })?
+            .metadata
+            .commitment;
+        let decision = policy.decide(l1_batch_number, commitment);
+        tracing::info!(
+            "Proof sampling decision for L1 batch #{l1_batch_number}: sampled = {}, reason = {}",
+            decision.sampled,
+            decision.reason
+        );
+
+        let mut transaction = conn.start_transaction().await?;
+        transaction
+            .proof_generation_dal()
+            .save_sampling_decision(&decision)
+            .await?;
+        if !decision.sampled {
+            transaction
+                .proof_generation_dal()
+                .mark_proof_generation_job_as_skipped(l1_batch_number)
+                .await?;
+        }
+        transaction.commit().await?;
+        Ok(decision.sampled)
+    }
+
+    /// Returns the proof sampling decision for the specified batch, so that it can be verified by third parties.
+    pub(crate) async fn get_sampling_decision(
+        &self,
+        Path(l1_batch_number): Path<u32>,
+    ) -> Result<Json<ProofSamplingDecisionResponse>, RequestProcessorError> {
+        let l1_batch_number = L1BatchNumber(l1_batch_number);
+        let decision = self
+            .pool
+            .connection()
+            .await?
+            .proof_generation_dal()
+            .get_sampling_decision(l1_batch_number)
+            .await?
+            .ok_or_else(|| {
+                RequestProcessorError::NotFound(format!(
+                    "No proof sampling decision for L1 batch #{l1_batch_number}"
+                ))
+            })?;
+        Ok(Json(sampling_decision_response(decision)))
+    }
+
     /// Marks the batch as 'unpicked', allowing it to be picked up by another prover.
     async fn unlock_batch(
         &self,
@@ -324,4 +416,48 @@ impl RequestProcessor {
 
         Ok(Json(SubmitProofResponse::Success))
     }
+
+    /// Saves proving statistics reported by the prover subsystem for a batch.
+    pub(crate) async fn submit_proving_stats(
+        &self,
+        Path(l1_batch_number): Path<u32>,
+        Json(payload): Json<SubmitProvingStatsRequest>,
+    ) -> Result<Json<SubmitProvingStatsResponse>, RequestProcessorError> {
+        tracing::debug!("Received proving stats for L1 batch #{l1_batch_number}: {payload:?}");
+        let l1_batch_number = L1BatchNumber(l1_batch_number);
+        let saved = self
+            .pool
+            .connection()
+            .await?
+            .proof_generation_dal()
+            .save_proving_stats(
+                l1_batch_number,
+                payload.base_layer_circuits,
+                Duration::from_millis(payload.proving_time_ms),
+            )
+            .await?;
+        if !saved {
+            return Err(RequestProcessorError::NotFound(format!(
+                "No proof generation details for L1 batch #{l1_batch_number}"
+            )));
+        }
+        Ok(Json(SubmitProvingStatsResponse::Success))
+    }
+}
+
+fn sampling_decision_response(decision: ProofSamplingDecision) -> ProofSamplingDecisionResponse {
+    use zksync_dal::proof_generation_dal::ProofSamplingReason as StorageReason;
+
+    ProofSamplingDecisionResponse {
+        l1_batch_number: decision.l1_batch_number,
+        sampled: decision.sampled,
+        reason: match decision.reason {
+            StorageReason::Rate => ProofSamplingReason::Rate,
+            StorageReason::AlwaysProveRange => ProofSamplingReason::AlwaysProveRange,
+            StorageReason::NeverProveRange => ProofSamplingReason::NeverProveRange,
+        },
+        commitment: decision.commitment,
+        seed: decision.seed,
+        sampling_rate: decision.sampling_rate,
+    }
 }
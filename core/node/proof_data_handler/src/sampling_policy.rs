@@ -0,0 +1,183 @@
+//! Policy deciding for which L1 batches real proofs are generated.
+
+use std::ops::RangeInclusive;
+
+use anyhow::Context as _;
+use zksync_config::configs::ProofDataHandlerConfig;
+use zksync_dal::proof_generation_dal::{ProofSamplingDecision, ProofSamplingReason};
+use zksync_types::{web3::keccak256, L1BatchNumber, H256};
+
+/// Policy sampling L1 batches for proof generation.
+///
+/// Batches in one of the always-prove ranges are always sampled; otherwise, batches in one of the never-prove ranges
+/// are never sampled. For the remaining batches, the decision is derived from a seed depending only on the batch number
+/// and commitment (see [`sampling_seed()`]), so that anyone can verify that the sampling was honest.
+#[derive(Debug, Clone)]
+pub(crate) struct ProofSamplingPolicy {
+    rate: f64,
+    always_prove: Vec<RangeInclusive<L1BatchNumber>>,
+    never_prove: Vec<RangeInclusive<L1BatchNumber>>,
+}
+
+impl ProofSamplingPolicy {
+    /// Creates a policy from the config. Returns `None` if sampling is disabled.
+    pub(crate) fn from_config(config: &ProofDataHandlerConfig) -> anyhow::Result<Option<Self>> {
+        let Some(rate) = config.proof_sampling_rate else {
+            return Ok(None);
+        };
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&rate),
+            "proof sampling rate must be in [0, 1], got {rate}"
+        );
+        Ok(Some(Self {
+            rate,
+            always_prove: parse_ranges(&config.proof_sampling_always_prove_ranges)
+                .context("proof_sampling_always_prove_ranges")?,
+            never_prove: parse_ranges(&config.proof_sampling_never_prove_ranges)
+                .context("proof_sampling_never_prove_ranges")?,
+        }))
+    }
+
+    /// Decides whether a real proof should be generated for the specified L1 batch.
+    pub(crate) fn decide(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        commitment: H256,
+    ) -> ProofSamplingDecision {
+        let seed = sampling_seed(l1_batch_number, commitment);
+        let contains = |range: &RangeInclusive<L1BatchNumber>| range.contains(&l1_batch_number);
+        let (sampled, reason) = if self.always_prove.iter().any(contains) {
+            (true, ProofSamplingReason::AlwaysProveRange)
+        } else if self.never_prove.iter().any(contains) {
+            (false, ProofSamplingReason::NeverProveRange)
+        } else {
+            (is_sampled(seed, self.rate), ProofSamplingReason::Rate)
+        };
+
+        ProofSamplingDecision {
+            l1_batch_number,
+            sampled,
+            reason,
+            commitment,
+            seed,
+            sampling_rate: self.rate,
+        }
+    }
+}
+
+/// Computes the sampling seed as the Keccak-256 hash of the batch commitment concatenated with
+/// the big-endian L1 batch number.
+pub(crate) fn sampling_seed(l1_batch_number: L1BatchNumber, commitment: H256) -> H256 {
+    let mut preimage = commitment.as_bytes().to_vec();
+    preimage.extend_from_slice(&l1_batch_number.0.to_be_bytes());
+    H256(keccak256(&preimage))
+}
+
+/// Checks whether the first 8 bytes of the seed, interpreted as a big-endian fraction of 2^64, are less than the rate.
+fn is_sampled(seed: H256, rate: f64) -> bool {
+    let value = u64::from_be_bytes(seed[..8].try_into().unwrap());
+    (value as f64) / 2.0_f64.powi(64) < rate
+}
+
+fn parse_ranges(ranges: &[String]) -> anyhow::Result<Vec<RangeInclusive<L1BatchNumber>>> {
+    ranges
+        .iter()
+        .map(|range| {
+            let (start, end) = range
+                .split_once('-')
+                .with_context(|| format!("range `{range}` is not in the `start-end` format"))?;
+            let start: u32 = start
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid start of range `{range}`"))?;
+            let end: u32 = end
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid end of range `{range}`"))?;
+            anyhow::ensure!(start <= end, "range `{range}` is empty");
+            Ok(L1BatchNumber(start)..=L1BatchNumber(end))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_config::configs::TeeConfig;
+
+    use super::*;
+
+    fn config(rate: f64, always_prove: &[&str], never_prove: &[&str]) -> ProofDataHandlerConfig {
+        ProofDataHandlerConfig {
+            http_port: 1337,
+            proof_generation_timeout_in_secs: 10,
+            tee_config: TeeConfig::default(),
+            proof_sampling_rate: Some(rate),
+            proof_sampling_always_prove_ranges: always_prove.iter().map(|&s| s.into()).collect(),
+            proof_sampling_never_prove_ranges: never_prove.iter().map(|&s| s.into()).collect(),
+        }
+    }
+
+    #[test]
+    fn parsing_ranges() {
+        let ranges = parse_ranges(&["1-10".into(), " 20 - 20 ".into()]).unwrap();
+        assert_eq!(
+            ranges,
+            [
+                L1BatchNumber(1)..=L1BatchNumber(10),
+                L1BatchNumber(20)..=L1BatchNumber(20)
+            ]
+        );
+
+        for invalid_range in ["1", "10-1", "a-2", "1-"] {
+            parse_ranges(&[invalid_range.into()]).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn invalid_sampling_rate() {
+        let err = ProofSamplingPolicy::from_config(&config(1.5, &[], &[])).unwrap_err();
+        assert!(err.to_string().contains("sampling rate"), "{err:#}");
+    }
+
+    #[test]
+    fn sampling_is_deterministic() {
+        let policy = ProofSamplingPolicy::from_config(&config(0.5, &[], &[]))
+            .unwrap()
+            .unwrap();
+        let mut sampled_count = 0;
+        for number in 0..1_000 {
+            let l1_batch_number = L1BatchNumber(number);
+            let commitment = H256::from_low_u64_be(number.into());
+            let decision = policy.decide(l1_batch_number, commitment);
+            assert_eq!(decision, policy.decide(l1_batch_number, commitment));
+            assert_eq!(decision.reason, ProofSamplingReason::Rate);
+            assert_eq!(decision.seed, sampling_seed(l1_batch_number, commitment));
+            sampled_count += usize::from(decision.sampled);
+        }
+        assert!((400..600).contains(&sampled_count), "{sampled_count}");
+
+        let policy = ProofSamplingPolicy::from_config(&config(0.0, &[], &[]))
+            .unwrap()
+            .unwrap();
+        assert!(!policy.decide(L1BatchNumber(1), H256::zero()).sampled);
+        let policy = ProofSamplingPolicy::from_config(&config(1.0, &[], &[]))
+            .unwrap()
+            .unwrap();
+        assert!(policy.decide(L1BatchNumber(1), H256::zero()).sampled);
+    }
+
+    #[test]
+    fn sampling_with_ranges() {
+        let policy = ProofSamplingPolicy::from_config(&config(0.5, &["5-10"], &["1-20"]))
+            .unwrap()
+            .unwrap();
+        let decision = policy.decide(L1BatchNumber(5), H256::zero());
+        assert!(decision.sampled);
+        assert_eq!(decision.reason, ProofSamplingReason::AlwaysProveRange);
+        let decision = policy.decide(L1BatchNumber(11), H256::zero());
+        assert!(!decision.sampled);
+        assert_eq!(decision.reason, ProofSamplingReason::NeverProveRange);
+        let decision = policy.decide(L1BatchNumber(21), H256::zero());
+        assert_eq!(decision.reason, ProofSamplingReason::Rate);
+    }
+}
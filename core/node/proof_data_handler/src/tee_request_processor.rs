@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use anyhow::Context as _;
 use axum::{extract::Path, Json};
 use chrono::{Duration as ChronoDuration, Utc};
 use zksync_config::configs::ProofDataHandlerConfig;
@@ -10,17 +11,34 @@ use zksync_dal::{
 use zksync_object_store::{ObjectStore, ObjectStoreError};
 use zksync_prover_interface::{
     api::{
-        RegisterTeeAttestationRequest, RegisterTeeAttestationResponse, SubmitProofResponse,
-        SubmitTeeProofRequest, TeeProofGenerationDataRequest, TeeProofGenerationDataResponse,
+        RegisterTeeAttestationRequest, RegisterTeeAttestationResponse, SignedTeeAuxiliaryData,
+        SubmitProofResponse, SubmitTeeProofRequest, TeeAuxiliaryDataKind,
+        TeeProofGenerationDataRequest, TeeProofGenerationDataResponse,
     },
     inputs::{
         TeeVerifierInput, V1TeeVerifierInput, VMRunWitnessInputData, WitnessInputMerklePaths,
     },
 };
-use zksync_types::{tee_types::TeeType, L1BatchNumber, L2ChainId};
+use zksync_types::{
+    tee_types::TeeType, K256PrivateKey, L1BatchNumber, L2ChainId, PackedEthSignature,
+};
 use zksync_vm_executor::storage::L1BatchParamsProvider;
 
-use crate::{errors::RequestProcessorError, metrics::METRICS};
+use crate::{
+    attestation_policy::{AttestationClaims, AttestationPolicy},
+    errors::RequestProcessorError,
+    metrics::METRICS,
+};
+
+/// Auxiliary data for a TEE proof generation job.
+#[derive(Debug)]
+enum AuxiliaryData {
+    /// No auxiliary data is attached to the job.
+    None,
+    /// Auxiliary data is required, but isn't available yet.
+    NotReady,
+    Signed(SignedTeeAuxiliaryData),
+}
 
 #[derive(Clone)]
 pub(crate) struct TeeRequestProcessor {
@@ -28,6 +46,8 @@ pub(crate) struct TeeRequestProcessor {
     pool: ConnectionPool<Core>,
     config: ProofDataHandlerConfig,
     l2_chain_id: L2ChainId,
+    attestation_policy: Option<Arc<AttestationPolicy>>,
+    auxiliary_data_signer: Option<K256PrivateKey>,
 }
 
 impl TeeRequestProcessor {
@@ -36,13 +56,19 @@ impl TeeRequestProcessor {
         pool: ConnectionPool<Core>,
         config: ProofDataHandlerConfig,
         l2_chain_id: L2ChainId,
-    ) -> Self {
-        Self {
+        auxiliary_data_signer: Option<K256PrivateKey>,
+    ) -> anyhow::Result<Self> {
+        let attestation_policy = AttestationPolicy::from_config(&config.tee_config)
+            .context("invalid TEE attestation policy")?
+            .map(Arc::new);
+        Ok(Self {
             blob_store,
             pool,
             config,
             l2_chain_id,
-        }
+            attestation_policy,
+            auxiliary_data_signer,
+        })
     }
 
     pub(crate) async fn get_proof_generation_data(
@@ -73,12 +99,41 @@ impl TeeRequestProcessor {
             };
             let batch_number = locked_batch.l1_batch_number;
 
-            match self
+            let result = match self
                 .tee_verifier_input_for_existing_batch(batch_number)
                 .await
             {
-                Ok(input) => {
-                    break Ok(Some(Json(TeeProofGenerationDataResponse(Box::new(input)))));
+                Ok(input) => self
+                    .auxiliary_data(batch_number)
+                    .await
+                    .map(|auxiliary_data| (input, auxiliary_data)),
+                Err(err) => Err(err),
+            };
+
+            match result {
+                Ok((input, AuxiliaryData::None)) => {
+                    break Ok(Some(Json(TeeProofGenerationDataResponse {
+                        input: Box::new(input),
+                        auxiliary_data: None,
+                    })));
+                }
+                Ok((input, AuxiliaryData::Signed(auxiliary_data))) => {
+                    break Ok(Some(Json(TeeProofGenerationDataResponse {
+                        input: Box::new(input),
+                        auxiliary_data: Some(auxiliary_data),
+                    })));
+                }
+                Ok((_, AuxiliaryData::NotReady)) => {
+                    // Auxiliary data isn't available yet; the batch will be retried after the timeout.
+                    self.unlock_batch(
+                        batch_number,
+                        request.tee_type,
+                        TeeProofGenerationJobStatus::Failed,
+                    )
+                    .await?;
+                    tracing::info!(
+                        "Auxiliary data for batch {batch_number} is not available yet; postponing TEE proof generation"
+                    );
                 }
                 Err(RequestProcessorError::ObjectStore(ObjectStoreError::KeyNotFound(_))) => {
                     let duration = Utc::now().signed_duration_since(locked_batch.created_at);
@@ -109,6 +164,46 @@ impl TeeRequestProcessor {
         }
     }
 
+    /// Loads and signs auxiliary data for the batch.
+    ///
+    /// Currently, the only kind of auxiliary data is the DA inclusion proof, which is attached
+    /// if the signer is configured and the batch was dispatched to the DA layer.
+    async fn auxiliary_data(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<AuxiliaryData, RequestProcessorError> {
+        let Some(signer) = &self.auxiliary_data_signer else {
+            return Ok(AuxiliaryData::None);
+        };
+        let da_details = self
+            .pool
+            .connection_tagged("tee_request_processor")
+            .await?
+            .data_availability_dal()
+            .get_da_details_by_batch_number(l1_batch_number)
+            .await?;
+        let Some(da_details) = da_details else {
+            return Ok(AuxiliaryData::None);
+        };
+        let Some(data) = da_details.inclusion_data else {
+            return Ok(AuxiliaryData::NotReady);
+        };
+
+        let kind = TeeAuxiliaryDataKind::DaInclusionProof;
+        let message = SignedTeeAuxiliaryData::signed_message(l1_batch_number, kind, &data);
+        let signature = PackedEthSignature::sign_raw(signer, &message).map_err(|err| {
+            RequestProcessorError::GeneralError(format!(
+                "Failed signing auxiliary data for batch {l1_batch_number}: {err}"
+            ))
+        })?;
+        Ok(AuxiliaryData::Signed(SignedTeeAuxiliaryData {
+            l1_batch_number,
+            kind,
+            data,
+            signature,
+        }))
+    }
+
     #[tracing::instrument(skip(self))]
     async fn tee_verifier_input_for_existing_batch(
         &self,
@@ -251,12 +346,47 @@ impl TeeRequestProcessor {
     ) -> Result<Json<RegisterTeeAttestationResponse>, RequestProcessorError> {
         tracing::info!("Received attestation: {:?}", payload);
 
+        let claims = match &self.attestation_policy {
+            Some(policy) => Some(Self::check_attestation(policy, &payload.attestation)?),
+            None => None,
+        };
+
         let mut connection = self.pool.connection_tagged("tee_request_processor").await?;
         let mut dal = connection.tee_proof_generation_dal();
 
-        dal.save_attestation(&payload.pubkey, &payload.attestation)
+        if let Some(claims) = claims {
+            let claims_json = serde_json::to_value(&claims).map_err(|err| {
+                RequestProcessorError::GeneralError(format!(
+                    "Failed serializing attestation claims: {err}"
+                ))
+            })?;
+            dal.save_verified_attestation(
+                &payload.pubkey,
+                &payload.attestation,
+                claims.tee_type(),
+                &claims_json,
+            )
             .await?;
+        } else {
+            dal.save_attestation(&payload.pubkey, &payload.attestation)
+                .await?;
+        }
 
         Ok(Json(RegisterTeeAttestationResponse::Success))
     }
+    fn check_attestation(
+        policy: &AttestationPolicy,
+        attestation: &[u8],
+    ) -> Result<AttestationClaims, RequestProcessorError> {
+        let claims = AttestationClaims::parse(attestation).map_err(|err| {
+            RequestProcessorError::BadRequest(format!("Malformed attestation quote: {err:#}"))
+        })?;
+        if let Err(reason) = policy.validate(&claims) {
+            tracing::warn!("Rejected attestation with claims {claims:?}: {reason}");
+            return Err(RequestProcessorError::Forbidden(format!(
+                "Attestation rejected by policy: {reason}"
+            )));
+        }
+        Ok(claims)
+    }
 }
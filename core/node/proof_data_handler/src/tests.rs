@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use axum::{
     body::Body,
     http::{self, Method, Request, StatusCode},
@@ -7,14 +9,27 @@ use axum::{
 use serde_json::json;
 use tower::ServiceExt;
 use zksync_config::configs::{ProofDataHandlerConfig, TeeConfig};
-use zksync_dal::{ConnectionPool, CoreDal};
+use zksync_dal::{
+    proof_generation_dal::{ProofGenerationAdminAction, ProofGenerationJobStatus},
+    ConnectionPool, CoreDal,
+};
 use zksync_object_store::MockObjectStore;
-use zksync_prover_interface::api::SubmitTeeProofRequest;
+use zksync_prover_interface::api::{
+    ProofSamplingDecisionResponse, ProofSamplingReason, RegisterTeeAttestationRequest,
+    SubmitProvingStatsRequest, SubmitTeeProofRequest,
+};
 use zksync_types::{
-    commitment::L1BatchCommitmentMode, tee_types::TeeType, L1BatchNumber, L2ChainId,
+    block::{L1BatchHeader, L1BatchTreeData},
+    commitment::{L1BatchCommitmentArtifacts, L1BatchCommitmentMode},
+    secrets::APIKey,
+    tee_types::TeeType,
+    L1BatchNumber, L2ChainId, ProtocolVersion, ProtocolVersionId, H256,
 };
 
-use crate::create_proof_processing_router;
+use crate::{
+    attestation_policy::testonly::mock_sgx_quote, create_proof_processing_router,
+    sampling_policy::sampling_seed,
+};
 
 #[tokio::test]
 async fn request_tee_proof_inputs() {
@@ -31,11 +46,18 @@ async fn request_tee_proof_inputs() {
                 first_tee_processed_batch: L1BatchNumber(0),
                 tee_proof_generation_timeout_in_secs: 600,
                 tee_batch_permanently_ignored_timeout_in_hours: 10 * 24,
+                ..TeeConfig::default()
             },
+            proof_sampling_rate: None,
+            proof_sampling_always_prove_ranges: vec![],
+            proof_sampling_never_prove_ranges: vec![],
         },
         L1BatchCommitmentMode::Rollup,
         L2ChainId::default(),
-    );
+        None,
+        None,
+    )
+    .unwrap();
     let test_cases = vec![
         (json!({ "tee_type": "sgx" }), StatusCode::NO_CONTENT),
         (
@@ -91,11 +113,18 @@ async fn submit_tee_proof() {
                 first_tee_processed_batch: L1BatchNumber(0),
                 tee_proof_generation_timeout_in_secs: 600,
                 tee_batch_permanently_ignored_timeout_in_hours: 10 * 24,
+                ..TeeConfig::default()
             },
+            proof_sampling_rate: None,
+            proof_sampling_always_prove_ranges: vec![],
+            proof_sampling_never_prove_ranges: vec![],
         },
         L1BatchCommitmentMode::Rollup,
         L2ChainId::default(),
-    );
+        None,
+        None,
+    )
+    .unwrap();
 
     // this should fail because we haven't saved the attestation for the pubkey yet
 
@@ -149,6 +178,83 @@ async fn submit_tee_proof() {
     assert_eq!(proof.pubkey.as_ref().unwrap(), &tee_proof_request.0.pubkey);
 }
 
+#[tokio::test]
+async fn register_tee_attestation_with_policy() {
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    let app = create_proof_processing_router(
+        MockObjectStore::arc(),
+        db_conn_pool.clone(),
+        ProofDataHandlerConfig {
+            http_port: 1337,
+            proof_generation_timeout_in_secs: 10,
+            tee_config: TeeConfig {
+                tee_support: true,
+                attestation_policy_enabled: true,
+                sgx_mrenclave_allowlist: vec![hex::encode([1; 32])],
+                ..TeeConfig::default()
+            },
+            proof_sampling_rate: None,
+            proof_sampling_always_prove_ranges: vec![],
+            proof_sampling_never_prove_ranges: vec![],
+        },
+        L1BatchCommitmentMode::Rollup,
+        L2ChainId::default(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    let test_cases = [
+        (vec![1, 2, 3], StatusCode::BAD_REQUEST),
+        (
+            mock_sgx_quote([2; 32], [2; 32], false),
+            StatusCode::FORBIDDEN,
+        ),
+        (
+            mock_sgx_quote([1; 32], [2; 32], true),
+            StatusCode::FORBIDDEN,
+        ),
+        (mock_sgx_quote([1; 32], [2; 32], false), StatusCode::OK),
+    ];
+    for (i, (attestation, expected_status)) in test_cases.into_iter().enumerate() {
+        let request = RegisterTeeAttestationRequest {
+            attestation,
+            pubkey: vec![i as u8; 33],
+        };
+        let req_body = Body::from(serde_json::to_vec(&request).unwrap());
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/tee/register_attestation")
+                    .header(http::header::CONTENT_TYPE, "application/json")
+                    .body(req_body)
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), expected_status, "case #{i}");
+    }
+
+    let mut conn = db_conn_pool.connection().await.unwrap();
+    let claims = conn
+        .tee_proof_generation_dal()
+        .get_attestation_claims(&[2; 33])
+        .await
+        .unwrap();
+    assert_eq!(claims, None);
+    let claims = conn
+        .tee_proof_generation_dal()
+        .get_attestation_claims(&[3; 33])
+        .await
+        .unwrap()
+        .expect("no claims recorded");
+    assert_eq!(claims["tee_type"], "sgx");
+    assert_eq!(claims["mrenclave"], hex::encode([1; 32]));
+    assert_eq!(claims["debug"], false);
+}
+
 // Mock SQL db with information about the status of the TEE proof generation
 async fn mock_tee_batch_status(
     db_conn_pool: ConnectionPool<zksync_dal::Core>,
@@ -197,3 +303,400 @@ async fn send_submit_tee_proof_request(
         .await
         .unwrap()
 }
+
+const ADMIN_AUTH_TOKEN: &str = "admin-token";
+
+fn admin_test_router(db_conn_pool: ConnectionPool<zksync_dal::Core>) -> Router {
+    create_proof_processing_router(
+        MockObjectStore::arc(),
+        db_conn_pool,
+        ProofDataHandlerConfig {
+            http_port: 1337,
+            proof_generation_timeout_in_secs: 10,
+            tee_config: TeeConfig::default(),
+            proof_sampling_rate: None,
+            proof_sampling_always_prove_ranges: vec![],
+            proof_sampling_never_prove_ranges: vec![],
+        },
+        L1BatchCommitmentMode::Rollup,
+        L2ChainId::default(),
+        Some(APIKey::from(ADMIN_AUTH_TOKEN)),
+        None,
+    )
+    .unwrap()
+}
+
+async fn send_admin_request(
+    app: &Router,
+    uri: &str,
+    body: serde_json::Value,
+    auth_token: Option<&str>,
+) -> Response {
+    let mut request = Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .header(http::header::CONTENT_TYPE, "application/json");
+    if let Some(token) = auth_token {
+        request = request.header(http::header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let request = request
+        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn proof_generation_status(
+    db_conn_pool: &ConnectionPool<zksync_dal::Core>,
+    batch_number: L1BatchNumber,
+) -> Option<ProofGenerationJobStatus> {
+    let mut storage = db_conn_pool.connection().await.unwrap();
+    let mut transaction = storage.start_transaction().await.unwrap();
+    transaction
+        .proof_generation_dal()
+        .get_status_for_update(batch_number)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn admin_requests_require_auth() {
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    let app = admin_test_router(db_conn_pool.clone());
+    let body = json!({ "reason": "test" });
+
+    let response = send_admin_request(&app, "/admin/skip_batch/1", body.clone(), None).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response =
+        send_admin_request(&app, "/admin/skip_batch/1", body.clone(), Some("wrong")).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Admin endpoints are not served if the auth token is not configured.
+    let app = create_proof_processing_router(
+        MockObjectStore::arc(),
+        db_conn_pool,
+        ProofDataHandlerConfig {
+            http_port: 1337,
+            proof_generation_timeout_in_secs: 10,
+            tee_config: TeeConfig::default(),
+            proof_sampling_rate: None,
+            proof_sampling_always_prove_ranges: vec![],
+            proof_sampling_never_prove_ranges: vec![],
+        },
+        L1BatchCommitmentMode::Rollup,
+        L2ChainId::default(),
+        None,
+        None,
+    )
+    .unwrap();
+    let response =
+        send_admin_request(&app, "/admin/skip_batch/1", body, Some(ADMIN_AUTH_TOKEN)).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn admin_actions_for_proof_generation_jobs() {
+    let batch_number = L1BatchNumber(1);
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    let mut storage = db_conn_pool.connection().await.unwrap();
+    storage
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+    let header = L1BatchHeader::new(
+        batch_number,
+        1,
+        Default::default(),
+        ProtocolVersionId::latest(),
+    );
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&header)
+        .await
+        .unwrap();
+    storage
+        .proof_generation_dal()
+        .insert_proof_generation_details(batch_number)
+        .await
+        .unwrap();
+
+    let app = admin_test_router(db_conn_pool.clone());
+    let token = Some(ADMIN_AUTH_TOKEN);
+
+    // The reason is mandatory for skipping.
+    let response =
+        send_admin_request(&app, "/admin/skip_batch/1", json!({ "reason": " " }), token).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = send_admin_request(
+        &app,
+        "/admin/skip_batch/2",
+        json!({ "reason": "test" }),
+        token,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = send_admin_request(
+        &app,
+        "/admin/skip_batch/1",
+        json!({ "reason": "broken witness" }),
+        token,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        proof_generation_status(&db_conn_pool, batch_number).await,
+        Some(ProofGenerationJobStatus::Skipped)
+    );
+
+    // There's no proof to invalidate.
+    let response = send_admin_request(
+        &app,
+        "/admin/invalidate_proof/1",
+        json!({ "reason": "test" }),
+        token,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let response = send_admin_request(&app, "/admin/requeue_batch/1", json!({}), token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        proof_generation_status(&db_conn_pool, batch_number).await,
+        Some(ProofGenerationJobStatus::Unpicked)
+    );
+    // The batch is already in the queue.
+    let response = send_admin_request(&app, "/admin/requeue_batch/1", json!({}), token).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    storage
+        .proof_generation_dal()
+        .save_proof_artifacts_metadata(batch_number, "proof")
+        .await
+        .unwrap();
+    let response = send_admin_request(
+        &app,
+        "/admin/invalidate_proof/1",
+        json!({ "reason": "invalid proof" }),
+        token,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        proof_generation_status(&db_conn_pool, batch_number).await,
+        Some(ProofGenerationJobStatus::Unpicked)
+    );
+
+    let actions = storage
+        .proof_generation_dal()
+        .get_admin_actions(batch_number)
+        .await
+        .unwrap();
+    let actions: Vec<_> = actions
+        .into_iter()
+        .map(|record| (record.action, record.previous_status, record.reason))
+        .collect();
+    assert_eq!(
+        actions,
+        [
+            (
+                ProofGenerationAdminAction::Skip,
+                ProofGenerationJobStatus::Unpicked,
+                Some("broken witness".to_owned())
+            ),
+            (
+                ProofGenerationAdminAction::Requeue,
+                ProofGenerationJobStatus::Skipped,
+                None
+            ),
+            (
+                ProofGenerationAdminAction::InvalidateProof,
+                ProofGenerationJobStatus::Generated,
+                Some("invalid proof".to_owned())
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn proof_sampling() {
+    let batch_number = L1BatchNumber(1);
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    let mut storage = db_conn_pool.connection().await.unwrap();
+    storage
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+    let header = L1BatchHeader::new(
+        batch_number,
+        1,
+        Default::default(),
+        ProtocolVersionId::latest(),
+    );
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&header)
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .save_l1_batch_tree_data(
+            batch_number,
+            &L1BatchTreeData {
+                hash: H256::zero(),
+                rollup_last_leaf_index: 1,
+            },
+        )
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .save_l1_batch_commitment_artifacts(batch_number, &L1BatchCommitmentArtifacts::default())
+        .await
+        .unwrap();
+    let mut proof_dal = storage.proof_generation_dal();
+    proof_dal
+        .insert_proof_generation_details(batch_number)
+        .await
+        .unwrap();
+    proof_dal
+        .save_vm_runner_artifacts_metadata(batch_number, "vm_run")
+        .await
+        .unwrap();
+    proof_dal
+        .save_merkle_paths_artifacts_metadata(batch_number, "merkle_paths")
+        .await
+        .unwrap();
+
+    let app = create_proof_processing_router(
+        MockObjectStore::arc(),
+        db_conn_pool.clone(),
+        ProofDataHandlerConfig {
+            http_port: 1337,
+            proof_generation_timeout_in_secs: 10,
+            tee_config: TeeConfig::default(),
+            proof_sampling_rate: Some(0.0),
+            proof_sampling_always_prove_ranges: vec![],
+            proof_sampling_never_prove_ranges: vec![],
+        },
+        L1BatchCommitmentMode::Rollup,
+        L2ChainId::default(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    let decision_request = || {
+        Request::builder()
+            .method(Method::GET)
+            .uri("/proof_sampling/1")
+            .body(Body::empty())
+            .unwrap()
+    };
+    let response = app.clone().oneshot(decision_request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // The only batch is not sampled, so no data should be returned to the prover.
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/proof_generation_data")
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(Body::from("{}"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body, json!({ "Success": null }));
+    assert_eq!(
+        proof_generation_status(&db_conn_pool, batch_number).await,
+        Some(ProofGenerationJobStatus::Skipped)
+    );
+
+    let response = app.clone().oneshot(decision_request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let decision: ProofSamplingDecisionResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(decision.l1_batch_number, batch_number);
+    assert!(!decision.sampled);
+    assert_eq!(decision.reason, ProofSamplingReason::Rate);
+    assert_eq!(decision.sampling_rate, 0.0);
+    assert_eq!(
+        decision.seed,
+        sampling_seed(batch_number, decision.commitment)
+    );
+}
+
+#[tokio::test]
+async fn submitting_proving_stats() {
+    let batch_number = L1BatchNumber(1);
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    let mut storage = db_conn_pool.connection().await.unwrap();
+    storage
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+    let header = L1BatchHeader::new(
+        batch_number,
+        1,
+        Default::default(),
+        ProtocolVersionId::latest(),
+    );
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&header)
+        .await
+        .unwrap();
+    storage
+        .proof_generation_dal()
+        .insert_proof_generation_details(batch_number)
+        .await
+        .unwrap();
+
+    let app = admin_test_router(db_conn_pool.clone());
+    let stats = SubmitProvingStatsRequest {
+        base_layer_circuits: 1_000,
+        proving_time_ms: 90_000,
+    };
+    let stats_request = |uri: &str| {
+        Request::builder()
+            .method(Method::POST)
+            .uri(uri)
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_vec(&stats).unwrap()))
+            .unwrap()
+    };
+    let response = app
+        .clone()
+        .oneshot(stats_request("/proving_stats/2"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = app
+        .clone()
+        .oneshot(stats_request("/proving_stats/1"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let saved_stats = storage
+        .proof_generation_dal()
+        .get_latest_proving_stats(10)
+        .await
+        .unwrap();
+    assert_eq!(saved_stats.len(), 1);
+    assert_eq!(saved_stats[0].l1_batch_number, batch_number);
+    assert_eq!(saved_stats[0].base_layer_circuits, 1_000);
+    assert_eq!(saved_stats[0].proving_time, Duration::from_secs(90));
+}
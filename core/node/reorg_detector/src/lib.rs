@@ -6,7 +6,10 @@ use tokio::sync::watch;
 use zksync_dal::{ConnectionPool, Core, CoreDal, DalError};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_shared_metrics::{CheckerComponent, EN_METRICS};
-use zksync_types::{L1BatchNumber, L2BlockNumber, H256};
+use zksync_types::{
+    api::{self, GetLogsFilter},
+    Bloom, L1BatchNumber, L2BlockNumber, H256,
+};
 use zksync_web3_decl::{
     client::{DynClient, L2},
     error::{ClientRpcContext, EnrichedClientError, EnrichedClientResult},
@@ -67,6 +70,45 @@ pub enum Error {
     EarliestL1BatchTruncated(L1BatchNumber),
     #[error("reorg detected, restart the node to revert to the last correct L1 batch #{0}.")]
     ReorgDetected(L1BatchNumber),
+    #[error(
+        "Unrecoverable error: reorg detected with the last correct L1 batch #{last_correct_l1_batch}, \
+        but rolling back {rollback_depth} L1 batches exceeds the configured limit of {max_rollback_depth}. \
+        Revert the node state manually using the block reverter"
+    )]
+    RollbackTooDeep {
+        last_correct_l1_batch: L1BatchNumber,
+        rollback_depth: u32,
+        max_rollback_depth: u32,
+    },
+    #[error(
+        "Unrecoverable error: locally executed L1 batches diverged from the main node, \
+        halting since cross-verification is enabled: {0:?}"
+    )]
+    Diverged(Box<DivergenceReport>),
+}
+
+/// Report on the divergence of locally executed L1 batches from the main node produced in the cross-verification mode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DivergenceReport {
+    /// Last L1 batch that matches the main node.
+    pub last_correct_l1_batch: L1BatchNumber,
+    /// First L1 batch that diverges from the main node.
+    pub diverged_l1_batch: L1BatchNumber,
+    /// State root hash of the diverged L1 batch computed locally.
+    pub local_root_hash: Option<H256>,
+    /// State root hash of the diverged L1 batch returned by the main node.
+    pub remote_root_hash: Option<H256>,
+}
+
+impl DivergenceReport {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "last_correct_l1_batch": self.last_correct_l1_batch,
+            "diverged_l1_batch": self.diverged_l1_batch,
+            "local_root_hash": self.local_root_hash,
+            "remote_root_hash": self.remote_root_hash,
+        })
+    }
 }
 
 impl HashMatchError {
@@ -111,6 +153,17 @@ trait MainNodeClient: fmt::Debug + Send + Sync {
 
     async fn l2_block_hash(&self, number: L2BlockNumber) -> EnrichedClientResult<Option<H256>>;
 
+    async fn l2_block_logs_bloom(
+        &self,
+        number: L2BlockNumber,
+    ) -> EnrichedClientResult<Option<Bloom>>;
+
+    /// Returns events emitted in the specified L2 block in the order of their emission.
+    async fn l2_block_events(
+        &self,
+        number: L2BlockNumber,
+    ) -> EnrichedClientResult<Option<Vec<api::Log>>>;
+
     async fn l1_batch_root_hash(
         &self,
         number: L1BatchNumber,
@@ -150,6 +203,36 @@ impl MainNodeClient for Box<DynClient<L2>> {
             .map(|block| block.hash))
     }
 
+    async fn l2_block_logs_bloom(
+        &self,
+        number: L2BlockNumber,
+    ) -> EnrichedClientResult<Option<Bloom>> {
+        Ok(self
+            .get_block_by_number(number.0.into(), false)
+            .rpc_context("l2_block_logs_bloom")
+            .with_arg("number", &number)
+            .await?
+            .map(|block| block.logs_bloom))
+    }
+
+    async fn l2_block_events(
+        &self,
+        number: L2BlockNumber,
+    ) -> EnrichedClientResult<Option<Vec<api::Log>>> {
+        // Unlike `eth_getLogs`, block receipts are not subject to the limit on the number of returned entities.
+        let receipts = self
+            .get_block_receipts(api::BlockId::Number(number.0.into()))
+            .rpc_context("l2_block_events")
+            .with_arg("number", &number)
+            .await?;
+        Ok(receipts.map(|receipts| {
+            receipts
+                .into_iter()
+                .flat_map(|receipt| receipt.logs)
+                .collect()
+        }))
+    }
+
     async fn l1_batch_root_hash(
         &self,
         number: L1BatchNumber,
@@ -177,6 +260,15 @@ trait HandleReorgDetectorEvent: fmt::Debug + Send + Sync {
 
     fn report_divergence(&mut self, diverged_l1_batch: L1BatchNumber);
 
+    fn report_cross_verification_failure(&mut self, report: &DivergenceReport);
+
+    fn report_reorg(
+        &mut self,
+        last_correct_l1_batch: L1BatchNumber,
+        rollback_depth: u32,
+        auto_rollback: bool,
+    );
+
     fn start_shutting_down(&mut self);
 }
 
@@ -220,6 +312,24 @@ impl HandleReorgDetectorEvent for HealthUpdater {
         self.update(Health::from(HealthStatus::Affected).with_details(health_details));
     }
 
+    fn report_cross_verification_failure(&mut self, report: &DivergenceReport) {
+        self.update(Health::from(HealthStatus::Affected).with_details(report.to_json()));
+    }
+
+    fn report_reorg(
+        &mut self,
+        last_correct_l1_batch: L1BatchNumber,
+        rollback_depth: u32,
+        auto_rollback: bool,
+    ) {
+        let health_details = serde_json::json!({
+            "last_correct_l1_batch": last_correct_l1_batch,
+            "rollback_depth": rollback_depth,
+            "auto_rollback": auto_rollback,
+        });
+        self.update(Health::from(HealthStatus::Affected).with_details(health_details));
+    }
+
     fn start_shutting_down(&mut self) {
         self.update(HealthStatus::ShuttingDown.into());
     }
@@ -240,6 +350,14 @@ impl HandleReorgDetectorEvent for HealthUpdater {
 /// This is the only component that is expected to finish its execution
 /// in the event of re-org, since we have to restart the node after a rollback is performed,
 /// and is special-cased in the `zksync_external_node` crate.
+///
+/// In the cross-verification mode, the detector additionally compares events emitted in L2 blocks (first logs blooms,
+/// then full event data, i.e. emitter addresses, topics and payloads), and instead of reverting diverged L1 batches,
+/// it halts the node with a [`DivergenceReport`]. Pubdata is not compared since the main node doesn't expose it;
+/// state diffs are covered by L1 batch root hashes.
+///
+/// The number of L1 batches that can be rolled back automatically can be bounded with [`Self::with_max_rollback_depth()`].
+/// If a detected reorg is deeper, the detector fails with [`Error::RollbackTooDeep`], and the node must be reverted manually.
 #[derive(Debug)]
 pub struct ReorgDetector {
     client: Box<dyn MainNodeClient>,
@@ -247,6 +365,8 @@ pub struct ReorgDetector {
     pool: ConnectionPool<Core>,
     sleep_interval: Duration,
     health_check: ReactiveHealthCheck,
+    cross_verification: bool,
+    max_rollback_depth: Option<u32>,
 }
 
 impl ReorgDetector {
@@ -260,9 +380,23 @@ impl ReorgDetector {
             pool,
             sleep_interval: Self::DEFAULT_SLEEP_INTERVAL,
             health_check,
+            cross_verification: false,
+            max_rollback_depth: None,
         }
     }
 
+    /// Enables or disables the cross-verification mode.
+    pub fn with_cross_verification(mut self, enabled: bool) -> Self {
+        self.cross_verification = enabled;
+        self
+    }
+
+    /// Sets the maximum number of L1 batches that can be rolled back automatically. If not set, the rollback depth is unbounded.
+    pub fn with_max_rollback_depth(mut self, max_depth: Option<u32>) -> Self {
+        self.max_rollback_depth = max_depth;
+        self
+    }
+
     pub fn health_check(&self) -> &ReactiveHealthCheck {
         &self.health_check
     }
@@ -294,7 +428,7 @@ impl ReorgDetector {
         let checked_l1_batch = local_l1_batch.min(remote_l1_batch);
         let checked_l2_block = local_l2_block.min(remote_l2_block);
         let root_hashes_match = self.root_hashes_match(checked_l1_batch).await?;
-        let l2_block_hashes_match = self.l2_block_hashes_match(checked_l2_block).await?;
+        let l2_block_hashes_match = self.l2_blocks_match(checked_l2_block).await?;
 
         // The only event that triggers re-org detection and node rollback is if the
         // hash mismatch at the same block height is detected, be it L2 blocks or batches.
@@ -339,7 +473,134 @@ impl ReorgDetector {
         tracing::info!("Searching for the first diverged L1 batch");
         let last_correct_l1_batch = self.detect_reorg(first_l1_batch, diverged_l1_batch).await?;
         tracing::info!("Reorg localized: last correct L1 batch is #{last_correct_l1_batch}");
-        Err(Error::ReorgDetected(last_correct_l1_batch))
+        if self.cross_verification {
+            let report = self.divergence_report(last_correct_l1_batch).await?;
+            tracing::error!("Cross-verification failed: {report:#?}");
+            self.event_handler
+                .report_cross_verification_failure(&report);
+            return Err(Error::Diverged(Box::new(report)));
+        }
+
+        let mut storage = self.pool.connection().await?;
+        let last_local_l1_batch = storage
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await?
+            .context("all L1 batches disappeared")?;
+        drop(storage);
+        let rollback_depth = last_local_l1_batch
+            .0
+            .saturating_sub(last_correct_l1_batch.0);
+        match self.max_rollback_depth {
+            Some(max_rollback_depth) if rollback_depth > max_rollback_depth => {
+                self.event_handler
+                    .report_reorg(last_correct_l1_batch, rollback_depth, false);
+                Err(Error::RollbackTooDeep {
+                    last_correct_l1_batch,
+                    rollback_depth,
+                    max_rollback_depth,
+                })
+            }
+            _ => {
+                self.event_handler
+                    .report_reorg(last_correct_l1_batch, rollback_depth, true);
+                Err(Error::ReorgDetected(last_correct_l1_batch))
+            }
+        }
+    }
+
+    async fn divergence_report(
+        &self,
+        last_correct_l1_batch: L1BatchNumber,
+    ) -> Result<DivergenceReport, HashMatchError> {
+        let diverged_l1_batch = last_correct_l1_batch + 1;
+        let mut storage = self.pool.connection().await?;
+        let local_root_hash = storage
+            .blocks_dal()
+            .get_l1_batch_state_root(diverged_l1_batch)
+            .await?;
+        drop(storage);
+        let remote_root_hash = self
+            .client
+            .l1_batch_root_hash(diverged_l1_batch)
+            .await?
+            .ok();
+
+        Ok(DivergenceReport {
+            last_correct_l1_batch,
+            diverged_l1_batch,
+            local_root_hash,
+            remote_root_hash,
+        })
+    }
+
+    /// Compares the given local L2 block with the same L2 block from main node. In the cross-verification mode,
+    /// compares logs blooms and emitted events in addition to block hashes.
+    async fn l2_blocks_match(&self, l2_block: L2BlockNumber) -> Result<bool, HashMatchError> {
+        let hashes_match = self.l2_block_hashes_match(l2_block).await?;
+        if !hashes_match || !self.cross_verification {
+            return Ok(hashes_match);
+        }
+        Ok(self.logs_blooms_match(l2_block).await? && self.events_match(l2_block).await?)
+    }
+
+    /// Compares events emitted in the given local L2 block with events from the same L2 block on the main node.
+    /// Only the event data (emitter address, topics and payload) and ordering are compared; other log fields
+    /// (e.g., the L2 block hash) are either covered by other checks or are not determined by execution.
+    async fn events_match(&self, l2_block: L2BlockNumber) -> Result<bool, HashMatchError> {
+        let mut storage = self.pool.connection().await?;
+        let filter = GetLogsFilter {
+            from_block: l2_block,
+            to_block: l2_block,
+            addresses: vec![],
+            topics: vec![],
+            l2_blocks: None,
+        };
+        let local_events = storage
+            .events_web3_dal()
+            .get_logs(filter, i32::MAX as usize)
+            .await?;
+        drop(storage);
+
+        let Some(remote_events) = self.client.l2_block_events(l2_block).await? else {
+            return Err(MissingData::L2Block.into());
+        };
+        let events_match = local_events.len() == remote_events.len()
+            && local_events
+                .iter()
+                .zip(&remote_events)
+                .all(|(local, remote)| {
+                    local.address == remote.address
+                        && local.topics == remote.topics
+                        && local.data == remote.data
+                });
+        if !events_match {
+            tracing::warn!(
+                "Divergence detected: local events don't match events from main node (L2 block #{l2_block})"
+            );
+        }
+        Ok(events_match)
+    }
+
+    async fn logs_blooms_match(&self, l2_block: L2BlockNumber) -> Result<bool, HashMatchError> {
+        let mut storage = self.pool.connection().await?;
+        let local_bloom = storage
+            .blocks_dal()
+            .get_l2_block_header(l2_block)
+            .await?
+            .with_context(|| format!("Header does not exist for local L2 block #{l2_block}"))?
+            .logs_bloom;
+        drop(storage);
+
+        let Some(remote_bloom) = self.client.l2_block_logs_bloom(l2_block).await? else {
+            return Err(MissingData::L2Block.into());
+        };
+        if remote_bloom != local_bloom {
+            tracing::warn!(
+                "Divergence detected: local logs bloom doesn't match the logs bloom from main node (L2 block #{l2_block})"
+            );
+        }
+        Ok(remote_bloom == local_bloom)
     }
 
     /// Compares hashes of the given local L2 block and the same L2 block from main node.
@@ -414,7 +675,7 @@ impl ReorgDetector {
             .with_context(|| format!("L1 batch #{l1_batch} does not have L2 blocks"))?;
         drop(storage);
 
-        self.l2_block_hashes_match(last_l2_block_in_batch).await
+        self.l2_blocks_match(last_l2_block_in_batch).await
     }
 
     /// Localizes a re-org: performs binary search to determine the last non-diverged L1 batch.
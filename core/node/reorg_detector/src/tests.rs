@@ -12,11 +12,12 @@ use assert_matches::assert_matches;
 use test_casing::{test_casing, Product};
 use tokio::sync::mpsc;
 use zksync_dal::{Connection, CoreDal};
+use zksync_health_check::CheckHealth;
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::{create_l1_batch, create_l2_block};
 use zksync_types::{
     block::{L2BlockHasher, L2BlockHeader},
-    ProtocolVersion,
+    Address, ProtocolVersion,
 };
 use zksync_web3_decl::jsonrpsee::core::ClientError as RpcError;
 
@@ -86,6 +87,8 @@ impl From<RpcErrorKind> for RpcError {
 #[derive(Debug, Default)]
 struct MockMainNodeClient {
     l2_block_hashes: BTreeMap<L2BlockNumber, H256>,
+    l2_block_logs_blooms: BTreeMap<L2BlockNumber, Bloom>,
+    l2_block_events: BTreeMap<L2BlockNumber, Vec<api::Log>>,
     l1_batch_root_hashes: BTreeMap<L1BatchNumber, Result<H256, MissingData>>,
     error_kind: Arc<Mutex<Option<RpcErrorKind>>>,
 }
@@ -125,6 +128,40 @@ impl MainNodeClient for MockMainNodeClient {
         Ok(self.l2_block_hashes.get(&number).copied())
     }
 
+    async fn l2_block_logs_bloom(
+        &self,
+        number: L2BlockNumber,
+    ) -> EnrichedClientResult<Option<Bloom>> {
+        self.check_error("l2_block_logs_bloom")
+            .map_err(|err| err.with_arg("number", &number))?;
+        if !self.l2_block_hashes.contains_key(&number) {
+            return Ok(None);
+        }
+        Ok(Some(
+            self.l2_block_logs_blooms
+                .get(&number)
+                .copied()
+                .unwrap_or_default(),
+        ))
+    }
+
+    async fn l2_block_events(
+        &self,
+        number: L2BlockNumber,
+    ) -> EnrichedClientResult<Option<Vec<api::Log>>> {
+        self.check_error("l2_block_events")
+            .map_err(|err| err.with_arg("number", &number))?;
+        if !self.l2_block_hashes.contains_key(&number) {
+            return Ok(None);
+        }
+        Ok(Some(
+            self.l2_block_events
+                .get(&number)
+                .cloned()
+                .unwrap_or_default(),
+        ))
+    }
+
     async fn l1_batch_root_hash(
         &self,
         number: L1BatchNumber,
@@ -154,6 +191,19 @@ impl HandleReorgDetectorEvent for mpsc::UnboundedSender<(L2BlockNumber, L1BatchN
         // Do nothing
     }
 
+    fn report_cross_verification_failure(&mut self, _report: &DivergenceReport) {
+        // Do nothing
+    }
+
+    fn report_reorg(
+        &mut self,
+        _last_correct_l1_batch: L1BatchNumber,
+        _rollback_depth: u32,
+        _auto_rollback: bool,
+    ) {
+        // Do nothing
+    }
+
     fn start_shutting_down(&mut self) {
         // Do nothing
     }
@@ -170,6 +220,8 @@ fn create_mock_detector(
         pool,
         sleep_interval: Duration::from_millis(10),
         health_check,
+        cross_verification: false,
+        max_rollback_depth: None,
     }
 }
 
@@ -327,6 +379,171 @@ async fn reorg_is_detected_on_batch_hash_mismatch() {
     assert!(detector.check_reorg_presence(stop_receiver).await.unwrap());
 }
 
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn rollback_depth_is_bounded(exceed_limit: bool) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let genesis_batch = insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let mut client = MockMainNodeClient::default();
+    client.l2_block_hashes.insert(
+        L2BlockNumber(0),
+        L2BlockHasher::legacy_hash(L2BlockNumber(0)),
+    );
+    client
+        .l1_batch_root_hashes
+        .insert(L1BatchNumber(0), Ok(genesis_batch.root_hash));
+
+    let l2_block_hash = H256::from_low_u64_be(23);
+    for number in 1..=3 {
+        client
+            .l2_block_hashes
+            .insert(L2BlockNumber(number), l2_block_hash);
+        client
+            .l1_batch_root_hashes
+            .insert(L1BatchNumber(number), Ok(H256::repeat_byte(number as u8)));
+        store_l2_block(&mut storage, number, l2_block_hash).await;
+        // L1 batches starting from #2 diverge from the main node.
+        let local_hash = if number == 1 {
+            H256::repeat_byte(1)
+        } else {
+            H256::repeat_byte(0xff)
+        };
+        seal_l1_batch(&mut storage, number, local_hash).await;
+    }
+
+    let max_rollback_depth = if exceed_limit { 1 } else { 2 };
+    let mut detector = create_mock_detector(client, pool.clone())
+        .with_max_rollback_depth(Some(max_rollback_depth));
+    let result = detector.check_consistency().await;
+    if exceed_limit {
+        assert_matches!(
+            result,
+            Err(Error::RollbackTooDeep {
+                last_correct_l1_batch: L1BatchNumber(1),
+                rollback_depth: 2,
+                max_rollback_depth: 1,
+            })
+        );
+    } else {
+        assert_matches!(result, Err(Error::ReorgDetected(L1BatchNumber(1))));
+    }
+
+    let health = detector.health_check().check_health().await;
+    assert_matches!(health.status(), HealthStatus::Affected);
+    let details = health.details().unwrap();
+    assert_eq!(details["rollback_depth"], 2);
+    assert_eq!(details["auto_rollback"], !exceed_limit);
+}
+
+#[tokio::test]
+async fn cross_verification_halts_on_logs_bloom_mismatch() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let genesis_batch = insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let mut client = MockMainNodeClient::default();
+    client.l2_block_hashes.insert(
+        L2BlockNumber(0),
+        L2BlockHasher::legacy_hash(L2BlockNumber(0)),
+    );
+    client
+        .l1_batch_root_hashes
+        .insert(L1BatchNumber(0), Ok(genesis_batch.root_hash));
+
+    let l2_block_hash = H256::from_low_u64_be(23);
+    for number in [1, 2] {
+        client
+            .l2_block_hashes
+            .insert(L2BlockNumber(number), l2_block_hash);
+        client
+            .l1_batch_root_hashes
+            .insert(L1BatchNumber(number), Ok(H256::repeat_byte(number as u8)));
+    }
+    // Events emitted in L2 block #2 differ from the local ones, while L2 block hashes match.
+    client
+        .l2_block_logs_blooms
+        .insert(L2BlockNumber(2), Bloom::repeat_byte(1));
+
+    let mut detector = create_mock_detector(client, pool.clone()).with_cross_verification(true);
+    store_l2_block(&mut storage, 1, l2_block_hash).await;
+    seal_l1_batch(&mut storage, 1, H256::repeat_byte(1)).await;
+    store_l2_block(&mut storage, 2, l2_block_hash).await;
+
+    let err = detector.check_consistency().await.unwrap_err();
+    let Error::Diverged(report) = err else {
+        panic!("Unexpected error: {err:?}");
+    };
+    assert_eq!(
+        *report,
+        DivergenceReport {
+            last_correct_l1_batch: L1BatchNumber(1),
+            diverged_l1_batch: L1BatchNumber(2),
+            local_root_hash: None,
+            remote_root_hash: Some(H256::repeat_byte(2)),
+        }
+    );
+}
+
+#[tokio::test]
+async fn cross_verification_halts_on_events_mismatch() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let genesis_batch = insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let mut client = MockMainNodeClient::default();
+    client.l2_block_hashes.insert(
+        L2BlockNumber(0),
+        L2BlockHasher::legacy_hash(L2BlockNumber(0)),
+    );
+    client
+        .l1_batch_root_hashes
+        .insert(L1BatchNumber(0), Ok(genesis_batch.root_hash));
+
+    let l2_block_hash = H256::from_low_u64_be(23);
+    for number in [1, 2] {
+        client
+            .l2_block_hashes
+            .insert(L2BlockNumber(number), l2_block_hash);
+        client
+            .l1_batch_root_hashes
+            .insert(L1BatchNumber(number), Ok(H256::repeat_byte(number as u8)));
+    }
+    // Main node emits an event in L2 block #2 that isn't emitted locally, while L2 block hashes and logs blooms match.
+    let event = api::Log {
+        address: Address::repeat_byte(1),
+        topics: vec![H256::repeat_byte(2)],
+        data: vec![3].into(),
+        block_hash: Some(l2_block_hash),
+        block_number: Some(2_u64.into()),
+        l1_batch_number: Some(2_u64.into()),
+        transaction_hash: Some(H256::repeat_byte(4)),
+        transaction_index: Some(0_u64.into()),
+        log_index: Some(0_u64.into()),
+        transaction_log_index: Some(0_u64.into()),
+        log_type: None,
+        removed: Some(false),
+        block_timestamp: None,
+    };
+    client.l2_block_events.insert(L2BlockNumber(2), vec![event]);
+
+    let mut detector = create_mock_detector(client, pool.clone()).with_cross_verification(true);
+    store_l2_block(&mut storage, 1, l2_block_hash).await;
+    seal_l1_batch(&mut storage, 1, H256::repeat_byte(1)).await;
+    store_l2_block(&mut storage, 2, l2_block_hash).await;
+
+    let err = detector.check_consistency().await.unwrap_err();
+    let Error::Diverged(report) = err else {
+        panic!("Unexpected error: {err:?}");
+    };
+    assert_eq!(report.last_correct_l1_batch, L1BatchNumber(1));
+    assert_eq!(report.diverged_l1_batch, L1BatchNumber(2));
+}
+
 #[tokio::test]
 async fn reorg_is_detected_on_l2_block_hash_mismatch() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -668,6 +885,20 @@ impl MainNodeClient for SlowMainNode {
         })
     }
 
+    async fn l2_block_logs_bloom(
+        &self,
+        number: L2BlockNumber,
+    ) -> EnrichedClientResult<Option<Bloom>> {
+        Ok((number == L2BlockNumber(0)).then(Bloom::zero))
+    }
+
+    async fn l2_block_events(
+        &self,
+        number: L2BlockNumber,
+    ) -> EnrichedClientResult<Option<Vec<api::Log>>> {
+        Ok((number == L2BlockNumber(0)).then(Vec::new))
+    }
+
     async fn l1_batch_root_hash(
         &self,
         number: L1BatchNumber,
@@ -0,0 +1,116 @@
+//! Read-only JSON-RPC API served from a snapshot without Postgres.
+//!
+//! The server recovers VM state for the newest snapshot on the main node from snapshot files in an object store
+//! into RocksDB, and then serves state-reading methods (`eth_getBalance`, `eth_getTransactionCount`,
+//! `eth_getStorageAt` and `eth_getCode`) for the snapshot L2 block, together with `eth_chainId`, `eth_blockNumber`
+//! and `zks_L1BatchNumber`. Snapshots don't contain blocks, transactions or events, so methods querying them
+//! are not supported. The state is not updated after recovery; to serve newer state, the RocksDB directory
+//! should be removed so that the state is recovered from a newer snapshot on restart.
+
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+
+use anyhow::Context as _;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_object_store::ObjectStore;
+use zksync_types::{L1BatchNumber, L2BlockNumber, L2ChainId};
+use zksync_web3_decl::{
+    client::{DynClient, L2},
+    jsonrpsee::server::ServerBuilder,
+};
+
+use crate::{rpc::build_rpc_methods, state::SnapshotState};
+
+mod rpc;
+mod state;
+#[cfg(test)]
+mod tests;
+
+/// Configuration of [`SnapshotApiServer`].
+#[derive(Debug, Clone)]
+pub struct SnapshotApiConfig {
+    /// Address to bind the HTTP JSON-RPC server to.
+    pub bind_address: SocketAddr,
+    /// Path to the RocksDB directory with the recovered snapshot state.
+    pub state_path: PathBuf,
+    pub l2_chain_id: L2ChainId,
+}
+
+#[derive(Debug, Serialize)]
+struct SnapshotApiHealth {
+    l1_batch_number: L1BatchNumber,
+    l2_block_number: L2BlockNumber,
+}
+
+/// HTTP JSON-RPC server serving state at the snapshot L2 block.
+#[derive(Debug)]
+pub struct SnapshotApiServer {
+    config: SnapshotApiConfig,
+    main_node_client: Box<DynClient<L2>>,
+    object_store: Arc<dyn ObjectStore>,
+    health_updater: HealthUpdater,
+}
+
+impl SnapshotApiServer {
+    pub fn new(
+        config: SnapshotApiConfig,
+        main_node_client: Box<DynClient<L2>>,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> Self {
+        Self {
+            config,
+            main_node_client: main_node_client.for_component("snapshot_api"),
+            object_store,
+            health_updater: ReactiveHealthCheck::new("snapshot_api").1,
+        }
+    }
+
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let state = SnapshotState::load_or_recover(
+            &self.config.state_path,
+            self.main_node_client.as_ref(),
+            self.object_store.as_ref(),
+            &stop_receiver,
+        )
+        .await?;
+        let Some(state) = state else {
+            tracing::info!("Stop request received during snapshot recovery, shutting down");
+            return Ok(());
+        };
+        let health = SnapshotApiHealth {
+            l1_batch_number: state.l1_batch_number,
+            l2_block_number: state.l2_block_number,
+        };
+
+        let methods = build_rpc_methods(state, self.config.l2_chain_id)?;
+        let server = ServerBuilder::default()
+            .http_only()
+            .build(self.config.bind_address)
+            .await
+            .context("Failed building HTTP JSON-RPC server")?;
+        let local_addr = server
+            .local_addr()
+            .context("Failed getting local address for JSON-RPC server")?;
+        let server_handle = server.start(methods);
+        tracing::info!("Initialized snapshot API on {local_addr:?}");
+        self.health_updater
+            .update(Health::from(HealthStatus::Ready).with_details(health));
+
+        tokio::select! {
+            _ = stop_receiver.changed() => {
+                tracing::info!("Stop request received, snapshot API is shutting down");
+            }
+            () = server_handle.clone().stopped() => {
+                anyhow::bail!("Snapshot API server stopped unexpectedly");
+            }
+        }
+        server_handle.stop().ok();
+        server_handle.stopped().await;
+        Ok(())
+    }
+}
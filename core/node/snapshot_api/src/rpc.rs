@@ -0,0 +1,142 @@
+//! JSON-RPC methods served from the snapshot state.
+
+use zksync_types::{
+    api::{BlockId, BlockIdVariant, BlockNumber},
+    bytecode::{trim_padded_evm_bytecode, BytecodeHash, BytecodeMarker},
+    get_code_key, get_nonce_key, h256_to_u256, u256_to_h256,
+    utils::{decompose_full_nonce, storage_key_for_eth_balance},
+    web3::Bytes,
+    AccountTreeId, Address, L2ChainId, StorageKey, FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256,
+    U256, U64,
+};
+use zksync_web3_decl::jsonrpsee::{
+    types::{error::ErrorCode, ErrorObjectOwned, Params},
+    Methods, RpcModule,
+};
+
+use crate::state::SnapshotState;
+
+type RpcResult<T> = Result<T, ErrorObjectOwned>;
+
+/// Parses an address followed by an optional block ID, which is the signature of most state-reading methods.
+fn parse_address_and_block(params: Params<'_>) -> RpcResult<(Address, Option<BlockIdVariant>)> {
+    let mut params = params.sequence();
+    Ok((params.next()?, params.optional_next()?))
+}
+
+fn internal_error(err: anyhow::Error) -> ErrorObjectOwned {
+    tracing::warn!("Internal error: {err:#}");
+    ErrorCode::InternalError.into()
+}
+
+#[derive(Debug)]
+struct RpcContext {
+    state: SnapshotState,
+    l2_chain_id: L2ChainId,
+}
+
+impl RpcContext {
+    /// Checks that the requested block refers to the snapshot L2 block, which is the only block with available state.
+    fn ensure_snapshot_block(&self, block: Option<BlockIdVariant>) -> RpcResult<()> {
+        let snapshot_block = self.state.l2_block_number;
+        let is_snapshot_block = match block.map(BlockId::from) {
+            None
+            | Some(BlockId::Number(
+                BlockNumber::Latest | BlockNumber::Committed | BlockNumber::Pending,
+            )) => true,
+            Some(BlockId::Number(BlockNumber::Number(number))) => {
+                number == U64::from(snapshot_block.0)
+            }
+            Some(_) => false,
+        };
+        if is_snapshot_block {
+            Ok(())
+        } else {
+            let message =
+                format!("state is only available for the snapshot L2 block #{snapshot_block}");
+            Err(ErrorObjectOwned::owned(
+                ErrorCode::InvalidParams.code(),
+                message,
+                None::<()>,
+            ))
+        }
+    }
+
+    async fn balance(&self, address: Address) -> anyhow::Result<U256> {
+        let value = self
+            .state
+            .read_value(storage_key_for_eth_balance(&address))
+            .await?;
+        Ok(h256_to_u256(value))
+    }
+
+    async fn transaction_count(&self, address: Address) -> anyhow::Result<U256> {
+        let full_nonce = self.state.read_value(get_nonce_key(&address)).await?;
+        Ok(decompose_full_nonce(h256_to_u256(full_nonce)).0)
+    }
+
+    async fn storage_at(&self, address: Address, idx: U256) -> anyhow::Result<H256> {
+        let key = StorageKey::new(AccountTreeId::new(address), u256_to_h256(idx));
+        self.state.read_value(key).await
+    }
+
+    async fn code(&self, address: Address) -> anyhow::Result<Bytes> {
+        let bytecode_hash = self.state.read_value(get_code_key(&address)).await?;
+        if bytecode_hash.is_zero() || bytecode_hash == FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH {
+            return Ok(Bytes::default());
+        }
+        let bytecode = self
+            .state
+            .load_factory_dep(bytecode_hash)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("bytecode with hash {bytecode_hash:?} is missing"))?;
+        // Same as in the full API server, EVM bytecodes are stored padded.
+        if BytecodeMarker::new(bytecode_hash) == Some(BytecodeMarker::Evm) {
+            let hash = BytecodeHash::try_from(bytecode_hash)?;
+            let trimmed = trim_padded_evm_bytecode(hash, &bytecode)?;
+            return Ok(trimmed.to_vec().into());
+        }
+        Ok(bytecode.into())
+    }
+}
+
+/// Builds RPC methods supported by the snapshot API.
+pub(crate) fn build_rpc_methods(
+    state: SnapshotState,
+    l2_chain_id: L2ChainId,
+) -> anyhow::Result<Methods> {
+    let mut rpc = RpcModule::new(RpcContext { state, l2_chain_id });
+    rpc.register_method("eth_chainId", |_params, ctx, _ext| {
+        U64::from(ctx.l2_chain_id.as_u64())
+    })?;
+    rpc.register_method("eth_blockNumber", |_params, ctx, _ext| {
+        U64::from(ctx.state.l2_block_number.0)
+    })?;
+    rpc.register_method("zks_L1BatchNumber", |_params, ctx, _ext| {
+        U64::from(ctx.state.l1_batch_number.0)
+    })?;
+
+    rpc.register_async_method("eth_getBalance", |params, ctx, _ext| async move {
+        let (address, block) = parse_address_and_block(params)?;
+        ctx.ensure_snapshot_block(block)?;
+        ctx.balance(address).await.map_err(internal_error)
+    })?;
+    rpc.register_async_method("eth_getTransactionCount", |params, ctx, _ext| async move {
+        let (address, block) = parse_address_and_block(params)?;
+        ctx.ensure_snapshot_block(block)?;
+        ctx.transaction_count(address).await.map_err(internal_error)
+    })?;
+    rpc.register_async_method("eth_getStorageAt", |params, ctx, _ext| async move {
+        let mut params = params.sequence();
+        let (address, idx): (Address, U256) = (params.next()?, params.next()?);
+        let block = params.optional_next()?;
+        ctx.ensure_snapshot_block(block)?;
+        ctx.storage_at(address, idx).await.map_err(internal_error)
+    })?;
+    rpc.register_async_method("eth_getCode", |params, ctx, _ext| async move {
+        let (address, block) = parse_address_and_block(params)?;
+        ctx.ensure_snapshot_block(block)?;
+        ctx.code(address).await.map_err(internal_error)
+    })?;
+    Ok(rpc.into())
+}
@@ -0,0 +1,197 @@
+//! VM state at the snapshot L2 block, recovered from snapshot files into RocksDB.
+
+use std::{collections::HashMap, path::Path};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_object_store::ObjectStore;
+use zksync_state::{interface::ReadStorage, RocksdbStorage, RocksdbStorageBuilder};
+use zksync_types::{
+    bytecode::BytecodeHash,
+    snapshots::{
+        SnapshotFactoryDependencies, SnapshotManifest, SnapshotStorageLog,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey, SnapshotVersion,
+    },
+    L1BatchNumber, L2BlockNumber, StorageKey, StorageValue, H256,
+};
+use zksync_web3_decl::{
+    client::{DynClient, L2},
+    error::ClientRpcContext,
+    namespaces::SnapshotsNamespaceClient,
+};
+
+/// VM state at the snapshot L2 block. The state is immutable; it's never updated after recovery.
+#[derive(Debug, Clone)]
+pub(crate) struct SnapshotState {
+    storage: RocksdbStorage,
+    pub l1_batch_number: L1BatchNumber,
+    pub l2_block_number: L2BlockNumber,
+}
+
+impl SnapshotState {
+    /// Loads the state from RocksDB at the specified path, or recovers it from the newest snapshot on the main node
+    /// if RocksDB is empty. Returns `Ok(None)` if recovery was interrupted by a stop request.
+    pub async fn load_or_recover(
+        path: &Path,
+        main_node_client: &DynClient<L2>,
+        object_store: &dyn ObjectStore,
+        stop_receiver: &watch::Receiver<bool>,
+    ) -> anyhow::Result<Option<Self>> {
+        let builder = RocksdbStorage::builder(path)
+            .await
+            .context("failed initializing RocksDB")?;
+        if let Some(next_l1_batch) = builder.l1_batch_number().await {
+            let l1_batch_number = next_l1_batch
+                .0
+                .checked_sub(1)
+                .with_context(|| format!("RocksDB at {path:?} is not recovered from a snapshot"))?;
+            let l1_batch_number = L1BatchNumber(l1_batch_number);
+            let manifest = main_node_client
+                .get_snapshot_manifest(Some(l1_batch_number))
+                .rpc_context("get_snapshot_manifest")
+                .with_arg("l1_batch_number", &l1_batch_number)
+                .await?
+                .with_context(|| {
+                    format!(
+                        "main node doesn't have a snapshot for L1 batch #{l1_batch_number} recovered in RocksDB"
+                    )
+                })?;
+            let l2_block_number = manifest.target().l2_block_number;
+            let storage = builder
+                .into_initialized()
+                .await
+                .context("RocksDB state disappeared")?;
+            tracing::info!(
+                "Loaded snapshot state for L1 batch #{l1_batch_number} (L2 block #{l2_block_number}) from RocksDB"
+            );
+            return Ok(Some(Self {
+                storage,
+                l1_batch_number,
+                l2_block_number,
+            }));
+        }
+
+        let manifest = main_node_client
+            .get_snapshot_manifest(None)
+            .rpc_context("get_snapshot_manifest")
+            .await?
+            .context("main node doesn't have snapshots")?;
+        manifest.validate()?;
+        let target = manifest.target();
+        let (l1_batch_number, l2_block_number) = (target.l1_batch_number, target.l2_block_number);
+        tracing::info!(
+            "Recovering snapshot state for L1 batch #{l1_batch_number} (L2 block #{l2_block_number}) \
+             from {} snapshot(s)",
+            manifest.deltas.len() + 1
+        );
+
+        let Some(storage) = Self::recover(builder, &manifest, object_store, stop_receiver).await?
+        else {
+            return Ok(None);
+        };
+        tracing::info!("Recovered snapshot state for L1 batch #{l1_batch_number}");
+        Ok(Some(Self {
+            storage,
+            l1_batch_number,
+            l2_block_number,
+        }))
+    }
+
+    async fn recover(
+        mut builder: RocksdbStorageBuilder,
+        manifest: &SnapshotManifest,
+        object_store: &dyn ObjectStore,
+        stop_receiver: &watch::Receiver<bool>,
+    ) -> anyhow::Result<Option<RocksdbStorage>> {
+        for header in manifest.snapshots() {
+            let l1_batch_number = header.l1_batch_number;
+            let factory_deps: SnapshotFactoryDependencies =
+                object_store.get(l1_batch_number).await.with_context(|| {
+                    format!("failed fetching factory deps for snapshot L1 batch #{l1_batch_number}")
+                })?;
+            let factory_deps: HashMap<_, _> = factory_deps
+                .factory_deps
+                .into_iter()
+                .map(|dep| {
+                    // Old snapshots may not contain hashes; such bytecodes are EraVM ones.
+                    let hash = dep
+                        .hash
+                        .unwrap_or_else(|| BytecodeHash::for_bytecode(&dep.bytecode.0).value());
+                    (hash, dep.bytecode.0)
+                })
+                .collect();
+            builder.save_snapshot_factory_deps(factory_deps).await?;
+        }
+
+        let base = &manifest.base;
+        let version = SnapshotVersion::try_from(base.version)
+            .with_context(|| format!("unrecognized snapshot version: {}", base.version))?;
+        let chunk_count = base.storage_logs_chunks.len();
+        for chunk in &base.storage_logs_chunks {
+            if *stop_receiver.borrow() {
+                return Ok(None);
+            }
+
+            let chunk_id = chunk.chunk_id;
+            let storage_key = SnapshotStorageLogsStorageKey {
+                l1_batch_number: base.l1_batch_number,
+                chunk_id,
+            };
+            let storage_logs = if version.has_hashed_keys() {
+                let logs: SnapshotStorageLogsChunk = object_store
+                    .get(storage_key)
+                    .await
+                    .with_context(|| format!("failed fetching storage logs {storage_key:?}"))?;
+                if let Some(index) = &chunk.index {
+                    index.verify(&logs)?;
+                }
+                logs.storage_logs
+            } else {
+                let logs: SnapshotStorageLogsChunk<StorageKey> = object_store
+                    .get(storage_key)
+                    .await
+                    .with_context(|| format!("failed fetching storage logs {storage_key:?}"))?;
+                logs.storage_logs
+                    .into_iter()
+                    .map(SnapshotStorageLog::drop_key_preimage)
+                    .collect()
+            };
+            builder.save_snapshot_storage_logs(storage_logs).await?;
+
+            // Values from differential snapshots overwrite the base values, so they must be saved in order.
+            for delta in &manifest.deltas {
+                let storage_key = SnapshotStorageLogsStorageKey {
+                    l1_batch_number: delta.l1_batch_number,
+                    chunk_id,
+                };
+                let logs: SnapshotStorageLogsChunk = object_store
+                    .get(storage_key)
+                    .await
+                    .with_context(|| format!("failed fetching storage logs {storage_key:?}"))?;
+                builder
+                    .save_snapshot_storage_logs(logs.storage_logs)
+                    .await?;
+            }
+            tracing::info!("Recovered storage logs chunk {chunk_id} / {chunk_count}");
+        }
+
+        let storage = builder
+            .finish_snapshot_recovery(manifest.target().l1_batch_number)
+            .await?;
+        Ok(Some(storage))
+    }
+
+    pub async fn read_value(&self, key: StorageKey) -> anyhow::Result<StorageValue> {
+        let mut storage = self.storage.clone();
+        tokio::task::spawn_blocking(move || storage.read_value(&key))
+            .await
+            .context("panicked reading storage value")
+    }
+
+    pub async fn load_factory_dep(&self, hash: H256) -> anyhow::Result<Option<Vec<u8>>> {
+        let mut storage = self.storage.clone();
+        tokio::task::spawn_blocking(move || storage.load_factory_dep(hash))
+            .await
+            .context("panicked loading factory dependency")
+    }
+}
@@ -0,0 +1,196 @@
+//! Tests for the snapshot API.
+
+use tempfile::TempDir;
+use zksync_object_store::MockObjectStore;
+use zksync_types::{
+    api::BlockNumber,
+    bytecode::BytecodeHash,
+    get_code_key, get_nonce_key,
+    snapshots::{
+        SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotHeader, SnapshotManifest,
+        SnapshotStorageLog, SnapshotStorageLogsChunk, SnapshotStorageLogsChunkMetadata,
+        SnapshotStorageLogsStorageKey,
+    },
+    utils::storage_key_for_eth_balance,
+    web3::Bytes,
+    AccountTreeId, Address, StorageKey, H256, U256, U64,
+};
+use zksync_web3_decl::{
+    client::MockClient,
+    jsonrpsee::{core::traits::ToRpcParams, rpc_params, Methods},
+};
+
+use super::*;
+
+const BASE_L1_BATCH: L1BatchNumber = L1BatchNumber(1);
+const DELTA_L1_BATCH: L1BatchNumber = L1BatchNumber(3);
+
+fn snapshot_header(
+    l1_batch_number: L1BatchNumber,
+    l2_block_number: L2BlockNumber,
+    base_l1_batch_number: Option<L1BatchNumber>,
+) -> SnapshotHeader {
+    SnapshotHeader {
+        version: 1,
+        l1_batch_number,
+        l2_block_number,
+        storage_logs_chunks: vec![SnapshotStorageLogsChunkMetadata {
+            chunk_id: 0,
+            filepath: "file0".to_owned(),
+            index: None,
+        }],
+        factory_deps_filepath: "factory_deps".to_owned(),
+        base_l1_batch_number,
+    }
+}
+
+fn storage_log(key: StorageKey, value: H256, enumeration_index: u64) -> SnapshotStorageLog {
+    SnapshotStorageLog {
+        key: key.hashed_key(),
+        value,
+        l1_batch_number_of_initial_write: BASE_L1_BATCH,
+        enumeration_index,
+    }
+}
+
+async fn put_snapshot(
+    object_store: &dyn ObjectStore,
+    l1_batch_number: L1BatchNumber,
+    factory_deps: Vec<SnapshotFactoryDependency>,
+    storage_logs: Vec<SnapshotStorageLog>,
+) {
+    object_store
+        .put(
+            l1_batch_number,
+            &SnapshotFactoryDependencies { factory_deps },
+        )
+        .await
+        .unwrap();
+    let key = SnapshotStorageLogsStorageKey {
+        l1_batch_number,
+        chunk_id: 0,
+    };
+    object_store
+        .put(key, &SnapshotStorageLogsChunk { storage_logs })
+        .await
+        .unwrap();
+}
+
+fn mock_main_node_client(manifest: SnapshotManifest) -> Box<DynClient<L2>> {
+    let client = MockClient::builder(L2::default())
+        .method(
+            "snapshots_getSnapshotManifest",
+            move |_number: Option<L1BatchNumber>| Ok(Some(manifest.clone())),
+        )
+        .build();
+    Box::new(client)
+}
+
+async fn call<T>(methods: &Methods, method: &str, params: impl ToRpcParams + Send) -> T
+where
+    T: serde::de::DeserializeOwned + Clone,
+{
+    methods.call(method, params).await.unwrap()
+}
+
+#[tokio::test]
+async fn state_is_recovered_from_snapshot_files_and_served() {
+    let account = Address::repeat_byte(1);
+    let contract = Address::repeat_byte(2);
+    let bytecode = vec![0; 32];
+    let bytecode_hash = BytecodeHash::for_bytecode(&bytecode).value();
+    let slot_key = StorageKey::new(AccountTreeId::new(contract), H256::from_low_u64_be(1));
+
+    let object_store = MockObjectStore::arc();
+    let base_logs = vec![
+        storage_log(
+            storage_key_for_eth_balance(&account),
+            H256::from_low_u64_be(100),
+            1,
+        ),
+        storage_log(get_nonce_key(&account), H256::from_low_u64_be(5), 2),
+        storage_log(get_code_key(&contract), bytecode_hash, 3),
+        storage_log(slot_key, H256::repeat_byte(0x23), 4),
+    ];
+    let factory_deps = vec![SnapshotFactoryDependency {
+        bytecode: Bytes(bytecode.clone()),
+        hash: Some(bytecode_hash),
+    }];
+    put_snapshot(&*object_store, BASE_L1_BATCH, factory_deps, base_logs).await;
+    // The differential snapshot updates the account balance.
+    let delta_logs = vec![storage_log(
+        storage_key_for_eth_balance(&account),
+        H256::from_low_u64_be(42),
+        1,
+    )];
+    put_snapshot(&*object_store, DELTA_L1_BATCH, vec![], delta_logs).await;
+
+    let manifest = SnapshotManifest {
+        base: snapshot_header(BASE_L1_BATCH, L2BlockNumber(2), None),
+        deltas: vec![snapshot_header(
+            DELTA_L1_BATCH,
+            L2BlockNumber(6),
+            Some(BASE_L1_BATCH),
+        )],
+    };
+    let client = mock_main_node_client(manifest);
+    let dir = TempDir::new().unwrap();
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let state =
+        SnapshotState::load_or_recover(dir.path(), client.as_ref(), &*object_store, &stop_receiver)
+            .await
+            .unwrap()
+            .expect("recovery was interrupted");
+    assert_eq!(state.l1_batch_number, DELTA_L1_BATCH);
+    assert_eq!(state.l2_block_number, L2BlockNumber(6));
+
+    let methods = build_rpc_methods(state, L2ChainId::default()).unwrap();
+    let block_number: U64 = call(&methods, "eth_blockNumber", rpc_params![]).await;
+    assert_eq!(block_number, U64::from(6));
+    let balance: U256 = call(&methods, "eth_getBalance", rpc_params![account]).await;
+    assert_eq!(balance, U256::from(42));
+    let nonce: U256 = call(
+        &methods,
+        "eth_getTransactionCount",
+        rpc_params![account, "latest"],
+    )
+    .await;
+    assert_eq!(nonce, U256::from(5));
+    let code: Bytes = call(&methods, "eth_getCode", rpc_params![contract]).await;
+    assert_eq!(code.0, bytecode);
+    let code: Bytes = call(&methods, "eth_getCode", rpc_params![account]).await;
+    assert!(code.0.is_empty());
+    let slot: H256 = call(
+        &methods,
+        "eth_getStorageAt",
+        rpc_params![contract, U256::from(1), BlockNumber::Number(6_u64.into())],
+    )
+    .await;
+    assert_eq!(slot, H256::repeat_byte(0x23));
+
+    let err = methods
+        .call::<_, U256>(
+            "eth_getBalance",
+            rpc_params![account, BlockNumber::Number(5_u64.into())],
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("snapshot L2 block #6"), "{err}");
+    // Release RocksDB so that it can be reopened.
+    drop(methods);
+
+    // The recovered state should be loaded without accessing the object store.
+    let empty_object_store = MockObjectStore::arc();
+    let state = SnapshotState::load_or_recover(
+        dir.path(),
+        client.as_ref(),
+        &*empty_object_store,
+        &stop_receiver,
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(state.l1_batch_number, DELTA_L1_BATCH);
+    let slot = state.read_value(slot_key).await.unwrap();
+    assert_eq!(slot, H256::repeat_byte(0x23));
+}
@@ -0,0 +1,459 @@
+//! Deterministic development mode for the state keeper.
+//!
+//! In this mode, each transaction is sealed in a separate L2 block, block timestamps are selected without waiting,
+//! and block production can be controlled via [`DevModeControl`] (e.g., by the `evm` API namespace). Since the VM
+//! doesn't allow empty non-fictive L2 blocks, mining a block seals the pending L1 batch. Snapshots
+//! are restricted to the pending (unsealed) L1 batch: reverting to a snapshot removes the L2 blocks produced after it
+//! from the storage and makes the state keeper re-execute the remaining pending L2 blocks. Since sealed L1 batches
+//! are never affected, neither the Merkle tree nor the state keeper cache need to be rolled back.
+
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use tokio::sync::{mpsc, oneshot};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::{L1BatchNumber, L2BlockNumber};
+
+use crate::{
+    io::{seal_logic::l2_block_seal_subtasks::L2BlockSealProcess, IoCursor},
+    seal_criteria::L1BatchSealRequest,
+    timestamp_policy::TimestampPolicy,
+    utils::millis_since_epoch,
+    MempoolGuard, UpdatesManager,
+};
+
+/// Error returned by [`DevModeControl`] methods if the state keeper is not running.
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[error("state keeper is not running")]
+pub struct StateKeeperUnavailable;
+
+#[derive(Debug)]
+enum DevModeRequest {
+    Mine(oneshot::Sender<L2BlockNumber>),
+    Snapshot(oneshot::Sender<u64>),
+    Revert {
+        snapshot_id: u64,
+        response: oneshot::Sender<bool>,
+    },
+}
+
+/// Handle allowing to control block production in the development mode.
+#[derive(Debug, Clone)]
+pub struct DevModeControl {
+    requests: mpsc::UnboundedSender<DevModeRequest>,
+    time_offset: Arc<AtomicU64>,
+}
+
+impl DevModeControl {
+    async fn send<T>(
+        &self,
+        request: impl FnOnce(oneshot::Sender<T>) -> DevModeRequest,
+    ) -> Result<T, StateKeeperUnavailable> {
+        let (response_sender, response) = oneshot::channel();
+        self.requests
+            .send(request(response_sender))
+            .map_err(|_| StateKeeperUnavailable)?;
+        response.await.map_err(|_| StateKeeperUnavailable)
+    }
+
+    /// Seals the pending L1 batch, which produces a new (fictive) L2 block without transactions. Returns the number
+    /// of the last sealed L2 block once the batch is persisted. If the pending batch has no transactions, no block
+    /// is produced.
+    ///
+    /// Sealing the batch discards all snapshots taken in it.
+    pub async fn mine(&self) -> Result<L2BlockNumber, StateKeeperUnavailable> {
+        self.send(DevModeRequest::Mine).await
+    }
+
+    /// Shifts timestamps of the following L2 blocks by `seconds` into the future. Returns the total time offset
+    /// in seconds.
+    pub fn increase_time(&self, seconds: u64) -> u64 {
+        let prev_offset = self
+            .time_offset
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |offset| {
+                Some(offset.saturating_add(seconds))
+            })
+            .unwrap(); // The closure always returns `Some(_)`
+        prev_offset.saturating_add(seconds)
+    }
+
+    /// Returns the current time offset in seconds.
+    pub fn time_offset(&self) -> u64 {
+        self.time_offset.load(Ordering::Relaxed)
+    }
+
+    /// Takes a snapshot of the chain state after the last sealed L2 block. Returns an ID that can be passed
+    /// to [`Self::revert()`].
+    pub async fn snapshot(&self) -> Result<u64, StateKeeperUnavailable> {
+        self.send(DevModeRequest::Snapshot).await
+    }
+
+    /// Reverts the chain state (including the time offset) to the snapshot with the specified ID. The snapshot
+    /// and all snapshots taken after it are discarded. Returns `false` if the snapshot is unknown, or if the L1 batch
+    /// it was taken in has been sealed since then.
+    pub async fn revert(&self, snapshot_id: u64) -> Result<bool, StateKeeperUnavailable> {
+        self.send(|response| DevModeRequest::Revert {
+            snapshot_id,
+            response,
+        })
+        .await
+    }
+}
+
+/// Timestamp policy used in the development mode. Uses the wall-clock time shifted by the offset
+/// set via [`DevModeControl::increase_time()`]. Unlike [`RealTimePolicy`](crate::timestamp_policy::RealTimePolicy),
+/// the policy never waits; if the shifted time doesn't exceed the previous timestamp, the previous timestamp
+/// is incremented.
+#[derive(Debug)]
+pub struct DevModeTimestampPolicy {
+    time_offset: Arc<AtomicU64>,
+}
+
+impl DevModeTimestampPolicy {
+    fn current_timestamp(&self) -> u64 {
+        let now = (millis_since_epoch() / 1_000) as u64;
+        now.saturating_add(self.time_offset.load(Ordering::Relaxed))
+    }
+}
+
+#[async_trait]
+impl TimestampPolicy for DevModeTimestampPolicy {
+    async fn next_timestamp(
+        &self,
+        prev_timestamp: u64,
+        _l2_block: L2BlockNumber,
+    ) -> anyhow::Result<u64> {
+        let next_timestamp = prev_timestamp
+            .checked_add(1)
+            .context("timestamp overflow")?;
+        Ok(self.current_timestamp().max(next_timestamp))
+    }
+
+    fn refresh_timestamp(&self, timestamp: &mut u64) {
+        *timestamp = self.current_timestamp().max(*timestamp);
+    }
+}
+
+/// Position of the state keeper in the chain as observed by [`DevMode`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DevModePosition {
+    l1_batch: L1BatchNumber,
+    last_sealed_l2_block: L2BlockNumber,
+    has_pending_txs: bool,
+}
+
+impl DevModePosition {
+    /// Returns `None` if the current L2 block contains transactions, but isn't sealed yet.
+    pub fn new(manager: &UpdatesManager) -> Option<Self> {
+        let last_sealed_l2_block = if manager.has_next_block_params() {
+            manager.l2_block.number
+        } else if manager.l2_block.executed_transactions.is_empty() {
+            manager.l2_block.number - 1
+        } else {
+            return None;
+        };
+        Some(Self {
+            l1_batch: manager.l1_batch.number,
+            last_sealed_l2_block,
+            has_pending_txs: manager.pending_executed_transactions_len() > 0,
+        })
+    }
+
+    /// Position before the L1 batch pointed to by the `cursor` is opened.
+    pub fn before_batch(cursor: &IoCursor) -> Self {
+        Self {
+            l1_batch: cursor.l1_batch,
+            last_sealed_l2_block: cursor.next_l2_block - 1,
+            has_pending_txs: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DevSnapshot {
+    l1_batch: L1BatchNumber,
+    last_sealed_l2_block: L2BlockNumber,
+    time_offset: u64,
+}
+
+/// I/O-side part of the development mode processing requests from [`DevModeControl`] handles.
+#[derive(Debug)]
+pub(crate) struct DevMode {
+    control: DevModeControl,
+    requests: mpsc::UnboundedReceiver<DevModeRequest>,
+    seal_request: L1BatchSealRequest,
+    snapshots: BTreeMap<u64, DevSnapshot>,
+    next_snapshot_id: u64,
+    /// Responses to `mine` requests, which are sent once the L1 batch is sealed.
+    pending_mine_responses: Vec<oneshot::Sender<L2BlockNumber>>,
+}
+
+impl DevMode {
+    pub fn new(seal_request: L1BatchSealRequest) -> Self {
+        let (requests_sender, requests) = mpsc::unbounded_channel();
+        Self {
+            control: DevModeControl {
+                requests: requests_sender,
+                time_offset: Arc::default(),
+            },
+            requests,
+            seal_request,
+            snapshots: BTreeMap::new(),
+            next_snapshot_id: 1,
+            pending_mine_responses: vec![],
+        }
+    }
+
+    pub fn control(&self) -> DevModeControl {
+        self.control.clone()
+    }
+
+    pub fn timestamp_policy(&self) -> DevModeTimestampPolicy {
+        DevModeTimestampPolicy {
+            time_offset: self.control.time_offset.clone(),
+        }
+    }
+
+    pub fn has_pending_requests(&self) -> bool {
+        !self.requests.is_empty()
+    }
+
+    /// Processes all pending requests. Returns `true` if the pending state was reverted in the storage,
+    /// in which case the state keeper must re-initialize.
+    pub async fn process_requests(
+        &mut self,
+        position: DevModePosition,
+        pool: &ConnectionPool<Core>,
+        mempool: &mut MempoolGuard,
+    ) -> anyhow::Result<bool> {
+        if !position.has_pending_txs {
+            // The previously requested L1 batch (if any) is sealed.
+            for response in self.pending_mine_responses.drain(..) {
+                response.send(position.last_sealed_l2_block).ok();
+            }
+        }
+
+        while let Ok(request) = self.requests.try_recv() {
+            match request {
+                DevModeRequest::Mine(response) => {
+                    if position.has_pending_txs {
+                        self.seal_request.request();
+                        self.pending_mine_responses.push(response);
+                    } else {
+                        response.send(position.last_sealed_l2_block).ok();
+                    }
+                }
+                DevModeRequest::Snapshot(response) => {
+                    let snapshot_id = self.next_snapshot_id;
+                    self.next_snapshot_id += 1;
+                    let snapshot = DevSnapshot {
+                        l1_batch: position.l1_batch,
+                        last_sealed_l2_block: position.last_sealed_l2_block,
+                        time_offset: self.control.time_offset(),
+                    };
+                    tracing::info!("Took dev mode snapshot #{snapshot_id}: {snapshot:?}");
+                    self.snapshots.insert(snapshot_id, snapshot);
+                    response.send(snapshot_id).ok();
+                }
+                DevModeRequest::Revert {
+                    snapshot_id,
+                    response,
+                } => {
+                    let Some(snapshot) = self.snapshots.get(&snapshot_id).copied() else {
+                        response.send(false).ok();
+                        continue;
+                    };
+                    if snapshot.l1_batch != position.l1_batch {
+                        tracing::warn!(
+                            "Cannot revert to dev mode snapshot #{snapshot_id}: L1 batch #{} is already sealed",
+                            snapshot.l1_batch
+                        );
+                        response.send(false).ok();
+                        continue;
+                    }
+
+                    self.snapshots.split_off(&snapshot_id);
+                    let has_reverted_blocks =
+                        snapshot.last_sealed_l2_block < position.last_sealed_l2_block;
+                    if has_reverted_blocks {
+                        Self::revert_pending_state(snapshot, pool, mempool).await?;
+                    }
+                    self.control
+                        .time_offset
+                        .store(snapshot.time_offset, Ordering::Relaxed);
+                    tracing::info!("Reverted to dev mode snapshot #{snapshot_id}: {snapshot:?}");
+                    response.send(true).ok();
+                    if has_reverted_blocks {
+                        // Remaining requests will be processed after re-initialization.
+                        return Ok(true);
+                    }
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    async fn revert_pending_state(
+        snapshot: DevSnapshot,
+        pool: &ConnectionPool<Core>,
+        mempool: &mut MempoolGuard,
+    ) -> anyhow::Result<()> {
+        let last_l2_block_to_keep = snapshot.last_sealed_l2_block;
+        let mut storage = pool.connection_tagged("state_keeper").await?;
+        let mut transaction = storage.start_transaction().await?;
+        let (_, last_l2_block_in_prev_batch) = transaction
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(snapshot.l1_batch - 1)
+            .await?
+            .with_context(|| format!("L1 batch #{} is not sealed", snapshot.l1_batch - 1))?;
+
+        // Reverted L2 transactions are removed altogether, while priority transactions are returned to the mempool.
+        let deleted_tx_count = transaction
+            .transactions_dal()
+            .delete_l2_transactions_after(last_l2_block_to_keep)
+            .await?;
+        L2BlockSealProcess::clear_pending_l2_block(&mut transaction, last_l2_block_to_keep).await?;
+        transaction
+            .unsealed_batch_checkpoints_dal()
+            .delete_checkpoints_starting_from(last_l2_block_to_keep + 1)
+            .await?;
+        transaction
+            .blocks_dal()
+            .delete_l2_blocks(last_l2_block_to_keep)
+            .await?;
+        if last_l2_block_to_keep <= last_l2_block_in_prev_batch {
+            // No L2 blocks are left in the pending batch.
+            transaction
+                .blocks_dal()
+                .delete_unsealed_l1_batch(snapshot.l1_batch - 1)
+                .await?;
+        }
+        // Make the mempool fetcher re-populate the mempool; nonces will be loaded from the reverted state.
+        transaction.transactions_dal().reset_mempool().await?;
+        let next_priority_id = transaction.transactions_dal().next_priority_id().await;
+        transaction.commit().await?;
+        mempool.clear(next_priority_id);
+
+        tracing::info!(
+            "Reverted pending L2 blocks after #{last_l2_block_to_keep}; deleted {deleted_tx_count} L2 transactions"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::PriorityOpId;
+
+    use super::*;
+
+    fn position(
+        l1_batch: u32,
+        last_sealed_l2_block: u32,
+        has_pending_txs: bool,
+    ) -> DevModePosition {
+        DevModePosition {
+            l1_batch: L1BatchNumber(l1_batch),
+            last_sealed_l2_block: L2BlockNumber(last_sealed_l2_block),
+            has_pending_txs,
+        }
+    }
+
+    #[tokio::test]
+    async fn dev_mode_timestamp_policy() {
+        let dev_mode = DevMode::new(L1BatchSealRequest::default());
+        let policy = dev_mode.timestamp_policy();
+        let control = dev_mode.control();
+        let now = (millis_since_epoch() / 1_000) as u64;
+
+        let timestamp = policy.next_timestamp(now, L2BlockNumber(1)).await.unwrap();
+        assert!(timestamp > now);
+        let far_future = now + 1_000_000;
+        let timestamp = policy
+            .next_timestamp(far_future, L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(timestamp, far_future + 1);
+
+        assert_eq!(control.increase_time(3_600), 3_600);
+        assert_eq!(control.increase_time(3_600), 7_200);
+        let timestamp = policy.next_timestamp(now, L2BlockNumber(1)).await.unwrap();
+        assert!(timestamp >= now + 7_200, "{timestamp}");
+
+        let mut timestamp = now;
+        policy.refresh_timestamp(&mut timestamp);
+        assert!(timestamp >= now + 7_200, "{timestamp}");
+    }
+
+    #[tokio::test]
+    async fn processing_dev_mode_requests() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut mempool = MempoolGuard::new(PriorityOpId(0), 100);
+        let seal_request = L1BatchSealRequest::default();
+        let mut dev_mode = DevMode::new(seal_request.clone());
+        let control = dev_mode.control();
+
+        let snapshot_task = tokio::spawn({
+            let control = control.clone();
+            async move { control.snapshot().await.unwrap() }
+        });
+        while !dev_mode.has_pending_requests() {
+            tokio::task::yield_now().await;
+        }
+        let reverted = dev_mode
+            .process_requests(position(1, 1, false), &pool, &mut mempool)
+            .await
+            .unwrap();
+        assert!(!reverted);
+        let snapshot_id = snapshot_task.await.unwrap();
+        assert_eq!(snapshot_id, 1);
+
+        control.increase_time(100);
+        let revert_task = tokio::spawn({
+            let control = control.clone();
+            async move {
+                let unknown = control.revert(snapshot_id + 1).await.unwrap();
+                let known = control.revert(snapshot_id).await.unwrap();
+                let discarded = control.revert(snapshot_id).await.unwrap();
+                (unknown, known, discarded)
+            }
+        });
+        // There are no blocks after the snapshot, so the storage isn't touched.
+        while !revert_task.is_finished() {
+            dev_mode
+                .process_requests(position(1, 1, false), &pool, &mut mempool)
+                .await
+                .unwrap();
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(revert_task.await.unwrap(), (false, true, false));
+        assert_eq!(control.time_offset(), 0);
+
+        // Mining with pending transactions requests to seal the batch and responds once it's sealed.
+        let mine_task = tokio::spawn({
+            let control = control.clone();
+            async move { control.mine().await.unwrap() }
+        });
+        while !dev_mode.has_pending_requests() {
+            tokio::task::yield_now().await;
+        }
+        dev_mode
+            .process_requests(position(1, 2, true), &pool, &mut mempool)
+            .await
+            .unwrap();
+        assert!(seal_request.is_requested());
+        assert!(!mine_task.is_finished());
+        dev_mode
+            .process_requests(position(2, 3, false), &pool, &mut mempool)
+            .await
+            .unwrap();
+        assert_eq!(mine_task.await.unwrap(), L2BlockNumber(3));
+    }
+}
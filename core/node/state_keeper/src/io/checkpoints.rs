@@ -0,0 +1,144 @@
+//! Execution checkpoints for the unsealed L1 batch.
+
+use std::{num::NonZeroU32, sync::Arc};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use zksync_dal::{
+    unsealed_batch_checkpoints_dal::UnsealedBatchCheckpoint, ConnectionPool, Core, CoreDal,
+};
+
+use crate::{
+    io::{IoCursor, StateKeeperOutputHandler},
+    updates::UpdatesManager,
+};
+
+/// Output handler periodically persisting [`UnsealedBatchCheckpoint`]s for the pending L1 batch.
+///
+/// After a restart, the state keeper re-executes the pending batch and checks the re-executed state against
+/// the persisted checkpoints. The state keeper stops at the first mismatching checkpoint, rather than
+/// sealing a batch with diverged state. Checkpoints don't capture the VM state, so the pending batch is still
+/// re-executed from its start.
+#[derive(Debug)]
+pub struct BatchCheckpointsPersistence {
+    pool: ConnectionPool<Core>,
+    interval: NonZeroU32,
+}
+
+impl BatchCheckpointsPersistence {
+    /// Creates a handler persisting a checkpoint every `interval` L2 blocks.
+    pub fn new(pool: ConnectionPool<Core>, interval: NonZeroU32) -> Self {
+        Self { pool, interval }
+    }
+}
+
+#[async_trait]
+impl StateKeeperOutputHandler for BatchCheckpointsPersistence {
+    async fn initialize(&mut self, cursor: &IoCursor) -> anyhow::Result<()> {
+        // Checkpoints may have been persisted for L2 blocks that never made it to Postgres, or were reverted since then.
+        let mut connection = self.pool.connection_tagged("state_keeper").await?;
+        connection
+            .unsealed_batch_checkpoints_dal()
+            .delete_checkpoints_starting_from(cursor.next_l2_block)
+            .await?;
+        Ok(())
+    }
+
+    async fn handle_l2_block(&mut self, updates_manager: &UpdatesManager) -> anyhow::Result<()> {
+        let l2_block_number = updates_manager.l2_block.number;
+        if l2_block_number.0 % self.interval.get() != 0 {
+            return Ok(());
+        }
+
+        let checkpoint = UnsealedBatchCheckpoint {
+            l1_batch_number: updates_manager.l1_batch.number,
+            l2_block_number,
+            tx_count: updates_manager.pending_executed_transactions_len(),
+            storage_logs_checksum: updates_manager.pending_storage_logs_checksum(),
+        };
+        let mut connection = self.pool.connection_tagged("state_keeper").await?;
+        connection
+            .unsealed_batch_checkpoints_dal()
+            .insert_checkpoint(&checkpoint)
+            .await
+            .with_context(|| format!("failed persisting checkpoint {checkpoint:?}"))?;
+        Ok(())
+    }
+
+    async fn handle_l1_batch(
+        &mut self,
+        updates_manager: Arc<UpdatesManager>,
+    ) -> anyhow::Result<()> {
+        let mut connection = self.pool.connection_tagged("state_keeper").await?;
+        connection
+            .unsealed_batch_checkpoints_dal()
+            .delete_checkpoints_for_sealed_batches(updates_manager.l1_batch.number)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_multivm::interface::VmExecutionMetrics;
+    use zksync_types::H256;
+
+    use super::*;
+    use crate::tests::{create_execution_result, create_transaction, create_updates_manager};
+
+    #[tokio::test]
+    async fn checkpoints_are_persisted_and_pruned() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut handler = BatchCheckpointsPersistence::new(pool.clone(), NonZeroU32::MIN);
+
+        let mut updates = create_updates_manager();
+        updates.extend_from_executed_transaction(
+            create_transaction(10, 100),
+            create_execution_result([]),
+            VmExecutionMetrics::default(),
+            vec![],
+        );
+        handler.handle_l2_block(&updates).await.unwrap();
+
+        let l1_batch_number = updates.l1_batch.number;
+        let mut storage = pool.connection().await.unwrap();
+        let checkpoints = storage
+            .unsealed_batch_checkpoints_dal()
+            .get_checkpoints(l1_batch_number)
+            .await
+            .unwrap();
+        assert_eq!(
+            checkpoints,
+            [UnsealedBatchCheckpoint {
+                l1_batch_number,
+                l2_block_number: updates.l2_block.number,
+                tx_count: 1,
+                storage_logs_checksum: updates.pending_storage_logs_checksum(),
+            }]
+        );
+
+        // A checkpoint for an L2 block that wasn't persisted must be dropped on initialization.
+        let cursor = IoCursor {
+            next_l2_block: updates.l2_block.number,
+            prev_l2_block_hash: H256::zero(),
+            prev_l2_block_timestamp: 0,
+            l1_batch: l1_batch_number,
+        };
+        handler.initialize(&cursor).await.unwrap();
+        let checkpoints = storage
+            .unsealed_batch_checkpoints_dal()
+            .get_checkpoints(l1_batch_number)
+            .await
+            .unwrap();
+        assert!(checkpoints.is_empty());
+
+        handler.handle_l2_block(&updates).await.unwrap();
+        handler.handle_l1_batch(Arc::new(updates)).await.unwrap();
+        let checkpoints = storage
+            .unsealed_batch_checkpoints_dal()
+            .get_checkpoints(l1_batch_number)
+            .await
+            .unwrap();
+        assert!(checkpoints.is_empty());
+    }
+}
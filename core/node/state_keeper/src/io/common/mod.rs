@@ -102,10 +102,15 @@ pub async fn load_pending_batch(
         l1_batch_env.number,
         l1_batch_env.first_l2_block
     );
+    let checkpoints = storage
+        .unsealed_batch_checkpoints_dal()
+        .get_checkpoints(l1_batch_env.number)
+        .await?;
     Ok(PendingBatchData {
         l1_batch_env,
         system_env,
         pubdata_params,
         pending_l2_blocks,
+        checkpoints,
     })
 }
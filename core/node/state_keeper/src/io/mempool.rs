@@ -1,5 +1,4 @@
 use std::{
-    cmp,
     collections::HashMap,
     sync::Arc,
     time::{Duration, Instant},
@@ -11,18 +10,22 @@ use zksync_config::configs::chain::StateKeeperConfig;
 use zksync_contracts::BaseSystemContracts;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_mempool::L2TxFilter;
-use zksync_multivm::{interface::Halt, utils::derive_base_fee_and_gas_per_pubdata};
+use zksync_multivm::{
+    interface::{Halt, VmEvent, VmExecutionResultAndLogs},
+    utils::derive_base_fee_and_gas_per_pubdata,
+};
 use zksync_node_fee_model::BatchFeeModelInputProvider;
 use zksync_types::{
     block::UnsealedL1BatchHeader,
     commitment::{PubdataParams, PubdataType},
     protocol_upgrade::ProtocolUpgradeTx,
-    utils::display_timestamp,
-    Address, L1BatchNumber, L2BlockNumber, L2ChainId, ProtocolVersionId, Transaction, H256, U256,
+    Address, ExecuteTransactionCommon, L1BatchNumber, L2ChainId, ProtocolVersionId, Transaction,
+    H256, U256,
 };
 use zksync_vm_executor::storage::{get_base_system_contracts_by_version_id, L1BatchParamsProvider};
 
 use crate::{
+    dev_mode::{DevMode, DevModeControl, DevModePosition},
     io::{
         common::{load_pending_batch, poll_iters, IoCursor},
         seal_logic::l2_block_seal_subtasks::L2BlockSealProcess,
@@ -31,11 +34,14 @@ use crate::{
     mempool_actor::l2_tx_filter,
     metrics::{L2BlockSealReason, AGGREGATION_METRICS, KEEPER_METRICS},
     seal_criteria::{
-        io_criteria::{L2BlockMaxPayloadSizeSealer, ProtocolUpgradeSealer, TimeoutSealer},
+        io_criteria::{
+            L1BatchSealRequest, L2BlockMaxPayloadSizeSealer, ProtocolUpgradeSealer, TimeoutSealer,
+        },
         IoSealCriteria, UnexecutableReason,
     },
+    timestamp_policy::{RealTimePolicy, TimestampPolicy},
+    tx_policy::{deployer_addresses, TxPolicy, TxPolicyStage, TxPolicyViolation},
     updates::UpdatesManager,
-    utils::millis_since_epoch,
     MempoolGuard,
 };
 
@@ -51,6 +57,7 @@ pub struct MempoolIO {
     timeout_sealer: TimeoutSealer,
     l2_block_max_payload_size_sealer: L2BlockMaxPayloadSizeSealer,
     protocol_upgrade_sealer: ProtocolUpgradeSealer,
+    seal_request: L1BatchSealRequest,
     filter: L2TxFilter,
     l1_batch_params_provider: L1BatchParamsProvider,
     fee_account: Address,
@@ -62,6 +69,9 @@ pub struct MempoolIO {
     chain_id: L2ChainId,
     l2_da_validator_address: Option<Address>,
     pubdata_type: PubdataType,
+    tx_policy: Option<Arc<TxPolicy>>,
+    timestamp_policy: Box<dyn TimestampPolicy>,
+    dev_mode: Option<DevMode>,
 }
 
 #[async_trait]
@@ -70,8 +80,18 @@ impl IoSealCriteria for MempoolIO {
         &mut self,
         manager: &UpdatesManager,
     ) -> anyhow::Result<bool> {
+        // In the dev mode, L1 batches are sealed on request (or by conditional sealers).
+        if self.dev_mode.is_none()
+            && self
+                .timeout_sealer
+                .should_seal_l1_batch_unconditionally(manager)
+                .await?
+        {
+            return Ok(true);
+        }
+
         if self
-            .timeout_sealer
+            .protocol_upgrade_sealer
             .should_seal_l1_batch_unconditionally(manager)
             .await?
         {
@@ -79,9 +99,8 @@ impl IoSealCriteria for MempoolIO {
         }
 
         if self
-            .protocol_upgrade_sealer
+            .seal_request
             .should_seal_l1_batch_unconditionally(manager)
-            .await?
         {
             return Ok(true);
         }
@@ -90,6 +109,14 @@ impl IoSealCriteria for MempoolIO {
     }
 
     fn should_seal_l2_block(&mut self, manager: &UpdatesManager) -> bool {
+        if self.dev_mode.is_some() {
+            if manager.l2_block.executed_transactions.is_empty() {
+                return false;
+            }
+            AGGREGATION_METRICS.l2_block_reason_inc(&L2BlockSealReason::DevMode);
+            return true;
+        }
+
         if self.timeout_sealer.should_seal_l2_block(manager) {
             AGGREGATION_METRICS.l2_block_reason_inc(&L2BlockSealReason::Timeout);
             return true;
@@ -206,16 +233,16 @@ impl StateKeeperIO for MempoolIO {
         // Block until at least one transaction in the mempool can match the filter (or timeout happens).
         // This is needed to ensure that block timestamp is not too old.
         for _ in 0..poll_iters(self.delay_interval, max_wait) {
-            // We cannot create two L1 batches or L2 blocks with the same timestamp (forbidden by the bootloader).
-            // Hence, we wait until the current timestamp is larger than the timestamp of the previous L2 block.
-            // We can use `timeout_at` since `sleep_past` is cancel-safe; it only uses `sleep()` async calls.
+            // We can use `timeout_at` since `next_timestamp()` is cancel-safe by contract.
             let timestamp = tokio::time::timeout_at(
                 deadline.into(),
-                sleep_past(cursor.prev_l2_block_timestamp, cursor.next_l2_block),
+                self.timestamp_policy
+                    .next_timestamp(cursor.prev_l2_block_timestamp, cursor.next_l2_block),
             );
             let Some(timestamp) = timestamp.await.ok() else {
                 return Ok(None);
             };
+            let timestamp = timestamp.context("failed selecting L1 batch timestamp")?;
 
             tracing::trace!(
                 "Fee input for L1 batch #{} is {:#?}",
@@ -254,6 +281,17 @@ impl StateKeeperIO for MempoolIO {
             .await
             .context("failed creating L2 transaction filter")?;
 
+            if let Some(dev_mode) = &mut self.dev_mode {
+                let position = DevModePosition::before_batch(cursor);
+                let reverted = dev_mode
+                    .process_requests(position, &self.pool, &mut self.mempool)
+                    .await?;
+                anyhow::ensure!(
+                    !reverted,
+                    "no L2 blocks can be reverted before opening L1 batch"
+                );
+            }
+
             // We do not populate mempool with upgrade tx so it should be checked separately.
             if !batch_with_upgrade_tx && !self.mempool.has_next(&self.filter) {
                 tokio::time::sleep(self.delay_interval).await;
@@ -294,16 +332,17 @@ impl StateKeeperIO for MempoolIO {
         cursor: &IoCursor,
         max_wait: Duration,
     ) -> anyhow::Result<Option<L2BlockParams>> {
-        // We must provide different timestamps for each L2 block.
-        // If L2 block sealing interval is greater than 1 second then `sleep_past` won't actually sleep.
+        // We must provide different timestamps for each L2 block; this is ensured by the timestamp policy.
         let timeout_result = tokio::time::timeout(
             max_wait,
-            sleep_past(cursor.prev_l2_block_timestamp, cursor.next_l2_block),
+            self.timestamp_policy
+                .next_timestamp(cursor.prev_l2_block_timestamp, cursor.next_l2_block),
         )
         .await;
         let Ok(timestamp) = timeout_result else {
             return Ok(None);
         };
+        let timestamp = timestamp.context("failed selecting L2 block timestamp")?;
 
         Ok(Some(L2BlockParams {
             timestamp,
@@ -313,16 +352,7 @@ impl StateKeeperIO for MempoolIO {
     }
 
     fn update_next_l2_block_timestamp(&mut self, block_timestamp: &mut u64) {
-        let current_timestamp_millis = millis_since_epoch();
-        let current_timestamp = (current_timestamp_millis / 1_000) as u64;
-
-        if current_timestamp < *block_timestamp {
-            tracing::warn!(
-                "Trying to update block timestamp {block_timestamp} with lower value timestamp {current_timestamp}",
-            );
-        } else {
-            *block_timestamp = current_timestamp;
-        }
+        self.timestamp_policy.refresh_timestamp(block_timestamp);
     }
 
     async fn wait_for_next_tx(
@@ -332,6 +362,15 @@ impl StateKeeperIO for MempoolIO {
     ) -> anyhow::Result<Option<Transaction>> {
         let started_at = Instant::now();
         while started_at.elapsed() <= max_wait {
+            if self
+                .dev_mode
+                .as_ref()
+                .is_some_and(DevMode::has_pending_requests)
+            {
+                // Return to the state keeper so that it processes the requests.
+                return Ok(None);
+            }
+
             let get_latency = KEEPER_METRICS.get_tx_from_mempool.start();
             let maybe_tx = self.mempool.next_transaction(&self.filter);
             get_latency.observe();
@@ -366,6 +405,13 @@ impl StateKeeperIO for MempoolIO {
                     continue;
                 }
 
+                // Deployments by factory contracts are checked after execution in `check_executed_tx()`.
+                if let Some(violation) = self.check_tx_policy(&tx, &[]) {
+                    self.reject(&tx, UnexecutableReason::PolicyViolation(violation))
+                        .await?;
+                    continue;
+                }
+
                 return Ok(Some(tx));
             } else {
                 tokio::time::sleep(self.delay_interval).await;
@@ -398,6 +444,19 @@ impl StateKeeperIO for MempoolIO {
 
         // Mark tx as rejected in the storage.
         let mut storage = self.pool.connection_tagged("state_keeper").await?;
+        if let (UnexecutableReason::PolicyViolation(violation), Some(tx_policy)) =
+            (&reason, &self.tx_policy)
+        {
+            tx_policy
+                .persist_decision(
+                    &mut storage,
+                    rejected.hash(),
+                    rejected.initiator_account(),
+                    TxPolicyStage::Inclusion,
+                    violation,
+                )
+                .await?;
+        }
 
         KEEPER_METRICS.inc_rejected_txs(reason.as_metric_label());
 
@@ -412,6 +471,15 @@ impl StateKeeperIO for MempoolIO {
         Ok(())
     }
 
+    fn check_executed_tx(
+        &self,
+        tx: &Transaction,
+        tx_result: &VmExecutionResultAndLogs,
+    ) -> Option<UnexecutableReason> {
+        self.check_tx_policy(tx, &tx_result.logs.events)
+            .map(UnexecutableReason::PolicyViolation)
+    }
+
     async fn load_base_system_contracts(
         &self,
         protocol_version: ProtocolVersionId,
@@ -452,6 +520,21 @@ impl StateKeeperIO for MempoolIO {
             .map_err(Into::into)
     }
 
+    async fn process_dev_mode_requests(
+        &mut self,
+        updates_manager: &UpdatesManager,
+    ) -> anyhow::Result<bool> {
+        let Some(dev_mode) = &mut self.dev_mode else {
+            return Ok(false);
+        };
+        let Some(position) = DevModePosition::new(updates_manager) else {
+            return Ok(false);
+        };
+        dev_mode
+            .process_requests(position, &self.pool, &mut self.mempool)
+            .await
+    }
+
     async fn load_batch_state_hash(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<H256> {
         tracing::trace!("Getting L1 batch hash for L1 batch #{l1_batch_number}");
         let wait_latency = KEEPER_METRICS.wait_for_prev_hash_time.start();
@@ -471,53 +554,6 @@ impl StateKeeperIO for MempoolIO {
     }
 }
 
-/// Sleeps until the current timestamp is larger than the provided `timestamp`.
-///
-/// Returns the current timestamp after the sleep. It is guaranteed to be larger than `timestamp`.
-async fn sleep_past(timestamp: u64, l2_block: L2BlockNumber) -> u64 {
-    let mut current_timestamp_millis = millis_since_epoch();
-    let mut current_timestamp = (current_timestamp_millis / 1_000) as u64;
-    match timestamp.cmp(&current_timestamp) {
-        cmp::Ordering::Less => return current_timestamp,
-        cmp::Ordering::Equal => {
-            tracing::info!(
-                "Current timestamp {} for L2 block #{l2_block} is equal to previous L2 block timestamp; waiting until \
-                 timestamp increases",
-                display_timestamp(current_timestamp)
-            );
-        }
-        cmp::Ordering::Greater => {
-            // This situation can be triggered if the system keeper is started on a pod with a different
-            // system time, or if it is buggy. Thus, a one-time error could require no actions if L1 batches
-            // are expected to be generated frequently.
-            tracing::error!(
-                "Previous L2 block timestamp {} is larger than the current timestamp {} for L2 block #{l2_block}",
-                display_timestamp(timestamp),
-                display_timestamp(current_timestamp)
-            );
-        }
-    }
-
-    // This loop should normally run once, since `tokio::time::sleep` sleeps *at least* the specified duration.
-    // The logic is organized in a loop for marginal cases, such as the system time getting changed during `sleep()`.
-    loop {
-        // Time to catch up to `timestamp`; panic / underflow on subtraction is never triggered
-        // since we've ensured that `timestamp >= current_timestamp`.
-        let wait_seconds = timestamp - current_timestamp;
-        // Time to wait until the current timestamp increases.
-        let wait_millis = 1_001 - (current_timestamp_millis % 1_000) as u64;
-        let wait = Duration::from_millis(wait_millis + wait_seconds * 1_000);
-
-        tokio::time::sleep(wait).await;
-        current_timestamp_millis = millis_since_epoch();
-        current_timestamp = (current_timestamp_millis / 1_000) as u64;
-
-        if current_timestamp > timestamp {
-            return current_timestamp;
-        }
-    }
-}
-
 impl MempoolIO {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -537,6 +573,7 @@ impl MempoolIO {
             timeout_sealer: TimeoutSealer::new(config),
             l2_block_max_payload_size_sealer: L2BlockMaxPayloadSizeSealer::new(config),
             protocol_upgrade_sealer: ProtocolUpgradeSealer::new(pool),
+            seal_request: L1BatchSealRequest::default(),
             filter: L2TxFilter::default(),
             // ^ Will be initialized properly on the first newly opened batch
             l1_batch_params_provider: L1BatchParamsProvider::uninitialized(),
@@ -548,9 +585,60 @@ impl MempoolIO {
             chain_id,
             l2_da_validator_address,
             pubdata_type,
+            tx_policy: None,
+            timestamp_policy: Box::new(RealTimePolicy),
+            dev_mode: None,
         })
     }
 
+    /// Sets the policy selecting timestamps for new L1 batches and L2 blocks. By default, [`RealTimePolicy`] is used.
+    #[must_use]
+    pub fn with_timestamp_policy(mut self, timestamp_policy: Box<dyn TimestampPolicy>) -> Self {
+        self.timestamp_policy = timestamp_policy;
+        self
+    }
+
+    /// Enables the deterministic development mode: each transaction is sealed in a separate L2 block, timestamps
+    /// are selected without waiting, and block production can be controlled via [`Self::dev_mode_control()`].
+    /// Overrides the timestamp policy.
+    #[must_use]
+    pub fn with_dev_mode(mut self) -> Self {
+        let dev_mode = DevMode::new(self.seal_request.clone());
+        self.timestamp_policy = Box::new(dev_mode.timestamp_policy());
+        self.dev_mode = Some(dev_mode);
+        self
+    }
+
+    /// Returns a handle controlling block production if the development mode is enabled.
+    pub fn dev_mode_control(&self) -> Option<DevModeControl> {
+        self.dev_mode.as_ref().map(DevMode::control)
+    }
+
+    /// Enforces the transaction policy for L2 transactions included into blocks.
+    #[must_use]
+    pub fn with_tx_policy(mut self, tx_policy: Arc<TxPolicy>) -> Self {
+        self.tx_policy = Some(tx_policy);
+        self
+    }
+
+    /// Returns a handle allowing to force sealing the current L1 batch.
+    pub fn seal_request(&self) -> L1BatchSealRequest {
+        self.seal_request.clone()
+    }
+
+    /// Checks the transaction policy for an L2 transaction. Deployer rules are checked both for direct deployments
+    /// and for contracts deployed during execution, according to the supplied VM `events`.
+    fn check_tx_policy(&self, tx: &Transaction, events: &[VmEvent]) -> Option<TxPolicyViolation> {
+        let tx_policy = self.tx_policy.as_ref()?;
+        if !matches!(tx.common_data, ExecuteTransactionCommon::L2(_)) {
+            return None;
+        }
+        if let Err(violation) = tx_policy.check_tx(tx.initiator_account(), tx.recipient_account()) {
+            return Some(violation);
+        }
+        deployer_addresses(events).find_map(|deployer| tx_policy.check_deployer(deployer).err())
+    }
+
     fn pubdata_params(&self, protocol_version: ProtocolVersionId) -> anyhow::Result<PubdataParams> {
         let pubdata_params = match (
             protocol_version.is_pre_gateway(),
@@ -575,53 +663,3 @@ impl MempoolIO {
         &self.filter
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use tokio::time::timeout_at;
-
-    use super::*;
-    use crate::tests::seconds_since_epoch;
-
-    // This test defensively uses large deadlines in order to account for tests running in parallel etc.
-    #[tokio::test]
-    async fn sleeping_past_timestamp() {
-        let past_timestamps = [0, 1_000, 1_000_000_000, seconds_since_epoch() - 10];
-        for timestamp in past_timestamps {
-            let deadline = Instant::now() + Duration::from_secs(1);
-            timeout_at(deadline.into(), sleep_past(timestamp, L2BlockNumber(1)))
-                .await
-                .unwrap();
-        }
-
-        let current_timestamp = seconds_since_epoch();
-        let deadline = Instant::now() + Duration::from_secs(2);
-        let ts = timeout_at(
-            deadline.into(),
-            sleep_past(current_timestamp, L2BlockNumber(1)),
-        )
-        .await
-        .unwrap();
-        assert!(ts > current_timestamp);
-
-        let future_timestamp = seconds_since_epoch() + 1;
-        let deadline = Instant::now() + Duration::from_secs(3);
-        let ts = timeout_at(
-            deadline.into(),
-            sleep_past(future_timestamp, L2BlockNumber(1)),
-        )
-        .await
-        .unwrap();
-        assert!(ts > future_timestamp);
-
-        let future_timestamp = seconds_since_epoch() + 1;
-        let deadline = Instant::now() + Duration::from_millis(100);
-        // ^ This deadline is too small (we need at least 1_000ms)
-        let result = timeout_at(
-            deadline.into(),
-            sleep_past(future_timestamp, L2BlockNumber(1)),
-        )
-        .await;
-        assert!(result.is_err());
-    }
-}
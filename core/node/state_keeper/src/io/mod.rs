@@ -2,7 +2,8 @@ use std::{fmt, time::Duration};
 
 use async_trait::async_trait;
 use zksync_contracts::BaseSystemContracts;
-use zksync_multivm::interface::{L1BatchEnv, SystemEnv};
+use zksync_dal::unsealed_batch_checkpoints_dal::UnsealedBatchCheckpoint;
+use zksync_multivm::interface::{L1BatchEnv, SystemEnv, VmExecutionResultAndLogs};
 use zksync_types::{
     block::L2BlockExecutionData, commitment::PubdataParams, fee_model::BatchFeeInput,
     protocol_upgrade::ProtocolUpgradeTx, Address, L1BatchNumber, L2ChainId, ProtocolVersionId,
@@ -11,19 +12,28 @@ use zksync_types::{
 use zksync_vm_executor::storage::l1_batch_params;
 
 pub use self::{
+    checkpoints::BatchCheckpointsPersistence,
     common::IoCursor,
     output_handler::{OutputHandler, StateKeeperOutputHandler},
     persistence::{L2BlockSealerTask, StateKeeperPersistence, TreeWritesPersistence},
+    soft_confirmations::SoftConfirmationsPersistence,
+    witness_inputs::WitnessInputsPersistence,
+};
+use super::{
+    seal_criteria::{IoSealCriteria, UnexecutableReason},
+    updates::UpdatesManager,
 };
-use super::seal_criteria::{IoSealCriteria, UnexecutableReason};
 
+mod checkpoints;
 pub mod common;
 pub(crate) mod mempool;
 mod output_handler;
 mod persistence;
 pub mod seal_logic;
+mod soft_confirmations;
 #[cfg(test)]
 mod tests;
+mod witness_inputs;
 
 /// Contains information about the un-synced execution state:
 /// Batch data and transactions that were executed before and are marked as so in the DB,
@@ -42,6 +52,8 @@ pub struct PendingBatchData {
     pub(crate) pubdata_params: PubdataParams,
     /// List of L2 blocks and corresponding transactions that were executed within batch.
     pub(crate) pending_l2_blocks: Vec<L2BlockExecutionData>,
+    /// Checkpoints persisted for the pending L2 blocks. Used to verify the state after re-execution.
+    pub(crate) checkpoints: Vec<UnsealedBatchCheckpoint>,
 }
 
 #[derive(Debug, Copy, Clone, Default, PartialEq)]
@@ -149,6 +161,16 @@ pub trait StateKeeperIO: 'static + Send + Sync + fmt::Debug + IoSealCriteria {
     async fn rollback(&mut self, tx: Transaction) -> anyhow::Result<()>;
     /// Marks the transaction as "rejected", e.g. one that is not correct and can't be executed.
     async fn reject(&mut self, tx: &Transaction, reason: UnexecutableReason) -> anyhow::Result<()>;
+    /// Checks a successfully executed transaction against I/O-specific inclusion rules that depend on the execution
+    /// result (e.g., contract deployments gated by the transaction policy). Returns the reason to reject
+    /// the transaction, or `None` if it can be included.
+    fn check_executed_tx(
+        &self,
+        _tx: &Transaction,
+        _tx_result: &VmExecutionResultAndLogs,
+    ) -> Option<UnexecutableReason> {
+        None
+    }
 
     /// Loads base system contracts with the specified version.
     async fn load_base_system_contracts(
@@ -169,4 +191,13 @@ pub trait StateKeeperIO: 'static + Send + Sync + fmt::Debug + IoSealCriteria {
     /// Loads state hash for the L1 batch with the specified number. The batch is guaranteed to be present
     /// in the storage.
     async fn load_batch_state_hash(&self, number: L1BatchNumber) -> anyhow::Result<H256>;
+
+    /// Processes pending development mode requests (if the development mode is supported and enabled). Returns `true`
+    /// if the pending state was reverted in the storage, in which case the state keeper must re-initialize.
+    async fn process_dev_mode_requests(
+        &mut self,
+        _updates_manager: &UpdatesManager,
+    ) -> anyhow::Result<bool> {
+        Ok(false)
+    }
 }
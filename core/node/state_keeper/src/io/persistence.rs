@@ -14,6 +14,7 @@ use crate::{
         seal_logic::l2_block_seal_subtasks::L2BlockSealProcess, IoCursor, StateKeeperOutputHandler,
     },
     metrics::{L2BlockQueueStage, L2_BLOCK_METRICS},
+    sequencer_lease::SequencerFence,
     updates::{L2BlockSealCommand, UpdatesManager},
 };
 
@@ -31,6 +32,8 @@ pub struct StateKeeperPersistence {
     l2_legacy_shared_bridge_addr: Option<Address>,
     pre_insert_txs: bool,
     insert_protective_reads: bool,
+    record_storage_slot_writers: bool,
+    fence: Option<SequencerFence>,
     commands_sender: mpsc::Sender<Completable<L2BlockSealCommand>>,
     latest_completion_receiver: Option<oneshot::Receiver<()>>,
     // If true, `submit_l2_block()` will wait for the operation to complete.
@@ -94,6 +97,8 @@ impl StateKeeperPersistence {
             l2_legacy_shared_bridge_addr,
             pre_insert_txs: false,
             insert_protective_reads: true,
+            record_storage_slot_writers: false,
+            fence: None,
             commands_sender,
             latest_completion_receiver: None,
             is_sync,
@@ -113,6 +118,21 @@ impl StateKeeperPersistence {
         self
     }
 
+    /// Enables persisting hashes of transactions that last modified each storage slot in an L2 block
+    /// together with storage logs, so that the writer of a slot can be queried via API.
+    pub fn with_storage_slot_writers(mut self) -> Self {
+        self.record_storage_slot_writers = true;
+        self
+    }
+
+    /// Checks the provided fence before persisting each L2 block and L1 batch, so that data is only persisted
+    /// while this instance holds the sequencer lease. All data for an L2 block is then persisted in a single transaction
+    /// together with the fence check, rather than by parallel sub-tasks.
+    pub fn with_sequencer_fence(mut self, fence: SequencerFence) -> Self {
+        self.fence = Some(fence);
+        self
+    }
+
     /// Submits a new sealing `command` to the sealer that this handle is attached to.
     ///
     /// If there are currently too many unprocessed commands, this method will wait until
@@ -189,7 +209,9 @@ impl StateKeeperOutputHandler for StateKeeperPersistence {
 
     async fn handle_l2_block(&mut self, updates_manager: &UpdatesManager) -> anyhow::Result<()> {
         let command = updates_manager
-            .seal_l2_block_command(self.l2_legacy_shared_bridge_addr, self.pre_insert_txs);
+            .seal_l2_block_command(self.l2_legacy_shared_bridge_addr, self.pre_insert_txs)
+            .with_fence(self.fence.clone())
+            .with_storage_slot_writers(self.record_storage_slot_writers);
         self.submit_l2_block(command).await;
         Ok(())
     }
@@ -207,6 +229,7 @@ impl StateKeeperOutputHandler for StateKeeperPersistence {
                 self.pool.clone(),
                 self.l2_legacy_shared_bridge_addr,
                 self.insert_protective_reads,
+                self.fence.clone(),
             )
             .await
             .with_context(|| format!("cannot persist L1 batch #{batch_number}"))?;
@@ -252,10 +252,18 @@ impl L2BlockSealSubtask for InsertStorageLogsSubtask {
 
         let progress = L2_BLOCK_METRICS.start(L2BlockSealStage::InsertStorageLogs, is_fictive);
 
-        connection
-            .storage_logs_dal()
-            .insert_storage_logs(command.l2_block.number, &write_logs)
-            .await?;
+        if command.record_storage_slot_writers {
+            let writers = command.extract_storage_slot_writers();
+            connection
+                .storage_logs_dal()
+                .insert_storage_logs_with_writers(command.l2_block.number, &write_logs, &writers)
+                .await?;
+        } else {
+            connection
+                .storage_logs_dal()
+                .insert_storage_logs(command.l2_block.number, &write_logs)
+                .await?;
+        }
 
         progress.observe(write_logs.len());
         Ok(())
@@ -533,6 +541,7 @@ mod tests {
                 executed_transactions,
                 events,
                 storage_logs,
+                tx_storage_log_ranges: vec![0..1],
                 user_l2_to_l1_logs,
                 system_l2_to_l1_logs: Default::default(),
                 new_factory_deps,
@@ -555,6 +564,8 @@ mod tests {
             l2_legacy_shared_bridge_addr: Default::default(),
             pre_insert_txs: false,
             pubdata_params: PubdataParams::default(),
+            fence: None,
+            record_storage_slot_writers: true,
         };
 
         // Run.
@@ -571,6 +582,16 @@ mod tests {
             .await;
         assert!(factory_deps.contains_key(&h256_to_u256(bytecode_hash)));
 
+        // Check that the storage slot writer is recorded.
+        let writer = connection
+            .storage_web3_dal()
+            .get_storage_slot_writer(storage_key.hashed_key(), L2BlockNumber(1))
+            .await
+            .unwrap()
+            .expect("no storage slot writer");
+        assert_eq!(writer.tx_hash, Some(tx_hash));
+        assert_eq!(writer.value, storage_value);
+
         // Rollback.
         L2BlockSealProcess::clear_pending_l2_block(&mut connection, L2BlockNumber(0))
             .await
@@ -2,6 +2,7 @@
 //! It contains the logic of the block sealing, which is used by both the mempool-based and external node IO.
 
 use std::{
+    collections::HashMap,
     ops,
     time::{Duration, Instant},
 };
@@ -31,6 +32,7 @@ use crate::{
         L1BatchSealStage, L2BlockSealStage, TxExecutionType, KEEPER_METRICS, L1_BATCH_METRICS,
         L2_BLOCK_METRICS,
     },
+    sequencer_lease::SequencerFence,
     updates::{L2BlockSealCommand, UpdatesManager},
 };
 
@@ -45,6 +47,7 @@ impl UpdatesManager {
         pool: ConnectionPool<Core>,
         l2_legacy_shared_bridge_addr: Option<Address>,
         insert_protective_reads: bool,
+        fence: Option<SequencerFence>,
     ) -> anyhow::Result<()> {
         let started_at = Instant::now();
         let finished_batch = self
@@ -55,10 +58,12 @@ impl UpdatesManager {
 
         let progress = L1_BATCH_METRICS.start(L1BatchSealStage::FictiveL2Block);
         // Seal fictive L2 block with last events and storage logs.
-        let l2_block_command = self.seal_l2_block_command(
-            l2_legacy_shared_bridge_addr,
-            false, // fictive L2 blocks don't have txs, so it's fine to pass `false` here.
-        );
+        let l2_block_command = self
+            .seal_l2_block_command(
+                l2_legacy_shared_bridge_addr,
+                false, // fictive L2 blocks don't have txs, so it's fine to pass `false` here.
+            )
+            .with_fence(fence);
 
         let mut connection = pool.connection_tagged("state_keeper").await?;
         let transaction = connection.start_transaction().await?;
@@ -155,6 +160,10 @@ impl UpdatesManager {
             .blocks_dal()
             .mark_l2_blocks_as_executed_in_l1_batch(self.l1_batch.number)
             .await?;
+        transaction
+            .blocks_dal()
+            .save_l1_batch_logs_bloom(self.l1_batch.number)
+            .await?;
         progress.observe(None);
 
         let progress = L1_BATCH_METRICS.start(L1BatchSealStage::MarkTxsAsExecutedInL1Batch);
@@ -320,9 +329,26 @@ impl<'pool> SealStrategy<'pool> {
 impl L2BlockSealCommand {
     pub(super) async fn seal(&self, pool: ConnectionPool<Core>) -> anyhow::Result<()> {
         let l2_block_number = self.l2_block.number;
-        self.seal_inner(&mut SealStrategy::Parallel(&pool), false)
+        if self.fence.is_none() {
+            return self
+                .seal_inner(&mut SealStrategy::Parallel(&pool), false)
+                .await
+                .with_context(|| format!("failed sealing L2 block #{l2_block_number}"));
+        }
+
+        // With a sequencer fence, all L2 block data must be persisted in the transaction in which the fence is checked,
+        // so sub-tasks cannot be run in parallel.
+        let mut connection = pool.connection_tagged("state_keeper").await?;
+        let transaction = connection.start_transaction().await?;
+        let mut strategy = SealStrategy::Sequential(transaction);
+        self.seal_inner(&mut strategy, false)
             .await
-            .with_context(|| format!("failed sealing L2 block #{l2_block_number}"))
+            .with_context(|| format!("failed sealing L2 block #{l2_block_number}"))?;
+        let SealStrategy::Sequential(transaction) = strategy else {
+            panic!("Sealing L2 block should not mutate type of strategy");
+        };
+        transaction.commit().await?;
+        Ok(())
     }
 
     /// Seals an L2 block with the given number.
@@ -352,6 +378,17 @@ impl L2BlockSealCommand {
             event_count = self.l2_block.events.len()
         );
 
+        if let Some(fence) = &self.fence {
+            let SealStrategy::Sequential(transaction) = strategy else {
+                anyhow::bail!(
+                    "L2 block with a sequencer fence must be sealed in a single transaction"
+                );
+            };
+            // The lease row is locked until the transaction is committed, so the lease cannot be transferred
+            // while the L2 block (or, for a fictive block, the entire L1 batch) is being persisted.
+            fence.check(transaction).await?;
+        }
+
         // Run sub-tasks in parallel.
         L2BlockSealProcess::run_subtasks(self, strategy).await?;
 
@@ -448,6 +485,23 @@ impl L2BlockSealCommand {
         )
     }
 
+    /// Returns hashes of transactions that were the last to modify each storage slot in the L2 block.
+    fn extract_storage_slot_writers(&self) -> HashMap<StorageKey, H256> {
+        let mut writers = HashMap::new();
+        let txs_with_logs = self
+            .l2_block
+            .executed_transactions
+            .iter()
+            .zip(&self.l2_block.tx_storage_log_ranges);
+        for (tx, log_range) in txs_with_logs {
+            let tx_logs = &self.l2_block.storage_logs[log_range.clone()];
+            for log in tx_logs.iter().filter(|log| log.log.is_write()) {
+                writers.insert(log.log.key, tx.hash);
+            }
+        }
+        writers
+    }
+
     fn transaction(&self, index: usize) -> &Transaction {
         let tx_result = &self.l2_block.executed_transactions[index - self.first_tx_index];
         &tx_result.transaction
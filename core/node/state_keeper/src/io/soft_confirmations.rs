@@ -0,0 +1,140 @@
+//! Soft confirmations issued by the state keeper for included transactions.
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::{soft_confirmation::SoftConfirmationCommitment, K256PrivateKey};
+
+use crate::{
+    io::{IoCursor, StateKeeperOutputHandler},
+    updates::UpdatesManager,
+};
+
+/// Output handler signing a [`SoftConfirmationCommitment`] for each transaction in a sealed L2 block
+/// and persisting the signatures to Postgres, from which they are served by the `zks_getSoftConfirmation` method.
+///
+/// Signatures are produced as soon as the state keeper seals an L2 block, i.e. before the block is persisted;
+/// hence, they are a promise by the sequencer that the transaction will end up at the signed position.
+#[derive(Debug)]
+pub struct SoftConfirmationsPersistence {
+    pool: ConnectionPool<Core>,
+    private_key: K256PrivateKey,
+}
+
+impl SoftConfirmationsPersistence {
+    pub fn new(pool: ConnectionPool<Core>, private_key: K256PrivateKey) -> Self {
+        Self { pool, private_key }
+    }
+}
+
+#[async_trait]
+impl StateKeeperOutputHandler for SoftConfirmationsPersistence {
+    async fn initialize(&mut self, cursor: &IoCursor) -> anyhow::Result<()> {
+        // Pending L2 blocks will be re-executed, so confirmations for them will be re-issued.
+        let mut connection = self.pool.connection_tagged("state_keeper").await?;
+        connection
+            .soft_confirmations_dal()
+            .delete_soft_confirmations_after(cursor.next_l2_block - 1)
+            .await?;
+        Ok(())
+    }
+
+    async fn handle_l2_block(&mut self, updates_manager: &UpdatesManager) -> anyhow::Result<()> {
+        let l2_block = &updates_manager.l2_block;
+        let confirmations = l2_block
+            .executed_transactions
+            .iter()
+            .enumerate()
+            .map(|(index_in_block, tx)| {
+                let commitment = SoftConfirmationCommitment {
+                    tx_hash: tx.hash,
+                    l2_block_number: l2_block.number,
+                    index_in_block: index_in_block as u32,
+                };
+                Ok((commitment, commitment.sign(&self.private_key)?))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let mut connection = self.pool.connection_tagged("state_keeper").await?;
+        connection
+            .soft_confirmations_dal()
+            .insert_soft_confirmations(self.private_key.address(), &confirmations)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed persisting soft confirmations for L2 block #{}",
+                    l2_block.number
+                )
+            })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_multivm::interface::VmExecutionMetrics;
+    use zksync_types::H256;
+
+    use super::*;
+    use crate::tests::{create_execution_result, create_transaction, create_updates_manager};
+
+    #[tokio::test]
+    async fn soft_confirmations_are_signed_and_persisted() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(0x23)).unwrap();
+        let mut handler = SoftConfirmationsPersistence::new(pool.clone(), private_key.clone());
+
+        let mut updates = create_updates_manager();
+        let tx_hashes: Vec<_> = (0..2)
+            .map(|_| {
+                let tx = create_transaction(10, 100);
+                let tx_hash = tx.hash();
+                updates.extend_from_executed_transaction(
+                    tx,
+                    create_execution_result([]),
+                    VmExecutionMetrics::default(),
+                    vec![],
+                );
+                tx_hash
+            })
+            .collect();
+        handler.handle_l2_block(&updates).await.unwrap();
+        let l2_block_number = updates.l2_block.number;
+
+        let mut storage = pool.connection().await.unwrap();
+        for (i, &tx_hash) in tx_hashes.iter().enumerate() {
+            let confirmation = storage
+                .soft_confirmations_dal()
+                .get_soft_confirmation(tx_hash)
+                .await
+                .unwrap()
+                .expect("no soft confirmation");
+            assert_eq!(confirmation.block_number, l2_block_number);
+            assert_eq!(confirmation.transaction_index, i as u32);
+            assert_eq!(confirmation.signer, private_key.address());
+
+            let commitment = SoftConfirmationCommitment {
+                tx_hash,
+                l2_block_number,
+                index_in_block: i as u32,
+            };
+            let signer = commitment.recover_signer(&confirmation.signature).unwrap();
+            assert_eq!(signer, private_key.address());
+        }
+
+        // Confirmations for pending L2 blocks must be dropped on initialization since these blocks will be re-executed.
+        let cursor = IoCursor {
+            next_l2_block: l2_block_number,
+            prev_l2_block_hash: H256::zero(),
+            prev_l2_block_timestamp: 0,
+            l1_batch: updates.l1_batch.number,
+        };
+        handler.initialize(&cursor).await.unwrap();
+        let confirmation = storage
+            .soft_confirmations_dal()
+            .get_soft_confirmation(tx_hashes[0])
+            .await
+            .unwrap();
+        assert!(confirmation.is_none());
+    }
+}
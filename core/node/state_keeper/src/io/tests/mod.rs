@@ -1,6 +1,12 @@
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
+use assert_matches::assert_matches;
 use test_casing::test_casing;
+use tokio::sync::watch;
+use zksync_config::configs::TxPolicyConfig;
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_mempool::L2TxFilter;
@@ -13,6 +19,7 @@ use zksync_multivm::{
 use zksync_node_test_utils::prepare_recovery_snapshot;
 use zksync_system_constants::KNOWN_CODES_STORAGE_ADDRESS;
 use zksync_types::{
+    address_to_h256,
     block::L2BlockHasher,
     bytecode::BytecodeHash,
     commitment::{L1BatchCommitmentMode, PubdataParams},
@@ -21,15 +28,19 @@ use zksync_types::{
     protocol_upgrade::ProtocolUpgradeTx,
     protocol_version::ProtocolSemanticVersion,
     AccountTreeId, Address, L1BatchNumber, L2BlockNumber, L2ChainId, ProtocolVersion,
-    ProtocolVersionId, StorageKey, TransactionTimeRangeConstraint, H256, U256,
+    ProtocolVersionId, StorageKey, TransactionTimeRangeConstraint, CONTRACT_DEPLOYER_ADDRESS, H256,
+    U256,
 };
 
 use self::tester::Tester;
 use crate::{
     io::{seal_logic::l2_block_seal_subtasks::L2BlockSealProcess, StateKeeperIO},
     mempool_actor::l2_tx_filter,
+    seal_criteria::UnexecutableReason,
+    sequencer_lease::SequencerLeaseTask,
     testonly::BASE_SYSTEM_CONTRACTS,
     tests::{create_execution_result, create_transaction, seconds_since_epoch, Query},
+    tx_policy::{TxPolicy, TxPolicyViolation},
     updates::{L2BlockSealCommand, L2BlockUpdates, UpdatesManager},
     StateKeeperOutputHandler, StateKeeperPersistence,
 };
@@ -249,6 +260,8 @@ fn create_block_seal_command(
         l2_legacy_shared_bridge_addr: Some(Address::default()),
         pre_insert_txs: false,
         pubdata_params: PubdataParams::default(),
+        fence: None,
+        record_storage_slot_writers: false,
     }
 }
 
@@ -341,6 +354,92 @@ async fn processing_storage_logs_when_sealing_l2_block() {
     }
 }
 
+#[tokio::test]
+async fn fenced_l2_block_is_not_persisted_after_losing_lease() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    pool.connection()
+        .await
+        .unwrap()
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+    let lease_task =
+        SequencerLeaseTask::new(pool.clone(), "primary".to_owned(), Duration::from_secs(60));
+    let fence = lease_task.fence();
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    tokio::spawn(lease_task.run(stop_receiver.clone()));
+    assert!(fence
+        .wait_for_lease(&mut stop_receiver.clone())
+        .await
+        .unwrap());
+
+    let l1_batch_number = L1BatchNumber(2);
+    let mut l2_block = L2BlockUpdates::new(
+        0,
+        L2BlockNumber(3),
+        H256::zero(),
+        1,
+        ProtocolVersionId::latest(),
+    );
+    let tx = create_transaction(10, 100);
+    let tx_hash = tx.hash();
+    l2_block.extend_from_executed_transaction(
+        tx,
+        create_execution_result([]),
+        VmExecutionMetrics::default(),
+        vec![],
+    );
+    let mut seal_command = create_block_seal_command(l1_batch_number, l2_block);
+    seal_command.fence = Some(fence);
+    seal_command.seal(pool.clone()).await.unwrap();
+
+    let mut conn = pool.connection().await.unwrap();
+    conn.sequencer_lease_dal()
+        .transfer_lease("standby", Duration::from_secs(60))
+        .await
+        .unwrap();
+
+    let mut l2_block = L2BlockUpdates::new(
+        1,
+        L2BlockNumber(4),
+        H256::zero(),
+        1,
+        ProtocolVersionId::latest(),
+    );
+    let tx = create_transaction(10, 100);
+    let lost_tx_hash = tx.hash();
+    l2_block.extend_from_executed_transaction(
+        tx,
+        create_execution_result([]),
+        VmExecutionMetrics::default(),
+        vec![],
+    );
+    seal_command.l2_block = l2_block;
+    let err = seal_command.seal(pool.clone()).await.unwrap_err();
+    assert!(format!("{err:#}").contains("lost"), "{err:#}");
+
+    // No data for the L2 block must be persisted, including data written by seal sub-tasks.
+    let sealed_l2_block = conn
+        .blocks_dal()
+        .get_sealed_l2_block_number()
+        .await
+        .unwrap();
+    assert_eq!(sealed_l2_block, Some(L2BlockNumber(3)));
+    let tx = conn
+        .transactions_dal()
+        .get_storage_tx_by_hash(tx_hash)
+        .await
+        .unwrap();
+    assert!(tx.is_some());
+    let lost_tx = conn
+        .transactions_dal()
+        .get_storage_tx_by_hash(lost_tx_hash)
+        .await
+        .unwrap();
+    assert!(lost_tx.is_none());
+}
+
 #[tokio::test]
 async fn processing_events_when_sealing_l2_block() {
     let pool =
@@ -850,6 +949,107 @@ async fn test_mempool_with_timestamp_assertion() {
     );
 }
 
+#[tokio::test]
+async fn mempool_enforces_tx_policy() {
+    let connection_pool = ConnectionPool::<Core>::constrained_test_pool(2).await;
+    let tester = Tester::new(L1BatchCommitmentMode::Rollup);
+    let mut storage = connection_pool.connection().await.unwrap();
+    tester.genesis(&connection_pool).await;
+
+    let (mempool, mut guard) = tester.create_test_mempool_io(connection_pool).await;
+    let filter = l2_tx_filter(
+        &tester.create_batch_fee_input_provider().await,
+        ProtocolVersionId::latest().into(),
+    )
+    .await
+    .unwrap();
+    let rejected_tx = tester.insert_tx(
+        &mut guard,
+        filter.fee_per_gas,
+        filter.gas_per_pubdata,
+        TransactionTimeRangeConstraint::default(),
+    );
+    let expected_tx = tester.insert_tx(
+        &mut guard,
+        filter.fee_per_gas,
+        filter.gas_per_pubdata,
+        TransactionTimeRangeConstraint::default(),
+    );
+    insert_l2_transaction(&mut storage, &rejected_tx).await;
+    insert_l2_transaction(&mut storage, &expected_tx).await;
+
+    let policy = TxPolicy::new(&TxPolicyConfig {
+        denied_addresses: vec![rejected_tx.initiator_account()],
+        ..TxPolicyConfig::default()
+    });
+    let mut mempool = mempool.with_tx_policy(Arc::new(policy));
+    mempool.initialize().await.unwrap();
+
+    let tx = mempool
+        .wait_for_next_tx(Duration::from_secs(2), seconds_since_epoch())
+        .await
+        .unwrap()
+        .expect("No expected transaction in the mempool");
+    assert_eq!(tx.hash(), expected_tx.hash());
+
+    let rejected_storage_tx = storage
+        .transactions_dal()
+        .get_storage_tx_by_hash(rejected_tx.hash())
+        .await
+        .unwrap()
+        .expect("Failed to find transaction");
+    assert!(rejected_storage_tx
+        .error
+        .unwrap()
+        .starts_with("rejected: Transaction policy violation"),);
+    let decisions = storage
+        .tx_policy_decisions_dal()
+        .get_latest_decisions(Some(rejected_tx.initiator_account()), 10)
+        .await
+        .unwrap();
+    assert_eq!(decisions.len(), 1);
+    assert_eq!(decisions[0].transaction_hash, rejected_tx.hash());
+    assert_eq!(decisions[0].stage, "inclusion");
+    assert_eq!(decisions[0].rule, "address_denied");
+}
+
+#[tokio::test]
+async fn mempool_enforces_deployer_policy_after_execution() {
+    let connection_pool = ConnectionPool::<Core>::constrained_test_pool(2).await;
+    let tester = Tester::new(L1BatchCommitmentMode::Rollup);
+    tester.genesis(&connection_pool).await;
+    let (mempool, _) = tester.create_test_mempool_io(connection_pool).await;
+
+    let factory = Address::repeat_byte(0x42);
+    let policy = TxPolicy::new(&TxPolicyConfig {
+        allowed_deployers: Some(vec![]),
+        ..TxPolicyConfig::default()
+    });
+    let mempool = mempool.with_tx_policy(Arc::new(policy));
+
+    let tx = create_transaction(10, 100);
+    let mut tx_result = create_execution_result([]);
+    assert!(mempool.check_executed_tx(&tx, &tx_result).is_none());
+
+    tx_result.logs.events.push(VmEvent {
+        location: (L1BatchNumber(1), 0),
+        address: CONTRACT_DEPLOYER_ADDRESS,
+        indexed_topics: vec![
+            VmEvent::DEPLOY_EVENT_SIGNATURE,
+            address_to_h256(&factory),
+            H256::zero(),
+            address_to_h256(&Address::repeat_byte(0x43)),
+        ],
+        value: vec![],
+    });
+    let reason = mempool.check_executed_tx(&tx, &tx_result).unwrap();
+    assert_matches!(
+        reason,
+        UnexecutableReason::PolicyViolation(TxPolicyViolation::DeployerNotAllowed(address))
+            if address == factory
+    );
+}
+
 #[tokio::test]
 async fn test_batch_params_with_protocol_upgrade_tx() {
     let connection_pool = ConnectionPool::<Core>::constrained_test_pool(2).await;
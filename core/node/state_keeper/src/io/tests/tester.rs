@@ -85,6 +85,8 @@ impl Tester {
             num_samples_for_blob_base_fee_estimate: 10,
             internal_pubdata_pricing_multiplier: 1.0,
             max_blob_base_fee: None,
+            fee_sources_quorum: None,
+            fee_sources_max_deviation: 0.5,
         };
 
         let client: Box<DynClient<L1>> = Box::new(eth_client.into_client());
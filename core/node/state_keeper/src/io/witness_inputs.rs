@@ -0,0 +1,303 @@
+//! Pre-generation of VM run data for witness inputs in the state keeper.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_object_store::ObjectStore;
+use zksync_prover_interface::inputs::VMRunWitnessInputData;
+use zksync_types::{
+    h256_to_u256, u256_to_h256, witness_block_state::WitnessStorageState, L1BatchNumber, H256, U256,
+};
+
+use crate::{io::StateKeeperOutputHandler, updates::UpdatesManager};
+
+/// Output handler generating [`VMRunWitnessInputData`] for sealed L1 batches.
+///
+/// The data is collected from the state keeper output, so that batches don't need to be re-executed by
+/// the basic witness input producer (BWIP) afterwards. Bytecodes deployed in the batch are collected incrementally
+/// as L2 blocks are sealed; other used bytecodes are loaded from Postgres once the batch is sealed. Batches processed
+/// by this handler are marked as processed for BWIP, so BWIP skips them if it's running.
+///
+/// This handler must be placed after [`StateKeeperPersistence`](crate::StateKeeperPersistence) so that
+/// the batch is persisted by the time the handler processes it.
+#[derive(Debug)]
+pub struct WitnessInputsPersistence {
+    pool: ConnectionPool<Core>,
+    object_store: Arc<dyn ObjectStore>,
+    /// Number of the L1 batch that `deployed_bytecodes` correspond to.
+    l1_batch_number: Option<L1BatchNumber>,
+    deployed_bytecodes: HashMap<H256, Vec<u8>>,
+}
+
+impl WitnessInputsPersistence {
+    pub fn new(pool: ConnectionPool<Core>, object_store: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            pool,
+            object_store,
+            l1_batch_number: None,
+            deployed_bytecodes: HashMap::new(),
+        }
+    }
+
+    async fn load_bytecode(
+        connection: &mut Connection<'_, Core>,
+        hash: H256,
+        name: &str,
+    ) -> anyhow::Result<Vec<u8>> {
+        connection
+            .factory_deps_dal()
+            .get_sealed_factory_dep(hash)
+            .await?
+            .with_context(|| format!("{name} bytecode {hash:?} is missing in Postgres"))
+    }
+
+    async fn witness_input_data(
+        &mut self,
+        connection: &mut Connection<'_, Core>,
+        updates_manager: &UpdatesManager,
+    ) -> anyhow::Result<VMRunWitnessInputData> {
+        let l1_batch_number = updates_manager.l1_batch.number;
+        let finished_batch = updates_manager
+            .l1_batch
+            .finished
+            .as_ref()
+            .context("L1 batch is not finished")?;
+        let storage_view_cache = updates_manager
+            .storage_view_cache()
+            .context("storage view cache is not set for the finished L1 batch")?;
+        let hashes = updates_manager.base_system_contract_hashes();
+
+        let bootloader_code =
+            Self::load_bytecode(connection, hashes.bootloader, "bootloader").await?;
+        let default_aa_code_hash = h256_to_u256(hashes.default_aa);
+        let evm_emulator_code_hash = hashes.evm_emulator.map(h256_to_u256);
+
+        let used_contract_hashes = &finished_batch.final_execution_state.used_contract_hashes;
+        let mut used_bytecodes = HashMap::with_capacity(used_contract_hashes.len());
+        let mut missing_hashes = HashSet::new();
+        for &hash in used_contract_hashes {
+            // SMA-1555: remove this hack once updated to the latest version of `zkevm_test_harness`
+            if hash == h256_to_u256(hashes.bootloader) {
+                continue;
+            }
+            let hash_h256 = u256_to_h256(hash);
+            if let Some(bytecode) = self.deployed_bytecodes.remove(&hash_h256) {
+                used_bytecodes.insert(hash, bytecode);
+            } else if hash == default_aa_code_hash {
+                let bytecode =
+                    Self::load_bytecode(connection, hashes.default_aa, "default account").await?;
+                used_bytecodes.insert(hash, bytecode);
+            } else if Some(hash) == evm_emulator_code_hash {
+                let bytecode = Self::load_bytecode(connection, hash_h256, "EVM emulator").await?;
+                used_bytecodes.insert(hash, bytecode);
+            } else {
+                missing_hashes.insert(hash_h256);
+            }
+        }
+        let loaded_bytecodes = connection
+            .factory_deps_dal()
+            .get_factory_deps(&missing_hashes)
+            .await;
+        anyhow::ensure!(
+            loaded_bytecodes.len() == missing_hashes.len(),
+            "{} used bytecodes are missing in Postgres",
+            missing_hashes.len() - loaded_bytecodes.len()
+        );
+        used_bytecodes.extend(loaded_bytecodes);
+
+        Ok(VMRunWitnessInputData {
+            l1_batch_number,
+            used_bytecodes: used_bytecodes
+                .into_iter()
+                .map(|(hash, code)| (hash, bytes_to_chunks(&code)))
+                .collect(),
+            initial_heap_content: finished_batch
+                .final_bootloader_memory
+                .clone()
+                .unwrap_or_default(),
+            protocol_version: updates_manager.protocol_version(),
+            bootloader_code: bytes_to_chunks(&bootloader_code),
+            default_account_code_hash: default_aa_code_hash,
+            evm_emulator_code_hash,
+            storage_refunds: finished_batch.final_execution_state.storage_refunds.clone(),
+            pubdata_costs: finished_batch.final_execution_state.pubdata_costs.clone(),
+            witness_block_state: WitnessStorageState {
+                read_storage_key: storage_view_cache.read_storage_keys(),
+                is_write_initial: storage_view_cache.initial_writes(),
+            },
+            base_system_contracts_hashes: Some(hashes),
+            precompile_inputs: finished_batch.precompile_inputs.clone(),
+            _marker: std::marker::PhantomData,
+        })
+    }
+}
+
+fn bytes_to_chunks(bytes: &[u8]) -> Vec<[u8; 32]> {
+    bytes
+        .chunks(32)
+        .map(|chunk| chunk.try_into().unwrap())
+        .collect()
+}
+
+#[async_trait]
+impl StateKeeperOutputHandler for WitnessInputsPersistence {
+    async fn handle_l2_block(&mut self, updates_manager: &UpdatesManager) -> anyhow::Result<()> {
+        let l1_batch_number = updates_manager.l1_batch.number;
+        if self.l1_batch_number != Some(l1_batch_number) {
+            self.l1_batch_number = Some(l1_batch_number);
+            self.deployed_bytecodes.clear();
+        }
+        self.deployed_bytecodes.extend(
+            updates_manager
+                .l2_block
+                .new_factory_deps
+                .iter()
+                .map(|(hash, bytecode)| (*hash, bytecode.clone())),
+        );
+        Ok(())
+    }
+
+    async fn handle_l1_batch(
+        &mut self,
+        updates_manager: Arc<UpdatesManager>,
+    ) -> anyhow::Result<()> {
+        let l1_batch_number = updates_manager.l1_batch.number;
+        if self.l1_batch_number != Some(l1_batch_number) {
+            // The batch was (partially) re-executed after a restart; bytecodes deployed in it will be loaded from Postgres.
+            self.deployed_bytecodes.clear();
+        }
+
+        let mut connection = self.pool.connection_tagged("state_keeper").await?;
+        let data = self
+            .witness_input_data(&mut connection, &updates_manager)
+            .await
+            .with_context(|| {
+                format!("failed generating VM run data for L1 batch #{l1_batch_number}")
+            })?;
+        self.l1_batch_number = None;
+        self.deployed_bytecodes.clear();
+
+        let blob_url = self.object_store.put(l1_batch_number, &data).await?;
+        tracing::info!("Saved VM run data for L1 batch #{l1_batch_number} to {blob_url}");
+
+        let mut transaction = connection.start_transaction().await?;
+        transaction
+            .proof_generation_dal()
+            .insert_proof_generation_details(l1_batch_number)
+            .await?;
+        transaction
+            .proof_generation_dal()
+            .save_vm_runner_artifacts_metadata(l1_batch_number, &blob_url)
+            .await?;
+        transaction
+            .vm_runner_dal()
+            .mark_bwip_batch_as_processing(l1_batch_number)
+            .await?;
+        transaction
+            .vm_runner_dal()
+            .mark_bwip_batch_as_completed(l1_batch_number)
+            .await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_multivm::interface::{storage::StorageViewCache, FinishedL1Batch};
+    use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+    use zksync_object_store::MockObjectStore;
+    use zksync_types::{block::UnsealedL1BatchHeader, bytecode::BytecodeHash};
+
+    use super::*;
+    use crate::tests::create_updates_manager;
+
+    #[tokio::test]
+    async fn witness_inputs_are_generated() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+        let object_store = MockObjectStore::arc();
+        let mut handler = WitnessInputsPersistence::new(pool.clone(), object_store.clone());
+
+        let mut updates = create_updates_manager();
+        let bytecode = vec![1_u8; 64];
+        let bytecode_hash = BytecodeHash::for_bytecode(&bytecode).value();
+        updates
+            .l2_block
+            .new_factory_deps
+            .insert(bytecode_hash, bytecode.clone());
+        handler.handle_l2_block(&updates).await.unwrap();
+
+        let mut finished_batch = FinishedL1Batch::mock();
+        finished_batch.final_execution_state.used_contract_hashes =
+            vec![h256_to_u256(bytecode_hash)];
+        finished_batch.final_execution_state.storage_refunds = vec![1, 2];
+        updates.finish_batch(finished_batch);
+        updates.update_storage_view_cache(StorageViewCache::default());
+        let l1_batch_number = updates.l1_batch.number;
+        // Emulate batch persistence by the main persistence handler.
+        storage
+            .blocks_dal()
+            .insert_l1_batch(UnsealedL1BatchHeader {
+                number: l1_batch_number,
+                timestamp: updates.batch_timestamp(),
+                protocol_version: Some(updates.protocol_version()),
+                fee_address: updates.fee_account_address,
+                fee_input: updates.batch_fee_input,
+            })
+            .await
+            .unwrap();
+
+        handler.handle_l1_batch(Arc::new(updates)).await.unwrap();
+
+        let data: VMRunWitnessInputData = object_store.get(l1_batch_number).await.unwrap();
+        assert_eq!(data.l1_batch_number, l1_batch_number);
+        assert_eq!(data.storage_refunds, [1, 2]);
+        assert_eq!(
+            data.used_bytecodes[&h256_to_u256(bytecode_hash)],
+            bytes_to_chunks(&bytecode)
+        );
+        assert!(!data.bootloader_code.is_empty());
+
+        let bwip_batch = storage
+            .vm_runner_dal()
+            .get_bwip_latest_processed_batch()
+            .await
+            .unwrap();
+        assert_eq!(bwip_batch, Some(l1_batch_number));
+    }
+
+    #[tokio::test]
+    async fn missing_bytecodes_are_reported() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+        let mut handler = WitnessInputsPersistence::new(pool.clone(), MockObjectStore::arc());
+
+        let mut updates = create_updates_manager();
+        handler.handle_l2_block(&updates).await.unwrap();
+        let mut finished_batch = FinishedL1Batch::mock();
+        finished_batch.final_execution_state.used_contract_hashes = vec![U256::from(1)];
+        updates.finish_batch(finished_batch);
+        updates.update_storage_view_cache(StorageViewCache::default());
+
+        let err = handler
+            .handle_l1_batch(Arc::new(updates))
+            .await
+            .unwrap_err();
+        assert!(
+            format!("{err:#}").contains("missing in Postgres"),
+            "{err:#}"
+        );
+    }
+}
@@ -7,6 +7,7 @@ use std::{
 use anyhow::Context as _;
 use tokio::sync::watch;
 use tracing::{info_span, Instrument};
+use zksync_dal::unsealed_batch_checkpoints_dal::UnsealedBatchCheckpoint;
 use zksync_health_check::{HealthUpdater, ReactiveHealthCheck};
 use zksync_multivm::{
     interface::{
@@ -28,7 +29,9 @@ use crate::{
     health::StateKeeperHealthDetails,
     io::{IoCursor, L1BatchParams, L2BlockParams, OutputHandler, PendingBatchData, StateKeeperIO},
     metrics::{AGGREGATION_METRICS, KEEPER_METRICS, L1_BATCH_METRICS},
+    pending_state::{PendingBatchStatePublisher, PendingBatchStateReader},
     seal_criteria::{ConditionalSealer, SealData, SealResolution, UnexecutableReason},
+    timestamp_policy::validate_next_timestamp,
     updates::UpdatesManager,
     utils::is_canceled,
 };
@@ -42,6 +45,10 @@ pub(super) const POLL_WAIT_DURATION: Duration = Duration::from_secs(1);
 pub(super) enum Error {
     #[error("canceled")]
     Canceled,
+    /// The pending state was reverted in the storage (only possible in the dev mode); the state keeper
+    /// must be re-initialized.
+    #[error("reinitialization requested")]
+    Reinitialize,
     #[error(transparent)]
     Fatal(#[from] anyhow::Error),
 }
@@ -50,6 +57,7 @@ impl Error {
     fn context(self, msg: &'static str) -> Self {
         match self {
             Self::Canceled => Self::Canceled,
+            Self::Reinitialize => Self::Reinitialize,
             Self::Fatal(err) => Self::Fatal(err.context(msg)),
         }
     }
@@ -73,6 +81,8 @@ pub struct ZkSyncStateKeeper {
     sealer: Arc<dyn ConditionalSealer>,
     storage_factory: Arc<dyn ReadStorageFactory>,
     health_updater: HealthUpdater,
+    pending_state: Option<PendingBatchStatePublisher>,
+    keep_storage_view_cache: bool,
 }
 
 impl ZkSyncStateKeeper {
@@ -90,15 +100,37 @@ impl ZkSyncStateKeeper {
             sealer,
             storage_factory,
             health_updater: ReactiveHealthCheck::new("state_keeper").1,
+            pending_state: None,
+            keep_storage_view_cache: false,
         }
     }
 
+    /// Makes the state keeper provide the storage view cache of each sealed L1 batch to output handlers
+    /// (see [`UpdatesManager::storage_view_cache()`]). The cache is cloned on each batch seal, so this should
+    /// only be enabled if some output handler needs it, e.g. to pre-generate witness inputs.
+    pub fn with_storage_view_cache(mut self, keep_storage_view_cache: bool) -> Self {
+        self.keep_storage_view_cache = keep_storage_view_cache;
+        self
+    }
+
+    /// Makes the state keeper publish state changes of the L1 batch being processed after each executed transaction
+    /// (see [`Self::pending_state()`]).
+    pub fn with_pending_batch_state(mut self, enabled: bool) -> Self {
+        self.pending_state = enabled.then(PendingBatchStatePublisher::new);
+        self
+    }
+
     pub async fn run(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
-        match self.run_inner(stop_receiver).await {
-            Err(Error::Fatal(err)) => Err(err).context("state_keeper failed"),
-            Err(Error::Canceled) => {
-                tracing::info!("Stop signal received, state keeper is shutting down");
-                Ok(())
+        loop {
+            match self.run_inner(stop_receiver.clone()).await {
+                Err(Error::Fatal(err)) => return Err(err).context("state_keeper failed"),
+                Err(Error::Canceled) => {
+                    tracing::info!("Stop signal received, state keeper is shutting down");
+                    return Ok(());
+                }
+                Err(Error::Reinitialize) => {
+                    tracing::info!("Pending state was reverted, re-initializing state keeper");
+                }
             }
         }
     }
@@ -108,6 +140,10 @@ impl ZkSyncStateKeeper {
         &mut self,
         mut stop_receiver: watch::Receiver<bool>,
     ) -> Result<Infallible, Error> {
+        // The pending state may have been reverted, so the published snapshot must be discarded.
+        if let Some(pending_state) = &mut self.pending_state {
+            pending_state.reset();
+        }
         let (cursor, pending_batch_params) = self.io.initialize().await?;
         self.output_handler.initialize(&cursor).await?;
         self.health_updater
@@ -124,6 +160,7 @@ impl ZkSyncStateKeeper {
             mut system_env,
             mut pubdata_params,
             pending_l2_blocks,
+            checkpoints,
         } = match pending_batch_params {
             Some(params) => {
                 tracing::info!(
@@ -148,6 +185,7 @@ impl ZkSyncStateKeeper {
                     pending_l2_blocks: Vec::new(),
                     system_env,
                     pubdata_params,
+                    checkpoints: Vec::new(),
                 }
             }
         };
@@ -179,6 +217,7 @@ impl ZkSyncStateKeeper {
             &mut *batch_executor,
             &mut updates_manager,
             pending_l2_blocks,
+            &checkpoints,
             &stop_receiver,
         )
         .await?;
@@ -207,9 +246,12 @@ impl ZkSyncStateKeeper {
                 Self::start_next_l2_block(&mut updates_manager, &mut *batch_executor).await?;
             }
 
-            let (finished_batch, _) = batch_executor.finish_batch().await?;
+            let (finished_batch, storage_view) = batch_executor.finish_batch().await?;
             let sealed_batch_protocol_version = updates_manager.protocol_version();
             updates_manager.finish_batch(finished_batch);
+            if self.keep_storage_view_cache {
+                updates_manager.update_storage_view_cache(storage_view.cache());
+            }
             let mut next_cursor = updates_manager.io_cursor();
             let previous_batch_protocol_version = updates_manager.protocol_version();
             self.output_handler
@@ -346,6 +388,12 @@ impl ZkSyncStateKeeper {
                 .wait_for_new_batch_params(cursor, POLL_WAIT_DURATION)
                 .await?
             {
+                validate_next_timestamp(
+                    cursor.prev_l2_block_timestamp,
+                    params.first_l2_block.timestamp,
+                    cursor.next_l2_block,
+                )
+                .with_context(|| format!("invalid params for L1 batch #{}", cursor.l1_batch))?;
                 return Ok(params);
             }
         }
@@ -410,6 +458,12 @@ impl ZkSyncStateKeeper {
                 .await
                 .context("error waiting for new L2 block params")?
             {
+                validate_next_timestamp(
+                    cursor.prev_l2_block_timestamp,
+                    params.timestamp,
+                    cursor.next_l2_block,
+                )
+                .context("invalid L2 block params")?;
                 self.health_updater
                     .update(StateKeeperHealthDetails::from(&cursor).into());
 
@@ -491,12 +545,15 @@ impl ZkSyncStateKeeper {
         batch_executor: &mut dyn BatchExecutor<OwnedStorage>,
         updates_manager: &mut UpdatesManager,
         l2_blocks_to_reexecute: Vec<L2BlockExecutionData>,
+        checkpoints: &[UnsealedBatchCheckpoint],
         stop_receiver: &watch::Receiver<bool>,
     ) -> Result<(), Error> {
         if l2_blocks_to_reexecute.is_empty() {
             return Ok(());
         }
 
+        // Checkpoints are ordered by the L2 block number, as are the re-executed L2 blocks.
+        let mut checkpoints = checkpoints.iter().peekable();
         for (index, l2_block) in l2_blocks_to_reexecute.into_iter().enumerate() {
             // Push any non-first L2 block to updates manager. The first one was pushed when `updates_manager` was initialized.
             if index > 0 {
@@ -553,6 +610,7 @@ impl ZkSyncStateKeeper {
                     *tx_execution_metrics,
                     call_tracer_result,
                 );
+                self.sync_pending_state(updates_manager);
 
                 tracing::debug!(
                     "Finished re-executing tx {tx_hash} by {initiator_account} (is_l1: {is_l1}, \
@@ -564,6 +622,14 @@ impl ZkSyncStateKeeper {
                     block_execution_metrics = updates_manager.pending_execution_metrics()
                 );
             }
+
+            while let Some(checkpoint) =
+                checkpoints.next_if(|checkpoint| checkpoint.l2_block_number <= l2_block_number)
+            {
+                if checkpoint.l2_block_number == l2_block_number {
+                    Self::verify_checkpoint(updates_manager, checkpoint)?;
+                }
+            }
         }
 
         tracing::debug!(
@@ -580,6 +646,28 @@ impl ZkSyncStateKeeper {
         Ok(())
     }
 
+    /// Checks that the state reached by re-executing the pending batch matches a checkpoint persisted
+    /// before the restart. A mismatch means that re-execution is not deterministic (e.g., because of a changed
+    /// VM version or corrupted storage), so the state keeper stops rather than seal a diverged batch.
+    fn verify_checkpoint(
+        updates_manager: &UpdatesManager,
+        checkpoint: &UnsealedBatchCheckpoint,
+    ) -> anyhow::Result<()> {
+        let tx_count = updates_manager.pending_executed_transactions_len();
+        let storage_logs_checksum = updates_manager.pending_storage_logs_checksum();
+        anyhow::ensure!(
+            tx_count == checkpoint.tx_count
+                && storage_logs_checksum == checkpoint.storage_logs_checksum,
+            "Re-executed state diverged from checkpoint {checkpoint:?}: tx_count = {tx_count}, \
+             storage_logs_checksum = {storage_logs_checksum:?}"
+        );
+        tracing::info!(
+            "Re-executed state matches checkpoint for L2 block #{}",
+            checkpoint.l2_block_number
+        );
+        Ok(())
+    }
+
     #[tracing::instrument(
         skip_all,
         fields(l1_batch = %updates_manager.l1_batch.number)
@@ -599,6 +687,15 @@ impl ZkSyncStateKeeper {
         while !is_canceled(stop_receiver) {
             let full_latency = KEEPER_METRICS.process_l1_batch_loop_iteration.start();
 
+            if self
+                .io
+                .process_dev_mode_requests(updates_manager)
+                .await
+                .context("failed processing dev mode requests")?
+            {
+                return Err(Error::Reinitialize);
+            }
+
             if self
                 .io
                 .should_seal_l1_batch_unconditionally(updates_manager)
@@ -695,6 +792,7 @@ impl ZkSyncStateKeeper {
                         *tx_execution_metrics,
                         call_tracer_result,
                     );
+                    self.sync_pending_state(updates_manager);
                 }
                 SealResolution::ExcludeAndSeal => {
                     batch_executor.rollback_last_tx().await.with_context(|| {
@@ -768,6 +866,7 @@ impl ZkSyncStateKeeper {
                     *tx_execution_metrics,
                     call_tracer_result,
                 );
+                self.sync_pending_state(updates_manager);
                 Ok(())
             }
             SealResolution::ExcludeAndSeal => {
@@ -907,6 +1006,15 @@ impl ZkSyncStateKeeper {
                 )
             }
         };
+        // Rules enforced by the I/O take precedence over the seal criteria, since a rejected transaction
+        // is never included.
+        let resolution = match &exec_result {
+            TxExecutionResult::Success { tx_result, .. } => self
+                .io
+                .check_executed_tx(&tx, tx_result)
+                .map_or(resolution, SealResolution::Unexecutable),
+            _ => resolution,
+        };
         latency.observe();
         Ok((resolution, exec_result))
     }
@@ -915,4 +1023,18 @@ impl ZkSyncStateKeeper {
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
     }
+
+    /// Returns a read handle for the state of the L1 batch being processed, including unsealed transactions.
+    /// Returns `None` unless publishing this state was enabled via [`Self::with_pending_batch_state()`].
+    pub fn pending_state(&self) -> Option<PendingBatchStateReader> {
+        self.pending_state
+            .as_ref()
+            .map(PendingBatchStatePublisher::subscribe)
+    }
+
+    fn sync_pending_state(&mut self, updates_manager: &UpdatesManager) {
+        if let Some(pending_state) = &mut self.pending_state {
+            pending_state.sync(updates_manager);
+        }
+    }
 }
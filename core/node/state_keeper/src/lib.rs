@@ -1,27 +1,39 @@
 pub use self::{
+    dev_mode::{DevModeControl, StateKeeperUnavailable},
     io::{
-        mempool::MempoolIO, L2BlockParams, L2BlockSealerTask, OutputHandler, StateKeeperIO,
-        StateKeeperOutputHandler, StateKeeperPersistence, TreeWritesPersistence,
+        mempool::MempoolIO, BatchCheckpointsPersistence, L2BlockParams, L2BlockSealerTask,
+        OutputHandler, SoftConfirmationsPersistence, StateKeeperIO, StateKeeperOutputHandler,
+        StateKeeperPersistence, TreeWritesPersistence, WitnessInputsPersistence,
     },
     keeper::ZkSyncStateKeeper,
     mempool_actor::MempoolFetcher,
-    seal_criteria::SequencerSealer,
+    pending_state::{PendingBatchState, PendingBatchStateReader},
+    seal_criteria::{
+        GeometryAdjustmentConfig, GeometrySealAdjuster, L1BatchSealRequest, SequencerSealer,
+        SharedSealerConfig,
+    },
+    sequencer_lease::{SequencerFence, SequencerLeaseTask},
     state_keeper_storage::AsyncRocksdbCache,
     types::MempoolGuard,
     updates::UpdatesManager,
 };
 
+pub mod dev_mode;
 pub mod executor;
 mod health;
 pub mod io;
 mod keeper;
 mod mempool_actor;
 pub mod metrics;
+mod pending_state;
 pub mod seal_criteria;
+mod sequencer_lease;
 mod state_keeper_storage;
 pub mod testonly;
 #[cfg(test)]
 pub(crate) mod tests;
+pub mod timestamp_policy;
+pub mod tx_policy;
 pub(crate) mod types;
 pub mod updates;
 pub(crate) mod utils;
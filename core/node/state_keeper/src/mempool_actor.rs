@@ -14,7 +14,7 @@ use zksync_types::H256;
 use zksync_types::{get_nonce_key, vm::VmVersion, Address, Nonce, Transaction};
 
 use super::{metrics::KEEPER_METRICS, types::MempoolGuard};
-use crate::v26_utils::find_unsafe_deposit;
+use crate::{sequencer_lease::SequencerFence, v26_utils::find_unsafe_deposit};
 
 /// Creates a mempool filter for L2 transactions based on the current L1 gas price.
 /// The filter is used to filter out transactions from the mempool that do not cover expenses
@@ -42,6 +42,7 @@ pub struct MempoolFetcher {
     stuck_tx_timeout: Option<Duration>,
     skip_unsafe_deposit_checks: bool,
     l1_to_l2_txs_paused: bool,
+    sequencer_fence: Option<SequencerFence>,
     #[cfg(test)]
     transaction_hashes_sender: mpsc::UnboundedSender<Vec<H256>>,
 }
@@ -61,13 +62,32 @@ impl MempoolFetcher {
             stuck_tx_timeout: config.remove_stuck_txs.then(|| config.stuck_tx_timeout()),
             skip_unsafe_deposit_checks: config.skip_unsafe_deposit_checks,
             l1_to_l2_txs_paused: config.l1_to_l2_txs_paused,
+            sequencer_fence: None,
             #[cfg(test)]
             transaction_hashes_sender: mpsc::unbounded_channel().0,
         }
     }
 
-    pub async fn run(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+    /// Makes the fetcher wait until this instance acquires the sequencer lease before populating the mempool.
+    pub fn with_sequencer_fence(mut self, fence: SequencerFence) -> Self {
+        self.sequencer_fence = Some(fence);
+        self
+    }
+
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        if let Some(fence) = &self.sequencer_fence {
+            if !fence.wait_for_lease(&mut stop_receiver).await? {
+                tracing::info!("Stop signal received, mempool is shutting down");
+                return Ok(());
+            }
+        }
+
         let mut storage = self.pool.connection_tagged("state_keeper").await?;
+        if self.sequencer_fence.is_some() {
+            // Priority operations may have been processed by the former lease holder since the mempool was created.
+            let next_priority_id = storage.transactions_dal().next_priority_id().await;
+            self.mempool.clear(next_priority_id);
+        }
         if let Some(stuck_tx_timeout) = self.stuck_tx_timeout {
             let removed_txs = storage
                 .transactions_dal()
@@ -76,7 +96,7 @@ impl MempoolFetcher {
                 .context("failed removing stuck transactions")?;
             tracing::info!("Number of stuck txs was removed: {removed_txs}");
         }
-        storage.transactions_dal().reset_mempool().await?;
+        recover_pending_l2_txs(&mut storage).await?;
         drop(storage);
 
         loop {
@@ -205,15 +225,69 @@ impl MempoolFetcher {
     }
 }
 
+/// Prepares pending L2 transactions persisted in Postgres to be loaded into the mempool after a restart.
+///
+/// L2 transactions are persisted by the API server before being acknowledged, so Postgres serves as a journal
+/// for the mempool: no acknowledged transactions are lost if the state keeper crashes. On startup, all pending
+/// transactions are returned to the mempool; transactions with a nonce that was already used by the initiator
+/// (e.g., because a transaction with the same nonce was executed before the crash) can never be executed, so they are
+/// marked as rejected instead of being replayed.
+async fn recover_pending_l2_txs(storage: &mut Connection<'_, Core>) -> anyhow::Result<()> {
+    let mut transaction = storage.start_transaction().await?;
+    transaction.transactions_dal().reset_mempool().await?;
+    let pending_txs = transaction
+        .transactions_dal()
+        .get_pending_l2_tx_nonces()
+        .await
+        .context("failed getting pending L2 transactions")?;
+    let initiators = pending_txs.iter().map(|&(_, initiator, _)| initiator);
+    let nonces = get_account_nonces(&mut transaction, initiators).await?;
+
+    let stale_tx_hashes: Vec<_> = pending_txs
+        .iter()
+        .filter(|(_, initiator, nonce)| nonces.get(initiator).is_some_and(|next| nonce < next))
+        .map(|&(hash, ..)| hash)
+        .collect();
+    let rejected_txs = if stale_tx_hashes.is_empty() {
+        0
+    } else {
+        transaction
+            .transactions_dal()
+            .reject_pending_l2_txs(&stale_tx_hashes, "rejected: nonce is already used")
+            .await
+            .context("failed rejecting stale L2 transactions")?
+    };
+    transaction.commit().await?;
+
+    KEEPER_METRICS
+        .mempool_recovered_l2_txs
+        .set(pending_txs.len() - rejected_txs);
+    tracing::info!(
+        "Recovered {} pending L2 transactions for the mempool; rejected {rejected_txs} transactions with stale nonces",
+        pending_txs.len() - rejected_txs
+    );
+    Ok(())
+}
+
 /// Loads nonces for all distinct `transactions` initiators from the storage.
 async fn get_transaction_nonces(
     storage: &mut Connection<'_, Core>,
     transactions: &[&Transaction],
 ) -> anyhow::Result<HashMap<Address, Nonce>> {
-    let (nonce_keys, address_by_nonce_key): (Vec<_>, HashMap<_, _>) = transactions
-        .iter()
-        .map(|tx| {
-            let address = tx.initiator_account();
+    get_account_nonces(
+        storage,
+        transactions.iter().map(|tx| tx.initiator_account()),
+    )
+    .await
+}
+
+/// Loads committed nonces for the specified accounts from the storage.
+async fn get_account_nonces(
+    storage: &mut Connection<'_, Core>,
+    accounts: impl Iterator<Item = Address>,
+) -> anyhow::Result<HashMap<Address, Nonce>> {
+    let (nonce_keys, address_by_nonce_key): (Vec<_>, HashMap<_, _>) = accounts
+        .map(|address| {
             let nonce_key = get_nonce_key(&address).hashed_key();
             (nonce_key, (nonce_key, address))
         })
@@ -464,4 +538,83 @@ mod tests {
         stop_sender.send_replace(true);
         fetcher_task.await.unwrap().expect("fetcher errored");
     }
+
+    #[tokio::test]
+    async fn recovering_pending_transactions_on_startup() {
+        let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+
+        let mempool = MempoolGuard::new(PriorityOpId(0), 100);
+        let fee_params_provider: Arc<dyn BatchFeeModelInputProvider> =
+            Arc::new(MockBatchFeeParamsProvider::default());
+        let fee_input = fee_params_provider.get_batch_fee_input().await.unwrap();
+        let (base_fee, gas_per_pubdata) =
+            derive_base_fee_and_gas_per_pubdata(fee_input, ProtocolVersionId::latest().into());
+
+        let transaction = create_l2_transaction(base_fee * 2, gas_per_pubdata * 2);
+        let stale_transaction = create_l2_transaction(base_fee * 2, gas_per_pubdata * 2);
+        let nonce_key = get_nonce_key(&stale_transaction.initiator_account());
+        let nonce_log = StorageLog::new_write_log(nonce_key, u256_to_h256(42.into()));
+        storage
+            .storage_logs_dal()
+            .append_storage_logs(L2BlockNumber(0), &[nonce_log])
+            .await
+            .unwrap();
+        for tx in [&transaction, &stale_transaction] {
+            storage
+                .transactions_dal()
+                .insert_transaction_l2(
+                    tx,
+                    TransactionExecutionMetrics::default(),
+                    ValidationTraces::default(),
+                )
+                .await
+                .unwrap();
+        }
+        // Emulate transactions being loaded into the mempool before a crash.
+        let loaded_txs = storage
+            .transactions_dal()
+            .sync_mempool(&[], &[], gas_per_pubdata as u32, base_fee, false, 100)
+            .await
+            .unwrap();
+        assert_eq!(loaded_txs.len(), 2);
+        drop(storage);
+
+        let mut fetcher = MempoolFetcher::new(
+            mempool.clone(),
+            fee_params_provider,
+            &TEST_MEMPOOL_CONFIG,
+            pool.clone(),
+        );
+        let (tx_hashes_sender, mut tx_hashes_receiver) = mpsc::unbounded_channel();
+        fetcher.transaction_hashes_sender = tx_hashes_sender;
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let fetcher_task = tokio::spawn(fetcher.run(stop_receiver));
+
+        // Only the transaction with a valid nonce should be replayed.
+        let tx_hashes = wait_for_new_transactions(&mut tx_hashes_receiver).await;
+        assert_eq!(tx_hashes, [transaction.hash()]);
+        assert_eq!(mempool.stats().l2_transaction_count, 1);
+
+        stop_sender.send_replace(true);
+        fetcher_task.await.unwrap().expect("fetcher errored");
+
+        let mut storage = pool.connection().await.unwrap();
+        let pending_txs = storage
+            .transactions_dal()
+            .get_pending_l2_tx_nonces()
+            .await
+            .unwrap();
+        assert_eq!(
+            pending_txs,
+            [(
+                transaction.hash(),
+                transaction.initiator_account(),
+                Nonce(0)
+            )]
+        );
+    }
 }
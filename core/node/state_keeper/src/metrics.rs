@@ -68,6 +68,12 @@ pub struct StateKeeperMetrics {
     pub mempool_stashed_accounts: Gauge<usize>,
     /// Number of purged accounts in mempool
     pub mempool_purged_accounts: Gauge<usize>,
+    /// Number of pending L2 transactions recovered from Postgres on mempool startup.
+    pub mempool_recovered_l2_txs: Gauge<usize>,
+    /// Current `close_block_at_geometry_percentage` as adjusted based on proving statistics.
+    pub close_block_at_geometry_percentage: Gauge<f64>,
+    /// Epoch of the sequencer lease held by this instance.
+    pub sequencer_lease_epoch: Gauge<u64>,
     /// Latency of the state keeper waiting for a transaction.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub waiting_for_tx: Histogram<Duration>,
@@ -207,6 +213,7 @@ impl From<&SealResolution> for SealResolutionLabel {
 pub(super) enum L2BlockSealReason {
     Timeout,
     PayloadSize,
+    DevMode,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
@@ -0,0 +1,300 @@
+//! Read-only view of the L1 batch currently processed by the state keeper.
+
+use std::{collections::HashMap, sync::Arc};
+
+use tokio::sync::watch;
+use zksync_types::{L1BatchNumber, L2BlockNumber, StorageKey, H256};
+
+use crate::updates::UpdatesManager;
+
+/// Storage writes and factory deps produced by a single L2 block.
+///
+/// Uses persistent maps, so that a snapshot of the open L2 block can be taken after each transaction
+/// without copying all changes accumulated in the block.
+#[derive(Debug, Clone, Default)]
+struct L2BlockChanges {
+    storage_writes: im::HashMap<StorageKey, H256>,
+    factory_deps: im::HashMap<H256, Arc<[u8]>>,
+}
+
+/// Snapshot of the state changes produced by the L1 batch currently processed by the state keeper,
+/// including the L2 block that is not sealed yet.
+///
+/// Changes are split by L2 block, starting from the first L2 block in the batch. Since L2 blocks are persisted
+/// asynchronously, a part of these changes may already be present in Postgres; hence, the snapshot can be applied
+/// on top of any persisted state between the last L2 block of the previous batch and the last L2 block preceding
+/// the open block (see [`Self::is_applicable_to()`]).
+#[derive(Debug, Clone)]
+pub struct PendingBatchState {
+    l1_batch: L1BatchNumber,
+    first_l2_block: L2BlockNumber,
+    l2_blocks: Vec<Arc<L2BlockChanges>>,
+    tx_count: usize,
+}
+
+impl PendingBatchState {
+    /// Returns the number of the processed L1 batch.
+    pub fn l1_batch(&self) -> L1BatchNumber {
+        self.l1_batch
+    }
+
+    /// Returns the number of the open (i.e., not sealed by the state keeper) L2 block.
+    pub fn open_l2_block(&self) -> L2BlockNumber {
+        self.first_l2_block + (self.l2_blocks.len() as u32).saturating_sub(1)
+    }
+
+    /// Returns the number of transactions executed in the batch so far.
+    pub fn tx_count(&self) -> usize {
+        self.tx_count
+    }
+
+    /// Checks whether this snapshot can be applied on top of the state persisted up to and including
+    /// `sealed_l2_block`. If this returns `false`, the snapshot is either stale (the open L2 block was already persisted),
+    /// or Postgres lags behind the start of the batch.
+    pub fn is_applicable_to(&self, sealed_l2_block: L2BlockNumber) -> bool {
+        sealed_l2_block + 1 >= self.first_l2_block && sealed_l2_block < self.open_l2_block()
+    }
+
+    /// Returns the latest value written to the specified storage slot in the batch.
+    pub fn storage_value(&self, key: &StorageKey) -> Option<H256> {
+        self.l2_blocks
+            .iter()
+            .rev()
+            .find_map(|block| block.storage_writes.get(key).copied())
+    }
+
+    /// Returns the latest values of all storage slots written in the batch.
+    pub fn storage_writes(&self) -> HashMap<StorageKey, H256> {
+        let mut writes = HashMap::new();
+        for block in &self.l2_blocks {
+            writes.extend(
+                block
+                    .storage_writes
+                    .iter()
+                    .map(|(key, value)| (*key, *value)),
+            );
+        }
+        writes
+    }
+
+    /// Iterates over all bytecodes published in the batch.
+    pub fn factory_deps(&self) -> impl Iterator<Item = (H256, &[u8])> + '_ {
+        self.l2_blocks.iter().flat_map(|block| {
+            block
+                .factory_deps
+                .iter()
+                .map(|(hash, bytecode)| (*hash, &bytecode[..]))
+        })
+    }
+}
+
+/// Shared read handle for [`PendingBatchState`] published by the state keeper.
+#[derive(Debug, Clone)]
+pub struct PendingBatchStateReader(watch::Receiver<Option<Arc<PendingBatchState>>>);
+
+impl PendingBatchStateReader {
+    /// Creates a reader with a fixed snapshot consisting of a single open L2 block with the specified storage writes.
+    // Intended for tests only.
+    #[doc(hidden)]
+    pub fn mock(
+        l1_batch: L1BatchNumber,
+        open_l2_block: L2BlockNumber,
+        storage_writes: HashMap<StorageKey, H256>,
+    ) -> Self {
+        let changes = L2BlockChanges {
+            storage_writes: storage_writes.into_iter().collect(),
+            factory_deps: im::HashMap::new(),
+        };
+        let snapshot = PendingBatchState {
+            l1_batch,
+            first_l2_block: open_l2_block,
+            l2_blocks: vec![Arc::new(changes)],
+            tx_count: 1,
+        };
+        Self(watch::channel(Some(Arc::new(snapshot))).1)
+    }
+
+    /// Returns the latest published snapshot, or `None` if the state keeper hasn't published a snapshot yet
+    /// (e.g., because it's not initialized).
+    pub fn snapshot(&self) -> Option<Arc<PendingBatchState>> {
+        self.0.borrow().clone()
+    }
+}
+
+/// Publisher of [`PendingBatchState`] snapshots. Changes are extracted from [`UpdatesManager`] incrementally,
+/// so [`Self::sync()`] must be called after each transaction executed by the state keeper.
+#[derive(Debug)]
+pub(crate) struct PendingBatchStatePublisher {
+    sender: watch::Sender<Option<Arc<PendingBatchState>>>,
+    l1_batch: L1BatchNumber,
+    first_l2_block: L2BlockNumber,
+    sealed_blocks: Vec<Arc<L2BlockChanges>>,
+    open_block: L2BlockChanges,
+    /// Number of storage logs in the open L2 block already reflected in `open_block`.
+    processed_log_count: usize,
+}
+
+impl PendingBatchStatePublisher {
+    pub fn new() -> Self {
+        Self {
+            sender: watch::channel(None).0,
+            l1_batch: L1BatchNumber(0),
+            first_l2_block: L2BlockNumber(0),
+            sealed_blocks: vec![],
+            open_block: L2BlockChanges::default(),
+            processed_log_count: 0,
+        }
+    }
+
+    pub fn subscribe(&self) -> PendingBatchStateReader {
+        PendingBatchStateReader(self.sender.subscribe())
+    }
+
+    /// Discards the published snapshot. Must be called if the pending state is reverted.
+    pub fn reset(&mut self) {
+        self.sender.send_replace(None);
+        self.start_batch(L1BatchNumber(0), L2BlockNumber(0));
+    }
+
+    fn start_batch(&mut self, l1_batch: L1BatchNumber, first_l2_block: L2BlockNumber) {
+        self.l1_batch = l1_batch;
+        self.first_l2_block = first_l2_block;
+        self.sealed_blocks.clear();
+        self.open_block = L2BlockChanges::default();
+        self.processed_log_count = 0;
+    }
+
+    fn open_l2_block(&self) -> L2BlockNumber {
+        self.first_l2_block + self.sealed_blocks.len() as u32
+    }
+
+    /// Synchronizes the published snapshot with the provided updates manager.
+    pub fn sync(&mut self, updates_manager: &UpdatesManager) {
+        let l2_block = &updates_manager.l2_block;
+        if updates_manager.l1_batch.number != self.l1_batch
+            || l2_block.number < self.open_l2_block()
+        {
+            self.start_batch(updates_manager.l1_batch.number, l2_block.number);
+        }
+        while self.open_l2_block() < l2_block.number {
+            let sealed_block = std::mem::take(&mut self.open_block);
+            self.sealed_blocks.push(Arc::new(sealed_block));
+            self.processed_log_count = 0;
+        }
+
+        let new_logs = &l2_block.storage_logs[self.processed_log_count..];
+        let open_block = &mut self.open_block;
+        for log in new_logs.iter().filter(|log| log.log.is_write()) {
+            open_block.storage_writes.insert(log.log.key, log.log.value);
+        }
+        for (hash, bytecode) in &l2_block.new_factory_deps {
+            if !open_block.factory_deps.contains_key(hash) {
+                open_block
+                    .factory_deps
+                    .insert(*hash, bytecode.as_slice().into());
+            }
+        }
+        self.processed_log_count = l2_block.storage_logs.len();
+
+        // Cloning persistent maps is O(1); the snapshot shares unchanged entries with the publisher.
+        let mut l2_blocks = self.sealed_blocks.clone();
+        l2_blocks.push(Arc::new(self.open_block.clone()));
+        let snapshot = PendingBatchState {
+            l1_batch: self.l1_batch,
+            first_l2_block: self.first_l2_block,
+            l2_blocks,
+            tx_count: updates_manager.pending_executed_transactions_len(),
+        };
+        self.sender.send_replace(Some(Arc::new(snapshot)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{AccountTreeId, Address, U256};
+
+    use super::*;
+    use crate::{
+        io::L2BlockParams,
+        tests::{create_execution_result, create_transaction, create_updates_manager, Query},
+    };
+
+    fn storage_key(key: u64) -> StorageKey {
+        StorageKey::new(
+            AccountTreeId::new(Address::default()),
+            H256::from_low_u64_be(key),
+        )
+    }
+
+    fn execute_tx(updates_manager: &mut UpdatesManager, logs: Vec<(U256, Query)>) {
+        updates_manager.extend_from_executed_transaction(
+            create_transaction(10, 100),
+            create_execution_result(logs),
+            Default::default(),
+            vec![],
+        );
+    }
+
+    #[test]
+    fn publishing_pending_batch_state() {
+        let mut updates_manager = create_updates_manager();
+        let mut publisher = PendingBatchStatePublisher::new();
+        let reader = publisher.subscribe();
+        assert!(reader.snapshot().is_none());
+
+        execute_tx(
+            &mut updates_manager,
+            vec![
+                (U256::from(1), Query::InitialWrite(U256::from(1))),
+                (U256::from(2), Query::Read(U256::from(2))),
+            ],
+        );
+        publisher.sync(&updates_manager);
+        let snapshot = reader.snapshot().unwrap();
+        let open_l2_block = updates_manager.l2_block.number;
+        assert_eq!(snapshot.open_l2_block(), open_l2_block);
+        assert_eq!(snapshot.tx_count(), 1);
+        assert_eq!(
+            snapshot.storage_value(&storage_key(1)),
+            Some(H256::from_low_u64_be(1))
+        );
+        assert_eq!(snapshot.storage_value(&storage_key(2)), None);
+        assert!(snapshot.is_applicable_to(open_l2_block - 1));
+        assert!(!snapshot.is_applicable_to(open_l2_block));
+
+        updates_manager.set_next_l2_block_params(L2BlockParams {
+            timestamp: updates_manager.l2_block.timestamp + 1,
+            virtual_blocks: 1,
+        });
+        updates_manager.push_l2_block();
+        execute_tx(
+            &mut updates_manager,
+            vec![
+                (
+                    U256::from(1),
+                    Query::RepeatedWrite(U256::from(1), U256::from(3)),
+                ),
+                (U256::from(4), Query::InitialWrite(U256::from(4))),
+            ],
+        );
+        publisher.sync(&updates_manager);
+
+        // The old snapshot must not be affected.
+        assert_eq!(
+            snapshot.storage_value(&storage_key(1)),
+            Some(H256::from_low_u64_be(1))
+        );
+        let snapshot = reader.snapshot().unwrap();
+        assert_eq!(snapshot.open_l2_block(), open_l2_block + 1);
+        assert_eq!(snapshot.tx_count(), 2);
+        assert!(snapshot.is_applicable_to(open_l2_block - 1));
+        assert!(snapshot.is_applicable_to(open_l2_block));
+        let writes = snapshot.storage_writes();
+        assert_eq!(writes.len(), 2);
+        assert_eq!(writes[&storage_key(1)], H256::from_low_u64_be(3));
+        assert_eq!(writes[&storage_key(4)], H256::from_low_u64_be(4));
+
+        publisher.reset();
+        assert!(reader.snapshot().is_none());
+    }
+}
@@ -4,7 +4,10 @@
 //! The conditional sealer abstraction allows to implement different sealing strategies, e.g. the actual
 //! sealing strategy for the main node or noop sealer for the external node.
 
-use std::fmt;
+use std::{
+    fmt,
+    sync::{Arc, RwLock, RwLockReadGuard},
+};
 
 use zksync_config::configs::chain::StateKeeperConfig;
 use zksync_types::ProtocolVersionId;
@@ -34,6 +37,20 @@ pub trait ConditionalSealer: 'static + fmt::Debug + Send + Sync {
         tx_data: &SealData,
         protocol_version: ProtocolVersionId,
     ) -> SealResolution;
+
+    /// Same as [`Self::should_seal_l1_batch()`], but without side effects (e.g., reporting metrics). Additionally,
+    /// returns the name of the criterion that has determined the resolution, if any.
+    ///
+    /// Can be used to simulate sealing without affecting the live L1 batch.
+    fn dry_run_should_seal_l1_batch(
+        &self,
+        block_open_timestamp_ms: u128,
+        tx_count: usize,
+        l1_tx_count: usize,
+        block_data: &SealData,
+        tx_data: &SealData,
+        protocol_version: ProtocolVersionId,
+    ) -> (SealResolution, Option<&'static str>);
 }
 
 /// Implementation of [`ConditionalSealer`] used by the main node.
@@ -43,7 +60,7 @@ pub trait ConditionalSealer: 'static + fmt::Debug + Send + Sync {
 /// Non-deterministic seal criteria are expressed using [`IoSealCriteria`](super::IoSealCriteria).
 #[derive(Debug, Default)]
 pub struct SequencerSealer {
-    config: StateKeeperConfig,
+    config: SharedSealerConfig,
     sealers: Vec<Box<dyn SealCriterion>>,
 }
 
@@ -53,12 +70,13 @@ impl ConditionalSealer for SequencerSealer {
         data: &SealData,
         protocol_version: ProtocolVersionId,
     ) -> Option<&'static str> {
+        let config = self.config.read();
         for sealer in &self.sealers {
             const MOCK_BLOCK_TIMESTAMP: u128 = 0;
             const TX_COUNT: usize = 1;
 
             let resolution = sealer.should_seal(
-                &self.config,
+                &config,
                 MOCK_BLOCK_TIMESTAMP,
                 TX_COUNT,
                 TX_COUNT,
@@ -89,41 +107,56 @@ impl ConditionalSealer for SequencerSealer {
             block_data.execution_metrics
         );
 
-        let mut final_seal_resolution = SealResolution::NoSeal;
-        for sealer in &self.sealers {
-            let seal_resolution = sealer.should_seal(
-                &self.config,
-                block_open_timestamp_ms,
-                tx_count,
-                l1_tx_count,
-                block_data,
-                tx_data,
-                protocol_version,
-            );
-            match &seal_resolution {
+        self.resolve(
+            block_open_timestamp_ms,
+            tx_count,
+            l1_tx_count,
+            block_data,
+            tx_data,
+            protocol_version,
+            |criterion, seal_resolution| match seal_resolution {
                 SealResolution::IncludeAndSeal
                 | SealResolution::ExcludeAndSeal
                 | SealResolution::Unexecutable(_) => {
                     tracing::debug!(
-                        "L1 batch #{l1_batch_number} processed by `{name}` with resolution {seal_resolution:?}",
-                        name = sealer.prom_criterion_name()
+                        "L1 batch #{l1_batch_number} processed by `{criterion}` with resolution {seal_resolution:?}"
                     );
-                    AGGREGATION_METRICS
-                        .l1_batch_reason_inc(sealer.prom_criterion_name(), &seal_resolution);
+                    AGGREGATION_METRICS.l1_batch_reason_inc(criterion, seal_resolution);
                 }
                 SealResolution::NoSeal => { /* Don't do anything */ }
-            }
+            },
+        )
+        .0
+    }
 
-            final_seal_resolution = final_seal_resolution.stricter(seal_resolution);
-        }
-        final_seal_resolution
+    fn dry_run_should_seal_l1_batch(
+        &self,
+        block_open_timestamp_ms: u128,
+        tx_count: usize,
+        l1_tx_count: usize,
+        block_data: &SealData,
+        tx_data: &SealData,
+        protocol_version: ProtocolVersionId,
+    ) -> (SealResolution, Option<&'static str>) {
+        self.resolve(
+            block_open_timestamp_ms,
+            tx_count,
+            l1_tx_count,
+            block_data,
+            tx_data,
+            protocol_version,
+            |_, _| { /* No side effects */ },
+        )
     }
 }
 
 impl SequencerSealer {
     pub fn new(config: StateKeeperConfig) -> Self {
         let sealers = Self::default_sealers(&config);
-        Self { config, sealers }
+        Self {
+            config: SharedSealerConfig::new(config),
+            sealers,
+        }
     }
 
     #[cfg(test)]
@@ -131,7 +164,52 @@ impl SequencerSealer {
         config: StateKeeperConfig,
         sealers: Vec<Box<dyn SealCriterion>>,
     ) -> Self {
-        Self { config, sealers }
+        Self {
+            config: SharedSealerConfig::new(config),
+            sealers,
+        }
+    }
+
+    /// Returns a handle that can be used to update seal criteria limits of this sealer at runtime.
+    pub fn shared_config(&self) -> SharedSealerConfig {
+        self.config.clone()
+    }
+
+    /// Runs all seal criteria and returns the strictest resolution together with the criterion that has determined it.
+    /// `observe` is called with the resolution of each criterion.
+    #[allow(clippy::too_many_arguments)]
+    fn resolve(
+        &self,
+        block_open_timestamp_ms: u128,
+        tx_count: usize,
+        l1_tx_count: usize,
+        block_data: &SealData,
+        tx_data: &SealData,
+        protocol_version: ProtocolVersionId,
+        mut observe: impl FnMut(&'static str, &SealResolution),
+    ) -> (SealResolution, Option<&'static str>) {
+        let config = self.config.read();
+        let mut final_seal_resolution = SealResolution::NoSeal;
+        let mut final_criterion = None;
+        for sealer in &self.sealers {
+            let seal_resolution = sealer.should_seal(
+                &config,
+                block_open_timestamp_ms,
+                tx_count,
+                l1_tx_count,
+                block_data,
+                tx_data,
+                protocol_version,
+            );
+            observe(sealer.prom_criterion_name(), &seal_resolution);
+
+            let stricter_resolution = final_seal_resolution.clone().stricter(seal_resolution);
+            if stricter_resolution != final_seal_resolution {
+                final_criterion = Some(sealer.prom_criterion_name());
+                final_seal_resolution = stricter_resolution;
+            }
+        }
+        (final_seal_resolution, final_criterion)
     }
 
     fn default_sealers(config: &StateKeeperConfig) -> Vec<Box<dyn SealCriterion>> {
@@ -149,6 +227,33 @@ impl SequencerSealer {
     }
 }
 
+/// State keeper config used by a [`SequencerSealer`] that can be updated at runtime.
+///
+/// Only percentage-based limits (`close_block_at_*_percentage`, `reject_tx_at_*_percentage`) and `transaction_slots`
+/// are picked up by an existing sealer; `max_pubdata_per_batch` is fixed when the sealer is created.
+#[derive(Debug, Clone, Default)]
+pub struct SharedSealerConfig(Arc<RwLock<StateKeeperConfig>>);
+
+impl SharedSealerConfig {
+    fn new(config: StateKeeperConfig) -> Self {
+        Self(Arc::new(RwLock::new(config)))
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, StateKeeperConfig> {
+        self.0.read().expect("sealer config is poisoned")
+    }
+
+    /// Atomically replaces the config. New limits will be used for all subsequent sealing decisions.
+    pub fn set(&self, config: StateKeeperConfig) {
+        *self.0.write().expect("sealer config is poisoned") = config;
+    }
+
+    /// Atomically modifies the config in place.
+    pub fn update(&self, update: impl FnOnce(&mut StateKeeperConfig)) {
+        update(&mut *self.0.write().expect("sealer config is poisoned"));
+    }
+}
+
 /// Implementation of [`ConditionalSealer`] that never seals the batch.
 ///
 /// Can be used in contexts where, for example, state keeper configuration is not available,
@@ -177,4 +282,16 @@ impl ConditionalSealer for NoopSealer {
     ) -> SealResolution {
         SealResolution::NoSeal
     }
+
+    fn dry_run_should_seal_l1_batch(
+        &self,
+        _block_open_timestamp_ms: u128,
+        _tx_count: usize,
+        _l1_tx_count: usize,
+        _block_data: &SealData,
+        _tx_data: &SealData,
+        _protocol_version: ProtocolVersionId,
+    ) -> (SealResolution, Option<&'static str>) {
+        (SealResolution::NoSeal, None)
+    }
 }
@@ -0,0 +1,233 @@
+//! Adjustment of the circuit seal criterion based on proving statistics reported by the prover subsystem.
+
+use std::time::Duration;
+
+use tokio::sync::watch;
+use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_dal::{proof_generation_dal::BatchProvingStats, ConnectionPool, Core, CoreDal};
+
+use super::SharedSealerConfig;
+use crate::metrics::KEEPER_METRICS;
+
+/// Parameters of the geometry adjustment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeometryAdjustmentConfig {
+    pub max_circuits_per_batch: usize,
+    pub target_proving_time: Duration,
+    pub min_percentage: f64,
+    pub max_percentage: f64,
+    pub window_batches: u32,
+}
+
+impl GeometryAdjustmentConfig {
+    /// Extracts the adjustment config from the state keeper config. Returns `None` if the adjustment is disabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the adjustment parameters are invalid.
+    pub fn from_state_keeper_config(config: &StateKeeperConfig) -> anyhow::Result<Option<Self>> {
+        if !config.geometry_adjustment_enabled {
+            return Ok(None);
+        }
+        let this = Self {
+            max_circuits_per_batch: config.max_circuits_per_batch,
+            target_proving_time: config.geometry_adjustment_target_proving_time(),
+            min_percentage: config.geometry_adjustment_min_percentage,
+            max_percentage: config.geometry_adjustment_max_percentage,
+            window_batches: config.geometry_adjustment_window_batches,
+        };
+        this.validate()?;
+        Ok(Some(this))
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        // Percentages above 1 would allow sealing batches exceeding the circuit capacity, i.e. unprovable ones.
+        anyhow::ensure!(
+            self.min_percentage > 0.0
+                && self.min_percentage <= self.max_percentage
+                && self.max_percentage <= 1.0,
+            "invalid geometry adjustment bounds: expected 0 < min_percentage ({}) <= max_percentage ({}) <= 1",
+            self.min_percentage,
+            self.max_percentage
+        );
+        anyhow::ensure!(
+            self.max_circuits_per_batch > 0,
+            "max_circuits_per_batch must be positive for geometry adjustment"
+        );
+        anyhow::ensure!(
+            !self.target_proving_time.is_zero(),
+            "geometry_adjustment_target_proving_time_sec must be positive"
+        );
+        anyhow::ensure!(
+            self.window_batches > 0,
+            "geometry_adjustment_window_batches must be positive"
+        );
+        Ok(())
+    }
+
+    /// Computes `close_block_at_geometry_percentage` based on proving statistics of recent batches.
+    /// Returns `None` if the statistics are insufficient to make a decision.
+    ///
+    /// The average proving time per base layer circuit is measured over all batches, and the percentage is chosen
+    /// so that a batch with the corresponding number of circuits is expected to be proven in `target_proving_time`.
+    /// The result is clamped to `[min_percentage, max_percentage]`.
+    pub fn percentage(&self, stats: &[BatchProvingStats]) -> Option<f64> {
+        let total_circuits: u64 = stats
+            .iter()
+            .map(|batch| u64::from(batch.base_layer_circuits))
+            .sum();
+        let total_proving_time: Duration = stats.iter().map(|batch| batch.proving_time).sum();
+        if total_circuits == 0 || total_proving_time.is_zero() || self.max_circuits_per_batch == 0 {
+            return None;
+        }
+
+        let proving_time_per_circuit = total_proving_time.as_secs_f64() / total_circuits as f64;
+        let target_circuits = self.target_proving_time.as_secs_f64() / proving_time_per_circuit;
+        let percentage = target_circuits / self.max_circuits_per_batch as f64;
+        Some(percentage.min(self.max_percentage).max(self.min_percentage))
+    }
+}
+
+/// Periodically adjusts `close_block_at_geometry_percentage` used by the [`SequencerSealer`](super::SequencerSealer)
+/// based on proving statistics of the latest proven L1 batches.
+///
+/// If the sealer config is reloaded at runtime, the adjusted percentage is reapplied on the next iteration.
+#[derive(Debug)]
+pub struct GeometrySealAdjuster {
+    pool: ConnectionPool<Core>,
+    config: GeometryAdjustmentConfig,
+    sealer_config: SharedSealerConfig,
+}
+
+impl GeometrySealAdjuster {
+    const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+    pub fn new(
+        pool: ConnectionPool<Core>,
+        config: GeometryAdjustmentConfig,
+        sealer_config: SharedSealerConfig,
+    ) -> Self {
+        Self {
+            pool,
+            config,
+            sealer_config,
+        }
+    }
+
+    async fn update(&self) -> anyhow::Result<()> {
+        let mut connection = self.pool.connection_tagged("state_keeper").await?;
+        let stats = connection
+            .proof_generation_dal()
+            .get_latest_proving_stats(self.config.window_batches as usize)
+            .await?;
+        drop(connection);
+
+        let Some(percentage) = self.config.percentage(&stats) else {
+            tracing::debug!(
+                "Not enough proving stats to adjust geometry seal criterion: {} batches",
+                stats.len()
+            );
+            return Ok(());
+        };
+        self.sealer_config.update(|config| {
+            if config.close_block_at_geometry_percentage != percentage {
+                tracing::debug!(
+                    "Adjusted close_block_at_geometry_percentage: {} -> {percentage}",
+                    config.close_block_at_geometry_percentage
+                );
+            }
+            config.close_block_at_geometry_percentage = percentage;
+        });
+        KEEPER_METRICS
+            .close_block_at_geometry_percentage
+            .set(percentage);
+        Ok(())
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow() {
+            if let Err(err) = self.update().await {
+                tracing::warn!("Failed adjusting geometry seal criterion: {err:#}");
+            }
+
+            if tokio::time::timeout(Self::POLL_INTERVAL, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, geometry seal adjuster is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::L1BatchNumber;
+
+    use super::*;
+
+    fn config() -> GeometryAdjustmentConfig {
+        GeometryAdjustmentConfig {
+            max_circuits_per_batch: 1_000,
+            target_proving_time: Duration::from_secs(1_000),
+            min_percentage: 0.5,
+            max_percentage: 0.9,
+            window_batches: 16,
+        }
+    }
+
+    fn batch(base_layer_circuits: u32, proving_time_secs: u64) -> BatchProvingStats {
+        BatchProvingStats {
+            l1_batch_number: L1BatchNumber(1),
+            base_layer_circuits,
+            proving_time: Duration::from_secs(proving_time_secs),
+        }
+    }
+
+    #[test]
+    fn geometry_percentage() {
+        let config = config();
+        assert_eq!(config.percentage(&[]), None);
+        assert_eq!(config.percentage(&[batch(0, 0)]), None);
+
+        // 1.25 s per circuit -> 800 circuits fit into the target time.
+        assert_eq!(config.percentage(&[batch(800, 1_000)]), Some(0.8));
+        // The time per circuit is averaged over all batches: 3_000 s / 2_400 circuits.
+        assert_eq!(
+            config.percentage(&[batch(800, 500), batch(1_600, 2_500)]),
+            Some(0.8)
+        );
+        // Complex batches decrease the percentage to the lower bound...
+        assert_eq!(config.percentage(&[batch(500, 5_000)]), Some(0.5));
+        // ...and simple ones increase it to the upper bound.
+        assert_eq!(config.percentage(&[batch(900, 100)]), Some(0.9));
+    }
+
+    #[test]
+    fn validating_geometry_adjustment_config() {
+        let mut sk_config = StateKeeperConfig::for_tests();
+        assert_eq!(
+            GeometryAdjustmentConfig::from_state_keeper_config(&sk_config).unwrap(),
+            None
+        );
+
+        sk_config.geometry_adjustment_enabled = true;
+        sk_config.geometry_adjustment_min_percentage = 0.5;
+        sk_config.geometry_adjustment_max_percentage = 0.95;
+        let config = GeometryAdjustmentConfig::from_state_keeper_config(&sk_config)
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.max_percentage, 0.95);
+
+        for (min, max) in [(0.5, 1.1), (0.9, 0.5), (0.0, 0.9), (f64::NAN, 0.9)] {
+            sk_config.geometry_adjustment_min_percentage = min;
+            sk_config.geometry_adjustment_max_percentage = max;
+            let err = GeometryAdjustmentConfig::from_state_keeper_config(&sk_config)
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains("invalid geometry adjustment bounds"), "{err}");
+        }
+    }
+}
@@ -1,4 +1,10 @@
-use std::time::Duration;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use anyhow::Context;
 use async_trait::async_trait;
@@ -76,6 +82,38 @@ impl IoSealCriteria for TimeoutSealer {
     }
 }
 
+/// Handle allowing to request sealing of the current L1 batch from outside the state keeper, e.g. by the node operator.
+///
+/// The request is fulfilled as soon as the current L1 batch contains at least one transaction; empty batches are never sealed.
+#[derive(Debug, Clone, Default)]
+pub struct L1BatchSealRequest(Arc<AtomicBool>);
+
+impl L1BatchSealRequest {
+    /// Requests sealing the current L1 batch.
+    pub fn request(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Checks whether there is a pending seal request.
+    pub fn is_requested(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn should_seal_l1_batch_unconditionally(&self, manager: &UpdatesManager) -> bool {
+        const RULE_NAME: &str = "operator_request";
+
+        if manager.pending_executed_transactions_len() == 0 {
+            return false;
+        }
+        let requested = self.0.swap(false, Ordering::Relaxed);
+        if requested {
+            AGGREGATION_METRICS.l1_batch_reason_inc_criterion(RULE_NAME);
+            tracing::info!("Decided to seal L1 batch using rule `{RULE_NAME}`");
+        }
+        requested
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct L2BlockMaxPayloadSizeSealer {
     max_payload_size: usize,
@@ -188,6 +226,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn l1_batch_seal_request() {
+        let seal_request = L1BatchSealRequest::default();
+        let mut manager = create_updates_manager();
+        assert!(!seal_request.should_seal_l1_batch_unconditionally(&manager));
+
+        seal_request.request();
+        assert!(seal_request.is_requested());
+        // The request must be retained until the batch has transactions.
+        assert!(!seal_request.should_seal_l1_batch_unconditionally(&manager));
+        assert!(seal_request.is_requested());
+
+        apply_tx_to_manager(create_transaction(10, 100), &mut manager);
+        assert!(seal_request.should_seal_l1_batch_unconditionally(&manager));
+        assert!(!seal_request.is_requested());
+        assert!(!seal_request.should_seal_l1_batch_unconditionally(&manager));
+    }
+
     /// This test mostly exists to make sure that we can't seal empty L2 blocks on the main node.
     #[test]
     fn timeout_l2_block_sealer() {
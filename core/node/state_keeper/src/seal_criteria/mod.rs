@@ -20,14 +20,18 @@ use zksync_multivm::{
 use zksync_types::{ProtocolVersionId, Transaction};
 
 pub use self::{
-    conditional_sealer::{ConditionalSealer, NoopSealer, SequencerSealer},
-    io_criteria::IoSealCriteria,
+    conditional_sealer::{ConditionalSealer, NoopSealer, SequencerSealer, SharedSealerConfig},
+    geometry_adjuster::{GeometryAdjustmentConfig, GeometrySealAdjuster},
+    io_criteria::{IoSealCriteria, L1BatchSealRequest},
+    simulation::{SealSimulation, SimulatedTxResolution},
 };
-use crate::metrics::AGGREGATION_METRICS;
+use crate::{metrics::AGGREGATION_METRICS, tx_policy::TxPolicyViolation};
 
 mod conditional_sealer;
 pub(super) mod criteria;
+mod geometry_adjuster;
 pub(super) mod io_criteria;
+mod simulation;
 
 fn halt_as_metric_label(halt: &Halt) -> &'static str {
     match halt {
@@ -67,6 +71,8 @@ pub enum UnexecutableReason {
     BootloaderOutOfGas,
     NotEnoughGasProvided,
     TooMuchUserL2L1Logs,
+    /// Transaction violates the configured transaction policy.
+    PolicyViolation(TxPolicyViolation),
 }
 
 impl UnexecutableReason {
@@ -82,6 +88,7 @@ impl UnexecutableReason {
             UnexecutableReason::BootloaderOutOfGas => "BootloaderOutOfGas",
             UnexecutableReason::NotEnoughGasProvided => "NotEnoughGasProvided",
             UnexecutableReason::TooMuchUserL2L1Logs => "TooMuchUserL2L1Logs",
+            UnexecutableReason::PolicyViolation(_) => "PolicyViolation",
         }
     }
 }
@@ -107,6 +114,9 @@ impl fmt::Display for UnexecutableReason {
             UnexecutableReason::BootloaderOutOfGas => write!(f, "Bootloader out of gas"),
             UnexecutableReason::NotEnoughGasProvided => write!(f, "Not enough gas provided"),
             UnexecutableReason::TooMuchUserL2L1Logs => write!(f, "Too much user l2 l1 logs"),
+            UnexecutableReason::PolicyViolation(violation) => {
+                write!(f, "Transaction policy violation: {violation}")
+            }
         }
     }
 }
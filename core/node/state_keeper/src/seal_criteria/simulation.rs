@@ -0,0 +1,155 @@
+//! Dry-run simulation of L1 batch sealing.
+
+use zksync_multivm::interface::{DeduplicatedWritesMetrics, VmExecutionMetrics};
+use zksync_types::ProtocolVersionId;
+
+use super::{ConditionalSealer, SealData, SealResolution};
+
+/// Block open timestamp passed to the sealer. Conditional seal criteria don't depend on it;
+/// timeouts are handled by [`IoSealCriteria`](super::IoSealCriteria), which are not simulated.
+const MOCK_BLOCK_OPEN_TIMESTAMP_MS: u128 = 0;
+
+/// Outcome of simulating the inclusion of a single transaction into the batch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SimulatedTxResolution {
+    /// Transaction is included; the batch remains open.
+    Included,
+    /// Transaction is included, and the batch is sealed after it by the specified criterion.
+    IncludedAndSealed(&'static str),
+    /// Transaction doesn't fit into the batch; the batch is sealed before it by the specified criterion.
+    ExcludedAndSealed(&'static str),
+    /// Transaction would be rejected by the state keeper according to the specified criterion.
+    Unexecutable(&'static str),
+}
+
+/// Simulates which seal criterion would trigger for a sequence of transactions, and after how many of them.
+///
+/// The simulation only uses [`ConditionalSealer::dry_run_should_seal_l1_batch()`], so it doesn't influence
+/// metrics or the live batch. Storage writes are summed without deduplication across transactions,
+/// so the simulation may overestimate pubdata compared to the actual batch.
+#[derive(Debug)]
+pub struct SealSimulation<'a> {
+    sealer: &'a dyn ConditionalSealer,
+    protocol_version: ProtocolVersionId,
+    block_data: SealData,
+    tx_count: usize,
+    l1_tx_count: usize,
+    is_sealed: bool,
+}
+
+impl<'a> SealSimulation<'a> {
+    pub fn new(sealer: &'a dyn ConditionalSealer, protocol_version: ProtocolVersionId) -> Self {
+        Self {
+            sealer,
+            protocol_version,
+            block_data: SealData::default(),
+            tx_count: 0,
+            l1_tx_count: 0,
+            is_sealed: false,
+        }
+    }
+
+    /// Returns the number of transactions included into the simulated batch.
+    pub fn tx_count(&self) -> usize {
+        self.tx_count
+    }
+
+    /// Returns the number of L1 transactions included into the simulated batch.
+    pub fn l1_tx_count(&self) -> usize {
+        self.l1_tx_count
+    }
+
+    /// Returns aggregated execution metrics of the transactions included into the simulated batch.
+    pub fn execution_metrics(&self) -> VmExecutionMetrics {
+        self.block_data.execution_metrics
+    }
+
+    /// Checks whether the simulated batch is sealed.
+    pub fn is_sealed(&self) -> bool {
+        self.is_sealed
+    }
+
+    /// Simulates inclusion of the next transaction into the batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the batch is already sealed.
+    pub fn push_transaction(&mut self, is_l1: bool, tx_data: SealData) -> SimulatedTxResolution {
+        assert!(!self.is_sealed, "simulated batch is already sealed");
+
+        let block_data = SealData {
+            execution_metrics: self.block_data.execution_metrics + tx_data.execution_metrics,
+            cumulative_size: self.block_data.cumulative_size + tx_data.cumulative_size,
+            writes_metrics: sum_writes_metrics(&self.block_data, &tx_data),
+            gas_remaining: tx_data.gas_remaining,
+        };
+        let (resolution, criterion) = self.sealer.dry_run_should_seal_l1_batch(
+            MOCK_BLOCK_OPEN_TIMESTAMP_MS,
+            self.tx_count + 1,
+            self.l1_tx_count + usize::from(is_l1),
+            &block_data,
+            &tx_data,
+            self.protocol_version,
+        );
+        let criterion = criterion.unwrap_or("unknown");
+
+        match resolution {
+            SealResolution::Unexecutable(_) => SimulatedTxResolution::Unexecutable(criterion),
+            SealResolution::ExcludeAndSeal => {
+                self.is_sealed = true;
+                SimulatedTxResolution::ExcludedAndSealed(criterion)
+            }
+            SealResolution::NoSeal | SealResolution::IncludeAndSeal => {
+                self.block_data = block_data;
+                self.tx_count += 1;
+                self.l1_tx_count += usize::from(is_l1);
+                if resolution == SealResolution::IncludeAndSeal {
+                    self.is_sealed = true;
+                    SimulatedTxResolution::IncludedAndSealed(criterion)
+                } else {
+                    SimulatedTxResolution::Included
+                }
+            }
+        }
+    }
+}
+
+fn sum_writes_metrics(block_data: &SealData, tx_data: &SealData) -> DeduplicatedWritesMetrics {
+    let (block, tx) = (&block_data.writes_metrics, &tx_data.writes_metrics);
+    DeduplicatedWritesMetrics {
+        initial_storage_writes: block.initial_storage_writes + tx.initial_storage_writes,
+        repeated_storage_writes: block.repeated_storage_writes + tx.repeated_storage_writes,
+        total_updated_values_size: block.total_updated_values_size + tx.total_updated_values_size,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_config::configs::chain::StateKeeperConfig;
+
+    use super::*;
+    use crate::seal_criteria::{criteria::SlotsCriterion, SequencerSealer};
+
+    #[test]
+    fn simulation_stops_at_slots_criterion() {
+        let config = StateKeeperConfig {
+            transaction_slots: 3,
+            ..Default::default()
+        };
+        let sealer = SequencerSealer::with_sealers(config, vec![Box::new(SlotsCriterion)]);
+        let mut simulation = SealSimulation::new(&sealer, ProtocolVersionId::latest());
+
+        for _ in 0..2 {
+            let resolution = simulation.push_transaction(false, SealData::default());
+            assert_eq!(resolution, SimulatedTxResolution::Included);
+        }
+        let resolution = simulation.push_transaction(true, SealData::default());
+        assert_eq!(
+            resolution,
+            SimulatedTxResolution::IncludedAndSealed("slots")
+        );
+        assert!(simulation.is_sealed());
+        assert_eq!(simulation.tx_count(), 3);
+        assert_eq!(simulation.l1_tx_count(), 1);
+    }
+}
@@ -0,0 +1,268 @@
+//! Sequencer lease allowing to run a standby sequencer instance.
+//!
+//! Each sequencer instance has a unique ID and competes for a lease stored in Postgres. Only the lease holder
+//! runs the state keeper and the mempool fetcher; other instances stay in the standby mode, keeping their RocksDB cache
+//! (and, if the corresponding components are run, the Merkle tree) up to date with the data sealed by the holder.
+//! The lease can be transferred to a standby instance via the `admin` API, or is taken over automatically
+//! once it expires.
+//!
+//! To prevent split-brain sealing, the lease epoch acts as a fencing token: [`SequencerFence`] is checked in the same
+//! Postgres transaction in which all data for an L2 block or L1 batch is persisted, and the lease row stays locked
+//! until the transaction commits. Thus, once the lease is transferred, a former holder cannot persist any data
+//! (it stops once it notices that the lease is lost).
+
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_state::KeepUpdatedTask;
+
+use crate::metrics::KEEPER_METRICS;
+
+/// Fence checked by the state keeper before sealing data. Cheaply cloneable.
+#[derive(Debug, Clone)]
+pub struct SequencerFence {
+    instance_id: Arc<str>,
+    epoch: watch::Receiver<Option<u64>>,
+}
+
+impl SequencerFence {
+    /// Returns the ID of this sequencer instance.
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    /// Waits until this instance acquires the sequencer lease. Returns `Ok(false)` if a stop request was received
+    /// while waiting.
+    pub async fn wait_for_lease(
+        &self,
+        stop_receiver: &mut watch::Receiver<bool>,
+    ) -> anyhow::Result<bool> {
+        if *stop_receiver.borrow() {
+            return Ok(false);
+        }
+        let mut epoch = self.epoch.clone();
+        tokio::select! {
+            res = epoch.wait_for(Option::is_some) => {
+                res.context("sequencer lease task stopped")?;
+                Ok(true)
+            }
+            _ = stop_receiver.changed() => Ok(false),
+        }
+    }
+
+    /// Keeps the provided RocksDB cache updated until this instance acquires the sequencer lease, so that the state keeper
+    /// can start without catching up. Returns `Ok(false)` if a stop request was received while waiting.
+    pub async fn run_standby(
+        &self,
+        rocksdb_updater: KeepUpdatedTask,
+        stop_receiver: &mut watch::Receiver<bool>,
+    ) -> anyhow::Result<bool> {
+        tracing::info!(
+            "Sequencer instance `{}` is waiting for the sequencer lease",
+            self.instance_id
+        );
+        let (updater_stop_sender, updater_stop_receiver) = watch::channel(false);
+        let updater_task = tokio::spawn(rocksdb_updater.run(updater_stop_receiver));
+        let lease_result = self.wait_for_lease(stop_receiver).await;
+
+        updater_stop_sender.send_replace(true);
+        updater_task
+            .await
+            .context("RocksDB updater panicked")?
+            .context("failed keeping RocksDB cache updated")?;
+        let acquired = lease_result?;
+        if acquired {
+            tracing::info!(
+                "Sequencer instance `{}` acquired the sequencer lease",
+                self.instance_id
+            );
+        }
+        Ok(acquired)
+    }
+
+    /// Checks that this instance still holds the lease. If called in a transaction, prevents the lease from changing hands
+    /// until the transaction ends.
+    pub(crate) async fn check(&self, connection: &mut Connection<'_, Core>) -> anyhow::Result<()> {
+        let epoch = (*self.epoch.borrow()).context("sequencer lease is not acquired")?;
+        let holds_lease = connection
+            .sequencer_lease_dal()
+            .holds_lease(&self.instance_id, epoch)
+            .await?;
+        anyhow::ensure!(
+            holds_lease,
+            "sequencer instance `{}` has lost the sequencer lease (epoch {epoch})",
+            self.instance_id
+        );
+        Ok(())
+    }
+}
+
+/// Task acquiring and renewing the sequencer lease for this instance.
+///
+/// The task errors if the lease is lost after being acquired, which stops the node.
+#[derive(Debug)]
+pub struct SequencerLeaseTask {
+    pool: ConnectionPool<Core>,
+    instance_id: Arc<str>,
+    ttl: Duration,
+    epoch_sender: watch::Sender<Option<u64>>,
+}
+
+impl SequencerLeaseTask {
+    /// Number of times the lease is renewed during its TTL.
+    const RENEWALS_PER_TTL: u32 = 3;
+
+    pub fn new(pool: ConnectionPool<Core>, instance_id: String, ttl: Duration) -> Self {
+        Self {
+            pool,
+            instance_id: instance_id.into(),
+            ttl,
+            epoch_sender: watch::channel(None).0,
+        }
+    }
+
+    /// Returns a fence for this sequencer instance.
+    pub fn fence(&self) -> SequencerFence {
+        SequencerFence {
+            instance_id: self.instance_id.clone(),
+            epoch: self.epoch_sender.subscribe(),
+        }
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let renew_interval = self.ttl / Self::RENEWALS_PER_TTL;
+        while !*stop_receiver.borrow() {
+            match self.try_acquire_lease().await {
+                Ok(epoch) => self.update_epoch(epoch)?,
+                Err(err) => {
+                    // The lease may expire if renewals keep failing, but sealing is still protected by the fence.
+                    tracing::warn!("Failed acquiring sequencer lease: {err:#}");
+                }
+            }
+
+            if tokio::time::timeout(renew_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, sequencer lease task is shutting down");
+        Ok(())
+    }
+
+    async fn try_acquire_lease(&self) -> anyhow::Result<Option<u64>> {
+        let mut connection = self.pool.connection_tagged("state_keeper").await?;
+        Ok(connection
+            .sequencer_lease_dal()
+            .try_acquire_lease(&self.instance_id, self.ttl)
+            .await?)
+    }
+
+    fn update_epoch(&self, epoch: Option<u64>) -> anyhow::Result<()> {
+        let prev_epoch = *self.epoch_sender.borrow();
+        match (prev_epoch, epoch) {
+            (None, Some(epoch)) => {
+                tracing::info!(
+                    "Sequencer instance `{}` acquired the sequencer lease with epoch {epoch}",
+                    self.instance_id
+                );
+                KEEPER_METRICS.sequencer_lease_epoch.set(epoch);
+                self.epoch_sender.send_replace(Some(epoch));
+            }
+            (None, None) => {
+                tracing::debug!(
+                    "Sequencer lease is held by another instance; `{}` stays in standby",
+                    self.instance_id
+                );
+            }
+            (Some(prev_epoch), Some(epoch)) if prev_epoch == epoch => { /* lease is renewed */ }
+            (Some(prev_epoch), _) => {
+                anyhow::bail!(
+                    "Sequencer instance `{}` has lost the sequencer lease (epoch {prev_epoch})",
+                    self.instance_id
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[tokio::test]
+    async fn standby_instance_is_promoted() {
+        const PRIMARY_TTL: Duration = Duration::from_secs(1);
+        const STANDBY_TTL: Duration = Duration::from_millis(150);
+
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let primary = SequencerLeaseTask::new(pool.clone(), "primary".to_owned(), PRIMARY_TTL);
+        let primary_fence = primary.fence();
+        let standby = SequencerLeaseTask::new(pool.clone(), "standby".to_owned(), STANDBY_TTL);
+        let standby_fence = standby.fence();
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let primary_task = tokio::spawn(primary.run(stop_receiver.clone()));
+        assert!(primary_fence
+            .wait_for_lease(&mut stop_receiver.clone())
+            .await
+            .unwrap());
+        let standby_task = tokio::spawn(standby.run(stop_receiver.clone()));
+
+        let mut storage = pool.connection().await.unwrap();
+        primary_fence.check(&mut storage).await.unwrap();
+        standby_fence.check(&mut storage).await.unwrap_err();
+
+        // Promote the standby instance.
+        storage
+            .sequencer_lease_dal()
+            .transfer_lease("standby", TTL)
+            .await
+            .unwrap();
+        let err = primary_fence.check(&mut storage).await.unwrap_err();
+        assert!(err.to_string().contains("lost"), "{err:#}");
+
+        assert!(standby_fence
+            .wait_for_lease(&mut stop_receiver.clone())
+            .await
+            .unwrap());
+        standby_fence.check(&mut storage).await.unwrap();
+        let err = primary_task.await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("lost"), "{err:#}");
+        assert!(!standby_task.is_finished());
+    }
+
+    #[tokio::test]
+    async fn lease_task_fails_after_losing_lease() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let task = SequencerLeaseTask::new(pool.clone(), "primary".to_owned(), TTL);
+        let fence = task.fence();
+
+        let epoch = task.try_acquire_lease().await.unwrap();
+        assert_eq!(epoch, Some(1));
+        task.update_epoch(epoch).unwrap();
+        task.update_epoch(epoch).unwrap();
+        assert!(fence
+            .wait_for_lease(&mut watch::channel(false).1)
+            .await
+            .unwrap());
+
+        pool.connection()
+            .await
+            .unwrap()
+            .sequencer_lease_dal()
+            .transfer_lease("standby", TTL)
+            .await
+            .unwrap();
+        let epoch = task.try_acquire_lease().await.unwrap();
+        assert_eq!(epoch, None);
+        let err = task.update_epoch(epoch).unwrap_err();
+        assert!(err.to_string().contains("lost"), "{err:#}");
+    }
+}
@@ -5,7 +5,8 @@ use async_trait::async_trait;
 use tokio::sync::watch;
 use zksync_dal::{ConnectionPool, Core};
 use zksync_state::{
-    AsyncCatchupTask, OwnedStorage, ReadStorageFactory, RocksdbCell, RocksdbStorageOptions,
+    AsyncCatchupTask, KeepUpdatedTask, OwnedStorage, ReadStorageFactory, RocksdbCell,
+    RocksdbStorageOptions,
 };
 use zksync_types::L1BatchNumber;
 
@@ -34,6 +35,12 @@ impl AsyncRocksdbCache {
             task.with_db_options(state_keeper_db_options),
         )
     }
+
+    /// Creates a task that keeps the cache updated with Postgres after the initial catch-up. Must not run concurrently
+    /// with the state keeper; used to keep the cache warm while the state keeper is in the standby mode.
+    pub fn keep_updated(&self) -> KeepUpdatedTask {
+        self.rocksdb_cell.keep_updated(self.pool.clone())
+    }
 }
 
 #[async_trait]
@@ -752,6 +752,8 @@ impl StateKeeperIO for TestIO {
 
         if let ScenarioItem::UpdateBlockTimestamp(_, timestamp) = action {
             *block_timestamp = timestamp;
+            // Ensure that timestamps of the following L2 blocks are monotonic.
+            self.timestamp = self.timestamp.max(timestamp + 1);
         } else {
             // Return the action to the scenario.
             self.actions.lock().unwrap().push_front(action);
@@ -61,6 +61,7 @@ pub(crate) fn pending_batch_data(pending_l2_blocks: Vec<L2BlockExecutionData>) -
         },
         pubdata_params: Default::default(),
         pending_l2_blocks,
+        checkpoints: Vec::new(),
     }
 }
 
@@ -0,0 +1,456 @@
+//! Policies selecting timestamps for new L1 batches and L2 blocks.
+//!
+//! Regardless of the policy, timestamps of consecutive L2 blocks must strictly increase (two L2 blocks or L1 batches
+//! with the same timestamp are rejected by the bootloader). This invariant is checked by [`validate_next_timestamp()`]
+//! for the output of any [`StateKeeperIO`](crate::StateKeeperIO) implementation, i.e., both for blocks produced
+//! on the main node and for blocks synced by the external node.
+
+use std::{
+    cmp, fmt,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use tokio::sync::watch;
+use zksync_config::configs::chain::{StateKeeperConfig, TimestampPolicyKind};
+use zksync_eth_client::EthInterface;
+use zksync_types::{
+    utils::display_timestamp,
+    web3::{BlockId, BlockNumber},
+    L2BlockNumber,
+};
+
+use crate::utils::millis_since_epoch;
+
+/// Error returned if the timestamp of an L2 block doesn't exceed the timestamp of the previous block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "timestamp {timestamp} of L2 block #{l2_block} is not greater than timestamp {prev_timestamp} of the previous L2 block"
+)]
+pub struct NonMonotonicTimestamp {
+    pub l2_block: L2BlockNumber,
+    pub prev_timestamp: u64,
+    pub timestamp: u64,
+}
+
+/// Checks that `timestamp` of `l2_block` is valid given the timestamp of the previous L2 block.
+pub fn validate_next_timestamp(
+    prev_timestamp: u64,
+    timestamp: u64,
+    l2_block: L2BlockNumber,
+) -> Result<(), NonMonotonicTimestamp> {
+    if timestamp > prev_timestamp {
+        Ok(())
+    } else {
+        Err(NonMonotonicTimestamp {
+            l2_block,
+            prev_timestamp,
+            timestamp,
+        })
+    }
+}
+
+/// Policy selecting timestamps for new L2 blocks (including the first L2 block in an L1 batch, whose timestamp
+/// is used as the batch timestamp).
+#[async_trait]
+pub trait TimestampPolicy: fmt::Debug + Send + Sync + 'static {
+    /// Returns the timestamp for `l2_block` given the timestamp of the previous L2 block. The returned timestamp
+    /// must be greater than `prev_timestamp`. The policy may wait until it can produce such a timestamp.
+    ///
+    /// This method must be cancel-safe.
+    async fn next_timestamp(
+        &self,
+        prev_timestamp: u64,
+        l2_block: L2BlockNumber,
+    ) -> anyhow::Result<u64>;
+
+    /// Refreshes the timestamp of an L2 block that was opened, but didn't receive any transactions yet.
+    /// The updated timestamp must not be lower than the original one. By default, the timestamp is left as is.
+    fn refresh_timestamp(&self, _timestamp: &mut u64) {}
+}
+
+/// Creates a timestamp policy based on the state keeper config. `l1_client` is only required for
+/// the [`TimestampPolicyKind::MedianOfL1`] policy.
+///
+/// Returns the policy together with the background task that must be run for the policy to work, if any.
+pub fn timestamp_policy_from_config(
+    config: &StateKeeperConfig,
+    l1_client: Option<Box<dyn EthInterface>>,
+) -> anyhow::Result<(Box<dyn TimestampPolicy>, Option<MedianOfL1Updater>)> {
+    Ok(match config.timestamp_policy {
+        TimestampPolicyKind::RealTime => (Box::new(RealTimePolicy), None),
+        TimestampPolicyKind::FixedIncrement => {
+            let policy = FixedIncrementPolicy::new(config.timestamp_increment_sec)?;
+            (Box::new(policy), None)
+        }
+        TimestampPolicyKind::MedianOfL1 => {
+            let l1_client = l1_client.context("L1 client is required for `median_of_l1` policy")?;
+            let (policy, updater) =
+                MedianOfL1Policy::new(l1_client, config.l1_timestamp_window as usize)?;
+            (Box::new(policy), Some(updater))
+        }
+    })
+}
+
+/// Uses the current wall-clock time. This is the default policy.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealTimePolicy;
+
+#[async_trait]
+impl TimestampPolicy for RealTimePolicy {
+    async fn next_timestamp(
+        &self,
+        prev_timestamp: u64,
+        l2_block: L2BlockNumber,
+    ) -> anyhow::Result<u64> {
+        // We cannot create two L1 batches or L2 blocks with the same timestamp (forbidden by the bootloader).
+        // Hence, we wait until the current timestamp is larger than the timestamp of the previous L2 block.
+        Ok(sleep_past(prev_timestamp, l2_block).await)
+    }
+
+    fn refresh_timestamp(&self, timestamp: &mut u64) {
+        let current_timestamp = (millis_since_epoch() / 1_000) as u64;
+        if current_timestamp < *timestamp {
+            tracing::warn!(
+                "Trying to update block timestamp {timestamp} with lower value timestamp {current_timestamp}",
+            );
+        } else {
+            *timestamp = current_timestamp;
+        }
+    }
+}
+
+/// Sleeps until the current timestamp is larger than the provided `timestamp`.
+///
+/// Returns the current timestamp after the sleep. It is guaranteed to be larger than `timestamp`.
+async fn sleep_past(timestamp: u64, l2_block: L2BlockNumber) -> u64 {
+    let mut current_timestamp_millis = millis_since_epoch();
+    let mut current_timestamp = (current_timestamp_millis / 1_000) as u64;
+    match timestamp.cmp(&current_timestamp) {
+        cmp::Ordering::Less => return current_timestamp,
+        cmp::Ordering::Equal => {
+            tracing::info!(
+                "Current timestamp {} for L2 block #{l2_block} is equal to previous L2 block timestamp; waiting until \
+                 timestamp increases",
+                display_timestamp(current_timestamp)
+            );
+        }
+        cmp::Ordering::Greater => {
+            // This situation can be triggered if the system keeper is started on a pod with a different
+            // system time, or if it is buggy. Thus, a one-time error could require no actions if L1 batches
+            // are expected to be generated frequently.
+            tracing::error!(
+                "Previous L2 block timestamp {} is larger than the current timestamp {} for L2 block #{l2_block}",
+                display_timestamp(timestamp),
+                display_timestamp(current_timestamp)
+            );
+        }
+    }
+
+    // This loop should normally run once, since `tokio::time::sleep` sleeps *at least* the specified duration.
+    // The logic is organized in a loop for marginal cases, such as the system time getting changed during `sleep()`.
+    loop {
+        // Time to catch up to `timestamp`; panic / underflow on subtraction is never triggered
+        // since we've ensured that `timestamp >= current_timestamp`.
+        let wait_seconds = timestamp - current_timestamp;
+        // Time to wait until the current timestamp increases.
+        let wait_millis = 1_001 - (current_timestamp_millis % 1_000) as u64;
+        let wait = Duration::from_millis(wait_millis + wait_seconds * 1_000);
+
+        tokio::time::sleep(wait).await;
+        current_timestamp_millis = millis_since_epoch();
+        current_timestamp = (current_timestamp_millis / 1_000) as u64;
+
+        if current_timestamp > timestamp {
+            return current_timestamp;
+        }
+    }
+}
+
+/// Advances the timestamp by a fixed increment for each L2 block without waiting. Block timestamps are thus
+/// fully determined by the block number and the timestamp of the genesis block, which is useful for test networks
+/// that need accelerated deterministic time.
+///
+/// Note that protocol upgrades are activated based on L2 block timestamps, so with this policy, they will be activated
+/// once the L2 time reaches the upgrade timestamp, rather than at the specified wall-clock time.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedIncrementPolicy {
+    increment: u64,
+}
+
+impl FixedIncrementPolicy {
+    /// Creates a policy with the specified increment in seconds. The increment must be positive.
+    pub fn new(increment: u64) -> anyhow::Result<Self> {
+        anyhow::ensure!(increment > 0, "timestamp increment must be positive");
+        Ok(Self { increment })
+    }
+}
+
+#[async_trait]
+impl TimestampPolicy for FixedIncrementPolicy {
+    async fn next_timestamp(
+        &self,
+        prev_timestamp: u64,
+        _l2_block: L2BlockNumber,
+    ) -> anyhow::Result<u64> {
+        prev_timestamp
+            .checked_add(self.increment)
+            .context("timestamp overflow")
+    }
+}
+
+/// Uses the median timestamp of the latest L1 blocks, similar to the median time past rule in Bitcoin. This makes
+/// L2 time follow L1 consensus time and makes it resistant to manipulation of individual L1 block timestamps.
+///
+/// The median is computed by [`MedianOfL1Updater`] in the background, so that selecting a timestamp never waits
+/// for L1. If the median doesn't exceed the timestamp of the previous L2 block (e.g., if several L2 blocks are produced
+/// within a single L1 block), or if the median isn't available (e.g., because L1 is unreachable), the timestamp
+/// is advanced by 1 second compared to the previous L2 block.
+#[derive(Debug)]
+pub struct MedianOfL1Policy {
+    median: watch::Receiver<Option<u64>>,
+}
+
+impl MedianOfL1Policy {
+    /// Creates a policy taking the median over `window` latest L1 blocks. The returned updater must be run
+    /// to keep the median up to date.
+    pub fn new(
+        client: Box<dyn EthInterface>,
+        window: usize,
+    ) -> anyhow::Result<(Self, MedianOfL1Updater)> {
+        anyhow::ensure!(window > 0, "L1 timestamp window must be positive");
+        let (sender, median) = watch::channel(None);
+        let updater = MedianOfL1Updater {
+            client,
+            window,
+            sender,
+            last_l1_block_number: None,
+        };
+        Ok((Self { median }, updater))
+    }
+}
+
+#[async_trait]
+impl TimestampPolicy for MedianOfL1Policy {
+    async fn next_timestamp(
+        &self,
+        prev_timestamp: u64,
+        l2_block: L2BlockNumber,
+    ) -> anyhow::Result<u64> {
+        let next_timestamp = prev_timestamp + 1;
+        let Some(median) = *self.median.borrow() else {
+            tracing::debug!(
+                "Median L1 timestamp is not available; using timestamp {} for L2 block #{l2_block}",
+                display_timestamp(next_timestamp)
+            );
+            return Ok(next_timestamp);
+        };
+        Ok(median.max(next_timestamp))
+    }
+}
+
+/// Background task periodically computing the median L1 timestamp for [`MedianOfL1Policy`]. Errors querying L1
+/// are logged and retried on the next iteration; the previously computed median is retained in the meantime.
+#[derive(Debug)]
+pub struct MedianOfL1Updater {
+    client: Box<dyn EthInterface>,
+    window: usize,
+    sender: watch::Sender<Option<u64>>,
+    last_l1_block_number: Option<u64>,
+}
+
+impl MedianOfL1Updater {
+    const UPDATE_INTERVAL: Duration = Duration::from_secs(5);
+
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow() {
+            if let Err(err) = self.update().await {
+                tracing::warn!("Failed updating median L1 timestamp: {err:#}");
+            }
+            // Error here means that the stop signal sender was dropped, which we treat as a stop signal as well.
+            tokio::time::timeout(Self::UPDATE_INTERVAL, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Stop signal received, median L1 timestamp updater is shutting down");
+        Ok(())
+    }
+
+    async fn update(&mut self) -> anyhow::Result<()> {
+        let l1_block_number = self.client.block_number().await?.as_u64();
+        if self.last_l1_block_number == Some(l1_block_number) {
+            return Ok(());
+        }
+
+        let started_at = Instant::now();
+        let first_block_number = l1_block_number.saturating_sub(self.window as u64 - 1);
+        let mut timestamps = Vec::with_capacity(self.window);
+        for number in first_block_number..=l1_block_number {
+            let block = self
+                .client
+                .block(BlockId::Number(BlockNumber::Number(number.into())))
+                .await?
+                .with_context(|| format!("L1 block #{number} is missing"))?;
+            timestamps.push(block.timestamp.as_u64());
+        }
+        let median = median(&mut timestamps);
+        tracing::debug!(
+            "Computed median timestamp {} for L1 blocks #{first_block_number}..=#{l1_block_number} in {:?}",
+            display_timestamp(median),
+            started_at.elapsed()
+        );
+
+        self.last_l1_block_number = Some(l1_block_number);
+        self.sender.send_replace(Some(median));
+        Ok(())
+    }
+}
+
+fn median(values: &mut [u64]) -> u64 {
+    values.sort_unstable();
+    values[values.len() / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::time::timeout_at;
+    use zksync_types::{web3, H256, U256, U64};
+    use zksync_web3_decl::{
+        client::{MockClient, L1},
+        jsonrpsee::core::ClientError,
+    };
+
+    use super::*;
+    use crate::tests::seconds_since_epoch;
+
+    // This test defensively uses large deadlines in order to account for tests running in parallel etc.
+    #[tokio::test]
+    async fn sleeping_past_timestamp() {
+        let past_timestamps = [0, 1_000, 1_000_000_000, seconds_since_epoch() - 10];
+        for timestamp in past_timestamps {
+            let deadline = Instant::now() + Duration::from_secs(1);
+            timeout_at(deadline.into(), sleep_past(timestamp, L2BlockNumber(1)))
+                .await
+                .unwrap();
+        }
+
+        let current_timestamp = seconds_since_epoch();
+        let deadline = Instant::now() + Duration::from_secs(2);
+        let ts = timeout_at(
+            deadline.into(),
+            sleep_past(current_timestamp, L2BlockNumber(1)),
+        )
+        .await
+        .unwrap();
+        assert!(ts > current_timestamp);
+
+        let future_timestamp = seconds_since_epoch() + 1;
+        let deadline = Instant::now() + Duration::from_secs(3);
+        let ts = timeout_at(
+            deadline.into(),
+            sleep_past(future_timestamp, L2BlockNumber(1)),
+        )
+        .await
+        .unwrap();
+        assert!(ts > future_timestamp);
+
+        let future_timestamp = seconds_since_epoch() + 1;
+        let deadline = Instant::now() + Duration::from_millis(100);
+        // ^ This deadline is too small (we need at least 1_000ms)
+        let result = timeout_at(
+            deadline.into(),
+            sleep_past(future_timestamp, L2BlockNumber(1)),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn validating_timestamps() {
+        validate_next_timestamp(10, 11, L2BlockNumber(1)).unwrap();
+        let err = validate_next_timestamp(10, 10, L2BlockNumber(1)).unwrap_err();
+        assert_eq!(
+            err,
+            NonMonotonicTimestamp {
+                l2_block: L2BlockNumber(1),
+                prev_timestamp: 10,
+                timestamp: 10,
+            }
+        );
+        validate_next_timestamp(10, 9, L2BlockNumber(1)).unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn fixed_increment_policy() {
+        FixedIncrementPolicy::new(0).unwrap_err();
+        let policy = FixedIncrementPolicy::new(12).unwrap();
+        let ts = policy.next_timestamp(100, L2BlockNumber(1)).await.unwrap();
+        assert_eq!(ts, 112);
+
+        let mut refreshed_ts = ts;
+        policy.refresh_timestamp(&mut refreshed_ts);
+        assert_eq!(refreshed_ts, ts);
+    }
+
+    #[test]
+    fn computing_median() {
+        assert_eq!(median(&mut [5]), 5);
+        assert_eq!(median(&mut [3, 1, 2]), 2);
+        assert_eq!(median(&mut [100, 1, 2, 3]), 3);
+    }
+
+    #[tokio::test]
+    async fn median_of_l1_policy() {
+        let client = MockClient::builder(L1::default())
+            .method("eth_blockNumber", || Ok(U64::from(10)))
+            .method(
+                "eth_getBlockByNumber",
+                |number: web3::BlockNumber, with_txs: bool| {
+                    assert!(!with_txs);
+                    let web3::BlockNumber::Number(number) = number else {
+                        panic!("Unexpected number: {number:?}");
+                    };
+                    // Emulate an L1 block with a manipulated timestamp.
+                    let timestamp = if number.as_u64() == 10 {
+                        1_000_000
+                    } else {
+                        1_000 + number.as_u64() * 12
+                    };
+                    Ok(Some(web3::Block::<H256> {
+                        number: Some(number),
+                        timestamp: U256::from(timestamp),
+                        ..web3::Block::default()
+                    }))
+                },
+            )
+            .build();
+        let (policy, mut updater) = MedianOfL1Policy::new(Box::new(client), 5).unwrap();
+
+        // The median is not computed yet, so the timestamp is incremented.
+        let ts = policy.next_timestamp(10, L2BlockNumber(1)).await.unwrap();
+        assert_eq!(ts, 11);
+
+        updater.update().await.unwrap();
+        // L1 blocks #6..=10 are used; the median is the timestamp of block #8.
+        let ts = policy.next_timestamp(0, L2BlockNumber(1)).await.unwrap();
+        assert_eq!(ts, 1_096);
+        // If the median doesn't exceed the previous timestamp, the timestamp is incremented.
+        let next_ts = policy.next_timestamp(ts, L2BlockNumber(2)).await.unwrap();
+        assert_eq!(next_ts, ts + 1);
+    }
+
+    #[tokio::test]
+    async fn median_of_l1_policy_with_unavailable_l1() {
+        let client = MockClient::builder(L1::default())
+            .method("eth_blockNumber", || {
+                Err::<U64, _>(ClientError::RequestTimeout)
+            })
+            .build();
+        let (policy, mut updater) = MedianOfL1Policy::new(Box::new(client), 5).unwrap();
+
+        updater.update().await.unwrap_err();
+        let ts = policy.next_timestamp(100, L2BlockNumber(1)).await.unwrap();
+        assert_eq!(ts, 101);
+    }
+}
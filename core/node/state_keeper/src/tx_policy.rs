@@ -0,0 +1,409 @@
+//! Policy engine deciding which L2 transactions are accepted by the node.
+//!
+//! The policy is evaluated twice: when a transaction is submitted via the API, and when the state keeper includes
+//! a transaction into a block. The latter protects against transactions that got into the mempool before the policy
+//! was changed. Rate limits are only enforced on submission, since rejecting transactions that have already been
+//! accepted into the mempool for that reason would be confusing to users.
+//!
+//! Rejections are persisted to Postgres, so that operators can inspect them. Persisting is throttled both per initiator
+//! and globally, and old decisions are pruned, so that rejected transactions cannot be used to bloat the database.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use zksync_config::configs::TxPolicyConfig;
+use zksync_dal::{Connection, Core, CoreDal, DalResult};
+use zksync_multivm::interface::VmEvent;
+use zksync_types::{h256_to_address, Address, CONTRACT_DEPLOYER_ADDRESS, H256};
+
+/// Stage of the transaction lifecycle at which a policy decision was made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxPolicyStage {
+    /// Transaction submission via the API.
+    Submission,
+    /// Transaction inclusion into a block by the state keeper.
+    Inclusion,
+}
+
+impl TxPolicyStage {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Submission => "submission",
+            Self::Inclusion => "inclusion",
+        }
+    }
+}
+
+/// Violation of the transaction policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum TxPolicyViolation {
+    #[error("initiator {0:?} is not in the allow list")]
+    InitiatorNotAllowed(Address),
+    #[error("address {0:?} is in the deny list")]
+    AddressDenied(Address),
+    #[error("address {0:?} is not allowed to deploy contracts")]
+    DeployerNotAllowed(Address),
+    #[error("initiator {0:?} has exceeded the limit of {1} transactions per minute")]
+    RateLimitExceeded(Address, u32),
+}
+
+impl TxPolicyViolation {
+    /// Returns the name of the violated rule. Used in metrics and persisted decisions.
+    pub fn rule(&self) -> &'static str {
+        match self {
+            Self::InitiatorNotAllowed(_) => "initiator_not_allowed",
+            Self::AddressDenied(_) => "address_denied",
+            Self::DeployerNotAllowed(_) => "deployer_not_allowed",
+            Self::RateLimitExceeded(..) => "rate_limit_exceeded",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RateLimiterState {
+    window_started_at: Instant,
+    total_count: u32,
+    tx_counts: HashMap<Address, u32>,
+}
+
+/// Fixed-window rate limiter. All counters are reset at the start of each window, which keeps memory usage
+/// proportional to the number of initiators active within a single window.
+#[derive(Debug)]
+struct RateLimiter {
+    max_txs_per_window: u32,
+    max_total_txs_per_window: Option<u32>,
+    window: Duration,
+    state: Mutex<RateLimiterState>,
+}
+
+impl RateLimiter {
+    fn new(max_txs_per_window: u32, window: Duration) -> Self {
+        Self {
+            max_txs_per_window,
+            max_total_txs_per_window: None,
+            window,
+            state: Mutex::new(RateLimiterState {
+                window_started_at: Instant::now(),
+                total_count: 0,
+                tx_counts: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Additionally limits the total number of transactions from all initiators within a window.
+    fn with_total_limit(mut self, max_total_txs_per_window: u32) -> Self {
+        self.max_total_txs_per_window = Some(max_total_txs_per_window);
+        self
+    }
+
+    fn try_acquire(&self, initiator: Address, now: Instant) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if now.duration_since(state.window_started_at) >= self.window {
+            state.window_started_at = now;
+            state.total_count = 0;
+            state.tx_counts.clear();
+        }
+        if let Some(max_total) = self.max_total_txs_per_window {
+            if state.total_count >= max_total {
+                return false;
+            }
+        }
+        let count = state.tx_counts.entry(initiator).or_default();
+        if *count >= self.max_txs_per_window {
+            return false;
+        }
+        *count += 1;
+        state.total_count += 1;
+        true
+    }
+}
+
+/// Returns addresses of deployers for all contracts deployed in the specified VM events.
+pub fn deployer_addresses(events: &[VmEvent]) -> impl Iterator<Item = Address> + '_ {
+    events.iter().filter_map(|event| {
+        let is_contract_deployed = event.address == CONTRACT_DEPLOYER_ADDRESS
+            && event.indexed_topics.first() == Some(&VmEvent::DEPLOY_EVENT_SIGNATURE);
+        if is_contract_deployed {
+            event.indexed_topics.get(1).map(h256_to_address)
+        } else {
+            None
+        }
+    })
+}
+
+/// Transaction policy engine.
+#[derive(Debug)]
+pub struct TxPolicy {
+    allowed_initiators: HashSet<Address>,
+    denied_addresses: HashSet<Address>,
+    allowed_deployers: Option<HashSet<Address>>,
+    rate_limiter: Option<RateLimiter>,
+    decisions_limiter: RateLimiter,
+    decisions_pruned_at: Mutex<Option<Instant>>,
+}
+
+impl TxPolicy {
+    const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+    /// Maximum number of decisions persisted for a single initiator per [`Self::RATE_LIMIT_WINDOW`].
+    const MAX_PERSISTED_DECISIONS_PER_ADDRESS: u32 = 10;
+    /// Maximum number of decisions persisted for all initiators per [`Self::RATE_LIMIT_WINDOW`].
+    const MAX_PERSISTED_DECISIONS: u32 = 600;
+    /// Persisted decisions older than this are pruned.
+    const DECISIONS_RETENTION: Duration = Duration::from_secs(7 * 86_400);
+    const DECISIONS_PRUNING_INTERVAL: Duration = Duration::from_secs(600);
+
+    pub fn new(config: &TxPolicyConfig) -> Self {
+        Self {
+            allowed_initiators: config.allowed_initiators.iter().copied().collect(),
+            denied_addresses: config.denied_addresses.iter().copied().collect(),
+            allowed_deployers: config
+                .allowed_deployers
+                .as_ref()
+                .map(|deployers| deployers.iter().copied().collect()),
+            rate_limiter: config
+                .max_txs_per_address_per_minute
+                .map(|limit| RateLimiter::new(limit, Self::RATE_LIMIT_WINDOW)),
+            decisions_limiter: RateLimiter::new(
+                Self::MAX_PERSISTED_DECISIONS_PER_ADDRESS,
+                Self::RATE_LIMIT_WINDOW,
+            )
+            .with_total_limit(Self::MAX_PERSISTED_DECISIONS),
+            decisions_pruned_at: Mutex::new(None),
+        }
+    }
+
+    /// Checks static rules for a transaction with the specified initiator and recipient. Transactions without
+    /// a recipient or sent to the contract deployer are treated as contract deployments.
+    pub fn check_tx(
+        &self,
+        initiator: Address,
+        recipient: Option<Address>,
+    ) -> Result<(), TxPolicyViolation> {
+        if self.denied_addresses.contains(&initiator) {
+            return Err(TxPolicyViolation::AddressDenied(initiator));
+        }
+        if let Some(recipient) = recipient {
+            if self.denied_addresses.contains(&recipient) {
+                return Err(TxPolicyViolation::AddressDenied(recipient));
+            }
+        }
+        if !self.allowed_initiators.is_empty() && !self.allowed_initiators.contains(&initiator) {
+            return Err(TxPolicyViolation::InitiatorNotAllowed(initiator));
+        }
+
+        let is_deployment = recipient.map_or(true, |address| address == CONTRACT_DEPLOYER_ADDRESS);
+        if is_deployment {
+            self.check_deployer(initiator)?;
+        }
+        Ok(())
+    }
+
+    /// Checks whether the specified address is allowed to deploy contracts. Unlike [`Self::check_tx()`], this can be used
+    /// with deployer addresses extracted from the transaction execution, e.g. for contracts deployed by factories.
+    pub fn check_deployer(&self, deployer: Address) -> Result<(), TxPolicyViolation> {
+        match &self.allowed_deployers {
+            Some(deployers) if !deployers.contains(&deployer) => {
+                Err(TxPolicyViolation::DeployerNotAllowed(deployer))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Accounts for a transaction from the specified initiator in the rate limit. If the limit is exceeded,
+    /// the transaction is not accounted for.
+    pub fn check_rate_limit(&self, initiator: Address) -> Result<(), TxPolicyViolation> {
+        self.check_rate_limit_at(initiator, Instant::now())
+    }
+
+    fn check_rate_limit_at(
+        &self,
+        initiator: Address,
+        now: Instant,
+    ) -> Result<(), TxPolicyViolation> {
+        let Some(rate_limiter) = &self.rate_limiter else {
+            return Ok(());
+        };
+        if rate_limiter.try_acquire(initiator, now) {
+            Ok(())
+        } else {
+            Err(TxPolicyViolation::RateLimitExceeded(
+                initiator,
+                rate_limiter.max_txs_per_window,
+            ))
+        }
+    }
+
+    /// Persists a rejection of the specified transaction, unless persisting is throttled, and prunes old decisions
+    /// if necessary. Rate limit violations are never persisted. Returns whether the decision was persisted.
+    pub async fn persist_decision(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        tx_hash: H256,
+        initiator: Address,
+        stage: TxPolicyStage,
+        violation: &TxPolicyViolation,
+    ) -> DalResult<bool> {
+        let now = Instant::now();
+        if matches!(violation, TxPolicyViolation::RateLimitExceeded(..))
+            || !self.decisions_limiter.try_acquire(initiator, now)
+        {
+            return Ok(false);
+        }
+
+        let mut dal = storage.tx_policy_decisions_dal();
+        dal.insert_decision(
+            tx_hash,
+            initiator,
+            stage.as_str(),
+            violation.rule(),
+            &violation.to_string(),
+        )
+        .await?;
+        if self.should_prune_decisions(now) {
+            let pruned_count = dal.prune_decisions(Self::DECISIONS_RETENTION).await?;
+            tracing::debug!("Pruned {pruned_count} old transaction policy decisions");
+        }
+        Ok(true)
+    }
+
+    fn should_prune_decisions(&self, now: Instant) -> bool {
+        let mut pruned_at = self.decisions_pruned_at.lock().unwrap();
+        let should_prune = pruned_at.map_or(true, |pruned_at| {
+            now.duration_since(pruned_at) >= Self::DECISIONS_PRUNING_INTERVAL
+        });
+        if should_prune {
+            *pruned_at = Some(now);
+        }
+        should_prune
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_policy_allows_everything() {
+        let policy = TxPolicy::new(&TxPolicyConfig::default());
+        let address = Address::repeat_byte(1);
+        policy
+            .check_tx(address, Some(Address::repeat_byte(2)))
+            .unwrap();
+        policy.check_tx(address, None).unwrap();
+        policy
+            .check_tx(address, Some(CONTRACT_DEPLOYER_ADDRESS))
+            .unwrap();
+        for _ in 0..1_000 {
+            policy.check_rate_limit(address).unwrap();
+        }
+    }
+
+    #[test]
+    fn allow_and_deny_lists() {
+        let allowed = Address::repeat_byte(1);
+        let denied = Address::repeat_byte(2);
+        let policy = TxPolicy::new(&TxPolicyConfig {
+            allowed_initiators: vec![allowed, denied],
+            denied_addresses: vec![denied],
+            ..TxPolicyConfig::default()
+        });
+
+        policy
+            .check_tx(allowed, Some(Address::repeat_byte(3)))
+            .unwrap();
+        assert_eq!(
+            policy.check_tx(denied, Some(Address::repeat_byte(3))),
+            Err(TxPolicyViolation::AddressDenied(denied))
+        );
+        assert_eq!(
+            policy.check_tx(allowed, Some(denied)),
+            Err(TxPolicyViolation::AddressDenied(denied))
+        );
+        let other = Address::repeat_byte(3);
+        assert_eq!(
+            policy.check_tx(other, Some(allowed)),
+            Err(TxPolicyViolation::InitiatorNotAllowed(other))
+        );
+    }
+
+    #[test]
+    fn deployment_gating() {
+        let deployer = Address::repeat_byte(1);
+        let other = Address::repeat_byte(2);
+        let policy = TxPolicy::new(&TxPolicyConfig {
+            allowed_deployers: Some(vec![deployer]),
+            ..TxPolicyConfig::default()
+        });
+
+        policy.check_tx(deployer, None).unwrap();
+        policy
+            .check_tx(deployer, Some(CONTRACT_DEPLOYER_ADDRESS))
+            .unwrap();
+        policy.check_tx(other, Some(deployer)).unwrap();
+        assert_eq!(
+            policy.check_tx(other, None),
+            Err(TxPolicyViolation::DeployerNotAllowed(other))
+        );
+        assert_eq!(
+            policy.check_tx(other, Some(CONTRACT_DEPLOYER_ADDRESS)),
+            Err(TxPolicyViolation::DeployerNotAllowed(other))
+        );
+
+        let policy = TxPolicy::new(&TxPolicyConfig {
+            allowed_deployers: Some(vec![]),
+            ..TxPolicyConfig::default()
+        });
+        policy.check_deployer(deployer).unwrap_err();
+    }
+
+    #[test]
+    fn rate_limiting() {
+        let policy = TxPolicy::new(&TxPolicyConfig {
+            max_txs_per_address_per_minute: Some(2),
+            ..TxPolicyConfig::default()
+        });
+        let address = Address::repeat_byte(1);
+        let other_address = Address::repeat_byte(2);
+        let start = Instant::now();
+
+        policy.check_rate_limit_at(address, start).unwrap();
+        policy.check_rate_limit_at(address, start).unwrap();
+        assert_eq!(
+            policy.check_rate_limit_at(address, start),
+            Err(TxPolicyViolation::RateLimitExceeded(address, 2))
+        );
+        policy.check_rate_limit_at(other_address, start).unwrap();
+
+        let next_window = start + TxPolicy::RATE_LIMIT_WINDOW;
+        policy.check_rate_limit_at(address, next_window).unwrap();
+    }
+
+    #[test]
+    fn rate_limiting_with_total_limit() {
+        let limiter = RateLimiter::new(2, TxPolicy::RATE_LIMIT_WINDOW).with_total_limit(3);
+        let start = Instant::now();
+        let addresses = [Address::repeat_byte(1), Address::repeat_byte(2)];
+
+        assert!(limiter.try_acquire(addresses[0], start));
+        assert!(limiter.try_acquire(addresses[0], start));
+        assert!(!limiter.try_acquire(addresses[0], start));
+        assert!(limiter.try_acquire(addresses[1], start));
+        assert!(!limiter.try_acquire(addresses[1], start));
+        assert!(!limiter.try_acquire(Address::repeat_byte(3), start));
+
+        let next_window = start + TxPolicy::RATE_LIMIT_WINDOW;
+        assert!(limiter.try_acquire(addresses[1], next_window));
+    }
+
+    #[test]
+    fn decisions_pruning_schedule() {
+        let policy = TxPolicy::new(&TxPolicyConfig::default());
+        let start = Instant::now();
+        assert!(policy.should_prune_decisions(start));
+        assert!(!policy.should_prune_decisions(start + Duration::from_secs(1)));
+        assert!(policy.should_prune_decisions(start + TxPolicy::DECISIONS_PRUNING_INTERVAL));
+    }
+}
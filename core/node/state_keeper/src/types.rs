@@ -76,6 +76,14 @@ impl MempoolGuard {
             .rollback(rejected)
     }
 
+    /// Removes all transactions from the mempool so that it can be re-populated from the storage.
+    pub fn clear(&mut self, next_priority_id: PriorityOpId) {
+        self.0
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .clear(next_priority_id);
+    }
+
     pub fn get_mempool_info(&mut self) -> MempoolInfo {
         self.0
             .lock()
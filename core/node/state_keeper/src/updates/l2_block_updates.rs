@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ops};
 
 use zksync_multivm::{
     interface::{
@@ -21,6 +21,8 @@ pub struct L2BlockUpdates {
     pub executed_transactions: Vec<TransactionExecutionResult>,
     pub events: Vec<VmEvent>,
     pub storage_logs: Vec<StorageLogWithPreviousValue>,
+    /// Ranges of `storage_logs` produced by each of `executed_transactions`.
+    pub tx_storage_log_ranges: Vec<ops::Range<usize>>,
     pub user_l2_to_l1_logs: Vec<UserL2ToL1Log>,
     pub system_l2_to_l1_logs: Vec<SystemL2ToL1Log>,
     pub new_factory_deps: HashMap<H256, Vec<u8>>,
@@ -47,6 +49,7 @@ impl L2BlockUpdates {
             executed_transactions: vec![],
             events: vec![],
             storage_logs: vec![],
+            tx_storage_log_ranges: vec![],
             user_l2_to_l1_logs: vec![],
             system_l2_to_l1_logs: vec![],
             new_factory_deps: HashMap::new(),
@@ -145,8 +148,11 @@ impl L2BlockUpdates {
             .extend(tx_execution_result.logs.user_l2_to_l1_logs);
         self.system_l2_to_l1_logs
             .extend(tx_execution_result.logs.system_l2_to_l1_logs);
+        let storage_logs_start = self.storage_logs.len();
         self.storage_logs
             .extend(tx_execution_result.logs.storage_logs);
+        self.tx_storage_log_ranges
+            .push(storage_logs_start..self.storage_logs.len());
         if tx.is_l1() {
             self.l1_tx_count += 1;
         }
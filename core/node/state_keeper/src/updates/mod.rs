@@ -7,14 +7,16 @@ use zksync_multivm::{
     utils::{get_batch_base_fee, StorageWritesDeduplicator},
 };
 use zksync_types::{
-    commitment::PubdataParams, fee_model::BatchFeeInput, Address, L1BatchNumber, L2BlockNumber,
-    ProtocolVersionId, Transaction,
+    commitment::PubdataParams, fee_model::BatchFeeInput, h256_to_u256, u256_to_h256,
+    web3::keccak256, Address, L1BatchNumber, L2BlockNumber, ProtocolVersionId, StorageKey,
+    StorageLogWithPreviousValue, Transaction, H256, U256,
 };
 
 pub(crate) use self::{l1_batch_updates::L1BatchUpdates, l2_block_updates::L2BlockUpdates};
 use super::{
     io::{IoCursor, L2BlockParams},
     metrics::{BATCH_TIP_METRICS, UPDATES_MANAGER_METRICS},
+    sequencer_lease::SequencerFence,
 };
 
 pub mod l1_batch_updates;
@@ -39,6 +41,9 @@ pub struct UpdatesManager {
     pub l1_batch: L1BatchUpdates,
     pub l2_block: L2BlockUpdates,
     pub storage_writes_deduplicator: StorageWritesDeduplicator,
+    /// Incrementally maintained checksum of the slots in `storage_writes_deduplicator`;
+    /// see [`Self::pending_storage_logs_checksum()`].
+    storage_logs_checksum: U256,
     pubdata_params: PubdataParams,
     next_l2_block_params: Option<L2BlockParams>,
     previous_batch_protocol_version: ProtocolVersionId,
@@ -68,6 +73,7 @@ impl UpdatesManager {
                 protocol_version,
             ),
             storage_writes_deduplicator: StorageWritesDeduplicator::new(),
+            storage_logs_checksum: U256::zero(),
             storage_view_cache: None,
             pubdata_params,
             next_l2_block_params: None,
@@ -129,6 +135,8 @@ impl UpdatesManager {
             l2_legacy_shared_bridge_addr,
             pre_insert_txs,
             pubdata_params: self.pubdata_params,
+            fence: None,
+            record_storage_slot_writers: false,
         }
     }
 
@@ -151,8 +159,7 @@ impl UpdatesManager {
         let latency = UPDATES_MANAGER_METRICS
             .extend_from_executed_transaction
             .start();
-        self.storage_writes_deduplicator
-            .apply(&tx_execution_result.logs.storage_logs);
+        self.apply_storage_logs(&tx_execution_result.logs.storage_logs);
         self.l2_block.extend_from_executed_transaction(
             tx,
             tx_execution_result,
@@ -173,8 +180,7 @@ impl UpdatesManager {
         let batch_tip_execution_metrics = result.get_execution_metrics();
 
         let before = self.storage_writes_deduplicator.metrics();
-        self.storage_writes_deduplicator
-            .apply(&result.logs.storage_logs);
+        self.apply_storage_logs(&result.logs.storage_logs);
         let after = self.storage_writes_deduplicator.metrics();
         BATCH_TIP_METRICS.observe_writes_metrics(&before, &after, self.protocol_version());
 
@@ -185,6 +191,44 @@ impl UpdatesManager {
         latency.observe();
     }
 
+    /// Applies storage logs to the deduplicator, updating the checksum for the affected slots.
+    fn apply_storage_logs(&mut self, logs: &[StorageLogWithPreviousValue]) {
+        let mut written_keys: Vec<_> = logs
+            .iter()
+            .filter(|log| log.log.is_write())
+            .map(|log| log.log.key)
+            .collect();
+        written_keys.sort_unstable();
+        written_keys.dedup();
+
+        let modified_slots = self.storage_writes_deduplicator.modified_key_values();
+        for key in &written_keys {
+            if let Some(slot) = modified_slots.get(key) {
+                let (checksum, _) = self
+                    .storage_logs_checksum
+                    .overflowing_sub(Self::slot_checksum(key, slot.value));
+                self.storage_logs_checksum = checksum;
+            }
+        }
+        self.storage_writes_deduplicator.apply(logs);
+        let modified_slots = self.storage_writes_deduplicator.modified_key_values();
+        for key in &written_keys {
+            if let Some(slot) = modified_slots.get(key) {
+                let (checksum, _) = self
+                    .storage_logs_checksum
+                    .overflowing_add(Self::slot_checksum(key, slot.value));
+                self.storage_logs_checksum = checksum;
+            }
+        }
+    }
+
+    fn slot_checksum(key: &StorageKey, value: H256) -> U256 {
+        let mut bytes = [0_u8; 64];
+        bytes[..32].copy_from_slice(key.hashed_key().as_bytes());
+        bytes[32..].copy_from_slice(value.as_bytes());
+        h256_to_u256(H256(keccak256(&bytes)))
+    }
+
     pub fn update_storage_view_cache(&mut self, storage_view_cache: StorageViewCache) {
         self.storage_view_cache = Some(storage_view_cache);
     }
@@ -239,6 +283,16 @@ impl UpdatesManager {
     pub(crate) fn pending_txs_encoding_size(&self) -> usize {
         self.l1_batch.txs_encoding_size + self.l2_block.txs_encoding_size
     }
+
+    /// Returns the checksum of storage slots modified by the batch so far, including the pending L2 block.
+    /// The checksum is deterministic for a given sequence of L2 blocks, so it can be used to check that a batch
+    /// re-executed after a restart has reached the same state.
+    ///
+    /// The checksum is the sum of hashes of all modified slots together with their values, so it doesn't depend
+    /// on the order of slots and is maintained incrementally as storage logs are applied.
+    pub(crate) fn pending_storage_logs_checksum(&self) -> H256 {
+        u256_to_h256(self.storage_logs_checksum)
+    }
 }
 
 /// Command to seal an L2 block containing all necessary data for it.
@@ -258,12 +312,30 @@ pub struct L2BlockSealCommand {
     /// before they are included into L2 blocks.
     pub pre_insert_txs: bool,
     pub pubdata_params: PubdataParams,
+    /// Fence checked before persisting the L2 block, if the sequencer lease is used.
+    pub fence: Option<SequencerFence>,
+    /// Whether hashes of transactions that last modified each storage slot should be persisted with storage logs.
+    pub record_storage_slot_writers: bool,
+}
+
+impl L2BlockSealCommand {
+    pub(crate) fn with_fence(mut self, fence: Option<SequencerFence>) -> Self {
+        self.fence = fence;
+        self
+    }
+
+    pub(crate) fn with_storage_slot_writers(mut self, record_storage_slot_writers: bool) -> Self {
+        self.record_storage_slot_writers = record_storage_slot_writers;
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests::{create_execution_result, create_transaction, create_updates_manager};
+    use crate::tests::{
+        create_execution_result, create_transaction, create_updates_manager, Query,
+    };
 
     #[test]
     fn apply_l2_block() {
@@ -298,4 +370,63 @@ mod tests {
         assert_eq!(updates_manager.l2_block.executed_transactions.len(), 0);
         assert_eq!(updates_manager.l1_batch.executed_transactions.len(), 1);
     }
+
+    #[test]
+    fn storage_logs_checksum_is_maintained_incrementally() {
+        let mut updates_manager = create_updates_manager();
+        assert_eq!(
+            updates_manager.pending_storage_logs_checksum(),
+            H256::zero()
+        );
+
+        let txs = [
+            vec![
+                (U256::from(1), Query::InitialWrite(U256::from(100))),
+                (
+                    U256::from(2),
+                    Query::RepeatedWrite(U256::from(5), U256::from(6)),
+                ),
+            ],
+            vec![
+                (
+                    U256::from(1),
+                    Query::RepeatedWrite(U256::from(100), U256::from(101)),
+                ),
+                // Reverts the slot to its initial value, so it must be excluded from the checksum.
+                (
+                    U256::from(2),
+                    Query::RepeatedWrite(U256::from(6), U256::from(5)),
+                ),
+                (U256::from(3), Query::Read(U256::from(7))),
+            ],
+        ];
+        for storage_logs in txs {
+            updates_manager.extend_from_executed_transaction(
+                create_transaction(10, 100),
+                create_execution_result(storage_logs),
+                VmExecutionMetrics::default(),
+                vec![],
+            );
+
+            let expected_checksum = updates_manager
+                .storage_writes_deduplicator
+                .modified_key_values()
+                .iter()
+                .fold(U256::zero(), |acc, (key, slot)| {
+                    acc.overflowing_add(UpdatesManager::slot_checksum(key, slot.value))
+                        .0
+                });
+            assert_eq!(
+                updates_manager.pending_storage_logs_checksum(),
+                u256_to_h256(expected_checksum)
+            );
+        }
+        assert_eq!(
+            updates_manager
+                .storage_writes_deduplicator
+                .modified_key_values()
+                .len(),
+            1
+        );
+    }
 }
@@ -278,6 +278,8 @@ async fn get_updates_manager_witness_input_data(
         storage_refunds,
         pubdata_costs,
         witness_block_state,
+        base_system_contracts_hashes: Some(system_env.base_system_smart_contracts.hashes()),
+        precompile_inputs: output.batch.precompile_inputs.clone(),
         _marker: std::marker::PhantomData,
     })
 }
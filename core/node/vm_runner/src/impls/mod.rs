@@ -3,6 +3,7 @@
 mod bwip;
 mod playground;
 mod protective_reads;
+mod upgrade_dry_run;
 
 pub use self::{
     bwip::{
@@ -13,4 +14,5 @@ pub use self::{
         VmPlaygroundStorageOptions, VmPlaygroundTasks,
     },
     protective_reads::{ProtectiveReadsIo, ProtectiveReadsWriter, ProtectiveReadsWriterTasks},
+    upgrade_dry_run::ProtocolUpgradeDryRunner,
 };
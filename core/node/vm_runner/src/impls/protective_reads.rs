@@ -1,19 +1,26 @@
-use std::sync::Arc;
+use std::{collections::HashMap, fmt::Write as _, sync::Arc};
 
 use async_trait::async_trait;
 use tokio::sync::watch;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
-use zksync_types::{L1BatchNumber, L2ChainId, StorageLog};
+use zksync_types::{vm::FastVmMode, L1BatchNumber, L2ChainId, StorageKey, StorageLog, H256};
 use zksync_vm_executor::batch::MainBatchExecutorFactory;
-use zksync_vm_interface::{L1BatchEnv, L2BlockEnv, SystemEnv};
+use zksync_vm_interface::{utils::DivergenceHandler, L1BatchEnv, L2BlockEnv, SystemEnv};
 
 use crate::{
-    storage::StorageSyncTask, ConcurrentOutputHandlerFactory, ConcurrentOutputHandlerFactoryTask,
-    L1BatchOutput, L2BlockOutput, OutputHandler, OutputHandlerFactory, VmRunner, VmRunnerIo,
-    VmRunnerStorage,
+    metrics::{DivergenceKind, METRICS},
+    storage::StorageSyncTask,
+    ConcurrentOutputHandlerFactory, ConcurrentOutputHandlerFactoryTask, L1BatchOutput,
+    L2BlockOutput, OutputHandler, OutputHandlerFactory, VmRunner, VmRunnerIo, VmRunnerStorage,
 };
 
 /// A standalone component that writes protective reads asynchronously to state keeper.
+///
+/// The component re-executes sealed L1 batches and derives protective reads from the storage logs produced by the VM,
+/// so that the state keeper doesn't need to persist them on the critical sealing path. Re-execution results are
+/// cross-checked against the state keeper output (storage writes, and protective reads if they were persisted
+/// by the state keeper); divergences are logged, reported in metrics and persisted to Postgres. If the fast VM is enabled,
+/// this also serves as a way to validate it on real workloads.
 #[derive(Debug)]
 pub struct ProtectiveReadsWriter {
     vm_runner: VmRunner,
@@ -28,6 +35,8 @@ impl ProtectiveReadsWriter {
         chain_id: L2ChainId,
         first_processed_batch: L1BatchNumber,
         window_size: u32,
+        fast_vm_mode: FastVmMode,
+        fail_on_divergence: bool,
     ) -> anyhow::Result<(Self, ProtectiveReadsWriterTasks)> {
         let io = ProtectiveReadsIo {
             first_processed_batch,
@@ -35,10 +44,22 @@ impl ProtectiveReadsWriter {
         };
         let (loader, loader_task) =
             VmRunnerStorage::new(pool.clone(), rocksdb_path, io.clone(), chain_id).await?;
-        let output_handler_factory = ProtectiveReadsOutputHandlerFactory { pool: pool.clone() };
+        let output_handler_factory = ProtectiveReadsOutputHandlerFactory {
+            pool: pool.clone(),
+            fail_on_divergence,
+        };
         let (output_handler_factory, output_handler_factory_task) =
             ConcurrentOutputHandlerFactory::new(pool.clone(), io.clone(), output_handler_factory);
-        let batch_processor = MainBatchExecutorFactory::<()>::new(false);
+        let mut batch_processor = MainBatchExecutorFactory::<()>::new(false);
+        batch_processor.set_fast_vm_mode(fast_vm_mode);
+        if !fail_on_divergence {
+            // The default handler panics on divergence, which is only desirable if divergences are fatal.
+            batch_processor.set_divergence_handler(DivergenceHandler::new(|err, dump| {
+                let l1_batch_number = dump.l1_batch_number();
+                tracing::error!("Fast VM diverged on L1 batch #{l1_batch_number}: {err}");
+                METRICS.protective_reads_divergences[&DivergenceKind::FastVm].inc();
+            }));
+        }
         let vm_runner = VmRunner::new(
             pool,
             Arc::new(io),
@@ -132,10 +153,80 @@ impl VmRunnerIo for ProtectiveReadsIo {
     }
 }
 
+/// Divergences between the state keeper output and a re-executed L1 batch.
+#[derive(Debug, Default)]
+struct Divergences {
+    entries: Vec<(DivergenceKind, StorageKey)>,
+}
+
+impl Divergences {
+    /// Max number of entries included into the persisted summary.
+    const MAX_REPORTED_ENTRIES: usize = 10;
+
+    fn push(&mut self, kind: DivergenceKind, key: StorageKey) {
+        METRICS.protective_reads_divergences[&kind].inc();
+        self.entries.push((kind, key));
+    }
+
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn summary(&self) -> String {
+        let mut summary = format!("{} divergence(s)", self.entries.len());
+        for (kind, key) in self.entries.iter().take(Self::MAX_REPORTED_ENTRIES) {
+            write!(summary, "; {kind:?} at {:?}:{:?}", key.address(), key.key()).unwrap();
+        }
+        if self.entries.len() > Self::MAX_REPORTED_ENTRIES {
+            summary.push_str("; ...");
+        }
+        summary
+    }
+}
+
 #[derive(Debug)]
 struct ProtectiveReadsOutputHandler {
     l1_batch_number: L1BatchNumber,
     pool: ConnectionPool<Core>,
+    fail_on_divergence: bool,
+}
+
+impl ProtectiveReadsOutputHandler {
+    /// Compares final values of storage slots written by the re-executed batch with the ones persisted by the state keeper.
+    fn check_writes(
+        &self,
+        computed_writes: &[StorageLog],
+        mut written_values: HashMap<StorageKey, H256>,
+        divergences: &mut Divergences,
+    ) {
+        let l1_batch_number = self.l1_batch_number;
+        let mut computed_values = HashMap::with_capacity(computed_writes.len());
+        for log in computed_writes {
+            computed_values.insert(log.key, log.value);
+        }
+
+        for (key, value) in computed_values {
+            let written_value = written_values.remove(&key);
+            if written_value != Some(value) {
+                tracing::error!(
+                    l1_batch_number = %l1_batch_number,
+                    address = %key.address(),
+                    key = %key.key(),
+                    "VM runner produced storage write with value {value:?}, while state keeper produced {written_value:?}"
+                );
+                divergences.push(DivergenceKind::StorageWrite, key);
+            }
+        }
+        for key in written_values.into_keys() {
+            tracing::error!(
+                l1_batch_number = %l1_batch_number,
+                address = %key.address(),
+                key = %key.key(),
+                "State keeper produced a storage write that did not happen in VM runner"
+            );
+            divergences.push(DivergenceKind::StorageWrite, key);
+        }
+    }
 }
 
 #[async_trait]
@@ -155,12 +246,13 @@ impl OutputHandler for ProtectiveReadsOutputHandler {
     )]
     async fn handle_l1_batch(self: Box<Self>, output: Arc<L1BatchOutput>) -> anyhow::Result<()> {
         let l1_batch_number = self.l1_batch_number;
-        let (_, computed_protective_reads): (Vec<StorageLog>, Vec<StorageLog>) = output
-            .batch
-            .final_execution_state
-            .deduplicated_storage_logs
-            .iter()
-            .partition(|log_query| log_query.is_write());
+        let (computed_writes, computed_protective_reads): (Vec<StorageLog>, Vec<StorageLog>) =
+            output
+                .batch
+                .final_execution_state
+                .deduplicated_storage_logs
+                .iter()
+                .partition(|log_query| log_query.is_write());
 
         let mut connection = self
             .pool
@@ -170,6 +262,13 @@ impl OutputHandler for ProtectiveReadsOutputHandler {
             .storage_logs_dedup_dal()
             .get_protective_reads_for_l1_batch(l1_batch_number)
             .await?;
+        let written_values = connection
+            .storage_logs_dal()
+            .get_touched_slots_for_executed_l1_batch(l1_batch_number)
+            .await?;
+
+        let mut divergences = Divergences::default();
+        self.check_writes(&computed_writes, written_values, &mut divergences);
 
         if !written_protective_reads.is_empty() {
             tracing::debug!(
@@ -186,6 +285,7 @@ impl OutputHandler for ProtectiveReadsOutputHandler {
                         key = %key,
                         "VM runner produced a protective read that did not happen in state keeper"
                     );
+                    divergences.push(DivergenceKind::MissingStateKeeperRead, protective_read.key);
                 }
             }
             for remaining_read in written_protective_reads {
@@ -195,6 +295,7 @@ impl OutputHandler for ProtectiveReadsOutputHandler {
                     key = %remaining_read.key(),
                     "State keeper produced a protective read that did not happen in VM runner"
                 );
+                divergences.push(DivergenceKind::MissingVmRunnerRead, remaining_read);
             }
         } else {
             tracing::debug!(
@@ -207,6 +308,17 @@ impl OutputHandler for ProtectiveReadsOutputHandler {
                 .await?;
         }
 
+        if !divergences.is_empty() {
+            let summary = divergences.summary();
+            connection
+                .vm_runner_dal()
+                .mark_protective_reads_batch_as_divergent(l1_batch_number, &summary)
+                .await?;
+            anyhow::ensure!(
+                !self.fail_on_divergence,
+                "L1 batch #{l1_batch_number} re-execution diverged from state keeper output: {summary}"
+            );
+        }
         Ok(())
     }
 }
@@ -214,6 +326,7 @@ impl OutputHandler for ProtectiveReadsOutputHandler {
 #[derive(Debug)]
 struct ProtectiveReadsOutputHandlerFactory {
     pool: ConnectionPool<Core>,
+    fail_on_divergence: bool,
 }
 
 #[async_trait]
@@ -226,6 +339,7 @@ impl OutputHandlerFactory for ProtectiveReadsOutputHandlerFactory {
         Ok(Box::new(ProtectiveReadsOutputHandler {
             pool: self.pool.clone(),
             l1_batch_number: l1_batch_env.number,
+            fail_on_divergence: self.fail_on_divergence,
         }))
     }
 }
@@ -0,0 +1,344 @@
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_state::OwnedStorage;
+use zksync_types::{
+    api::SystemContractDiff, h256_to_address, protocol_upgrade::ProtocolUpgradeTx, Address,
+    L1BatchNumber, L2ChainId, ProtocolVersionId, StorageLogWithPreviousValue, Transaction,
+    ACCOUNT_CODE_STORAGE_ADDRESS, H256,
+};
+use zksync_vm_executor::{
+    batch::MainBatchExecutorFactory,
+    storage::{get_base_system_contracts_by_version_id, l1_batch_params},
+};
+use zksync_vm_interface::{executor::BatchExecutorFactory, ExecutionResult};
+
+use crate::metrics::{UpgradeDryRunOutcome, METRICS};
+
+/// Outcome of executing the upgrade transaction.
+#[derive(Debug)]
+struct DryRunOutput {
+    base_l1_batch_number: L1BatchNumber,
+    error: Option<String>,
+    system_contract_diffs: Vec<SystemContractDiff>,
+}
+
+/// Component executing protocol upgrade transactions in a sandbox before the upgrade is activated.
+///
+/// Once an upgrade transaction for a protocol version not yet used by any L1 batch is saved by the L1 watcher,
+/// this component executes it on top of the latest sealed L1 batch in a batch using the new base system contracts,
+/// and persists the outcome together with bytecode changes made by the transaction. A failing dry run
+/// means that the state keeper will likely be unable to seal a batch once the upgrade is activated.
+///
+/// Each upgrade is dry-run once; results can be queried via the `unstable_getProtocolUpgradeDryRun` API method.
+#[derive(Debug)]
+pub struct ProtocolUpgradeDryRunner {
+    pool: ConnectionPool<Core>,
+    chain_id: L2ChainId,
+    validation_computational_gas_limit: u32,
+    poll_interval: Duration,
+}
+
+impl ProtocolUpgradeDryRunner {
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+    /// Creates a new dry runner.
+    pub fn new(
+        pool: ConnectionPool<Core>,
+        chain_id: L2ChainId,
+        validation_computational_gas_limit: u32,
+    ) -> Self {
+        Self {
+            pool,
+            chain_id,
+            validation_computational_gas_limit,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Sets the interval between checks for new upgrades.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Continuously checks for pending protocol upgrades and dry-runs them.
+    ///
+    /// # Errors
+    ///
+    /// Propagates Postgres errors. Errors during upgrade execution are persisted as dry run results instead.
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow_and_update() {
+            self.process_pending_upgrades().await?;
+            if tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, protocol upgrade dry runner is shutting down");
+        Ok(())
+    }
+
+    /// Returns protocol versions that have an upgrade transaction, but haven't been used by any sealed L1 batch yet.
+    async fn pending_upgrades(
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<Vec<ProtocolVersionId>> {
+        let Some(last_used_version) = storage.protocol_versions_dal().last_used_version_id().await
+        else {
+            return Ok(vec![]);
+        };
+        let Some(latest_version) = storage
+            .protocol_versions_dal()
+            .latest_semantic_version()
+            .await?
+        else {
+            return Ok(vec![]);
+        };
+
+        Ok((last_used_version as u16 + 1..=latest_version.minor as u16)
+            .filter_map(|id| ProtocolVersionId::try_from(id).ok())
+            .collect())
+    }
+
+    /// Dry-runs all pending upgrades that weren't processed yet. Returns the number of processed upgrades.
+    pub(crate) async fn process_pending_upgrades(&self) -> anyhow::Result<usize> {
+        let mut storage = self.pool.connection_tagged("upgrade_dry_run").await?;
+        let mut processed_upgrades = 0;
+        for version in Self::pending_upgrades(&mut storage).await? {
+            let already_processed = storage
+                .protocol_upgrade_dry_runs_dal()
+                .get_dry_run(version)
+                .await?
+                .is_some();
+            if already_processed {
+                continue;
+            }
+            let Some(upgrade_tx) = storage
+                .protocol_versions_dal()
+                .get_protocol_upgrade_tx(version)
+                .await?
+            else {
+                continue;
+            };
+            let upgrade_tx_hash = upgrade_tx.common_data.hash();
+            tracing::info!("Dry-running upgrade transaction {upgrade_tx_hash:?} for protocol version {version:?}");
+
+            let output = self.dry_run(&mut storage, version, upgrade_tx).await?;
+            if let Some(err) = &output.error {
+                METRICS.upgrade_dry_runs[&UpgradeDryRunOutcome::Failure].inc();
+                tracing::error!(
+                    "Dry run of upgrade transaction {upgrade_tx_hash:?} for protocol version {version:?} failed \
+                     on top of L1 batch #{}: {err}",
+                    output.base_l1_batch_number
+                );
+            } else {
+                METRICS.upgrade_dry_runs[&UpgradeDryRunOutcome::Success].inc();
+                tracing::info!(
+                    "Dry run of upgrade transaction {upgrade_tx_hash:?} for protocol version {version:?} succeeded \
+                     on top of L1 batch #{}; system contract diffs: {:?}",
+                    output.base_l1_batch_number,
+                    output.system_contract_diffs
+                );
+            }
+
+            storage
+                .protocol_upgrade_dry_runs_dal()
+                .insert_dry_run(
+                    version,
+                    upgrade_tx_hash,
+                    output.base_l1_batch_number,
+                    output.error.as_deref(),
+                    &output.system_contract_diffs,
+                )
+                .await?;
+            processed_upgrades += 1;
+        }
+        Ok(processed_upgrades)
+    }
+
+    async fn dry_run(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        version: ProtocolVersionId,
+        upgrade_tx: ProtocolUpgradeTx,
+    ) -> anyhow::Result<DryRunOutput> {
+        let base_l1_batch_number = storage
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await?
+            .context("no sealed L1 batches")?;
+        let (_, last_l2_block_number) = storage
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(base_l1_batch_number)
+            .await?
+            .with_context(|| format!("L1 batch #{base_l1_batch_number} has no L2 blocks"))?;
+        let last_l2_block = storage
+            .blocks_dal()
+            .get_l2_block_header(last_l2_block_number)
+            .await?
+            .with_context(|| format!("L2 block #{last_l2_block_number} is missing"))?;
+        let previous_batch_hash = storage
+            .blocks_dal()
+            .get_l1_batch_state_root(base_l1_batch_number)
+            .await?
+            .unwrap_or_default();
+        let upgrade_timestamp = storage
+            .protocol_versions_dal()
+            .get_protocol_version_with_latest_patch(version)
+            .await?
+            .map_or(0, |version| version.timestamp);
+
+        let failed = |error: String| DryRunOutput {
+            base_l1_batch_number,
+            error: Some(error),
+            system_contract_diffs: vec![],
+        };
+        let base_system_contracts =
+            match get_base_system_contracts_by_version_id(storage, version).await {
+                Ok(Some(contracts)) => contracts,
+                Ok(None) => {
+                    return Ok(failed(format!(
+                        "base system contracts for {version:?} are not in Postgres"
+                    )));
+                }
+                Err(err) => {
+                    return Ok(failed(format!(
+                        "failed loading base system contracts: {err:#}"
+                    )));
+                }
+            };
+
+        let (system_env, l1_batch_env) = l1_batch_params(
+            base_l1_batch_number + 1,
+            last_l2_block.fee_account_address,
+            upgrade_timestamp.max(last_l2_block.timestamp + 1),
+            previous_batch_hash,
+            last_l2_block.batch_fee_input,
+            last_l2_block_number + 1,
+            last_l2_block.hash,
+            base_system_contracts,
+            self.validation_computational_gas_limit,
+            version,
+            last_l2_block.virtual_blocks,
+            self.chain_id,
+        );
+        let vm_storage = self.pool.connection_tagged("upgrade_dry_run").await?;
+        let vm_storage =
+            OwnedStorage::from(OwnedStorage::postgres(vm_storage, base_l1_batch_number).await?);
+
+        let mut executor_factory = MainBatchExecutorFactory::<()>::new(false);
+        let mut executor = executor_factory.init_batch(
+            vm_storage,
+            l1_batch_env,
+            system_env,
+            last_l2_block.pubdata_params,
+        );
+        let tx_result = match executor.execute_tx(Transaction::from(upgrade_tx)).await {
+            Ok(tx_result) => tx_result,
+            Err(err) => {
+                return Ok(failed(format!(
+                    "failed executing upgrade transaction: {err:#}"
+                )));
+            }
+        };
+        let system_contract_diffs = system_contract_diffs(&tx_result.tx_result.logs.storage_logs);
+
+        let error = match &tx_result.tx_result.result {
+            ExecutionResult::Success { .. } => match executor.finish_batch().await {
+                Ok(_) => None,
+                Err(err) => Some(format!("failed finishing batch: {err:#}")),
+            },
+            ExecutionResult::Revert { output } => Some(format!("reverted: {output}")),
+            ExecutionResult::Halt { reason } => Some(format!("halted: {reason}")),
+        };
+        Ok(DryRunOutput {
+            base_l1_batch_number,
+            error,
+            system_contract_diffs,
+        })
+    }
+}
+
+/// Extracts bytecode changes from the storage logs produced by a transaction.
+fn system_contract_diffs(storage_logs: &[StorageLogWithPreviousValue]) -> Vec<SystemContractDiff> {
+    let mut diffs = HashMap::<Address, SystemContractDiff>::new();
+    let code_writes = storage_logs
+        .iter()
+        .filter(|log| log.log.is_write() && *log.log.key.address() == ACCOUNT_CODE_STORAGE_ADDRESS);
+    for log in code_writes {
+        let address = h256_to_address(log.log.key.key());
+        diffs
+            .entry(address)
+            .or_insert(SystemContractDiff {
+                address,
+                previous_code_hash: log.previous_value,
+                new_code_hash: H256::zero(),
+            })
+            .new_code_hash = log.log.value;
+    }
+
+    let mut diffs: Vec<_> = diffs
+        .into_values()
+        .filter(|diff| diff.previous_code_hash != diff.new_code_hash)
+        .collect();
+    diffs.sort_unstable_by_key(|diff| diff.address);
+    diffs
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{AccountTreeId, StorageKey, StorageLog};
+
+    use super::*;
+
+    fn code_write(address: Address, previous: H256, new: H256) -> StorageLogWithPreviousValue {
+        let key = StorageKey::new(
+            AccountTreeId::new(ACCOUNT_CODE_STORAGE_ADDRESS),
+            address.into(),
+        );
+        StorageLogWithPreviousValue {
+            log: StorageLog::new_write_log(key, new),
+            previous_value: previous,
+        }
+    }
+
+    #[test]
+    fn extracting_system_contract_diffs() {
+        let first = Address::from_low_u64_be(0x8002);
+        let second = Address::from_low_u64_be(0x8003);
+        let unchanged = Address::from_low_u64_be(0x8004);
+        let other_key = StorageKey::new(AccountTreeId::new(first), H256::zero());
+        let logs = [
+            code_write(second, H256::repeat_byte(1), H256::repeat_byte(2)),
+            code_write(first, H256::zero(), H256::repeat_byte(3)),
+            code_write(second, H256::repeat_byte(2), H256::repeat_byte(4)),
+            code_write(unchanged, H256::repeat_byte(5), H256::repeat_byte(6)),
+            code_write(unchanged, H256::repeat_byte(6), H256::repeat_byte(5)),
+            StorageLogWithPreviousValue {
+                log: StorageLog::new_write_log(other_key, H256::repeat_byte(1)),
+                previous_value: H256::zero(),
+            },
+        ];
+
+        let diffs = system_contract_diffs(&logs);
+        assert_eq!(
+            diffs,
+            [
+                SystemContractDiff {
+                    address: first,
+                    previous_code_hash: H256::zero(),
+                    new_code_hash: H256::repeat_byte(3),
+                },
+                SystemContractDiff {
+                    address: second,
+                    previous_code_hash: H256::repeat_byte(1),
+                    new_code_hash: H256::repeat_byte(4),
+                },
+            ]
+        );
+    }
+}
@@ -2,7 +2,9 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
+};
 use zksync_state::OwnedStorage;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
@@ -25,6 +27,28 @@ impl StorageKind {
     }
 }
 
+/// Kind of divergence between the state keeper output and a re-executed L1 batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "kind", rename_all = "snake_case")]
+pub(super) enum DivergenceKind {
+    /// Protective read produced by the VM runner, but not by the state keeper.
+    MissingStateKeeperRead,
+    /// Protective read produced by the state keeper, but not by the VM runner.
+    MissingVmRunnerRead,
+    /// Storage write with a differing value, or a write produced by only one side.
+    StorageWrite,
+    /// Divergence between the legacy and fast VMs reported in the shadow mode.
+    FastVm,
+}
+
+/// Outcome of a protocol upgrade transaction dry run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "outcome", rename_all = "snake_case")]
+pub(super) enum UpgradeDryRunOutcome {
+    Success,
+    Failure,
+}
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "vm_runner")]
 pub(super) struct VmRunnerMetrics {
@@ -46,6 +70,10 @@ pub(super) struct VmRunnerMetrics {
     /// Total latency of handling output of an L1 batch.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub output_handle_time: Histogram<Duration>,
+    /// Number of divergences detected by the protective reads writer, grouped by kind.
+    pub protective_reads_divergences: Family<DivergenceKind, Counter>,
+    /// Number of protocol upgrade transaction dry runs, grouped by the outcome.
+    pub upgrade_dry_runs: Family<UpgradeDryRunOutcome, Counter>,
 }
 
 #[vise::register]
@@ -30,6 +30,7 @@ use super::*;
 mod output_handler;
 mod playground;
 mod process;
+mod protective_reads;
 mod storage;
 mod storage_writer;
 
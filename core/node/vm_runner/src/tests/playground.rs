@@ -18,7 +18,7 @@ impl From<&tempfile::TempDir> for VmPlaygroundStorageOptions {
     }
 }
 
-async fn setup_storage(
+pub(super) async fn setup_storage(
     pool: &ConnectionPool<Core>,
     batch_count: u32,
     insert_protective_reads: bool,
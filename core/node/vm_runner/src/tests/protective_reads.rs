@@ -0,0 +1,113 @@
+use test_casing::{test_casing, Product};
+use tokio::sync::watch;
+use zksync_types::{vm::FastVmMode, L2ChainId};
+
+use super::*;
+use crate::impls::ProtectiveReadsWriter;
+
+async fn run_protective_reads_writer(pool: ConnectionPool<Core>, fast_vm_mode: FastVmMode) {
+    let rocksdb_dir = tempfile::TempDir::new().unwrap();
+    let (writer, tasks) = ProtectiveReadsWriter::new(
+        pool.clone(),
+        rocksdb_dir.path().to_str().unwrap().to_owned(),
+        L2ChainId::default(),
+        L1BatchNumber(0),
+        1,
+        fast_vm_mode,
+        false,
+    )
+    .await
+    .unwrap();
+
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let task_handles = [
+        tokio::spawn(tasks.loader_task.run(stop_receiver.clone())),
+        tokio::spawn(tasks.output_handler_factory_task.run(stop_receiver.clone())),
+        tokio::spawn(async move { writer.run(&stop_receiver).await }),
+    ];
+
+    let mut conn = pool.connection().await.unwrap();
+    let sealed_batch = conn
+        .blocks_dal()
+        .get_sealed_l1_batch_number()
+        .await
+        .unwrap()
+        .expect("No batches in storage");
+    tokio::time::timeout(TEST_TIMEOUT, async {
+        loop {
+            let processed_batch = conn
+                .vm_runner_dal()
+                .get_protective_reads_latest_processed_batch()
+                .await
+                .unwrap();
+            if processed_batch == Some(sealed_batch) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("timed out waiting for protective reads writer");
+
+    stop_sender.send_replace(true);
+    for handle in task_handles {
+        handle.await.unwrap().unwrap();
+    }
+}
+
+#[test_casing(4, Product(([false, true], [FastVmMode::Old, FastVmMode::Shadow])))]
+#[tokio::test]
+async fn protective_reads_writer_detects_divergences(
+    insert_protective_reads: bool,
+    fast_vm_mode: FastVmMode,
+) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    playground::setup_storage(&pool, 3, insert_protective_reads).await;
+
+    // Emulate a storage write not produced by the VM in the last L2 block of batch #2.
+    let mut conn = pool.connection().await.unwrap();
+    let (_, last_l2_block) = conn
+        .blocks_dal()
+        .get_l2_block_range_of_l1_batch(L1BatchNumber(2))
+        .await
+        .unwrap()
+        .unwrap();
+    let divergent_key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(1)), H256::zero());
+    conn.storage_logs_dal()
+        .append_storage_logs(
+            last_l2_block,
+            &[StorageLog::new_write_log(
+                divergent_key,
+                H256::repeat_byte(1),
+            )],
+        )
+        .await
+        .unwrap();
+
+    run_protective_reads_writer(pool.clone(), fast_vm_mode).await;
+
+    for l1_batch_number in [1, 3] {
+        let divergence = conn
+            .vm_runner_dal()
+            .get_protective_reads_divergence(L1BatchNumber(l1_batch_number))
+            .await
+            .unwrap();
+        assert_eq!(divergence, None, "L1 batch #{l1_batch_number}");
+    }
+    let divergence = conn
+        .vm_runner_dal()
+        .get_protective_reads_divergence(L1BatchNumber(2))
+        .await
+        .unwrap()
+        .expect("divergence not recorded");
+    assert!(divergence.starts_with("1 divergence(s)"), "{divergence}");
+    assert!(divergence.contains("StorageWrite"), "{divergence}");
+
+    // Protective reads must be persisted regardless of divergences.
+    let protective_reads = conn
+        .storage_logs_dedup_dal()
+        .get_protective_reads_for_l1_batch(L1BatchNumber(3))
+        .await
+        .unwrap();
+    assert!(!protective_reads.is_empty());
+}
@@ -156,7 +156,7 @@ where
         );
         self.wallet
             .provider
-            .estimate_fee(l2_tx.into(), None)
+            .estimate_fee(l2_tx.into(), None, None)
             .await
             .map_err(Into::into)
     }
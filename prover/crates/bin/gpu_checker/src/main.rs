@@ -224,6 +224,7 @@ async fn main() -> anyhow::Result<()> {
         },
         max_retries: 1,
         local_mirror_path: None,
+        content_addressing: zksync_config::configs::object_store::ContentAddressingMode::Disabled,
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
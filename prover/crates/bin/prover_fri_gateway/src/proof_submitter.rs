@@ -4,7 +4,10 @@ use async_trait::async_trait;
 use zksync_object_store::ObjectStore;
 use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
 use zksync_prover_interface::{
-    api::{SubmitProofRequest, SubmitProofResponse},
+    api::{
+        SubmitProofRequest, SubmitProofResponse, SubmitProvingStatsRequest,
+        SubmitProvingStatsResponse,
+    },
     outputs::L1BatchProofForL1,
     Bincode,
 };
@@ -14,11 +17,16 @@ use crate::{client::ProverApiClient, traits::PeriodicApi};
 
 /// The path to the API endpoint that submits the proof.
 const SUBMIT_PROOF_PATH: &str = "/submit_proof";
+/// The path to the API endpoint that submits proving statistics for a batch.
+const PROVING_STATS_PATH: &str = "/proving_stats";
 
 /// Poller structure that will periodically check the database for new proofs to submit.
-/// Once a new proof is detected, it will be sent to the prover API.
+/// Once a new proof is detected, it will be sent to the prover API, followed by proving statistics for the batch.
 #[derive(Debug)]
-pub struct ProofSubmitter(ProverApiClient);
+pub struct ProofSubmitter {
+    client: ProverApiClient,
+    proving_stats_url: String,
+}
 
 impl ProofSubmitter {
     pub(crate) fn new(
@@ -27,15 +35,17 @@ impl ProofSubmitter {
         pool: ConnectionPool<Prover>,
     ) -> Self {
         let api_url = format!("{base_url}{SUBMIT_PROOF_PATH}");
-        let inner = ProverApiClient::new(blob_store, pool, api_url);
-        Self(inner)
+        Self {
+            client: ProverApiClient::new(blob_store, pool, api_url),
+            proving_stats_url: format!("{base_url}{PROVING_STATS_PATH}"),
+        }
     }
 }
 
 impl ProofSubmitter {
     async fn next_submit_proof_request(&self) -> Option<(L1BatchNumber, SubmitProofRequest)> {
         let (l1_batch_number, protocol_version, status) = self
-            .0
+            .client
             .pool
             .connection()
             .await
@@ -47,14 +57,14 @@ impl ProofSubmitter {
         let request = match status {
             ProofCompressionJobStatus::Successful => {
                 let proof: L1BatchProofForL1 = match self
-                    .0
+                    .client
                     .blob_store
                     .get((l1_batch_number, protocol_version))
                     .await
                 {
                     Ok(proof) => proof,
                     Err(_) => self
-                        .0
+                        .client
                         .blob_store
                         .get::<L1BatchProofForL1<Bincode>>((l1_batch_number, protocol_version))
                         .await
@@ -75,7 +85,7 @@ impl ProofSubmitter {
     }
 
     async fn save_successful_sent_proof(&self, l1_batch_number: L1BatchNumber) {
-        self.0
+        self.client
             .pool
             .connection()
             .await
@@ -84,6 +94,38 @@ impl ProofSubmitter {
             .mark_proof_sent_to_server(l1_batch_number)
             .await;
     }
+
+    /// Sends proving statistics for the batch to the prover API. Failures are logged and otherwise ignored,
+    /// since the statistics are only used to tune batch sizes on the server.
+    async fn submit_proving_stats(&self, l1_batch_number: L1BatchNumber) {
+        let stats = self
+            .client
+            .pool
+            .connection()
+            .await
+            .unwrap()
+            .fri_prover_dal()
+            .get_batch_proving_stats(l1_batch_number)
+            .await;
+        let Some((base_layer_circuits, proving_time)) = stats else {
+            return;
+        };
+
+        let request = SubmitProvingStatsRequest {
+            base_layer_circuits,
+            proving_time_ms: proving_time.as_millis() as u64,
+        };
+        let endpoint = format!("{}/{l1_batch_number}", self.proving_stats_url);
+        let response = self
+            .client
+            .send_http_request::<_, SubmitProvingStatsResponse>(request, &endpoint)
+            .await;
+        if let Err(err) = response {
+            tracing::warn!(
+                "Failed submitting proving stats for L1 batch #{l1_batch_number}: {err}"
+            );
+        }
+    }
 }
 
 #[async_trait]
@@ -103,12 +145,13 @@ impl PeriodicApi for ProofSubmitter {
         job_id: Self::JobId,
         request: SubmitProofRequest,
     ) -> reqwest::Result<Self::Response> {
-        let endpoint = format!("{}/{job_id}", self.0.api_url);
-        self.0.send_http_request(request, &endpoint).await
+        let endpoint = format!("{}/{job_id}", self.client.api_url);
+        self.client.send_http_request(request, &endpoint).await
     }
 
     async fn handle_response(&self, job_id: L1BatchNumber, response: Self::Response) {
         tracing::info!("Received response: {:?}", response);
         self.save_successful_sent_proof(job_id).await;
+        self.submit_proving_stats(job_id).await;
     }
 }
@@ -1,5 +1,6 @@
 use std::{sync::Arc, time::Instant};
 
+use anyhow::Context as _;
 use async_trait::async_trait;
 use circuit_definitions::zkevm_circuits::scheduler::{
     block_header::BlockAuxilaryOutputWitness, input::SchedulerCircuitInstanceWitness,
@@ -81,6 +82,13 @@ impl JobManager for BasicCircuits {
             AggregationRound::BasicCircuits,
             block_number.0
         );
+        // Fail early with a clear error if the server executed the batch with different system contracts
+        // than the ones provided in the input; otherwise, the mismatch surfaces as an obscure proof failure.
+        job.vm_run_data
+            .check_base_system_contracts()
+            .with_context(|| {
+                format!("witness input for block {block_number} has inconsistent system contracts")
+            })?;
 
         let (circuit_urls, queue_urls, scheduler_witness, aux_output_witness) =
             generate_witness(block_number, object_store, job, max_circuits_in_flight).await;
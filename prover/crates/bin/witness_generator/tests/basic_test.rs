@@ -2,7 +2,10 @@ use std::time::Instant;
 
 use serde::Serialize;
 use zksync_circuit_prover_service::types::circuit_wrapper::CircuitWrapper;
-use zksync_config::{configs::object_store::ObjectStoreMode, ObjectStoreConfig};
+use zksync_config::{
+    configs::object_store::{ContentAddressingMode, ObjectStoreMode},
+    ObjectStoreConfig,
+};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_prover_fri_types::keys::{AggregationsKey, FriCircuitKey};
 use zksync_prover_fri_utils::get_recursive_layer_circuit_id_for_base_layer;
@@ -42,6 +45,7 @@ async fn test_leaf_witness_gen() {
         },
         max_retries: 5,
         local_mirror_path: None,
+        content_addressing: ContentAddressingMode::Disabled,
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
@@ -65,6 +69,7 @@ async fn test_leaf_witness_gen() {
         },
         max_retries: 5,
         local_mirror_path: None,
+        content_addressing: ContentAddressingMode::Disabled,
     };
     let expected_object_store = ObjectStoreFactory::new(expected_results_object_store_config)
         .create_store()
@@ -117,6 +122,7 @@ async fn test_node_witness_gen() {
         },
         max_retries: 5,
         local_mirror_path: None,
+        content_addressing: ContentAddressingMode::Disabled,
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
@@ -151,6 +157,7 @@ async fn test_node_witness_gen() {
         },
         max_retries: 5,
         local_mirror_path: None,
+        content_addressing: ContentAddressingMode::Disabled,
     };
     let expected_object_store = ObjectStoreFactory::new(expected_results_object_store_config)
         .create_store()
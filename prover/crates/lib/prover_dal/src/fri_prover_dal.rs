@@ -786,6 +786,49 @@ impl FriProverDal<'_, '_> {
         .collect()
     }
 
+    /// Returns the number of base layer circuits and the total time taken by all successful prover jobs
+    /// for the specified batch, or `None` if there are no base layer jobs for the batch.
+    pub async fn get_batch_proving_stats(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Option<(u32, Duration)> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) FILTER (
+                    WHERE
+                    aggregation_round = 0
+                ) AS "base_layer_circuits!",
+                COALESCE(
+                    SUM(EXTRACT(
+                        EPOCH
+                        FROM
+                        time_taken
+                    )),
+                    0
+                )::DOUBLE PRECISION AS "proving_time_sec!"
+            FROM
+                prover_jobs_fri
+            WHERE
+                l1_batch_number = $1
+                AND status = 'successful'
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_batch_proving_stats")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_one(self.storage)
+        .await
+        .unwrap();
+
+        (row.base_layer_circuits > 0).then(|| {
+            (
+                row.base_layer_circuits as u32,
+                Duration::from_secs_f64(row.proving_time_sec.max(0.0)),
+            )
+        })
+    }
+
     pub async fn delete_prover_jobs_fri_batch_data(
         &mut self,
         l1_batch_number: L1BatchNumber,